@@ -89,6 +89,8 @@ pub async fn main() {
         quiet: false,
         maxrows: datafusion_cli::print_options::MaxRows::Unlimited,
         color: true,
+        timing: Default::default(),
+        output: None,
     };
 
     exec_from_repl(&my_ctx, &mut print_options).await.unwrap();
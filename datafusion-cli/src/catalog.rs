@@ -72,6 +72,10 @@ impl CatalogProviderList for DynamicObjectStoreCatalog {
             Arc::new(DynamicObjectStoreCatalogProvider::new(catalog, state)) as _
         })
     }
+
+    fn deregister_catalog(&self, name: &str) -> Result<Option<Arc<dyn CatalogProvider>>> {
+        self.inner.deregister_catalog(name)
+    }
 }
 
 /// Wraps another catalog provider
@@ -113,6 +117,14 @@ impl CatalogProvider for DynamicObjectStoreCatalogProvider {
     ) -> Result<Option<Arc<dyn SchemaProvider>>> {
         self.inner.register_schema(name, schema)
     }
+
+    fn deregister_schema(
+        &self,
+        name: &str,
+        cascade: bool,
+    ) -> Result<Option<Arc<dyn SchemaProvider>>> {
+        self.inner.deregister_schema(name, cascade)
+    }
 }
 
 /// Wraps another schema provider. [DynamicObjectStoreSchemaProvider] is responsible for registering the required
@@ -216,6 +228,14 @@ impl SchemaProvider for DynamicObjectStoreSchemaProvider {
     fn table_exist(&self, name: &str) -> bool {
         self.inner.table_exist(name)
     }
+
+    async fn table_exists(&self, name: &str) -> Result<bool> {
+        self.inner.table_exists(name).await
+    }
+
+    fn is_cheap_to_enumerate(&self) -> bool {
+        self.inner.is_cheap_to_enumerate()
+    }
 }
 
 pub fn substitute_tilde(cur: String) -> String {
@@ -18,10 +18,10 @@
 //! Command within CLI
 
 use crate::cli_context::CliSessionContext;
-use crate::exec::{exec_and_print, exec_from_lines};
+use crate::exec::{exec_and_print, exec_from_lines, exec_watch};
 use crate::functions::{display_all_functions, Function};
 use crate::print_format::PrintFormat;
-use crate::print_options::PrintOptions;
+use crate::print_options::{PrintOptions, Timing};
 use clap::ValueEnum;
 use datafusion::arrow::array::{ArrayRef, StringArray};
 use datafusion::arrow::datatypes::{DataType, Field, Schema};
@@ -31,8 +31,10 @@ use datafusion::common::instant::Instant;
 use datafusion::error::{DataFusionError, Result};
 use std::fs::File;
 use std::io::BufReader;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Command
 #[derive(Debug)]
@@ -46,6 +48,10 @@ pub enum Command {
     SearchFunctions(String),
     QuietMode(Option<bool>),
     OutputFormat(Option<String>),
+    Format(Option<String>),
+    Output(Option<String>),
+    Timing(Option<Timing>),
+    Watch(Duration, String),
 }
 
 pub enum OutputFormat {
@@ -70,6 +76,7 @@ impl Command {
                     schema,
                     &[command_batch],
                     now,
+                    None,
                     num_rows,
                     config,
                 )
@@ -124,6 +131,44 @@ impl Command {
             Self::OutputFormat(_) => exec_err!(
                 "Unexpected change output format, this should be handled outside"
             ),
+            Self::Format(format) => {
+                if let Some(format) = format {
+                    OutputFormat::ChangeFormat(format.clone())
+                        .execute(print_options)
+                        .await
+                } else {
+                    println!("Output format is {:?}.", print_options.format);
+                    Ok(())
+                }
+            }
+            Self::Output(path) => {
+                if let Some(path) = path {
+                    let path = PathBuf::from(path);
+                    // Results are appended to the file by each query, except
+                    // Parquet files, which are rewritten by each query
+                    File::create(&path).map_err(|e| {
+                        DataFusionError::Execution(format!("Error creating {path:?} {e}"))
+                    })?;
+                    println!("Output is written to {}.", path.display());
+                    print_options.output = Some(path);
+                } else {
+                    print_options.output = None;
+                    println!("Output is written to stdout.");
+                }
+                Ok(())
+            }
+            Self::Timing(timing) => {
+                if let Some(timing) = timing {
+                    print_options.timing = *timing;
+                    println!("Timing set to {}", print_options.timing);
+                } else {
+                    println!("Timing is {}", print_options.timing);
+                }
+                Ok(())
+            }
+            Self::Watch(interval, sql) => {
+                exec_watch(ctx, print_options, *interval, sql).await
+            }
         }
     }
 
@@ -142,11 +187,24 @@ impl Command {
             Self::OutputFormat(_) => {
                 ("\\pset [NAME [VALUE]]", "set table output option\n(format)")
             }
+            Self::Format(_) => ("\\format [FORMAT]", "print or set output format"),
+            Self::Output(_) => (
+                "\\o [filename]",
+                "write results to filename,\nas Parquet for .parquet files,\nor to stdout",
+            ),
+            Self::Timing(_) => (
+                "\\timing (total|detailed)?",
+                "print or set how query\ntimes are printed",
+            ),
+            Self::Watch(_, _) => (
+                "\\watch seconds query",
+                "run query every seconds\nuntil Ctrl-C",
+            ),
         }
     }
 }
 
-const ALL_COMMANDS: [Command; 9] = [
+const ALL_COMMANDS: [Command; 13] = [
     Command::ListTables,
     Command::DescribeTableStmt(String::new()),
     Command::Quit,
@@ -156,6 +214,10 @@ const ALL_COMMANDS: [Command; 9] = [
     Command::SearchFunctions(String::new()),
     Command::QuietMode(None),
     Command::OutputFormat(None),
+    Command::Format(None),
+    Command::Output(None),
+    Command::Timing(None),
+    Command::Watch(Duration::ZERO, String::new()),
 ];
 
 fn all_commands_info() -> RecordBatch {
@@ -206,6 +268,22 @@ impl FromStr for Command {
                 Self::OutputFormat(Some(subcommand.to_string()))
             }
             ("pset", None) => Self::OutputFormat(None),
+            ("format", format) => Self::Format(format.map(str::to_string)),
+            ("o", filename) => Self::Output(filename.map(str::to_string)),
+            ("timing", None) => Self::Timing(None),
+            ("timing", Some(timing)) => {
+                Self::Timing(Some(timing.parse().map_err(|_| ())?))
+            }
+            ("watch", Some(arg)) => {
+                let (seconds, sql) = arg.split_once(' ').ok_or(())?;
+                let interval = seconds
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                    .filter(|interval| !interval.is_zero())
+                    .ok_or(())?;
+                Self::Watch(interval, sql.to_string())
+            }
             _ => return Err(()),
         })
     }
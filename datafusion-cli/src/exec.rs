@@ -18,20 +18,25 @@
 //! Execution functions
 
 use crate::cli_context::CliSessionContext;
-use crate::helper::split_from_semicolon;
+use crate::helper::{split_from_semicolon, split_statements};
 use crate::print_format::PrintFormat;
 use crate::{
     command::{Command, OutputFormat},
     helper::CliHelper,
     object_storage::get_object_store,
     print_options::{MaxRows, PrintOptions},
+    progress::ProgressBar,
 };
+use datafusion::arrow::array::UInt64Array;
 use datafusion::common::instant::Instant;
 use datafusion::common::{plan_datafusion_err, plan_err};
 use datafusion::config::ConfigFileType;
+use datafusion::dataframe::DataFrameWriteOptions;
 use datafusion::datasource::listing::ListingTableUrl;
 use datafusion::error::{DataFusionError, Result};
 use datafusion::execution::memory_pool::MemoryConsumer;
+use datafusion::execution::progress::ProgressTracker;
+use datafusion::execution::TaskContext;
 use datafusion::logical_expr::{DdlStatement, LogicalPlan};
 use datafusion::physical_plan::execution_plan::EmissionType;
 use datafusion::physical_plan::spill::get_record_batch_memory_size;
@@ -43,11 +48,14 @@ use futures::StreamExt;
 use log::warn;
 use object_store::Error::Generic;
 use rustyline::error::ReadlineError;
-use rustyline::Editor;
+use rustyline::{Config, Editor};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufReader;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::signal;
 
 /// run and execute SQL statements and commands, against a context with the given print options
@@ -76,21 +84,17 @@ pub async fn exec_from_lines(
             Ok(line) if line.starts_with("#!") => {
                 continue;
             }
-            Ok(line) if line.starts_with("--") => {
-                continue;
-            }
             Ok(line) => {
-                let line = line.trim_end();
-                query.push_str(line);
-                if line.ends_with(';') {
-                    match exec_and_print(ctx, print_options, query).await {
+                query.push_str(line.trim_end());
+                query.push('\n');
+                let (statements, remainder) = split_statements(&query);
+                for statement in statements {
+                    match exec_and_print(ctx, print_options, statement).await {
                         Ok(_) => {}
                         Err(err) => eprintln!("{err}"),
                     }
-                    query = "".to_string();
-                } else {
-                    query.push('\n');
                 }
+                query = remainder;
             }
             _ => {
                 break;
@@ -98,9 +102,9 @@ pub async fn exec_from_lines(
         }
     }
 
-    // run the left over query if the last statement doesn't contain ‘;’
-    // ignore if it only consists of '\n'
-    if query.contains(|c| c != '\n') {
+    // run the left over query if the last statement doesn't contain ‘;’,
+    // the remainder being empty if it only consists of comments
+    if !query.trim_end().is_empty() {
         exec_and_print(ctx, print_options, query).await?;
     }
 
@@ -130,12 +134,14 @@ pub async fn exec_from_repl(
     ctx: &dyn CliSessionContext,
     print_options: &mut PrintOptions,
 ) -> rustyline::Result<()> {
-    let mut rl = Editor::new()?;
+    let config = Config::builder().history_ignore_dups(true)?.build();
+    let mut rl = Editor::with_config(config)?;
     rl.set_helper(Some(CliHelper::new(
         &ctx.task_ctx().session_config().options().sql_parser.dialect,
         print_options.color,
     )));
-    rl.load_history(".history").ok();
+    let history = history_path();
+    rl.load_history(&history).ok();
 
     loop {
         match rl.readline("> ") {
@@ -206,7 +212,45 @@ pub async fn exec_from_repl(
         }
     }
 
-    rl.save_history(".history")
+    if let Some(dir) = history.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    rl.save_history(&history)
+}
+
+/// Returns the file of the history of the REPL, `~/.datafusion/history`, or
+/// `.history` in the current directory if the home directory is unknown
+fn history_path() -> PathBuf {
+    match dirs::home_dir() {
+        Some(home) => home.join(".datafusion").join("history"),
+        None => PathBuf::from(".history"),
+    }
+}
+
+/// Runs the SQL statements of `sql` every `interval`, until they fail or
+/// Ctrl-C is pressed, which cancels the statement in flight
+pub(super) async fn exec_watch(
+    ctx: &dyn CliSessionContext,
+    print_options: &PrintOptions,
+    interval: Duration,
+    sql: &str,
+) -> Result<()> {
+    loop {
+        tokio::select! {
+            res = exec_and_print(ctx, print_options, sql.to_string()) => res?,
+            _ = signal::ctrl_c() => {
+                println!("^C");
+                return Ok(());
+            },
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {},
+            _ = signal::ctrl_c() => {
+                println!("^C");
+                return Ok(());
+            },
+        }
+    }
 }
 
 pub(super) async fn exec_and_print(
@@ -246,11 +290,47 @@ pub(super) async fn exec_and_print(
             }
             Err(e) => return Err(e),
         };
+
+        // Queries writing to a Parquet output file go through the Parquet
+        // sink rather than being printed
+        if let Some(path) = print_options.parquet_output() {
+            if !matches!(df.logical_plan(), LogicalPlan::EmptyRelation(_)) {
+                let planning_time = now.elapsed();
+                let written = df
+                    .write_parquet(
+                        &path.to_string_lossy(),
+                        DataFrameWriteOptions::new().with_single_file_output(true),
+                        None,
+                    )
+                    .await?;
+                let row_count = written
+                    .first()
+                    .and_then(|batch| {
+                        batch
+                            .column(0)
+                            .as_any()
+                            .downcast_ref::<UInt64Array>()
+                            .map(|count| count.value(0) as usize)
+                    })
+                    .unwrap_or_default();
+                print_options.print_written(row_count, now, Some(planning_time))?;
+                continue;
+            }
+        }
+
         let physical_plan = df.create_physical_plan().await?;
+        let planning_time = now.elapsed();
+
+        // The scans of the query report their progress for the progress bar
+        let progress = ProgressTracker::new();
+        let query_ctx = Arc::new(
+            TaskContext::from(&ctx.session_state())
+                .with_progress_tracker(progress.clone()),
+        );
 
         // Track memory usage for the query result if it's bounded
         let mut reservation =
-            MemoryConsumer::new("DataFusion-Cli").register(task_ctx.memory_pool());
+            MemoryConsumer::new("DataFusion-Cli").register(query_ctx.memory_pool());
 
         if physical_plan.boundedness().is_unbounded() {
             if physical_plan.pipeline_behavior() == EmissionType::Final {
@@ -261,14 +341,16 @@ pub(super) async fn exec_and_print(
             }
             // As the input stream comes, we can generate results.
             // However, memory safety is not guaranteed.
-            let stream = execute_stream(physical_plan, task_ctx.clone())?;
+            let stream = execute_stream(physical_plan, query_ctx)?;
             print_options
-                .print_stream(stream, now, &options.format)
+                .print_stream(stream, now, Some(planning_time), &options.format)
                 .await?;
         } else {
             // Bounded stream; collected results size is limited by the maxrows option
             let schema = physical_plan.schema();
-            let mut stream = execute_stream(physical_plan, task_ctx.clone())?;
+            let progress_bar =
+                (!print_options.quiet).then(|| ProgressBar::start(progress));
+            let mut stream = execute_stream(physical_plan, query_ctx)?;
             let mut results = vec![];
             let mut row_count = 0_usize;
             let max_rows = match print_options.maxrows {
@@ -280,17 +362,19 @@ pub(super) async fn exec_and_print(
                 let curr_num_rows = batch.num_rows();
                 // Stop collecting results if the number of rows exceeds the limit
                 // results batch should include the last batch that exceeds the limit
-                if row_count < max_rows + curr_num_rows {
+                if row_count < max_rows.saturating_add(curr_num_rows) {
                     // Try to grow the reservation to accommodate the batch in memory
                     reservation.try_grow(get_record_batch_memory_size(&batch))?;
                     results.push(batch);
                 }
                 row_count += curr_num_rows;
             }
+            drop(progress_bar);
             adjusted.into_inner().print_batches(
                 schema,
                 &results,
                 now,
+                Some(planning_time),
                 row_count,
                 &options.format,
             )?;
@@ -695,4 +779,45 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn exec_and_print_to_output_files() -> Result<()> {
+        let ctx = SessionContext::new();
+        let dir = tempfile::tempdir()?;
+        let mut print_options = PrintOptions {
+            format: PrintFormat::NdJson,
+            quiet: true,
+            maxrows: MaxRows::Unlimited,
+            color: false,
+            timing: Default::default(),
+            output: Some(dir.path().join("results.ndjson")),
+        };
+        let sql = "SELECT * FROM (VALUES (1, 'a'), (2, 'b')) t(x, y)";
+        exec_and_print(&ctx, &print_options, sql.to_string()).await?;
+        exec_and_print(&ctx, &print_options, sql.to_string()).await?;
+        let ndjson = std::fs::read_to_string(dir.path().join("results.ndjson"))?;
+        assert_eq!(
+            ndjson,
+            "{\"x\":1,\"y\":\"a\"}\n{\"x\":2,\"y\":\"b\"}\n".repeat(2)
+        );
+
+        let parquet = dir.path().join("results.parquet");
+        print_options.output = Some(parquet.clone());
+        // Statements that return no results don't write the output file
+        exec_and_print(
+            &ctx,
+            &print_options,
+            "SET datafusion.execution.batch_size = 1024".to_string(),
+        )
+        .await?;
+        assert!(!parquet.exists());
+        exec_and_print(&ctx, &print_options, sql.to_string()).await?;
+        let batches = ctx
+            .read_parquet(parquet.to_str().unwrap(), Default::default())
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 2);
+        Ok(())
+    }
 }
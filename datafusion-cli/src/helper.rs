@@ -59,7 +59,10 @@ impl CliHelper {
     }
 
     fn validate_input(&self, input: &str) -> Result<ValidationResult> {
-        if let Some(sql) = input.strip_suffix(';') {
+        if input.starts_with('\\') {
+            // command
+            Ok(ValidationResult::Valid(None))
+        } else if is_complete_statement(input) {
             let dialect = match dialect_from_str(&self.dialect) {
                 Some(dialect) => dialect,
                 None => {
@@ -69,7 +72,7 @@ impl CliHelper {
                     ))))
                 }
             };
-            let lines = split_from_semicolon(sql);
+            let (lines, _) = split_statements(input);
             for line in lines {
                 match DFParser::parse_sql_with_dialect(&line, dialect.as_ref()) {
                     Ok(statements) if statements.is_empty() => {
@@ -86,9 +89,6 @@ impl CliHelper {
                 }
             }
             Ok(ValidationResult::Valid(None))
-        } else if input.starts_with('\\') {
-            // command
-            Ok(ValidationResult::Valid(None))
         } else {
             Ok(ValidationResult::Incomplete)
         }
@@ -154,35 +154,146 @@ impl Validator for CliHelper {
 
 impl Helper for CliHelper {}
 
-/// Splits a string which consists of multiple queries.
-pub(crate) fn split_from_semicolon(sql: &str) -> Vec<String> {
-    let mut commands = Vec::new();
-    let mut current_command = String::new();
-    let mut in_single_quote = false;
-    let mut in_double_quote = false;
-
-    for c in sql.chars() {
-        if c == '\'' && !in_double_quote {
-            in_single_quote = !in_single_quote;
-        } else if c == '"' && !in_single_quote {
-            in_double_quote = !in_double_quote;
+/// Splits SQL text into its complete statements and the remaining incomplete
+/// input.
+///
+/// Statements end with a semicolon, or with `\g`, outside of string literals,
+/// quoted identifiers, dollar-quoted strings (`$$...$$` or `$tag$...$tag$`),
+/// `--` line comments and `/* */` block comments. Each complete statement is
+/// returned trimmed and ending with a semicolon; statements consisting only of
+/// comments are dropped. The remainder is empty if the rest of the input only
+/// consists of comments and whitespace, and otherwise starts at the first
+/// character of the unterminated statement.
+pub(crate) fn split_statements(sql: &str) -> (Vec<String>, String) {
+    let bytes = sql.as_bytes();
+    let mut statements = Vec::new();
+    // The start of the current statement, and whether it contains anything
+    // but comments and whitespace
+    let mut start = 0;
+    let mut has_code = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let next = bytes.get(i + 1).copied();
+        match bytes[i] {
+            b';' => {
+                if has_code {
+                    statements.push(sql[start..=i].trim().to_string());
+                }
+                start = i + 1;
+                has_code = false;
+                i += 1;
+            }
+            b'\\' if next == Some(b'g') => {
+                if has_code {
+                    statements.push(format!("{};", sql[start..i].trim()));
+                }
+                start = i + 2;
+                has_code = false;
+                i += 2;
+            }
+            b'-' if next == Some(b'-') => {
+                i = match sql[i..].find('\n') {
+                    Some(end) => i + end + 1,
+                    None => bytes.len(),
+                };
+            }
+            b'/' if next == Some(b'*') => match block_comment_end(&sql[i..]) {
+                Some(end) => i += end,
+                None => return (statements, sql[start..].trim_start().to_string()),
+            },
+            quote @ (b'\'' | b'"' | b'`') => {
+                has_code = true;
+                // A doubled quote is an escaped quote, which is skipped as
+                // the end of a quoted text followed by the start of another
+                match sql[i + 1..].find(quote as char) {
+                    Some(end) => i += end + 2,
+                    None => return (statements, sql[start..].trim_start().to_string()),
+                }
+            }
+            b'$' if !is_identifier_part(i.checked_sub(1).map(|j| bytes[j])) => {
+                has_code = true;
+                match dollar_quote_tag(&sql[i..]) {
+                    Some(tag) => match sql[i + tag.len()..].find(tag) {
+                        Some(end) => i += end + 2 * tag.len(),
+                        None => {
+                            return (statements, sql[start..].trim_start().to_string())
+                        }
+                    },
+                    None => i += 1,
+                }
+            }
+            c => {
+                has_code |= !c.is_ascii_whitespace();
+                i += 1;
+            }
         }
+    }
+    let remainder = if has_code {
+        sql[start..].trim_start().to_string()
+    } else {
+        String::new()
+    };
+    (statements, remainder)
+}
 
-        if c == ';' && !in_single_quote && !in_double_quote {
-            if !current_command.trim().is_empty() {
-                commands.push(format!("{};", current_command.trim()));
-                current_command.clear();
+/// Returns the length of the (possibly nested) block comment starting `sql`,
+/// or `None` if it is not terminated
+fn block_comment_end(sql: &str) -> Option<usize> {
+    let bytes = sql.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        match (bytes[i], bytes[i + 1]) {
+            (b'/', b'*') => {
+                depth += 1;
+                i += 2;
             }
-        } else {
-            current_command.push(c);
+            (b'*', b'/') => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => i += 1,
         }
     }
+    None
+}
 
-    if !current_command.trim().is_empty() {
-        commands.push(format!("{};", current_command.trim()));
-    }
+/// Returns the tag of the dollar-quoted string starting `sql`, such as `$$`
+/// or `$tag$`, or `None` if `sql` does not start with one, as for the
+/// placeholder `$1`
+fn dollar_quote_tag(sql: &str) -> Option<&str> {
+    let name_len = sql[1..]
+        .bytes()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == b'_')
+        .count();
+    let starts_with_digit = sql[1..].starts_with(|c: char| c.is_ascii_digit());
+    (!starts_with_digit && sql[1 + name_len..].starts_with('$'))
+        .then(|| &sql[..name_len + 2])
+}
+
+fn is_identifier_part(c: Option<u8>) -> bool {
+    c.is_some_and(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'$')
+}
 
-    commands
+/// Returns true if `sql` holds no unterminated statement, string or comment,
+/// and at least one statement or a lone semicolon
+pub(crate) fn is_complete_statement(sql: &str) -> bool {
+    let (statements, remainder) = split_statements(sql);
+    remainder.is_empty() && (!statements.is_empty() || sql.trim_end().ends_with(';'))
+}
+
+/// Splits a string which consists of multiple queries, the last of which
+/// may lack its terminating semicolon.
+pub(crate) fn split_from_semicolon(sql: &str) -> Vec<String> {
+    let (mut statements, remainder) = split_statements(sql);
+    let remainder = remainder.trim_end();
+    if !remainder.is_empty() {
+        statements.push(format!("{remainder};"));
+    }
+    statements
 }
 
 #[cfg(test)]
@@ -331,4 +442,104 @@ mod tests {
         let expected = vec!["SELECT 1;"];
         assert_eq!(split_from_semicolon(sql), expected);
     }
+
+    #[test]
+    fn test_split_statements() {
+        let split = |sql| split_statements(sql);
+
+        // Semicolons in strings, quoted identifiers and escaped quotes
+        assert_eq!(
+            split("SELECT 'it''s; here', \"a;b\", `c;d`; SELECT 2"),
+            (
+                vec!["SELECT 'it''s; here', \"a;b\", `c;d`;".to_string()],
+                "SELECT 2".to_string()
+            )
+        );
+
+        // Dollar-quoted strings, which are not confused with placeholders
+        assert_eq!(
+            split("SELECT $$a;b$$, $tag$c;$$;d$tag$; SELECT $1;"),
+            (
+                vec![
+                    "SELECT $$a;b$$, $tag$c;$$;d$tag$;".to_string(),
+                    "SELECT $1;".to_string()
+                ],
+                String::new()
+            )
+        );
+        assert_eq!(
+            split("SELECT $body$;"),
+            (vec![], "SELECT $body$;".to_string())
+        );
+
+        // Semicolons in comments
+        assert_eq!(
+            split("SELECT 1 -- one; two\n, 2 /* three; /* nested; */ four; */;"),
+            (
+                vec![
+                    "SELECT 1 -- one; two\n, 2 /* three; /* nested; */ four; */;"
+                        .to_string()
+                ],
+                String::new()
+            )
+        );
+
+        // Comment-only statements are dropped
+        assert_eq!(
+            split("-- one\n; SELECT 1; -- two"),
+            (vec!["SELECT 1;".to_string()], String::new())
+        );
+
+        // Unterminated strings and comments
+        assert_eq!(
+            split("SELECT 1; SELECT 'a;"),
+            (vec!["SELECT 1;".to_string()], "SELECT 'a;".to_string())
+        );
+        assert_eq!(
+            split("SELECT 1; /* a;"),
+            (vec!["SELECT 1;".to_string()], "/* a;".to_string())
+        );
+        assert_eq!(
+            split("SELECT 1 /* a /* b */;"),
+            (vec![], "SELECT 1 /* a /* b */;".to_string())
+        );
+
+        // \g terminates statements like a semicolon
+        assert_eq!(
+            split("SELECT 1 \\g SELECT '\\g'\\g"),
+            (
+                vec!["SELECT 1;".to_string(), "SELECT '\\g';".to_string()],
+                String::new()
+            )
+        );
+    }
+
+    #[test]
+    fn test_is_complete_statement() {
+        assert!(is_complete_statement("SELECT 1;"));
+        assert!(is_complete_statement("SELECT\n1\n;"));
+        assert!(is_complete_statement("SELECT 1; -- done"));
+        assert!(is_complete_statement("SELECT 1 \\g"));
+        assert!(is_complete_statement(";"));
+        assert!(!is_complete_statement(""));
+        assert!(!is_complete_statement("SELECT 1"));
+        assert!(!is_complete_statement("SELECT 1 -- not done;"));
+        assert!(!is_complete_statement("SELECT ';"));
+        assert!(!is_complete_statement("SELECT 1; SELECT"));
+        assert!(!is_complete_statement("SELECT 1 /* ; */ /* ;"));
+    }
+
+    #[test]
+    fn validate_multi_line_input() -> Result<()> {
+        let validator = CliHelper::default();
+        let result = validator.validate_input("SELECT 1,\n-- two;\n2")?;
+        assert!(matches!(result, ValidationResult::Incomplete));
+        let result = validator.validate_input("SELECT 1,\n-- two;\n2;")?;
+        assert!(matches!(result, ValidationResult::Valid(None)));
+        let result = validator.validate_input("SELECT 1,\n2 \\g")?;
+        assert!(matches!(result, ValidationResult::Valid(None)));
+        let result = validator.validate_input("SELECT 1 FROM;")?;
+        assert!(matches!(result, ValidationResult::Invalid(Some(_))));
+        Ok(())
+    }
 }
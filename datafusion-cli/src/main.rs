@@ -224,6 +224,8 @@ async fn main_inner() -> Result<()> {
         quiet: args.quiet,
         maxrows: args.maxrows,
         color: args.color,
+        timing: Default::default(),
+        output: None,
     };
 
     let commands = args.command;
@@ -36,6 +36,7 @@ pub enum PrintFormat {
     Tsv,
     Table,
     Json,
+    #[value(alias = "ndjson")]
     NdJson,
     Automatic,
 }
@@ -16,9 +16,12 @@
 // under the License.
 
 use std::fmt::{Display, Formatter};
+use std::fs::OpenOptions;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::str::FromStr;
+use std::time::Duration;
 
 use crate::print_format::PrintFormat;
 
@@ -67,12 +70,48 @@ impl Display for MaxRows {
     }
 }
 
+/// How the time taken by queries is printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Timing {
+    /// Only print the total elapsed time
+    #[default]
+    Total,
+    /// Also print the time spent planning and executing the query
+    Detailed,
+}
+
+impl FromStr for Timing {
+    type Err = String;
+
+    fn from_str(timing: &str) -> Result<Self, Self::Err> {
+        match timing.to_lowercase().as_str() {
+            "total" => Ok(Self::Total),
+            "detailed" => Ok(Self::Detailed),
+            _ => Err(format!(
+                "Invalid timing {timing}. Valid inputs are 'total' or 'detailed'."
+            )),
+        }
+    }
+}
+
+impl Display for Timing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Total => write!(f, "total"),
+            Self::Detailed => write!(f, "detailed"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PrintOptions {
     pub format: PrintFormat,
     pub quiet: bool,
     pub maxrows: MaxRows,
     pub color: bool,
+    pub timing: Timing,
+    /// The file the results are appended to instead of stdout, if any
+    pub output: Option<PathBuf>,
 }
 
 // Returns the query execution details formatted
@@ -80,6 +119,8 @@ fn get_execution_details_formatted(
     row_count: usize,
     maxrows: MaxRows,
     query_start_time: Instant,
+    planning_time: Option<Duration>,
+    timing: Timing,
 ) -> String {
     let nrows_shown_msg = match maxrows {
         MaxRows::Limited(nrows) if nrows < row_count => {
@@ -88,26 +129,79 @@ fn get_execution_details_formatted(
         _ => String::new(),
     };
 
+    let elapsed = query_start_time.elapsed();
+    let timing_details = match (timing, planning_time) {
+        (Timing::Detailed, Some(planning_time)) => format!(
+            " (planning {:.3} seconds, execution {:.3} seconds)",
+            planning_time.as_secs_f64(),
+            elapsed.saturating_sub(planning_time).as_secs_f64()
+        ),
+        _ => String::new(),
+    };
+
     format!(
-        "{} row(s) fetched. {}\nElapsed {:.3} seconds.\n",
+        "{} row(s) fetched. {}\nElapsed {:.3} seconds{}.\n",
         row_count,
         nrows_shown_msg,
-        query_start_time.elapsed().as_secs_f64()
+        elapsed.as_secs_f64(),
+        timing_details
     )
 }
 
 impl PrintOptions {
-    /// Print the batches to stdout using the specified format
+    /// Returns the [`Self::output`] file if it is a Parquet file, which the
+    /// results of queries are written to with the Parquet sink
+    pub fn parquet_output(&self) -> Option<&Path> {
+        self.output.as_deref().filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("parquet"))
+        })
+    }
+
+    /// Returns the writer of the results: the [`Self::output`] file, opened
+    /// for appending, or stdout if there is none or it is a Parquet file
+    fn results_writer(&self) -> Result<Box<dyn Write>> {
+        match &self.output {
+            Some(path) if self.parquet_output().is_none() => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .map_err(|e| {
+                        DataFusionError::Execution(format!(
+                            "Error opening {} {e}",
+                            path.display()
+                        ))
+                    })?;
+                Ok(Box::new(std::io::BufWriter::new(file)))
+            }
+            _ => Ok(Box::new(std::io::stdout().lock())),
+        }
+    }
+
+    /// Print the execution details of a query to stdout, unless quiet
+    fn print_execution_details(&self, details: String) -> Result<()> {
+        if !self.quiet {
+            writeln!(std::io::stdout(), "{details}")?;
+        }
+        Ok(())
+    }
+
+    /// Print the batches to stdout, or the [`Self::output`] file, using the
+    /// specified format
+    ///
+    /// `planning_time` is the part of the time since `query_start_time`
+    /// spent planning the query, printed with [`Timing::Detailed`]
     pub fn print_batches(
         &self,
         schema: SchemaRef,
         batches: &[RecordBatch],
         query_start_time: Instant,
+        planning_time: Option<Duration>,
         row_count: usize,
         format_options: &FormatOptions,
     ) -> Result<()> {
-        let stdout = std::io::stdout();
-        let mut writer = stdout.lock();
+        let mut writer = self.results_writer()?;
 
         self.format.print_batches(
             &mut writer,
@@ -117,8 +211,9 @@ impl PrintOptions {
             true,
             format_options,
         )?;
+        writer.flush()?;
 
-        let formatted_exec_details = get_execution_details_formatted(
+        self.print_execution_details(get_execution_details_formatted(
             row_count,
             if self.format == PrintFormat::Table {
                 self.maxrows
@@ -126,20 +221,18 @@ impl PrintOptions {
                 MaxRows::Unlimited
             },
             query_start_time,
-        );
-
-        if !self.quiet {
-            writeln!(writer, "{formatted_exec_details}")?;
-        }
-
-        Ok(())
+            planning_time,
+            self.timing,
+        ))
     }
 
-    /// Print the stream to stdout using the specified format
+    /// Print the stream to stdout, or the [`Self::output`] file, using the
+    /// specified format
     pub async fn print_stream(
         &self,
         mut stream: Pin<Box<dyn RecordBatchStream>>,
         query_start_time: Instant,
+        planning_time: Option<Duration>,
         format_options: &FormatOptions,
     ) -> Result<()> {
         if self.format == PrintFormat::Table {
@@ -148,8 +241,7 @@ impl PrintOptions {
             ));
         };
 
-        let stdout = std::io::stdout();
-        let mut writer = stdout.lock();
+        let mut writer = self.results_writer()?;
 
         let mut row_count = 0_usize;
         let mut with_header = true;
@@ -165,19 +257,33 @@ impl PrintOptions {
                 with_header,
                 format_options,
             )?;
+            writer.flush()?;
             with_header = false;
         }
 
-        let formatted_exec_details = get_execution_details_formatted(
+        self.print_execution_details(get_execution_details_formatted(
             row_count,
             MaxRows::Unlimited,
             query_start_time,
-        );
-
-        if !self.quiet {
-            writeln!(writer, "{formatted_exec_details}")?;
-        }
+            planning_time,
+            self.timing,
+        ))
+    }
 
-        Ok(())
+    /// Print the execution details of a query whose results were written to
+    /// the [`Self::output`] file by a sink, rather than printed
+    pub fn print_written(
+        &self,
+        row_count: usize,
+        query_start_time: Instant,
+        planning_time: Option<Duration>,
+    ) -> Result<()> {
+        self.print_execution_details(get_execution_details_formatted(
+            row_count,
+            MaxRows::Unlimited,
+            query_start_time,
+            planning_time,
+            self.timing,
+        ))
     }
 }
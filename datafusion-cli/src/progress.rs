@@ -0,0 +1,127 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+//! Progress bar of the scans of a query, rendered on stderr
+
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use datafusion::common::runtime::SpawnedTask;
+use datafusion::execution::memory_pool::human_readable_size;
+use datafusion::execution::progress::{ProgressSnapshot, ProgressTracker};
+
+/// Queries completing faster than this show no progress bar
+const PROGRESS_BAR_DELAY: Duration = Duration::from_millis(500);
+const PROGRESS_BAR_INTERVAL: Duration = Duration::from_millis(100);
+const PROGRESS_BAR_WIDTH: usize = 30;
+
+/// Renders the progress of the file scans of a query on stderr, while it
+/// is a terminal. The progress bar is cleared once dropped.
+pub struct ProgressBar {
+    task: Option<SpawnedTask<()>>,
+    state: Arc<ProgressBarState>,
+}
+
+/// Accessed while holding the lock of stderr, so that the bar is not drawn
+/// again once cleared
+#[derive(Default)]
+struct ProgressBarState {
+    drawn: AtomicBool,
+    stopped: AtomicBool,
+}
+
+impl ProgressBar {
+    /// Start rendering the progress reported into `tracker`
+    pub fn start(tracker: ProgressTracker) -> Self {
+        let state = Arc::new(ProgressBarState::default());
+        if !std::io::stderr().is_terminal() {
+            return Self { task: None, state };
+        }
+        let task_state = Arc::clone(&state);
+        let task = SpawnedTask::spawn(async move {
+            tokio::time::sleep(PROGRESS_BAR_DELAY).await;
+            let mut interval = tokio::time::interval(PROGRESS_BAR_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Some(line) = render(&tracker.snapshot()) {
+                    let mut stderr = std::io::stderr().lock();
+                    if task_state.stopped.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    let _ = write!(stderr, "\r{line}");
+                    let _ = stderr.flush();
+                    task_state.drawn.store(true, Ordering::Relaxed);
+                }
+            }
+        });
+        Self {
+            task: Some(task),
+            state,
+        }
+    }
+}
+
+impl Drop for ProgressBar {
+    fn drop(&mut self) {
+        // Aborts the task
+        self.task.take();
+        let mut stderr = std::io::stderr().lock();
+        self.state.stopped.store(true, Ordering::Relaxed);
+        if self.state.drawn.load(Ordering::Relaxed) {
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+        }
+    }
+}
+
+/// Returns the line of the progress bar of `snapshot`, or `None` if the
+/// query scans no files
+fn render(snapshot: &ProgressSnapshot) -> Option<String> {
+    let fraction = snapshot.fraction()?;
+    let filled = (fraction * PROGRESS_BAR_WIDTH as f64).round() as usize;
+    Some(format!(
+        "[{}{}] {:>3.0}% {} / {}, {} rows",
+        "#".repeat(filled),
+        "-".repeat(PROGRESS_BAR_WIDTH - filled),
+        fraction * 100.0,
+        human_readable_size(snapshot.bytes_scanned as usize),
+        human_readable_size(snapshot.total_bytes as usize),
+        snapshot.rows_produced,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        assert_eq!(render(&ProgressSnapshot::default()), None);
+
+        let snapshot = ProgressSnapshot {
+            bytes_scanned: 3 << 20,
+            total_bytes: 12 << 20,
+            rows_produced: 1000,
+            operators_completed: 1,
+        };
+        assert_eq!(
+            render(&snapshot).unwrap(),
+            "[########----------------------]  25% 3.0 MB / 12.0 MB, 1000 rows"
+        );
+    }
+}
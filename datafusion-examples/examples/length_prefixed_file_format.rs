@@ -0,0 +1,653 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! This example shows how to implement a file format from scratch, covering
+//! schema inference, scanning and writing, and how to use it from SQL once it
+//! is registered with the [`SessionState`]:
+//!
+//! * `CREATE EXTERNAL TABLE ... STORED AS LPF OPTIONS ('lpf.magic' ...)`
+//! * `INSERT INTO` the table
+//! * `SELECT` from the table
+//! * `COPY ... TO ... STORED AS LPF`
+//!
+//! The toy "length prefixed" (LPF) format used here starts every file with a
+//! magic string followed by a sequence of records, each made of a little
+//! endian `u32` length and that many bytes of payload. The first record holds
+//! the schema, every following record holds one row.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Int64Array, Int64Builder, StringArray, StringBuilder};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use datafusion::assert_batches_eq;
+use datafusion::catalog::Session;
+use datafusion::common::runtime::{JoinSet, SpawnedTask};
+use datafusion::common::{
+    config_err, exec_err, internal_err, not_impl_err, DataFusionError, GetExt, Statistics,
+};
+use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
+use datafusion::datasource::file_format::write::demux::DemuxedStreamReceiver;
+use datafusion::datasource::file_format::{FileFormat, FileFormatFactory};
+use datafusion::datasource::listing::PartitionedFile;
+use datafusion::datasource::physical_plan::{
+    FileMeta, FileOpenFuture, FileOpener, FileScanConfig, FileScanConfigBuilder,
+    FileSink, FileSinkConfig, FileSource,
+};
+use datafusion::datasource::sink::{DataSink, DataSinkExec};
+use datafusion::datasource::source::DataSourceExec;
+use datafusion::error::Result;
+use datafusion::execution::session_state::SessionStateBuilder;
+use datafusion::execution::{SendableRecordBatchStream, TaskContext};
+use datafusion::logical_expr::dml::InsertOp;
+use datafusion::physical_expr_common::sort_expr::LexRequirement;
+use datafusion::physical_plan::metrics::ExecutionPlanMetricsSet;
+use datafusion::physical_plan::{DisplayAs, DisplayFormatType, ExecutionPlan};
+use datafusion::prelude::{SessionConfig, SessionContext};
+use futures::StreamExt;
+use object_store::{ObjectMeta, ObjectStore, PutPayload};
+use tempfile::tempdir;
+
+/// Magic string used when the `lpf.magic` option is not set
+const DEFAULT_MAGIC: &str = "LPF1";
+
+/// Type tags used in the schema record
+const INT64_TAG: u8 = 0;
+const UTF8_TAG: u8 = 1;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // LPF files only store `Utf8` strings, so keep `VARCHAR` columns from
+    // being planned as `Utf8View`
+    let config = SessionConfig::new()
+        .set_bool("datafusion.sql_parser.map_string_types_to_utf8view", false);
+    // Register the format, which makes `STORED AS LPF` available in
+    // `CREATE EXTERNAL TABLE` and `COPY`
+    let mut state = SessionStateBuilder::new()
+        .with_config(config)
+        .with_default_features()
+        .build();
+    state.register_file_format(Arc::new(LengthPrefixedFormatFactory), true)?;
+    let ctx = SessionContext::new_with_state(state);
+
+    let dir = tempdir()?;
+    let location = dir.path().join("events");
+    std::fs::create_dir(&location)?;
+
+    // Options prefixed with the name of the format are passed to the
+    // `FileFormatFactory` as they are
+    ctx.sql(&format!(
+        "CREATE EXTERNAL TABLE events (id BIGINT, name VARCHAR) \
+         STORED AS LPF LOCATION '{}/' OPTIONS ('lpf.magic' 'TOY1')",
+        location.display()
+    ))
+    .await?;
+
+    // INSERT INTO writes a new file using the `FileSink` of the format
+    ctx.sql("INSERT INTO events VALUES (2, 'two'), (1, 'one'), (3, NULL)")
+        .await?
+        .collect()
+        .await?;
+
+    let batches = ctx
+        .sql("SELECT id, name FROM events ORDER BY id")
+        .await?
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        [
+            "+----+------+",
+            "| id | name |",
+            "+----+------+",
+            "| 1  | one  |",
+            "| 2  | two  |",
+            "| 3  |      |",
+            "+----+------+",
+        ],
+        &batches
+    );
+
+    // COPY TO resolves the format by the name given in STORED AS
+    let copied = dir.path().join("copied.lpf");
+    ctx.sql(&format!(
+        "COPY (SELECT name, id * 10 AS id FROM events WHERE name IS NOT NULL) \
+         TO '{}' STORED AS LPF OPTIONS ('lpf.magic' 'TOY1')",
+        copied.display()
+    ))
+    .await?
+    .collect()
+    .await?;
+
+    ctx.sql(&format!(
+        "CREATE EXTERNAL TABLE copied STORED AS LPF LOCATION '{}' \
+         OPTIONS ('lpf.magic' 'TOY1')",
+        copied.display()
+    ))
+    .await?;
+    let batches = ctx
+        .sql("SELECT * FROM copied ORDER BY id")
+        .await?
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        [
+            "+------+----+",
+            "| name | id |",
+            "+------+----+",
+            "| one  | 10 |",
+            "| two  | 20 |",
+            "+------+----+",
+        ],
+        &batches
+    );
+
+    // Files written with another magic string are rejected
+    ctx.sql(&format!(
+        "CREATE EXTERNAL TABLE wrong_magic STORED AS LPF LOCATION '{}'",
+        copied.display()
+    ))
+    .await
+    .expect_err("the magic string does not match");
+
+    Ok(())
+}
+
+/// Creates [`LengthPrefixedFormat`]s from the options of `CREATE EXTERNAL
+/// TABLE` and `COPY` statements
+#[derive(Debug)]
+struct LengthPrefixedFormatFactory;
+
+impl FileFormatFactory for LengthPrefixedFormatFactory {
+    fn create(
+        &self,
+        _state: &dyn Session,
+        format_options: &HashMap<String, String>,
+    ) -> Result<Arc<dyn FileFormat>> {
+        let mut magic = DEFAULT_MAGIC.to_string();
+        for (key, value) in format_options {
+            match key.as_str() {
+                "lpf.magic" => magic = value.clone(),
+                // Options such as `execution.keep_partition_by_columns` are
+                // handled by the planner
+                key if key.starts_with("execution.") => {}
+                _ => return config_err!("Unsupported LPF option \"{key}\""),
+            }
+        }
+        Ok(Arc::new(LengthPrefixedFormat { magic }))
+    }
+
+    fn default(&self) -> Arc<dyn FileFormat> {
+        Arc::new(LengthPrefixedFormat {
+            magic: DEFAULT_MAGIC.to_string(),
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl GetExt for LengthPrefixedFormatFactory {
+    fn get_ext(&self) -> String {
+        "lpf".to_string()
+    }
+}
+
+/// The length prefixed file format
+#[derive(Debug)]
+struct LengthPrefixedFormat {
+    magic: String,
+}
+
+#[async_trait]
+impl FileFormat for LengthPrefixedFormat {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_ext(&self) -> String {
+        "lpf".to_string()
+    }
+
+    fn get_ext_with_compression(&self, c: &FileCompressionType) -> Result<String> {
+        if c.is_compressed() {
+            return not_impl_err!("Compressed LPF files are not supported");
+        }
+        Ok(self.get_ext())
+    }
+
+    async fn infer_schema(
+        &self,
+        _state: &dyn Session,
+        store: &Arc<dyn ObjectStore>,
+        objects: &[ObjectMeta],
+    ) -> Result<SchemaRef> {
+        let mut schemas = vec![];
+        for object in objects {
+            let data = store.get(&object.location).await?.bytes().await?;
+            let mut reader = Reader::try_new(data, &self.magic)?;
+            schemas.push(reader.read_schema()?);
+        }
+        Ok(Arc::new(Schema::try_merge(schemas)?))
+    }
+
+    async fn infer_stats(
+        &self,
+        _state: &dyn Session,
+        _store: &Arc<dyn ObjectStore>,
+        table_schema: SchemaRef,
+        _object: &ObjectMeta,
+    ) -> Result<Statistics> {
+        Ok(Statistics::new_unknown(&table_schema))
+    }
+
+    async fn create_physical_plan(
+        &self,
+        _state: &dyn Session,
+        conf: FileScanConfig,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let config = FileScanConfigBuilder::from(conf)
+            .with_source(self.file_source())
+            .build();
+        Ok(DataSourceExec::from_data_source(config))
+    }
+
+    async fn create_writer_physical_plan(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        _state: &dyn Session,
+        conf: FileSinkConfig,
+        order_requirements: Option<LexRequirement>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if conf.insert_op != InsertOp::Append {
+            return not_impl_err!("Overwrites are not implemented for LPF files");
+        }
+        for field in conf.output_schema().fields() {
+            type_tag(field.data_type())?;
+        }
+        let sink = Arc::new(LengthPrefixedSink {
+            config: conf,
+            magic: self.magic.clone(),
+        });
+        Ok(Arc::new(DataSinkExec::new(input, sink, order_requirements)))
+    }
+
+    fn file_source(&self) -> Arc<dyn FileSource> {
+        Arc::new(LengthPrefixedSource {
+            magic: self.magic.clone(),
+            batch_size: 8192,
+            projected_statistics: None,
+            metrics: ExecutionPlanMetricsSet::new(),
+        })
+    }
+}
+
+/// [`FileSource`] for LPF files
+#[derive(Debug, Clone)]
+struct LengthPrefixedSource {
+    magic: String,
+    batch_size: usize,
+    projected_statistics: Option<Statistics>,
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl FileSource for LengthPrefixedSource {
+    fn create_file_opener(
+        &self,
+        object_store: Arc<dyn ObjectStore>,
+        base_config: &FileScanConfig,
+        _partition: usize,
+    ) -> Arc<dyn FileOpener> {
+        Arc::new(LengthPrefixedOpener {
+            object_store,
+            magic: self.magic.clone(),
+            batch_size: self.batch_size,
+            projection: base_config.file_column_projection_indices(),
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn with_batch_size(&self, batch_size: usize) -> Arc<dyn FileSource> {
+        Arc::new(Self {
+            batch_size,
+            ..self.clone()
+        })
+    }
+
+    fn with_schema(&self, _schema: SchemaRef) -> Arc<dyn FileSource> {
+        Arc::new(self.clone())
+    }
+
+    fn with_projection(&self, _config: &FileScanConfig) -> Arc<dyn FileSource> {
+        Arc::new(self.clone())
+    }
+
+    fn with_statistics(&self, statistics: Statistics) -> Arc<dyn FileSource> {
+        Arc::new(Self {
+            projected_statistics: Some(statistics),
+            ..self.clone()
+        })
+    }
+
+    fn metrics(&self) -> &ExecutionPlanMetricsSet {
+        &self.metrics
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        match &self.projected_statistics {
+            Some(statistics) => Ok(statistics.clone()),
+            None => internal_err!("projected_statistics must be set"),
+        }
+    }
+
+    fn file_type(&self) -> &str {
+        "lpf"
+    }
+}
+
+/// Reads whole LPF files and decodes them into [`RecordBatch`]es
+struct LengthPrefixedOpener {
+    object_store: Arc<dyn ObjectStore>,
+    magic: String,
+    batch_size: usize,
+    projection: Option<Vec<usize>>,
+}
+
+impl FileOpener for LengthPrefixedOpener {
+    fn open(
+        &self,
+        file_meta: FileMeta,
+        _file: PartitionedFile,
+    ) -> Result<FileOpenFuture> {
+        let object_store = Arc::clone(&self.object_store);
+        let magic = self.magic.clone();
+        let batch_size = self.batch_size;
+        let projection = self.projection.clone();
+        Ok(Box::pin(async move {
+            let data = object_store
+                .get(file_meta.location())
+                .await?
+                .bytes()
+                .await?;
+            let mut reader = Reader::try_new(data, &magic)?;
+            let schema = Arc::new(reader.read_schema()?);
+            let mut batches = vec![];
+            while reader.has_remaining() {
+                let batch = reader.read_batch(&schema, batch_size)?;
+                let batch = match &projection {
+                    Some(projection) => batch.project(projection)?,
+                    None => batch,
+                };
+                batches.push(Ok(batch));
+            }
+            Ok(futures::stream::iter(batches).boxed())
+        }))
+    }
+}
+
+/// Decodes the records of an LPF file held in memory
+struct Reader {
+    data: Bytes,
+}
+
+impl Reader {
+    fn try_new(mut data: Bytes, magic: &str) -> Result<Self> {
+        if !data.starts_with(magic.as_bytes()) {
+            return exec_err!("Not an LPF file with magic string \"{magic}\"");
+        }
+        data.advance(magic.len());
+        Ok(Self { data })
+    }
+
+    fn has_remaining(&self) -> bool {
+        self.data.has_remaining()
+    }
+
+    fn read_record(&mut self) -> Result<Bytes> {
+        let len = self.data.try_get_u32_le().map_err(truncated)? as usize;
+        if self.data.remaining() < len {
+            return exec_err!("Truncated LPF record");
+        }
+        Ok(self.data.split_to(len))
+    }
+
+    fn read_schema(&mut self) -> Result<Schema> {
+        let mut record = self.read_record()?;
+        let mut fields = vec![];
+        while record.has_remaining() {
+            let data_type = match record.try_get_u8() {
+                Ok(INT64_TAG) => DataType::Int64,
+                Ok(UTF8_TAG) => DataType::Utf8,
+                _ => return exec_err!("Invalid LPF schema record"),
+            };
+            let name = read_string(&mut record)?;
+            fields.push(Field::new(name, data_type, true));
+        }
+        Ok(Schema::new(fields))
+    }
+
+    fn read_batch(
+        &mut self,
+        schema: &SchemaRef,
+        batch_size: usize,
+    ) -> Result<RecordBatch> {
+        let mut builders: Vec<ColumnBuilder> = schema
+            .fields()
+            .iter()
+            .map(|field| match field.data_type() {
+                DataType::Int64 => ColumnBuilder::Int64(Int64Builder::new()),
+                _ => ColumnBuilder::Utf8(StringBuilder::new()),
+            })
+            .collect();
+        let mut rows = 0;
+        while rows < batch_size && self.has_remaining() {
+            let mut record = self.read_record()?;
+            for builder in builders.iter_mut() {
+                let is_valid = record.try_get_u8().map_err(truncated)? == 1;
+                match builder {
+                    ColumnBuilder::Int64(builder) if is_valid => {
+                        builder.append_value(record.try_get_i64_le().map_err(truncated)?)
+                    }
+                    ColumnBuilder::Utf8(builder) if is_valid => {
+                        builder.append_value(read_string(&mut record)?)
+                    }
+                    ColumnBuilder::Int64(builder) => builder.append_null(),
+                    ColumnBuilder::Utf8(builder) => builder.append_null(),
+                }
+            }
+            rows += 1;
+        }
+        let columns = builders
+            .into_iter()
+            .map(|builder| -> ArrayRef {
+                match builder {
+                    ColumnBuilder::Int64(mut builder) => Arc::new(builder.finish()),
+                    ColumnBuilder::Utf8(mut builder) => Arc::new(builder.finish()),
+                }
+            })
+            .collect();
+        Ok(RecordBatch::try_new(Arc::clone(schema), columns)?)
+    }
+}
+
+enum ColumnBuilder {
+    Int64(Int64Builder),
+    Utf8(StringBuilder),
+}
+
+fn truncated<E>(_: E) -> DataFusionError {
+    DataFusionError::Execution("Truncated LPF record".to_string())
+}
+
+fn read_string(record: &mut Bytes) -> Result<String> {
+    let len = record.try_get_u32_le().map_err(truncated)? as usize;
+    if record.remaining() < len {
+        return exec_err!("Truncated LPF string");
+    }
+    String::from_utf8(record.split_to(len).to_vec())
+        .map_err(|e| DataFusionError::Execution(format!("Invalid LPF string: {e}")))
+}
+
+fn type_tag(data_type: &DataType) -> Result<u8> {
+    match data_type {
+        DataType::Int64 => Ok(INT64_TAG),
+        DataType::Utf8 => Ok(UTF8_TAG),
+        _ => not_impl_err!("LPF files cannot store columns of type {data_type}"),
+    }
+}
+
+/// Encodes [`RecordBatch`]es as LPF records
+fn put_record(buf: &mut BytesMut, record: &[u8]) {
+    buf.put_u32_le(record.len() as u32);
+    buf.put_slice(record);
+}
+
+fn put_string(buf: &mut BytesMut, value: &str) {
+    buf.put_u32_le(value.len() as u32);
+    buf.put_slice(value.as_bytes());
+}
+
+fn encode_schema(buf: &mut BytesMut, schema: &Schema) -> Result<()> {
+    let mut record = BytesMut::new();
+    for field in schema.fields() {
+        record.put_u8(type_tag(field.data_type())?);
+        put_string(&mut record, field.name());
+    }
+    put_record(buf, &record);
+    Ok(())
+}
+
+fn encode_batch(buf: &mut BytesMut, batch: &RecordBatch) -> Result<()> {
+    for row in 0..batch.num_rows() {
+        let mut record = BytesMut::new();
+        for column in batch.columns() {
+            if column.is_null(row) {
+                record.put_u8(0);
+                continue;
+            }
+            record.put_u8(1);
+            if let Some(array) = column.as_any().downcast_ref::<Int64Array>() {
+                record.put_i64_le(array.value(row));
+            } else if let Some(array) = column.as_any().downcast_ref::<StringArray>() {
+                put_string(&mut record, array.value(row));
+            } else {
+                return not_impl_err!(
+                    "LPF files cannot store columns of type {}",
+                    column.data_type()
+                );
+            }
+        }
+        put_record(buf, &record);
+    }
+    Ok(())
+}
+
+/// Writes the output of `INSERT INTO` and `COPY` as LPF files
+#[derive(Debug)]
+struct LengthPrefixedSink {
+    config: FileSinkConfig,
+    magic: String,
+}
+
+#[async_trait]
+impl FileSink for LengthPrefixedSink {
+    fn config(&self) -> &FileSinkConfig {
+        &self.config
+    }
+
+    async fn spawn_writer_tasks_and_join(
+        &self,
+        _context: &Arc<TaskContext>,
+        demux_task: SpawnedTask<Result<()>>,
+        mut file_stream_rx: DemuxedStreamReceiver,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> Result<u64> {
+        let mut file_write_tasks = JoinSet::new();
+        while let Some((path, mut rx)) = file_stream_rx.recv().await {
+            let mut buf = BytesMut::from(self.magic.as_bytes());
+            let mut schema_written = false;
+            let object_store = Arc::clone(&object_store);
+            file_write_tasks.spawn(async move {
+                let mut row_count = 0;
+                while let Some(batch) = rx.recv().await {
+                    if !schema_written {
+                        encode_schema(&mut buf, &batch.schema())?;
+                        schema_written = true;
+                    }
+                    encode_batch(&mut buf, &batch)?;
+                    row_count += batch.num_rows() as u64;
+                }
+                object_store
+                    .put(&path, PutPayload::from_bytes(buf.freeze()))
+                    .await?;
+                Ok::<_, DataFusionError>(row_count)
+            });
+        }
+
+        let mut row_count = 0;
+        while let Some(result) = file_write_tasks.join_next().await {
+            row_count += result.map_err(DataFusionError::ExecutionJoin)??;
+        }
+
+        demux_task
+            .join_unwind()
+            .await
+            .map_err(DataFusionError::ExecutionJoin)??;
+        Ok(row_count)
+    }
+}
+
+impl DisplayAs for LengthPrefixedSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "LengthPrefixedSink(magic={})", self.magic)
+            }
+            DisplayFormatType::TreeRender => {
+                writeln!(f, "format: lpf")?;
+                write!(f, "file={}", &self.config.original_url)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DataSink for LengthPrefixedSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> &SchemaRef {
+        self.config.output_schema()
+    }
+
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        FileSink::write_all(self, data, context).await
+    }
+}
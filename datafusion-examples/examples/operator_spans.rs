@@ -0,0 +1,138 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! This example shows how to export a `tracing` span for each partition of each
+//! operator of a query, with the `InstrumentStreams` physical optimizer rule and
+//! the `TracingInstrumentation` of the `tracing` feature.
+//!
+//! The spans are printed when they close, with the number of rows the operator
+//! produced and its metrics for the partition:
+//!
+//! ```text
+//! INFO datafusion.operator{operator="HashJoinExec" partition=0 output_rows=7 output_batches=1 metrics=output_rows=7, elapsed_compute=...}: close time.busy=... time.idle=...
+//! ```
+//!
+//! To export the spans to OpenTelemetry, replace the `fmt` subscriber with one
+//! that has a `tracing-opentelemetry` layer.
+
+use std::sync::Arc;
+
+use datafusion::common::runtime::{set_join_set_tracer, JoinSetTracer};
+use datafusion::error::Result;
+use datafusion::execution::SessionStateBuilder;
+use datafusion::physical_optimizer::instrument_streams::InstrumentStreams;
+use datafusion::physical_plan::instrument::TracingInstrumentation;
+use datafusion::prelude::*;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use std::any::Any;
+use tracing::{Instrument, Span};
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// TPC-H query 3, the shipping priority query
+const TPCH_Q3: &str = "
+SELECT l_orderkey,
+       sum(l_extendedprice * (1 - l_discount)) AS revenue,
+       o_orderdate,
+       o_shippriority
+FROM customer, orders, lineitem
+WHERE c_mktsegment = 'BUILDING'
+  AND c_custkey = o_custkey
+  AND l_orderkey = o_orderkey
+  AND o_orderdate < DATE '1995-03-15'
+  AND l_shipdate > DATE '1995-03-15'
+GROUP BY l_orderkey, o_orderdate, o_shippriority
+ORDER BY revenue DESC, o_orderdate
+LIMIT 10";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Print the spans of the operators when they close
+    tracing_subscriber::fmt()
+        .with_span_events(FmtSpan::CLOSE)
+        .init();
+    // Make the spans of inputs executed in spawned tasks children of the span
+    // of the operator spawning them
+    set_join_set_tracer(&SpanTracer).expect("Failed to set tracer");
+
+    // Add the rule after the default rules, so it wraps the final plan
+    let state = SessionStateBuilder::new()
+        .with_default_features()
+        .with_physical_optimizer_rule(Arc::new(InstrumentStreams::new(Arc::new(
+            TracingInstrumentation::new(),
+        ))))
+        .build();
+    let ctx = SessionContext::new_with_state(state);
+    // Create the tables without instrumentation, to only see the spans of
+    // the query
+    let tables = SessionContext::new();
+    create_tables(&tables).await?;
+    for name in ["customer", "orders", "lineitem"] {
+        ctx.register_table(name, tables.table_provider(name).await?)?;
+    }
+
+    let df = ctx.sql(TPCH_Q3).await?;
+    df.show().instrument(tracing::info_span!("tpch_q3")).await?;
+    Ok(())
+}
+
+/// Creates small in-memory versions of the TPC-H tables of query 3, whose
+/// dates are between 1995-01-01 (day 9131) and 1995-06-01
+async fn create_tables(ctx: &SessionContext) -> Result<()> {
+    let tables = [
+        "CREATE TABLE customer AS
+         SELECT value AS c_custkey,
+                CASE WHEN value % 5 = 0 THEN 'BUILDING' ELSE 'MACHINERY' END AS c_mktsegment
+         FROM range(1, 1001)",
+        "CREATE TABLE orders AS
+         SELECT value AS o_orderkey,
+                value % 1000 + 1 AS o_custkey,
+                arrow_cast(CAST(9131 + value % 150 AS INT), 'Date32') AS o_orderdate,
+                0 AS o_shippriority
+         FROM range(1, 10001)",
+        "CREATE TABLE lineitem AS
+         SELECT value % 10000 + 1 AS l_orderkey,
+                (value % 100) * 10.5 AS l_extendedprice,
+                (value % 10) / 100.0 AS l_discount,
+                arrow_cast(CAST(9162 + value % 120 AS INT), 'Date32') AS l_shipdate
+         FROM range(1, 40001)",
+    ];
+    for sql in tables {
+        ctx.sql(sql).await?.collect().await?;
+    }
+    Ok(())
+}
+
+/// Runs the tasks spawned by DataFusion in the current span
+struct SpanTracer;
+
+impl JoinSetTracer for SpanTracer {
+    fn trace_future(
+        &self,
+        fut: BoxFuture<'static, Box<dyn Any + Send>>,
+    ) -> BoxFuture<'static, Box<dyn Any + Send>> {
+        fut.in_current_span().boxed()
+    }
+
+    fn trace_block(
+        &self,
+        f: Box<dyn FnOnce() -> Box<dyn Any + Send> + Send>,
+    ) -> Box<dyn FnOnce() -> Box<dyn Any + Send> + Send> {
+        let span = Span::current();
+        Box::new(move || span.in_scope(f))
+    }
+}
@@ -0,0 +1,162 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+/// This example shows how a [`SchemaProvider`] that fronts a slow, remote
+/// metastore is used by DataFusion:
+///
+/// 1. Planning a query only calls the async [`SchemaProvider::table`] for the
+///    tables the query references, and never enumerates the schema
+/// 2. [`CachingSchemaProvider`] avoids fetching the same table again for
+///    repeated queries
+/// 3. `information_schema` can skip schemas that can't be enumerated cheaply
+use arrow::array::record_batch;
+use async_trait::async_trait;
+use datafusion::catalog::{
+    CachingSchemaProvider, MemTable, SchemaProvider, TableProvider,
+};
+use datafusion::common::{assert_batches_eq, internal_datafusion_err, Result};
+use datafusion::prelude::{SessionConfig, SessionContext};
+use std::any::Any;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let config = SessionConfig::new().with_information_schema(true);
+    let ctx = SessionContext::new_with_config(config);
+    let catalog = ctx
+        .catalog("datafusion")
+        .ok_or_else(|| internal_datafusion_err!("default catalog was not installed"))?;
+
+    // Register the remote schema directly: every query fetches its tables
+    let remote = Arc::new(RemoteSchema::default());
+    catalog.register_schema("remote", Arc::clone(&remote) as _)?;
+
+    // Planning a query referencing a single table performs a single lookup
+    // and never lists the tables of the schema
+    let df = ctx.sql("SELECT id, name FROM remote.users").await?;
+    assert_eq!(remote.lookups(), 1);
+    assert_eq!(remote.listings(), 0);
+
+    assert_batches_eq!(
+        [
+            "+----+-------+",
+            "| id | name  |",
+            "+----+-------+",
+            "| 1  | alpha |",
+            "| 2  | beta  |",
+            "+----+-------+",
+        ],
+        &df.collect().await?
+    );
+
+    // Without caching, every query fetches the table again
+    ctx.sql("SELECT count(*) FROM remote.users").await?;
+    assert_eq!(remote.lookups(), 2);
+
+    // Wrap the remote schema to cache its lookups for a minute
+    let remote = Arc::new(RemoteSchema::default());
+    let cached =
+        CachingSchemaProvider::new(Arc::clone(&remote) as _, Duration::from_secs(60));
+    catalog.register_schema("remote", Arc::new(cached))?;
+
+    for _ in 0..3 {
+        ctx.sql("SELECT id FROM remote.users")
+            .await?
+            .collect()
+            .await?;
+    }
+    assert_eq!(remote.lookups(), 1);
+
+    // Listing every table in information_schema would fetch the whole
+    // remote schema. As `RemoteSchema` reports that enumerating it is
+    // expensive, information_schema can be configured to skip it
+    ctx.sql("SET datafusion.catalog.information_schema_skip_expensive_schemas = true")
+        .await?;
+    let tables = ctx
+        .sql("SELECT table_name FROM information_schema.tables WHERE table_schema = 'remote'")
+        .await?
+        .collect()
+        .await?;
+    assert_eq!(tables.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+    assert_eq!(remote.listings(), 0);
+
+    println!(
+        "Ran 3 queries against the cached schema with {} remote lookup(s)",
+        remote.lookups()
+    );
+    Ok(())
+}
+
+/// A [`SchemaProvider`] for a remote metastore, where every call is a network
+/// round trip. It counts its calls so the example can check how often
+/// DataFusion uses it.
+#[derive(Debug, Default)]
+struct RemoteSchema {
+    lookups: AtomicUsize,
+    listings: AtomicUsize,
+}
+
+impl RemoteSchema {
+    /// Simulated latency of a call to the remote metastore
+    const LATENCY: Duration = Duration::from_millis(50);
+
+    fn lookups(&self) -> usize {
+        self.lookups.load(Ordering::SeqCst)
+    }
+
+    fn listings(&self) -> usize {
+        self.listings.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl SchemaProvider for RemoteSchema {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn table_names(&self) -> Vec<String> {
+        // A real implementation would have to block on the remote metastore
+        // here, which is why DataFusion avoids calling it during planning
+        self.listings.fetch_add(1, Ordering::SeqCst);
+        std::thread::sleep(Self::LATENCY);
+        vec!["users".to_string()]
+    }
+
+    async fn table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Self::LATENCY).await;
+        if name != "users" {
+            return Ok(None);
+        }
+
+        let batch =
+            record_batch!(("id", Int32, [1, 2]), ("name", Utf8, ["alpha", "beta"]))?;
+        let table = MemTable::try_new(batch.schema(), vec![vec![batch]])?;
+        Ok(Some(Arc::new(table)))
+    }
+
+    fn table_exist(&self, name: &str) -> bool {
+        name == "users"
+    }
+
+    fn is_cheap_to_enumerate(&self) -> bool {
+        false
+    }
+}
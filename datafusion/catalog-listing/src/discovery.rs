@@ -0,0 +1,390 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`DiscoveringSchemaProvider`]: a [`SchemaProvider`] discovering its tables
+//! below an object store prefix
+
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use datafusion_catalog::{SchemaProvider, TableProvider};
+use datafusion_common::instant::Instant;
+use datafusion_common::{DFSchema, Result, TableReference};
+use datafusion_datasource::ListingTableUrl;
+use datafusion_expr::CreateExternalTable;
+use futures::TryStreamExt;
+use object_store::path::DELIMITER;
+use object_store::{ObjectMeta, ObjectStore};
+
+/// The file types recognized by default by a [`DiscoveringSchemaProvider`]
+pub const DEFAULT_DISCOVERED_FILE_TYPES: &[&str] =
+    &["parquet", "csv", "json", "avro", "arrow"];
+
+/// Creates the tables discovered by a [`DiscoveringSchemaProvider`]
+///
+/// Unlike a `TableProviderFactory`, the tables are created without a
+/// `Session`, as they are created when first referenced by a query.
+#[async_trait]
+pub trait DiscoveredTableFactory: Debug + Sync + Send {
+    /// Creates the table described by `cmd`
+    async fn create(&self, cmd: &CreateExternalTable) -> Result<Arc<dyn TableProvider>>;
+}
+
+/// A [`SchemaProvider`] discovering its tables below an object store prefix
+///
+/// Each subdirectory of the prefix is a table, named after the subdirectory,
+/// and so is each file directly below the prefix, named after the file
+/// without its extensions. Given the prefix `s3://bucket/landing/`:
+///
+/// - `s3://bucket/landing/events/day=1/part-0.parquet` belongs to the
+///   partitioned parquet table `events`, with the partition column `day`
+/// - `s3://bucket/landing/users.csv` is the csv table `users`
+///
+/// The format of a table is inferred from the extension of its files, which
+/// must be one of the [recognized file types]. Files and directories whose
+/// name starts with `.` or `_`, such as `_SUCCESS`, are ignored.
+///
+/// The prefix is listed when a table is first referenced, and then again
+/// once the [refresh interval] has elapsed, or on [`Self::refresh`]. The
+/// tables are created by a [`DiscoveredTableFactory`] on first reference, and
+/// kept across listings as long as their location, format and partition
+/// columns are unchanged.
+///
+/// [recognized file types]: Self::with_file_types
+/// [refresh interval]: Self::with_refresh_interval
+#[derive(Debug)]
+pub struct DiscoveringSchemaProvider {
+    root: ListingTableUrl,
+    store: Arc<dyn ObjectStore>,
+    factory: Arc<dyn DiscoveredTableFactory>,
+    file_types: Vec<String>,
+    format_options: HashMap<String, HashMap<String, String>>,
+    detect_partitions: bool,
+    refresh_interval: Option<Duration>,
+    state: Mutex<DiscoveryState>,
+}
+
+/// The tables found by the last listing of a [`DiscoveringSchemaProvider`]
+#[derive(Debug, Default)]
+struct DiscoveryState {
+    listed_at: Option<Instant>,
+    tables: BTreeMap<String, DiscoveredTable>,
+}
+
+#[derive(Debug)]
+struct DiscoveredTable {
+    cmd: CreateExternalTable,
+    provider: Option<Arc<dyn TableProvider>>,
+}
+
+impl DiscoveredTable {
+    /// Returns true if `self` and `other` describe the same table
+    fn same_table(&self, other: &CreateExternalTable) -> bool {
+        self.cmd.location == other.location
+            && self.cmd.file_type == other.file_type
+            && self.cmd.table_partition_cols == other.table_partition_cols
+    }
+}
+
+impl DiscoveringSchemaProvider {
+    /// Create a new `DiscoveringSchemaProvider` discovering the tables below
+    /// `root` in `store`, created by `factory`
+    pub fn new(
+        root: ListingTableUrl,
+        store: Arc<dyn ObjectStore>,
+        factory: Arc<dyn DiscoveredTableFactory>,
+    ) -> Self {
+        Self {
+            root,
+            store,
+            factory,
+            file_types: DEFAULT_DISCOVERED_FILE_TYPES
+                .iter()
+                .map(|file_type| file_type.to_string())
+                .collect(),
+            format_options: HashMap::new(),
+            detect_partitions: true,
+            refresh_interval: None,
+            state: Mutex::new(DiscoveryState::default()),
+        }
+    }
+
+    /// Set the recognized file types, which are also the extensions of the
+    /// files of the tables, defaults to [`DEFAULT_DISCOVERED_FILE_TYPES`]
+    pub fn with_file_types(mut self, file_types: Vec<String>) -> Self {
+        self.file_types = file_types
+            .into_iter()
+            .map(|file_type| file_type.to_lowercase())
+            .collect();
+        self
+    }
+
+    /// Set the options, such as `format.has_header`, of the tables of
+    /// `file_type`
+    pub fn with_format_options(
+        mut self,
+        file_type: &str,
+        options: HashMap<String, String>,
+    ) -> Self {
+        self.format_options
+            .insert(file_type.to_lowercase(), options);
+        self
+    }
+
+    /// Set whether the hive-style partition directories of the tables, such
+    /// as `day=1/`, are detected as partition columns, defaults to `true`
+    pub fn with_partition_detection(mut self, detect_partitions: bool) -> Self {
+        self.detect_partitions = detect_partitions;
+        self
+    }
+
+    /// Set the interval after which the prefix is listed again when a table
+    /// is referenced. When `None`, the default, the prefix is only listed on
+    /// first reference and on [`Self::refresh`].
+    pub fn with_refresh_interval(mut self, refresh_interval: Option<Duration>) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    /// Lists the prefix again, discovering the new tables and forgetting the
+    /// removed ones
+    pub async fn refresh(&self) -> Result<()> {
+        let mut entries: Vec<ObjectMeta> = self
+            .store
+            .list(Some(self.root.prefix()))
+            .try_collect()
+            .await?;
+        entries.sort_unstable_by(|a, b| a.location.cmp(&b.location));
+
+        let mut discovered = BTreeMap::new();
+        for entry in &entries {
+            if let Some((name, cmd)) = self.discover(entry) {
+                discovered.entry(name).or_insert(cmd);
+            }
+        }
+
+        let mut state = self.state.lock().expect("Can't lock tables");
+        let mut previous = std::mem::take(&mut state.tables);
+        state.tables = discovered
+            .into_iter()
+            .map(|(name, cmd)| {
+                let provider = previous
+                    .remove(&name)
+                    .filter(|table| table.same_table(&cmd))
+                    .and_then(|table| table.provider);
+                (name, DiscoveredTable { cmd, provider })
+            })
+            .collect();
+        state.listed_at = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Returns the name and the description of the table the file `entry`
+    /// belongs to, if any
+    fn discover(&self, entry: &ObjectMeta) -> Option<(String, CreateExternalTable)> {
+        let segments: Vec<_> = self.root.strip_prefix(&entry.location)?.collect();
+        if segments
+            .iter()
+            .any(|segment| segment.starts_with('.') || segment.starts_with('_'))
+        {
+            return None;
+        }
+        let (file_name, directories) = segments.split_last()?;
+        let (_, extension) = file_name.rsplit_once('.')?;
+        let file_type = extension.to_lowercase();
+        if !self.file_types.contains(&file_type) {
+            return None;
+        }
+
+        let prefix = self.root.prefix().as_ref();
+        let (name, location, partitions) = match directories.split_first() {
+            None => {
+                let (name, _) = file_name.split_once('.')?;
+                (name, entry.location.to_string(), vec![])
+            }
+            Some((directory, partitions)) => {
+                let location = match prefix.is_empty() {
+                    true => format!("{directory}{DELIMITER}"),
+                    false => format!("{prefix}{DELIMITER}{directory}{DELIMITER}"),
+                };
+                (*directory, location, partitions.to_vec())
+            }
+        };
+
+        let table_partition_cols = match self.detect_partitions {
+            true => partitions
+                .iter()
+                .map_while(|partition| {
+                    partition
+                        .split_once('=')
+                        .map(|(column, _)| column.to_string())
+                })
+                .collect(),
+            false => vec![],
+        };
+        let cmd = CreateExternalTable {
+            schema: Arc::new(DFSchema::empty()),
+            name: TableReference::bare(name),
+            location: format!("{}{location}", self.root.object_store().as_str()),
+            file_type: file_type.clone(),
+            table_partition_cols,
+            if_not_exists: false,
+            temporary: false,
+            definition: None,
+            order_exprs: vec![],
+            unbounded: false,
+            options: self
+                .format_options
+                .get(&file_type)
+                .cloned()
+                .unwrap_or_default(),
+            constraints: Default::default(),
+            column_defaults: Default::default(),
+        };
+        Some((name.to_string(), cmd))
+    }
+
+    /// Returns true if the prefix has never been listed, or was listed
+    /// longer than the refresh interval ago
+    fn needs_refresh(&self) -> bool {
+        let state = self.state.lock().expect("Can't lock tables");
+        match (state.listed_at, self.refresh_interval) {
+            (None, _) => true,
+            (Some(listed_at), Some(interval)) => listed_at.elapsed() >= interval,
+            (Some(_), None) => false,
+        }
+    }
+}
+
+#[async_trait]
+impl SchemaProvider for DiscoveringSchemaProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn table_names(&self) -> Vec<String> {
+        let state = self.state.lock().expect("Can't lock tables");
+        state.tables.keys().cloned().collect()
+    }
+
+    async fn table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+        if self.needs_refresh() {
+            self.refresh().await?;
+        }
+        let cmd = {
+            let state = self.state.lock().expect("Can't lock tables");
+            match state.tables.get(name) {
+                None => return Ok(None),
+                Some(DiscoveredTable {
+                    provider: Some(provider),
+                    ..
+                }) => return Ok(Some(Arc::clone(provider))),
+                Some(DiscoveredTable { cmd, .. }) => cmd.clone(),
+            }
+        };
+
+        let provider = self.factory.create(&cmd).await?;
+        let mut state = self.state.lock().expect("Can't lock tables");
+        if let Some(table) = state.tables.get_mut(name) {
+            if table.same_table(&cmd) {
+                table.provider = Some(Arc::clone(&provider));
+            }
+        }
+        Ok(Some(provider))
+    }
+
+    fn table_exist(&self, name: &str) -> bool {
+        let state = self.state.lock().expect("Can't lock tables");
+        state.tables.contains_key(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion_common::not_impl_err;
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+    use object_store::PutPayload;
+
+    #[derive(Debug)]
+    struct UnimplementedFactory;
+
+    #[async_trait]
+    impl DiscoveredTableFactory for UnimplementedFactory {
+        async fn create(
+            &self,
+            _cmd: &CreateExternalTable,
+        ) -> Result<Arc<dyn TableProvider>> {
+            not_impl_err!("create")
+        }
+    }
+
+    #[tokio::test]
+    async fn discover_tables() -> Result<()> {
+        let store = Arc::new(InMemory::new());
+        for path in [
+            "landing/events/day=1/part-0.parquet",
+            "landing/events/day=2/part-0.parquet",
+            "landing/events/_SUCCESS",
+            "landing/users.csv",
+            "landing/notes.txt",
+            "landing/.hidden/part-0.json",
+            "other/logs.json",
+        ] {
+            store.put(&Path::from(path), PutPayload::new()).await?;
+        }
+        let root = ListingTableUrl::parse("memory:///landing/")?;
+        let provider = DiscoveringSchemaProvider::new(
+            root,
+            Arc::clone(&store) as _,
+            Arc::new(UnimplementedFactory),
+        )
+        .with_format_options(
+            "csv",
+            HashMap::from([("format.has_header".to_string(), "false".to_string())]),
+        );
+        provider.refresh().await?;
+        assert_eq!(provider.table_names(), vec!["events", "users"]);
+
+        {
+            let state = provider.state.lock().unwrap();
+            let events = &state.tables["events"].cmd;
+            assert_eq!(events.location, "memory:///landing/events/");
+            assert_eq!(events.file_type, "parquet");
+            assert_eq!(events.table_partition_cols, vec!["day"]);
+            let users = &state.tables["users"].cmd;
+            assert_eq!(users.location, "memory:///landing/users.csv");
+            assert_eq!(users.file_type, "csv");
+            assert_eq!(users.options["format.has_header"], "false");
+        }
+
+        store
+            .put(&Path::from("landing/logs.json"), PutPayload::new())
+            .await?;
+        assert!(!provider.table_exist("logs"));
+        provider.refresh().await?;
+        assert!(provider.table_exist("logs"));
+        assert!(provider.table("logs").await.is_err());
+        assert!(provider.table("missing").await?.is_none());
+        Ok(())
+    }
+}
@@ -30,7 +30,7 @@ use datafusion_expr::{BinaryExpr, Operator};
 use arrow::{
     array::{Array, ArrayRef, AsArray, StringBuilder},
     compute::{and, cast, prep_null_mask_filter},
-    datatypes::{DataType, Field, Fields, Schema},
+    datatypes::{DataType, Decimal128Type, DecimalType, Field, Fields, Schema},
     record_batch::RecordBatch,
 };
 use datafusion_expr::execution_props::ExecutionProps;
@@ -118,6 +118,45 @@ pub fn expr_applicable_for_cols(col_names: &[&str], expr: &Expr) -> bool {
     is_applicable
 }
 
+/// Derive from `expr` an expression that can be resolved using only the
+/// columns `col_names`, and that is true whenever `expr` is true.
+///
+/// References to other columns, such as data columns of a partitioned table,
+/// are treated as unknown: conjuncts that reference them are dropped and a
+/// disjunction that references them is only kept if every one of its branches
+/// can be resolved. The derived expression can therefore be used to prune
+/// partitions, but a partition where it is true may still contain rows for
+/// which `expr` is false.
+///
+/// Returns `None` if nothing is known about `expr` from the columns `col_names`.
+pub fn partition_pruning_expr(col_names: &[&str], expr: &Expr) -> Option<Expr> {
+    if expr_applicable_for_cols(col_names, expr) {
+        return Some(expr.clone());
+    }
+    match expr {
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        }) => match (
+            partition_pruning_expr(col_names, left),
+            partition_pruning_expr(col_names, right),
+        ) {
+            (Some(left), Some(right)) => Some(left.and(right)),
+            (left, right) => left.or(right),
+        },
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Or,
+            right,
+        }) => Some(
+            partition_pruning_expr(col_names, left)?
+                .or(partition_pruning_expr(col_names, right)?),
+        ),
+        _ => None,
+    }
+}
+
 /// The maximum number of concurrent listing requests
 const CONCURRENCY_LIMIT: usize = 100;
 
@@ -191,19 +230,56 @@ pub async fn list_partitions(
     max_depth: usize,
     partition_prefix: Option<Path>,
 ) -> Result<Vec<Partition>> {
-    let partition = Partition {
-        path: match partition_prefix {
-            Some(prefix) => Path::from_iter(
+    list_pruned_partitions(store, table_path, max_depth, partition_prefix, &[], &[]).await
+}
+
+/// Returns a recursive list of the partitions in `table_path` up to `max_depth`,
+/// skipping the directories whose partition values show that they cannot
+/// match `filters`.
+///
+/// Each listed directory is pruned as soon as it is discovered, using the
+/// values of the partition columns it and its parents are named after, so
+/// that pruned directories are never listed.
+async fn list_pruned_partitions(
+    store: &dyn ObjectStore,
+    table_path: &ListingTableUrl,
+    max_depth: usize,
+    partition_prefix: Option<Path>,
+    filters: &[Expr],
+    partition_cols: &[(String, DataType)],
+) -> Result<Vec<Partition>> {
+    let partition = match partition_prefix {
+        Some(prefix) => Partition {
+            path: Path::from_iter(
                 Path::from(table_path.prefix().as_ref())
                     .parts()
                     .chain(Path::from(prefix.as_ref()).parts()),
             ),
-            None => table_path.prefix().clone(),
+            depth: prefix.parts().count(),
+            files: None,
+        },
+        None => Partition {
+            path: table_path.prefix().clone(),
+            depth: 0,
+            files: None,
         },
-        depth: 0,
-        files: None,
     };
 
+    // The filters that can be evaluated knowing only the values of the
+    // first `depth` partition columns, indexed by `depth`
+    let col_names = partition_cols
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    let filters_by_depth = (0..=col_names.len())
+        .map(|depth| {
+            filters
+                .iter()
+                .filter_map(|filter| partition_pruning_expr(&col_names[..depth], filter))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
     let mut out = Vec::with_capacity(64);
 
     let mut pending = vec![];
@@ -220,12 +296,21 @@ pub async fn list_partitions(
 
         let depth = partition.depth;
         out.push(partition);
-        for path in paths {
-            let child = Partition {
+        let children = paths
+            .into_iter()
+            .map(|path| Partition {
                 path,
                 depth: depth + 1,
                 files: None,
-            };
+            })
+            .collect();
+        let children = match filters_by_depth.get(depth + 1) {
+            Some(filters) => {
+                prune_partitions(table_path, children, filters, partition_cols).await?
+            }
+            None => children,
+        };
+        for child in children {
             match depth < max_depth {
                 true => match futures.len() < CONCURRENCY_LIMIT {
                     true => futures.push(child.list(store)),
@@ -348,10 +433,11 @@ fn populate_partition_values<'a>(
             Operator::Eq => match (left.as_ref(), right.as_ref()) {
                 (Expr::Column(Column { ref name, .. }), Expr::Literal(val, _))
                 | (Expr::Literal(val, _), Expr::Column(Column { ref name, .. })) => {
-                    if partition_values
-                        .insert(name, PartitionValue::Single(val.to_string()))
-                        .is_some()
-                    {
+                    let value = match partition_path_value(val) {
+                        Some(value) => PartitionValue::Single(value),
+                        None => PartitionValue::Multi,
+                    };
+                    if partition_values.insert(name, value).is_some() {
                         partition_values.insert(name, PartitionValue::Multi);
                     }
                 }
@@ -366,6 +452,20 @@ fn populate_partition_values<'a>(
     }
 }
 
+/// Formats `value` like the partition values of the paths written for
+/// `PARTITIONED BY` columns, or returns `None` for a null value, which
+/// matches no path
+fn partition_path_value(value: &ScalarValue) -> Option<String> {
+    match value {
+        value if value.is_null() => None,
+        ScalarValue::Decimal128(Some(v), precision, scale) => {
+            Some(Decimal128Type::format_decimal(*v, *precision, *scale))
+        }
+        ScalarValue::Dictionary(_, value) => partition_path_value(value),
+        value => Some(value.to_string()),
+    }
+}
+
 pub fn evaluate_partition_prefix<'a>(
     partition_cols: &'a [(String, DataType)],
     filters: &'a [Expr],
@@ -432,9 +532,15 @@ pub async fn pruned_partition_list<'a>(
     }
 
     let partition_prefix = evaluate_partition_prefix(partition_cols, filters);
-    let partitions =
-        list_partitions(store, table_path, partition_cols.len(), partition_prefix)
-            .await?;
+    let partitions = list_pruned_partitions(
+        store,
+        table_path,
+        partition_cols.len(),
+        partition_prefix,
+        filters,
+        partition_cols,
+    )
+    .await?;
     debug!("Listed {} partitions", partitions.len());
 
     let pruned =
@@ -537,6 +643,10 @@ mod tests {
     use datafusion_execution::runtime_env::RuntimeEnv;
     use futures::FutureExt;
     use object_store::memory::InMemory;
+    use object_store::{
+        GetOptions, GetResult, ListResult, MultipartUpload, PutMultipartOpts, PutOptions,
+        PutPayload, PutResult,
+    };
     use std::any::Any;
     use std::ops::Not;
 
@@ -1002,6 +1112,267 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_evaluate_decimal_partition_prefix() {
+        // Formatted with the scale of the type, as the partition is written
+        let partitions = &[("a".to_string(), DataType::Decimal128(5, 2))];
+        assert_eq!(
+            evaluate_partition_prefix(
+                partitions,
+                &[col("a").eq(Expr::Literal(
+                    ScalarValue::Decimal128(Some(150), 5, 2),
+                    None
+                ))],
+            ),
+            Some(Path::from("a=1.50")),
+        );
+
+        // A null literal matches no partition path
+        assert_eq!(
+            evaluate_partition_prefix(
+                partitions,
+                &[col("a").eq(Expr::Literal(ScalarValue::Decimal128(None, 5, 2), None))],
+            ),
+            None,
+        );
+    }
+
+    #[test]
+    fn test_partition_pruning_expr() {
+        let cols = &["part1", "part2"];
+        let part = col("part1").gt_eq(lit("p1v2"));
+        let data = col("c1").eq(lit(1));
+
+        assert_eq!(partition_pruning_expr(cols, &part), Some(part.clone()));
+        assert_eq!(partition_pruning_expr(cols, &data), None);
+        assert_eq!(
+            partition_pruning_expr(cols, &part.clone().and(data.clone())),
+            Some(part.clone())
+        );
+        // An OR prunes only if all its branches do
+        assert_eq!(
+            partition_pruning_expr(cols, &part.clone().or(data.clone())),
+            None
+        );
+        let other_part = col("part2").eq(lit("p2v1"));
+        assert_eq!(
+            partition_pruning_expr(
+                cols,
+                &part
+                    .clone()
+                    .and(data.clone())
+                    .or(other_part.clone().and(data.clone()))
+            ),
+            Some(part.clone().or(other_part.clone()))
+        );
+        // Nothing is known about a negated expression over a data column
+        assert_eq!(
+            partition_pruning_expr(cols, &part.clone().and(data.clone()).not()),
+            None
+        );
+        // Only the columns known so far can be used
+        assert_eq!(
+            partition_pruning_expr(&cols[..1], &part.clone().and(other_part)),
+            Some(part)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pruned_partition_list_range() {
+        let (store, state) = make_test_store_and_state(&[
+            ("tablepath/date=2024-01-01/hour=1/file.parquet", 100),
+            ("tablepath/date=2024-02-01/hour=1/file.parquet", 100),
+            ("tablepath/date=2024-03-01/hour=1/file.parquet", 100),
+            ("tablepath/date=2024-03-01/hour=12/file.parquet", 100),
+            ("tablepath/date=2024-04-01/hour=3/file.parquet", 100),
+            ("tablepath/date=2024-04-01/hour=13/file.parquet", 100),
+        ]);
+        let store = RecordingObjectStore::new(store);
+        let partition_cols = [
+            (String::from("date"), DataType::Date32),
+            (String::from("hour"), DataType::Int32),
+        ];
+        let date = |s: &str| {
+            lit(ScalarValue::try_from_string(s.to_string(), &DataType::Date32).unwrap())
+        };
+        let filters = [
+            col("date").gt_eq(date("2024-03-01")),
+            col("hour").lt(lit(12)),
+        ];
+
+        let pruned = pruned_partition_list(
+            state.as_ref(),
+            &store,
+            &ListingTableUrl::parse("file:///tablepath/").unwrap(),
+            &filters,
+            ".parquet",
+            &partition_cols,
+        )
+        .await
+        .expect("partition pruning failed")
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+        let mut locations = pruned
+            .iter()
+            .map(|f| f.object_meta.location.as_ref())
+            .collect::<Vec<_>>();
+        locations.sort();
+        assert_eq!(
+            locations,
+            vec![
+                "tablepath/date=2024-03-01/hour=1/file.parquet",
+                "tablepath/date=2024-04-01/hour=3/file.parquet",
+            ]
+        );
+        // The directories of the dates before 2024-03-01 and of the hours
+        // from 12 are never listed
+        assert_eq!(
+            store.listed(),
+            vec![
+                "tablepath",
+                "tablepath/date=2024-03-01",
+                "tablepath/date=2024-03-01/hour=1",
+                "tablepath/date=2024-04-01",
+                "tablepath/date=2024-04-01/hour=3",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pruned_partition_list_with_data_columns() {
+        let (store, state) = make_test_store_and_state(&[
+            ("tablepath/year=2022/file.parquet", 100),
+            ("tablepath/year=2023/file.parquet", 100),
+            ("tablepath/year=2024/file.parquet", 100),
+        ]);
+        let store = RecordingObjectStore::new(store);
+        let partition_cols = [(String::from("year"), DataType::Int32)];
+        // `year = 2022 OR (year = 2024 AND c1 > 5)` can only be true in the
+        // partitions of 2022 and 2024
+        let filter = col("year")
+            .eq(lit(2022))
+            .or(col("year").eq(lit(2024)).and(col("c1").gt(lit(5))));
+        let filters = partition_pruning_expr(&["year"], &filter)
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        let pruned = pruned_partition_list(
+            state.as_ref(),
+            &store,
+            &ListingTableUrl::parse("file:///tablepath/").unwrap(),
+            &filters,
+            ".parquet",
+            &partition_cols,
+        )
+        .await
+        .expect("partition pruning failed")
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+        assert_eq!(pruned.len(), 2);
+        assert_eq!(
+            store.listed(),
+            vec!["tablepath", "tablepath/year=2022", "tablepath/year=2024"]
+        );
+    }
+
+    /// An [`ObjectStore`] recording the prefixes it lists
+    #[derive(Debug)]
+    struct RecordingObjectStore {
+        inner: Arc<InMemory>,
+        listed: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl RecordingObjectStore {
+        fn new(inner: Arc<InMemory>) -> Self {
+            Self {
+                inner,
+                listed: Default::default(),
+            }
+        }
+
+        /// The listed prefixes, sorted
+        fn listed(&self) -> Vec<String> {
+            let mut listed = self.listed.lock().unwrap().clone();
+            listed.sort();
+            listed
+        }
+
+        fn record(&self, prefix: Option<&Path>) {
+            let prefix = prefix.map(|p| p.to_string()).unwrap_or_default();
+            self.listed.lock().unwrap().push(prefix);
+        }
+    }
+
+    impl std::fmt::Display for RecordingObjectStore {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "RecordingObjectStore({})", self.inner)
+        }
+    }
+
+    #[async_trait]
+    impl ObjectStore for RecordingObjectStore {
+        async fn put_opts(
+            &self,
+            location: &Path,
+            payload: PutPayload,
+            opts: PutOptions,
+        ) -> object_store::Result<PutResult> {
+            self.inner.put_opts(location, payload, opts).await
+        }
+
+        async fn put_multipart_opts(
+            &self,
+            location: &Path,
+            opts: PutMultipartOpts,
+        ) -> object_store::Result<Box<dyn MultipartUpload>> {
+            self.inner.put_multipart_opts(location, opts).await
+        }
+
+        async fn get_opts(
+            &self,
+            location: &Path,
+            options: GetOptions,
+        ) -> object_store::Result<GetResult> {
+            self.inner.get_opts(location, options).await
+        }
+
+        async fn delete(&self, location: &Path) -> object_store::Result<()> {
+            self.inner.delete(location).await
+        }
+
+        fn list(
+            &self,
+            prefix: Option<&Path>,
+        ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+            self.record(prefix);
+            self.inner.list(prefix)
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            prefix: Option<&Path>,
+        ) -> object_store::Result<ListResult> {
+            self.record(prefix);
+            self.inner.list_with_delimiter(prefix).await
+        }
+
+        async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+            self.inner.copy(from, to).await
+        }
+
+        async fn copy_if_not_exists(
+            &self,
+            from: &Path,
+            to: &Path,
+        ) -> object_store::Result<()> {
+            self.inner.copy_if_not_exists(from, to).await
+        }
+    }
+
     pub fn make_test_store_and_state(
         files: &[(&str, u64)],
     ) -> (Arc<InMemory>, Arc<dyn Session>) {
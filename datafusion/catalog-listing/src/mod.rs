@@ -24,4 +24,5 @@
 // https://github.com/apache/datafusion/issues/11143
 #![cfg_attr(not(test), deny(clippy::clone_on_ref_ptr))]
 
+pub mod discovery;
 pub mod helpers;
@@ -0,0 +1,220 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`CachingSchemaProvider`] caches the table lookups of a slow
+//! [`SchemaProvider`]
+
+use std::any::Any;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{SchemaProvider, TableProvider};
+use datafusion_common::instant::Instant;
+use datafusion_common::Result;
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use parking_lot::Mutex;
+
+/// Wraps a [`SchemaProvider`] whose lookups are slow, such as one fronting a
+/// remote metastore, caching the results of [`SchemaProvider::table`] and
+/// [`SchemaProvider::table_names`] for a time to live, so that repeated
+/// queries don't fetch the same tables again.
+///
+/// Lookups of missing tables are cached too. Registering or deregistering a
+/// table through the wrapper invalidates its cached lookup.
+#[derive(Debug)]
+pub struct CachingSchemaProvider {
+    inner: Arc<dyn SchemaProvider>,
+    ttl: Duration,
+    tables: DashMap<String, Cached<Option<Arc<dyn TableProvider>>>>,
+    table_names: Mutex<Option<Cached<Vec<String>>>>,
+}
+
+#[derive(Debug, Clone)]
+struct Cached<T> {
+    value: T,
+    fetched_at: Instant,
+}
+
+impl CachingSchemaProvider {
+    /// Caches the lookups of `inner` for `ttl`
+    pub fn new(inner: Arc<dyn SchemaProvider>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            tables: DashMap::new(),
+            table_names: Mutex::new(None),
+        }
+    }
+
+    /// Returns the wrapped schema provider
+    pub fn inner(&self) -> &Arc<dyn SchemaProvider> {
+        &self.inner
+    }
+
+    /// Discards all cached lookups
+    pub fn invalidate(&self) {
+        self.tables.clear();
+        *self.table_names.lock() = None;
+    }
+
+    fn is_fresh<T>(&self, cached: &Cached<T>) -> bool {
+        cached.fetched_at.elapsed() < self.ttl
+    }
+
+    fn cached_table(&self, name: &str) -> Option<Option<Arc<dyn TableProvider>>> {
+        let cached = self.tables.get(name)?;
+        self.is_fresh(&cached).then(|| cached.value.clone())
+    }
+
+    fn invalidate_table(&self, name: &str) {
+        self.tables.remove(name);
+        *self.table_names.lock() = None;
+    }
+}
+
+#[async_trait]
+impl SchemaProvider for CachingSchemaProvider {
+    fn owner_name(&self) -> Option<&str> {
+        self.inner.owner_name()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn table_names(&self) -> Vec<String> {
+        let mut table_names = self.table_names.lock();
+        if let Some(cached) = table_names.as_ref().filter(|c| self.is_fresh(c)) {
+            return cached.value.clone();
+        }
+        let names = self.inner.table_names();
+        *table_names = Some(Cached {
+            value: names.clone(),
+            fetched_at: Instant::now(),
+        });
+        names
+    }
+
+    async fn table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+        if let Some(table) = self.cached_table(name) {
+            return Ok(table);
+        }
+        let table = self.inner.table(name).await?;
+        self.tables.insert(
+            name.to_string(),
+            Cached {
+                value: table.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+        Ok(table)
+    }
+
+    fn register_table(
+        &self,
+        name: String,
+        table: Arc<dyn TableProvider>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        self.invalidate_table(&name);
+        self.inner.register_table(name, table)
+    }
+
+    fn deregister_table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+        self.invalidate_table(name);
+        self.inner.deregister_table(name)
+    }
+
+    fn table_exist(&self, name: &str) -> bool {
+        match self.cached_table(name) {
+            Some(table) => table.is_some(),
+            None => self.inner.table_exist(name),
+        }
+    }
+
+    async fn table_exists(&self, name: &str) -> Result<bool> {
+        Ok(self.table(name).await?.is_some())
+    }
+
+    fn is_cheap_to_enumerate(&self) -> bool {
+        self.inner.is_cheap_to_enumerate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemTable;
+    use arrow::datatypes::Schema;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts the lookups of its single table `t`
+    #[derive(Debug, Default)]
+    struct CountingSchema {
+        lookups: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl SchemaProvider for CountingSchema {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn table_names(&self) -> Vec<String> {
+            vec!["t".to_string()]
+        }
+
+        async fn table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            if name != "t" {
+                return Ok(None);
+            }
+            let table = MemTable::try_new(Arc::new(Schema::empty()), vec![vec![]])?;
+            Ok(Some(Arc::new(table)))
+        }
+
+        fn table_exist(&self, name: &str) -> bool {
+            name == "t"
+        }
+    }
+
+    #[tokio::test]
+    async fn caches_lookups_until_expired() -> Result<()> {
+        let inner = Arc::new(CountingSchema::default());
+        let schema = CachingSchemaProvider::new(
+            Arc::clone(&inner) as _,
+            Duration::from_secs(3600),
+        );
+        assert!(schema.table("t").await?.is_some());
+        assert!(schema.table("t").await?.is_some());
+        assert!(schema.table_exists("t").await?);
+        assert!(!schema.table_exists("missing").await?);
+        assert!(!schema.table_exists("missing").await?);
+        assert_eq!(inner.lookups.load(Ordering::SeqCst), 2);
+
+        schema.invalidate();
+        assert!(schema.table("t").await?.is_some());
+        assert_eq!(inner.lookups.load(Ordering::SeqCst), 3);
+
+        let schema = CachingSchemaProvider::new(Arc::clone(&inner) as _, Duration::ZERO);
+        assert!(schema.table("t").await?.is_some());
+        assert!(schema.table("t").await?.is_some());
+        assert_eq!(inner.lookups.load(Ordering::SeqCst), 5);
+        Ok(())
+    }
+}
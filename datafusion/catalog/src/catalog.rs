@@ -174,4 +174,17 @@ pub trait CatalogProviderList: Debug + Sync + Send {
 
     /// Retrieves a specific catalog by name, provided it exists.
     fn catalog(&self, name: &str) -> Option<Arc<dyn CatalogProvider>>;
+
+    /// Removes a catalog from this catalog list, returning it if it existed.
+    ///
+    /// Implementations are not expected to check whether the catalog is
+    /// empty: `DROP CATALOG` deregisters the schemas of the catalog first.
+    ///
+    /// By default returns a "Not Implemented" error
+    fn deregister_catalog(
+        &self,
+        _name: &str,
+    ) -> Result<Option<Arc<dyn CatalogProvider>>> {
+        not_impl_err!("Deregistering catalogs is not supported")
+    }
 }
@@ -0,0 +1,268 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Comments documenting tables and their columns, see
+//! [`TableProvider::comment`]
+
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::{Session, TableProvider};
+use arrow::datatypes::{Field, Schema, SchemaRef};
+use async_trait::async_trait;
+use datafusion_common::{plan_err, Constraints, Result, Statistics};
+use datafusion_expr::dml::InsertOp;
+use datafusion_expr::{
+    Expr, LogicalPlan, TableProviderFilterPushDown, TableType, COMMENT_METADATA_KEY,
+};
+use datafusion_physical_plan::ExecutionPlan;
+
+/// Returns a copy of `schema` where `comment` is the
+/// [`COMMENT_METADATA_KEY`] metadata of the field named `column`, or of the
+/// schema itself when `column` is `None`. A `comment` of `None` removes the
+/// metadata.
+pub fn schema_with_comment(
+    schema: &Schema,
+    column: Option<&str>,
+    comment: Option<&str>,
+) -> Result<Schema> {
+    let set_comment = |metadata: &mut HashMap<String, String>| match comment {
+        Some(comment) => {
+            metadata.insert(COMMENT_METADATA_KEY.to_string(), comment.to_string());
+        }
+        None => {
+            metadata.remove(COMMENT_METADATA_KEY);
+        }
+    };
+
+    let Some(column) = column else {
+        let mut metadata = schema.metadata().clone();
+        set_comment(&mut metadata);
+        return Ok(schema.clone().with_metadata(metadata));
+    };
+    if schema.field_with_name(column).is_err() {
+        return plan_err!("Column '{column}' not found");
+    }
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            if field.name() != column {
+                return Arc::clone(field);
+            }
+            let mut metadata = field.metadata().clone();
+            set_comment(&mut metadata);
+            Arc::new(Field::clone(field).with_metadata(metadata))
+        })
+        .collect::<Vec<_>>();
+    Ok(Schema::new_with_metadata(fields, schema.metadata().clone()))
+}
+
+/// A [`TableProvider`] keeping the comments of a table that can't store them
+/// itself, see [`TableProvider::with_comment`].
+///
+/// The comments are only kept as long as the `CommentedTable` is registered,
+/// and are not part of the schema of the table. Everything else is delegated
+/// to the wrapped table.
+#[derive(Debug)]
+pub struct CommentedTable {
+    inner: Arc<dyn TableProvider>,
+    table_comment: Option<String>,
+    column_comments: HashMap<String, String>,
+}
+
+impl CommentedTable {
+    /// Wraps `inner`, starting from the comments it reports
+    pub fn new(inner: Arc<dyn TableProvider>) -> Self {
+        let column_comments = inner
+            .schema()
+            .fields()
+            .iter()
+            .filter_map(|field| {
+                let comment = inner.comment(Some(field.name()))?;
+                Some((field.name().clone(), comment))
+            })
+            .collect();
+        Self {
+            table_comment: inner.comment(None),
+            column_comments,
+            inner,
+        }
+    }
+
+    /// Returns the wrapped table
+    pub fn inner(&self) -> &Arc<dyn TableProvider> {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl TableProvider for CommentedTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.inner.schema()
+    }
+
+    fn constraints(&self) -> Option<&Constraints> {
+        self.inner.constraints()
+    }
+
+    fn table_type(&self) -> TableType {
+        self.inner.table_type()
+    }
+
+    fn get_table_definition(&self) -> Option<&str> {
+        self.inner.get_table_definition()
+    }
+
+    fn create_statement(&self, table_name: &str) -> Option<String> {
+        self.inner.create_statement(table_name)
+    }
+
+    fn get_logical_plan(&self) -> Option<Cow<LogicalPlan>> {
+        self.inner.get_logical_plan()
+    }
+
+    fn get_column_default(&self, column: &str) -> Option<&Expr> {
+        self.inner.get_column_default(column)
+    }
+
+    fn get_generated_column_expr(&self, column: &str) -> Option<&Expr> {
+        self.inner.get_generated_column_expr(column)
+    }
+
+    fn partition_columns(&self) -> Vec<String> {
+        self.inner.partition_columns()
+    }
+
+    fn comment(&self, column: Option<&str>) -> Option<String> {
+        match column {
+            Some(column) => self.column_comments.get(column).cloned(),
+            None => self.table_comment.clone(),
+        }
+    }
+
+    fn with_comment(
+        &self,
+        column: Option<&str>,
+        comment: Option<&str>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        let mut table_comment = self.table_comment.clone();
+        let mut column_comments = self.column_comments.clone();
+        match (column, comment) {
+            (Some(column), _) if self.inner.schema().field_with_name(column).is_err() => {
+                return plan_err!("Column '{column}' not found");
+            }
+            (Some(column), Some(comment)) => {
+                column_comments.insert(column.to_string(), comment.to_string());
+            }
+            (Some(column), None) => {
+                column_comments.remove(column);
+            }
+            (None, comment) => table_comment = comment.map(str::to_string),
+        }
+        Ok(Some(Arc::new(Self {
+            inner: Arc::clone(&self.inner),
+            table_comment,
+            column_comments,
+        })))
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.inner.scan(state, projection, filters, limit).await
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        self.inner.supports_filters_pushdown(filters)
+    }
+
+    fn statistics(&self) -> Option<Statistics> {
+        self.inner.statistics()
+    }
+
+    async fn insert_into(
+        &self,
+        state: &dyn Session,
+        input: Arc<dyn ExecutionPlan>,
+        insert_op: InsertOp,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.inner.insert_into(state, input, insert_op).await
+    }
+
+    async fn delete_from(
+        &self,
+        state: &dyn Session,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.inner.delete_from(state, filters).await
+    }
+
+    async fn update(
+        &self,
+        state: &dyn Session,
+        assignments: Vec<(String, Expr)>,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.inner.update(state, assignments, filters).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemTable;
+    use arrow::datatypes::DataType;
+
+    #[test]
+    fn comments_kept_by_wrapper() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let table = MemTable::try_new(Arc::clone(&schema), vec![vec![]])?;
+        let table = table.with_comment(Some("a"), Some("first"))?.unwrap();
+
+        let commented = CommentedTable::new(table);
+        assert_eq!(commented.comment(Some("a")).as_deref(), Some("first"));
+        let commented = commented
+            .with_comment(None, Some("a table"))?
+            .unwrap()
+            .with_comment(Some("a"), None)?
+            .unwrap()
+            .with_comment(Some("b"), Some("second"))?
+            .unwrap();
+        assert_eq!(commented.comment(None).as_deref(), Some("a table"));
+        assert_eq!(commented.comment(Some("a")), None);
+        assert_eq!(commented.comment(Some("b")).as_deref(), Some("second"));
+        assert!(commented.with_comment(Some("c"), Some("missing")).is_err());
+        Ok(())
+    }
+}
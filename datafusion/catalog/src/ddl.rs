@@ -0,0 +1,127 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Helpers to reconstruct the `CREATE` statement of a table, see
+//! [`TableProvider::create_statement`](crate::TableProvider::create_statement)
+
+use arrow::datatypes::{DataType, Field, IntervalUnit, TimeUnit};
+use datafusion_common::utils::quote_identifier;
+
+/// Returns the SQL column definitions of `fields`, such as
+/// `(a BIGINT NOT NULL, b VARCHAR)`
+pub fn column_definitions<'a>(fields: impl IntoIterator<Item = &'a Field>) -> String {
+    let columns = fields
+        .into_iter()
+        .map(column_definition)
+        .collect::<Vec<_>>();
+    format!("({})", columns.join(", "))
+}
+
+/// Returns the SQL definition of the column `field`, such as
+/// `a BIGINT NOT NULL`
+pub fn column_definition(field: &Field) -> String {
+    let mut definition = format!(
+        "{} {}",
+        quote_identifier(field.name()),
+        sql_data_type(field.data_type())
+    );
+    if !field.is_nullable() {
+        definition.push_str(" NOT NULL");
+    }
+    definition
+}
+
+/// Returns the SQL type that is planned as `data_type`, or the Arrow name
+/// of `data_type` for types without a SQL equivalent
+pub fn sql_data_type(data_type: &DataType) -> String {
+    match data_type {
+        DataType::Boolean => "BOOLEAN".to_string(),
+        DataType::Int8 => "TINYINT".to_string(),
+        DataType::Int16 => "SMALLINT".to_string(),
+        DataType::Int32 => "INT".to_string(),
+        DataType::Int64 => "BIGINT".to_string(),
+        DataType::UInt8 => "TINYINT UNSIGNED".to_string(),
+        DataType::UInt16 => "SMALLINT UNSIGNED".to_string(),
+        DataType::UInt32 => "INT UNSIGNED".to_string(),
+        DataType::UInt64 => "BIGINT UNSIGNED".to_string(),
+        DataType::Float32 => "REAL".to_string(),
+        DataType::Float64 => "DOUBLE".to_string(),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+            "VARCHAR".to_string()
+        }
+        DataType::Binary | DataType::LargeBinary | DataType::BinaryView => {
+            "BYTEA".to_string()
+        }
+        DataType::Date32 => "DATE".to_string(),
+        DataType::Time64(TimeUnit::Nanosecond) => "TIME".to_string(),
+        DataType::Timestamp(unit, tz) => {
+            let precision = match unit {
+                TimeUnit::Second => 0,
+                TimeUnit::Millisecond => 3,
+                TimeUnit::Microsecond => 6,
+                TimeUnit::Nanosecond => 9,
+            };
+            match tz {
+                Some(_) => format!("TIMESTAMP({precision}) WITH TIME ZONE"),
+                None => format!("TIMESTAMP({precision})"),
+            }
+        }
+        DataType::Decimal128(precision, scale) => {
+            format!("DECIMAL({precision}, {scale})")
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => "INTERVAL".to_string(),
+        DataType::List(field) => format!("{}[]", sql_data_type(field.data_type())),
+        // Dictionary encoding is not part of the SQL type
+        DataType::Dictionary(_, value_type) => sql_data_type(value_type),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_definitions() {
+        let fields = [
+            Field::new("id", DataType::Int64, false),
+            Field::new("Name", DataType::Utf8View, true),
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Millisecond, Some("+00:00".into())),
+                true,
+            ),
+            Field::new("amount", DataType::Decimal128(10, 2), true),
+            Field::new_list("tags", Field::new_list_field(DataType::Utf8, true), true),
+            Field::new(
+                "part",
+                DataType::Dictionary(
+                    Box::new(DataType::UInt16),
+                    Box::new(DataType::Utf8),
+                ),
+                false,
+            ),
+            Field::new("d", DataType::Duration(TimeUnit::Second), true),
+        ];
+        assert_eq!(
+            column_definitions(&fields),
+            "(id BIGINT NOT NULL, \"Name\" VARCHAR, \
+             ts TIMESTAMP(3) WITH TIME ZONE, amount DECIMAL(10, 2), \
+             tags VARCHAR[], part VARCHAR NOT NULL, d Duration(Second))"
+        );
+    }
+}
@@ -23,7 +23,7 @@ use std::{any::Any, borrow::Cow};
 use crate::TableProvider;
 
 use arrow::datatypes::SchemaRef;
-use datafusion_common::{internal_err, Constraints};
+use datafusion_common::{internal_err, Constraints, Statistics};
 use datafusion_expr::{Expr, TableProviderFilterPushDown, TableSource, TableType};
 
 /// Implements [`TableSource`] for a [`TableProvider`]
@@ -85,6 +85,18 @@ impl TableSource for DefaultTableSource {
     fn get_column_default(&self, column: &str) -> Option<&Expr> {
         self.table_provider.get_column_default(column)
     }
+
+    fn get_generated_column_expr(&self, column: &str) -> Option<&Expr> {
+        self.table_provider.get_generated_column_expr(column)
+    }
+
+    fn comment(&self, column: Option<&str>) -> Option<String> {
+        self.table_provider.comment(column)
+    }
+
+    fn statistics(&self) -> Option<Statistics> {
+        self.table_provider.statistics()
+    }
 }
 
 /// Wrap TableProvider in TableSource
@@ -66,6 +66,13 @@ impl CatalogProviderList for DynamicFileCatalog {
             )) as _
         })
     }
+
+    fn deregister_catalog(
+        &self,
+        name: &str,
+    ) -> datafusion_common::Result<Option<Arc<dyn CatalogProvider>>> {
+        self.inner.deregister_catalog(name)
+    }
 }
 
 /// Wraps another catalog provider
@@ -111,6 +118,14 @@ impl CatalogProvider for DynamicFileCatalogProvider {
     ) -> datafusion_common::Result<Option<Arc<dyn SchemaProvider>>> {
         self.inner.register_schema(name, schema)
     }
+
+    fn deregister_schema(
+        &self,
+        name: &str,
+        cascade: bool,
+    ) -> datafusion_common::Result<Option<Arc<dyn SchemaProvider>>> {
+        self.inner.deregister_schema(name, cascade)
+    }
 }
 
 /// Implements the [DynamicFileSchemaProvider] that can create tables provider from the file path.
@@ -174,6 +189,14 @@ impl SchemaProvider for DynamicFileSchemaProvider {
     fn table_exist(&self, name: &str) -> bool {
         self.inner.table_exist(name)
     }
+
+    async fn table_exists(&self, name: &str) -> datafusion_common::Result<bool> {
+        self.inner.table_exists(name).await
+    }
+
+    fn is_cheap_to_enumerate(&self) -> bool {
+        self.inner.is_cheap_to_enumerate()
+    }
 }
 
 /// [UrlTableFactory] is a factory that can create a table provider from the given url.
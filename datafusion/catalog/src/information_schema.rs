@@ -20,7 +20,7 @@
 //! [Information Schema]: https://en.wikipedia.org/wiki/Information_schema
 
 use crate::streaming::StreamingTable;
-use crate::{CatalogProviderList, SchemaProvider, TableProvider};
+use crate::{CatalogProvider, CatalogProviderList, SchemaProvider, TableProvider};
 use arrow::array::builder::{BooleanBuilder, UInt8Builder};
 use arrow::{
     array::{StringBuilder, UInt64Builder},
@@ -31,7 +31,7 @@ use async_trait::async_trait;
 use datafusion_common::config::{ConfigEntry, ConfigOptions};
 use datafusion_common::error::Result;
 use datafusion_common::types::NativeType;
-use datafusion_common::DataFusionError;
+use datafusion_common::{Constraint, DataFusionError};
 use datafusion_execution::TaskContext;
 use datafusion_expr::{AggregateUDF, ScalarUDF, Signature, TypeSignature, WindowUDF};
 use datafusion_expr::{TableType, Volatility};
@@ -50,6 +50,8 @@ pub(crate) const DF_SETTINGS: &str = "df_settings";
 pub(crate) const SCHEMATA: &str = "schemata";
 pub(crate) const ROUTINES: &str = "routines";
 pub(crate) const PARAMETERS: &str = "parameters";
+pub(crate) const TABLE_CONSTRAINTS: &str = "table_constraints";
+pub(crate) const KEY_COLUMN_USAGE: &str = "key_column_usage";
 
 /// All information schema tables
 pub const INFORMATION_SCHEMA_TABLES: &[&str] = &[
@@ -60,6 +62,8 @@ pub const INFORMATION_SCHEMA_TABLES: &[&str] = &[
     SCHEMATA,
     ROUTINES,
     PARAMETERS,
+    TABLE_CONSTRAINTS,
+    KEY_COLUMN_USAGE,
 ];
 
 /// Implements the `information_schema` virtual schema and tables
@@ -91,6 +95,7 @@ impl InformationSchemaConfig {
     /// Construct the `information_schema.tables` virtual table
     async fn make_tables(
         &self,
+        config_options: &ConfigOptions,
         builder: &mut InformationSchemaTablesBuilder,
     ) -> Result<(), DataFusionError> {
         // create a mem table with the names of tables
@@ -101,7 +106,11 @@ impl InformationSchemaConfig {
             for schema_name in catalog.schema_names() {
                 if schema_name != INFORMATION_SCHEMA {
                     // schema name may not exist in the catalog, so we need to check
-                    if let Some(schema) = catalog.schema(&schema_name) {
+                    if let Some(schema) = self.enumerated_schema(
+                        catalog.as_ref(),
+                        &schema_name,
+                        config_options,
+                    ) {
                         for table_name in schema.table_names() {
                             if let Some(table_type) =
                                 schema.table_type(&table_name).await?
@@ -132,6 +141,22 @@ impl InformationSchemaConfig {
         Ok(())
     }
 
+    /// Returns the schema `schema_name` of `catalog`, unless its tables are
+    /// skipped because it is expensive to enumerate
+    fn enumerated_schema(
+        &self,
+        catalog: &dyn CatalogProvider,
+        schema_name: &str,
+        config_options: &ConfigOptions,
+    ) -> Option<Arc<dyn SchemaProvider>> {
+        let schema = catalog.schema(schema_name)?;
+        let skip = config_options
+            .catalog
+            .information_schema_skip_expensive_schemas
+            && !schema.is_cheap_to_enumerate();
+        (!skip).then_some(schema)
+    }
+
     async fn make_schemata(&self, builder: &mut InformationSchemataBuilder) {
         for catalog_name in self.catalog_list.catalog_names() {
             let catalog = self.catalog_list.catalog(&catalog_name).unwrap();
@@ -144,11 +169,15 @@ impl InformationSchemaConfig {
                     }
                 }
             }
+
+            // The information schema itself is not registered in the catalog
+            builder.add_schemata(&catalog_name, INFORMATION_SCHEMA, None);
         }
     }
 
     async fn make_views(
         &self,
+        config_options: &ConfigOptions,
         builder: &mut InformationSchemaViewBuilder,
     ) -> Result<(), DataFusionError> {
         for catalog_name in self.catalog_list.catalog_names() {
@@ -157,14 +186,18 @@ impl InformationSchemaConfig {
             for schema_name in catalog.schema_names() {
                 if schema_name != INFORMATION_SCHEMA {
                     // schema name may not exist in the catalog, so we need to check
-                    if let Some(schema) = catalog.schema(&schema_name) {
+                    if let Some(schema) = self.enumerated_schema(
+                        catalog.as_ref(),
+                        &schema_name,
+                        config_options,
+                    ) {
                         for table_name in schema.table_names() {
                             if let Some(table) = schema.table(&table_name).await? {
                                 builder.add_view(
                                     &catalog_name,
                                     &schema_name,
                                     &table_name,
-                                    table.get_table_definition(),
+                                    table.create_statement(&table_name),
                                 )
                             }
                         }
@@ -179,6 +212,7 @@ impl InformationSchemaConfig {
     /// Construct the `information_schema.columns` virtual table
     async fn make_columns(
         &self,
+        config_options: &ConfigOptions,
         builder: &mut InformationSchemaColumnsBuilder,
     ) -> Result<(), DataFusionError> {
         for catalog_name in self.catalog_list.catalog_names() {
@@ -187,9 +221,14 @@ impl InformationSchemaConfig {
             for schema_name in catalog.schema_names() {
                 if schema_name != INFORMATION_SCHEMA {
                     // schema name may not exist in the catalog, so we need to check
-                    if let Some(schema) = catalog.schema(&schema_name) {
+                    if let Some(schema) = self.enumerated_schema(
+                        catalog.as_ref(),
+                        &schema_name,
+                        config_options,
+                    ) {
                         for table_name in schema.table_names() {
                             if let Some(table) = schema.table(&table_name).await? {
+                                let partition_columns = table.partition_columns();
                                 for (field_position, field) in
                                     table.schema().fields().iter().enumerate()
                                 {
@@ -199,6 +238,110 @@ impl InformationSchemaConfig {
                                         &table_name,
                                         field_position,
                                         field,
+                                        partition_columns.contains(field.name()),
+                                        table.comment(Some(field.name())),
+                                    )
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Construct the `information_schema.table_constraints` virtual table
+    async fn make_table_constraints(
+        &self,
+        config_options: &ConfigOptions,
+        builder: &mut InformationSchemaTableConstraintsBuilder,
+    ) -> Result<(), DataFusionError> {
+        for catalog_name in self.catalog_list.catalog_names() {
+            let catalog = self.catalog_list.catalog(&catalog_name).unwrap();
+
+            for schema_name in catalog.schema_names() {
+                if schema_name != INFORMATION_SCHEMA {
+                    // schema name may not exist in the catalog, so we need to check
+                    if let Some(schema) = self.enumerated_schema(
+                        catalog.as_ref(),
+                        &schema_name,
+                        config_options,
+                    ) {
+                        for table_name in schema.table_names() {
+                            let Some(table) = schema.table(&table_name).await? else {
+                                continue;
+                            };
+                            let Some(constraints) = table.constraints() else {
+                                continue;
+                            };
+                            let table_schema = table.schema();
+                            for constraint in constraints.iter() {
+                                builder.add_constraint(
+                                    &catalog_name,
+                                    &schema_name,
+                                    &table_name,
+                                    &constraint_name(
+                                        &table_name,
+                                        &table_schema,
+                                        constraint,
+                                    ),
+                                    constraint_type(constraint),
+                                )
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Construct the `information_schema.key_column_usage` virtual table
+    async fn make_key_column_usage(
+        &self,
+        config_options: &ConfigOptions,
+        builder: &mut InformationSchemaKeyColumnUsageBuilder,
+    ) -> Result<(), DataFusionError> {
+        for catalog_name in self.catalog_list.catalog_names() {
+            let catalog = self.catalog_list.catalog(&catalog_name).unwrap();
+
+            for schema_name in catalog.schema_names() {
+                if schema_name != INFORMATION_SCHEMA {
+                    // schema name may not exist in the catalog, so we need to check
+                    if let Some(schema) = self.enumerated_schema(
+                        catalog.as_ref(),
+                        &schema_name,
+                        config_options,
+                    ) {
+                        for table_name in schema.table_names() {
+                            let Some(table) = schema.table(&table_name).await? else {
+                                continue;
+                            };
+                            let Some(constraints) = table.constraints() else {
+                                continue;
+                            };
+                            let table_schema = table.schema();
+                            for constraint in constraints.iter() {
+                                let name = constraint_name(
+                                    &table_name,
+                                    &table_schema,
+                                    constraint,
+                                );
+                                let indices = match constraint {
+                                    Constraint::PrimaryKey(indices)
+                                    | Constraint::Unique(indices) => indices,
+                                };
+                                for (position, index) in indices.iter().enumerate() {
+                                    builder.add_key_column(
+                                        &catalog_name,
+                                        &schema_name,
+                                        &table_name,
+                                        &name,
+                                        table_schema.field(*index).name(),
+                                        position + 1,
                                     )
                                 }
                             }
@@ -402,6 +545,31 @@ impl InformationSchemaConfig {
     }
 }
 
+/// Returns the name of `constraint` of the table `table_name`, following
+/// the PostgreSQL conventions of `t_pkey` for primary keys and `t_a_b_key`
+/// for unique constraints on columns `a` and `b`
+fn constraint_name(table_name: &str, schema: &Schema, constraint: &Constraint) -> String {
+    match constraint {
+        Constraint::PrimaryKey(_) => format!("{table_name}_pkey"),
+        Constraint::Unique(indices) => {
+            let mut name = table_name.to_string();
+            for index in indices {
+                name.push('_');
+                name.push_str(schema.field(*index).name());
+            }
+            name.push_str("_key");
+            name
+        }
+    }
+}
+
+fn constraint_type(constraint: &Constraint) -> &'static str {
+    match constraint {
+        Constraint::PrimaryKey(_) => "PRIMARY KEY",
+        Constraint::Unique(_) => "UNIQUE",
+    }
+}
+
 /// get the arguments and return types of a UDF
 /// returns a tuple of (arg_types, return_type)
 fn get_udf_args_and_return_types(
@@ -509,6 +677,8 @@ impl SchemaProvider for InformationSchemaProvider {
             SCHEMATA => Arc::new(InformationSchemata::new(config)),
             ROUTINES => Arc::new(InformationSchemaRoutines::new(config)),
             PARAMETERS => Arc::new(InformationSchemaParameters::new(config)),
+            TABLE_CONSTRAINTS => Arc::new(InformationSchemaTableConstraints::new(config)),
+            KEY_COLUMN_USAGE => Arc::new(InformationSchemaKeyColumnUsage::new(config)),
             _ => return Ok(None),
         };
 
@@ -556,14 +726,16 @@ impl PartitionStream for InformationSchemaTables {
         &self.schema
     }
 
-    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+    fn execute(&self, ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
         let mut builder = self.builder();
         let config = self.config.clone();
         Box::pin(RecordBatchStreamAdapter::new(
             Arc::clone(&self.schema),
             // TODO: Stream this
             futures::stream::once(async move {
-                config.make_tables(&mut builder).await?;
+                config
+                    .make_tables(ctx.session_config().options(), &mut builder)
+                    .await?;
                 Ok(builder.finish())
             }),
         ))
@@ -648,14 +820,16 @@ impl PartitionStream for InformationSchemaViews {
         &self.schema
     }
 
-    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+    fn execute(&self, ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
         let mut builder = self.builder();
         let config = self.config.clone();
         Box::pin(RecordBatchStreamAdapter::new(
             Arc::clone(&self.schema),
             // TODO: Stream this
             futures::stream::once(async move {
-                config.make_views(&mut builder).await?;
+                config
+                    .make_views(ctx.session_config().options(), &mut builder)
+                    .await?;
                 Ok(builder.finish())
             }),
         ))
@@ -726,6 +900,8 @@ impl InformationSchemaColumns {
             Field::new("numeric_scale", DataType::UInt64, true),
             Field::new("datetime_precision", DataType::UInt64, true),
             Field::new("interval_type", DataType::Utf8, true),
+            Field::new("is_partition_column", DataType::Utf8, false),
+            Field::new("comment", DataType::Utf8, true),
         ]));
 
         Self { schema, config }
@@ -753,6 +929,8 @@ impl InformationSchemaColumns {
             numeric_scales: UInt64Builder::with_capacity(default_capacity),
             datetime_precisions: UInt64Builder::with_capacity(default_capacity),
             interval_types: StringBuilder::new(),
+            is_partition_columns: StringBuilder::new(),
+            comments: StringBuilder::new(),
             schema: Arc::clone(&self.schema),
         }
     }
@@ -763,14 +941,16 @@ impl PartitionStream for InformationSchemaColumns {
         &self.schema
     }
 
-    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+    fn execute(&self, ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
         let mut builder = self.builder();
         let config = self.config.clone();
         Box::pin(RecordBatchStreamAdapter::new(
             Arc::clone(&self.schema),
             // TODO: Stream this
             futures::stream::once(async move {
-                config.make_columns(&mut builder).await?;
+                config
+                    .make_columns(ctx.session_config().options(), &mut builder)
+                    .await?;
                 Ok(builder.finish())
             }),
         ))
@@ -797,9 +977,12 @@ struct InformationSchemaColumnsBuilder {
     numeric_scales: UInt64Builder,
     datetime_precisions: UInt64Builder,
     interval_types: StringBuilder,
+    is_partition_columns: StringBuilder,
+    comments: StringBuilder,
 }
 
 impl InformationSchemaColumnsBuilder {
+    #[allow(clippy::too_many_arguments)]
     fn add_column(
         &mut self,
         catalog_name: &str,
@@ -807,6 +990,8 @@ impl InformationSchemaColumnsBuilder {
         table_name: &str,
         field_position: usize,
         field: &Field,
+        is_partition_column: bool,
+        comment: Option<String>,
     ) {
         use DataType::*;
 
@@ -892,6 +1077,16 @@ impl InformationSchemaColumnsBuilder {
 
         self.datetime_precisions.append_option(None);
         self.interval_types.append_null();
+
+        // Not part of the standard: YES if the values of the column are
+        // derived from the location of the data, see
+        // `TableProvider::partition_columns`
+        let partition_str = if is_partition_column { "YES" } else { "NO" };
+        self.is_partition_columns.append_value(partition_str);
+
+        // Not part of the standard: the comment documenting the column, see
+        // `TableProvider::comment`
+        self.comments.append_option(comment);
     }
 
     fn finish(&mut self) -> RecordBatch {
@@ -913,6 +1108,8 @@ impl InformationSchemaColumnsBuilder {
                 Arc::new(self.numeric_scales.finish()),
                 Arc::new(self.datetime_precisions.finish()),
                 Arc::new(self.interval_types.finish()),
+                Arc::new(self.is_partition_columns.finish()),
+                Arc::new(self.comments.finish()),
             ],
         )
         .unwrap()
@@ -1021,6 +1218,256 @@ impl PartitionStream for InformationSchemata {
     }
 }
 
+#[derive(Debug)]
+struct InformationSchemaTableConstraints {
+    schema: SchemaRef,
+    config: InformationSchemaConfig,
+}
+
+impl InformationSchemaTableConstraints {
+    fn new(config: InformationSchemaConfig) -> Self {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("constraint_catalog", DataType::Utf8, false),
+            Field::new("constraint_schema", DataType::Utf8, false),
+            Field::new("constraint_name", DataType::Utf8, false),
+            Field::new("table_catalog", DataType::Utf8, false),
+            Field::new("table_schema", DataType::Utf8, false),
+            Field::new("table_name", DataType::Utf8, false),
+            Field::new("constraint_type", DataType::Utf8, false),
+            Field::new("is_deferrable", DataType::Utf8, false),
+            Field::new("initially_deferred", DataType::Utf8, false),
+            Field::new("enforced", DataType::Utf8, false),
+        ]));
+
+        Self { schema, config }
+    }
+
+    fn builder(&self) -> InformationSchemaTableConstraintsBuilder {
+        InformationSchemaTableConstraintsBuilder {
+            schema: Arc::clone(&self.schema),
+            constraint_catalogs: StringBuilder::new(),
+            constraint_schemas: StringBuilder::new(),
+            constraint_names: StringBuilder::new(),
+            table_catalogs: StringBuilder::new(),
+            table_schemas: StringBuilder::new(),
+            table_names: StringBuilder::new(),
+            constraint_types: StringBuilder::new(),
+            is_deferrables: StringBuilder::new(),
+            initially_deferreds: StringBuilder::new(),
+            enforceds: StringBuilder::new(),
+        }
+    }
+}
+
+impl PartitionStream for InformationSchemaTableConstraints {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let mut builder = self.builder();
+        let config = self.config.clone();
+        Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            // TODO: Stream this
+            futures::stream::once(async move {
+                config
+                    .make_table_constraints(ctx.session_config().options(), &mut builder)
+                    .await?;
+                Ok(builder.finish())
+            }),
+        ))
+    }
+}
+
+/// Builds the `information_schema.TABLE_CONSTRAINTS` table row by row
+///
+/// Columns are based on <https://www.postgresql.org/docs/current/infoschema-table-constraints.html>
+struct InformationSchemaTableConstraintsBuilder {
+    schema: SchemaRef,
+    constraint_catalogs: StringBuilder,
+    constraint_schemas: StringBuilder,
+    constraint_names: StringBuilder,
+    table_catalogs: StringBuilder,
+    table_schemas: StringBuilder,
+    table_names: StringBuilder,
+    constraint_types: StringBuilder,
+    is_deferrables: StringBuilder,
+    initially_deferreds: StringBuilder,
+    enforceds: StringBuilder,
+}
+
+impl InformationSchemaTableConstraintsBuilder {
+    fn add_constraint(
+        &mut self,
+        catalog_name: &str,
+        schema_name: &str,
+        table_name: &str,
+        constraint_name: &str,
+        constraint_type: &str,
+    ) {
+        // Constraints live in the schema of their table
+        self.constraint_catalogs.append_value(catalog_name);
+        self.constraint_schemas.append_value(schema_name);
+        self.constraint_names.append_value(constraint_name);
+        self.table_catalogs.append_value(catalog_name);
+        self.table_schemas.append_value(schema_name);
+        self.table_names.append_value(table_name);
+        self.constraint_types.append_value(constraint_type);
+        // Constraints are checked by each statement, if at all
+        self.is_deferrables.append_value("NO");
+        self.initially_deferreds.append_value("NO");
+        // DataFusion uses constraints for planning but does not check them
+        // when data is inserted
+        self.enforceds.append_value("NO");
+    }
+
+    fn finish(&mut self) -> RecordBatch {
+        RecordBatch::try_new(
+            Arc::clone(&self.schema),
+            vec![
+                Arc::new(self.constraint_catalogs.finish()),
+                Arc::new(self.constraint_schemas.finish()),
+                Arc::new(self.constraint_names.finish()),
+                Arc::new(self.table_catalogs.finish()),
+                Arc::new(self.table_schemas.finish()),
+                Arc::new(self.table_names.finish()),
+                Arc::new(self.constraint_types.finish()),
+                Arc::new(self.is_deferrables.finish()),
+                Arc::new(self.initially_deferreds.finish()),
+                Arc::new(self.enforceds.finish()),
+            ],
+        )
+        .unwrap()
+    }
+}
+
+#[derive(Debug)]
+struct InformationSchemaKeyColumnUsage {
+    schema: SchemaRef,
+    config: InformationSchemaConfig,
+}
+
+impl InformationSchemaKeyColumnUsage {
+    fn new(config: InformationSchemaConfig) -> Self {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("constraint_catalog", DataType::Utf8, false),
+            Field::new("constraint_schema", DataType::Utf8, false),
+            Field::new("constraint_name", DataType::Utf8, false),
+            Field::new("table_catalog", DataType::Utf8, false),
+            Field::new("table_schema", DataType::Utf8, false),
+            Field::new("table_name", DataType::Utf8, false),
+            Field::new("column_name", DataType::Utf8, false),
+            Field::new("ordinal_position", DataType::UInt64, false),
+            Field::new("position_in_unique_constraint", DataType::UInt64, true),
+        ]));
+
+        Self { schema, config }
+    }
+
+    fn builder(&self) -> InformationSchemaKeyColumnUsageBuilder {
+        // StringBuilder requires providing an initial capacity, so
+        // pick 10 here arbitrarily as this is not performance
+        // critical code and the number of tables is unavailable here.
+        let default_capacity = 10;
+
+        InformationSchemaKeyColumnUsageBuilder {
+            schema: Arc::clone(&self.schema),
+            constraint_catalogs: StringBuilder::new(),
+            constraint_schemas: StringBuilder::new(),
+            constraint_names: StringBuilder::new(),
+            table_catalogs: StringBuilder::new(),
+            table_schemas: StringBuilder::new(),
+            table_names: StringBuilder::new(),
+            column_names: StringBuilder::new(),
+            ordinal_positions: UInt64Builder::with_capacity(default_capacity),
+            positions_in_unique_constraint: UInt64Builder::with_capacity(
+                default_capacity,
+            ),
+        }
+    }
+}
+
+impl PartitionStream for InformationSchemaKeyColumnUsage {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let mut builder = self.builder();
+        let config = self.config.clone();
+        Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            // TODO: Stream this
+            futures::stream::once(async move {
+                config
+                    .make_key_column_usage(ctx.session_config().options(), &mut builder)
+                    .await?;
+                Ok(builder.finish())
+            }),
+        ))
+    }
+}
+
+/// Builds the `information_schema.KEY_COLUMN_USAGE` table row by row
+///
+/// Columns are based on <https://www.postgresql.org/docs/current/infoschema-key-column-usage.html>
+struct InformationSchemaKeyColumnUsageBuilder {
+    schema: SchemaRef,
+    constraint_catalogs: StringBuilder,
+    constraint_schemas: StringBuilder,
+    constraint_names: StringBuilder,
+    table_catalogs: StringBuilder,
+    table_schemas: StringBuilder,
+    table_names: StringBuilder,
+    column_names: StringBuilder,
+    ordinal_positions: UInt64Builder,
+    positions_in_unique_constraint: UInt64Builder,
+}
+
+impl InformationSchemaKeyColumnUsageBuilder {
+    fn add_key_column(
+        &mut self,
+        catalog_name: &str,
+        schema_name: &str,
+        table_name: &str,
+        constraint_name: &str,
+        column_name: &str,
+        key_position: usize,
+    ) {
+        self.constraint_catalogs.append_value(catalog_name);
+        self.constraint_schemas.append_value(schema_name);
+        self.constraint_names.append_value(constraint_name);
+        self.table_catalogs.append_value(catalog_name);
+        self.table_schemas.append_value(schema_name);
+        self.table_names.append_value(table_name);
+        self.column_names.append_value(column_name);
+        // "Ordinal position of the column within the constraint key (count
+        // starts at 1)"
+        self.ordinal_positions.append_value(key_position as u64);
+        // Only set for foreign keys, which DataFusion does not support
+        self.positions_in_unique_constraint.append_null();
+    }
+
+    fn finish(&mut self) -> RecordBatch {
+        RecordBatch::try_new(
+            Arc::clone(&self.schema),
+            vec![
+                Arc::new(self.constraint_catalogs.finish()),
+                Arc::new(self.constraint_schemas.finish()),
+                Arc::new(self.constraint_names.finish()),
+                Arc::new(self.table_catalogs.finish()),
+                Arc::new(self.table_schemas.finish()),
+                Arc::new(self.table_names.finish()),
+                Arc::new(self.column_names.finish()),
+                Arc::new(self.ordinal_positions.finish()),
+                Arc::new(self.positions_in_unique_constraint.finish()),
+            ],
+        )
+        .unwrap()
+    }
+}
+
 #[derive(Debug)]
 struct InformationSchemaDfSettings {
     schema: SchemaRef,
@@ -1365,7 +1812,7 @@ impl PartitionStream for InformationSchemaParameters {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::CatalogProvider;
+    use arrow::array::Array;
 
     #[tokio::test]
     async fn make_tables_uses_table_type() {
@@ -1380,11 +1827,42 @@ mod tests {
             schema: Arc::new(Schema::empty()),
         };
 
-        assert!(config.make_tables(&mut builder).await.is_ok());
+        assert!(config
+            .make_tables(&ConfigOptions::default(), &mut builder)
+            .await
+            .is_ok());
 
         assert_eq!("BASE TABLE", builder.table_types.finish().value(0));
     }
 
+    #[tokio::test]
+    async fn make_tables_skips_expensive_schemas() {
+        let config = InformationSchemaConfig {
+            catalog_list: Arc::new(Fixture),
+        };
+        let mut builder = InformationSchemaTablesBuilder {
+            catalog_names: StringBuilder::new(),
+            schema_names: StringBuilder::new(),
+            table_names: StringBuilder::new(),
+            table_types: StringBuilder::new(),
+            schema: Arc::new(Schema::empty()),
+        };
+        let mut config_options = ConfigOptions::default();
+        config_options
+            .catalog
+            .information_schema_skip_expensive_schemas = true;
+
+        config
+            .make_tables(&config_options, &mut builder)
+            .await
+            .unwrap();
+
+        // Only the information_schema views themselves are listed
+        let table_names = builder.table_names.finish();
+        assert_eq!(table_names.len(), INFORMATION_SCHEMA_TABLES.len());
+        assert!(!table_names.iter().any(|name| name == Some("atable")));
+    }
+
     #[derive(Debug)]
     struct Fixture;
 
@@ -1412,6 +1890,10 @@ mod tests {
         fn table_exist(&self, _: &str) -> bool {
             unimplemented!("not required for these tests")
         }
+
+        fn is_cheap_to_enumerate(&self) -> bool {
+            false
+        }
     }
 
     impl CatalogProviderList for Fixture {
@@ -32,6 +32,7 @@
 //! * Listing schema: [`listing_schema`]
 
 pub mod cte_worktable;
+pub mod ddl;
 pub mod default_table_source;
 pub mod information_schema;
 pub mod listing_schema;
@@ -41,12 +42,16 @@ pub mod streaming;
 pub mod view;
 
 mod r#async;
+mod caching_schema;
 mod catalog;
+mod comment;
 mod dynamic_file;
 mod schema;
 mod table;
 
+pub use caching_schema::CachingSchemaProvider;
 pub use catalog::*;
+pub use comment::{schema_with_comment, CommentedTable};
 pub use datafusion_session::Session;
 pub use dynamic_file::catalog::*;
 pub use memory::{
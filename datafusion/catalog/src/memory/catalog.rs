@@ -66,6 +66,13 @@ impl CatalogProviderList for MemoryCatalogProviderList {
     fn catalog(&self, name: &str) -> Option<Arc<dyn CatalogProvider>> {
         self.catalogs.get(name).map(|c| Arc::clone(c.value()))
     }
+
+    fn deregister_catalog(
+        &self,
+        name: &str,
+    ) -> datafusion_common::Result<Option<Arc<dyn CatalogProvider>>> {
+        Ok(self.catalogs.remove(name).map(|(_, catalog)| catalog))
+    }
 }
 
 /// Simple in-memory implementation of a catalog.
@@ -119,6 +126,9 @@ impl CatalogProvider for MemoryCatalogProvider {
             let table_names = schema.table_names();
             match (table_names.is_empty(), cascade) {
                 (true, _) | (false, true) => {
+                    for table_name in &table_names {
+                        schema.deregister_table(table_name)?;
+                    }
                     let (_, removed) = self.schemas.remove(name).unwrap();
                     Ok(Some(removed))
                 }
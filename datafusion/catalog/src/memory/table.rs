@@ -22,19 +22,23 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
-use crate::TableProvider;
+use crate::ddl::column_definitions;
+use crate::{schema_with_comment, TableProvider};
 
 use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
 use datafusion_common::error::Result;
+use datafusion_common::utils::quote_identifier;
 use datafusion_common::{not_impl_err, plan_err, Constraints, DFSchema, SchemaExt};
 use datafusion_common_runtime::JoinSet;
-use datafusion_datasource::memory::{MemSink, MemorySourceConfig};
+use datafusion_datasource::memory::{MemDmlOp, MemDmlSink, MemSink, MemorySourceConfig};
 use datafusion_datasource::sink::DataSinkExec;
 use datafusion_datasource::source::DataSourceExec;
 use datafusion_expr::dml::InsertOp;
+use datafusion_expr::utils::conjunction;
 use datafusion_expr::{Expr, SortExpr, TableType};
-use datafusion_physical_expr::{create_physical_sort_exprs, LexOrdering};
+use datafusion_physical_expr::{create_physical_sort_exprs, LexOrdering, PhysicalExpr};
+use datafusion_physical_plan::empty::EmptyExec;
 use datafusion_physical_plan::repartition::RepartitionExec;
 use datafusion_physical_plan::{
     common, ExecutionPlan, ExecutionPlanProperties, Partitioning,
@@ -61,6 +65,7 @@ pub struct MemTable {
     pub batches: Vec<PartitionData>,
     constraints: Constraints,
     column_defaults: HashMap<String, Expr>,
+    generated_columns: HashMap<String, Expr>,
     /// Optional pre-known sort order(s). Must be `SortExpr`s.
     /// inserting data into this table removes the order
     pub sort_order: Arc<Mutex<Vec<Vec<SortExpr>>>>,
@@ -88,6 +93,7 @@ impl MemTable {
                 .collect::<Vec<_>>(),
             constraints: Constraints::default(),
             column_defaults: HashMap::new(),
+            generated_columns: HashMap::new(),
             sort_order: Arc::new(Mutex::new(vec![])),
         })
     }
@@ -107,6 +113,15 @@ impl MemTable {
         self
     }
 
+    /// Assign the expressions computing generated columns
+    pub fn with_generated_columns(
+        mut self,
+        generated_columns: HashMap<String, Expr>,
+    ) -> Self {
+        self.generated_columns = generated_columns;
+        self
+    }
+
     /// Specify an optional pre-known sort order(s). Must be `SortExpr`s.
     ///
     /// If the data is not sorted by this order, DataFusion may produce
@@ -194,6 +209,38 @@ impl MemTable {
     }
 }
 
+impl MemTable {
+    /// Returns the physical predicate of the conjunction of `filters`, if any
+    fn dml_predicate(
+        &self,
+        state: &dyn Session,
+        filters: Vec<Expr>,
+    ) -> Result<Option<Arc<dyn PhysicalExpr>>> {
+        let df_schema = DFSchema::try_from(Arc::clone(&self.schema))?;
+        conjunction(filters)
+            .map(|predicate| state.create_physical_expr(predicate, &df_schema))
+            .transpose()
+    }
+
+    /// Returns a plan changing the rows for which `predicate` is true with
+    /// `op`, and returning the number of changed rows
+    fn dml_plan(
+        &self,
+        predicate: Option<Arc<dyn PhysicalExpr>>,
+        op: MemDmlOp,
+    ) -> Arc<dyn ExecutionPlan> {
+        let sink = MemDmlSink::new(
+            self.batches.clone(),
+            Arc::clone(&self.schema),
+            predicate,
+            op,
+        )
+        .with_sort_order(Arc::clone(&self.sort_order));
+        let input = Arc::new(EmptyExec::new(Arc::clone(&self.schema)));
+        Arc::new(DataSinkExec::new(input, Arc::new(sink), None))
+    }
+}
+
 #[async_trait]
 impl TableProvider for MemTable {
     fn as_any(&self) -> &dyn Any {
@@ -212,6 +259,14 @@ impl TableProvider for MemTable {
         TableType::Base
     }
 
+    fn create_statement(&self, table_name: &str) -> Option<String> {
+        Some(format!(
+            "CREATE TABLE {} {}",
+            quote_identifier(table_name),
+            column_definitions(self.schema.fields().iter().map(AsRef::as_ref))
+        ))
+    }
+
     async fn scan(
         &self,
         state: &dyn Session,
@@ -284,7 +339,60 @@ impl TableProvider for MemTable {
         Ok(Arc::new(DataSinkExec::new(input, Arc::new(sink), None)))
     }
 
+    /// Returns a plan that deletes the rows for which all `filters` are
+    /// true, and returns the number of deleted rows.
+    async fn delete_from(
+        &self,
+        state: &dyn Session,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let predicate = self.dml_predicate(state, filters)?;
+        Ok(self.dml_plan(predicate, MemDmlOp::Delete))
+    }
+
+    /// Returns a plan that updates the rows for which all `filters` are
+    /// true, and returns the number of updated rows.
+    async fn update(
+        &self,
+        state: &dyn Session,
+        assignments: Vec<(String, Expr)>,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let df_schema = DFSchema::try_from(Arc::clone(&self.schema))?;
+        let assignments = assignments
+            .into_iter()
+            .map(|(name, expr)| {
+                let index = self.schema.index_of(&name)?;
+                Ok((index, state.create_physical_expr(expr, &df_schema)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let predicate = self.dml_predicate(state, filters)?;
+        Ok(self.dml_plan(predicate, MemDmlOp::Update(assignments)))
+    }
+
     fn get_column_default(&self, column: &str) -> Option<&Expr> {
         self.column_defaults.get(column)
     }
+
+    fn get_generated_column_expr(&self, column: &str) -> Option<&Expr> {
+        self.generated_columns.get(column)
+    }
+
+    /// The comments are stored in the metadata of the schema of the table,
+    /// which shares its data with the returned copy
+    fn with_comment(
+        &self,
+        column: Option<&str>,
+        comment: Option<&str>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        let schema = schema_with_comment(&self.schema, column, comment)?;
+        Ok(Some(Arc::new(Self {
+            schema: Arc::new(schema),
+            batches: self.batches.clone(),
+            constraints: self.constraints.clone(),
+            column_defaults: self.column_defaults.clone(),
+            generated_columns: self.generated_columns.clone(),
+            sort_order: Arc::clone(&self.sort_order),
+        })))
+    }
 }
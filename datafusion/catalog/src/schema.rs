@@ -32,6 +32,15 @@ use datafusion_expr::TableType;
 ///
 /// Please see [`CatalogProvider`] for details of implementing a custom catalog.
 ///
+/// Planning a query only calls the asynchronous [`Self::table`] for the
+/// tables the query references, and [`Self::table_exists`] for the tables
+/// created by DDL statements. The tables are only enumerated with
+/// [`Self::table_names`] by `information_schema`, see
+/// [`Self::is_cheap_to_enumerate`]. Providers that are slow to look tables
+/// up can be wrapped in a [`CachingSchemaProvider`].
+///
+/// [`CachingSchemaProvider`]: crate::CachingSchemaProvider
+///
 /// [`CatalogProvider`]: super::CatalogProvider
 #[async_trait]
 pub trait SchemaProvider: Debug + Sync + Send {
@@ -88,4 +97,24 @@ pub trait SchemaProvider: Debug + Sync + Send {
 
     /// Returns true if table exist in the schema provider, false otherwise.
     fn table_exist(&self, name: &str) -> bool;
+
+    /// Returns true if table exist in the schema provider, false otherwise.
+    ///
+    /// Unlike [`Self::table_exist`] this can await, so it is what DataFusion
+    /// calls while planning. The default looks the table up with
+    /// [`Self::table`], so that providers fronting a remote catalog only
+    /// need to implement a single lookup.
+    async fn table_exists(&self, name: &str) -> Result<bool> {
+        Ok(self.table(name).await?.is_some())
+    }
+
+    /// Returns true if listing the tables with [`Self::table_names`] and
+    /// looking each of them up is cheap, which is the case unless the
+    /// tables are fetched from a remote catalog.
+    ///
+    /// `information_schema` skips the tables of schemas returning false when
+    /// `datafusion.catalog.information_schema_skip_expensive_schemas` is set.
+    fn is_cheap_to_enumerate(&self) -> bool {
+        true
+    }
 }
@@ -29,7 +29,8 @@ use datafusion_expr::Expr;
 
 use datafusion_expr::dml::InsertOp;
 use datafusion_expr::{
-    CreateExternalTable, LogicalPlan, TableProviderFilterPushDown, TableType,
+    schema_comment, CreateExternalTable, LogicalPlan, TableProviderFilterPushDown,
+    TableType,
 };
 use datafusion_physical_plan::ExecutionPlan;
 
@@ -74,6 +75,16 @@ pub trait TableProvider: Debug + Sync + Send {
         None
     }
 
+    /// Get a create statement for this table registered as `table_name`,
+    /// as shown by `SHOW CREATE TABLE`.
+    ///
+    /// Defaults to [`Self::get_table_definition`]. Tables created without a
+    /// statement may reconstruct one from their configuration, see the
+    /// helpers of [`crate::ddl`].
+    fn create_statement(&self, _table_name: &str) -> Option<String> {
+        self.get_table_definition().map(ToString::to_string)
+    }
+
     /// Get the [`LogicalPlan`] of this table, if available.
     fn get_logical_plan(&self) -> Option<Cow<LogicalPlan>> {
         None
@@ -84,6 +95,51 @@ pub trait TableProvider: Debug + Sync + Send {
         None
     }
 
+    /// Get the expression computing the value of a generated column from
+    /// the other columns of a row, if `column` is generated.
+    ///
+    /// Generated columns can not be inserted into directly: `INSERT`
+    /// computes their values from the inserted rows instead.
+    fn get_generated_column_expr(&self, _column: &str) -> Option<&Expr> {
+        None
+    }
+
+    /// Get the names of the columns of [`Self::schema`] whose values are
+    /// derived from the location of the data, such as the `year=2024`
+    /// directories of a hive partitioned table, if any.
+    fn partition_columns(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Get the comment documenting `column`, or the table itself when
+    /// `column` is `None`, if any.
+    ///
+    /// Defaults to the [`COMMENT_METADATA_KEY`] metadata of the field or of
+    /// [`Self::schema`].
+    ///
+    /// [`COMMENT_METADATA_KEY`]: datafusion_expr::COMMENT_METADATA_KEY
+    fn comment(&self, column: Option<&str>) -> Option<String> {
+        schema_comment(&self.schema(), column)
+    }
+
+    /// Returns a copy of this table where `comment` documents `column`, or
+    /// the table itself when `column` is `None`, as set by `COMMENT ON`. A
+    /// `comment` of `None` removes the comment.
+    ///
+    /// Returns `Ok(None)` if the table can't store comments, which is the
+    /// default. The comments of such tables are kept by a
+    /// [`CommentedTable`] wrapping them instead, for as long as it is
+    /// registered.
+    ///
+    /// [`CommentedTable`]: crate::CommentedTable
+    fn with_comment(
+        &self,
+        _column: Option<&str>,
+        _comment: Option<&str>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        Ok(None)
+    }
+
     /// Create an [`ExecutionPlan`] for scanning the table with optionally
     /// specified `projection`, `filter` and `limit`, described below.
     ///
@@ -262,13 +318,33 @@ pub trait TableProvider: Debug + Sync + Send {
     }
 
     /// Get statistics for this table, if available
-    /// Although not presently used in mainline DataFusion, this allows implementation specific
-    /// behavior for downstream repositories, in conjunction with specialized optimizer rules to
-    /// perform operations such as re-ordering of joins.
+    ///
+    /// These are exposed to the logical optimizer through [`TableSource::statistics`],
+    /// e.g. to decide whether pre-aggregating an input below a join is worthwhile.
+    /// They also allow implementation specific behavior for downstream repositories,
+    /// in conjunction with specialized optimizer rules to perform operations such as
+    /// re-ordering of joins.
+    ///
+    /// [`TableSource::statistics`]: datafusion_expr::TableSource::statistics
     fn statistics(&self) -> Option<Statistics> {
         None
     }
 
+    /// Returns a token identifying the current version of the data of this
+    /// table, if known.
+    ///
+    /// The token must change whenever the data changes. Sessions with a
+    /// result cache only reuse the result of a query while the tokens of the
+    /// tables it scans are unchanged, and never cache the results of queries
+    /// scanning a table returning `None`, the default.
+    ///
+    /// It is called each time such a session plans a query scanning this
+    /// table, including when the result of the query is not cached yet, so it
+    /// should be cheap to compute.
+    async fn snapshot_token(&self, _state: &dyn Session) -> Result<Option<String>> {
+        Ok(None)
+    }
+
     /// Return an [`ExecutionPlan`] to insert data into this table, if
     /// supported.
     ///
@@ -297,6 +373,41 @@ pub trait TableProvider: Debug + Sync + Send {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         not_impl_err!("Insert into not implemented for this table")
     }
+
+    /// Return an [`ExecutionPlan`] to delete the rows of this table for which
+    /// all `filters` are true, if supported. Without `filters`, all rows are
+    /// deleted.
+    ///
+    /// The `filters` are the conjuncts of the `WHERE` clause of a
+    /// `DELETE FROM` statement, and their columns are unqualified. As for
+    /// [`Self::insert_into`], the returned plan should return a single row
+    /// in a UInt64 column called "count", holding the number of deleted rows.
+    async fn delete_from(
+        &self,
+        _state: &dyn Session,
+        _filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Delete not implemented for this table")
+    }
+
+    /// Return an [`ExecutionPlan`] to update the rows of this table for which
+    /// all `filters` are true, if supported. Without `filters`, all rows are
+    /// updated.
+    ///
+    /// The `assignments` hold the name of each updated column and the
+    /// expression of its new value, cast to the type of the column, which
+    /// may refer to the values of the row before the update. Their columns
+    /// and the columns of the `filters` are unqualified. As for
+    /// [`Self::insert_into`], the returned plan should return a single row
+    /// in a UInt64 column called "count", holding the number of updated rows.
+    async fn update(
+        &self,
+        _state: &dyn Session,
+        _assignments: Vec<(String, Expr)>,
+        _filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Update not implemented for this table")
+    }
 }
 
 /// A factory which creates [`TableProvider`]s at runtime given a URL.
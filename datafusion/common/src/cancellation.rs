@@ -0,0 +1,170 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`CancellationToken`] for cancelling the execution of a query
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll};
+
+use futures::task::AtomicWaker;
+
+use crate::{DataFusionError, Result};
+
+/// A token cancelling the execution of a query once [`Self::cancel`] is
+/// called on it, or on any of its clones.
+///
+/// Dropping the stream of a query stops polling its operators, but a
+/// synchronous computation in progress, such as a slow scalar function
+/// invoked on a large batch, runs to completion first. Cancelling the token
+/// also stops the scans of the query as soon as possible, aborting their
+/// in-flight object store requests, and lets long-running functions stop
+/// early by checking it with [`Self::check`].
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    /// The wakers of the listeners to wake once cancelled
+    wakers: Mutex<Vec<Weak<AtomicWaker>>>,
+}
+
+impl CancellationToken {
+    /// Create a new token that is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the query, waking up the tasks waiting for the cancellation
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Release);
+        let wakers = std::mem::take(&mut *self.inner.wakers.lock().unwrap());
+        for waker in wakers.iter().filter_map(Weak::upgrade) {
+            waker.wake();
+        }
+    }
+
+    /// Returns true if the query is cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Returns an error if the query is cancelled
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(cancelled_error());
+        }
+        Ok(())
+    }
+
+    /// Returns a listener for the cancellation of the query, which a task,
+    /// such as a stream, polls to be woken up once the query is cancelled
+    pub fn listener(&self) -> CancellationListener {
+        let waker = Arc::new(AtomicWaker::new());
+        let mut wakers = self.inner.wakers.lock().unwrap();
+        // Forget the wakers of the dropped listeners
+        wakers.retain(|waker| waker.strong_count() > 0);
+        wakers.push(Arc::downgrade(&waker));
+        CancellationListener {
+            token: self.clone(),
+            waker,
+        }
+    }
+}
+
+/// Wakes up the task polling it once its [`CancellationToken`] is cancelled,
+/// see [`CancellationToken::listener`]
+///
+/// The listener is registered with the token once, so that polling it only
+/// checks the cancellation flag and updates the waker of the task, without
+/// locking the token.
+#[derive(Debug)]
+pub struct CancellationListener {
+    token: CancellationToken,
+    waker: Arc<AtomicWaker>,
+}
+
+impl CancellationListener {
+    /// Returns `Poll::Ready` with the error of [`CancellationToken::check`]
+    /// if the query is cancelled, and otherwise schedules the task of `cx` to
+    /// be woken up once it is
+    pub fn poll_cancelled(&self, cx: &mut Context<'_>) -> Poll<DataFusionError> {
+        if self.token.is_cancelled() {
+            return Poll::Ready(cancelled_error());
+        }
+        self.waker.register(cx.waker());
+        // Cancelled before the waker was registered
+        if self.token.is_cancelled() {
+            return Poll::Ready(cancelled_error());
+        }
+        Poll::Pending
+    }
+}
+
+fn cancelled_error() -> DataFusionError {
+    DataFusionError::Execution("The query was cancelled".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::AtomicUsize;
+    use std::task::{Wake, Waker};
+
+    #[derive(Default)]
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn cancel_wakes_waiting_tasks() {
+        let token = CancellationToken::new();
+        let counter = Arc::new(CountingWaker::default());
+        let waker = Waker::from(Arc::clone(&counter));
+        let mut cx = Context::from_waker(&waker);
+
+        let listener = token.listener();
+        drop(token.listener());
+        // The wakers of dropped listeners are forgotten
+        let _other = token.listener();
+        assert_eq!(token.inner.wakers.lock().unwrap().len(), 2);
+
+        assert!(token.check().is_ok());
+        assert!(listener.poll_cancelled(&mut cx).is_pending());
+        // The task is only woken up once
+        assert!(listener.poll_cancelled(&mut cx).is_pending());
+
+        token.clone().cancel();
+        assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+        assert!(token.is_cancelled());
+        assert!(listener.poll_cancelled(&mut cx).is_ready());
+        // Listeners created after the cancellation are ready immediately
+        assert!(token.listener().poll_cancelled(&mut cx).is_ready());
+        assert_eq!(
+            token.check().unwrap_err().strip_backtrace(),
+            "Execution error: The query was cancelled"
+        );
+    }
+}
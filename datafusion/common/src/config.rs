@@ -216,6 +216,11 @@ config_namespace! {
         /// virtual tables for displaying schema information
         pub information_schema: bool, default = false
 
+        /// When set to true, `information_schema` skips the tables of the schemas
+        /// that can't list and look up their tables cheaply, such as schemas
+        /// backed by a remote metastore, see `SchemaProvider::is_cheap_to_enumerate`
+        pub information_schema_skip_expensive_schemas: bool, default = false
+
         /// Location scanned to load tables for `default` schema
         pub location: Option<String>, default = None
 
@@ -337,6 +342,54 @@ impl From<SpillCompression> for Option<CompressionType> {
     }
 }
 
+/// How the memory of a single query is shared among its operators, when
+/// the query has its own memory limit
+/// (see [`ExecutionOptions::per_query_memory_limit`])
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum QueryMemoryPolicy {
+    /// Operators reserve memory first come first served
+    #[default]
+    Greedy,
+    /// Spilling operators are limited to an even share of the memory
+    /// not reserved by operators that cannot spill
+    FairSpill,
+}
+
+impl FromStr for QueryMemoryPolicy {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "greedy" | "" => Ok(Self::Greedy),
+            "fair_spill" => Ok(Self::FairSpill),
+            other => Err(DataFusionError::Configuration(format!(
+                "Invalid query memory policy: {other}. Expected one of: greedy, fair_spill"
+            ))),
+        }
+    }
+}
+
+impl ConfigField for QueryMemoryPolicy {
+    fn visit<V: Visit>(&self, v: &mut V, key: &str, description: &'static str) {
+        v.some(key, self, description)
+    }
+
+    fn set(&mut self, _: &str, value: &str) -> Result<()> {
+        *self = QueryMemoryPolicy::from_str(value)?;
+        Ok(())
+    }
+}
+
+impl Display for QueryMemoryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            Self::Greedy => "greedy",
+            Self::FairSpill => "fair_spill",
+        };
+        write!(f, "{str}")
+    }
+}
+
 config_namespace! {
     /// Options related to query execution
     ///
@@ -414,11 +467,30 @@ config_namespace! {
         /// (i.e., if there's no `DiskManager` configured).
         pub sort_spill_reservation_bytes: usize, default = 10 * 1024 * 1024
 
+        /// Maximum memory, in bytes, that the operators of a single query may
+        /// reserve. The memory is still reserved from the memory pool of the
+        /// runtime, shared by all queries, but a query that exceeds its own
+        /// limit spills or fails without exhausting the memory of the other
+        /// queries. If NULL, queries are only limited by the runtime memory pool
+        pub per_query_memory_limit: Option<usize>, default = None
+
+        /// How the memory of `per_query_memory_limit` is shared among the
+        /// operators of a query. Valid values are: greedy, where operators
+        /// reserve memory first come first served, and fair_spill, where each
+        /// spilling operator is limited to an even share of the memory
+        pub per_query_memory_policy: QueryMemoryPolicy, default = QueryMemoryPolicy::Greedy
+
         /// When sorting, below what size should data be concatenated
         /// and sorted in a single RecordBatch rather than sorted in
         /// batches and merged.
         pub sort_in_place_threshold_bytes: usize, default = 1024 * 1024
 
+        /// Maximum number of spill files merged at once by a sort. If a sort
+        /// spilled more files, they are merged in multiple passes, each
+        /// writing the merged files into a new spill file, which bounds the
+        /// memory and open files needed by the merge.
+        pub sort_merge_fan_in: usize, default = 64
+
         /// Number of files to read in parallel when inferring schema and statistics
         pub meta_fetch_concurrency: usize, default = 32
 
@@ -431,9 +503,24 @@ config_namespace! {
         /// Target number of rows in output files when writing multiple.
         /// This is a soft max, so it can be exceeded slightly. There also
         /// will be one file smaller than the limit if the total
-        /// number of rows written is not roughly divisible by the soft max
+        /// number of rows written is not roughly divisible by the soft max.
+        /// Partitioned writes roll to a new file of the same partition once
+        /// this is reached
         pub soft_max_rows_per_output_file: usize, default = 50000000
 
+        /// Directory name of the null values of the partition columns of a
+        /// partitioned write, e.g. `date=__HIVE_DEFAULT_PARTITION__/`
+        pub null_partition_value: String, default = "__HIVE_DEFAULT_PARTITION__".to_string()
+
+        /// Template of the names of the files written to a directory, without
+        /// their extension. The placeholders `{partition_values}`, the
+        /// partition values of the file joined by `_`, `{uuid}`, an identifier
+        /// unique to the write, and `{count}`, the index of the file within its
+        /// partition, are substituted. The template must contain `{uuid}` or
+        /// `{count}`. When not set, the names are generated from a random
+        /// identifier
+        pub output_file_name_template: Option<String>, default = None
+
         /// This is the maximum number of RecordBatches buffered
         /// for each output file being worked. Higher values can potentially
         /// give faster write performance at the cost of higher peak
@@ -446,6 +533,21 @@ config_namespace! {
         /// tables (e.g. `/table/year=2021/month=01/data.parquet`).
         pub listing_table_ignore_subdirectory: bool, default = true
 
+        /// Should the files of a listing table be allowed to have different but
+        /// compatible column types. When true, the file schemas are merged into
+        /// the widest of their types when inferring the schema of the table, and
+        /// the columns of each file are promoted to the type of the table when
+        /// scanning it, provided the promotion is lossless, such as Int32 to Int64
+        /// or Utf8 to LargeUtf8. A column that can't be promoted is an error
+        /// naming the file and the column.
+        pub listing_table_schema_coercion: bool, default = false
+
+        /// (reading) If true, filter expressions pushed down into the scans of CSV
+        /// and JSON files are evaluated while decoding them, so that the other
+        /// columns are only decoded for the batches with matching rows. The
+        /// filters are still evaluated again above the scan.
+        pub decoder_pushdown_filters: bool, default = false
+
         /// Should DataFusion support recursive CTEs
         pub enable_recursive_ctes: bool, default = true
 
@@ -480,6 +582,17 @@ config_namespace! {
         /// tables with a highly-selective join filter, but is also slightly slower.
         pub enforce_batch_size_in_joins: bool, default = false
 
+        /// Maximum number of batches a `RepartitionExec` buffers for each of
+        /// its output partitions before the tasks producing them wait for the
+        /// partition to be consumed. The limit is exceeded while the consumer
+        /// of another output partition waits for data, so that consumers that
+        /// depend on each other can not deadlock. `0`, the default, means no
+        /// limit, as before this option was added: the memory used by the
+        /// buffered batches is already accounted for in the memory pool, and a
+        /// limit slows down the producers of plans whose consumers read their
+        /// output partitions unevenly.
+        pub repartition_max_buffered_batches: usize, default = 0
+
         /// Size (bytes) of data buffer DataFusion uses when writing output files.
         /// This affects the size of the data chunks that are uploaded to remote
         /// object stores (e.g. AWS S3). If very large (>= 100 GiB) output files are being
@@ -564,6 +677,14 @@ config_namespace! {
         /// valid values are "1.0" and "2.0"
         pub writer_version: String, default = "1.0".to_string()
 
+        /// (writing) Sets the version of the data pages: "1.0" for data page v1
+        /// and "2.0" for data page v2. The parquet writer writes data page v2 if
+        /// and only if `writer_version` is "2.0", so this must match `writer_version`.
+        /// If NULL, uses the data page version of `writer_version`.
+        /// Page level statistics, in the page headers and the page index, are
+        /// written with either data page version when `statistics_enabled` is "page"
+        pub data_page_version: Option<String>, default = None
+
         /// (writing) Skip encoding the embedded arrow metadata in the KV_meta
         ///
         /// This is analogous to the `ArrowWriterOptions::with_skip_arrow_metadata`.
@@ -698,19 +819,40 @@ config_namespace! {
         /// during aggregations, if possible
         pub enable_topk_aggregation: bool, default = true
 
+        /// When set to true, the default, the optimizer will replace a `ROW_NUMBER`
+        /// window function partitioned by some keys and followed by a filter keeping
+        /// its first rows, as well as `SELECT DISTINCT ON` queries with an `ORDER BY`,
+        /// with a grouped TopK operator, which only keeps the first rows of each
+        /// group in memory instead of sorting the whole input. When set to false,
+        /// they sort the whole input
+        pub enable_grouped_topk: bool, default = true
+
         /// When set to true attempts to push down dynamic filters generated by operators into the file scan phase.
         /// For example, for a query such as `SELECT * FROM t ORDER BY timestamp DESC LIMIT 10`, the optimizer
         /// will attempt to push down the current top 10 timestamps that the TopK operator references into the file scans.
         /// This means that if we already have 10 timestamps in the year 2025
         /// any files that only have timestamps in the year 2024 can be skipped / pruned at various stages in the scan.
+        /// Similarly, hash joins collecting their build side once for all partitions push down the bounds of its join keys into the scan of their probe side.
         pub enable_dynamic_filter_pushdown: bool, default = true
 
+        /// When set to true, identical subplans, such as a common table expression
+        /// referenced more than once, are executed only once and their output is
+        /// buffered (spilling to disk if needed) and fed to every consumer.
+        pub enable_subplan_sharing: bool, default = false
+
         /// When set to true, the optimizer will insert filters before a join between
         /// a nullable and non-nullable column to filter out nulls on the nullable side. This
         /// filter can add additional overhead when the file format does not fully support
         /// predicate push down.
         pub filter_null_join_keys: bool, default = false
 
+        /// When set to true, the optimizer will try to compute SUM, COUNT, MIN, MAX
+        /// and AVG aggregates of a grouped aggregate below an inner join, grouped by
+        /// the join keys, when the join keys of the other input functionally determine
+        /// its grouping columns. The rewrite is only applied when table statistics
+        /// indicate that the pre-aggregation reduces the number of rows significantly.
+        pub enable_eager_aggregation: bool, default = false
+
         /// Should DataFusion repartition data using the aggregate keys to execute aggregates
         /// in parallel using the provided `target_partitions` level
         pub repartition_aggregations: bool, default = true
@@ -718,6 +860,14 @@ config_namespace! {
         /// Minimum total files size in bytes to perform file scan repartitioning.
         pub repartition_file_min_size: usize, default = 10 * 1024 * 1024
 
+        /// Minimum estimated number of rows of an input for DataFusion to add
+        /// round robin repartitioning (or file scan repartitioning) above it.
+        /// Inputs whose statistics estimate fewer rows, such as point lookups,
+        /// are processed in their existing partitions. Unlike
+        /// `use_row_number_estimates_to_optimize_partitioning`, inexact row
+        /// counts are also compared to this threshold. `0` disables the check.
+        pub repartition_threshold_rows: usize, default = 0
+
         /// Should DataFusion repartition data using the join keys to execute joins in parallel
         /// using the provided `target_partitions` level
         pub repartition_joins: bool, default = true
@@ -842,6 +992,12 @@ config_namespace! {
         /// Display format of explain. Default is "indent".
         /// When set to "tree", it will print the plan in a tree-rendered format.
         pub format: String, default = "indent".to_string()
+
+        /// When set to true, the tree explain format will print the output
+        /// partitioning, output ordering, estimated number of rows and predicate
+        /// pushdown status of each operator, and only the first details of
+        /// operators with many details, followed by the number of omitted ones
+        pub tree_show_properties: bool, default = false
     }
 }
 
@@ -1727,6 +1883,14 @@ impl TableOptions {
             return Ok(());
         }
 
+        // Column-scoped parquet keys, e.g. `parquet.column.<column>.<property>`
+        #[cfg(feature = "parquet")]
+        if key.starts_with("parquet.column.")
+            && matches!(self.current_format, Some(ConfigFileType::PARQUET))
+        {
+            return self.parquet.set(&key["parquet.".len()..], value);
+        }
+
         let Some(e) = self.extensions.0.get_mut(prefix) else {
             return _config_err!("Could not find config namespace \"{prefix}\"");
         };
@@ -1819,7 +1983,9 @@ impl TableOptions {
 pub struct TableParquetOptions {
     /// Global Parquet options that propagates to all columns.
     pub global: ParquetOptions,
-    /// Column specific options. Default usage is parquet.XX::column.
+    /// Column specific options. Default usage is parquet.XX::column, or
+    /// parquet.column.<column>.XX. The dots of nested column paths separate
+    /// their fields, literal dots in names are escaped as `\.`.
     pub column_specific_options: HashMap<String, ParquetColumnOptions>,
     /// Additional file-level metadata to include. Inserted into the key_value_metadata
     /// for the written [`FileMetaData`](https://docs.rs/parquet/latest/parquet/file/metadata/struct.FileMetaData.html).
@@ -1906,6 +2072,25 @@ impl ConfigField for TableParquetOptions {
             Ok(())
         } else if let Some(crypto_feature) = key.strip_prefix("crypto.") {
             self.crypto.set(crypto_feature, value)
+        } else if let Some(column_key) = key.strip_prefix("column.") {
+            // The property follows the last dot, which can't be escaped
+            let Some((column, property)) = column_key
+                .rsplit_once('.')
+                .filter(|(column, _)| !column.is_empty() && !column.ends_with('\\'))
+            else {
+                return _config_err!(
+                    "Invalid column key \"{key}\", expected column.<column>.<property>"
+                );
+            };
+            let properties = ParquetColumnOptions::property_names();
+            if !properties.contains(&property.to_string()) {
+                return _config_err!(
+                    "Unknown per-column parquet property \"{property}\", supported properties are: {}",
+                    properties.join(", ")
+                );
+            }
+            self.column_specific_options
+                .set(&format!("{property}::{column}"), value)
         } else if key.contains("::") {
             self.column_specific_options.set(key, value)
         } else {
@@ -2056,6 +2241,29 @@ config_namespace_with_hashmap! {
     }
 }
 
+impl ParquetColumnOptions {
+    /// Returns the names of the properties that can be set per column
+    pub fn property_names() -> Vec<String> {
+        struct Visitor(Vec<String>);
+
+        impl Visit for Visitor {
+            fn some<V: Display>(&mut self, key: &str, _: V, _: &'static str) {
+                self.0.push(key.to_string())
+            }
+
+            fn none(&mut self, key: &str, _: &'static str) {
+                self.0.push(key.to_string())
+            }
+        }
+
+        let mut v = Visitor(vec![]);
+        Self::default().visit(&mut v, "", "");
+        v.0.into_iter()
+            .map(|key| key.trim_start_matches('.').to_string())
+            .collect()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ConfigFileEncryptionProperties {
     /// Should the parquet footer be encrypted
@@ -2688,6 +2896,36 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_table_column_scoped_options() {
+        let mut table_config = TableOptions::new();
+        table_config.set_config_format(ConfigFileType::PARQUET);
+        table_config
+            .set("parquet.column.user_id.dictionary_enabled", "false")
+            .unwrap();
+        table_config
+            .set("parquet.column.s.a\\.b.compression", "ZSTD(9)")
+            .unwrap();
+        let options = &table_config.parquet.column_specific_options;
+        assert_eq!(options["user_id"].dictionary_enabled, Some(false));
+        assert_eq!(options["s.a\\.b"].compression, Some("zstd(9)".to_string()));
+
+        let err = table_config
+            .set("parquet.column.user_id.page_size", "1")
+            .unwrap_err();
+        assert!(err.to_string().contains(
+            "Unknown per-column parquet property \"page_size\", supported properties are: \
+            bloom_filter_enabled, encoding, dictionary_enabled, compression"
+        ));
+        let err = table_config
+            .set("parquet.column.compression", "snappy")
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected column.<column>.<property>"));
+    }
+
     #[cfg(feature = "parquet")]
     #[test]
     fn parquet_table_encryption() {
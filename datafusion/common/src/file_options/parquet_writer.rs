@@ -126,7 +126,7 @@ impl TryFrom<&TableParquetOptions> for WriterPropertiesBuilder {
 
         // Apply column-specific options:
         for (column, options) in column_specific_options {
-            let path = ColumnPath::new(column.split('.').map(|s| s.to_owned()).collect());
+            let path = parse_column_path(column);
 
             if let Some(bloom_filter_enabled) = options.bloom_filter_enabled {
                 builder = builder
@@ -233,6 +233,9 @@ impl ParquetOptions {
             bloom_filter_fpp,
             bloom_filter_ndv,
 
+            // determined by the writer version
+            data_page_version,
+
             // not in WriterProperties
             enable_page_index: _,
             pruning: _,
@@ -250,10 +253,15 @@ impl ParquetOptions {
             skip_arrow_metadata: _,
         } = self;
 
+        let writer_version = parse_version_string(writer_version.as_str())?;
+        if let Some(data_page_version) = data_page_version {
+            validate_data_page_version(data_page_version, writer_version)?;
+        }
+
         let mut builder = WriterProperties::builder()
             .set_data_page_size_limit(*data_pagesize_limit)
             .set_write_batch_size(*write_batch_size)
-            .set_writer_version(parse_version_string(writer_version.as_str())?)
+            .set_writer_version(writer_version)
             .set_dictionary_page_size_limit(*dictionary_page_size_limit)
             .set_statistics_enabled(
                 statistics_enabled
@@ -298,6 +306,26 @@ impl ParquetOptions {
     }
 }
 
+/// Parses the key of a column of [`TableParquetOptions::column_specific_options`]
+/// to its parquet column path: dots separate the fields of nested columns,
+/// and `\.` is a dot in the name of a field
+pub fn parse_column_path(column: &str) -> ColumnPath {
+    let mut parts = vec![String::new()];
+    let mut chars = column.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('.') => parts.last_mut().unwrap().push('.'),
+                Some(c) => parts.last_mut().unwrap().extend(['\\', c]),
+                None => parts.last_mut().unwrap().push('\\'),
+            },
+            '.' => parts.push(String::new()),
+            c => parts.last_mut().unwrap().push(c),
+        }
+    }
+    ColumnPath::new(parts)
+}
+
 /// Parses datafusion.execution.parquet.encoding String to a parquet::basic::Encoding
 pub(crate) fn parse_encoding_string(
     str_setting: &str,
@@ -431,6 +459,33 @@ pub(crate) fn parse_version_string(str_setting: &str) -> Result<WriterVersion> {
     }
 }
 
+/// Checks that data pages of version `str_setting` are written by the
+/// `writer_version`, which writes data page v2 if and only if it is 2.0
+pub(crate) fn validate_data_page_version(
+    str_setting: &str,
+    writer_version: WriterVersion,
+) -> Result<()> {
+    let data_page_version = match str_setting.to_lowercase().as_str() {
+        "1.0" => WriterVersion::PARQUET_1_0,
+        "2.0" => WriterVersion::PARQUET_2_0,
+        _ => {
+            return Err(DataFusionError::Configuration(format!(
+                "Unknown or unsupported parquet data page version {str_setting} \
+                valid options are 1.0 and 2.0"
+            )))
+        }
+    };
+    if data_page_version != writer_version {
+        return Err(DataFusionError::Configuration(format!(
+            "Parquet data page version {str_setting} requires writer version {}.0, \
+            but the writer version is {}.0",
+            data_page_version.as_num(),
+            writer_version.as_num()
+        )));
+    }
+    Ok(())
+}
+
 pub(crate) fn parse_statistics_string(str_setting: &str) -> Result<EnabledStatistics> {
     let str_setting_lower: &str = &str_setting.to_lowercase();
     match str_setting_lower {
@@ -511,6 +566,7 @@ mod tests {
             bloom_filter_ndv: Some(42),
 
             // not in WriterProperties, but itemizing here to not skip newly added props
+            data_page_version: defaults.data_page_version,
             enable_page_index: defaults.enable_page_index,
             pruning: defaults.pruning,
             skip_metadata: defaults.skip_metadata,
@@ -620,6 +676,7 @@ mod tests {
                 bloom_filter_ndv: default_col_props.bloom_filter_ndv,
 
                 // not in WriterProperties
+                data_page_version: global_options_defaults.data_page_version,
                 enable_page_index: global_options_defaults.enable_page_index,
                 pruning: global_options_defaults.pruning,
                 skip_metadata: global_options_defaults.skip_metadata,
@@ -877,4 +934,43 @@ mod tests {
             "should have only the ndv set, and the fpp at default",
         );
     }
+
+    #[test]
+    fn test_parse_column_path() {
+        assert_eq!(parse_column_path("a").parts(), ["a"]);
+        assert_eq!(parse_column_path("a.b.c").parts(), ["a", "b", "c"]);
+        assert_eq!(parse_column_path("s.a\\.b").parts(), ["s", "a.b"]);
+        assert_eq!(parse_column_path("a\\b").parts(), ["a\\b"]);
+    }
+
+    #[test]
+    fn test_data_page_version() {
+        let mut table_writer_opts = TableParquetOptions::default();
+        table_writer_opts.arrow_schema(&Arc::new(Schema::empty()));
+        table_writer_opts.global.writer_version = "2.0".into();
+        table_writer_opts.global.data_page_version = Some("2.0".into());
+        let props = WriterPropertiesBuilder::try_from(&table_writer_opts)
+            .unwrap()
+            .build();
+        assert_eq!(props.writer_version(), WriterVersion::PARQUET_2_0);
+
+        // data page v2 is only written by the 2.0 writer
+        table_writer_opts.global.writer_version = "1.0".into();
+        let Err(err) = WriterPropertiesBuilder::try_from(&table_writer_opts) else {
+            panic!("expected an invalid data page version");
+        };
+        assert_eq!(
+            err.strip_backtrace(),
+            "Invalid or Unsupported Configuration: Parquet data page version 2.0 \
+            requires writer version 2.0, but the writer version is 1.0"
+        );
+
+        table_writer_opts.global.data_page_version = Some("3.0".into());
+        let Err(err) = WriterPropertiesBuilder::try_from(&table_writer_opts) else {
+            panic!("expected an invalid data page version");
+        };
+        assert!(err
+            .to_string()
+            .contains("Unknown or unsupported parquet data page version 3.0"));
+    }
 }
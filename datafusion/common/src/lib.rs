@@ -36,6 +36,7 @@ mod table_reference;
 mod unnest;
 
 pub mod alias;
+pub mod cancellation;
 pub mod cast;
 pub mod config;
 pub mod cse;
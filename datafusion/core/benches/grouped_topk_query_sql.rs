@@ -0,0 +1,100 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Benchmarks of queries keeping the first rows of each group, comparing
+//! the grouped TopK with the sort and `ROW_NUMBER` window function it replaces
+
+#[macro_use]
+extern crate criterion;
+extern crate arrow;
+extern crate datafusion;
+
+mod data_utils;
+use crate::criterion::Criterion;
+use data_utils::create_table_provider;
+use datafusion::error::Result;
+use datafusion::execution::context::SessionContext;
+use datafusion::prelude::SessionConfig;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+fn query(ctx: Arc<Mutex<SessionContext>>, rt: &Runtime, sql: &str) {
+    let df = rt.block_on(ctx.lock().sql(sql)).unwrap();
+    criterion::black_box(rt.block_on(df.collect()).unwrap());
+}
+
+fn create_context(
+    partitions_len: usize,
+    array_len: usize,
+    batch_size: usize,
+    use_grouped_topk: bool,
+) -> Result<Arc<Mutex<SessionContext>>> {
+    let mut config = SessionConfig::new();
+    config.options_mut().optimizer.enable_grouped_topk = use_grouped_topk;
+    let ctx = SessionContext::new_with_config(config);
+    let provider = create_table_provider(partitions_len, array_len, batch_size)?;
+    ctx.register_table("t", provider)?;
+    Ok(Arc::new(Mutex::new(ctx)))
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let partitions_len = 8;
+    let array_len = 1024 * 1024;
+    let batch_size = 8 * 1024;
+    let rt = Runtime::new().unwrap();
+
+    for use_grouped_topk in [true, false] {
+        let ctx = create_context(partitions_len, array_len, batch_size, use_grouped_topk)
+            .unwrap();
+        let plan = if use_grouped_topk {
+            "grouped topk"
+        } else {
+            "window"
+        };
+
+        c.bench_function(&format!("{plan}, top 3 rows per group"), |b| {
+            b.iter(|| {
+                query(
+                    ctx.clone(),
+                    &rt,
+                    "SELECT * FROM ( \
+                        SELECT *, ROW_NUMBER() OVER ( \
+                            PARTITION BY u64_narrow ORDER BY u64_wide DESC \
+                        ) AS rn \
+                        FROM t \
+                    ) WHERE rn <= 3",
+                )
+            })
+        });
+
+        c.bench_function(&format!("{plan}, distinct on"), |b| {
+            b.iter(|| {
+                query(
+                    ctx.clone(),
+                    &rt,
+                    "SELECT DISTINCT ON (utf8) utf8, f64, u64_wide \
+                    FROM t \
+                    ORDER BY utf8, f64 DESC",
+                )
+            })
+        });
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,95 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Benchmarks of writing a single parquet file, serially and with an
+//! increasing number of row groups encoded in parallel
+
+#[macro_use]
+extern crate criterion;
+extern crate datafusion;
+
+use crate::criterion::Criterion;
+use datafusion::datasource::MemTable;
+use datafusion::execution::context::SessionContext;
+use datafusion::prelude::SessionConfig;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use test_utils::AccessLogGenerator;
+use tokio::runtime::Runtime;
+
+fn create_context() -> SessionContext {
+    let ctx =
+        SessionContext::new_with_config(SessionConfig::new().with_target_partitions(1));
+    let generator = AccessLogGenerator::new()
+        .with_row_limit(500_000)
+        .with_max_batch_size(8192);
+    let batches = generator.collect::<Vec<_>>();
+    let schema = batches[0].schema();
+    let table = MemTable::try_new(schema, vec![batches]).unwrap();
+    ctx.register_table("logs", Arc::new(table)).unwrap();
+    ctx
+}
+
+fn write_parquet(ctx: &SessionContext, rt: &Runtime, path: &str, options: &str) {
+    let sql = format!(
+        "COPY logs TO '{path}' STORED AS PARQUET \
+        OPTIONS (execution.single_file_output true, \
+        'format.max_row_group_size' 65536, {options})"
+    );
+    rt.block_on(async { ctx.sql(&sql).await?.collect().await })
+        .unwrap();
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let ctx = create_context();
+    let rt = Runtime::new().unwrap();
+    let tmp_dir = TempDir::new().unwrap();
+    let path = tmp_dir.path().join("logs.parquet");
+    let path = path.to_str().unwrap();
+
+    let mut group = c.benchmark_group("parquet_write");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    group.bench_function("serial", |b| {
+        b.iter(|| {
+            write_parquet(
+                &ctx,
+                &rt,
+                path,
+                "'format.allow_single_file_parallelism' false",
+            )
+        })
+    });
+
+    for row_groups in [1, 2, 4, 8] {
+        group.bench_function(format!("parallel_row_groups_{row_groups}"), |b| {
+            let options = format!(
+                "'format.allow_single_file_parallelism' true, \
+                'format.maximum_parallel_row_group_writers' {row_groups}, \
+                'format.maximum_buffered_record_batches_per_stream' {}",
+                2 * row_groups
+            );
+            b.iter(|| write_parquet(&ctx, &rt, path, &options))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
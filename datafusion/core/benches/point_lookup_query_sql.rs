@@ -0,0 +1,102 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Benchmarks point lookup style queries, which select a handful of rows,
+//! with and without `datafusion.optimizer.repartition_threshold_rows`
+
+use arrow::{
+    array::{Int64Array, StringArray},
+    datatypes::{DataType, Field, Schema},
+    record_batch::RecordBatch,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+use datafusion::prelude::{SessionConfig, SessionContext};
+use datafusion::{datasource::MemTable, error::Result};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+fn query(ctx: &SessionContext, rt: &Runtime, sql: &str) {
+    let df = rt.block_on(ctx.sql(sql)).unwrap();
+    criterion::black_box(rt.block_on(df.collect()).unwrap());
+}
+
+fn create_context(
+    array_len: usize,
+    batch_size: usize,
+    repartition_threshold_rows: usize,
+) -> Result<SessionContext> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+    ]));
+
+    let batches = (0..array_len / batch_size)
+        .map(|i| {
+            let ids = (i * batch_size..(i + 1) * batch_size).map(|id| id as i64);
+            RecordBatch::try_new(
+                Arc::clone(&schema),
+                vec![
+                    Arc::new(Int64Array::from_iter_values(ids.clone())),
+                    Arc::new(StringArray::from_iter_values(
+                        ids.map(|id| format!("name {}", id % 100)),
+                    )),
+                ],
+            )
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let config = SessionConfig::new()
+        .with_target_partitions(16)
+        .with_repartition_threshold_rows(repartition_threshold_rows);
+    let ctx = SessionContext::new_with_config(config);
+    let provider = MemTable::try_new(schema, vec![batches])?;
+    ctx.register_table("t", Arc::new(provider))?;
+
+    Ok(ctx)
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let array_len = 65_536; // 2^16
+    let batch_size = 4096; // 2^12
+    let rt = Runtime::new().unwrap();
+
+    let queries = [
+        ("lookup", "select id, name from t where id = 4242"),
+        (
+            "lookup_aggregate",
+            "select name, count(*) from t where id = 4242 group by name",
+        ),
+        (
+            "lookup_distinct",
+            "select distinct name from t where id in (1, 2, 3)",
+        ),
+    ];
+
+    for (name, sql) in queries {
+        for (suffix, repartition_threshold_rows) in [("", 0), ("_threshold", 100_000)] {
+            let ctx = create_context(array_len, batch_size, repartition_threshold_rows)
+                .unwrap();
+            c.bench_function(&format!("{name}{suffix}"), |b| {
+                b.iter(|| query(&ctx, &rt, sql))
+            });
+        }
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -122,6 +122,10 @@ fn criterion_benchmark(c: &mut Criterion) {
             &utf8_view_high_cardinality_streams,
         ),
         ("utf8 tuple", &utf8_tuple_streams),
+        (
+            "utf8 low cardinality i64 tuple",
+            &utf8_low_i64_tuple_streams,
+        ),
         ("utf8 view tuple", &utf8_view_tuple_streams),
         ("utf8 dictionary", &dictionary_streams),
         ("utf8 dictionary tuple", &dictionary_tuple_streams),
@@ -386,6 +390,35 @@ fn utf8_tuple_streams(sorted: bool) -> PartitionedBatches {
     })
 }
 
+/// Create a batch of (utf8_low, i64)
+fn utf8_low_i64_tuple_streams(sorted: bool) -> PartitionedBatches {
+    let mut gen = DataGenerator::new();
+
+    // need to sort by the combined key, so combine them together
+    let mut tuples: Vec<_> = gen
+        .utf8_low_cardinality_values()
+        .into_iter()
+        .zip(gen.i64_values())
+        .collect();
+
+    if sorted {
+        tuples.sort_unstable();
+    }
+
+    split_tuples(tuples, |tuples| {
+        let (utf8_low, i64_values): (Vec<_>, Vec<_>) = tuples.into_iter().unzip();
+
+        let utf8_low: StringArray = utf8_low.into_iter().collect();
+        let i64_values: Int64Array = i64_values.into_iter().collect();
+
+        RecordBatch::try_from_iter(vec![
+            ("utf_low", Arc::new(utf8_low) as _),
+            ("i64", Arc::new(i64_values) as _),
+        ])
+        .unwrap()
+    })
+}
+
 /// Create a batch of (utf8_view_low, utf8_view_low, utf8_view_high)
 fn utf8_view_tuple_streams(sorted: bool) -> PartitionedBatches {
     let mut gen = DataGenerator::new();
@@ -0,0 +1,307 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`AvroManifestTable`]: a table of the Avro files listed in a manifest file
+
+use std::any::Any;
+use std::sync::Arc;
+
+use crate::datasource::file_format::avro::AvroFormat;
+use crate::datasource::listing::ListingTableUrl;
+use crate::datasource::TableProvider;
+use crate::error::Result;
+
+use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+use datafusion_catalog::Session;
+use datafusion_common::stats::Precision;
+use datafusion_common::{exec_datafusion_err, exec_err, project_schema, Statistics};
+use datafusion_datasource::compute_all_files_statistics;
+use datafusion_datasource::file_format::FileFormat;
+use datafusion_datasource::file_groups::FileGroup;
+use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
+use datafusion_datasource::PartitionedFile;
+use datafusion_datasource_avro::AvroBlockStream;
+use datafusion_execution::object_store::ObjectStoreUrl;
+use datafusion_expr::{Expr, TableType};
+use datafusion_physical_plan::empty::EmptyExec;
+use datafusion_physical_plan::ExecutionPlan;
+use futures::{StreamExt, TryStreamExt};
+use object_store::{ObjectMeta, ObjectStore};
+
+/// A [`TableProvider`] scanning the Avro files listed in a manifest file as a
+/// single table, as table formats track their data files.
+///
+/// The manifest is either a JSON list of paths, or lists one path per line.
+/// Paths are URLs, absolute paths, or paths relative to the manifest.
+///
+/// The files are read when the table is created: the schema of the table is
+/// inferred from their headers, and the number of records of each file is
+/// counted from the headers of its blocks, without decoding them.
+#[derive(Debug)]
+pub struct AvroManifestTable {
+    manifest: ListingTableUrl,
+    format: AvroFormat,
+    object_store_url: ObjectStoreUrl,
+    schema: SchemaRef,
+    /// The files listed in the manifest, along with their statistics
+    files: Vec<PartitionedFile>,
+}
+
+impl AvroManifestTable {
+    /// Create a table of the Avro files listed in the `manifest` file, read
+    /// with the default [`AvroFormat`](struct@AvroFormat)
+    pub async fn try_new(state: &dyn Session, manifest: ListingTableUrl) -> Result<Self> {
+        Self::try_new_with_format(state, manifest, AvroFormat::default()).await
+    }
+
+    /// Create a table of the Avro files listed in the `manifest` file, read
+    /// with `format`
+    pub async fn try_new_with_format(
+        state: &dyn Session,
+        manifest: ListingTableUrl,
+        format: AvroFormat,
+    ) -> Result<Self> {
+        let object_store_url = manifest.object_store();
+        let store = state.runtime_env().object_store(&object_store_url)?;
+        let contents = store.get(manifest.prefix()).await?.bytes().await?;
+        let Ok(contents) = std::str::from_utf8(&contents) else {
+            return exec_err!("Avro manifest {manifest} is not valid UTF-8");
+        };
+
+        let mut locations = vec![];
+        for path in parse_manifest(contents)? {
+            let url = resolve_path(&manifest, &path)?;
+            if url.object_store() != object_store_url {
+                return exec_err!(
+                    "File {path} of Avro manifest {manifest} is not in the object store of the manifest"
+                );
+            }
+            locations.push(url.prefix().clone());
+        }
+        let objects = futures::stream::iter(locations)
+            .map(|location| {
+                let store = Arc::clone(&store);
+                async move { store.head(&location).await }
+            })
+            .buffered(state.config_options().execution.meta_fetch_concurrency)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let schema = format.infer_schema(state, &store, &objects).await?;
+        let files = futures::stream::iter(objects)
+            .map(|object| partitioned_file(state, &format, &store, &schema, object))
+            .buffered(state.config_options().execution.meta_fetch_concurrency)
+            .try_collect()
+            .await?;
+
+        Ok(Self {
+            manifest,
+            format,
+            object_store_url,
+            schema,
+            files,
+        })
+    }
+
+    /// The location of the manifest file
+    pub fn manifest(&self) -> &ListingTableUrl {
+        &self.manifest
+    }
+
+    /// The files listed in the manifest, along with their statistics
+    pub fn files(&self) -> &[PartitionedFile] {
+        &self.files
+    }
+}
+
+#[async_trait]
+impl TableProvider for AvroManifestTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if self.files.is_empty() {
+            let projected_schema = project_schema(&self.schema, projection)?;
+            return Ok(Arc::new(EmptyExec::new(projected_schema)));
+        }
+
+        let file_groups = FileGroup::new(self.files.clone())
+            .split_files(state.config_options().execution.target_partitions);
+        let (file_groups, statistics) =
+            compute_all_files_statistics(file_groups, self.schema(), true, false)?;
+        let config = FileScanConfigBuilder::new(
+            self.object_store_url.clone(),
+            self.schema(),
+            self.format.file_source(),
+        )
+        .with_file_groups(file_groups)
+        .with_statistics(statistics)
+        .with_projection(projection.cloned())
+        .with_limit(limit)
+        .build();
+        self.format.create_physical_plan(state, config).await
+    }
+
+    fn statistics(&self) -> Option<Statistics> {
+        let statistics = self
+            .files
+            .iter()
+            .filter_map(|file| file.statistics.as_deref());
+        Statistics::try_merge_iter(statistics, &self.schema).ok()
+    }
+}
+
+/// Returns the paths listed in a manifest: a JSON list of strings, or one
+/// path per line, ignoring blank lines
+fn parse_manifest(contents: &str) -> Result<Vec<String>> {
+    let contents = contents.trim();
+    if contents.starts_with('[') {
+        return serde_json::from_str(contents)
+            .map_err(|e| exec_datafusion_err!("Invalid Avro manifest: {e}"));
+    }
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Resolves a `path` listed in `manifest`: URLs and absolute paths are used
+/// as they are, and other paths are relative to the directory of the manifest
+fn resolve_path(manifest: &ListingTableUrl, path: &str) -> Result<ListingTableUrl> {
+    if path.contains("://") || std::path::Path::new(path).is_absolute() {
+        return ListingTableUrl::parse(path);
+    }
+    let Ok(url) = manifest.get_url().join(path) else {
+        return exec_err!("Invalid path {path} in Avro manifest {manifest}");
+    };
+    ListingTableUrl::parse(url)
+}
+
+/// Returns the [`PartitionedFile`] of an Avro file, with statistics holding
+/// the number of its records. Falls back to the statistics inferred by
+/// `format` for files which can't be read block by block, such as compressed
+/// files
+async fn partitioned_file(
+    state: &dyn Session,
+    format: &AvroFormat,
+    store: &Arc<dyn ObjectStore>,
+    schema: &SchemaRef,
+    object: ObjectMeta,
+) -> Result<PartitionedFile> {
+    let record_count = match AvroBlockStream::open(store.as_ref(), &object.location).await
+    {
+        Ok(stream) => stream.count_records().await.ok(),
+        Err(_) => None,
+    };
+    let statistics = match record_count {
+        Some(record_count) => Statistics::new_unknown(schema)
+            .with_num_rows(Precision::Exact(record_count as usize)),
+        None => {
+            format
+                .infer_stats(state, store, Arc::clone(schema), &object)
+                .await?
+        }
+    };
+    Ok(PartitionedFile::from(object).with_statistics(Arc::new(statistics)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::SessionContext;
+
+    use datafusion_common::{assert_batches_eq, test_util};
+    use tempfile::TempDir;
+
+    /// Returns the paths of the `alltypes_plain.avro` (8 rows) and
+    /// `alltypes_dictionary.avro` (2 rows) fixtures
+    fn fixtures() -> Vec<String> {
+        let testdata = test_util::arrow_test_data();
+        ["alltypes_plain.avro", "alltypes_dictionary.avro"]
+            .iter()
+            .map(|file| format!("{testdata}/avro/{file}"))
+            .collect()
+    }
+
+    async fn assert_manifest_table(contents: &str) -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let manifest = tmp_dir.path().join("manifest");
+        std::fs::write(&manifest, contents)?;
+
+        let ctx = SessionContext::new();
+        let manifest = ListingTableUrl::parse(manifest.to_str().unwrap())?;
+        let table = AvroManifestTable::try_new(&ctx.state(), manifest).await?;
+        assert_eq!(table.files().len(), 2);
+        assert_eq!(table.statistics().unwrap().num_rows, Precision::Exact(10));
+
+        ctx.register_table("t", Arc::new(table))?;
+        let batches = ctx.sql("SELECT count(*) FROM t").await?.collect().await?;
+        assert_batches_eq!(
+            [
+                "+----------+",
+                "| count(*) |",
+                "+----------+",
+                "| 10       |",
+                "+----------+",
+            ],
+            &batches
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_newline_delimited_manifest() -> Result<()> {
+        assert_manifest_table(&format!("{}\n\n", fixtures().join("\n"))).await
+    }
+
+    #[tokio::test]
+    async fn read_json_manifest() -> Result<()> {
+        assert_manifest_table(&serde_json::to_string(&fixtures()).unwrap()).await
+    }
+
+    #[test]
+    fn resolve_manifest_paths() -> Result<()> {
+        let manifest = ListingTableUrl::parse("s3://bucket/table/manifest.txt")?;
+        assert_eq!(
+            resolve_path(&manifest, "data/part-0.avro")?.as_str(),
+            "s3://bucket/table/data/part-0.avro"
+        );
+        assert_eq!(
+            resolve_path(&manifest, "s3://other/part-0.avro")?.as_str(),
+            "s3://other/part-0.avro"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,401 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`AvroRegistryCatalogProvider`] and [`AvroRegistrySchemaProvider`]: catalogs
+//! of Avro-backed tables discovered through an [`AvroTableRegistry`]
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::catalog::{CatalogProvider, SchemaProvider};
+use crate::datasource::file_format::avro::AvroFormat;
+use crate::datasource::listing::{
+    ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+};
+use crate::datasource::TableProvider;
+use crate::error::Result;
+
+use async_trait::async_trait;
+use datafusion_common::{exec_err, DEFAULT_AVRO_EXTENSION};
+pub use datafusion_datasource_avro::registry::{AvroTableDefinition, AvroTableRegistry};
+
+/// A [`CatalogProvider`] exposing every namespace of an [`AvroTableRegistry`]
+/// as an [`AvroRegistrySchemaProvider`].
+///
+/// The namespaces and table names are fetched from the registry by
+/// [`Self::refresh`]; tables themselves are only constructed when first
+/// accessed.
+#[derive(Debug)]
+pub struct AvroRegistryCatalogProvider {
+    registry: Arc<dyn AvroTableRegistry>,
+    schemas: Mutex<HashMap<String, Arc<AvroRegistrySchemaProvider>>>,
+}
+
+impl AvroRegistryCatalogProvider {
+    /// Create a new, empty `AvroRegistryCatalogProvider`. Call
+    /// [`Self::refresh`] to discover the namespaces and tables of `registry`
+    pub fn new(registry: Arc<dyn AvroTableRegistry>) -> Self {
+        Self {
+            registry,
+            schemas: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a new `AvroRegistryCatalogProvider` and discover the namespaces
+    /// and tables of `registry`
+    pub async fn try_new(registry: Arc<dyn AvroTableRegistry>) -> Result<Self> {
+        let catalog = Self::new(registry);
+        catalog.refresh().await?;
+        Ok(catalog)
+    }
+
+    /// Reload the namespaces and table names from the registry
+    pub async fn refresh(&self) -> Result<()> {
+        let mut schemas = HashMap::new();
+        for namespace in self.registry.list_namespaces().await? {
+            let schema = AvroRegistrySchemaProvider::try_new(
+                Arc::clone(&self.registry),
+                namespace.clone(),
+            )
+            .await?;
+            schemas.insert(namespace, Arc::new(schema));
+        }
+        *self.schemas.lock().expect("Can't lock schemas") = schemas;
+        Ok(())
+    }
+}
+
+impl CatalogProvider for AvroRegistryCatalogProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema_names(&self) -> Vec<String> {
+        self.schemas
+            .lock()
+            .expect("Can't lock schemas")
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    fn schema(&self, name: &str) -> Option<Arc<dyn SchemaProvider>> {
+        self.schemas
+            .lock()
+            .expect("Can't lock schemas")
+            .get(name)
+            .map(|schema| Arc::clone(schema) as Arc<dyn SchemaProvider>)
+    }
+}
+
+/// A [`SchemaProvider`] for the tables of one namespace of an
+/// [`AvroTableRegistry`].
+///
+/// Each table is a [`ListingTable`] reading the Avro files at the location
+/// resolved by the registry with [`AvroFormat`](struct@AvroFormat), using
+/// the schema resolved by the registry instead of inferring it from the
+/// files. Tables are constructed when first accessed and cached afterwards.
+#[derive(Debug)]
+pub struct AvroRegistrySchemaProvider {
+    registry: Arc<dyn AvroTableRegistry>,
+    namespace: String,
+    /// The known tables, `None` if not constructed yet
+    tables: Mutex<HashMap<String, Option<Arc<dyn TableProvider>>>>,
+}
+
+impl AvroRegistrySchemaProvider {
+    /// Create a new, empty `AvroRegistrySchemaProvider` for `namespace`. Call
+    /// [`Self::refresh`] to discover its tables
+    pub fn new(registry: Arc<dyn AvroTableRegistry>, namespace: String) -> Self {
+        Self {
+            registry,
+            namespace,
+            tables: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a new `AvroRegistrySchemaProvider` for `namespace` and discover
+    /// its tables
+    pub async fn try_new(
+        registry: Arc<dyn AvroTableRegistry>,
+        namespace: String,
+    ) -> Result<Self> {
+        let schema = Self::new(registry, namespace);
+        schema.refresh().await?;
+        Ok(schema)
+    }
+
+    /// The namespace of the registry this schema exposes
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Reload the table names from the registry. Tables which are still
+    /// known to the registry keep their constructed [`TableProvider`]
+    pub async fn refresh(&self) -> Result<()> {
+        let names = self.registry.list_tables(&self.namespace).await?;
+        let mut tables = self.tables.lock().expect("Can't lock tables");
+        let mut refreshed = HashMap::with_capacity(names.len());
+        for name in names {
+            let table = tables.remove(&name).flatten();
+            refreshed.insert(name, table);
+        }
+        *tables = refreshed;
+        Ok(())
+    }
+
+    /// Create the [`ListingTable`] for `definition`
+    fn create_table(definition: &AvroTableDefinition) -> Result<Arc<dyn TableProvider>> {
+        let table_path = ListingTableUrl::parse(definition.location())?;
+        let options = ListingOptions::new(Arc::new(AvroFormat::default()))
+            .with_file_extension(DEFAULT_AVRO_EXTENSION);
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(options)
+            .with_schema(Arc::new(definition.arrow_schema()?));
+        Ok(Arc::new(ListingTable::try_new(config)?))
+    }
+}
+
+#[async_trait]
+impl SchemaProvider for AvroRegistrySchemaProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn table_names(&self) -> Vec<String> {
+        self.tables
+            .lock()
+            .expect("Can't lock tables")
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    async fn table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+        match self.tables.lock().expect("Can't lock tables").get(name) {
+            Some(Some(table)) => return Ok(Some(Arc::clone(table))),
+            Some(None) => {}
+            None => return Ok(None),
+        }
+
+        let Some(definition) = self.registry.resolve_table(&self.namespace, name).await?
+        else {
+            return Ok(None);
+        };
+        let table = Self::create_table(&definition)?;
+        self.tables
+            .lock()
+            .expect("Can't lock tables")
+            .insert(name.to_string(), Some(Arc::clone(&table)));
+        Ok(Some(table))
+    }
+
+    fn register_table(
+        &self,
+        name: String,
+        _table: Arc<dyn TableProvider>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        exec_err!(
+            "Can not register table {name}: tables of registry namespace {} are read-only",
+            self.namespace
+        )
+    }
+
+    fn table_exist(&self, name: &str) -> bool {
+        self.tables
+            .lock()
+            .expect("Can't lock tables")
+            .contains_key(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::prelude::{SessionConfig, SessionContext};
+
+    use apache_avro::types::Record;
+    use apache_avro::{Schema as AvroSchema, Writer};
+    use datafusion_common::{assert_batches_eq, assert_batches_sorted_eq};
+    use tempfile::TempDir;
+
+    const USERS_SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "user",
+        "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "name", "type": "string"}
+        ]
+    }"#;
+
+    const EVENTS_SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "event",
+        "fields": [
+            {"name": "user_id", "type": "long"},
+            {"name": "kind", "type": "string"}
+        ]
+    }"#;
+
+    /// A registry with a single `analytics` namespace containing the `users`
+    /// and `events` tables
+    #[derive(Debug)]
+    struct MockRegistry {
+        root: String,
+    }
+
+    #[async_trait]
+    impl AvroTableRegistry for MockRegistry {
+        async fn list_namespaces(&self) -> Result<Vec<String>> {
+            Ok(vec!["analytics".to_string()])
+        }
+
+        async fn list_tables(&self, namespace: &str) -> Result<Vec<String>> {
+            match namespace {
+                "analytics" => Ok(vec!["users".to_string(), "events".to_string()]),
+                _ => Ok(vec![]),
+            }
+        }
+
+        async fn resolve_table(
+            &self,
+            namespace: &str,
+            table: &str,
+        ) -> Result<Option<AvroTableDefinition>> {
+            let schema = match (namespace, table) {
+                ("analytics", "users") => USERS_SCHEMA,
+                ("analytics", "events") => EVENTS_SCHEMA,
+                _ => return Ok(None),
+            };
+            let location = format!("{}/{table}/", self.root);
+            Ok(Some(AvroTableDefinition::try_new_from_json(
+                location, schema,
+            )?))
+        }
+    }
+
+    fn write_avro(path: &Path, schema: &str, rows: &[(i64, &str)]) -> Result<()> {
+        let schema = AvroSchema::parse_str(schema)?;
+        let fields = match &schema {
+            AvroSchema::Record(record) => record
+                .fields
+                .iter()
+                .map(|field| field.name.clone())
+                .collect::<Vec<_>>(),
+            _ => unreachable!(),
+        };
+        let mut writer = Writer::new(&schema, Vec::new());
+        for (number, text) in rows {
+            let mut record = Record::new(&schema).unwrap();
+            record.put(&fields[0], *number);
+            record.put(&fields[1], *text);
+            writer.append(record)?;
+        }
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(path, writer.into_inner()?)?;
+        Ok(())
+    }
+
+    async fn registry_context(tmp_dir: &TempDir) -> Result<SessionContext> {
+        let root = tmp_dir.path();
+        write_avro(
+            &root.join("users/part-0.avro"),
+            USERS_SCHEMA,
+            &[(1, "alice"), (2, "bob")],
+        )?;
+        write_avro(
+            &root.join("events/part-0.avro"),
+            EVENTS_SCHEMA,
+            &[(1, "login"), (2, "login"), (1, "logout")],
+        )?;
+
+        let registry = Arc::new(MockRegistry {
+            root: root.to_str().unwrap().to_string(),
+        });
+        let catalog = AvroRegistryCatalogProvider::try_new(registry).await?;
+
+        let ctx = SessionContext::new_with_config(
+            SessionConfig::new().with_information_schema(true),
+        );
+        ctx.register_catalog("registry", Arc::new(catalog));
+        Ok(ctx)
+    }
+
+    #[tokio::test]
+    async fn query_registry_tables() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let ctx = registry_context(&tmp_dir).await?;
+
+        let batches = ctx
+            .sql(
+                "SELECT u.name, e.kind FROM registry.analytics.users u \
+                 JOIN registry.analytics.events e ON u.id = e.user_id",
+            )
+            .await?
+            .collect()
+            .await?;
+        assert_batches_sorted_eq!(
+            [
+                "+-------+--------+",
+                "| name  | kind   |",
+                "+-------+--------+",
+                "| alice | login  |",
+                "| alice | logout |",
+                "| bob   | login  |",
+                "+-------+--------+",
+            ],
+            &batches
+        );
+
+        let unknown = ctx.sql("SELECT * FROM registry.analytics.unknown").await;
+        assert!(unknown.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn registry_tables_in_information_schema() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let ctx = registry_context(&tmp_dir).await?;
+
+        let batches = ctx
+            .sql(
+                "SELECT table_schema, table_name, column_name, data_type \
+                 FROM information_schema.columns WHERE table_catalog = 'registry' \
+                 ORDER BY table_name, ordinal_position",
+            )
+            .await?
+            .collect()
+            .await?;
+        assert_batches_eq!(
+            [
+                "+--------------+------------+-------------+-----------+",
+                "| table_schema | table_name | column_name | data_type |",
+                "+--------------+------------+-------------+-----------+",
+                "| analytics    | events     | user_id     | Int64     |",
+                "| analytics    | events     | kind        | Utf8      |",
+                "| analytics    | users      | id          | Int64     |",
+                "| analytics    | users      | name        | Utf8      |",
+                "+--------------+------------+-------------+-----------+",
+            ],
+            &batches
+        );
+        Ok(())
+    }
+}
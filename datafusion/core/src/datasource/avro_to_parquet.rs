@@ -0,0 +1,167 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`transcode_avro_to_parquet`]: rewrite a set of Avro files as Parquet
+
+use std::sync::Arc;
+
+use crate::dataframe::DataFrameWriteOptions;
+use crate::datasource::file_format::avro::AvroFormat;
+use crate::datasource::listing::{
+    ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+};
+use crate::error::Result;
+use crate::execution::context::SessionContext;
+
+use arrow::array::{Array, UInt64Array};
+use datafusion_common::config::TableParquetOptions;
+use datafusion_common::{internal_datafusion_err, DEFAULT_AVRO_EXTENSION};
+
+/// Rewrites the Avro files matching `input_glob` as Parquet files in
+/// `output_dir`, returning the number of rows written.
+///
+/// The files are streamed through a DataFusion plan into a Parquet sink, so
+/// they are never loaded into memory all at once. The schema of the Avro
+/// files is preserved, and hive-style partition directories below the input
+/// path, such as `year=2024/`, are inferred and written as the same
+/// partition directories below `output_dir`.
+///
+/// `parquet_options` configures the Parquet writer, such as its compression.
+/// When `None`, the writer options of `ctx` are used.
+///
+/// ```no_run
+/// # use datafusion::datasource::avro_to_parquet::transcode_avro_to_parquet;
+/// # use datafusion::error::Result;
+/// # use datafusion::prelude::SessionContext;
+/// # async fn f() -> Result<()> {
+/// let ctx = SessionContext::new();
+/// let rows =
+///     transcode_avro_to_parquet(&ctx, "/data/events/", "/data/events_parquet/", None)
+///         .await?;
+/// println!("transcoded {rows} rows");
+/// # Ok(())
+/// # }
+/// ```
+pub async fn transcode_avro_to_parquet(
+    ctx: &SessionContext,
+    input_glob: &str,
+    output_dir: &str,
+    parquet_options: Option<TableParquetOptions>,
+) -> Result<u64> {
+    let state = ctx.state();
+    let table_path = ListingTableUrl::parse(input_glob)?;
+    let options = ListingOptions::new(Arc::new(AvroFormat))
+        .with_file_extension(DEFAULT_AVRO_EXTENSION)
+        .with_target_partitions(state.config().target_partitions());
+    let config = ListingTableConfig::new(table_path)
+        .with_listing_options(options)
+        .infer_partitions_from_path(&state)
+        .await?
+        .infer_schema(&state)
+        .await?;
+
+    let partition_by = config
+        .options
+        .as_ref()
+        .map(|options| {
+            options
+                .table_partition_cols
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let table = ListingTable::try_new(config)?;
+    let batches = ctx
+        .read_table(Arc::new(table))?
+        .write_parquet(
+            output_dir,
+            DataFrameWriteOptions::new().with_partition_by(partition_by),
+            parquet_options,
+        )
+        .await?;
+
+    let mut rows = 0;
+    for batch in &batches {
+        let counts = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .ok_or_else(|| internal_datafusion_err!("Unexpected Parquet write result"))?;
+        rows += counts.iter().flatten().sum::<u64>();
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{AvroReadOptions, ParquetReadOptions};
+    use arrow::datatypes::DataType;
+    use datafusion_common::test_util::{arrow_test_data, batches_to_sort_string};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn transcode_partitioned_avro() -> Result<()> {
+        let input = TempDir::new()?;
+        let output = TempDir::new()?;
+        let fixture = format!("{}/avro/alltypes_plain.avro", arrow_test_data());
+        for year in ["2023", "2024"] {
+            let dir = input.path().join(format!("year={year}"));
+            std::fs::create_dir(&dir)?;
+            std::fs::copy(&fixture, dir.join("alltypes_plain.avro"))?;
+        }
+        let input_dir = format!("{}/", input.path().to_str().unwrap());
+        let output_dir = format!("{}/", output.path().to_str().unwrap());
+
+        let ctx = SessionContext::new();
+        let mut parquet_options = TableParquetOptions::default();
+        parquet_options.global.compression = Some("zstd(1)".to_string());
+        let rows = transcode_avro_to_parquet(
+            &ctx,
+            &input_dir,
+            &output_dir,
+            Some(parquet_options),
+        )
+        .await?;
+        assert_eq!(rows, 16);
+
+        let partition_cols = vec![(
+            "year".to_string(),
+            DataType::Dictionary(Box::new(DataType::UInt16), Box::new(DataType::Utf8)),
+        )];
+        let avro = ctx
+            .read_avro(
+                input_dir.as_str(),
+                AvroReadOptions::default().table_partition_cols(partition_cols.clone()),
+            )
+            .await?;
+        let parquet = ctx
+            .read_parquet(
+                output_dir.as_str(),
+                ParquetReadOptions::default().table_partition_cols(partition_cols),
+            )
+            .await?;
+        assert_eq!(avro.schema().fields(), parquet.schema().fields());
+        assert_eq!(
+            batches_to_sort_string(&avro.collect().await?),
+            batches_to_sort_string(&parquet.collect().await?)
+        );
+        Ok(())
+    }
+}
@@ -16,10 +16,14 @@
 // under the License.
 
 //! dynamic_file_schema contains an [`UrlTableFactory`] implementation that
-//! can create a [`ListingTable`] from the given url.
+//! can create a [`ListingTable`] from the given url, which also creates the
+//! tables of a [`DiscoveringSchemaProvider`].
+//!
+//! [`DiscoveringSchemaProvider`]: crate::datasource::listing::discovery::DiscoveringSchemaProvider
 
 use std::sync::Arc;
 
+use crate::datasource::listing::discovery::DiscoveredTableFactory;
 use crate::datasource::listing::{ListingTable, ListingTableConfig, ListingTableUrl};
 use crate::datasource::TableProvider;
 use crate::error::Result;
@@ -27,6 +31,7 @@ use crate::execution::context::SessionState;
 
 use datafusion_catalog::UrlTableFactory;
 use datafusion_common::plan_datafusion_err;
+use datafusion_expr::CreateExternalTable;
 use datafusion_session::SessionStore;
 
 use async_trait::async_trait;
@@ -48,17 +53,10 @@ impl DynamicListTableFactory {
     pub fn session_store(&self) -> &SessionStore {
         &self.session_store
     }
-}
 
-#[async_trait]
-impl UrlTableFactory for DynamicListTableFactory {
-    async fn try_new(&self, url: &str) -> Result<Option<Arc<dyn TableProvider>>> {
-        let Ok(table_url) = ListingTableUrl::parse(url) else {
-            return Ok(None);
-        };
-
-        let state = &self
-            .session_store()
+    /// Get a snapshot of the current session state
+    fn session_state(&self) -> Result<SessionState> {
+        self.session_store()
             .get_session()
             .upgrade()
             .and_then(|session| {
@@ -68,7 +66,18 @@ impl UrlTableFactory for DynamicListTableFactory {
                     .downcast_ref::<SessionState>()
                     .cloned()
             })
-            .ok_or_else(|| plan_datafusion_err!("get current SessionStore error"))?;
+            .ok_or_else(|| plan_datafusion_err!("get current SessionStore error"))
+    }
+}
+
+#[async_trait]
+impl UrlTableFactory for DynamicListTableFactory {
+    async fn try_new(&self, url: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+        let Ok(table_url) = ListingTableUrl::parse(url) else {
+            return Ok(None);
+        };
+
+        let state = &self.session_state()?;
 
         match ListingTableConfig::new(table_url.clone())
             .infer_options(state)
@@ -87,3 +96,17 @@ impl UrlTableFactory for DynamicListTableFactory {
         }
     }
 }
+
+#[async_trait]
+impl DiscoveredTableFactory for DynamicListTableFactory {
+    async fn create(&self, cmd: &CreateExternalTable) -> Result<Arc<dyn TableProvider>> {
+        let state = self.session_state()?;
+        let factory = state
+            .table_factories()
+            .get(cmd.file_type.to_uppercase().as_str())
+            .ok_or_else(|| {
+                plan_datafusion_err!("Unable to find factory for {}", cmd.file_type)
+            })?;
+        factory.create(&state, cmd).await
+    }
+}
@@ -17,12 +17,15 @@
 
 //! [`ArrowFormat`]: Apache Arrow [`FileFormat`] abstractions
 //!
-//! Works with files following the [Arrow IPC format](https://arrow.apache.org/docs/format/Columnar.html#ipc-file-format)
+//! Works with files following the [Arrow IPC format](https://arrow.apache.org/docs/format/Columnar.html#ipc-file-format),
+//! as well as files of the [IPC streaming format](https://arrow.apache.org/docs/format/Columnar.html#ipc-streaming-format)
 
 use std::any::Any;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::{self, Debug};
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::str::FromStr;
 use std::sync::Arc;
 
 use super::file_compression_type::FileCompressionType;
@@ -35,16 +38,18 @@ use crate::datasource::physical_plan::{ArrowSource, FileSink, FileSinkConfig};
 use crate::error::Result;
 use crate::physical_plan::{DisplayAs, DisplayFormatType, ExecutionPlan};
 
+use arrow::array::RecordBatch;
 use arrow::datatypes::{Schema, SchemaRef};
 use arrow::error::ArrowError;
 use arrow::ipc::convert::fb_to_schema;
-use arrow::ipc::reader::FileReader;
-use arrow::ipc::writer::IpcWriteOptions;
+use arrow::ipc::reader::{FileReader, StreamReader};
+use arrow::ipc::writer::{FileWriter, IpcWriteOptions, StreamWriter};
 use arrow::ipc::{root_as_message, CompressionType};
 use datafusion_catalog::Session;
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::{
-    not_impl_err, DataFusionError, GetExt, Statistics, DEFAULT_ARROW_EXTENSION,
+    config_err, not_impl_err, DataFusionError, GetExt, Statistics,
+    DEFAULT_ARROW_EXTENSION,
 };
 use datafusion_common_runtime::{JoinSet, SpawnedTask};
 use datafusion_datasource::display::FileGroupDisplay;
@@ -71,6 +76,9 @@ const INITIAL_BUFFER_BYTES: usize = 1048576;
 /// If the buffered Arrow data exceeds this size, it is flushed to object store
 const BUFFER_FLUSH_BYTES: usize = 1024000;
 
+/// The extension of files in the IPC stream format
+const ARROW_STREAM_EXTENSION: &str = "arrows";
+
 #[derive(Default, Debug)]
 /// Factory struct used to create [ArrowFormat]
 pub struct ArrowFormatFactory;
@@ -86,13 +94,21 @@ impl FileFormatFactory for ArrowFormatFactory {
     fn create(
         &self,
         _state: &dyn Session,
-        _format_options: &HashMap<String, String>,
+        format_options: &HashMap<String, String>,
     ) -> Result<Arc<dyn FileFormat>> {
-        Ok(Arc::new(ArrowFormat))
+        let mut format = ArrowFormat::default();
+        for (key, value) in format_options {
+            match key.as_str() {
+                "format.ipc_format" => format = format.with_ipc_format(value.parse()?),
+                _ if key.starts_with("execution.") => {}
+                _ => return config_err!("Unsupported Arrow format option \"{key}\""),
+            }
+        }
+        Ok(Arc::new(format))
     }
 
     fn default(&self) -> Arc<dyn FileFormat> {
-        Arc::new(ArrowFormat)
+        Arc::new(ArrowFormat::default())
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -107,9 +123,53 @@ impl GetExt for ArrowFormatFactory {
     }
 }
 
+/// The formats of the Arrow IPC protocol
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArrowIpcFormat {
+    /// The IPC file format, whose footer indexes the record batches and
+    /// dictionaries of the file
+    #[default]
+    File,
+    /// The IPC streaming format, a sequence of messages without footer
+    Stream,
+}
+
+impl FromStr for ArrowIpcFormat {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "file" => Ok(Self::File),
+            "stream" => Ok(Self::Stream),
+            _ => config_err!(
+                "Invalid Arrow IPC format \"{s}\", expected \"file\" or \"stream\""
+            ),
+        }
+    }
+}
+
 /// Arrow `FileFormat` implementation.
+///
+/// Files in both the IPC file format and the IPC stream format are read, the
+/// format of each file being detected from its first bytes. Files are written
+/// in the format set with [`Self::with_ipc_format`].
 #[derive(Default, Debug)]
-pub struct ArrowFormat;
+pub struct ArrowFormat {
+    ipc_format: ArrowIpcFormat,
+}
+
+impl ArrowFormat {
+    /// Sets the IPC format of the files written, the file format by default
+    pub fn with_ipc_format(mut self, ipc_format: ArrowIpcFormat) -> Self {
+        self.ipc_format = ipc_format;
+        self
+    }
+
+    /// The IPC format of the files written
+    pub fn ipc_format(&self) -> ArrowIpcFormat {
+        self.ipc_format
+    }
+}
 
 #[async_trait]
 impl FileFormat for ArrowFormat {
@@ -118,7 +178,10 @@ impl FileFormat for ArrowFormat {
     }
 
     fn get_ext(&self) -> String {
-        ArrowFormatFactory::new().get_ext()
+        match self.ipc_format {
+            ArrowIpcFormat::File => ArrowFormatFactory::new().get_ext(),
+            ArrowIpcFormat::Stream => ARROW_STREAM_EXTENSION.to_string(),
+        }
     }
 
     fn get_ext_with_compression(
@@ -146,8 +209,15 @@ impl FileFormat for ArrowFormat {
             let schema = match r.payload {
                 #[cfg(not(target_arch = "wasm32"))]
                 GetResultPayload::File(mut file, _) => {
-                    let reader = FileReader::try_new(&mut file, None)?;
-                    reader.schema()
+                    let mut magic = [0; ARROW_MAGIC.len()];
+                    let is_file_format =
+                        file.read_exact(&mut magic).is_ok() && magic == ARROW_MAGIC;
+                    file.seek(SeekFrom::Start(0))?;
+                    if is_file_format {
+                        FileReader::try_new(&mut file, None)?.schema()
+                    } else {
+                        StreamReader::try_new(BufReader::new(file), None)?.schema()
+                    }
                 }
                 GetResultPayload::Stream(stream) => {
                     infer_schema_from_file_stream(stream).await?
@@ -171,12 +241,26 @@ impl FileFormat for ArrowFormat {
 
     async fn create_physical_plan(
         &self,
-        _state: &dyn Session,
+        state: &dyn Session,
         conf: FileScanConfig,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        let source = Arc::new(ArrowSource::default());
+        let mut source = ArrowSource::default();
+        let options = &state.config_options().optimizer;
+        if options.repartition_file_scans {
+            // Only files large enough to be split into byte ranges are checked
+            let min_size = options.repartition_file_min_size as u64;
+            let store = state.runtime_env().object_store(&conf.object_store_url)?;
+            for file in conf.file_groups.iter().flat_map(|group| group.iter()) {
+                if file.object_meta.size >= min_size
+                    && is_stream_file(&store, &file.object_meta).await?
+                {
+                    source = source.with_stream_files(true);
+                    break;
+                }
+            }
+        }
         let config = FileScanConfigBuilder::from(conf)
-            .with_source(source)
+            .with_source(Arc::new(source))
             .build();
 
         Ok(DataSourceExec::from_data_source(config))
@@ -189,11 +273,13 @@ impl FileFormat for ArrowFormat {
         conf: FileSinkConfig,
         order_requirements: Option<LexRequirement>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        if conf.insert_op != InsertOp::Append {
-            return not_impl_err!("Overwrites are not implemented yet for Arrow format");
+        if conf.insert_op == InsertOp::Replace {
+            return not_impl_err!(
+                "Replacing rows is not implemented yet for Arrow format"
+            );
         }
 
-        let sink = Arc::new(ArrowFileSink::new(conf));
+        let sink = Arc::new(ArrowFileSink::new(conf, self.ipc_format));
 
         Ok(Arc::new(DataSinkExec::new(input, sink, order_requirements)) as _)
     }
@@ -206,11 +292,52 @@ impl FileFormat for ArrowFormat {
 /// Implements [`FileSink`] for writing to arrow_ipc files
 struct ArrowFileSink {
     config: FileSinkConfig,
+    ipc_format: ArrowIpcFormat,
 }
 
 impl ArrowFileSink {
-    fn new(config: FileSinkConfig) -> Self {
-        Self { config }
+    fn new(config: FileSinkConfig, ipc_format: ArrowIpcFormat) -> Self {
+        Self { config, ipc_format }
+    }
+}
+
+/// A writer of either of the [`ArrowIpcFormat`]s
+enum IpcWriter {
+    File(FileWriter<SharedBuffer>),
+    Stream(StreamWriter<SharedBuffer>),
+}
+
+impl IpcWriter {
+    fn try_new(
+        ipc_format: ArrowIpcFormat,
+        buffer: SharedBuffer,
+        schema: &Schema,
+        options: IpcWriteOptions,
+    ) -> Result<Self> {
+        Ok(match ipc_format {
+            ArrowIpcFormat::File => {
+                Self::File(FileWriter::try_new_with_options(buffer, schema, options)?)
+            }
+            ArrowIpcFormat::Stream => {
+                Self::Stream(StreamWriter::try_new_with_options(buffer, schema, options)?)
+            }
+        })
+    }
+
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        match self {
+            Self::File(writer) => writer.write(batch)?,
+            Self::Stream(writer) => writer.write(batch)?,
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        match self {
+            Self::File(writer) => writer.finish()?,
+            Self::Stream(writer) => writer.finish()?,
+        }
+        Ok(())
     }
 }
 
@@ -235,7 +362,8 @@ impl FileSink for ArrowFileSink {
                 .try_with_compression(Some(CompressionType::LZ4_FRAME))?;
         while let Some((path, mut rx)) = file_stream_rx.recv().await {
             let shared_buffer = SharedBuffer::new(INITIAL_BUFFER_BYTES);
-            let mut arrow_writer = arrow_ipc::writer::FileWriter::try_new_with_options(
+            let mut arrow_writer = IpcWriter::try_new(
+                self.ipc_format,
                 shared_buffer.clone(),
                 &get_writer_schema(&self.config),
                 ipc_options.clone(),
@@ -340,7 +468,7 @@ impl DataSink for ArrowFileSink {
     }
 }
 
-const ARROW_MAGIC: [u8; 6] = [b'A', b'R', b'R', b'O', b'W', b'1'];
+pub(crate) const ARROW_MAGIC: [u8; 6] = [b'A', b'R', b'R', b'O', b'W', b'1'];
 const CONTINUATION_MARKER: [u8; 4] = [0xff; 4];
 
 /// Custom implementation of inferring schema. Should eventually be moved upstream to arrow-rs.
@@ -349,8 +477,8 @@ async fn infer_schema_from_file_stream(
     mut stream: BoxStream<'static, object_store::Result<Bytes>>,
 ) -> Result<SchemaRef> {
     // Expected format:
-    // <magic number "ARROW1"> - 6 bytes
-    // <empty padding bytes [to 8 byte boundary]> - 2 bytes
+    // <magic number "ARROW1"> - 6 bytes, not present in stream files
+    // <empty padding bytes [to 8 byte boundary]> - 2 bytes, not present in stream files
     // <continuation: 0xFFFFFFFF> - 4 bytes, not present below v0.15.0
     // <metadata_size: int32> - 4 bytes
     // <metadata_flatbuffer: bytes>
@@ -360,19 +488,20 @@ async fn infer_schema_from_file_stream(
     // which is 6 + 2 + 4 + 4 = 16 bytes.
     let bytes = collect_at_least_n_bytes(&mut stream, 16, None).await?;
 
-    // Files should start with these magic bytes
-    if bytes[0..6] != ARROW_MAGIC {
-        return Err(ArrowError::ParseError(
-            "Arrow file does not contain correct header".to_string(),
-        ))?;
-    }
+    // Files start with these magic bytes, while stream files directly start
+    // with the schema message
+    let message_start = if bytes[0..6] == ARROW_MAGIC { 8 } else { 0 };
 
     // Since continuation marker bytes added in later versions
-    let (meta_len, rest_of_bytes_start_index) = if bytes[8..12] == CONTINUATION_MARKER {
-        (&bytes[12..16], 16)
-    } else {
-        (&bytes[8..12], 12)
-    };
+    let (meta_len, rest_of_bytes_start_index) =
+        if bytes[message_start..message_start + 4] == CONTINUATION_MARKER {
+            (
+                &bytes[message_start + 4..message_start + 8],
+                message_start + 8,
+            )
+        } else {
+            (&bytes[message_start..message_start + 4], message_start + 4)
+        };
 
     let meta_len = [meta_len[0], meta_len[1], meta_len[2], meta_len[3]];
     let meta_len = i32::from_le_bytes(meta_len);
@@ -406,6 +535,21 @@ async fn infer_schema_from_file_stream(
     Ok(Arc::new(schema))
 }
 
+/// Returns true if `object` is in the IPC stream format, i.e. doesn't start
+/// with the magic bytes of the IPC file format
+async fn is_stream_file(
+    store: &Arc<dyn ObjectStore>,
+    object: &ObjectMeta,
+) -> Result<bool> {
+    if object.size < ARROW_MAGIC.len() as u64 {
+        return Ok(true);
+    }
+    let magic = store
+        .get_range(&object.location, 0..ARROW_MAGIC.len() as u64)
+        .await?;
+    Ok(magic.as_ref() != ARROW_MAGIC)
+}
+
 async fn collect_at_least_n_bytes(
     stream: &mut BoxStream<'static, object_store::Result<Bytes>>,
     n: usize,
@@ -431,9 +575,18 @@ async fn collect_at_least_n_bytes(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::datasource::file_format::options::ArrowReadOptions;
     use crate::execution::context::SessionContext;
+    use crate::physical_plan::displayable;
+    use crate::prelude::SessionConfig;
 
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::compute::cast;
+    use arrow::datatypes::DataType;
     use chrono::DateTime;
+    use datafusion_common::assert_contains;
+    use datafusion_common::test_util::batches_to_string;
+    use insta::assert_snapshot;
     use object_store::{chunked::ChunkedStore, memory::InMemory, path::Path};
 
     #[tokio::test]
@@ -454,7 +607,7 @@ mod tests {
             version: None,
         };
 
-        let arrow_format = ArrowFormat {};
+        let arrow_format = ArrowFormat::default();
         let expected = vec!["f0: Int64", "f1: Utf8", "f2: Boolean"];
 
         // Test chunk sizes where too small so we keep having to read more bytes
@@ -497,7 +650,7 @@ mod tests {
             version: None,
         };
 
-        let arrow_format = ArrowFormat {};
+        let arrow_format = ArrowFormat::default();
 
         let store = Arc::new(ChunkedStore::new(in_memory_store.clone(), 7));
         let err = arrow_format
@@ -516,4 +669,124 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_infer_schema_stream_format() -> Result<()> {
+        let bytes = std::fs::read("tests/data/example_dict.arrows")?;
+        let location = Path::parse("example_dict.arrows")?;
+        let in_memory_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        in_memory_store.put(&location, bytes.into()).await?;
+
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+        let object_meta = in_memory_store.head(&location).await?;
+
+        let arrow_format = ArrowFormat::default();
+        let expected = vec!["f0: Int64", "f1: Dictionary(Int32, Utf8)", "f2: Boolean"];
+        for chunk_size in [7, 3000] {
+            let store = Arc::new(ChunkedStore::new(in_memory_store.clone(), chunk_size));
+            let inferred_schema = arrow_format
+                .infer_schema(
+                    &state,
+                    &(store.clone() as Arc<dyn ObjectStore>),
+                    std::slice::from_ref(&object_meta),
+                )
+                .await?;
+            let actual_fields = inferred_schema
+                .fields()
+                .iter()
+                .map(|f| format!("{}: {:?}", f.name(), f.data_type()))
+                .collect::<Vec<_>>();
+            assert_eq!(expected, actual_fields);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_stream_format() -> Result<()> {
+        // Small enough files would be split between the partitions
+        let config = SessionConfig::new()
+            .with_target_partitions(4)
+            .with_repartition_file_min_size(1);
+        let ctx = SessionContext::new_with_config(config);
+        ctx.register_arrow(
+            "t",
+            "tests/data/example_dict.arrows",
+            ArrowReadOptions {
+                file_extension: ".arrows",
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        let df = ctx.sql("SELECT f1, f0 FROM t").await?;
+        let plan = df.clone().create_physical_plan().await?;
+        assert_contains!(
+            displayable(plan.as_ref()).indent(true).to_string(),
+            "file_groups={1 group: [["
+        );
+        let batches = df.collect().await?;
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +----+----+
+        | f1 | f0 |
+        +----+----+
+        | a  | 1  |
+        | b  | 2  |
+        | a  | 3  |
+        | c  | 4  |
+        |    | 5  |
+        +----+----+
+        ");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_stream_format() -> Result<()> {
+        let ctx = SessionContext::new();
+        let tmp_dir = tempfile::TempDir::new()?;
+        let path = tmp_dir.path().join("example.arrows");
+        ctx.sql(&format!(
+            "COPY (SELECT column1 AS id, arrow_cast(column2, 'Dictionary(Int32, Utf8)') AS name \
+            FROM (VALUES (1, 'a'), (2, 'b'), (3, 'a'))) \
+            TO '{}' STORED AS ARROW OPTIONS ('ipc_format' 'stream')",
+            path.display()
+        ))
+        .await?
+        .collect()
+        .await?;
+
+        let reader = StreamReader::try_new(std::fs::File::open(&path)?, None)?;
+        let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+        let batch = arrow::compute::concat_batches(&batches[0].schema(), &batches)?;
+        assert_eq!(
+            batch.schema().field(1).data_type(),
+            &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+        );
+        assert_eq!(
+            batch.column(0).as_ref(),
+            &Int64Array::from(vec![1, 2, 3]) as &dyn arrow::array::Array
+        );
+        assert_eq!(
+            cast(batch.column(1), &DataType::Utf8)?.as_ref(),
+            &StringArray::from(vec!["a", "b", "a"]) as &dyn arrow::array::Array
+        );
+
+        let err = ctx
+            .sql(&format!(
+                "COPY (SELECT 1) TO '{}' STORED AS ARROW OPTIONS ('ipc_format' 'feather')",
+                path.display()
+            ))
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "Invalid Arrow IPC format \"feather\", expected \"file\" or \"stream\""
+        );
+
+        Ok(())
+    }
 }
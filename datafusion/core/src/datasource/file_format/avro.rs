@@ -23,23 +23,55 @@ pub use datafusion_datasource_avro::file_format::*;
 mod tests {
     use std::sync::Arc;
 
+    use std::path::Path;
+
+    use crate::datasource::listing::ListingTableUrl;
     use crate::{
-        datasource::file_format::test_util::scan_format, prelude::SessionContext,
+        datasource::{file_format::test_util::scan_format, listing::ListingOptions},
+        prelude::SessionContext,
+        test::object_store::local_unpartitioned_file,
+    };
+    use apache_avro::{types::Value, Decimal};
+    use arrow::array::{
+        as_string_array, Array, ArrayRef, FixedSizeBinaryArray, Int32Array, RecordBatch,
     };
-    use arrow::array::{as_string_array, Array};
+    use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
     use datafusion_catalog::Session;
     use datafusion_common::test_util::batches_to_string;
     use datafusion_common::{
+        assert_contains,
         cast::{
             as_binary_array, as_boolean_array, as_float32_array, as_float64_array,
             as_int32_array, as_timestamp_microsecond_array,
+            as_timestamp_millisecond_array,
         },
+        stats::Precision,
         test_util, Result,
     };
 
-    use datafusion_datasource_avro::AvroFormat;
+    use datafusion_datasource::file_format::FileFormat;
+    use datafusion_datasource::file_groups::FileGroup;
+    use datafusion_datasource::file_sink_config::{FileOutputMode, FileSinkConfig};
+    use datafusion_datasource::sink::DataSink;
+    use datafusion_datasource_avro::avro_to_arrow::TimestampPrecision;
+    use datafusion_datasource_avro::source::AvroMetricsLabels;
+    use datafusion_datasource_avro::{
+        AvroFormat, AvroSink, DecodePool, SchemaMergeStrategy,
+    };
     use datafusion_execution::config::SessionConfig;
-    use datafusion_physical_plan::{collect, ExecutionPlan};
+    use datafusion_execution::object_store::ObjectStoreUrl;
+    use datafusion_expr::dml::InsertOp;
+    use datafusion_expr::Operator;
+    use datafusion_physical_expr::expressions::{binary, col, lit};
+    use datafusion_physical_optimizer::filter_pushdown::FilterPushdown;
+    use datafusion_physical_optimizer::PhysicalOptimizerRule;
+    use datafusion_physical_plan::filter::FilterExec;
+    use datafusion_physical_plan::metrics::Label;
+    use datafusion_physical_plan::projection::ProjectionExec;
+    use datafusion_physical_plan::stream::RecordBatchStreamAdapter;
+    use datafusion_physical_plan::{
+        collect, displayable, ExecutionPlan, SendableRecordBatchStream,
+    };
     use futures::StreamExt;
     use insta::assert_snapshot;
 
@@ -373,6 +405,1176 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn read_long_as_timestamp() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+        let task_ctx = state.task_ctx();
+        let testdata = test_util::arrow_test_data();
+        let format = AvroFormat::default()
+            .with_timestamp_columns(
+                vec!["bigint_col".to_string()],
+                TimestampPrecision::Millisecond,
+            )
+            .with_timestamp_timezone("UTC");
+        let projection = Some(vec![5]);
+        let exec = scan_format(
+            &state,
+            &format,
+            None,
+            &format!("{testdata}/avro"),
+            "alltypes_plain.avro",
+            projection,
+            None,
+        )
+        .await?;
+
+        let expected = DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()));
+        assert_eq!(exec.schema().field(0).data_type(), &expected);
+
+        let batches = collect(exec, task_ctx).await?;
+        assert_eq!(batches.len(), 1);
+        let values = as_timestamp_millisecond_array(batches[0].column(0))?;
+        assert_eq!(values.data_type(), &expected);
+        assert_eq!(values.values().as_ref(), [0, 10, 0, 10, 0, 10, 0, 10]);
+
+        // Only long columns can be read as timestamps
+        let format = AvroFormat::default().with_timestamp_columns(
+            vec!["double_col".to_string()],
+            TimestampPrecision::Millisecond,
+        );
+        let err = scan_format(
+            &state,
+            &format,
+            None,
+            &format!("{testdata}/avro"),
+            "alltypes_plain.avro",
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "Avro timestamp column double_col must be a long, found Float64"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn merge_decimal_columns_with_different_scales() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        // 123.45 and -1.00
+        write_decimal_file(&tmp_dir.path().join("a.avro"), 10, 2, &[12345, -100])?;
+        // 9876.5432 and 0.0001
+        write_decimal_file(&tmp_dir.path().join("b.avro"), 12, 4, &[98765432, 1])?;
+        let table_path = format!("{}/", tmp_dir.path().to_str().unwrap());
+
+        // the default strategy requires identical column types
+        let ctx = SessionContext::new();
+        let options = ListingOptions::new(Arc::new(AvroFormat::default()));
+        let err = ctx
+            .register_listing_table("t", &table_path, options, None, None)
+            .await
+            .unwrap_err();
+        assert_contains!(err.to_string(), "Fail to merge schema field 'amount'");
+
+        let format = AvroFormat::default()
+            .with_schema_merge_strategy(SchemaMergeStrategy::Widening);
+        let options = ListingOptions::new(Arc::new(format));
+        ctx.register_listing_table("t", &table_path, options, None, None)
+            .await?;
+
+        let df = ctx.sql("SELECT amount FROM t ORDER BY amount").await?;
+        assert_eq!(
+            df.schema().field(0).data_type(),
+            &DataType::Decimal128(12, 4)
+        );
+        let batches = df.collect().await?;
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +-----------+
+        | amount    |
+        +-----------+
+        | -1.0000   |
+        | 0.0001    |
+        | 123.4500  |
+        | 9876.5432 |
+        +-----------+
+        ");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_in_table_column_order() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        // The fields of the file, and of its nested record, are in the
+        // reverse order of the table schema
+        let schema = r#"{
+          "type": "record",
+          "name": "r1",
+          "fields": [
+            {"name": "point", "type": {
+              "type": "record",
+              "name": "r2",
+              "fields": [
+                {"name": "label", "type": "string"},
+                {"name": "n", "type": "long"}
+              ]
+            }},
+            {"name": "name", "type": "string"},
+            {"name": "id", "type": "long"}
+          ]
+        }"#;
+        let records = (0..2).map(|id| {
+            let point = Value::Record(vec![
+                ("label".to_string(), Value::String(format!("p{id}"))),
+                ("n".to_string(), Value::Long(id * 10)),
+            ]);
+            Value::Record(vec![
+                ("point".to_string(), point),
+                ("name".to_string(), Value::String(format!("name_{id}"))),
+                ("id".to_string(), Value::Long(id)),
+            ])
+        });
+        write_avro_file(&tmp_dir.path().join("data.avro"), schema, records)?;
+
+        let table_schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, false),
+            Field::new_struct(
+                "point",
+                vec![
+                    Field::new("n", DataType::Int64, false),
+                    Field::new("label", DataType::Utf8, false),
+                ],
+                false,
+            ),
+        ]));
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+        let task_ctx = state.task_ctx();
+        let store_root = tmp_dir.path().to_str().unwrap();
+
+        let exec = scan_format(
+            &state,
+            &AvroFormat::default(),
+            Some(Arc::clone(&table_schema)),
+            store_root,
+            "data.avro",
+            None,
+            None,
+        )
+        .await?;
+        let batches = collect(exec, Arc::clone(&task_ctx)).await?;
+        assert_eq!(batches[0].schema(), table_schema);
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +----+--------+--------------------+
+        | id | name   | point              |
+        +----+--------+--------------------+
+        | 0  | name_0 | {n: 0, label: p0}  |
+        | 1  | name_1 | {n: 10, label: p1} |
+        +----+--------+--------------------+
+        ");
+
+        // Projected columns are in projection order
+        let exec = scan_format(
+            &state,
+            &AvroFormat::default(),
+            Some(table_schema),
+            store_root,
+            "data.avro",
+            Some(vec![2, 0]),
+            None,
+        )
+        .await?;
+        let batches = collect(exec, task_ctx).await?;
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +--------------------+----+
+        | point              | id |
+        +--------------------+----+
+        | {n: 0, label: p0}  | 0  |
+        | {n: 10, label: p1} | 1  |
+        +--------------------+----+
+        ");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_with_case_insensitive_field_resolution() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        let schema = r#"{
+          "type": "record",
+          "name": "r1",
+          "fields": [
+            {"name": "userId", "type": "long"},
+            {"name": "Name", "type": "string"}
+          ]
+        }"#;
+        let records = (0..3).map(|id| {
+            Value::Record(vec![
+                ("userId".to_string(), Value::Long(id)),
+                ("Name".to_string(), Value::String(format!("name_{id}"))),
+            ])
+        });
+        write_avro_file(&tmp_dir.path().join("data.avro"), schema, records)?;
+
+        // The identifiers of the query are normalized to lowercase
+        let table_schema = Arc::new(Schema::new(vec![
+            Field::new("userid", DataType::Int64, true),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        let table_path = tmp_dir.path().to_str().unwrap();
+        let sql = "SELECT name, userid FROM t WHERE userid > 0 ORDER BY userid";
+
+        let ctx = SessionContext::new();
+        let format = AvroFormat::default().with_case_insensitive_field_resolution(true);
+        let options = ListingOptions::new(Arc::new(format));
+        ctx.register_listing_table(
+            "t",
+            table_path,
+            options,
+            Some(Arc::clone(&table_schema)),
+            None,
+        )
+        .await?;
+        let batches = ctx.sql(sql).await?.collect().await?;
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +--------+--------+
+        | name   | userid |
+        +--------+--------+
+        | name_1 | 1      |
+        | name_2 | 2      |
+        +--------+--------+
+        ");
+
+        // Without the option, the columns are missing from the file
+        let ctx = SessionContext::new();
+        let options = ListingOptions::new(Arc::new(AvroFormat::default()));
+        ctx.register_listing_table("t", table_path, options, Some(table_schema), None)
+            .await?;
+        let batches = ctx.sql(sql).await?.collect().await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+
+        // Files with both casings are merged into one column when inferring
+        // the schema
+        let schema = r#"{
+          "type": "record",
+          "name": "r1",
+          "fields": [
+            {"name": "userid", "type": "long"},
+            {"name": "name", "type": "string"}
+          ]
+        }"#;
+        let record = Value::Record(vec![
+            ("userid".to_string(), Value::Long(3)),
+            ("name".to_string(), Value::String("name_3".to_string())),
+        ]);
+        write_avro_file(&tmp_dir.path().join("lowercase.avro"), schema, [record])?;
+
+        let ctx = SessionContext::new();
+        let state = ctx.state();
+        let store = ctx
+            .runtime_env()
+            .object_store(ObjectStoreUrl::local_filesystem())?;
+        let format = AvroFormat::default().with_case_insensitive_field_resolution(true);
+        let files = [
+            local_unpartitioned_file(tmp_dir.path().join("data.avro")),
+            local_unpartitioned_file(tmp_dir.path().join("lowercase.avro")),
+        ];
+        let schema = format.infer_schema(&state, &store, &files).await?;
+        assert_eq!(
+            schema.fields().iter().map(|f| f.name()).collect::<Vec<_>>(),
+            ["userId", "Name"]
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn read_gzip_wrapped_file_by_magic() -> Result<()> {
+        use datafusion_datasource::file_compression_type::CompressionDetection;
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        // A gzip-wrapped Avro file mislabeled with the plain `.avro` extension
+        let tmp_dir = tempfile::TempDir::new()?;
+        let testdata = test_util::arrow_test_data();
+        let avro = std::fs::read(format!("{testdata}/avro/alltypes_plain.avro"))?;
+        let mut encoder = GzEncoder::new(
+            std::fs::File::create(tmp_dir.path().join("data.avro"))?,
+            Compression::default(),
+        );
+        encoder.write_all(&avro)?;
+        encoder.finish()?;
+
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+        let task_ctx = state.task_ctx();
+        let store_root = tmp_dir.path().to_str().unwrap();
+
+        let format = AvroFormat::default()
+            .with_compression_detection(CompressionDetection::ByMagic);
+        let exec =
+            scan_format(&state, &format, None, store_root, "data.avro", None, None)
+                .await?;
+        let batches = collect(exec, Arc::clone(&task_ctx)).await?;
+        let expected = collect(
+            get_exec(&state, "alltypes_plain.avro", None, None).await?,
+            task_ctx,
+        )
+        .await?;
+        assert_eq!(batches_to_string(&batches), batches_to_string(&expected));
+
+        // By default the compression is taken from the extension
+        let err = scan_format(
+            &state,
+            &AvroFormat::default(),
+            None,
+            store_root,
+            "data.avro",
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_contains!(err.to_string(), "Avro error");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_headerless_file_with_sidecar_schema() -> Result<()> {
+        use datafusion_datasource_avro::DEFAULT_SIDECAR_SCHEMA_FILE;
+
+        let schema_json = r#"{
+          "type": "record",
+          "name": "r1",
+          "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "name", "type": "string"}
+          ]
+        }"#;
+        let schema = apache_avro::Schema::parse_str(schema_json).unwrap();
+        let tmp_dir = tempfile::TempDir::new()?;
+        std::fs::write(
+            tmp_dir.path().join(DEFAULT_SIDECAR_SCHEMA_FILE),
+            schema_json,
+        )?;
+        let mut data = vec![];
+        for id in 0..3 {
+            let record = Value::Record(vec![
+                ("id".to_string(), Value::Long(id)),
+                ("name".to_string(), Value::String(format!("name_{id}"))),
+            ]);
+            data.extend(apache_avro::to_avro_datum(&schema, record).unwrap());
+        }
+        std::fs::write(tmp_dir.path().join("data.avro"), data)?;
+
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+        let task_ctx = state.task_ctx();
+        let store_root = tmp_dir.path().to_str().unwrap();
+
+        let format = AvroFormat::default()
+            .with_sidecar_schema_file(Some(DEFAULT_SIDECAR_SCHEMA_FILE.to_string()));
+        let exec =
+            scan_format(&state, &format, None, store_root, "data.avro", None, None)
+                .await?;
+        let batches = collect(exec, task_ctx).await?;
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +----+--------+
+        | id | name   |
+        +----+--------+
+        | 0  | name_0 |
+        | 1  | name_1 |
+        | 2  | name_2 |
+        +----+--------+
+        ");
+
+        // Without the sidecar file, the file is expected to have a header
+        let err = scan_format(
+            &state,
+            &AvroFormat::default(),
+            None,
+            store_root,
+            "data.avro",
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_contains!(err.to_string(), "Avro error");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn list_files_with_custom_extension() -> Result<()> {
+        use datafusion_common::GetExt;
+        use datafusion_datasource::file_format::FileFormatFactory;
+        use datafusion_datasource_avro::AvroFormatFactory;
+
+        let schema = r#"{
+          "type": "record",
+          "name": "r1",
+          "fields": [{"name": "id", "type": "long"}]
+        }"#;
+        let tmp_dir = tempfile::TempDir::new()?;
+        for (file, num_rows) in [("data.avro.bin", 3), ("other.avro", 2)] {
+            let records = (0..num_rows)
+                .map(|id| Value::Record(vec![("id".to_string(), Value::Long(id))]));
+            write_avro_file(&tmp_dir.path().join(file), schema, records)?;
+        }
+
+        assert_eq!(AvroFormatFactory::new().get_ext(), "avro");
+        let factory = AvroFormatFactory::new().with_file_extension(".avro.bin");
+        assert_eq!(factory.get_ext(), "avro.bin");
+        let format = factory.default();
+        assert_eq!(format.get_ext(), "avro.bin");
+
+        // Only the files with the custom extension are listed
+        let ctx = SessionContext::new();
+        let options = ListingOptions::new(format);
+        assert_eq!(options.file_extension, "avro.bin");
+        let table_path = tmp_dir.path().to_str().unwrap();
+        ctx.register_listing_table("t", table_path, options, None, None)
+            .await?;
+        let batches = ctx.sql("SELECT count(*) FROM t").await?.collect().await?;
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +----------+
+        | count(*) |
+        +----------+
+        | 3        |
+        +----------+
+        ");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_uuid_as_extension_type() -> Result<()> {
+        use arrow_schema::extension::EXTENSION_TYPE_NAME_KEY;
+        use datafusion_datasource_avro::avro_to_arrow::UUID_EXTENSION_NAME;
+
+        let schema = r#"{
+          "type": "record",
+          "name": "r1",
+          "fields": [
+            {"name": "id", "type": {"type": "string", "logicalType": "uuid"}}
+          ]
+        }"#;
+        let uuid = uuid::Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
+        let record = Value::Record(vec![("id".to_string(), Value::Uuid(uuid))]);
+        let tmp_dir = tempfile::TempDir::new()?;
+        write_avro_file(&tmp_dir.path().join("data.avro"), schema, [record])?;
+
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+        let store_root = tmp_dir.path().to_str().unwrap();
+        let exec = scan_format(
+            &state,
+            &AvroFormat::default(),
+            None,
+            store_root,
+            "data.avro",
+            None,
+            None,
+        )
+        .await?;
+        let field = exec.schema().field(0).clone();
+        assert_eq!(field.data_type(), &DataType::FixedSizeBinary(16));
+        assert_eq!(
+            field.metadata().get(EXTENSION_TYPE_NAME_KEY),
+            Some(&UUID_EXTENSION_NAME.to_string())
+        );
+        let batches = collect(exec, state.task_ctx()).await?;
+        let values = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<FixedSizeBinaryArray>()
+            .unwrap();
+        assert_eq!(values.value(0), uuid.as_bytes());
+
+        let format = AvroFormat::default().with_extension_types(false);
+        let exec =
+            scan_format(&state, &format, None, store_root, "data.avro", None, None)
+                .await?;
+        assert!(exec.schema().field(0).metadata().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn filter_pushed_down_into_scan() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        write_filter_file(&tmp_dir.path().join("data.avro"), 1000)?;
+        let config = SessionConfig::new().with_batch_size(64);
+        let session_ctx = SessionContext::new_with_config(config);
+        let state = session_ctx.state();
+        let task_ctx = state.task_ctx();
+
+        let exec = scan_format(
+            &state,
+            &AvroFormat::default(),
+            None,
+            tmp_dir.path().to_str().unwrap(),
+            "data.avro",
+            None,
+            None,
+        )
+        .await?;
+        let schema = exec.schema();
+        // id % 3 = 0 AND score > 250.0, where score is null for every 7th record
+        let predicate = binary(
+            binary(
+                binary(col("id", &schema)?, Operator::Modulo, lit(3i64), &schema)?,
+                Operator::Eq,
+                lit(0i64),
+                &schema,
+            )?,
+            Operator::And,
+            binary(col("score", &schema)?, Operator::Gt, lit(250.0), &schema)?,
+            &schema,
+        )?;
+        let filter = Arc::new(FilterExec::try_new(predicate, exec)?);
+        let plan: Arc<dyn ExecutionPlan> = Arc::new(ProjectionExec::try_new(
+            vec![(col("name", &filter.schema())?, "name".to_string())],
+            filter,
+        )?);
+        let expected = collect(Arc::clone(&plan), Arc::clone(&task_ctx)).await?;
+
+        let optimized = FilterPushdown::new().optimize(plan, state.config_options())?;
+        let displayed = displayable(optimized.as_ref()).indent(true).to_string();
+        assert!(!displayed.contains("FilterExec"), "{displayed}");
+        assert_contains!(displayed, "predicate=id@0 % 3 = 0 AND score@2 > 250");
+        let actual = collect(optimized, task_ctx).await?;
+
+        assert_eq!(batches_to_string(&actual), batches_to_string(&expected));
+        assert_eq!(
+            actual.iter().map(|b| b.num_rows()).sum::<usize>(),
+            (0..1000)
+                .filter(|i| i % 3 == 0 && i % 7 != 0 && *i as f64 > 250.0)
+                .count()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn max_in_flight_batches_bounds_decoder() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        write_filter_file(&tmp_dir.path().join("data.avro"), 100)?;
+        let config = SessionConfig::new().with_batch_size(5);
+        let session_ctx = SessionContext::new_with_config(config);
+        let state = session_ctx.state();
+
+        let max_in_flight_batches = 2;
+        let format =
+            AvroFormat::default().with_max_in_flight_batches(Some(max_in_flight_batches));
+        let exec = scan_format(
+            &state,
+            &format,
+            None,
+            tmp_dir.path().to_str().unwrap(),
+            "data.avro",
+            None,
+            None,
+        )
+        .await?;
+        let batches_decoded = || {
+            exec.metrics()
+                .unwrap()
+                .sum_by_name("batches_decoded")
+                .map(|m| m.as_usize())
+                .unwrap_or_default()
+        };
+
+        let mut stream = exec.execute(0, state.task_ctx())?;
+        let mut consumed = 0;
+        let mut rows = 0;
+        while let Some(batch) = stream.next().await {
+            rows += batch?.num_rows();
+            consumed += 1;
+            // Give the decoder time to race ahead of this slow consumer
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            // The decoder may hold one more batch, waiting for room in the buffer
+            assert!(
+                batches_decoded() <= consumed + max_in_flight_batches + 1,
+                "decoded {} batches with {consumed} consumed",
+                batches_decoded()
+            );
+        }
+        assert_eq!(rows, 100);
+        assert_eq!(consumed, 20);
+        assert_eq!(batches_decoded(), 20);
+        Ok(())
+    }
+
+    /// Checks that the empty file `empty.avro` of `dir`, next to the file
+    /// `a.avro` of 100 rows, doesn't contribute any row
+    async fn check_empty_file(dir: &Path) -> Result<()> {
+        let ctx = SessionContext::new();
+        let state = ctx.state();
+        let store = ctx
+            .runtime_env()
+            .object_store(ObjectStoreUrl::local_filesystem())?;
+        let format = AvroFormat::default();
+        let files = [
+            local_unpartitioned_file(dir.join("a.avro")),
+            local_unpartitioned_file(dir.join("empty.avro")),
+        ];
+        let schema = format.infer_schema(&state, &store, &files).await?;
+        assert_eq!(
+            schema.fields().iter().map(|f| f.name()).collect::<Vec<_>>(),
+            ["id", "name", "score"]
+        );
+
+        let stats = format
+            .infer_stats(&state, &store, Arc::clone(&schema), &files[1])
+            .await?;
+        assert_eq!(stats.num_rows, Precision::Exact(0));
+        let stats = format
+            .infer_stats(&state, &store, Arc::clone(&schema), &files[0])
+            .await?;
+        assert_eq!(stats.num_rows, Precision::Absent);
+
+        ctx.register_avro(
+            "t",
+            &format!("{}/", dir.to_str().unwrap()),
+            Default::default(),
+        )
+        .await?;
+        assert_eq!(ctx.table("t").await?.count().await?, 100);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn header_only_file() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        write_filter_file(&tmp_dir.path().join("a.avro"), 100)?;
+        write_filter_file(&tmp_dir.path().join("empty.avro"), 0)?;
+        check_empty_file(tmp_dir.path()).await
+    }
+
+    #[tokio::test]
+    async fn zero_byte_file() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        write_filter_file(&tmp_dir.path().join("a.avro"), 100)?;
+        std::fs::File::create(tmp_dir.path().join("empty.avro"))?;
+        check_empty_file(tmp_dir.path()).await
+    }
+
+    #[tokio::test]
+    async fn metrics_labels_from_session_config() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        write_filter_file(&tmp_dir.path().join("data.avro"), 100)?;
+        let config =
+            SessionConfig::new().with_extension(Arc::new(AvroMetricsLabels(vec![
+                Label::new("tenant", "acme"),
+                Label::new("query", "q1"),
+            ])));
+        let session_ctx = SessionContext::new_with_config(config);
+        let state = session_ctx.state();
+
+        let exec = scan_format(
+            &state,
+            &AvroFormat::default(),
+            None,
+            tmp_dir.path().to_str().unwrap(),
+            "data.avro",
+            None,
+            None,
+        )
+        .await?;
+        collect(Arc::clone(&exec), state.task_ctx()).await?;
+
+        let metrics = exec.metrics().unwrap();
+        let batches_decoded = metrics
+            .iter()
+            .find(|metric| metric.value().name() == "batches_decoded")
+            .unwrap();
+        assert_eq!(
+            batches_decoded.labels(),
+            [Label::new("tenant", "acme"), Label::new("query", "q1")]
+        );
+        assert_eq!(batches_decoded.partition(), Some(0));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn decode_pool_interleaves_files() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        write_filter_file(&tmp_dir.path().join("data.avro"), 100)?;
+        let config = SessionConfig::new().with_batch_size(5);
+        let session_ctx = SessionContext::new_with_config(config);
+        let state = session_ctx.state();
+
+        let decode_pool = Arc::new(DecodePool::try_new(1)?);
+        let format = AvroFormat::default().with_decode_pool(Some(decode_pool));
+        let exec = scan_format(
+            &state,
+            &format,
+            None,
+            tmp_dir.path().to_str().unwrap(),
+            "data.avro",
+            None,
+            None,
+        )
+        .await?;
+        async fn count_rows(mut stream: SendableRecordBatchStream) -> Result<usize> {
+            let mut rows = 0;
+            while let Some(batch) = stream.next().await {
+                rows += batch?.num_rows();
+            }
+            Ok(rows)
+        }
+
+        // The first file waits for its consumer without holding the only
+        // thread of the pool, which decodes the second file meanwhile
+        let mut first = exec.execute(0, state.task_ctx())?;
+        assert_eq!(first.next().await.unwrap()?.num_rows(), 5);
+        let second = exec.execute(0, state.task_ctx())?;
+        assert_eq!(count_rows(second).await?, 100);
+        assert_eq!(count_rows(first).await?, 95);
+
+        // Dropping a stream in the middle of its file frees the pool as well
+        let mut third = exec.execute(0, state.task_ctx())?;
+        third.next().await.unwrap()?;
+        drop(third);
+        let fourth = exec.execute(0, state.task_ctx())?;
+        assert_eq!(count_rows(fourth).await?, 100);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn output_ordering_from_schema_order() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        let schema = r#"{
+          "type": "record",
+          "name": "r1",
+          "fields": [
+            {"name": "day", "type": "int"},
+            {"name": "comment", "type": "string", "order": "ignore"},
+            {"name": "score", "type": "double", "order": "descending"},
+            {"name": "kind", "type": {"type": "enum", "name": "e1", "symbols": ["b", "a"]}},
+            {"name": "id", "type": "long"}
+          ]
+        }"#;
+        let records = [(1, 2.0, "b"), (1, 1.0, "a"), (2, 5.0, "b")]
+            .into_iter()
+            .map(|(day, score, kind)| {
+                Value::Record(vec![
+                    ("day".to_string(), Value::Int(day)),
+                    ("comment".to_string(), Value::String(format!("day {day}"))),
+                    ("score".to_string(), Value::Double(score)),
+                    (
+                        "kind".to_string(),
+                        Value::Enum(if kind == "b" { 0 } else { 1 }, kind.to_string()),
+                    ),
+                    ("id".to_string(), Value::Long(0)),
+                ])
+            })
+            .collect::<Vec<_>>();
+        write_with_schema_json(&tmp_dir.path().join("data.avro"), schema, &records)?;
+        let table_path = format!("{}/", tmp_dir.path().to_str().unwrap());
+
+        let ctx = SessionContext::new();
+        let format = AvroFormat::default().with_sorted_by_schema_order(true);
+        let options = ListingOptions::new(Arc::new(format));
+        ctx.register_listing_table("t", &table_path, options, None, None)
+            .await?;
+
+        // The ordering skips the ignored column and ends before the enum column
+        let df = ctx
+            .sql("SELECT day, score FROM t ORDER BY day NULLS FIRST, score DESC NULLS LAST")
+            .await?;
+        let plan = df.clone().create_physical_plan().await?;
+        let displayed = displayable(plan.as_ref()).indent(true).to_string();
+        assert!(!displayed.contains("SortExec"), "{displayed}");
+        assert_contains!(
+            displayed,
+            "output_ordering=[day@0 ASC, score@1 DESC NULLS LAST]"
+        );
+        let batches = df.collect().await?;
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +-----+-------+
+        | day | score |
+        +-----+-------+
+        | 1   | 2.0   |
+        | 1   | 1.0   |
+        | 2   | 5.0   |
+        +-----+-------+
+        ");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn preflight_validation_fails_before_any_batch() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        write_filter_file(&tmp_dir.path().join("a.avro"), 100)?;
+        // `id` is a record, which can not be cast to the `Int64` table column
+        let schema = r#"{
+          "type": "record",
+          "name": "r1",
+          "fields": [
+            {"name": "id", "type": {
+              "type": "record", "name": "r2", "fields": [{"name": "x", "type": "long"}]
+            }},
+            {"name": "name", "type": "string"}
+          ]
+        }"#;
+        let records = [Value::Record(vec![
+            (
+                "id".to_string(),
+                Value::Record(vec![("x".to_string(), Value::Long(0))]),
+            ),
+            ("name".to_string(), Value::String("name_0".to_string())),
+        ])];
+        write_with_schema_json(&tmp_dir.path().join("b.avro"), schema, &records)?;
+        let table_path = format!("{}/", tmp_dir.path().to_str().unwrap());
+
+        let config = SessionConfig::new().with_target_partitions(1);
+        let ctx = SessionContext::new_with_config(config);
+        let table_schema = AvroFormat::default()
+            .infer_schema(
+                &ctx.state(),
+                &ctx.runtime_env()
+                    .object_store(ObjectStoreUrl::local_filesystem())?,
+                &[local_unpartitioned_file(tmp_dir.path().join("a.avro"))],
+            )
+            .await?;
+
+        // Without validation, the scan produces the batches of the first
+        // file before failing on the second one
+        let options = ListingOptions::new(Arc::new(AvroFormat::default()));
+        ctx.register_listing_table(
+            "t",
+            &table_path,
+            options,
+            Some(Arc::clone(&table_schema)),
+            None,
+        )
+        .await?;
+        let mut stream = ctx.sql("SELECT id FROM t").await?.execute_stream().await?;
+        assert_eq!(stream.next().await.unwrap()?.num_rows(), 100);
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert_contains!(err.to_string(), "Cannot cast file schema field id");
+
+        // With validation, planning the scan fails with the incompatible file
+        let format = AvroFormat::default().with_preflight_validation(true);
+        let options = ListingOptions::new(Arc::new(format));
+        ctx.register_listing_table("v", &table_path, options, Some(table_schema), None)
+            .await?;
+        let err = ctx
+            .sql("SELECT id FROM v")
+            .await?
+            .create_physical_plan()
+            .await
+            .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "b.avro is incompatible with the table schema"
+        );
+        assert_contains!(err.to_string(), "Cannot cast file schema field id");
+
+        // Files that are not read by the scan are not validated
+        let batches = ctx.sql("SELECT name FROM v").await?.collect().await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 101);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn write_with_expected_schema() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        let table_path = format!("{}/", tmp_dir.path().to_str().unwrap());
+        let table_schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        // `id` is promoted from int to long and `country` takes its default
+        let expected_schema = r#"{
+          "type": "record",
+          "name": "user",
+          "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "name", "type": ["null", "string"]},
+            {"name": "country", "type": "string", "default": "unknown"}
+          ]
+        }"#;
+
+        let ctx = SessionContext::new();
+        let format = AvroFormat::default().with_expected_write_schema(expected_schema);
+        let options = ListingOptions::new(Arc::new(format));
+        ctx.register_listing_table("t", &table_path, options, Some(table_schema), None)
+            .await?;
+        ctx.sql("INSERT INTO t VALUES (1, 'a'), (2, NULL)")
+            .await?
+            .collect()
+            .await?;
+
+        let options = ListingOptions::new(Arc::new(AvroFormat::default()));
+        ctx.register_listing_table("r", &table_path, options, None, None)
+            .await?;
+        let batches = ctx
+            .sql("SELECT * FROM r ORDER BY id")
+            .await?
+            .collect()
+            .await?;
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +----+------+---------+
+        | id | name | country |
+        +----+------+---------+
+        | 1  | a    | unknown |
+        | 2  |      | unknown |
+        +----+------+---------+
+        ");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn write_incompatible_with_expected_schema() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        let table_path = format!("{}/", tmp_dir.path().to_str().unwrap());
+        let table_schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ]));
+        let expected_schema = r#"{
+          "type": "record",
+          "name": "user",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "name", "type": "string"},
+            {"name": "country", "type": "string"}
+          ]
+        }"#;
+
+        let ctx = SessionContext::new();
+        let format = AvroFormat::default().with_expected_write_schema(expected_schema);
+        let options = ListingOptions::new(Arc::new(format));
+        ctx.register_listing_table("t", &table_path, options, Some(table_schema), None)
+            .await?;
+        let err = ctx
+            .sql("INSERT INTO t VALUES (1, 'a', 1.0)")
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert_snapshot!(err.strip_backtrace(), @r"
+        Error during planning: The data to write is incompatible with the expected Avro schema:
+          id: found int, expected string
+          name: found nullable string, expected non nullable string
+          country: missing, expected string without a default
+          score: found nullable double, not in the expected schema
+        ");
+        // The error is raised before any file is written
+        assert_eq!(std::fs::read_dir(tmp_dir.path())?.count(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn write_validates_row_count() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        let table_path = format!("{}/", tmp_dir.path().to_str().unwrap());
+        let batch = RecordBatch::try_from_iter([(
+            "id",
+            Arc::new(Int32Array::from_iter_values(0..1000)) as ArrayRef,
+        )])?;
+
+        let ctx = SessionContext::new();
+        ctx.register_batch("src", batch.clone())?;
+        let format = AvroFormat::default().with_validate_written_row_count(true);
+        let options = ListingOptions::new(Arc::new(format));
+        ctx.register_listing_table("t", &table_path, options, Some(batch.schema()), None)
+            .await?;
+        let batches = ctx
+            .sql("INSERT INTO t SELECT * FROM src")
+            .await?
+            .collect()
+            .await?;
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +-------+
+        | count |
+        +-------+
+        | 1000  |
+        +-------+
+        ");
+
+        // The sink fails if it writes fewer records than expected
+        let config = FileSinkConfig {
+            original_url: table_path.clone(),
+            object_store_url: ObjectStoreUrl::local_filesystem(),
+            file_group: FileGroup::default(),
+            table_paths: vec![ListingTableUrl::parse(&table_path)?],
+            output_schema: batch.schema(),
+            table_partition_cols: vec![],
+            insert_op: InsertOp::Append,
+            keep_partition_by_columns: false,
+            file_extension: "avro".into(),
+            file_output_mode: FileOutputMode::Automatic,
+        };
+        let sink = AvroSink::try_new(config, None)?.with_expected_row_count(Some(1001));
+        let stream = Box::pin(RecordBatchStreamAdapter::new(
+            batch.schema(),
+            futures::stream::iter(vec![Ok(batch)]),
+        ));
+        let err = DataSink::write_all(&sink, stream, &ctx.task_ctx())
+            .await
+            .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            format!(
+                "Wrote 1000 Avro records to {table_path} but the input has 1001 rows"
+            )
+        );
+        Ok(())
+    }
+
+    /// Writes `records` to an Avro file whose header holds `schema` verbatim,
+    /// as [`apache_avro::Writer`] drops the `order` attributes of the fields
+    fn write_with_schema_json(
+        path: &Path,
+        schema: &str,
+        records: &[Value],
+    ) -> Result<()> {
+        use apache_avro::{to_avro_datum, Schema};
+
+        let parsed = Schema::parse_str(schema).unwrap();
+        let metadata =
+            Schema::parse_str(r#"{"type": "map", "values": "bytes"}"#).unwrap();
+        let sync_marker = [7u8; 16];
+        let mut data = vec![];
+        for record in records {
+            data.extend(to_avro_datum(&parsed, record.clone()).unwrap());
+        }
+
+        let mut file = b"Obj\x01".to_vec();
+        file.extend(
+            to_avro_datum(
+                &metadata,
+                Value::Map(
+                    [(
+                        "avro.schema".to_string(),
+                        Value::Bytes(schema.as_bytes().to_vec()),
+                    )]
+                    .into(),
+                ),
+            )
+            .unwrap(),
+        );
+        file.extend(sync_marker);
+        file.extend(to_avro_datum(&Schema::Long, records.len() as i64).unwrap());
+        file.extend(to_avro_datum(&Schema::Long, data.len() as i64).unwrap());
+        file.extend(data);
+        file.extend(sync_marker);
+        std::fs::write(path, file)?;
+        Ok(())
+    }
+
+    /// Writes `records` to the Avro file `path` with the schema `schema`
+    fn write_avro_file(
+        path: &Path,
+        schema: &str,
+        records: impl IntoIterator<Item = Value>,
+    ) -> Result<()> {
+        let schema = apache_avro::Schema::parse_str(schema).unwrap();
+        let mut writer = apache_avro::Writer::new(&schema, std::fs::File::create(path)?);
+        for record in records {
+            writer.append(record).unwrap();
+        }
+        writer.flush().unwrap();
+        Ok(())
+    }
+
+    fn write_filter_file(path: &Path, num_rows: i64) -> Result<()> {
+        let schema = r#"{
+          "type": "record",
+          "name": "r1",
+          "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "name", "type": "string"},
+            {"name": "score", "type": ["null", "double"]}
+          ]
+        }"#;
+        let records = (0..num_rows).map(|id| {
+            let score = match id % 7 {
+                0 => Value::Union(0, Box::new(Value::Null)),
+                _ => Value::Union(1, Box::new(Value::Double(id as f64))),
+            };
+            Value::Record(vec![
+                ("id".to_string(), Value::Long(id)),
+                ("name".to_string(), Value::String(format!("name_{id}"))),
+                ("score".to_string(), score),
+            ])
+        });
+        write_avro_file(path, schema, records)
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn scan_cost_estimate_of_row_format() {
+        use crate::datasource::file_format::parquet::ParquetFormat;
+        use datafusion_common::stats::Precision;
+        use datafusion_common::{ColumnStatistics, Statistics};
+
+        let stats = Statistics {
+            num_rows: Precision::Exact(100),
+            total_byte_size: Precision::Exact(1000),
+            column_statistics: vec![ColumnStatistics::new_unknown(); 4],
+        };
+        let avro = AvroFormat::default();
+        let parquet = ParquetFormat::default();
+
+        for projection in [None, Some([0].as_slice())] {
+            let avro_cost = avro.scan_cost_estimate(&stats, projection);
+            let parquet_cost = parquet.scan_cost_estimate(&stats, projection);
+            assert!(
+                avro_cost.cpu_cost_per_byte().unwrap()
+                    > parquet_cost.cpu_cost_per_byte().unwrap(),
+                "{avro_cost:?} {parquet_cost:?}"
+            );
+        }
+
+        // Projections don't save I/O in Avro, only decoding
+        let full = avro.scan_cost_estimate(&stats, None);
+        let projected = avro.scan_cost_estimate(&stats, Some(&[0]));
+        assert_eq!(projected.io_bytes, full.io_bytes);
+        assert!(
+            projected.cpu_cost.get_value().unwrap() < full.cpu_cost.get_value().unwrap()
+        );
+        assert_eq!(
+            parquet.scan_cost_estimate(&stats, Some(&[0])).io_bytes,
+            Precision::Inexact(250)
+        );
+    }
+
+    fn write_decimal_file(
+        path: &Path,
+        precision: usize,
+        scale: usize,
+        unscaled_values: &[i128],
+    ) -> Result<()> {
+        let schema = format!(
+            r#"{{
+              "type": "record",
+              "name": "r1",
+              "fields": [{{
+                "name": "amount",
+                "type": {{
+                  "type": "bytes",
+                  "logicalType": "decimal",
+                  "precision": {precision},
+                  "scale": {scale}
+                }}
+              }}]
+            }}"#
+        );
+        let records = unscaled_values.iter().map(|value| {
+            Value::Record(vec![(
+                "amount".to_string(),
+                Value::Decimal(Decimal::from(value.to_be_bytes())),
+            )])
+        });
+        write_avro_file(path, &schema, records)
+    }
+
     async fn get_exec(
         state: &dyn Session,
         file_name: &str,
@@ -381,7 +1583,7 @@ mod tests {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let testdata = test_util::arrow_test_data();
         let store_root = format!("{testdata}/avro");
-        let format = AvroFormat {};
+        let format = AvroFormat::default();
         scan_format(
             state,
             &format,
@@ -681,7 +681,7 @@ impl ReadOptions<'_> for ArrowReadOptions<'_> {
         config: &SessionConfig,
         _table_options: TableOptions,
     ) -> ListingOptions {
-        let file_format = ArrowFormat;
+        let file_format = ArrowFormat::default();
 
         ListingOptions::new(Arc::new(file_format))
             .with_file_extension(self.file_extension)
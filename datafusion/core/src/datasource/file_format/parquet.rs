@@ -113,6 +113,7 @@ mod tests {
 
     use crate::datasource::file_format::parquet::test_util::store_parquet;
     use crate::datasource::file_format::test_util::scan_format;
+    use crate::datasource::MemTable;
     use crate::execution::SessionState;
     use crate::physical_plan::metrics::MetricValue;
     use crate::prelude::{ParquetReadOptions, SessionConfig, SessionContext};
@@ -120,6 +121,7 @@ mod tests {
     use arrow::array::RecordBatch;
     use arrow_schema::Schema;
     use datafusion_catalog::Session;
+    use datafusion_common::assert_contains;
     use datafusion_common::cast::{
         as_binary_array, as_binary_view_array, as_boolean_array, as_float32_array,
         as_float64_array, as_int32_array, as_timestamp_nanosecond_array,
@@ -128,9 +130,11 @@ mod tests {
     use datafusion_common::stats::Precision;
     use datafusion_common::test_util::batches_to_string;
     use datafusion_common::ScalarValue::Utf8;
-    use datafusion_common::{Result, ScalarValue};
+    use datafusion_common::{DataFusionError, Result, ScalarValue};
     use datafusion_datasource::file_format::FileFormat;
-    use datafusion_datasource::file_sink_config::{FileSink, FileSinkConfig};
+    use datafusion_datasource::file_sink_config::{
+        FileOutputMode, FileSink, FileSinkConfig,
+    };
     use datafusion_datasource::{ListingTableUrl, PartitionedFile};
     use datafusion_datasource_parquet::{
         fetch_parquet_metadata, fetch_statistics, statistics_from_parquet_meta_calc,
@@ -147,6 +151,7 @@ mod tests {
         types::Int32Type, Array, ArrayRef, DictionaryArray, Int32Array, Int64Array,
         StringArray,
     };
+    use arrow::compute::concat_batches;
     use arrow::datatypes::{DataType, Field};
     use async_trait::async_trait;
     use datafusion_datasource::file_groups::FileGroup;
@@ -162,9 +167,10 @@ mod tests {
     };
     use parquet::arrow::arrow_reader::ArrowReaderOptions;
     use parquet::arrow::ParquetRecordBatchStreamBuilder;
+    use parquet::basic::{Compression, Encoding};
     use parquet::file::metadata::{KeyValue, ParquetColumnIndex, ParquetOffsetIndex};
     use parquet::file::page_index::index::Index;
-    use parquet::format::FileMetaData;
+    use parquet::format::{FileMetaData, PageType};
     use tokio::fs::File;
 
     use crate::test_util::bounded_stream;
@@ -1262,6 +1268,37 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn parquet_sink_write_data_page_v2() -> Result<()> {
+        let opts = ParquetOptions {
+            writer_version: "2.0".into(),
+            data_page_version: Some("2.0".into()),
+            ..Default::default()
+        };
+
+        let parquet_sink =
+            create_written_parquet_sink_using_config("file:///", opts).await?;
+        let (_, file_metadata) = get_written(parquet_sink)?;
+
+        // the file and each of its data pages have the chosen version
+        assert_eq!(file_metadata.version, 2);
+        for column in &file_metadata.row_groups[0].columns {
+            let encoding_stats = column
+                .meta_data
+                .as_ref()
+                .and_then(|meta_data| meta_data.encoding_stats.as_ref())
+                .unwrap();
+            let page_types = encoding_stats
+                .iter()
+                .map(|stats| stats.page_type)
+                .filter(|page_type| *page_type != PageType::DICTIONARY_PAGE)
+                .collect::<Vec<_>>();
+            assert_eq!(page_types, vec![PageType::DATA_PAGE_V2]);
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_parquet_write_empty_file() -> Result<()> {
         // Case 1. write to a single file
@@ -1317,6 +1354,201 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_parquet_write_ordered_single_file() -> Result<()> {
+        let config = SessionConfig::new()
+            .with_target_partitions(4)
+            .with_batch_size(100);
+        let ctx = SessionContext::new_with_config(config);
+
+        // 4 partitions of 10 batches of shuffled values
+        let schema = Arc::new(Schema::new(vec![Field::new("v", DataType::Int32, false)]));
+        let partitions = (0..4)
+            .map(|partition| {
+                (0..10)
+                    .map(|batch| {
+                        let values = (0..100).map(|i| {
+                            let row = partition * 1000 + batch * 100 + i;
+                            (row * 7919) % 4000
+                        });
+                        RecordBatch::try_new(
+                            Arc::clone(&schema),
+                            vec![Arc::new(Int32Array::from_iter_values(values))],
+                        )
+                    })
+                    .collect::<std::result::Result<Vec<_>, _>>()
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let table = MemTable::try_new(Arc::clone(&schema), partitions)?;
+        ctx.register_table("t", Arc::new(table))?;
+
+        // The path has no extension, so would be a directory by default
+        let tmp_dir = tempfile::TempDir::new()?;
+        let path = tmp_dir.path().join("sorted");
+        ctx.sql(&format!(
+            "COPY (SELECT v FROM t ORDER BY v) TO '{}' STORED AS PARQUET \
+            OPTIONS (execution.single_file_output true, 'format.max_row_group_size' 300)",
+            path.display()
+        ))
+        .await?
+        .collect()
+        .await?;
+        assert!(path.is_file());
+
+        let file = File::open(&path).await?;
+        let builder = ParquetRecordBatchStreamBuilder::new(file).await?;
+        assert!(builder.metadata().num_row_groups() > 1);
+        let batches = builder
+            .build()?
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let values = batches
+            .iter()
+            .flat_map(|batch| as_int32_array(batch.column(0)).unwrap().values().to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(values, (0..4000).collect::<Vec<_>>());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_parquet_write_parallel_row_groups_matches_serial() -> Result<()> {
+        let config = SessionConfig::new()
+            .with_target_partitions(1)
+            .with_batch_size(1000);
+        let ctx = SessionContext::new_with_config(config);
+        let tmp_dir = tempfile::TempDir::new()?;
+
+        // Row groups span several batches, and batches several row groups
+        let write = |name: &str, options: &str| {
+            let path = tmp_dir.path().join(name);
+            let sql = format!(
+                "COPY (SELECT value AS v, value % 7 AS k, 'row ' || value AS s \
+                FROM range(20000)) TO '{}' STORED AS PARQUET \
+                OPTIONS (execution.single_file_output true, \
+                'format.max_row_group_size' 1500, {options})",
+                path.display()
+            );
+            let ctx = ctx.clone();
+            async move {
+                ctx.sql(&sql).await?.collect().await?;
+                let file = File::open(&path).await?;
+                let builder = ParquetRecordBatchStreamBuilder::new(file).await?;
+                let row_groups = builder
+                    .metadata()
+                    .row_groups()
+                    .iter()
+                    .map(|rg| rg.num_rows())
+                    .collect::<Vec<_>>();
+                let batches = builder
+                    .build()?
+                    .collect::<Vec<_>>()
+                    .await
+                    .into_iter()
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                let batch = concat_batches(&batches[0].schema(), &batches)?;
+                Ok::<_, DataFusionError>((row_groups, batch))
+            }
+        };
+
+        let serial = write(
+            "serial.parquet",
+            "'format.allow_single_file_parallelism' false",
+        )
+        .await?;
+        let parallel = write(
+            "parallel.parquet",
+            "'format.allow_single_file_parallelism' true, \
+            'format.maximum_parallel_row_group_writers' 4, \
+            'format.maximum_buffered_record_batches_per_stream' 4",
+        )
+        .await?;
+
+        assert_eq!(serial.0.len(), 14);
+        assert_eq!(serial.0, parallel.0);
+        assert_eq!(serial.1.num_rows(), 20000);
+        assert_eq!(serial.1, parallel.1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_parquet_write_column_scoped_options() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE VIEW t AS SELECT column1 AS user_id, column2 AS payload, \
+            named_struct('ts', column1, 'a.b', column1) AS event \
+            FROM (VALUES (1, 'a'), (2, 'b'), (3, 'c'))",
+        )
+        .await?;
+
+        let tmp_dir = tempfile::TempDir::new()?;
+        let path = tmp_dir.path().join("columns.parquet");
+        ctx.sql(&format!(
+            "COPY t TO '{}' STORED AS PARQUET OPTIONS ( \
+            'compression' 'snappy', \
+            'parquet.column.user_id.dictionary_enabled' 'false', \
+            'parquet.column.payload.compression' 'zstd(9)', \
+            'parquet.column.event.ts.encoding' 'delta_binary_packed', \
+            'parquet.column.event.ts.dictionary_enabled' 'false', \
+            'parquet.column.event.a\\.b.compression' 'lz4_raw')",
+            path.display()
+        ))
+        .await?
+        .collect()
+        .await?;
+
+        let file = File::open(&path).await?;
+        let builder = ParquetRecordBatchStreamBuilder::new(file).await?;
+        let row_group = builder.metadata().row_group(0);
+        let user_id = row_group.column(0);
+        assert_eq!(user_id.column_path().string(), "user_id");
+        assert_eq!(user_id.dictionary_page_offset(), None);
+        let payload = row_group.column(1);
+        assert_eq!(payload.column_path().string(), "payload");
+        // The compression level is not recorded in the file
+        assert!(matches!(payload.compression(), Compression::ZSTD(_)));
+        assert!(payload.dictionary_page_offset().is_some());
+        let ts = row_group.column(2);
+        assert_eq!(ts.column_path().parts(), ["event", "ts"]);
+        assert!(ts.encodings().contains(&Encoding::DELTA_BINARY_PACKED));
+        let a_b = row_group.column(3);
+        assert_eq!(a_b.column_path().parts(), ["event", "a.b"]);
+        assert_eq!(a_b.compression(), Compression::LZ4_RAW);
+        assert_eq!(user_id.compression(), Compression::SNAPPY);
+
+        // Columns missing from the output, and unknown properties, are errors
+        let err = ctx
+            .sql(&format!(
+                "COPY t TO '{}' STORED AS PARQUET \
+                OPTIONS ('parquet.column.event.id.compression' 'snappy')",
+                path.display()
+            ))
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "Parquet column options set for column \"event.id\", but no field \"id\" exists in the output schema"
+        );
+        let err = ctx
+            .sql(&format!(
+                "COPY t TO '{}' STORED AS PARQUET \
+                OPTIONS ('parquet.column.user_id.page_size' '1')",
+                path.display()
+            ))
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "Unknown per-column parquet property \"page_size\""
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn parquet_sink_write_insert_schema_into_metadata() -> Result<()> {
         // expected kv metadata without schema
@@ -1476,6 +1708,7 @@ mod tests {
             insert_op: InsertOp::Overwrite,
             keep_partition_by_columns: false,
             file_extension: "parquet".into(),
+            file_output_mode: FileOutputMode::Automatic,
         };
         let parquet_sink = Arc::new(ParquetSink::new(
             file_sink_config,
@@ -1562,6 +1795,7 @@ mod tests {
             insert_op: InsertOp::Overwrite,
             keep_partition_by_columns: false,
             file_extension: "parquet".into(),
+            file_output_mode: FileOutputMode::Automatic,
         };
         let parquet_sink = Arc::new(ParquetSink::new(
             file_sink_config,
@@ -1648,6 +1882,7 @@ mod tests {
                 insert_op: InsertOp::Overwrite,
                 keep_partition_by_columns: false,
                 file_extension: "parquet".into(),
+                file_output_mode: FileOutputMode::Automatic,
             };
             let parquet_sink = Arc::new(ParquetSink::new(
                 file_sink_config,
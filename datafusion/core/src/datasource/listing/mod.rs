@@ -19,7 +19,7 @@
 //! to get the list of files to process.
 
 mod table;
-pub use datafusion_catalog_listing::helpers;
+pub use datafusion_catalog_listing::{discovery, helpers};
 pub use datafusion_datasource::{
     FileRange, ListingTableUrl, PartitionedFile, PartitionedFileStream,
 };
@@ -18,27 +18,33 @@
 //! The table implementation.
 
 use super::{
-    helpers::{expr_applicable_for_cols, pruned_partition_list},
+    helpers::{expr_applicable_for_cols, partition_pruning_expr, pruned_partition_list},
     ListingTableUrl, PartitionedFile,
 };
 use crate::{
     datasource::file_format::{file_compression_type::FileCompressionType, FileFormat},
-    datasource::{create_ordering, physical_plan::FileSinkConfig},
+    datasource::{
+        create_ordering,
+        physical_plan::{FileOutputMode, FileSinkConfig},
+    },
     execution::context::SessionState,
 };
 use arrow::datatypes::{DataType, Field, SchemaBuilder, SchemaRef};
 use arrow_schema::Schema;
 use async_trait::async_trait;
-use datafusion_catalog::{Session, TableProvider};
+use datafusion_catalog::ddl::column_definitions;
+use datafusion_catalog::{schema_with_comment, Session, TableProvider};
 use datafusion_common::{
     config_datafusion_err, config_err, internal_err, plan_err, project_schema,
-    stats::Precision, Constraints, DataFusionError, Result, SchemaExt,
+    stats::Precision, utils::quote_identifier, Constraints, DataFusionError, Result,
+    SchemaExt,
 };
 use datafusion_datasource::{
     compute_all_files_statistics,
     file_groups::FileGroup,
     file_scan_config::{FileScanConfig, FileScanConfigBuilder},
-    schema_adapter::DefaultSchemaAdapterFactory,
+    schema_adapter::{DefaultSchemaAdapterFactory, PromotingSchemaAdapterFactory},
+    write::demux::validate_file_name_template,
 };
 use datafusion_execution::{
     cache::{cache_manager::FileStatisticsCache, cache_unit::DefaultFileStatisticsCache},
@@ -949,10 +955,17 @@ impl TableProvider for ListingTable {
             .collect::<Vec<_>>();
         // If the filters can be resolved using only partition cols, there is no need to
         // pushdown it to TableScan, otherwise, `unhandled` pruning predicates will be generated
-        let (partition_filters, filters): (Vec<_>, Vec<_>) =
+        let (mut partition_filters, filters): (Vec<_>, Vec<_>) =
             filters.iter().cloned().partition(|filter| {
                 can_be_evaluted_for_partition_pruning(&table_partition_col_names, filter)
             });
+        // The other filters can still prune the partitions for which they are
+        // false regardless of the values of the data columns
+        if !table_partition_col_names.is_empty() {
+            partition_filters.extend(filters.iter().filter_map(|filter| {
+                partition_pruning_expr(&table_partition_col_names, filter)
+            }));
+        }
 
         // We should not limit the number of partitioned files to scan if there are filters and limit
         // at the same time. This is because the limit should be applied after the filters are applied.
@@ -1002,6 +1015,21 @@ impl TableProvider for ListingTable {
             return Ok(Arc::new(EmptyExec::new(Arc::new(Schema::empty()))));
         };
 
+        let mut file_source = self.options.format.file_source();
+        if state
+            .config_options()
+            .execution
+            .listing_table_schema_coercion
+            && file_source.schema_adapter_factory().is_none()
+        {
+            // Sources that don't map file schemas are left as they are
+            if let Ok(source) = file_source
+                .with_schema_adapter_factory(Arc::new(PromotingSchemaAdapterFactory))
+            {
+                file_source = source;
+            }
+        }
+
         // create the execution plan
         self.options
             .format
@@ -1010,7 +1038,7 @@ impl TableProvider for ListingTable {
                 FileScanConfigBuilder::new(
                     object_store_url,
                     Arc::clone(&self.file_schema),
-                    self.options.format.file_source(),
+                    file_source,
                 )
                 .with_file_groups(partitioned_file_lists)
                 .with_constraints(self.constraints.clone())
@@ -1052,6 +1080,74 @@ impl TableProvider for ListingTable {
         self.definition.as_deref()
     }
 
+    /// Returns the statement the table was created with if any, or one
+    /// reconstructed from the [`ListingOptions`] otherwise. The options of
+    /// the file format are not part of a reconstructed statement.
+    fn create_statement(&self, table_name: &str) -> Option<String> {
+        if let Some(definition) = &self.definition {
+            return Some(definition.clone());
+        }
+        // A statement has a single location
+        let [table_path] = self.table_paths.as_slice() else {
+            return None;
+        };
+
+        let mut statement = format!(
+            "CREATE EXTERNAL TABLE {} {} STORED AS {}",
+            quote_identifier(table_name),
+            column_definitions(self.table_schema.fields().iter().map(AsRef::as_ref)),
+            self.options.format.get_ext().to_uppercase()
+        );
+        if !self.options.table_partition_cols.is_empty() {
+            let partitions = self
+                .options
+                .table_partition_cols
+                .iter()
+                .map(|(name, _)| quote_identifier(name))
+                .join(", ");
+            statement.push_str(&format!(" PARTITIONED BY ({partitions})"));
+        }
+        for sort_exprs in &self.options.file_sort_order {
+            statement
+                .push_str(&format!(" WITH ORDER ({})", sort_exprs.iter().join(", ")));
+        }
+        statement.push_str(&format!(
+            " LOCATION '{}'",
+            table_path.as_str().replace('\'', "''")
+        ));
+        Some(statement)
+    }
+
+    /// Returns the number of files of the table and their latest
+    /// modification time, as listed by the object store.
+    ///
+    /// This lists every file of the table each time a query scanning it is
+    /// planned, in addition to the listing of the scan itself. Configure a
+    /// [`CacheManagerConfig::list_files_cache`] to reuse the listing of the
+    /// files instead, in which case the token is not updated while they are
+    /// cached.
+    ///
+    /// [`CacheManagerConfig::list_files_cache`]: datafusion_execution::cache::cache_manager::CacheManagerConfig::list_files_cache
+    async fn snapshot_token(&self, state: &dyn Session) -> Result<Option<String>> {
+        let mut num_files = 0;
+        let mut last_modified = None;
+        for table_path in &self.table_paths {
+            let store = state.runtime_env().object_store(table_path)?;
+            let mut files = table_path
+                .list_all_files(state, store.as_ref(), &self.options.file_extension)
+                .await?;
+            while let Some(file) = files.try_next().await? {
+                num_files += 1;
+                last_modified =
+                    last_modified.max(file.last_modified.timestamp_nanos_opt());
+            }
+        }
+        Ok(Some(format!(
+            "files={num_files},last_modified={}",
+            last_modified.unwrap_or_default()
+        )))
+    }
+
     async fn insert_into(
         &self,
         state: &dyn Session,
@@ -1086,6 +1182,11 @@ impl TableProvider for ListingTable {
         let file_group = file_list_stream.try_collect::<Vec<_>>().await?.into();
         let keep_partition_by_columns =
             state.config_options().execution.keep_partition_by_columns;
+        if let Some(template) =
+            &state.config_options().execution.output_file_name_template
+        {
+            validate_file_name_template(template)?;
+        }
 
         // Sink related option, apart from format
         let config = FileSinkConfig {
@@ -1098,6 +1199,7 @@ impl TableProvider for ListingTable {
             insert_op,
             keep_partition_by_columns,
             file_extension: self.options().format.get_ext(),
+            file_output_mode: FileOutputMode::Automatic,
         };
 
         let orderings = self.try_create_output_ordering()?;
@@ -1113,6 +1215,35 @@ impl TableProvider for ListingTable {
     fn get_column_default(&self, column: &str) -> Option<&Expr> {
         self.column_defaults.get(column)
     }
+
+    fn partition_columns(&self) -> Vec<String> {
+        self.options
+            .table_partition_cols
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// The comments are stored in the metadata of the schema of the table.
+    /// Partition columns can't store comments, as their fields are derived
+    /// from [`ListingOptions::table_partition_cols`].
+    fn with_comment(
+        &self,
+        column: Option<&str>,
+        comment: Option<&str>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        if let Some(column) = column {
+            if self.file_schema.field_with_name(column).is_err() {
+                return Ok(None);
+            }
+        }
+        let mut table = self.clone();
+        table.file_schema =
+            Arc::new(schema_with_comment(&self.file_schema, column, comment)?);
+        table.table_schema =
+            Arc::new(schema_with_comment(&self.table_schema, column, comment)?);
+        Ok(Some(Arc::new(table)))
+    }
 }
 
 impl ListingTable {
@@ -1312,7 +1443,8 @@ mod tests {
         execution::options::ArrowReadOptions,
         test::{
             columns, object_store::ensure_head_concurrency,
-            object_store::make_test_store_and_state, object_store::register_test_store,
+            object_store::fail_writes_after, object_store::make_test_store_and_state,
+            object_store::register_test_store,
         },
     };
     use arrow::{compute::SortOptions, record_batch::RecordBatch};
@@ -1325,6 +1457,7 @@ mod tests {
     use datafusion_expr::{BinaryExpr, LogicalPlanBuilder, Operator};
     use datafusion_physical_expr::PhysicalSortExpr;
     use datafusion_physical_plan::{collect, ExecutionPlanProperties};
+    use object_store::local::LocalFileSystem;
     use std::io::Write;
     use tempfile::TempDir;
     use url::Url;
@@ -2553,4 +2686,76 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_insert_overwrite_keeps_files_if_writing_fails() -> Result<()> {
+        fn count_files(dir: &std::path::Path) -> usize {
+            std::fs::read_dir(dir)
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .map(|path| if path.is_dir() { count_files(&path) } else { 1 })
+                .sum()
+        }
+
+        let ctx = SessionContext::new();
+        let tmp_dir = TempDir::new()?;
+        let path = format!("{}/", tmp_dir.path().to_str().unwrap());
+        ctx.sql(&format!(
+            "CREATE EXTERNAL TABLE t (a INT, p VARCHAR) STORED AS CSV \
+             LOCATION '{path}' PARTITIONED BY (p)"
+        ))
+        .await?
+        .collect()
+        .await?;
+        ctx.sql("INSERT INTO t VALUES (1, 'x'), (2, 'y')")
+            .await?
+            .collect()
+            .await?;
+
+        // Writing the second of the three new files fails
+        let url = Url::parse("file://").unwrap();
+        ctx.register_object_store(
+            &url,
+            fail_writes_after(Arc::new(LocalFileSystem::new()), 1),
+        );
+        let overwrite = "INSERT OVERWRITE t VALUES (3, 'x'), (4, 'y'), (5, 'z')";
+        let err = ctx.sql(overwrite).await?.collect().await.unwrap_err();
+        assert_contains!(err.to_string(), "Failed to write");
+
+        // The existing files are kept and the staged files deleted
+        let batches = ctx
+            .sql("SELECT a, p FROM t ORDER BY a")
+            .await?
+            .collect()
+            .await?;
+        insta::assert_snapshot!(batches_to_string(&batches), @r###"
+        +---+---+
+        | a | p |
+        +---+---+
+        | 1 | x |
+        | 2 | y |
+        +---+---+
+        "###);
+        assert_eq!(count_files(tmp_dir.path()), 2);
+
+        ctx.register_object_store(&url, Arc::new(LocalFileSystem::new()));
+        ctx.sql(overwrite).await?.collect().await?;
+        let batches = ctx
+            .sql("SELECT a, p FROM t ORDER BY a")
+            .await?
+            .collect()
+            .await?;
+        insta::assert_snapshot!(batches_to_string(&batches), @r###"
+        +---+---+
+        | a | p |
+        +---+---+
+        | 3 | x |
+        | 4 | y |
+        | 5 | z |
+        +---+---+
+        "###);
+        assert_eq!(count_files(tmp_dir.path()), 3);
+
+        Ok(())
+    }
 }
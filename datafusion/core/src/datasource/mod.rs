@@ -19,6 +19,12 @@
 //!
 //! [`ListingTable`]: crate::datasource::listing::ListingTable
 
+#[cfg(feature = "avro")]
+pub mod avro_manifest;
+#[cfg(feature = "avro")]
+pub mod avro_registry;
+#[cfg(all(feature = "avro", feature = "parquet"))]
+pub mod avro_to_parquet;
 pub mod dynamic_file;
 pub mod empty;
 pub mod file_format;
@@ -16,33 +16,60 @@
 // under the License.
 
 use std::any::Any;
+use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::sync::Arc;
 
+use crate::datasource::file_format::arrow::ARROW_MAGIC;
 use crate::datasource::physical_plan::{FileMeta, FileOpenFuture, FileOpener};
 use crate::error::Result;
 use datafusion_datasource::as_file_source;
 use datafusion_datasource::schema_adapter::SchemaAdapterFactory;
 
+use arrow::array::RecordBatch;
 use arrow::buffer::Buffer;
 use arrow::datatypes::SchemaRef;
+use arrow::ipc::reader::{FileReader, StreamReader};
 use arrow_ipc::reader::FileDecoder;
 use datafusion_common::Statistics;
 use datafusion_datasource::file::FileSource;
+use datafusion_datasource::file_groups::FileGroupPartitioner;
 use datafusion_datasource::file_scan_config::FileScanConfig;
 use datafusion_datasource::PartitionedFile;
+use datafusion_physical_expr_common::sort_expr::LexOrdering;
 use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
 
+use futures::stream::BoxStream;
 use futures::StreamExt;
 use itertools::Itertools;
 use object_store::{GetOptions, GetRange, GetResultPayload, ObjectStore};
 
 /// Arrow configuration struct that is given to DataSourceExec
-/// Does not hold anything special, since [`FileScanConfig`] is sufficient for arrow
+///
+/// Reads files in both the IPC file format and the IPC stream format, the
+/// format of each file being detected from its first bytes.
 #[derive(Clone, Default)]
 pub struct ArrowSource {
     metrics: ExecutionPlanMetricsSet,
     projected_statistics: Option<Statistics>,
     schema_adapter_factory: Option<Arc<dyn SchemaAdapterFactory>>,
+    stream_files: bool,
+}
+
+impl ArrowSource {
+    /// Sets whether some of the scanned files are in the IPC stream format.
+    ///
+    /// The record batches of stream files aren't indexed by a footer, so
+    /// the files aren't split into byte ranges to be scanned in parallel.
+    pub fn with_stream_files(&self, stream_files: bool) -> Self {
+        let mut conf = self.clone();
+        conf.stream_files = stream_files;
+        conf
+    }
+
+    /// Whether some of the scanned files are in the IPC stream format
+    pub fn stream_files(&self) -> bool {
+        self.stream_files
+    }
 }
 
 impl From<ArrowSource> for Arc<dyn FileSource> {
@@ -100,6 +127,36 @@ impl FileSource for ArrowSource {
         "arrow"
     }
 
+    fn output_identity(&self) -> Option<String> {
+        if self.schema_adapter_factory.is_some() {
+            return None;
+        }
+        Some(format!("stream_files={}", self.stream_files))
+    }
+
+    fn repartitioned(
+        &self,
+        target_partitions: usize,
+        repartition_file_min_size: usize,
+        output_ordering: Option<LexOrdering>,
+        config: &FileScanConfig,
+    ) -> Result<Option<FileScanConfig>> {
+        if self.stream_files {
+            return Ok(None);
+        }
+        let partitioner = FileGroupPartitioner::new()
+            .with_target_partitions(target_partitions)
+            .with_repartition_file_min_size(repartition_file_min_size)
+            .with_preserve_order_within_groups(output_ordering.is_some());
+        Ok(partitioner
+            .repartition_file_groups(&config.file_groups)
+            .map(|file_groups| {
+                let mut config = config.clone();
+                config.file_groups = file_groups;
+                config
+            }))
+    }
+
     fn with_schema_adapter_factory(
         &self,
         schema_adapter_factory: Arc<dyn SchemaAdapterFactory>,
@@ -136,19 +193,18 @@ impl FileOpener for ArrowOpener {
                     let r = object_store.get(file_meta.location()).await?;
                     match r.payload {
                         #[cfg(not(target_arch = "wasm32"))]
-                        GetResultPayload::File(file, _) => {
-                            let arrow_reader = arrow::ipc::reader::FileReader::try_new(
-                                file, projection,
-                            )?;
-                            Ok(futures::stream::iter(arrow_reader).boxed())
+                        GetResultPayload::File(mut file, _) => {
+                            let mut magic = [0; ARROW_MAGIC.len()];
+                            let is_file_format = file.read_exact(&mut magic).is_ok()
+                                && magic == ARROW_MAGIC;
+                            file.seek(SeekFrom::Start(0))?;
+                            read_ipc(file, is_file_format, projection)
                         }
                         GetResultPayload::Stream(_) => {
                             let bytes = r.bytes().await?;
+                            let is_file_format = bytes.starts_with(&ARROW_MAGIC);
                             let cursor = std::io::Cursor::new(bytes);
-                            let arrow_reader = arrow::ipc::reader::FileReader::try_new(
-                                cursor, projection,
-                            )?;
-                            Ok(futures::stream::iter(arrow_reader).boxed())
+                            read_ipc(cursor, is_file_format, projection)
                         }
                     }
                 }
@@ -163,6 +219,21 @@ impl FileOpener for ArrowOpener {
                         .get_opts(file_meta.location(), get_option)
                         .await?;
                     let footer_len_buf = get_result.bytes().await?;
+                    if !footer_len_buf.ends_with(&ARROW_MAGIC) {
+                        // Stream files have no footer to find the record
+                        // batches of the range in, so the first range reads
+                        // the whole file
+                        if range.start != 0 {
+                            return Ok(futures::stream::empty().boxed());
+                        }
+                        let bytes = object_store
+                            .get(file_meta.location())
+                            .await?
+                            .bytes()
+                            .await?;
+                        let cursor = std::io::Cursor::new(bytes);
+                        return read_ipc(cursor, false, projection);
+                    }
                     let footer_len = arrow_ipc::reader::read_footer_length(
                         footer_len_buf[..].try_into().unwrap(),
                     )?;
@@ -254,3 +325,21 @@ impl FileOpener for ArrowOpener {
         }))
     }
 }
+
+/// Reads the record batches of an IPC file, in the file format if
+/// `is_file_format`, or else in the stream format. The stream format has no
+/// footer, so its messages are replayed from the start, the projection being
+/// applied to the decoded batches.
+fn read_ipc<R: Read + Seek + Send + 'static>(
+    reader: R,
+    is_file_format: bool,
+    projection: Option<Vec<usize>>,
+) -> Result<BoxStream<'static, arrow::error::Result<RecordBatch>>> {
+    if is_file_format {
+        let reader = FileReader::try_new(reader, projection)?;
+        Ok(futures::stream::iter(reader).boxed())
+    } else {
+        let reader = StreamReader::try_new(BufReader::new(reader), projection)?;
+        Ok(futures::stream::iter(reader).boxed())
+    }
+}
@@ -30,14 +30,18 @@ mod tests {
     use crate::prelude::SessionContext;
     use crate::test::object_store::local_unpartitioned_file;
     use arrow::datatypes::{DataType, Field, SchemaBuilder};
+    use datafusion_common::cast::{as_int32_array, as_int64_array};
     use datafusion_common::test_util::batches_to_string;
     use datafusion_common::{test_util, Result, ScalarValue};
     use datafusion_datasource::file_format::FileFormat;
+    use datafusion_datasource::file_groups::FileGroup;
     use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
     use datafusion_datasource::PartitionedFile;
+    use datafusion_datasource_avro::avro_to_arrow::ROW_INDEX_COLUMN;
     use datafusion_datasource_avro::source::AvroSource;
     use datafusion_datasource_avro::AvroFormat;
     use datafusion_execution::object_store::ObjectStoreUrl;
+    use datafusion_physical_plan::common::collect;
     use datafusion_physical_plan::ExecutionPlan;
 
     use datafusion_datasource::source::DataSourceExec;
@@ -77,7 +81,7 @@ mod tests {
         let filename = format!("{testdata}/avro/alltypes_plain.avro");
         let meta = local_unpartitioned_file(filename);
 
-        let file_schema = AvroFormat {}
+        let file_schema = AvroFormat::default()
             .infer_schema(&state, &store, std::slice::from_ref(&meta))
             .await?;
 
@@ -146,7 +150,7 @@ mod tests {
         let object_store = Arc::new(LocalFileSystem::new()) as _;
         let object_store_url = ObjectStoreUrl::local_filesystem();
         let meta = local_unpartitioned_file(filename);
-        let actual_schema = AvroFormat {}
+        let actual_schema = AvroFormat::default()
             .infer_schema(&state, &object_store, std::slice::from_ref(&meta))
             .await?;
 
@@ -209,6 +213,55 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn avro_exec_with_row_index() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+
+        let testdata = test_util::arrow_test_data();
+        let filename = format!("{testdata}/avro/alltypes_plain.avro");
+        let object_store = Arc::new(LocalFileSystem::new()) as _;
+        let object_store_url = ObjectStoreUrl::local_filesystem();
+        let meta = local_unpartitioned_file(filename);
+        let actual_schema = AvroFormat::default()
+            .infer_schema(&state, &object_store, std::slice::from_ref(&meta))
+            .await?;
+
+        let mut builder = SchemaBuilder::from(actual_schema.fields());
+        builder.push(Field::new(ROW_INDEX_COLUMN, DataType::Int64, false));
+        let file_schema = Arc::new(builder.finish());
+        let projection = Some(vec![0, actual_schema.fields().len()]);
+
+        // The file is read by two partitions, in batches of 3 rows
+        let source = Arc::new(AvroSource::new().with_row_index(true));
+        let conf = FileScanConfigBuilder::new(object_store_url, file_schema, source)
+            .with_file_groups(vec![
+                FileGroup::new(vec![meta.clone().into()]),
+                FileGroup::new(vec![meta.into()]),
+            ])
+            .with_projection(projection)
+            .with_batch_size(Some(3))
+            .build();
+        let source_exec = DataSourceExec::from_data_source(conf);
+
+        for partition in 0..2 {
+            let batches =
+                collect(source_exec.execute(partition, state.task_ctx())?).await?;
+            assert_eq!(batches.len(), 3);
+            let mut ids = vec![];
+            let mut row_indices = vec![];
+            for batch in &batches {
+                ids.extend(as_int32_array(batch.column(0))?.values().iter().copied());
+                row_indices
+                    .extend(as_int64_array(batch.column(1))?.values().iter().copied());
+            }
+            assert_eq!(ids, vec![4, 5, 6, 7, 2, 3, 0, 1]);
+            assert_eq!(row_indices, (0..8).collect::<Vec<_>>());
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn avro_exec_with_partition() -> Result<()> {
         let session_ctx = SessionContext::new();
@@ -219,7 +272,7 @@ mod tests {
         let object_store = Arc::new(LocalFileSystem::new()) as _;
         let object_store_url = ObjectStoreUrl::local_filesystem();
         let meta = local_unpartitioned_file(filename);
-        let file_schema = AvroFormat {}
+        let file_schema = AvroFormat::default()
             .infer_schema(&state, &object_store, std::slice::from_ref(&meta))
             .await?;
 
@@ -48,10 +48,13 @@ mod tests {
     use datafusion_datasource_csv::source::CsvSource;
     use futures::{StreamExt, TryStreamExt};
 
+    use arrow::array::RecordBatch;
     use arrow::datatypes::*;
     use bytes::Bytes;
     use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
     use datafusion_datasource::source::DataSourceExec;
+    use datafusion_datasource::PartitionedFile;
+    use datafusion_execution::object_store::ObjectStoreUrl;
     use insta::assert_snapshot;
     use object_store::chunked::ChunkedStore;
     use object_store::local::LocalFileSystem;
@@ -636,6 +639,132 @@ mod tests {
         Ok(())
     }
 
+    /// Runs `sql`, returning its results and the number of rows pruned and
+    /// matched by the predicate pushed down into the scan
+    async fn collect_with_pushdown_metrics(
+        ctx: &SessionContext,
+        sql: &str,
+    ) -> Result<(Vec<RecordBatch>, usize, usize)> {
+        fn scan_metrics(plan: &Arc<dyn ExecutionPlan>) -> Option<MetricsSet> {
+            if plan.as_any().is::<DataSourceExec>() {
+                return plan.metrics();
+            }
+            plan.children().into_iter().find_map(scan_metrics)
+        }
+
+        let plan = ctx.sql(sql).await?.create_physical_plan().await?;
+        let batches =
+            crate::physical_plan::collect(Arc::clone(&plan), ctx.task_ctx()).await?;
+        let metrics = scan_metrics(&plan).expect("plan has a scan");
+        let count = |name| {
+            metrics
+                .sum_by_name(name)
+                .map(|value| value.as_usize())
+                .unwrap_or_default()
+        };
+        Ok((
+            batches,
+            count("pushdown_rows_pruned"),
+            count("pushdown_rows_matched"),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_filter_pushdown() -> Result<()> {
+        let mut config = SessionConfig::new()
+            .with_batch_size(10)
+            .with_target_partitions(1);
+        config.options_mut().execution.decoder_pushdown_filters = true;
+        let ctx = SessionContext::new_with_config(config);
+
+        // Only the 5th batch has rows with `b = 'x'`, and the file does not
+        // end with a newline
+        let data = (0..100)
+            .map(|i| {
+                format!(
+                    "{i},{},{}",
+                    if (42..45).contains(&i) { "x" } else { "y" },
+                    i * 2
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let data = format!("a,b,c\n{data}");
+
+        // Read both from a local file and from a stream of chunks
+        let tmp_dir = TempDir::new()?;
+        let path = tmp_dir.path().join("data.csv");
+        fs::write(&path, &data)?;
+        ctx.register_csv("local", path.to_str().unwrap(), CsvReadOptions::new())
+            .await?;
+        let store = object_store::memory::InMemory::new();
+        store
+            .put(&object_store::path::Path::from("data.csv"), data.into())
+            .await?;
+        ctx.register_object_store(
+            &Url::parse("memory://").unwrap(),
+            Arc::new(ChunkedStore::new(Arc::new(store), 7)),
+        );
+        ctx.register_csv("chunked", "memory:///data.csv", CsvReadOptions::new())
+            .await?;
+
+        for table in ["local", "chunked"] {
+            let sql = format!("SELECT a, c FROM {table} WHERE b = 'x'");
+            let (results, pruned, matched) =
+                collect_with_pushdown_metrics(&ctx, &sql).await?;
+            let expected = [
+                "+----+----+",
+                "| a  | c  |",
+                "+----+----+",
+                "| 42 | 84 |",
+                "| 43 | 86 |",
+                "| 44 | 88 |",
+                "+----+----+",
+            ];
+            assert_batches_eq!(expected, &results);
+            assert_eq!((pruned, matched), (97, 3), "{table}");
+
+            // Rows of the first and last batches are selected
+            let sql = format!("SELECT a, c FROM {table} WHERE a < 2 OR a > 98");
+            let (results, pruned, matched) =
+                collect_with_pushdown_metrics(&ctx, &sql).await?;
+            let expected = [
+                "+----+-----+",
+                "| a  | c   |",
+                "+----+-----+",
+                "| 0  | 0   |",
+                "| 1  | 2   |",
+                "| 99 | 198 |",
+                "+----+-----+",
+            ];
+            assert_batches_eq!(expected, &results);
+            assert_eq!((pruned, matched), (97, 3), "{table}");
+        }
+
+        let plan = ctx
+            .sql("EXPLAIN SELECT a FROM local WHERE b = 'x'")
+            .await?
+            .collect()
+            .await?;
+        let plan = batches_to_string(&plan);
+        assert!(
+            plan.contains("has_header=true, predicate=b@1 = x"),
+            "{plan}"
+        );
+
+        // The filters are not pushed down into the scan by default
+        ctx.sql("SET datafusion.execution.decoder_pushdown_filters = false")
+            .await?;
+        let plan = ctx
+            .sql("EXPLAIN SELECT a FROM local WHERE b = 'x'")
+            .await?
+            .collect()
+            .await?;
+        let plan = batches_to_string(&plan);
+        assert!(!plan.contains("predicate="), "{plan}");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn write_csv_results_error_handling() -> Result<()> {
         let ctx = SessionContext::new();
@@ -759,6 +888,30 @@ mod tests {
         Ok(())
     }
 
+    /// Scans of the same files displayed the same way only share their output
+    /// if they read them from the same store with the same options
+    #[test]
+    fn csv_output_identity() -> Result<()> {
+        let scan = |url: &str, delimiter: u8| -> Result<DataSourceExec> {
+            let source = Arc::new(CsvSource::new(true, delimiter, b'"'));
+            let config = FileScanConfigBuilder::new(
+                ObjectStoreUrl::parse(url)?,
+                aggr_test_schema(),
+                source,
+            )
+            .with_file(PartitionedFile::new("t.csv", 100))
+            .build();
+            Ok(DataSourceExec::new(Arc::new(config)))
+        };
+        let identity = |exec: DataSourceExec| exec.output_identity().unwrap();
+
+        let base = identity(scan("s3://a", b',')?);
+        assert_eq!(base, identity(scan("s3://a", b',')?));
+        assert_ne!(base, identity(scan("s3://b", b',')?));
+        assert_ne!(base, identity(scan("s3://a", b';')?));
+        Ok(())
+    }
+
     fn get_value(metrics: &MetricsSet, metric_name: &str) -> usize {
         match metrics.sum_by_name(metric_name) {
             Some(v) => v.as_usize(),
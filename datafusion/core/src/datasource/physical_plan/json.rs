@@ -32,11 +32,12 @@ mod tests {
 
     use crate::dataframe::DataFrameWriteOptions;
     use crate::execution::SessionState;
+    use crate::physical_plan::collect;
     use crate::prelude::{CsvReadOptions, NdJsonReadOptions, SessionContext};
     use crate::test::partitioned_file_groups;
     use datafusion_common::cast::{as_int32_array, as_int64_array, as_string_array};
     use datafusion_common::test_util::batches_to_string;
-    use datafusion_common::Result;
+    use datafusion_common::{assert_batches_eq, Result};
     use datafusion_datasource::file_compression_type::FileCompressionType;
     use datafusion_datasource::file_format::FileFormat;
     use datafusion_datasource_json::JsonFormat;
@@ -374,6 +375,75 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_filter_pushdown() -> Result<()> {
+        let mut config = SessionConfig::new()
+            .with_batch_size(10)
+            .with_target_partitions(1);
+        config.options_mut().execution.decoder_pushdown_filters = true;
+        let ctx = SessionContext::new_with_config(config);
+
+        // Only the 5th batch has rows with `b = 'x'`
+        let data = (0..100)
+            .map(|i| {
+                let b = if (42..45).contains(&i) { "x" } else { "y" };
+                format!(r#"{{"a": {i}, "b": "{b}", "c": {}}}"#, i * 2)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // Read both from a local file and from a stream of chunks
+        let tmp_dir = TempDir::new()?;
+        let path = tmp_dir.path().join("data.json");
+        fs::write(&path, &data)?;
+        ctx.register_json(
+            "local",
+            path.to_str().unwrap(),
+            NdJsonReadOptions::default(),
+        )
+        .await?;
+        let store = object_store::memory::InMemory::new();
+        store
+            .put(&object_store::path::Path::from("data.json"), data.into())
+            .await?;
+        ctx.register_object_store(
+            &Url::parse("memory://").unwrap(),
+            Arc::new(ChunkedStore::new(Arc::new(store), 7)),
+        );
+        ctx.register_json(
+            "chunked",
+            "memory:///data.json",
+            NdJsonReadOptions::default(),
+        )
+        .await?;
+
+        for table in ["local", "chunked"] {
+            let sql = format!("SELECT a, c FROM {table} WHERE b = 'x'");
+            let plan = ctx.sql(&sql).await?.create_physical_plan().await?;
+            let results = collect(Arc::clone(&plan), ctx.task_ctx()).await?;
+            let expected = [
+                "+----+----+",
+                "| a  | c  |",
+                "+----+----+",
+                "| 42 | 84 |",
+                "| 43 | 86 |",
+                "| 44 | 88 |",
+                "+----+----+",
+            ];
+            assert_batches_eq!(expected, &results);
+
+            let mut scan = plan;
+            while !scan.as_any().is::<DataSourceExec>() {
+                scan = Arc::clone(scan.children()[0]);
+            }
+            let metrics = scan.metrics().unwrap();
+            let count = |name| metrics.sum_by_name(name).unwrap().as_usize();
+            assert_eq!(count("pushdown_rows_pruned"), 97, "{table}");
+            assert_eq!(count("pushdown_rows_matched"), 3, "{table}");
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn write_json_results() -> Result<()> {
         // create partitioned input file and context
@@ -0,0 +1,701 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`CatalogStore`] persists the views and external tables created by DDL
+//! statements across sessions
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::execution::session_state::SessionState;
+
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion_common::{
+    exec_datafusion_err, Constraint, Constraints, DFSchema, DataFusionError,
+    ResolvedTableReference, Result, TableReference,
+};
+use datafusion_expr::{CreateExternalTable, SortExpr};
+use datafusion_sql::unparser::expr_to_sql;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::{json, Map, Value};
+
+/// A pluggable interface to persist the views and external tables created
+/// by `CREATE VIEW` and `CREATE EXTERNAL TABLE` statements.
+///
+/// A [`SessionContext`] with a catalog store saves the definition of every
+/// view and external table it creates, removes the definitions of the ones
+/// it drops, and registers the stored definitions again in
+/// [`SessionContext::refresh_catalogs`].
+///
+/// [`JsonFileCatalogStore`] stores the definitions in a local JSON file.
+///
+/// [`SessionContext`]: crate::execution::context::SessionContext
+/// [`SessionContext::refresh_catalogs`]: crate::execution::context::SessionContext::refresh_catalogs
+#[async_trait]
+pub trait CatalogStore: Debug + Sync + Send {
+    /// Returns all stored definitions
+    async fn load(&self) -> Result<Vec<StoredDefinition>>;
+
+    /// Stores `definition`, replacing the definition of the same name, if any
+    async fn save(&self, definition: StoredDefinition) -> Result<()>;
+
+    /// Removes the definition named `name`, if any
+    async fn remove(&self, name: &ResolvedTableReference) -> Result<()>;
+}
+
+/// The definition of a view or an external table, see [`CatalogStore`]
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum StoredDefinition {
+    /// A view created by `CREATE VIEW`
+    View(StoredView),
+    /// A table created by `CREATE EXTERNAL TABLE`
+    ExternalTable(StoredExternalTable),
+}
+
+impl StoredDefinition {
+    /// Returns the name of the view or table
+    pub fn name(&self) -> &ResolvedTableReference {
+        match self {
+            StoredDefinition::View(view) => &view.name,
+            StoredDefinition::ExternalTable(table) => &table.name,
+        }
+    }
+}
+
+/// The definition of a view created by `CREATE VIEW`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredView {
+    /// The name of the view
+    pub name: ResolvedTableReference,
+    /// The `CREATE VIEW` statement of the view. Its query is planned again
+    /// with the default catalog and schema of the session loading it.
+    pub definition: String,
+}
+
+/// The definition of a table created by `CREATE EXTERNAL TABLE`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredExternalTable {
+    /// The name of the table
+    pub name: ResolvedTableReference,
+    /// The location of the files of the table
+    pub location: String,
+    /// The file type, such as `PARQUET`
+    pub file_type: String,
+    /// The declared schema of the table, empty if the schema is inferred
+    pub schema: SchemaRef,
+    /// The partition columns
+    pub table_partition_cols: Vec<String>,
+    /// The orderings of the files, as given by `WITH ORDER`
+    pub order_exprs: Vec<Vec<StoredSortExpr>>,
+    /// Whether the table is an infinite stream
+    pub unbounded: bool,
+    /// The options of the table. [`CatalogStore`] implementations writing
+    /// to untrusted storage should leave out the options holding secrets,
+    /// see [`SecretOptions`].
+    pub options: HashMap<String, String>,
+    /// The primary key and unique constraints
+    pub constraints: Constraints,
+    /// The SQL expressions of the column defaults
+    pub column_defaults: HashMap<String, String>,
+}
+
+/// A sort expression of [`StoredExternalTable::order_exprs`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredSortExpr {
+    /// The SQL expression to sort by
+    pub expr: String,
+    /// Whether the order is ascending
+    pub asc: bool,
+    /// Whether nulls sort first
+    pub nulls_first: bool,
+}
+
+impl StoredExternalTable {
+    /// Returns the definition of the table created by `cmd`, named `name`
+    pub fn try_new(
+        name: ResolvedTableReference,
+        cmd: &CreateExternalTable,
+    ) -> Result<Self> {
+        let order_exprs = cmd
+            .order_exprs
+            .iter()
+            .map(|ordering| {
+                ordering
+                    .iter()
+                    .map(|sort| {
+                        Ok(StoredSortExpr {
+                            expr: expr_to_sql(&sort.expr)?.to_string(),
+                            asc: sort.asc,
+                            nulls_first: sort.nulls_first,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let column_defaults = cmd
+            .column_defaults
+            .iter()
+            .map(|(column, expr)| Ok((column.clone(), expr_to_sql(expr)?.to_string())))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            name,
+            location: cmd.location.clone(),
+            file_type: cmd.file_type.clone(),
+            schema: Arc::clone(cmd.schema.inner()),
+            table_partition_cols: cmd.table_partition_cols.clone(),
+            order_exprs,
+            unbounded: cmd.unbounded,
+            options: cmd.options.clone(),
+            constraints: cmd.constraints.clone(),
+            column_defaults,
+        })
+    }
+
+    /// Plans the `CREATE EXTERNAL TABLE` statement of the table again
+    pub fn to_plan(&self, state: &SessionState) -> Result<CreateExternalTable> {
+        let df_schema = DFSchema::try_from(self.schema.as_ref().clone())?;
+        let order_exprs = self
+            .order_exprs
+            .iter()
+            .map(|ordering| {
+                ordering
+                    .iter()
+                    .map(|sort| {
+                        let expr = state.create_logical_expr(&sort.expr, &df_schema)?;
+                        Ok(SortExpr::new(expr, sort.asc, sort.nulls_first))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let column_defaults = self
+            .column_defaults
+            .iter()
+            .map(|(column, expr)| {
+                Ok((column.clone(), state.create_logical_expr(expr, &df_schema)?))
+            })
+            .collect::<Result<_>>()?;
+        Ok(CreateExternalTable {
+            schema: Arc::new(df_schema),
+            name: TableReference::from(self.name.clone()),
+            location: self.location.clone(),
+            file_type: self.file_type.clone(),
+            table_partition_cols: self.table_partition_cols.clone(),
+            if_not_exists: false,
+            temporary: false,
+            definition: None,
+            order_exprs,
+            unbounded: self.unbounded,
+            options: self.options.clone(),
+            constraints: self.constraints.clone(),
+            column_defaults,
+        })
+    }
+}
+
+/// How [`JsonFileCatalogStore`] stores the options of external tables that
+/// hold secrets: the options whose key contains `secret`, `password`,
+/// `token`, `credential`, `access_key` or `private_key`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SecretOptions {
+    /// Leave the options out. The tables are created again without them,
+    /// so the credentials must be provided another way, for example by
+    /// registering the object store of the tables.
+    #[default]
+    Redact,
+    /// Store the options as they are
+    PassThrough,
+}
+
+/// A [`CatalogStore`] storing the definitions in a JSON file
+///
+/// The file is created on the first save, and replaced as a whole on
+/// every change.
+#[derive(Debug)]
+pub struct JsonFileCatalogStore {
+    path: PathBuf,
+    secret_options: SecretOptions,
+    /// Serializes the reads and writes of the file
+    lock: Mutex<()>,
+}
+
+impl JsonFileCatalogStore {
+    /// Creates a store of the definitions in the JSON file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            secret_options: SecretOptions::default(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Sets how the options of external tables holding secrets are stored.
+    /// Defaults to [`SecretOptions::Redact`].
+    pub fn with_secret_options(mut self, secret_options: SecretOptions) -> Self {
+        self.secret_options = secret_options;
+        self
+    }
+
+    /// Returns the path of the JSON file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read(&self) -> Result<Vec<StoredDefinition>> {
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+        let contents = std::fs::read(&self.path)?;
+        let value: Value = serde_json::from_slice(&contents)
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        let invalid = |e: DataFusionError| {
+            exec_datafusion_err!("Invalid catalog store {}: {e}", self.path.display())
+        };
+        let definitions = value
+            .get("definitions")
+            .and_then(Value::as_array)
+            .ok_or_else(|| invalid(exec_datafusion_err!("missing definitions")))?;
+        definitions
+            .iter()
+            .map(definition_from_json)
+            .collect::<Result<_>>()
+            .map_err(invalid)
+    }
+
+    fn write(&self, definitions: &[StoredDefinition]) -> Result<()> {
+        let definitions = definitions
+            .iter()
+            .map(|definition| definition_to_json(definition, self.secret_options))
+            .collect::<Vec<_>>();
+        let contents = serde_json::to_vec_pretty(
+            &json!({ "version": 1, "definitions": definitions }),
+        )
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        // Write to a temporary file first so that the file is never left
+        // half written
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        std::fs::write(&tmp_path, contents)?;
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CatalogStore for JsonFileCatalogStore {
+    async fn load(&self) -> Result<Vec<StoredDefinition>> {
+        let _guard = self.lock.lock();
+        self.read()
+    }
+
+    async fn save(&self, definition: StoredDefinition) -> Result<()> {
+        let _guard = self.lock.lock();
+        let mut definitions = self.read()?;
+        match definitions
+            .iter_mut()
+            .find(|stored| stored.name() == definition.name())
+        {
+            Some(stored) => *stored = definition,
+            None => definitions.push(definition),
+        }
+        self.write(&definitions)
+    }
+
+    async fn remove(&self, name: &ResolvedTableReference) -> Result<()> {
+        let _guard = self.lock.lock();
+        let mut definitions = self.read()?;
+        let len = definitions.len();
+        definitions.retain(|stored| stored.name() != name);
+        if definitions.len() != len {
+            self.write(&definitions)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_secret_option(key: &str) -> bool {
+    let key = key.to_lowercase();
+    [
+        "secret",
+        "password",
+        "token",
+        "credential",
+        "access_key",
+        "private_key",
+    ]
+    .iter()
+    .any(|pattern| key.contains(pattern))
+}
+
+fn definition_to_json(definition: &StoredDefinition, secrets: SecretOptions) -> Value {
+    let name = definition.name();
+    let mut value = json!({
+        "catalog": name.catalog.as_ref(),
+        "schema": name.schema.as_ref(),
+        "table": name.table.as_ref(),
+    });
+    let fields = value.as_object_mut().unwrap();
+    match definition {
+        StoredDefinition::View(view) => {
+            fields.insert("kind".into(), json!("view"));
+            fields.insert("definition".into(), json!(view.definition));
+        }
+        StoredDefinition::ExternalTable(table) => {
+            let columns = table
+                .schema
+                .fields()
+                .iter()
+                .map(|field| {
+                    json!({
+                        "name": field.name(),
+                        "data_type": field.data_type().to_string(),
+                        "nullable": field.is_nullable(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            let order_exprs = table
+                .order_exprs
+                .iter()
+                .map(|ordering| {
+                    ordering
+                        .iter()
+                        .map(|sort| {
+                            json!({
+                                "expr": sort.expr,
+                                "asc": sort.asc,
+                                "nulls_first": sort.nulls_first,
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let constraints = table
+                .constraints
+                .iter()
+                .map(|constraint| match constraint {
+                    Constraint::PrimaryKey(indices) => json!({ "primary_key": indices }),
+                    Constraint::Unique(indices) => json!({ "unique": indices }),
+                })
+                .collect::<Vec<_>>();
+            let (options, redacted): (Map<_, _>, Vec<_>) = match secrets {
+                SecretOptions::PassThrough => (
+                    table
+                        .options
+                        .iter()
+                        .map(|(key, value)| (key.clone(), json!(value)))
+                        .collect(),
+                    vec![],
+                ),
+                SecretOptions::Redact => {
+                    let mut options = Map::new();
+                    let mut redacted = vec![];
+                    for (key, value) in &table.options {
+                        if is_secret_option(key) {
+                            redacted.push(key.clone());
+                        } else {
+                            options.insert(key.clone(), json!(value));
+                        }
+                    }
+                    redacted.sort();
+                    (options, redacted)
+                }
+            };
+            fields.insert("kind".into(), json!("external_table"));
+            fields.insert("location".into(), json!(table.location));
+            fields.insert("file_type".into(), json!(table.file_type));
+            fields.insert("columns".into(), json!(columns));
+            fields.insert(
+                "table_partition_cols".into(),
+                json!(table.table_partition_cols),
+            );
+            fields.insert("order_exprs".into(), json!(order_exprs));
+            fields.insert("unbounded".into(), json!(table.unbounded));
+            fields.insert("options".into(), Value::Object(options));
+            fields.insert("redacted_options".into(), json!(redacted));
+            fields.insert("constraints".into(), json!(constraints));
+            fields.insert("column_defaults".into(), json!(table.column_defaults));
+        }
+    }
+    value
+}
+
+fn definition_from_json(value: &Value) -> Result<StoredDefinition> {
+    let name = ResolvedTableReference {
+        catalog: get_str(value, "catalog")?.into(),
+        schema: get_str(value, "schema")?.into(),
+        table: get_str(value, "table")?.into(),
+    };
+    match get_str(value, "kind")? {
+        "view" => Ok(StoredDefinition::View(StoredView {
+            name,
+            definition: get_str(value, "definition")?.to_string(),
+        })),
+        "external_table" => {
+            let fields = get_array(value, "columns")?
+                .iter()
+                .map(|column| {
+                    let data_type = DataType::from_str(get_str(column, "data_type")?)?;
+                    let nullable = get(column, "nullable")?.as_bool().unwrap_or(true);
+                    Ok(Field::new(get_str(column, "name")?, data_type, nullable))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let order_exprs = get_array(value, "order_exprs")?
+                .iter()
+                .map(|ordering| {
+                    as_array(ordering, "order_exprs")?
+                        .iter()
+                        .map(|sort| {
+                            Ok(StoredSortExpr {
+                                expr: get_str(sort, "expr")?.to_string(),
+                                asc: get_bool(sort, "asc")?,
+                                nulls_first: get_bool(sort, "nulls_first")?,
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let constraints = get_array(value, "constraints")?
+                .iter()
+                .map(|constraint| {
+                    if let Some(indices) = constraint.get("primary_key") {
+                        Ok(Constraint::PrimaryKey(as_indices(indices)?))
+                    } else if let Some(indices) = constraint.get("unique") {
+                        Ok(Constraint::Unique(as_indices(indices)?))
+                    } else {
+                        Err(exec_datafusion_err!("unknown constraint {constraint}"))
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(StoredDefinition::ExternalTable(StoredExternalTable {
+                name,
+                location: get_str(value, "location")?.to_string(),
+                file_type: get_str(value, "file_type")?.to_string(),
+                schema: Arc::new(Schema::new(fields)),
+                table_partition_cols: get_strings(value, "table_partition_cols")?,
+                order_exprs,
+                unbounded: get_bool(value, "unbounded")?,
+                options: get_string_map(value, "options")?,
+                constraints: Constraints::new_unverified(constraints),
+                column_defaults: get_string_map(value, "column_defaults")?,
+            }))
+        }
+        kind => Err(exec_datafusion_err!("unknown definition kind '{kind}'")),
+    }
+}
+
+fn get<'a>(value: &'a Value, key: &str) -> Result<&'a Value> {
+    value
+        .get(key)
+        .ok_or_else(|| exec_datafusion_err!("missing field '{key}'"))
+}
+
+fn get_str<'a>(value: &'a Value, key: &str) -> Result<&'a str> {
+    get(value, key)?
+        .as_str()
+        .ok_or_else(|| exec_datafusion_err!("field '{key}' is not a string"))
+}
+
+fn get_bool(value: &Value, key: &str) -> Result<bool> {
+    get(value, key)?
+        .as_bool()
+        .ok_or_else(|| exec_datafusion_err!("field '{key}' is not a boolean"))
+}
+
+fn get_array<'a>(value: &'a Value, key: &str) -> Result<&'a Vec<Value>> {
+    as_array(get(value, key)?, key)
+}
+
+fn as_array<'a>(value: &'a Value, key: &str) -> Result<&'a Vec<Value>> {
+    value
+        .as_array()
+        .ok_or_else(|| exec_datafusion_err!("field '{key}' is not an array"))
+}
+
+fn as_indices(value: &Value) -> Result<Vec<usize>> {
+    as_array(value, "constraints")?
+        .iter()
+        .map(|index| {
+            index
+                .as_u64()
+                .map(|index| index as usize)
+                .ok_or_else(|| exec_datafusion_err!("invalid column index {index}"))
+        })
+        .collect()
+}
+
+fn get_strings(value: &Value, key: &str) -> Result<Vec<String>> {
+    get_array(value, key)?
+        .iter()
+        .map(|item| {
+            item.as_str().map(str::to_string).ok_or_else(|| {
+                exec_datafusion_err!("field '{key}' has a non string item")
+            })
+        })
+        .collect()
+}
+
+fn get_string_map(value: &Value, key: &str) -> Result<HashMap<String, String>> {
+    get(value, key)?
+        .as_object()
+        .ok_or_else(|| exec_datafusion_err!("field '{key}' is not an object"))?
+        .iter()
+        .map(|(name, item)| {
+            let item = item.as_str().ok_or_else(|| {
+                exec_datafusion_err!("field '{key}' has a non string value")
+            })?;
+            Ok((name.clone(), item.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::context::SessionContext;
+    use datafusion_common::assert_batches_eq;
+
+    #[tokio::test]
+    async fn round_trip_view_and_external_table() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        let store_path = tmp_dir.path().join("catalog.json");
+        let csv_path = tmp_dir.path().join("data.csv");
+        std::fs::write(&csv_path, "a,b\n1,x\n2,y\n3,z\n")?;
+
+        let ctx = SessionContext::new()
+            .with_catalog_store(Arc::new(JsonFileCatalogStore::new(&store_path)));
+        ctx.sql(&format!(
+            "CREATE EXTERNAL TABLE t (a BIGINT NOT NULL, b VARCHAR) STORED AS CSV \
+             WITH ORDER (a DESC) LOCATION '{}' OPTIONS ('format.has_header' 'true')",
+            csv_path.display()
+        ))
+        .await?;
+        ctx.sql("CREATE SCHEMA s").await?;
+        ctx.sql("CREATE VIEW s.v AS SELECT a * 10 AS a10, b FROM t WHERE a > 1")
+            .await?;
+        ctx.sql("CREATE VIEW dropped AS SELECT 1").await?;
+        ctx.sql("DROP VIEW dropped").await?;
+
+        // A new context gets the view and the table back from the store
+        let ctx = SessionContext::new()
+            .with_catalog_store(Arc::new(JsonFileCatalogStore::new(&store_path)));
+        ctx.refresh_catalogs().await?;
+
+        let batches = ctx
+            .sql("SELECT * FROM s.v ORDER BY a10")
+            .await?
+            .collect()
+            .await?;
+        assert_batches_eq!(
+            [
+                "+-----+---+",
+                "| a10 | b |",
+                "+-----+---+",
+                "| 20  | y |",
+                "| 30  | z |",
+                "+-----+---+",
+            ],
+            &batches
+        );
+        let batches = ctx.sql("SELECT count(*) FROM t").await?.collect().await?;
+        assert_batches_eq!(
+            [
+                "+----------+",
+                "| count(*) |",
+                "+----------+",
+                "| 3        |",
+                "+----------+",
+            ],
+            &batches
+        );
+        assert!(ctx.table("dropped").await.is_err());
+
+        let definitions = JsonFileCatalogStore::new(&store_path).load().await?;
+        assert_eq!(definitions.len(), 2);
+        let StoredDefinition::ExternalTable(table) = &definitions[0] else {
+            panic!("expected an external table, got {:?}", definitions[0]);
+        };
+        assert_eq!(table.name.to_string(), "datafusion.public.t");
+        assert_eq!(
+            table.order_exprs,
+            vec![vec![StoredSortExpr {
+                expr: "a".to_string(),
+                asc: false,
+                nulls_first: true,
+            }]]
+        );
+        assert_eq!(table.options["format.has_header"], "true");
+        assert_eq!(definitions[1].name().to_string(), "datafusion.s.v");
+
+        // Dropping the schema drops the definitions of its views
+        ctx.sql("DROP SCHEMA s CASCADE").await?;
+        let definitions = JsonFileCatalogStore::new(&store_path).load().await?;
+        assert_eq!(definitions.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn secret_options() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        let table = StoredExternalTable {
+            name: TableReference::bare("t").resolve("datafusion", "public"),
+            location: "s3://bucket/t/".to_string(),
+            file_type: "PARQUET".to_string(),
+            schema: Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)])),
+            table_partition_cols: vec![],
+            order_exprs: vec![],
+            unbounded: false,
+            options: HashMap::from([
+                ("aws.region".to_string(), "us-east-1".to_string()),
+                ("aws.secret_access_key".to_string(), "hunter2".to_string()),
+            ]),
+            constraints: Constraints::new_unverified(vec![Constraint::PrimaryKey(vec![
+                0,
+            ])]),
+            column_defaults: HashMap::new(),
+        };
+
+        // Secrets are left out of the file by default
+        let store = JsonFileCatalogStore::new(tmp_dir.path().join("redacted.json"));
+        store
+            .save(StoredDefinition::ExternalTable(table.clone()))
+            .await?;
+        let contents = std::fs::read_to_string(store.path())?;
+        assert!(!contents.contains("hunter2"), "{contents}");
+        let mut expected = table.clone();
+        expected.options.remove("aws.secret_access_key");
+        assert_eq!(
+            store.load().await?,
+            vec![StoredDefinition::ExternalTable(expected)]
+        );
+
+        let store = JsonFileCatalogStore::new(tmp_dir.path().join("secrets.json"))
+            .with_secret_options(SecretOptions::PassThrough);
+        store
+            .save(StoredDefinition::ExternalTable(table.clone()))
+            .await?;
+        assert_eq!(
+            store.load().await?,
+            vec![StoredDefinition::ExternalTable(table)]
+        );
+        Ok(())
+    }
+}
@@ -23,6 +23,9 @@ use std::sync::{Arc, Weak};
 
 use super::options::ReadOptions;
 use crate::datasource::dynamic_file::DynamicListTableFactory;
+use crate::execution::catalog_store::{
+    CatalogStore, StoredDefinition, StoredExternalTable, StoredView,
+};
 use crate::execution::session_state::SessionStateBuilder;
 use crate::{
     catalog::listing_schema::ListingSchemaProvider,
@@ -33,7 +36,9 @@ use crate::{
     datasource::listing::{
         ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
     },
-    datasource::{provider_as_source, MemTable, ViewTable},
+    datasource::{
+        provider::DefaultTableFactory, provider_as_source, MemTable, ViewTable,
+    },
     error::{DataFusionError, Result},
     execution::{
         options::ArrowReadOptions,
@@ -43,13 +48,13 @@ use crate::{
     logical_expr::AggregateUDF,
     logical_expr::ScalarUDF,
     logical_expr::{
-        CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
-        CreateMemoryTable, CreateView, DropCatalogSchema, DropFunction, DropTable,
-        DropView, Execute, LogicalPlan, LogicalPlanBuilder, Prepare, SetVariable,
-        TableType, UNNAMED_TABLE,
+        CommentOn, CreateCatalog, CreateCatalogSchema, CreateExternalTable,
+        CreateFunction, CreateMemoryTable, CreateView, DropCatalog, DropCatalogSchema,
+        DropFunction, DropTable, DropView, Execute, LogicalPlan, LogicalPlanBuilder,
+        Prepare, SetVariable, TableType, UNNAMED_TABLE,
     },
     physical_expr::PhysicalExpr,
-    physical_plan::ExecutionPlan,
+    physical_plan::{execute_stream, ExecutionPlan, SendableRecordBatchStream},
     variable::{VarProvider, VarType},
 };
 
@@ -61,16 +66,20 @@ use arrow::record_batch::RecordBatch;
 use datafusion_catalog::memory::MemorySchemaProvider;
 use datafusion_catalog::MemoryCatalogProvider;
 use datafusion_catalog::{
-    DynamicFileCatalog, TableFunction, TableFunctionImpl, UrlTableFactory,
+    CommentedTable, DynamicFileCatalog, TableFunction, TableFunctionImpl, UrlTableFactory,
 };
 use datafusion_common::config::ConfigOptions;
 use datafusion_common::{
+    cancellation::CancellationToken,
     config::{ConfigExtension, TableOptions},
     exec_datafusion_err, exec_err, not_impl_err, plan_datafusion_err, plan_err,
     tree_node::{TreeNodeRecursion, TreeNodeVisitor},
-    DFSchema, ParamValues, ScalarValue, SchemaReference, TableReference,
+    DFSchema, ParamValues, ResolvedTableReference, ScalarValue, SchemaReference,
+    TableReference,
 };
+use datafusion_execution::cache::result_cache::ResultCache;
 pub use datafusion_execution::config::SessionConfig;
+use datafusion_execution::progress::ProgressTracker;
 use datafusion_execution::registry::SerializerRegistry;
 pub use datafusion_execution::TaskContext;
 pub use datafusion_expr::execution_props::ExecutionProps;
@@ -293,26 +302,6 @@ impl SessionContext {
         Self::new_with_config(SessionConfig::new())
     }
 
-    /// Finds any [`ListingSchemaProvider`]s and instructs them to reload tables from "disk"
-    pub async fn refresh_catalogs(&self) -> Result<()> {
-        let cat_names = self.catalog_names().clone();
-        for cat_name in cat_names.iter() {
-            let cat = self.catalog(cat_name.as_str()).ok_or_else(|| {
-                DataFusionError::Internal("Catalog not found!".to_string())
-            })?;
-            for schema_name in cat.schema_names() {
-                let schema = cat.schema(schema_name.as_str()).ok_or_else(|| {
-                    DataFusionError::Internal("Schema not found!".to_string())
-                })?;
-                let lister = schema.as_any().downcast_ref::<ListingSchemaProvider>();
-                if let Some(lister) = lister {
-                    lister.refresh(&self.state()).await?;
-                }
-            }
-        }
-        Ok(())
-    }
-
     /// Creates a new `SessionContext` using the provided
     /// [`SessionConfig`] and a new [`RuntimeEnv`].
     ///
@@ -464,6 +453,83 @@ impl SessionContext {
         self
     }
 
+    /// Registers a [`CatalogStore`] to persist the views and external tables
+    /// created by `CREATE VIEW` and `CREATE EXTERNAL TABLE` statements.
+    ///
+    /// Call [`Self::refresh_catalogs`] to register the stored views and
+    /// tables, for example when the application starts.
+    pub fn with_catalog_store(self, catalog_store: Arc<dyn CatalogStore>) -> Self {
+        self.state.write().set_catalog_store(catalog_store);
+        self
+    }
+
+    /// Registers a [`ResultCache`] storing the results of the queries of this
+    /// context, which are then reused while the tables they scan are
+    /// unchanged.
+    ///
+    /// Only queries calling immutable functions, and scanning tables
+    /// returning a [`TableProvider::snapshot_token`], are cached. `EXPLAIN
+    /// ANALYZE` shows whether the result of a query was found in the cache.
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use std::time::Duration;
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::execution::cache::result_cache::ResultCache;
+    /// let ctx = SessionContext::new();
+    /// let memory_pool = &ctx.runtime_env().memory_pool;
+    /// let cache = ResultCache::new(64 * 1024 * 1024, Duration::from_secs(60), memory_pool);
+    /// let ctx = ctx.with_result_cache(Arc::new(cache));
+    /// ```
+    pub fn with_result_cache(self, result_cache: Arc<ResultCache>) -> Self {
+        self.state.write().set_result_cache(result_cache);
+        self
+    }
+
+    /// Finds any [`ListingSchemaProvider`]s and instructs them to reload tables from "disk",
+    /// then registers the views and external tables stored in the [`CatalogStore`]
+    /// of this context, replacing the tables of the same names. The catalogs
+    /// and schemas of the stored views and tables are created if needed.
+    pub async fn refresh_catalogs(&self) -> Result<()> {
+        let cat_names = self.catalog_names().clone();
+        for cat_name in cat_names.iter() {
+            let cat = self.catalog(cat_name.as_str()).ok_or_else(|| {
+                DataFusionError::Internal("Catalog not found!".to_string())
+            })?;
+            for schema_name in cat.schema_names() {
+                let schema = cat.schema(schema_name.as_str()).ok_or_else(|| {
+                    DataFusionError::Internal("Schema not found!".to_string())
+                })?;
+                let lister = schema.as_any().downcast_ref::<ListingSchemaProvider>();
+                if let Some(lister) = lister {
+                    lister.refresh(&self.state()).await?;
+                }
+            }
+        }
+        let Some(catalog_store) = self.catalog_store() else {
+            return Ok(());
+        };
+        let mut pending = catalog_store.load().await?;
+        // Views may select from the tables and views stored after them, so
+        // the definitions are registered until none of the rest can be
+        while !pending.is_empty() {
+            let num_pending = pending.len();
+            let mut failed = vec![];
+            let mut first_error = None;
+            for definition in pending {
+                if let Err(e) = self.register_stored_definition(&definition).await {
+                    first_error.get_or_insert(e);
+                    failed.push(definition);
+                }
+            }
+            if failed.len() == num_pending {
+                return Err(first_error.unwrap());
+            }
+            pending = failed;
+        }
+        Ok(())
+    }
+
     /// Adds an optimizer rule to the end of the existing rules.
     ///
     /// See [`SessionState`] for more control of when the rule is applied.
@@ -688,6 +754,10 @@ impl SessionContext {
                     DdlStatement::DropCatalogSchema(cmd) => {
                         Box::pin(self.drop_schema(cmd)).await
                     }
+                    DdlStatement::DropCatalog(cmd) => {
+                        Box::pin(self.drop_catalog(cmd)).await
+                    }
+                    DdlStatement::CommentOn(cmd) => Box::pin(self.comment_on(cmd)).await,
                     DdlStatement::CreateFunction(cmd) => {
                         Box::pin(self.create_function(cmd)).await
                     }
@@ -783,7 +853,8 @@ impl SessionContext {
         &self,
         cmd: &CreateExternalTable,
     ) -> Result<DataFrame> {
-        let exist = self.table_exist(cmd.name.clone())?;
+        let schema = self.state.read().schema_for_ref(cmd.name.clone())?;
+        let exist = schema.table_exists(cmd.name.table()).await?;
 
         if cmd.temporary {
             return not_impl_err!("Temporary tables not supported");
@@ -801,6 +872,13 @@ impl SessionContext {
         let table_provider: Arc<dyn TableProvider> =
             self.create_custom_table(cmd).await?;
         self.register_table(cmd.name.clone(), table_provider)?;
+        if let Some(catalog_store) = self.catalog_store() {
+            let name = self.state.read().resolve_table_ref(cmd.name.clone());
+            let table = StoredExternalTable::try_new(name, cmd)?;
+            catalog_store
+                .save(StoredDefinition::ExternalTable(table))
+                .await?;
+        }
         self.return_empty_dataframe()
     }
 
@@ -812,41 +890,47 @@ impl SessionContext {
             or_replace,
             constraints,
             column_defaults,
+            generated_columns,
             temporary,
         } = cmd;
 
         let input = Arc::unwrap_or_clone(input);
         let input = self.state().optimize(&input)?;
 
-        if temporary {
-            return not_impl_err!("Temporary tables not supported");
-        }
-
-        let table = self.table(name.clone()).await;
-        match (if_not_exists, or_replace, table) {
-            (true, false, Ok(_)) => self.return_empty_dataframe(),
-            (false, true, Ok(_)) => {
-                self.deregister_table(name.clone())?;
-                let schema = Arc::new(input.schema().as_ref().into());
-                let physical = DataFrame::new(self.state(), input);
-
-                let batches: Vec<_> = physical.collect_partitioned().await?;
-                let table = Arc::new(
-                    // pass constraints and column defaults to the mem table.
-                    MemTable::try_new(schema, batches)?
-                        .with_constraints(constraints)
-                        .with_column_defaults(column_defaults.into_iter().collect()),
+        // Temporary tables live in a namespace of their own, addressed by
+        // unqualified names only
+        let temporary_tables = if temporary {
+            if !matches!(name, TableReference::Bare { .. }) {
+                return plan_err!(
+                    "Temporary table '{name}' can not be created in a schema"
                 );
-
-                self.register_table(name.clone(), table)?;
-                self.return_empty_dataframe()
             }
-            (true, true, Ok(_)) => {
+            Some(self.state.read().temporary_tables())
+        } else {
+            None
+        };
+        let exists = match &temporary_tables {
+            Some(tables) => tables.table_exist(name.table()),
+            None => {
+                let schema = self.state.read().schema_for_ref(name.clone())?;
+                schema.table_exists(name.table()).await?
+            }
+        };
+
+        match (if_not_exists, or_replace, exists) {
+            (true, false, true) => self.return_empty_dataframe(),
+            (true, true, true) => {
                 exec_err!("'IF NOT EXISTS' cannot coexist with 'REPLACE'")
             }
-            (_, _, Err(_)) => {
-                let df_schema = input.schema();
-                let schema = Arc::new(df_schema.as_ref().into());
+            (false, false, true) => exec_err!("Table '{name}' already exists"),
+            (_, _, _) => {
+                if exists {
+                    match &temporary_tables {
+                        Some(tables) => tables.deregister_table(name.table())?,
+                        None => self.deregister_table(name.clone())?,
+                    };
+                }
+                let schema = Arc::new(input.schema().as_ref().into());
                 let physical = DataFrame::new(self.state(), input);
 
                 let batches: Vec<_> = physical.collect_partitioned().await?;
@@ -854,13 +938,18 @@ impl SessionContext {
                     // pass constraints and column defaults to the mem table.
                     MemTable::try_new(schema, batches)?
                         .with_constraints(constraints)
-                        .with_column_defaults(column_defaults.into_iter().collect()),
+                        .with_column_defaults(column_defaults.into_iter().collect())
+                        .with_generated_columns(generated_columns.into_iter().collect()),
                 );
 
-                self.register_table(name, table)?;
+                match temporary_tables {
+                    Some(tables) => {
+                        tables.register_table(name.table().to_string(), table)?
+                    }
+                    None => self.register_table(name, table)?,
+                };
                 self.return_empty_dataframe()
             }
-            (false, false, Ok(_)) => exec_err!("Table '{name}' already exists"),
         }
     }
 
@@ -892,19 +981,22 @@ impl SessionContext {
         match (or_replace, view) {
             (true, Ok(_)) => {
                 self.deregister_table(name.clone())?;
-                let input = Self::apply_type_coercion(input.as_ref().clone())?;
-                let table = Arc::new(ViewTable::new(input, definition));
-                self.register_table(name, table)?;
-                self.return_empty_dataframe()
             }
-            (_, Err(_)) => {
-                let input = Self::apply_type_coercion(input.as_ref().clone())?;
-                let table = Arc::new(ViewTable::new(input, definition));
-                self.register_table(name, table)?;
-                self.return_empty_dataframe()
-            }
-            (false, Ok(_)) => exec_err!("Table '{name}' already exists"),
+            (_, Err(_)) => {}
+            (false, Ok(_)) => return exec_err!("Table '{name}' already exists"),
+        }
+        let input = Self::apply_type_coercion(input.as_ref().clone())?;
+        let table = Arc::new(ViewTable::new(input, definition.clone()));
+        self.register_table(name.clone(), table)?;
+        if let (Some(catalog_store), Some(definition)) =
+            (self.catalog_store(), definition)
+        {
+            let name = self.state.read().resolve_table_ref(name);
+            catalog_store
+                .save(StoredDefinition::View(StoredView { name, definition }))
+                .await?;
         }
+        self.return_empty_dataframe()
     }
 
     async fn create_catalog_schema(&self, cmd: CreateCatalogSchema) -> Result<DataFrame> {
@@ -976,11 +1068,21 @@ impl SessionContext {
         let DropTable {
             name, if_exists, ..
         } = cmd;
+        // A temporary table shadows the permanent table of the same name
+        if let TableReference::Bare { table } = &name {
+            let temporary_tables = self.state.read().temporary_tables();
+            if temporary_tables.deregister_table(table)?.is_some() {
+                return self.return_empty_dataframe();
+            }
+        }
         let result = self
             .find_and_deregister(name.clone(), TableType::Base)
             .await;
         match (result, if_exists) {
-            (Ok(true), _) => self.return_empty_dataframe(),
+            (Ok(true), _) => {
+                self.remove_stored_definition(name).await?;
+                self.return_empty_dataframe()
+            }
             (_, true) => self.return_empty_dataframe(),
             (_, _) => exec_err!("Table '{name}' doesn't exist."),
         }
@@ -994,12 +1096,56 @@ impl SessionContext {
             .find_and_deregister(name.clone(), TableType::View)
             .await;
         match (result, if_exists) {
-            (Ok(true), _) => self.return_empty_dataframe(),
+            (Ok(true), _) => {
+                self.remove_stored_definition(name).await?;
+                self.return_empty_dataframe()
+            }
             (_, true) => self.return_empty_dataframe(),
             (_, _) => exec_err!("View '{name}' doesn't exist."),
         }
     }
 
+    async fn comment_on(&self, cmd: CommentOn) -> Result<DataFrame> {
+        let CommentOn {
+            table,
+            column,
+            comment,
+            if_exists,
+            ..
+        } = cmd;
+        let temporary_tables = self.state.read().temporary_tables();
+        let schema = match &table {
+            TableReference::Bare { table } if temporary_tables.table_exist(table) => {
+                temporary_tables
+            }
+            _ => self.state.read().schema_for_ref(table.clone())?,
+        };
+        let name = table.table();
+        let Some(provider) = schema.table(name).await? else {
+            if if_exists {
+                return self.return_empty_dataframe();
+            }
+            return exec_err!("Table '{table}' doesn't exist.");
+        };
+        if let Some(column) = &column {
+            if provider.schema().field_with_name(column).is_err() {
+                return plan_err!("Column '{column}' not found in table '{table}'");
+            }
+        }
+
+        let (column, comment) = (column.as_deref(), comment.as_deref());
+        // Tables that can't store comments themselves are wrapped to keep them
+        let updated = match provider.with_comment(column, comment)? {
+            Some(updated) => updated,
+            None => CommentedTable::new(provider)
+                .with_comment(column, comment)?
+                .ok_or_else(|| exec_datafusion_err!("Could not comment on '{table}'"))?,
+        };
+        schema.deregister_table(name)?;
+        schema.register_table(name.to_string(), updated)?;
+        self.return_empty_dataframe()
+    }
+
     async fn drop_schema(&self, cmd: DropCatalogSchema) -> Result<DataFrame> {
         let DropCatalogSchema {
             name,
@@ -1007,7 +1153,7 @@ impl SessionContext {
             cascade,
             schema: _,
         } = cmd;
-        let catalog = {
+        let (catalog_name, catalog) = {
             let state = self.state.read();
             let catalog_name = match &name {
                 SchemaReference::Full { catalog, .. } => catalog.to_string(),
@@ -1016,7 +1162,7 @@ impl SessionContext {
                 }
             };
             if let Some(catalog) = state.catalog_list().catalog(&catalog_name) {
-                catalog
+                (catalog_name, catalog)
             } else if allow_missing {
                 return self.return_empty_dataframe();
             } else {
@@ -1027,7 +1173,14 @@ impl SessionContext {
         match (dereg, allow_missing) {
             (None, true) => self.return_empty_dataframe(),
             (None, false) => self.schema_doesnt_exist_err(name),
-            (Some(_), _) => self.return_empty_dataframe(),
+            (Some(_), _) => {
+                self.remove_stored_definitions(|stored| {
+                    *stored.catalog == catalog_name
+                        && *stored.schema == *name.schema_name()
+                })
+                .await?;
+                self.return_empty_dataframe()
+            }
         }
     }
 
@@ -1035,6 +1188,116 @@ impl SessionContext {
         exec_err!("Schema '{schemaref}' doesn't exist.")
     }
 
+    async fn drop_catalog(&self, cmd: DropCatalog) -> Result<DataFrame> {
+        let DropCatalog {
+            catalog_name,
+            if_exists,
+            cascade,
+            schema: _,
+        } = cmd;
+        let catalog_list = {
+            let state = self.state.read();
+            if state.config_options().catalog.default_catalog == catalog_name {
+                return exec_err!("Cannot drop the default catalog '{catalog_name}'");
+            }
+            Arc::clone(state.catalog_list())
+        };
+        let Some(catalog) = catalog_list.catalog(&catalog_name) else {
+            return if if_exists {
+                self.return_empty_dataframe()
+            } else {
+                exec_err!("Catalog '{catalog_name}' doesn't exist.")
+            };
+        };
+        let schema_names = catalog.schema_names();
+        if !cascade && !schema_names.is_empty() {
+            return exec_err!(
+                "Cannot drop catalog {} because other schemas depend on it: {}",
+                catalog_name,
+                itertools::join(schema_names.iter(), ", ")
+            );
+        }
+        for schema_name in &schema_names {
+            catalog.deregister_schema(schema_name, true)?;
+        }
+        catalog_list.deregister_catalog(&catalog_name)?;
+        self.remove_stored_definitions(|stored| *stored.catalog == catalog_name)
+            .await?;
+        self.return_empty_dataframe()
+    }
+
+    fn catalog_store(&self) -> Option<Arc<dyn CatalogStore>> {
+        self.state.read().catalog_store().cloned()
+    }
+
+    /// Removes the definition of the view or table `name` from the
+    /// [`CatalogStore`], if any
+    async fn remove_stored_definition(&self, name: TableReference) -> Result<()> {
+        if let Some(catalog_store) = self.catalog_store() {
+            let name = self.state.read().resolve_table_ref(name);
+            catalog_store.remove(&name).await?;
+        }
+        Ok(())
+    }
+
+    /// Removes the definitions of the views and tables whose names match
+    /// `predicate` from the [`CatalogStore`], if any
+    async fn remove_stored_definitions(
+        &self,
+        predicate: impl Fn(&ResolvedTableReference) -> bool,
+    ) -> Result<()> {
+        if let Some(catalog_store) = self.catalog_store() {
+            for definition in catalog_store.load().await? {
+                if predicate(definition.name()) {
+                    catalog_store.remove(definition.name()).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Registers a view or table loaded from the [`CatalogStore`]
+    async fn register_stored_definition(
+        &self,
+        definition: &StoredDefinition,
+    ) -> Result<()> {
+        let name = definition.name();
+        let catalog = match self.catalog(&name.catalog) {
+            Some(catalog) => catalog,
+            None => {
+                let catalog: Arc<dyn CatalogProvider> =
+                    Arc::new(MemoryCatalogProvider::new());
+                self.register_catalog(name.catalog.to_string(), Arc::clone(&catalog));
+                catalog
+            }
+        };
+        if catalog.schema(&name.schema).is_none() {
+            catalog
+                .register_schema(&name.schema, Arc::new(MemorySchemaProvider::new()))?;
+        }
+
+        let table: Arc<dyn TableProvider> = match definition {
+            StoredDefinition::View(view) => {
+                let plan = self.state().create_logical_plan(&view.definition).await?;
+                let LogicalPlan::Ddl(DdlStatement::CreateView(cmd)) = plan else {
+                    return exec_err!(
+                        "Stored definition of view {name} is not a CREATE VIEW statement"
+                    );
+                };
+                let input = Self::apply_type_coercion(Arc::unwrap_or_clone(cmd.input))?;
+                Arc::new(ViewTable::new(input, cmd.definition))
+            }
+            StoredDefinition::ExternalTable(table) => {
+                let cmd = table.to_plan(&self.state())?;
+                self.create_custom_table(&cmd).await?
+            }
+        };
+        let name = TableReference::from(name.clone());
+        self.deregister_table(name.clone())?;
+        self.register_table(name, table)?;
+        Ok(())
+    }
+
     async fn set_variable(&self, stmt: SetVariable) -> Result<DataFrame> {
         let SetVariable {
             variable, value, ..
@@ -1067,6 +1330,20 @@ impl SessionContext {
                     .with_runtime_env(Arc::new(builder.build()?))
                     .build();
             }
+            "object_store_requests_per_second" => {
+                let requests_per_second: u32 = value.parse().map_err(|_| {
+                    DataFusionError::Plan(format!(
+                        "Failed to parse object store requests per second '{value}'"
+                    ))
+                })?;
+
+                let mut state = self.state.write();
+                let builder = RuntimeEnvBuilder::from_runtime_env(state.runtime_env())
+                    .with_object_store_requests_per_second(requests_per_second);
+                *state = SessionStateBuilder::from(state.clone())
+                    .with_runtime_env(Arc::new(builder.build()?))
+                    .build();
+            }
             _ => {
                 return Err(DataFusionError::Plan(format!(
                     "Unknown runtime configuration: {variable}"
@@ -1110,17 +1387,18 @@ impl SessionContext {
     ) -> Result<Arc<dyn TableProvider>> {
         let state = self.state.read().clone();
         let file_type = cmd.file_type.to_uppercase();
-        let factory =
-            state
-                .table_factories()
-                .get(file_type.as_str())
-                .ok_or_else(|| {
-                    DataFusionError::Execution(format!(
-                        "Unable to find factory for {}",
-                        cmd.file_type
-                    ))
-                })?;
-        let table = (*factory).create(&state, cmd).await?;
+        let factory = match state.table_factories().get(file_type.as_str()) {
+            Some(factory) => Arc::clone(factory),
+            // Tables of the file formats registered with
+            // `SessionState::register_file_format` are listing tables
+            None if state.get_file_format_factory(&file_type).is_some() => {
+                Arc::new(DefaultTableFactory::new())
+            }
+            None => {
+                return exec_err!("Unable to find factory for {}", cmd.file_type);
+            }
+        };
+        let table = factory.create(&state, cmd).await?;
         Ok(table)
     }
 
@@ -1562,16 +1840,18 @@ impl SessionContext {
             .deregister_table(&table)
     }
 
-    /// Return `true` if the specified table exists in the schema provider.
+    /// Return `true` if the specified table exists in the schema provider,
+    /// or is a temporary table of this session.
     pub fn table_exist(&self, table_ref: impl Into<TableReference>) -> Result<bool> {
         let table_ref: TableReference = table_ref.into();
         let table = table_ref.table();
-        let table_ref = table_ref.clone();
-        Ok(self
-            .state
-            .read()
-            .schema_for_ref(table_ref)?
-            .table_exist(table))
+        let state = self.state.read();
+        if matches!(table_ref, TableReference::Bare { .. })
+            && state.temporary_tables().table_exist(table)
+        {
+            return Ok(true);
+        }
+        Ok(state.schema_for_ref(table_ref.clone())?.table_exist(table))
     }
 
     /// Retrieves a [`DataFrame`] representing a table previously
@@ -1608,11 +1888,20 @@ impl SessionContext {
     }
 
     /// Return a [`TableProvider`] for the specified table.
+    ///
+    /// Unqualified names refer to the temporary tables of this session
+    /// before the tables of the default schema.
     pub async fn table_provider(
         &self,
         table_ref: impl Into<TableReference>,
     ) -> Result<Arc<dyn TableProvider>> {
         let table_ref = table_ref.into();
+        if let TableReference::Bare { table } = &table_ref {
+            let temporary_tables = self.state.read().temporary_tables();
+            if let Some(provider) = temporary_tables.table(table).await? {
+                return Ok(provider);
+            }
+        }
         let table = table_ref.table().to_string();
         let schema = self.state.read().schema_for_ref(table_ref)?;
         match schema.table(&table).await? {
@@ -1626,6 +1915,75 @@ impl SessionContext {
         Arc::new(TaskContext::from(self))
     }
 
+    /// Execute the [`ExecutionPlan`] in this session, returning a stream of
+    /// its results that can be stopped with `cancellation` while it runs.
+    ///
+    /// Dropping the returned stream stops polling the operators of the plan,
+    /// but a computation in progress, such as a slow scalar function
+    /// evaluated on a large batch in a task spawned by the plan, keeps running
+    /// until it completes. Cancelling the token instead makes the stream
+    /// return an error promptly:
+    ///
+    /// * The scans of the plan stop, aborting their in-flight object store
+    ///   requests.
+    /// * Scalar functions opting in with [`ScalarUDFImpl::with_cancellation`]
+    ///   receive the token and can return early.
+    ///
+    /// [`ScalarUDFImpl::with_cancellation`]: crate::logical_expr::ScalarUDFImpl::with_cancellation
+    pub fn execute_with_cancellation(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        cancellation: CancellationToken,
+    ) -> Result<SendableRecordBatchStream> {
+        let task_ctx = TaskContext::from(self).with_cancellation_token(cancellation);
+        execute_stream(plan, Arc::new(task_ctx))
+    }
+
+    /// Execute the [`ExecutionPlan`] in this session, returning a stream of
+    /// its results while reporting the progress of its execution into
+    /// `progress`.
+    ///
+    /// The scans of files of the plan report the bytes they scan out of the
+    /// bytes of their files, so the progress can be followed by polling
+    /// [`ProgressTracker::snapshot`] or registering a callback:
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::execution::progress::ProgressTracker;
+    /// # use futures::TryStreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// ctx.register_csv("example", "tests/data/example.csv", CsvReadOptions::new())
+    ///     .await?;
+    /// let plan = ctx
+    ///     .sql("SELECT * FROM example")
+    ///     .await?
+    ///     .create_physical_plan()
+    ///     .await?;
+    ///
+    /// let progress = ProgressTracker::new();
+    /// progress.register_callback(|snapshot| {
+    ///     if let Some(fraction) = snapshot.fraction() {
+    ///         println!("{:.0}% scanned", fraction * 100.0);
+    ///     }
+    /// });
+    /// let stream = ctx.execute_with_progress(plan, progress.clone())?;
+    /// stream.try_collect::<Vec<_>>().await?;
+    /// assert_eq!(progress.snapshot().fraction(), Some(1.0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_with_progress(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        progress: ProgressTracker,
+    ) -> Result<SendableRecordBatchStream> {
+        let task_ctx = TaskContext::from(self).with_progress_tracker(progress);
+        execute_stream(plan, Arc::new(task_ctx))
+    }
+
     /// Return a new  [`SessionState`] suitable for executing a single query.
     ///
     /// Notes:
@@ -1897,6 +2255,7 @@ mod tests {
     use std::error::Error;
     use std::path::PathBuf;
 
+    use datafusion_common::assert_contains;
     use datafusion_common::test_util::batches_to_string;
     use datafusion_common_runtime::SpawnedTask;
     use insta::{allow_duplicates, assert_snapshot};
@@ -2095,6 +2454,84 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn with_discovering_schema_provider() -> Result<()> {
+        use crate::datasource::listing::discovery::DiscoveringSchemaProvider;
+        use object_store::local::LocalFileSystem;
+
+        let tmp_dir = TempDir::new()?;
+        for (day, rows) in [("1", "id,name\n1,a\n2,b\n"), ("2", "id,name\n3,c\n")] {
+            let dir = tmp_dir.path().join(format!("events/day={day}"));
+            std::fs::create_dir_all(&dir)?;
+            std::fs::write(dir.join("part-0.csv"), rows)?;
+        }
+        std::fs::write(
+            tmp_dir.path().join("users.json"),
+            "{\"id\": 1, \"user\": \"alice\"}\n{\"id\": 2, \"user\": \"bob\"}\n",
+        )?;
+
+        let ctx = SessionContext::new();
+        let factory = Arc::new(DynamicListTableFactory::new(SessionStore::new()));
+        factory.session_store().with_state(ctx.state_weak_ref());
+        let root = ListingTableUrl::parse(format!("{}/", tmp_dir.path().display()))?;
+        let provider = Arc::new(DiscoveringSchemaProvider::new(
+            root,
+            Arc::new(LocalFileSystem::new()),
+            factory,
+        ));
+        let catalog = MemoryCatalogProvider::new();
+        catalog.register_schema("landing", Arc::clone(&provider) as _)?;
+        ctx.register_catalog("my_catalog", Arc::new(catalog));
+
+        let result = plan_and_collect(
+            &ctx,
+            "select id, name, day from my_catalog.landing.events order by id",
+        )
+        .await?;
+        assert_snapshot!(batches_to_string(&result), @r"
+        +----+------+-----+
+        | id | name | day |
+        +----+------+-----+
+        | 1  | a    | 1   |
+        | 2  | b    | 1   |
+        | 3  | c    | 2   |
+        +----+------+-----+
+        ");
+        let result =
+            plan_and_collect(&ctx, "select * from my_catalog.landing.users order by id")
+                .await?;
+        assert_snapshot!(batches_to_string(&result), @r"
+        +----+-------+
+        | id | user  |
+        +----+-------+
+        | 1  | alice |
+        | 2  | bob   |
+        +----+-------+
+        ");
+
+        // New tables are discovered on refresh
+        std::fs::create_dir(tmp_dir.path().join("logs"))?;
+        std::fs::write(
+            tmp_dir.path().join("logs/part-0.json"),
+            "{\"level\": \"info\"}\n",
+        )?;
+        let err = plan_and_collect(&ctx, "select * from my_catalog.landing.logs")
+            .await
+            .unwrap_err();
+        assert_contains!(err.to_string(), "table 'my_catalog.landing.logs' not found");
+        provider.refresh().await?;
+        let result =
+            plan_and_collect(&ctx, "select * from my_catalog.landing.logs").await?;
+        assert_snapshot!(batches_to_string(&result), @r"
+        +-------+
+        | level |
+        +-------+
+        | info  |
+        +-------+
+        ");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn custom_query_planner() -> Result<()> {
         let runtime = Arc::new(RuntimeEnv::default());
@@ -2313,6 +2750,88 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn temporary_tables_isolated_between_sessions() -> Result<()> {
+        let ctx1 = SessionContext::new_with_config(
+            SessionConfig::new().with_information_schema(true),
+        );
+        // A second session sharing the catalogs of the first one
+        let ctx2 = SessionContext::new_with_state(
+            SessionStateBuilder::new_from_existing(ctx1.state()).build(),
+        );
+
+        ctx1.sql("CREATE TABLE t AS VALUES ('permanent')").await?;
+        ctx1.sql("CREATE TEMP TABLE t AS VALUES ('temporary 1')")
+            .await?;
+        ctx1.sql("CREATE TEMP TABLE only_1 AS VALUES (1)").await?;
+        ctx2.sql("CREATE TEMP TABLE t AS VALUES ('temporary 2')")
+            .await?;
+
+        let values = |ctx: SessionContext, sql: &'static str| async move {
+            let batches = ctx.sql(sql).await?.collect().await?;
+            Ok::<_, DataFusionError>(batches_to_string(&batches))
+        };
+        assert_snapshot!(values(ctx1.clone(), "SELECT * FROM t").await?, @r"
+        +-------------+
+        | column1     |
+        +-------------+
+        | temporary 1 |
+        +-------------+
+        ");
+        assert_snapshot!(values(ctx2.clone(), "SELECT * FROM t").await?, @r"
+        +-------------+
+        | column1     |
+        +-------------+
+        | temporary 2 |
+        +-------------+
+        ");
+        assert_snapshot!(values(ctx2.clone(), "SELECT * FROM public.t").await?, @r"
+        +-----------+
+        | column1   |
+        +-----------+
+        | permanent |
+        +-----------+
+        ");
+
+        assert!(ctx1.table_exist("only_1")?);
+        assert!(!ctx2.table_exist("only_1")?);
+        assert_contains!(
+            ctx2.sql("SELECT * FROM only_1")
+                .await
+                .unwrap_err()
+                .to_string(),
+            "table 'datafusion.public.only_1' not found"
+        );
+        let tables = ctx2
+            .sql("SELECT table_name FROM information_schema.tables WHERE table_name = 'only_1'")
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(tables.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn temporary_tables_dropped_with_session() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TEMP TABLE t AS VALUES (1), (2)").await?;
+        ctx.sql("INSERT INTO t VALUES (3)").await?.collect().await?;
+        let results = ctx.sql("SELECT count(*) FROM t").await?.collect().await?;
+        assert_snapshot!(batches_to_string(&results), @r"
+        +----------+
+        | count(*) |
+        +----------+
+        | 3        |
+        +----------+
+        ");
+
+        let table = Arc::downgrade(&ctx.table_provider("t").await?);
+        drop(results);
+        drop(ctx);
+        assert_eq!(Weak::strong_count(&table), 0);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn custom_type_planner() -> Result<()> {
         let state = SessionStateBuilder::new()
@@ -17,7 +17,9 @@
 
 //! Shared state for query planning and execution.
 
+pub mod catalog_store;
 pub mod context;
+mod result_cache;
 pub mod session_state;
 pub use session_state::{SessionState, SessionStateBuilder};
 
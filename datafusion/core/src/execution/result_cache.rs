@@ -0,0 +1,126 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Decides which queries may be answered from a [`ResultCache`], and the key
+//! of their results
+//!
+//! [`ResultCache`]: datafusion_execution::cache::result_cache::ResultCache
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use datafusion_catalog::default_table_source::source_as_provider;
+use datafusion_catalog::Session;
+use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
+use datafusion_common::Result;
+use datafusion_execution::cache::result_cache::ResultKey;
+use datafusion_expr::{Expr, LogicalPlan, Volatility, WindowFunctionDefinition};
+
+/// Returns true if the result of `plan` may be cached: `plan` must be a
+/// query, and all of its functions must be immutable.
+///
+/// Functions such as `now()` are replaced by constants when the plan is
+/// optimized, so this must be called with the unoptimized plan.
+pub(crate) fn is_cacheable(plan: &LogicalPlan) -> Result<bool> {
+    let mut cacheable = true;
+    plan.apply_with_subqueries(|node| {
+        cacheable = match node {
+            LogicalPlan::Dml(_)
+            | LogicalPlan::Ddl(_)
+            | LogicalPlan::Copy(_)
+            | LogicalPlan::Statement(_)
+            | LogicalPlan::Explain(_)
+            | LogicalPlan::DescribeTable(_) => false,
+            _ => {
+                let mut deterministic = true;
+                node.apply_expressions(|expr| {
+                    deterministic = is_deterministic(expr)?;
+                    Ok(if deterministic {
+                        TreeNodeRecursion::Continue
+                    } else {
+                        TreeNodeRecursion::Stop
+                    })
+                })?;
+                deterministic
+            }
+        };
+        Ok(if cacheable {
+            TreeNodeRecursion::Continue
+        } else {
+            TreeNodeRecursion::Stop
+        })
+    })?;
+    Ok(cacheable)
+}
+
+/// Returns true if `expr` only calls immutable functions
+fn is_deterministic(expr: &Expr) -> Result<bool> {
+    let non_deterministic = expr.exists(|expr| {
+        let volatility = match expr {
+            Expr::ScalarFunction(f) => f.func.signature().volatility,
+            Expr::AggregateFunction(f) => f.func.signature().volatility,
+            Expr::WindowFunction(f) => match &f.fun {
+                WindowFunctionDefinition::AggregateUDF(f) => f.signature().volatility,
+                WindowFunctionDefinition::WindowUDF(f) => f.signature().volatility,
+            },
+            Expr::ScalarVariable(_, _) => Volatility::Stable,
+            _ => Volatility::Immutable,
+        };
+        Ok(volatility != Volatility::Immutable)
+    })?;
+    Ok(!non_deterministic)
+}
+
+/// Returns the key of the result of the optimized `plan`
+pub(crate) fn result_key(plan: &LogicalPlan) -> ResultKey {
+    let mut hasher = DefaultHasher::new();
+    plan.hash(&mut hasher);
+    ResultKey::new(hasher.finish(), plan.display_indent_schema().to_string())
+}
+
+/// Returns the snapshot tokens of the tables scanned by `plan`, or `None`
+/// if the version of one of them is unknown
+pub(crate) async fn snapshot_tokens(
+    plan: &LogicalPlan,
+    state: &dyn Session,
+) -> Result<Option<Vec<String>>> {
+    let mut providers = vec![];
+    let mut supported = true;
+    plan.apply_with_subqueries(|node| {
+        if let LogicalPlan::TableScan(scan) = node {
+            match source_as_provider(&scan.source) {
+                Ok(provider) => providers.push(provider),
+                Err(_) => {
+                    supported = false;
+                    return Ok(TreeNodeRecursion::Stop);
+                }
+            }
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+    if !supported {
+        return Ok(None);
+    }
+    let mut tokens = Vec::with_capacity(providers.len());
+    for provider in providers {
+        match provider.snapshot_token(state).await? {
+            Some(token) => tokens.push(token),
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(tokens))
+}
@@ -23,11 +23,15 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::sync::Arc;
 
-use crate::catalog::{CatalogProviderList, SchemaProvider, TableProviderFactory};
+use crate::catalog::{
+    CatalogProviderList, SchemaProvider, TableProvider, TableProviderFactory,
+};
 use crate::datasource::cte_worktable::CteWorkTable;
 use crate::datasource::file_format::{format_as_file_type, FileFormatFactory};
 use crate::datasource::provider_as_source;
+use crate::execution::catalog_store::CatalogStore;
 use crate::execution::context::{EmptySerializerRegistry, FunctionFactory, QueryPlanner};
+use crate::execution::result_cache;
 use crate::execution::SessionStateDefaults;
 use crate::physical_planner::{DefaultPhysicalPlanner, PhysicalPlanner};
 use datafusion_catalog::information_schema::{
@@ -35,7 +39,7 @@ use datafusion_catalog::information_schema::{
 };
 
 use arrow::datatypes::{DataType, SchemaRef};
-use datafusion_catalog::MemoryCatalogProviderList;
+use datafusion_catalog::{MemoryCatalogProviderList, MemorySchemaProvider};
 use datafusion_catalog::{TableFunction, TableFunctionImpl};
 use datafusion_common::alias::AliasGenerator;
 use datafusion_common::config::{ConfigExtension, ConfigOptions, TableOptions};
@@ -46,6 +50,7 @@ use datafusion_common::{
     config_err, exec_err, not_impl_err, plan_datafusion_err, DFSchema, DataFusionError,
     ResolvedTableReference, TableReference,
 };
+use datafusion_execution::cache::result_cache::ResultCache;
 use datafusion_execution::config::SessionConfig;
 use datafusion_execution::runtime_env::RuntimeEnv;
 use datafusion_execution::TaskContext;
@@ -67,6 +72,8 @@ use datafusion_physical_expr::create_physical_expr;
 use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
 use datafusion_physical_optimizer::optimizer::PhysicalOptimizer;
 use datafusion_physical_optimizer::PhysicalOptimizerRule;
+use datafusion_physical_plan::analyze::AnalyzeExec;
+use datafusion_physical_plan::result_cache::ResultCacheExec;
 use datafusion_physical_plan::ExecutionPlan;
 use datafusion_session::Session;
 use datafusion_sql::parser::{DFParserBuilder, Statement};
@@ -176,9 +183,20 @@ pub struct SessionState {
     /// It will be invoked on `CREATE FUNCTION` statements.
     /// thus, changing dialect o PostgreSql is required
     function_factory: Option<Arc<dyn FunctionFactory>>,
+    /// [CatalogStore] persisting the views and external tables created by
+    /// DDL statements
+    catalog_store: Option<Arc<dyn CatalogStore>>,
     /// Cache logical plans of prepared statements for later execution.
     /// Key is the prepared statement name.
     prepared_plans: HashMap<String, Arc<PreparedPlan>>,
+    /// Tables created by `CREATE TEMPORARY TABLE`, only visible to this
+    /// session and dropped along with it.
+    ///
+    /// Unqualified table names resolve to these tables before the tables of
+    /// the default schema.
+    temporary_tables: Arc<MemorySchemaProvider>,
+    /// [ResultCache] storing the results of queries, if enabled
+    result_cache: Option<Arc<ResultCache>>,
 }
 
 impl Debug for SessionState {
@@ -196,6 +214,8 @@ impl Debug for SessionState {
             .field("table_options", &self.table_options)
             .field("table_factories", &self.table_factories)
             .field("function_factory", &self.function_factory)
+            .field("catalog_store", &self.catalog_store)
+            .field("result_cache", &self.result_cache)
             .field("expr_planners", &self.expr_planners)
             .field("type_planner", &self.type_planner)
             .field("query_planners", &self.query_planner)
@@ -207,6 +227,7 @@ impl Debug for SessionState {
             .field("aggregate_functions", &self.aggregate_functions)
             .field("window_functions", &self.window_functions)
             .field("prepared_plans", &self.prepared_plans)
+            .field("temporary_tables", &self.temporary_tables)
             .finish()
     }
 }
@@ -323,6 +344,25 @@ impl SessionState {
             })
     }
 
+    /// The tables created by `CREATE TEMPORARY TABLE` in this session
+    pub fn temporary_tables(&self) -> Arc<dyn SchemaProvider> {
+        Arc::clone(&self.temporary_tables) as _
+    }
+
+    /// Retrieve the temporary table `table_ref` refers to, if any.
+    ///
+    /// Only unqualified names refer to temporary tables, which shadow the
+    /// tables of the default schema with the same name.
+    pub async fn temporary_table(
+        &self,
+        table_ref: &TableReference,
+    ) -> datafusion_common::Result<Option<Arc<dyn TableProvider>>> {
+        match table_ref {
+            TableReference::Bare { table } => self.temporary_tables.table(table).await,
+            _ => Ok(None),
+        }
+    }
+
     /// Add `analyzer_rule` to the end of the list of
     /// [`AnalyzerRule`]s used to rewrite queries.
     pub fn add_analyzer_rule(
@@ -353,6 +393,28 @@ impl SessionState {
         self.function_factory.as_ref()
     }
 
+    /// Registers a [`CatalogStore`] to persist the views and external
+    /// tables created by DDL statements
+    pub fn set_catalog_store(&mut self, catalog_store: Arc<dyn CatalogStore>) {
+        self.catalog_store = Some(catalog_store);
+    }
+
+    /// Get the catalog store
+    pub fn catalog_store(&self) -> Option<&Arc<dyn CatalogStore>> {
+        self.catalog_store.as_ref()
+    }
+
+    /// Registers a [`ResultCache`] storing the results of queries, reused by
+    /// [`Self::create_physical_plan`] while the scanned tables are unchanged
+    pub fn set_result_cache(&mut self, result_cache: Arc<ResultCache>) {
+        self.result_cache = Some(result_cache);
+    }
+
+    /// Get the result cache
+    pub fn result_cache(&self) -> Option<&Arc<ResultCache>> {
+        self.result_cache.as_ref()
+    }
+
     /// Get the table factories
     pub fn table_factories(&self) -> &HashMap<String, Arc<dyn TableProviderFactory>> {
         &self.table_factories
@@ -467,9 +529,16 @@ impl SessionState {
         let mut provider = SessionContextProvider {
             state: self,
             tables: HashMap::with_capacity(references.len()),
+            temporary_tables: HashMap::new(),
         };
 
         for reference in references {
+            if let Some(table) = self.temporary_table(&reference).await? {
+                provider
+                    .temporary_tables
+                    .insert(reference.table().to_string(), provider_as_source(table));
+                continue;
+            }
             let resolved = self.resolve_table_ref(reference);
             if let Entry::Vacant(v) = provider.tables.entry(resolved) {
                 let resolved = v.key();
@@ -536,6 +605,7 @@ impl SessionState {
         let provider = SessionContextProvider {
             state: self,
             tables: HashMap::new(),
+            temporary_tables: HashMap::new(),
         };
 
         let query = SqlToRel::new_with_options(&provider, self.get_parser_options());
@@ -653,10 +723,62 @@ impl SessionState {
         &self,
         logical_plan: &LogicalPlan,
     ) -> datafusion_common::Result<Arc<dyn ExecutionPlan>> {
+        let result_cache = match &self.result_cache {
+            Some(cache) if result_cache::is_cacheable(logical_plan)? => Some(cache),
+            _ => None,
+        };
         let logical_plan = self.optimize(logical_plan)?;
-        self.query_planner
-            .create_physical_plan(&logical_plan, self)
-            .await
+        let Some(cache) = result_cache else {
+            return self
+                .query_planner
+                .create_physical_plan(&logical_plan, self)
+                .await;
+        };
+        // The analyzed query of `EXPLAIN ANALYZE` is looked up in the cache
+        let LogicalPlan::Analyze(analyze) = &logical_plan else {
+            return self.create_cached_physical_plan(cache, &logical_plan).await;
+        };
+        let input = self
+            .create_cached_physical_plan(cache, &analyze.input)
+            .await?;
+        let schema = SchemaRef::new(analyze.schema.as_arrow().clone());
+        let show_statistics = self.config_options().explain.show_statistics;
+        Ok(Arc::new(
+            AnalyzeExec::new(analyze.verbose, show_statistics, input, schema)
+                .with_format(analyze.format),
+        ))
+    }
+
+    /// Returns a [`ResultCacheExec`] returning the result of the optimized
+    /// `logical_plan` from `cache`, or storing it in `cache`
+    async fn create_cached_physical_plan(
+        &self,
+        cache: &Arc<ResultCache>,
+        logical_plan: &LogicalPlan,
+    ) -> datafusion_common::Result<Arc<dyn ExecutionPlan>> {
+        let Some(snapshot) = result_cache::snapshot_tokens(logical_plan, self).await?
+        else {
+            return self
+                .query_planner
+                .create_physical_plan(logical_plan, self)
+                .await;
+        };
+        let key = result_cache::result_key(logical_plan);
+        if let Some(result) = cache.get(&key, &snapshot) {
+            if result.schema().as_ref() == logical_plan.schema().as_arrow() {
+                return Ok(Arc::new(ResultCacheExec::hit(result)));
+            }
+        }
+        let plan = self
+            .query_planner
+            .create_physical_plan(logical_plan, self)
+            .await?;
+        Ok(Arc::new(ResultCacheExec::miss(
+            plan,
+            Arc::clone(cache),
+            key,
+            snapshot,
+        )))
     }
 
     /// Create a [`PhysicalExpr`] from an [`Expr`] after applying type
@@ -910,6 +1032,9 @@ pub struct SessionStateBuilder {
     table_factories: Option<HashMap<String, Arc<dyn TableProviderFactory>>>,
     runtime_env: Option<Arc<RuntimeEnv>>,
     function_factory: Option<Arc<dyn FunctionFactory>>,
+    catalog_store: Option<Arc<dyn CatalogStore>>,
+    result_cache: Option<Arc<ResultCache>>,
+    temporary_tables: Option<Arc<MemorySchemaProvider>>,
     // fields to support convenience functions
     analyzer_rules: Option<Vec<Arc<dyn AnalyzerRule + Send + Sync>>>,
     optimizer_rules: Option<Vec<Arc<dyn OptimizerRule + Send + Sync>>>,
@@ -946,6 +1071,9 @@ impl SessionStateBuilder {
             table_factories: None,
             runtime_env: None,
             function_factory: None,
+            catalog_store: None,
+            result_cache: None,
+            temporary_tables: None,
             // fields to support convenience functions
             analyzer_rules: None,
             optimizer_rules: None,
@@ -997,6 +1125,9 @@ impl SessionStateBuilder {
             table_factories: Some(existing.table_factories),
             runtime_env: Some(existing.runtime_env),
             function_factory: existing.function_factory,
+            catalog_store: existing.catalog_store,
+            result_cache: existing.result_cache,
+            temporary_tables: Some(existing.temporary_tables),
 
             // fields to support convenience functions
             analyzer_rules: None,
@@ -1285,6 +1416,22 @@ impl SessionStateBuilder {
         self
     }
 
+    /// Set a [`CatalogStore`] to persist the views and external tables
+    /// created by DDL statements
+    pub fn with_catalog_store(
+        mut self,
+        catalog_store: Option<Arc<dyn CatalogStore>>,
+    ) -> Self {
+        self.catalog_store = catalog_store;
+        self
+    }
+
+    /// Set a [`ResultCache`] storing the results of queries
+    pub fn with_result_cache(mut self, result_cache: Option<Arc<ResultCache>>) -> Self {
+        self.result_cache = result_cache;
+        self
+    }
+
     /// Register an `ObjectStore` to the [`RuntimeEnv`]. See [`RuntimeEnv::register_object_store`]
     /// for more details.
     ///
@@ -1347,6 +1494,9 @@ impl SessionStateBuilder {
             table_factories,
             runtime_env,
             function_factory,
+            catalog_store,
+            result_cache,
+            temporary_tables,
             analyzer_rules,
             optimizer_rules,
             physical_optimizer_rules,
@@ -1382,7 +1532,11 @@ impl SessionStateBuilder {
             table_factories: table_factories.unwrap_or_default(),
             runtime_env,
             function_factory,
+            catalog_store,
+            result_cache,
             prepared_plans: HashMap::new(),
+            temporary_tables: temporary_tables
+                .unwrap_or_else(|| Arc::new(MemorySchemaProvider::new())),
         };
 
         if let Some(file_formats) = file_formats {
@@ -1565,6 +1719,16 @@ impl SessionStateBuilder {
         &mut self.function_factory
     }
 
+    /// Returns the current catalog_store value
+    pub fn catalog_store(&mut self) -> &mut Option<Arc<dyn CatalogStore>> {
+        &mut self.catalog_store
+    }
+
+    /// Returns the current result_cache value
+    pub fn result_cache(&mut self) -> &mut Option<Arc<ResultCache>> {
+        &mut self.result_cache
+    }
+
     /// Returns the current analyzer_rules value
     pub fn analyzer_rules(
         &mut self,
@@ -1602,6 +1766,9 @@ impl Debug for SessionStateBuilder {
             .field("table_options", &self.table_options)
             .field("table_factories", &self.table_factories)
             .field("function_factory", &self.function_factory)
+            .field("catalog_store", &self.catalog_store)
+            .field("result_cache", &self.result_cache)
+            .field("temporary_tables", &self.temporary_tables)
             .field("expr_planners", &self.expr_planners)
             .field("type_planner", &self.type_planner)
             .field("query_planners", &self.query_planner)
@@ -1638,6 +1805,8 @@ impl From<SessionState> for SessionStateBuilder {
 struct SessionContextProvider<'a> {
     state: &'a SessionState,
     tables: HashMap<ResolvedTableReference, Arc<dyn TableSource>>,
+    /// The temporary tables referenced by unqualified names
+    temporary_tables: HashMap<String, Arc<dyn TableSource>>,
 }
 
 impl ContextProvider for SessionContextProvider<'_> {
@@ -1657,6 +1826,11 @@ impl ContextProvider for SessionContextProvider<'_> {
         &self,
         name: TableReference,
     ) -> datafusion_common::Result<Arc<dyn TableSource>> {
+        if let TableReference::Bare { table } = &name {
+            if let Some(source) = self.temporary_tables.get(table.as_ref()) {
+                return Ok(Arc::clone(source));
+            }
+        }
         let name = self.state.resolve_table_ref(name);
         self.tables
             .get(&name)
@@ -1971,7 +2145,7 @@ pub(crate) struct PreparedPlan {
 #[cfg(test)]
 mod tests {
     use super::{SessionContextProvider, SessionStateBuilder};
-    use crate::common::assert_contains;
+    use crate::common::{assert_batches_eq, assert_contains};
     use crate::config::ConfigOptions;
     use crate::datasource::empty::EmptyTable;
     use crate::datasource::provider_as_source;
@@ -1980,6 +2154,7 @@ mod tests {
     use crate::logical_expr::planner::ExprPlanner;
     use crate::logical_expr::{AggregateUDF, ScalarUDF, TableSource, WindowUDF};
     use crate::physical_plan::ExecutionPlan;
+    use crate::prelude::SessionContext;
     use crate::sql::planner::ContextProvider;
     use crate::sql::{ResolvedTableReference, TableReference};
     use arrow::array::{ArrayRef, Int32Array, RecordBatch, StringArray};
@@ -2003,6 +2178,7 @@ mod tests {
             let provider = SessionContextProvider {
                 state,
                 tables: HashMap::new(),
+                temporary_tables: HashMap::new(),
             };
 
             let sql = "[1,2,3]";
@@ -2089,6 +2265,31 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_from_existing_temporary_tables() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TEMPORARY TABLE t AS VALUES (1), (2)")
+            .await?
+            .collect()
+            .await?;
+
+        let state = SessionStateBuilder::new_from_existing(ctx.state()).build();
+        let ctx = SessionContext::new_with_state(state);
+        let results = ctx.sql("SELECT * FROM t").await?.collect().await?;
+        assert_batches_eq!(
+            [
+                "+---------+",
+                "| column1 |",
+                "+---------+",
+                "| 1       |",
+                "| 2       |",
+                "+---------+",
+            ],
+            &results
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_session_state_with_optimizer_rules() {
         #[derive(Default, Debug)]
@@ -23,7 +23,7 @@ use std::sync::Arc;
 
 use crate::datasource::file_format::file_type_to_format;
 use crate::datasource::listing::ListingTableUrl;
-use crate::datasource::physical_plan::FileSinkConfig;
+use crate::datasource::physical_plan::{FileOutputMode, FileSinkConfig};
 use crate::datasource::{source_as_provider, DefaultTableSource};
 use crate::error::{DataFusionError, Result};
 use crate::execution::context::{ExecutionProps, SessionState};
@@ -57,7 +57,7 @@ use crate::physical_plan::{
 };
 use crate::schema_equivalence::schema_satisfied_by;
 
-use arrow::array::{builder::StringBuilder, RecordBatch};
+use arrow::array::{builder::StringBuilder, ArrayRef, RecordBatch};
 use arrow::compute::SortOptions;
 use arrow::datatypes::{Schema, SchemaRef};
 use datafusion_common::display::ToStringifiedPlan;
@@ -70,17 +70,19 @@ use datafusion_common::{
 };
 use datafusion_datasource::file_groups::FileGroup;
 use datafusion_datasource::memory::MemorySourceConfig;
+use datafusion_datasource::write::demux::validate_file_name_template;
 use datafusion_expr::dml::{CopyTo, InsertOp};
 use datafusion_expr::expr::{
     physical_name, AggregateFunction, AggregateFunctionParams, Alias, GroupingSet,
     WindowFunction, WindowFunctionParams,
 };
-use datafusion_expr::expr_rewriter::unnormalize_cols;
+use datafusion_expr::expr_rewriter::{unnormalize_col, unnormalize_cols};
 use datafusion_expr::logical_plan::builder::wrap_projection_for_join_if_necessary;
+use datafusion_expr::utils::split_conjunction;
 use datafusion_expr::{
-    Analyze, DescribeTable, DmlStatement, Explain, ExplainFormat, Extension, FetchType,
-    Filter, JoinType, RecursiveQuery, SkipType, StringifiedPlan, WindowFrame,
-    WindowFrameBound, WriteOp,
+    lit, Analyze, DescribeTable, DmlStatement, Explain, ExplainFormat, Extension,
+    FetchType, Filter, JoinType, RecursiveQuery, SkipType, StringifiedPlan, WindowFrame,
+    WindowFrameBound, WriteOp, COMMENT_METADATA_KEY,
 };
 use datafusion_physical_expr::aggregate::{AggregateExprBuilder, AggregateFunctionExpr};
 use datafusion_physical_expr::expressions::{Column, Literal};
@@ -530,6 +532,31 @@ impl DefaultPhysicalPlanner {
                         return Err(DataFusionError::Configuration(format!("provided value for 'execution.keep_partition_by_columns' was not recognized: \"{value}\""))),
                 };
 
+                let file_output_mode = match source_option_tuples
+                    .get("execution.single_file_output")
+                    .map(|v| v.trim()) {
+                    None => FileOutputMode::Automatic,
+                    Some("true") => FileOutputMode::SingleFile,
+                    Some("false") => FileOutputMode::Directory,
+                    Some(value) =>
+                        return Err(DataFusionError::Configuration(format!("provided value for 'execution.single_file_output' was not recognized: \"{value}\""))),
+                };
+                if file_output_mode == FileOutputMode::SingleFile
+                    && !table_partition_cols.is_empty()
+                {
+                    return plan_err!(
+                        "'execution.single_file_output' can't be used with PARTITIONED BY"
+                    );
+                }
+
+                if let Some(template) = &session_state
+                    .config_options()
+                    .execution
+                    .output_file_name_template
+                {
+                    validate_file_name_template(template)?;
+                }
+
                 let sink_format = file_type_to_format(file_type)?
                     .create(session_state, source_option_tuples)?;
 
@@ -544,6 +571,7 @@ impl DefaultPhysicalPlanner {
                     insert_op: InsertOp::Append,
                     keep_partition_by_columns,
                     file_extension: sink_format.get_ext(),
+                    file_output_mode,
                 };
 
                 sink_format
@@ -569,6 +597,47 @@ impl DefaultPhysicalPlanner {
                     );
                 }
             }
+            LogicalPlan::Dml(DmlStatement {
+                target,
+                op: WriteOp::Delete,
+                input,
+                ..
+            }) => {
+                if let Some(provider) =
+                    target.as_any().downcast_ref::<DefaultTableSource>()
+                {
+                    let (filters, _) = dml_input(input)?;
+                    provider
+                        .table_provider
+                        .delete_from(session_state, filters)
+                        .await?
+                } else {
+                    return exec_err!(
+                        "Table source can't be downcasted to DefaultTableSource"
+                    );
+                }
+            }
+            LogicalPlan::Dml(DmlStatement {
+                target,
+                op: WriteOp::Update,
+                input,
+                ..
+            }) => {
+                if let Some(provider) =
+                    target.as_any().downcast_ref::<DefaultTableSource>()
+                {
+                    let (filters, columns) = dml_input(input)?;
+                    let assignments = update_assignments(columns);
+                    provider
+                        .table_provider
+                        .update(session_state, assignments, filters)
+                        .await?
+                } else {
+                    return exec_err!(
+                        "Table source can't be downcasted to DefaultTableSource"
+                    );
+                }
+            }
             LogicalPlan::Window(Window { window_expr, .. }) => {
                 if window_expr.is_empty() {
                     return internal_err!("Impossibly got empty window expression");
@@ -1766,6 +1835,7 @@ impl DefaultPhysicalPlanner {
                 stringified_plans.push(StringifiedPlan::new(
                     FinalPhysicalPlan,
                     displayable(optimized_plan.as_ref())
+                        .set_show_properties(config.tree_show_properties)
                         .tree_render()
                         .to_string(),
                 ));
@@ -1925,12 +1995,10 @@ impl DefaultPhysicalPlanner {
         let input = self.create_physical_plan(&a.input, session_state).await?;
         let schema = SchemaRef::new((*a.schema).clone().into());
         let show_statistics = session_state.config_options().explain.show_statistics;
-        Ok(Arc::new(AnalyzeExec::new(
-            a.verbose,
-            show_statistics,
-            input,
-            schema,
-        )))
+        Ok(Arc::new(
+            AnalyzeExec::new(a.verbose, show_statistics, input, schema)
+                .with_format(a.format),
+        ))
     }
 
     /// Optimize a physical plan by applying each physical optimizer,
@@ -2000,6 +2068,7 @@ impl DefaultPhysicalPlanner {
         let mut column_names = StringBuilder::new();
         let mut data_types = StringBuilder::new();
         let mut is_nullables = StringBuilder::new();
+        let mut comments = StringBuilder::new();
         for field in table_schema.fields() {
             column_names.append_value(field.name());
 
@@ -2010,16 +2079,20 @@ impl DefaultPhysicalPlanner {
             // "YES if the column is possibly nullable, NO if it is known not nullable. "
             let nullable_str = if field.is_nullable() { "YES" } else { "NO" };
             is_nullables.append_value(nullable_str);
+
+            // Only part of the output of `DESCRIBE EXTENDED`
+            comments.append_option(field.metadata().get(COMMENT_METADATA_KEY));
         }
 
-        let record_batch = RecordBatch::try_new(
-            output_schema,
-            vec![
-                Arc::new(column_names.finish()),
-                Arc::new(data_types.finish()),
-                Arc::new(is_nullables.finish()),
-            ],
-        )?;
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(column_names.finish()),
+            Arc::new(data_types.finish()),
+            Arc::new(is_nullables.finish()),
+        ];
+        if output_schema.fields().len() > columns.len() {
+            columns.push(Arc::new(comments.finish()));
+        }
+        let record_batch = RecordBatch::try_new(output_schema, columns)?;
 
         let schema = record_batch.schema();
         let partitions = vec![vec![record_batch]];
@@ -2164,6 +2237,92 @@ enum PlanAsyncExpr {
     Async(AsyncMapper, PlannedExprResult),
 }
 
+/// The filters and projected column expressions returned by [`dml_input`]
+type DmlInput = (Vec<Expr>, Vec<(datafusion_common::Column, Expr)>);
+
+/// Returns the filters selecting the rows changed by a `DELETE` or `UPDATE`
+/// statement with the given `input`, and the expressions of the columns
+/// projected by `input`, in terms of the unqualified columns of the target
+/// table.
+///
+/// Projections added by the optimizer, e.g. for common subexpressions, are
+/// inlined into the filters and expressions above them.
+fn dml_input(input: &LogicalPlan) -> Result<DmlInput> {
+    let inline = |expr: &Expr, columns: &[(datafusion_common::Column, Expr)]| {
+        expr.clone()
+            .transform(|expr| {
+                if let Expr::Column(column) = &expr {
+                    if let Some((_, inlined)) =
+                        columns.iter().find(|(projected, _)| projected == column)
+                    {
+                        return Ok(Transformed::yes(inlined.clone()));
+                    }
+                }
+                Ok(Transformed::no(expr))
+            })
+            .map(|expr| unnormalize_col(expr.data))
+    };
+
+    match input {
+        LogicalPlan::TableScan(scan) => {
+            Ok((unnormalize_cols(scan.filters.clone()), vec![]))
+        }
+        // The optimizer replaces the input by an empty relation when the
+        // filters are always false
+        LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: false,
+            ..
+        }) => Ok((vec![lit(false)], vec![])),
+        LogicalPlan::Filter(filter) => {
+            let (mut filters, columns) = dml_input(&filter.input)?;
+            for predicate in split_conjunction(&filter.predicate) {
+                filters.push(inline(predicate, &columns)?);
+            }
+            Ok((filters, columns))
+        }
+        LogicalPlan::Projection(projection) => {
+            let (filters, columns) = dml_input(&projection.input)?;
+            let projected = projection
+                .expr
+                .iter()
+                .zip(projection.schema.columns())
+                .map(|(expr, column)| {
+                    let expr = match expr {
+                        Expr::Alias(Alias { expr, .. }) => expr.as_ref(),
+                        expr => expr,
+                    };
+                    Ok((column, inline(expr, &columns)?))
+                })
+                .collect::<Result<_>>()?;
+            Ok((filters, projected))
+        }
+        LogicalPlan::SubqueryAlias(alias) => dml_input(&alias.input),
+        LogicalPlan::Join(_) => {
+            not_impl_err!("UPDATE with a FROM clause is not supported")
+        }
+        plan => not_impl_err!(
+            "Unsupported input of a DELETE or UPDATE statement: {}",
+            plan.display()
+        ),
+    }
+}
+
+/// Returns the names of the columns assigned by an `UPDATE` statement and
+/// the expressions of their new values, given the columns projected by its
+/// input, see [`dml_input`]
+fn update_assignments(
+    columns: Vec<(datafusion_common::Column, Expr)>,
+) -> Vec<(String, Expr)> {
+    columns
+        .into_iter()
+        // Columns that are not assigned keep their values
+        .filter(|(projected, expr)| {
+            !matches!(expr, Expr::Column(column) if column.name == projected.name)
+        })
+        .map(|(column, expr)| (column.name, expr))
+        .collect()
+}
+
 fn tuple_err<T, R>(value: (Result<T>, Result<R>)) -> Result<(T, R)> {
     match value {
         (Ok(e), Ok(e1)) => Ok((e, e1)),
@@ -28,6 +28,7 @@ use object_store::{
     PutResult,
 };
 use std::fmt::{Debug, Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::{
     sync::Barrier,
@@ -189,3 +190,115 @@ impl ObjectStore for BlockingObjectStore {
         self.inner.copy_if_not_exists(from, to).await
     }
 }
+
+/// Fails the object_store `put` and `put_multipart` calls once `max_files`
+/// files were written.
+pub fn fail_writes_after(
+    object_store: Arc<dyn ObjectStore>,
+    max_files: usize,
+) -> Arc<dyn ObjectStore> {
+    Arc::new(FailingObjectStore {
+        inner: object_store,
+        max_files,
+        files_written: AtomicUsize::new(0),
+    })
+}
+
+/// An object store that fails to write files once `max_files` files were
+/// written.
+#[derive(Debug)]
+struct FailingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    max_files: usize,
+    files_written: AtomicUsize,
+}
+
+impl FailingObjectStore {
+    const NAME: &'static str = "FailingObjectStore";
+
+    fn start_write(&self, location: &Path) -> object_store::Result<()> {
+        if self.files_written.fetch_add(1, Ordering::SeqCst) < self.max_files {
+            return Ok(());
+        }
+        Err(Error::Generic {
+            store: FailingObjectStore::NAME,
+            source: format!("Failed to write {location}").into(),
+        })
+    }
+}
+
+impl Display for FailingObjectStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.inner, f)
+    }
+}
+
+/// All trait methods are forwarded to the inner object store, except for
+/// the `put` methods which fail once `max_files` files were written.
+#[async_trait::async_trait]
+impl ObjectStore for FailingObjectStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.start_write(location)?;
+        self.inner.put_opts(location, payload, opts).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.start_write(location)?;
+        self.inner.put_multipart_opts(location, opts).await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        self.inner.get_opts(location, options).await
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.inner.head(location).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.inner.delete(location).await
+    }
+
+    fn list(
+        &self,
+        prefix: Option<&Path>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.inner.list(prefix)
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<ListResult> {
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        self.inner.copy_if_not_exists(from, to).await
+    }
+}
@@ -2910,32 +2910,31 @@ async fn test_count_wildcard_on_where_scalar_subquery() -> Result<()> {
     assert_snapshot!(
         pretty_format_batches(&sql_results).unwrap(),
         @r"
-    +---------------+---------------------------------------------------------------------------------------------------------------------------+
-    | plan_type     | plan                                                                                                                      |
-    +---------------+---------------------------------------------------------------------------------------------------------------------------+
-    | logical_plan  | Projection: t1.a, t1.b                                                                                                    |
-    |               |   Filter: CASE WHEN __scalar_sq_1.__always_true IS NULL THEN Int64(0) ELSE __scalar_sq_1.count(*) END > Int64(0)          |
-    |               |     Projection: t1.a, t1.b, __scalar_sq_1.count(*), __scalar_sq_1.__always_true                                           |
-    |               |       Left Join: t1.a = __scalar_sq_1.a                                                                                   |
-    |               |         TableScan: t1 projection=[a, b]                                                                                   |
-    |               |         SubqueryAlias: __scalar_sq_1                                                                                      |
-    |               |           Projection: count(Int64(1)) AS count(*), t2.a, Boolean(true) AS __always_true                                   |
-    |               |             Aggregate: groupBy=[[t2.a]], aggr=[[count(Int64(1))]]                                                         |
-    |               |               TableScan: t2 projection=[a]                                                                                |
-    | physical_plan | CoalesceBatchesExec: target_batch_size=8192                                                                               |
-    |               |   FilterExec: CASE WHEN __always_true@3 IS NULL THEN 0 ELSE count(*)@2 END > 0, projection=[a@0, b@1]                     |
-    |               |     CoalesceBatchesExec: target_batch_size=8192                                                                           |
-    |               |       HashJoinExec: mode=CollectLeft, join_type=Left, on=[(a@0, a@1)], projection=[a@0, b@1, count(*)@2, __always_true@4] |
-    |               |         DataSourceExec: partitions=1, partition_sizes=[1]                                                                 |
-    |               |         ProjectionExec: expr=[count(Int64(1))@1 as count(*), a@0 as a, true as __always_true]                             |
-    |               |           AggregateExec: mode=FinalPartitioned, gby=[a@0 as a], aggr=[count(Int64(1))]                                    |
-    |               |             CoalesceBatchesExec: target_batch_size=8192                                                                   |
-    |               |               RepartitionExec: partitioning=Hash([a@0], 4), input_partitions=4                                            |
-    |               |                 RepartitionExec: partitioning=RoundRobinBatch(4), input_partitions=1                                      |
-    |               |                   AggregateExec: mode=Partial, gby=[a@0 as a], aggr=[count(Int64(1))]                                     |
-    |               |                     DataSourceExec: partitions=1, partition_sizes=[1]                                                     |
-    |               |                                                                                                                           |
-    +---------------+---------------------------------------------------------------------------------------------------------------------------+
+    +---------------+---------------------------------------------------------------------------------------------+
+    | plan_type     | plan                                                                                        |
+    +---------------+---------------------------------------------------------------------------------------------+
+    | logical_plan  | Projection: t1.a, t1.b                                                                      |
+    |               |   Inner Join: t1.a = __scalar_sq_1.a                                                        |
+    |               |     TableScan: t1 projection=[a, b]                                                         |
+    |               |     SubqueryAlias: __scalar_sq_1                                                            |
+    |               |       Projection: t2.a                                                                      |
+    |               |         Filter: CASE WHEN Boolean(false) THEN Int64(0) ELSE count(Int64(1)) END > Int64(0)  |
+    |               |           Aggregate: groupBy=[[t2.a]], aggr=[[count(Int64(1))]]                             |
+    |               |             TableScan: t2 projection=[a]                                                    |
+    | physical_plan | CoalesceBatchesExec: target_batch_size=8192                                                 |
+    |               |   HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(a@0, a@0)], projection=[a@1, b@2]   |
+    |               |     CoalescePartitionsExec                                                                  |
+    |               |       CoalesceBatchesExec: target_batch_size=8192                                           |
+    |               |         FilterExec: CASE WHEN false THEN 0 ELSE count(Int64(1))@1 END > 0, projection=[a@0] |
+    |               |           AggregateExec: mode=FinalPartitioned, gby=[a@0 as a], aggr=[count(Int64(1))]      |
+    |               |             CoalesceBatchesExec: target_batch_size=8192                                     |
+    |               |               RepartitionExec: partitioning=Hash([a@0], 4), input_partitions=4              |
+    |               |                 RepartitionExec: partitioning=RoundRobinBatch(4), input_partitions=1        |
+    |               |                   AggregateExec: mode=Partial, gby=[a@0 as a], aggr=[count(Int64(1))]       |
+    |               |                     DataSourceExec: partitions=1, partition_sizes=[1]                       |
+    |               |     DataSourceExec: partitions=1, partition_sizes=[1]                                       |
+    |               |                                                                                             |
+    +---------------+---------------------------------------------------------------------------------------------+
     "
     );
 
@@ -2967,32 +2966,31 @@ async fn test_count_wildcard_on_where_scalar_subquery() -> Result<()> {
     assert_snapshot!(
         pretty_format_batches(&df_results).unwrap(),
         @r"
-    +---------------+---------------------------------------------------------------------------------------------------------------------------+
-    | plan_type     | plan                                                                                                                      |
-    +---------------+---------------------------------------------------------------------------------------------------------------------------+
-    | logical_plan  | Projection: t1.a, t1.b                                                                                                    |
-    |               |   Filter: CASE WHEN __scalar_sq_1.__always_true IS NULL THEN Int64(0) ELSE __scalar_sq_1.count(*) END > Int64(0)          |
-    |               |     Projection: t1.a, t1.b, __scalar_sq_1.count(*), __scalar_sq_1.__always_true                                           |
-    |               |       Left Join: t1.a = __scalar_sq_1.a                                                                                   |
-    |               |         TableScan: t1 projection=[a, b]                                                                                   |
-    |               |         SubqueryAlias: __scalar_sq_1                                                                                      |
-    |               |           Projection: count(*), t2.a, Boolean(true) AS __always_true                                                      |
-    |               |             Aggregate: groupBy=[[t2.a]], aggr=[[count(Int64(1)) AS count(*)]]                                             |
-    |               |               TableScan: t2 projection=[a]                                                                                |
-    | physical_plan | CoalesceBatchesExec: target_batch_size=8192                                                                               |
-    |               |   FilterExec: CASE WHEN __always_true@3 IS NULL THEN 0 ELSE count(*)@2 END > 0, projection=[a@0, b@1]                     |
-    |               |     CoalesceBatchesExec: target_batch_size=8192                                                                           |
-    |               |       HashJoinExec: mode=CollectLeft, join_type=Left, on=[(a@0, a@1)], projection=[a@0, b@1, count(*)@2, __always_true@4] |
-    |               |         DataSourceExec: partitions=1, partition_sizes=[1]                                                                 |
-    |               |         ProjectionExec: expr=[count(*)@1 as count(*), a@0 as a, true as __always_true]                                    |
-    |               |           AggregateExec: mode=FinalPartitioned, gby=[a@0 as a], aggr=[count(*)]                                           |
-    |               |             CoalesceBatchesExec: target_batch_size=8192                                                                   |
-    |               |               RepartitionExec: partitioning=Hash([a@0], 4), input_partitions=4                                            |
-    |               |                 RepartitionExec: partitioning=RoundRobinBatch(4), input_partitions=1                                      |
-    |               |                   AggregateExec: mode=Partial, gby=[a@0 as a], aggr=[count(*)]                                            |
-    |               |                     DataSourceExec: partitions=1, partition_sizes=[1]                                                     |
-    |               |                                                                                                                           |
-    +---------------+---------------------------------------------------------------------------------------------------------------------------+
+    +---------------+-------------------------------------------------------------------------------------------+
+    | plan_type     | plan                                                                                      |
+    +---------------+-------------------------------------------------------------------------------------------+
+    | logical_plan  | Projection: t1.a, t1.b                                                                    |
+    |               |   Inner Join: t1.a = __scalar_sq_1.a                                                      |
+    |               |     TableScan: t1 projection=[a, b]                                                       |
+    |               |     SubqueryAlias: __scalar_sq_1                                                          |
+    |               |       Projection: t2.a                                                                    |
+    |               |         Filter: CASE WHEN Boolean(false) THEN Int64(0) ELSE count(*) END > Int64(0)       |
+    |               |           Aggregate: groupBy=[[t2.a]], aggr=[[count(Int64(1)) AS count(*)]]               |
+    |               |             TableScan: t2 projection=[a]                                                  |
+    | physical_plan | CoalesceBatchesExec: target_batch_size=8192                                               |
+    |               |   HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(a@0, a@0)], projection=[a@1, b@2] |
+    |               |     CoalescePartitionsExec                                                                |
+    |               |       CoalesceBatchesExec: target_batch_size=8192                                         |
+    |               |         FilterExec: CASE WHEN false THEN 0 ELSE count(*)@1 END > 0, projection=[a@0]      |
+    |               |           AggregateExec: mode=FinalPartitioned, gby=[a@0 as a], aggr=[count(*)]           |
+    |               |             CoalesceBatchesExec: target_batch_size=8192                                   |
+    |               |               RepartitionExec: partitioning=Hash([a@0], 4), input_partitions=4            |
+    |               |                 RepartitionExec: partitioning=RoundRobinBatch(4), input_partitions=1      |
+    |               |                   AggregateExec: mode=Partial, gby=[a@0 as a], aggr=[count(*)]            |
+    |               |                     DataSourceExec: partitions=1, partition_sizes=[1]                     |
+    |               |     DataSourceExec: partitions=1, partition_sizes=[1]                                     |
+    |               |                                                                                           |
+    +---------------+-------------------------------------------------------------------------------------------+
     "
     );
 
@@ -17,3 +17,5 @@
 
 mod coop;
 mod logical_plan;
+mod progress;
+mod result_cache;
@@ -0,0 +1,135 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+//! Tests of the progress reported while executing queries
+
+use std::sync::Arc;
+
+use datafusion::execution::progress::{ProgressSnapshot, ProgressTracker};
+use datafusion::prelude::*;
+use datafusion_common::Result;
+use futures::TryStreamExt;
+use parking_lot::Mutex;
+use tempfile::TempDir;
+
+/// Writes `num_files` CSV files of 100 rows each into a new directory,
+/// returning the directory and the bytes of the files
+fn write_csv_files(num_files: usize) -> Result<(TempDir, u64)> {
+    let dir = TempDir::new()?;
+    let mut total_bytes = 0;
+    for file in 0..num_files {
+        let mut data = String::from("a,b\n");
+        for row in 0..100 {
+            data.push_str(&format!("{},value {row}\n", file * 100 + row));
+        }
+        std::fs::write(dir.path().join(format!("{file}.csv")), &data)?;
+        total_bytes += data.len() as u64;
+    }
+    Ok((dir, total_bytes))
+}
+
+/// Executes `sql` reporting its progress, returning the number of rows of
+/// the result and the reported snapshots
+async fn execute(
+    ctx: &SessionContext,
+    sql: &str,
+) -> Result<(usize, ProgressTracker, Vec<ProgressSnapshot>)> {
+    let plan = ctx.sql(sql).await?.create_physical_plan().await?;
+    let progress = ProgressTracker::new();
+    let snapshots = Arc::new(Mutex::new(vec![]));
+    let captured = Arc::clone(&snapshots);
+    progress.register_callback(move |snapshot| captured.lock().push(*snapshot));
+
+    let batches = ctx
+        .execute_with_progress(plan, progress.clone())?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let num_rows = batches.iter().map(|batch| batch.num_rows()).sum();
+    let snapshots = snapshots.lock().clone();
+    Ok((num_rows, progress, snapshots))
+}
+
+fn assert_monotonic(snapshots: &[ProgressSnapshot]) {
+    assert!(!snapshots.is_empty());
+    for pair in snapshots.windows(2) {
+        assert!(pair[0].bytes_scanned <= pair[1].bytes_scanned);
+        assert!(pair[0].total_bytes <= pair[1].total_bytes);
+        assert!(pair[0].rows_produced <= pair[1].rows_produced);
+        assert!(pair[0].operators_completed <= pair[1].operators_completed);
+    }
+}
+
+#[tokio::test]
+async fn progress_of_multi_file_csv_scan() -> Result<()> {
+    let (dir, total_bytes) = write_csv_files(5)?;
+    let config = SessionConfig::new().with_target_partitions(2);
+    let ctx = SessionContext::new_with_config(config);
+    ctx.register_csv("t", dir.path().to_str().unwrap(), CsvReadOptions::new())
+        .await?;
+
+    let (num_rows, progress, snapshots) =
+        execute(&ctx, "SELECT a, b FROM t WHERE a % 2 = 0").await?;
+    assert_eq!(num_rows, 250);
+    assert_monotonic(&snapshots);
+
+    let snapshot = progress.snapshot();
+    assert_eq!(snapshot.total_bytes, total_bytes);
+    assert_eq!(snapshot.bytes_scanned, total_bytes);
+    assert_eq!(snapshot.fraction(), Some(1.0));
+    assert_eq!(snapshot.rows_produced, 250);
+    assert_eq!(snapshot.operators_completed, 2);
+    assert_eq!(snapshots.last(), Some(&snapshot));
+
+    // Some progress was reported before the scans completed
+    assert!(snapshots
+        .iter()
+        .any(|s| s.fraction().is_some_and(|f| f > 0.0 && f < 1.0)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn progress_completes_at_limit() -> Result<()> {
+    let (dir, total_bytes) = write_csv_files(5)?;
+    let config = SessionConfig::new()
+        .with_target_partitions(1)
+        .with_batch_size(10);
+    let ctx = SessionContext::new_with_config(config);
+    ctx.register_csv("t", dir.path().to_str().unwrap(), CsvReadOptions::new())
+        .await?;
+
+    let (num_rows, progress, snapshots) =
+        execute(&ctx, "SELECT a FROM t LIMIT 10").await?;
+    assert_eq!(num_rows, 10);
+    assert_monotonic(&snapshots);
+
+    // The files left unscanned at the limit count as scanned
+    let snapshot = progress.snapshot();
+    assert_eq!(snapshot.total_bytes, total_bytes);
+    assert_eq!(snapshot.fraction(), Some(1.0));
+    assert_eq!(snapshot.rows_produced, 10);
+    Ok(())
+}
+
+#[tokio::test]
+async fn no_progress_without_file_scans() -> Result<()> {
+    let ctx = SessionContext::new();
+    let (num_rows, progress, _) =
+        execute(&ctx, "SELECT * FROM (VALUES (1), (2)) AS t(a)").await?;
+    assert_eq!(num_rows, 2);
+    assert_eq!(progress.snapshot(), ProgressSnapshot::default());
+    assert_eq!(progress.snapshot().fraction(), None);
+    Ok(())
+}
@@ -0,0 +1,164 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Tests of the results of queries cached by a [`ResultCache`]
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use arrow::array::{Int64Array, RecordBatch};
+use arrow::datatypes::{DataType, Field, Schema};
+use datafusion::execution::cache::result_cache::ResultCache;
+use datafusion::prelude::*;
+use datafusion_common::test_util::batches_to_string;
+use datafusion_common::{assert_contains, assert_not_contains, Result};
+use insta::assert_snapshot;
+use parquet::arrow::ArrowWriter;
+use tempfile::TempDir;
+
+/// Writes a parquet file holding the `values` of column `a`, last modified at
+/// `modified`
+fn write_file(path: &Path, values: Vec<i64>, modified: SystemTime) -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(Int64Array::from(values))],
+    )?;
+    let file = File::create(path)?;
+    let mut writer = ArrowWriter::try_new(file.try_clone()?, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    file.set_modified(modified)?;
+    Ok(())
+}
+
+async fn new_context(dir: &TempDir) -> Result<(SessionContext, Arc<ResultCache>)> {
+    let ctx = SessionContext::new();
+    let cache = Arc::new(ResultCache::new(
+        1024 * 1024,
+        Duration::from_secs(3600),
+        &ctx.runtime_env().memory_pool,
+    ));
+    let ctx = ctx.with_result_cache(Arc::clone(&cache));
+    ctx.register_parquet("t", dir.path().to_str().unwrap(), Default::default())
+        .await?;
+    Ok((ctx, cache))
+}
+
+async fn query(ctx: &SessionContext, sql: &str) -> Result<String> {
+    let batches = ctx.sql(sql).await?.collect().await?;
+    Ok(batches_to_string(&batches))
+}
+
+async fn explain_analyze(ctx: &SessionContext, sql: &str) -> Result<String> {
+    let batches = ctx
+        .sql(&format!("EXPLAIN ANALYZE {sql}"))
+        .await?
+        .collect()
+        .await?;
+    Ok(batches_to_string(&batches))
+}
+
+#[tokio::test]
+async fn result_cache_invalidated_by_modified_file() -> Result<()> {
+    let dir = TempDir::new()?;
+    let file = dir.path().join("data.parquet");
+    let modified = SystemTime::now() - Duration::from_secs(60);
+    write_file(&file, vec![1, 2, 3], modified)?;
+    let (ctx, cache) = new_context(&dir).await?;
+    let sql = "SELECT sum(a) AS total FROM t";
+
+    let expected = query(&ctx, sql).await?;
+    assert_snapshot!(expected, @r"
+    +-------+
+    | total |
+    +-------+
+    | 6     |
+    +-------+
+    ");
+    assert_eq!(cache.len(), 1);
+    assert_eq!(query(&ctx, sql).await?, expected);
+    assert_eq!((cache.hits(), cache.misses()), (1, 1));
+    assert_contains!(
+        explain_analyze(&ctx, sql).await?,
+        "ResultCacheExec: hit=true"
+    );
+
+    // Rewriting the file changes its modification time
+    write_file(&file, vec![10, 20], modified + Duration::from_secs(1))?;
+    assert_contains!(
+        explain_analyze(&ctx, sql).await?,
+        "ResultCacheExec: hit=false"
+    );
+    assert_snapshot!(query(&ctx, sql).await?, @r"
+    +-------+
+    | total |
+    +-------+
+    | 30    |
+    +-------+
+    ");
+    assert_eq!(cache.len(), 1);
+
+    // So does adding a file
+    write_file(&dir.path().join("more.parquet"), vec![5], modified)?;
+    assert_snapshot!(query(&ctx, sql).await?, @r"
+    +-------+
+    | total |
+    +-------+
+    | 35    |
+    +-------+
+    ");
+    assert_eq!((cache.hits(), cache.misses()), (3, 3));
+    assert!(cache.size() > 0);
+    assert_eq!(ctx.runtime_env().memory_pool.reserved(), cache.size());
+    Ok(())
+}
+
+#[tokio::test]
+async fn result_cache_bypassed_by_non_deterministic_queries() -> Result<()> {
+    let dir = TempDir::new()?;
+    write_file(
+        &dir.path().join("data.parquet"),
+        vec![1, 2, 3],
+        SystemTime::now(),
+    )?;
+    let (ctx, cache) = new_context(&dir).await?;
+
+    for sql in [
+        "SELECT a, random() AS r FROM t",
+        "SELECT now() AS n, count(*) FROM t",
+        "SELECT a FROM t WHERE a < (SELECT random() * 10)",
+    ] {
+        query(&ctx, sql).await?;
+        assert_not_contains!(explain_analyze(&ctx, sql).await?, "ResultCacheExec");
+    }
+    assert!(cache.is_empty());
+    assert_eq!((cache.hits(), cache.misses()), (0, 0));
+
+    // Tables without a snapshot token are not cached either
+    let batch =
+        RecordBatch::try_from_iter([("a", Arc::new(Int64Array::from(vec![1, 2])) as _)])?;
+    ctx.register_batch("m", batch)?;
+    assert_not_contains!(
+        explain_analyze(&ctx, "SELECT * FROM m").await?,
+        "ResultCacheExec"
+    );
+    assert!(cache.is_empty());
+    Ok(())
+}
@@ -145,6 +145,28 @@ async fn join_by_key_single_partition() {
         .await
 }
 
+#[tokio::test]
+async fn join_by_key_spill() {
+    let config = SessionConfig::new().with_target_partitions(1);
+    let test = TestCase::new()
+        .with_query("select count(*) from t t1 JOIN t t2 ON t1.time = t2.time")
+        .with_memory_limit(20_000)
+        .with_config(config);
+
+    test.clone()
+        .with_expected_errors(vec![
+            "Resources exhausted: Additional allocation failed with top memory consumers (across reservations) as:\n  HashJoinInput",
+        ])
+        .run()
+        .await;
+
+    // The build side does not fit in memory, so both inputs are spilled
+    test.with_disk_manager_builder(DiskManagerBuilder::default())
+        .with_expected_success()
+        .run()
+        .await;
+}
+
 #[tokio::test]
 async fn join_by_expression() {
     TestCase::new()
@@ -157,6 +179,30 @@ async fn join_by_expression() {
         .await
 }
 
+#[tokio::test]
+async fn join_by_expression_spill() {
+    for join_type in ["LEFT", "FULL"] {
+        let test = TestCase::new()
+            .with_query(format!(
+                "select count(*) from t t1 {join_type} JOIN t t2 ON t1.service != t2.service"
+            ))
+            .with_memory_limit(20_000);
+
+        test.clone()
+            .with_expected_errors(vec![
+                "Resources exhausted: Additional allocation failed with top memory consumers (across reservations) as:\n  NestedLoopJoinLoad",
+            ])
+            .run()
+            .await;
+
+        // The build side does not fit in memory, so it is joined in chunks
+        test.with_disk_manager_builder(DiskManagerBuilder::default())
+            .with_expected_success()
+            .run()
+            .await;
+    }
+}
+
 #[tokio::test]
 async fn cross_join() {
     TestCase::new()
@@ -422,6 +468,124 @@ async fn oom_grouped_hash_aggregate() {
         .await
 }
 
+/// Grouping on keys the input is sorted by emits each group as soon as it is
+/// complete, so it succeeds with far less memory than the hash table for all
+/// groups, which fails when spilling is disabled
+#[tokio::test]
+async fn group_by_sorted_external_table() -> Result<()> {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sorted.csv");
+    let mut data = "k,v\n".to_string();
+    for i in 0..200_000 {
+        data.push_str(&format!("key-{i:08},{i}\n"));
+    }
+    std::fs::write(&path, data).unwrap();
+
+    let runtime = RuntimeEnvBuilder::new()
+        .with_disk_manager_builder(
+            DiskManagerBuilder::default().with_mode(DiskManagerMode::Disabled),
+        )
+        .with_memory_limit(2_000_000, 1.0)
+        .build_arc()?;
+    let config = SessionConfig::new().with_target_partitions(1);
+    let ctx = SessionContext::new_with_config_rt(config, runtime);
+    let location = path.to_str().unwrap();
+    ctx.sql(&format!(
+        "CREATE EXTERNAL TABLE sorted (k VARCHAR, v BIGINT) STORED AS CSV \
+         LOCATION '{location}' OPTIONS ('format.has_header' 'true') WITH ORDER (k ASC)"
+    ))
+    .await?;
+    ctx.sql(&format!(
+        "CREATE EXTERNAL TABLE unsorted (k VARCHAR, v BIGINT) STORED AS CSV \
+         LOCATION '{location}' OPTIONS ('format.has_header' 'true')"
+    ))
+    .await?;
+
+    // casting to a view type keeps the ordering of the strings
+    let query = |table: &str| {
+        format!(
+            "SELECT count(*) FROM \
+             (SELECT arrow_cast(k, 'Utf8View'), sum(v) FROM {table} \
+              GROUP BY arrow_cast(k, 'Utf8View'))"
+        )
+    };
+
+    let plan = ctx
+        .sql(&format!("EXPLAIN {}", query("sorted")))
+        .await?
+        .collect()
+        .await?;
+    assert_contains!(
+        arrow::util::pretty::pretty_format_batches(&plan)?.to_string(),
+        "ordering_mode=Sorted"
+    );
+    let batches = ctx.sql(&query("sorted")).await?.collect().await?;
+    assert_batches_eq!(
+        [
+            "+----------+",
+            "| count(*) |",
+            "+----------+",
+            "| 200000   |",
+            "+----------+",
+        ],
+        &batches
+    );
+
+    let err = ctx
+        .sql(&query("unsorted"))
+        .await?
+        .collect()
+        .await
+        .unwrap_err();
+    assert_contains!(err.to_string(), "Resources exhausted");
+    Ok(())
+}
+
+/// Grouping on keys the input is only partially sorted by emits the groups of
+/// each completed sort prefix, but fails rather than exceeding the memory
+/// limit when the groups of a single prefix do not fit in it
+#[tokio::test]
+async fn group_by_partially_sorted_single_prefix() -> Result<()> {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("partially_sorted.csv");
+    let mut data = "p,k,v\n".to_string();
+    for i in 0..200_000 {
+        data.push_str(&format!("p,key-{i:08},{i}\n"));
+    }
+    std::fs::write(&path, data).unwrap();
+
+    let runtime = RuntimeEnvBuilder::new()
+        .with_disk_manager_builder(
+            DiskManagerBuilder::default().with_mode(DiskManagerMode::Disabled),
+        )
+        .with_memory_limit(2_000_000, 1.0)
+        .build_arc()?;
+    let config = SessionConfig::new().with_target_partitions(1);
+    let ctx = SessionContext::new_with_config_rt(config, runtime);
+    let location = path.to_str().unwrap();
+    ctx.sql(&format!(
+        "CREATE EXTERNAL TABLE partially_sorted (p VARCHAR, k VARCHAR, v BIGINT) \
+         STORED AS CSV LOCATION '{location}' OPTIONS ('format.has_header' 'true') \
+         WITH ORDER (p ASC)"
+    ))
+    .await?;
+
+    let query = "SELECT count(*) FROM \
+                 (SELECT p, k, sum(v) FROM partially_sorted GROUP BY p, k)";
+    let plan = ctx
+        .sql(&format!("EXPLAIN {query}"))
+        .await?
+        .collect()
+        .await?;
+    assert_contains!(
+        arrow::util::pretty::pretty_format_batches(&plan)?.to_string(),
+        "ordering_mode=PartiallySorted"
+    );
+    let err = ctx.sql(query).await?.collect().await.unwrap_err();
+    assert_contains!(err.to_string(), "Resources exhausted");
+    Ok(())
+}
+
 /// For regression case: if spilled `StringViewArray`'s buffer will be referenced by
 /// other batches which are also need to be spilled, then the spill writer will
 /// repeatedly write out the same buffer, and after reading back, each batch's size
@@ -567,6 +731,7 @@ async fn setup_context(
         disk_manager: Arc::new(disk_manager),
         cache_manager: runtime.cache_manager.clone(),
         object_store_registry: runtime.object_store_registry.clone(),
+        object_store_rate_limiter: None,
     });
 
     let config = SessionConfig::new()
@@ -593,8 +758,12 @@ async fn test_disk_spill_limit_reached() -> Result<()> {
 
     let err = df.collect().await.unwrap_err();
     assert_contains!(
-    err.to_string(),
-    "The used disk space during the spilling process has exceeded the allowable limit"
+        err.to_string(),
+        "Disk quota exceeded while Sorting: the temporary files of all queries use"
+    );
+    assert_contains!(
+        err.to_string(),
+        "more than the allowable limit of 1024.0 KB"
     );
 
     Ok(())
@@ -706,6 +875,132 @@ async fn test_spill_file_compressed_with_lz4_frame() -> Result<()> {
 
     Ok(())
 }
+
+/// A sort spilling more than the disk limit of each query fails, and all the
+/// disk usage of the query is released afterwards
+#[tokio::test]
+async fn test_per_query_disk_spill_limit_reached() -> Result<()> {
+    let runtime = RuntimeEnvBuilder::new()
+        .with_memory_pool(Arc::new(FairSpillPool::new(1024 * 1024)))
+        .with_max_temp_directory_size(100 * 1024 * 1024)
+        .with_max_temp_directory_size_per_query(1024 * 1024)
+        .build_arc()?;
+    let config = SessionConfig::new()
+        .with_sort_spill_reservation_bytes(64 * 1024)
+        .with_sort_in_place_threshold_bytes(0)
+        .with_batch_size(64)
+        .with_target_partitions(1);
+    let ctx = SessionContext::new_with_config_rt(config, Arc::clone(&runtime));
+
+    let err = ctx
+        .sql("select * from generate_series(1, 1000000000000) as t1(v1) order by v1")
+        .await?
+        .collect()
+        .await
+        .unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "Disk quota exceeded while Sorting: the temporary files of the query use"
+    );
+    assert_contains!(
+        err.to_string(),
+        "more than the allowable limit of 1024.0 KB per query"
+    );
+
+    let usage = runtime.disk_usage();
+    assert_eq!(usage.used, 0);
+    assert_eq!(usage.limit, 100 * 1024 * 1024);
+    assert!(usage.queries.is_empty());
+
+    Ok(())
+}
+
+/// The spill files of a running query are reported by
+/// `RuntimeEnv::disk_usage`, and released when the query is cancelled
+#[tokio::test]
+async fn test_disk_usage_of_running_query() -> Result<()> {
+    let ctx = setup_context(10 * 1024 * 1024, 128 * 1024, SpillCompression::Uncompressed)
+        .await?;
+    let runtime = ctx.runtime_env();
+
+    let df = ctx
+        .sql("select * from generate_series(1, 100000) as t1(v1) order by v1")
+        .await?;
+    let plan = df.create_physical_plan().await?;
+    let task_ctx = ctx.task_ctx();
+    let mut stream = plan.execute(0, Arc::clone(&task_ctx))?;
+    // the sort has spilled its input once it outputs its first batch
+    stream.next().await.unwrap()?;
+
+    let usage = runtime.disk_usage();
+    assert!(usage.used > 0);
+    assert_eq!(usage.queries.len(), 1);
+    assert_eq!(usage.queries[0].session_id, ctx.session_id());
+    assert_eq!(usage.queries[0].used, usage.used);
+
+    drop(stream);
+    drop(task_ctx);
+    let usage = runtime.disk_usage();
+    assert_eq!(usage.used, 0);
+    assert!(usage.queries.is_empty());
+
+    Ok(())
+}
+
+// Tests for the memory limit of each query (`per_query_memory_limit`)
+// -------------------------------------------------------------------
+
+/// A query that exceeds its own memory limit fails without affecting another
+/// query running concurrently in the same memory pool
+#[tokio::test]
+async fn test_per_query_memory_limit() -> Result<()> {
+    let runtime = RuntimeEnvBuilder::new()
+        .with_memory_pool(Arc::new(GreedyMemoryPool::new(100 * 1024 * 1024)))
+        .with_disk_manager_builder(
+            DiskManagerBuilder::default().with_mode(DiskManagerMode::Disabled),
+        )
+        .build_arc()?;
+    let sql = "select * from generate_series(1, 100000) as t1(v1) order by v1";
+
+    let limited_config = SessionConfig::new()
+        .with_query_memory_limit(100 * 1024)
+        .with_target_partitions(1);
+    let limited_ctx =
+        SessionContext::new_with_config_rt(limited_config, Arc::clone(&runtime));
+    let limited_plan = limited_ctx.sql(sql).await?.create_physical_plan().await?;
+    let limited_task_ctx = limited_ctx.task_ctx();
+
+    let config = SessionConfig::new()
+        .with_query_memory_limit(50 * 1024 * 1024)
+        .with_target_partitions(1);
+    let ctx = SessionContext::new_with_config_rt(config, Arc::clone(&runtime));
+    let plan = ctx.sql(sql).await?.create_physical_plan().await?;
+    let task_ctx = ctx.task_ctx();
+
+    let (limited_result, result) = tokio::join!(
+        collect_batches(limited_plan, Arc::clone(&limited_task_ctx)),
+        collect_batches(plan, Arc::clone(&task_ctx)),
+    );
+
+    let err = limited_result.unwrap_err().strip_backtrace();
+    assert_contains!(&err, "Query memory limit of 100.0 KB exceeded");
+    assert_contains!(&err, "ExternalSorter");
+
+    let rows: usize = result?.iter().map(|batch| batch.num_rows()).sum();
+    assert_eq!(rows, 100000);
+
+    // the peak memory of each query is reported after execution
+    let limited_pool = limited_task_ctx.query_memory_pool().unwrap();
+    assert!(limited_pool.peak() <= 100 * 1024);
+    let pool = task_ctx.query_memory_pool().unwrap();
+    assert!(pool.peak() > 100 * 1024);
+
+    // all memory is returned to the shared pool
+    assert_eq!(runtime.memory_pool.reserved(), 0);
+
+    Ok(())
+}
+
 /// Run the query with the specified memory limit,
 /// and verifies the expected errors are returned
 #[derive(Clone, Debug)]
@@ -15,18 +15,20 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::fs::File;
+use std::path::Path;
 use std::sync::Arc;
 
 use arrow::array::{
-    types::Int32Type, ArrayRef, DictionaryArray, Float32Array, Int64Array, RecordBatch,
-    StringArray,
+    types::Int32Type, ArrayRef, DictionaryArray, Float32Array, Int32Array, Int64Array,
+    RecordBatch, StringArray, TimestampMillisecondArray, TimestampNanosecondArray,
 };
-use arrow::datatypes::{DataType, Field, Schema};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
 use datafusion::datasource::physical_plan::ParquetSource;
 use datafusion::physical_plan::collect;
-use datafusion::prelude::SessionContext;
+use datafusion::prelude::{ParquetReadOptions, SessionConfig, SessionContext};
 use datafusion::test::object_store::local_unpartitioned_file;
-use datafusion_common::test_util::batches_to_sort_string;
+use datafusion_common::test_util::{batches_to_sort_string, batches_to_string};
 use datafusion_common::Result;
 use datafusion_execution::object_store::ObjectStoreUrl;
 
@@ -36,7 +38,7 @@ use insta::assert_snapshot;
 use object_store::ObjectMeta;
 use parquet::arrow::ArrowWriter;
 use parquet::file::properties::WriterProperties;
-use tempfile::NamedTempFile;
+use tempfile::{NamedTempFile, TempDir};
 
 /// Test for reading data from multiple parquet files with different schemas and coercing them into a single schema.
 #[tokio::test]
@@ -149,6 +151,96 @@ async fn multi_parquet_coercion_projection() {
     ");
 }
 
+/// Test for scanning a listing table whose files have different but compatible
+/// schemas when `listing_table_schema_coercion` is enabled.
+#[tokio::test]
+async fn listing_table_schema_coercion() -> Result<()> {
+    let dir = TempDir::new()?;
+    let id: ArrayRef = Arc::new(Int32Array::from(vec![1, 2]));
+    let ts: ArrayRef = Arc::new(TimestampMillisecondArray::from(vec![1_000, 2_000]));
+    write_parquet(
+        &dir.path().join("1.parquet"),
+        RecordBatch::try_from_iter(vec![("id", id), ("ts", ts)])?,
+    );
+    let id: ArrayRef = Arc::new(Int64Array::from(vec![3]));
+    let ts: ArrayRef = Arc::new(TimestampNanosecondArray::from(vec![3_000_000_000]));
+    write_parquet(
+        &dir.path().join("2.parquet"),
+        RecordBatch::try_from_iter(vec![("id", id), ("ts", ts)])?,
+    );
+
+    let config = SessionConfig::new()
+        .set_bool("datafusion.execution.listing_table_schema_coercion", true);
+    let ctx = SessionContext::new_with_config(config);
+    ctx.register_parquet(
+        "t",
+        dir.path().to_str().unwrap(),
+        ParquetReadOptions::default(),
+    )
+    .await?;
+
+    // The widest type of each column is inferred
+    let df = ctx.sql("SELECT id, ts FROM t ORDER BY id").await?;
+    let schema = df.schema().as_arrow().clone();
+    assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+    assert_eq!(
+        schema.field(1).data_type(),
+        &DataType::Timestamp(TimeUnit::Nanosecond, None)
+    );
+    assert_snapshot!(batches_to_string(&df.collect().await?), @r"
+    +----+---------------------+
+    | id | ts                  |
+    +----+---------------------+
+    | 1  | 1970-01-01T00:00:01 |
+    | 2  | 1970-01-01T00:00:02 |
+    | 3  | 1970-01-01T00:00:03 |
+    +----+---------------------+
+    ");
+
+    // A file whose column can't be promoted is an error naming the file
+    let name: ArrayRef = Arc::new(StringArray::from(vec!["four"]));
+    write_parquet(
+        &dir.path().join("3.parquet"),
+        RecordBatch::try_from_iter(vec![("id", name)])?,
+    );
+    ctx.sql(&format!(
+        "CREATE EXTERNAL TABLE t2 (id BIGINT) STORED AS PARQUET LOCATION '{}'",
+        dir.path().to_str().unwrap()
+    ))
+    .await?;
+    let err = ctx
+        .sql("SELECT id FROM t2")
+        .await?
+        .collect()
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("3.parquet"), "{err}");
+    assert!(err.contains("Cannot promote column id"), "{err}");
+
+    // Neither can the schemas of the files be merged
+    let err = ctx
+        .register_parquet(
+            "t3",
+            dir.path().to_str().unwrap(),
+            ParquetReadOptions::default(),
+        )
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Cannot merge column id"), "{err}");
+    Ok(())
+}
+
+/// Writes `batch` to a parquet file at `path`
+fn write_parquet(path: &Path, batch: RecordBatch) {
+    let file = File::create(path).expect("creating file");
+    let mut writer =
+        ArrowWriter::try_new(file, batch.schema(), None).expect("creating writer");
+    writer.write(&batch).expect("Writing batch");
+    writer.close().unwrap();
+}
+
 /// Writes `batches` to a temporary parquet file
 pub async fn store_parquet(
     batches: Vec<RecordBatch>,
@@ -38,6 +38,7 @@ use datafusion::datasource::source::DataSourceExec;
 use datafusion::datasource::MemTable;
 use datafusion::prelude::{SessionConfig, SessionContext};
 use datafusion_common::error::Result;
+use datafusion_common::stats::Precision;
 use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
 use datafusion_common::ScalarValue;
 use datafusion_datasource::file_groups::FileGroup;
@@ -176,6 +177,20 @@ fn parquet_exec() -> Arc<DataSourceExec> {
     parquet_exec_with_sort(schema(), vec![])
 }
 
+/// Created a parquet exec whose statistics estimate `num_rows` rows
+fn parquet_exec_with_num_rows(num_rows: Precision<usize>) -> Arc<DataSourceExec> {
+    let config = FileScanConfigBuilder::new(
+        ObjectStoreUrl::parse("test:///").unwrap(),
+        schema(),
+        Arc::new(ParquetSource::default()),
+    )
+    .with_file(PartitionedFile::new("x".to_string(), 100))
+    .with_statistics(Statistics::new_unknown(&schema()).with_num_rows(num_rows))
+    .build();
+
+    DataSourceExec::from_data_source(config)
+}
+
 fn parquet_exec_multiple() -> Arc<DataSourceExec> {
     parquet_exec_multiple_sorted(vec![])
 }
@@ -433,6 +448,12 @@ impl TestConfig {
         self
     }
 
+    /// Only repartition inputs estimated to have at least `rows` rows.
+    fn with_repartition_threshold_rows(mut self, rows: usize) -> Self {
+        self.config.optimizer.repartition_threshold_rows = rows;
+        self
+    }
+
     /// Set the preferred target partitions for query execution concurrency.
     fn with_query_execution_partitions(mut self, target_partitions: usize) -> Self {
         self.config.execution.target_partitions = target_partitions;
@@ -2443,6 +2464,51 @@ fn parallelization_single_partition() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn parallelization_below_threshold_rows() -> Result<()> {
+    let alias = vec![("a".to_string(), "a".to_string())];
+    let test_config = TestConfig::default()
+        .with_prefer_repartition_file_scans(10)
+        .with_query_execution_partitions(2)
+        .with_repartition_threshold_rows(100);
+
+    // Inputs estimated to be small are neither split nor round robin
+    // repartitioned, whether the estimate is exact or not
+    for num_rows in [Precision::Exact(5), Precision::Inexact(5)] {
+        let plan = aggregate_exec_with_alias(
+            filter_exec(parquet_exec_with_num_rows(num_rows)),
+            alias.clone(),
+        );
+        let expected = [
+            "AggregateExec: mode=FinalPartitioned, gby=[a@0 as a], aggr=[]",
+            "  AggregateExec: mode=Partial, gby=[a@0 as a], aggr=[]",
+            "    FilterExec: c@2 = 0",
+            "      DataSourceExec: file_groups={1 group: [[x]]}, projection=[a, b, c, d, e], file_type=parquet",
+        ];
+        test_config.run(&expected, plan.clone(), &DISTRIB_DISTRIB_SORT)?;
+        test_config.run(&expected, plan, &SORT_DISTRIB_DISTRIB)?;
+    }
+
+    // Inputs estimated to be large, or without estimates, are repartitioned
+    for num_rows in [Precision::Inexact(1000), Precision::Absent] {
+        let plan = aggregate_exec_with_alias(
+            filter_exec(parquet_exec_with_num_rows(num_rows)),
+            alias.clone(),
+        );
+        let expected = [
+            "AggregateExec: mode=FinalPartitioned, gby=[a@0 as a], aggr=[]",
+            "  RepartitionExec: partitioning=Hash([a@0], 2), input_partitions=2",
+            "    AggregateExec: mode=Partial, gby=[a@0 as a], aggr=[]",
+            "      FilterExec: c@2 = 0",
+            "        DataSourceExec: file_groups={2 groups: [[x:0..50], [x:50..100]]}, projection=[a, b, c, d, e], file_type=parquet",
+        ];
+        test_config.run(&expected, plan.clone(), &DISTRIB_DISTRIB_SORT)?;
+        test_config.run(&expected, plan, &SORT_DISTRIB_DISTRIB)?;
+    }
+
+    Ok(())
+}
+
 #[test]
 fn parallelization_multiple_files() -> Result<()> {
     let schema = schema();
@@ -489,6 +489,222 @@ STORED AS PARQUET;
     // Pushdown pruned most rows
 }
 
+#[tokio::test]
+async fn test_hash_join_dynamic_filter_pushdown() {
+    use datafusion_common::{JoinType, NullEquality};
+    use datafusion_physical_plan::joins::{HashJoinExec, PartitionMode};
+
+    let build_batches = vec![record_batch!(
+        ("a", Utf8, ["aa", "ab"]),
+        ("b", Utf8, ["ba", "bb"]),
+        ("c", Float64, [1.0, 2.0])
+    )
+    .unwrap()];
+    let build_scan = TestScanBuilder::new(schema())
+        .with_support(false)
+        .with_batches(build_batches)
+        .build();
+    let probe_batches = vec![record_batch!(
+        ("a", Utf8, ["aa", "ab", "ac", "ad"]),
+        ("b", Utf8, ["ba", "bb", "bc", "bd"]),
+        ("c", Float64, [1.0, 2.0, 3.0, 4.0])
+    )
+    .unwrap()];
+    let probe_scan = TestScanBuilder::new(schema())
+        .with_support(true)
+        .with_batches(probe_batches)
+        .build();
+    let on = vec![(col("a", &schema()).unwrap(), col("a", &schema()).unwrap())];
+    let plan = Arc::new(
+        HashJoinExec::try_new(
+            build_scan,
+            probe_scan,
+            on,
+            None,
+            &JoinType::Inner,
+            None,
+            PartitionMode::CollectLeft,
+            NullEquality::NullEqualsNothing,
+        )
+        .unwrap(),
+    ) as Arc<dyn ExecutionPlan>;
+
+    // expect the filter to be pushed down into the probe side only
+    insta::assert_snapshot!(
+        OptimizationTest::new(Arc::clone(&plan), FilterPushdown::new_post_optimization(), true),
+        @r"
+    OptimizationTest:
+      input:
+        - HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(a@0, a@0)]
+        -   DataSourceExec: file_groups={1 group: [[test.parquet]]}, projection=[a, b, c], file_type=test, pushdown_supported=false
+        -   DataSourceExec: file_groups={1 group: [[test.parquet]]}, projection=[a, b, c], file_type=test, pushdown_supported=true
+      output:
+        Ok:
+          - HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(a@0, a@0)]
+          -   DataSourceExec: file_groups={1 group: [[test.parquet]]}, projection=[a, b, c], file_type=test, pushdown_supported=false
+          -   DataSourceExec: file_groups={1 group: [[test.parquet]]}, projection=[a, b, c], file_type=test, pushdown_supported=true, predicate=DynamicFilterPhysicalExpr [ true ]
+    "
+    );
+
+    let mut config = ConfigOptions::default();
+    config.execution.parquet.pushdown_filters = true;
+    let plan = FilterPushdown::new_post_optimization()
+        .optimize(plan, &config)
+        .unwrap();
+    let session_ctx = SessionContext::new_with_config(SessionConfig::new());
+    session_ctx.register_object_store(
+        ObjectStoreUrl::parse("test://").unwrap().as_ref(),
+        Arc::new(InMemory::new()),
+    );
+    let task_ctx = session_ctx.state().task_ctx();
+    let mut stream = plan.execute(0, Arc::clone(&task_ctx)).unwrap();
+    // Probing requires the build side to be complete
+    stream.next().await.unwrap().unwrap();
+    // The filter reflects the keys of the build side
+    insta::assert_snapshot!(
+        format!("{}", format_plan_for_test(&plan)),
+        @r#"
+    - HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(a@0, a@0)]
+    -   DataSourceExec: file_groups={1 group: [[test.parquet]]}, projection=[a, b, c], file_type=test, pushdown_supported=false
+    -   DataSourceExec: file_groups={1 group: [[test.parquet]]}, projection=[a, b, c], file_type=test, pushdown_supported=true, predicate=DynamicFilterPhysicalExpr [ a@0 >= aa AND a@0 <= ab AND Use a@0 IN (SET) ([Literal { value: Utf8("aa"), field: Field { name: "lit", data_type: Utf8, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} } }, Literal { value: Utf8("ab"), field: Field { name: "lit", data_type: Utf8, nullable: false, dict_id: 0, dict_is_ordered: false, metadata: {} } }]) ]
+    "#
+    );
+}
+
+#[test]
+fn test_partitioned_hash_join_no_dynamic_filter_pushdown() {
+    use datafusion_common::{JoinType, NullEquality};
+    use datafusion_physical_plan::joins::{HashJoinExec, PartitionMode};
+
+    let build_scan = TestScanBuilder::new(schema()).with_support(false).build();
+    let probe_scan = TestScanBuilder::new(schema()).with_support(true).build();
+    let on = vec![(col("a", &schema()).unwrap(), col("a", &schema()).unwrap())];
+    let plan = Arc::new(
+        HashJoinExec::try_new(
+            build_scan,
+            probe_scan,
+            on,
+            None,
+            &JoinType::Inner,
+            None,
+            PartitionMode::Partitioned,
+            NullEquality::NullEqualsNothing,
+        )
+        .unwrap(),
+    ) as Arc<dyn ExecutionPlan>;
+
+    // every partition collects its own build side, no filter is pushed down
+    insta::assert_snapshot!(
+        OptimizationTest::new(plan, FilterPushdown::new_post_optimization(), true),
+        @r"
+    OptimizationTest:
+      input:
+        - HashJoinExec: mode=Partitioned, join_type=Inner, on=[(a@0, a@0)]
+        -   DataSourceExec: file_groups={1 group: [[test.parquet]]}, projection=[a, b, c], file_type=test, pushdown_supported=false
+        -   DataSourceExec: file_groups={1 group: [[test.parquet]]}, projection=[a, b, c], file_type=test, pushdown_supported=true
+      output:
+        Ok:
+          - HashJoinExec: mode=Partitioned, join_type=Inner, on=[(a@0, a@0)]
+          -   DataSourceExec: file_groups={1 group: [[test.parquet]]}, projection=[a, b, c], file_type=test, pushdown_supported=false
+          -   DataSourceExec: file_groups={1 group: [[test.parquet]]}, projection=[a, b, c], file_type=test, pushdown_supported=true
+    "
+    );
+}
+
+/// Integration test for dynamic filter pushdown from the build side of a hash
+/// join into a parquet scan on the probe side
+#[tokio::test]
+async fn test_hash_join_dynamic_filter_pushdown_integration() {
+    let store = Arc::new(InMemory::new()) as Arc<dyn ObjectStore>;
+    let mut cfg = SessionConfig::new();
+    cfg.options_mut().execution.parquet.pushdown_filters = true;
+    cfg.options_mut().execution.parquet.max_row_group_size = 128;
+    let ctx = SessionContext::new_with_config(cfg);
+    ctx.register_object_store(
+        ObjectStoreUrl::parse("memory://").unwrap().as_ref(),
+        Arc::clone(&store),
+    );
+    ctx.sql(
+        r"
+COPY (
+  SELECT value AS id, value % 7 AS amount
+  FROM generate_series(0, 9999)
+  ORDER BY id
+) TO 'memory:///fact.parquet'
+STORED AS PARQUET;
+  ",
+    )
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+    ctx.sql(
+        r"
+COPY (
+  SELECT value AS id, value / 1000 AS year
+  FROM generate_series(0, 9999)
+) TO 'memory:///dim.parquet'
+STORED AS PARQUET;
+  ",
+    )
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+    ctx.register_parquet(
+        "fact",
+        "memory:///fact.parquet",
+        ParquetReadOptions::default(),
+    )
+    .await
+    .unwrap();
+    ctx.register_parquet(
+        "dim",
+        "memory:///dim.parquet",
+        ParquetReadOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    let query = "SELECT count(*), sum(f.amount) FROM fact f JOIN dim d ON f.id = d.id WHERE d.year = 3";
+    let batches = ctx.sql(query).await.unwrap().collect().await.unwrap();
+    insta::assert_snapshot!(
+        pretty_format_batches(&batches).unwrap(),
+        @r"
+    +----------+---------------+
+    | count(*) | sum(f.amount) |
+    +----------+---------------+
+    | 1000     | 3000          |
+    +----------+---------------+
+    "
+    );
+
+    let batches = ctx
+        .sql(&format!("EXPLAIN ANALYZE {query}"))
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    let explain = format!("{}", pretty_format_batches(&batches).unwrap());
+    let fact_scan = explain.split("[[fact.parquet]]").nth(1).unwrap();
+    // Only the row groups containing the ids of the dimension rows are read
+    assert!(
+        fact_scan.contains("DynamicFilterPhysicalExpr [ id@0 >= 3000 AND id@0 <= 3999 ]"),
+        "{explain}"
+    );
+    assert!(
+        fact_scan.contains("row_groups_matched_statistics=9"),
+        "{explain}"
+    );
+    assert!(
+        fact_scan.contains("row_groups_pruned_statistics=70"),
+        "{explain}"
+    );
+}
+
 /// Schema:
 /// a: String
 /// b: String
@@ -31,7 +31,9 @@ use datafusion_common::{Result, Statistics};
 use datafusion_execution::{RecordBatchStream, SendableRecordBatchStream, TaskContext};
 use datafusion_expr::Operator;
 use datafusion_physical_expr::expressions::col;
-use datafusion_physical_expr::expressions::{BinaryExpr, Column, NegativeExpr};
+use datafusion_physical_expr::expressions::{
+    in_list, lit, BinaryExpr, Column, NegativeExpr,
+};
 use datafusion_physical_expr::intervals::utils::check_support;
 use datafusion_physical_expr::PhysicalExprRef;
 use datafusion_physical_expr::{EquivalenceProperties, Partitioning, PhysicalExpr};
@@ -40,6 +42,7 @@ use datafusion_physical_optimizer::join_selection::{
 };
 use datafusion_physical_optimizer::PhysicalOptimizerRule;
 use datafusion_physical_plan::displayable;
+use datafusion_physical_plan::filter::FilterExec;
 use datafusion_physical_plan::joins::utils::ColumnIndex;
 use datafusion_physical_plan::joins::utils::JoinFilter;
 use datafusion_physical_plan::joins::{HashJoinExec, NestedLoopJoinExec, PartitionMode};
@@ -496,6 +499,85 @@ async fn test_join_no_swap() {
     );
 }
 
+#[tokio::test]
+async fn test_join_with_swap_selective_in_list() {
+    let (_, small) = create_big_and_small();
+    let big_statistics = big_statistics();
+    let big_rows = *big_statistics.num_rows.get_value().unwrap();
+    let big = Arc::new(StatisticsExec::new(
+        Statistics {
+            column_statistics: create_column_stats(
+                Some(1),
+                Some(big_rows as u64),
+                Some(big_rows),
+            ),
+            ..big_statistics
+        },
+        Schema::new(vec![Field::new("big_col", DataType::UInt64, false)]),
+    ));
+
+    let join = |negated: bool| {
+        // big_col [NOT] IN (1, 2, 3)
+        let predicate = in_list(
+            col("big_col", &big.schema()).unwrap(),
+            vec![lit(1u64), lit(2u64), lit(3u64)],
+            &negated,
+            &big.schema(),
+        )
+        .unwrap();
+        let filter: Arc<dyn ExecutionPlan> =
+            Arc::new(FilterExec::try_new(predicate, Arc::clone(&big) as _).unwrap());
+        let join = Arc::new(
+            HashJoinExec::try_new(
+                Arc::clone(&small),
+                Arc::clone(&filter),
+                vec![(
+                    col("small_col", &small.schema()).unwrap(),
+                    col("big_col", &filter.schema()).unwrap(),
+                )],
+                None,
+                &JoinType::Inner,
+                None,
+                PartitionMode::CollectLeft,
+                NullEquality::NullEqualsNothing,
+            )
+            .unwrap(),
+        );
+        JoinSelection::new()
+            .optimize(join, &ConfigOptions::new())
+            .unwrap()
+    };
+
+    // Only 3 of the distinct values of big_col are selected, so the filtered
+    // side becomes the build side
+    let optimized_join = join(false);
+    let swapped_join = optimized_join
+        .as_any()
+        .downcast_ref::<ProjectionExec>()
+        .expect("A proj is required to swap columns back to their original order")
+        .input()
+        .as_any()
+        .downcast_ref::<HashJoinExec>()
+        .expect("The type of the plan should not be changed");
+    assert!(swapped_join.left().as_any().is::<FilterExec>());
+    assert_eq!(
+        swapped_join
+            .left()
+            .partition_statistics(None)
+            .unwrap()
+            .total_byte_size,
+        Precision::Inexact(24)
+    );
+
+    // All but 3 of the distinct values of big_col are selected
+    let optimized_join = join(true);
+    let join = optimized_join
+        .as_any()
+        .downcast_ref::<HashJoinExec>()
+        .expect("The type of the plan should not be changed");
+    assert!(join.right().as_any().is::<FilterExec>());
+}
+
 #[rstest(
     join_type,
     case::inner(JoinType::Inner),
@@ -27,12 +27,13 @@ mod test {
     use datafusion_common::stats::Precision;
     use datafusion_common::Result;
     use datafusion_common::{ColumnStatistics, ScalarValue, Statistics};
+    use datafusion_common::{JoinType, NullEquality};
     use datafusion_execution::config::SessionConfig;
     use datafusion_execution::TaskContext;
     use datafusion_expr_common::operator::Operator;
     use datafusion_functions_aggregate::count::count_udaf;
     use datafusion_physical_expr::aggregate::AggregateExprBuilder;
-    use datafusion_physical_expr::expressions::{binary, col, lit, Column};
+    use datafusion_physical_expr::expressions::{binary, col, lit, CastExpr, Column};
     use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
     use datafusion_physical_expr_common::sort_expr::PhysicalSortExpr;
     use datafusion_physical_plan::aggregates::{
@@ -42,10 +43,11 @@ mod test {
     use datafusion_physical_plan::coalesce_partitions::CoalescePartitionsExec;
     use datafusion_physical_plan::empty::EmptyExec;
     use datafusion_physical_plan::filter::FilterExec;
-    use datafusion_physical_plan::joins::CrossJoinExec;
+    use datafusion_physical_plan::joins::{CrossJoinExec, HashJoinExec, PartitionMode};
     use datafusion_physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
     use datafusion_physical_plan::projection::ProjectionExec;
     use datafusion_physical_plan::sorts::sort::SortExec;
+    use datafusion_physical_plan::test::exec::StatisticsExec;
     use datafusion_physical_plan::union::UnionExec;
     use datafusion_physical_plan::{
         execute_stream_partitioned, get_plan_string, ExecutionPlan,
@@ -728,4 +730,154 @@ mod test {
 
         Ok(())
     }
+
+    fn exact_column_statistics(
+        min: i32,
+        max: i32,
+        distinct_count: usize,
+        null_count: usize,
+    ) -> ColumnStatistics {
+        ColumnStatistics {
+            null_count: Precision::Exact(null_count),
+            max_value: Precision::Exact(ScalarValue::Int32(Some(max))),
+            min_value: Precision::Exact(ScalarValue::Int32(Some(min))),
+            sum_value: Precision::Absent,
+            distinct_count: Precision::Exact(distinct_count),
+        }
+    }
+
+    /// Returns the row count, and the null and distinct counts of every column
+    fn counts(
+        statistics: &Statistics,
+    ) -> (Precision<usize>, Vec<(Precision<usize>, Precision<usize>)>) {
+        let column_counts = statistics
+            .column_statistics
+            .iter()
+            .map(|s| (s.null_count, s.distinct_count))
+            .collect();
+        (statistics.num_rows, column_counts)
+    }
+
+    #[tokio::test]
+    async fn test_column_statistics_through_projection_filter_and_join() -> Result<()> {
+        use Precision::*;
+
+        // left: a in [0, 99] with 100 distinct values, b with 10 nulls
+        let left = Arc::new(StatisticsExec::new(
+            Statistics {
+                num_rows: Exact(1000),
+                total_byte_size: Absent,
+                column_statistics: vec![
+                    exact_column_statistics(0, 99, 100, 0),
+                    exact_column_statistics(0, 999, 1000, 10),
+                ],
+            },
+            Schema::new(vec![
+                Field::new("a", DataType::Int32, false),
+                Field::new("b", DataType::Int32, true),
+            ]),
+        ));
+        // right: c in [0, 49] with 40 distinct values, d with 20 nulls
+        let right: Arc<dyn ExecutionPlan> = Arc::new(StatisticsExec::new(
+            Statistics {
+                num_rows: Exact(200),
+                total_byte_size: Absent,
+                column_statistics: vec![
+                    exact_column_statistics(0, 49, 40, 0),
+                    exact_column_statistics(0, 199, 180, 20),
+                ],
+            },
+            Schema::new(vec![
+                Field::new("c", DataType::Int32, false),
+                Field::new("d", DataType::Int32, true),
+            ]),
+        ));
+
+        // SELECT a, CAST(b AS BIGINT) AS b FROM left
+        let projection: Arc<dyn ExecutionPlan> = Arc::new(ProjectionExec::try_new(
+            vec![
+                (col("a", &left.schema())?, "a".to_string()),
+                (
+                    Arc::new(CastExpr::new(
+                        col("b", &left.schema())?,
+                        DataType::Int64,
+                        None,
+                    )),
+                    "b".to_string(),
+                ),
+            ],
+            left,
+        )?);
+        assert_eq!(
+            counts(&projection.partition_statistics(None)?),
+            (
+                Exact(1000),
+                vec![(Exact(0), Exact(100)), (Inexact(10), Inexact(1000))]
+            )
+        );
+
+        // ... WHERE a < 50 selects half of the rows
+        let predicate = binary(
+            col("a", &projection.schema())?,
+            Operator::Lt,
+            lit(50i32),
+            &projection.schema(),
+        )?;
+        let filter: Arc<dyn ExecutionPlan> =
+            Arc::new(FilterExec::try_new(predicate, projection)?);
+        assert_eq!(
+            counts(&filter.partition_statistics(None)?),
+            (
+                Inexact(500),
+                vec![(Inexact(0), Inexact(50)), (Inexact(5), Inexact(500))]
+            )
+        );
+
+        let join = |join_type| -> Result<Arc<dyn ExecutionPlan>> {
+            let on = vec![(col("a", &filter.schema())?, col("c", &right.schema())?)];
+            Ok(Arc::new(HashJoinExec::try_new(
+                Arc::clone(&filter),
+                Arc::clone(&right),
+                on,
+                None,
+                &join_type,
+                None,
+                PartitionMode::CollectLeft,
+                NullEquality::NullEqualsNothing,
+            )?))
+        };
+
+        // Both join keys hold at most the 40 distinct values of c, and
+        // 500 * 200 / max(50, 40) rows are expected
+        let inner = join(JoinType::Inner)?;
+        assert_eq!(
+            counts(&inner.partition_statistics(None)?),
+            (
+                Inexact(2000),
+                vec![
+                    (Inexact(0), Inexact(40)),
+                    (Inexact(5), Inexact(500)),
+                    (Inexact(0), Inexact(40)),
+                    (Inexact(20), Inexact(180)),
+                ]
+            )
+        );
+
+        // The left side of a left join is preserved, and the nulls padding
+        // the right side are unknown
+        let left_join = join(JoinType::Left)?;
+        assert_eq!(
+            counts(&left_join.partition_statistics(None)?),
+            (
+                Inexact(2000),
+                vec![
+                    (Inexact(0), Inexact(50)),
+                    (Inexact(5), Inexact(500)),
+                    (Absent, Inexact(40)),
+                    (Absent, Inexact(180)),
+                ]
+            )
+        );
+        Ok(())
+    }
 }
@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use datafusion::datasource::file_format::parquet::ParquetFormat;
+use datafusion::datasource::listing::{ListingOptions, ListingTableUrl};
 use datafusion::execution::session_state::SessionStateBuilder;
 use datafusion::test_util::TestTableFactory;
 
@@ -66,3 +68,201 @@ async fn create_external_table_with_ddl() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn show_create_registered_listing_table() -> Result<()> {
+    let config = SessionConfig::new().with_information_schema(true);
+    let ctx = SessionContext::new_with_config(config);
+    let tmp_dir = TempDir::new()?;
+    let path = format!("{}/", tmp_dir.path().to_str().unwrap());
+    ctx.sql(&format!(
+        "COPY (VALUES (1, 'a'), (2, 'b')) TO '{path}' \
+         STORED AS PARQUET PARTITIONED BY (column2)"
+    ))
+    .await?
+    .collect()
+    .await?;
+
+    // A table registered without a statement
+    let options = ListingOptions::new(Arc::new(ParquetFormat::default()))
+        .with_file_extension(".parquet")
+        .with_table_partition_cols(vec![("column2".to_string(), DataType::Utf8)])
+        .with_file_sort_order(vec![vec![col("column1").sort(true, false)]]);
+    ctx.register_listing_table("t", &path, options, None, None)
+        .await?;
+
+    let batches = ctx.sql("SHOW CREATE TABLE t").await?.collect().await?;
+    let definition = batches[0].column_by_name("definition").unwrap();
+    let location = ListingTableUrl::parse(&path)?;
+    assert_eq!(
+        array_value_to_string(definition, 0)?,
+        format!(
+            "CREATE EXTERNAL TABLE t (column1 BIGINT, column2 VARCHAR NOT NULL) \
+             STORED AS PARQUET PARTITIONED BY (column2) \
+             WITH ORDER (column1 ASC NULLS LAST) LOCATION '{}'",
+            location.as_str()
+        )
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn copy_partitioned_output_files() -> Result<()> {
+    let config = SessionConfig::new()
+        .with_target_partitions(1)
+        .set_usize("datafusion.execution.soft_max_rows_per_output_file", 2)
+        .set_str(
+            "datafusion.execution.output_file_name_template",
+            "part-{partition_values}-{count}",
+        );
+    let ctx = SessionContext::new_with_config(config);
+    ctx.sql("CREATE TABLE t (id INT, date VARCHAR)")
+        .await?
+        .collect()
+        .await?;
+    // One batch per row, so that the files of a partition roll over
+    for (id, date) in [
+        (1, "'2024-01-01'"),
+        (2, "'2024-01-01'"),
+        (3, "NULL"),
+        (4, "'2024-01-01'"),
+        (5, "'2024-01-02'"),
+    ] {
+        ctx.sql(&format!("INSERT INTO t VALUES ({id}, {date})"))
+            .await?
+            .collect()
+            .await?;
+    }
+
+    let tmp_dir = TempDir::new()?;
+    let path = format!("{}/", tmp_dir.path().to_str().unwrap());
+    ctx.sql(&format!(
+        "COPY t TO '{path}' STORED AS CSV PARTITIONED BY (date) \
+         OPTIONS ('format.has_header' 'false')"
+    ))
+    .await?
+    .collect()
+    .await?;
+
+    let mut files = vec![];
+    for partition in std::fs::read_dir(tmp_dir.path())? {
+        let partition = partition?.path();
+        for file in std::fs::read_dir(&partition)? {
+            let file = file?.path();
+            let rows = std::fs::read_to_string(&file)?.lines().count();
+            let relative = file.strip_prefix(tmp_dir.path()).unwrap();
+            files.push((relative.to_str().unwrap().to_string(), rows));
+        }
+    }
+    files.sort();
+    assert_eq!(
+        files,
+        vec![
+            ("date=2024-01-01/part-2024-01-01-0.csv".to_string(), 2),
+            ("date=2024-01-01/part-2024-01-01-1.csv".to_string(), 1),
+            ("date=2024-01-02/part-2024-01-02-0.csv".to_string(), 1),
+            (
+                "date=__HIVE_DEFAULT_PARTITION__/part-__HIVE_DEFAULT_PARTITION__-0.csv"
+                    .to_string(),
+                1
+            ),
+        ]
+    );
+
+    // The template is validated when planning
+    ctx.sql("SET datafusion.execution.output_file_name_template = 'part-{id}'")
+        .await?
+        .collect()
+        .await?;
+    let err = ctx
+        .sql(&format!(
+            "COPY t TO '{path}' STORED AS CSV PARTITIONED BY (date)"
+        ))
+        .await?
+        .create_physical_plan()
+        .await
+        .unwrap_err();
+    assert_contains!(err.to_string(), "unknown placeholder '{id}'");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn insert_into_partitioned_external_table() -> Result<()> {
+    let ctx = SessionContext::new();
+    let tmp_dir = TempDir::new()?;
+    let path = format!("{}/", tmp_dir.path().to_str().unwrap());
+    ctx.sql(&format!(
+        "CREATE EXTERNAL TABLE sales \
+         (amount INT, region VARCHAR, day DATE, price DECIMAL(5, 2)) \
+         STORED AS PARQUET LOCATION '{path}' PARTITIONED BY (region, day, price)"
+    ))
+    .await?
+    .collect()
+    .await?;
+    ctx.sql(
+        "INSERT INTO sales VALUES \
+         (1, 'eu', '2024-01-01', 1.5), (2, 'eu', '2024-01-02', 2), \
+         (3, 'us', '2024-01-02', 1.5)",
+    )
+    .await?
+    .collect()
+    .await?;
+    // Appends a file to an existing partition
+    ctx.sql("INSERT INTO sales VALUES (4, 'eu', '2024-01-01', 1.5)")
+        .await?
+        .collect()
+        .await?;
+
+    fn partition_dirs(dir: &std::path::Path, out: &mut Vec<String>) -> Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?.path();
+            if entry.is_dir() {
+                partition_dirs(&entry, out)?;
+            } else {
+                out.push(dir.to_str().unwrap().to_string());
+            }
+        }
+        Ok(())
+    }
+    let mut dirs = vec![];
+    partition_dirs(tmp_dir.path(), &mut dirs)?;
+    let mut dirs = dirs
+        .iter()
+        .map(|dir| dir.strip_prefix(tmp_dir.path().to_str().unwrap()).unwrap())
+        .collect::<Vec<_>>();
+    dirs.sort();
+    assert_eq!(
+        dirs,
+        vec![
+            "/region=eu/day=2024-01-01/price=1.50",
+            "/region=eu/day=2024-01-01/price=1.50",
+            "/region=eu/day=2024-01-02/price=2.00",
+            "/region=us/day=2024-01-02/price=1.50",
+        ]
+    );
+
+    // The partition values are parsed back to the types of the columns
+    let results = ctx
+        .sql(
+            "SELECT * FROM sales WHERE region = 'eu' \
+             AND day = DATE '2024-01-01' AND price = 1.5 ORDER BY amount",
+        )
+        .await?
+        .collect()
+        .await?;
+    datafusion::assert_batches_eq!(
+        [
+            "+--------+--------+------------+-------+",
+            "| amount | region | day        | price |",
+            "+--------+--------+------------+-------+",
+            "| 1      | eu     | 2024-01-01 | 1.50  |",
+            "| 4      | eu     | 2024-01-01 | 1.50  |",
+            "+--------+--------+------------+-------+",
+        ],
+        &results
+    );
+
+    Ok(())
+}
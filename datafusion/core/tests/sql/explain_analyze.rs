@@ -20,6 +20,7 @@ use insta::assert_snapshot;
 use rstest::rstest;
 
 use datafusion::config::ConfigOptions;
+use datafusion::logical_expr::{ExplainFormat, ExplainOption};
 use datafusion::physical_plan::display::DisplayableExecutionPlan;
 use datafusion::physical_plan::metrics::Timestamp;
 
@@ -826,3 +827,448 @@ async fn csv_explain_analyze_with_statistics() {
         ", statistics=[Rows=Absent, Bytes=Absent, [(Col[0]:)]]"
     );
 }
+
+#[tokio::test]
+async fn explain_analyze_shared_subplan() {
+    let config = SessionConfig::new().with_target_partitions(1);
+    let ctx = SessionContext::new_with_config(config);
+    ctx.sql("CREATE TABLE t AS VALUES (1, 10), (1, 20), (2, 30), (3, 40)")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    ctx.sql("SET datafusion.optimizer.enable_subplan_sharing = true")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    let sql = "EXPLAIN ANALYZE \
+               WITH heavy AS (SELECT column1 AS k, sum(column2) AS s FROM t GROUP BY column1) \
+               SELECT a.k, a.s, b.s FROM heavy a JOIN heavy b ON a.k = b.k";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let formatted = arrow::util::pretty::pretty_format_batches(&actual)
+        .unwrap()
+        .to_string();
+
+    // The shared subplan is referenced by both sides of the join, but only
+    // executed once
+    let shared_lines = formatted
+        .lines()
+        .filter(|line| line.contains("SharedExec: consumers=2"))
+        .collect::<Vec<_>>();
+    assert_eq!(shared_lines.len(), 2, "{formatted}");
+    for line in shared_lines {
+        assert_contains!(line, "input_executions=1");
+        assert_contains!(line, "output_rows=6");
+    }
+    assert_metrics!(
+        &formatted,
+        "AggregateExec: mode=Single",
+        "metrics=[output_rows=3,"
+    );
+}
+
+/// Creates tables `t` and `u` in a context with 2 target partitions, for a
+/// join of 3 rows aggregated into 2 groups
+async fn aggregate_join_context() -> SessionContext {
+    let config = SessionConfig::new().with_target_partitions(2);
+    let ctx = SessionContext::new_with_config(config);
+    for sql in [
+        "CREATE TABLE t AS VALUES (1, 10), (1, 20), (2, 30), (3, 40)",
+        "CREATE TABLE u AS VALUES (1, 'a'), (2, 'b')",
+    ] {
+        ctx.sql(sql).await.unwrap().collect().await.unwrap();
+    }
+    ctx
+}
+
+const AGGREGATE_JOIN: &str = "SELECT t.column1, count(*), sum(t.column2) \
+     FROM t JOIN u ON t.column1 = u.column1 GROUP BY t.column1";
+
+/// Checks that `node` is an operator of the JSON document of
+/// `EXPLAIN (ANALYZE, FORMAT JSON)`, returning the operators of its tree
+fn check_json_operator<'a>(
+    node: &'a serde_json::Value,
+    operators: &mut Vec<&'a serde_json::Value>,
+) {
+    let object = node.as_object().expect("operators are objects");
+    let mut keys = object.keys().map(String::as_str).collect::<Vec<_>>();
+    keys.sort_unstable();
+    assert_eq!(
+        keys,
+        [
+            "children",
+            "description",
+            "metrics",
+            "operator",
+            "partitions"
+        ]
+    );
+
+    let operator = node["operator"].as_str().unwrap();
+    assert!(node["description"].as_str().unwrap().starts_with(operator));
+    let check_metrics = |metrics: &serde_json::Value| {
+        for value in metrics.as_object().unwrap().values() {
+            assert!(value.is_u64(), "{value}");
+        }
+    };
+    check_metrics(&node["metrics"]);
+    for partition in node["partitions"].as_array().unwrap() {
+        assert!(partition["partition"].is_u64(), "{partition}");
+        check_metrics(&partition["metrics"]);
+    }
+
+    operators.push(node);
+    for child in node["children"].as_array().unwrap() {
+        check_json_operator(child, operators);
+    }
+}
+
+#[tokio::test]
+async fn explain_analyze_json() {
+    let ctx = aggregate_join_context().await;
+    let sql = format!("EXPLAIN (ANALYZE, FORMAT JSON) {AGGREGATE_JOIN}");
+    let actual = execute_to_batches(&ctx, &sql).await;
+    assert_eq!(actual.len(), 1);
+    assert_eq!(actual[0].num_rows(), 1);
+    let plan_type = actual[0].column(0).as_string::<i32>().value(0);
+    assert_eq!(plan_type, "Plan with Metrics");
+    let plan = actual[0].column(1).as_string::<i32>().value(0);
+
+    let document: serde_json::Value = serde_json::from_str(plan).unwrap();
+    assert_eq!(
+        document["version"],
+        datafusion::physical_plan::metrics::METRICS_JSON_VERSION
+    );
+    let mut operators = vec![];
+    check_json_operator(&document["plan"], &mut operators);
+
+    let find = |name: &str| {
+        *operators
+            .iter()
+            .find(|node| node["operator"] == name)
+            .unwrap_or_else(|| panic!("No {name} in {plan}"))
+    };
+    let join = find("HashJoinExec");
+    assert_eq!(join["metrics"]["output_rows"], 3, "{plan}");
+    // The total of the metrics is the sum over the partitions
+    let partition_rows = join["partitions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|partition| partition["metrics"]["output_rows"].as_u64().unwrap())
+        .sum::<u64>();
+    assert_eq!(partition_rows, 3, "{plan}");
+
+    // The final aggregation is the first found
+    let aggregate = find("AggregateExec");
+    assert_eq!(aggregate["metrics"]["output_rows"], 2, "{plan}");
+    assert!(aggregate["metrics"]["elapsed_compute"].is_u64(), "{plan}");
+}
+
+#[tokio::test]
+async fn collect_plan_metrics_of_executed_plan() -> Result<()> {
+    use datafusion::physical_plan::metrics::{collect_plan_metrics, MetricsNode};
+
+    let ctx = aggregate_join_context().await;
+    let plan = ctx
+        .sql(AGGREGATE_JOIN)
+        .await?
+        .create_physical_plan()
+        .await?;
+    let results = collect(Arc::clone(&plan), ctx.task_ctx()).await?;
+    assert_eq!(results.iter().map(|b| b.num_rows()).sum::<usize>(), 2);
+
+    let tree = collect_plan_metrics(plan.as_ref());
+    assert_eq!(tree.operator, plan.name());
+    assert_eq!(tree.children.len(), plan.children().len());
+
+    fn find<'a>(node: &'a MetricsNode, operator: &str) -> Option<&'a MetricsNode> {
+        if node.operator == operator {
+            return Some(node);
+        }
+        node.children.iter().find_map(|child| find(child, operator))
+    }
+    let join = find(&tree, "HashJoinExec").unwrap();
+    assert_eq!(join.metrics["output_rows"], 3);
+    assert_eq!(
+        join.partitions
+            .values()
+            .map(|metrics| metrics["output_rows"])
+            .sum::<usize>(),
+        3
+    );
+    assert_eq!(
+        find(&tree, "AggregateExec").unwrap().metrics["output_rows"],
+        2
+    );
+
+    // The JSON document holds the same tree
+    let document: serde_json::Value = serde_json::from_str(&tree.to_json()).unwrap();
+    let mut operators = vec![];
+    check_json_operator(&document["plan"], &mut operators);
+    assert_eq!(operators[0]["operator"], tree.operator);
+    Ok(())
+}
+
+/// Returns a context showing the plan properties in tree explain plans,
+/// with a table `t` of 3 rows of the `Int32` columns `a` to `l`
+fn tree_properties_context() -> SessionContext {
+    let mut config = ConfigOptions::new();
+    config.execution.target_partitions = 1;
+    config.explain.tree_show_properties = true;
+    let ctx = SessionContext::new_with_config(config.into());
+    let columns = ('a'..='l')
+        .map(|name| {
+            let array: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+            (name.to_string(), array)
+        })
+        .collect::<Vec<_>>();
+    ctx.register_batch("t", RecordBatch::try_from_iter(columns).unwrap())
+        .unwrap();
+    ctx
+}
+
+/// Returns the tree explain plan of `sql` on the [`tree_properties_context`]
+async fn explain_tree_with_properties(sql: &str) -> String {
+    let ctx = tree_properties_context();
+    let batches = execute_to_batches(&ctx, sql).await;
+    batches[0].column(1).as_string::<i32>().value(0).to_string()
+}
+
+#[tokio::test]
+async fn explain_tree_properties() {
+    let actual = explain_tree_with_properties(
+        "EXPLAIN (FORMAT TREE) SELECT a, b FROM t WHERE a > 1 ORDER BY b",
+    )
+    .await;
+    assert_snapshot!(actual, @r"
+    ┌───────────────────────────┐
+    │          SortExec         │
+    │    --------------------   │
+    │     b@1 ASC NULLS LAST    │
+    │                           │
+    │     estimated_rows: ~3    │
+    │                           │
+    │      output_ordering:     │
+    │     b@1 ASC NULLS LAST    │
+    │                           │
+    │    output_partitioning:   │
+    │   UnknownPartitioning(1)  │
+    └─────────────┬─────────────┘
+    ┌─────────────┴─────────────┐
+    │    CoalesceBatchesExec    │
+    │    --------------------   │
+    │     estimated_rows: ~3    │
+    │                           │
+    │    output_partitioning:   │
+    │   UnknownPartitioning(1)  │
+    │                           │
+    │     target_batch_size:    │
+    │            8192           │
+    └─────────────┬─────────────┘
+    ┌─────────────┴─────────────┐
+    │         FilterExec        │
+    │    --------------------   │
+    │     estimated_rows: ~3    │
+    │                           │
+    │    output_partitioning:   │
+    │   UnknownPartitioning(1)  │
+    │                           │
+    │      predicate: a > 1     │
+    │                           │
+    │    predicate_pushdown:    │
+    │      not pushed down      │
+    └─────────────┬─────────────┘
+    ┌─────────────┴─────────────┐
+    │       DataSourceExec      │
+    │    --------------------   │
+    │        bytes: 1296        │
+    │     estimated_rows: 3     │
+    │       format: memory      │
+    │                           │
+    │    output_partitioning:   │
+    │   UnknownPartitioning(1)  │
+    │                           │
+    │          rows: 1          │
+    └───────────────────────────┘
+    ");
+}
+
+#[tokio::test]
+async fn explain_tree_properties_truncates_details() {
+    let actual = explain_tree_with_properties(
+        "EXPLAIN (FORMAT TREE) SELECT a + 1, b + 1, c + 1, d + 1, e + 1, f + 1, \
+         g + 1, h + 1, i + 1, j + 1, k + 1, l + 1 FROM t",
+    )
+    .await;
+    assert_snapshot!(actual, @r"
+    ┌───────────────────────────┐
+    │       ProjectionExec      │
+    │    --------------------   │
+    │     estimated_rows: 3     │
+    │                           │
+    │    output_partitioning:   │
+    │   UnknownPartitioning(1)  │
+    │                           │
+    │      t.a + Int64(1):      │
+    │    CAST(a AS Int64) + 1   │
+    │                           │
+    │      t.b + Int64(1):      │
+    │    CAST(b AS Int64) + 1   │
+    │                           │
+    │      t.c + Int64(1):      │
+    │    CAST(c AS Int64) + 1   │
+    │                           │
+    │      t.d + Int64(1):      │
+    │    CAST(d AS Int64) + 1   │
+    │                           │
+    │      t.e + Int64(1):      │
+    │    CAST(e AS Int64) + 1   │
+    │                           │
+    │      t.f + Int64(1):      │
+    │    CAST(f AS Int64) + 1   │
+    │                           │
+    │      t.g + Int64(1):      │
+    │    CAST(g AS Int64) + 1   │
+    │                           │
+    │      t.h + Int64(1):      │
+    │    CAST(h AS Int64) + 1   │
+    │                           │
+    │         ... 4 more        │
+    └─────────────┬─────────────┘
+    ┌─────────────┴─────────────┐
+    │       DataSourceExec      │
+    │    --------------------   │
+    │        bytes: 1296        │
+    │     estimated_rows: 3     │
+    │       format: memory      │
+    │                           │
+    │    output_partitioning:   │
+    │   UnknownPartitioning(1)  │
+    │                           │
+    │          rows: 1          │
+    └───────────────────────────┘
+    ");
+}
+
+#[tokio::test]
+async fn explain_tree_properties_of_dataframe() -> Result<()> {
+    let sql = "SELECT a, count(*) FROM t GROUP BY a";
+    let actual =
+        explain_tree_with_properties(&format!("EXPLAIN (FORMAT TREE) {sql}")).await;
+    assert_snapshot!(actual, @r"
+    ┌───────────────────────────┐
+    │       ProjectionExec      │
+    │    --------------------   │
+    │            a: a           │
+    │                           │
+    │         count(*):         │
+    │      count(Int64(1))      │
+    │                           │
+    │     estimated_rows: ~3    │
+    │                           │
+    │    output_partitioning:   │
+    │   UnknownPartitioning(1)  │
+    └─────────────┬─────────────┘
+    ┌─────────────┴─────────────┐
+    │       AggregateExec       │
+    │    --------------------   │
+    │       aggr: count(1)      │
+    │     estimated_rows: ~3    │
+    │        group_by: a        │
+    │        mode: Single       │
+    │                           │
+    │    output_partitioning:   │
+    │   UnknownPartitioning(1)  │
+    └─────────────┬─────────────┘
+    ┌─────────────┴─────────────┐
+    │       DataSourceExec      │
+    │    --------------------   │
+    │        bytes: 1296        │
+    │     estimated_rows: 3     │
+    │       format: memory      │
+    │                           │
+    │    output_partitioning:   │
+    │   UnknownPartitioning(1)  │
+    │                           │
+    │          rows: 1          │
+    └───────────────────────────┘
+    ");
+
+    let batches = tree_properties_context()
+        .sql(sql)
+        .await?
+        .explain_with_options(ExplainOption::default().with_format(ExplainFormat::Tree))?
+        .collect()
+        .await?;
+    assert_eq!(batches[0].column(1).as_string::<i32>().value(0), actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn explain_tree_properties_of_pushed_down_filter() -> Result<()> {
+    let ctx = tree_properties_context();
+    let dir = TempDir::new()?;
+    let path = dir.path().join("t.parquet");
+    ctx.sql(&format!(
+        "COPY (SELECT a, b FROM t) TO '{}'",
+        path.display()
+    ))
+    .await?
+    .collect()
+    .await?;
+    ctx.register_parquet("p", path.to_str().unwrap(), ParquetReadOptions::default())
+        .await?;
+    let batches = ctx
+        .sql("EXPLAIN (FORMAT TREE) SELECT b FROM p WHERE a > 1")
+        .await?
+        .collect()
+        .await?;
+    let actual = batches[0].column(1).as_string::<i32>().value(0);
+    assert_snapshot!(actual, @r"
+    ┌───────────────────────────┐
+    │    CoalesceBatchesExec    │
+    │    --------------------   │
+    │     estimated_rows: ~1    │
+    │                           │
+    │    output_partitioning:   │
+    │   UnknownPartitioning(1)  │
+    │                           │
+    │     target_batch_size:    │
+    │            8192           │
+    └─────────────┬─────────────┘
+    ┌─────────────┴─────────────┐
+    │         FilterExec        │
+    │    --------------------   │
+    │     estimated_rows: ~1    │
+    │                           │
+    │    output_partitioning:   │
+    │   UnknownPartitioning(1)  │
+    │                           │
+    │      predicate: a > 1     │
+    │                           │
+    │    predicate_pushdown:    │
+    │      not pushed down      │
+    └─────────────┬─────────────┘
+    ┌─────────────┴─────────────┐
+    │       DataSourceExec      │
+    │    --------------------   │
+    │     estimated_rows: ~3    │
+    │          files: 1         │
+    │      format: parquet      │
+    │                           │
+    │    output_partitioning:   │
+    │   UnknownPartitioning(1)  │
+    │                           │
+    │      predicate: a > 1     │
+    │                           │
+    │    predicate_pushdown:    │
+    │      pushed into scan     │
+    └───────────────────────────┘
+    ");
+    Ok(())
+}
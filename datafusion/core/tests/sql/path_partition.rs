@@ -21,7 +21,7 @@ use std::collections::BTreeSet;
 use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
 use std::ops::Range;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use arrow::datatypes::DataType;
 use datafusion::datasource::listing::ListingTableUrl;
@@ -31,7 +31,7 @@ use datafusion::{
         listing::{ListingOptions, ListingTable, ListingTableConfig},
     },
     error::Result,
-    physical_plan::ColumnStatistics,
+    physical_plan::{collect, displayable, ColumnStatistics},
     prelude::SessionContext,
     test_util::{self, arrow_test_data, parquet_test_data},
 };
@@ -47,8 +47,8 @@ use chrono::{TimeZone, Utc};
 use futures::stream::{self, BoxStream};
 use insta::assert_snapshot;
 use object_store::{
-    path::Path, GetOptions, GetResult, GetResultPayload, ListResult, ObjectMeta,
-    ObjectStore, PutOptions, PutResult,
+    path::Path, GetOptions, GetRange, GetResult, GetResultPayload, ListResult,
+    ObjectMeta, ObjectStore, PutOptions, PutResult,
 };
 use object_store::{Attributes, MultipartUpload, PutMultipartOpts, PutPayload};
 use url::Url;
@@ -464,10 +464,19 @@ async fn parquet_statistics() -> Result<()> {
     assert_eq!(stat_cols.len(), 4);
     // stats for the first col are read from the parquet file
     assert_eq!(stat_cols[0].null_count, Precision::Exact(3));
-    // TODO assert partition column (1,2,3) stats once implemented (#1186)
-    assert_eq!(stat_cols[1], ColumnStatistics::new_unknown(),);
-    assert_eq!(stat_cols[2], ColumnStatistics::new_unknown(),);
-    assert_eq!(stat_cols[3], ColumnStatistics::new_unknown(),);
+    // stats for the partition columns are computed from the partition values
+    assert_eq!(
+        stat_cols[1],
+        partition_column_statistics(ScalarValue::Int32(Some(2021)), None, 1)
+    );
+    assert_eq!(
+        stat_cols[2],
+        partition_column_statistics(ScalarValue::from("09"), Some("10".into()), 2)
+    );
+    assert_eq!(
+        stat_cols[3],
+        partition_column_statistics(ScalarValue::from("09"), Some("28".into()), 2)
+    );
 
     //// WITH PROJECTION ////
     let dataframe = ctx.sql("SELECT mycol, day FROM t WHERE day='28'").await?;
@@ -479,12 +488,141 @@ async fn parquet_statistics() -> Result<()> {
     assert_eq!(stat_cols.len(), 2);
     // stats for the first col are read from the parquet file
     assert_eq!(stat_cols[0].null_count, Precision::Exact(1));
-    // TODO assert partition column stats once implemented (#1186)
-    assert_eq!(stat_cols[1], ColumnStatistics::new_unknown());
+    // only the files of the filtered partition are scanned
+    assert_eq!(
+        stat_cols[1],
+        partition_column_statistics(ScalarValue::from("28"), None, 1)
+    );
+
+    Ok(())
+}
+
+/// Returns the exact statistics of a partition column without nulls, whose
+/// values range from `min` to `max`, or are all `min` if `max` is `None`
+fn partition_column_statistics(
+    min: ScalarValue,
+    max: Option<ScalarValue>,
+    distinct_count: usize,
+) -> ColumnStatistics {
+    ColumnStatistics {
+        null_count: Precision::Exact(0),
+        max_value: Precision::Exact(max.unwrap_or_else(|| min.clone())),
+        min_value: Precision::Exact(min),
+        sum_value: Precision::Absent,
+        distinct_count: Precision::Exact(distinct_count),
+    }
+}
+
+#[tokio::test]
+async fn parquet_count_from_metadata() -> Result<()> {
+    let ctx = SessionContext::new();
+    let store_paths = [
+        "year=2021/month=09/day=09/file.parquet",
+        "year=2021/month=10/day=09/file.parquet",
+        "year=2021/month=10/day=28/file.parquet",
+    ];
+    register_partitioned_alltypes_parquet(
+        &ctx,
+        &store_paths,
+        &[
+            ("year", DataType::Int32),
+            ("month", DataType::Utf8),
+            ("day", DataType::Utf8),
+        ],
+        "mirror:///",
+        "alltypes_plain.parquet",
+    )
+    .await;
+    // Record the requests to the files, whose footers are read when planning
+    let store = Arc::new(RecordingObjectStore::new(MirroringObjectStore::new_arc(
+        format!("{}/alltypes_plain.parquet", parquet_test_data()),
+        &store_paths,
+    )));
+    ctx.register_object_store(&Url::parse("mirror://").unwrap(), Arc::clone(&store) as _);
+
+    let (plan, result, read) =
+        run_recorded(&ctx, &store, "SELECT count(*) FROM t").await?;
+    assert_snapshot!(plan, @r"
+    ProjectionExec: expr=[24 as count(*)]
+      PlaceholderRowExec
+    ");
+    assert_snapshot!(result, @r"
+    +----------+
+    | count(*) |
+    +----------+
+    | 24       |
+    +----------+
+    ");
+    assert!(!read);
+
+    // The files are pruned by the filter on the partition column
+    let (plan, result, read) = run_recorded(
+        &ctx,
+        &store,
+        "SELECT count(*), count(month) FROM t WHERE month = '10'",
+    )
+    .await?;
+    assert_snapshot!(plan, @r"
+    ProjectionExec: expr=[16 as count(*), 16 as count(t.month)]
+      PlaceholderRowExec
+    ");
+    assert_snapshot!(result, @r"
+    +----------+----------------+
+    | count(*) | count(t.month) |
+    +----------+----------------+
+    | 16       | 16             |
+    +----------+----------------+
+    ");
+    assert!(!read);
+
+    // Grouping by partition columns only, the count of each group is the sum
+    // of the number of rows of its files
+    let (plan, result, read) = run_recorded(
+        &ctx,
+        &store,
+        "SELECT year, month, count(*) FROM t GROUP BY year, month",
+    )
+    .await?;
+    assert!(!plan.contains("file_groups"), "{plan}");
+    assert_snapshot!(result, @r"
+    +------+-------+----------+
+    | year | month | count(*) |
+    +------+-------+----------+
+    | 2021 | 09    | 8        |
+    | 2021 | 10    | 16       |
+    +------+-------+----------+
+    ");
+    assert!(!read);
+
+    // Filters on the columns of the files are evaluated on their data
+    let (_, result, read) =
+        run_recorded(&ctx, &store, "SELECT count(*) FROM t WHERE id > 3").await?;
+    assert_snapshot!(result, @r"
+    +----------+
+    | count(*) |
+    +----------+
+    | 12       |
+    +----------+
+    ");
+    assert!(read);
 
     Ok(())
 }
 
+/// Executes `sql`, returning its plan, its result and whether files were read
+/// from `store` while executing it
+async fn run_recorded(
+    ctx: &SessionContext,
+    store: &RecordingObjectStore,
+    sql: &str,
+) -> Result<(String, String, bool)> {
+    let plan = ctx.sql(sql).await?.create_physical_plan().await?;
+    store.clear();
+    let batches = collect(Arc::clone(&plan), ctx.task_ctx()).await?;
+    let plan = displayable(plan.as_ref()).indent(true).to_string();
+    Ok((plan, batches_to_sort_string(&batches), store.has_requests()))
+}
+
 #[tokio::test]
 async fn parquet_overlapping_columns() -> Result<()> {
     let ctx = SessionContext::new();
@@ -779,3 +917,112 @@ impl ObjectStore for MirroringObjectStore {
         unimplemented!()
     }
 }
+
+/// An object store recording the ranges of the files read from another store
+#[derive(Debug)]
+struct RecordingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    /// The locations read from, with the range of the read, if any
+    requests: Mutex<Vec<(Path, Option<GetRange>)>>,
+}
+
+impl RecordingObjectStore {
+    fn new(inner: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            inner,
+            requests: Mutex::new(vec![]),
+        }
+    }
+
+    /// Forgets the recorded requests
+    fn clear(&self) {
+        self.requests.lock().unwrap().clear();
+    }
+
+    /// Returns whether files were read since the requests were last cleared
+    fn has_requests(&self) -> bool {
+        !self.requests.lock().unwrap().is_empty()
+    }
+}
+
+impl std::fmt::Display for RecordingObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RecordingObjectStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for RecordingObjectStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        put_payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.inner.put_opts(location, put_payload, opts).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.inner.put_multipart_opts(location, opts).await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        if !options.head {
+            self.requests
+                .lock()
+                .unwrap()
+                .push((location.clone(), options.range.clone()));
+        }
+        self.inner.get_opts(location, options).await
+    }
+
+    async fn get_range(
+        &self,
+        location: &Path,
+        range: Range<u64>,
+    ) -> object_store::Result<Bytes> {
+        self.requests
+            .lock()
+            .unwrap()
+            .push((location.clone(), Some(GetRange::Bounded(range.clone()))));
+        self.inner.get_range(location, range).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.inner.delete(location).await
+    }
+
+    fn list(
+        &self,
+        prefix: Option<&Path>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.inner.list(prefix)
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<ListResult> {
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.inner.copy(from, to).await
+    }
+
+    async fn copy_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        self.inner.copy_if_not_exists(from, to).await
+    }
+}
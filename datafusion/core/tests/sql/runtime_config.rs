@@ -21,6 +21,7 @@ use std::sync::Arc;
 
 use datafusion::execution::context::SessionContext;
 use datafusion::execution::context::TaskContext;
+use datafusion::execution::object_store::ObjectStoreUrl;
 use datafusion_physical_plan::common::collect;
 
 #[tokio::test]
@@ -164,3 +165,62 @@ async fn test_unknown_runtime_config() {
     let error_message = result.unwrap_err().to_string();
     assert!(error_message.contains("Unknown runtime configuration"));
 }
+
+#[tokio::test]
+async fn test_object_store_requests_per_second() {
+    let ctx = SessionContext::new();
+    let url = ObjectStoreUrl::local_filesystem();
+    assert!(ctx.runtime_env().object_store_rate_limiter.is_none());
+
+    ctx.sql("SET datafusion.runtime.object_store_requests_per_second = 20")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    let limiter = ctx.runtime_env().object_store_rate_limiter.clone().unwrap();
+    assert_eq!(limiter.requests_per_second(), 20);
+    let store = ctx.runtime_env().object_store(&url).unwrap();
+    assert!(store
+        .to_string()
+        .starts_with("RateLimitedObjectStore(20 req/s"));
+
+    // every scan shares the limiter of the runtime
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("t.csv");
+    std::fs::write(&path, "a\n1\n2\n").unwrap();
+    ctx.register_csv("t", path.to_str().unwrap(), Default::default())
+        .await
+        .unwrap();
+    let batches = ctx
+        .sql("SELECT sum(a) FROM t")
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert!(batches.is_ok());
+    assert!(Arc::ptr_eq(
+        &limiter,
+        ctx.runtime_env()
+            .object_store_rate_limiter
+            .as_ref()
+            .unwrap()
+    ));
+
+    ctx.sql("SET datafusion.runtime.object_store_requests_per_second = 0")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    assert!(ctx.runtime_env().object_store_rate_limiter.is_none());
+    let store = ctx.runtime_env().object_store(&url).unwrap();
+    assert!(!store.to_string().starts_with("RateLimitedObjectStore"));
+
+    let result = ctx
+        .sql("SET datafusion.runtime.object_store_requests_per_second = 'fast'")
+        .await;
+    let error_message = result.unwrap_err().to_string();
+    assert!(error_message.contains("Failed to parse object store requests per second"));
+}
@@ -19,6 +19,7 @@ use std::any::Any;
 use std::collections::HashMap;
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::sync::Arc;
+use std::time::Duration;
 
 use arrow::array::{as_string_array, create_array, record_batch, Int8Array, UInt64Array};
 use arrow::array::{
@@ -33,13 +34,16 @@ use datafusion::common::test_util::batches_to_string;
 use datafusion::execution::context::{FunctionFactory, RegisterFunction, SessionState};
 use datafusion::prelude::*;
 use datafusion::{execution::registry::FunctionRegistry, test_util};
+use datafusion_common::cancellation::CancellationToken;
 use datafusion_common::cast::{as_float64_array, as_int32_array};
+use datafusion_common::instant::Instant;
 use datafusion_common::tree_node::{Transformed, TreeNode};
 use datafusion_common::utils::take_function_args;
 use datafusion_common::{
     assert_batches_eq, assert_batches_sorted_eq, assert_contains, exec_err, not_impl_err,
     plan_err, DFSchema, DataFusionError, Result, ScalarValue,
 };
+use datafusion_common_runtime::SpawnedTask;
 use datafusion_expr::expr::FieldMetadata;
 use datafusion_expr::simplify::{ExprSimplifyResult, SimplifyInfo};
 use datafusion_expr::{
@@ -1770,3 +1774,79 @@ async fn test_extension_based_udf() -> Result<()> {
     ctx.deregister_table("t")?;
     Ok(())
 }
+
+/// A scalar function taking about 10 seconds to evaluate, unless the query is
+/// cancelled
+#[derive(Debug)]
+struct SlowUDF {
+    signature: Signature,
+    cancellation: Option<CancellationToken>,
+}
+
+impl SlowUDF {
+    fn new() -> Self {
+        Self {
+            signature: Signature::exact(vec![DataType::Int32], Volatility::Volatile),
+            cancellation: None,
+        }
+    }
+}
+
+impl ScalarUDFImpl for SlowUDF {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "slow"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Int32)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        for _ in 0..1000 {
+            if let Some(cancellation) = &self.cancellation {
+                cancellation.check()?;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        Ok(args.args[0].clone())
+    }
+
+    fn with_cancellation(
+        &self,
+        cancellation: &CancellationToken,
+    ) -> Option<Arc<dyn ScalarUDFImpl>> {
+        Some(Arc::new(Self {
+            signature: self.signature.clone(),
+            cancellation: Some(cancellation.clone()),
+        }))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_cancel_slow_scalar_udf() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.register_batch("t", record_batch!(("a", Int32, [1, 2, 3]))?)?;
+    ctx.register_udf(ScalarUDF::from(SlowUDF::new()));
+
+    let plan = ctx.sql("SELECT slow(a) FROM t").await?;
+    let plan = plan.create_physical_plan().await?;
+    let cancellation = CancellationToken::new();
+    let stream = ctx.execute_with_cancellation(plan, cancellation.clone())?;
+    let query = SpawnedTask::spawn(datafusion::physical_plan::common::collect(stream));
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let start = Instant::now();
+    cancellation.cancel();
+    let err = query.join().await.unwrap().unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_contains!(err.to_string(), "The query was cancelled");
+    Ok(())
+}
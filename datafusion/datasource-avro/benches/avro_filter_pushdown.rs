@@ -0,0 +1,183 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Compares evaluating a predicate while decoding Avro records against
+//! decoding all columns and filtering them afterwards with a `FilterExec`
+
+use std::path::Path;
+use std::sync::Arc;
+
+use apache_avro::types::Value;
+use arrow::datatypes::SchemaRef;
+use datafusion_common::Result;
+use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
+use datafusion_datasource::source::DataSourceExec;
+use datafusion_datasource::PartitionedFile;
+use datafusion_datasource_avro::avro_to_arrow::read_avro_schema_from_reader;
+use datafusion_datasource_avro::source::AvroSource;
+use datafusion_execution::object_store::ObjectStoreUrl;
+use datafusion_execution::TaskContext;
+use datafusion_expr_common::operator::Operator;
+use datafusion_physical_expr::expressions::{binary, col, lit};
+use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
+use datafusion_physical_plan::filter::FilterExec;
+use datafusion_physical_plan::projection::ProjectionExec;
+use datafusion_physical_plan::{collect, ExecutionPlan};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use tokio::runtime::Builder;
+
+const NUM_ROWS: i64 = 100_000;
+const NUM_PAYLOAD_COLUMNS: usize = 8;
+
+fn write_file(path: &Path) {
+    let payload_fields = (0..NUM_PAYLOAD_COLUMNS)
+        .map(|i| format!(r#"{{"name": "payload_{i}", "type": "string"}}"#))
+        .collect::<Vec<_>>()
+        .join(",");
+    let schema = apache_avro::Schema::parse_str(&format!(
+        r#"{{
+          "type": "record",
+          "name": "r1",
+          "fields": [{{"name": "id", "type": "long"}}, {payload_fields}]
+        }}"#
+    ))
+    .unwrap();
+    let mut writer =
+        apache_avro::Writer::new(&schema, std::fs::File::create(path).unwrap());
+    for id in 0..NUM_ROWS {
+        let mut fields = vec![("id".to_string(), Value::Long(id))];
+        fields.extend((0..NUM_PAYLOAD_COLUMNS).map(|i| {
+            (
+                format!("payload_{i}"),
+                Value::String(format!("payload value {i} of record {id}")),
+            )
+        }));
+        writer.append(Value::Record(fields)).unwrap();
+    }
+    writer.flush().unwrap();
+}
+
+/// `id % 100 < selectivity_percent`
+fn predicate(schema: &SchemaRef, selectivity_percent: i64) -> Arc<dyn PhysicalExpr> {
+    binary(
+        binary(
+            col("id", schema).unwrap(),
+            Operator::Modulo,
+            lit(100i64),
+            schema,
+        )
+        .unwrap(),
+        Operator::Lt,
+        lit(selectivity_percent),
+        schema,
+    )
+    .unwrap()
+}
+
+fn scan(
+    path: &Path,
+    schema: &SchemaRef,
+    source: AvroSource,
+    projection: Vec<usize>,
+) -> Arc<dyn ExecutionPlan> {
+    let size = std::fs::metadata(path).unwrap().len();
+    let file = PartitionedFile::new(path.to_str().unwrap(), size);
+    let config = FileScanConfigBuilder::new(
+        ObjectStoreUrl::local_filesystem(),
+        Arc::clone(schema),
+        Arc::new(source),
+    )
+    .with_file(file)
+    .with_projection(Some(projection))
+    .build();
+    DataSourceExec::from_data_source(config)
+}
+
+/// Decodes the predicate and payload columns, then filters and projects them
+fn filter_after_scan(
+    path: &Path,
+    schema: &SchemaRef,
+    selectivity_percent: i64,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    let input = scan(
+        path,
+        schema,
+        AvroSource::new(),
+        (0..schema.fields().len()).collect(),
+    );
+    let filter = Arc::new(FilterExec::try_new(
+        predicate(schema, selectivity_percent),
+        input,
+    )?);
+    let exprs = (1..schema.fields().len())
+        .map(|i| {
+            let name = schema.field(i).name();
+            Ok((col(name, &filter.schema())?, name.to_string()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Arc::new(ProjectionExec::try_new(exprs, filter)?))
+}
+
+/// Evaluates the predicate while decoding, building the payload columns for
+/// the selected records only
+fn filter_during_decode(
+    path: &Path,
+    schema: &SchemaRef,
+    selectivity_percent: i64,
+) -> Arc<dyn ExecutionPlan> {
+    let source = AvroSource::new().with_predicate(predicate(schema, selectivity_percent));
+    scan(path, schema, source, (1..schema.fields().len()).collect())
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let tmp_dir = tempfile::TempDir::new().unwrap();
+    let path = tmp_dir.path().join("data.avro");
+    write_file(&path);
+    let schema = Arc::new(
+        read_avro_schema_from_reader(&mut std::fs::File::open(&path).unwrap()).unwrap(),
+    );
+    let rt = Builder::new_current_thread().build().unwrap();
+    let task_ctx = Arc::new(TaskContext::default());
+
+    let mut group = c.benchmark_group("avro_filter_pushdown");
+    for selectivity_percent in [1, 10, 50, 100] {
+        group.bench_function(
+            BenchmarkId::new("filter_after_scan", selectivity_percent),
+            |b| {
+                b.iter(|| {
+                    let plan =
+                        filter_after_scan(&path, &schema, selectivity_percent).unwrap();
+                    rt.block_on(collect(plan, Arc::clone(&task_ctx))).unwrap()
+                })
+            },
+        );
+        group.bench_function(
+            BenchmarkId::new("filter_during_decode", selectivity_percent),
+            |b| {
+                b.iter(|| {
+                    let plan = filter_during_decode(&path, &schema, selectivity_percent);
+                    rt.block_on(collect(plan, Arc::clone(&task_ctx))).unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,183 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Measures scans of many small Avro files, with and without a shared
+//! [`DecodeBufferPool`], and checks that the pool reduces the number of
+//! allocations of a scan
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use apache_avro::types::Value;
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion_datasource::file_groups::FileGroup;
+use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
+use datafusion_datasource::source::DataSourceExec;
+use datafusion_datasource::PartitionedFile;
+use datafusion_datasource_avro::source::AvroSource;
+use datafusion_datasource_avro::DecodeBufferPool;
+use datafusion_execution::object_store::ObjectStoreUrl;
+use datafusion_execution::TaskContext;
+use datafusion_physical_plan::{collect, ExecutionPlan};
+use object_store::memory::InMemory;
+use object_store::path::Path;
+use object_store::ObjectStore;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use tokio::runtime::{Builder, Runtime};
+
+const NUM_FILES: usize = 200;
+const FILE_ROWS: i64 = 100;
+const NUM_PARTITIONS: usize = 4;
+
+/// Counts the allocations of the process
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Writes `NUM_FILES` files of `FILE_ROWS` records to `store`, returning
+/// them split into `NUM_PARTITIONS` groups
+async fn write_files(store: &InMemory) -> Vec<FileGroup> {
+    let schema = apache_avro::Schema::parse_str(
+        r#"{
+          "type": "record",
+          "name": "r1",
+          "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "name", "type": "string"}
+          ]
+        }"#,
+    )
+    .unwrap();
+    let mut groups = vec![vec![]; NUM_PARTITIONS];
+    for file in 0..NUM_FILES {
+        let mut writer = apache_avro::Writer::new(&schema, vec![]);
+        for id in 0..FILE_ROWS {
+            writer
+                .append(Value::Record(vec![
+                    ("id".to_string(), Value::Long(id)),
+                    ("name".to_string(), Value::String(format!("name_{id}"))),
+                ]))
+                .unwrap();
+        }
+        let data = writer.into_inner().unwrap();
+        let path = format!("data/{file}.avro");
+        groups[file % NUM_PARTITIONS]
+            .push(PartitionedFile::new(&path, data.len() as u64));
+        store.put(&Path::from(path), data.into()).await.unwrap();
+    }
+    groups.into_iter().map(FileGroup::new).collect()
+}
+
+fn scan(
+    schema: &SchemaRef,
+    groups: &[FileGroup],
+    pool: Option<&Arc<DecodeBufferPool>>,
+) -> Arc<dyn ExecutionPlan> {
+    let source = AvroSource::new().with_decode_buffer_pool(pool.cloned());
+    let config = FileScanConfigBuilder::new(
+        ObjectStoreUrl::parse("memory://").unwrap(),
+        Arc::clone(schema),
+        Arc::new(source),
+    )
+    .with_file_groups(groups.to_vec())
+    .build();
+    DataSourceExec::from_data_source(config)
+}
+
+/// Returns the number of allocations of a scan of all the files
+fn count_allocations(
+    rt: &Runtime,
+    task_ctx: &Arc<TaskContext>,
+    plan: Arc<dyn ExecutionPlan>,
+) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let batches = rt.block_on(collect(plan, Arc::clone(task_ctx))).unwrap();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    let rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+    assert_eq!(rows, NUM_FILES * FILE_ROWS as usize);
+    allocations
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let rt = Builder::new_current_thread().build().unwrap();
+    let store = Arc::new(InMemory::new());
+    let groups = rt.block_on(write_files(&store));
+    let task_ctx = Arc::new(TaskContext::default());
+    task_ctx.runtime_env().register_object_store(
+        ObjectStoreUrl::parse("memory://").unwrap().as_ref(),
+        store,
+    );
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+    ]));
+    let pool = Arc::new(DecodeBufferPool::new(2 * NUM_PARTITIONS));
+
+    // Fill the pool, then compare the allocations of a scan with and
+    // without it
+    count_allocations(&rt, &task_ctx, scan(&schema, &groups, Some(&pool)));
+    let unpooled = count_allocations(&rt, &task_ctx, scan(&schema, &groups, None));
+    let pooled = count_allocations(&rt, &task_ctx, scan(&schema, &groups, Some(&pool)));
+    println!("allocations of a scan of {NUM_FILES} files: {unpooled} without a buffer pool, {pooled} with a buffer pool");
+    assert!(
+        pooled < unpooled,
+        "a buffer pool should reduce allocations: {pooled} >= {unpooled}"
+    );
+
+    let mut group = c.benchmark_group("avro_many_files");
+    group.throughput(Throughput::Elements(NUM_FILES as u64));
+
+    group.bench_function("without_buffer_pool", |b| {
+        b.iter(|| {
+            let plan = scan(&schema, &groups, None);
+            rt.block_on(collect(plan, Arc::clone(&task_ctx))).unwrap()
+        })
+    });
+
+    group.bench_function("with_buffer_pool", |b| {
+        b.iter(|| {
+            let plan = scan(&schema, &groups, Some(&pool));
+            rt.block_on(collect(plan, Arc::clone(&task_ctx))).unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,157 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Measures the throughput, in rows per second, of full, projected and
+//! filtered scans of a multi-block Avro file
+
+use std::path::Path;
+use std::sync::Arc;
+
+use apache_avro::types::Value;
+use arrow::datatypes::SchemaRef;
+use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
+use datafusion_datasource::source::DataSourceExec;
+use datafusion_datasource::PartitionedFile;
+use datafusion_datasource_avro::avro_to_arrow::read_avro_schema_from_reader;
+use datafusion_datasource_avro::source::AvroSource;
+use datafusion_execution::object_store::ObjectStoreUrl;
+use datafusion_execution::TaskContext;
+use datafusion_expr_common::operator::Operator;
+use datafusion_physical_expr::expressions::{binary, col, lit};
+use datafusion_physical_plan::{collect, ExecutionPlan};
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use tokio::runtime::Builder;
+
+const NUM_ROWS: i64 = 200_000;
+const BLOCK_ROWS: i64 = 4_096;
+
+/// Writes `NUM_ROWS` records of numeric, string and nullable columns in
+/// blocks of `BLOCK_ROWS` records
+fn write_file(path: &Path) {
+    let schema = apache_avro::Schema::parse_str(
+        r#"{
+          "type": "record",
+          "name": "r1",
+          "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "amount", "type": "double"},
+            {"name": "quantity", "type": "int"},
+            {"name": "flag", "type": "boolean"},
+            {"name": "name", "type": "string"},
+            {"name": "comment", "type": ["null", "string"]}
+          ]
+        }"#,
+    )
+    .unwrap();
+    let mut writer =
+        apache_avro::Writer::new(&schema, std::fs::File::create(path).unwrap());
+    for id in 0..NUM_ROWS {
+        let comment = if id % 3 == 0 {
+            Value::Union(0, Box::new(Value::Null))
+        } else {
+            Value::Union(1, Box::new(Value::String(format!("comment {id}"))))
+        };
+        writer
+            .append(Value::Record(vec![
+                ("id".to_string(), Value::Long(id)),
+                ("amount".to_string(), Value::Double(id as f64 * 0.25)),
+                ("quantity".to_string(), Value::Int((id % 1000) as i32)),
+                ("flag".to_string(), Value::Boolean(id % 2 == 0)),
+                (
+                    "name".to_string(),
+                    Value::String(format!("name_{}", id % 500)),
+                ),
+                ("comment".to_string(), comment),
+            ]))
+            .unwrap();
+        if id % BLOCK_ROWS == BLOCK_ROWS - 1 {
+            writer.flush().unwrap();
+        }
+    }
+    writer.flush().unwrap();
+}
+
+fn scan(
+    path: &Path,
+    schema: &SchemaRef,
+    source: AvroSource,
+    projection: Vec<usize>,
+) -> Arc<dyn ExecutionPlan> {
+    let size = std::fs::metadata(path).unwrap().len();
+    let file = PartitionedFile::new(path.to_str().unwrap(), size);
+    let config = FileScanConfigBuilder::new(
+        ObjectStoreUrl::local_filesystem(),
+        Arc::clone(schema),
+        Arc::new(source),
+    )
+    .with_file(file)
+    .with_projection(Some(projection))
+    .build();
+    DataSourceExec::from_data_source(config)
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let tmp_dir = tempfile::TempDir::new().unwrap();
+    let path = tmp_dir.path().join("data.avro");
+    write_file(&path);
+    let schema = Arc::new(
+        read_avro_schema_from_reader(&mut std::fs::File::open(&path).unwrap()).unwrap(),
+    );
+    let rt = Builder::new_current_thread().build().unwrap();
+    let task_ctx = Arc::new(TaskContext::default());
+    let all_columns = (0..schema.fields().len()).collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("avro_scan");
+    group.throughput(Throughput::Elements(NUM_ROWS as u64));
+
+    group.bench_function("full_scan", |b| {
+        b.iter(|| {
+            let plan = scan(&path, &schema, AvroSource::new(), all_columns.clone());
+            rt.block_on(collect(plan, Arc::clone(&task_ctx))).unwrap()
+        })
+    });
+
+    // `id` and `name`
+    group.bench_function("projected_scan", |b| {
+        b.iter(|| {
+            let plan = scan(&path, &schema, AvroSource::new(), vec![0, 4]);
+            rt.block_on(collect(plan, Arc::clone(&task_ctx))).unwrap()
+        })
+    });
+
+    // `quantity < 100`, selecting 10% of the rows
+    let predicate = binary(
+        col("quantity", &schema).unwrap(),
+        Operator::Lt,
+        lit(100i32),
+        &schema,
+    )
+    .unwrap();
+    group.bench_function("filtered_scan", |b| {
+        b.iter(|| {
+            let source = AvroSource::new().with_predicate(Arc::clone(&predicate));
+            let plan = scan(&path, &schema, source, all_columns.clone());
+            rt.block_on(collect(plan, Arc::clone(&task_ctx))).unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,600 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Conversion of Arrow schemas and record batches to Avro schemas and values,
+//! used to write Avro files
+
+use std::collections::BTreeMap;
+
+use apache_avro::schema::{
+    ArraySchema, DecimalSchema, FixedSchema, MapSchema, Name, RecordField,
+    RecordFieldOrder, RecordSchema, UnionSchema,
+};
+use apache_avro::types::Value;
+use apache_avro::Schema as AvroSchema;
+use arrow::array::{Array, ArrayRef, AsArray, RecordBatch};
+use arrow::compute::{cast_with_options, CastOptions};
+use arrow::datatypes::{
+    DataType, Date32Type, Decimal128Type, Field, Fields, Float32Type, Float64Type,
+    Int32Type, Int64Type, Schema, Time32MillisecondType, Time64MicrosecondType, TimeUnit,
+    TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+};
+use arrow_schema::extension::EXTENSION_TYPE_NAME_KEY;
+use datafusion_common::{not_impl_err, DataFusionError, Result};
+use uuid::Uuid;
+
+use crate::avro_to_arrow::UUID_EXTENSION_NAME;
+
+/// The name of the top level record of the Avro schema of written files
+const RECORD_NAME: &str = "record";
+
+/// Returns the Avro schema of the records of files written from batches with
+/// the Arrow schema `schema`.
+///
+/// The schema is a record with a field for each Arrow field, whose nullable
+/// fields are `["null", T]` unions. Nested records and fixed types are named
+/// after the path of their field. Fields of the [`UUID_EXTENSION_NAME`]
+/// extension type are written as `uuid`s.
+pub fn to_avro_schema(schema: &Schema) -> Result<AvroSchema> {
+    record_schema(RECORD_NAME, schema.fields())
+}
+
+/// Converts each row of `batch` to an Avro record with the schema returned
+/// by [`to_avro_schema`] for the schema of the batch
+pub fn to_avro_values(batch: &RecordBatch) -> Result<Vec<Value>> {
+    let schema = batch.schema();
+    let mut columns = batch
+        .columns()
+        .iter()
+        .zip(schema.fields())
+        .map(|(column, field)| Ok(field_values(column, field)?.into_iter()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok((0..batch.num_rows())
+        .map(|_| {
+            let fields = schema
+                .fields()
+                .iter()
+                .zip(columns.iter_mut())
+                .map(|(field, values)| (field.name().clone(), values.next().unwrap()))
+                .collect();
+            Value::Record(fields)
+        })
+        .collect())
+}
+
+fn record_schema(name: &str, fields: &Fields) -> Result<AvroSchema> {
+    let fields = fields
+        .iter()
+        .enumerate()
+        .map(|(position, field)| {
+            Ok(RecordField {
+                name: field.name().clone(),
+                doc: None,
+                aliases: None,
+                default: None,
+                schema: field_schema(&format!("{name}_{}", field.name()), field)?,
+                order: RecordFieldOrder::Ascending,
+                position,
+                custom_attributes: BTreeMap::new(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let lookup = fields
+        .iter()
+        .map(|field| (field.name.clone(), field.position))
+        .collect();
+    Ok(AvroSchema::Record(RecordSchema {
+        name: schema_name(name)?,
+        aliases: None,
+        doc: None,
+        fields,
+        lookup,
+        attributes: BTreeMap::new(),
+    }))
+}
+
+/// Returns the schema of the values of `field`, named after `path` if it
+/// is a named type
+fn field_schema(path: &str, field: &Field) -> Result<AvroSchema> {
+    let schema = if is_uuid(field) {
+        AvroSchema::Uuid
+    } else {
+        data_type_schema(path, field.data_type())?
+    };
+    if field.is_nullable() && !matches!(schema, AvroSchema::Null) {
+        Ok(AvroSchema::Union(UnionSchema::new(vec![
+            AvroSchema::Null,
+            schema,
+        ])?))
+    } else {
+        Ok(schema)
+    }
+}
+
+/// Returns true if `field` has the [`UUID_EXTENSION_NAME`] extension type
+fn is_uuid(field: &Field) -> bool {
+    field.data_type() == &DataType::FixedSizeBinary(16)
+        && field
+            .metadata()
+            .get(EXTENSION_TYPE_NAME_KEY)
+            .is_some_and(|name| name == UUID_EXTENSION_NAME)
+}
+
+fn data_type_schema(path: &str, data_type: &DataType) -> Result<AvroSchema> {
+    Ok(match data_type {
+        DataType::Null => AvroSchema::Null,
+        DataType::Boolean => AvroSchema::Boolean,
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::UInt8
+        | DataType::UInt16 => AvroSchema::Int,
+        DataType::Int64 | DataType::UInt32 | DataType::UInt64 => AvroSchema::Long,
+        DataType::Float16 | DataType::Float32 => AvroSchema::Float,
+        DataType::Float64 => AvroSchema::Double,
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => AvroSchema::String,
+        DataType::Binary | DataType::LargeBinary | DataType::BinaryView => {
+            AvroSchema::Bytes
+        }
+        DataType::FixedSizeBinary(size) => AvroSchema::Fixed(FixedSchema {
+            name: schema_name(path)?,
+            aliases: None,
+            doc: None,
+            size: *size as usize,
+            default: None,
+            attributes: BTreeMap::new(),
+        }),
+        DataType::Decimal128(precision, scale) if *scale >= 0 => {
+            AvroSchema::Decimal(DecimalSchema {
+                precision: *precision as usize,
+                scale: *scale as usize,
+                inner: Box::new(AvroSchema::Bytes),
+            })
+        }
+        DataType::Date32 => AvroSchema::Date,
+        DataType::Time32(TimeUnit::Millisecond) => AvroSchema::TimeMillis,
+        DataType::Time64(TimeUnit::Microsecond) => AvroSchema::TimeMicros,
+        DataType::Timestamp(TimeUnit::Second | TimeUnit::Millisecond, _) => {
+            AvroSchema::TimestampMillis
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => AvroSchema::TimestampMicros,
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => AvroSchema::TimestampNanos,
+        DataType::List(item)
+        | DataType::LargeList(item)
+        | DataType::FixedSizeList(item, _) => AvroSchema::Array(ArraySchema {
+            items: Box::new(field_schema(path, item)?),
+            attributes: BTreeMap::new(),
+        }),
+        DataType::Struct(fields) => record_schema(path, fields)?,
+        DataType::Map(entries, _) => match entries.data_type() {
+            DataType::Struct(fields)
+                if fields.len() == 2
+                    && matches!(
+                        fields[0].data_type(),
+                        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+                    ) =>
+            {
+                AvroSchema::Map(MapSchema {
+                    types: Box::new(field_schema(path, &fields[1])?),
+                    attributes: BTreeMap::new(),
+                })
+            }
+            _ => return not_impl_err!("Writing Avro maps requires string keys"),
+        },
+        DataType::Dictionary(_, value_type) => data_type_schema(path, value_type)?,
+        data_type => {
+            return not_impl_err!("Writing {data_type} columns to Avro is not supported")
+        }
+    })
+}
+
+/// Returns a valid Avro name for `name`, replacing the characters that are
+/// not allowed in names by underscores
+fn schema_name(name: &str) -> Result<Name> {
+    let name = name
+        .chars()
+        .enumerate()
+        .map(|(index, c)| match c {
+            'A'..='Z' | 'a'..='z' | '_' => c,
+            '0'..='9' if index > 0 => c,
+            _ => '_',
+        })
+        .collect::<String>();
+    Ok(Name::new(&name)?)
+}
+
+/// Returns the values of `array` with the schema returned by
+/// [`field_schema`] for `field`
+fn field_values(array: &ArrayRef, field: &Field) -> Result<Vec<Value>> {
+    let values = if is_uuid(field) {
+        uuid_values(array)?
+    } else {
+        data_type_values(array)?
+    };
+    if field.is_nullable() && !matches!(field.data_type(), DataType::Null) {
+        Ok(values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                if array.is_null(index) {
+                    Value::Union(0, Box::new(Value::Null))
+                } else {
+                    Value::Union(1, Box::new(value))
+                }
+            })
+            .collect())
+    } else {
+        Ok(values)
+    }
+}
+
+/// Returns the values of `array`, with [`Value::Null`] for null values
+fn data_type_values(array: &ArrayRef) -> Result<Vec<Value>> {
+    let values = match array.data_type() {
+        DataType::Null => vec![Value::Null; array.len()],
+        DataType::Boolean => array
+            .as_boolean()
+            .iter()
+            .map(|value| value.map(Value::Boolean).unwrap_or(Value::Null))
+            .collect(),
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::UInt8
+        | DataType::UInt16 => cast(array, &DataType::Int32)?
+            .as_primitive::<Int32Type>()
+            .iter()
+            .map(|value| value.map(Value::Int).unwrap_or(Value::Null))
+            .collect(),
+        DataType::Int64 | DataType::UInt32 | DataType::UInt64 => {
+            cast(array, &DataType::Int64)?
+                .as_primitive::<Int64Type>()
+                .iter()
+                .map(|value| value.map(Value::Long).unwrap_or(Value::Null))
+                .collect()
+        }
+        DataType::Float16 | DataType::Float32 => cast(array, &DataType::Float32)?
+            .as_primitive::<Float32Type>()
+            .iter()
+            .map(|value| value.map(Value::Float).unwrap_or(Value::Null))
+            .collect(),
+        DataType::Float64 => array
+            .as_primitive::<Float64Type>()
+            .iter()
+            .map(|value| value.map(Value::Double).unwrap_or(Value::Null))
+            .collect(),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+            cast(array, &DataType::LargeUtf8)?
+                .as_string::<i64>()
+                .iter()
+                .map(|value| {
+                    value
+                        .map(|value| Value::String(value.to_string()))
+                        .unwrap_or(Value::Null)
+                })
+                .collect()
+        }
+        DataType::Binary | DataType::LargeBinary | DataType::BinaryView => {
+            cast(array, &DataType::LargeBinary)?
+                .as_binary::<i64>()
+                .iter()
+                .map(|value| {
+                    value
+                        .map(|value| Value::Bytes(value.to_vec()))
+                        .unwrap_or(Value::Null)
+                })
+                .collect()
+        }
+        DataType::FixedSizeBinary(size) => array
+            .as_fixed_size_binary()
+            .iter()
+            .map(|value| {
+                value
+                    .map(|value| Value::Fixed(*size as usize, value.to_vec()))
+                    .unwrap_or(Value::Null)
+            })
+            .collect(),
+        DataType::Decimal128(_, _) => array
+            .as_primitive::<Decimal128Type>()
+            .iter()
+            .map(|value| {
+                value
+                    .map(|value| Value::Decimal(value.to_be_bytes().into()))
+                    .unwrap_or(Value::Null)
+            })
+            .collect(),
+        DataType::Date32 => array
+            .as_primitive::<Date32Type>()
+            .iter()
+            .map(|value| value.map(Value::Date).unwrap_or(Value::Null))
+            .collect(),
+        DataType::Time32(TimeUnit::Millisecond) => array
+            .as_primitive::<Time32MillisecondType>()
+            .iter()
+            .map(|value| value.map(Value::TimeMillis).unwrap_or(Value::Null))
+            .collect(),
+        DataType::Time64(TimeUnit::Microsecond) => array
+            .as_primitive::<Time64MicrosecondType>()
+            .iter()
+            .map(|value| value.map(Value::TimeMicros).unwrap_or(Value::Null))
+            .collect(),
+        DataType::Timestamp(TimeUnit::Second | TimeUnit::Millisecond, tz) => cast(
+            array,
+            &DataType::Timestamp(TimeUnit::Millisecond, tz.clone()),
+        )?
+        .as_primitive::<TimestampMillisecondType>()
+        .iter()
+        .map(|value| value.map(Value::TimestampMillis).unwrap_or(Value::Null))
+        .collect(),
+        DataType::Timestamp(TimeUnit::Microsecond, _) => array
+            .as_primitive::<TimestampMicrosecondType>()
+            .iter()
+            .map(|value| value.map(Value::TimestampMicros).unwrap_or(Value::Null))
+            .collect(),
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => array
+            .as_primitive::<TimestampNanosecondType>()
+            .iter()
+            .map(|value| value.map(Value::TimestampNanos).unwrap_or(Value::Null))
+            .collect(),
+        DataType::List(item) => {
+            let list = array.as_list::<i32>();
+            list_values(list.iter(), item)?
+        }
+        DataType::LargeList(item) => {
+            let list = array.as_list::<i64>();
+            list_values(list.iter(), item)?
+        }
+        DataType::FixedSizeList(item, _) => {
+            let list = array.as_fixed_size_list();
+            list_values(list.iter(), item)?
+        }
+        DataType::Struct(fields) => {
+            let array = array.as_struct();
+            let mut columns = array
+                .columns()
+                .iter()
+                .zip(fields)
+                .map(|(column, field)| Ok(field_values(column, field)?.into_iter()))
+                .collect::<Result<Vec<_>>>()?;
+            (0..array.len())
+                .map(|index| {
+                    let fields = fields
+                        .iter()
+                        .zip(columns.iter_mut())
+                        .map(|(field, values)| {
+                            (field.name().clone(), values.next().unwrap())
+                        })
+                        .collect();
+                    if array.is_null(index) {
+                        Value::Null
+                    } else {
+                        Value::Record(fields)
+                    }
+                })
+                .collect()
+        }
+        DataType::Map(_, _) => {
+            let map = array.as_map();
+            let keys = data_type_values(map.keys())?;
+            let DataType::Struct(fields) = map.entries().data_type() else {
+                unreachable!("Map entries are structs")
+            };
+            let values = field_values(map.values(), &fields[1])?;
+            map.value_offsets()
+                .windows(2)
+                .enumerate()
+                .map(|(index, offsets)| {
+                    if map.is_null(index) {
+                        return Value::Null;
+                    }
+                    let entries = (offsets[0] as usize..offsets[1] as usize)
+                        .map(|entry| match &keys[entry] {
+                            Value::String(key) => (key.clone(), values[entry].clone()),
+                            _ => unreachable!("Map keys are strings"),
+                        })
+                        .collect();
+                    Value::Map(entries)
+                })
+                .collect()
+        }
+        DataType::Dictionary(_, value_type) => {
+            data_type_values(&cast(array, value_type)?)?
+        }
+        data_type => {
+            return not_impl_err!("Writing {data_type} columns to Avro is not supported")
+        }
+    };
+    Ok(values)
+}
+
+/// Returns the values of the `FixedSizeBinary(16)` `array` as Avro `uuid`s
+fn uuid_values(array: &ArrayRef) -> Result<Vec<Value>> {
+    array
+        .as_fixed_size_binary()
+        .iter()
+        .map(|value| match value {
+            Some(value) => Uuid::from_slice(value)
+                .map(Value::Uuid)
+                .map_err(|e| DataFusionError::External(Box::new(e))),
+            None => Ok(Value::Null),
+        })
+        .collect()
+}
+
+fn list_values(
+    lists: impl Iterator<Item = Option<ArrayRef>>,
+    item: &Field,
+) -> Result<Vec<Value>> {
+    lists
+        .map(|list| match list {
+            Some(list) => Ok(Value::Array(field_values(&list, item)?)),
+            None => Ok(Value::Null),
+        })
+        .collect()
+}
+
+/// Casts `array` to `data_type`, failing instead of producing nulls for
+/// values that do not fit
+fn cast(array: &ArrayRef, data_type: &DataType) -> Result<ArrayRef> {
+    let options = CastOptions {
+        safe: false,
+        ..Default::default()
+    };
+    Ok(cast_with_options(array, data_type, &options)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use crate::avro_to_arrow::ReaderBuilder;
+    use arrow::array::{
+        Decimal128Array, FixedSizeBinaryArray, Float64Array, Int64Array, ListBuilder,
+        StringBuilder, StructArray, TimestampMicrosecondArray,
+    };
+    use arrow::buffer::NullBuffer;
+
+    #[test]
+    fn test_round_trip() -> Result<()> {
+        let point_fields = Fields::from(vec![
+            Field::new("x", DataType::Float64, false),
+            Field::new("y", DataType::Float64, true),
+        ]);
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new(
+                "tags",
+                DataType::List(Arc::new(Field::new("element", DataType::Utf8, false))),
+                true,
+            ),
+            Field::new("point", DataType::Struct(point_fields.clone()), true),
+            Field::new("amount", DataType::Decimal128(10, 2), true),
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Microsecond, None),
+                false,
+            ),
+        ]));
+
+        let mut tags = ListBuilder::new(StringBuilder::new())
+            .with_field(Arc::new(Field::new("element", DataType::Utf8, false)));
+        tags.append_value([Some("a"), Some("b")]);
+        tags.append_null();
+        tags.append_value([Some("c")]);
+        let point = StructArray::new(
+            point_fields,
+            vec![
+                Arc::new(Float64Array::from(vec![1.0, 0.0, 3.0])),
+                Arc::new(Float64Array::from(vec![Some(1.5), None, None])),
+            ],
+            Some(NullBuffer::from(vec![true, false, true])),
+        );
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3])),
+                Arc::new(tags.finish()),
+                Arc::new(point),
+                Arc::new(
+                    Decimal128Array::from(vec![Some(12345), None, Some(-5)])
+                        .with_precision_and_scale(10, 2)?,
+                ),
+                Arc::new(TimestampMicrosecondArray::from(vec![0, 1_000_000, -1])),
+            ],
+        )?;
+
+        let avro_schema = to_avro_schema(&schema)?;
+        let mut writer = apache_avro::Writer::new(&avro_schema, vec![]);
+        for value in to_avro_values(&batch)? {
+            writer.append(value).unwrap();
+        }
+        let data = writer.into_inner().unwrap();
+
+        let mut reader = ReaderBuilder::new()
+            .read_schema()
+            .build(std::io::Cursor::new(data))?;
+        let read = reader.next().unwrap()?;
+        assert_eq!(read, batch);
+        Ok(())
+    }
+
+    #[test]
+    fn test_uuid_round_trip() -> Result<()> {
+        let uuid_field = Field::new("id", DataType::FixedSizeBinary(16), true)
+            .with_metadata(HashMap::from([(
+                EXTENSION_TYPE_NAME_KEY.to_string(),
+                UUID_EXTENSION_NAME.to_string(),
+            )]));
+        let schema = Arc::new(Schema::new(vec![uuid_field]));
+        let uuid = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(
+                FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+                    [Some(uuid.as_bytes().to_vec()), None].into_iter(),
+                    16,
+                )?,
+            )],
+        )?;
+
+        let avro_schema = to_avro_schema(&schema)?;
+        let mut writer = apache_avro::Writer::new(&avro_schema, vec![]);
+        for value in to_avro_values(&batch)? {
+            writer.append(value).unwrap();
+        }
+        let data = writer.into_inner().unwrap();
+
+        let mut reader = ReaderBuilder::new()
+            .read_schema()
+            .build(std::io::Cursor::new(data.clone()))?;
+        let read = reader.next().unwrap()?;
+        assert_eq!(
+            read.schema()
+                .field(0)
+                .metadata()
+                .get(EXTENSION_TYPE_NAME_KEY),
+            Some(&UUID_EXTENSION_NAME.to_string())
+        );
+        assert_eq!(read, batch);
+
+        // Without extension types, only the storage type is left
+        let mut reader = ReaderBuilder::new()
+            .read_schema()
+            .with_extension_types(false)
+            .build(std::io::Cursor::new(data))?;
+        let read = reader.next().unwrap()?;
+        assert!(read.schema().field(0).metadata().is_empty());
+        assert_eq!(read.column(0), batch.column(0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsupported_type() {
+        let schema = Schema::new(vec![Field::new(
+            "d",
+            DataType::Duration(TimeUnit::Second),
+            false,
+        )]);
+        let err = to_avro_schema(&schema).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Writing Duration(Second) columns to Avro is not supported"));
+    }
+}
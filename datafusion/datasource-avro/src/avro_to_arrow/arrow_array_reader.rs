@@ -17,6 +17,15 @@
 
 //! Avro to Arrow array readers
 
+use super::date_columns::resolve_date;
+use super::decode_error::{BlockPosition, CountingReader};
+use super::{
+    with_map_entries, MapDuplicateKeyPolicy, NonMidnightPolicy, UnionRepresentation,
+    ROW_INDEX_COLUMN,
+};
+use crate::decode_buffer_pool::{DecodeBufferPool, RecordBuffer};
+use crate::row_filter::AvroRowFilter;
+use crate::sample::RecordSample;
 use apache_avro::schema::RecordSchema;
 use apache_avro::{
     schema::{Schema as AvroSchema, SchemaKind},
@@ -25,49 +34,115 @@ use apache_avro::{
 };
 use arrow::array::{
     make_array, Array, ArrayBuilder, ArrayData, ArrayDataBuilder, ArrayRef,
-    BooleanBuilder, LargeStringArray, ListBuilder, NullArray, OffsetSizeTrait,
-    PrimitiveArray, StringArray, StringBuilder, StringDictionaryBuilder,
+    BooleanBuilder, Int64Array, LargeStringArray, ListBuilder, NullArray,
+    OffsetSizeTrait, PrimitiveArray, StringArray, StringBuilder, StringDictionaryBuilder,
+};
+use arrow::array::{
+    Decimal128Array, FixedSizeBinaryArray, GenericBinaryArray, GenericListArray,
+    GenericStringArray,
 };
-use arrow::array::{BinaryArray, FixedSizeBinaryArray, GenericListArray};
 use arrow::buffer::{Buffer, MutableBuffer};
 use arrow::datatypes::{
-    ArrowDictionaryKeyType, ArrowNumericType, ArrowPrimitiveType, DataType, Date32Type,
-    Date64Type, Field, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type,
-    Int8Type, Time32MillisecondType, Time32SecondType, Time64MicrosecondType,
-    Time64NanosecondType, TimeUnit, TimestampMicrosecondType, TimestampMillisecondType,
-    TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type, UInt64Type,
-    UInt8Type,
+    ArrowDictionaryKeyType, ArrowNumericType, ArrowPrimitiveType, ArrowTimestampType,
+    DataType, Date32Type, Date64Type, Field, Float32Type, Float64Type, Int16Type,
+    Int32Type, Int64Type, Int8Type, Time32MillisecondType, Time32SecondType,
+    Time64MicrosecondType, Time64NanosecondType, TimeUnit, TimestampMicrosecondType,
+    TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType, UInt16Type,
+    UInt32Type, UInt64Type, UInt8Type,
 };
 use arrow::datatypes::{Fields, SchemaRef};
 use arrow::error::ArrowError;
 use arrow::error::ArrowError::SchemaError;
 use arrow::error::Result as ArrowResult;
-use arrow::record_batch::RecordBatch;
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
 use arrow::util::bit_util;
 use datafusion_common::arrow_err;
 use datafusion_common::error::{DataFusionError, Result};
 use num_traits::NumCast;
-use std::collections::BTreeMap;
-use std::io::Read;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::{Chain, Cursor, Read};
 use std::sync::Arc;
 
 type RecordSlice<'a> = &'a [&'a Vec<(String, Value)>];
+/// Records along with their indices in the file
+type IndexedRecords<'a> = (Vec<&'a Vec<(String, Value)>>, Vec<i64>);
 
 pub struct AvroArrowArrayReader<'a, R: Read> {
-    reader: AvroReader<'a, R>,
+    /// Reads the file, with its maps decoded as lists of entries unless
+    /// keeping the last occurrence of duplicate keys, see
+    /// [`with_map_entries`]
+    reader: AvroReader<'a, Chain<Cursor<Vec<u8>>, CountingReader<R>>>,
+    /// The position of the block being decoded, to locate decoding errors
+    position: BlockPosition,
+    /// The path of the file, to locate decoding errors
+    location: Option<String>,
     schema: SchemaRef,
     schema_lookup: BTreeMap<String, usize>,
+    /// The paths of `schema_lookup` by their lowercase form, when fields are
+    /// resolved ignoring case. Paths that are only distinct by case are left
+    /// out, as they cannot be resolved.
+    case_insensitive_paths: Option<BTreeMap<String, String>>,
+    /// Paths of the multi-branch unions decoded as [`UnionRepresentation::Struct`]
+    union_struct_paths: BTreeSet<String>,
+    map_duplicate_key_policy: MapDuplicateKeyPolicy,
+    non_midnight_policy: NonMidnightPolicy,
+    row_filter: Option<AvroRowFilter>,
+    /// The sample of the records returned, if any
+    sample: Option<RecordSample>,
+    /// Whether the [`ROW_INDEX_COLUMN`] of the schema is numbered with the
+    /// index of each record in the file, rather than read from it
+    row_index: bool,
+    /// Number of records read so far
+    records_read: usize,
+    /// The records of the batch being decoded, reused across batches
+    records: RecordBuffer,
+    /// The pool `records` is returned to once the file is decoded
+    buffer_pool: Option<Arc<DecodeBufferPool>>,
+}
+
+/// Returns the record buffer to the pool it was taken from
+impl<R: Read> Drop for AvroArrowArrayReader<'_, R> {
+    fn drop(&mut self) {
+        if let Some(buffer_pool) = &self.buffer_pool {
+            buffer_pool.put_records(std::mem::take(&mut self.records));
+        }
+    }
 }
 
 impl<R: Read> AvroArrowArrayReader<'_, R> {
-    pub fn try_new(reader: R, schema: SchemaRef) -> Result<Self> {
+    pub fn try_new(
+        reader: R,
+        schema: SchemaRef,
+        map_duplicate_key_policy: MapDuplicateKeyPolicy,
+    ) -> Result<Self> {
+        let (reader, position) = BlockPosition::new(reader);
+        let reader = match map_duplicate_key_policy {
+            // Decoding the maps keeps the value of the last occurrence of
+            // each key, the other policies need all their entries
+            MapDuplicateKeyPolicy::LastWins => Cursor::new(vec![]).chain(reader),
+            MapDuplicateKeyPolicy::Error | MapDuplicateKeyPolicy::Keep => {
+                with_map_entries(reader)?
+            }
+        };
         let reader = AvroReader::new(reader)?;
         let writer_schema = reader.writer_schema().clone();
         let schema_lookup = Self::schema_lookup(writer_schema)?;
         Ok(Self {
             reader,
+            position,
+            location: None,
             schema,
             schema_lookup,
+            case_insensitive_paths: None,
+            union_struct_paths: BTreeSet::new(),
+            map_duplicate_key_policy,
+            non_midnight_policy: NonMidnightPolicy::default(),
+            row_filter: None,
+            sample: None,
+            row_index: false,
+            records_read: 0,
+            records: vec![],
+            buffer_pool: None,
         })
     }
 
@@ -112,6 +187,19 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
                             schema_lookup,
                         )?;
                     }
+                } else {
+                    // Positions in the rows built by `union_struct_row`
+                    schema_lookup.insert(format!("{parent_field_name}.tag"), 0);
+                    for (index, sub_schema) in sub_schemas.iter().enumerate() {
+                        let sub_parent_field_name =
+                            format!("{parent_field_name}.branch_{index}");
+                        schema_lookup.insert(sub_parent_field_name.clone(), index + 1);
+                        Self::child_schema_lookup(
+                            &sub_parent_field_name,
+                            sub_schema,
+                            schema_lookup,
+                        )?;
+                    }
                 }
             }
             AvroSchema::Record(RecordSchema { fields, lookup, .. }) => {
@@ -138,40 +226,303 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
                     schema_lookup,
                 )?;
             }
+            AvroSchema::Map(schema) => {
+                // Positions in the entries built by `map_entry_rows`
+                schema_lookup.insert(format!("{parent_field_name}.element.key"), 0);
+                let sub_parent_field_name = format!("{parent_field_name}.element.value");
+                schema_lookup.insert(sub_parent_field_name.clone(), 1);
+                Self::child_schema_lookup(
+                    &sub_parent_field_name,
+                    &schema.types,
+                    schema_lookup,
+                )?;
+            }
             _ => (),
         }
         Ok(schema_lookup)
     }
 
+    /// Set how multi-branch unions are decoded, which must match the
+    /// representation of the reader's schema
+    pub(crate) fn set_union_representation(
+        &mut self,
+        union_representation: UnionRepresentation,
+    ) {
+        self.union_struct_paths.clear();
+        if union_representation == UnionRepresentation::Struct {
+            if let AvroSchema::Record(RecordSchema { fields, .. }) =
+                self.reader.writer_schema()
+            {
+                for field in fields {
+                    union_paths(&field.name, &field.schema, &mut self.union_struct_paths);
+                }
+            }
+        }
+    }
+
+    /// Resolve the fields of the reader's schema to the fields of the file
+    /// ignoring case, when the file has no field of the exact same name
+    pub(crate) fn set_case_insensitive_field_resolution(&mut self) {
+        let mut paths = BTreeMap::new();
+        let mut ambiguous = BTreeSet::new();
+        for path in self.schema_lookup.keys() {
+            let lowercase = path.to_ascii_lowercase();
+            if paths.insert(lowercase.clone(), path.clone()).is_some() {
+                ambiguous.insert(lowercase);
+            }
+        }
+        paths.retain(|lowercase, _| !ambiguous.contains(lowercase));
+        self.case_insensitive_paths = Some(paths);
+    }
+
+    /// The path of the field of the file read for the reader field at
+    /// `path`, which only differs from `path` when resolving fields ignoring
+    /// case
+    fn writer_path<'p>(&'p self, path: &'p str) -> &'p str {
+        if self.schema_lookup.contains_key(path) {
+            return path;
+        }
+        self.case_insensitive_paths
+            .as_ref()
+            .and_then(|paths| paths.get(&path.to_ascii_lowercase()))
+            .map_or(path, String::as_str)
+    }
+
+    /// Set how the timestamps of `Date32` columns that are not at midnight
+    /// are decoded
+    pub(crate) fn set_non_midnight_policy(
+        &mut self,
+        non_midnight_policy: NonMidnightPolicy,
+    ) {
+        self.non_midnight_policy = non_midnight_policy;
+    }
+
+    /// Set whether the [`ROW_INDEX_COLUMN`] of the schema is numbered with
+    /// the index of each record in the file
+    pub(crate) fn set_row_index(&mut self, row_index: bool) {
+        self.row_index = row_index;
+    }
+
+    /// Name the file at `location` in the errors of undecodable records
+    pub(crate) fn set_location(&mut self, location: String) {
+        self.location = Some(location);
+    }
+
+    /// Only return the records selected by `row_filter`
+    pub(crate) fn set_row_filter(&mut self, row_filter: AvroRowFilter) {
+        self.row_filter = Some(row_filter);
+    }
+
+    /// Only return the records kept by `sample`
+    pub(crate) fn set_sample(&mut self, sample: RecordSample) {
+        self.sample = Some(sample);
+    }
+
+    /// Decode the records of the batches into a buffer of `buffer_pool`,
+    /// returned to it once the reader is dropped
+    pub(crate) fn set_buffer_pool(
+        &mut self,
+        buffer_pool: Arc<DecodeBufferPool>,
+        batch_size: usize,
+    ) {
+        let records = buffer_pool.take_records(batch_size);
+        if let Some(previous) = self.buffer_pool.replace(buffer_pool) {
+            previous.put_records(std::mem::replace(&mut self.records, records));
+        } else {
+            self.records = records;
+        }
+    }
+
     /// Read the next batch of records
+    ///
+    /// If a row filter or a sample is set, records not selected by it are
+    /// skipped, so the returned batch may contain fewer than `batch_size`
+    /// records.
     pub fn next_batch(&mut self, batch_size: usize) -> Option<ArrowResult<RecordBatch>> {
-        let rows_result = self
-            .reader
-            .by_ref()
-            .take(batch_size)
-            .map(|value| match value {
-                Ok(Value::Record(v)) => Ok(v),
-                Err(e) => Err(ArrowError::ParseError(format!(
-                    "Failed to parse avro value: {e:?}"
-                ))),
-                other => Err(ArrowError::ParseError(format!(
-                    "Row needs to be of type object, got: {other:?}"
-                ))),
-            })
-            .collect::<ArrowResult<Vec<Vec<(String, Value)>>>>();
-
-        let rows = match rows_result {
-            // Return error early
-            Err(e) => return Some(Err(e)),
-            // No rows: return None early
-            Ok(rows) if rows.is_empty() => return None,
-            Ok(rows) => rows,
-        };
+        loop {
+            // Decode into the buffer of the reader, put back once the batch
+            // is built
+            let mut records = std::mem::take(&mut self.records);
+            records.clear();
+            let read = self.read_records(&mut records, batch_size);
+            let first_record = self.records_read;
+            self.records_read += records.len();
+            let batch = match read {
+                Err(e) => Some(Err(e)),
+                // No rows: the file is decoded
+                Ok(()) if records.is_empty() => {
+                    self.records = records;
+                    return None;
+                }
+                Ok(()) => self.decode_records(&records, first_record),
+            };
+            records.clear();
+            self.records = records;
+            if batch.is_some() {
+                return batch;
+            }
+        }
+    }
+
+    /// Builds the batch of `rows`, the first of which is the
+    /// `first_record`th record of the file, or returns `None` if the sample
+    /// or the row filter selects none of them
+    fn decode_records(
+        &self,
+        rows: &[Vec<(String, Value)>],
+        first_record: usize,
+    ) -> Option<ArrowResult<RecordBatch>> {
+        if self.map_duplicate_key_policy == MapDuplicateKeyPolicy::Error {
+            if let Err(e) = self.check_map_keys(rows, first_record) {
+                return Some(Err(e));
+            }
+        }
 
         let rows = rows.iter().collect::<Vec<&Vec<(String, Value)>>>();
-        let arrays = self.build_struct_array(&rows, "", self.schema.fields());
+        let row_indices = (first_record..first_record + rows.len())
+            .map(|index| index as i64)
+            .collect::<Vec<_>>();
+        let (rows, row_indices): IndexedRecords = match &self.sample {
+            Some(sample) => rows
+                .into_iter()
+                .zip(row_indices)
+                .filter(|(_, index)| sample.keeps(*index))
+                .unzip(),
+            None => (rows, row_indices),
+        };
+        if rows.is_empty() {
+            return None;
+        }
+        let (rows, row_indices) = match &self.row_filter {
+            Some(row_filter) => match self.filter_rows(row_filter, rows, row_indices) {
+                Ok((rows, _)) if rows.is_empty() => return None,
+                Ok(selected) => selected,
+                Err(e) => return Some(Err(e)),
+            },
+            None => (rows, row_indices),
+        };
+        let arrays = self.build_columns(&rows, &row_indices, self.schema.fields());
+
+        Some(arrays.and_then(|arr| {
+            RecordBatch::try_new_with_options(
+                Arc::clone(&self.schema),
+                arr,
+                &RecordBatchOptions::new().with_row_count(Some(rows.len())),
+            )
+        }))
+    }
+
+    /// Reads the next `batch_size` records of the file, or the remaining
+    /// ones, into `records`, failing with an [`AvroDecodeError`] if they
+    /// can't be decoded
+    ///
+    /// [`AvroDecodeError`]: super::AvroDecodeError
+    fn read_records(
+        &mut self,
+        records: &mut RecordBuffer,
+        batch_size: usize,
+    ) -> ArrowResult<()> {
+        while records.len() < batch_size {
+            self.position.start_record();
+            let value = self.reader.next();
+            self.position.end_record();
+            match value {
+                None => break,
+                Some(Ok(Value::Record(v))) => records.push(v),
+                Some(Err(e)) => {
+                    let error = self.position.error(self.location.as_deref(), e);
+                    return Err(ArrowError::ExternalError(Box::new(error)));
+                }
+                Some(other) => {
+                    return Err(ArrowError::ParseError(format!(
+                        "Row needs to be of type object, got: {other:?}"
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails on the first key occurring more than once in a map of the
+    /// columns of `rows`, the first of which is the `first_record`th record
+    /// of the file
+    fn check_map_keys(
+        &self,
+        rows: &[Vec<(String, Value)>],
+        first_record: usize,
+    ) -> ArrowResult<()> {
+        let mut fields = self.schema.fields().iter().collect::<Vec<_>>();
+        if let Some(row_filter) = &self.row_filter {
+            fields.extend(row_filter.file_schema().fields().iter());
+        }
+        for (index, row) in rows.iter().enumerate() {
+            for field in &fields {
+                let Some(value) = self.field_lookup(field.name(), row) else {
+                    continue;
+                };
+                if let Some(key) = duplicate_map_key(value, field.data_type()) {
+                    return Err(ArrowError::ParseError(format!(
+                        "Duplicate key {key:?} in a map of column {} of record {}",
+                        field.name(),
+                        first_record + index
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds the columns read by `row_filter` for all `rows`, and returns
+    /// the rows selected by it along with their indices in the file
+    fn filter_rows<'b>(
+        &self,
+        row_filter: &AvroRowFilter,
+        rows: Vec<&'b Vec<(String, Value)>>,
+        row_indices: Vec<i64>,
+    ) -> ArrowResult<IndexedRecords<'b>> {
+        let filter_schema = row_filter.file_schema();
+        let arrays = self.build_columns(&rows, &row_indices, filter_schema.fields())?;
+        let batch = RecordBatch::try_new_with_options(
+            Arc::clone(filter_schema),
+            arrays,
+            &RecordBatchOptions::new().with_row_count(Some(rows.len())),
+        )?;
+        let mask = row_filter.evaluate(batch)?;
+        Ok(rows
+            .into_iter()
+            .zip(row_indices)
+            .zip(mask.values())
+            .filter_map(|(row, selected)| selected.then_some(row))
+            .unzip())
+    }
 
-        Some(arrays.and_then(|arr| RecordBatch::try_new(Arc::clone(&self.schema), arr)))
+    /// Builds the top level columns `fields` of `rows`, whose indices in the
+    /// file are `row_indices`
+    fn build_columns(
+        &self,
+        rows: RecordSlice,
+        row_indices: &[i64],
+        fields: &Fields,
+    ) -> ArrowResult<Vec<ArrayRef>> {
+        if !self.row_index {
+            return self.build_struct_array(rows, "", fields);
+        }
+        fields
+            .iter()
+            .map(|field| {
+                if field.name() == ROW_INDEX_COLUMN {
+                    return Ok(
+                        Arc::new(Int64Array::from(row_indices.to_vec())) as ArrayRef
+                    );
+                }
+                let mut arrays = self.build_struct_array(
+                    rows,
+                    "",
+                    &Fields::from(vec![Arc::clone(field)]),
+                )?;
+                Ok(arrays.remove(0))
+            })
+            .collect()
     }
 
     fn build_boolean_array(&self, rows: RecordSlice, col_name: &str) -> ArrayRef {
@@ -205,6 +556,43 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
         )
     }
 
+    /// Builds a `Date32` array from dates, or from timestamps at midnight
+    fn build_date_array(
+        &self,
+        rows: RecordSlice,
+        col_name: &str,
+    ) -> ArrowResult<ArrayRef> {
+        let dates = rows
+            .iter()
+            .map(|row| match self.field_lookup(col_name, row) {
+                Some(value) => resolve_date(value, col_name, self.non_midnight_policy),
+                None => Ok(None),
+            })
+            .collect::<ArrowResult<PrimitiveArray<Date32Type>>>()?;
+        Ok(Arc::new(dates))
+    }
+
+    /// Builds a timestamp array of `T` in the time zone `tz`
+    fn build_timestamp_array<T>(
+        &self,
+        rows: RecordSlice,
+        col_name: &str,
+        tz: Option<Arc<str>>,
+    ) -> ArrayRef
+    where
+        T: ArrowTimestampType + Resolver,
+    {
+        Arc::new(
+            rows.iter()
+                .map(|row| {
+                    self.field_lookup(col_name, row)
+                        .and_then(|value| resolve_item::<T>(value))
+                })
+                .collect::<PrimitiveArray<T>>()
+                .with_timezone_opt(tz),
+        )
+    }
+
     #[inline(always)]
     fn build_string_dictionary_builder<T>(
         &self,
@@ -449,6 +837,40 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
         }
     }
 
+    /// Build a string array of the values of the field at `field_path`
+    fn build_string_array<O: OffsetSizeTrait>(
+        &self,
+        rows: RecordSlice,
+        field_path: &str,
+    ) -> ArrowResult<ArrayRef> {
+        let values = rows
+            .iter()
+            .map(|row| match self.field_lookup(field_path, row) {
+                None => Ok(None),
+                Some(v) => resolve_string(v),
+            })
+            .collect::<ArrowResult<Vec<_>>>()?;
+        check_offsets::<O>(field_path, string_bytes(&values))?;
+        Ok(Arc::new(GenericStringArray::<O>::from(values)))
+    }
+
+    /// Build a binary array of the values of the field at `field_path`
+    fn build_binary_array<O: OffsetSizeTrait>(
+        &self,
+        rows: RecordSlice,
+        field_path: &str,
+    ) -> ArrowResult<ArrayRef> {
+        let values = rows
+            .iter()
+            .map(|row| self.field_lookup(field_path, row).and_then(resolve_bytes))
+            .collect::<Vec<_>>();
+        let num_bytes = values.iter().flatten().map(Vec::len).sum();
+        check_offsets::<O>(field_path, num_bytes)?;
+        Ok(Arc::new(
+            values.into_iter().collect::<GenericBinaryArray<O>>(),
+        ))
+    }
+
     /// Build a nested GenericListArray from a list of unnested `Value`s
     fn build_nested_list_array<OffsetSize: OffsetSizeTrait>(
         &self,
@@ -456,6 +878,15 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
         rows: &[&Value],
         list_field: &Field,
     ) -> ArrowResult<ArrayRef> {
+        let num_items = rows
+            .iter()
+            .map(|v| match maybe_resolve_union(v) {
+                Value::Array(a) => a.len(),
+                Value::Null => 0,
+                _ => 1,
+            })
+            .sum();
+        check_offsets::<OffsetSize>(parent_field_name, num_items)?;
         // build list offsets
         let mut cur_offset = OffsetSize::zero();
         let list_len = rows.len();
@@ -472,7 +903,9 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
             } else if let Value::Null = v {
                 // value is null, not incremented
             } else {
+                // a scalar is a single-value list, see `flatten_values`
                 cur_offset += OffsetSize::one();
+                bit_util::set_bit(&mut list_nulls, i);
             }
             offsets.push(cur_offset);
         });
@@ -485,21 +918,17 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
                 let mut bool_nulls =
                     MutableBuffer::new(num_bytes).with_bitset(num_bytes, true);
                 let mut curr_index = 0;
-                rows.iter().for_each(|v| {
-                    if let Value::Array(vs) = v {
-                        vs.iter().for_each(|value| {
-                            if let Value::Boolean(child) = value {
-                                // if valid boolean, append value
-                                if *child {
-                                    bit_util::set_bit(&mut bool_values, curr_index);
-                                }
-                            } else {
-                                // null slot
-                                bit_util::unset_bit(&mut bool_nulls, curr_index);
-                            }
-                            curr_index += 1;
-                        });
+                flatten_values(rows).into_iter().for_each(|value| {
+                    if let Value::Boolean(child) = maybe_resolve_union(value) {
+                        // if valid boolean, append value
+                        if *child {
+                            bit_util::set_bit(&mut bool_values, curr_index);
+                        }
+                    } else {
+                        // null slot
+                        bit_util::unset_bit(&mut bool_nulls, curr_index);
                     }
+                    curr_index += 1;
                 });
                 ArrayData::builder(list_field.data_type().clone())
                     .len(valid_len)
@@ -530,14 +959,14 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
                     "Temporal types are not yet supported, see ARROW-4803".to_string(),
                 ))
             }
-            DataType::Utf8 => flatten_string_values(rows)
-                .into_iter()
-                .collect::<StringArray>()
-                .into_data(),
-            DataType::LargeUtf8 => flatten_string_values(rows)
-                .into_iter()
-                .collect::<LargeStringArray>()
-                .into_data(),
+            DataType::Utf8 => {
+                let values = flatten_string_values(rows);
+                check_offsets::<i32>(parent_field_name, string_bytes(&values))?;
+                StringArray::from(values).into_data()
+            }
+            DataType::LargeUtf8 => {
+                LargeStringArray::from(flatten_string_values(rows)).into_data()
+            }
             DataType::List(field) => {
                 let child = self.build_nested_list_array::<i32>(
                     parent_field_name,
@@ -560,6 +989,7 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
                     .iter()
                     .map(|row| match maybe_resolve_union(row) {
                         Value::Array(values) => values.len(),
+                        Value::Null => 0,
                         _ => 1,
                     })
                     .sum();
@@ -588,6 +1018,9 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
                                     other => panic!("expected Record, got {other:?}"),
                                 })
                                 .collect::<Vec<&Vec<(String, Value)>>>()
+                        } else if let Value::Null = row {
+                            // a null list has no items
+                            vec![]
                         } else {
                             struct_index += 1;
                             vec![&null_struct_array]
@@ -614,7 +1047,12 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
             }
         };
         // build list
-        let list_data = ArrayData::builder(DataType::List(Arc::new(list_field.clone())))
+        let list_type = if OffsetSize::IS_LARGE {
+            DataType::LargeList(Arc::new(list_field.clone()))
+        } else {
+            DataType::List(Arc::new(list_field.clone()))
+        };
+        let list_data = ArrayData::builder(list_type)
             .len(list_len)
             .add_buffer(Buffer::from_slice_ref(&offsets))
             .add_child_data(array_data)
@@ -676,34 +1114,39 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
                         self.build_primitive_array::<UInt8Type>(rows, &field_path)
                     }
                     // TODO: this is incomplete
-                    DataType::Timestamp(unit, _) => match unit {
-                        TimeUnit::Second => self
-                            .build_primitive_array::<TimestampSecondType>(
-                                rows,
-                                &field_path,
-                            ),
-                        TimeUnit::Microsecond => self
-                            .build_primitive_array::<TimestampMicrosecondType>(
-                                rows,
-                                &field_path,
-                            ),
-                        TimeUnit::Millisecond => self
-                            .build_primitive_array::<TimestampMillisecondType>(
-                                rows,
-                                &field_path,
-                            ),
-                        TimeUnit::Nanosecond => self
-                            .build_primitive_array::<TimestampNanosecondType>(
-                                rows,
-                                &field_path,
-                            ),
-                    },
+                    DataType::Timestamp(unit, tz) => {
+                        let tz = tz.clone();
+                        match unit {
+                            TimeUnit::Second => self
+                                .build_timestamp_array::<TimestampSecondType>(
+                                    rows,
+                                    &field_path,
+                                    tz,
+                                ),
+                            TimeUnit::Microsecond => self
+                                .build_timestamp_array::<TimestampMicrosecondType>(
+                                    rows,
+                                    &field_path,
+                                    tz,
+                                ),
+                            TimeUnit::Millisecond => self
+                                .build_timestamp_array::<TimestampMillisecondType>(
+                                    rows,
+                                    &field_path,
+                                    tz,
+                                ),
+                            TimeUnit::Nanosecond => self
+                                .build_timestamp_array::<TimestampNanosecondType>(
+                                    rows,
+                                    &field_path,
+                                    tz,
+                                ),
+                        }
+                    }
                     DataType::Date64 => {
                         self.build_primitive_array::<Date64Type>(rows, &field_path)
                     }
-                    DataType::Date32 => {
-                        self.build_primitive_array::<Date32Type>(rows, &field_path)
-                    }
+                    DataType::Date32 => self.build_date_array(rows, &field_path)?,
                     DataType::Time64(unit) => match unit {
                         TimeUnit::Microsecond => self
                             .build_primitive_array::<Time64MicrosecondType>(
@@ -735,27 +1178,18 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
                             )))
                         }
                     },
-                    DataType::Utf8 | DataType::LargeUtf8 => Arc::new(
-                        rows.iter()
-                            .map(|row| {
-                                let maybe_value = self.field_lookup(&field_path, row);
-                                match maybe_value {
-                                    None => Ok(None),
-                                    Some(v) => resolve_string(v),
-                                }
-                            })
-                            .collect::<ArrowResult<StringArray>>()?,
-                    )
-                        as ArrayRef,
-                    DataType::Binary | DataType::LargeBinary => Arc::new(
-                        rows.iter()
-                            .map(|row| {
-                                let maybe_value = self.field_lookup(&field_path, row);
-                                maybe_value.and_then(resolve_bytes)
-                            })
-                            .collect::<BinaryArray>(),
-                    )
-                        as ArrayRef,
+                    DataType::Utf8 => {
+                        self.build_string_array::<i32>(rows, &field_path)?
+                    }
+                    DataType::LargeUtf8 => {
+                        self.build_string_array::<i64>(rows, &field_path)?
+                    }
+                    DataType::Binary => {
+                        self.build_binary_array::<i32>(rows, &field_path)?
+                    }
+                    DataType::LargeBinary => {
+                        self.build_binary_array::<i64>(rows, &field_path)?
+                    }
                     DataType::FixedSizeBinary(ref size) => {
                         Arc::new(FixedSizeBinaryArray::try_from_sparse_iter_with_size(
                             rows.iter().map(|row| {
@@ -765,6 +1199,16 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
                             *size,
                         )?) as ArrayRef
                     }
+                    DataType::Decimal128(precision, scale) => Arc::new(
+                        rows.iter()
+                            .map(|row| {
+                                self.field_lookup(&field_path, row)
+                                    .and_then(resolve_decimal128)
+                            })
+                            .collect::<Decimal128Array>()
+                            .with_precision_and_scale(*precision, *scale)?,
+                    )
+                        as ArrayRef,
                     DataType::List(ref list_field) => {
                         match list_field.data_type() {
                             DataType::Dictionary(ref key_ty, _) => {
@@ -787,6 +1231,23 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
                             }
                         }
                     }
+                    DataType::LargeList(ref list_field) => {
+                        let extracted_rows = rows
+                            .iter()
+                            .map(|row| {
+                                self.field_lookup(&field_path, row)
+                                    .unwrap_or(&Value::Null)
+                            })
+                            .collect::<Vec<&Value>>();
+                        self.build_nested_list_array::<i64>(
+                            &field_path,
+                            &extracted_rows,
+                            list_field,
+                        )?
+                    }
+                    DataType::Map(entries_field, _) => {
+                        self.build_map_array(rows, &field_path, field, entries_field)?
+                    }
                     DataType::Dictionary(ref key_ty, ref val_ty) => self
                         .build_string_dictionary_array(
                             rows,
@@ -794,6 +1255,35 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
                             key_ty,
                             val_ty,
                         )?,
+                    DataType::Struct(fields)
+                        if self
+                            .union_struct_paths
+                            .contains(self.writer_path(&field_path)) =>
+                    {
+                        let len = rows.len();
+                        let num_bytes = bit_util::ceil(len, 8);
+                        let mut null_buffer = MutableBuffer::from_len_zeroed(num_bytes);
+                        let union_rows = rows
+                            .iter()
+                            .enumerate()
+                            .map(|(i, row)| match self.field_lookup(&field_path, row) {
+                                Some(Value::Union(index, value)) => {
+                                    bit_util::set_bit(&mut null_buffer, i);
+                                    union_struct_row(*index, value)
+                                }
+                                _ => vec![],
+                            })
+                            .collect::<Vec<_>>();
+                        let union_rows = union_rows.iter().collect::<Vec<_>>();
+                        let arrays =
+                            self.build_struct_array(&union_rows, &field_path, fields)?;
+                        let data = ArrayDataBuilder::new(field.data_type().clone())
+                            .len(len)
+                            .null_bit_buffer(Some(null_buffer.into()))
+                            .child_data(arrays.into_iter().map(|a| a.to_data()).collect())
+                            .build()?;
+                        make_array(data)
+                    }
                     DataType::Struct(fields) => {
                         let len = rows.len();
                         let num_bytes = bit_util::ceil(len, 8);
@@ -841,6 +1331,89 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
         arrays
     }
 
+    /// Builds the Arrow `Map` array of `field` from the Avro maps, or from
+    /// the lists of entries they are decoded as, applying the duplicate key
+    /// policy
+    fn build_map_array(
+        &self,
+        rows: RecordSlice,
+        field_path: &str,
+        field: &Field,
+        entries_field: &Field,
+    ) -> ArrowResult<ArrayRef> {
+        let DataType::Struct(entry_fields) = entries_field.data_type() else {
+            return Err(SchemaError(format!(
+                "map entries must be a struct, got {}",
+                entries_field.data_type()
+            )));
+        };
+        let len = rows.len();
+        let mut null_buffer = MutableBuffer::from_len_zeroed(bit_util::ceil(len, 8));
+        let mut offsets = Vec::with_capacity(len + 1);
+        offsets.push(0i32);
+        let values = rows
+            .iter()
+            .map(|row| self.field_lookup(field_path, row).map(maybe_resolve_union))
+            .collect::<Vec<_>>();
+        let map_entries = values
+            .iter()
+            .map(|value| match value {
+                Some(Value::Map(map)) => map_entry_rows(map),
+                _ => vec![],
+            })
+            .collect::<Vec<_>>();
+        let mut entry_rows = vec![];
+        for (i, value) in values.into_iter().enumerate() {
+            if let Some(Value::Map(_)) = value {
+                bit_util::set_bit(&mut null_buffer, i);
+                entry_rows.extend(&map_entries[i]);
+            } else if let Some(Value::Array(entries)) = value {
+                bit_util::set_bit(&mut null_buffer, i);
+                let entries = entries
+                    .iter()
+                    .filter_map(|entry| match entry {
+                        Value::Record(entry) => Some(entry),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                match self.map_duplicate_key_policy {
+                    MapDuplicateKeyPolicy::LastWins => {
+                        // Keep the last entry of each key, in the order of
+                        // the last entries
+                        let mut seen = HashSet::new();
+                        let mut last_entries = entries
+                            .into_iter()
+                            .rev()
+                            .filter(|entry| seen.insert(map_entry_key(entry)))
+                            .collect::<Vec<_>>();
+                        last_entries.reverse();
+                        entry_rows.extend(last_entries);
+                    }
+                    MapDuplicateKeyPolicy::Error | MapDuplicateKeyPolicy::Keep => {
+                        entry_rows.extend(entries)
+                    }
+                }
+            }
+            offsets.push(entry_rows.len() as i32);
+        }
+        let arrays = self.build_struct_array(
+            &entry_rows,
+            &format!("{field_path}.element"),
+            entry_fields,
+        )?;
+        let entries = ArrayDataBuilder::new(entries_field.data_type().clone())
+            .len(entry_rows.len())
+            .child_data(arrays.into_iter().map(|a| a.to_data()).collect())
+            .build()?;
+        let data = ArrayDataBuilder::new(field.data_type().clone())
+            .len(len)
+            .add_buffer(Buffer::from_vec(offsets))
+            .null_bit_buffer(Some(null_buffer.into()))
+            .child_data(vec![entries])
+            .build()?;
+        Ok(make_array(data))
+    }
+
     /// Read the primitive list's values into ArrayData
     fn read_primitive_list_values<T>(&self, rows: &[&Value]) -> ArrayData
     where
@@ -874,11 +1447,153 @@ impl<R: Read> AvroArrowArrayReader<'_, R> {
     ) -> Option<&'b Value> {
         self.schema_lookup
             .get(name)
+            .or_else(|| {
+                let paths = self.case_insensitive_paths.as_ref()?;
+                self.schema_lookup
+                    .get(paths.get(&name.to_ascii_lowercase())?)
+            })
             .and_then(|i| row.get(*i))
             .map(|o| &o.1)
     }
 }
 
+/// Collects the paths of the multi-branch unions in `schema`, named like the
+/// fields of the Arrow schema
+fn union_paths(
+    parent_field_name: &str,
+    schema: &AvroSchema,
+    paths: &mut BTreeSet<String>,
+) {
+    match schema {
+        AvroSchema::Union(us) => {
+            let sub_schemas = us.variants();
+            let is_nullable = sub_schemas.len() == 2
+                && sub_schemas.iter().any(|s| matches!(s, AvroSchema::Null));
+            if is_nullable {
+                if let Some(sub_schema) =
+                    sub_schemas.iter().find(|&s| !matches!(s, AvroSchema::Null))
+                {
+                    union_paths(parent_field_name, sub_schema, paths);
+                }
+            } else {
+                paths.insert(parent_field_name.to_string());
+                for (index, sub_schema) in sub_schemas.iter().enumerate() {
+                    union_paths(
+                        &format!("{parent_field_name}.branch_{index}"),
+                        sub_schema,
+                        paths,
+                    );
+                }
+            }
+        }
+        AvroSchema::Record(RecordSchema { fields, .. }) => {
+            for field in fields {
+                union_paths(
+                    &format!("{}.{}", parent_field_name, field.name),
+                    &field.schema,
+                    paths,
+                );
+            }
+        }
+        AvroSchema::Array(schema) => {
+            union_paths(
+                &format!("{parent_field_name}.element"),
+                &schema.items,
+                paths,
+            );
+        }
+        AvroSchema::Map(schema) => {
+            union_paths(
+                &format!("{parent_field_name}.element.value"),
+                &schema.types,
+                paths,
+            );
+        }
+        _ => (),
+    }
+}
+
+/// Returns the entries of a decoded Avro map as `key` and `value` records,
+/// sorted by key as the order of the entries in the file is lost
+fn map_entry_rows(map: &HashMap<String, Value>) -> Vec<Vec<(String, Value)>> {
+    let mut entries = map
+        .iter()
+        .map(|(key, value)| {
+            vec![
+                ("key".to_string(), Value::String(key.clone())),
+                ("value".to_string(), value.clone()),
+            ]
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|a, b| map_entry_key(a).cmp(&map_entry_key(b)));
+    entries
+}
+
+/// Returns the key of an entry of a map decoded as a list of entries
+fn map_entry_key(entry: &[(String, Value)]) -> Option<&str> {
+    match entry.first() {
+        Some((_, Value::String(key))) => Some(key),
+        _ => None,
+    }
+}
+
+/// Returns the first key occurring more than once in a map of `value`, an
+/// Avro value decoded as `data_type` with its maps decoded as lists of
+/// entries
+fn duplicate_map_key<'a>(value: &'a Value, data_type: &DataType) -> Option<&'a str> {
+    match (maybe_resolve_union(value), data_type) {
+        (Value::Array(entries), DataType::Map(entries_field, _)) => {
+            let value_type = match entries_field.data_type() {
+                DataType::Struct(fields) if fields.len() == 2 => fields[1].data_type(),
+                _ => return None,
+            };
+            let mut keys = HashSet::new();
+            entries.iter().find_map(|entry| {
+                let Value::Record(entry) = entry else {
+                    return None;
+                };
+                let key = map_entry_key(entry)?;
+                if !keys.insert(key) {
+                    return Some(key);
+                }
+                entry
+                    .get(1)
+                    .and_then(|(_, value)| duplicate_map_key(value, value_type))
+            })
+        }
+        (Value::Array(items), DataType::List(item)) => items
+            .iter()
+            .find_map(|item_value| duplicate_map_key(item_value, item.data_type())),
+        (Value::Record(record), DataType::Struct(fields)) => {
+            fields.iter().find_map(|field| {
+                record
+                    .iter()
+                    .find(|(name, _)| name == field.name())
+                    .and_then(|(_, value)| duplicate_map_key(value, field.data_type()))
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Builds the fields of a union decoded as [`UnionRepresentation::Struct`]:
+/// the tag, followed by one field per branch up to the active one, of which
+/// only the field of the active branch is non-null. The fields of the later
+/// branches are missing, and read as null like those of a projected struct
+/// holding only some of the branches.
+fn union_struct_row(index: u32, value: &Value) -> Vec<(String, Value)> {
+    std::iter::once(("tag".to_string(), Value::Int(index as i32)))
+        .chain((0..=index as usize).map(|branch| {
+            let value = if branch == index as usize {
+                value.clone()
+            } else {
+                Value::Null
+            };
+            (format!("branch_{branch}"), value)
+        }))
+        .collect()
+}
+
 /// Flattens a list of Avro values, by flattening lists, and treating all other values as
 /// single-value lists.
 /// This is used to read into nested lists (list of list, list of struct) and non-dictionary lists.
@@ -890,6 +1605,9 @@ fn flatten_values<'a>(values: &[&'a Value]) -> Vec<&'a Value> {
             let v = maybe_resolve_union(row);
             if let Value::Array(values) = v {
                 values.iter().collect()
+            } else if let Value::Null = v {
+                // a null list has no items
+                vec![]
             } else {
                 // we interpret a scalar as a single-value list to minimise data loss
                 vec![v]
@@ -898,6 +1616,24 @@ fn flatten_values<'a>(values: &[&'a Value]) -> Vec<&'a Value> {
         .collect()
 }
 
+/// Returns an error if `len` values or bytes of the field at `field_path`
+/// overflow the offsets of an array of `O` offsets
+fn check_offsets<O: OffsetSizeTrait>(field_path: &str, len: usize) -> ArrowResult<()> {
+    if O::from_usize(len).is_none() {
+        return Err(SchemaError(format!(
+            "Avro field {field_path} holds {len} values or bytes in a batch, \
+             overflowing the 32-bit offsets of its Arrow array: decode it with \
+             large offsets or in smaller batches"
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the number of bytes of the strings of `values`
+fn string_bytes(values: &[Option<String>]) -> usize {
+    values.iter().flatten().map(String::len).sum()
+}
+
 /// Flattens a list into string values, dropping Value::Null in the process.
 /// This is useful for interpreting any Avro array as string, dropping nulls.
 /// See `value_as_string`.
@@ -975,6 +1711,26 @@ fn resolve_fixed(v: &Value, size: usize) -> Option<Vec<u8>> {
                 None
             }
         }
+        Value::Uuid(uuid) if size == 16 => Some(uuid.as_bytes().to_vec()),
+        _ => None,
+    }
+}
+
+/// Reads the unscaled value of an Avro decimal, stored as a big-endian two's
+/// complement integer, as an `i128`
+fn resolve_decimal128(v: &Value) -> Option<i128> {
+    let v = if let Value::Union(_, b) = v { b } else { v };
+    match v {
+        Value::Decimal(decimal) => {
+            let bytes = Vec::<u8>::try_from(decimal).ok()?;
+            if bytes.is_empty() || bytes.len() > 16 {
+                return None;
+            }
+            let sign_byte = if bytes[0] & 0x80 != 0 { 0xFF } else { 0x00 };
+            let mut be_bytes = [sign_byte; 16];
+            be_bytes[16 - bytes.len()..].copy_from_slice(&bytes);
+            Some(i128::from_be_bytes(be_bytes))
+        }
         _ => None,
     }
 }
@@ -1035,13 +1791,17 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::avro_to_arrow::{Reader, ReaderBuilder};
+    use crate::avro_to_arrow::{
+        MapDuplicateKeyPolicy, Reader, ReaderBuilder, UnionRepresentation,
+    };
+    use apache_avro::types::Value;
     use arrow::array::Array;
     use arrow::datatypes::DataType;
     use arrow::datatypes::{Field, TimeUnit};
     use datafusion_common::assert_batches_eq;
     use datafusion_common::cast::{
-        as_int32_array, as_int64_array, as_list_array, as_timestamp_microsecond_array,
+        as_boolean_array, as_int32_array, as_int64_array, as_int8_array, as_list_array,
+        as_struct_array, as_timestamp_microsecond_array,
     };
     use std::fs::File;
     use std::sync::Arc;
@@ -1609,6 +2369,78 @@ mod test {
         assert_batches_eq!(expected, &[batch]);
     }
 
+    #[test]
+    fn test_avro_null_only_fields() {
+        let schema = apache_avro::Schema::parse_str(
+            r#"
+            {
+              "type": "record",
+              "name": "r1",
+              "fields": [
+                { "name": "id", "type": "long" },
+                { "name": "nothing", "type": "null" },
+                {
+                  "name": "nested",
+                  "type": {
+                    "type": "record",
+                    "name": "r2",
+                    "fields": [
+                      { "name": "nothing", "type": "null" },
+                      { "name": "value", "type": "int" }
+                    ]
+                  }
+                },
+                { "name": "nothings", "type": { "type": "array", "items": "null" } }
+              ]
+            }"#,
+        )
+        .unwrap();
+        let mut w = apache_avro::Writer::new(&schema, vec![]);
+        for id in 0..3 {
+            w.append(Value::Record(vec![
+                ("id".to_string(), Value::Long(id)),
+                ("nothing".to_string(), Value::Null),
+                (
+                    "nested".to_string(),
+                    Value::Record(vec![
+                        ("nothing".to_string(), Value::Null),
+                        ("value".to_string(), Value::Int(id as i32 * 10)),
+                    ]),
+                ),
+                (
+                    "nothings".to_string(),
+                    Value::Array(vec![Value::Null; id as usize]),
+                ),
+            ]))
+            .unwrap();
+        }
+        let bytes = w.into_inner().unwrap();
+
+        let mut reader = ReaderBuilder::new()
+            .read_schema()
+            .with_batch_size(3)
+            .build(std::io::Cursor::new(bytes))
+            .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        let nothing = batch.schema().field_with_name("nothing").unwrap().clone();
+        assert_eq!(nothing.data_type(), &DataType::Null);
+        assert!(nothing.is_nullable());
+        let nothing = batch.column_by_name("nothing").unwrap();
+        assert_eq!(nothing.data_type(), &DataType::Null);
+        assert_eq!(nothing.len(), 3);
+
+        let expected = [
+            "+----+---------+------------------------+----------+",
+            "| id | nothing | nested                 | nothings |",
+            "+----+---------+------------------------+----------+",
+            "| 0  |         | {nothing: , value: 0}  | []       |",
+            "| 1  |         | {nothing: , value: 10} | []       |",
+            "| 2  |         | {nothing: , value: 20} | [, ]     |",
+            "+----+---------+------------------------+----------+",
+        ];
+        assert_batches_eq!(expected, &[batch]);
+    }
+
     #[test]
     fn test_avro_nullable_struct_array() {
         let schema = apache_avro::Schema::parse_str(
@@ -1696,6 +2528,330 @@ mod test {
         assert_batches_eq!(expected, &[batch]);
     }
 
+    #[test]
+    fn test_avro_list_nullability() {
+        let schema = apache_avro::Schema::parse_str(
+            r#"
+            {
+              "type": "record",
+              "name": "r1",
+              "fields": [
+                {"name": "list", "type": {"type": "array", "items": "int"}},
+                {
+                  "name": "nullable_items",
+                  "type": {"type": "array", "items": ["null", "int"]}
+                },
+                {
+                  "name": "nullable_list",
+                  "type": ["null", {"type": "array", "items": "int"}]
+                },
+                {
+                  "name": "nullable_list_items",
+                  "type": ["null", {"type": "array", "items": ["null", "int"]}]
+                },
+                {
+                  "name": "flags",
+                  "type": ["null", {"type": "array", "items": ["null", "boolean"]}]
+                }
+              ]
+            }"#,
+        )
+        .unwrap();
+        let rows = [
+            serde_json::json!({
+                "list": [1, 2],
+                "nullable_items": [1, null],
+                "nullable_list": null,
+                "nullable_list_items": [null, 3],
+                "flags": [true, null],
+            }),
+            serde_json::json!({
+                "list": [],
+                "nullable_items": [null],
+                "nullable_list": [4],
+                "nullable_list_items": null,
+                "flags": null,
+            }),
+            serde_json::json!({
+                "list": [5],
+                "nullable_items": [6],
+                "nullable_list": [7, 8],
+                "nullable_list_items": [9],
+                "flags": [false],
+            }),
+        ];
+        let mut w = apache_avro::Writer::new(&schema, vec![]);
+        for row in rows {
+            let value = apache_avro::to_value(row)
+                .unwrap()
+                .resolve(&schema)
+                .unwrap();
+            w.append(value).unwrap();
+        }
+        let bytes = w.into_inner().unwrap();
+
+        let mut reader = ReaderBuilder::new()
+            .read_schema()
+            .build(std::io::Cursor::new(bytes))
+            .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+
+        // (column, nullable list, nullable items)
+        let expected_nullability = [
+            ("list", false, false),
+            ("nullable_items", false, true),
+            ("nullable_list", true, false),
+            ("nullable_list_items", true, true),
+            ("flags", true, true),
+        ];
+        for (name, nullable, nullable_items) in expected_nullability {
+            let field = batch.schema().field_with_name(name).unwrap().clone();
+            assert_eq!(field.is_nullable(), nullable, "{name}");
+            let DataType::List(item) = field.data_type() else {
+                panic!("{name} is not a list: {field:?}");
+            };
+            assert_eq!(item.is_nullable(), nullable_items, "{name}");
+        }
+
+        // (column, list validity, items)
+        let expected_values = [
+            ("list", [true, true, true], vec![Some(1), Some(2), Some(5)]),
+            (
+                "nullable_items",
+                [true, true, true],
+                vec![Some(1), None, None, Some(6)],
+            ),
+            (
+                "nullable_list",
+                [false, true, true],
+                vec![Some(4), Some(7), Some(8)],
+            ),
+            (
+                "nullable_list_items",
+                [true, false, true],
+                vec![None, Some(3), Some(9)],
+            ),
+        ];
+        for (name, valid, items) in expected_values {
+            let list = as_list_array(batch.column_by_name(name).unwrap()).unwrap();
+            let validity = (0..list.len())
+                .map(|i| list.is_valid(i))
+                .collect::<Vec<_>>();
+            assert_eq!(validity, valid, "{name}");
+            let values = as_int32_array(list.values()).unwrap();
+            assert_eq!(values.iter().collect::<Vec<_>>(), items, "{name}");
+        }
+
+        let flags = as_list_array(batch.column_by_name("flags").unwrap()).unwrap();
+        assert!(flags.is_null(1));
+        let values = as_boolean_array(flags.values()).unwrap();
+        assert_eq!(
+            values.iter().collect::<Vec<_>>(),
+            vec![Some(true), None, Some(false)]
+        );
+    }
+
+    #[test]
+    fn test_avro_union_as_struct() {
+        let schema = apache_avro::Schema::parse_str(
+            r#"
+            {
+              "type": "record",
+              "name": "r1",
+              "fields": [
+                {
+                  "name": "u",
+                  "type": [
+                    "int",
+                    "string",
+                    {
+                      "type": "record",
+                      "name": "r2",
+                      "fields": [{ "name": "x", "type": "long" }]
+                    }
+                  ]
+                }
+              ]
+            }"#,
+        )
+        .unwrap();
+        let values = [
+            Value::Union(0, Box::new(Value::Int(7))),
+            Value::Union(1, Box::new(Value::String("seven".to_string()))),
+            Value::Union(
+                2,
+                Box::new(Value::Record(vec![("x".to_string(), Value::Long(77))])),
+            ),
+            Value::Union(0, Box::new(Value::Int(8))),
+        ];
+        let mut w = apache_avro::Writer::new(&schema, vec![]);
+        for value in values {
+            w.append(Value::Record(vec![("u".to_string(), value)]))
+                .unwrap();
+        }
+        let bytes = w.into_inner().unwrap();
+
+        let mut reader = ReaderBuilder::new()
+            .read_schema()
+            .with_union_representation(UnionRepresentation::Struct)
+            .with_batch_size(4)
+            .build(std::io::Cursor::new(bytes))
+            .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        let expected = [
+            "+-----------------------------------------------------+",
+            "| u                                                   |",
+            "+-----------------------------------------------------+",
+            "| {tag: 0, branch_0: 7, branch_1: , branch_2: }       |",
+            "| {tag: 1, branch_0: , branch_1: seven, branch_2: }   |",
+            "| {tag: 2, branch_0: , branch_1: , branch_2: {x: 77}} |",
+            "| {tag: 0, branch_0: 8, branch_1: , branch_2: }       |",
+            "+-----------------------------------------------------+",
+        ];
+        assert_batches_eq!(expected, &[batch.clone()]);
+
+        let u = as_struct_array(batch.column(0)).unwrap();
+        assert_eq!(
+            u.column(0).data_type(),
+            &DataType::Int8,
+            "the tag is an Int8"
+        );
+        let tags = as_int8_array(u.column(0)).unwrap();
+        for row in 0..batch.num_rows() {
+            let non_null_branches = (1..u.num_columns())
+                .filter(|&branch| u.column(branch).is_valid(row))
+                .map(|branch| branch as i8 - 1)
+                .collect::<Vec<_>>();
+            assert_eq!(non_null_branches, vec![tags.value(row)]);
+        }
+    }
+
+    /// Writes an Avro file with a map column `m` whose second record holds
+    /// the key `a` twice, which can't be written through a map value
+    fn duplicate_map_key_file() -> Vec<u8> {
+        use apache_avro::{to_avro_datum, Schema};
+
+        let header_schema = r#"{
+          "type": "record",
+          "name": "r1",
+          "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "m", "type": {"type": "map", "values": "long"}}
+          ]
+        }"#;
+        // Encoded like the map of the header schema
+        let entries_schema = Schema::parse_str(
+            r#"{
+              "type": "record",
+              "name": "r1",
+              "fields": [
+                {"name": "id", "type": "long"},
+                {"name": "m", "type": {"type": "array", "items": {
+                  "type": "record",
+                  "name": "entry",
+                  "fields": [
+                    {"name": "key", "type": "string"},
+                    {"name": "value", "type": "long"}
+                  ]
+                }}}
+              ]
+            }"#,
+        )
+        .unwrap();
+        let records = [
+            (0, vec![("a", 1), ("b", 2)]),
+            (1, vec![("a", 1), ("b", 2), ("a", 3)]),
+        ];
+        let num_records = records.len();
+        let mut data = vec![];
+        for (id, entries) in records {
+            let entries = entries
+                .into_iter()
+                .map(|(key, value)| {
+                    Value::Record(vec![
+                        ("key".to_string(), Value::String(key.to_string())),
+                        ("value".to_string(), Value::Long(value)),
+                    ])
+                })
+                .collect();
+            let record = Value::Record(vec![
+                ("id".to_string(), Value::Long(id)),
+                ("m".to_string(), Value::Array(entries)),
+            ]);
+            data.extend(to_avro_datum(&entries_schema, record).unwrap());
+        }
+
+        let metadata =
+            Schema::parse_str(r#"{"type": "map", "values": "bytes"}"#).unwrap();
+        let sync_marker = [7u8; 16];
+        let mut file = b"Obj\x01".to_vec();
+        file.extend(
+            to_avro_datum(
+                &metadata,
+                Value::Map(
+                    [(
+                        "avro.schema".to_string(),
+                        Value::Bytes(header_schema.as_bytes().to_vec()),
+                    )]
+                    .into(),
+                ),
+            )
+            .unwrap(),
+        );
+        file.extend(sync_marker);
+        file.extend(to_avro_datum(&Schema::Long, num_records as i64).unwrap());
+        file.extend(to_avro_datum(&Schema::Long, data.len() as i64).unwrap());
+        file.extend(data);
+        file.extend(sync_marker);
+        file
+    }
+
+    #[test]
+    fn test_avro_map_duplicate_keys() {
+        let read = |policy| {
+            ReaderBuilder::new()
+                .read_schema()
+                .with_map_duplicate_key_policy(policy)
+                .build(std::io::Cursor::new(duplicate_map_key_file()))
+                .unwrap()
+                .next()
+                .unwrap()
+        };
+
+        // The maps are decoded without their order, and sorted by key
+        let batch = read(MapDuplicateKeyPolicy::LastWins).unwrap();
+        assert!(matches!(
+            batch.schema().field(1).data_type(),
+            DataType::Map(_, _)
+        ));
+        let expected = [
+            "+----+--------------+",
+            "| id | m            |",
+            "+----+--------------+",
+            "| 0  | {a: 1, b: 2} |",
+            "| 1  | {a: 3, b: 2} |",
+            "+----+--------------+",
+        ];
+        assert_batches_eq!(expected, &[batch]);
+
+        let batch = read(MapDuplicateKeyPolicy::Keep).unwrap();
+        let expected = [
+            "+----+--------------------+",
+            "| id | m                  |",
+            "+----+--------------------+",
+            "| 0  | {a: 1, b: 2}       |",
+            "| 1  | {a: 1, b: 2, a: 3} |",
+            "+----+--------------------+",
+        ];
+        assert_batches_eq!(expected, &[batch]);
+
+        let err = read(MapDuplicateKeyPolicy::Error).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parser error: Duplicate key \"a\" in a map of column m of record 1"
+        );
+    }
+
     #[test]
     fn test_avro_iterator() {
         let reader = build_reader("alltypes_plain.avro", 5);
@@ -1719,4 +2875,18 @@ mod test {
         assert_eq!(2, num_batches);
         assert_eq!(28, sum_id);
     }
+
+    #[test]
+    fn test_check_offsets() {
+        let len = i32::MAX as usize + 1;
+        let err = super::check_offsets::<i32>("r.data", len).unwrap_err();
+        assert!(
+            err.to_string().contains(&format!(
+                "Avro field r.data holds {len} values or bytes in a batch, overflowing the 32-bit offsets"
+            )),
+            "{err}"
+        );
+        super::check_offsets::<i32>("r.data", i32::MAX as usize).unwrap();
+        super::check_offsets::<i64>("r.data", len).unwrap();
+    }
 }
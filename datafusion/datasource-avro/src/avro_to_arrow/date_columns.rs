@@ -0,0 +1,203 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Decoding of `timestamp` columns holding midnights as dates
+
+use apache_avro::types::Value;
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::error::{ArrowError, Result as ArrowResult};
+use datafusion_common::{plan_err, Result};
+
+/// How the timestamps of date columns that are not at midnight are decoded,
+/// see [`ReaderBuilder::with_date_columns`]
+///
+/// [`ReaderBuilder::with_date_columns`]: super::ReaderBuilder::with_date_columns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NonMidnightPolicy {
+    /// Fail with the timestamp and the column holding it
+    #[default]
+    Error,
+    /// Decode the timestamp as null
+    Null,
+}
+
+/// Returns `schema` with its top level `columns` decoded as `Date32`,
+/// failing if one of the columns is not a timestamp
+pub(crate) fn apply_date_columns(schema: Schema, columns: &[String]) -> Result<Schema> {
+    if columns.is_empty() {
+        return Ok(schema);
+    }
+    for column in columns {
+        let Ok(field) = schema.field_with_name(column) else {
+            return plan_err!("Avro date column {column} does not exist");
+        };
+        if !matches!(field.data_type(), DataType::Timestamp(_, _)) {
+            return plan_err!(
+                "Avro date column {column} must be a timestamp, found {}",
+                field.data_type()
+            );
+        }
+    }
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            if columns.contains(field.name()) {
+                Field::clone(field).with_data_type(DataType::Date32)
+            } else {
+                Field::clone(field)
+            }
+        })
+        .collect::<Vec<_>>();
+    Ok(Schema::new_with_metadata(fields, schema.metadata().clone()))
+}
+
+/// Returns the days since the Unix epoch of the date `value` of `column`,
+/// or of the timestamp `value` if it is at midnight. Other timestamps are
+/// handled according to `policy`.
+pub(crate) fn resolve_date(
+    value: &Value,
+    column: &str,
+    policy: NonMidnightPolicy,
+) -> ArrowResult<Option<i32>> {
+    let value = match value {
+        Value::Union(_, value) => value,
+        value => value,
+    };
+    let (timestamp, per_day, unit) = match value {
+        Value::Date(days) => return Ok(Some(*days)),
+        Value::TimestampMillis(t) => (*t, 86_400_000, "ms"),
+        Value::TimestampMicros(t) => (*t, 86_400_000_000, "µs"),
+        Value::TimestampNanos(t) => (*t, 86_400_000_000_000, "ns"),
+        _ => return Ok(None),
+    };
+    if timestamp.rem_euclid(per_day) == 0 {
+        return Ok(i32::try_from(timestamp.div_euclid(per_day)).ok());
+    }
+    match policy {
+        NonMidnightPolicy::Error => Err(ArrowError::ParseError(format!(
+            "Timestamp {timestamp}{unit} of Avro date column {column} is not at midnight"
+        ))),
+        NonMidnightPolicy::Null => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avro_to_arrow::test_util::{avro_file, record};
+    use crate::avro_to_arrow::ReaderBuilder;
+
+    use arrow::array::{Array, AsArray};
+    use arrow::datatypes::Date32Type;
+    use std::io::Cursor;
+
+    /// Returns a file whose `timestamp-micros` column `snapshot` holds the
+    /// days `days` at midnight, plus `offset` microseconds for the last one
+    fn snapshot_file(days: &[i64], offset: i64) -> Vec<u8> {
+        let schema = r#"
+            {
+              "type": "record",
+              "name": "r1",
+              "fields": [
+                { "name": "id", "type": "long" },
+                {
+                  "name": "snapshot",
+                  "type": ["null", {"type": "long", "logicalType": "timestamp-micros"}]
+                }
+              ]
+            }"#;
+        avro_file(
+            schema,
+            days.iter().enumerate().map(|(id, day)| {
+                let mut micros = day * 86_400_000_000;
+                if id == days.len() - 1 {
+                    micros += offset;
+                }
+                record(vec![
+                    ("id", Value::Long(id as i64)),
+                    (
+                        "snapshot",
+                        Value::Union(1, Box::new(Value::TimestampMicros(micros))),
+                    ),
+                ])
+            }),
+        )
+    }
+
+    fn read(data: Vec<u8>, policy: NonMidnightPolicy) -> ArrowResult<Option<i32>> {
+        let mut reader = ReaderBuilder::new()
+            .read_schema()
+            .with_date_columns(vec!["snapshot".to_string()])
+            .with_non_midnight_policy(policy)
+            .build(Cursor::new(data))
+            .unwrap();
+        assert_eq!(reader.schema().field(1).data_type(), &DataType::Date32);
+        let batch = reader.next().unwrap()?;
+        let dates = batch.column(1).as_primitive::<Date32Type>();
+        Ok(dates
+            .is_valid(dates.len() - 1)
+            .then(|| dates.value(dates.len() - 1)))
+    }
+
+    #[test]
+    fn test_midnight_timestamps_as_dates() -> ArrowResult<()> {
+        let mut reader = ReaderBuilder::new()
+            .read_schema()
+            .with_date_columns(vec!["snapshot".to_string()])
+            .build(Cursor::new(snapshot_file(&[19_700, -1, 0], 0)))
+            .unwrap();
+        let batch = reader.next().unwrap()?;
+        assert_eq!(batch.column(1).data_type(), &DataType::Date32);
+        let dates = batch.column(1).as_primitive::<Date32Type>();
+        assert_eq!(dates.values().to_vec(), vec![19_700, -1, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_non_midnight_timestamp() {
+        let err = read(
+            snapshot_file(&[19_700, 19_701], 1),
+            NonMidnightPolicy::Error,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(
+            "Timestamp 1702166400000001µs of Avro date column snapshot is not at midnight"
+        ));
+
+        let date = read(snapshot_file(&[19_700, 19_701], 1), NonMidnightPolicy::Null);
+        assert_eq!(date.unwrap(), None);
+        let date = read(
+            snapshot_file(&[19_700, 19_701], 0),
+            NonMidnightPolicy::Error,
+        );
+        assert_eq!(date.unwrap(), Some(19_701));
+    }
+
+    #[test]
+    fn test_date_column_not_timestamp() {
+        let err = ReaderBuilder::new()
+            .read_schema()
+            .with_date_columns(vec!["id".to_string()])
+            .build(Cursor::new(snapshot_file(&[0], 0)))
+            .err()
+            .unwrap();
+        assert!(err
+            .to_string()
+            .contains("Avro date column id must be a timestamp, found Int64"));
+    }
+}
@@ -0,0 +1,250 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Errors locating where the decoding of an Avro file failed
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use apache_avro::Error as AvroError;
+
+/// The error of an Avro file that fails to decode, with the location of the
+/// failure in the file.
+///
+/// Decoding errors of Avro scans are [`arrow::error::ArrowError::ExternalError`]s
+/// holding an `AvroDecodeError`, which [`Self::find`] returns from the errors
+/// of the scan.
+#[derive(Debug)]
+pub struct AvroDecodeError {
+    /// The path of the file, if known
+    pub location: Option<String>,
+    /// The offset in the file of the block whose decoding failed, or of the
+    /// bytes following the last block read if the failure occurred between
+    /// blocks. Offsets of headerless files, whose schema is in a sidecar
+    /// file, and of compressed files are offsets in the equivalent
+    /// uncompressed Avro object container file.
+    pub byte_offset: u64,
+    /// The index of that block in the file, counting from 0
+    pub block_index: usize,
+    /// The error of the Avro decoder
+    pub source: AvroError,
+}
+
+impl AvroDecodeError {
+    /// Returns the `AvroDecodeError` of `error` or of the errors it wraps
+    pub fn find<'a>(error: &'a (dyn Error + 'static)) -> Option<&'a Self> {
+        let mut error = Some(error);
+        while let Some(e) = error {
+            if let Some(decode_error) = e.downcast_ref::<Self>() {
+                return Some(decode_error);
+            }
+            error = e.source();
+        }
+        None
+    }
+}
+
+impl Display for AvroDecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed to parse avro value at byte offset {} (block {})",
+            self.byte_offset, self.block_index
+        )?;
+        if let Some(location) = &self.location {
+            write!(f, " of {location}")?;
+        }
+        write!(f, ": {}", self.source)
+    }
+}
+
+impl Error for AvroDecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Counts the bytes read from a reader, shared with the [`BlockPosition`]
+/// tracking the blocks of the file
+pub(crate) struct CountingReader<R> {
+    inner: R,
+    bytes_read: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.bytes_read.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+/// The position of the block of an Avro file being decoded.
+///
+/// The Avro decoder reads a whole block at once when it runs out of records,
+/// so a record reading bytes from the file starts a new block at the offset
+/// of the bytes read before it.
+#[derive(Debug)]
+pub(crate) struct BlockPosition {
+    bytes_read: Arc<AtomicU64>,
+    /// The bytes read when the current record started
+    record_start: u64,
+    /// The offset of the current block
+    block_offset: u64,
+    /// The number of blocks started
+    blocks: usize,
+}
+
+impl BlockPosition {
+    /// Returns the reader of `reader` counting its bytes for the position
+    pub(crate) fn new<R: Read>(reader: R) -> (CountingReader<R>, Self) {
+        let bytes_read = Arc::new(AtomicU64::new(0));
+        let reader = CountingReader {
+            inner: reader,
+            bytes_read: Arc::clone(&bytes_read),
+        };
+        let position = Self {
+            bytes_read,
+            record_start: 0,
+            block_offset: 0,
+            blocks: 0,
+        };
+        (reader, position)
+    }
+
+    /// Called before reading a record
+    pub(crate) fn start_record(&mut self) {
+        self.record_start = self.bytes_read.load(Ordering::Relaxed);
+    }
+
+    /// Called after reading a record, successfully or not
+    pub(crate) fn end_record(&mut self) {
+        if self.bytes_read.load(Ordering::Relaxed) != self.record_start {
+            self.block_offset = self.record_start;
+            self.blocks += 1;
+        }
+    }
+
+    /// Returns `source` located at the current block
+    pub(crate) fn error(
+        &self,
+        location: Option<&str>,
+        source: AvroError,
+    ) -> AvroDecodeError {
+        let (byte_offset, block_index) = match self.blocks {
+            // No block was read yet: the failure is after the header
+            0 => (self.bytes_read.load(Ordering::Relaxed), 0),
+            blocks => (self.block_offset, blocks - 1),
+        };
+        AvroDecodeError {
+            location: location.map(str::to_string),
+            byte_offset,
+            block_index,
+            source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::source::AvroSource;
+    use apache_avro::types::Value;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::Result;
+    use datafusion_datasource::file_groups::FileGroup;
+    use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
+    use datafusion_datasource::source::DataSourceExec;
+    use datafusion_datasource::PartitionedFile;
+    use datafusion_execution::object_store::ObjectStoreUrl;
+    use datafusion_execution::TaskContext;
+    use datafusion_physical_plan::ExecutionPlan;
+    use futures::StreamExt;
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+    use object_store::ObjectStore;
+
+    #[tokio::test]
+    async fn test_corrupt_block_error() -> Result<()> {
+        // Three blocks of 10 records
+        let schema = apache_avro::Schema::parse_str(
+            r#"{"type": "record", "name": "r", "fields": [{"name": "id", "type": "long"}]}"#,
+        )
+        .unwrap();
+        let mut writer = apache_avro::Writer::new(&schema, vec![]);
+        for block in 0..3 {
+            for id in block * 10..(block + 1) * 10 {
+                writer
+                    .append(Value::Record(vec![("id".to_string(), Value::Long(id))]))
+                    .unwrap();
+            }
+            writer.flush().unwrap();
+        }
+        let mut data = writer.into_inner().unwrap();
+        // The header and each block end with the sync marker of the file
+        let marker = data[data.len() - 16..].to_vec();
+        let block_ends = (16..=data.len())
+            .filter(|end| data[end - 16..*end] == marker)
+            .skip(1)
+            .collect::<Vec<_>>();
+        assert_eq!(block_ends.len(), 3);
+        // Corrupt the sync marker ending the second block
+        data[block_ends[1] - 1] ^= 0xFF;
+
+        let store = Arc::new(InMemory::new());
+        let file = PartitionedFile::new("dir/corrupt.avro", data.len() as u64);
+        store
+            .put(&Path::from("dir/corrupt.avro"), data.into())
+            .await?;
+        let task_ctx = Arc::new(TaskContext::default());
+        let url = ObjectStoreUrl::parse("memory://")?;
+        task_ctx
+            .runtime_env()
+            .register_object_store(url.as_ref(), store);
+        let file_schema =
+            Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let conf =
+            FileScanConfigBuilder::new(url, file_schema, Arc::new(AvroSource::new()))
+                .with_file_groups(vec![FileGroup::new(vec![file])])
+                .with_batch_size(Some(10))
+                .build();
+        let exec = DataSourceExec::from_data_source(conf);
+        let mut stream = exec.execute(0, task_ctx)?;
+
+        // The first block decodes
+        assert_eq!(stream.next().await.unwrap()?.num_rows(), 10);
+        let err = stream.next().await.unwrap().unwrap_err();
+        let decode_error = AvroDecodeError::find(&err).expect("an AvroDecodeError");
+        assert_eq!(decode_error.location.as_deref(), Some("dir/corrupt.avro"));
+        assert_eq!(decode_error.byte_offset, block_ends[0] as u64);
+        assert_eq!(decode_error.block_index, 1);
+        assert!(matches!(decode_error.source, AvroError::GetBlockMarker));
+        let message = err.to_string();
+        assert!(
+            message.contains(&format!(
+                "Failed to parse avro value at byte offset {} (block 1) of dir/corrupt.avro",
+                block_ends[0]
+            )),
+            "{message}"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,459 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Strict validation of the binary encoding of Avro files
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::str::FromStr;
+
+use apache_avro::schema::{Name, ResolvedSchema};
+use apache_avro::{Codec, Schema as AvroSchema};
+use datafusion_common::{exec_err, DataFusionError, Result};
+
+use crate::block_stream::SYNC_LENGTH;
+use crate::encoding::{
+    decode_varint, parse_header, zigzag_long, Varint, MAX_INT_LENGTH, MAX_LONG_LENGTH,
+};
+
+/// How strictly the binary encoding of Avro files is checked when decoding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Trust the encoding of the file, which may turn malformed encodings
+    /// into wrong values rather than errors
+    #[default]
+    Lenient,
+    /// Validate the encoding of every record before decoding the file,
+    /// failing with the byte offset of the first malformed value.
+    ///
+    /// This reads the whole file once more, see [`validate_encoding`].
+    Strict,
+}
+
+/// Validates the binary encoding of the Avro object container file read
+/// from `reader` against the schema in its header:
+///
+/// - variable length `int`s and `long`s terminate within 5 and 10 bytes and
+///   do not overflow
+/// - `float`s, `double`s and `fixed`s have all their bytes
+/// - lengths, union branches and enum symbols are in range, and strings are
+///   valid UTF-8
+/// - each block holds exactly its records and is followed by the sync
+///   marker of the file
+///
+/// Offsets in errors are file offsets, or offsets in the decompressed data
+/// of a block for compressed files.
+pub(crate) fn validate_encoding<R: Read>(mut reader: R) -> Result<()> {
+    let mut file = vec![];
+    reader.read_to_end(&mut file)?;
+    let Some(header) = parse_header(&file)? else {
+        return exec_err!("Unexpected end of Avro file header");
+    };
+    let mut input = Input::new(&file, Location::File);
+    input.position = header.len;
+
+    let Some(schema) = header.get("avro.schema") else {
+        return exec_err!("Avro file header has no schema");
+    };
+    let schema = AvroSchema::parse_reader(&mut &schema[..])?;
+    let resolved = ResolvedSchema::try_from(&schema)?;
+    let codec = match header.get("avro.codec") {
+        Some(codec) => {
+            let codec = std::str::from_utf8(codec)
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+            Codec::from_str(codec).map_err(|_| {
+                DataFusionError::Execution(format!("Unsupported Avro codec {codec}"))
+            })?
+        }
+        None => Codec::Null,
+    };
+
+    while !input.is_empty() {
+        let block_offset = input.position;
+        let record_count = input.read_length()?;
+        let size = input.read_length()?;
+        let data_offset = input.position;
+        let data = input.take(size, "block")?;
+        if input.take(SYNC_LENGTH, "sync marker")? != header.sync {
+            return exec_err!(
+                "Invalid sync marker after Avro block at offset {block_offset}"
+            );
+        }
+
+        let decompressed;
+        let mut block = match codec {
+            Codec::Null => Input::new(
+                data,
+                Location::Block {
+                    offset: data_offset,
+                },
+            ),
+            codec => {
+                let mut data = data.to_vec();
+                codec.decompress(&mut data)?;
+                decompressed = data;
+                Input::new(&decompressed, Location::Decompressed { block_offset })
+            }
+        };
+        for _ in 0..record_count {
+            block.validate(&schema, resolved.get_names())?;
+        }
+        if !block.is_empty() {
+            return exec_err!(
+                "Invalid Avro encoding at {}: {} bytes follow the {record_count} \
+                 records of the block",
+                block.location(),
+                block.buf.len() - block.position
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Where the bytes of an [`Input`] are in the file
+#[derive(Debug, Clone, Copy)]
+enum Location {
+    /// The bytes of the whole file
+    File,
+    /// The uncompressed data of a block starting at file offset `offset`
+    Block { offset: usize },
+    /// The decompressed data of the block at file offset `block_offset`
+    Decompressed { block_offset: usize },
+}
+
+/// Bytes being validated, along with the position of the next value
+struct Input<'a> {
+    buf: &'a [u8],
+    position: usize,
+    location: Location,
+}
+
+impl<'a> Input<'a> {
+    fn new(buf: &'a [u8], location: Location) -> Self {
+        Self {
+            buf,
+            position: 0,
+            location,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.position == self.buf.len()
+    }
+
+    /// Describes the location of the byte at `position`
+    fn location_of(&self, position: usize) -> String {
+        match self.location {
+            Location::File => format!("offset {position}"),
+            Location::Block { offset } => format!("offset {}", offset + position),
+            Location::Decompressed { block_offset } => format!(
+                "offset {position} of the decompressed block at offset {block_offset}"
+            ),
+        }
+    }
+
+    /// Describes the location of the next value
+    fn location(&self) -> String {
+        self.location_of(self.position)
+    }
+
+    /// Consumes the next `len` bytes, which encode a `what`
+    fn take(&mut self, len: usize, what: &str) -> Result<&'a [u8]> {
+        let remaining = self.buf.len() - self.position;
+        if remaining < len {
+            return exec_err!(
+                "Invalid Avro encoding at {}: {what} needs {len} bytes, \
+                 only {remaining} remain",
+                self.location()
+            );
+        }
+        let bytes = &self.buf[self.position..self.position + len];
+        self.position += len;
+        Ok(bytes)
+    }
+
+    /// Consumes a zigzag encoded variable length integer of at most
+    /// `max_length` bytes, returning it before zigzag decoding
+    fn read_varint(&mut self, max_length: usize) -> Result<u64> {
+        let start = self.position;
+        match decode_varint(&self.buf[start..], max_length) {
+            Varint::Value(value, len) => {
+                self.position += len;
+                Ok(value)
+            }
+            Varint::Incomplete => exec_err!(
+                "Invalid Avro encoding at {}: varint is not terminated before \
+                 the end of the data",
+                self.location_of(start)
+            ),
+            Varint::Unterminated => exec_err!(
+                "Invalid Avro encoding at {}: varint is not terminated within \
+                 {max_length} bytes",
+                self.location_of(start)
+            ),
+            Varint::Overflow => exec_err!(
+                "Invalid Avro encoding at {}: varint overflows 64 bits",
+                self.location_of(start)
+            ),
+        }
+    }
+
+    fn read_long(&mut self) -> Result<i64> {
+        Ok(zigzag_long(self.read_varint(MAX_LONG_LENGTH)?))
+    }
+
+    fn read_int(&mut self) -> Result<i32> {
+        let start = self.position;
+        let value = self.read_varint(MAX_INT_LENGTH)?;
+        let Ok(value) = u32::try_from(value) else {
+            return exec_err!(
+                "Invalid Avro encoding at {}: int overflows 32 bits",
+                self.location_of(start)
+            );
+        };
+        Ok(((value >> 1) as i32) ^ -((value & 1) as i32))
+    }
+
+    /// Consumes a `long` that must not be negative
+    fn read_length(&mut self) -> Result<usize> {
+        let start = self.position;
+        let length = self.read_long()?;
+        usize::try_from(length).or_else(|_| {
+            exec_err!(
+                "Invalid Avro encoding at {}: negative length {length}",
+                self.location_of(start)
+            )
+        })
+    }
+
+    /// Consumes a length prefixed sequence of bytes
+    fn read_bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.read_length()?;
+        self.take(len, "bytes")
+    }
+
+    fn read_string(&mut self) -> Result<()> {
+        let start = self.position;
+        let bytes = self.read_bytes()?;
+        if std::str::from_utf8(bytes).is_err() {
+            return exec_err!(
+                "Invalid Avro encoding at {}: string is not valid UTF-8",
+                self.location_of(start)
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks that `index`, encoded at `start`, is in `0..len`
+    fn check_index(
+        &self,
+        start: usize,
+        index: i64,
+        len: usize,
+        what: &str,
+    ) -> Result<usize> {
+        match usize::try_from(index) {
+            Ok(index) if index < len => Ok(index),
+            _ => exec_err!(
+                "Invalid Avro encoding at {}: {what} {index} out of range, \
+                 expected fewer than {len}",
+                self.location_of(start)
+            ),
+        }
+    }
+
+    /// Consumes the blocks of an array or map, calling `item` for each item
+    fn read_blocks(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<()>,
+    ) -> Result<()> {
+        loop {
+            let count = self.read_long()?;
+            if count == 0 {
+                return Ok(());
+            }
+            if count < 0 {
+                // A negative count is followed by the size of the items
+                self.read_length()?;
+            }
+            for _ in 0..count.unsigned_abs() {
+                item(self)?;
+            }
+        }
+    }
+
+    /// Consumes a value of `schema`
+    fn validate(
+        &mut self,
+        schema: &AvroSchema,
+        names: &HashMap<Name, &AvroSchema>,
+    ) -> Result<()> {
+        match schema {
+            AvroSchema::Null => {}
+            AvroSchema::Boolean => {
+                let start = self.position;
+                let byte = self.take(1, "boolean")?[0];
+                if byte > 1 {
+                    return exec_err!(
+                        "Invalid Avro encoding at {}: boolean byte {byte}",
+                        self.location_of(start)
+                    );
+                }
+            }
+            AvroSchema::Int | AvroSchema::Date | AvroSchema::TimeMillis => {
+                self.read_int()?;
+            }
+            AvroSchema::Long
+            | AvroSchema::TimeMicros
+            | AvroSchema::TimestampMillis
+            | AvroSchema::TimestampMicros
+            | AvroSchema::TimestampNanos
+            | AvroSchema::LocalTimestampMillis
+            | AvroSchema::LocalTimestampMicros
+            | AvroSchema::LocalTimestampNanos => {
+                self.read_long()?;
+            }
+            AvroSchema::Float => {
+                self.take(4, "float")?;
+            }
+            AvroSchema::Double => {
+                self.take(8, "double")?;
+            }
+            AvroSchema::Bytes | AvroSchema::BigDecimal => {
+                self.read_bytes()?;
+            }
+            AvroSchema::String | AvroSchema::Uuid => self.read_string()?,
+            AvroSchema::Fixed(fixed) => {
+                self.take(fixed.size, "fixed")?;
+            }
+            AvroSchema::Duration => {
+                self.take(12, "duration")?;
+            }
+            AvroSchema::Decimal(decimal) => self.validate(&decimal.inner, names)?,
+            AvroSchema::Enum(e) => {
+                let start = self.position;
+                let index = self.read_int()?;
+                self.check_index(start, index.into(), e.symbols.len(), "enum symbol")?;
+            }
+            AvroSchema::Array(array) => {
+                self.read_blocks(|input| input.validate(&array.items, names))?
+            }
+            AvroSchema::Map(map) => self.read_blocks(|input| {
+                input.read_string()?;
+                input.validate(&map.types, names)
+            })?,
+            AvroSchema::Union(union) => {
+                let variants = union.variants();
+                let start = self.position;
+                let index = self.read_long()?;
+                let index =
+                    self.check_index(start, index, variants.len(), "union branch")?;
+                self.validate(&variants[index], names)?;
+            }
+            AvroSchema::Record(record) => {
+                for field in &record.fields {
+                    self.validate(&field.schema, names)?;
+                }
+            }
+            AvroSchema::Ref { name } => match names.get(name) {
+                Some(schema) => self.validate(schema, names)?,
+                None => return exec_err!("Unknown Avro schema {name}"),
+            },
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avro_to_arrow::test_util::{avro_file, record};
+    use crate::avro_to_arrow::ReaderBuilder;
+
+    use apache_avro::types::Value;
+    use arrow::array::AsArray;
+    use arrow::datatypes::Int64Type;
+    use std::io::Cursor;
+
+    /// Writes a file of a single block with the longs `ids`
+    fn ids_file(ids: &[i64]) -> Vec<u8> {
+        let schema = r#"{"type": "record", "name": "r", "fields": [
+            {"name": "id", "type": "long"}
+        ]}"#;
+        avro_file(
+            schema,
+            ids.iter().map(|id| record(vec![("id", Value::Long(*id))])),
+        )
+    }
+
+    fn read(file: Vec<u8>, decode_mode: DecodeMode) -> Result<Vec<i64>> {
+        let reader = ReaderBuilder::new()
+            .read_schema()
+            .with_decode_mode(decode_mode)
+            .build(Cursor::new(file))?;
+        let mut ids = vec![];
+        for batch in reader {
+            ids.extend(batch?.column(0).as_primitive::<Int64Type>().values());
+        }
+        Ok(ids)
+    }
+
+    #[test]
+    fn test_strict_valid_file() -> Result<()> {
+        let ids = vec![0, -1, i64::MAX, i64::MIN];
+        assert_eq!(read(ids_file(&ids), DecodeMode::Strict)?, ids);
+        Ok(())
+    }
+
+    #[test]
+    fn test_strict_unterminated_varint() {
+        // The block holds the single byte 0x02 encoding 1, followed by the
+        // sync marker
+        let mut file = ids_file(&[1]);
+        let offset = file.len() - SYNC_LENGTH - 1;
+        assert_eq!(file[offset], 0x02);
+        file[offset] = 0x82;
+
+        let err = read(file, DecodeMode::Strict).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            format!(
+                "Execution error: Invalid Avro encoding at offset {offset}: \
+                 varint is not terminated before the end of the data"
+            )
+        );
+    }
+
+    #[test]
+    fn test_strict_overlong_varint() {
+        let mut input = Input::new(&[0xff; 11], Location::File);
+        let err = input.read_long().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("offset 0: varint overflows 64 bits"));
+
+        let mut input = Input::new(&[0x80; 6], Location::File);
+        let err = input.read_int().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("offset 0: varint is not terminated within 5 bytes"));
+
+        let mut input = Input::new(&[0xfe, 0xff, 0xff, 0xff, 0x1f], Location::File);
+        let err = input.read_int().unwrap_err();
+        assert!(err.to_string().contains("offset 0: int overflows 32 bits"));
+    }
+}
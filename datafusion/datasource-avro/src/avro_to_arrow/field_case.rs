@@ -0,0 +1,124 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Resolution of columns to the fields of an Avro file ignoring case
+
+use arrow::datatypes::{Field, Schema};
+use datafusion_common::{plan_err, Result};
+
+/// Returns `schema` with each top level field renamed to the one of `names`
+/// it matches ignoring case, so that it is resolved by name like a field of
+/// the exact same name.
+///
+/// Fails if a name matches several fields that are only distinct by case,
+/// or a field matches several names.
+pub(crate) fn with_field_case<'a>(
+    schema: Schema,
+    names: impl IntoIterator<Item = &'a str>,
+) -> Result<Schema> {
+    let mut renamed: Vec<Option<&str>> = vec![None; schema.fields().len()];
+    for name in names {
+        let mut matches = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.name().eq_ignore_ascii_case(name));
+        let Some((index, field)) = matches.next() else {
+            continue;
+        };
+        if let Some((_, other)) = matches.next() {
+            return plan_err!(
+                "Column {name} is ambiguous, it matches the Avro fields {} and {} ignoring case",
+                field.name(),
+                other.name()
+            );
+        }
+        match renamed[index] {
+            Some(other) if other != name => {
+                return plan_err!(
+                    "Avro field {} is ambiguous, it matches the columns {other} and {name} ignoring case",
+                    field.name()
+                );
+            }
+            _ => renamed[index] = Some(name),
+        }
+    }
+    let fields = schema
+        .fields()
+        .iter()
+        .zip(renamed)
+        .map(|(field, name)| match name {
+            Some(name) => Field::clone(field).with_name(name),
+            None => Field::clone(field),
+        })
+        .collect::<Vec<_>>();
+    Ok(Schema::new_with_metadata(fields, schema.metadata().clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::datatypes::DataType;
+
+    fn schema(names: &[&str]) -> Schema {
+        Schema::new(
+            names
+                .iter()
+                .map(|name| Field::new(*name, DataType::Int64, true))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn names(schema: &Schema) -> Vec<&str> {
+        schema
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn renames_fields_to_column_case() -> Result<()> {
+        let resolved =
+            with_field_case(schema(&["userId", "Name", "other"]), ["userid", "name"])?;
+        assert_eq!(names(&resolved), ["userid", "name", "other"]);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_case_only_duplicates() {
+        let err = with_field_case(schema(&["userId", "userid"]), ["USERID"]).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Column USERID is ambiguous, it matches the Avro fields userId and userid ignoring case"),
+            "{err}"
+        );
+
+        // Fields only distinct by case are fine as long as no column matches them
+        let resolved = with_field_case(schema(&["userId", "userid"]), ["other"]).unwrap();
+        assert_eq!(names(&resolved), ["userId", "userid"]);
+
+        let err = with_field_case(schema(&["userId"]), ["userid", "USERID"]).unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "Avro field userId is ambiguous, it matches the columns userid and USERID ignoring case"
+            ),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Decoding of Avro maps as lists of entries, keeping their order and
+//! duplicate keys
+
+use std::io::{Chain, Cursor, Read};
+
+use datafusion_common::{exec_err, DataFusionError, Result};
+use serde_json::{json, Value as JsonValue};
+
+use crate::block_stream::MAGIC;
+use crate::encoding::{parse_header, read_header};
+
+/// How keys occurring more than once in an Avro map are handled when
+/// decoding the map as an Arrow `Map`
+///
+/// Avro maps should not have duplicate keys, but malformed data sometimes
+/// does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MapDuplicateKeyPolicy {
+    /// Keep the value of the last occurrence of the key, with the entries of
+    /// the map sorted by key rather than in the order they were written
+    #[default]
+    LastWins,
+    /// Fail with the key and the index of the record in the file
+    Error,
+    /// Keep one entry per occurrence of the key
+    Keep,
+}
+
+/// Prefix of the names of the records holding the entries of a map
+const ENTRIES_RECORD_NAME: &str = "datafusion_map_entries";
+
+/// Returns the Avro object container file read from `reader`, with the
+/// maps of the schema in its header replaced by arrays of `key` and `value`
+/// records. The file is returned unchanged if its schema has no map.
+///
+/// Maps and arrays of records share their binary encoding, so the records
+/// decode the entries of each map in the order they were written, including
+/// the entries of duplicate keys that decoding a map would drop.
+pub(crate) fn with_map_entries<R: Read>(
+    mut reader: R,
+) -> Result<Chain<Cursor<Vec<u8>>, R>> {
+    let mut buf = vec![];
+    let len = read_header(&mut reader, &mut buf)?;
+    let Some(file_header) = parse_header(&buf)? else {
+        return exec_err!("Unexpected end of Avro file header");
+    };
+    let Some(schema) = file_header.get("avro.schema") else {
+        return exec_err!("Avro file header has no schema");
+    };
+    let schema = serde_json::from_slice(schema)
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+    let mut num_maps = 0;
+    let schema = maps_as_entries(schema, &mut num_maps);
+    if num_maps == 0 {
+        return Ok(Cursor::new(buf).chain(reader));
+    }
+    let schema = serde_json::to_vec(&schema)
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+    let mut header = MAGIC.to_vec();
+    write_long(&mut header, file_header.metadata.len() as i64);
+    for &(key, value) in &file_header.metadata {
+        write_bytes(&mut header, key);
+        if key == b"avro.schema" {
+            write_bytes(&mut header, &schema);
+        } else {
+            write_bytes(&mut header, value);
+        }
+    }
+    write_long(&mut header, 0);
+    header.extend_from_slice(&file_header.sync);
+    // Bytes following the header that were read along with it
+    header.extend_from_slice(&buf[len..]);
+    Ok(Cursor::new(header).chain(reader))
+}
+
+/// Replaces the maps of the JSON Avro `schema` by arrays of records with a
+/// `key` and a `value` field, naming the records after `count`
+fn maps_as_entries(schema: JsonValue, count: &mut usize) -> JsonValue {
+    match schema {
+        JsonValue::Array(branches) => JsonValue::Array(
+            branches
+                .into_iter()
+                .map(|branch| maps_as_entries(branch, count))
+                .collect(),
+        ),
+        JsonValue::Object(mut object) => {
+            match object.get("type").and_then(JsonValue::as_str) {
+                Some("map") => {
+                    let values = object.remove("values").unwrap_or(JsonValue::Null);
+                    let name = format!("{ENTRIES_RECORD_NAME}_{count}");
+                    *count += 1;
+                    return json!({
+                        "type": "array",
+                        "items": {
+                            "type": "record",
+                            "name": name,
+                            "fields": [
+                                {"name": "key", "type": "string"},
+                                {"name": "value", "type": maps_as_entries(values, count)},
+                            ],
+                        },
+                    });
+                }
+                Some("array") => {
+                    if let Some(items) = object.remove("items") {
+                        object.insert("items".to_string(), maps_as_entries(items, count));
+                    }
+                }
+                Some("record") | Some("error") => {
+                    if let Some(JsonValue::Array(fields)) = object.get_mut("fields") {
+                        for field in fields {
+                            if let Some(field_type) =
+                                field.as_object_mut().and_then(|f| f.remove("type"))
+                            {
+                                field["type"] = maps_as_entries(field_type, count);
+                            }
+                        }
+                    }
+                }
+                Some(_) => {}
+                // The type of the schema is a schema itself
+                None => {
+                    if let Some(inner) = object.remove("type") {
+                        object.insert("type".to_string(), maps_as_entries(inner, count));
+                    }
+                }
+            }
+            JsonValue::Object(object)
+        }
+        schema => schema,
+    }
+}
+
+fn write_long(buf: &mut Vec<u8>, value: i64) {
+    let mut value = ((value << 1) ^ (value >> 63)) as u64;
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn write_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_long(buf, bytes.len() as i64);
+    buf.extend_from_slice(bytes);
+}
@@ -20,20 +20,99 @@
 //! [Avro]: https://avro.apache.org/docs/1.2.0/
 
 mod arrow_array_reader;
+mod date_columns;
+mod decode_error;
+mod decode_mode;
+mod field_case;
+mod map_entries;
 mod reader;
 mod schema;
+mod schema_interner;
+mod string_encoding;
+#[cfg(test)]
+mod test_util;
+mod timestamp_columns;
 
 use arrow::datatypes::Schema;
-pub use reader::{Reader, ReaderBuilder};
+pub(crate) use date_columns::apply_date_columns;
+pub use date_columns::NonMidnightPolicy;
+pub use decode_error::AvroDecodeError;
+pub(crate) use decode_mode::validate_encoding;
+pub use decode_mode::DecodeMode;
+pub(crate) use field_case::with_field_case;
+pub(crate) use map_entries::with_map_entries;
+pub use map_entries::MapDuplicateKeyPolicy;
+pub use reader::{Reader, ReaderBuilder, ROW_INDEX_COLUMN};
 
-pub use schema::to_arrow_schema;
+pub use schema::{
+    avro_sort_order, merge_schemas_widening, to_arrow_schema, NameCollisionPolicy,
+    UnionRepresentation, AVRO_ENUM_DEFAULT_METADATA_KEY, AVRO_ORDER_METADATA_KEY,
+    AVRO_ORIGINAL_NAME_METADATA_KEY, AVRO_SYMBOLS_METADATA_KEY, UUID_EXTENSION_NAME,
+};
+pub(crate) use schema::{with_large_offsets, without_extension_types};
+pub(crate) use schema_interner::SchemaInterner;
 use std::io::Read;
+pub(crate) use string_encoding::StringCardinalities;
+pub use string_encoding::StringEncoding;
+pub(crate) use timestamp_columns::apply_timestamp_columns;
+pub use timestamp_columns::TimestampPrecision;
 
 /// Read Avro schema given a reader
 pub fn read_avro_schema_from_reader<R: Read>(
     reader: &mut R,
+) -> datafusion_common::Result<Schema> {
+    read_avro_schema_with_union_representation(reader, UnionRepresentation::default())
+}
+
+/// Read Avro schema given a reader, representing multi-branch unions as
+/// specified by `union_representation`
+pub fn read_avro_schema_with_union_representation<R: Read>(
+    reader: &mut R,
+    union_representation: UnionRepresentation,
+) -> datafusion_common::Result<Schema> {
+    read_avro_schema_with_options(
+        reader,
+        union_representation,
+        NameCollisionPolicy::default(),
+    )
+}
+
+/// Read Avro schema given a reader, representing multi-branch unions as
+/// specified by `union_representation` and handling fields with the same
+/// name as specified by `name_collision_policy`
+pub fn read_avro_schema_with_options<R: Read>(
+    reader: &mut R,
+    union_representation: UnionRepresentation,
+    name_collision_policy: NameCollisionPolicy,
 ) -> datafusion_common::Result<Schema> {
     let avro_reader = apache_avro::Reader::new(reader)?;
-    let schema = avro_reader.writer_schema();
-    to_arrow_schema(schema)
+    avro_schema_to_arrow(
+        avro_reader.writer_schema(),
+        union_representation,
+        name_collision_policy,
+        false,
+    )
+}
+
+/// Converts the Avro `schema` of a file to the Arrow schema its records are
+/// decoded as, representing multi-branch unions as specified by
+/// `union_representation`, handling fields with the same name as specified
+/// by `name_collision_policy`, and declaring the symbols of the enum fields
+/// if `enum_symbols` is set
+pub(crate) fn avro_schema_to_arrow(
+    schema: &apache_avro::Schema,
+    union_representation: UnionRepresentation,
+    name_collision_policy: NameCollisionPolicy,
+    enum_symbols: bool,
+) -> datafusion_common::Result<Schema> {
+    let schema = if enum_symbols {
+        schema::with_enum_symbols(to_arrow_schema(schema)?, schema)
+    } else {
+        to_arrow_schema(schema)?
+    };
+    let schema = match union_representation {
+        UnionRepresentation::Union => schema,
+        UnionRepresentation::Struct => schema::unions_as_structs(schema),
+    };
+    schema::resolve_name_collisions(schema, name_collision_policy)
 }
@@ -16,13 +16,28 @@
 // under the License.
 
 use super::arrow_array_reader::AvroArrowArrayReader;
-use arrow::datatypes::{Fields, SchemaRef};
+use super::{
+    apply_date_columns, apply_timestamp_columns, validate_encoding, with_field_case,
+    with_large_offsets, without_extension_types, DecodeMode, MapDuplicateKeyPolicy,
+    NameCollisionPolicy, NonMidnightPolicy, StringCardinalities, StringEncoding,
+    TimestampPrecision, UnionRepresentation,
+};
+use crate::decode_buffer_pool::DecodeBufferPool;
+use crate::row_filter::AvroRowFilter;
+use crate::sample::RecordSample;
+use arrow::datatypes::{DataType, Field, Fields, Schema, SchemaRef};
 use arrow::error::Result as ArrowResult;
 use arrow::record_batch::RecordBatch;
 use datafusion_common::Result;
+use object_store::path::Path;
+use std::collections::BTreeMap;
 use std::io::{Read, Seek};
 use std::sync::Arc;
 
+/// Name of the column numbering the records of a file from 0, appended to
+/// the schema by [`ReaderBuilder::with_row_index`]
+pub const ROW_INDEX_COLUMN: &str = "__row_index__";
+
 /// Avro file reader builder
 #[derive(Debug)]
 pub struct ReaderBuilder {
@@ -36,6 +51,35 @@ pub struct ReaderBuilder {
     batch_size: usize,
     /// Optional projection for which columns to load (zero-based column indices)
     projection: Option<Vec<String>>,
+    /// How multi-branch unions are decoded
+    union_representation: UnionRepresentation,
+    /// How fields with the same name are handled when inferring the schema
+    name_collision_policy: NameCollisionPolicy,
+    /// How top level string columns are decoded, by column name
+    string_encodings: BTreeMap<String, StringEncoding>,
+    /// Top level `long` columns decoded as timestamps
+    timestamp_columns: Vec<String>,
+    /// Precision of the timestamps of `timestamp_columns`
+    timestamp_precision: TimestampPrecision,
+    /// Time zone of the timestamps of `timestamp_columns`
+    timestamp_timezone: Option<Arc<str>>,
+    /// Top level timestamp columns decoded as dates
+    date_columns: Vec<String>,
+    /// How the timestamps of `date_columns` not at midnight are decoded
+    non_midnight_policy: NonMidnightPolicy,
+    /// How strictly the binary encoding of the file is checked
+    decode_mode: DecodeMode,
+    /// How keys occurring more than once in a map are handled
+    map_duplicate_key_policy: MapDuplicateKeyPolicy,
+    /// Whether the [`ROW_INDEX_COLUMN`] is appended to the schema
+    row_index: bool,
+    /// Whether the inferred schema declares Arrow extension types
+    extension_types: bool,
+    /// Whether the inferred schema uses the Arrow types with 64-bit offsets
+    large_offsets: bool,
+    /// Whether the projection and the fields of the schema are matched to
+    /// the fields of the file ignoring case
+    case_insensitive_field_resolution: bool,
 }
 
 impl Default for ReaderBuilder {
@@ -44,6 +88,20 @@ impl Default for ReaderBuilder {
             schema: None,
             batch_size: 1024,
             projection: None,
+            union_representation: UnionRepresentation::default(),
+            name_collision_policy: NameCollisionPolicy::default(),
+            string_encodings: BTreeMap::new(),
+            timestamp_columns: vec![],
+            timestamp_precision: TimestampPrecision::default(),
+            timestamp_timezone: None,
+            date_columns: vec![],
+            non_midnight_policy: NonMidnightPolicy::default(),
+            decode_mode: DecodeMode::default(),
+            map_duplicate_key_policy: MapDuplicateKeyPolicy::default(),
+            row_index: false,
+            extension_types: true,
+            large_offsets: false,
+            case_insensitive_field_resolution: false,
         }
     }
 }
@@ -104,6 +162,158 @@ impl ReaderBuilder {
         self
     }
 
+    /// Set how multi-branch unions are decoded
+    /// - defaults to [`UnionRepresentation::Union`]
+    pub fn with_union_representation(
+        mut self,
+        union_representation: UnionRepresentation,
+    ) -> Self {
+        self.union_representation = union_representation;
+        self
+    }
+
+    /// Set how fields with the same name are handled when inferring the
+    /// schema
+    /// - defaults to [`NameCollisionPolicy::Error`]
+    pub fn with_name_collision_policy(
+        mut self,
+        name_collision_policy: NameCollisionPolicy,
+    ) -> Self {
+        self.name_collision_policy = name_collision_policy;
+        self
+    }
+
+    /// Set how the top level string column `column` is decoded
+    /// - defaults to [`StringEncoding::Plain`]
+    ///
+    /// A [`StringEncoding::Auto`] encoding reads the whole file once more
+    /// to count the distinct values of the column.
+    pub fn with_string_encoding(
+        mut self,
+        column: impl Into<String>,
+        encoding: StringEncoding,
+    ) -> Self {
+        self.string_encodings.insert(column.into(), encoding);
+        self
+    }
+
+    /// Decode the top level `long` columns named in `columns` as timestamps
+    /// of `precision`, for files storing epoch timestamps without a logical
+    /// type
+    /// - defaults to no column
+    ///
+    /// Building the reader fails if one of the columns is not a `long`.
+    pub fn with_timestamp_columns(
+        mut self,
+        columns: Vec<String>,
+        precision: TimestampPrecision,
+    ) -> Self {
+        self.timestamp_columns = columns;
+        self.timestamp_precision = precision;
+        self
+    }
+
+    /// Set the time zone of the timestamps of the columns set with
+    /// [`Self::with_timestamp_columns`]
+    /// - defaults to no time zone
+    pub fn with_timestamp_timezone(mut self, timezone: impl Into<Arc<str>>) -> Self {
+        self.timestamp_timezone = Some(timezone.into());
+        self
+    }
+
+    /// Decode the top level timestamp columns named in `columns` as `Date32`,
+    /// for files storing dates as timestamps at midnight
+    /// - defaults to no column
+    ///
+    /// Building the reader fails if one of the columns is not a timestamp.
+    /// Timestamps that are not at midnight are handled according to
+    /// [`Self::with_non_midnight_policy`].
+    pub fn with_date_columns(mut self, columns: Vec<String>) -> Self {
+        self.date_columns = columns;
+        self
+    }
+
+    /// Set how the timestamps of `Date32` columns, such as the columns set
+    /// with [`Self::with_date_columns`], that are not at midnight are decoded
+    /// - defaults to [`NonMidnightPolicy::Error`]
+    pub fn with_non_midnight_policy(
+        mut self,
+        non_midnight_policy: NonMidnightPolicy,
+    ) -> Self {
+        self.non_midnight_policy = non_midnight_policy;
+        self
+    }
+
+    /// Set how strictly the binary encoding of the file is checked
+    /// - defaults to [`DecodeMode::Lenient`]
+    pub fn with_decode_mode(mut self, decode_mode: DecodeMode) -> Self {
+        self.decode_mode = decode_mode;
+        self
+    }
+
+    /// Set how keys occurring more than once in a map are handled
+    /// - defaults to [`MapDuplicateKeyPolicy::LastWins`]
+    ///
+    /// With [`MapDuplicateKeyPolicy::Error`], reading fails with the key and
+    /// the index of the record in the file.
+    pub fn with_map_duplicate_key_policy(
+        mut self,
+        map_duplicate_key_policy: MapDuplicateKeyPolicy,
+    ) -> Self {
+        self.map_duplicate_key_policy = map_duplicate_key_policy;
+        self
+    }
+
+    /// Append the non-null `Int64` [`ROW_INDEX_COLUMN`] to the schema,
+    /// holding the index of each record in the file
+    /// - defaults to `false`
+    ///
+    /// The index keeps counting across batches, and counts the records
+    /// skipped by a row filter. A projection must name the column to read it.
+    pub fn with_row_index(mut self, row_index: bool) -> Self {
+        self.row_index = row_index;
+        self
+    }
+
+    /// Declare the Arrow canonical extension types of Avro logical types in
+    /// the inferred schema, such as [`UUID_EXTENSION_NAME`] for `uuid`
+    /// - defaults to `true`
+    ///
+    /// Without extension types, the fields only have their storage type.
+    ///
+    /// [`UUID_EXTENSION_NAME`]: crate::avro_to_arrow::UUID_EXTENSION_NAME
+    pub fn with_extension_types(mut self, extension_types: bool) -> Self {
+        self.extension_types = extension_types;
+        self
+    }
+
+    /// Infer the Avro strings, bytes and arrays as `LargeUtf8`,
+    /// `LargeBinary` and `LargeList`, whose 64-bit offsets address the values
+    /// of batches holding more than 2GB of them in a single column
+    /// - defaults to `false`
+    ///
+    /// Without large offsets, reading such a batch fails.
+    pub fn with_large_offsets(mut self, large_offsets: bool) -> Self {
+        self.large_offsets = large_offsets;
+        self
+    }
+
+    /// Match the columns of the projection and the fields of the schema to
+    /// the fields of the file ignoring case, for files whose field casing
+    /// changed between versions of their schema (`userId` and `userid`)
+    /// - defaults to `false`
+    ///
+    /// Projected columns are named as in the projection. Building the reader
+    /// fails if a column of the projection matches several fields of the
+    /// file that are only distinct by case.
+    pub fn with_case_insensitive_field_resolution(
+        mut self,
+        case_insensitive_field_resolution: bool,
+    ) -> Self {
+        self.case_insensitive_field_resolution = case_insensitive_field_resolution;
+        self
+    }
+
     /// Create a new `Reader` from the `ReaderBuilder`
     pub fn build<'a, R>(self, source: R) -> Result<Reader<'a, R>>
     where
@@ -114,10 +324,91 @@ impl ReaderBuilder {
         // check if schema should be inferred
         let schema = match self.schema {
             Some(schema) => schema,
-            None => Arc::new(super::read_avro_schema_from_reader(&mut source)?),
+            None => {
+                let schema = super::read_avro_schema_with_options(
+                    &mut source,
+                    self.union_representation,
+                    self.name_collision_policy,
+                )?;
+                let schema = if self.extension_types {
+                    schema
+                } else {
+                    without_extension_types(schema)
+                };
+                if self.large_offsets {
+                    Arc::new(with_large_offsets(schema))
+                } else {
+                    Arc::new(schema)
+                }
+            }
         };
         source.rewind()?;
-        Reader::try_new(source, schema, self.batch_size, self.projection)
+        if self.decode_mode == DecodeMode::Strict {
+            validate_encoding(&mut source)?;
+            source.rewind()?;
+        }
+        let schema = if self.string_encodings.is_empty() {
+            schema
+        } else {
+            let mut cardinalities = StringCardinalities::new(&self.string_encodings);
+            if !cardinalities.is_empty() {
+                cardinalities.update(&mut source)?;
+                source.rewind()?;
+            }
+            Arc::new(
+                cardinalities.apply(Arc::unwrap_or_clone(schema), &self.string_encodings),
+            )
+        };
+        let schema = if self.date_columns.is_empty() {
+            schema
+        } else {
+            Arc::new(apply_date_columns(
+                Arc::unwrap_or_clone(schema),
+                &self.date_columns,
+            )?)
+        };
+        let schema = if self.timestamp_columns.is_empty() {
+            schema
+        } else {
+            Arc::new(apply_timestamp_columns(
+                Arc::unwrap_or_clone(schema),
+                &self.timestamp_columns,
+                self.timestamp_precision,
+                self.timestamp_timezone.as_ref(),
+            )?)
+        };
+        let schema =
+            if self.row_index && schema.field_with_name(ROW_INDEX_COLUMN).is_err() {
+                let mut fields = schema.fields().to_vec();
+                fields.push(Arc::new(Field::new(
+                    ROW_INDEX_COLUMN,
+                    DataType::Int64,
+                    false,
+                )));
+                Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()))
+            } else {
+                schema
+            };
+        let schema = match &self.projection {
+            Some(projection) if self.case_insensitive_field_resolution => {
+                Arc::new(with_field_case(
+                    Arc::unwrap_or_clone(schema),
+                    projection.iter().map(String::as_str),
+                )?)
+            }
+            _ => schema,
+        };
+        Ok(Reader::try_new_with_map_duplicate_key_policy(
+            source,
+            schema,
+            self.batch_size,
+            self.projection,
+            self.map_duplicate_key_policy,
+        )?
+        .with_union_representation(self.union_representation)
+        .with_non_midnight_policy(self.non_midnight_policy)
+        .with_row_index(self.row_index)
+        .with_case_insensitive_field_resolution(self.case_insensitive_field_resolution))
     }
 }
 
@@ -135,31 +426,47 @@ impl<R: Read> Reader<'_, R> {
     /// inference, use `ReaderBuilder`.
     ///
     /// If projection is provided, it uses a schema with only the fields in the projection, respecting their order.
-    /// Only the first level of projection is handled. No further projection currently occurs, but would be
-    /// useful if plucking values from a struct, e.g. getting `a.b.c.e` from `a.b.c.{d, e}`.
+    /// A projected name can also be the path of a field nested in structs, e.g. `a.b.c.e`, which reads the
+    /// column `a` with only the fields on the projected paths, getting `a.b.c.e` but not `a.b.c.d` from
+    /// `a.b.c.{d, e}`. The other fields of the structs are not built.
     pub fn try_new(
         reader: R,
         schema: SchemaRef,
         batch_size: usize,
         projection: Option<Vec<String>>,
+    ) -> Result<Self> {
+        Self::try_new_with_map_duplicate_key_policy(
+            reader,
+            schema,
+            batch_size,
+            projection,
+            MapDuplicateKeyPolicy::default(),
+        )
+    }
+
+    /// Create a new Avro Reader handling keys occurring more than once in a
+    /// map with `map_duplicate_key_policy`, see [`Self::try_new`]
+    ///
+    /// The policy is given when opening the file, as only
+    /// [`MapDuplicateKeyPolicy::Error`] and [`MapDuplicateKeyPolicy::Keep`]
+    /// rewrite its header to decode the maps as lists of entries.
+    pub(crate) fn try_new_with_map_duplicate_key_policy(
+        reader: R,
+        schema: SchemaRef,
+        batch_size: usize,
+        projection: Option<Vec<String>>,
+        map_duplicate_key_policy: MapDuplicateKeyPolicy,
     ) -> Result<Self> {
         let projected_schema = projection.as_ref().filter(|p| !p.is_empty()).map_or_else(
             || Arc::clone(&schema),
-            |proj| {
-                Arc::new(arrow::datatypes::Schema::new(
-                    proj.iter()
-                        .filter_map(|name| {
-                            schema.column_with_name(name).map(|(_, f)| f.clone())
-                        })
-                        .collect::<Fields>(),
-                ))
-            },
+            |proj| Arc::new(Schema::new(project_fields(&schema, proj))),
         );
 
         Ok(Self {
             array_reader: AvroArrowArrayReader::try_new(
                 reader,
                 Arc::clone(&projected_schema),
+                map_duplicate_key_policy,
             )?,
             schema: projected_schema,
             batch_size,
@@ -171,6 +478,139 @@ impl<R: Read> Reader<'_, R> {
     pub fn schema(&self) -> SchemaRef {
         Arc::clone(&self.schema)
     }
+
+    /// Set how multi-branch unions are decoded, which must match the
+    /// representation used by the reader's schema
+    /// - defaults to [`UnionRepresentation::Union`]
+    pub fn with_union_representation(
+        mut self,
+        union_representation: UnionRepresentation,
+    ) -> Self {
+        self.array_reader
+            .set_union_representation(union_representation);
+        self
+    }
+
+    /// Set how the timestamps of the `Date32` columns of the reader's schema
+    /// that are not at midnight are decoded
+    /// - defaults to [`NonMidnightPolicy::Error`]
+    pub fn with_non_midnight_policy(
+        mut self,
+        non_midnight_policy: NonMidnightPolicy,
+    ) -> Self {
+        self.array_reader
+            .set_non_midnight_policy(non_midnight_policy);
+        self
+    }
+
+    /// Number the [`ROW_INDEX_COLUMN`] of the reader's schema with the index
+    /// of each record in the file, rather than reading it from the file
+    /// - defaults to `false`
+    pub fn with_row_index(mut self, row_index: bool) -> Self {
+        self.array_reader.set_row_index(row_index);
+        self
+    }
+
+    /// Match the fields of the reader's schema to the fields of the file
+    /// ignoring case, when the file has no field of the exact same name
+    /// - defaults to `false`
+    pub fn with_case_insensitive_field_resolution(
+        mut self,
+        case_insensitive_field_resolution: bool,
+    ) -> Self {
+        if case_insensitive_field_resolution {
+            self.array_reader.set_case_insensitive_field_resolution();
+        }
+        self
+    }
+
+    /// Name the file at `location` in the errors of the records that fail
+    /// to decode, see [`super::AvroDecodeError`]
+    pub(crate) fn with_location(mut self, location: &Path) -> Self {
+        self.array_reader.set_location(location.to_string());
+        self
+    }
+
+    /// Only return the records kept by `sample`
+    pub(crate) fn with_sample(mut self, sample: RecordSample) -> Self {
+        self.array_reader.set_sample(sample);
+        self
+    }
+
+    /// Only return the records selected by `row_filter`. The columns of the
+    /// reader's schema are only built for the selected records.
+    pub(crate) fn with_row_filter(mut self, row_filter: AvroRowFilter) -> Self {
+        self.array_reader.set_row_filter(row_filter);
+        self
+    }
+
+    /// Decode the records of the batches into a buffer of `buffer_pool`,
+    /// returned to it once the reader is dropped
+    pub(crate) fn with_buffer_pool(mut self, buffer_pool: Arc<DecodeBufferPool>) -> Self {
+        self.array_reader
+            .set_buffer_pool(buffer_pool, self.batch_size);
+        self
+    }
+}
+
+/// Returns the fields of `schema` named by `projection`, in its order.
+///
+/// A name that is not the name of a field is the path of a field nested in
+/// structs, like `event.user.id`. Its top level field is projected once, with
+/// structs that only hold the fields on its projected paths, in the order of
+/// the schema. Names matching no field are ignored.
+fn project_fields(schema: &Schema, projection: &[String]) -> Fields {
+    let mut columns: Vec<(&Field, Vec<Vec<&str>>)> = vec![];
+    for name in projection {
+        let (field, path) = match schema.column_with_name(name) {
+            Some((_, field)) => (field, vec![]),
+            None => {
+                let mut parts = name.split('.');
+                let Some((_, field)) = parts
+                    .next()
+                    .and_then(|column| schema.column_with_name(column))
+                else {
+                    continue;
+                };
+                (field, parts.collect())
+            }
+        };
+        match columns.iter_mut().find(|(f, _)| f.name() == field.name()) {
+            Some((_, paths)) => paths.push(path),
+            None => columns.push((field, vec![path])),
+        }
+    }
+    columns
+        .into_iter()
+        .filter_map(|(field, paths)| project_field(field, &paths))
+        .collect()
+}
+
+/// Projects `field` to the `paths` of its nested fields, keeping the whole
+/// field for an empty path, or returns `None` if none of the paths exists
+fn project_field(field: &Field, paths: &[Vec<&str>]) -> Option<Field> {
+    if paths.iter().any(|path| path.is_empty()) {
+        return Some(field.clone());
+    }
+    let DataType::Struct(children) = field.data_type() else {
+        return None;
+    };
+    let children = children
+        .iter()
+        .filter_map(|child| {
+            let child_paths = paths
+                .iter()
+                .filter(|path| path[0] == child.name().as_str())
+                .map(|path| path[1..].to_vec())
+                .collect::<Vec<_>>();
+            if child_paths.is_empty() {
+                return None;
+            }
+            project_field(child, &child_paths)
+        })
+        .collect::<Fields>();
+    (!children.is_empty())
+        .then(|| field.clone().with_data_type(DataType::Struct(children)))
 }
 
 impl<R: Read> Iterator for Reader<'_, R> {
@@ -193,6 +633,7 @@ mod tests {
     };
     use arrow::datatypes::TimeUnit;
     use arrow::datatypes::{DataType, Field};
+    use datafusion_common::assert_batches_eq;
     use std::fs::File;
 
     fn build_reader(name: &str, projection: Option<Vec<String>>) -> Reader<File> {
@@ -354,4 +795,217 @@ mod tests {
         assert!(col.value(0));
         assert!(!col.value(1));
     }
+
+    #[test]
+    fn test_avro_with_nested_projection() {
+        let schema = apache_avro::Schema::parse_str(
+            r#"
+            {
+              "type": "record",
+              "name": "r1",
+              "fields": [
+                {"name": "id", "type": "long"},
+                {
+                  "name": "event",
+                  "type": {
+                    "type": "record",
+                    "name": "r2",
+                    "fields": [
+                      {
+                        "name": "user",
+                        "type": {
+                          "type": "record",
+                          "name": "r3",
+                          "fields": [
+                            {"name": "id", "type": "long"},
+                            {"name": "name", "type": "string"}
+                          ]
+                        }
+                      }
+                    ]
+                  }
+                }
+              ]
+            }"#,
+        )
+        .unwrap();
+        let mut writer = apache_avro::Writer::new(&schema, vec![]);
+        for (id, name) in [(1, "alice"), (2, "bob")] {
+            let record = apache_avro::to_value(serde_json::json!({
+                "id": id,
+                "event": {"user": {"id": id * 10, "name": name}}
+            }))
+            .unwrap()
+            .resolve(&schema)
+            .unwrap();
+            writer.append(record).unwrap();
+        }
+        let bytes = writer.into_inner().unwrap();
+
+        let mut reader = ReaderBuilder::new()
+            .read_schema()
+            .with_projection(vec!["event.user.id".to_string()])
+            .build(std::io::Cursor::new(bytes))
+            .unwrap();
+        let user = Field::new(
+            "user",
+            DataType::Struct(Fields::from(vec![Field::new(
+                "id",
+                DataType::Int64,
+                false,
+            )])),
+            false,
+        );
+        assert_eq!(
+            reader.schema().fields().as_ref(),
+            [Arc::new(Field::new(
+                "event",
+                DataType::Struct(Fields::from(vec![user])),
+                false
+            ))]
+        );
+        let batch = reader.next().unwrap().unwrap();
+        let expected = [
+            "+------------------+",
+            "| event            |",
+            "+------------------+",
+            "| {user: {id: 10}} |",
+            "| {user: {id: 20}} |",
+            "+------------------+",
+        ];
+        assert_batches_eq!(expected, &[batch]);
+    }
+
+    /// Writes the records of `values` to an Avro file of `schema`
+    fn write_avro(schema: &str, values: Vec<serde_json::Value>) -> Vec<u8> {
+        let schema = apache_avro::Schema::parse_str(schema).unwrap();
+        let mut writer = apache_avro::Writer::new(&schema, vec![]);
+        for value in values {
+            let record = apache_avro::to_value(value)
+                .unwrap()
+                .resolve(&schema)
+                .unwrap();
+            writer.append(record).unwrap();
+        }
+        writer.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_avro_large_offsets() {
+        let bytes = write_avro(
+            r#"
+            {
+              "type": "record",
+              "name": "r",
+              "fields": [
+                {"name": "name", "type": "string"},
+                {"name": "data", "type": "bytes"},
+                {"name": "tags", "type": {"type": "array", "items": "string"}},
+                {
+                  "name": "owner",
+                  "type": {
+                    "type": "record",
+                    "name": "o",
+                    "fields": [{"name": "ids", "type": {"type": "array", "items": "long"}}]
+                  }
+                }
+              ]
+            }"#,
+            vec![
+                serde_json::json!({
+                    "name": "alice", "data": [1, 2], "tags": ["a", "b"], "owner": {"ids": [1]}
+                }),
+                serde_json::json!({
+                    "name": "bob", "data": [], "tags": [], "owner": {"ids": [2, 3]}
+                }),
+            ],
+        );
+
+        let mut reader = ReaderBuilder::new()
+            .read_schema()
+            .with_large_offsets(true)
+            .build(std::io::Cursor::new(bytes))
+            .unwrap();
+        let data_types = reader
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.data_type().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            data_types,
+            [
+                DataType::LargeUtf8,
+                DataType::LargeBinary,
+                DataType::LargeList(Arc::new(Field::new(
+                    "element",
+                    DataType::LargeUtf8,
+                    false
+                ))),
+                DataType::Struct(Fields::from(vec![Field::new(
+                    "ids",
+                    DataType::LargeList(Arc::new(Field::new(
+                        "element",
+                        DataType::Int64,
+                        false
+                    ))),
+                    false,
+                )])),
+            ]
+        );
+        let batch = reader.next().unwrap().unwrap();
+        let expected = [
+            "+-------+------+--------+---------------+",
+            "| name  | data | tags   | owner         |",
+            "+-------+------+--------+---------------+",
+            "| alice | 0102 | [a, b] | {ids: [1]}    |",
+            "| bob   |      | []     | {ids: [2, 3]} |",
+            "+-------+------+--------+---------------+",
+        ];
+        assert_batches_eq!(expected, &[batch]);
+    }
+
+    #[test]
+    #[ignore = "decodes a batch of more than 2GB"]
+    fn test_avro_offset_overflow() {
+        let schema = apache_avro::Schema::parse_str(
+            r#"{"type": "record", "name": "r", "fields": [{"name": "data", "type": "bytes"}]}"#,
+        )
+        .unwrap();
+        let mut writer = apache_avro::Writer::new(&schema, vec![]);
+        // Two values of 1.25GB overflow the 32-bit offsets of a batch
+        for _ in 0..2 {
+            let value = apache_avro::types::Value::Bytes(vec![0u8; 5 << 28]);
+            writer
+                .append(apache_avro::types::Value::Record(vec![(
+                    "data".to_string(),
+                    value,
+                )]))
+                .unwrap();
+        }
+        let bytes = writer.into_inner().unwrap();
+
+        let err = ReaderBuilder::new()
+            .read_schema()
+            .build(std::io::Cursor::new(&bytes))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("overflowing the 32-bit offsets"),
+            "{err}"
+        );
+
+        let batch = ReaderBuilder::new()
+            .read_schema()
+            .with_large_offsets(true)
+            .build(std::io::Cursor::new(&bytes))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let data = batch.column(0).as_binary::<i64>();
+        assert_eq!(data.value_offsets(), [0, 5 << 28, 5 << 29]);
+    }
 }
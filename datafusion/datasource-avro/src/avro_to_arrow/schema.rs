@@ -16,27 +16,306 @@
 // under the License.
 
 use apache_avro::schema::{
-    Alias, DecimalSchema, EnumSchema, FixedSchema, Name, RecordSchema,
+    Alias, DecimalSchema, EnumSchema, FixedSchema, Name, RecordField, RecordFieldOrder,
+    RecordSchema,
 };
 use apache_avro::types::Value;
 use apache_avro::Schema as AvroSchema;
-use arrow::datatypes::{DataType, IntervalUnit, Schema, TimeUnit, UnionMode};
-use arrow::datatypes::{Field, UnionFields};
+use arrow::compute::SortOptions;
+use arrow::datatypes::{
+    DataType, IntervalUnit, Schema, TimeUnit, UnionMode, DECIMAL128_MAX_PRECISION,
+};
+use arrow::datatypes::{Field, FieldRef, Fields, UnionFields};
+use arrow_schema::extension::{EXTENSION_TYPE_METADATA_KEY, EXTENSION_TYPE_NAME_KEY};
 use datafusion_common::error::Result;
-use std::collections::HashMap;
+use datafusion_common::plan_err;
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_expr_common::sort_expr::{LexOrdering, PhysicalSortExpr};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+/// Field metadata key holding the `order` attribute of an Avro record field,
+/// either `descending` or `ignore`. Fields with the default `ascending` order
+/// have no such metadata.
+pub const AVRO_ORDER_METADATA_KEY: &str = "avro::order";
+
+/// Field metadata key holding the symbols of an Avro enum, as `[s1,s2]`
+pub const AVRO_SYMBOLS_METADATA_KEY: &str = "avro::symbols";
+
+/// Field metadata key holding the `default` symbol of an Avro enum, the
+/// symbol of the enum's [`AVRO_SYMBOLS_METADATA_KEY`] metadata that symbols
+/// unknown to the enum resolve to
+pub const AVRO_ENUM_DEFAULT_METADATA_KEY: &str = "avro::enum_default";
+
+/// Name of the Arrow canonical extension type of the fields decoded from Avro
+/// `uuid` values, stored as `FixedSizeBinary(16)`
+pub const UUID_EXTENSION_NAME: &str = "arrow.uuid";
+
+/// How multi-branch Avro unions are represented in Arrow
+///
+/// Unions of `null` and a single other type are always decoded as a nullable
+/// column of the other type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnionRepresentation {
+    /// Decode unions as a dense Arrow `Union`
+    #[default]
+    Union,
+    /// Decode unions as a `Struct{ tag: Int8, branch_0: T0, branch_1: T1, ... }`,
+    /// where `tag` is the index of the branch of each value and only the
+    /// field of that branch is non-null
+    Struct,
+}
+
+/// Field metadata key holding the name of a field before it was renamed by
+/// [`NameCollisionPolicy::Suffix`]
+pub const AVRO_ORIGINAL_NAME_METADATA_KEY: &str = "avro::original_name";
+
+/// How sibling Arrow fields whose names collide are handled when translating
+/// an Avro schema
+///
+/// The fields of an Avro record have distinct names, but the fields of the
+/// Arrow `Union` of a multi-branch union are named after their types: the
+/// branches of a union of two records are for example both named `struct`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NameCollisionPolicy {
+    /// Fail schema translation with an error naming the colliding field
+    #[default]
+    Error,
+    /// Append `_1`, `_2`, ... to the names of the second, third, ... fields
+    /// with the same name, recording the original name in the
+    /// [`AVRO_ORIGINAL_NAME_METADATA_KEY`] metadata of the renamed fields
+    Suffix,
+}
+
+/// Applies `policy` to sibling fields of `schema` with the same name,
+/// including nested ones
+pub(crate) fn resolve_name_collisions(
+    schema: Schema,
+    policy: NameCollisionPolicy,
+) -> Result<Schema> {
+    let fields = resolve_field_names(schema.fields(), policy, None)?;
+    Ok(Schema::new_with_metadata(fields, schema.metadata().clone()))
+}
+
+/// Applies `policy` to `fields`, the children of the field at `path`
+fn resolve_field_names<'a>(
+    fields: impl IntoIterator<Item = &'a FieldRef>,
+    policy: NameCollisionPolicy,
+    path: Option<&str>,
+) -> Result<Vec<Field>> {
+    let fields = fields.into_iter().collect::<Vec<_>>();
+    // renamed fields must not collide with the original name of another field
+    let original_names = fields
+        .iter()
+        .map(|field| field.name().as_str())
+        .collect::<HashSet<_>>();
+    let mut names = HashSet::with_capacity(fields.len());
+    fields
+        .into_iter()
+        .map(|field| {
+            let field_path = match path {
+                Some(path) => format!("{path}.{}", field.name()),
+                None => field.name().clone(),
+            };
+            let data_type =
+                resolve_data_type_names(field.data_type(), policy, &field_path)?;
+            let field = field.as_ref().clone().with_data_type(data_type);
+            if names.insert(field.name().clone()) {
+                return Ok(field);
+            }
+            match policy {
+                NameCollisionPolicy::Error => plan_err!(
+                    "Avro schema translation results in several fields named {field_path}, \
+                     consider using NameCollisionPolicy::Suffix"
+                ),
+                NameCollisionPolicy::Suffix => {
+                    let name = (1..)
+                        .map(|i| format!("{}_{i}", field.name()))
+                        .find(|name| {
+                            !names.contains(name) && !original_names.contains(name.as_str())
+                        })
+                        .expect("unbounded suffixes");
+                    names.insert(name.clone());
+                    let mut metadata = field.metadata().clone();
+                    metadata.insert(
+                        AVRO_ORIGINAL_NAME_METADATA_KEY.to_string(),
+                        field.name().clone(),
+                    );
+                    Ok(field.with_name(name).with_metadata(metadata))
+                }
+            }
+        })
+        .collect()
+}
+
+/// Applies `policy` to the fields nested in `data_type`, the type of the
+/// field at `path`
+fn resolve_data_type_names(
+    data_type: &DataType,
+    policy: NameCollisionPolicy,
+    path: &str,
+) -> Result<DataType> {
+    Ok(match data_type {
+        DataType::Struct(fields) => {
+            DataType::Struct(resolve_field_names(fields, policy, Some(path))?.into())
+        }
+        DataType::Union(union_fields, mode) => {
+            let (type_ids, fields): (Vec<_>, Vec<_>) = union_fields
+                .iter()
+                .map(|(type_id, field)| (type_id, Arc::clone(field)))
+                .unzip();
+            let fields = resolve_field_names(&fields, policy, Some(path))?;
+            DataType::Union(UnionFields::new(type_ids, fields), *mode)
+        }
+        DataType::List(item) => {
+            DataType::List(Arc::new(item.as_ref().clone().with_data_type(
+                resolve_data_type_names(item.data_type(), policy, path)?,
+            )))
+        }
+        DataType::Map(entries, sorted) => {
+            DataType::Map(
+                Arc::new(entries.as_ref().clone().with_data_type(
+                    resolve_data_type_names(entries.data_type(), policy, path)?,
+                )),
+                *sorted,
+            )
+        }
+        DataType::Dictionary(key_type, value_type) => DataType::Dictionary(
+            key_type.clone(),
+            Box::new(resolve_data_type_names(value_type, policy, path)?),
+        ),
+        data_type => data_type.clone(),
+    })
+}
+
+/// Replaces every Arrow `Union` in `schema`, including nested ones, by the
+/// `Struct` of [`UnionRepresentation::Struct`]
+pub(crate) fn unions_as_structs(schema: Schema) -> Schema {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| union_field_as_struct(field))
+        .collect::<Vec<_>>();
+    Schema::new_with_metadata(fields, schema.metadata().clone())
+}
+
+fn union_field_as_struct(field: &Field) -> Field {
+    let data_type = match field.data_type() {
+        DataType::Union(union_fields, _) => {
+            let branches = union_fields.iter().map(|(type_id, branch)| {
+                union_field_as_struct(branch)
+                    .with_name(format!("branch_{type_id}"))
+                    .with_nullable(true)
+            });
+            DataType::Struct(
+                std::iter::once(Field::new("tag", DataType::Int8, false))
+                    .chain(branches)
+                    .collect(),
+            )
+        }
+        DataType::Struct(fields) => DataType::Struct(
+            fields
+                .iter()
+                .map(|field| union_field_as_struct(field))
+                .collect(),
+        ),
+        DataType::List(item) => DataType::List(Arc::new(union_field_as_struct(item))),
+        DataType::Map(entries, sorted) => {
+            DataType::Map(Arc::new(union_field_as_struct(entries)), *sorted)
+        }
+        data_type => data_type.clone(),
+    };
+    field.clone().with_data_type(data_type)
+}
+
+/// Removes the Arrow extension type metadata of the fields of `schema`,
+/// including nested ones, leaving their storage types
+pub(crate) fn without_extension_types(schema: Schema) -> Schema {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| field_without_extension_type(field))
+        .collect::<Vec<_>>();
+    Schema::new_with_metadata(fields, schema.metadata().clone())
+}
+
+fn field_without_extension_type(field: &Field) -> Field {
+    let data_type = match field.data_type() {
+        DataType::Union(union_fields, mode) => DataType::Union(
+            union_fields
+                .iter()
+                .map(|(type_id, field)| {
+                    (type_id, field_without_extension_type(field).into())
+                })
+                .collect(),
+            *mode,
+        ),
+        DataType::Struct(fields) => DataType::Struct(
+            fields
+                .iter()
+                .map(|field| field_without_extension_type(field))
+                .collect(),
+        ),
+        DataType::List(item) => {
+            DataType::List(Arc::new(field_without_extension_type(item)))
+        }
+        DataType::Map(entries, sorted) => {
+            DataType::Map(Arc::new(field_without_extension_type(entries)), *sorted)
+        }
+        data_type => data_type.clone(),
+    };
+    let mut metadata = field.metadata().clone();
+    metadata.remove(EXTENSION_TYPE_NAME_KEY);
+    metadata.remove(EXTENSION_TYPE_METADATA_KEY);
+    field
+        .clone()
+        .with_data_type(data_type)
+        .with_metadata(metadata)
+}
+
+/// Replaces the `Utf8`, `Binary` and `List` fields of `schema`, including the
+/// ones nested in structs and lists, by their `LargeUtf8`, `LargeBinary` and
+/// `LargeList` variants with 64-bit offsets
+pub(crate) fn with_large_offsets(schema: Schema) -> Schema {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| field_with_large_offsets(field))
+        .collect::<Vec<_>>();
+    Schema::new_with_metadata(fields, schema.metadata().clone())
+}
+
+fn field_with_large_offsets(field: &Field) -> Field {
+    let data_type = match field.data_type() {
+        DataType::Utf8 => DataType::LargeUtf8,
+        DataType::Binary => DataType::LargeBinary,
+        DataType::List(item) => {
+            DataType::LargeList(Arc::new(field_with_large_offsets(item)))
+        }
+        DataType::Struct(fields) => DataType::Struct(
+            fields
+                .iter()
+                .map(|field| field_with_large_offsets(field))
+                .collect(),
+        ),
+        data_type => data_type.clone(),
+    };
+    field.clone().with_data_type(data_type)
+}
+
 /// Converts an avro schema to an arrow schema
 pub fn to_arrow_schema(avro_schema: &apache_avro::Schema) -> Result<Schema> {
     let mut schema_fields = vec![];
     match avro_schema {
         AvroSchema::Record(RecordSchema { fields, .. }) => {
             for field in fields {
+                let mut props = external_props(&field.schema);
+                props.extend(order_props(field));
                 schema_fields.push(schema_to_field_with_props(
                     &field.schema,
                     Some(&field.name),
                     field.is_nullable(),
-                    Some(external_props(&field.schema)),
+                    Some(props),
                 )?)
             }
         }
@@ -47,6 +326,78 @@ pub fn to_arrow_schema(avro_schema: &apache_avro::Schema) -> Result<Schema> {
     Ok(schema)
 }
 
+/// Merges the schemas of several Avro files into a single schema, widening
+/// columns whose types differ between files where no value can be lost.
+///
+/// Top level `Decimal128` columns with differing precision and scale are
+/// reconciled to the maximum scale and a precision large enough to hold the
+/// integral digits of every input. An error is returned if the required
+/// precision exceeds [`DECIMAL128_MAX_PRECISION`].
+///
+/// All other columns are merged with [`Schema::try_merge`].
+pub fn merge_schemas_widening(schemas: Vec<Schema>) -> Result<Schema> {
+    let mut decimal_types: HashMap<String, (u8, i8)> = HashMap::new();
+    for field in schemas.iter().flat_map(|schema| schema.fields()) {
+        let DataType::Decimal128(precision, scale) = field.data_type() else {
+            continue;
+        };
+        let widened = match decimal_types.get(field.name()) {
+            Some(&(current_precision, current_scale)) => widen_decimal(
+                field.name(),
+                (current_precision, current_scale),
+                (*precision, *scale),
+            )?,
+            None => (*precision, *scale),
+        };
+        decimal_types.insert(field.name().clone(), widened);
+    }
+
+    let schemas = schemas
+        .into_iter()
+        .map(|schema| {
+            let fields =
+                schema
+                    .fields()
+                    .iter()
+                    .map(|field| {
+                        match (field.data_type(), decimal_types.get(field.name())) {
+                            (DataType::Decimal128(_, _), Some(&(precision, scale))) => {
+                                Arc::new(field.as_ref().clone().with_data_type(
+                                    DataType::Decimal128(precision, scale),
+                                ))
+                            }
+                            _ => Arc::clone(field),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+            Schema::new_with_metadata(fields, schema.metadata().clone())
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Schema::try_merge(schemas)?)
+}
+
+/// Returns the smallest decimal type able to represent every value of both
+/// `(precision, scale)` inputs without loss
+fn widen_decimal(name: &str, left: (u8, i8), right: (u8, i8)) -> Result<(u8, i8)> {
+    let scale = left.1.max(right.1);
+    let integral_digits =
+        (left.0 as i16 - left.1 as i16).max(right.0 as i16 - right.1 as i16);
+    let precision = integral_digits + scale as i16;
+    if precision > DECIMAL128_MAX_PRECISION as i16 {
+        return plan_err!(
+            "Cannot merge column {name} of types Decimal128({}, {}) and Decimal128({}, {}): \
+            the merged type requires precision {precision} which exceeds the maximum \
+            Decimal128 precision of {DECIMAL128_MAX_PRECISION}",
+            left.0,
+            left.1,
+            right.0,
+            right.1
+        );
+    }
+    Ok((precision as u8, scale))
+}
+
 fn schema_to_field(
     schema: &apache_avro::Schema,
     name: Option<&str>,
@@ -64,7 +415,11 @@ fn schema_to_field_with_props(
     let mut nullable = nullable;
     let field_type: DataType = match schema {
         AvroSchema::Ref { .. } => todo!("Add support for AvroSchema::Ref"),
-        AvroSchema::Null => DataType::Null,
+        AvroSchema::Null => {
+            // Every value of a `null` field is null
+            nullable = true;
+            DataType::Null
+        }
         AvroSchema::Boolean => DataType::Boolean,
         AvroSchema::Int => DataType::Int32,
         AvroSchema::Long => DataType::Int64,
@@ -82,10 +437,15 @@ fn schema_to_field_with_props(
                 false,
                 None,
             )?;
-            DataType::Dictionary(
-                Box::new(DataType::Utf8),
-                Box::new(value_field.data_type().clone()),
-            )
+            let entries = Field::new(
+                "entries",
+                DataType::Struct(Fields::from(vec![
+                    Field::new("key", DataType::Utf8, false),
+                    value_field,
+                ])),
+                false,
+            );
+            DataType::Map(Arc::new(entries), false)
         }
         AvroSchema::Union(us) => {
             // If there are only two variants and one of them is null, set the other type as the field data type
@@ -122,7 +482,7 @@ fn schema_to_field_with_props(
             let fields: Result<_> = fields
                 .iter()
                 .map(|field| {
-                    let mut props = HashMap::new();
+                    let mut props = order_props(field);
                     if let Some(doc) = &field.doc {
                         props.insert("avro::doc".to_string(), doc.clone());
                     }
@@ -163,8 +523,10 @@ fn schema_to_field_with_props(
     let data_type = field_type.clone();
     let name = name.unwrap_or_else(|| default_field_name(&data_type));
 
+    let mut props = props.unwrap_or_default();
+    props.extend(extension_props(schema));
     let mut field = Field::new(name, field_type, nullable);
-    field.set_metadata(props.unwrap_or_default());
+    field.set_metadata(props);
     Ok(field)
 }
 
@@ -225,7 +587,7 @@ fn default_field_name(dt: &DataType) -> &str {
         DataType::Struct(_) => "struct",
         DataType::Union(_, _) => "union",
         DataType::Dictionary(_, _) => "map",
-        DataType::Map(_, _) => unimplemented!("Map support not implemented"),
+        DataType::Map(_, _) => "map",
         DataType::RunEndEncoded(_, _) => {
             unimplemented!("RunEndEncoded support not implemented")
         }
@@ -240,6 +602,143 @@ fn default_field_name(dt: &DataType) -> &str {
     }
 }
 
+/// Returns the [`AVRO_ORDER_METADATA_KEY`] metadata of a record field
+fn order_props(field: &RecordField) -> HashMap<String, String> {
+    let order = match field.order {
+        RecordFieldOrder::Ascending => return HashMap::new(),
+        RecordFieldOrder::Descending => "descending",
+        RecordFieldOrder::Ignore => "ignore",
+    };
+    HashMap::from([(AVRO_ORDER_METADATA_KEY.to_string(), order.to_string())])
+}
+
+/// Returns the non-null branch of a union of null and another type, or
+/// `schema` itself if it is not such a union
+fn non_null_branch(schema: &AvroSchema) -> &AvroSchema {
+    match schema {
+        AvroSchema::Union(union)
+            if union.is_nullable() && union.variants().len() == 2 =>
+        {
+            union
+                .variants()
+                .iter()
+                .find(|schema| !matches!(schema, AvroSchema::Null))
+                .unwrap_or(schema)
+        }
+        _ => schema,
+    }
+}
+
+/// Adds the [`AVRO_SYMBOLS_METADATA_KEY`] and [`AVRO_ENUM_DEFAULT_METADATA_KEY`]
+/// metadata to the fields of `schema`, converted from the Avro record schema
+/// `avro_schema` by [`to_arrow_schema`], that are decoded from enums
+pub(crate) fn with_enum_symbols(schema: Schema, avro_schema: &AvroSchema) -> Schema {
+    let AvroSchema::Record(RecordSchema {
+        fields: avro_fields,
+        ..
+    }) = avro_schema
+    else {
+        return schema;
+    };
+    let fields = schema
+        .fields()
+        .iter()
+        .zip(avro_fields)
+        .map(|(field, avro_field)| {
+            let mut metadata = field.metadata().clone();
+            metadata.extend(symbols_props(&avro_field.schema));
+            field.as_ref().clone().with_metadata(metadata)
+        })
+        .collect::<Vec<_>>();
+    Schema::new_with_metadata(fields, schema.metadata().clone())
+}
+
+/// Returns the [`AVRO_SYMBOLS_METADATA_KEY`] and [`AVRO_ENUM_DEFAULT_METADATA_KEY`]
+/// metadata of an enum, or of a union of null and an enum
+fn symbols_props(schema: &AvroSchema) -> HashMap<String, String> {
+    match non_null_branch(schema) {
+        AvroSchema::Enum(EnumSchema {
+            symbols, default, ..
+        }) => {
+            let mut props = HashMap::from([(
+                AVRO_SYMBOLS_METADATA_KEY.to_string(),
+                format!("[{}]", symbols.join(",")),
+            )]);
+            if let Some(default) = default {
+                props.insert(AVRO_ENUM_DEFAULT_METADATA_KEY.to_string(), default.clone());
+            }
+            props
+        }
+        _ => HashMap::new(),
+    }
+}
+
+/// Returns the Arrow extension type metadata of the field decoded from a
+/// logical type with a canonical extension type, or from a union of null and
+/// such a logical type
+fn extension_props(schema: &AvroSchema) -> HashMap<String, String> {
+    match non_null_branch(schema) {
+        AvroSchema::Uuid => HashMap::from([(
+            EXTENSION_TYPE_NAME_KEY.to_string(),
+            UUID_EXTENSION_NAME.to_string(),
+        )]),
+        _ => HashMap::new(),
+    }
+}
+
+/// Returns the ordering of the records of Avro files with the arrow schema
+/// `schema`, as defined by the `order` attributes of the fields of their
+/// record schema, or `None` if no field can be ordered.
+///
+/// Avro compares records field by field in the order they are declared,
+/// skipping fields whose order is `ignore`. The ordering ends before the
+/// first compared field whose Avro sort order cannot be expressed as an
+/// ordering of the decoded Arrow values, such as enums, which are compared
+/// by the position of their symbols, and nested types. Nulls are ordered as
+/// in a `["null", T]` union: first in ascending order.
+///
+/// Enum columns are told apart from string columns by their
+/// [`AVRO_SYMBOLS_METADATA_KEY`] metadata, which the schema inferred by an
+/// `AvroFormat` declares when the files are sorted by schema order.
+///
+/// This does not mean that the records are sorted, which the Avro
+/// specification does not require.
+pub fn avro_sort_order(schema: &Schema) -> Option<LexOrdering> {
+    let mut sort_exprs = vec![];
+    for (index, field) in schema.fields().iter().enumerate() {
+        let descending = match field.metadata().get(AVRO_ORDER_METADATA_KEY) {
+            None => false,
+            Some(order) if order == "descending" => true,
+            Some(_) => continue,
+        };
+        let orderable = match field.data_type() {
+            DataType::Utf8 => !field.metadata().contains_key(AVRO_SYMBOLS_METADATA_KEY),
+            data_type => matches!(
+                data_type,
+                DataType::Boolean
+                    | DataType::Int32
+                    | DataType::Int64
+                    | DataType::Float32
+                    | DataType::Float64
+                    | DataType::Binary
+                    | DataType::FixedSizeBinary(_)
+                    | DataType::Date32
+                    | DataType::Time32(_)
+                    | DataType::Time64(_)
+                    | DataType::Timestamp(_, _)
+            ),
+        };
+        if !orderable {
+            break;
+        }
+        sort_exprs.push(PhysicalSortExpr::new(
+            Arc::new(Column::new(field.name(), index)),
+            SortOptions::new(descending, !descending),
+        ));
+    }
+    LexOrdering::new(sort_exprs)
+}
+
 fn external_props(schema: &AvroSchema) -> HashMap<String, String> {
     let mut props = HashMap::new();
     match &schema {
@@ -306,11 +805,16 @@ pub fn aliased(
 
 #[cfg(test)]
 mod test {
-    use super::{aliased, external_props, to_arrow_schema};
+    use super::{
+        aliased, avro_sort_order, external_props, merge_schemas_widening,
+        resolve_name_collisions, to_arrow_schema, unions_as_structs, with_enum_symbols,
+        NameCollisionPolicy, AVRO_ORDER_METADATA_KEY, AVRO_ORIGINAL_NAME_METADATA_KEY,
+        AVRO_SYMBOLS_METADATA_KEY,
+    };
     use apache_avro::schema::{Alias, EnumSchema, FixedSchema, Name, RecordSchema};
     use apache_avro::Schema as AvroSchema;
     use arrow::datatypes::DataType::{Binary, Float32, Float64, Timestamp, Utf8};
-    use arrow::datatypes::DataType::{Boolean, Int32, Int64};
+    use arrow::datatypes::DataType::{Boolean, Decimal128, Int32, Int64, Null};
     use arrow::datatypes::TimeUnit::Microsecond;
     use arrow::datatypes::{Field, Schema};
 
@@ -506,6 +1010,139 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_order_metadata() {
+        let avro_schema = AvroSchema::parse_str(
+            r#"
+            {
+              "type": "record",
+              "name": "r1",
+              "fields": [
+                {"name": "a", "type": "long", "order": "ascending"},
+                {"name": "b", "type": "string", "order": "descending"},
+                {"name": "c", "type": ["null", "double"], "order": "ignore"},
+                {
+                  "name": "d",
+                  "type": {
+                    "type": "record",
+                    "name": "r2",
+                    "fields": [{"name": "e", "type": "int", "order": "descending"}]
+                  }
+                },
+                {"name": "f", "type": "int"}
+              ]
+            }"#,
+        )
+        .unwrap();
+        let schema = to_arrow_schema(&avro_schema).unwrap();
+        let order =
+            |field: &Field| field.metadata().get(AVRO_ORDER_METADATA_KEY).cloned();
+        assert_eq!(order(schema.field(0)), None);
+        assert_eq!(order(schema.field(1)), Some("descending".to_string()));
+        assert_eq!(order(schema.field(2)), Some("ignore".to_string()));
+        let arrow::datatypes::DataType::Struct(nested) = schema.field(3).data_type()
+        else {
+            panic!("expected a struct, got {}", schema.field(3).data_type());
+        };
+        assert_eq!(order(&nested[0]), Some("descending".to_string()));
+
+        // The ordering skips the ignored column and ends before the struct
+        let ordering = avro_sort_order(&schema).unwrap();
+        assert_eq!(ordering.to_string(), "a@0 ASC, b@1 DESC NULLS LAST");
+    }
+
+    #[test]
+    fn test_enum_ends_sort_order() {
+        let avro_schema = AvroSchema::parse_str(
+            r#"
+            {
+              "type": "record",
+              "name": "r1",
+              "fields": [
+                {
+                  "name": "kind",
+                  "type": ["null", {"type": "enum", "name": "e1", "symbols": ["b", "a"]}]
+                },
+                {"name": "id", "type": "long"}
+              ]
+            }"#,
+        )
+        .unwrap();
+        let schema = to_arrow_schema(&avro_schema).unwrap();
+        assert_eq!(
+            schema.field(0).metadata().get(AVRO_SYMBOLS_METADATA_KEY),
+            None
+        );
+
+        let schema = with_enum_symbols(schema, &avro_schema);
+        assert_eq!(
+            schema.field(0).metadata().get(AVRO_SYMBOLS_METADATA_KEY),
+            Some(&"[b,a]".to_string())
+        );
+        assert_eq!(avro_sort_order(&schema), None);
+    }
+
+    #[test]
+    fn test_merge_schemas_widening_decimal() {
+        let schema = |precision, scale| {
+            Schema::new(vec![
+                Field::new("id", Int32, false),
+                Field::new("amount", Decimal128(precision, scale), true),
+            ])
+        };
+
+        let merged = merge_schemas_widening(vec![schema(10, 2), schema(12, 4)]).unwrap();
+        assert_eq!(merged, schema(12, 4));
+
+        // 8 integral digits from the first input, scale 4 from the second
+        let merged = merge_schemas_widening(vec![schema(10, 2), schema(6, 4)]).unwrap();
+        assert_eq!(merged, schema(12, 4));
+
+        let err = merge_schemas_widening(vec![schema(38, 0), schema(10, 2)])
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("exceeds the maximum Decimal128 precision"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_merge_null_only_field() {
+        let null_only = to_arrow_schema(
+            &AvroSchema::parse_str(
+                r#"{"type": "record", "name": "r", "fields": [
+                    {"name": "id", "type": "long"},
+                    {"name": "extra", "type": "null"}
+                ]}"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            null_only.field_with_name("extra").unwrap(),
+            &Field::new("extra", Null, true)
+        );
+        let long = Schema::new(vec![
+            Field::new("id", Int64, false),
+            Field::new("extra", Int64, false),
+        ]);
+        let expected = Schema::new(vec![
+            Field::new("id", Int64, false),
+            Field::new("extra", Int64, true),
+        ]);
+
+        // `Null` widens to the type of the other field, which becomes nullable
+        let strip = |schema: Schema| Schema::new(schema.fields().clone());
+        for schemas in [
+            vec![null_only.clone(), long.clone()],
+            vec![long.clone(), null_only.clone()],
+        ] {
+            assert_eq!(strip(Schema::try_merge(schemas.clone()).unwrap()), expected);
+            assert_eq!(strip(merge_schemas_widening(schemas).unwrap()), expected);
+        }
+    }
+
     #[test]
     fn test_non_record_schema() {
         let arrow_schema = to_arrow_schema(&AvroSchema::String);
@@ -515,4 +1152,64 @@ mod test {
             Schema::new(vec![Field::new("", Utf8, false)])
         );
     }
+
+    #[test]
+    fn test_name_collisions() {
+        let avro_schema = AvroSchema::parse_str(
+            r#"{"type": "record", "name": "r", "fields": [
+                {"name": "u", "type": [
+                    {"type": "record", "name": "a", "fields": [{"name": "x", "type": "long"}]},
+                    {"type": "record", "name": "b", "fields": [{"name": "y", "type": "long"}]},
+                    {"type": "enum", "name": "e", "symbols": ["A"]},
+                    "string",
+                    {"type": "record", "name": "c", "fields": [{"name": "z", "type": "long"}]}
+                ]}
+            ]}"#,
+        )
+        .unwrap();
+        let schema = to_arrow_schema(&avro_schema).unwrap();
+
+        let err = resolve_name_collisions(schema.clone(), NameCollisionPolicy::Error)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("several fields named u.struct"), "{err}");
+
+        let resolved =
+            resolve_name_collisions(schema.clone(), NameCollisionPolicy::Suffix).unwrap();
+        let arrow::datatypes::DataType::Union(branches, _) =
+            resolved.field(0).data_type()
+        else {
+            panic!("expected a union: {resolved}");
+        };
+        let names = branches
+            .iter()
+            .map(|(_, branch)| {
+                (
+                    branch.name().as_str(),
+                    branch
+                        .metadata()
+                        .get(AVRO_ORIGINAL_NAME_METADATA_KEY)
+                        .map(String::as_str),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                ("struct", None),
+                ("struct_1", Some("struct")),
+                ("varchar", None),
+                ("varchar_1", Some("varchar")),
+                ("struct_2", Some("struct")),
+            ]
+        );
+
+        // branches of unions represented as structs are named by position
+        let as_structs = unions_as_structs(schema);
+        assert_eq!(
+            resolve_name_collisions(as_structs.clone(), NameCollisionPolicy::Error)
+                .unwrap(),
+            as_structs
+        );
+    }
 }
@@ -0,0 +1,251 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Interning of the schemas embedded in the headers of Avro files
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use apache_avro::types::Value;
+use apache_avro::{from_avro_datum, Error as AvroError, Schema as AvroSchema};
+use arrow::datatypes::Schema;
+use datafusion_common::Result;
+use datafusion_datasource::file_format::FileFormat;
+
+use super::{avro_schema_to_arrow, NameCollisionPolicy, UnionRepresentation};
+use crate::block_stream::MAGIC;
+
+/// Converts the `avro.schema` JSON of the headers of Avro files to Arrow
+/// schemas, parsing each distinct JSON only once
+///
+/// Datasets written by a single job usually embed byte-identical schemas in
+/// all their files: only the raw bytes of the schema of each file are then
+/// hashed, and the Arrow schema parsed for the first file is reused for the
+/// others. Avro schemas that differ only in their JSON, such as in their
+/// whitespace or documentation, share the Arrow schema they convert to,
+/// keyed by its [`FileFormat::schema_fingerprint`].
+#[derive(Debug)]
+pub(crate) struct SchemaInterner<'a> {
+    format: &'a dyn FileFormat,
+    union_representation: UnionRepresentation,
+    name_collision_policy: NameCollisionPolicy,
+    enum_symbols: bool,
+    /// The fingerprints of the Arrow schemas parsed so far, by the JSON of
+    /// their Avro schema
+    fingerprints: HashMap<Vec<u8>, u64>,
+    /// The distinct Arrow schemas parsed so far, by fingerprint
+    schemas: HashMap<u64, Schema>,
+}
+
+impl<'a> SchemaInterner<'a> {
+    /// Create an interner fingerprinting the schemas with `format`
+    pub(crate) fn new(
+        format: &'a dyn FileFormat,
+        union_representation: UnionRepresentation,
+        name_collision_policy: NameCollisionPolicy,
+        enum_symbols: bool,
+    ) -> Self {
+        Self {
+            format,
+            union_representation,
+            name_collision_policy,
+            enum_symbols,
+            fingerprints: HashMap::new(),
+            schemas: HashMap::new(),
+        }
+    }
+
+    /// Reads the header of the Avro file of `reader`, returning the Arrow
+    /// schema of the file. The reader is left after the metadata of the
+    /// header.
+    pub(crate) fn read_schema<R: Read>(&mut self, reader: &mut R) -> Result<Schema> {
+        let json = read_header_schema(reader)?;
+        if let Some(schema) = self
+            .fingerprints
+            .get(&json)
+            .and_then(|fingerprint| self.schemas.get(fingerprint))
+        {
+            return Ok(schema.clone());
+        }
+        let schema = avro_schema_to_arrow(
+            &AvroSchema::parse_reader(&mut json.as_slice())?,
+            self.union_representation,
+            self.name_collision_policy,
+            self.enum_symbols,
+        )?;
+        let fingerprint = self.format.schema_fingerprint(&schema);
+        match self.schemas.get(&fingerprint) {
+            Some(interned) if *interned == schema => {
+                self.fingerprints.insert(json, fingerprint);
+                Ok(interned.clone())
+            }
+            // The rare schema colliding with another one isn't interned
+            Some(_) => Ok(schema),
+            None => {
+                self.fingerprints.insert(json, fingerprint);
+                self.schemas.insert(fingerprint, schema.clone());
+                Ok(schema)
+            }
+        }
+    }
+
+    /// Returns the number of distinct Avro schemas parsed
+    #[cfg(test)]
+    pub(crate) fn num_parsed(&self) -> usize {
+        self.fingerprints.len()
+    }
+
+    /// Returns the number of distinct Arrow schemas parsed
+    #[cfg(test)]
+    pub(crate) fn num_distinct(&self) -> usize {
+        self.schemas.len()
+    }
+}
+
+/// Returns the `avro.schema` entry of the metadata of the header of the Avro
+/// file of `reader`, failing with the errors of [`apache_avro::Reader`]
+fn read_header_schema<R: Read>(reader: &mut R) -> Result<Vec<u8>> {
+    let mut magic = [0; MAGIC.len()];
+    reader
+        .read_exact(&mut magic)
+        .map_err(AvroError::ReadHeader)?;
+    if magic != MAGIC {
+        return Err(AvroError::HeaderMagic.into());
+    }
+    let metadata_schema = AvroSchema::map(AvroSchema::Bytes);
+    let Value::Map(mut metadata) = from_avro_datum(&metadata_schema, reader, None)?
+    else {
+        return Err(AvroError::GetHeaderMetadata.into());
+    };
+    match metadata.remove("avro.schema") {
+        Some(Value::Bytes(json)) => Ok(json),
+        _ => Err(AvroError::GetAvroSchemaFromMap.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::avro_to_arrow::test_util::avro_file;
+    use crate::AvroFormat;
+    use arrow::datatypes::{DataType, Field};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_identical_schemas_parsed_once() {
+        let record = |field_type: &str| {
+            format!(
+                r#"{{"type": "record", "name": "r", "fields": [{{"name": "a", "type": "{field_type}"}}]}}"#
+            )
+        };
+        let long_file = avro_file(&record("long"), []);
+        let string_file = avro_file(&record("string"), []);
+
+        let format = AvroFormat::default();
+        let mut interner = SchemaInterner::new(
+            &format,
+            UnionRepresentation::default(),
+            Default::default(),
+            false,
+        );
+        for index in 0..100 {
+            let file = if index % 10 == 0 {
+                &string_file
+            } else {
+                &long_file
+            };
+            let schema = interner.read_schema(&mut file.as_slice()).unwrap();
+            let expected = if index % 10 == 0 {
+                DataType::Utf8
+            } else {
+                DataType::Int64
+            };
+            assert_eq!(schema, Schema::new(vec![Field::new("a", expected, false)]));
+        }
+        assert_eq!(interner.num_parsed(), 2);
+        assert_eq!(interner.num_distinct(), 2);
+    }
+
+    #[test]
+    fn test_equivalent_schemas_interned_once() {
+        let format = AvroFormat::default();
+        let mut interner = SchemaInterner::new(
+            &format,
+            UnionRepresentation::default(),
+            Default::default(),
+            false,
+        );
+        for schema in [
+            r#"{"type": "record", "name": "r", "fields": [{"name": "a", "type": "long"}]}"#,
+            r#"{"type": "record", "name": "r", "doc": "d", "fields": [{"name": "a", "type": "long"}]}"#,
+        ] {
+            interner
+                .read_schema(&mut avro_file(schema, []).as_slice())
+                .unwrap();
+        }
+        assert_eq!(interner.num_parsed(), 2);
+        assert_eq!(interner.num_distinct(), 1);
+    }
+
+    #[test]
+    fn test_schema_fingerprint() {
+        let format = AvroFormat::default();
+        let schema = |a_type: DataType, metadata: &[(&str, &str)]| {
+            let metadata = metadata
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>();
+            Schema::new(vec![
+                Field::new("a", a_type, true).with_metadata(metadata.clone()),
+                Field::new("b", DataType::Utf8, false),
+            ])
+            .with_metadata(metadata)
+        };
+        let fingerprint = |schema: &Schema| format.schema_fingerprint(schema);
+
+        let metadata = [("k1", "v1"), ("k2", "v2"), ("k3", "v3")];
+        let reversed = [("k3", "v3"), ("k2", "v2"), ("k1", "v1")];
+        assert_eq!(
+            fingerprint(&schema(DataType::Int64, &metadata)),
+            fingerprint(&schema(DataType::Int64, &reversed))
+        );
+        assert_ne!(
+            fingerprint(&schema(DataType::Int64, &metadata)),
+            fingerprint(&schema(DataType::Int32, &metadata))
+        );
+        assert_ne!(
+            fingerprint(&schema(DataType::Int64, &metadata)),
+            fingerprint(&schema(DataType::Int64, &metadata[..2]))
+        );
+    }
+
+    #[test]
+    fn test_not_avro_file() {
+        let format = AvroFormat::default();
+        let mut interner = SchemaInterner::new(
+            &format,
+            UnionRepresentation::default(),
+            Default::default(),
+            false,
+        );
+        let err = interner
+            .read_schema(&mut b"PAR1....".as_slice())
+            .unwrap_err();
+        assert!(err.to_string().contains("wrong magic in header"));
+    }
+}
@@ -0,0 +1,232 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Dictionary encoding of Avro string columns
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Read;
+
+use apache_avro::types::Value;
+use arrow::datatypes::{DataType, Field, Schema};
+use datafusion_common::Result;
+
+/// How a top level Avro string column is decoded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StringEncoding {
+    /// Decode the column as plain `Utf8`
+    #[default]
+    Plain,
+    /// Decode the column as `Dictionary(Int32, Utf8)`, interning its values
+    /// while decoding
+    Dictionary,
+    /// Decode the column as `Dictionary(Int32, Utf8)` if it holds at most
+    /// `max_cardinality` distinct values, and as plain `Utf8` otherwise.
+    ///
+    /// The distinct values are counted over all the records when the schema
+    /// is inferred.
+    Auto {
+        /// The maximum number of distinct values of a dictionary encoded
+        /// column
+        max_cardinality: usize,
+    },
+}
+
+/// The data type of dictionary encoded string columns
+fn dictionary_type() -> DataType {
+    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+}
+
+/// Counts the distinct values of the columns with a [`StringEncoding::Auto`]
+/// encoding, up to their `max_cardinality`
+#[derive(Debug, Default)]
+pub(crate) struct StringCardinalities {
+    /// The distinct values of each column, or `None` once the column holds
+    /// more than its `max_cardinality` distinct values
+    columns: HashMap<String, (usize, Option<HashSet<String>>)>,
+}
+
+impl StringCardinalities {
+    pub(crate) fn new(encodings: &BTreeMap<String, StringEncoding>) -> Self {
+        let columns = encodings
+            .iter()
+            .filter_map(|(name, encoding)| match encoding {
+                StringEncoding::Auto { max_cardinality } => {
+                    Some((name.clone(), (*max_cardinality, Some(HashSet::new()))))
+                }
+                _ => None,
+            })
+            .collect();
+        Self { columns }
+    }
+
+    /// Returns true if no column needs its distinct values counted
+    pub(crate) fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Counts the distinct values of the records of the Avro file `reader`,
+    /// stopping early once every column exceeds its `max_cardinality`
+    pub(crate) fn update<R: Read>(&mut self, reader: R) -> Result<()> {
+        for record in apache_avro::Reader::new(reader)? {
+            let Value::Record(fields) = record? else {
+                continue;
+            };
+            for (name, value) in &fields {
+                let Some((max_cardinality, distinct)) = self.columns.get_mut(name) else {
+                    continue;
+                };
+                let (Some(values), Some(value)) =
+                    (distinct.as_mut(), string_value(value))
+                else {
+                    continue;
+                };
+                if !values.contains(value) {
+                    values.insert(value.to_string());
+                }
+                if values.len() > *max_cardinality {
+                    *distinct = None;
+                }
+            }
+            if self.columns.values().all(|(_, values)| values.is_none()) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `schema` with its string columns decoded according to
+    /// `encodings`
+    pub(crate) fn apply(
+        &self,
+        schema: Schema,
+        encodings: &BTreeMap<String, StringEncoding>,
+    ) -> Schema {
+        if encodings.is_empty() {
+            return schema;
+        }
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let dictionary = field.data_type() == &DataType::Utf8
+                    && match encodings.get(field.name()) {
+                        None | Some(StringEncoding::Plain) => false,
+                        Some(StringEncoding::Dictionary) => true,
+                        Some(StringEncoding::Auto { .. }) => self
+                            .columns
+                            .get(field.name())
+                            .is_some_and(|(_, values)| values.is_some()),
+                    };
+                if dictionary {
+                    Field::clone(field).with_data_type(dictionary_type())
+                } else {
+                    Field::clone(field)
+                }
+            })
+            .collect::<Vec<_>>();
+        Schema::new_with_metadata(fields, schema.metadata().clone())
+    }
+}
+
+fn string_value(value: &Value) -> Option<&str> {
+    match value {
+        Value::Union(_, value) => string_value(value),
+        Value::String(s) | Value::Enum(_, s) => Some(s),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avro_to_arrow::test_util::{avro_file, record};
+    use crate::avro_to_arrow::ReaderBuilder;
+
+    fn status_file(values: impl Iterator<Item = String>) -> Vec<u8> {
+        let schema = r#"
+            {
+              "type": "record",
+              "name": "r1",
+              "fields": [
+                { "name": "status", "type": "string" },
+                { "name": "id", "type": "long" }
+              ]
+            }"#;
+        avro_file(
+            schema,
+            values.enumerate().map(|(id, value)| {
+                record(vec![
+                    ("status", Value::String(value)),
+                    ("id", Value::Long(id as i64)),
+                ])
+            }),
+        )
+    }
+
+    fn read_status_type(bytes: Vec<u8>, encoding: StringEncoding) -> DataType {
+        let reader = ReaderBuilder::new()
+            .read_schema()
+            .with_string_encoding("status", encoding)
+            .with_batch_size(1000)
+            .build(std::io::Cursor::new(bytes))
+            .unwrap();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 100);
+        let data_type = batches[0].column(0).data_type().clone();
+        assert_eq!(batches[0].schema().field(0).data_type(), &data_type);
+        data_type
+    }
+
+    #[test]
+    fn test_low_cardinality_dictionary() {
+        let statuses = ["ok", "not found", "error"];
+        let low_cardinality =
+            || (0..100).map(|i| statuses[i % statuses.len()].to_string());
+
+        let data_type = read_status_type(
+            status_file(low_cardinality()),
+            StringEncoding::Auto { max_cardinality: 3 },
+        );
+        assert_eq!(data_type, dictionary_type());
+
+        let data_type =
+            read_status_type(status_file(low_cardinality()), StringEncoding::Dictionary);
+        assert_eq!(data_type, dictionary_type());
+
+        let data_type =
+            read_status_type(status_file(low_cardinality()), StringEncoding::Plain);
+        assert_eq!(data_type, DataType::Utf8);
+    }
+
+    #[test]
+    fn test_high_cardinality_plain() {
+        let high_cardinality = || (0..100).map(|i| format!("status {i}"));
+
+        let data_type = read_status_type(
+            status_file(high_cardinality()),
+            StringEncoding::Auto {
+                max_cardinality: 10,
+            },
+        );
+        assert_eq!(data_type, DataType::Utf8);
+
+        // Forcing the dictionary encoding does not count the distinct values
+        let data_type =
+            read_status_type(status_file(high_cardinality()), StringEncoding::Dictionary);
+        assert_eq!(data_type, dictionary_type());
+    }
+}
@@ -0,0 +1,44 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Avro files written by the tests of the Avro reader
+
+use apache_avro::types::Value;
+
+/// Writes an Avro object container file of a single block holding
+/// `records`, of the Avro schema whose JSON is `schema`
+pub(crate) fn avro_file(
+    schema: &str,
+    records: impl IntoIterator<Item = Value>,
+) -> Vec<u8> {
+    let schema = apache_avro::Schema::parse_str(schema).unwrap();
+    let mut writer = apache_avro::Writer::new(&schema, vec![]);
+    for record in records {
+        writer.append(record).unwrap();
+    }
+    writer.into_inner().unwrap()
+}
+
+/// Returns a record with the `fields` of the given names and values
+pub(crate) fn record(fields: Vec<(&str, Value)>) -> Value {
+    Value::Record(
+        fields
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+    )
+}
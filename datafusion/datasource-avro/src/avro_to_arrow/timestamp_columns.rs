@@ -0,0 +1,177 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Decoding of plain `long` columns as timestamps
+
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use datafusion_common::{plan_err, Result};
+
+/// The precision of the epoch timestamps stored in `long` columns without
+/// a logical type, see [`ReaderBuilder::with_timestamp_columns`]
+///
+/// [`ReaderBuilder::with_timestamp_columns`]: super::ReaderBuilder::with_timestamp_columns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimestampPrecision {
+    /// Milliseconds since the Unix epoch
+    Millisecond,
+    /// Microseconds since the Unix epoch
+    #[default]
+    Microsecond,
+}
+
+impl TimestampPrecision {
+    fn time_unit(self) -> TimeUnit {
+        match self {
+            Self::Millisecond => TimeUnit::Millisecond,
+            Self::Microsecond => TimeUnit::Microsecond,
+        }
+    }
+}
+
+/// Returns `schema` with its top level `columns` decoded as timestamps of
+/// `precision` in `timezone`, failing if one of the columns is not a
+/// `long`
+pub(crate) fn apply_timestamp_columns(
+    schema: Schema,
+    columns: &[String],
+    precision: TimestampPrecision,
+    timezone: Option<&Arc<str>>,
+) -> Result<Schema> {
+    if columns.is_empty() {
+        return Ok(schema);
+    }
+    for column in columns {
+        let Ok(field) = schema.field_with_name(column) else {
+            return plan_err!("Avro timestamp column {column} does not exist");
+        };
+        if field.data_type() != &DataType::Int64 {
+            return plan_err!(
+                "Avro timestamp column {column} must be a long, found {}",
+                field.data_type()
+            );
+        }
+    }
+    let data_type = DataType::Timestamp(precision.time_unit(), timezone.cloned());
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            if columns.contains(field.name()) {
+                Field::clone(field).with_data_type(data_type.clone())
+            } else {
+                Field::clone(field)
+            }
+        })
+        .collect::<Vec<_>>();
+    Ok(Schema::new_with_metadata(fields, schema.metadata().clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avro_to_arrow::test_util::{avro_file, record};
+    use crate::avro_to_arrow::ReaderBuilder;
+
+    use apache_avro::types::Value;
+    use arrow::array::AsArray;
+    use arrow::datatypes::TimestampMillisecondType;
+    use std::io::Cursor;
+
+    fn created_at_file() -> Vec<u8> {
+        let schema = r#"
+            {
+              "type": "record",
+              "name": "r1",
+              "fields": [
+                { "name": "id", "type": "long" },
+                { "name": "created_at", "type": ["null", "long"] },
+                { "name": "name", "type": "string" }
+              ]
+            }"#;
+        avro_file(
+            schema,
+            (0..3).map(|id| {
+                record(vec![
+                    ("id", Value::Long(id)),
+                    (
+                        "created_at",
+                        Value::Union(1, Box::new(Value::Long(1_700_000_000_000 + id))),
+                    ),
+                    ("name", Value::String(format!("name_{id}"))),
+                ])
+            }),
+        )
+    }
+
+    #[test]
+    fn test_long_as_timestamp() -> Result<()> {
+        let mut reader = ReaderBuilder::new()
+            .read_schema()
+            .with_timestamp_columns(
+                vec!["created_at".to_string()],
+                TimestampPrecision::Millisecond,
+            )
+            .with_timestamp_timezone("UTC")
+            .build(Cursor::new(created_at_file()))?;
+
+        let expected = DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()));
+        let schema = reader.schema();
+        assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+        assert_eq!(schema.field(1).data_type(), &expected);
+        assert!(schema.field(1).is_nullable());
+
+        let batch = reader.next().unwrap()?;
+        assert_eq!(batch.column(1).data_type(), &expected);
+        let values = batch.column(1).as_primitive::<TimestampMillisecondType>();
+        assert_eq!(
+            values.values().to_vec(),
+            vec![1_700_000_000_000, 1_700_000_000_001, 1_700_000_000_002]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_timestamp_column_not_long() {
+        let err = ReaderBuilder::new()
+            .read_schema()
+            .with_timestamp_columns(
+                vec!["name".to_string()],
+                TimestampPrecision::Microsecond,
+            )
+            .build(Cursor::new(created_at_file()))
+            .err()
+            .unwrap();
+        assert!(err
+            .to_string()
+            .contains("Avro timestamp column name must be a long, found Utf8"));
+
+        let err = ReaderBuilder::new()
+            .read_schema()
+            .with_timestamp_columns(
+                vec!["missing".to_string()],
+                TimestampPrecision::Microsecond,
+            )
+            .build(Cursor::new(created_at_file()))
+            .err()
+            .unwrap();
+        assert!(err
+            .to_string()
+            .contains("Avro timestamp column missing does not exist"));
+    }
+}
@@ -0,0 +1,494 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`AvroBlockStream`]: decodes an Avro file one block at a time
+
+use std::io::Cursor;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use bytes::{Bytes, BytesMut};
+use datafusion_common::{exec_err, DataFusionError, Result};
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::ObjectStore;
+
+use crate::avro_to_arrow::{to_arrow_schema, Reader};
+use crate::encoding::{parse_header, read_long};
+
+/// Magic bytes at the start of an Avro object container file
+pub(crate) const MAGIC: &[u8] = b"Obj\x01";
+
+/// Length of the sync marker following the header and each block
+pub(crate) const SYNC_LENGTH: usize = 16;
+
+/// Metadata of a block of an Avro object container file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AvroBlockMetadata {
+    /// Byte offset of the block in the file
+    pub offset: u64,
+    /// Number of records in the block
+    pub record_count: u64,
+    /// Size in bytes of the serialized, possibly compressed, records
+    pub compressed_size: u64,
+    /// Compression codec of the file, such as `null` or `deflate`
+    pub codec: String,
+}
+
+/// A stream of the blocks of an Avro object container file, each decoded
+/// into a single [`RecordBatch`] along with its [`AvroBlockMetadata`]
+///
+/// Unlike [`Reader`], the records of a block are neither split nor merged
+/// into batches of a configured size, which lets consumers map the batches
+/// back to the blocks of the file.
+pub struct AvroBlockStream {
+    /// Arrow schema of the file
+    file_schema: SchemaRef,
+    /// Arrow schema of the decoded batches
+    schema: SchemaRef,
+    /// Columns to decode, all the columns if `None`
+    projection: Option<Vec<String>>,
+    /// Compression codec of the file
+    codec: String,
+    /// The decoder of the blocks, until the stream is first polled
+    decoder: Option<BlockDecoder>,
+    /// The decoded blocks, once the stream is first polled
+    blocks: Option<BoxStream<'static, Result<(AvroBlockMetadata, RecordBatch)>>>,
+}
+
+impl AvroBlockStream {
+    /// Create a new `AvroBlockStream` over the bytes of an Avro file,
+    /// reading its header
+    pub async fn try_new(input: BoxStream<'static, Result<Bytes>>) -> Result<Self> {
+        let mut decoder = BlockDecoder {
+            input,
+            buffer: BytesMut::new(),
+            position: 0,
+            header: Bytes::new(),
+            sync: [0; SYNC_LENGTH],
+        };
+        let (file_schema, codec) = decoder.read_header().await?;
+        let file_schema = Arc::new(file_schema);
+        Ok(Self {
+            schema: Arc::clone(&file_schema),
+            file_schema,
+            projection: None,
+            codec,
+            decoder: Some(decoder),
+            blocks: None,
+        })
+    }
+
+    /// Create a new `AvroBlockStream` over the Avro file at `location` in
+    /// `store`
+    pub async fn open(store: &dyn ObjectStore, location: &Path) -> Result<Self> {
+        let input = store
+            .get(location)
+            .await?
+            .into_stream()
+            .map_err(DataFusionError::from)
+            .boxed();
+        Self::try_new(input).await
+    }
+
+    /// Only decode the columns named in `projection`
+    pub fn with_projection(mut self, projection: Vec<String>) -> Self {
+        self.schema = Arc::new(Schema::new(
+            projection
+                .iter()
+                .filter_map(|name| {
+                    self.file_schema
+                        .column_with_name(name)
+                        .map(|(_, f)| f.clone())
+                })
+                .collect::<Vec<_>>(),
+        ));
+        self.projection = Some(projection);
+        self
+    }
+
+    /// Arrow schema of the decoded batches
+    pub fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    /// Arrow schema of the file, before projection
+    pub fn file_schema(&self) -> SchemaRef {
+        Arc::clone(&self.file_schema)
+    }
+
+    /// Compression codec of the file, such as `null` or `deflate`
+    pub fn codec(&self) -> &str {
+        &self.codec
+    }
+
+    /// Returns the number of records of the file, read from the headers of
+    /// its blocks without decoding them
+    pub async fn count_records(mut self) -> Result<u64> {
+        let Some(mut decoder) = self.decoder.take() else {
+            return exec_err!("The blocks of the Avro file are already decoded");
+        };
+        let mut record_count = 0;
+        while let Some((_, count, _, _)) = decoder.read_block().await? {
+            record_count += count;
+        }
+        Ok(record_count)
+    }
+
+    /// Returns the stream of decoded blocks, created on the first call
+    fn blocks(
+        &mut self,
+    ) -> &mut BoxStream<'static, Result<(AvroBlockMetadata, RecordBatch)>> {
+        if let Some(decoder) = self.decoder.take() {
+            let file_schema = Arc::clone(&self.file_schema);
+            let schema = Arc::clone(&self.schema);
+            let projection = self.projection.clone();
+            let codec = self.codec.clone();
+            self.blocks = Some(
+                futures::stream::try_unfold(decoder, move |mut decoder| {
+                    let file_schema = Arc::clone(&file_schema);
+                    let schema = Arc::clone(&schema);
+                    let projection = projection.clone();
+                    let codec = codec.clone();
+                    async move {
+                        let Some((offset, record_count, compressed_size, block)) =
+                            decoder.read_block().await?
+                        else {
+                            return Ok(None);
+                        };
+                        let batch = decode_block(
+                            &decoder.header,
+                            &block,
+                            record_count,
+                            file_schema,
+                            schema,
+                            projection,
+                        )?;
+                        let metadata = AvroBlockMetadata {
+                            offset,
+                            record_count,
+                            compressed_size,
+                            codec,
+                        };
+                        Ok(Some(((metadata, batch), decoder)))
+                    }
+                })
+                .boxed(),
+            );
+        }
+        self.blocks
+            .get_or_insert_with(|| futures::stream::empty().boxed())
+    }
+}
+
+impl Stream for AvroBlockStream {
+    type Item = Result<(AvroBlockMetadata, RecordBatch)>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.blocks().poll_next_unpin(cx)
+    }
+}
+
+impl std::fmt::Debug for AvroBlockStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AvroBlockStream")
+            .field("schema", &self.schema)
+            .field("projection", &self.projection)
+            .field("codec", &self.codec)
+            .finish()
+    }
+}
+
+/// Splits the bytes of an Avro object container file into its header and
+/// blocks
+struct BlockDecoder {
+    input: BoxStream<'static, Result<Bytes>>,
+    /// Bytes read from `input` and not consumed yet
+    buffer: BytesMut,
+    /// Offset in the file of the start of `buffer`
+    position: u64,
+    /// The header of the file, including its sync marker
+    header: Bytes,
+    sync: [u8; SYNC_LENGTH],
+}
+
+impl BlockDecoder {
+    /// Reads from `input` until at least `len` bytes are buffered, returning
+    /// false if the file ends before
+    async fn fill(&mut self, len: usize) -> Result<bool> {
+        while self.buffer.len() < len {
+            match self.input.next().await.transpose()? {
+                Some(bytes) => self.buffer.extend_from_slice(&bytes),
+                None => return Ok(false),
+            }
+        }
+        Ok(true)
+    }
+
+    /// Reads `len` bytes at offset `start` of the buffer
+    async fn read_exact(&mut self, start: usize, len: usize) -> Result<&[u8]> {
+        if !self.fill(start + len).await? {
+            return exec_err!(
+                "Unexpected end of Avro file at offset {}",
+                self.position + self.buffer.len() as u64
+            );
+        }
+        Ok(&self.buffer[start..start + len])
+    }
+
+    /// Reads more bytes from `input`, failing if the file ends before
+    async fn fill_more(&mut self) -> Result<()> {
+        if !self.fill(self.buffer.len() + 1).await? {
+            return exec_err!(
+                "Unexpected end of Avro file at offset {}",
+                self.position + self.buffer.len() as u64
+            );
+        }
+        Ok(())
+    }
+
+    /// Reads a zigzag encoded long at offset `start` of the buffer, returning
+    /// it along with its encoded length
+    async fn read_long(&mut self, start: usize) -> Result<(i64, usize)> {
+        loop {
+            if let Some(long) = read_long(&self.buffer, start, self.position)? {
+                return Ok(long);
+            }
+            self.fill_more().await?;
+        }
+    }
+
+    /// Consumes `len` bytes of the buffer
+    fn consume(&mut self, len: usize) -> Bytes {
+        self.position += len as u64;
+        self.buffer.split_to(len).freeze()
+    }
+
+    /// Reads the header of the file: its metadata and sync marker, returning
+    /// the Arrow schema and the compression codec of the file
+    async fn read_header(&mut self) -> Result<(Schema, String)> {
+        let (schema, codec, len) = loop {
+            if let Some(header) = parse_header(&self.buffer)? {
+                self.sync = header.sync;
+                break (
+                    header.get("avro.schema").map(<[u8]>::to_vec),
+                    header.get("avro.codec").map(<[u8]>::to_vec),
+                    header.len,
+                );
+            }
+            self.fill_more().await?;
+        };
+
+        let (schema, codec) = header_schema_and_codec(schema, codec)?;
+        self.header = self.consume(len);
+        Ok((schema, codec))
+    }
+
+    /// Reads the next block of the file, returning its offset, record count,
+    /// compressed size and bytes, including the sync marker
+    async fn read_block(&mut self) -> Result<Option<(u64, u64, u64, Bytes)>> {
+        if !self.fill(1).await? {
+            return Ok(None);
+        }
+        let offset = self.position;
+        let (record_count, count_size) = self.read_long(0).await?;
+        let (compressed_size, size_size) = self.read_long(count_size).await?;
+        let (Ok(record_count), Ok(compressed_size)) = (
+            u64::try_from(record_count),
+            usize::try_from(compressed_size),
+        ) else {
+            return exec_err!("Invalid Avro block at offset {offset}");
+        };
+        let data_end = count_size + size_size + compressed_size;
+        let sync = self.sync;
+        if self.read_exact(data_end, SYNC_LENGTH).await? != sync {
+            return exec_err!("Invalid sync marker after Avro block at offset {offset}");
+        }
+
+        let block = self.consume(data_end + SYNC_LENGTH);
+        Ok(Some((offset, record_count, compressed_size as u64, block)))
+    }
+}
+
+/// Returns the Arrow schema and the compression codec of a file from the
+/// `avro.schema` and `avro.codec` entries of its header
+pub(crate) fn header_schema_and_codec(
+    schema: Option<Vec<u8>>,
+    codec: Option<Vec<u8>>,
+) -> Result<(Schema, String)> {
+    let Some(schema) = schema else {
+        return exec_err!("Avro file header has no schema");
+    };
+    let schema =
+        to_arrow_schema(&apache_avro::Schema::parse_reader(&mut schema.as_slice())?)?;
+    let codec = match codec {
+        Some(codec) => String::from_utf8(codec)
+            .map_err(|e| DataFusionError::External(Box::new(e)))?,
+        None => "null".to_string(),
+    };
+    Ok((schema, codec))
+}
+
+/// Decodes the `record_count` records of `block`, a block of the file with
+/// the given `header`, into a batch of `schema`
+pub(crate) fn decode_block(
+    header: &[u8],
+    block: &[u8],
+    record_count: u64,
+    file_schema: SchemaRef,
+    schema: SchemaRef,
+    projection: Option<Vec<String>>,
+) -> Result<RecordBatch> {
+    // Decode the block as a file of a single block
+    let mut file = Vec::with_capacity(header.len() + block.len());
+    file.extend_from_slice(header);
+    file.extend_from_slice(block);
+    let batch_size = usize::try_from(record_count)
+        .map_err(|e| DataFusionError::External(Box::new(e)))?
+        .max(1);
+    let mut reader =
+        Reader::try_new(Cursor::new(file), file_schema, batch_size, projection)?;
+    Ok(match reader.next().transpose()? {
+        Some(batch) => batch,
+        None => RecordBatch::new_empty(schema),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use apache_avro::types::Value;
+    use apache_avro::Codec;
+    use arrow::array::{AsArray, Int64Array};
+    use arrow::datatypes::Int64Type;
+    use object_store::memory::InMemory;
+
+    /// Writes an Avro file of 1000 records in blocks of 100 records
+    fn write_file(codec: Codec) -> Vec<u8> {
+        let schema = apache_avro::Schema::parse_str(
+            r#"{"type": "record", "name": "r", "fields": [
+                {"name": "id", "type": "long"},
+                {"name": "name", "type": "string"}
+            ]}"#,
+        )
+        .unwrap();
+        let mut writer = apache_avro::Writer::with_codec(&schema, vec![], codec);
+        for id in 0..1000 {
+            writer
+                .append(Value::Record(vec![
+                    ("id".to_string(), Value::Long(id)),
+                    ("name".to_string(), Value::String(format!("name_{id}"))),
+                ]))
+                .unwrap();
+            if id % 100 == 99 {
+                writer.flush().unwrap();
+            }
+        }
+        writer.into_inner().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_block_stream() -> Result<()> {
+        let store = InMemory::new();
+        let location = Path::from("data.avro");
+        store.put(&location, write_file(Codec::Null).into()).await?;
+
+        let stream = AvroBlockStream::open(&store, &location).await?;
+        assert_eq!(stream.codec(), "null");
+        assert_eq!(stream.schema().fields().len(), 2);
+        let blocks: Vec<_> = stream.try_collect().await?;
+
+        assert_eq!(blocks.len(), 10);
+        for window in blocks.windows(2) {
+            assert!(window[0].0.offset < window[1].0.offset);
+        }
+        let mut record_count = 0;
+        for (metadata, batch) in &blocks {
+            assert_eq!(metadata.codec, "null");
+            assert_eq!(batch.num_rows() as u64, metadata.record_count);
+            record_count += metadata.record_count;
+        }
+        assert_eq!(record_count, 1000);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_block_stream_compressed_chunked_input() -> Result<()> {
+        let data = write_file(Codec::Deflate);
+        // Feed the file in small chunks, splitting the header and the blocks
+        let chunks = data
+            .chunks(7)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect::<Vec<_>>();
+        let stream = AvroBlockStream::try_new(futures::stream::iter(chunks).boxed())
+            .await?
+            .with_projection(vec!["id".to_string()]);
+        assert_eq!(stream.codec(), "deflate");
+        assert_eq!(stream.schema().fields().len(), 1);
+        let blocks: Vec<_> = stream.try_collect().await?;
+
+        assert_eq!(blocks.len(), 10);
+        let ids = blocks
+            .iter()
+            .flat_map(|(_, batch)| {
+                batch
+                    .column(0)
+                    .as_primitive::<Int64Type>()
+                    .values()
+                    .to_vec()
+            })
+            .collect::<Int64Array>();
+        assert_eq!(ids, (0..1000).collect::<Int64Array>());
+        assert!(blocks
+            .iter()
+            .all(|(metadata, _)| metadata.codec == "deflate"
+                && metadata.compressed_size < data.len() as u64));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_count_records() -> Result<()> {
+        let store = InMemory::new();
+        let location = Path::from("data.avro");
+        store
+            .put(&location, write_file(Codec::Deflate).into())
+            .await?;
+
+        let stream = AvroBlockStream::open(&store, &location).await?;
+        assert_eq!(stream.count_records().await?, 1000);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_block_stream_invalid_file() {
+        let input = futures::stream::iter(vec![Ok(Bytes::from_static(b"PAR1"))]).boxed();
+        let err = AvroBlockStream::try_new(input).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Not an Avro object container file"));
+    }
+}
@@ -0,0 +1,306 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A pool of the scratch buffers of Avro decoders, shared by the files of
+//! scans
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use apache_avro::types::Value;
+
+/// The records of a batch being decoded
+pub(crate) type RecordBuffer = Vec<Vec<(String, Value)>>;
+
+/// Byte buffers with a larger capacity are not kept by the pool, so that a
+/// single large file doesn't hold its memory for the lifetime of the pool
+const MAX_POOLED_BYTES_CAPACITY: usize = 64 * 1024 * 1024;
+
+/// The scratch buffers of the decoders of Avro files, reused across the
+/// files of the scans using the pool, see [`AvroSource::with_decode_buffer_pool`]
+///
+/// Each file otherwise allocates its own buffers: the buffer holding the
+/// records of the batch being decoded, and the buffer holding the whole
+/// decompressed file for compressed files. Scanning many small files
+/// concurrently then spends a significant share of its time in the
+/// allocator. A decoder takes its buffers from the pool when its file is
+/// opened and returns them, cleared, once the file is decoded.
+///
+/// The pool keeps at most `max_idle_buffers` buffers of each kind, which
+/// bounds the memory it holds once the scans are done, and can be shared by
+/// any number of scans and threads.
+///
+/// [`AvroSource::with_decode_buffer_pool`]: crate::source::AvroSource::with_decode_buffer_pool
+pub struct DecodeBufferPool {
+    records: Mutex<Vec<RecordBuffer>>,
+    bytes: Mutex<Vec<Vec<u8>>>,
+    max_idle_buffers: usize,
+    /// Number of buffers allocated because none was idle
+    allocated: AtomicUsize,
+    /// Number of idle buffers taken from the pool
+    reused: AtomicUsize,
+}
+
+impl DecodeBufferPool {
+    /// Creates a pool keeping at most `max_idle_buffers` buffers of each
+    /// kind, typically the number of files decoded at once
+    pub fn new(max_idle_buffers: usize) -> Self {
+        Self {
+            records: Mutex::new(vec![]),
+            bytes: Mutex::new(vec![]),
+            max_idle_buffers,
+            allocated: AtomicUsize::new(0),
+            reused: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the maximum number of idle buffers of each kind
+    pub fn max_idle_buffers(&self) -> usize {
+        self.max_idle_buffers
+    }
+
+    /// Returns the number of buffers allocated because no idle buffer was
+    /// available
+    pub fn buffers_allocated(&self) -> usize {
+        self.allocated.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of idle buffers reused by decoders
+    pub fn buffers_reused(&self) -> usize {
+        self.reused.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of buffers currently idle in the pool
+    pub fn idle_buffers(&self) -> usize {
+        self.records.lock().unwrap().len() + self.bytes.lock().unwrap().len()
+    }
+
+    /// Takes an empty buffer for the records of batches of `batch_size`
+    /// records
+    pub(crate) fn take_records(&self, batch_size: usize) -> RecordBuffer {
+        let mut records = self.take(&self.records);
+        records.reserve(batch_size);
+        records
+    }
+
+    /// Returns a buffer taken by [`Self::take_records`]
+    pub(crate) fn put_records(&self, mut records: RecordBuffer) {
+        records.clear();
+        self.put(&self.records, records);
+    }
+
+    /// Takes an empty byte buffer, returned to the pool when dropped
+    pub(crate) fn take_bytes(self: &Arc<Self>) -> PooledBytes {
+        PooledBytes {
+            bytes: self.take(&self.bytes),
+            pool: Arc::clone(self),
+        }
+    }
+
+    fn take<T: Default>(&self, buffers: &Mutex<Vec<T>>) -> T {
+        match buffers.lock().unwrap().pop() {
+            Some(buffer) => {
+                self.reused.fetch_add(1, Ordering::Relaxed);
+                buffer
+            }
+            None => {
+                self.allocated.fetch_add(1, Ordering::Relaxed);
+                T::default()
+            }
+        }
+    }
+
+    fn put<T>(&self, buffers: &Mutex<Vec<T>>, buffer: T) {
+        let mut buffers = buffers.lock().unwrap();
+        if buffers.len() < self.max_idle_buffers {
+            buffers.push(buffer);
+        }
+    }
+}
+
+impl fmt::Debug for DecodeBufferPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodeBufferPool")
+            .field("max_idle_buffers", &self.max_idle_buffers)
+            .field("buffers_allocated", &self.buffers_allocated())
+            .field("buffers_reused", &self.buffers_reused())
+            .finish()
+    }
+}
+
+/// A byte buffer of a [`DecodeBufferPool`], returned to the pool when
+/// dropped
+pub(crate) struct PooledBytes {
+    bytes: Vec<u8>,
+    pool: Arc<DecodeBufferPool>,
+}
+
+impl PooledBytes {
+    pub(crate) fn as_mut_vec(&mut self) -> &mut Vec<u8> {
+        &mut self.bytes
+    }
+}
+
+impl AsRef<[u8]> for PooledBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Drop for PooledBytes {
+    fn drop(&mut self) {
+        let mut bytes = std::mem::take(&mut self.bytes);
+        if bytes.capacity() <= MAX_POOLED_BYTES_CAPACITY {
+            bytes.clear();
+            self.pool.put(&self.pool.bytes, bytes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::source::AvroSource;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::Result;
+    use datafusion_datasource::file_groups::FileGroup;
+    use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
+    use datafusion_datasource::source::DataSourceExec;
+    use datafusion_datasource::PartitionedFile;
+    use datafusion_execution::object_store::ObjectStoreUrl;
+    use datafusion_execution::TaskContext;
+    use datafusion_physical_plan::{collect, ExecutionPlan};
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+    use object_store::ObjectStore;
+    use std::thread;
+
+    #[test]
+    fn test_buffers_are_reset_and_reused() {
+        let pool = Arc::new(DecodeBufferPool::new(2));
+
+        let mut records = pool.take_records(16);
+        assert!(records.capacity() >= 16);
+        records.push(vec![("a".to_string(), Value::Long(1))]);
+        let ptr = records.as_ptr();
+        pool.put_records(records);
+
+        // The buffer is reused empty, and grown to larger batch sizes
+        let records = pool.take_records(16);
+        assert_eq!(records.as_ptr(), ptr);
+        assert!(records.is_empty());
+        pool.put_records(records);
+        let records = pool.take_records(32);
+        assert!(records.capacity() >= 32);
+        pool.put_records(records);
+
+        let mut bytes = pool.take_bytes();
+        bytes.as_mut_vec().extend_from_slice(b"avro");
+        drop(bytes);
+        let bytes = pool.take_bytes();
+        assert!(bytes.as_ref().is_empty());
+        assert!(bytes.bytes.capacity() >= 4);
+
+        assert_eq!(pool.buffers_allocated(), 2);
+        assert_eq!(pool.buffers_reused(), 3);
+        drop(bytes);
+        assert_eq!(pool.idle_buffers(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_scan_reuses_buffers_across_files() -> Result<()> {
+        let schema = apache_avro::Schema::parse_str(
+            r#"{"type": "record", "name": "r", "fields": [{"name": "id", "type": "long"}]}"#,
+        )
+        .unwrap();
+        let store = Arc::new(InMemory::new());
+        let mut files = vec![];
+        for file in 0..8 {
+            let mut writer = apache_avro::Writer::new(&schema, vec![]);
+            for id in 0..10 {
+                writer
+                    .append(Value::Record(vec![("id".to_string(), Value::Long(id))]))
+                    .unwrap();
+            }
+            let data = writer.into_inner().unwrap();
+            let path = format!("dir/{file}.avro");
+            files.push(PartitionedFile::new(&path, data.len() as u64));
+            store.put(&Path::from(path), data.into()).await?;
+        }
+        let task_ctx = Arc::new(TaskContext::default());
+        let url = ObjectStoreUrl::parse("memory://")?;
+        task_ctx
+            .runtime_env()
+            .register_object_store(url.as_ref(), store);
+
+        let pool = Arc::new(DecodeBufferPool::new(4));
+        let file_schema =
+            Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let source = AvroSource::new().with_decode_buffer_pool(Some(Arc::clone(&pool)));
+        let conf = FileScanConfigBuilder::new(url, file_schema, Arc::new(source))
+            .with_file_groups(vec![FileGroup::new(files)])
+            .with_batch_size(Some(4))
+            .build();
+        let exec: Arc<dyn ExecutionPlan> = DataSourceExec::from_data_source(conf);
+        let batches = collect(exec, task_ctx).await?;
+
+        let rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(rows, 80);
+        // The files are decoded one after the other, each with the buffer
+        // returned by the previous one
+        assert!(pool.buffers_allocated() <= 2, "{pool:?}");
+        assert_eq!(pool.buffers_allocated() + pool.buffers_reused(), 8);
+        assert_eq!(pool.idle_buffers(), pool.buffers_allocated());
+        Ok(())
+    }
+
+    #[test]
+    fn test_idle_buffers_are_bounded() {
+        let pool = Arc::new(DecodeBufferPool::new(1));
+        let buffers = (0..3).map(|_| pool.take_records(8)).collect::<Vec<_>>();
+        buffers.into_iter().for_each(|b| pool.put_records(b));
+        assert_eq!(pool.idle_buffers(), 1);
+
+        // Large byte buffers are not kept
+        let mut bytes = pool.take_bytes();
+        bytes.as_mut_vec().reserve(MAX_POOLED_BYTES_CAPACITY + 1);
+        drop(bytes);
+        assert_eq!(pool.idle_buffers(), 1);
+    }
+
+    #[test]
+    fn test_shared_across_threads() {
+        let pool = Arc::new(DecodeBufferPool::new(4));
+        let threads = (0..4)
+            .map(|_| {
+                let pool = Arc::clone(&pool);
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        let records = pool.take_records(8);
+                        assert!(records.is_empty());
+                        pool.put_records(records);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        threads.into_iter().for_each(|t| t.join().unwrap());
+        assert_eq!(pool.buffers_allocated() + pool.buffers_reused(), 400);
+        assert!(pool.buffers_allocated() <= 4);
+    }
+}
@@ -0,0 +1,231 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A thread pool decoding Avro files outside of the async runtime
+
+use std::fmt;
+use std::mem;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{plan_err, Result};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use tokio::sync::mpsc;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The batches of a file, decoded as they are iterated
+type Batches = Box<dyn Iterator<Item = Result<RecordBatch, ArrowError>> + Send>;
+
+/// A fixed number of threads decoding Avro files, see
+/// [`AvroSource::with_decode_pool`]
+///
+/// Decoding is CPU heavy: when it runs on the threads of the async runtime,
+/// it delays the I/O and the cancellation of the tasks sharing them. The
+/// files of scans using a pool are decoded on its threads instead, so that
+/// the runtime only waits for the decoded batches. A pool can be shared by
+/// any number of scans.
+///
+/// Each job of the pool decodes a single batch of a file. A file whose
+/// decoded batches wait for their consumer does not hold a thread, so that
+/// consumers reading several files at once, such as merges and joins, never
+/// wait for a thread held by a file they do not read yet.
+///
+/// The threads stop once the pool is dropped and their current batches are
+/// decoded.
+///
+/// [`AvroSource::with_decode_pool`]: crate::source::AvroSource::with_decode_pool
+pub struct DecodePool {
+    sender: Mutex<Sender<Job>>,
+    num_threads: usize,
+}
+
+impl DecodePool {
+    /// Starts a pool of `num_threads` threads
+    pub fn try_new(num_threads: usize) -> Result<Self> {
+        if num_threads == 0 {
+            return plan_err!("An Avro decode pool needs at least one thread");
+        }
+        let (sender, receiver) = channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for i in 0..num_threads {
+            let receiver = Arc::clone(&receiver);
+            thread::Builder::new()
+                .name(format!("avro-decode-{i}"))
+                .spawn(move || run(&receiver))?;
+        }
+        Ok(Self {
+            sender: Mutex::new(sender),
+            num_threads,
+        })
+    }
+
+    /// Returns the number of threads of the pool
+    pub fn num_threads(&self) -> usize {
+        self.num_threads
+    }
+
+    /// Runs `job` on the first idle thread of the pool
+    fn spawn(&self, job: impl FnOnce() + Send + 'static) {
+        // The threads only stop once the sender is dropped
+        let _ = self.sender.lock().unwrap().send(Box::new(job));
+    }
+
+    /// Returns the stream of `batches`, decoded on the threads of the pool
+    /// up to `capacity` batches ahead of the consumer
+    ///
+    /// Decoding starts once the stream is first polled, and stops after the
+    /// first error or once the stream is dropped.
+    pub(crate) fn decode(
+        self: &Arc<Self>,
+        batches: Batches,
+        capacity: usize,
+    ) -> BoxStream<'static, Result<RecordBatch, ArrowError>> {
+        let pool = Arc::clone(self);
+        futures::stream::once(async move {
+            let (tx, rx) = mpsc::channel(capacity.max(1));
+            let decoder = Arc::new(FileDecoder {
+                pool,
+                state: Mutex::new(DecoderState::Scheduled),
+            });
+            decoder.schedule(batches, tx);
+            futures::stream::unfold((rx, decoder), |(mut rx, decoder)| async move {
+                let batch = rx.recv().await?;
+                // Receiving the batch made room for the next one
+                decoder.resume();
+                Some((batch, (rx, decoder)))
+            })
+        })
+        .flatten()
+        .boxed()
+    }
+}
+
+/// The decoding of a file on a [`DecodePool`], one batch per job
+struct FileDecoder {
+    pool: Arc<DecodePool>,
+    state: Mutex<DecoderState>,
+}
+
+enum DecoderState {
+    /// The next batch is decoded by a job of the pool
+    Scheduled,
+    /// The channel is full, the next batch is decoded once the consumer
+    /// received a batch
+    Waiting(Batches, mpsc::Sender<Result<RecordBatch, ArrowError>>),
+    /// All batches were decoded, or the consumer is gone
+    Done,
+}
+
+impl FileDecoder {
+    /// Schedules decoding the next batch if the channel has room for it, and
+    /// waits for the consumer otherwise
+    fn schedule(
+        self: &Arc<Self>,
+        batches: Batches,
+        tx: mpsc::Sender<Result<RecordBatch, ArrowError>>,
+    ) {
+        // Checking for room while holding the state ensures that the
+        // consumer either sees the decoder waiting, or made room before
+        let mut state = self.state.lock().unwrap();
+        match tx.try_reserve_owned() {
+            Ok(permit) => {
+                *state = DecoderState::Scheduled;
+                drop(state);
+                let decoder = Arc::clone(self);
+                self.pool
+                    .spawn(move || decoder.decode_batch(batches, permit));
+            }
+            Err(mpsc::error::TrySendError::Full(tx)) => {
+                *state = DecoderState::Waiting(batches, tx);
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                *state = DecoderState::Done;
+            }
+        }
+    }
+
+    /// Schedules decoding the next batch if the decoder waits for room in
+    /// the channel
+    fn resume(self: &Arc<Self>) {
+        let mut state = self.state.lock().unwrap();
+        match mem::replace(&mut *state, DecoderState::Scheduled) {
+            DecoderState::Waiting(batches, tx) => {
+                drop(state);
+                self.schedule(batches, tx);
+            }
+            other => *state = other,
+        }
+    }
+
+    /// Decodes the next batch into `permit` and schedules the batch after it
+    fn decode_batch(
+        self: Arc<Self>,
+        mut batches: Batches,
+        permit: mpsc::OwnedPermit<Result<RecordBatch, ArrowError>>,
+    ) {
+        let (batch, is_last) = match catch_unwind(AssertUnwindSafe(|| batches.next())) {
+            Ok(Some(batch)) => {
+                let is_err = batch.is_err();
+                (batch, is_err)
+            }
+            Ok(None) => {
+                *self.state.lock().unwrap() = DecoderState::Done;
+                return;
+            }
+            Err(_) => {
+                let error = ArrowError::ComputeError(
+                    "Decoding the Avro file panicked".to_string(),
+                );
+                (Err(error), true)
+            }
+        };
+        let tx = permit.send(batch);
+        // Stop after the first error
+        if is_last {
+            *self.state.lock().unwrap() = DecoderState::Done;
+            return;
+        }
+        self.schedule(batches, tx);
+    }
+}
+
+impl fmt::Debug for DecodePool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodePool")
+            .field("num_threads", &self.num_threads)
+            .finish()
+    }
+}
+
+/// Runs the jobs received by a thread of the pool until the pool is dropped
+fn run(receiver: &Mutex<Receiver<Job>>) {
+    loop {
+        let job = receiver.lock().unwrap().recv();
+        let Ok(job) = job else {
+            return;
+        };
+        // A panicking job must not stop the thread. Jobs report their own
+        // panics, see `FileDecoder::decode_batch`
+        let _ = catch_unwind(AssertUnwindSafe(job));
+    }
+}
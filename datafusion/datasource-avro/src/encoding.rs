@@ -0,0 +1,282 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Decoding of the variable length integers and of the header of Avro
+//! object container files
+
+use std::io::Read;
+
+use datafusion_common::{exec_err, internal_datafusion_err, Result};
+
+use crate::block_stream::{MAGIC, SYNC_LENGTH};
+
+/// Maximum encoded length of an `int`
+pub(crate) const MAX_INT_LENGTH: usize = 5;
+
+/// Maximum encoded length of a `long`
+pub(crate) const MAX_LONG_LENGTH: usize = 10;
+
+/// A variable length integer decoded by [`decode_varint`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Varint {
+    /// The integer before zigzag decoding, and its encoded length
+    Value(u64, usize),
+    /// The bytes end before the last byte of the integer
+    Incomplete,
+    /// The integer is not terminated within its maximum length
+    Unterminated,
+    /// The integer overflows 64 bits
+    Overflow,
+}
+
+/// Decodes the variable length integer of at most `max_length` bytes at the
+/// start of `buf`
+pub(crate) fn decode_varint(buf: &[u8], max_length: usize) -> Varint {
+    let mut value: u64 = 0;
+    for index in 0..max_length {
+        let Some(&byte) = buf.get(index) else {
+            return Varint::Incomplete;
+        };
+        // The last byte of a long only holds the highest bit
+        if index == MAX_LONG_LENGTH - 1 && byte > 1 {
+            return Varint::Overflow;
+        }
+        value |= u64::from(byte & 0x7f) << (7 * index);
+        if byte & 0x80 == 0 {
+            return Varint::Value(value, index + 1);
+        }
+    }
+    Varint::Unterminated
+}
+
+/// Zigzag decodes a `long`
+pub(crate) fn zigzag_long(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+/// Reads a zigzag encoded long at offset `start` of `buf`, returning it
+/// along with its encoded length, or `None` if `buf` ends before. `position`
+/// is the offset of `buf` in the file.
+pub(crate) fn read_long(
+    buf: &[u8],
+    start: usize,
+    position: u64,
+) -> Result<Option<(i64, usize)>> {
+    match decode_varint(buf.get(start..).unwrap_or_default(), MAX_LONG_LENGTH) {
+        Varint::Value(value, len) => Ok(Some((zigzag_long(value), len))),
+        Varint::Incomplete => Ok(None),
+        Varint::Unterminated | Varint::Overflow => exec_err!(
+            "Invalid long in Avro file at offset {}",
+            position + start as u64
+        ),
+    }
+}
+
+/// Reads a length-prefixed sequence of bytes at offset `start` of `buf`,
+/// returning it along with its encoded length, or `None` if `buf` ends
+/// before. `position` is the offset of `buf` in the file.
+pub(crate) fn read_bytes(
+    buf: &[u8],
+    start: usize,
+    position: u64,
+) -> Result<Option<(&[u8], usize)>> {
+    let Some((len, len_size)) = read_long(buf, start, position)? else {
+        return Ok(None);
+    };
+    let Ok(len) = usize::try_from(len) else {
+        return exec_err!("Invalid length {len} in Avro file");
+    };
+    let begin = start + len_size;
+    Ok(buf
+        .get(begin..begin + len)
+        .map(|bytes| (bytes, len_size + len)))
+}
+
+/// The header of an Avro object container file
+#[derive(Debug)]
+pub(crate) struct Header<'a> {
+    /// The key and value of each entry of the metadata of the file
+    pub(crate) metadata: Vec<(&'a [u8], &'a [u8])>,
+    pub(crate) sync: [u8; SYNC_LENGTH],
+    /// Length of the header, including its sync marker
+    pub(crate) len: usize,
+}
+
+impl<'a> Header<'a> {
+    /// Returns the value of the metadata entry `key`, such as `avro.schema`
+    pub(crate) fn get(&self, key: &str) -> Option<&'a [u8]> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| *k == key.as_bytes())
+            .map(|(_, value)| *value)
+    }
+}
+
+/// Parses the header at the start of `buf`, returning `None` if `buf` ends
+/// before the end of the header
+pub(crate) fn parse_header(buf: &[u8]) -> Result<Option<Header<'_>>> {
+    let magic_len = buf.len().min(MAGIC.len());
+    if buf[..magic_len] != MAGIC[..magic_len] {
+        return exec_err!("Not an Avro object container file");
+    }
+    if magic_len < MAGIC.len() {
+        return Ok(None);
+    }
+
+    let mut end = MAGIC.len();
+    let mut metadata = vec![];
+    loop {
+        let Some((count, size)) = read_long(buf, end, 0)? else {
+            return Ok(None);
+        };
+        end += size;
+        if count == 0 {
+            break;
+        }
+        if count < 0 {
+            // A negative count is followed by the size of the entries
+            let Some((_, size)) = read_long(buf, end, 0)? else {
+                return Ok(None);
+            };
+            end += size;
+        }
+        for _ in 0..count.unsigned_abs() {
+            let Some((key, size)) = read_bytes(buf, end, 0)? else {
+                return Ok(None);
+            };
+            end += size;
+            let Some((value, size)) = read_bytes(buf, end, 0)? else {
+                return Ok(None);
+            };
+            end += size;
+            metadata.push((key, value));
+        }
+    }
+    let Some(sync) = buf.get(end..end + SYNC_LENGTH) else {
+        return Ok(None);
+    };
+    let sync = sync
+        .try_into()
+        .map_err(|_| internal_datafusion_err!("Invalid Avro sync marker length"))?;
+    Ok(Some(Header {
+        metadata,
+        sync,
+        len: end + SYNC_LENGTH,
+    }))
+}
+
+/// Reads from `reader` into `buf` until it holds the whole header of the
+/// file, returning the length of the header.
+///
+/// `buf` may hold bytes following the header.
+pub(crate) fn read_header<R: Read>(reader: &mut R, buf: &mut Vec<u8>) -> Result<usize> {
+    loop {
+        if let Some(header) = parse_header(buf)? {
+            return Ok(header.len);
+        }
+        if reader.by_ref().take(4096).read_to_end(buf)? == 0 {
+            return exec_err!("Unexpected end of Avro file header");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use apache_avro::Codec;
+
+    #[test]
+    fn test_decode_varint() {
+        assert_eq!(decode_varint(&[0x02], MAX_LONG_LENGTH), Varint::Value(2, 1));
+        assert_eq!(
+            decode_varint(&[0xac, 0x02, 0xff], MAX_LONG_LENGTH),
+            Varint::Value(300, 2)
+        );
+        assert_eq!(
+            decode_varint(&[0x80, 0x80], MAX_LONG_LENGTH),
+            Varint::Incomplete
+        );
+        assert_eq!(
+            decode_varint(&[0x80; 6], MAX_INT_LENGTH),
+            Varint::Unterminated
+        );
+        assert_eq!(
+            decode_varint(&[0xff; 11], MAX_LONG_LENGTH),
+            Varint::Overflow
+        );
+
+        for value in [0, 1, -1, i64::MAX, i64::MIN] {
+            let mut buf = vec![];
+            let mut encoded = ((value << 1) ^ (value >> 63)) as u64;
+            while encoded >= 0x80 {
+                buf.push((encoded as u8) | 0x80);
+                encoded >>= 7;
+            }
+            buf.push(encoded as u8);
+            assert_eq!(read_long(&buf, 0, 0).unwrap(), Some((value, buf.len())));
+        }
+    }
+
+    #[test]
+    fn test_read_long_offset() {
+        assert_eq!(read_long(&[0x00, 0x04], 1, 10).unwrap(), Some((2, 1)));
+        assert_eq!(read_long(&[0x00], 1, 10).unwrap(), None);
+        let err = read_long(
+            &[
+                0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            ],
+            1,
+            10,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid long in Avro file at offset 11"));
+    }
+
+    #[test]
+    fn test_parse_header() -> Result<()> {
+        let schema = apache_avro::Schema::parse_str(r#""long""#).unwrap();
+        let mut writer = apache_avro::Writer::with_codec(&schema, vec![], Codec::Deflate);
+        writer.append(1_i64).unwrap();
+        let file = writer.into_inner().unwrap();
+
+        let header = parse_header(&file)?.unwrap();
+        assert!(header.get("avro.schema").is_some());
+        assert_eq!(header.get("avro.codec"), Some(&b"deflate"[..]));
+        assert_eq!(header.get("missing"), None);
+        // The first block is followed by the sync marker of the header
+        assert_eq!(&file[file.len() - SYNC_LENGTH..], header.sync);
+        for len in 0..header.len {
+            assert!(parse_header(&file[..len])?.is_none());
+        }
+
+        let mut buf = vec![];
+        assert_eq!(read_header(&mut file.as_slice(), &mut buf)?, header.len);
+        let err = read_header(&mut &file[..header.len - 1], &mut vec![]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unexpected end of Avro file header"));
+
+        let err = parse_header(b"PAR1").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Not an Avro object container file"));
+        Ok(())
+    }
+}
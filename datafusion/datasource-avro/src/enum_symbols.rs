@@ -0,0 +1,309 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Resolution of the symbols of Avro enums against the symbols of the table
+//! schema
+
+use std::collections::HashSet;
+
+use arrow::array::{ArrayRef, AsArray, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{exec_err, plan_err, Result};
+
+use crate::avro_to_arrow::{AVRO_ENUM_DEFAULT_METADATA_KEY, AVRO_SYMBOLS_METADATA_KEY};
+
+/// What an Avro scan does with a value of an enum column that is not one of
+/// the symbols of the column in the table schema, when the enum of the table
+/// schema has no `default` symbol
+///
+/// Such values are read from files written with a newer version of the enum,
+/// which gained symbols since the table schema was taken. Following the Avro
+/// schema resolution rules, they are replaced with the `default` symbol of
+/// the enum when it has one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownEnumSymbolPolicy {
+    /// Fail the scan
+    #[default]
+    Error,
+    /// Replace the value with null, which fails the scan if the column is
+    /// not nullable
+    NullOut,
+}
+
+/// Resolves the values of the enum columns of the decoded batches against
+/// the symbols of the table schema, see [`AvroSource::with_enum_symbol_policy`]
+///
+/// [`AvroSource::with_enum_symbol_policy`]: crate::source::AvroSource::with_enum_symbol_policy
+#[derive(Debug)]
+pub(crate) struct EnumSymbols {
+    columns: Vec<EnumColumn>,
+    policy: UnknownEnumSymbolPolicy,
+}
+
+/// An enum column of the batches, with the symbols of its enum in the table
+/// schema
+#[derive(Debug)]
+struct EnumColumn {
+    index: usize,
+    name: String,
+    symbols: HashSet<String>,
+    default: Option<String>,
+}
+
+impl EnumSymbols {
+    /// Returns the resolution of the enum columns of `projected_schema`, the
+    /// schema of the batches, or of none of them if `policy` is `None`. Fails
+    /// if the `default` of an enum is not one of its symbols.
+    pub(crate) fn try_new(
+        policy: Option<UnknownEnumSymbolPolicy>,
+        projected_schema: &Schema,
+    ) -> Result<Self> {
+        let Some(policy) = policy else {
+            return Ok(Self {
+                columns: vec![],
+                policy: UnknownEnumSymbolPolicy::default(),
+            });
+        };
+        let mut columns = vec![];
+        for (index, field) in projected_schema.fields().iter().enumerate() {
+            let Some(symbols) = enum_symbols(field) else {
+                continue;
+            };
+            let default = field
+                .metadata()
+                .get(AVRO_ENUM_DEFAULT_METADATA_KEY)
+                .cloned();
+            if let Some(default) = &default {
+                if !symbols.contains(default) {
+                    return plan_err!(
+                        "Default {default} of Avro enum column {} is not one of its symbols",
+                        field.name()
+                    );
+                }
+            }
+            columns.push(EnumColumn {
+                index,
+                name: field.name().clone(),
+                symbols,
+                default,
+            });
+        }
+        Ok(Self { columns, policy })
+    }
+
+    /// Returns `batch` with the unknown symbols of the enum columns replaced
+    /// with the default symbol of their enum, or with null
+    pub(crate) fn apply(&self, batch: RecordBatch) -> Result<RecordBatch> {
+        let mut arrays = batch.columns().to_vec();
+        let mut resolved = false;
+        for column in &self.columns {
+            if let Some(array) = column.resolve(&arrays[column.index], self.policy)? {
+                arrays[column.index] = array;
+                resolved = true;
+            }
+        }
+        if !resolved {
+            return Ok(batch);
+        }
+        Ok(RecordBatch::try_new(batch.schema(), arrays)?)
+    }
+}
+
+impl EnumColumn {
+    /// Returns `array` with its unknown symbols resolved, or `None` if all
+    /// of its symbols are known
+    fn resolve(
+        &self,
+        array: &ArrayRef,
+        policy: UnknownEnumSymbolPolicy,
+    ) -> Result<Option<ArrayRef>> {
+        let strings = cast(array, &DataType::Utf8)?;
+        let strings = strings.as_string::<i32>();
+        if strings.iter().flatten().all(|s| self.symbols.contains(s)) {
+            return Ok(None);
+        }
+        let resolved = strings
+            .iter()
+            .map(|value| match value {
+                Some(symbol) if !self.symbols.contains(symbol) => {
+                    match (&self.default, policy) {
+                        (Some(default), _) => Ok(Some(default.as_str())),
+                        (None, UnknownEnumSymbolPolicy::NullOut) => Ok(None),
+                        (None, UnknownEnumSymbolPolicy::Error) => exec_err!(
+                            "Unknown symbol {symbol} of Avro enum column {}",
+                            self.name
+                        ),
+                    }
+                }
+                value => Ok(value),
+            })
+            .collect::<Result<StringArray>>()?;
+        Ok(Some(cast(&resolved, array.data_type())?))
+    }
+}
+
+/// Returns the symbols of the [`AVRO_SYMBOLS_METADATA_KEY`] metadata of a
+/// column decoded from an Avro enum, if it is one
+pub(crate) fn enum_symbols(field: &Field) -> Option<HashSet<String>> {
+    let symbols = field.metadata().get(AVRO_SYMBOLS_METADATA_KEY)?;
+    let symbols = symbols.strip_prefix('[')?.strip_suffix(']')?;
+    Some(
+        symbols
+            .split(',')
+            .filter(|symbol| !symbol.is_empty())
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use crate::source::AvroSource;
+    use apache_avro::types::Value;
+    use datafusion_datasource::file_groups::FileGroup;
+    use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
+    use datafusion_datasource::source::DataSourceExec;
+    use datafusion_datasource::PartitionedFile;
+    use datafusion_execution::object_store::ObjectStoreUrl;
+    use datafusion_execution::TaskContext;
+    use datafusion_physical_plan::{common, ExecutionPlan};
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+    use object_store::ObjectStore;
+
+    /// Scans a file whose enum column `e`, with the symbols A, B and C,
+    /// holds A, C, B, C, against a table schema whose enum only has the
+    /// symbols A and B, and `default` as its default symbol
+    async fn scan(
+        policy: Option<UnknownEnumSymbolPolicy>,
+        default: Option<&str>,
+    ) -> Result<Vec<Option<String>>> {
+        let schema = apache_avro::Schema::parse_str(
+            r#"{"type": "record", "name": "r", "fields": [
+                {"name": "e", "type": {"type": "enum", "name": "e", "symbols": ["A", "B", "C"]}}
+            ]}"#,
+        )
+        .unwrap();
+        let mut writer = apache_avro::Writer::new(&schema, vec![]);
+        for (index, symbol) in [(0, "A"), (2, "C"), (1, "B"), (2, "C")] {
+            writer
+                .append(Value::Record(vec![(
+                    "e".to_string(),
+                    Value::Enum(index, symbol.to_string()),
+                )]))
+                .unwrap();
+        }
+        let data = writer.into_inner().unwrap();
+        let store = Arc::new(InMemory::new());
+        let file = PartitionedFile::new("file.avro", data.len() as u64);
+        store.put(&Path::from("file.avro"), data.into()).await?;
+
+        let task_ctx = Arc::new(TaskContext::default());
+        let url = ObjectStoreUrl::parse("memory://")?;
+        task_ctx
+            .runtime_env()
+            .register_object_store(url.as_ref(), store);
+        let mut metadata =
+            HashMap::from([(AVRO_SYMBOLS_METADATA_KEY.to_string(), "[A,B]".to_string())]);
+        if let Some(default) = default {
+            metadata.insert(
+                AVRO_ENUM_DEFAULT_METADATA_KEY.to_string(),
+                default.to_string(),
+            );
+        }
+        let table_schema =
+            Arc::new(Schema::new(vec![
+                Field::new("e", DataType::Utf8, true).with_metadata(metadata)
+            ]));
+        let source = Arc::new(AvroSource::new().with_enum_symbol_policy(policy));
+        let conf = FileScanConfigBuilder::new(url, table_schema, source)
+            .with_file_groups(vec![FileGroup::new(vec![file])])
+            .with_batch_size(Some(1024))
+            .build();
+        let exec = DataSourceExec::from_data_source(conf);
+        let batches = common::collect(exec.execute(0, task_ctx)?).await?;
+        Ok(batches
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(0)
+                    .as_string::<i32>()
+                    .iter()
+                    .map(|value| value.map(str::to_string))
+                    .collect::<Vec<_>>()
+            })
+            .collect())
+    }
+
+    fn symbols(symbols: &[Option<&str>]) -> Vec<Option<String>> {
+        symbols.iter().map(|s| s.map(str::to_string)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_unknown_enum_symbols() -> Result<()> {
+        let err = scan(Some(UnknownEnumSymbolPolicy::Error), None)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Unknown symbol C of Avro enum column e"),
+            "{err}"
+        );
+
+        assert_eq!(
+            scan(Some(UnknownEnumSymbolPolicy::NullOut), None).await?,
+            symbols(&[Some("A"), None, Some("B"), None])
+        );
+
+        // The default symbol of the enum takes precedence over the policy
+        for policy in [
+            UnknownEnumSymbolPolicy::Error,
+            UnknownEnumSymbolPolicy::NullOut,
+        ] {
+            assert_eq!(
+                scan(Some(policy), Some("A")).await?,
+                symbols(&[Some("A"), Some("A"), Some("B"), Some("A")])
+            );
+        }
+
+        // Without a policy, the symbols are read as they are
+        assert_eq!(
+            scan(None, None).await?,
+            symbols(&[Some("A"), Some("C"), Some("B"), Some("C")])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_enum_default() {
+        let err = scan(Some(UnknownEnumSymbolPolicy::Error), Some("C"))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Default C of Avro enum column e is not one of its symbols"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,406 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Fetching of Avro files from an [`ObjectStore`] in blocks, coalescing
+//! nearby block fetches into fewer range requests
+
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
+
+use bytes::Bytes;
+use datafusion_common::{internal_err, Result};
+use object_store::{ObjectMeta, ObjectStore};
+
+/// How Avro files are fetched from an [`ObjectStore`] by the
+/// [`AvroSource`](crate::source::AvroSource)
+///
+/// A file is fetched in blocks of `block_size` bytes. Blocks separated by at
+/// most `coalesce_max_gap` bytes are merged into a single range, as long as
+/// the merged range is at most `coalesce_max_size` bytes, and all the ranges
+/// of a file are requested with a single [`ObjectStore::get_ranges`] call.
+///
+/// Merging ranges reduces the number of requests, which matters for stores
+/// with a high latency per request, at the cost of reading the bytes in
+/// between the merged blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockFetchOptions {
+    /// Size of the blocks a file is fetched in
+    pub block_size: u64,
+    /// Maximum number of bytes between two blocks merged into one range
+    pub coalesce_max_gap: u64,
+    /// Maximum size of a range merging several blocks. Blocks are not merged
+    /// if it is not larger than `block_size`.
+    pub coalesce_max_size: u64,
+}
+
+impl Default for BlockFetchOptions {
+    fn default() -> Self {
+        Self {
+            block_size: 1024 * 1024,
+            coalesce_max_gap: 1024 * 1024,
+            coalesce_max_size: 16 * 1024 * 1024,
+        }
+    }
+}
+
+impl BlockFetchOptions {
+    /// Returns these options with the blocks fetched by separate ranges
+    pub fn without_coalescing(self) -> Self {
+        Self {
+            coalesce_max_gap: 0,
+            coalesce_max_size: 0,
+            ..self
+        }
+    }
+
+    /// Merges the sorted, non-overlapping `ranges` that are at most
+    /// `coalesce_max_gap` bytes apart into ranges of at most
+    /// `coalesce_max_size` bytes
+    pub(crate) fn coalesce_ranges(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut merged: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last)
+                    if range.start <= last.end + self.coalesce_max_gap
+                        && range.end - last.start <= self.coalesce_max_size =>
+                {
+                    last.end = last.end.max(range.end);
+                }
+                _ => merged.push(range.clone()),
+            }
+        }
+        merged
+    }
+
+    /// Fetches `ranges` of `object` with a single request for all of them,
+    /// coalescing them according to these options, and returns the bytes of
+    /// each range
+    pub(crate) async fn get_ranges(
+        &self,
+        store: &dyn ObjectStore,
+        object: &ObjectMeta,
+        ranges: &[Range<u64>],
+    ) -> Result<Vec<Bytes>> {
+        let merged = self.coalesce_ranges(ranges);
+        let merged_bytes = store.get_ranges(&object.location, &merged).await?;
+
+        let mut merged = merged.iter().zip(merged_bytes).peekable();
+        let mut result = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            while merged.next_if(|(m, _)| m.end < range.end).is_some() {}
+            let Some((merged_range, bytes)) = merged.peek() else {
+                return internal_err!("Range {range:?} not fetched");
+            };
+            let start = (range.start - merged_range.start) as usize;
+            let end = (range.end - merged_range.start) as usize;
+            result.push(bytes.slice(start..end));
+        }
+        Ok(result)
+    }
+
+    /// Fetches the whole `object` in blocks, returning a reader over them
+    pub(crate) async fn fetch(
+        &self,
+        store: &dyn ObjectStore,
+        object: &ObjectMeta,
+    ) -> Result<BlockReader> {
+        let block_size = self.block_size.max(1);
+        let blocks = (0..object.size)
+            .step_by(block_size as usize)
+            .map(|start| start..(start + block_size).min(object.size))
+            .collect::<Vec<_>>();
+        let blocks = self.get_ranges(store, object, &blocks).await?;
+        Ok(BlockReader::new(blocks))
+    }
+}
+
+/// A reader over the contiguous blocks of a file
+pub(crate) struct BlockReader {
+    blocks: Vec<Bytes>,
+    /// Offset of the start of each block in the file
+    offsets: Vec<u64>,
+    len: u64,
+    position: u64,
+}
+
+impl BlockReader {
+    fn new(blocks: Vec<Bytes>) -> Self {
+        let mut offsets = Vec::with_capacity(blocks.len());
+        let mut len = 0;
+        for block in &blocks {
+            offsets.push(len);
+            len += block.len() as u64;
+        }
+        Self {
+            blocks,
+            offsets,
+            len,
+            position: 0,
+        }
+    }
+}
+
+impl Read for BlockReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.position >= self.len {
+            return Ok(0);
+        }
+        let index = self.offsets.partition_point(|o| *o <= self.position) - 1;
+        let block = &self.blocks[index][(self.position - self.offsets[index]) as usize..];
+        let n = block.len().min(buf.len());
+        buf[..n].copy_from_slice(&block[..n]);
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for BlockReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+        self.position = position.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avro_to_arrow::read_avro_schema_from_reader;
+    use crate::source::AvroSource;
+
+    use std::fmt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use apache_avro::types::Value;
+    use async_trait::async_trait;
+    use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
+    use datafusion_datasource::source::DataSourceExec;
+    use datafusion_datasource::PartitionedFile;
+    use datafusion_execution::object_store::ObjectStoreUrl;
+    use datafusion_execution::TaskContext;
+    use datafusion_physical_plan::{common, ExecutionPlan};
+    use futures::stream::BoxStream;
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+    use object_store::{
+        GetOptions, GetResult, ListResult, MultipartUpload, PutMultipartOpts, PutOptions,
+        PutPayload, PutResult,
+    };
+
+    /// Counts the ranges requested from an in memory store
+    #[derive(Debug, Default)]
+    struct RequestCountingStore {
+        inner: InMemory,
+        requests: AtomicUsize,
+    }
+
+    impl fmt::Display for RequestCountingStore {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "RequestCountingStore")
+        }
+    }
+
+    #[async_trait]
+    impl ObjectStore for RequestCountingStore {
+        async fn put_opts(
+            &self,
+            location: &Path,
+            payload: PutPayload,
+            opts: PutOptions,
+        ) -> object_store::Result<PutResult> {
+            self.inner.put_opts(location, payload, opts).await
+        }
+
+        async fn put_multipart_opts(
+            &self,
+            location: &Path,
+            opts: PutMultipartOpts,
+        ) -> object_store::Result<Box<dyn MultipartUpload>> {
+            self.inner.put_multipart_opts(location, opts).await
+        }
+
+        async fn get_opts(
+            &self,
+            location: &Path,
+            options: GetOptions,
+        ) -> object_store::Result<GetResult> {
+            self.requests.fetch_add(1, Ordering::Relaxed);
+            self.inner.get_opts(location, options).await
+        }
+
+        async fn get_ranges(
+            &self,
+            location: &Path,
+            ranges: &[Range<u64>],
+        ) -> object_store::Result<Vec<Bytes>> {
+            self.requests.fetch_add(ranges.len(), Ordering::Relaxed);
+            self.inner.get_ranges(location, ranges).await
+        }
+
+        async fn delete(&self, location: &Path) -> object_store::Result<()> {
+            self.inner.delete(location).await
+        }
+
+        fn list(
+            &self,
+            prefix: Option<&Path>,
+        ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+            self.inner.list(prefix)
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            prefix: Option<&Path>,
+        ) -> object_store::Result<ListResult> {
+            self.inner.list_with_delimiter(prefix).await
+        }
+
+        async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+            self.inner.copy(from, to).await
+        }
+
+        async fn copy_if_not_exists(
+            &self,
+            from: &Path,
+            to: &Path,
+        ) -> object_store::Result<()> {
+            self.inner.copy_if_not_exists(from, to).await
+        }
+    }
+
+    /// Scans a file of 2000 records with `block_fetch`, returning the number
+    /// of records read and of ranges requested
+    async fn scan(block_fetch: Option<BlockFetchOptions>) -> Result<(usize, usize)> {
+        let schema = apache_avro::Schema::parse_str(
+            r#"{"type": "record", "name": "r", "fields": [{"name": "id", "type": "long"}]}"#,
+        )
+        .unwrap();
+        let mut writer = apache_avro::Writer::new(&schema, vec![]);
+        for id in 0..2000 {
+            writer
+                .append(Value::Record(vec![("id".to_string(), Value::Long(id))]))
+                .unwrap();
+        }
+        let data = writer.into_inner().unwrap();
+        let file_schema = Arc::new(read_avro_schema_from_reader(&mut data.as_slice())?);
+
+        let store = Arc::new(RequestCountingStore::default());
+        let location = Path::from("data.avro");
+        let size = data.len() as u64;
+        store.put(&location, data.into()).await?;
+
+        let task_ctx = TaskContext::default();
+        let url = ObjectStoreUrl::parse("memory://")?;
+        task_ctx
+            .runtime_env()
+            .register_object_store(url.as_ref(), Arc::clone(&store) as _);
+
+        let source = Arc::new(AvroSource::new().with_block_fetch(block_fetch));
+        let conf = FileScanConfigBuilder::new(url, file_schema, source)
+            .with_file(PartitionedFile::new(location.to_string(), size))
+            .build();
+        let exec = DataSourceExec::from_data_source(conf);
+        let batches = common::collect(exec.execute(0, Arc::new(task_ctx))?).await?;
+        let rows = batches.iter().map(|b| b.num_rows()).sum();
+        Ok((rows, store.requests.load(Ordering::Relaxed)))
+    }
+
+    #[tokio::test]
+    async fn test_coalesced_block_fetch() -> Result<()> {
+        assert_eq!(scan(None).await?, (2000, 1));
+
+        let options = BlockFetchOptions {
+            block_size: 512,
+            ..Default::default()
+        };
+        let (rows, uncoalesced_requests) =
+            scan(Some(options.without_coalescing())).await?;
+        assert_eq!(rows, 2000);
+        assert!(uncoalesced_requests > 4, "{uncoalesced_requests}");
+
+        assert_eq!(scan(Some(options)).await?, (2000, 1));
+
+        // Merged ranges are limited to 2 blocks
+        let options = BlockFetchOptions {
+            coalesce_max_size: 1024,
+            ..options
+        };
+        assert_eq!(
+            scan(Some(options)).await?,
+            (2000, uncoalesced_requests.div_ceil(2))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_coalesce_ranges() {
+        let options = BlockFetchOptions {
+            block_size: 10,
+            coalesce_max_gap: 5,
+            coalesce_max_size: 40,
+        };
+        let ranges = [0..10, 10..20, 22..30, 40..50, 50..60, 60..70];
+        assert_eq!(options.coalesce_ranges(&ranges), vec![0..30, 40..70]);
+
+        let options = BlockFetchOptions {
+            coalesce_max_size: 25,
+            ..options
+        };
+        assert_eq!(
+            options.coalesce_ranges(&ranges),
+            vec![0..20, 22..30, 40..60, 60..70]
+        );
+
+        assert_eq!(
+            options.without_coalescing().coalesce_ranges(&ranges),
+            ranges.to_vec()
+        );
+    }
+
+    #[test]
+    fn test_block_reader() {
+        let blocks = vec![
+            Bytes::from_static(b"abc"),
+            Bytes::from_static(b"de"),
+            Bytes::from_static(b"fghi"),
+        ];
+        let mut reader = BlockReader::new(blocks);
+        let mut all = String::new();
+        reader.read_to_string(&mut all).unwrap();
+        assert_eq!(all, "abcdefghi");
+
+        reader.seek(SeekFrom::Start(2)).unwrap();
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"cdef");
+        reader.seek(SeekFrom::End(-1)).unwrap();
+        reader.seek(SeekFrom::Current(-1)).unwrap();
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "hi");
+    }
+}
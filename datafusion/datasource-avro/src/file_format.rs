@@ -18,39 +18,105 @@
 //! Apache Avro [`FileFormat`] abstractions
 
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::io::Seek;
 use std::sync::Arc;
 
-use crate::avro_to_arrow::read_avro_schema_from_reader;
-use crate::source::AvroSource;
+use crate::arrow_to_avro::{to_avro_schema, to_avro_values};
+use crate::avro_to_arrow::{
+    apply_date_columns, apply_timestamp_columns, avro_schema_to_arrow, avro_sort_order,
+    merge_schemas_widening, with_field_case, with_large_offsets, without_extension_types,
+    MapDuplicateKeyPolicy, NameCollisionPolicy, NonMidnightPolicy, SchemaInterner,
+    StringCardinalities, StringEncoding, TimestampPrecision, UnionRepresentation,
+};
+use crate::decode_buffer_pool::DecodeBufferPool;
+use crate::decode_pool::DecodePool;
+use crate::enum_symbols::UnknownEnumSymbolPolicy;
+use crate::fetch::BlockFetchOptions;
+use crate::missing_file::MissingFilePolicy;
+use crate::resolution::{explain_resolution, write_schema_differences, ResolutionReport};
+use crate::sidecar::{datums_to_container, fetch_sidecar_schema};
+use crate::source::{decompress, detect_compression, AvroMetricsLabels, AvroSource};
+use crate::tail::header_len;
 
+use apache_avro::Schema as AvroSchema;
+use arrow::array::RecordBatch;
 use arrow::datatypes::Schema;
 use arrow::datatypes::SchemaRef;
-use datafusion_common::internal_err;
 use datafusion_common::parsers::CompressionTypeVariant;
+use datafusion_common::stats::Precision;
 use datafusion_common::GetExt;
 use datafusion_common::DEFAULT_AVRO_EXTENSION;
-use datafusion_common::{Result, Statistics};
+use datafusion_common::{exec_err, internal_err, not_impl_err, plan_err};
+use datafusion_common::{Result, ScalarValue, Statistics};
+use datafusion_common_runtime::SpawnedTask;
+use datafusion_datasource::display::FileGroupDisplay;
 use datafusion_datasource::file::FileSource;
-use datafusion_datasource::file_compression_type::FileCompressionType;
-use datafusion_datasource::file_format::{FileFormat, FileFormatFactory};
+use datafusion_datasource::file_compression_type::{
+    CompressionDetection, FileCompressionType,
+};
+use datafusion_datasource::file_format::{
+    projected_byte_size, FileFormat, FileFormatFactory, ScanCost,
+};
 use datafusion_datasource::file_scan_config::{FileScanConfig, FileScanConfigBuilder};
+use datafusion_datasource::file_sink_config::{FileSink, FileSinkConfig};
+use datafusion_datasource::sink::{DataSink, DataSinkExec};
 use datafusion_datasource::source::DataSourceExec;
-use datafusion_physical_plan::ExecutionPlan;
+use datafusion_datasource::write::demux::DemuxedStreamReceiver;
+use datafusion_datasource::write::get_writer_schema;
+use datafusion_datasource::write::orchestration::spawn_writer_tasks_and_join;
+use datafusion_datasource::write::BatchSerializer;
+use datafusion_execution::{SendableRecordBatchStream, TaskContext};
+use datafusion_expr::dml::InsertOp;
+use datafusion_physical_expr_common::sort_expr::LexRequirement;
+use datafusion_physical_plan::{DisplayAs, DisplayFormatType, ExecutionPlan};
 use datafusion_session::Session;
 
 use async_trait::async_trait;
+use bytes::Bytes;
+use log::warn;
 use object_store::{GetResultPayload, ObjectMeta, ObjectStore};
 
+/// Cost of walking over one byte of an Avro datum, whether its column is
+/// projected or not, relative to decoding one byte of a columnar format
+const AVRO_SKIP_COST_PER_BYTE: usize = 1;
+
+/// Additional cost of decoding one byte of a projected Avro column into
+/// an Arrow array, relative to decoding one byte of a columnar format
+const AVRO_DECODE_COST_PER_BYTE: usize = 2;
+
+/// Size up to which files are fetched by [`AvroFormat::infer_stats`] to check
+/// whether they are made of a header only
+const HEADER_ONLY_MAX_BYTES: u64 = 64 * 1024;
+
 #[derive(Default)]
-/// Factory struct used to create [`AvroFormat`]
-pub struct AvroFormatFactory;
+/// Factory struct used to create [`AvroFormat`](struct@AvroFormat)
+pub struct AvroFormatFactory {
+    file_extension: Option<String>,
+}
 
 impl AvroFormatFactory {
     /// Creates an instance of [`AvroFormatFactory`]
     pub fn new() -> Self {
-        Self {}
+        Self {
+            file_extension: None,
+        }
+    }
+
+    /// Set the extension of the files of the formats created by this factory,
+    /// without its leading dot, e.g. `avro.bin`
+    /// - defaults to `avro`
+    ///
+    /// Listing tables only read the files with this extension.
+    pub fn with_file_extension(mut self, file_extension: impl Into<String>) -> Self {
+        let file_extension = file_extension.into();
+        self.file_extension = Some(file_extension.trim_start_matches('.').to_string());
+        self
+    }
+
+    fn format(&self) -> AvroFormat {
+        AvroFormat::default().with_file_extension(self.file_extension.clone())
     }
 }
 
@@ -60,11 +126,11 @@ impl FileFormatFactory for AvroFormatFactory {
         _state: &dyn Session,
         _format_options: &HashMap<String, String>,
     ) -> Result<Arc<dyn FileFormat>> {
-        Ok(Arc::new(AvroFormat))
+        Ok(Arc::new(self.format()))
     }
 
     fn default(&self) -> Arc<dyn FileFormat> {
-        Arc::new(AvroFormat)
+        Arc::new(self.format())
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -74,20 +140,662 @@ impl FileFormatFactory for AvroFormatFactory {
 
 impl fmt::Debug for AvroFormatFactory {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("AvroFormatFactory").finish()
+        f.debug_struct("AvroFormatFactory")
+            .field("file_extension", &self.file_extension)
+            .finish()
     }
 }
 
 impl GetExt for AvroFormatFactory {
     fn get_ext(&self) -> String {
-        // Removes the dot, i.e. ".avro" -> "avro"
-        DEFAULT_AVRO_EXTENSION[1..].to_string()
+        match &self.file_extension {
+            Some(file_extension) => file_extension.clone(),
+            // Removes the dot, i.e. ".avro" -> "avro"
+            None => DEFAULT_AVRO_EXTENSION[1..].to_string(),
+        }
     }
 }
 
+/// Strategy used by [`AvroFormat`](struct@AvroFormat) to combine the schemas of several files
+/// into a single table schema during schema inference
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SchemaMergeStrategy {
+    /// Merge the file schemas with [`Schema::try_merge`], which requires
+    /// columns with the same name to have identical types
+    #[default]
+    Strict,
+    /// Widen columns whose types differ between files where no value can be
+    /// lost by doing so.
+    ///
+    /// `Decimal128` columns with differing precision and scale are reconciled
+    /// to the maximum scale and a precision wide enough to hold the integral
+    /// digits of every input. Per-file values are rescaled to the table type
+    /// by the [`SchemaAdapter`] while decoding.
+    ///
+    /// [`SchemaAdapter`]: datafusion_datasource::schema_adapter::SchemaAdapter
+    Widening,
+}
+
 /// Avro [`FileFormat`] implementation.
-#[derive(Default, Debug)]
-pub struct AvroFormat;
+#[derive(Debug, Clone)]
+pub struct AvroFormat {
+    schema_merge_strategy: SchemaMergeStrategy,
+    sorted_by_schema_order: bool,
+    union_representation: UnionRepresentation,
+    name_collision_policy: NameCollisionPolicy,
+    without_extension_types: bool,
+    large_offsets: bool,
+    map_duplicate_key_policy: MapDuplicateKeyPolicy,
+    string_encodings: BTreeMap<String, StringEncoding>,
+    timestamp_columns: Vec<String>,
+    timestamp_precision: TimestampPrecision,
+    timestamp_timezone: Option<Arc<str>>,
+    date_columns: Vec<String>,
+    non_midnight_policy: NonMidnightPolicy,
+    block_fetch: Option<BlockFetchOptions>,
+    max_in_flight_batches: Option<usize>,
+    decode_pool: Option<Arc<DecodePool>>,
+    decode_buffer_pool: Option<Arc<DecodeBufferPool>>,
+    compression_detection: CompressionDetection,
+    sidecar_schema_file: Option<String>,
+    preflight_validation: bool,
+    expected_write_schema: Option<String>,
+    validate_written_row_count: bool,
+    case_insensitive_field_resolution: bool,
+    missing_file_policy: MissingFilePolicy,
+    sample_fraction: Option<f64>,
+    sample_seed: u64,
+    null_defaults: BTreeMap<String, ScalarValue>,
+    enum_symbol_policy: Option<UnknownEnumSymbolPolicy>,
+    enum_symbols: bool,
+    file_extension: Option<String>,
+}
+
+/// An [`AvroFormat`](struct@AvroFormat) with the default options, so that
+/// `AvroFormat` keeps creating one as it did when the format had none.
+/// Prefer [`AvroFormat::default`] in new code.
+#[allow(non_upper_case_globals)]
+pub const AvroFormat: AvroFormat = AvroFormat::new();
+
+impl Default for AvroFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AvroFormat {
+    /// Create an [`AvroFormat`](struct@AvroFormat) with the default options
+    pub const fn new() -> Self {
+        Self {
+            schema_merge_strategy: SchemaMergeStrategy::Strict,
+            sorted_by_schema_order: false,
+            union_representation: UnionRepresentation::Union,
+            name_collision_policy: NameCollisionPolicy::Error,
+            without_extension_types: false,
+            large_offsets: false,
+            map_duplicate_key_policy: MapDuplicateKeyPolicy::LastWins,
+            string_encodings: BTreeMap::new(),
+            timestamp_columns: Vec::new(),
+            timestamp_precision: TimestampPrecision::Microsecond,
+            timestamp_timezone: None,
+            date_columns: Vec::new(),
+            non_midnight_policy: NonMidnightPolicy::Error,
+            block_fetch: None,
+            max_in_flight_batches: None,
+            decode_pool: None,
+            decode_buffer_pool: None,
+            compression_detection: CompressionDetection::ByExtension,
+            sidecar_schema_file: None,
+            preflight_validation: false,
+            expected_write_schema: None,
+            validate_written_row_count: false,
+            case_insensitive_field_resolution: false,
+            missing_file_policy: MissingFilePolicy::Error,
+            sample_fraction: None,
+            sample_seed: 0,
+            null_defaults: BTreeMap::new(),
+            enum_symbol_policy: None,
+            enum_symbols: false,
+            file_extension: None,
+        }
+    }
+
+    /// Set the strategy used to merge the schemas of multiple files
+    /// - defaults to [`SchemaMergeStrategy::Strict`]
+    pub fn with_schema_merge_strategy(
+        mut self,
+        schema_merge_strategy: SchemaMergeStrategy,
+    ) -> Self {
+        self.schema_merge_strategy = schema_merge_strategy;
+        self
+    }
+
+    /// Returns the strategy used to merge the schemas of multiple files
+    pub fn schema_merge_strategy(&self) -> SchemaMergeStrategy {
+        self.schema_merge_strategy
+    }
+
+    /// Declare that the records of every file are sorted according to the
+    /// `order` attributes of the fields of their Avro record schema
+    /// - defaults to false.
+    ///
+    /// The Avro specification does not require files to be sorted: this
+    /// must only be set when the writer of the files guarantees it. Scans
+    /// then declare the ordering returned by [`avro_sort_order`] as their
+    /// output ordering, unless an output ordering is already specified, for
+    /// example with [`ListingOptions::with_file_sort_order`].
+    ///
+    /// [`ListingOptions::with_file_sort_order`]: https://docs.rs/datafusion/latest/datafusion/datasource/listing/struct.ListingOptions.html#method.with_file_sort_order
+    pub fn with_sorted_by_schema_order(mut self, sorted_by_schema_order: bool) -> Self {
+        self.sorted_by_schema_order = sorted_by_schema_order;
+        self
+    }
+
+    /// Returns true if the files are declared to be sorted according to the
+    /// `order` attributes of their Avro record schema
+    pub fn sorted_by_schema_order(&self) -> bool {
+        self.sorted_by_schema_order
+    }
+
+    /// Set how multi-branch unions are represented in the inferred schema
+    /// and decoded
+    /// - defaults to [`UnionRepresentation::Union`]
+    pub fn with_union_representation(
+        mut self,
+        union_representation: UnionRepresentation,
+    ) -> Self {
+        self.union_representation = union_representation;
+        self
+    }
+
+    /// Returns how multi-branch unions are represented
+    pub fn union_representation(&self) -> UnionRepresentation {
+        self.union_representation
+    }
+
+    /// Set how sibling fields with the same name in the inferred schema are
+    /// handled, see [`NameCollisionPolicy`]
+    /// - defaults to [`NameCollisionPolicy::Error`]
+    pub fn with_name_collision_policy(
+        mut self,
+        name_collision_policy: NameCollisionPolicy,
+    ) -> Self {
+        self.name_collision_policy = name_collision_policy;
+        self
+    }
+
+    /// Returns how sibling fields with the same name are handled
+    pub fn name_collision_policy(&self) -> NameCollisionPolicy {
+        self.name_collision_policy
+    }
+
+    /// Declare the Arrow canonical extension types of Avro logical types in
+    /// the inferred schema, such as [`UUID_EXTENSION_NAME`] for `uuid`
+    /// - defaults to `true`
+    ///
+    /// Consumers not supporting extension types can disable them, the fields
+    /// then only have their storage type.
+    ///
+    /// [`UUID_EXTENSION_NAME`]: crate::avro_to_arrow::UUID_EXTENSION_NAME
+    pub fn with_extension_types(mut self, extension_types: bool) -> Self {
+        self.without_extension_types = !extension_types;
+        self
+    }
+
+    /// Returns whether the inferred schema declares Arrow extension types
+    pub fn extension_types(&self) -> bool {
+        !self.without_extension_types
+    }
+
+    /// Infer the Avro strings, bytes and arrays as `LargeUtf8`,
+    /// `LargeBinary` and `LargeList`, whose 64-bit offsets address the values
+    /// of batches holding more than 2GB of them in a single column
+    /// - defaults to `false`
+    ///
+    /// Without large offsets, scanning such a batch fails.
+    pub fn with_large_offsets(mut self, large_offsets: bool) -> Self {
+        self.large_offsets = large_offsets;
+        self
+    }
+
+    /// Returns whether the inferred schema uses the Arrow types with 64-bit
+    /// offsets
+    pub fn large_offsets(&self) -> bool {
+        self.large_offsets
+    }
+
+    /// Set how keys occurring more than once in an Avro map are handled
+    /// when decoding it as an Arrow `Map`
+    /// - defaults to [`MapDuplicateKeyPolicy::LastWins`]
+    pub fn with_map_duplicate_key_policy(
+        mut self,
+        map_duplicate_key_policy: MapDuplicateKeyPolicy,
+    ) -> Self {
+        self.map_duplicate_key_policy = map_duplicate_key_policy;
+        self
+    }
+
+    /// Returns how keys occurring more than once in an Avro map are handled
+    pub fn map_duplicate_key_policy(&self) -> MapDuplicateKeyPolicy {
+        self.map_duplicate_key_policy
+    }
+
+    /// Set how the top level string column `column` is decoded
+    /// - defaults to [`StringEncoding::Plain`]
+    ///
+    /// A [`StringEncoding::Auto`] encoding reads every file during schema
+    /// inference to count the distinct values of the column.
+    pub fn with_string_encoding(
+        mut self,
+        column: impl Into<String>,
+        encoding: StringEncoding,
+    ) -> Self {
+        self.string_encodings.insert(column.into(), encoding);
+        self
+    }
+
+    /// Returns how the top level string columns are decoded, by column name
+    pub fn string_encodings(&self) -> &BTreeMap<String, StringEncoding> {
+        &self.string_encodings
+    }
+
+    /// Infer the top level `long` columns named in `columns` as timestamps
+    /// of `precision`, for files storing epoch timestamps without a logical
+    /// type
+    /// - defaults to no column
+    ///
+    /// Schema inference fails if one of the columns is not a `long`.
+    pub fn with_timestamp_columns(
+        mut self,
+        columns: Vec<String>,
+        precision: TimestampPrecision,
+    ) -> Self {
+        self.timestamp_columns = columns;
+        self.timestamp_precision = precision;
+        self
+    }
+
+    /// Returns the top level `long` columns inferred as timestamps
+    pub fn timestamp_columns(&self) -> &[String] {
+        &self.timestamp_columns
+    }
+
+    /// Returns the precision of the timestamps of the
+    /// [`Self::timestamp_columns`]
+    pub fn timestamp_precision(&self) -> TimestampPrecision {
+        self.timestamp_precision
+    }
+
+    /// Set the time zone of the timestamps of the columns set with
+    /// [`Self::with_timestamp_columns`]
+    /// - defaults to no time zone
+    pub fn with_timestamp_timezone(mut self, timezone: impl Into<Arc<str>>) -> Self {
+        self.timestamp_timezone = Some(timezone.into());
+        self
+    }
+
+    /// Infer the top level timestamp columns named in `columns` as `Date32`,
+    /// for files storing dates as timestamps at midnight
+    /// - defaults to no column
+    ///
+    /// Schema inference fails if one of the columns is not a timestamp.
+    /// Timestamps that are not at midnight are handled according to
+    /// [`Self::with_non_midnight_policy`] when the files are scanned.
+    pub fn with_date_columns(mut self, columns: Vec<String>) -> Self {
+        self.date_columns = columns;
+        self
+    }
+
+    /// Returns the top level timestamp columns inferred as dates
+    pub fn date_columns(&self) -> &[String] {
+        &self.date_columns
+    }
+
+    /// Set how the timestamps of the columns set with
+    /// [`Self::with_date_columns`] that are not at midnight are decoded
+    /// - defaults to [`NonMidnightPolicy::Error`]
+    pub fn with_non_midnight_policy(
+        mut self,
+        non_midnight_policy: NonMidnightPolicy,
+    ) -> Self {
+        self.non_midnight_policy = non_midnight_policy;
+        self
+    }
+
+    /// Returns how the timestamps of the date columns that are not at
+    /// midnight are decoded
+    pub fn non_midnight_policy(&self) -> NonMidnightPolicy {
+        self.non_midnight_policy
+    }
+
+    /// Set how the files are fetched in blocks when they are scanned
+    /// - defaults to `None`, fetching each file with a single request
+    pub fn with_block_fetch(mut self, block_fetch: Option<BlockFetchOptions>) -> Self {
+        self.block_fetch = block_fetch;
+        self
+    }
+
+    /// Returns how the files are fetched in blocks, if they are
+    pub fn block_fetch(&self) -> Option<BlockFetchOptions> {
+        self.block_fetch
+    }
+
+    /// Set the maximum number of decoded batches buffered ahead of the
+    /// consumer of a scan, see [`AvroSource::with_max_in_flight_batches`]
+    /// - defaults to `None`, decoding the files as the scan is polled
+    pub fn with_max_in_flight_batches(
+        mut self,
+        max_in_flight_batches: Option<usize>,
+    ) -> Self {
+        self.max_in_flight_batches = max_in_flight_batches;
+        self
+    }
+
+    /// Returns the maximum number of decoded batches buffered ahead of the
+    /// consumer of a scan, if any
+    pub fn max_in_flight_batches(&self) -> Option<usize> {
+        self.max_in_flight_batches
+    }
+
+    /// Set the pool whose threads decode the files of a scan, see
+    /// [`AvroSource::with_decode_pool`]
+    /// - defaults to `None`, decoding the files on the async runtime
+    pub fn with_decode_pool(mut self, decode_pool: Option<Arc<DecodePool>>) -> Self {
+        self.decode_pool = decode_pool;
+        self
+    }
+
+    /// Returns the pool whose threads decode the files of a scan, if any
+    pub fn decode_pool(&self) -> Option<&Arc<DecodePool>> {
+        self.decode_pool.as_ref()
+    }
+
+    /// Set the pool of the scratch buffers of the decoders of a scan, see
+    /// [`AvroSource::with_decode_buffer_pool`]
+    /// - defaults to `None`, allocating the buffers of each file
+    pub fn with_decode_buffer_pool(
+        mut self,
+        decode_buffer_pool: Option<Arc<DecodeBufferPool>>,
+    ) -> Self {
+        self.decode_buffer_pool = decode_buffer_pool;
+        self
+    }
+
+    /// Returns the pool of the scratch buffers of the decoders of a scan, if
+    /// any
+    pub fn decode_buffer_pool(&self) -> Option<&Arc<DecodeBufferPool>> {
+        self.decode_buffer_pool.as_ref()
+    }
+
+    /// Set how the compression of the files is detected, see
+    /// [`AvroSource::with_compression_detection`]
+    /// - defaults to [`CompressionDetection::ByExtension`], reading the
+    ///   files uncompressed
+    pub fn with_compression_detection(
+        mut self,
+        compression_detection: CompressionDetection,
+    ) -> Self {
+        self.compression_detection = compression_detection;
+        self
+    }
+
+    /// Returns how the compression of the files is detected
+    pub fn compression_detection(&self) -> CompressionDetection {
+        self.compression_detection
+    }
+
+    /// Set the name of the sidecar file holding the Avro schema, in its JSON
+    /// representation, of the headerless files of a directory, such as
+    /// [`DEFAULT_SIDECAR_SCHEMA_FILE`](crate::DEFAULT_SIDECAR_SCHEMA_FILE)
+    /// - defaults to `None`, reading every file as an Avro object container
+    ///   file
+    ///
+    /// Some datasets strip the header of their files to save space. The
+    /// files of a directory with this sidecar file are read as a sequence of
+    /// bare, uncompressed, datums of its schema, which is their writer
+    /// schema. The files of other directories are read as usual.
+    pub fn with_sidecar_schema_file(
+        mut self,
+        sidecar_schema_file: Option<String>,
+    ) -> Self {
+        self.sidecar_schema_file = sidecar_schema_file;
+        self
+    }
+
+    /// Returns the name of the sidecar file holding the Avro schema of the
+    /// headerless files of a directory, if any
+    pub fn sidecar_schema_file(&self) -> Option<&str> {
+        self.sidecar_schema_file.as_deref()
+    }
+
+    /// Set the extension of the files of this format, without its leading
+    /// dot, see [`AvroFormatFactory::with_file_extension`]
+    /// - defaults to `None`, using `avro`
+    pub fn with_file_extension(mut self, file_extension: Option<String>) -> Self {
+        self.file_extension = file_extension
+            .map(|file_extension| file_extension.trim_start_matches('.').to_string());
+        self
+    }
+
+    /// Check the header schema of every file against the table schema when
+    /// the scan is planned, before any record is read
+    /// - defaults to false.
+    ///
+    /// Without this check, a scan fails once it opens the first file that
+    /// is incompatible with the table schema, possibly after producing
+    /// batches from other files. The check fetches the header of every file
+    /// an extra time, and fails with the path of the first incompatible file.
+    pub fn with_preflight_validation(mut self, preflight_validation: bool) -> Self {
+        self.preflight_validation = preflight_validation;
+        self
+    }
+
+    /// Returns true if the files are checked against the table schema when
+    /// the scan is planned
+    pub fn preflight_validation(&self) -> bool {
+        self.preflight_validation
+    }
+
+    /// Set the Avro schema, in its JSON representation, that the files
+    /// written by this format must have
+    /// - defaults to `None`, writing files with the schema derived from the
+    ///   Arrow schema of the data.
+    ///
+    /// When set, planning a write fails with the list of differences if
+    /// the Avro schema derived from the data is not compatible with the
+    /// expected schema, before any file is written. The schemas are
+    /// compatible if they have the same fields, except for expected fields
+    /// with a default, and if the written types are the expected ones or can
+    /// be promoted to them, such as `int` to `long`. Nullable columns must
+    /// be nullable in the expected schema. The files are written with the
+    /// expected schema.
+    pub fn with_expected_write_schema(mut self, avro_json: impl Into<String>) -> Self {
+        self.expected_write_schema = Some(avro_json.into());
+        self
+    }
+
+    /// Returns the JSON representation of the Avro schema that written
+    /// files must have, if any
+    pub fn expected_write_schema(&self) -> Option<&str> {
+        self.expected_write_schema.as_deref()
+    }
+
+    /// Check that writes produce as many records as their input has rows
+    /// - defaults to false.
+    ///
+    /// The check is only made when the statistics of the input plan have
+    /// an exact row count, and fails the write once all files are written
+    /// if the number of records written differs from it, which indicates
+    /// that rows were lost or duplicated on the way to the files.
+    pub fn with_validate_written_row_count(
+        mut self,
+        validate_written_row_count: bool,
+    ) -> Self {
+        self.validate_written_row_count = validate_written_row_count;
+        self
+    }
+
+    /// Returns true if the records written are checked against the row
+    /// count of the input
+    pub fn validate_written_row_count(&self) -> bool {
+        self.validate_written_row_count
+    }
+
+    /// Match the columns of the table to the fields of the files ignoring
+    /// case, for upstreams whose field casing changes between versions of
+    /// their schema (`userId` and `userid`)
+    /// - defaults to false.
+    ///
+    /// The fields of the inferred schema that are only distinct by case are
+    /// merged into one column, named like in the first file that has it.
+    /// Scanning a file fails if a column matches several of its fields that
+    /// are only distinct by case.
+    pub fn with_case_insensitive_field_resolution(
+        mut self,
+        case_insensitive_field_resolution: bool,
+    ) -> Self {
+        self.case_insensitive_field_resolution = case_insensitive_field_resolution;
+        self
+    }
+
+    /// Returns true if the columns of the table are matched to the fields of
+    /// the files ignoring case
+    pub fn case_insensitive_field_resolution(&self) -> bool {
+        self.case_insensitive_field_resolution
+    }
+
+    /// Set what the scan does when a file listed while planning is not found
+    /// once it is opened
+    /// - defaults to [`MissingFilePolicy::Error`]
+    pub fn with_missing_file_policy(
+        mut self,
+        missing_file_policy: MissingFilePolicy,
+    ) -> Self {
+        self.missing_file_policy = missing_file_policy;
+        self
+    }
+
+    /// Returns what the scan does when a file is not found once it is opened
+    pub fn missing_file_policy(&self) -> MissingFilePolicy {
+        self.missing_file_policy
+    }
+
+    /// Only scan a deterministic random sample of the rows, keeping each row
+    /// with probability `fraction`, see [`AvroSource::with_sample_fraction`]
+    /// - defaults to `None`, scanning all the rows
+    pub fn with_sample_fraction(mut self, fraction: Option<f64>) -> Self {
+        self.sample_fraction = fraction;
+        self
+    }
+
+    /// Returns the probability of each row to be scanned, if the rows are
+    /// sampled
+    pub fn sample_fraction(&self) -> Option<f64> {
+        self.sample_fraction
+    }
+
+    /// Set the seed of the sample of [`Self::with_sample_fraction`]
+    /// - defaults to 0
+    pub fn with_sample_seed(mut self, seed: u64) -> Self {
+        self.sample_seed = seed;
+        self
+    }
+
+    /// Returns the seed of the sample of the rows
+    pub fn sample_seed(&self) -> u64 {
+        self.sample_seed
+    }
+
+    /// Set the values replacing the nulls of columns of the table, by column
+    /// name, see [`AvroSource::with_null_defaults`] - defaults to none
+    pub fn with_null_defaults(
+        mut self,
+        null_defaults: HashMap<String, ScalarValue>,
+    ) -> Self {
+        self.null_defaults = null_defaults.into_iter().collect();
+        self
+    }
+
+    /// Returns the values replacing the nulls of columns of the table, by
+    /// column name
+    pub fn null_defaults(&self) -> &BTreeMap<String, ScalarValue> {
+        &self.null_defaults
+    }
+
+    /// Set how the values of enum columns are resolved against the symbols
+    /// of their enum in the table schema, see
+    /// [`AvroSource::with_enum_symbol_policy`] - defaults to `None`
+    pub fn with_enum_symbol_policy(
+        mut self,
+        enum_symbol_policy: Option<UnknownEnumSymbolPolicy>,
+    ) -> Self {
+        self.enum_symbol_policy = enum_symbol_policy;
+        self
+    }
+
+    /// Returns how the values of enum columns are resolved against the
+    /// symbols of their enum, if they are
+    pub fn enum_symbol_policy(&self) -> Option<UnknownEnumSymbolPolicy> {
+        self.enum_symbol_policy
+    }
+
+    /// Declare the symbols and the default symbol of the Avro enums in the
+    /// [`AVRO_SYMBOLS_METADATA_KEY`] and [`AVRO_ENUM_DEFAULT_METADATA_KEY`]
+    /// metadata of the columns of the inferred schema decoded from them
+    /// - defaults to `false`
+    ///
+    /// The enum symbol policy resolves the values of enum columns against
+    /// these symbols. They are always declared when the files are sorted by
+    /// schema order, see [`Self::with_sorted_by_schema_order`].
+    ///
+    /// [`AVRO_SYMBOLS_METADATA_KEY`]: crate::avro_to_arrow::AVRO_SYMBOLS_METADATA_KEY
+    /// [`AVRO_ENUM_DEFAULT_METADATA_KEY`]: crate::avro_to_arrow::AVRO_ENUM_DEFAULT_METADATA_KEY
+    pub fn with_enum_symbols(mut self, enum_symbols: bool) -> Self {
+        self.enum_symbols = enum_symbols;
+        self
+    }
+
+    /// Returns whether the inferred schema declares the symbols of the enums
+    pub fn enum_symbols(&self) -> bool {
+        self.enum_symbols
+    }
+
+    /// Explains how data written with `writer_schema` is resolved against
+    /// `reader_schema`: whether each field is matched by name or alias,
+    /// defaulted or dropped, and the type promotions applied.
+    ///
+    /// This is a debugging aid that does not read any data. It fails with the
+    /// first field that can not be resolved.
+    pub fn explain_resolution(
+        writer_schema: &AvroSchema,
+        reader_schema: &AvroSchema,
+    ) -> Result<ResolutionReport> {
+        explain_resolution(writer_schema, reader_schema)
+    }
+
+    /// The [`AvroSource`] scanning the files of this format
+    fn avro_source(&self) -> AvroSource {
+        AvroSource::new()
+            .with_union_representation(self.union_representation)
+            .with_name_collision_policy(self.name_collision_policy)
+            .with_map_duplicate_key_policy(self.map_duplicate_key_policy)
+            .with_non_midnight_policy(self.non_midnight_policy)
+            .with_block_fetch(self.block_fetch)
+            .with_max_in_flight_batches(self.max_in_flight_batches)
+            .with_decode_pool(self.decode_pool.clone())
+            .with_decode_buffer_pool(self.decode_buffer_pool.clone())
+            .with_compression_detection(self.compression_detection)
+            .with_sidecar_schema_file(self.sidecar_schema_file.clone())
+            .with_case_insensitive_field_resolution(
+                self.case_insensitive_field_resolution,
+            )
+            .with_missing_file_policy(self.missing_file_policy)
+            .with_sample_fraction(self.sample_fraction)
+            .with_sample_seed(self.sample_seed)
+            .with_null_defaults(self.null_defaults.clone().into_iter().collect())
+            .with_enum_symbol_policy(self.enum_symbol_policy)
+    }
+}
 
 #[async_trait]
 impl FileFormat for AvroFormat {
@@ -96,7 +804,9 @@ impl FileFormat for AvroFormat {
     }
 
     fn get_ext(&self) -> String {
-        AvroFormatFactory::new().get_ext()
+        self.file_extension
+            .clone()
+            .unwrap_or_else(|| AvroFormatFactory::new().get_ext())
     }
 
     fn get_ext_with_compression(
@@ -117,46 +827,434 @@ impl FileFormat for AvroFormat {
         objects: &[ObjectMeta],
     ) -> Result<SchemaRef> {
         let mut schemas = vec![];
+        let mut cardinalities = StringCardinalities::new(&self.string_encodings);
+        // Files with identical schemas, e.g. written by the same job, only
+        // have their schema parsed once
+        // Enum columns must be told apart from string columns to order the
+        // files by schema order
+        let enum_symbols = self.enum_symbols || self.sorted_by_schema_order;
+        let mut interner = SchemaInterner::new(
+            self,
+            self.union_representation,
+            self.name_collision_policy,
+            enum_symbols,
+        );
         for object in objects {
+            if object.size == 0 {
+                warn!(
+                    "Skipping the zero-byte file {} during Avro schema inference",
+                    object.location
+                );
+                continue;
+            }
+            if let Some(name) = &self.sidecar_schema_file {
+                if let Some(schema) =
+                    fetch_sidecar_schema(store.as_ref(), &object.location, name).await?
+                {
+                    if !cardinalities.is_empty() {
+                        let data = store.get(&object.location).await?.bytes().await?;
+                        cardinalities.update(datums_to_container(&schema, &data)?)?;
+                    }
+                    schemas.push(avro_schema_to_arrow(
+                        &schema,
+                        self.union_representation,
+                        self.name_collision_policy,
+                        enum_symbols,
+                    )?);
+                    continue;
+                }
+            }
+
+            let compression =
+                detect_compression(self.compression_detection, store.as_ref(), object)
+                    .await?;
+            if compression.is_compressed() {
+                let data = store.as_ref().get(&object.location).await?.bytes().await?;
+                let data = decompress(compression, data)?.into_inner();
+                schemas.push(interner.read_schema(&mut data.as_slice())?);
+                if !cardinalities.is_empty() {
+                    cardinalities.update(data.as_slice())?;
+                }
+                continue;
+            }
+
             let r = store.as_ref().get(&object.location).await?;
             let schema = match r.payload {
                 GetResultPayload::File(mut file, _) => {
-                    read_avro_schema_from_reader(&mut file)?
+                    let schema = interner.read_schema(&mut file)?;
+                    if !cardinalities.is_empty() {
+                        file.rewind()?;
+                        cardinalities.update(&mut file)?;
+                    }
+                    schema
                 }
                 GetResultPayload::Stream(_) => {
                     // TODO: Fetching entire file to get schema is potentially wasteful
                     let data = r.bytes().await?;
-                    read_avro_schema_from_reader(&mut data.as_ref())?
+                    let schema = interner.read_schema(&mut data.as_ref())?;
+                    if !cardinalities.is_empty() {
+                        cardinalities.update(data.as_ref())?;
+                    }
+                    schema
                 }
             };
             schemas.push(schema);
         }
-        let merged_schema = Schema::try_merge(schemas)?;
-        Ok(Arc::new(merged_schema))
+        if self.case_insensitive_field_resolution {
+            // Name the fields of each file like the fields of the previous
+            // files they match ignoring case
+            let mut names: Vec<String> = vec![];
+            schemas = schemas
+                .into_iter()
+                .map(|schema| {
+                    let schema =
+                        with_field_case(schema, names.iter().map(String::as_str))?;
+                    for field in schema.fields() {
+                        if !names.contains(field.name()) {
+                            names.push(field.name().clone());
+                        }
+                    }
+                    Ok(schema)
+                })
+                .collect::<Result<_>>()?;
+        }
+        let merged_schema = match self.schema_merge_strategy {
+            SchemaMergeStrategy::Strict => Schema::try_merge(schemas)?,
+            SchemaMergeStrategy::Widening => merge_schemas_widening(schemas)?,
+        };
+        let schema = apply_date_columns(
+            cardinalities.apply(merged_schema, &self.string_encodings),
+            &self.date_columns,
+        )?;
+        let schema = apply_timestamp_columns(
+            schema,
+            &self.timestamp_columns,
+            self.timestamp_precision,
+            self.timestamp_timezone.as_ref(),
+        )?;
+        let schema = if self.without_extension_types {
+            without_extension_types(schema)
+        } else {
+            schema
+        };
+        if self.large_offsets {
+            Ok(Arc::new(with_large_offsets(schema)))
+        } else {
+            Ok(Arc::new(schema))
+        }
     }
 
+    /// Files are only known to be empty, either zero-byte files or files
+    /// made of a header and no block: the statistics of other files are
+    /// unknown.
     async fn infer_stats(
         &self,
         _state: &dyn Session,
-        _store: &Arc<dyn ObjectStore>,
+        store: &Arc<dyn ObjectStore>,
         table_schema: SchemaRef,
-        _object: &ObjectMeta,
+        object: &ObjectMeta,
     ) -> Result<Statistics> {
-        Ok(Statistics::new_unknown(&table_schema))
+        let empty = Statistics::new_unknown(&table_schema)
+            .with_num_rows(Precision::Exact(0))
+            .with_total_byte_size(Precision::Exact(0));
+        if object.size == 0 {
+            return Ok(empty);
+        }
+        // Files with a larger header are not checked. Headerless files with a
+        // sidecar schema, or compressed files, don't start with a header.
+        if object.size > HEADER_ONLY_MAX_BYTES {
+            return Ok(Statistics::new_unknown(&table_schema));
+        }
+        let bytes = store.get_range(&object.location, 0..object.size).await?;
+        match header_len(&bytes) {
+            Ok(Some(len)) if len == bytes.len() => Ok(empty),
+            _ => Ok(Statistics::new_unknown(&table_schema)),
+        }
     }
 
     async fn create_physical_plan(
         &self,
-        _state: &dyn Session,
-        conf: FileScanConfig,
+        state: &dyn Session,
+        mut conf: FileScanConfig,
     ) -> Result<Arc<dyn ExecutionPlan>> {
+        if self.sorted_by_schema_order && conf.output_ordering.is_empty() {
+            conf.output_ordering =
+                avro_sort_order(&conf.file_schema).into_iter().collect();
+        }
+        let mut source = self.avro_source();
+        if let Some(labels) = state.config().get_extension::<AvroMetricsLabels>() {
+            source = source.with_metrics_labels(labels.0.clone());
+        }
         let config = FileScanConfigBuilder::from(conf)
-            .with_source(self.file_source())
+            .with_source(Arc::new(source))
             .build();
+        if self.preflight_validation {
+            let source = config.file_source().with_projection(&config);
+            if let Some(source) = source.as_any().downcast_ref::<AvroSource>() {
+                let object_store =
+                    state.runtime_env().object_store(&config.object_store_url)?;
+                let files = config
+                    .file_groups
+                    .iter()
+                    .flat_map(|group| group.iter())
+                    .map(|file| file.object_meta.clone())
+                    .collect::<Vec<_>>();
+                source.validate_files(&object_store, &files).await?;
+            }
+        }
         Ok(DataSourceExec::from_data_source(config))
     }
 
+    async fn create_writer_physical_plan(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        _state: &dyn Session,
+        conf: FileSinkConfig,
+        order_requirements: Option<LexRequirement>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if conf.insert_op == InsertOp::Replace {
+            return not_impl_err!("Replacing rows is not implemented yet for Avro");
+        }
+
+        let expected_schema = self
+            .expected_write_schema
+            .as_deref()
+            .map(AvroSchema::parse_str)
+            .transpose()?;
+        let mut sink = AvroSink::try_new(conf, expected_schema)?;
+        if self.validate_written_row_count {
+            if let Precision::Exact(num_rows) = input.partition_statistics(None)?.num_rows
+            {
+                sink = sink.with_expected_row_count(Some(num_rows as u64));
+            }
+        }
+        let sink = Arc::new(sink);
+
+        Ok(Arc::new(DataSinkExec::new(input, sink, order_requirements)) as _)
+    }
+
     fn file_source(&self) -> Arc<dyn FileSource> {
-        Arc::new(AvroSource::new())
+        Arc::new(self.avro_source())
+    }
+
+    /// Avro is row oriented, so all bytes are read and every field of every
+    /// record is decoded to find the next one. A projection only saves the
+    /// cost of building the arrays of the columns that are not projected.
+    fn scan_cost_estimate(
+        &self,
+        stats: &Statistics,
+        projection: Option<&[usize]>,
+    ) -> ScanCost {
+        let skip_cost = stats
+            .total_byte_size
+            .multiply(&Precision::Exact(AVRO_SKIP_COST_PER_BYTE));
+        let decode_cost = projected_byte_size(stats, projection)
+            .multiply(&Precision::Exact(AVRO_DECODE_COST_PER_BYTE));
+        ScanCost {
+            io_bytes: stats.total_byte_size,
+            cpu_cost: skip_cost.add(&decode_cost),
+        }
+    }
+}
+
+/// Serializes record batches to the blocks of an Avro file
+pub struct AvroSerializer {
+    /// The schema of the written records
+    schema: AvroSchema,
+    /// Whether the records must be resolved against `schema`, which is not
+    /// the schema derived from the Arrow schema of the batches
+    resolve: bool,
+    /// The sync marker that ends each block
+    marker: [u8; 16],
+}
+
+impl AvroSerializer {
+    /// Creates a serializer of records with the schema `schema` derived from
+    /// the Arrow schema of the batches by [`to_avro_schema`]
+    pub fn new(schema: AvroSchema) -> Self {
+        Self {
+            schema,
+            resolve: false,
+            marker: rand::random(),
+        }
+    }
+
+    /// Creates a serializer of records with the schema `schema`, resolving
+    /// the records derived from the batches against it
+    pub fn new_resolved(schema: AvroSchema) -> Self {
+        Self {
+            resolve: true,
+            ..Self::new(schema)
+        }
+    }
+}
+
+impl BatchSerializer for AvroSerializer {
+    fn serialize(&self, batch: RecordBatch, initial: bool) -> Result<Bytes> {
+        let mut buffer = Vec::with_capacity(4096);
+        // The first batch of a file starts with the header, and the blocks
+        // of all the batches end with the same sync marker
+        let mut writer = if initial {
+            apache_avro::Writer::builder()
+                .schema(&self.schema)
+                .writer(&mut buffer)
+                .marker(self.marker)
+                .build()
+        } else {
+            apache_avro::Writer::append_to(&self.schema, &mut buffer, self.marker)
+        };
+        for value in to_avro_values(&batch)? {
+            if self.resolve {
+                writer.append(value.resolve(&self.schema)?)?;
+            } else {
+                writer.append(value)?;
+            }
+        }
+        writer.into_inner()?;
+        Ok(Bytes::from(buffer))
+    }
+}
+
+/// Implements [`DataSink`] for writing to an Avro file.
+pub struct AvroSink {
+    /// Config options for writing data
+    config: FileSinkConfig,
+    /// The schema of the written records
+    schema: AvroSchema,
+    /// Whether `schema` is an expected schema rather than the schema derived
+    /// from the data
+    expected: bool,
+    /// The number of records the input holds, if they must be checked
+    /// against the records written
+    expected_row_count: Option<u64>,
+}
+
+impl fmt::Debug for AvroSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AvroSink").finish()
+    }
+}
+
+impl DisplayAs for AvroSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "AvroSink(file_groups=",)?;
+                FileGroupDisplay(&self.config.file_group).fmt_as(t, f)?;
+                write!(f, ")")
+            }
+            DisplayFormatType::TreeRender => {
+                writeln!(f, "format: avro")?;
+                write!(f, "file={}", &self.config.original_url)
+            }
+        }
+    }
+}
+
+impl AvroSink {
+    /// Create from config, writing files with the schema `expected_schema`
+    /// if any, or with the schema derived from the data otherwise.
+    ///
+    /// Fails with the differences between the schemas if the data can not be
+    /// written with `expected_schema`.
+    pub fn try_new(
+        config: FileSinkConfig,
+        expected_schema: Option<AvroSchema>,
+    ) -> Result<Self> {
+        let schema = to_avro_schema(&get_writer_schema(&config))?;
+        let Some(expected_schema) = expected_schema else {
+            return Ok(Self {
+                config,
+                schema,
+                expected: false,
+                expected_row_count: None,
+            });
+        };
+        let differences = write_schema_differences(&schema, &expected_schema);
+        if !differences.is_empty() {
+            return plan_err!(
+                "The data to write is incompatible with the expected Avro schema:\n  {}",
+                differences.join("\n  ")
+            );
+        }
+        Ok(Self {
+            config,
+            schema: expected_schema,
+            expected: true,
+            expected_row_count: None,
+        })
+    }
+
+    /// Fail writes once all files are written if the number of records
+    /// written is not `expected_row_count`
+    pub fn with_expected_row_count(mut self, expected_row_count: Option<u64>) -> Self {
+        self.expected_row_count = expected_row_count;
+        self
+    }
+
+    /// Returns the number of records that writes must produce, if checked
+    pub fn expected_row_count(&self) -> Option<u64> {
+        self.expected_row_count
+    }
+
+    /// Retrieve the schema of the written records
+    pub fn avro_schema(&self) -> &AvroSchema {
+        &self.schema
+    }
+}
+
+#[async_trait]
+impl FileSink for AvroSink {
+    fn config(&self) -> &FileSinkConfig {
+        &self.config
+    }
+
+    async fn spawn_writer_tasks_and_join(
+        &self,
+        context: &Arc<TaskContext>,
+        demux_task: SpawnedTask<Result<()>>,
+        file_stream_rx: DemuxedStreamReceiver,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> Result<u64> {
+        let serializer = if self.expected {
+            AvroSerializer::new_resolved(self.schema.clone())
+        } else {
+            AvroSerializer::new(self.schema.clone())
+        };
+        spawn_writer_tasks_and_join(
+            context,
+            Arc::new(serializer),
+            FileCompressionType::UNCOMPRESSED,
+            object_store,
+            demux_task,
+            file_stream_rx,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl DataSink for AvroSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> &SchemaRef {
+        self.config.output_schema()
+    }
+
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let written = FileSink::write_all(self, data, context).await?;
+        match self.expected_row_count {
+            Some(expected) if expected != written => exec_err!(
+                "Wrote {written} Avro records to {} but the input has {expected} rows",
+                self.config.original_url
+            ),
+            _ => Ok(written),
+        }
     }
 }
@@ -0,0 +1,239 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Handling of Avro files that are missing from the object store when scanned
+
+use std::future::Future;
+use std::time::Duration;
+
+use datafusion_common::{DataFusionError, Result};
+use log::warn;
+use object_store::path::Path;
+
+/// What an Avro scan does when a file listed while planning is not found in
+/// the object store once it is opened, as happens briefly with eventually
+/// consistent stores
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MissingFilePolicy {
+    /// Fail the scan
+    #[default]
+    Error,
+    /// Skip the file with a warning, as if it had no rows
+    Skip,
+    /// Retry opening the file, and skip it with a warning if it is still
+    /// missing after the last retry
+    RetryThenSkip(MissingFileRetry),
+}
+
+/// How [`MissingFilePolicy::RetryThenSkip`] retries opening a missing file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingFileRetry {
+    /// The number of times opening the file is retried - defaults to 3
+    pub max_retries: usize,
+    /// The delay before the first retry, doubled before each of the next
+    /// ones - defaults to 100ms
+    pub backoff: Duration,
+}
+
+impl Default for MissingFileRetry {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            backoff: Duration::from_millis(100),
+        }
+    }
+}
+
+impl MissingFilePolicy {
+    /// Runs `open` on the file at `location`, applying the policy when it
+    /// fails because the file is not found, and returns `None` if the file is
+    /// skipped
+    pub(crate) async fn open<T, F, Fut>(
+        &self,
+        location: &Path,
+        mut open: F,
+    ) -> Result<Option<T>>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut retries = 0;
+        let mut backoff = Duration::ZERO;
+        loop {
+            let error = match open().await {
+                Err(e) if is_not_found(&e) => e,
+                result => return result.map(Some),
+            };
+            match self {
+                Self::Error => return Err(error),
+                Self::Skip => {}
+                Self::RetryThenSkip(retry) if retries < retry.max_retries => {
+                    backoff = if retries == 0 {
+                        retry.backoff
+                    } else {
+                        backoff * 2
+                    };
+                    retries += 1;
+                    tokio::time::sleep(backoff).await;
+                    continue;
+                }
+                Self::RetryThenSkip(_) => {}
+            }
+            warn!("Skipping Avro file {location} not found in the object store: {error}");
+            return Ok(None);
+        }
+    }
+}
+
+/// Returns whether `error` is caused by a file not found in the object store
+fn is_not_found(error: &DataFusionError) -> bool {
+    matches!(
+        error.find_root(),
+        DataFusionError::ObjectStore(object_store::Error::NotFound { .. })
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::source::AvroSource;
+    use apache_avro::types::Value;
+    use datafusion_common::DataFusionError;
+    use datafusion_datasource::file_groups::FileGroup;
+    use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
+    use datafusion_datasource::source::DataSourceExec;
+    use datafusion_datasource::PartitionedFile;
+    use datafusion_execution::object_store::ObjectStoreUrl;
+    use datafusion_execution::TaskContext;
+    use datafusion_physical_plan::{common, ExecutionPlan};
+    use object_store::memory::InMemory;
+    use object_store::ObjectStore;
+
+    fn not_found() -> DataFusionError {
+        DataFusionError::ObjectStore(object_store::Error::NotFound {
+            path: "data.avro".to_string(),
+            source: "404".into(),
+        })
+    }
+
+    /// Scans `present.avro` and `missing.avro`, of 3 records each, from a store
+    /// holding only `present.avro`, returning the number of records read
+    async fn scan(policy: MissingFilePolicy) -> Result<usize> {
+        let schema = apache_avro::Schema::parse_str(
+            r#"{"type": "record", "name": "r", "fields": [{"name": "id", "type": "long"}]}"#,
+        )
+        .unwrap();
+        let mut writer = apache_avro::Writer::new(&schema, vec![]);
+        for id in 0..3 {
+            writer
+                .append(Value::Record(vec![("id".to_string(), Value::Long(id))]))
+                .unwrap();
+        }
+        let data = writer.into_inner().unwrap();
+        let size = data.len() as u64;
+        let file_schema = Arc::new(crate::avro_to_arrow::read_avro_schema_from_reader(
+            &mut data.as_slice(),
+        )?);
+
+        let store = Arc::new(InMemory::new());
+        store.put(&Path::from("present.avro"), data.into()).await?;
+
+        let task_ctx = TaskContext::default();
+        let url = ObjectStoreUrl::parse("memory://")?;
+        task_ctx
+            .runtime_env()
+            .register_object_store(url.as_ref(), store);
+
+        let source = Arc::new(AvroSource::new().with_missing_file_policy(policy));
+        let conf = FileScanConfigBuilder::new(url, file_schema, source)
+            .with_file_group(FileGroup::new(vec![
+                PartitionedFile::new("present.avro", size),
+                PartitionedFile::new("missing.avro", size),
+            ]))
+            .build();
+        let exec = DataSourceExec::from_data_source(conf);
+        let batches = common::collect(exec.execute(0, Arc::new(task_ctx))?).await?;
+        Ok(batches.iter().map(|b| b.num_rows()).sum())
+    }
+
+    #[tokio::test]
+    async fn test_skip_missing_file() -> Result<()> {
+        let err = scan(MissingFilePolicy::Error).await.unwrap_err();
+        assert!(is_not_found(&err), "{err}");
+
+        assert_eq!(scan(MissingFilePolicy::Skip).await?, 3);
+
+        let retry = MissingFileRetry {
+            max_retries: 2,
+            backoff: Duration::from_millis(1),
+        };
+        assert_eq!(scan(MissingFilePolicy::RetryThenSkip(retry)).await?, 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retry_missing_file() -> Result<()> {
+        let location = Path::from("data.avro");
+        let retry = MissingFileRetry {
+            max_retries: 2,
+            backoff: Duration::from_millis(1),
+        };
+
+        // Found on the last retry
+        let mut attempts = 0;
+        let opened = MissingFilePolicy::RetryThenSkip(retry)
+            .open(&location, || {
+                attempts += 1;
+                let result = if attempts < 3 {
+                    Err(not_found())
+                } else {
+                    Ok(attempts)
+                };
+                async move { result }
+            })
+            .await?;
+        assert_eq!(opened, Some(3));
+
+        // Still missing after the last retry
+        let mut attempts = 0;
+        let opened = MissingFilePolicy::RetryThenSkip(retry)
+            .open(&location, || {
+                attempts += 1;
+                async { Err::<(), _>(not_found()) }
+            })
+            .await?;
+        assert_eq!((opened, attempts), (None, 3));
+
+        // Other errors are not retried
+        let mut attempts = 0;
+        let err = MissingFilePolicy::RetryThenSkip(retry)
+            .open(&location, || {
+                attempts += 1;
+                async { Err::<(), _>(DataFusionError::Execution("boom".to_string())) }
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            (err.strip_backtrace(), attempts),
+            ("Execution error: boom".to_string(), 1)
+        );
+        Ok(())
+    }
+}
@@ -26,8 +26,33 @@
 
 //! An [Avro](https://avro.apache.org/) based [`FileSource`](datafusion_datasource::file::FileSource) implementation and related functionality.
 
+pub mod arrow_to_avro;
 pub mod avro_to_arrow;
+pub mod block_stream;
+pub mod decode_buffer_pool;
+pub mod decode_pool;
+mod encoding;
+mod enum_symbols;
+mod fetch;
 pub mod file_format;
+mod missing_file;
+mod null_defaults;
+pub mod push_decoder;
+pub mod registry;
+pub mod resolution;
+mod row_filter;
+mod sample;
+pub mod sidecar;
 pub mod source;
+pub mod tail;
 
+pub use block_stream::{AvroBlockMetadata, AvroBlockStream};
+pub use decode_buffer_pool::DecodeBufferPool;
+pub use decode_pool::DecodePool;
+pub use enum_symbols::UnknownEnumSymbolPolicy;
+pub use fetch::BlockFetchOptions;
 pub use file_format::*;
+pub use missing_file::{MissingFilePolicy, MissingFileRetry};
+pub use push_decoder::PushAvroDecoder;
+pub use sidecar::DEFAULT_SIDECAR_SCHEMA_FILE;
+pub use tail::AvroTailStream;
@@ -0,0 +1,197 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Substitution of the null values of columns of Avro scans with defaults
+
+use std::collections::HashMap;
+
+use arrow::compute::is_not_null;
+use arrow::compute::kernels::zip::zip;
+use arrow::datatypes::Schema;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{plan_err, Result, ScalarValue};
+
+/// Replaces the nulls of columns of the decoded batches with a default value
+/// per column, see [`AvroSource::with_null_defaults`]
+///
+/// [`AvroSource::with_null_defaults`]: crate::source::AvroSource::with_null_defaults
+#[derive(Debug)]
+pub(crate) struct NullDefaults {
+    /// The index of each substituted column in the batches, with its default
+    columns: Vec<(usize, ScalarValue)>,
+}
+
+impl NullDefaults {
+    /// Checks that `defaults` are non-null values of the type of columns of
+    /// `table_schema`, returning the substitution of the columns of
+    /// `projected_schema`, the schema of the batches
+    pub(crate) fn try_new(
+        defaults: &HashMap<String, ScalarValue>,
+        table_schema: &Schema,
+        projected_schema: &Schema,
+    ) -> Result<Self> {
+        let mut columns = vec![];
+        for (name, default) in defaults {
+            let Ok(field) = table_schema.field_with_name(name) else {
+                return plan_err!("Avro null default for the unknown column {name}");
+            };
+            if default.is_null() {
+                return plan_err!("Avro null default of column {name} is null");
+            }
+            if &default.data_type() != field.data_type() {
+                return plan_err!(
+                    "Avro null default {default} of column {name} is of type {}, not of the column type {}",
+                    default.data_type(),
+                    field.data_type()
+                );
+            }
+            if let Some((index, _)) = projected_schema.column_with_name(name) {
+                columns.push((index, default.clone()));
+            }
+        }
+        Ok(Self { columns })
+    }
+
+    /// Returns `batch` with the nulls of the substituted columns replaced
+    /// with their default
+    pub(crate) fn apply(&self, batch: RecordBatch) -> Result<RecordBatch> {
+        if self
+            .columns
+            .iter()
+            .all(|(index, _)| batch.column(*index).null_count() == 0)
+        {
+            return Ok(batch);
+        }
+        let mut arrays = batch.columns().to_vec();
+        for (index, default) in &self.columns {
+            let array = &arrays[*index];
+            if array.null_count() > 0 {
+                arrays[*index] = zip(&is_not_null(array)?, array, &default.to_scalar()?)?;
+            }
+        }
+        Ok(RecordBatch::try_new(batch.schema(), arrays)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::source::AvroSource;
+    use apache_avro::types::Value;
+    use arrow::array::{Array, AsArray};
+    use arrow::datatypes::{DataType, Field, Int32Type};
+    use datafusion_datasource::file_groups::FileGroup;
+    use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
+    use datafusion_datasource::source::DataSourceExec;
+    use datafusion_datasource::PartitionedFile;
+    use datafusion_execution::object_store::ObjectStoreUrl;
+    use datafusion_execution::TaskContext;
+    use datafusion_physical_plan::{common, ExecutionPlan};
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+    use object_store::ObjectStore;
+
+    /// Scans a file whose nullable `int` column `a` holds 1, null, 3, null
+    /// and whose `string` column `b` holds "x" for every record, with
+    /// `defaults`
+    async fn scan(defaults: HashMap<String, ScalarValue>) -> Result<Vec<RecordBatch>> {
+        let schema = apache_avro::Schema::parse_str(
+            r#"{"type": "record", "name": "r", "fields": [
+                {"name": "a", "type": ["null", "int"]},
+                {"name": "b", "type": "string"}
+            ]}"#,
+        )
+        .unwrap();
+        let mut writer = apache_avro::Writer::new(&schema, vec![]);
+        for a in [Some(1), None, Some(3), None] {
+            let a = match a {
+                Some(a) => Value::Union(1, Box::new(Value::Int(a))),
+                None => Value::Union(0, Box::new(Value::Null)),
+            };
+            writer
+                .append(Value::Record(vec![
+                    ("a".to_string(), a),
+                    ("b".to_string(), Value::String("x".to_string())),
+                ]))
+                .unwrap();
+        }
+        let data = writer.into_inner().unwrap();
+        let store = Arc::new(InMemory::new());
+        let file = PartitionedFile::new("file.avro", data.len() as u64);
+        store.put(&Path::from("file.avro"), data.into()).await?;
+
+        let task_ctx = Arc::new(TaskContext::default());
+        let url = ObjectStoreUrl::parse("memory://")?;
+        task_ctx
+            .runtime_env()
+            .register_object_store(url.as_ref(), store);
+        let file_schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, false),
+        ]));
+        let source = Arc::new(AvroSource::new().with_null_defaults(defaults));
+        let conf = FileScanConfigBuilder::new(url, file_schema, source)
+            .with_file_groups(vec![FileGroup::new(vec![file])])
+            .with_batch_size(Some(1024))
+            .build();
+        let exec = DataSourceExec::from_data_source(conf);
+        common::collect(exec.execute(0, task_ctx)?).await
+    }
+
+    #[tokio::test]
+    async fn test_null_defaults() -> Result<()> {
+        let batches = scan(HashMap::from([(
+            "a".to_string(),
+            ScalarValue::Int32(Some(0)),
+        )]))
+        .await?;
+        let a = batches[0].column(0).as_primitive::<Int32Type>();
+        assert_eq!(a.null_count(), 0);
+        assert_eq!(a.values(), &[1, 0, 3, 0]);
+
+        let batches = scan(HashMap::new()).await?;
+        assert_eq!(batches[0].column(0).null_count(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_null_defaults() {
+        for (defaults, expected) in [
+            (
+                ("a", ScalarValue::Int64(Some(0))),
+                "Avro null default 0 of column a is of type Int64, not of the column type Int32",
+            ),
+            (
+                ("a", ScalarValue::Int32(None)),
+                "Avro null default of column a is null",
+            ),
+            (
+                ("c", ScalarValue::Int32(Some(0))),
+                "Avro null default for the unknown column c",
+            ),
+        ] {
+            let (name, default) = defaults;
+            let err = scan(HashMap::from([(name.to_string(), default)]))
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+    }
+}
@@ -0,0 +1,299 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`PushAvroDecoder`]: decodes the bytes of an Avro file as they are pushed
+//! into it
+
+use std::sync::Arc;
+
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use bytes::{Bytes, BytesMut};
+use datafusion_common::{exec_err, Result};
+
+use crate::block_stream::{decode_block, header_schema_and_codec, SYNC_LENGTH};
+use crate::encoding::{parse_header, read_long};
+
+/// Returns `Ok(None)` from the enclosing function when not enough bytes are
+/// buffered to decode `$e`
+macro_rules! need_more {
+    ($e:expr) => {
+        match $e? {
+            Some(value) => value,
+            None => return Ok(None),
+        }
+    };
+}
+
+/// A push-based decoder of an Avro object container file
+///
+/// Unlike [`Reader`](crate::avro_to_arrow::Reader) and
+/// [`AvroBlockStream`](crate::AvroBlockStream), which pull bytes from their
+/// input, the bytes of the file are pushed into the decoder with
+/// [`Self::decode`], in chunks of any size, such as the bytes received from a
+/// socket. Bytes are buffered until a whole block has arrived, and each block
+/// is decoded into a single [`RecordBatch`].
+///
+/// ```
+/// # use datafusion_common::Result;
+/// # use datafusion_datasource_avro::PushAvroDecoder;
+/// # fn f(chunks: Vec<Vec<u8>>) -> Result<()> {
+/// let mut decoder = PushAvroDecoder::new();
+/// for chunk in chunks {
+///     for batch in decoder.decode(&chunk)? {
+///         println!("decoded {} rows", batch.num_rows());
+///     }
+/// }
+/// // Fails if the file was truncated
+/// decoder.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct PushAvroDecoder {
+    /// Columns to decode, all the columns if `None`
+    projection: Option<Vec<String>>,
+    /// Bytes pushed and not decoded yet
+    buffer: BytesMut,
+    /// Offset in the file of the start of `buffer`
+    position: u64,
+    /// The header of the file, once it has arrived
+    header: Option<Header>,
+}
+
+/// The header of the file being decoded by a [`PushAvroDecoder`]
+#[derive(Debug)]
+struct Header {
+    /// The bytes of the header, including its sync marker
+    bytes: Bytes,
+    sync: [u8; SYNC_LENGTH],
+    codec: String,
+    file_schema: SchemaRef,
+    schema: SchemaRef,
+}
+
+impl PushAvroDecoder {
+    /// Create a new `PushAvroDecoder`, expecting the bytes of a file from its
+    /// start
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only decode the columns named in `projection`
+    pub fn with_projection(mut self, projection: Vec<String>) -> Self {
+        self.projection = Some(projection);
+        self
+    }
+
+    /// Arrow schema of the decoded batches, once the header of the file has
+    /// been decoded
+    pub fn schema(&self) -> Option<SchemaRef> {
+        self.header
+            .as_ref()
+            .map(|header| Arc::clone(&header.schema))
+    }
+
+    /// Compression codec of the file, such as `null` or `deflate`, once the
+    /// header of the file has been decoded
+    pub fn codec(&self) -> Option<&str> {
+        self.header.as_ref().map(|header| header.codec.as_str())
+    }
+
+    /// Pushes the next `bytes` of the file, returning the batches of the
+    /// blocks they complete, if any
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Vec<RecordBatch>> {
+        self.buffer.extend_from_slice(bytes);
+        if self.header.is_none() {
+            self.header = self.read_header()?;
+        }
+        let mut batches = vec![];
+        while let Some(batch) = self.read_block()? {
+            batches.push(batch);
+        }
+        Ok(batches)
+    }
+
+    /// Signals the end of the file, returning an error if it ended in the
+    /// middle of its header or of a block
+    pub fn finish(self) -> Result<()> {
+        if self.header.is_none() || !self.buffer.is_empty() {
+            return exec_err!(
+                "Unexpected end of Avro file at offset {}",
+                self.position + self.buffer.len() as u64
+            );
+        }
+        Ok(())
+    }
+
+    /// Consumes `len` bytes of the buffer
+    fn consume(&mut self, len: usize) -> Bytes {
+        self.position += len as u64;
+        self.buffer.split_to(len).freeze()
+    }
+
+    /// Decodes the header of the file, if it has fully arrived
+    fn read_header(&mut self) -> Result<Option<Header>> {
+        let header = need_more!(parse_header(&self.buffer));
+        let schema = header.get("avro.schema").map(<[u8]>::to_vec);
+        let codec = header.get("avro.codec").map(<[u8]>::to_vec);
+        let (sync, len) = (header.sync, header.len);
+
+        let (file_schema, codec) = header_schema_and_codec(schema, codec)?;
+        let file_schema = Arc::new(file_schema);
+        let schema = match &self.projection {
+            Some(projection) => Arc::new(Schema::new(
+                projection
+                    .iter()
+                    .filter_map(|name| {
+                        file_schema.column_with_name(name).map(|(_, f)| f.clone())
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+            None => Arc::clone(&file_schema),
+        };
+        Ok(Some(Header {
+            bytes: self.consume(len),
+            sync,
+            codec,
+            file_schema,
+            schema,
+        }))
+    }
+
+    /// Decodes the next block of the file, if it has fully arrived
+    fn read_block(&mut self) -> Result<Option<RecordBatch>> {
+        let Some(header) = &self.header else {
+            return Ok(None);
+        };
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+        let offset = self.position;
+        let (record_count, count_size) = need_more!(read_long(&self.buffer, 0, offset));
+        let (compressed_size, size_size) =
+            need_more!(read_long(&self.buffer, count_size, offset));
+        let (Ok(record_count), Ok(compressed_size)) = (
+            u64::try_from(record_count),
+            usize::try_from(compressed_size),
+        ) else {
+            return exec_err!("Invalid Avro block at offset {offset}");
+        };
+        let data_end = count_size + size_size + compressed_size;
+        let Some(sync) = self.buffer.get(data_end..data_end + SYNC_LENGTH) else {
+            return Ok(None);
+        };
+        if sync != header.sync {
+            return exec_err!("Invalid sync marker after Avro block at offset {offset}");
+        }
+
+        self.position += (data_end + SYNC_LENGTH) as u64;
+        let block = self.buffer.split_to(data_end + SYNC_LENGTH);
+        decode_block(
+            &header.bytes,
+            &block,
+            record_count,
+            Arc::clone(&header.file_schema),
+            Arc::clone(&header.schema),
+            self.projection.clone(),
+        )
+        .map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Cursor;
+
+    use crate::avro_to_arrow::Reader;
+    use apache_avro::types::Value;
+    use apache_avro::Codec;
+    use arrow::compute::concat_batches;
+
+    /// Writes an Avro file of 1000 records in blocks of 100 records
+    fn write_file(codec: Codec) -> Vec<u8> {
+        let schema = apache_avro::Schema::parse_str(
+            r#"{"type": "record", "name": "r", "fields": [
+                {"name": "id", "type": "long"},
+                {"name": "name", "type": "string"}
+            ]}"#,
+        )
+        .unwrap();
+        let mut writer = apache_avro::Writer::with_codec(&schema, vec![], codec);
+        for id in 0..1000 {
+            writer
+                .append(Value::Record(vec![
+                    ("id".to_string(), Value::Long(id)),
+                    ("name".to_string(), Value::String(format!("name_{id}"))),
+                ]))
+                .unwrap();
+            if id % 100 == 99 {
+                writer.flush().unwrap();
+            }
+        }
+        writer.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_push_decoder_chunked_input() -> Result<()> {
+        let data = write_file(Codec::Deflate);
+
+        let mut decoder = PushAvroDecoder::new();
+        let mut batches = vec![];
+        let mut remaining = data.as_slice();
+        // Push chunks of varying sizes, splitting the header and the blocks
+        for chunk_size in [1, 3, 7, 64, 500, 4096].into_iter().cycle() {
+            if remaining.is_empty() {
+                break;
+            }
+            let (chunk, rest) = remaining.split_at(chunk_size.min(remaining.len()));
+            batches.extend(decoder.decode(chunk)?);
+            remaining = rest;
+        }
+        assert_eq!(decoder.codec(), Some("deflate"));
+        let schema = decoder.schema().unwrap();
+        decoder.finish()?;
+        assert_eq!(batches.len(), 10);
+
+        let reader = Reader::try_new(Cursor::new(data), Arc::clone(&schema), 1000, None)?;
+        let expected = reader.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            concat_batches(&schema, &batches)?,
+            concat_batches(&schema, &expected)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_decoder_truncated_input() -> Result<()> {
+        let data = write_file(Codec::Null);
+
+        let mut decoder = PushAvroDecoder::new().with_projection(vec!["id".to_string()]);
+        let batches = decoder.decode(&data[..data.len() - 1])?;
+        assert_eq!(batches.len(), 9);
+        assert_eq!(decoder.schema().unwrap().fields().len(), 1);
+        let err = decoder.finish().unwrap_err();
+        assert!(err.to_string().contains("Unexpected end of Avro file"));
+
+        let err = PushAvroDecoder::new().decode(b"PAR1").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Not an Avro object container file"));
+        Ok(())
+    }
+}
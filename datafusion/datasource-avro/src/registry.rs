@@ -0,0 +1,89 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Resolution of Avro table definitions from a schema registry or metastore
+
+use std::fmt::Debug;
+
+use crate::avro_to_arrow::to_arrow_schema;
+
+use apache_avro::Schema as AvroSchema;
+use arrow::datatypes::Schema;
+use async_trait::async_trait;
+use datafusion_common::Result;
+
+/// The definition of an Avro-backed table, as resolved by an
+/// [`AvroTableRegistry`]
+#[derive(Debug, Clone)]
+pub struct AvroTableDefinition {
+    location: String,
+    schema: AvroSchema,
+}
+
+impl AvroTableDefinition {
+    /// Create a definition for the Avro files at `location` (a URL or path
+    /// understood by `ListingTableUrl`) with the given Avro `schema`
+    pub fn new(location: impl Into<String>, schema: AvroSchema) -> Self {
+        Self {
+            location: location.into(),
+            schema,
+        }
+    }
+
+    /// Create a definition from the JSON representation of the Avro schema,
+    /// as commonly returned by schema registries
+    pub fn try_new_from_json(location: impl Into<String>, schema: &str) -> Result<Self> {
+        Ok(Self::new(location, AvroSchema::parse_str(schema)?))
+    }
+
+    /// The location of the Avro files of the table
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// The Avro schema of the table
+    pub fn avro_schema(&self) -> &AvroSchema {
+        &self.schema
+    }
+
+    /// The Arrow schema of the table, converted from its Avro schema
+    pub fn arrow_schema(&self) -> Result<Schema> {
+        to_arrow_schema(&self.schema)
+    }
+}
+
+/// A client of a schema registry or metastore that knows about Avro-backed
+/// tables, organized in namespaces.
+///
+/// Used by catalog implementations to discover tables and resolve their
+/// schemas without reading the Avro files.
+#[async_trait]
+pub trait AvroTableRegistry: Debug + Send + Sync {
+    /// Returns the names of all namespaces in the registry
+    async fn list_namespaces(&self) -> Result<Vec<String>>;
+
+    /// Returns the names of all tables in `namespace`
+    async fn list_tables(&self, namespace: &str) -> Result<Vec<String>>;
+
+    /// Resolves the definition of `table` in `namespace`, returning `None`
+    /// if the registry does not know about it
+    async fn resolve_table(
+        &self,
+        namespace: &str,
+        table: &str,
+    ) -> Result<Option<AvroTableDefinition>>;
+}
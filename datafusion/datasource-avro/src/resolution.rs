@@ -0,0 +1,544 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Diagnostics of the resolution of an Avro writer schema against a reader
+//! schema, following the [schema resolution] rules of the specification
+//!
+//! [schema resolution]: https://avro.apache.org/docs/1.11.1/specification/#schema-resolution
+
+use std::fmt;
+
+use apache_avro::schema::{RecordSchema, SchemaKind, UnionSchema};
+use apache_avro::Schema as AvroSchema;
+use datafusion_common::{plan_err, Result};
+
+/// How a field is resolved between the writer and the reader schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldResolutionKind {
+    /// The reader field has the name of a writer field
+    MatchedByName,
+    /// One of the aliases of the reader field is the name of a writer field
+    MatchedByAlias {
+        /// The name of the field in the writer schema
+        writer_name: String,
+    },
+    /// The reader field is not in the writer schema, and takes its default
+    Defaulted {
+        /// The JSON representation of the default value
+        default: String,
+    },
+    /// The writer field is not in the reader schema, and is skipped
+    Dropped,
+}
+
+/// A type promotion applied to the values of a field, such as `int` to
+/// `long`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypePromotion {
+    /// The type of the field in the writer schema
+    pub writer_type: String,
+    /// The type of the field in the reader schema
+    pub reader_type: String,
+}
+
+/// The resolution of a single field, nested fields included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldResolution {
+    /// The dot separated path of the field, using the reader name for
+    /// matched fields and the writer name for dropped ones
+    pub path: String,
+    /// How the field is resolved
+    pub kind: FieldResolutionKind,
+    /// The type promotion applied to the values of a matched field, if any
+    pub promotion: Option<TypePromotion>,
+}
+
+/// The resolution of every field of a writer schema against a reader schema,
+/// as returned by [`AvroFormat::explain_resolution`]
+///
+/// The fields of each record are listed in the order of the reader schema,
+/// followed by the dropped writer fields.
+///
+/// [`AvroFormat::explain_resolution`]: crate::AvroFormat::explain_resolution
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolutionReport {
+    /// The resolution of each field
+    pub fields: Vec<FieldResolution>,
+}
+
+impl ResolutionReport {
+    /// Returns the resolution of the field at `path`
+    pub fn field(&self, path: &str) -> Option<&FieldResolution> {
+        self.fields.iter().find(|field| field.path == path)
+    }
+}
+
+impl fmt::Display for ResolutionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for field in &self.fields {
+            write!(f, "{}: ", field.path)?;
+            match &field.kind {
+                FieldResolutionKind::MatchedByName => write!(f, "matched by name")?,
+                FieldResolutionKind::MatchedByAlias { writer_name } => {
+                    write!(f, "matched by alias {writer_name}")?
+                }
+                FieldResolutionKind::Defaulted { default } => {
+                    write!(f, "defaulted to {default}")?
+                }
+                FieldResolutionKind::Dropped => write!(f, "dropped")?,
+            }
+            if let Some(promotion) = &field.promotion {
+                write!(
+                    f,
+                    ", promoted from {} to {}",
+                    promotion.writer_type, promotion.reader_type
+                )?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolves `writer` against `reader`, failing if a reader field has neither
+/// a matching writer field nor a default, or if the types of matched fields
+/// are incompatible
+pub(crate) fn explain_resolution(
+    writer: &AvroSchema,
+    reader: &AvroSchema,
+) -> Result<ResolutionReport> {
+    let mut report = ResolutionReport::default();
+    resolve("", writer, reader, &mut report.fields)?;
+    Ok(report)
+}
+
+/// Resolves the type of the field at `path`, adding the resolution of its
+/// nested fields to `fields`, and returns the type promotion applied, if any
+fn resolve(
+    path: &str,
+    writer: &AvroSchema,
+    reader: &AvroSchema,
+    fields: &mut Vec<FieldResolution>,
+) -> Result<Option<TypePromotion>> {
+    if let Some(writer) = nullable_branch(writer) {
+        return resolve(path, writer, reader, fields);
+    }
+    if let Some(reader) = nullable_branch(reader) {
+        return resolve(path, writer, reader, fields);
+    }
+    match (writer, reader) {
+        (AvroSchema::Union(union), _) => {
+            // Each branch the writer may have written must be readable
+            for branch in union.variants() {
+                resolve(path, branch, reader, fields)?;
+            }
+            Ok(None)
+        }
+        (_, AvroSchema::Union(union)) => {
+            // The first reader branch matching the writer type is used
+            for branch in union.variants() {
+                let mut branch_fields = vec![];
+                if let Ok(promotion) = resolve(path, writer, branch, &mut branch_fields) {
+                    fields.extend(branch_fields);
+                    return Ok(promotion);
+                }
+            }
+            plan_err!(
+                "Avro field '{path}' of type {} matches no branch of the reader union",
+                type_name(writer)
+            )
+        }
+        (AvroSchema::Record(writer), AvroSchema::Record(reader)) => {
+            resolve_record(path, writer, reader, fields)?;
+            Ok(None)
+        }
+        (AvroSchema::Array(writer), AvroSchema::Array(reader)) => {
+            resolve(path, &writer.items, &reader.items, fields)
+        }
+        (AvroSchema::Map(writer), AvroSchema::Map(reader)) => {
+            resolve(path, &writer.types, &reader.types, fields)
+        }
+        _ if SchemaKind::from(writer) == SchemaKind::from(reader) => Ok(None),
+        _ if is_promotable(writer, reader) => Ok(Some(TypePromotion {
+            writer_type: type_name(writer),
+            reader_type: type_name(reader),
+        })),
+        _ => plan_err!(
+            "Avro field '{path}' of type {} can not be read as {}",
+            type_name(writer),
+            type_name(reader)
+        ),
+    }
+}
+
+fn resolve_record(
+    path: &str,
+    writer: &RecordSchema,
+    reader: &RecordSchema,
+    fields: &mut Vec<FieldResolution>,
+) -> Result<()> {
+    let field_path = |name: &str| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{path}.{name}")
+        }
+    };
+
+    let mut matched = vec![false; writer.fields.len()];
+    for reader_field in &reader.fields {
+        let path = field_path(&reader_field.name);
+        let by_name = writer
+            .lookup
+            .get(&reader_field.name)
+            .map(|position| (*position, FieldResolutionKind::MatchedByName));
+        let by_alias = || {
+            reader_field.aliases.iter().flatten().find_map(|alias| {
+                writer.lookup.get(alias).map(|position| {
+                    let writer_name = alias.clone();
+                    (
+                        *position,
+                        FieldResolutionKind::MatchedByAlias { writer_name },
+                    )
+                })
+            })
+        };
+        match by_name.or_else(by_alias) {
+            Some((position, kind)) => {
+                matched[position] = true;
+                let index = fields.len();
+                fields.push(FieldResolution {
+                    path: path.clone(),
+                    kind,
+                    promotion: None,
+                });
+                fields[index].promotion = resolve(
+                    &path,
+                    &writer.fields[position].schema,
+                    &reader_field.schema,
+                    fields,
+                )?;
+            }
+            None => match &reader_field.default {
+                Some(default) => fields.push(FieldResolution {
+                    path,
+                    kind: FieldResolutionKind::Defaulted {
+                        default: default.to_string(),
+                    },
+                    promotion: None,
+                }),
+                None => {
+                    return plan_err!(
+                        "Avro reader field '{path}' is missing from the writer schema and has no default"
+                    )
+                }
+            },
+        }
+    }
+
+    for (writer_field, matched) in writer.fields.iter().zip(matched) {
+        if !matched {
+            fields.push(FieldResolution {
+                path: field_path(&writer_field.name),
+                kind: FieldResolutionKind::Dropped,
+                promotion: None,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Compares the Avro schema `written`, derived from the Arrow schema of data
+/// to write, with the schema `expected` of the written files, returning a
+/// description of each difference that prevents writing the data with the
+/// expected schema.
+///
+/// Both schemas must have the same fields, except for expected fields with a
+/// default, and the types of the written fields must be the expected ones or
+/// promotable to them. A nullable field is only compatible with an expected
+/// type that allows null.
+pub(crate) fn write_schema_differences(
+    written: &AvroSchema,
+    expected: &AvroSchema,
+) -> Vec<String> {
+    let mut differences = vec![];
+    write_difference("", written, expected, &mut differences);
+    differences
+}
+
+fn write_difference(
+    path: &str,
+    written: &AvroSchema,
+    expected: &AvroSchema,
+    differences: &mut Vec<String>,
+) {
+    let field = if path.is_empty() { "<root>" } else { path };
+    if let Some(written) = nullable_branch(written) {
+        if !matches!(expected, AvroSchema::Union(union) if union.is_nullable()) {
+            differences.push(format!(
+                "{field}: found nullable {}, expected non nullable {}",
+                type_name(written),
+                type_name(expected)
+            ));
+            return;
+        }
+    }
+    match (written, expected) {
+        (_, AvroSchema::Union(union)) => {
+            let written = nullable_branch(written).unwrap_or(written);
+            let matches_branch = union.variants().iter().any(|branch| {
+                let mut branch_differences = vec![];
+                write_difference(path, written, branch, &mut branch_differences);
+                branch_differences.is_empty()
+            });
+            if !matches_branch {
+                differences.push(format!(
+                    "{field}: found {}, which matches no branch of the expected union",
+                    type_name(written)
+                ));
+            }
+        }
+        (AvroSchema::Record(written), AvroSchema::Record(expected)) => {
+            let field_path = |name: &str| {
+                if path.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{path}.{name}")
+                }
+            };
+            for expected_field in &expected.fields {
+                let path = field_path(&expected_field.name);
+                match written.lookup.get(&expected_field.name) {
+                    Some(position) => write_difference(
+                        &path,
+                        &written.fields[*position].schema,
+                        &expected_field.schema,
+                        differences,
+                    ),
+                    None if expected_field.default.is_some() => {}
+                    None => differences.push(format!(
+                        "{path}: missing, expected {} without a default",
+                        nullable_type_name(&expected_field.schema)
+                    )),
+                }
+            }
+            for written_field in &written.fields {
+                if !expected.lookup.contains_key(&written_field.name) {
+                    differences.push(format!(
+                        "{}: found {}, not in the expected schema",
+                        field_path(&written_field.name),
+                        nullable_type_name(&written_field.schema)
+                    ));
+                }
+            }
+        }
+        (AvroSchema::Array(written), AvroSchema::Array(expected)) => {
+            write_difference(path, &written.items, &expected.items, differences)
+        }
+        (AvroSchema::Map(written), AvroSchema::Map(expected)) => {
+            write_difference(path, &written.types, &expected.types, differences)
+        }
+        (AvroSchema::Decimal(written), AvroSchema::Decimal(expected))
+            if written.precision != expected.precision
+                || written.scale != expected.scale =>
+        {
+            differences.push(format!(
+                "{field}: found decimal({}, {}), expected decimal({}, {})",
+                written.precision, written.scale, expected.precision, expected.scale
+            ))
+        }
+        (AvroSchema::Fixed(written), AvroSchema::Fixed(expected))
+            if written.size != expected.size =>
+        {
+            differences.push(format!(
+                "{field}: found fixed of size {}, expected fixed of size {}",
+                written.size, expected.size
+            ))
+        }
+        _ if SchemaKind::from(written) == SchemaKind::from(expected)
+            || is_promotable(written, expected) => {}
+        _ => differences.push(format!(
+            "{field}: found {}, expected {}",
+            type_name(written),
+            type_name(expected)
+        )),
+    }
+}
+
+/// Returns the non null branch of a `["null", T]` union
+fn nullable_branch(schema: &AvroSchema) -> Option<&AvroSchema> {
+    let AvroSchema::Union(union) = schema else {
+        return None;
+    };
+    match non_null_variants(union).as_slice() {
+        [branch] if union.variants().len() == 2 => Some(branch),
+        _ => None,
+    }
+}
+
+fn non_null_variants(union: &UnionSchema) -> Vec<&AvroSchema> {
+    union
+        .variants()
+        .iter()
+        .filter(|variant| !matches!(variant, AvroSchema::Null))
+        .collect()
+}
+
+/// Returns true if values of the `writer` type can be promoted to the
+/// `reader` type
+fn is_promotable(writer: &AvroSchema, reader: &AvroSchema) -> bool {
+    matches!(
+        (writer, reader),
+        (
+            AvroSchema::Int,
+            AvroSchema::Long | AvroSchema::Float | AvroSchema::Double
+        ) | (AvroSchema::Long, AvroSchema::Float | AvroSchema::Double)
+            | (AvroSchema::Float, AvroSchema::Double)
+            | (AvroSchema::String, AvroSchema::Bytes)
+            | (AvroSchema::Bytes, AvroSchema::String)
+    )
+}
+
+/// Returns the name of the type of `schema`, prefixed with `nullable` for a
+/// `["null", T]` union
+fn nullable_type_name(schema: &AvroSchema) -> String {
+    match nullable_branch(schema) {
+        Some(branch) => format!("nullable {}", type_name(branch)),
+        None => type_name(schema),
+    }
+}
+
+fn type_name(schema: &AvroSchema) -> String {
+    match schema {
+        AvroSchema::Null => "null".to_string(),
+        AvroSchema::Boolean => "boolean".to_string(),
+        AvroSchema::Int => "int".to_string(),
+        AvroSchema::Long => "long".to_string(),
+        AvroSchema::Float => "float".to_string(),
+        AvroSchema::Double => "double".to_string(),
+        AvroSchema::Bytes => "bytes".to_string(),
+        AvroSchema::String => "string".to_string(),
+        AvroSchema::Record(record) => format!("record {}", record.name),
+        AvroSchema::Enum(e) => format!("enum {}", e.name),
+        AvroSchema::Fixed(fixed) => format!("fixed {}", fixed.name),
+        AvroSchema::Ref { name } => name.to_string(),
+        other => format!("{:?}", SchemaKind::from(other)).to_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_added_field_with_default() -> Result<()> {
+        let writer = AvroSchema::parse_str(
+            r#"{
+              "type": "record",
+              "name": "user",
+              "fields": [
+                { "name": "id", "type": "int" },
+                { "name": "username", "type": "string" },
+                { "name": "legacy", "type": "boolean" }
+              ]
+            }"#,
+        )?;
+        let reader = AvroSchema::parse_str(
+            r#"{
+              "type": "record",
+              "name": "user",
+              "fields": [
+                { "name": "id", "type": "long" },
+                { "name": "name", "type": "string", "aliases": ["username"] },
+                { "name": "country", "type": "string", "default": "unknown" }
+              ]
+            }"#,
+        )?;
+
+        let report = explain_resolution(&writer, &reader)?;
+        assert_eq!(
+            report.field("id"),
+            Some(&FieldResolution {
+                path: "id".to_string(),
+                kind: FieldResolutionKind::MatchedByName,
+                promotion: Some(TypePromotion {
+                    writer_type: "int".to_string(),
+                    reader_type: "long".to_string(),
+                }),
+            })
+        );
+        assert_eq!(
+            report.to_string(),
+            "id: matched by name, promoted from int to long\n\
+             name: matched by alias username\n\
+             country: defaulted to \"unknown\"\n\
+             legacy: dropped\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_and_unresolvable_fields() -> Result<()> {
+        let writer = AvroSchema::parse_str(
+            r#"{
+              "type": "record",
+              "name": "r",
+              "fields": [
+                { "name": "address", "type": ["null", {
+                  "type": "record",
+                  "name": "address",
+                  "fields": [{ "name": "zip", "type": "int" }]
+                }] }
+              ]
+            }"#,
+        )?;
+        let reader = AvroSchema::parse_str(
+            r#"{
+              "type": "record",
+              "name": "r",
+              "fields": [
+                { "name": "address", "type": ["null", {
+                  "type": "record",
+                  "name": "address",
+                  "fields": [
+                    { "name": "zip", "type": "double" },
+                    { "name": "street", "type": ["null", "string"], "default": null }
+                  ]
+                }] }
+              ]
+            }"#,
+        )?;
+        let report = explain_resolution(&writer, &reader)?;
+        assert_eq!(
+            report.to_string(),
+            "address: matched by name\n\
+             address.zip: matched by name, promoted from int to double\n\
+             address.street: defaulted to null\n"
+        );
+
+        // Without a default, the added field can not be resolved
+        let err = explain_resolution(
+            &AvroSchema::parse_str(r#"{"type": "record", "name": "r", "fields": []}"#)?,
+            &AvroSchema::parse_str(
+                r#"{"type": "record", "name": "r", "fields": [{"name": "a", "type": "int"}]}"#,
+            )?,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("'a' is missing"), "{err}");
+        Ok(())
+    }
+}
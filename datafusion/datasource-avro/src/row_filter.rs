@@ -0,0 +1,104 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Evaluation of pushed down predicates while decoding Avro records
+//!
+//! The Avro decoder converts records to Arrow one column at a time. When a
+//! predicate is pushed down into the scan, the [`AvroRowFilter`] lets the
+//! decoder build only the columns referenced by the predicate first, evaluate
+//! it to a selection mask, and then build the projected columns for the
+//! selected records only.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use arrow::array::{Array, BooleanArray};
+use arrow::compute::prep_null_mask_filter;
+use arrow::datatypes::{Fields, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::cast::as_boolean_array;
+use datafusion_common::Result;
+use datafusion_datasource::schema_adapter::{SchemaAdapterFactory, SchemaMapper};
+use datafusion_physical_expr::utils::{collect_columns, reassign_predicate_columns};
+use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
+
+/// A predicate evaluated against the decoded records of an Avro file
+pub(crate) struct AvroRowFilter {
+    /// The columns of the file read by the predicate
+    file_schema: SchemaRef,
+    /// Adapts the decoded predicate columns to the table schema
+    mapper: Arc<dyn SchemaMapper>,
+    /// The predicate, with column indices relative to the mapped batch
+    predicate: Arc<dyn PhysicalExpr>,
+}
+
+impl AvroRowFilter {
+    /// Create a filter for `predicate`, expressed in terms of the columns of
+    /// `table_schema`, when reading a file with schema `file_schema`
+    pub(crate) fn try_new(
+        predicate: Arc<dyn PhysicalExpr>,
+        file_schema: &Schema,
+        table_schema: &SchemaRef,
+        schema_adapter_factory: &Arc<dyn SchemaAdapterFactory>,
+    ) -> Result<Self> {
+        let column_names = collect_columns(&predicate)
+            .into_iter()
+            .map(|column| column.name().to_string())
+            .collect::<HashSet<_>>();
+        let predicate_schema = Arc::new(Schema::new(
+            table_schema
+                .fields()
+                .iter()
+                .filter(|field| column_names.contains(field.name()))
+                .cloned()
+                .collect::<Fields>(),
+        ));
+
+        let (mapper, file_projection) = schema_adapter_factory
+            .create(Arc::clone(&predicate_schema), Arc::clone(table_schema))
+            .map_schema(file_schema)?;
+        let predicate = reassign_predicate_columns(predicate, &predicate_schema, false)?;
+
+        Ok(Self {
+            file_schema: Arc::new(file_schema.project(&file_projection)?),
+            mapper,
+            predicate,
+        })
+    }
+
+    /// The columns of the file that must be decoded to evaluate the predicate
+    pub(crate) fn file_schema(&self) -> &SchemaRef {
+        &self.file_schema
+    }
+
+    /// Evaluates the predicate on `batch`, which holds the columns of
+    /// [`Self::file_schema`], returning a mask of the selected records.
+    ///
+    /// Records for which the predicate evaluates to `NULL` are not selected.
+    pub(crate) fn evaluate(&self, batch: RecordBatch) -> Result<BooleanArray> {
+        let batch = self.mapper.map_batch(batch)?;
+        let mask = self
+            .predicate
+            .evaluate(&batch)?
+            .into_array(batch.num_rows())?;
+        let mask = as_boolean_array(&mask)?;
+        Ok(match mask.null_count() {
+            0 => mask.clone(),
+            _ => prep_null_mask_filter(mask),
+        })
+    }
+}
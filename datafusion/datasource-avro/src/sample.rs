@@ -0,0 +1,194 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Deterministic random sampling of the records of Avro files
+
+use datafusion_common::{plan_err, Result};
+use object_store::path::Path;
+
+/// Keeps each record of a file with probability `fraction`, drawing a
+/// pseudo-random number from the seed, the path of the file and the index of
+/// the record in it.
+///
+/// The draws of a record don't depend on the other records, so the sample
+/// is the same however the files are partitioned and split into batches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RecordSample {
+    fraction: f64,
+    seed: u64,
+}
+
+impl RecordSample {
+    /// Create a sample of `fraction` of the records for `seed`, failing if
+    /// `fraction` is not between 0 and 1
+    pub(crate) fn try_new(fraction: f64, seed: u64) -> Result<Self> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return plan_err!("Avro sample fraction {fraction} is not between 0 and 1");
+        }
+        Ok(Self { fraction, seed })
+    }
+
+    /// Returns the sample of the records of the file at `location`
+    pub(crate) fn for_file(&self, location: &Path) -> Self {
+        let seed = location
+            .as_ref()
+            .bytes()
+            .fold(self.seed, |seed, byte| mix(seed ^ u64::from(byte)));
+        Self { seed, ..*self }
+    }
+
+    /// Returns whether the `record_index`th record of the file is kept
+    pub(crate) fn keeps(&self, record_index: i64) -> bool {
+        let draw = mix(self.seed ^ mix(record_index as u64));
+        // The 53 high bits as a float uniformly distributed in [0, 1)
+        ((draw >> 11) as f64) * (1.0 / (1u64 << 53) as f64) < self.fraction
+    }
+}
+
+/// The SplitMix64 finalizer, mapping consecutive integers to seemingly
+/// independent ones
+fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::source::AvroSource;
+    use apache_avro::types::Value;
+    use arrow::array::AsArray;
+    use arrow::datatypes::Int64Type;
+    use datafusion_datasource::file_groups::FileGroup;
+    use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
+    use datafusion_datasource::source::DataSourceExec;
+    use datafusion_datasource::PartitionedFile;
+    use datafusion_execution::object_store::ObjectStoreUrl;
+    use datafusion_execution::TaskContext;
+    use datafusion_physical_plan::{common, ExecutionPlan};
+    use object_store::memory::InMemory;
+    use object_store::ObjectStore;
+
+    /// Scans a sample of two files of 500 records each, whose `id`s are
+    /// 0..1000, in `partitions` partitions, returning the sampled ids
+    async fn sample(
+        fraction: f64,
+        seed: u64,
+        partitions: usize,
+        batch_size: usize,
+    ) -> Result<Vec<i64>> {
+        let schema = apache_avro::Schema::parse_str(
+            r#"{"type": "record", "name": "r", "fields": [{"name": "id", "type": "long"}]}"#,
+        )
+        .unwrap();
+        let store = Arc::new(InMemory::new());
+        let mut files = vec![];
+        for file in 0..2 {
+            let mut writer = apache_avro::Writer::new(&schema, vec![]);
+            for id in file * 500..(file + 1) * 500 {
+                writer
+                    .append(Value::Record(vec![("id".to_string(), Value::Long(id))]))
+                    .unwrap();
+            }
+            let data = writer.into_inner().unwrap();
+            let location = format!("file{file}.avro");
+            files.push(PartitionedFile::new(location.clone(), data.len() as u64));
+            store.put(&Path::from(location), data.into()).await?;
+        }
+        let file_schema = Arc::new(arrow::datatypes::Schema::new(vec![
+            arrow::datatypes::Field::new("id", arrow::datatypes::DataType::Int64, false),
+        ]));
+
+        let task_ctx = Arc::new(TaskContext::default());
+        let url = ObjectStoreUrl::parse("memory://")?;
+        task_ctx
+            .runtime_env()
+            .register_object_store(url.as_ref(), store);
+
+        let source = Arc::new(
+            AvroSource::new()
+                .with_sample_fraction(Some(fraction))
+                .with_sample_seed(seed),
+        );
+        let file_groups = match partitions {
+            1 => vec![FileGroup::new(files)],
+            _ => files
+                .into_iter()
+                .map(|file| FileGroup::new(vec![file]))
+                .collect(),
+        };
+        let conf = FileScanConfigBuilder::new(url, file_schema, source)
+            .with_file_groups(file_groups)
+            .with_batch_size(Some(batch_size))
+            .build();
+        let exec = DataSourceExec::from_data_source(conf);
+        let mut ids = vec![];
+        for partition in 0..partitions {
+            let batches =
+                common::collect(exec.execute(partition, Arc::clone(&task_ctx))?).await?;
+            for batch in batches {
+                ids.extend(batch.column(0).as_primitive::<Int64Type>().values());
+            }
+        }
+        Ok(ids)
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_sample() -> Result<()> {
+        let ids = sample(0.2, 42, 1, 1024).await?;
+        assert!((150..250).contains(&ids.len()), "{}", ids.len());
+
+        // The same seed samples the same rows, however the files are
+        // partitioned and batched
+        assert_eq!(sample(0.2, 42, 2, 7).await?, ids);
+        // Another seed samples other rows
+        assert_ne!(sample(0.2, 43, 1, 1024).await?, ids);
+
+        assert_eq!(
+            sample(1.0, 42, 2, 1024).await?,
+            (0..1000).collect::<Vec<_>>()
+        );
+        assert!(sample(0.0, 42, 2, 1024).await?.is_empty());
+
+        let err = sample(1.5, 42, 1, 1024).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Avro sample fraction 1.5 is not between 0 and 1"),
+            "{err}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sample_depends_on_file() -> Result<()> {
+        let sample = RecordSample::try_new(0.5, 42)?;
+        let kept = |location: &str| {
+            let sample = sample.for_file(&Path::from(location));
+            (0..64)
+                .filter(|index| sample.keeps(*index))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(kept("a.avro"), kept("a.avro"));
+        assert_ne!(kept("a.avro"), kept("b.avro"));
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Headerless Avro files, whose schema is stored out-of-band in a sidecar
+//! file of their directory, see [`AvroFormat::with_sidecar_schema_file`]
+//!
+//! [`AvroFormat::with_sidecar_schema_file`]: crate::AvroFormat::with_sidecar_schema_file
+
+use std::io::Cursor;
+
+use apache_avro::Schema as AvroSchema;
+use datafusion_common::{DataFusionError, Result};
+use object_store::path::Path;
+use object_store::ObjectStore;
+
+/// Conventional name of the sidecar file holding the Avro schema, in its
+/// JSON representation, of the headerless files of a directory
+pub const DEFAULT_SIDECAR_SCHEMA_FILE: &str = "_schema.avsc";
+
+/// Returns the location of the sidecar file `name` in the directory of the
+/// file at `location`
+fn sidecar_location(location: &Path, name: &str) -> Path {
+    match location.as_ref().rsplit_once(object_store::path::DELIMITER) {
+        Some((directory, _)) => Path::from(format!("{directory}/{name}")),
+        None => Path::from(name),
+    }
+}
+
+/// Fetches and parses the sidecar schema file `name` of the directory of the
+/// file at `location`, returning `None` if the directory has none
+pub(crate) async fn fetch_sidecar_schema(
+    store: &dyn ObjectStore,
+    location: &Path,
+    name: &str,
+) -> Result<Option<AvroSchema>> {
+    let sidecar = sidecar_location(location, name);
+    let bytes = match store.get(&sidecar).await {
+        Ok(result) => result.bytes().await?,
+        Err(object_store::Error::NotFound { .. }) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    AvroSchema::parse_reader(&mut bytes.as_ref())
+        .map(Some)
+        .map_err(|e| {
+            DataFusionError::from(e)
+                .context(format!("Invalid Avro schema in sidecar file {sidecar}"))
+        })
+}
+
+/// Converts `data`, a sequence of bare datums of `schema` without a header,
+/// into an Avro object container file so that it is decoded like any other
+/// file
+pub(crate) fn datums_to_container(
+    schema: &AvroSchema,
+    data: &[u8],
+) -> Result<Cursor<Vec<u8>>> {
+    let mut writer = apache_avro::Writer::new(schema, vec![]);
+    let mut remaining = data;
+    while !remaining.is_empty() {
+        let value = apache_avro::from_avro_datum(schema, &mut remaining, None)?;
+        writer.append(value)?;
+    }
+    Ok(Cursor::new(writer.into_inner()?))
+}
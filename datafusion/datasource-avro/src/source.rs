@@ -18,28 +18,100 @@
 //! Execution plan for reading line-delimited Avro files
 
 use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Formatter;
+use std::io::Read;
 use std::sync::Arc;
 
-use crate::avro_to_arrow::Reader as AvroReader;
+use crate::avro_to_arrow::{
+    avro_schema_to_arrow, read_avro_schema_with_options, validate_encoding,
+    with_field_case, DecodeMode, MapDuplicateKeyPolicy, NameCollisionPolicy,
+    NonMidnightPolicy, Reader as AvroReader, UnionRepresentation, ROW_INDEX_COLUMN,
+};
+use crate::decode_buffer_pool::{DecodeBufferPool, PooledBytes};
+use crate::decode_pool::DecodePool;
+use crate::enum_symbols::{enum_symbols, EnumSymbols, UnknownEnumSymbolPolicy};
+use crate::fetch::BlockFetchOptions;
+use crate::missing_file::MissingFilePolicy;
+use crate::null_defaults::NullDefaults;
+use crate::row_filter::AvroRowFilter;
+use crate::sample::RecordSample;
+use crate::sidecar::{datums_to_container, fetch_sidecar_schema};
 
-use arrow::datatypes::SchemaRef;
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion_common::config::ConfigOptions;
 use datafusion_common::error::Result;
-use datafusion_common::Statistics;
+use datafusion_common::{ScalarValue, Statistics};
 use datafusion_datasource::file::FileSource;
+use datafusion_datasource::file_compression_type::{
+    CompressionDetection, FileCompressionType,
+};
 use datafusion_datasource::file_scan_config::FileScanConfig;
 use datafusion_datasource::file_stream::FileOpener;
-use datafusion_datasource::schema_adapter::SchemaAdapterFactory;
+use datafusion_datasource::schema_adapter::{
+    DefaultSchemaAdapterFactory, SchemaAdapterFactory, SchemaMapper,
+};
+use datafusion_physical_expr::conjunction;
+use datafusion_physical_expr::utils::collect_columns;
+use datafusion_physical_expr_common::physical_expr::{fmt_sql, PhysicalExpr};
 use datafusion_physical_expr_common::sort_expr::LexOrdering;
-use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
+use datafusion_physical_plan::filter_pushdown::{
+    FilterPushdownPropagation, PredicateSupports,
+};
+use datafusion_physical_plan::metrics::{ExecutionPlanMetricsSet, Label, MetricBuilder};
+use datafusion_physical_plan::DisplayFormatType;
 
-use object_store::ObjectStore;
+use bytes::Bytes;
+use object_store::path::Path;
+use object_store::{GetResultPayload, ObjectMeta, ObjectStore};
+
+/// Static labels attached to the metrics of Avro scans, such as the tenant or
+/// the query they are run for
+///
+/// Set as an extension of the [`SessionConfig`] to label the metrics of all
+/// the Avro scans planned with it, see [`AvroSource::with_metrics_labels`].
+///
+/// [`SessionConfig`]: datafusion_execution::config::SessionConfig
+#[derive(Debug, Clone, Default)]
+pub struct AvroMetricsLabels(pub Vec<Label>);
 
 /// AvroSource holds the extra configuration that is necessary for opening avro files
+///
+/// Filters pushed down into the scan (see [`FileSource::try_pushdown_filters`])
+/// are evaluated while decoding: the columns referenced by the predicate are
+/// built first, and the projected columns are only built for the records that
+/// pass the predicate.
+///
+/// Files are decoded as the output stream is polled, unless
+/// [`Self::with_max_in_flight_batches`] is set, in which case they are decoded
+/// on a separate task that runs ahead of the consumer by a bounded number of
+/// batches, or [`Self::with_decode_pool`] is set, in which case they are
+/// decoded on the threads of a [`DecodePool`].
 #[derive(Clone, Default)]
 pub struct AvroSource {
     schema: Option<SchemaRef>,
     batch_size: Option<usize>,
     projection: Option<Vec<String>>,
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    union_representation: UnionRepresentation,
+    name_collision_policy: NameCollisionPolicy,
+    block_fetch: Option<BlockFetchOptions>,
+    decode_mode: DecodeMode,
+    map_duplicate_key_policy: MapDuplicateKeyPolicy,
+    non_midnight_policy: NonMidnightPolicy,
+    row_index: bool,
+    max_in_flight_batches: Option<usize>,
+    decode_pool: Option<Arc<DecodePool>>,
+    decode_buffer_pool: Option<Arc<DecodeBufferPool>>,
+    compression_detection: CompressionDetection,
+    sidecar_schema_file: Option<String>,
+    case_insensitive_field_resolution: bool,
+    missing_file_policy: MissingFilePolicy,
+    sample_fraction: Option<f64>,
+    sample_seed: u64,
+    null_defaults: HashMap<String, ScalarValue>,
+    enum_symbol_policy: Option<UnknownEnumSymbolPolicy>,
+    metrics_labels: Vec<Label>,
     metrics: ExecutionPlanMetricsSet,
     projected_statistics: Option<Statistics>,
     schema_adapter_factory: Option<Arc<dyn SchemaAdapterFactory>>,
@@ -51,13 +123,691 @@ impl AvroSource {
         Self::default()
     }
 
-    fn open<R: std::io::Read>(&self, reader: R) -> Result<AvroReader<'static, R>> {
-        AvroReader::try_new(
+    /// Set the predicate evaluated while decoding, expressed in terms of the
+    /// columns of the table schema
+    pub fn with_predicate(&self, predicate: Arc<dyn PhysicalExpr>) -> Self {
+        let mut conf = self.clone();
+        conf.predicate = Some(predicate);
+        conf
+    }
+
+    /// The predicate evaluated while decoding, if any
+    pub fn predicate(&self) -> Option<&Arc<dyn PhysicalExpr>> {
+        self.predicate.as_ref()
+    }
+
+    /// Set how multi-branch unions are decoded, which must match the
+    /// representation used by the table schema
+    pub fn with_union_representation(
+        &self,
+        union_representation: UnionRepresentation,
+    ) -> Self {
+        let mut conf = self.clone();
+        conf.union_representation = union_representation;
+        conf
+    }
+
+    /// Returns how multi-branch unions are decoded
+    pub fn union_representation(&self) -> UnionRepresentation {
+        self.union_representation
+    }
+
+    /// Set how fields with the same name are handled, which must match the
+    /// policy used to infer the table schema
+    pub fn with_name_collision_policy(
+        &self,
+        name_collision_policy: NameCollisionPolicy,
+    ) -> Self {
+        let mut conf = self.clone();
+        conf.name_collision_policy = name_collision_policy;
+        conf
+    }
+
+    /// Returns how fields with the same name are handled
+    pub fn name_collision_policy(&self) -> NameCollisionPolicy {
+        self.name_collision_policy
+    }
+
+    /// Set how files are fetched in blocks, rather than with a single
+    /// request for the whole file
+    pub fn with_block_fetch(&self, block_fetch: Option<BlockFetchOptions>) -> Self {
+        let mut conf = self.clone();
+        conf.block_fetch = block_fetch;
+        conf
+    }
+
+    /// Returns how files are fetched in blocks, if they are
+    pub fn block_fetch(&self) -> Option<BlockFetchOptions> {
+        self.block_fetch
+    }
+
+    /// Set how strictly the binary encoding of files is checked, where
+    /// [`DecodeMode::Strict`] reads each file once more before decoding it
+    pub fn with_decode_mode(&self, decode_mode: DecodeMode) -> Self {
+        let mut conf = self.clone();
+        conf.decode_mode = decode_mode;
+        conf
+    }
+
+    /// Returns how strictly the binary encoding of files is checked
+    pub fn decode_mode(&self) -> DecodeMode {
+        self.decode_mode
+    }
+
+    /// Set how keys occurring more than once in an Avro map are handled
+    /// when decoding it as an Arrow `Map`
+    pub fn with_map_duplicate_key_policy(
+        &self,
+        map_duplicate_key_policy: MapDuplicateKeyPolicy,
+    ) -> Self {
+        let mut conf = self.clone();
+        conf.map_duplicate_key_policy = map_duplicate_key_policy;
+        conf
+    }
+
+    /// Returns how keys occurring more than once in an Avro map are handled
+    pub fn map_duplicate_key_policy(&self) -> MapDuplicateKeyPolicy {
+        self.map_duplicate_key_policy
+    }
+
+    /// Set how the timestamps decoded as `Date32` columns of the table
+    /// schema, see [`AvroFormat::with_date_columns`], that are not at
+    /// midnight are handled
+    ///
+    /// [`AvroFormat::with_date_columns`]: crate::file_format::AvroFormat::with_date_columns
+    pub fn with_non_midnight_policy(
+        &self,
+        non_midnight_policy: NonMidnightPolicy,
+    ) -> Self {
+        let mut conf = self.clone();
+        conf.non_midnight_policy = non_midnight_policy;
+        conf
+    }
+
+    /// Returns how the timestamps of date columns that are not at midnight
+    /// are handled
+    pub fn non_midnight_policy(&self) -> NonMidnightPolicy {
+        self.non_midnight_policy
+    }
+
+    /// Set whether the [`ROW_INDEX_COLUMN`] of the table schema holds the
+    /// index of each row in its file, rather than being read from the files
+    /// - defaults to `false`
+    ///
+    /// The index is a non-null `Int64` counting from 0 at the start of each
+    /// file and across its batches, including the rows skipped by a pushed
+    /// down predicate. Files are never split between partitions, so the
+    /// index doesn't depend on how the files are partitioned.
+    pub fn with_row_index(&self, row_index: bool) -> Self {
+        let mut conf = self.clone();
+        conf.row_index = row_index;
+        conf
+    }
+
+    /// Returns whether the [`ROW_INDEX_COLUMN`] holds the index of each row
+    /// in its file
+    pub fn row_index(&self) -> bool {
+        self.row_index
+    }
+
+    /// Set the maximum number of decoded batches buffered ahead of the
+    /// consumer of the scan, decoding files on a separate task that pauses
+    /// while that many batches are waiting to be consumed
+    /// - defaults to `None`, decoding the files as the scan is polled
+    ///
+    /// This bounds the memory held by batches the consumer hasn't caught up
+    /// with yet, while letting decoding overlap with downstream work. A
+    /// bound of 0 is treated as 1.
+    pub fn with_max_in_flight_batches(
+        &self,
+        max_in_flight_batches: Option<usize>,
+    ) -> Self {
+        let mut conf = self.clone();
+        conf.max_in_flight_batches = max_in_flight_batches;
+        conf
+    }
+
+    /// Returns the maximum number of decoded batches buffered ahead of the
+    /// consumer, if decoding runs on a separate task
+    pub fn max_in_flight_batches(&self) -> Option<usize> {
+        self.max_in_flight_batches
+    }
+
+    /// Set the pool whose threads decode the files, instead of the task
+    /// polling the scan
+    /// - defaults to `None`, decoding the files on the async runtime
+    ///
+    /// Decoding on a pool keeps the threads of the runtime free for I/O and
+    /// cancellation, at the cost of handing each batch over to the runtime,
+    /// which only pays off for large files. The decoder runs ahead of the
+    /// consumer by [`Self::max_in_flight_batches`] batches, or a single
+    /// batch if it is not set.
+    pub fn with_decode_pool(&self, decode_pool: Option<Arc<DecodePool>>) -> Self {
+        let mut conf = self.clone();
+        conf.decode_pool = decode_pool;
+        conf
+    }
+
+    /// Returns the pool whose threads decode the files, if any
+    pub fn decode_pool(&self) -> Option<&Arc<DecodePool>> {
+        self.decode_pool.as_ref()
+    }
+
+    /// Set the pool of the scratch buffers of the decoders, reusing them
+    /// across the files of this scan and of any other scan sharing the pool
+    /// - defaults to `None`, allocating the buffers of each file
+    ///
+    /// This reduces the allocations of scans of many small files.
+    pub fn with_decode_buffer_pool(
+        &self,
+        decode_buffer_pool: Option<Arc<DecodeBufferPool>>,
+    ) -> Self {
+        let mut conf = self.clone();
+        conf.decode_buffer_pool = decode_buffer_pool;
+        conf
+    }
+
+    /// Returns the pool of the scratch buffers of the decoders, if any
+    pub fn decode_buffer_pool(&self) -> Option<&Arc<DecodeBufferPool>> {
+        self.decode_buffer_pool.as_ref()
+    }
+
+    /// Set how the compression of the files is detected. Files can only be
+    /// compressed as a whole when their compression is detected from their
+    /// magic bytes, as Avro files have no compression extension
+    /// - defaults to [`CompressionDetection::ByExtension`], reading the
+    ///   files uncompressed
+    ///
+    /// Compressed files are fetched and decompressed in full before they
+    /// are decoded, and can't be fetched in blocks.
+    pub fn with_compression_detection(
+        &self,
+        compression_detection: CompressionDetection,
+    ) -> Self {
+        let mut conf = self.clone();
+        conf.compression_detection = compression_detection;
+        conf
+    }
+
+    /// Returns how the compression of the files is detected
+    pub fn compression_detection(&self) -> CompressionDetection {
+        self.compression_detection
+    }
+
+    /// Set the name of the sidecar file holding the Avro schema of the
+    /// headerless files of a directory, such as
+    /// [`DEFAULT_SIDECAR_SCHEMA_FILE`](crate::DEFAULT_SIDECAR_SCHEMA_FILE)
+    /// - defaults to `None`, reading every file as an Avro object container
+    ///   file
+    ///
+    /// The files of a directory with this sidecar file are read as a
+    /// sequence of bare, uncompressed, datums of its schema, and are fetched
+    /// in full before they are decoded.
+    pub fn with_sidecar_schema_file(&self, sidecar_schema_file: Option<String>) -> Self {
+        let mut conf = self.clone();
+        conf.sidecar_schema_file = sidecar_schema_file;
+        conf
+    }
+
+    /// Returns the name of the sidecar file holding the Avro schema of the
+    /// headerless files of a directory, if any
+    pub fn sidecar_schema_file(&self) -> Option<&str> {
+        self.sidecar_schema_file.as_deref()
+    }
+
+    /// Set whether the columns of the table schema are matched to the fields
+    /// of the files ignoring case, for files whose field casing changed
+    /// between versions of their schema (`userId` and `userid`)
+    /// - defaults to `false`
+    ///
+    /// Opening a file fails if a column matches several of its fields that
+    /// are only distinct by case.
+    pub fn with_case_insensitive_field_resolution(
+        &self,
+        case_insensitive_field_resolution: bool,
+    ) -> Self {
+        let mut conf = self.clone();
+        conf.case_insensitive_field_resolution = case_insensitive_field_resolution;
+        conf
+    }
+
+    /// Returns whether the columns of the table schema are matched to the
+    /// fields of the files ignoring case
+    pub fn case_insensitive_field_resolution(&self) -> bool {
+        self.case_insensitive_field_resolution
+    }
+
+    /// Set what the scan does when a file listed while planning is not found
+    /// once it is opened, such as skipping it rather than failing the scan
+    /// - defaults to [`MissingFilePolicy::Error`]
+    pub fn with_missing_file_policy(
+        &self,
+        missing_file_policy: MissingFilePolicy,
+    ) -> Self {
+        let mut conf = self.clone();
+        conf.missing_file_policy = missing_file_policy;
+        conf
+    }
+
+    /// Returns what the scan does when a file is not found once it is opened
+    pub fn missing_file_policy(&self) -> MissingFilePolicy {
+        self.missing_file_policy
+    }
+
+    /// Only return a random sample of the rows, keeping each row with
+    /// probability `fraction`, between 0 and 1 - defaults to `None`, returning
+    /// all the rows
+    ///
+    /// The sample is deterministic: the draw of each row is derived from the
+    /// [`Self::sample_seed`], the path of its file and its index in the file,
+    /// so the same seed returns the same sample of unchanged files, whatever
+    /// the number of partitions or the batch size. Opening a file fails if
+    /// `fraction` is not between 0 and 1.
+    pub fn with_sample_fraction(&self, fraction: Option<f64>) -> Self {
+        let mut conf = self.clone();
+        conf.sample_fraction = fraction;
+        conf
+    }
+
+    /// Returns the probability of each row to be returned, if the rows are
+    /// sampled
+    pub fn sample_fraction(&self) -> Option<f64> {
+        self.sample_fraction
+    }
+
+    /// Set the seed of the sample of [`Self::with_sample_fraction`]
+    /// - defaults to 0
+    pub fn with_sample_seed(&self, seed: u64) -> Self {
+        let mut conf = self.clone();
+        conf.sample_seed = seed;
+        conf
+    }
+
+    /// Returns the seed of the sample of the rows
+    pub fn sample_seed(&self) -> u64 {
+        self.sample_seed
+    }
+
+    /// Set the values replacing the nulls of columns of the table, by column
+    /// name - defaults to none
+    ///
+    /// Unlike the defaults of columns missing from a file, the nulls read
+    /// from the files are substituted, such as replacing null `Int32`
+    /// values with `ScalarValue::Int32(Some(0))`. Filters on these columns
+    /// are not pushed down into the scan, so that they see the substituted
+    /// values. Opening a file fails if a column is not in the table schema,
+    /// or if its default is null or not of the type of the column.
+    pub fn with_null_defaults(
+        &self,
+        null_defaults: HashMap<String, ScalarValue>,
+    ) -> Self {
+        let mut conf = self.clone();
+        conf.null_defaults = null_defaults;
+        conf
+    }
+
+    /// Returns the values replacing the nulls of columns of the table, by
+    /// column name
+    pub fn null_defaults(&self) -> &HashMap<String, ScalarValue> {
+        &self.null_defaults
+    }
+
+    /// Set how the values of enum columns are resolved against the symbols
+    /// of their enum in the table schema - defaults to `None`, reading the
+    /// symbols as they are
+    ///
+    /// Values that are not symbols of the enum, written with a newer version
+    /// of it, are replaced with the `default` symbol of the enum if it has
+    /// one, and handled according to the [`UnknownEnumSymbolPolicy`]
+    /// otherwise. Filters on enum columns are not pushed down into the scan
+    /// when they are resolved, so that they see the resolved values.
+    ///
+    /// Enum columns are the columns of the table schema with symbols in
+    /// their [`AVRO_SYMBOLS_METADATA_KEY`] metadata, which the schema inferred
+    /// by [`AvroFormat::with_enum_symbols`] declares.
+    ///
+    /// [`AVRO_SYMBOLS_METADATA_KEY`]: crate::avro_to_arrow::AVRO_SYMBOLS_METADATA_KEY
+    /// [`AvroFormat::with_enum_symbols`]: crate::file_format::AvroFormat::with_enum_symbols
+    pub fn with_enum_symbol_policy(
+        &self,
+        enum_symbol_policy: Option<UnknownEnumSymbolPolicy>,
+    ) -> Self {
+        let mut conf = self.clone();
+        conf.enum_symbol_policy = enum_symbol_policy;
+        conf
+    }
+
+    /// Returns how the values of enum columns are resolved against the
+    /// symbols of their enum, if they are
+    pub fn enum_symbol_policy(&self) -> Option<UnknownEnumSymbolPolicy> {
+        self.enum_symbol_policy
+    }
+
+    /// Set the labels attached to the metrics of the scan, in addition to
+    /// the partition - defaults to none
+    pub fn with_metrics_labels(&self, metrics_labels: Vec<Label>) -> Self {
+        let mut conf = self.clone();
+        conf.metrics_labels = metrics_labels;
+        conf
+    }
+
+    /// Returns the labels attached to the metrics of the scan
+    pub fn metrics_labels(&self) -> &[Label] {
+        &self.metrics_labels
+    }
+
+    /// Opens `reader`, the file at `location`, with the schema found in its
+    /// header, returning the reader together with a [`SchemaMapper`] that
+    /// adapts the decoded batches to the projected table schema (reordering,
+    /// casting and filling missing columns)
+    fn open<R: Read + std::io::Seek>(
+        &self,
+        mut reader: R,
+        location: &Path,
+    ) -> Result<(AvroReader<'static, R>, Arc<dyn SchemaMapper>)> {
+        let table_schema = self.schema.as_ref().expect("Schema must set before open");
+        let file_schema = read_avro_schema_with_options(
+            &mut reader,
+            self.union_representation,
+            self.name_collision_policy,
+        )?;
+        let file_schema = self.resolve_file_schema(file_schema, table_schema)?;
+        reader.rewind()?;
+        if self.decode_mode == DecodeMode::Strict {
+            validate_encoding(&mut reader)?;
+            reader.rewind()?;
+        }
+
+        let schema_adapter_factory = self.schema_adapter_factory_or_default();
+        let schema_adapter = schema_adapter_factory.create(
+            self.projected_table_schema(table_schema),
+            Arc::clone(table_schema),
+        );
+        let (schema_mapper, file_projection) = schema_adapter.map_schema(&file_schema)?;
+        let row_filter = self
+            .predicate
+            .as_ref()
+            .map(|predicate| {
+                AvroRowFilter::try_new(
+                    Arc::clone(predicate),
+                    &file_schema,
+                    table_schema,
+                    &schema_adapter_factory,
+                )
+            })
+            .transpose()?;
+
+        // If no column is read from the file, still decode it so that the
+        // mapped batches carry the correct number of rows
+        let file_schema = if file_projection.is_empty() {
+            Arc::new(file_schema)
+        } else {
+            Arc::new(file_schema.project(&file_projection)?)
+        };
+        let reader = AvroReader::try_new_with_map_duplicate_key_policy(
             reader,
-            Arc::clone(self.schema.as_ref().expect("Schema must set before open")),
+            file_schema,
             self.batch_size.expect("Batch size must set before open"),
-            self.projection.clone(),
-        )
+            None,
+            self.map_duplicate_key_policy,
+        )?
+        .with_union_representation(self.union_representation)
+        .with_non_midnight_policy(self.non_midnight_policy)
+        .with_row_index(self.row_index)
+        .with_case_insensitive_field_resolution(self.case_insensitive_field_resolution)
+        .with_location(location);
+        let reader = match row_filter {
+            Some(row_filter) => reader.with_row_filter(row_filter),
+            None => reader,
+        };
+        let reader = match self.sample_fraction {
+            Some(fraction) => reader.with_sample(
+                RecordSample::try_new(fraction, self.sample_seed)?.for_file(location),
+            ),
+            None => reader,
+        };
+        let reader = match &self.decode_buffer_pool {
+            Some(buffer_pool) => reader.with_buffer_pool(Arc::clone(buffer_pool)),
+            None => reader,
+        };
+        Ok((reader, schema_mapper))
+    }
+
+    /// Checks that files whose header holds `file_schema` can be read as the
+    /// projected table schema, failing with the incompatibility otherwise.
+    ///
+    /// This is the check performed when a file is opened, without reading
+    /// any record.
+    pub fn validate(&self, file_schema: Schema) -> Result<()> {
+        let table_schema = self
+            .schema
+            .as_ref()
+            .expect("Schema must set before validate");
+        let file_schema = self.resolve_file_schema(file_schema, table_schema)?;
+        self.schema_adapter_factory_or_default()
+            .create(
+                self.projected_table_schema(table_schema),
+                Arc::clone(table_schema),
+            )
+            .map_schema(&file_schema)?;
+        Ok(())
+    }
+
+    /// [`Self::validate`]s the header schema of every file in `files`,
+    /// failing with the path of the first incompatible file.
+    ///
+    /// The header of each file is fetched from `object_store`, which is extra
+    /// I/O compared to a scan that only opens each file once.
+    pub async fn validate_files(
+        &self,
+        object_store: &Arc<dyn ObjectStore>,
+        files: &[ObjectMeta],
+    ) -> Result<()> {
+        for object in files {
+            if let Some(name) = &self.sidecar_schema_file {
+                if let Some(schema) =
+                    fetch_sidecar_schema(object_store.as_ref(), &object.location, name)
+                        .await?
+                {
+                    let file_schema = avro_schema_to_arrow(
+                        &schema,
+                        self.union_representation,
+                        self.name_collision_policy,
+                        false,
+                    );
+                    self.validate_file_schema(object, file_schema)?;
+                    continue;
+                }
+            }
+
+            let compression = detect_compression(
+                self.compression_detection,
+                object_store.as_ref(),
+                object,
+            )
+            .await?;
+            if compression.is_compressed() {
+                let data = object_store.get(&object.location).await?.bytes().await?;
+                let file_schema = decompress(compression, data).and_then(|mut data| {
+                    read_avro_schema_with_options(
+                        &mut data,
+                        self.union_representation,
+                        self.name_collision_policy,
+                    )
+                });
+                self.validate_file_schema(object, file_schema)?;
+                continue;
+            }
+
+            let r = object_store.get(&object.location).await?;
+            let file_schema = match r.payload {
+                GetResultPayload::File(mut file, _) => read_avro_schema_with_options(
+                    &mut file,
+                    self.union_representation,
+                    self.name_collision_policy,
+                ),
+                GetResultPayload::Stream(_) => {
+                    let data = r.bytes().await?;
+                    read_avro_schema_with_options(
+                        &mut data.as_ref(),
+                        self.union_representation,
+                        self.name_collision_policy,
+                    )
+                }
+            };
+            self.validate_file_schema(object, file_schema)?;
+        }
+        Ok(())
+    }
+
+    /// [`Self::validate`]s the header schema read from `object`
+    fn validate_file_schema(
+        &self,
+        object: &ObjectMeta,
+        file_schema: Result<Schema>,
+    ) -> Result<()> {
+        file_schema
+            .and_then(|file_schema| self.validate(file_schema))
+            .map_err(|e| {
+                e.context(format!(
+                    "Avro file {} is incompatible with the table schema",
+                    object.location
+                ))
+            })
+    }
+
+    /// Returns `file_schema` as it is decoded to be mapped to `table_schema`:
+    /// its fields named like the table columns they match if they are
+    /// resolved ignoring case, then decoded with the table dictionaries, in
+    /// the table field order and with the row index
+    fn resolve_file_schema(
+        &self,
+        file_schema: Schema,
+        table_schema: &Schema,
+    ) -> Result<Schema> {
+        let file_schema = if self.case_insensitive_field_resolution {
+            with_field_case(
+                file_schema,
+                table_schema
+                    .fields()
+                    .iter()
+                    .map(|field| field.name().as_str()),
+            )?
+        } else {
+            file_schema
+        };
+        Ok(self.with_row_index_field(with_table_field_order(
+            with_table_dictionaries(file_schema, table_schema),
+            table_schema,
+        )))
+    }
+
+    /// Returns `file_schema` with the [`ROW_INDEX_COLUMN`] appended if the
+    /// row index is enabled, so that it is mapped like a column of the file
+    fn with_row_index_field(&self, file_schema: Schema) -> Schema {
+        if !self.row_index || file_schema.field_with_name(ROW_INDEX_COLUMN).is_ok() {
+            return file_schema;
+        }
+        let metadata = file_schema.metadata().clone();
+        let mut fields = file_schema.fields().to_vec();
+        fields.push(Arc::new(Field::new(
+            ROW_INDEX_COLUMN,
+            DataType::Int64,
+            false,
+        )));
+        Schema::new_with_metadata(fields, metadata)
+    }
+
+    /// The factory of the [`SchemaAdapter`]s mapping file schemas to the
+    /// table schema
+    ///
+    /// [`SchemaAdapter`]: datafusion_datasource::schema_adapter::SchemaAdapter
+    fn schema_adapter_factory_or_default(&self) -> Arc<dyn SchemaAdapterFactory> {
+        self.schema_adapter_factory
+            .clone()
+            .unwrap_or_else(|| Arc::new(DefaultSchemaAdapterFactory))
+    }
+
+    /// The table schema restricted to the projected columns, in projection order
+    fn projected_table_schema(&self, table_schema: &SchemaRef) -> SchemaRef {
+        match &self.projection {
+            Some(projection) => Arc::new(Schema::new_with_metadata(
+                projection
+                    .iter()
+                    .filter_map(|name| {
+                        table_schema.column_with_name(name).map(|(_, f)| f.clone())
+                    })
+                    .collect::<Vec<_>>(),
+                table_schema.metadata().clone(),
+            )),
+            None => Arc::clone(table_schema),
+        }
+    }
+}
+
+/// Returns `file_schema` with the string columns that are dictionary encoded
+/// in `table_schema` decoded directly into dictionaries, rather than cast to
+/// them after decoding
+fn with_table_dictionaries(file_schema: Schema, table_schema: &Schema) -> Schema {
+    let fields = file_schema
+        .fields()
+        .iter()
+        .map(|field| match table_schema.field_with_name(field.name()) {
+            Ok(table_field)
+                if field.data_type() == &DataType::Utf8
+                    && matches!(
+                        table_field.data_type(),
+                        DataType::Dictionary(_, value_type)
+                            if value_type.as_ref() == &DataType::Utf8
+                    ) =>
+            {
+                Field::clone(field).with_data_type(table_field.data_type().clone())
+            }
+            _ => Field::clone(field),
+        })
+        .collect::<Vec<_>>();
+    Schema::new_with_metadata(fields, file_schema.metadata().clone())
+}
+
+/// Returns `file_schema` with the fields of its records decoded in the order
+/// of the matching struct columns of `table_schema`.
+///
+/// The decoder looks up the fields of a record by name, so decoding them in
+/// the table order is free, whereas the [`SchemaMapper`] only reorders top
+/// level columns and would cast the fields of a struct by position.
+fn with_table_field_order(file_schema: Schema, table_schema: &Schema) -> Schema {
+    let fields = file_schema
+        .fields()
+        .iter()
+        .map(|field| match table_schema.field_with_name(field.name()) {
+            Ok(table_field) => reorder_struct_fields(field, table_field),
+            Err(_) => Field::clone(field),
+        })
+        .collect::<Vec<_>>();
+    Schema::new_with_metadata(fields, file_schema.metadata().clone())
+}
+
+/// Reorders the children of the struct `field` to the order of `table_field`,
+/// leaving `field` as is if they don't have the same children
+fn reorder_struct_fields(field: &Field, table_field: &Field) -> Field {
+    let (DataType::Struct(children), DataType::Struct(table_children)) =
+        (field.data_type(), table_field.data_type())
+    else {
+        return field.clone();
+    };
+    let reordered = table_children
+        .iter()
+        .map(|table_child| {
+            children
+                .find(table_child.name())
+                .map(|(_, child)| reorder_struct_fields(child, table_child))
+        })
+        .collect::<Option<Vec<_>>>();
+    match reordered {
+        Some(reordered) if reordered.len() == children.len() => field
+            .clone()
+            .with_data_type(DataType::Struct(reordered.into())),
+        _ => field.clone(),
     }
 }
 
@@ -66,11 +816,17 @@ impl FileSource for AvroSource {
         &self,
         object_store: Arc<dyn ObjectStore>,
         _base_config: &FileScanConfig,
-        _partition: usize,
+        partition: usize,
     ) -> Arc<dyn FileOpener> {
         Arc::new(private::AvroOpener {
             config: Arc::new(self.clone()),
             object_store,
+            batches_decoded: self
+                .metrics_labels
+                .iter()
+                .cloned()
+                .fold(MetricBuilder::new(&self.metrics), MetricBuilder::with_label)
+                .counter("batches_decoded", partition),
         })
     }
 
@@ -106,16 +862,49 @@ impl FileSource for AvroSource {
     }
 
     fn statistics(&self) -> Result<Statistics> {
-        let statistics = &self.projected_statistics;
-        Ok(statistics
+        let statistics = self
+            .projected_statistics
             .clone()
-            .expect("projected_statistics must be set"))
+            .expect("projected_statistics must be set");
+        // Sampling and filters evaluated while decoding drop an unknown number
+        // of rows
+        if self.sample_fraction.is_some() || self.predicate.is_some() {
+            return Ok(statistics.to_inexact());
+        }
+        Ok(statistics)
     }
 
     fn file_type(&self) -> &str {
         "avro"
     }
 
+    fn fmt_extra(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                if let Some(predicate) = &self.predicate {
+                    write!(f, ", predicate={predicate}")?;
+                }
+                if let Some(fraction) = self.sample_fraction {
+                    write!(
+                        f,
+                        ", sample_fraction={fraction}, sample_seed={}",
+                        self.sample_seed
+                    )?;
+                }
+            }
+            DisplayFormatType::TreeRender => {
+                if let Some(predicate) = &self.predicate {
+                    writeln!(f, "predicate={}", fmt_sql(predicate.as_ref()))?;
+                }
+                if let Some(fraction) = self.sample_fraction {
+                    writeln!(f, "sample_fraction={fraction}")?;
+                    writeln!(f, "sample_seed={}", self.sample_seed)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn repartitioned(
         &self,
         _target_partitions: usize,
@@ -126,6 +915,38 @@ impl FileSource for AvroSource {
         Ok(None)
     }
 
+    fn try_pushdown_filters(
+        &self,
+        filters: Vec<Arc<dyn PhysicalExpr>>,
+        _config: &ConfigOptions,
+    ) -> Result<FilterPushdownPropagation<Arc<dyn FileSource>>> {
+        let Some(table_schema) = self.schema.clone() else {
+            return Ok(FilterPushdownPropagation::unsupported(filters));
+        };
+        // Filters on columns that are not read from the file (e.g. partition
+        // columns) can not be evaluated while decoding, nor filters on
+        // columns whose values are substituted after decoding
+        let filters = PredicateSupports::new_with_supported_check(filters, |filter| {
+            collect_columns(filter).iter().all(|column| {
+                table_schema
+                    .field_with_name(column.name())
+                    .is_ok_and(|field| {
+                        !self.null_defaults.contains_key(column.name())
+                            && (self.enum_symbol_policy.is_none()
+                                || enum_symbols(field).is_none())
+                    })
+            })
+        });
+        if filters.is_all_unsupported() {
+            return Ok(FilterPushdownPropagation::with_filters(filters));
+        }
+        let supported_filters = filters.collect_supported();
+        let predicate =
+            conjunction(self.predicate.iter().cloned().chain(supported_filters));
+        let source = Arc::new(self.with_predicate(predicate));
+        Ok(FilterPushdownPropagation::with_filters(filters).with_updated_node(source))
+    }
+
     fn with_schema_adapter_factory(
         &self,
         schema_adapter_factory: Arc<dyn SchemaAdapterFactory>,
@@ -141,19 +962,75 @@ impl FileSource for AvroSource {
     }
 }
 
+/// Returns the compression of the Avro file `object`, fetching its first
+/// bytes if `compression_detection` needs them
+pub(crate) async fn detect_compression(
+    compression_detection: CompressionDetection,
+    object_store: &dyn ObjectStore,
+    object: &ObjectMeta,
+) -> Result<FileCompressionType> {
+    // Avro files have no compression extension
+    let from_extension = FileCompressionType::UNCOMPRESSED;
+    if !compression_detection.needs_magic(from_extension) {
+        return Ok(from_extension);
+    }
+    let head_len = object.size.min(CompressionDetection::MAGIC_LEN as u64);
+    let head = object_store
+        .get_range(&object.location, 0..head_len)
+        .await?;
+    compression_detection.resolve(from_extension, &head)
+}
+
+/// Decompresses the whole of `bytes`, returning a reader of the result
+pub(crate) fn decompress(
+    compression: FileCompressionType,
+    bytes: Bytes,
+) -> Result<std::io::Cursor<Vec<u8>>> {
+    let mut decompressed = vec![];
+    decompress_into(compression, bytes, &mut decompressed)?;
+    Ok(std::io::Cursor::new(decompressed))
+}
+
+/// [`decompress`]es `bytes` into a byte buffer of `buffer_pool`
+fn decompress_pooled(
+    compression: FileCompressionType,
+    bytes: Bytes,
+    buffer_pool: &Arc<DecodeBufferPool>,
+) -> Result<std::io::Cursor<PooledBytes>> {
+    let mut decompressed = buffer_pool.take_bytes();
+    decompress_into(compression, bytes, decompressed.as_mut_vec())?;
+    Ok(std::io::Cursor::new(decompressed))
+}
+
+fn decompress_into(
+    compression: FileCompressionType,
+    bytes: Bytes,
+    decompressed: &mut Vec<u8>,
+) -> Result<()> {
+    compression
+        .convert_read(std::io::Cursor::new(bytes))?
+        .read_to_end(decompressed)?;
+    Ok(())
+}
+
 mod private {
     use super::*;
 
-    use bytes::Buf;
+    use arrow::error::ArrowError;
+    use arrow::record_batch::RecordBatch;
     use datafusion_datasource::{
         file_meta::FileMeta, file_stream::FileOpenFuture, PartitionedFile,
     };
+    use datafusion_physical_plan::metrics::Count;
+    use datafusion_physical_plan::stream::RecordBatchReceiverStreamBuilder;
+    use futures::stream::BoxStream;
     use futures::StreamExt;
-    use object_store::{GetResultPayload, ObjectStore};
 
     pub struct AvroOpener {
         pub config: Arc<AvroSource>,
         pub object_store: Arc<dyn ObjectStore>,
+        /// Number of batches decoded, whether or not they were consumed yet
+        pub batches_decoded: Count,
     }
 
     impl FileOpener for AvroOpener {
@@ -164,20 +1041,161 @@ mod private {
         ) -> Result<FileOpenFuture> {
             let config = Arc::clone(&self.config);
             let object_store = Arc::clone(&self.object_store);
+            let batches_decoded = self.batches_decoded.clone();
             Ok(Box::pin(async move {
-                let r = object_store.get(file_meta.location()).await?;
-                match r.payload {
-                    GetResultPayload::File(file, _) => {
-                        let reader = config.open(file)?;
-                        Ok(futures::stream::iter(reader).boxed())
-                    }
-                    GetResultPayload::Stream(_) => {
-                        let bytes = r.bytes().await?;
-                        let reader = config.open(bytes.reader())?;
-                        Ok(futures::stream::iter(reader).boxed())
-                    }
-                }
+                let opened = config
+                    .missing_file_policy
+                    .open(file_meta.location(), || {
+                        open_file(
+                            &config,
+                            object_store.as_ref(),
+                            &file_meta,
+                            batches_decoded.clone(),
+                        )
+                    })
+                    .await?;
+                Ok(opened.unwrap_or_else(|| futures::stream::empty().boxed()))
             }))
         }
     }
+
+    /// Fetches the file of `file_meta` and returns the stream of its batches
+    async fn open_file(
+        config: &AvroSource,
+        object_store: &dyn ObjectStore,
+        file_meta: &FileMeta,
+        batches_decoded: Count,
+    ) -> Result<BoxStream<'static, Result<RecordBatch, ArrowError>>> {
+        // Zero-byte files, skipped by schema inference, have no rows
+        if file_meta.object_meta.size == 0 {
+            return Ok(futures::stream::empty().boxed());
+        }
+        if let Some(name) = &config.sidecar_schema_file {
+            if let Some(schema) =
+                fetch_sidecar_schema(object_store, file_meta.location(), name).await?
+            {
+                let bytes = object_store
+                    .get(file_meta.location())
+                    .await?
+                    .bytes()
+                    .await?;
+                let reader = datums_to_container(&schema, &bytes)?;
+                return decode(config, reader, file_meta.location(), batches_decoded);
+            }
+        }
+
+        let compression = detect_compression(
+            config.compression_detection,
+            object_store,
+            &file_meta.object_meta,
+        )
+        .await?;
+        if compression.is_compressed() {
+            let bytes = object_store
+                .get(file_meta.location())
+                .await?
+                .bytes()
+                .await?;
+            if let Some(buffer_pool) = &config.decode_buffer_pool {
+                let reader = decompress_pooled(compression, bytes, buffer_pool)?;
+                return decode(config, reader, file_meta.location(), batches_decoded);
+            }
+            let reader = decompress(compression, bytes)?;
+            return decode(config, reader, file_meta.location(), batches_decoded);
+        }
+
+        if let Some(block_fetch) = config.block_fetch {
+            let reader = block_fetch
+                .fetch(object_store, &file_meta.object_meta)
+                .await?;
+            return decode(config, reader, file_meta.location(), batches_decoded);
+        }
+
+        let r = object_store.get(file_meta.location()).await?;
+        match r.payload {
+            GetResultPayload::File(file, _) => {
+                decode(config, file, file_meta.location(), batches_decoded)
+            }
+            GetResultPayload::Stream(_) => {
+                let bytes = r.bytes().await?;
+                decode(
+                    config,
+                    std::io::Cursor::new(bytes),
+                    file_meta.location(),
+                    batches_decoded,
+                )
+            }
+        }
+    }
+
+    /// Opens `reader`, the file at `location`, and returns the stream of its
+    /// batches, mapped to the projected table schema.
+    ///
+    /// With [`AvroSource::decode_pool`] or [`AvroSource::max_in_flight_batches`]
+    /// set, the batches are decoded on the threads of the pool or a blocking
+    /// task, sending them through a bounded channel, so that decoding pauses
+    /// while the channel is full.
+    fn decode<R>(
+        config: &AvroSource,
+        reader: R,
+        location: &Path,
+        batches_decoded: Count,
+    ) -> Result<BoxStream<'static, Result<RecordBatch, ArrowError>>>
+    where
+        R: Read + std::io::Seek + Send + 'static,
+    {
+        let (reader, mapper) = config.open(reader, location)?;
+        let table_schema = config.schema.as_ref().expect("Schema must set before open");
+        let projected_table_schema = config.projected_table_schema(table_schema);
+        let enum_symbols =
+            EnumSymbols::try_new(config.enum_symbol_policy, &projected_table_schema)?;
+        let null_defaults = NullDefaults::try_new(
+            &config.null_defaults,
+            table_schema,
+            &projected_table_schema,
+        )?;
+        let batches = reader.map(move |batch| {
+            batches_decoded.add(1);
+            batch.and_then(|b| {
+                mapper
+                    .map_batch(b)
+                    .and_then(|b| enum_symbols.apply(b))
+                    .and_then(|b| null_defaults.apply(b))
+                    .map_err(Into::into)
+            })
+        });
+        if let Some(decode_pool) = &config.decode_pool {
+            let capacity = config.max_in_flight_batches.unwrap_or(1);
+            return Ok(decode_pool.decode(Box::new(batches), capacity));
+        }
+        let Some(max_in_flight_batches) = config.max_in_flight_batches else {
+            return Ok(futures::stream::iter(batches).boxed());
+        };
+
+        // Spawn the decoder once the file is first polled, rather than when
+        // it is opened ahead of time by the `FileStream`
+        let schema = config.projected_table_schema(
+            config.schema.as_ref().expect("Schema must set before open"),
+        );
+        let decoded = futures::stream::once(async move {
+            let mut builder = RecordBatchReceiverStreamBuilder::new(
+                schema,
+                max_in_flight_batches.max(1),
+            );
+            let tx = builder.tx();
+            builder.spawn_blocking(move || {
+                for batch in batches {
+                    let is_err = batch.is_err();
+                    // Stop after the first error, or once the stream is dropped
+                    if tx.blocking_send(batch.map_err(Into::into)).is_err() || is_err {
+                        break;
+                    }
+                }
+                Ok(())
+            });
+            builder.build()
+        })
+        .flatten();
+        Ok(decoded.map(|batch| batch.map_err(Into::into)).boxed())
+    }
 }
@@ -0,0 +1,393 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`AvroTailStream`]: streams the blocks appended to Avro files
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use bytes::Bytes;
+use datafusion_catalog::streaming::StreamingTable;
+use datafusion_common::{exec_err, Result};
+use datafusion_execution::{SendableRecordBatchStream, TaskContext};
+use datafusion_physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion_physical_plan::streaming::PartitionStream;
+use futures::{StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{ObjectMeta, ObjectStore};
+
+use crate::block_stream::{AvroBlockStream, SYNC_LENGTH};
+use crate::encoding::{parse_header, read_long};
+
+/// Streams the records of append-only Avro object container files, such as
+/// the files written by a long running producer, polling the files for
+/// new blocks
+///
+/// A block is only decoded once its sync marker is written, so a block
+/// that is partially written when the files are polled is read by a later
+/// poll. The stream never ends, and [`Self::into_table`] serves it as an
+/// unbounded table.
+#[derive(Debug, Clone)]
+pub struct AvroTailStream {
+    store: Arc<dyn ObjectStore>,
+    /// The file, or the directory of files, to tail
+    location: Path,
+    is_directory: bool,
+    schema: SchemaRef,
+    poll_interval: Duration,
+}
+
+impl AvroTailStream {
+    /// Tail the Avro file at `location` in `store`, whose records are
+    /// decoded as `schema`, such as its [`AvroBlockStream::file_schema`]
+    pub fn new_file(
+        store: Arc<dyn ObjectStore>,
+        location: Path,
+        schema: SchemaRef,
+    ) -> Self {
+        Self {
+            store,
+            location,
+            is_directory: false,
+            schema,
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Tail the Avro files under `prefix` in `store`, reading the blocks
+    /// appended to the files as well as the files added to the directory
+    pub fn new_directory(
+        store: Arc<dyn ObjectStore>,
+        prefix: Path,
+        schema: SchemaRef,
+    ) -> Self {
+        Self {
+            is_directory: true,
+            ..Self::new_file(store, prefix, schema)
+        }
+    }
+
+    /// Set the interval at which the files are polled when no new block
+    /// was read, one second by default
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Returns an unbounded [`StreamingTable`] of the records of the files
+    pub fn into_table(self) -> Result<StreamingTable> {
+        Ok(
+            StreamingTable::try_new(Arc::clone(&self.schema), vec![Arc::new(self)])?
+                .with_infinite_table(true),
+        )
+    }
+}
+
+impl PartitionStream for AvroTailStream {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let state = TailState {
+            tail: self.clone(),
+            files: BTreeMap::new(),
+            batches: VecDeque::new(),
+        };
+        let batch_size = ctx.session_config().batch_size();
+        let stream = futures::stream::try_unfold(state, move |mut state| async move {
+            loop {
+                if let Some(batch) = state.batches.pop_front() {
+                    return Ok(Some((batch, state)));
+                }
+                state.poll(batch_size).await?;
+                if state.batches.is_empty() {
+                    tokio::time::sleep(state.tail.poll_interval).await;
+                }
+            }
+        });
+        Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            stream,
+        ))
+    }
+}
+
+/// The state of an executed [`AvroTailStream`]
+struct TailState {
+    tail: AvroTailStream,
+    /// The files read so far, by location
+    files: BTreeMap<Path, TailFile>,
+    /// Batches read and not emitted yet
+    batches: VecDeque<RecordBatch>,
+}
+
+impl TailState {
+    /// Reads the blocks completed since the last poll
+    async fn poll(&mut self, batch_size: usize) -> Result<()> {
+        let store = &self.tail.store;
+        let mut objects: Vec<ObjectMeta> = if self.tail.is_directory {
+            store.list(Some(&self.tail.location)).try_collect().await?
+        } else {
+            match store.head(&self.tail.location).await {
+                Ok(meta) => vec![meta],
+                // The file may not be created yet
+                Err(object_store::Error::NotFound { .. }) => vec![],
+                Err(e) => return Err(e.into()),
+            }
+        };
+        objects.sort_by(|a, b| a.location.cmp(&b.location));
+
+        for object in objects {
+            let file = self.files.entry(object.location.clone()).or_default();
+            if let Some(blocks) = file.read(store.as_ref(), &object).await? {
+                let mut stream = AvroBlockStream::try_new(
+                    futures::stream::once(async move { Ok(blocks) }).boxed(),
+                )
+                .await?
+                .with_projection(
+                    self.tail
+                        .schema
+                        .fields()
+                        .iter()
+                        .map(|f| f.name().clone())
+                        .collect(),
+                );
+                while let Some((_, batch)) = stream.try_next().await? {
+                    // Split blocks larger than the batch size
+                    let mut offset = 0;
+                    while offset < batch.num_rows() {
+                        let len = batch_size.min(batch.num_rows() - offset);
+                        self.batches.push_back(batch.slice(offset, len));
+                        offset += len;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The read progress of a tailed file
+#[derive(Debug, Default)]
+struct TailFile {
+    /// The header of the file, once completely written
+    header: Option<Bytes>,
+    sync: [u8; SYNC_LENGTH],
+    /// Offset of the first byte not read yet
+    offset: u64,
+}
+
+impl TailFile {
+    /// Reads the blocks completed since the last read, returning them along
+    /// with the header of the file, or `None` if no block was completed
+    async fn read(
+        &mut self,
+        store: &dyn ObjectStore,
+        object: &ObjectMeta,
+    ) -> Result<Option<Bytes>> {
+        if object.size <= self.offset {
+            return Ok(None);
+        }
+        let bytes = store
+            .get_range(&object.location, self.offset..object.size)
+            .await?;
+
+        let mut start = 0;
+        let header = match &self.header {
+            Some(header) => header.clone(),
+            None => {
+                let Some(len) = header_len(&bytes)? else {
+                    return Ok(None);
+                };
+                self.sync.copy_from_slice(&bytes[len - SYNC_LENGTH..len]);
+                self.header = Some(bytes.slice(..len));
+                self.offset += len as u64;
+                start = len;
+                bytes.slice(..len)
+            }
+        };
+
+        let len = complete_blocks_len(&bytes[start..], &self.sync, self.offset)?;
+        if len == 0 {
+            return Ok(None);
+        }
+        self.offset += len as u64;
+        let mut file = Vec::with_capacity(header.len() + len);
+        file.extend_from_slice(&header);
+        file.extend_from_slice(&bytes[start..start + len]);
+        Ok(Some(file.into()))
+    }
+}
+
+/// Returns the length of the header at the start of `buf`, including its
+/// sync marker, or `None` if the header is not completely written
+pub(crate) fn header_len(buf: &[u8]) -> Result<Option<usize>> {
+    Ok(parse_header(buf)?.map(|header| header.len))
+}
+
+/// Returns the length of the complete blocks at the start of `buf`, each
+/// followed by the `sync` marker, where `buf` starts at `offset` of the
+/// file
+fn complete_blocks_len(buf: &[u8], sync: &[u8], offset: u64) -> Result<usize> {
+    let mut end = 0;
+    loop {
+        let Some((record_count, count_size)) = read_long(buf, end, offset)? else {
+            return Ok(end);
+        };
+        let Some((size, size_size)) = read_long(buf, end + count_size, offset)? else {
+            return Ok(end);
+        };
+        let block_offset = offset + end as u64;
+        let (Ok(_), Ok(size)) = (u64::try_from(record_count), usize::try_from(size))
+        else {
+            return exec_err!("Invalid Avro block at offset {block_offset}");
+        };
+        let sync_start = end + count_size + size_size + size;
+        let Some(block_sync) = buf.get(sync_start..sync_start + SYNC_LENGTH) else {
+            // The block is still being written
+            return Ok(end);
+        };
+        if block_sync != sync {
+            return exec_err!(
+                "Invalid sync marker after Avro block at offset {block_offset}"
+            );
+        }
+        end = sync_start + SYNC_LENGTH;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use apache_avro::types::Value;
+    use arrow::array::AsArray;
+    use arrow::datatypes::Int64Type;
+    use object_store::memory::InMemory;
+
+    /// Writes an Avro file of 300 records in blocks of 100 records
+    fn write_file(first_id: i64) -> Vec<u8> {
+        let schema = apache_avro::Schema::parse_str(
+            r#"{"type": "record", "name": "r", "fields": [
+                {"name": "id", "type": "long"}
+            ]}"#,
+        )
+        .unwrap();
+        let mut writer = apache_avro::Writer::new(&schema, vec![]);
+        for id in first_id..first_id + 300 {
+            writer
+                .append(Value::Record(vec![("id".to_string(), Value::Long(id))]))
+                .unwrap();
+            if id % 100 == 99 {
+                writer.flush().unwrap();
+            }
+        }
+        writer.into_inner().unwrap()
+    }
+
+    /// Returns the offsets of the blocks of the Avro file `data`, and its
+    /// Arrow schema
+    async fn block_offsets(data: &[u8]) -> Result<(Vec<usize>, SchemaRef)> {
+        let data = Bytes::copy_from_slice(data);
+        let stream = AvroBlockStream::try_new(
+            futures::stream::once(async move { Ok(data) }).boxed(),
+        )
+        .await?;
+        let schema = stream.file_schema();
+        let offsets = stream
+            .map_ok(|(metadata, _)| metadata.offset as usize)
+            .try_collect()
+            .await?;
+        Ok((offsets, schema))
+    }
+
+    fn ids(batch: &RecordBatch) -> Vec<i64> {
+        batch
+            .column(0)
+            .as_primitive::<Int64Type>()
+            .values()
+            .to_vec()
+    }
+
+    #[tokio::test]
+    async fn test_tail_appended_blocks() -> Result<()> {
+        let data = write_file(0);
+        let (offsets, schema) = block_offsets(&data).await?;
+        assert_eq!(offsets.len(), 3);
+
+        // The first block, and the start of the second
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let location = Path::from("data.avro");
+        let partial = Bytes::copy_from_slice(&data[..offsets[1] + 10]);
+        store.put(&location, partial.into()).await?;
+
+        let tail = AvroTailStream::new_file(
+            Arc::clone(&store),
+            location.clone(),
+            Arc::clone(&schema),
+        )
+        .with_poll_interval(Duration::from_millis(10));
+        let mut stream = tail.execute(Arc::new(TaskContext::default()));
+
+        let batch = stream.next().await.unwrap()?;
+        assert_eq!(ids(&batch), (0..100).collect::<Vec<_>>());
+        // The second block is not emitted until its sync marker is written
+        let next = tokio::time::timeout(Duration::from_millis(50), stream.next()).await;
+        assert!(next.is_err());
+
+        // Append the rest of the second block between polls
+        let appended = Bytes::copy_from_slice(&data[..offsets[2]]);
+        store.put(&location, appended.into()).await?;
+        let batch = stream.next().await.unwrap()?;
+        assert_eq!(ids(&batch), (100..200).collect::<Vec<_>>());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tail_directory() -> Result<()> {
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let first = write_file(0);
+        let (_, schema) = block_offsets(&first).await?;
+        store.put(&Path::from("dir/1.avro"), first.into()).await?;
+
+        let tail =
+            AvroTailStream::new_directory(Arc::clone(&store), Path::from("dir"), schema)
+                .with_poll_interval(Duration::from_millis(10));
+        let mut stream = tail.execute(Arc::new(TaskContext::default()));
+        let mut read = vec![];
+        for _ in 0..3 {
+            read.extend(ids(&stream.next().await.unwrap()?));
+        }
+        assert_eq!(read, (0..300).collect::<Vec<_>>());
+
+        // A file added to the directory
+        store
+            .put(&Path::from("dir/2.avro"), write_file(300).into())
+            .await?;
+        for _ in 0..3 {
+            read.extend(ids(&stream.next().await.unwrap()?));
+        }
+        assert_eq!(read, (0..600).collect::<Vec<_>>());
+
+        Ok(())
+    }
+}
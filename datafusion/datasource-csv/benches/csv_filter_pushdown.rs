@@ -0,0 +1,159 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Compares evaluating a predicate while decoding a wide CSV file against
+//! decoding all columns and only filtering them with a `FilterExec`
+
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion_common::Result;
+use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
+use datafusion_datasource::source::DataSourceExec;
+use datafusion_datasource::PartitionedFile;
+use datafusion_datasource_csv::source::CsvSource;
+use datafusion_execution::object_store::ObjectStoreUrl;
+use datafusion_execution::TaskContext;
+use datafusion_expr_common::operator::Operator;
+use datafusion_physical_expr::expressions::{binary, col, lit};
+use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
+use datafusion_physical_plan::filter::FilterExec;
+use datafusion_physical_plan::{collect, ExecutionPlan};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use tokio::runtime::Builder;
+
+const NUM_ROWS: i64 = 20_000;
+const NUM_PAYLOAD_COLUMNS: usize = 200;
+
+fn schema() -> SchemaRef {
+    let mut fields = vec![Field::new("id", DataType::Int64, false)];
+    fields.extend((0..NUM_PAYLOAD_COLUMNS).map(|i| {
+        let data_type = if i % 2 == 0 {
+            DataType::Utf8
+        } else {
+            DataType::Float64
+        };
+        Field::new(format!("payload_{i}"), data_type, false)
+    }));
+    Arc::new(Schema::new(fields))
+}
+
+fn write_file(path: &Path) {
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path).unwrap());
+    let header = (0..NUM_PAYLOAD_COLUMNS)
+        .map(|i| format!("payload_{i}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    writeln!(file, "id,{header}").unwrap();
+    for id in 0..NUM_ROWS {
+        let payload = (0..NUM_PAYLOAD_COLUMNS)
+            .map(|i| {
+                if i % 2 == 0 {
+                    format!("value {i} of row {id}")
+                } else {
+                    format!("{}.5", id * i as i64)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        writeln!(file, "{id},{payload}").unwrap();
+    }
+    file.flush().unwrap();
+}
+
+/// `id / 1000 % 100 < selectivity_percent`, which selects runs of 1000 rows
+fn predicate(schema: &SchemaRef, selectivity_percent: i64) -> Arc<dyn PhysicalExpr> {
+    let block = binary(
+        col("id", schema).unwrap(),
+        Operator::Divide,
+        lit(1000i64),
+        schema,
+    )
+    .unwrap();
+    binary(
+        binary(block, Operator::Modulo, lit(100i64), schema).unwrap(),
+        Operator::Lt,
+        lit(selectivity_percent),
+        schema,
+    )
+    .unwrap()
+}
+
+/// Scans all the columns of the file, filtering them with a `FilterExec`
+fn plan(
+    path: &Path,
+    schema: &SchemaRef,
+    source: CsvSource,
+    selectivity_percent: i64,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    let size = std::fs::metadata(path).unwrap().len();
+    let file = PartitionedFile::new(path.to_str().unwrap(), size);
+    let config = FileScanConfigBuilder::new(
+        ObjectStoreUrl::local_filesystem(),
+        Arc::clone(schema),
+        Arc::new(source),
+    )
+    .with_file(file)
+    .build();
+    let scan = DataSourceExec::from_data_source(config);
+    Ok(Arc::new(FilterExec::try_new(
+        predicate(schema, selectivity_percent),
+        scan,
+    )?))
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let tmp_dir = tempfile::TempDir::new().unwrap();
+    let path = tmp_dir.path().join("data.csv");
+    write_file(&path);
+    let schema = schema();
+    let rt = Builder::new_current_thread().build().unwrap();
+    let task_ctx = Arc::new(TaskContext::default());
+
+    let mut group = c.benchmark_group("csv_filter_pushdown");
+    group.sample_size(10);
+    for selectivity_percent in [1, 10, 50, 100] {
+        group.bench_function(
+            BenchmarkId::new("filter_after_scan", selectivity_percent),
+            |b| {
+                b.iter(|| {
+                    let source = CsvSource::new(true, b',', b'"');
+                    let plan = plan(&path, &schema, source, selectivity_percent).unwrap();
+                    rt.block_on(collect(plan, Arc::clone(&task_ctx))).unwrap()
+                })
+            },
+        );
+        group.bench_function(
+            BenchmarkId::new("filter_during_decode", selectivity_percent),
+            |b| {
+                b.iter(|| {
+                    let source = CsvSource::new(true, b',', b'"')
+                        .with_predicate(predicate(&schema, selectivity_percent));
+                    let plan = plan(&path, &schema, source, selectivity_percent).unwrap();
+                    rt.block_on(collect(plan, Arc::clone(&task_ctx))).unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
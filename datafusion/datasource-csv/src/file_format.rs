@@ -44,6 +44,7 @@ use datafusion_datasource::file_format::{
 };
 use datafusion_datasource::file_scan_config::{FileScanConfig, FileScanConfigBuilder};
 use datafusion_datasource::file_sink_config::{FileSink, FileSinkConfig};
+use datafusion_datasource::schema_adapter::merge_schemas_with_promotion;
 use datafusion_datasource::sink::{DataSink, DataSinkExec};
 use datafusion_datasource::write::demux::DemuxedStreamReceiver;
 use datafusion_datasource::write::orchestration::spawn_writer_tasks_and_join;
@@ -389,7 +390,15 @@ impl FileFormat for CsvFormat {
             }
         }
 
-        let merged_schema = Schema::try_merge(schemas)?;
+        let merged_schema = if state
+            .config_options()
+            .execution
+            .listing_table_schema_coercion
+        {
+            merge_schemas_with_promotion(schemas)?
+        } else {
+            Schema::try_merge(schemas)?
+        };
         Ok(Arc::new(merged_schema))
     }
 
@@ -441,8 +450,8 @@ impl FileFormat for CsvFormat {
         conf: FileSinkConfig,
         order_requirements: Option<LexRequirement>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        if conf.insert_op != InsertOp::Append {
-            return not_impl_err!("Overwrites are not implemented yet for CSV");
+        if conf.insert_op == InsertOp::Replace {
+            return not_impl_err!("Replacing rows is not implemented yet for CSV");
         }
 
         // `has_header` and `newlines_in_values` fields of CsvOptions may inherit
@@ -20,11 +20,14 @@
 use datafusion_datasource::schema_adapter::SchemaAdapterFactory;
 use std::any::Any;
 use std::fmt;
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::sync::Arc;
 use std::task::Poll;
 
-use datafusion_datasource::decoder::{deserialize_stream, DecoderDeserializer};
+use datafusion_datasource::decoder::{
+    deserialize_reader, deserialize_stream, DecoderDeserializer, DecoderRowFilter,
+    FilteringDecoder, FilteringDecoderMetrics,
+};
 use datafusion_datasource::file_compression_type::FileCompressionType;
 use datafusion_datasource::file_meta::FileMeta;
 use datafusion_datasource::file_stream::{FileOpenFuture, FileOpener};
@@ -35,11 +38,18 @@ use datafusion_datasource::{
 
 use arrow::csv;
 use arrow::datatypes::SchemaRef;
+use datafusion_common::config::ConfigOptions;
 use datafusion_common::{DataFusionError, Result, Statistics};
 use datafusion_common_runtime::JoinSet;
 use datafusion_datasource::file::FileSource;
 use datafusion_datasource::file_scan_config::FileScanConfig;
 use datafusion_execution::TaskContext;
+use datafusion_physical_expr::conjunction;
+use datafusion_physical_expr::utils::collect_columns;
+use datafusion_physical_expr_common::physical_expr::{fmt_sql, PhysicalExpr};
+use datafusion_physical_plan::filter_pushdown::{
+    FilterPushdownPropagation, PredicateSupports,
+};
 use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
 use datafusion_physical_plan::{
     DisplayFormatType, ExecutionPlan, ExecutionPlanProperties,
@@ -53,6 +63,10 @@ use tokio::io::AsyncWriteExt;
 
 /// A Config for [`CsvOpener`]
 ///
+/// When `datafusion.execution.decoder_pushdown_filters` is enabled, filters
+/// pushed down into the scan are evaluated while decoding, see
+/// [`FilteringDecoder`].
+///
 /// # Example: create a `DataSourceExec` for CSV
 /// ```
 /// # use std::sync::Arc;
@@ -91,6 +105,7 @@ pub struct CsvSource {
     terminator: Option<u8>,
     escape: Option<u8>,
     comment: Option<u8>,
+    predicate: Option<Arc<dyn PhysicalExpr>>,
     metrics: ExecutionPlanMetricsSet,
     projected_statistics: Option<Statistics>,
     schema_adapter_factory: Option<Arc<dyn SchemaAdapterFactory>>,
@@ -156,6 +171,19 @@ impl CsvSource {
         conf.comment = comment;
         conf
     }
+
+    /// Initialize a CsvSource with a predicate evaluated while decoding,
+    /// expressed in terms of the columns of the file schema
+    pub fn with_predicate(&self, predicate: Arc<dyn PhysicalExpr>) -> Self {
+        let mut conf = self.clone();
+        conf.predicate = Some(predicate);
+        conf
+    }
+
+    /// The predicate evaluated while decoding, if any
+    pub fn predicate(&self) -> Option<&Arc<dyn PhysicalExpr>> {
+        self.predicate.as_ref()
+    }
 }
 
 impl CsvSource {
@@ -191,6 +219,30 @@ impl CsvSource {
 
         builder
     }
+
+    /// Returns a decoder that evaluates `row_filter` before decoding all the
+    /// projected columns
+    fn filtering_decoder(
+        &self,
+        row_filter: DecoderRowFilter,
+        metrics: FilteringDecoderMetrics,
+    ) -> FilteringDecoder<CsvDecoder> {
+        let predicate_decoder = self
+            .builder()
+            .with_projection(row_filter.projection().to_vec())
+            .build_decoder();
+        let config = self.clone();
+        FilteringDecoder::new(
+            CsvDecoder::new(predicate_decoder),
+            // Only the decoder of the first bytes of the file sees the header
+            Box::new(move |at_start| {
+                let builder = config.builder().with_header(config.has_header && at_start);
+                Ok(CsvDecoder::new(builder.build_decoder()))
+            }),
+            row_filter,
+            metrics,
+        )
+    }
 }
 
 /// A [`FileOpener`] that opens a CSV file and yields a [`FileOpenFuture`]
@@ -198,6 +250,7 @@ pub struct CsvOpener {
     config: Arc<CsvSource>,
     file_compression_type: FileCompressionType,
     object_store: Arc<dyn ObjectStore>,
+    partition: usize,
 }
 
 impl CsvOpener {
@@ -211,6 +264,7 @@ impl CsvOpener {
             config,
             file_compression_type,
             object_store,
+            partition: 0,
         }
     }
 }
@@ -226,12 +280,13 @@ impl FileSource for CsvSource {
         &self,
         object_store: Arc<dyn ObjectStore>,
         base_config: &FileScanConfig,
-        _partition: usize,
+        partition: usize,
     ) -> Arc<dyn FileOpener> {
         Arc::new(CsvOpener {
             config: Arc::new(self.clone()),
             file_compression_type: base_config.file_compression_type,
             object_store,
+            partition,
         })
     }
 
@@ -275,15 +330,66 @@ impl FileSource for CsvSource {
     fn file_type(&self) -> &str {
         "csv"
     }
+    fn output_identity(&self) -> Option<String> {
+        if self.schema_adapter_factory.is_some() {
+            return None;
+        }
+        Some(format!(
+            "delimiter={} quote={} terminator={:?} escape={:?} comment={:?}",
+            self.delimiter, self.quote, self.terminator, self.escape, self.comment
+        ))
+    }
     fn fmt_extra(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
-                write!(f, ", has_header={}", self.has_header)
+                write!(f, ", has_header={}", self.has_header)?;
+                if let Some(predicate) = &self.predicate {
+                    write!(f, ", predicate={predicate}")?;
+                }
+                Ok(())
+            }
+            DisplayFormatType::TreeRender => {
+                if let Some(predicate) = &self.predicate {
+                    writeln!(f, "predicate={}", fmt_sql(predicate.as_ref()))?;
+                }
+                Ok(())
             }
-            DisplayFormatType::TreeRender => Ok(()),
         }
     }
 
+    fn try_pushdown_filters(
+        &self,
+        filters: Vec<Arc<dyn PhysicalExpr>>,
+        config: &ConfigOptions,
+    ) -> Result<FilterPushdownPropagation<Arc<dyn FileSource>>> {
+        if !config.execution.decoder_pushdown_filters {
+            return Ok(FilterPushdownPropagation::unsupported(filters));
+        }
+        let Some(file_schema) = self.file_schema.clone() else {
+            return Ok(FilterPushdownPropagation::unsupported(filters));
+        };
+        // Filters must reference at least one column, and only columns of the
+        // file (not partition columns)
+        let filters = PredicateSupports::new_with_supported_check(filters, |filter| {
+            let columns = collect_columns(filter);
+            !columns.is_empty()
+                && columns
+                    .iter()
+                    .all(|column| file_schema.field_with_name(column.name()).is_ok())
+        });
+        if filters.is_all_unsupported() {
+            return Ok(FilterPushdownPropagation::with_filters(filters));
+        }
+        let supported_filters = filters.collect_supported();
+        let predicate =
+            conjunction(self.predicate.iter().cloned().chain(supported_filters));
+        let source = Arc::new(self.with_predicate(predicate));
+        Ok(
+            FilterPushdownPropagation::with_filters(filters.make_unsupported())
+                .with_updated_node(source),
+        )
+    }
+
     fn with_schema_adapter_factory(
         &self,
         schema_adapter_factory: Arc<dyn SchemaAdapterFactory>,
@@ -354,6 +460,22 @@ impl FileOpener for CsvOpener {
 
         let store = Arc::clone(&self.object_store);
         let terminator = self.config.terminator;
+        let row_filter = config
+            .predicate
+            .as_ref()
+            .map(|predicate| {
+                let file_schema = config
+                    .file_schema
+                    .as_ref()
+                    .expect("Schema must be set before opening files");
+                DecoderRowFilter::try_new(Arc::clone(predicate), file_schema)
+            })
+            .transpose()?
+            .map(|row_filter| {
+                let metrics =
+                    FilteringDecoderMetrics::new(&config.metrics, self.partition);
+                (row_filter, metrics)
+            });
 
         Ok(Box::pin(async move {
             // Current partition contains bytes [start_byte, end_byte) (might contain incomplete lines at boundaries)
@@ -382,7 +504,7 @@ impl FileOpener for CsvOpener {
                 #[cfg(not(target_arch = "wasm32"))]
                 GetResultPayload::File(mut file, _) => {
                     let is_whole_file_scanned = file_meta.range.is_none();
-                    let decoder = if is_whole_file_scanned {
+                    let decoder_read = if is_whole_file_scanned {
                         // Don't seek if no range as breaks FIFO files
                         file_compression_type.convert_read(file)?
                     } else {
@@ -392,17 +514,38 @@ impl FileOpener for CsvOpener {
                         )?
                     };
 
-                    Ok(futures::stream::iter(config.open(decoder)?).boxed())
+                    match row_filter {
+                        Some((row_filter, metrics)) => {
+                            let decoder = config.filtering_decoder(row_filter, metrics);
+                            Ok(futures::stream::iter(deserialize_reader(
+                                BufReader::new(decoder_read),
+                                decoder,
+                            ))
+                            .boxed())
+                        }
+                        None => {
+                            Ok(futures::stream::iter(config.open(decoder_read)?).boxed())
+                        }
+                    }
                 }
                 GetResultPayload::Stream(s) => {
-                    let decoder = config.builder().build_decoder();
                     let s = s.map_err(DataFusionError::from);
                     let input = file_compression_type.convert_stream(s.boxed())?.fuse();
 
-                    Ok(deserialize_stream(
-                        input,
-                        DecoderDeserializer::new(CsvDecoder::new(decoder)),
-                    ))
+                    Ok(match row_filter {
+                        Some((row_filter, metrics)) => deserialize_stream(
+                            input,
+                            DecoderDeserializer::new(
+                                config.filtering_decoder(row_filter, metrics),
+                            ),
+                        ),
+                        None => deserialize_stream(
+                            input,
+                            DecoderDeserializer::new(CsvDecoder::new(
+                                config.builder().build_decoder(),
+                            )),
+                        ),
+                    })
                 }
             }
         }))
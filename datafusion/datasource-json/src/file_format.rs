@@ -46,6 +46,7 @@ use datafusion_datasource::file_format::{
 };
 use datafusion_datasource::file_scan_config::{FileScanConfig, FileScanConfigBuilder};
 use datafusion_datasource::file_sink_config::{FileSink, FileSinkConfig};
+use datafusion_datasource::schema_adapter::merge_schemas_with_promotion;
 use datafusion_datasource::sink::{DataSink, DataSinkExec};
 use datafusion_datasource::write::demux::DemuxedStreamReceiver;
 use datafusion_datasource::write::orchestration::spawn_writer_tasks_and_join;
@@ -187,7 +188,7 @@ impl FileFormat for JsonFormat {
 
     async fn infer_schema(
         &self,
-        _state: &dyn Session,
+        state: &dyn Session,
         store: &Arc<dyn ObjectStore>,
         objects: &[ObjectMeta],
     ) -> Result<SchemaRef> {
@@ -230,7 +231,15 @@ impl FileFormat for JsonFormat {
             }
         }
 
-        let schema = Schema::try_merge(schemas)?;
+        let schema = if state
+            .config_options()
+            .execution
+            .listing_table_schema_coercion
+        {
+            merge_schemas_with_promotion(schemas)?
+        } else {
+            Schema::try_merge(schemas)?
+        };
         Ok(Arc::new(schema))
     }
 
@@ -266,8 +275,8 @@ impl FileFormat for JsonFormat {
         conf: FileSinkConfig,
         order_requirements: Option<LexRequirement>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        if conf.insert_op != InsertOp::Append {
-            return not_impl_err!("Overwrites are not implemented yet for Json");
+        if conf.insert_op == InsertOp::Replace {
+            return not_impl_err!("Replacing rows is not implemented yet for Json");
         }
 
         let writer_options = JsonWriterOptions::try_from(&self.options)?;
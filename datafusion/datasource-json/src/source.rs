@@ -24,9 +24,13 @@ use std::task::Poll;
 
 use crate::file_format::JsonDecoder;
 
+use datafusion_common::config::ConfigOptions;
 use datafusion_common::error::{DataFusionError, Result};
 use datafusion_common_runtime::JoinSet;
-use datafusion_datasource::decoder::{deserialize_stream, DecoderDeserializer};
+use datafusion_datasource::decoder::{
+    deserialize_reader, deserialize_stream, DecoderDeserializer, DecoderRowFilter,
+    FilteringDecoder, FilteringDecoderMetrics,
+};
 use datafusion_datasource::file_compression_type::FileCompressionType;
 use datafusion_datasource::file_meta::FileMeta;
 use datafusion_datasource::file_stream::{FileOpenFuture, FileOpener};
@@ -34,7 +38,15 @@ use datafusion_datasource::schema_adapter::SchemaAdapterFactory;
 use datafusion_datasource::{
     as_file_source, calculate_range, ListingTableUrl, PartitionedFile, RangeCalculation,
 };
-use datafusion_physical_plan::{ExecutionPlan, ExecutionPlanProperties};
+use datafusion_physical_expr::conjunction;
+use datafusion_physical_expr::utils::collect_columns;
+use datafusion_physical_expr_common::physical_expr::{fmt_sql, PhysicalExpr};
+use datafusion_physical_plan::filter_pushdown::{
+    FilterPushdownPropagation, PredicateSupports,
+};
+use datafusion_physical_plan::{
+    DisplayFormatType, ExecutionPlan, ExecutionPlanProperties,
+};
 
 use arrow::json::ReaderBuilder;
 use arrow::{datatypes::SchemaRef, json};
@@ -55,6 +67,11 @@ pub struct JsonOpener {
     projected_schema: SchemaRef,
     file_compression_type: FileCompressionType,
     object_store: Arc<dyn ObjectStore>,
+    /// The predicate evaluated while decoding, with the file schema it is
+    /// expressed in terms of
+    predicate: Option<(Arc<dyn PhysicalExpr>, SchemaRef)>,
+    metrics: ExecutionPlanMetricsSet,
+    partition: usize,
 }
 
 impl JsonOpener {
@@ -70,14 +87,23 @@ impl JsonOpener {
             projected_schema,
             file_compression_type,
             object_store,
+            predicate: None,
+            metrics: ExecutionPlanMetricsSet::new(),
+            partition: 0,
         }
     }
 }
 
 /// JsonSource holds the extra configuration that is necessary for [`JsonOpener`]
+///
+/// When `datafusion.execution.decoder_pushdown_filters` is enabled, filters
+/// pushed down into the scan are evaluated while decoding, see
+/// [`FilteringDecoder`].
 #[derive(Clone, Default)]
 pub struct JsonSource {
     batch_size: Option<usize>,
+    file_schema: Option<SchemaRef>,
+    predicate: Option<Arc<dyn PhysicalExpr>>,
     metrics: ExecutionPlanMetricsSet,
     projected_statistics: Option<Statistics>,
     schema_adapter_factory: Option<Arc<dyn SchemaAdapterFactory>>,
@@ -88,6 +114,19 @@ impl JsonSource {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Initialize a JsonSource with a predicate evaluated while decoding,
+    /// expressed in terms of the columns of the file schema
+    pub fn with_predicate(&self, predicate: Arc<dyn PhysicalExpr>) -> Self {
+        let mut conf = self.clone();
+        conf.predicate = Some(predicate);
+        conf
+    }
+
+    /// The predicate evaluated while decoding, if any
+    pub fn predicate(&self) -> Option<&Arc<dyn PhysicalExpr>> {
+        self.predicate.as_ref()
+    }
 }
 
 impl From<JsonSource> for Arc<dyn FileSource> {
@@ -101,7 +140,7 @@ impl FileSource for JsonSource {
         &self,
         object_store: Arc<dyn ObjectStore>,
         base_config: &FileScanConfig,
-        _partition: usize,
+        partition: usize,
     ) -> Arc<dyn FileOpener> {
         Arc::new(JsonOpener {
             batch_size: self
@@ -110,6 +149,11 @@ impl FileSource for JsonSource {
             projected_schema: base_config.projected_file_schema(),
             file_compression_type: base_config.file_compression_type,
             object_store,
+            predicate: self.predicate.as_ref().map(|predicate| {
+                (Arc::clone(predicate), Arc::clone(&base_config.file_schema))
+            }),
+            metrics: self.metrics.clone(),
+            partition,
         })
     }
 
@@ -123,8 +167,10 @@ impl FileSource for JsonSource {
         Arc::new(conf)
     }
 
-    fn with_schema(&self, _schema: SchemaRef) -> Arc<dyn FileSource> {
-        Arc::new(Self { ..self.clone() })
+    fn with_schema(&self, schema: SchemaRef) -> Arc<dyn FileSource> {
+        let mut conf = self.clone();
+        conf.file_schema = Some(schema);
+        Arc::new(conf)
     }
     fn with_statistics(&self, statistics: Statistics) -> Arc<dyn FileSource> {
         let mut conf = self.clone();
@@ -151,6 +197,62 @@ impl FileSource for JsonSource {
         "json"
     }
 
+    fn output_identity(&self) -> Option<String> {
+        // JSON files have no format options
+        self.schema_adapter_factory.is_none().then(String::new)
+    }
+
+    fn fmt_extra(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        let Some(predicate) = &self.predicate else {
+            return Ok(());
+        };
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, ", predicate={predicate}")
+            }
+            DisplayFormatType::TreeRender => {
+                writeln!(f, "predicate={}", fmt_sql(predicate.as_ref()))
+            }
+        }
+    }
+
+    fn try_pushdown_filters(
+        &self,
+        filters: Vec<Arc<dyn PhysicalExpr>>,
+        config: &ConfigOptions,
+    ) -> Result<FilterPushdownPropagation<Arc<dyn FileSource>>> {
+        if !config.execution.decoder_pushdown_filters {
+            return Ok(FilterPushdownPropagation::unsupported(filters));
+        }
+        let Some(file_schema) = self.file_schema.clone() else {
+            return Ok(FilterPushdownPropagation::unsupported(filters));
+        };
+        // Filters must reference at least one column, and only columns of the
+        // file (not partition columns)
+        let filters = PredicateSupports::new_with_supported_check(filters, |filter| {
+            let columns = collect_columns(filter);
+            !columns.is_empty()
+                && columns
+                    .iter()
+                    .all(|column| file_schema.field_with_name(column.name()).is_ok())
+        });
+        if filters.is_all_unsupported() {
+            return Ok(FilterPushdownPropagation::with_filters(filters));
+        }
+        let supported_filters = filters.collect_supported();
+        let predicate =
+            conjunction(self.predicate.iter().cloned().chain(supported_filters));
+        let source = Arc::new(self.with_predicate(predicate));
+        Ok(
+            FilterPushdownPropagation::with_filters(filters.make_unsupported())
+                .with_updated_node(source),
+        )
+    }
+
     fn with_schema_adapter_factory(
         &self,
         schema_adapter_factory: Arc<dyn SchemaAdapterFactory>,
@@ -185,6 +287,16 @@ impl FileOpener for JsonOpener {
         let schema = Arc::clone(&self.projected_schema);
         let batch_size = self.batch_size;
         let file_compression_type = self.file_compression_type.to_owned();
+        let row_filter = self
+            .predicate
+            .as_ref()
+            .map(|(predicate, file_schema)| {
+                let row_filter =
+                    DecoderRowFilter::try_new(Arc::clone(predicate), file_schema)?;
+                let metrics = FilteringDecoderMetrics::new(&self.metrics, self.partition);
+                Ok::<_, DataFusionError>((row_filter, metrics))
+            })
+            .transpose()?;
 
         Ok(Box::pin(async move {
             let calculated_range = calculate_range(&file_meta, &store, None).await?;
@@ -218,30 +330,80 @@ impl FileOpener for JsonOpener {
                         }
                     };
 
-                    let reader = ReaderBuilder::new(schema)
-                        .with_batch_size(batch_size)
-                        .build(BufReader::new(bytes))?;
+                    match row_filter {
+                        Some((row_filter, metrics)) => {
+                            let decoder = filtering_decoder(
+                                schema, batch_size, row_filter, metrics,
+                            )?;
+                            Ok(futures::stream::iter(deserialize_reader(
+                                BufReader::new(bytes),
+                                decoder,
+                            ))
+                            .boxed())
+                        }
+                        None => {
+                            let reader = ReaderBuilder::new(schema)
+                                .with_batch_size(batch_size)
+                                .build(BufReader::new(bytes))?;
 
-                    Ok(futures::stream::iter(reader).boxed())
+                            Ok(futures::stream::iter(reader).boxed())
+                        }
+                    }
                 }
                 GetResultPayload::Stream(s) => {
                     let s = s.map_err(DataFusionError::from);
-
-                    let decoder = ReaderBuilder::new(schema)
-                        .with_batch_size(batch_size)
-                        .build_decoder()?;
                     let input = file_compression_type.convert_stream(s.boxed())?.fuse();
 
-                    Ok(deserialize_stream(
-                        input,
-                        DecoderDeserializer::new(JsonDecoder::new(decoder)),
-                    ))
+                    match row_filter {
+                        Some((row_filter, metrics)) => {
+                            let decoder = filtering_decoder(
+                                schema, batch_size, row_filter, metrics,
+                            )?;
+                            Ok(deserialize_stream(
+                                input,
+                                DecoderDeserializer::new(decoder),
+                            ))
+                        }
+                        None => {
+                            let decoder = ReaderBuilder::new(schema)
+                                .with_batch_size(batch_size)
+                                .build_decoder()?;
+                            Ok(deserialize_stream(
+                                input,
+                                DecoderDeserializer::new(JsonDecoder::new(decoder)),
+                            ))
+                        }
+                    }
                 }
             }
         }))
     }
 }
 
+/// Returns a decoder of the columns of `schema` that evaluates `row_filter`
+/// before decoding all of them
+fn filtering_decoder(
+    schema: SchemaRef,
+    batch_size: usize,
+    row_filter: DecoderRowFilter,
+    metrics: FilteringDecoderMetrics,
+) -> Result<FilteringDecoder<JsonDecoder>> {
+    let predicate_decoder = ReaderBuilder::new(Arc::clone(row_filter.schema()))
+        .with_batch_size(batch_size)
+        .build_decoder()?;
+    Ok(FilteringDecoder::new(
+        JsonDecoder::new(predicate_decoder),
+        Box::new(move |_| {
+            let decoder = ReaderBuilder::new(Arc::clone(&schema))
+                .with_batch_size(batch_size)
+                .build_decoder()?;
+            Ok(JsonDecoder::new(decoder))
+        }),
+        row_filter,
+        metrics,
+    ))
+}
+
 pub async fn plan_to_json(
     task_ctx: Arc<TaskContext>,
     plan: Arc<dyn ExecutionPlan>,
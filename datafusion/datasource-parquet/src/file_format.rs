@@ -39,10 +39,11 @@ use datafusion_datasource::write::demux::DemuxedStreamReceiver;
 use arrow::compute::sum;
 use arrow::datatypes::{DataType, Field, FieldRef};
 use datafusion_common::config::{ConfigField, ConfigFileType, TableParquetOptions};
+use datafusion_common::file_options::parquet_writer::parse_column_path;
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::stats::Precision;
 use datafusion_common::{
-    internal_datafusion_err, internal_err, not_impl_err, ColumnStatistics,
+    internal_datafusion_err, internal_err, not_impl_err, plan_err, ColumnStatistics,
     DataFusionError, GetExt, HashSet, Result, DEFAULT_PARQUET_EXTENSION,
 };
 use datafusion_common::{HashMap, Statistics};
@@ -50,6 +51,7 @@ use datafusion_common_runtime::{JoinSet, SpawnedTask};
 use datafusion_datasource::display::FileGroupDisplay;
 use datafusion_datasource::file::FileSource;
 use datafusion_datasource::file_scan_config::{FileScanConfig, FileScanConfigBuilder};
+use datafusion_datasource::schema_adapter::merge_schemas_with_promotion;
 use datafusion_datasource::sink::{DataSink, DataSinkExec};
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation};
 use datafusion_execution::{SendableRecordBatchStream, TaskContext};
@@ -387,11 +389,20 @@ impl FileFormat for ParquetFormat {
             .map(|(_, schema)| schema)
             .collect::<Vec<_>>();
 
-        let schema = if self.skip_metadata() {
-            Schema::try_merge(clear_metadata(schemas))
+        let schemas = if self.skip_metadata() {
+            clear_metadata(schemas).collect()
         } else {
-            Schema::try_merge(schemas)
-        }?;
+            schemas
+        };
+        let schema = if state
+            .config_options()
+            .execution
+            .listing_table_schema_coercion
+        {
+            merge_schemas_with_promotion(schemas)?
+        } else {
+            Schema::try_merge(schemas)?
+        };
 
         let schema = if self.binary_as_string() {
             transform_binary_to_string(&schema)
@@ -467,8 +478,11 @@ impl FileFormat for ParquetFormat {
         conf: FileSinkConfig,
         order_requirements: Option<LexRequirement>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        if conf.insert_op != InsertOp::Append {
-            return not_impl_err!("Overwrites are not implemented yet for Parquet");
+        if conf.insert_op == InsertOp::Replace {
+            return not_impl_err!("Replacing rows is not implemented yet for Parquet");
+        }
+        for column in self.options.column_specific_options.keys() {
+            validate_column_path(conf.output_schema(), column)?;
         }
 
         let sink = Arc::new(ParquetSink::new(conf, self.options.clone()));
@@ -481,6 +495,37 @@ impl FileFormat for ParquetFormat {
     }
 }
 
+/// Checks that the column of the per-column writer options `column` exists in
+/// `schema`, `column` being a dot-separated path into nested structs
+fn validate_column_path(schema: &Schema, column: &str) -> Result<()> {
+    let path = parse_column_path(column);
+    let mut parts = path.parts().iter();
+    let mut fields = schema.fields();
+    while let Some(part) = parts.next() {
+        let Some((_, field)) = fields.find(part) else {
+            return plan_err!(
+                "Parquet column options set for column \"{column}\", but no field \"{part}\" exists in the output schema"
+            );
+        };
+        match field.data_type() {
+            DataType::Struct(children) => fields = children,
+            // The column paths of lists and maps contain segments added by
+            // the Parquet encoding, which are not checked
+            DataType::List(_)
+            | DataType::LargeList(_)
+            | DataType::FixedSizeList(_, _)
+            | DataType::Map(_, _) => return Ok(()),
+            _ if parts.len() > 0 => {
+                return plan_err!(
+                    "Parquet column options set for column \"{column}\", but field \"{part}\" is not a struct"
+                );
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
 /// Apply necessary schema type coercions to make file schema match table schema.
 ///
 /// This function performs two main types of transformations in a single pass:
@@ -1294,6 +1339,15 @@ impl FileSink for ParquetSink {
         &self.config
     }
 
+    fn staged_files_committed(&self, moves: &[(Path, Path)]) {
+        let mut written_files = self.written.lock();
+        for (from, to) in moves {
+            if let Some(file_metadata) = written_files.remove(from) {
+                written_files.insert(to.clone(), file_metadata);
+            }
+        }
+    }
+
     async fn spawn_writer_tasks_and_join(
         &self,
         context: &Arc<TaskContext>,
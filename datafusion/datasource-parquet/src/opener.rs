@@ -276,8 +276,9 @@ impl FileOpener for ParquetOpener {
                 reader_metadata,
             );
 
-            let (schema_mapping, adapted_projections) =
-                schema_adapter.map_schema(&physical_file_schema)?;
+            let (schema_mapping, adapted_projections) = schema_adapter
+                .map_schema(&physical_file_schema)
+                .map_err(|e| e.context(format!("Cannot read file {file_name}")))?;
 
             let mask = ProjectionMask::roots(
                 builder.parquet_schema(),
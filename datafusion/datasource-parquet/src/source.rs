@@ -565,6 +565,15 @@ impl FileSource for ParquetSource {
         "parquet"
     }
 
+    fn output_identity(&self) -> Option<String> {
+        if self.parquet_file_reader_factory.is_some()
+            || self.schema_adapter_factory.is_some()
+        {
+            return None;
+        }
+        Some(format!("{:?}", self.table_parquet_options.global))
+    }
+
     fn fmt_extra(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
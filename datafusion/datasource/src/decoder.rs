@@ -20,15 +20,24 @@
 
 use ::arrow::array::RecordBatch;
 
+use arrow::array::{Array, BooleanArray};
+use arrow::compute::{filter_record_batch, prep_null_mask_filter};
+use arrow::datatypes::{Schema, SchemaRef};
 use arrow::error::ArrowError;
 use bytes::Buf;
 use bytes::Bytes;
+use datafusion_common::cast::as_boolean_array;
 use datafusion_common::Result;
+use datafusion_physical_expr::utils::{collect_columns, reassign_predicate_columns};
+use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
+use datafusion_physical_plan::metrics::{Count, ExecutionPlanMetricsSet, MetricBuilder};
 use futures::stream::BoxStream;
 use futures::StreamExt as _;
 use futures::{ready, Stream};
 use std::collections::VecDeque;
 use std::fmt;
+use std::io::BufRead;
+use std::sync::Arc;
 use std::task::Poll;
 
 /// Possible outputs of a [`BatchDeserializer`].
@@ -189,3 +198,247 @@ pub fn deserialize_stream<'a>(
     })
     .boxed()
 }
+
+/// Deserializes the bytes of `reader` into an iterator of [`RecordBatch`]es
+/// using `decoder`, like [`deserialize_stream`] does for a stream of bytes.
+pub fn deserialize_reader<R: BufRead, T: Decoder>(
+    mut reader: R,
+    mut decoder: T,
+) -> impl Iterator<Item = Result<RecordBatch, ArrowError>> {
+    std::iter::from_fn(move || loop {
+        let buf = match reader.fill_buf() {
+            Ok(buf) => buf,
+            Err(e) => return Some(Err(e.into())),
+        };
+        let input_ended = buf.is_empty();
+        let decoded = match decoder.decode(buf) {
+            Ok(decoded) => decoded,
+            Err(e) => return Some(Err(e)),
+        };
+        reader.consume(decoded);
+
+        // Flush when the input ends or batch size is reached
+        if decoded == 0 || decoder.can_flush_early() {
+            match decoder.flush().transpose() {
+                Some(batch) => return Some(batch),
+                None if input_ended => return None,
+                None => continue,
+            }
+        }
+    })
+}
+
+/// A predicate pushed down into the scan of a row oriented text format, such
+/// as CSV or JSON, that is evaluated by a [`FilteringDecoder`]
+#[derive(Debug, Clone)]
+pub struct DecoderRowFilter {
+    /// The predicate, with column indices relative to [`Self::schema`]
+    predicate: Arc<dyn PhysicalExpr>,
+    /// The indices of the columns of the file referenced by the predicate
+    projection: Vec<usize>,
+    /// The columns of the file referenced by the predicate
+    schema: SchemaRef,
+}
+
+impl DecoderRowFilter {
+    /// Create a filter for `predicate`, whose columns are resolved by name
+    /// against `file_schema`
+    pub fn try_new(
+        predicate: Arc<dyn PhysicalExpr>,
+        file_schema: &Schema,
+    ) -> Result<Self> {
+        let mut projection = collect_columns(&predicate)
+            .iter()
+            .map(|column| file_schema.index_of(column.name()))
+            .collect::<Result<Vec<_>, _>>()?;
+        projection.sort_unstable();
+        let schema = Arc::new(file_schema.project(&projection)?);
+        let predicate = reassign_predicate_columns(predicate, &schema, false)?;
+        Ok(Self {
+            predicate,
+            projection,
+            schema,
+        })
+    }
+
+    /// The indices of the columns of the file the predicate decoder must
+    /// decode
+    pub fn projection(&self) -> &[usize] {
+        &self.projection
+    }
+
+    /// The columns of the file the predicate decoder must decode
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Evaluates the predicate on `batch`, which holds the columns of
+    /// [`Self::schema`], returning a mask of the selected rows.
+    ///
+    /// Rows for which the predicate evaluates to `NULL` are not selected.
+    fn evaluate(&self, batch: &RecordBatch) -> Result<BooleanArray> {
+        let mask = self
+            .predicate
+            .evaluate(batch)?
+            .into_array(batch.num_rows())?;
+        let mask = as_boolean_array(&mask)?;
+        Ok(match mask.null_count() {
+            0 => mask.clone(),
+            _ => prep_null_mask_filter(mask),
+        })
+    }
+}
+
+/// Metrics of the rows filtered by a [`FilteringDecoder`]
+#[derive(Debug, Clone)]
+pub struct FilteringDecoderMetrics {
+    /// Rows not selected by the predicate
+    pub pushdown_rows_pruned: Count,
+    /// Rows selected by the predicate
+    pub pushdown_rows_matched: Count,
+}
+
+impl FilteringDecoderMetrics {
+    /// Create the metrics of the scan of `partition`
+    pub fn new(metrics: &ExecutionPlanMetricsSet, partition: usize) -> Self {
+        Self {
+            pushdown_rows_pruned: MetricBuilder::new(metrics)
+                .counter("pushdown_rows_pruned", partition),
+            pushdown_rows_matched: MetricBuilder::new(metrics)
+                .counter("pushdown_rows_matched", partition),
+        }
+    }
+}
+
+/// Creates the [`Decoder`] of all the projected columns of a
+/// [`FilteringDecoder`], given whether the bytes it decodes start at the
+/// beginning of the input (for example, to skip a header)
+pub type DecoderFactory<T> = Box<dyn FnMut(bool) -> Result<T, ArrowError> + Send>;
+
+/// A [`Decoder`] that evaluates a [`DecoderRowFilter`] before decoding all the
+/// projected columns.
+///
+/// The rows are first decoded by a predicate decoder, which only decodes the
+/// columns referenced by the predicate, keeping the bytes that make up the
+/// batch being decoded. Once the batch is flushed, the predicate is evaluated
+/// and:
+/// - if no row is selected, the batch is dropped without decoding its other
+///   columns
+/// - otherwise, its bytes are decoded again by a decoder of all the projected
+///   columns, and the resulting batch is filtered
+///
+/// The file sources using it report the filters pushed down into their scan
+/// as unsupported: the filters only spare decoding the other columns of some
+/// batches, and the parents of the scan still evaluate them.
+pub struct FilteringDecoder<T: Decoder> {
+    /// Decodes the columns referenced by the predicate
+    predicate_decoder: T,
+    /// Creates `decoder` when a batch first selects rows
+    new_decoder: DecoderFactory<T>,
+    /// Decodes the projected columns of the batches with selected rows
+    decoder: Option<T>,
+    row_filter: DecoderRowFilter,
+    metrics: FilteringDecoderMetrics,
+    /// The bytes consumed by `predicate_decoder` since its last flush
+    buffer: Vec<u8>,
+    /// Whether no batch has been flushed yet
+    at_start: bool,
+    /// Whether the end of the input has been signaled to `predicate_decoder`
+    input_ended: bool,
+}
+
+impl<T: Decoder> FilteringDecoder<T> {
+    /// Create a decoder filtering the rows of `predicate_decoder`, which
+    /// decodes the columns of [`DecoderRowFilter::schema`], with `row_filter`
+    pub fn new(
+        predicate_decoder: T,
+        new_decoder: DecoderFactory<T>,
+        row_filter: DecoderRowFilter,
+        metrics: FilteringDecoderMetrics,
+    ) -> Self {
+        Self {
+            predicate_decoder,
+            new_decoder,
+            decoder: None,
+            row_filter,
+            metrics,
+            buffer: vec![],
+            at_start: true,
+            input_ended: false,
+        }
+    }
+}
+
+impl<T: Decoder> fmt::Debug for FilteringDecoder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilteringDecoder")
+            .field("predicate_decoder", &self.predicate_decoder)
+            .field("decoder", &self.decoder)
+            .field("row_filter", &self.row_filter)
+            .finish()
+    }
+}
+
+impl<T: Decoder> Decoder for FilteringDecoder<T> {
+    fn decode(&mut self, buf: &[u8]) -> Result<usize, ArrowError> {
+        self.input_ended |= buf.is_empty();
+        let decoded = self.predicate_decoder.decode(buf)?;
+        self.buffer.extend_from_slice(&buf[..decoded]);
+        Ok(decoded)
+    }
+
+    fn flush(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
+        let Some(batch) = self.predicate_decoder.flush()? else {
+            return Ok(None);
+        };
+        let at_start = std::mem::replace(&mut self.at_start, false);
+        let mask = self
+            .row_filter
+            .evaluate(&batch)
+            .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+        let selected = mask.true_count();
+        self.metrics
+            .pushdown_rows_pruned
+            .add(batch.num_rows() - selected);
+        self.metrics.pushdown_rows_matched.add(selected);
+        if selected == 0 {
+            self.buffer.clear();
+            return Ok(None);
+        }
+
+        let decoder = match &mut self.decoder {
+            Some(decoder) => decoder,
+            decoder => decoder.insert((self.new_decoder)(at_start)?),
+        };
+        let mut buf = self.buffer.as_slice();
+        while !buf.is_empty() {
+            let decoded = decoder.decode(buf)?;
+            if decoded == 0 {
+                return Err(ArrowError::ParseError(
+                    "Filtered batch decoded into more rows than the predicate batch"
+                        .to_string(),
+                ));
+            }
+            buf = &buf[decoded..];
+        }
+        if self.input_ended {
+            decoder.decode(&[])?;
+        }
+        self.buffer.clear();
+
+        match decoder.flush()? {
+            Some(values) if values.num_rows() == batch.num_rows() => {
+                Ok(Some(filter_record_batch(&values, &mask)?))
+            }
+            values => Err(ArrowError::ParseError(format!(
+                "Filtered batch decoded into {} rows instead of {}",
+                values.map(|values| values.num_rows()).unwrap_or_default(),
+                batch.num_rows()
+            ))),
+        }
+    }
+
+    fn can_flush_early(&self) -> bool {
+        self.predicate_decoder.can_flush_early()
+    }
+}
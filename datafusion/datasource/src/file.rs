@@ -79,6 +79,15 @@ pub trait FileSource: Send + Sync {
     fn fmt_extra(&self, _t: DisplayFormatType, _f: &mut Formatter) -> fmt::Result {
         Ok(())
     }
+    /// Returns what identifies the data read by this FileSource beyond the
+    /// display of its scan, such as the options of its format, or `None` (the
+    /// default) if it can not describe all of them.
+    /// See [`ExecutionPlan::output_identity`] for more details.
+    ///
+    /// [`ExecutionPlan::output_identity`]: datafusion_physical_plan::ExecutionPlan::output_identity
+    fn output_identity(&self) -> Option<String> {
+        None
+    }
 
     /// If supported by the [`FileSource`], redistribute files across partitions
     /// according to their size. Allows custom file formats to implement their
@@ -122,6 +122,29 @@ impl FileCompressionType {
         self.variant.is_compressed()
     }
 
+    /// Detect the compression of a file from its first bytes, returning
+    /// `None` if they match no known compression format
+    ///
+    /// Snappy framed files are recognized, but as they can't be decompressed
+    /// they result in an error.
+    pub fn from_magic_bytes(head: &[u8]) -> Result<Option<Self>> {
+        Ok(if head.starts_with(&[0x1f, 0x8b]) {
+            Some(Self::GZIP)
+        } else if head.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Self::ZSTD)
+        } else if head.starts_with(b"BZh") {
+            Some(Self::BZIP2)
+        } else if head.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Some(Self::XZ)
+        } else if head.starts_with(SNAPPY_FRAME_MAGIC) {
+            return Err(DataFusionError::NotImplemented(
+                "Snappy framed compression is not supported".to_owned(),
+            ));
+        } else {
+            None
+        })
+    }
+
     /// Given a `Stream`, create a `Stream` which data are compressed with `FileCompressionType`.
     pub fn convert_to_compress_stream<'a>(
         &self,
@@ -244,6 +267,55 @@ impl FileCompressionType {
     }
 }
 
+/// Magic bytes of a snappy framed stream, which can be detected but not
+/// decompressed
+const SNAPPY_FRAME_MAGIC: &[u8] = b"\xff\x06\x00\x00sNaPpY";
+
+/// How the compression of a file being read is determined
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompressionDetection {
+    /// Use the compression implied by the file extension
+    #[default]
+    ByExtension,
+    /// Detect the compression from the magic bytes at the start of each
+    /// file, ignoring the file extension. Files whose first bytes match no
+    /// known compression format are read uncompressed
+    ByMagic,
+    /// Use the compression implied by the file extension, or detect it from
+    /// the magic bytes of files whose extension implies none
+    ExtensionThenMagic,
+}
+
+impl CompressionDetection {
+    /// Number of bytes at the start of a file needed by
+    /// [`Self::resolve`] to detect its compression
+    pub const MAGIC_LEN: usize = SNAPPY_FRAME_MAGIC.len();
+
+    /// Returns true if [`Self::resolve`] needs the first bytes of a file
+    pub fn needs_magic(&self, from_extension: FileCompressionType) -> bool {
+        match self {
+            Self::ByExtension => false,
+            Self::ByMagic => true,
+            Self::ExtensionThenMagic => !from_extension.is_compressed(),
+        }
+    }
+
+    /// Returns the compression of a file, given the compression implied by
+    /// its extension and the first [`Self::MAGIC_LEN`] bytes of the file (or
+    /// all of them, for shorter files)
+    pub fn resolve(
+        &self,
+        from_extension: FileCompressionType,
+        head: &[u8],
+    ) -> Result<FileCompressionType> {
+        if !self.needs_magic(from_extension) {
+            return Ok(from_extension);
+        }
+        Ok(FileCompressionType::from_magic_bytes(head)?
+            .unwrap_or(FileCompressionType::UNCOMPRESSED))
+    }
+}
+
 /// Trait for extending the functionality of the `FileType` enum.
 pub trait FileTypeExt {
     /// Given a `FileCompressionType`, return the `FileType`'s extension with compression suffix
@@ -254,7 +326,7 @@ pub trait FileTypeExt {
 mod tests {
     use std::str::FromStr;
 
-    use super::FileCompressionType;
+    use super::{CompressionDetection, FileCompressionType};
     use datafusion_common::error::DataFusionError;
 
     use bytes::Bytes;
@@ -291,6 +363,53 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn compression_detection() -> Result<(), DataFusionError> {
+        let gzip = [0x1f, 0x8b, 0x08, 0x00];
+        let zstd = [0x28, 0xb5, 0x2f, 0xfd, 0x00];
+        let avro = b"Obj\x01";
+
+        let by_extension = CompressionDetection::ByExtension;
+        assert_eq!(
+            by_extension.resolve(FileCompressionType::UNCOMPRESSED, &gzip)?,
+            FileCompressionType::UNCOMPRESSED
+        );
+
+        let by_magic = CompressionDetection::ByMagic;
+        assert_eq!(
+            by_magic.resolve(FileCompressionType::UNCOMPRESSED, &gzip)?,
+            FileCompressionType::GZIP
+        );
+        assert_eq!(
+            by_magic.resolve(FileCompressionType::GZIP, &zstd)?,
+            FileCompressionType::ZSTD
+        );
+        assert_eq!(
+            by_magic.resolve(FileCompressionType::GZIP, avro)?,
+            FileCompressionType::UNCOMPRESSED
+        );
+        assert_eq!(
+            by_magic.resolve(FileCompressionType::UNCOMPRESSED, &[])?,
+            FileCompressionType::UNCOMPRESSED
+        );
+        assert!(matches!(
+            by_magic
+                .resolve(FileCompressionType::UNCOMPRESSED, b"\xff\x06\x00\x00sNaPpY"),
+            Err(DataFusionError::NotImplemented(_))
+        ));
+
+        let extension_then_magic = CompressionDetection::ExtensionThenMagic;
+        assert_eq!(
+            extension_then_magic.resolve(FileCompressionType::BZIP2, &gzip)?,
+            FileCompressionType::BZIP2
+        );
+        assert_eq!(
+            extension_then_magic.resolve(FileCompressionType::UNCOMPRESSED, &zstd)?,
+            FileCompressionType::ZSTD
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_bgzip_stream_decoding() -> Result<(), DataFusionError> {
         // As described in https://samtools.github.io/hts-specs/SAMv1.pdf ("The BGZF compression format")
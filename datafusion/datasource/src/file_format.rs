@@ -21,6 +21,7 @@
 use std::any::Any;
 use std::collections::HashMap;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use crate::file::FileSource;
@@ -28,8 +29,9 @@ use crate::file_compression_type::FileCompressionType;
 use crate::file_scan_config::FileScanConfig;
 use crate::file_sink_config::FileSinkConfig;
 
-use arrow::datatypes::SchemaRef;
+use arrow::datatypes::{Schema, SchemaRef};
 use datafusion_common::file_options::file_type::FileType;
+use datafusion_common::stats::Precision;
 use datafusion_common::{internal_err, not_impl_err, GetExt, Result, Statistics};
 use datafusion_physical_expr::LexRequirement;
 use datafusion_physical_plan::ExecutionPlan;
@@ -109,6 +111,98 @@ pub trait FileFormat: Send + Sync + fmt::Debug {
 
     /// Return the related FileSource such as `CsvSource`, `JsonSource`, etc.
     fn file_source(&self) -> Arc<dyn FileSource>;
+
+    /// Estimate the cost of scanning files with the statistics `stats`,
+    /// reading only the columns in `projection` (all columns if `None`).
+    ///
+    /// The default assumes a columnar format: only the bytes of the
+    /// projected columns are read, and decoding costs one unit per byte
+    /// read. Formats that can't prune columns while reading, or are more
+    /// expensive to decode, should override this.
+    fn scan_cost_estimate(
+        &self,
+        stats: &Statistics,
+        projection: Option<&[usize]>,
+    ) -> ScanCost {
+        let io_bytes = projected_byte_size(stats, projection);
+        ScanCost {
+            io_bytes,
+            cpu_cost: io_bytes,
+        }
+    }
+
+    /// Returns a fingerprint of `schema`, a hash of the names, types,
+    /// nullability and metadata of its fields and of its metadata, for
+    /// example to key caches of the schemas of files.
+    ///
+    /// Equal schemas have equal fingerprints, whatever the order of the
+    /// entries of their metadata. Fingerprints are stable across runs and
+    /// processes, but not across versions of Arrow.
+    fn schema_fingerprint(&self, schema: &Schema) -> u64 {
+        let mut hasher = FingerprintHasher::default();
+        schema.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// The 64 bit FNV-1a hash, which unlike the hashers of the standard library
+/// isn't seeded per process, see [`FileFormat::schema_fingerprint`]
+struct FingerprintHasher(u64);
+
+impl Default for FingerprintHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FingerprintHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// Estimated cost of a file scan, see [`FileFormat::scan_cost_estimate`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanCost {
+    /// Bytes read from storage
+    pub io_bytes: Precision<usize>,
+    /// Cost of decoding the bytes read into Arrow arrays, in units of the
+    /// cost of decoding one byte of a columnar format
+    pub cpu_cost: Precision<usize>,
+}
+
+impl ScanCost {
+    /// Returns the decoding cost per byte read, if both are known
+    pub fn cpu_cost_per_byte(&self) -> Option<f64> {
+        match (self.cpu_cost.get_value(), self.io_bytes.get_value()) {
+            (Some(cpu_cost), Some(io_bytes)) if *io_bytes > 0 => {
+                Some(*cpu_cost as f64 / *io_bytes as f64)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns the bytes of the columns in `projection` out of
+/// `stats.total_byte_size`, assuming all columns are of the same size
+pub fn projected_byte_size(
+    stats: &Statistics,
+    projection: Option<&[usize]>,
+) -> Precision<usize> {
+    let num_columns = stats.column_statistics.len();
+    match projection {
+        Some(projection) if num_columns > 0 && projection.len() < num_columns => stats
+            .total_byte_size
+            .map(|bytes| bytes * projection.len() / num_columns)
+            .to_inexact(),
+        _ => stats.total_byte_size,
+    }
 }
 
 /// Factory for creating [`FileFormat`] instances based on session and command level options
@@ -19,8 +19,15 @@
 //! file sources.
 
 use std::{
-    any::Any, borrow::Cow, collections::HashMap, fmt::Debug, fmt::Formatter,
-    fmt::Result as FmtResult, marker::PhantomData, sync::Arc,
+    any::Any,
+    borrow::Cow,
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    fmt::Formatter,
+    fmt::Result as FmtResult,
+    marker::PhantomData,
+    sync::Arc,
 };
 
 use crate::file_groups::FileGroup;
@@ -45,6 +52,7 @@ use arrow::{
     datatypes::{ArrowNativeType, DataType, Field, Schema, SchemaRef, UInt16Type},
 };
 use datafusion_common::config::ConfigOptions;
+use datafusion_common::stats::Precision;
 use datafusion_common::{
     exec_err, ColumnStatistics, Constraints, DataFusionError, Result, ScalarValue,
     Statistics,
@@ -488,7 +496,9 @@ impl DataSource for FileScanConfig {
 
         let opener = source.create_file_opener(object_store, self, partition);
 
-        let stream = FileStream::new(self, partition, opener, source.metrics())?;
+        let stream = FileStream::new(self, partition, opener, source.metrics())?
+            .with_cancellation_token(context.cancellation_token().clone())
+            .with_progress_tracker(context.progress_tracker().clone());
         Ok(Box::pin(cooperative(stream)))
     }
 
@@ -577,6 +587,78 @@ impl DataSource for FileScanConfig {
         self.limit
     }
 
+    fn output_identity(&self) -> Option<String> {
+        // The files are listed in the display, but not the store they are read
+        // from, the types of their columns, nor how they are decoded
+        let source = self.file_source.output_identity()?;
+        let fields = self
+            .file_schema
+            .fields()
+            .iter()
+            .chain(self.table_partition_cols.iter())
+            .map(|field| format!("{}: {}", field.name(), field.data_type()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(format!(
+            "{} [{fields}] {:?} new_lines_in_values={} {source}",
+            self.object_store_url, self.file_compression_type, self.new_lines_in_values
+        ))
+    }
+
+    fn num_rows_by_values(
+        &self,
+        columns: &[usize],
+    ) -> Result<Option<Vec<(Vec<ScalarValue>, usize)>>> {
+        // Predicates pushed down into the scan make its number of rows inexact
+        if self.limit.is_some()
+            || !self.projected_stats().num_rows.is_exact().unwrap_or(false)
+        {
+            return Ok(None);
+        }
+
+        // The index of each column among the partition columns
+        let num_file_columns = self.file_schema.fields().len();
+        let mut partition_indices = vec![];
+        for column in columns {
+            let index = match &self.projection {
+                Some(projection) => projection[*column],
+                None => *column,
+            };
+            if index < num_file_columns {
+                return Ok(None);
+            }
+            partition_indices.push(index - num_file_columns);
+        }
+
+        // The number of rows of each combination of partition values, in the
+        // order they are first found
+        let mut groups: Vec<(Vec<ScalarValue>, usize)> = vec![];
+        let mut group_indices = HashMap::new();
+        for file in self.file_groups.iter().flat_map(|group| group.iter()) {
+            let Some(Precision::Exact(num_rows)) =
+                file.statistics.as_ref().map(|stats| stats.num_rows)
+            else {
+                return Ok(None);
+            };
+            if num_rows == 0 {
+                continue;
+            }
+            let Some(values) = partition_indices
+                .iter()
+                .map(|index| file.partition_values.get(*index).cloned())
+                .collect::<Option<Vec<_>>>()
+            else {
+                return Ok(None);
+            };
+            let index = *group_indices.entry(values.clone()).or_insert_with(|| {
+                groups.push((values, 0));
+                groups.len() - 1
+            });
+            groups[index].1 += num_rows;
+        }
+        Ok(Some(groups))
+    }
+
     fn metrics(&self) -> ExecutionPlanMetricsSet {
         self.file_source.metrics().clone()
     }
@@ -723,8 +805,16 @@ impl FileScanConfig {
                 if idx < self.file_schema.fields().len() {
                     statistics.column_statistics[idx].clone()
                 } else {
-                    // TODO provide accurate stat for partition column (#1186)
-                    ColumnStatistics::new_unknown()
+                    let stats = self.partition_column_statistics(
+                        idx - self.file_schema.fields().len(),
+                    );
+                    // Pushed down filters make the statistics of the files
+                    // inexact, see `FileSource::statistics`
+                    if statistics.num_rows.is_exact().unwrap_or(false) {
+                        stats
+                    } else {
+                        stats.to_inexact()
+                    }
                 }
             })
             .collect();
@@ -737,6 +827,59 @@ impl FileScanConfig {
         }
     }
 
+    /// Returns the statistics of the partition column at `index` of the table
+    /// partition columns, computed from the partition values of the files.
+    ///
+    /// The statistics are exact when the number of rows of every file is,
+    /// since the values of files without rows are not read, and inexact
+    /// otherwise.
+    fn partition_column_statistics(&self, index: usize) -> ColumnStatistics {
+        let files = self.file_groups.iter().flat_map(FileGroup::iter);
+        let mut values = HashSet::new();
+        let mut null_count = Precision::Exact(0);
+        let mut exact = true;
+        for file in files {
+            let Some(value) = file.partition_values.get(index) else {
+                return ColumnStatistics::new_unknown();
+            };
+            let num_rows = file
+                .statistics
+                .as_ref()
+                .map_or(Precision::Absent, |stats| stats.num_rows);
+            if num_rows == Precision::Exact(0) {
+                continue;
+            }
+            exact &= num_rows.is_exact().unwrap_or(false);
+            if value.is_null() {
+                null_count = null_count.add(&num_rows);
+            } else {
+                values.insert(value.clone());
+            }
+        }
+        if values.is_empty() && null_count == Precision::Exact(0) {
+            return ColumnStatistics::new_unknown();
+        }
+
+        let min = values
+            .iter()
+            .min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let max = values
+            .iter()
+            .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let stats = ColumnStatistics {
+            null_count,
+            min_value: min.cloned().map_or(Precision::Absent, Precision::Exact),
+            max_value: max.cloned().map_or(Precision::Absent, Precision::Exact),
+            sum_value: Precision::Absent,
+            distinct_count: Precision::Exact(values.len()),
+        };
+        if exact {
+            stats
+        } else {
+            stats.to_inexact()
+        }
+    }
+
     pub fn projected_schema(&self) -> Arc<Schema> {
         let table_fields: Vec<_> = self
             .projection_indices()
@@ -1650,6 +1793,78 @@ mod tests {
         assert_eq!(col_indices, Some(vec![0]));
     }
 
+    #[test]
+    fn physical_plan_config_partition_column_statistics() {
+        let file_schema = aggr_test_schema();
+        let file = |date: Option<&str>, num_rows| {
+            let mut file =
+                PartitionedFile::new(format!("{}.csv", date.unwrap_or("null")), 10)
+                    .with_statistics(Arc::new(
+                        Statistics::new_unknown(&file_schema).with_num_rows(num_rows),
+                    ));
+            file.partition_values = vec![ScalarValue::from(date)];
+            file
+        };
+        let conf = |files: Vec<PartitionedFile>| {
+            let num_rows = files
+                .iter()
+                .map(|file| file.statistics.as_ref().unwrap().num_rows)
+                .fold(Precision::Exact(0), |acc, num_rows| acc.add(&num_rows));
+            FileScanConfigBuilder::new(
+                ObjectStoreUrl::parse("test:///").unwrap(),
+                Arc::clone(&file_schema),
+                Arc::new(MockSource::default()),
+            )
+            .with_projection(Some(vec![file_schema.fields().len()]))
+            .with_statistics(
+                Statistics::new_unknown(&file_schema).with_num_rows(num_rows),
+            )
+            .with_table_partition_cols(vec![Field::new("date", DataType::Utf8, true)])
+            .with_file_groups(vec![
+                FileGroup::new(files[..1].to_vec()),
+                FileGroup::new(files[1..].to_vec()),
+            ])
+            .build()
+        };
+
+        // The values of the file without rows are not read
+        let stats = conf(vec![
+            file(Some("2021-10-26"), Precision::Exact(3)),
+            file(Some("2021-10-27"), Precision::Exact(2)),
+            file(None, Precision::Exact(4)),
+            file(Some("2021-10-28"), Precision::Exact(0)),
+            file(Some("2021-10-26"), Precision::Exact(1)),
+        ])
+        .projected_stats();
+        assert_eq!(stats.num_rows, Precision::Exact(10));
+        assert_eq!(
+            stats.column_statistics,
+            vec![ColumnStatistics {
+                null_count: Precision::Exact(4),
+                min_value: Precision::Exact(ScalarValue::from("2021-10-26")),
+                max_value: Precision::Exact(ScalarValue::from("2021-10-27")),
+                sum_value: Precision::Absent,
+                distinct_count: Precision::Exact(2),
+            }]
+        );
+
+        // The statistics of a partition column are inexact if the number of
+        // rows of a file is
+        let stats = conf(vec![
+            file(Some("2021-10-26"), Precision::Exact(3)),
+            file(Some("2021-10-27"), Precision::Inexact(2)),
+        ])
+        .projected_stats();
+        assert_eq!(
+            stats.column_statistics[0].min_value,
+            Precision::Inexact(ScalarValue::from("2021-10-26"))
+        );
+        assert_eq!(
+            stats.column_statistics[0].distinct_count,
+            Precision::Inexact(2)
+        );
+    }
+
     #[test]
     fn partition_column_projector() {
         let file_batch = build_table_i32(
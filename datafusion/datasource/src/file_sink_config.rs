@@ -20,6 +20,7 @@ use std::sync::Arc;
 use crate::file_groups::FileGroup;
 use crate::sink::DataSink;
 use crate::write::demux::{start_demuxer_task, DemuxedStreamReceiver};
+use crate::write::staging::StagingDir;
 use crate::ListingTableUrl;
 
 use arrow::array::RecordBatch;
@@ -32,6 +33,7 @@ use datafusion_expr::dml::InsertOp;
 use datafusion_physical_plan::stream::RecordBatchStreamAdapter;
 
 use async_trait::async_trait;
+use object_store::path::Path;
 use object_store::ObjectStore;
 
 /// General behaviors for files that do `DataSink` operations
@@ -68,7 +70,21 @@ pub trait FileSink: DataSink {
         object_store: Arc<dyn ObjectStore>,
     ) -> Result<u64>;
 
+    /// Called once the files staged by an overwrite were moved to the table
+    /// directory, with the staged and the final path of each file.
+    ///
+    /// Sinks keeping track of the files they wrote should update their paths.
+    fn staged_files_committed(&self, _moves: &[(Path, Path)]) {}
+
     /// File sink implementation of the [`DataSink::write_all`] method.
+    ///
+    /// Overwrites of a directory stage the new files in a directory below the
+    /// table directory named after [`STAGING_DIR_PREFIX`], and replace the
+    /// existing files of the table only once all new files were written. If
+    /// writing fails, the staged files are deleted and the existing files are
+    /// kept.
+    ///
+    /// [`STAGING_DIR_PREFIX`]: crate::write::staging::STAGING_DIR_PREFIX
     async fn write_all(
         &self,
         data: SendableRecordBatchStream,
@@ -78,8 +94,72 @@ pub trait FileSink: DataSink {
         let object_store = context
             .runtime_env()
             .object_store(&config.object_store_url)?;
+        // Writing a single file replaces it as a whole
+        let table_path = &config.table_paths[0];
+        if config.insert_op != InsertOp::Overwrite
+            || !table_path.is_collection()
+            || config.file_output_mode.is_single_file(table_path)
+        {
+            return write_files(self, config, data, context, object_store).await;
+        }
+
+        let staging_dir = StagingDir::try_new(&config.table_paths[0])?;
+        let staging_config = FileSinkConfig {
+            table_paths: vec![staging_dir.url().clone()],
+            ..config.clone()
+        };
+        let written = write_files(
+            self,
+            &staging_config,
+            data,
+            context,
+            Arc::clone(&object_store),
+        )
+        .await;
+        match written {
+            Ok(num_rows) => {
+                let moves = staging_dir
+                    .commit(object_store.as_ref(), &config.file_group)
+                    .await?;
+                self.staged_files_committed(&moves);
+                Ok(num_rows)
+            }
+            Err(e) => {
+                staging_dir.abort(object_store.as_ref()).await;
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Writes `data` to the files of `config` using the writers of `sink`
+async fn write_files<S: FileSink + ?Sized>(
+    sink: &S,
+    config: &FileSinkConfig,
+    data: SendableRecordBatchStream,
+    context: &Arc<TaskContext>,
+    object_store: Arc<dyn ObjectStore>,
+) -> Result<u64> {
+    let (demux_task, file_stream_rx) = start_demuxer_task(config, data, context);
+    let mut num_rows = sink
+        .spawn_writer_tasks_and_join(
+            context,
+            demux_task,
+            file_stream_rx,
+            Arc::clone(&object_store),
+        )
+        .await?;
+    if num_rows == 0 {
+        // If no rows were written, then no files are output either.
+        // In this case, send an empty record batch through to ensure the output file is generated
+        let schema = Arc::clone(&config.output_schema);
+        let empty_batch = RecordBatch::new_empty(Arc::clone(&schema));
+        let data = Box::pin(RecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::iter(vec![Ok(empty_batch)]),
+        ));
         let (demux_task, file_stream_rx) = start_demuxer_task(config, data, context);
-        let mut num_rows = self
+        num_rows = sink
             .spawn_writer_tasks_and_join(
                 context,
                 demux_task,
@@ -87,27 +167,8 @@ pub trait FileSink: DataSink {
                 Arc::clone(&object_store),
             )
             .await?;
-        if num_rows == 0 {
-            // If no rows were written, then no files are output either.
-            // In this case, send an empty record batch through to ensure the output file is generated
-            let schema = Arc::clone(&config.output_schema);
-            let empty_batch = RecordBatch::new_empty(Arc::clone(&schema));
-            let data = Box::pin(RecordBatchStreamAdapter::new(
-                schema,
-                futures::stream::iter(vec![Ok(empty_batch)]),
-            ));
-            let (demux_task, file_stream_rx) = start_demuxer_task(config, data, context);
-            num_rows = self
-                .spawn_writer_tasks_and_join(
-                    context,
-                    demux_task,
-                    file_stream_rx,
-                    Arc::clone(&object_store),
-                )
-                .await?;
-        }
-        Ok(num_rows)
     }
+    Ok(num_rows)
 }
 
 /// The base configurations to provide when creating a physical plan for
@@ -135,6 +196,35 @@ pub struct FileSinkConfig {
     pub keep_partition_by_columns: bool,
     /// File extension without a dot(.)
     pub file_extension: String,
+    /// Controls whether the output is written to a single file or to a
+    /// directory of files
+    pub file_output_mode: FileOutputMode,
+}
+
+/// Whether a write produces a single file or a directory of files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FileOutputMode {
+    /// A single file is written if the output path has a file extension and
+    /// does not end with `/`, a directory otherwise
+    #[default]
+    Automatic,
+    /// A single file is written at the output path, whatever its extension.
+    /// The file is encoded in parallel and keeps the order of the input
+    SingleFile,
+    /// A directory of files is written below the output path, whatever its
+    /// extension
+    Directory,
+}
+
+impl FileOutputMode {
+    /// Returns whether a single file is written at `path`
+    pub fn is_single_file(&self, path: &ListingTableUrl) -> bool {
+        match self {
+            Self::Automatic => !path.is_collection() && path.file_extension().is_some(),
+            Self::SingleFile => true,
+            Self::Directory => false,
+        }
+    }
 }
 
 impl FileSinkConfig {
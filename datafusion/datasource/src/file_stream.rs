@@ -31,7 +31,9 @@ use crate::file_meta::FileMeta;
 use crate::file_scan_config::{FileScanConfig, PartitionColumnProjector};
 use crate::PartitionedFile;
 use arrow::datatypes::SchemaRef;
+use datafusion_common::cancellation::{CancellationListener, CancellationToken};
 use datafusion_common::error::Result;
+use datafusion_execution::progress::ProgressTracker;
 use datafusion_execution::RecordBatchStream;
 use datafusion_physical_plan::metrics::{
     BaselineMetrics, Count, ExecutionPlanMetricsSet, MetricBuilder, Time,
@@ -68,6 +70,53 @@ pub struct FileStream {
     baseline_metrics: BaselineMetrics,
     /// Describes the behavior of the `FileStream` if file opening or scanning fails
     on_error: OnError,
+    /// Stops the stream once the query is cancelled
+    cancellation: Option<CancellationListener>,
+    /// Progress of the scan, if reported
+    progress: Option<FileStreamProgress>,
+}
+
+/// Reports the progress of a [`FileStream`] into a [`ProgressTracker`]
+struct FileStreamProgress {
+    tracker: ProgressTracker,
+    /// Bytes of the files being opened or scanned, in the order they complete
+    open_files: VecDeque<u64>,
+    /// Bytes registered with the tracker that were not reported as scanned
+    remaining: u64,
+    completed: bool,
+}
+
+impl FileStreamProgress {
+    /// Report the file that was opened or scanned first as scanned
+    fn file_completed(progress: &mut Option<Self>) {
+        if let Some(progress) = progress {
+            if let Some(bytes) = progress.open_files.pop_front() {
+                progress.remaining -= bytes;
+                progress.tracker.add_bytes_scanned(bytes);
+            }
+        }
+    }
+
+    /// Report the stream as completed, along with the bytes of the files it
+    /// did not scan, such as when it stopped at its limit
+    fn stream_completed(&mut self) {
+        if !self.completed {
+            self.completed = true;
+            if self.remaining > 0 {
+                self.tracker.add_bytes_scanned(self.remaining);
+                self.remaining = 0;
+            }
+            self.tracker.operator_completed();
+        }
+    }
+}
+
+/// Returns the bytes of `file` scanned by a [`FileStream`]
+fn file_bytes(file: &PartitionedFile) -> u64 {
+    match &file.range {
+        Some(range) => (range.end - range.start).max(0) as u64,
+        None => file.object_meta.size,
+    }
 }
 
 impl FileStream {
@@ -100,6 +149,8 @@ impl FileStream {
             file_stream_metrics: FileStreamMetrics::new(metrics, partition),
             baseline_metrics: BaselineMetrics::new(metrics, partition),
             on_error: OnError::Fail,
+            cancellation: None,
+            progress: None,
         })
     }
 
@@ -112,12 +163,41 @@ impl FileStream {
         self
     }
 
+    /// Stop the stream with an error once `cancellation` is cancelled
+    ///
+    /// The file being opened or scanned is dropped as soon as the query is
+    /// cancelled, aborting its in-flight object store requests, even while
+    /// the stream is waiting for them.
+    pub fn with_cancellation_token(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = Some(cancellation.listener());
+        self
+    }
+
+    /// Report the progress of the stream into `tracker`
+    ///
+    /// The bytes of all the files of the stream are registered with the
+    /// tracker, and reported as scanned once each file was read.
+    pub fn with_progress_tracker(mut self, tracker: ProgressTracker) -> Self {
+        let total = self.file_iter.iter().map(file_bytes).sum();
+        tracker.add_total_bytes(total);
+        self.progress = Some(FileStreamProgress {
+            tracker,
+            open_files: VecDeque::new(),
+            remaining: total,
+            completed: false,
+        });
+        self
+    }
+
     /// Begin opening the next file in parallel while decoding the current file in FileStream.
     ///
     /// Since file opening is mostly IO (and may involve a
     /// bunch of sequential IO), it can be parallelized with decoding.
     fn start_next_file(&mut self) -> Option<Result<(FileOpenFuture, Vec<ScalarValue>)>> {
         let part_file = self.file_iter.pop_front()?;
+        if let Some(progress) = &mut self.progress {
+            progress.open_files.push_back(file_bytes(&part_file));
+        }
 
         let file_meta = FileMeta {
             object_meta: part_file.object_meta.clone(),
@@ -135,6 +215,14 @@ impl FileStream {
     }
 
     fn poll_inner(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<RecordBatch>>> {
+        if let Some(cancellation) = &self.cancellation {
+            if !matches!(self.state, FileStreamState::Error | FileStreamState::Limit) {
+                if let Poll::Ready(e) = cancellation.poll_cancelled(cx) {
+                    self.state = FileStreamState::Error;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
         loop {
             match &mut self.state {
                 FileStreamState::Idle => {
@@ -195,6 +283,7 @@ impl FileStream {
                         self.file_stream_metrics.file_open_errors.add(1);
                         match self.on_error {
                             OnError::Skip => {
+                                FileStreamProgress::file_completed(&mut self.progress);
                                 self.file_stream_metrics.time_opening.stop();
                                 self.state = FileStreamState::Idle
                             }
@@ -241,10 +330,17 @@ impl FileStream {
                                     None => batch,
                                 });
 
-                            if result.is_err() {
+                            match &result {
+                                Ok(batch) => {
+                                    if let Some(progress) = &self.progress {
+                                        progress
+                                            .tracker
+                                            .add_rows_produced(batch.num_rows());
+                                    }
+                                }
                                 // If the partition value projection fails, this is not governed by
                                 // the `OnError` behavior
-                                self.state = FileStreamState::Error
+                                Err(_) => self.state = FileStreamState::Error,
                             }
                             self.file_stream_metrics.time_scanning_total.start();
                             return Poll::Ready(Some(result.map_err(Into::into)));
@@ -254,6 +350,10 @@ impl FileStream {
                             self.file_stream_metrics.time_scanning_until_data.stop();
                             self.file_stream_metrics.time_scanning_total.stop();
 
+                            // A skipped file is not scanned further
+                            if matches!(self.on_error, OnError::Skip) {
+                                FileStreamProgress::file_completed(&mut self.progress);
+                            }
                             match self.on_error {
                                 // If `OnError::Skip` we skip the file as soon as we hit the first error
                                 OnError::Skip => match mem::take(next) {
@@ -289,6 +389,7 @@ impl FileStream {
                             self.file_stream_metrics.time_scanning_until_data.stop();
                             self.file_stream_metrics.time_scanning_total.stop();
 
+                            FileStreamProgress::file_completed(&mut self.progress);
                             match mem::take(next) {
                                 Some((future, partition_values)) => {
                                     self.file_stream_metrics.time_opening.start();
@@ -333,6 +434,9 @@ impl Stream for FileStream {
         self.file_stream_metrics.time_processing.start();
         let result = self.poll_inner(cx);
         self.file_stream_metrics.time_processing.stop();
+        if let (Poll::Ready(None), Some(progress)) = (&result, &mut self.progress) {
+            progress.stream_completed();
+        }
         self.baseline_metrics.record_poll(result)
     }
 }
@@ -527,11 +631,12 @@ mod tests {
     use crate::tests::make_partition;
     use crate::PartitionedFile;
     use arrow::error::ArrowError;
+    use datafusion_common::cancellation::CancellationToken;
     use datafusion_common::error::Result;
     use datafusion_execution::object_store::ObjectStoreUrl;
     use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
     use futures::{FutureExt as _, StreamExt as _};
-    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
     use std::sync::Arc;
 
     use crate::file_meta::FileMeta;
@@ -988,4 +1093,68 @@ mod tests {
 
         Ok(())
     }
+
+    /// `FileOpener` whose files never finish opening, recording whether the
+    /// opening was aborted
+    #[derive(Default)]
+    struct PendingOpener {
+        aborted: Arc<AtomicBool>,
+    }
+
+    struct AbortGuard(Arc<AtomicBool>);
+
+    impl Drop for AbortGuard {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    impl FileOpener for PendingOpener {
+        fn open(
+            &self,
+            _file_meta: FileMeta,
+            _file: PartitionedFile,
+        ) -> Result<FileOpenFuture> {
+            let guard = AbortGuard(Arc::clone(&self.aborted));
+            Ok(async move {
+                let _guard = guard;
+                futures::future::pending().await
+            }
+            .boxed())
+        }
+    }
+
+    #[tokio::test]
+    async fn cancel_aborts_opening() -> Result<()> {
+        let opener = PendingOpener::default();
+        let aborted = Arc::clone(&opener.aborted);
+        let config = FileScanConfigBuilder::new(
+            ObjectStoreUrl::parse("test:///").unwrap(),
+            Arc::new(Schema::empty()),
+            Arc::new(MockSource::default()),
+        )
+        .with_file(PartitionedFile::new("mock_file", 10))
+        .build();
+        let cancellation = CancellationToken::new();
+        let mut file_stream = FileStream::new(
+            &config,
+            0,
+            Arc::new(opener),
+            &ExecutionPlanMetricsSet::new(),
+        )?
+        .with_cancellation_token(cancellation.clone());
+
+        assert!(futures::poll!(file_stream.next()).is_pending());
+        assert!(!aborted.load(Ordering::SeqCst));
+
+        cancellation.cancel();
+        let err = file_stream.next().await.unwrap().unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Execution error: The query was cancelled"
+        );
+        assert!(aborted.load(Ordering::SeqCst));
+        assert!(file_stream.next().await.is_none());
+        Ok(())
+    }
 }
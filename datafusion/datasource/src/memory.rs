@@ -25,10 +25,14 @@ use std::sync::Arc;
 use crate::sink::DataSink;
 use crate::source::{DataSource, DataSourceExec};
 
-use arrow::array::{RecordBatch, RecordBatchOptions};
+use arrow::array::{BooleanArray, RecordBatch, RecordBatchOptions};
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{filter_record_batch, not, prep_null_mask_filter};
 use arrow::datatypes::{Schema, SchemaRef};
+use datafusion_common::cast::as_boolean_array;
 use datafusion_common::{internal_err, plan_err, project_schema, Result, ScalarValue};
 use datafusion_execution::TaskContext;
+use datafusion_expr::SortExpr;
 use datafusion_physical_expr::equivalence::{
     OrderingEquivalenceClass, ProjectionMapping,
 };
@@ -39,6 +43,7 @@ use datafusion_physical_plan::memory::MemoryStream;
 use datafusion_physical_plan::projection::{
     all_alias_free_columns, new_projections_for_columns, ProjectionExec,
 };
+use datafusion_physical_plan::shared::batches_identity;
 use datafusion_physical_plan::{
     common, ColumnarValue, DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning,
     PhysicalExpr, SendableRecordBatchStream, Statistics,
@@ -49,6 +54,7 @@ use datafusion_physical_plan::coop::cooperative;
 use datafusion_physical_plan::execution_plan::SchedulingType;
 use futures::StreamExt;
 use itertools::Itertools;
+use parking_lot::Mutex;
 use tokio::sync::RwLock;
 
 /// Data source configuration for reading in-memory batches of data
@@ -211,6 +217,10 @@ impl DataSource for MemorySourceConfig {
         self.fetch
     }
 
+    fn output_identity(&self) -> Option<String> {
+        Some(batches_identity(&self.partitions))
+    }
+
     fn try_swapping_with_projection(
         &self,
         projection: &ProjectionExec,
@@ -765,6 +775,159 @@ impl DataSink for MemSink {
     }
 }
 
+/// The change made by a [`MemDmlSink`] to the rows it selects
+#[derive(Debug, Clone)]
+pub enum MemDmlOp {
+    /// Deletes the rows
+    Delete,
+    /// Sets the columns at the given indices to the values of the expressions,
+    /// evaluated on the rows before the update
+    Update(Vec<(usize, Arc<dyn PhysicalExpr>)>),
+}
+
+/// Implements `DELETE` and `UPDATE` statements for a [`MemTable`], changing
+/// the rows for which a predicate is true
+///
+/// The sink ignores the data it is given: all partitions are rewritten at
+/// once, while holding their locks, and the number of changed rows is
+/// returned.
+///
+/// [`MemTable`]: <https://docs.rs/datafusion/latest/datafusion/datasource/memory/struct.MemTable.html>
+pub struct MemDmlSink {
+    batches: Vec<PartitionData>,
+    schema: SchemaRef,
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    op: MemDmlOp,
+    /// The sort order of the table, reset once rows were updated
+    sort_order: Option<Arc<Mutex<Vec<Vec<SortExpr>>>>>,
+}
+
+impl Debug for MemDmlSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemDmlSink")
+            .field("num_partitions", &self.batches.len())
+            .field("op", &self.op)
+            .finish()
+    }
+}
+
+impl DisplayAs for MemDmlSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self.op {
+            MemDmlOp::Delete => "delete",
+            MemDmlOp::Update(_) => "update",
+        };
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "MemoryTable {op}")
+            }
+            DisplayFormatType::TreeRender => write!(f, "{op}"),
+        }
+    }
+}
+
+impl MemDmlSink {
+    /// Creates a new [`MemDmlSink`] changing the rows of `batches` for which
+    /// `predicate` is true, or all rows without a predicate
+    pub fn new(
+        batches: Vec<PartitionData>,
+        schema: SchemaRef,
+        predicate: Option<Arc<dyn PhysicalExpr>>,
+        op: MemDmlOp,
+    ) -> Self {
+        Self {
+            batches,
+            schema,
+            predicate,
+            op,
+            sort_order: None,
+        }
+    }
+
+    /// Set the sort order of the table, which is reset once an update
+    /// succeeded, as the updated values may be out of order
+    pub fn with_sort_order(mut self, sort_order: Arc<Mutex<Vec<Vec<SortExpr>>>>) -> Self {
+        self.sort_order = Some(sort_order);
+        self
+    }
+
+    /// Returns `batch` after the change, and the number of changed rows
+    fn apply(&self, batch: &RecordBatch) -> Result<(Option<RecordBatch>, usize)> {
+        let num_rows = batch.num_rows();
+        let selected = match &self.predicate {
+            Some(predicate) => {
+                let selected = predicate.evaluate(batch)?.into_array(num_rows)?;
+                // Rows for which the predicate is null are not selected
+                prep_null_mask_filter(as_boolean_array(&selected)?)
+            }
+            None => BooleanArray::from(vec![true; num_rows]),
+        };
+        let count = selected.true_count();
+        if count == 0 {
+            return Ok((Some(batch.clone()), 0));
+        }
+        let batch = match &self.op {
+            MemDmlOp::Delete => {
+                let kept = filter_record_batch(batch, &not(&selected)?)?;
+                (kept.num_rows() > 0).then_some(kept)
+            }
+            MemDmlOp::Update(assignments) => {
+                let mut columns = batch.columns().to_vec();
+                for (index, expr) in assignments {
+                    let values = expr.evaluate(batch)?.into_array(num_rows)?;
+                    columns[*index] = zip(&selected, &values, batch.column(*index))?;
+                }
+                Some(RecordBatch::try_new(batch.schema(), columns)?)
+            }
+        };
+        Ok((batch, count))
+    }
+}
+
+#[async_trait]
+impl DataSink for MemDmlSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    async fn write_all(
+        &self,
+        _data: SendableRecordBatchStream,
+        _context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        // Lock all partitions before changing any, so that the statement
+        // either changes all partitions or fails without changes
+        let mut partitions = Vec::with_capacity(self.batches.len());
+        for partition in &self.batches {
+            partitions.push(partition.write().await);
+        }
+        let mut new_partitions = Vec::with_capacity(partitions.len());
+        let mut count = 0;
+        for batches in &partitions {
+            let mut new_batches = Vec::with_capacity(batches.len());
+            for batch in batches.iter() {
+                let (batch, changed) = self.apply(batch)?;
+                new_batches.extend(batch);
+                count += changed;
+            }
+            new_partitions.push(new_batches);
+        }
+        for (partition, new_batches) in partitions.iter_mut().zip(new_partitions) {
+            **partition = new_batches;
+        }
+        if let (MemDmlOp::Update(_), Some(sort_order)) = (&self.op, &self.sort_order) {
+            if count > 0 {
+                sort_order.lock().clear();
+            }
+        }
+        Ok(count as u64)
+    }
+}
+
 #[cfg(test)]
 mod memory_source_tests {
     use std::sync::Arc;
@@ -23,13 +23,16 @@
 use arrow::{
     array::{new_null_array, ArrayRef, RecordBatch, RecordBatchOptions},
     compute::can_cast_types,
-    datatypes::{DataType, Field, Schema, SchemaRef},
+    datatypes::{
+        DataType, Field, Schema, SchemaRef, TimeUnit, DECIMAL128_MAX_PRECISION,
+        DECIMAL256_MAX_PRECISION,
+    },
 };
 use datafusion_common::{
     nested_struct::{cast_column, validate_struct_compatibility},
-    plan_err, ColumnStatistics,
+    plan_datafusion_err, plan_err, ColumnStatistics,
 };
-use std::{fmt::Debug, sync::Arc};
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
 /// Function used by [`SchemaMapping`] to adapt a column from the file schema to
 /// the table schema.
 pub type CastColumnFn =
@@ -298,6 +301,227 @@ impl SchemaAdapter for DefaultSchemaAdapter {
     }
 }
 
+/// [`SchemaAdapterFactory`] for [`SchemaAdapter`]s that only convert the
+/// columns of a file to the type of the table when no value is lost, see
+/// [`is_safe_promotion`].
+///
+/// Unlike [`DefaultSchemaAdapterFactory`], which casts a column whenever Arrow
+/// supports the cast, mapping a file schema where a column can't be promoted,
+/// such as an `Int64` column of an `Int32` table column, is an error naming the
+/// column. Listing tables use this factory when
+/// `datafusion.execution.listing_table_schema_coercion` is enabled.
+#[derive(Clone, Debug, Default)]
+pub struct PromotingSchemaAdapterFactory;
+
+impl SchemaAdapterFactory for PromotingSchemaAdapterFactory {
+    fn create(
+        &self,
+        projected_table_schema: SchemaRef,
+        _table_schema: SchemaRef,
+    ) -> Box<dyn SchemaAdapter> {
+        Box::new(PromotingSchemaAdapter {
+            projected_table_schema,
+        })
+    }
+}
+
+/// The [`SchemaAdapter`] of [`PromotingSchemaAdapterFactory`]
+#[derive(Clone, Debug)]
+struct PromotingSchemaAdapter {
+    /// The schema for the table, projected to include only the fields being
+    /// output (projected) by the associated file source
+    projected_table_schema: SchemaRef,
+}
+
+/// Checks if a file field can be promoted to a table field without loss
+///
+/// Returns Ok(true) if it can, or an error naming the field otherwise
+pub(crate) fn can_promote_field(
+    file_field: &Field,
+    table_field: &Field,
+) -> datafusion_common::Result<bool> {
+    match (file_field.data_type(), table_field.data_type()) {
+        (DataType::Struct(source_fields), DataType::Struct(target_fields)) => {
+            validate_struct_compatibility(source_fields, target_fields)
+        }
+        (file_type, table_type) => {
+            if is_safe_promotion(file_type, table_type) {
+                Ok(true)
+            } else {
+                plan_err!(
+                    "Cannot promote column {} of type {file_type} to the type {table_type} of the table schema",
+                    file_field.name()
+                )
+            }
+        }
+    }
+}
+
+impl SchemaAdapter for PromotingSchemaAdapter {
+    fn map_column_index(&self, index: usize, file_schema: &Schema) -> Option<usize> {
+        let field = self.projected_table_schema.field(index);
+        Some(file_schema.fields.find(field.name())?.0)
+    }
+
+    fn map_schema(
+        &self,
+        file_schema: &Schema,
+    ) -> datafusion_common::Result<(Arc<dyn SchemaMapper>, Vec<usize>)> {
+        let (field_mappings, projection) = create_field_mapping(
+            file_schema,
+            &self.projected_table_schema,
+            can_promote_field,
+        )?;
+
+        Ok((
+            Arc::new(SchemaMapping::new(
+                Arc::clone(&self.projected_table_schema),
+                field_mappings,
+                Arc::new(|array: &ArrayRef, field: &Field| cast_column(array, field)),
+            )),
+            projection,
+        ))
+    }
+}
+
+/// Returns true if every value of type `from` can be converted to type `to`
+/// without loss: widening integers and floats, changing the representation of
+/// strings or binaries, increasing the precision of timestamps and decimals,
+/// and promoting the items or fields of lists and structs.
+pub fn is_safe_promotion(from: &DataType, to: &DataType) -> bool {
+    use DataType::*;
+    if from == to {
+        return true;
+    }
+    match (from, to) {
+        (Null, _)
+        | (Int8, Int16 | Int32 | Int64 | Float32 | Float64)
+        | (Int16, Int32 | Int64 | Float32 | Float64)
+        | (Int32, Int64 | Float64)
+        | (UInt8, UInt16 | UInt32 | UInt64 | Int16 | Int32 | Int64 | Float32 | Float64)
+        | (UInt16, UInt32 | UInt64 | Int32 | Int64 | Float32 | Float64)
+        | (UInt32, UInt64 | Int64 | Float64)
+        | (Float16, Float32 | Float64)
+        | (Float32, Float64)
+        | (Utf8 | LargeUtf8 | Utf8View, Utf8 | LargeUtf8 | Utf8View)
+        | (Binary | LargeBinary | BinaryView, Binary | LargeBinary | BinaryView) => true,
+        (Timestamp(from_unit, from_tz), Timestamp(to_unit, to_tz)) => {
+            from_tz == to_tz && unit_rank(from_unit) <= unit_rank(to_unit)
+        }
+        (
+            Decimal128(from_precision, from_scale),
+            Decimal128(to_precision, to_scale) | Decimal256(to_precision, to_scale),
+        )
+        | (Decimal256(from_precision, from_scale), Decimal256(to_precision, to_scale)) => {
+            to_scale >= from_scale
+                && *to_precision as i16 - *to_scale as i16
+                    >= *from_precision as i16 - *from_scale as i16
+        }
+        (List(from_item), List(to_item) | LargeList(to_item))
+        | (LargeList(from_item), LargeList(to_item)) => {
+            is_safe_promotion(from_item.data_type(), to_item.data_type())
+        }
+        (Struct(from_fields), Struct(to_fields)) => from_fields.iter().all(|from| {
+            to_fields.find(from.name()).is_some_and(|(_, to)| {
+                is_safe_promotion(from.data_type(), to.data_type())
+            })
+        }),
+        _ => false,
+    }
+}
+
+/// Orders time units from the least to the most precise
+fn unit_rank(unit: &TimeUnit) -> u8 {
+    match unit {
+        TimeUnit::Second => 0,
+        TimeUnit::Millisecond => 1,
+        TimeUnit::Microsecond => 2,
+        TimeUnit::Nanosecond => 3,
+    }
+}
+
+/// Returns the narrowest type both `left` and `right` can be promoted to
+/// without loss, if any
+fn promoted_type(left: &DataType, right: &DataType) -> Option<DataType> {
+    use DataType::*;
+    if is_safe_promotion(right, left) {
+        return Some(left.clone());
+    }
+    if is_safe_promotion(left, right) {
+        return Some(right.clone());
+    }
+    match (left, right) {
+        (
+            Decimal128(left_precision, left_scale)
+            | Decimal256(left_precision, left_scale),
+            Decimal128(right_precision, right_scale)
+            | Decimal256(right_precision, right_scale),
+        ) => {
+            let scale = *left_scale.max(right_scale);
+            let integral_digits = (*left_precision as i16 - *left_scale as i16)
+                .max(*right_precision as i16 - *right_scale as i16);
+            let precision = integral_digits + scale as i16;
+            let decimal128 = matches!((left, right), (Decimal128(..), Decimal128(..)));
+            if decimal128 && precision <= DECIMAL128_MAX_PRECISION as i16 {
+                Some(Decimal128(precision as u8, scale))
+            } else if precision <= DECIMAL256_MAX_PRECISION as i16 {
+                Some(Decimal256(precision as u8, scale))
+            } else {
+                None
+            }
+        }
+        // Such as Int8 and UInt8, or Int16 and UInt16
+        _ => [Int16, Int32, Int64, Float32, Float64]
+            .into_iter()
+            .find(|to| is_safe_promotion(left, to) && is_safe_promotion(right, to)),
+    }
+}
+
+/// Merges `schemas` like [`Schema::try_merge`], except that a column with
+/// different types in different schemas gets the narrowest type all of them
+/// can be promoted to, see [`is_safe_promotion`], such as `Int64` for `Int32`
+/// and `Int64` columns.
+///
+/// An error naming the column is returned if there is no such type.
+pub fn merge_schemas_with_promotion(
+    schemas: Vec<Schema>,
+) -> datafusion_common::Result<Schema> {
+    let mut types: HashMap<String, DataType> = HashMap::new();
+    for field in schemas.iter().flat_map(|schema| schema.fields()) {
+        let merged = match types.get(field.name()) {
+            Some(current) => {
+                promoted_type(current, field.data_type()).ok_or_else(|| {
+                    plan_datafusion_err!(
+                        "Cannot merge column {} of types {current} and {}",
+                        field.name(),
+                        field.data_type()
+                    )
+                })?
+            }
+            None => field.data_type().clone(),
+        };
+        types.insert(field.name().clone(), merged);
+    }
+
+    let schemas = schemas
+        .into_iter()
+        .map(|schema| {
+            let fields = schema
+                .fields()
+                .iter()
+                .map(|field| match types.get(field.name()) {
+                    Some(data_type) if data_type != field.data_type() => {
+                        Arc::new(field.as_ref().clone().with_data_type(data_type.clone()))
+                    }
+                    _ => Arc::clone(field),
+                })
+                .collect::<Vec<_>>();
+            Schema::new_with_metadata(fields, schema.metadata().clone())
+        })
+        .collect::<Vec<_>>();
+    Ok(Schema::try_merge(schemas)?)
+}
+
 /// Helper function that creates field mappings between file schema and table schema
 ///
 /// Maps columns from the file schema to their corresponding positions in the table schema,
@@ -1009,4 +1233,97 @@ mod tests {
             sum_value: sum_value.map_or_else(|| Precision::Absent, Precision::Exact),
         }
     }
+
+    #[test]
+    fn test_is_safe_promotion() {
+        use DataType::*;
+        assert!(is_safe_promotion(&Int32, &Int64));
+        assert!(is_safe_promotion(&UInt32, &Int64));
+        assert!(is_safe_promotion(&Float32, &Float64));
+        assert!(is_safe_promotion(&Utf8, &Utf8View));
+        assert!(is_safe_promotion(&LargeUtf8, &Utf8));
+        assert!(is_safe_promotion(
+            &Timestamp(TimeUnit::Millisecond, None),
+            &Timestamp(TimeUnit::Nanosecond, None)
+        ));
+        assert!(is_safe_promotion(&Decimal128(10, 2), &Decimal128(12, 3)));
+
+        assert!(!is_safe_promotion(&Int64, &Int32));
+        assert!(!is_safe_promotion(&Int64, &Float64));
+        assert!(!is_safe_promotion(&Utf8, &Int64));
+        assert!(!is_safe_promotion(
+            &Timestamp(TimeUnit::Nanosecond, None),
+            &Timestamp(TimeUnit::Second, None)
+        ));
+        assert!(!is_safe_promotion(
+            &Timestamp(TimeUnit::Second, None),
+            &Timestamp(TimeUnit::Second, Some("UTC".into()))
+        ));
+        assert!(!is_safe_promotion(&Decimal128(10, 2), &Decimal128(10, 3)));
+    }
+
+    #[test]
+    fn test_merge_schemas_with_promotion() -> Result<()> {
+        let schemas = vec![
+            Schema::new(vec![
+                Field::new("a", DataType::Int32, false),
+                Field::new("b", DataType::Decimal128(10, 2), true),
+                Field::new("c", DataType::Int8, true),
+            ]),
+            Schema::new(vec![
+                Field::new("a", DataType::Int64, true),
+                Field::new("b", DataType::Decimal128(5, 4), true),
+                Field::new("c", DataType::UInt8, true),
+                Field::new("d", DataType::Utf8, true),
+            ]),
+        ];
+        let merged = merge_schemas_with_promotion(schemas)?;
+        assert_eq!(
+            merged,
+            Schema::new(vec![
+                Field::new("a", DataType::Int64, true),
+                Field::new("b", DataType::Decimal128(12, 4), true),
+                Field::new("c", DataType::Int16, true),
+                Field::new("d", DataType::Utf8, true),
+            ])
+        );
+
+        let err = merge_schemas_with_promotion(vec![
+            Schema::new(vec![Field::new("a", DataType::Int64, true)]),
+            Schema::new(vec![Field::new("a", DataType::Utf8, true)]),
+        ])
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Cannot merge column a of types Int64 and Utf8"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_promoting_schema_adapter() -> Result<()> {
+        let table_schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let adapter = PromotingSchemaAdapterFactory
+            .create(Arc::clone(&table_schema), Arc::clone(&table_schema));
+
+        let file_schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let (mapper, projection) = adapter.map_schema(&file_schema)?;
+        assert_eq!(projection, vec![0]);
+        let batch = RecordBatch::try_new(
+            Arc::new(file_schema),
+            vec![Arc::new(arrow::array::Int32Array::from(vec![1, 2]))],
+        )?;
+        let mapped = mapper.map_batch(batch)?;
+        assert_eq!(mapped.schema(), table_schema);
+        assert_eq!(mapped.column(0).data_type(), &DataType::Int64);
+
+        let file_schema = Schema::new(vec![Field::new("b", DataType::Int64, true)]);
+        let err = adapter.map_schema(&file_schema).unwrap_err();
+        assert!(err.to_string().contains(
+            "Cannot promote column b of type Int64 to the type Int32 of the table schema"
+        ));
+        Ok(())
+    }
 }
@@ -33,7 +33,7 @@ use datafusion_physical_plan::{
 
 use crate::file_scan_config::FileScanConfig;
 use datafusion_common::config::ConfigOptions;
-use datafusion_common::{Constraints, Result, Statistics};
+use datafusion_common::{Constraints, Result, ScalarValue, Statistics};
 use datafusion_execution::{SendableRecordBatchStream, TaskContext};
 use datafusion_physical_expr::{EquivalenceProperties, Partitioning, PhysicalExpr};
 use datafusion_physical_expr_common::sort_expr::LexOrdering;
@@ -159,6 +159,20 @@ pub trait DataSource: Send + Sync + Debug {
         &self,
         _projection: &ProjectionExec,
     ) -> Result<Option<Arc<dyn ExecutionPlan>>>;
+    /// Returns what identifies the data of this DataSource beyond its display.
+    /// See [`ExecutionPlan::output_identity`] for more details.
+    fn output_identity(&self) -> Option<String> {
+        None
+    }
+    /// Returns the number of rows of this DataSource for each combination of
+    /// values of the given output `columns`, if known without reading data.
+    /// See [`ExecutionPlan::num_rows_by_values`] for more details.
+    fn num_rows_by_values(
+        &self,
+        _columns: &[usize],
+    ) -> Result<Option<Vec<(Vec<ScalarValue>, usize)>>> {
+        Ok(None)
+    }
     /// Try to push down filters into this DataSource.
     /// See [`ExecutionPlan::handle_child_pushdown_result`] for more details.
     ///
@@ -301,6 +315,17 @@ impl ExecutionPlan for DataSourceExec {
         self.data_source.fetch()
     }
 
+    fn output_identity(&self) -> Option<String> {
+        self.data_source.output_identity()
+    }
+
+    fn num_rows_by_values(
+        &self,
+        columns: &[usize],
+    ) -> Result<Option<Vec<(Vec<ScalarValue>, usize)>>> {
+        self.data_source.num_rows_by_values(columns)
+    }
+
     fn try_swapping_with_projection(
         &self,
         projection: &ProjectionExec,
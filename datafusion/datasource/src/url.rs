@@ -17,6 +17,8 @@
 
 use std::sync::Arc;
 
+use crate::write::staging::STAGING_DIR_PREFIX;
+
 use datafusion_common::{DataFusionError, Result};
 use datafusion_execution::object_store::ObjectStoreUrl;
 use datafusion_session::Session;
@@ -167,9 +169,18 @@ impl ListingTableUrl {
             return false;
         };
 
+        // files staged by an overwrite are not part of the table yet
+        let all_segments = all_segments.collect::<Vec<_>>();
+        if all_segments
+            .iter()
+            .any(|s| s.starts_with(STAGING_DIR_PREFIX))
+        {
+            return false;
+        }
+
         // remove any segments that contain `=` as they are allowed even
         // when ignore subdirectories is `true`.
-        let mut segments = all_segments.filter(|s| !s.contains('='));
+        let mut segments = all_segments.into_iter().filter(|s| !s.contains('='));
 
         match &self.glob {
             Some(glob) => {
@@ -548,6 +559,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_contains_staged_files() {
+        let url = ListingTableUrl::parse("file:///foo/").unwrap();
+        for ignore_subdirectory in [true, false] {
+            let path = Path::parse("/foo/a=1/file.parquet").unwrap();
+            assert!(url.contains(&path, ignore_subdirectory));
+            let path = Path::parse("/foo/_datafusion_staging_x/file.parquet").unwrap();
+            assert!(!url.contains(&path, ignore_subdirectory));
+            let path =
+                Path::parse("/foo/_datafusion_staging_x/a=1/file.parquet").unwrap();
+            assert!(!url.contains(&path, ignore_subdirectory));
+        }
+    }
+
     #[test]
     fn test_file_extension() {
         fn test(input: &str, expected: Option<&str>, message: &str) {
@@ -33,12 +33,12 @@ use arrow::array::{
 };
 use arrow::datatypes::{DataType, Schema};
 use datafusion_common::cast::{
-    as_boolean_array, as_date32_array, as_date64_array, as_float16_array,
-    as_float32_array, as_float64_array, as_int16_array, as_int32_array, as_int64_array,
-    as_int8_array, as_string_array, as_string_view_array, as_uint16_array,
-    as_uint32_array, as_uint64_array, as_uint8_array,
+    as_boolean_array, as_date32_array, as_date64_array, as_decimal128_array,
+    as_float16_array, as_float32_array, as_float64_array, as_int16_array, as_int32_array,
+    as_int64_array, as_int8_array, as_string_array, as_string_view_array,
+    as_uint16_array, as_uint32_array, as_uint64_array, as_uint8_array,
 };
-use datafusion_common::{exec_datafusion_err, not_impl_err, DataFusionError};
+use datafusion_common::{exec_datafusion_err, not_impl_err, plan_err, DataFusionError};
 use datafusion_common_runtime::SpawnedTask;
 use datafusion_execution::TaskContext;
 
@@ -66,7 +66,8 @@ pub type DemuxedStreamReceiver = UnboundedReceiver<(Path, RecordBatchReceiver)>;
 ///
 /// A path with an extension will force only a single file to
 /// be written with the extension from the path. Otherwise the default extension
-/// will be used and the output will be split into multiple files.
+/// will be used and the output will be split into multiple files. The
+/// [`FileSinkConfig::file_output_mode`] can override this choice.
 ///
 /// Examples of `base_output_path`
 ///  * `tmp/dataset/` -> is a folder since it ends in `/`
@@ -101,8 +102,8 @@ pub(crate) fn start_demuxer_task(
     let file_extension = config.file_extension.clone();
     let base_output_path = config.table_paths[0].clone();
     let task = if config.table_partition_cols.is_empty() {
-        let single_file_output = !base_output_path.is_collection()
-            && base_output_path.file_extension().is_some();
+        let single_file_output =
+            config.file_output_mode.is_single_file(&base_output_path);
         SpawnedTask::spawn(async move {
             row_count_demuxer(
                 tx,
@@ -150,6 +151,7 @@ async fn row_count_demuxer(
     let max_rows_per_file = exec_options.soft_max_rows_per_output_file;
     let max_buffered_batches = exec_options.max_buffered_batches_per_output_file;
     let minimum_parallel_files = exec_options.minimum_parallel_output_files;
+    let file_name_template = exec_options.output_file_name_template.clone();
     let mut part_idx = 0;
     let write_id = rand::distr::Alphanumeric.sample_string(&mut rand::rng(), 16);
 
@@ -180,6 +182,7 @@ async fn row_count_demuxer(
                 part_idx,
                 &file_extension,
                 single_file_output,
+                file_name_template.as_deref(),
                 max_buffered_batches,
                 &mut tx,
             )?);
@@ -193,6 +196,7 @@ async fn row_count_demuxer(
                 part_idx,
                 &file_extension,
                 single_file_output,
+                file_name_template.as_deref(),
                 max_buffered_batches,
                 &mut tx,
             )?;
@@ -220,23 +224,29 @@ fn generate_file_path(
     part_idx: usize,
     file_extension: &str,
     single_file_output: bool,
+    file_name_template: Option<&str>,
 ) -> Path {
-    if !single_file_output {
-        base_output_path
-            .prefix()
-            .child(format!("{write_id}_{part_idx}.{file_extension}"))
-    } else {
-        base_output_path.prefix().to_owned()
+    if single_file_output {
+        return base_output_path.prefix().to_owned();
     }
+    let file_name = match file_name_template {
+        Some(template) => render_file_name_template(template, &[], write_id, part_idx),
+        None => format!("{write_id}_{part_idx}"),
+    };
+    base_output_path
+        .prefix()
+        .child(format!("{file_name}.{file_extension}"))
 }
 
 /// Helper for row count demuxer
+#[allow(clippy::too_many_arguments)]
 fn create_new_file_stream(
     base_output_path: &ListingTableUrl,
     write_id: &str,
     part_idx: usize,
     file_extension: &str,
     single_file_output: bool,
+    file_name_template: Option<&str>,
     max_buffered_batches: usize,
     tx: &mut UnboundedSender<(Path, Receiver<RecordBatch>)>,
 ) -> Result<Sender<RecordBatch>> {
@@ -246,6 +256,7 @@ fn create_new_file_stream(
         part_idx,
         file_extension,
         single_file_output,
+        file_name_template,
     );
     let (tx_file, rx_file) = mpsc::channel(max_buffered_batches / 2);
     tx.send((file_path, rx_file)).map_err(|_| {
@@ -254,9 +265,22 @@ fn create_new_file_stream(
     Ok(tx_file)
 }
 
+/// The file being written for a distinct partition key by the hive style
+/// partitions demuxer
+struct PartitionFile {
+    tx: Sender<RecordBatch>,
+    /// Number of rows sent to the file
+    row_count: usize,
+    /// Index of the file within its partition
+    file_idx: usize,
+}
+
 /// Splits an input stream based on the distinct values of a set of columns
 /// Assumes standard hive style partition paths such as
 /// /col1=val1/col2=val2/outputfile.parquet
+///
+/// A new file is started for a partition once `soft_max_rows_per_output_file`
+/// rows were written to its current file.
 async fn hive_style_partitions_demuxer(
     tx: UnboundedSender<(Path, Receiver<RecordBatch>)>,
     mut input: SendableRecordBatchStream,
@@ -270,13 +294,17 @@ async fn hive_style_partitions_demuxer(
 
     let exec_options = &context.session_config().options().execution;
     let max_buffered_recordbatches = exec_options.max_buffered_batches_per_output_file;
+    let max_rows_per_file = exec_options.soft_max_rows_per_output_file;
+    let null_partition_value = exec_options.null_partition_value.as_str();
+    let file_name_template = exec_options.output_file_name_template.as_deref();
 
     // To support non string partition col types, cast the type to &str first
-    let mut value_map: HashMap<Vec<String>, Sender<RecordBatch>> = HashMap::new();
+    let mut value_map: HashMap<Vec<String>, PartitionFile> = HashMap::new();
 
     while let Some(rb) = input.next().await.transpose()? {
         // First compute partition key for each row of batch, e.g. (col1=val1, col2=val2, ...)
-        let all_partition_values = compute_partition_keys_by_row(&rb, &partition_by)?;
+        let all_partition_values =
+            compute_partition_keys_by_row(&rb, &partition_by, null_partition_value)?;
 
         // Next compute how the batch should be split up to take each distinct key to its own batch
         let take_map = compute_take_arrays(&rb, all_partition_values);
@@ -291,35 +319,49 @@ async fn hive_style_partitions_demuxer(
                 arrow::compute::take(&struct_array, &take_indices, None)?.as_struct(),
             );
 
-            // Get or create channel for this batch
-            let part_tx = match value_map.get_mut(&part_key) {
-                Some(part_tx) => part_tx,
-                None => {
-                    // Create channel for previously unseen distinct partition key and notify consumer of new file
-                    let (part_tx, part_rx) =
-                        mpsc::channel::<RecordBatch>(max_buffered_recordbatches);
-                    let file_path = compute_hive_style_file_path(
-                        &part_key,
-                        &partition_by,
-                        &write_id,
-                        &file_extension,
-                        &base_output_path,
-                    );
-
-                    tx.send((file_path, part_rx)).map_err(|_| {
-                        DataFusionError::Execution(
-                            "Error sending new file stream!".into(),
-                        )
-                    })?;
-
-                    value_map.insert(part_key.clone(), part_tx);
-                    value_map
-                        .get_mut(&part_key)
-                        .ok_or(DataFusionError::Internal(
-                            "Key must exist since it was just inserted!".into(),
-                        ))?
+            // Create a channel for a previously unseen distinct partition key, or
+            // when the current file of the partition is full, and notify consumer
+            // of the new file
+            let new_file_idx = match value_map.get(&part_key) {
+                None => Some(0),
+                Some(file) if file.row_count >= max_rows_per_file => {
+                    Some(file.file_idx + 1)
                 }
+                Some(_) => None,
             };
+            if let Some(file_idx) = new_file_idx {
+                let (part_tx, part_rx) =
+                    mpsc::channel::<RecordBatch>(max_buffered_recordbatches);
+                let file_path = compute_hive_style_file_path(
+                    &part_key,
+                    &partition_by,
+                    &write_id,
+                    file_idx,
+                    &file_extension,
+                    &base_output_path,
+                    file_name_template,
+                );
+
+                tx.send((file_path, part_rx)).map_err(|_| {
+                    DataFusionError::Execution("Error sending new file stream!".into())
+                })?;
+
+                // Replacing the previous file of the partition closes it
+                value_map.insert(
+                    part_key.clone(),
+                    PartitionFile {
+                        tx: part_tx,
+                        row_count: 0,
+                        file_idx,
+                    },
+                );
+            }
+            let part_file =
+                value_map
+                    .get_mut(&part_key)
+                    .ok_or(DataFusionError::Internal(
+                        "Key must exist since it was just inserted!".into(),
+                    ))?;
 
             let final_batch_to_send = if keep_partition_by_columns {
                 parted_batch
@@ -328,7 +370,8 @@ async fn hive_style_partitions_demuxer(
             };
 
             // Finally send the partial batch partitioned by distinct value!
-            part_tx.send(final_batch_to_send).await.map_err(|_| {
+            part_file.row_count += final_batch_to_send.num_rows();
+            part_file.tx.send(final_batch_to_send).await.map_err(|_| {
                 DataFusionError::Internal("Unexpected error sending parted batch!".into())
             })?;
         }
@@ -340,6 +383,7 @@ async fn hive_style_partitions_demuxer(
 fn compute_partition_keys_by_row<'a>(
     rb: &'a RecordBatch,
     partition_by: &'a [(String, DataType)],
+    null_partition_value: &'a str,
 ) -> Result<Vec<Vec<Cow<'a, str>>>> {
     let mut all_partition_values = vec![];
 
@@ -398,8 +442,9 @@ fn compute_partition_keys_by_row<'a>(
                 // ISO-8601/RFC3339 format - yyyy-mm-dd
                 let format = "%Y-%m-%d";
                 for i in 0..rb.num_rows() {
+                    // Round towards negative infinity for dates before the epoch
                     let date = NaiveDate::from_num_days_from_ce_opt(
-                        EPOCH_DAYS_FROM_CE + (array.value(i) / 86_400_000) as i32,
+                        EPOCH_DAYS_FROM_CE + array.value(i).div_euclid(86_400_000) as i32,
                     )
                     .unwrap()
                     .format(format)
@@ -473,6 +518,14 @@ fn compute_partition_keys_by_row<'a>(
                     partition_values.push(Cow::from(array.value(i).to_string()));
                 }
             }
+            DataType::Decimal128(_, _) => {
+                // Formatted with the scale of the type, e.g. `1.50`, which
+                // is parsed back by casting
+                let array = as_decimal128_array(col_array)?;
+                for i in 0..rb.num_rows() {
+                    partition_values.push(Cow::from(array.value_as_string(i)));
+                }
+            }
             DataType::Dictionary(_, _) => {
                 downcast_dictionary_array!(
                     col_array =>  {
@@ -495,6 +548,14 @@ fn compute_partition_keys_by_row<'a>(
             }
         }
 
+        if let Some(nulls) = col_array.logical_nulls() {
+            for (value, valid) in partition_values.iter_mut().zip(nulls.iter()) {
+                if !valid {
+                    *value = Cow::from(null_partition_value);
+                }
+            }
+        }
+
         all_partition_values.push(partition_values);
     }
 
@@ -546,13 +607,109 @@ fn compute_hive_style_file_path(
     part_key: &[String],
     partition_by: &[(String, DataType)],
     write_id: &str,
+    file_idx: usize,
     file_extension: &str,
     base_output_path: &ListingTableUrl,
+    file_name_template: Option<&str>,
 ) -> Path {
     let mut file_path = base_output_path.prefix().clone();
     for j in 0..part_key.len() {
         file_path = file_path.child(format!("{}={}", partition_by[j].0, part_key[j]));
     }
 
-    file_path.child(format!("{write_id}.{file_extension}"))
+    let file_name = match (file_name_template, file_idx) {
+        (Some(template), _) => {
+            render_file_name_template(template, part_key, write_id, file_idx)
+        }
+        (None, 0) => write_id.to_string(),
+        (None, _) => format!("{write_id}_{file_idx}"),
+    };
+    file_path.child(format!("{file_name}.{file_extension}"))
+}
+
+/// The placeholders of `datafusion.execution.output_file_name_template`
+const FILE_NAME_PLACEHOLDERS: [&str; 3] = ["partition_values", "uuid", "count"];
+
+/// Checks that `template` is a valid
+/// `datafusion.execution.output_file_name_template`: it only contains known
+/// placeholders, at least one of which makes the names of the files unique,
+/// and no path delimiter.
+pub fn validate_file_name_template(template: &str) -> Result<()> {
+    let mut placeholders = vec![];
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return plan_err!(
+                "Invalid output file name template '{template}': unmatched '}}'"
+            );
+        }
+        let Some(len) = rest[start..].find('}') else {
+            return plan_err!(
+                "Invalid output file name template '{template}': unmatched '{{'"
+            );
+        };
+        let placeholder = &rest[start + 1..start + len];
+        if !FILE_NAME_PLACEHOLDERS.contains(&placeholder) {
+            return plan_err!(
+                "Invalid output file name template '{template}': unknown placeholder '{{{placeholder}}}', expected one of {{partition_values}}, {{uuid}} or {{count}}"
+            );
+        }
+        placeholders.push(placeholder);
+        rest = &rest[start + len + 1..];
+    }
+    if template.contains(object_store::path::DELIMITER) {
+        return plan_err!(
+            "Invalid output file name template '{template}': it must not contain '/'"
+        );
+    }
+    if !placeholders.contains(&"uuid") && !placeholders.contains(&"count") {
+        return plan_err!(
+            "Invalid output file name template '{template}': it must contain {{uuid}} or {{count}} for the file names to be unique"
+        );
+    }
+    Ok(())
+}
+
+/// Substitutes the placeholders of a template validated by
+/// [`validate_file_name_template`]
+fn render_file_name_template(
+    template: &str,
+    partition_values: &[String],
+    write_id: &str,
+    file_idx: usize,
+) -> String {
+    // The partition values are substituted last, as they may contain braces
+    template
+        .replace("{uuid}", write_id)
+        .replace("{count}", &file_idx.to_string())
+        .replace("{partition_values}", &partition_values.join("_"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_name_template() -> Result<()> {
+        let template = "part-{partition_values}-{count}-{uuid}";
+        validate_file_name_template(template)?;
+        let partition_values = ["2024-01-01".to_string(), "{count}".to_string()];
+        assert_eq!(
+            render_file_name_template(template, &partition_values, "abc", 2),
+            "part-2024-01-01_{count}-2-abc"
+        );
+        validate_file_name_template("{count}")?;
+
+        for (template, error) in [
+            ("{partition_values}", "it must contain {uuid} or {count}"),
+            ("part-{id}", "unknown placeholder '{id}'"),
+            ("part-{count", "unmatched '{'"),
+            ("part}-{count}", "unmatched '}'"),
+            ("dir/{count}", "it must not contain '/'"),
+        ] {
+            let err = validate_file_name_template(template).unwrap_err();
+            assert!(err.to_string().contains(error), "{err}");
+        }
+        Ok(())
+    }
 }
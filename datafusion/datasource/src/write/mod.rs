@@ -35,6 +35,7 @@ use tokio::io::AsyncWrite;
 
 pub mod demux;
 pub mod orchestration;
+pub mod staging;
 
 /// A buffer with interior mutability shared by the SerializedFileWriter and
 /// ObjectStore writer
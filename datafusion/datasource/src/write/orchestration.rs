@@ -212,7 +212,7 @@ pub(crate) async fn stateless_serialize_and_write_files(
     for mut writer in finished_writers.into_iter() {
         writer.shutdown()
                     .await
-                    .map_err(|_| internal_datafusion_err!("Error encountered while finalizing writes! Partial results may have been written to ObjectStore!"))?;
+                    .map_err(|e| internal_datafusion_err!("Error encountered while finalizing writes! Partial results may have been written to ObjectStore! {e}"))?;
     }
 
     if any_errors {
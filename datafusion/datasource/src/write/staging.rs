@@ -0,0 +1,179 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Staged writes replacing the files of a table only once all new files
+//! were written
+
+use std::collections::HashSet;
+
+use crate::file_groups::FileGroup;
+use crate::ListingTableUrl;
+
+use datafusion_common::{not_impl_err, DataFusionError, Result};
+
+use futures::{StreamExt, TryStreamExt};
+use log::warn;
+use object_store::path::Path;
+use object_store::ObjectStore;
+use rand::distr::SampleString;
+
+/// Prefix of the directories new files are staged in below the table
+/// directory. Paths in these directories are never part of a listing.
+pub const STAGING_DIR_PREFIX: &str = "_datafusion_staging_";
+
+/// Maximum number of concurrent object store requests made when moving or
+/// deleting files
+const CONCURRENCY: usize = 10;
+
+/// A directory below a table directory holding the new files of an
+/// overwrite until they replace the existing files of the table
+///
+/// Files are written to [`Self::url`] as if it was the table directory. Once
+/// all files were written, [`Self::commit`] moves them to the table directory
+/// and deletes the existing files. Until then, readers of the table only see
+/// the existing files, and [`Self::abort`] deletes the staged files if the
+/// write failed.
+///
+/// Object stores have no atomic multi-file rename, so readers listing the
+/// table while the files are moved may see both new and existing files, and
+/// scans started before the commit fail once their files were deleted.
+#[derive(Debug)]
+pub(crate) struct StagingDir {
+    table_path: ListingTableUrl,
+    url: ListingTableUrl,
+}
+
+impl StagingDir {
+    /// Creates a new staging directory below `table_path`
+    pub(crate) fn try_new(table_path: &ListingTableUrl) -> Result<Self> {
+        if !table_path.is_collection() {
+            return not_impl_err!(
+                "Overwrites are only supported for directories, got {table_path}"
+            );
+        }
+        let id = rand::distr::Alphanumeric.sample_string(&mut rand::rng(), 16);
+        let url = table_path
+            .get_url()
+            .join(&format!("{STAGING_DIR_PREFIX}{id}/"))
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        Ok(Self {
+            table_path: table_path.clone(),
+            url: ListingTableUrl::try_new(url, None)?,
+        })
+    }
+
+    /// The URL to write the new files to
+    pub(crate) fn url(&self) -> &ListingTableUrl {
+        &self.url
+    }
+
+    /// Moves the staged files to the table directory, keeping their paths
+    /// relative to the staging directory, then deletes the `existing` files
+    /// of the table that were not replaced. Returns the staged and the final
+    /// path of each file.
+    ///
+    /// If moving a file fails, the moved and staged files are deleted, and
+    /// the existing files are kept.
+    pub(crate) async fn commit(
+        self,
+        store: &dyn ObjectStore,
+        existing: &FileGroup,
+    ) -> Result<Vec<(Path, Path)>> {
+        let staged = self.list(store).await?;
+        let moves = staged
+            .into_iter()
+            .map(|from| {
+                let to =
+                    Path::from_iter(self.table_path.prefix().parts().chain(
+                        from.prefix_match(self.url.prefix()).into_iter().flatten(),
+                    ));
+                (from, to)
+            })
+            .collect::<Vec<_>>();
+
+        // The futures are created eagerly: closures borrowing `moves` would
+        // make the future of `FileSink::write_all` not `Send`
+        let renames = moves
+            .iter()
+            .map(|(from, to)| store.rename(from, to))
+            .collect::<Vec<_>>();
+        let results = futures::stream::iter(renames)
+            .buffered(CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await;
+        if let Some(position) = results.iter().position(Result::is_err) {
+            let moved = moves
+                .iter()
+                .zip(&results)
+                .filter(|(_, result)| result.is_ok())
+                .map(|((_, to), _)| to.clone());
+            delete_all(store, moved.collect()).await;
+            self.abort(store).await;
+            let (from, _) = &moves[position];
+            let error = results.into_iter().nth(position).unwrap().unwrap_err();
+            return Err(DataFusionError::ObjectStore(error)
+                .context(format!("Failed to move staged file {from}")));
+        }
+
+        let new_files = moves.iter().map(|(_, to)| to).collect::<HashSet<_>>();
+        let deletes = existing
+            .iter()
+            .map(|file| &file.object_meta.location)
+            .filter(|location| !new_files.contains(*location))
+            .map(|location| store.delete(location))
+            .collect::<Vec<_>>();
+        futures::stream::iter(deletes)
+            .buffer_unordered(CONCURRENCY)
+            .map(|result| match result {
+                Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+                Err(e) => Err(DataFusionError::ObjectStore(e)),
+            })
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(moves)
+    }
+
+    /// Deletes the staged files, logging the files that could not be
+    /// deleted
+    pub(crate) async fn abort(self, store: &dyn ObjectStore) {
+        match self.list(store).await {
+            Ok(staged) => delete_all(store, staged).await,
+            Err(e) => warn!("Failed to list staged files in {}: {e}", self.url),
+        }
+    }
+
+    async fn list(&self, store: &dyn ObjectStore) -> Result<Vec<Path>> {
+        Ok(store
+            .list(Some(self.url.prefix()))
+            .map_ok(|meta| meta.location)
+            .try_collect()
+            .await?)
+    }
+}
+
+/// Deletes `paths`, logging the files that could not be deleted
+async fn delete_all(store: &dyn ObjectStore, paths: Vec<Path>) {
+    futures::stream::iter(paths)
+        .map(|path| async move {
+            if let Err(e) = store.delete(&path).await {
+                warn!("Failed to delete staged file {path}: {e}");
+            }
+        })
+        .buffer_unordered(CONCURRENCY)
+        .collect::<()>()
+        .await;
+}
@@ -0,0 +1,396 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A cache of the results of queries, see [`ResultCache`]
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::instant::Instant;
+use parking_lot::Mutex;
+
+use crate::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation};
+
+/// The materialized result of a query, as stored in a [`ResultCache`]
+#[derive(Debug)]
+pub struct CachedResult {
+    schema: SchemaRef,
+    partitions: Vec<Vec<RecordBatch>>,
+}
+
+impl CachedResult {
+    /// Create a result from the output batches of each partition of a plan
+    pub fn new(schema: SchemaRef, partitions: Vec<Vec<RecordBatch>>) -> Self {
+        Self { schema, partitions }
+    }
+
+    /// Returns the schema of the result
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Returns the output batches of each partition
+    pub fn partitions(&self) -> &[Vec<RecordBatch>] {
+        &self.partitions
+    }
+
+    /// Returns the number of rows of the result
+    pub fn num_rows(&self) -> usize {
+        self.partitions.iter().flatten().map(|b| b.num_rows()).sum()
+    }
+
+    /// Returns the memory used by the batches of the result
+    pub fn size(&self) -> usize {
+        self.partitions
+            .iter()
+            .flatten()
+            .map(|b| b.get_array_memory_size())
+            .sum()
+    }
+}
+
+/// The key of the result of a query in a [`ResultCache`]: a hash of its
+/// optimized logical plan, along with the display of the plan, which is
+/// compared on lookup so that plans of the same hash never share a result
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResultKey {
+    hash: u64,
+    plan: String,
+}
+
+impl ResultKey {
+    /// Create the key of the plan of hash `hash` and display `plan`
+    pub fn new(hash: u64, plan: impl Into<String>) -> Self {
+        Self {
+            hash,
+            plan: plan.into(),
+        }
+    }
+
+    /// Returns the hash of the plan
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Returns the display of the plan
+    pub fn plan(&self) -> &str {
+        &self.plan
+    }
+}
+
+/// A cache of the results of queries, opted into by registering it in a
+/// session, see `SessionContext::with_result_cache`.
+///
+/// Results are keyed by the optimized logical plan of the query, see
+/// [`ResultKey`], and stored along with the snapshot tokens of the tables scanned by the
+/// query, see `TableProvider::snapshot_token`. A result is only returned
+/// while these tokens are unchanged, and for at most `ttl` after it was
+/// stored: stale results are removed when looked up.
+///
+/// The memory used by the stored results is accounted in a memory pool, and
+/// is at most `max_size` bytes. The least recently used results are evicted
+/// to make room for new ones, or when the memory pool is exhausted.
+pub struct ResultCache {
+    max_size: usize,
+    ttl: Duration,
+    state: Mutex<ResultCacheState>,
+}
+
+struct ResultCacheState {
+    entries: HashMap<ResultKey, ResultCacheEntry>,
+    /// Accounts for the memory used by the stored results
+    reservation: MemoryReservation,
+    /// Incremented on each access, to find the least recently used entry
+    clock: u64,
+    hits: usize,
+    misses: usize,
+}
+
+struct ResultCacheEntry {
+    snapshot: Vec<String>,
+    result: Arc<CachedResult>,
+    size: usize,
+    inserted_at: Instant,
+    last_used: u64,
+}
+
+impl ResultCache {
+    /// Create a cache storing at most `max_size` bytes of results, each for
+    /// at most `ttl`, in `memory_pool`
+    pub fn new(
+        max_size: usize,
+        ttl: Duration,
+        memory_pool: &Arc<dyn MemoryPool>,
+    ) -> Self {
+        let reservation = MemoryConsumer::new("ResultCache").register(memory_pool);
+        Self {
+            max_size,
+            ttl,
+            state: Mutex::new(ResultCacheState {
+                entries: HashMap::new(),
+                reservation,
+                clock: 0,
+                hits: 0,
+                misses: 0,
+            }),
+        }
+    }
+
+    /// Returns the maximum memory used by the stored results, in bytes
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Returns the time for which a result is kept
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the result stored for `key`, if it was stored with the same
+    /// `snapshot` tokens less than `ttl` ago
+    pub fn get(&self, key: &ResultKey, snapshot: &[String]) -> Option<Arc<CachedResult>> {
+        let mut state = self.state.lock();
+        state.clock += 1;
+        let clock = state.clock;
+        let fresh = match state.entries.get_mut(key) {
+            Some(entry)
+                if entry.snapshot == snapshot
+                    && entry.inserted_at.elapsed() < self.ttl =>
+            {
+                entry.last_used = clock;
+                Some(Arc::clone(&entry.result))
+            }
+            Some(_) => {
+                state.remove(key);
+                None
+            }
+            None => None,
+        };
+        match fresh {
+            Some(_) => state.hits += 1,
+            None => state.misses += 1,
+        }
+        fresh
+    }
+
+    /// Store the result of `key`, computed from the tables with the
+    /// `snapshot` tokens, returning whether the result was stored.
+    ///
+    /// Results larger than `max_size`, or for which no memory could be
+    /// reserved after evicting every other result, are not stored.
+    pub fn put(
+        &self,
+        key: ResultKey,
+        snapshot: Vec<String>,
+        result: CachedResult,
+    ) -> bool {
+        let size = result.size();
+        if size > self.max_size {
+            return false;
+        }
+        let mut state = self.state.lock();
+        state.remove(&key);
+        loop {
+            if state.reservation.size() + size <= self.max_size
+                && state.reservation.try_grow(size).is_ok()
+            {
+                break;
+            }
+            if !state.evict_least_recently_used() {
+                return false;
+            }
+        }
+        state.clock += 1;
+        let entry = ResultCacheEntry {
+            snapshot,
+            result: Arc::new(result),
+            size,
+            inserted_at: Instant::now(),
+            last_used: state.clock,
+        };
+        state.entries.insert(key, entry);
+        true
+    }
+
+    /// Remove every stored result
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        state.entries.clear();
+        state.reservation.free();
+    }
+
+    /// Returns the number of stored results
+    pub fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    /// Returns true if no result is stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the memory used by the stored results, in bytes
+    pub fn size(&self) -> usize {
+        self.state.lock().reservation.size()
+    }
+
+    /// Returns the number of lookups that returned a result
+    pub fn hits(&self) -> usize {
+        self.state.lock().hits
+    }
+
+    /// Returns the number of lookups that returned no result
+    pub fn misses(&self) -> usize {
+        self.state.lock().misses
+    }
+}
+
+impl ResultCacheState {
+    fn remove(&mut self, key: &ResultKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.reservation.shrink(entry.size);
+        }
+    }
+
+    /// Evicts the least recently used result, returning false if there is
+    /// none
+    fn evict_least_recently_used(&mut self) -> bool {
+        let key = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone());
+        match key {
+            Some(key) => {
+                self.remove(&key);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Debug for ResultCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let state = self.state.lock();
+        f.debug_struct("ResultCache")
+            .field("max_size", &self.max_size)
+            .field("ttl", &self.ttl)
+            .field("entries", &state.entries.len())
+            .field("size", &state.reservation.size())
+            .field("hits", &state.hits)
+            .field("misses", &state.misses)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_pool::GreedyMemoryPool;
+
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn result(num_rows: i32) -> CachedResult {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(Int32Array::from_iter_values(0..num_rows))],
+        )
+        .unwrap();
+        CachedResult::new(schema, vec![vec![batch]])
+    }
+
+    fn key(hash: u64) -> ResultKey {
+        ResultKey::new(hash, format!("plan {hash}"))
+    }
+
+    fn snapshot(token: &str) -> Vec<String> {
+        vec![token.to_string()]
+    }
+
+    #[test]
+    fn test_get_checks_snapshot_and_ttl() {
+        let pool: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(1 << 20));
+        let cache = ResultCache::new(1 << 20, Duration::from_secs(3600), &pool);
+        assert!(cache.put(key(1), snapshot("v1"), result(10)));
+        assert_eq!(cache.get(&key(1), &snapshot("v1")).unwrap().num_rows(), 10);
+        assert!(cache.get(&key(2), &snapshot("v1")).is_none());
+        assert_eq!(pool.reserved(), cache.size());
+
+        // A result of an older snapshot is removed
+        assert!(cache.get(&key(1), &snapshot("v2")).is_none());
+        assert!(cache.is_empty());
+        assert_eq!(pool.reserved(), 0);
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+
+        // Plans of the same hash do not share a result
+        assert!(cache.put(key(1), snapshot("v1"), result(10)));
+        assert!(cache
+            .get(&ResultKey::new(1, "other plan"), &snapshot("v1"))
+            .is_none());
+        assert!(cache.get(&key(1), &snapshot("v1")).is_some());
+
+        let cache = ResultCache::new(1 << 20, Duration::ZERO, &pool);
+        assert!(cache.put(key(1), snapshot("v1"), result(10)));
+        assert!(cache.get(&key(1), &snapshot("v1")).is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let size = result(1000).size();
+        let pool: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(1 << 20));
+        let cache = ResultCache::new(2 * size, Duration::from_secs(3600), &pool);
+        assert!(cache.put(key(1), snapshot("v1"), result(1000)));
+        assert!(cache.put(key(2), snapshot("v1"), result(1000)));
+        assert!(cache.get(&key(1), &snapshot("v1")).is_some());
+        assert!(cache.put(key(3), snapshot("v1"), result(1000)));
+        assert!(cache.get(&key(1), &snapshot("v1")).is_some());
+        assert!(cache.get(&key(2), &snapshot("v1")).is_none());
+        assert!(cache.get(&key(3), &snapshot("v1")).is_some());
+        assert_eq!(cache.size(), 2 * size);
+
+        // Results larger than the cache are not stored
+        assert!(!cache.put(key(4), snapshot("v1"), result(10_000)));
+        assert_eq!(cache.len(), 2);
+        cache.clear();
+        assert_eq!(pool.reserved(), 0);
+    }
+
+    #[test]
+    fn test_respects_memory_pool() {
+        let size = result(1000).size();
+        let pool: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(size + size / 2));
+        let cache = ResultCache::new(1 << 20, Duration::from_secs(3600), &pool);
+        assert!(cache.put(key(1), snapshot("v1"), result(1000)));
+        // The pool has no room for both results
+        assert!(cache.put(key(2), snapshot("v1"), result(1000)));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(&key(2), &snapshot("v1")).is_some());
+
+        let mut other = MemoryConsumer::new("other").register(&pool);
+        other.grow(size);
+        assert!(!cache.put(key(3), snapshot("v1"), result(1000)));
+        assert!(cache.is_empty());
+    }
+}
@@ -23,7 +23,7 @@ use std::{
 };
 
 use datafusion_common::{
-    config::{ConfigExtension, ConfigOptions, SpillCompression},
+    config::{ConfigExtension, ConfigOptions, QueryMemoryPolicy, SpillCompression},
     Result, ScalarValue,
 };
 
@@ -304,6 +304,15 @@ impl SessionConfig {
         self
     }
 
+    /// Sets the minimum estimated number of rows of an input above which
+    /// round robin repartitioning is added, see [repartition_threshold_rows]
+    ///
+    /// [repartition_threshold_rows]: datafusion_common::config::OptimizerOptions::repartition_threshold_rows
+    pub fn with_repartition_threshold_rows(mut self, rows: usize) -> Self {
+        self.options.optimizer.repartition_threshold_rows = rows;
+        self
+    }
+
     /// Enables or disables the allowing unordered symmetric hash join
     pub fn with_allow_symmetric_joins_without_pruning(mut self, enabled: bool) -> Self {
         self.options.optimizer.allow_symmetric_joins_without_pruning = enabled;
@@ -434,6 +443,27 @@ impl SessionConfig {
         self
     }
 
+    /// Limit the memory each query of the session may reserve to
+    /// [`per_query_memory_limit`] bytes
+    ///
+    /// [`per_query_memory_limit`]: datafusion_common::config::ExecutionOptions::per_query_memory_limit
+    pub fn with_query_memory_limit(mut self, per_query_memory_limit: usize) -> Self {
+        self.options.execution.per_query_memory_limit = Some(per_query_memory_limit);
+        self
+    }
+
+    /// Set the [`per_query_memory_policy`] sharing the memory of a query
+    /// among its operators
+    ///
+    /// [`per_query_memory_policy`]: datafusion_common::config::ExecutionOptions::per_query_memory_policy
+    pub fn with_query_memory_policy(
+        mut self,
+        per_query_memory_policy: QueryMemoryPolicy,
+    ) -> Self {
+        self.options.execution.per_query_memory_policy = per_query_memory_policy;
+        self
+    }
+
     /// Set the size of [`sort_in_place_threshold_bytes`] to control
     /// how sort does things.
     ///
@@ -447,6 +477,15 @@ impl SessionConfig {
         self
     }
 
+    /// Set the maximum number of spill files merged at once by a sort
+    /// [`sort_merge_fan_in`]
+    ///
+    /// [`sort_merge_fan_in`]: datafusion_common::config::ExecutionOptions::sort_merge_fan_in
+    pub fn with_sort_merge_fan_in(mut self, sort_merge_fan_in: usize) -> Self {
+        self.options.execution.sort_merge_fan_in = sort_merge_fan_in;
+        self
+    }
+
     /// Enables or disables the enforcement of batch size in joins
     pub fn with_enforce_batch_size_in_joins(
         mut self,
@@ -461,6 +500,13 @@ impl SessionConfig {
         self.options.execution.enforce_batch_size_in_joins
     }
 
+    /// Set the maximum number of batches buffered for each output partition
+    /// of a `RepartitionExec`, `0` meaning no limit
+    pub fn with_repartition_max_buffered_batches(mut self, batches: usize) -> Self {
+        self.options.execution.repartition_max_buffered_batches = batches;
+        self
+    }
+
     /// Convert configuration options to name-value pairs with values
     /// converted to strings.
     ///
@@ -25,7 +25,7 @@ use parking_lot::Mutex;
 use rand::{rng, Rng};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use tempfile::{Builder, NamedTempFile, TempDir};
 
 use crate::memory_pool::human_readable_size;
@@ -40,6 +40,9 @@ pub struct DiskManagerBuilder {
     /// The maximum amount of data (in bytes) stored inside the temporary directories.
     /// Default to 100GB
     max_temp_directory_size: u64,
+    /// The maximum amount of data (in bytes) each query may store inside the
+    /// temporary directories. Default to no limit
+    max_temp_directory_size_per_query: Option<u64>,
 }
 
 impl Default for DiskManagerBuilder {
@@ -47,6 +50,7 @@ impl Default for DiskManagerBuilder {
         Self {
             mode: DiskManagerMode::OsTmpDirectory,
             max_temp_directory_size: DEFAULT_MAX_TEMP_DIRECTORY_SIZE,
+            max_temp_directory_size_per_query: None,
         }
     }
 }
@@ -70,31 +74,37 @@ impl DiskManagerBuilder {
         self
     }
 
+    /// Limit the data each query stores inside the temporary directories to
+    /// `value` bytes, in addition to the limit of all queries set by
+    /// [`Self::set_max_temp_directory_size`]. `None` removes the limit.
+    ///
+    /// Queries are scoped by [`DiskManager::for_query`].
+    pub fn set_max_temp_directory_size_per_query(&mut self, value: Option<u64>) {
+        self.max_temp_directory_size_per_query = value;
+    }
+
+    pub fn with_max_temp_directory_size_per_query(mut self, value: u64) -> Self {
+        self.set_max_temp_directory_size_per_query(Some(value));
+        self
+    }
+
     /// Create a DiskManager given the builder
     pub fn build(self) -> Result<DiskManager> {
-        match self.mode {
-            DiskManagerMode::OsTmpDirectory => Ok(DiskManager {
-                local_dirs: Mutex::new(Some(vec![])),
-                max_temp_directory_size: self.max_temp_directory_size,
-                used_disk_space: Arc::new(AtomicU64::new(0)),
-            }),
+        let local_dirs = match self.mode {
+            DiskManagerMode::OsTmpDirectory => Some(vec![]),
             DiskManagerMode::Directories(conf_dirs) => {
                 let local_dirs = create_local_dirs(conf_dirs)?;
                 debug!(
                     "Created local dirs {local_dirs:?} as DataFusion working directory"
                 );
-                Ok(DiskManager {
-                    local_dirs: Mutex::new(Some(local_dirs)),
-                    max_temp_directory_size: self.max_temp_directory_size,
-                    used_disk_space: Arc::new(AtomicU64::new(0)),
-                })
+                Some(local_dirs)
             }
-            DiskManagerMode::Disabled => Ok(DiskManager {
-                local_dirs: Mutex::new(None),
-                max_temp_directory_size: self.max_temp_directory_size,
-                used_disk_space: Arc::new(AtomicU64::new(0)),
-            }),
-        }
+            DiskManagerMode::Disabled => None,
+        };
+        let mut disk_manager = DiskManager::new(local_dirs, self.max_temp_directory_size);
+        disk_manager.max_temp_directory_size_per_query =
+            self.max_temp_directory_size_per_query;
+        Ok(disk_manager)
     }
 }
 
@@ -171,13 +181,70 @@ pub struct DiskManager {
     ///
     /// If `Some(vec![])` a new OS specified temporary directory will be created
     /// If `None` an error will be returned (configured not to spill)
-    local_dirs: Mutex<Option<Vec<Arc<TempDir>>>>,
+    ///
+    /// Shared with the disk managers of queries, see [`Self::for_query`]
+    local_dirs: Arc<Mutex<Option<Vec<Arc<TempDir>>>>>,
     /// The maximum amount of data (in bytes) stored inside the temporary directories.
     /// Default to 100GB
     max_temp_directory_size: u64,
     /// Used disk space in the temporary directories. Now only spilled data for
     /// external executors are counted.
     used_disk_space: Arc<AtomicU64>,
+    /// The maximum amount of data (in bytes) each query may store inside the
+    /// temporary directories, unlimited if `None`
+    max_temp_directory_size_per_query: Option<u64>,
+    /// The queries scoped by [`Self::for_query`], shared by the disk managers
+    /// of these queries
+    queries: Arc<Mutex<QueryRegistry>>,
+    /// The query whose temporary files this disk manager creates, if it was
+    /// returned by [`Self::for_query`]
+    query: Option<Arc<QueryDiskSpace>>,
+}
+
+/// The queries using the temporary directories of a [`DiskManager`]
+#[derive(Debug, Default)]
+struct QueryRegistry {
+    next_query_id: u64,
+    /// Released when the disk manager of the query and all its temporary
+    /// files are dropped
+    queries: Vec<Weak<QueryDiskSpace>>,
+}
+
+/// The disk space used by the temporary files of a query
+#[derive(Debug)]
+struct QueryDiskSpace {
+    query_id: u64,
+    session_id: String,
+    task_id: Option<String>,
+    used_disk_space: AtomicU64,
+}
+
+/// The disk space used by the temporary files of a [`DiskManager`], see
+/// [`DiskManager::disk_usage`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskUsage {
+    /// Bytes used by the temporary files of all queries
+    pub used: u64,
+    /// The maximum of `used`
+    pub limit: u64,
+    /// The bytes used by each query that is running or still holds
+    /// temporary files, ordered by query id
+    pub queries: Vec<QueryDiskUsage>,
+}
+
+/// The disk space used by the temporary files of a query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryDiskUsage {
+    /// Identifies the query among the queries of the [`DiskManager`]
+    pub query_id: u64,
+    /// The session running the query
+    pub session_id: String,
+    /// The task id of the query, if any
+    pub task_id: Option<String>,
+    /// Bytes used by the temporary files of the query
+    pub used: u64,
+    /// The maximum of `used`, if any
+    pub limit: Option<u64>,
 }
 
 impl DiskManager {
@@ -186,33 +253,40 @@ impl DiskManager {
         DiskManagerBuilder::default()
     }
 
+    fn new(local_dirs: Option<Vec<Arc<TempDir>>>, max_temp_directory_size: u64) -> Self {
+        Self {
+            local_dirs: Arc::new(Mutex::new(local_dirs)),
+            max_temp_directory_size,
+            used_disk_space: Arc::new(AtomicU64::new(0)),
+            max_temp_directory_size_per_query: None,
+            queries: Default::default(),
+            query: None,
+        }
+    }
+
     /// Create a DiskManager given the configuration
     #[allow(deprecated)]
     #[deprecated(since = "48.0.0", note = "Use DiskManager::builder() instead")]
     pub fn try_new(config: DiskManagerConfig) -> Result<Arc<Self>> {
         match config {
             DiskManagerConfig::Existing(manager) => Ok(manager),
-            DiskManagerConfig::NewOs => Ok(Arc::new(Self {
-                local_dirs: Mutex::new(Some(vec![])),
-                max_temp_directory_size: DEFAULT_MAX_TEMP_DIRECTORY_SIZE,
-                used_disk_space: Arc::new(AtomicU64::new(0)),
-            })),
+            DiskManagerConfig::NewOs => Ok(Arc::new(Self::new(
+                Some(vec![]),
+                DEFAULT_MAX_TEMP_DIRECTORY_SIZE,
+            ))),
             DiskManagerConfig::NewSpecified(conf_dirs) => {
                 let local_dirs = create_local_dirs(conf_dirs)?;
                 debug!(
                     "Created local dirs {local_dirs:?} as DataFusion working directory"
                 );
-                Ok(Arc::new(Self {
-                    local_dirs: Mutex::new(Some(local_dirs)),
-                    max_temp_directory_size: DEFAULT_MAX_TEMP_DIRECTORY_SIZE,
-                    used_disk_space: Arc::new(AtomicU64::new(0)),
-                }))
+                Ok(Arc::new(Self::new(
+                    Some(local_dirs),
+                    DEFAULT_MAX_TEMP_DIRECTORY_SIZE,
+                )))
+            }
+            DiskManagerConfig::Disabled => {
+                Ok(Arc::new(Self::new(None, DEFAULT_MAX_TEMP_DIRECTORY_SIZE)))
             }
-            DiskManagerConfig::Disabled => Ok(Arc::new(Self {
-                local_dirs: Mutex::new(None),
-                max_temp_directory_size: DEFAULT_MAX_TEMP_DIRECTORY_SIZE,
-                used_disk_space: Arc::new(AtomicU64::new(0)),
-            })),
         }
     }
 
@@ -256,6 +330,59 @@ impl DiskManager {
         self.used_disk_space.load(Ordering::Relaxed)
     }
 
+    /// Returns a disk manager creating the temporary files of a query of
+    /// session `session_id` in the directories of this disk manager.
+    ///
+    /// The files count towards the limit of all queries, and towards the
+    /// per query limit set by
+    /// [`DiskManagerBuilder::with_max_temp_directory_size_per_query`], and
+    /// are reported by [`Self::disk_usage`] until they are dropped.
+    pub fn for_query(&self, session_id: &str, task_id: Option<&str>) -> Arc<Self> {
+        let mut registry = self.queries.lock();
+        let query = Arc::new(QueryDiskSpace {
+            query_id: registry.next_query_id,
+            session_id: session_id.to_string(),
+            task_id: task_id.map(str::to_string),
+            used_disk_space: AtomicU64::new(0),
+        });
+        registry.next_query_id += 1;
+        registry.queries.retain(|query| query.strong_count() > 0);
+        registry.queries.push(Arc::downgrade(&query));
+
+        Arc::new(Self {
+            local_dirs: Arc::clone(&self.local_dirs),
+            max_temp_directory_size: self.max_temp_directory_size,
+            used_disk_space: Arc::clone(&self.used_disk_space),
+            max_temp_directory_size_per_query: self.max_temp_directory_size_per_query,
+            queries: Arc::clone(&self.queries),
+            query: Some(query),
+        })
+    }
+
+    /// Returns the disk space used by the temporary files of all queries,
+    /// and of each query scoped by [`Self::for_query`]
+    pub fn disk_usage(&self) -> DiskUsage {
+        let queries = self
+            .queries
+            .lock()
+            .queries
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|query| QueryDiskUsage {
+                query_id: query.query_id,
+                session_id: query.session_id.clone(),
+                task_id: query.task_id.clone(),
+                used: query.used_disk_space.load(Ordering::Relaxed),
+                limit: self.max_temp_directory_size_per_query,
+            })
+            .collect();
+        DiskUsage {
+            used: self.used_disk_space(),
+            limit: self.max_temp_directory_size,
+            queries,
+        }
+    }
+
     /// Return true if this disk manager supports creating temporary
     /// files. If this returns false, any call to `create_tmp_file`
     /// will error.
@@ -299,6 +426,7 @@ impl DiskManager {
                 .map_err(DataFusionError::IoError)?,
             current_file_disk_usage: 0,
             disk_manager: Arc::clone(self),
+            request_description: request_description.to_string(),
         })
     }
 }
@@ -322,6 +450,8 @@ pub struct RefCountedTempFile {
     current_file_disk_usage: u64,
     /// The disk manager that created and manages this temporary file
     disk_manager: Arc<DiskManager>,
+    /// Describes the operator that created this file in quota errors
+    request_description: String,
 }
 
 impl RefCountedTempFile {
@@ -333,36 +463,61 @@ impl RefCountedTempFile {
         &self.tempfile
     }
 
-    /// Updates the global disk usage counter after modifications to the underlying file.
+    /// Updates the global disk usage counter, and the counter of the query
+    /// of the file if any, after modifications to the underlying file.
     ///
     /// # Errors
-    /// - Returns an error if the global disk usage exceeds the configured limit.
+    /// - Returns an error if the global disk usage, or the disk usage of the
+    ///   query, exceeds the configured limit.
     pub fn update_disk_usage(&mut self) -> Result<()> {
         // Get new file size from OS
         let metadata = self.tempfile.as_file().metadata()?;
         let new_disk_usage = metadata.len();
 
-        // Update the global disk usage by:
-        // 1. Subtracting the old file size from the global counter
-        self.disk_manager
-            .used_disk_space
-            .fetch_sub(self.current_file_disk_usage, Ordering::Relaxed);
-        // 2. Adding the new file size to the global counter
-        self.disk_manager
-            .used_disk_space
-            .fetch_add(new_disk_usage, Ordering::Relaxed);
+        // 1. Replace the old file size with the new one in the counters. The
+        // local file size tracking is updated even if a limit is exceeded, so
+        // that dropping the file releases all its usage.
+        let disk_manager = &self.disk_manager;
+        let old_disk_usage = self.current_file_disk_usage;
+        self.current_file_disk_usage = new_disk_usage;
+        let used_disk_space = &disk_manager.used_disk_space;
+        used_disk_space.fetch_sub(old_disk_usage, Ordering::Relaxed);
+        used_disk_space.fetch_add(new_disk_usage, Ordering::Relaxed);
+        if let Some(query) = &disk_manager.query {
+            query
+                .used_disk_space
+                .fetch_sub(old_disk_usage, Ordering::Relaxed);
+            query
+                .used_disk_space
+                .fetch_add(new_disk_usage, Ordering::Relaxed);
+        }
 
-        // 3. Check if the updated global disk usage exceeds the configured limit
-        let global_disk_usage = self.disk_manager.used_disk_space.load(Ordering::Relaxed);
-        if global_disk_usage > self.disk_manager.max_temp_directory_size {
+        // 2. Check if the updated global disk usage exceeds the configured limit
+        let global_disk_usage = disk_manager.used_disk_space.load(Ordering::Relaxed);
+        if global_disk_usage > disk_manager.max_temp_directory_size {
             return resources_err!(
-                "The used disk space during the spilling process has exceeded the allowable limit of {}. Try increasing the `max_temp_directory_size` in the disk manager configuration.",
-                human_readable_size(self.disk_manager.max_temp_directory_size as usize)
+                "Disk quota exceeded while {}: the temporary files of all queries use {}, more than the allowable limit of {}. Try increasing the `max_temp_directory_size` in the disk manager configuration.",
+                self.request_description,
+                human_readable_size(global_disk_usage as usize),
+                human_readable_size(disk_manager.max_temp_directory_size as usize)
             );
         }
 
-        // 4. Update the local file size tracking
-        self.current_file_disk_usage = new_disk_usage;
+        // 3. Check if the updated disk usage of the query exceeds its limit
+        if let (Some(query), Some(limit)) = (
+            &disk_manager.query,
+            disk_manager.max_temp_directory_size_per_query,
+        ) {
+            let query_disk_usage = query.used_disk_space.load(Ordering::Relaxed);
+            if query_disk_usage > limit {
+                return resources_err!(
+                    "Disk quota exceeded while {}: the temporary files of the query use {}, more than the allowable limit of {} per query. Try increasing the `max_temp_directory_size_per_query` in the disk manager configuration.",
+                    self.request_description,
+                    human_readable_size(query_disk_usage as usize),
+                    human_readable_size(limit as usize)
+                );
+            }
+        }
 
         Ok(())
     }
@@ -375,10 +530,16 @@ impl RefCountedTempFile {
 /// When the temporary file is dropped, subtract its disk usage from the disk manager's total
 impl Drop for RefCountedTempFile {
     fn drop(&mut self) {
-        // Subtract the current file's disk usage from the global counter
+        // Subtract the current file's disk usage from the global counter and
+        // the counter of its query
         self.disk_manager
             .used_disk_space
             .fetch_sub(self.current_file_disk_usage, Ordering::Relaxed);
+        if let Some(query) = &self.disk_manager.query {
+            query
+                .used_disk_space
+                .fetch_sub(self.current_file_disk_usage, Ordering::Relaxed);
+        }
     }
 }
 
@@ -495,6 +656,69 @@ mod tests {
         assert!(found, "Can't find {file_path:?} in dirs: {dirs:?}");
     }
 
+    #[test]
+    fn test_per_query_disk_usage() -> Result<()> {
+        use std::io::Write;
+
+        let dm = Arc::new(
+            DiskManagerBuilder::default()
+                .with_max_temp_directory_size(1000)
+                .with_max_temp_directory_size_per_query(100)
+                .build()?,
+        );
+        let query1 = dm.for_query("session", None);
+        let query2 = dm.for_query("session", Some("task"));
+
+        let mut file1 = query1.create_tmp_file("Sorting")?;
+        file1.inner().as_file().write_all(&[0; 60])?;
+        file1.update_disk_usage()?;
+        let mut file2 = query2.create_tmp_file("Testing")?;
+        file2.inner().as_file().write_all(&[0; 30])?;
+        file2.update_disk_usage()?;
+
+        let usage = dm.disk_usage();
+        assert_eq!(usage.used, 90);
+        assert_eq!(usage.limit, 1000);
+        let queries = usage
+            .queries
+            .iter()
+            .map(|q| (q.query_id, q.task_id.as_deref(), q.used, q.limit))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            queries,
+            vec![(0, None, 60, Some(100)), (1, Some("task"), 30, Some(100))]
+        );
+
+        // The second query is within its limit, the first one is not
+        file2.inner().as_file().write_all(&[0; 50])?;
+        file2.update_disk_usage()?;
+        file1.inner().as_file().write_all(&[0; 50])?;
+        assert_eq!(
+            file1.update_disk_usage().unwrap_err().strip_backtrace(),
+            "Resources exhausted: Disk quota exceeded while Sorting: the temporary files of the query use 110.0 B, more than the allowable limit of 100.0 B per query. Try increasing the `max_temp_directory_size_per_query` in the disk manager configuration."
+        );
+        assert_eq!(dm.used_disk_space(), 190);
+
+        // Dropping the files and the disk managers of the queries releases
+        // their usage, even after a limit was exceeded
+        drop(file1);
+        drop(file2);
+        assert_eq!(dm.used_disk_space(), 0);
+        assert_eq!(dm.disk_usage().queries.len(), 2);
+        drop(query1);
+        drop(query2);
+        assert_eq!(
+            dm.disk_usage(),
+            DiskUsage {
+                used: 0,
+                limit: 1000,
+                queries: vec![]
+            }
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_temp_file_still_alive_after_disk_manager_dropped() -> Result<()> {
         // Test for the case using OS arranged temporary directory
@@ -31,6 +31,7 @@ pub mod config;
 pub mod disk_manager;
 pub mod memory_pool;
 pub mod object_store;
+pub mod progress;
 pub mod runtime_env;
 mod stream;
 mod task;
@@ -18,6 +18,7 @@
 use crate::memory_pool::{
     human_readable_size, MemoryConsumer, MemoryLimit, MemoryPool, MemoryReservation,
 };
+use datafusion_common::config::QueryMemoryPolicy;
 use datafusion_common::HashMap;
 use datafusion_common::{resources_datafusion_err, DataFusionError, Result};
 use log::debug;
@@ -25,6 +26,7 @@ use parking_lot::Mutex;
 use std::{
     num::NonZeroUsize,
     sync::atomic::{AtomicUsize, Ordering},
+    sync::Arc,
 };
 
 /// A [`MemoryPool`] that enforces no limit
@@ -264,6 +266,116 @@ fn insufficient_capacity_err(
     human_readable_size(additional), reservation.registration.consumer.name, human_readable_size(reservation.size), human_readable_size(available))
 }
 
+/// A [`MemoryPool`] for the reservations of a single query, carved from a
+/// `parent` pool that is shared with other queries.
+///
+/// Reservations are first limited by the query's own limit, according to a
+/// [`QueryMemoryPolicy`], and then must also fit in the `parent` pool. A query
+/// that exceeds its limit therefore spills or fails on its own, without
+/// exhausting the memory available to the other queries of the `parent`.
+///
+/// The pool also records the peak memory reserved by the query, which can be
+/// retrieved with [`Self::peak`] after the query has executed.
+#[derive(Debug)]
+pub struct QueryMemoryPool {
+    /// The pool shared with the other queries
+    parent: Arc<dyn MemoryPool>,
+    /// The pool enforcing the limit of this query
+    local: Box<dyn MemoryPool>,
+    /// The memory limit of this query
+    limit: usize,
+    /// The maximum memory reserved by this query so far
+    peak: AtomicUsize,
+}
+
+impl QueryMemoryPool {
+    /// Create a new pool that allows the reservations of a query to use up
+    /// to `limit` bytes of the `parent` pool, shared among the operators of
+    /// the query according to `policy`
+    pub fn new(
+        parent: Arc<dyn MemoryPool>,
+        limit: usize,
+        policy: QueryMemoryPolicy,
+    ) -> Self {
+        let local: Box<dyn MemoryPool> = match policy {
+            QueryMemoryPolicy::Greedy => Box::new(GreedyMemoryPool::new(limit)),
+            QueryMemoryPolicy::FairSpill => Box::new(FairSpillPool::new(limit)),
+        };
+        Self {
+            parent,
+            local,
+            limit,
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    /// Return the maximum amount of memory reserved by the query so far
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    fn update_peak(&self) {
+        self.peak
+            .fetch_max(self.local.reserved(), Ordering::Relaxed);
+    }
+}
+
+impl MemoryPool for QueryMemoryPool {
+    fn register(&self, consumer: &MemoryConsumer) {
+        self.local.register(consumer);
+        self.parent.register(consumer);
+    }
+
+    fn unregister(&self, consumer: &MemoryConsumer) {
+        self.local.unregister(consumer);
+        self.parent.unregister(consumer);
+    }
+
+    fn grow(&self, reservation: &MemoryReservation, additional: usize) {
+        self.local.grow(reservation, additional);
+        self.parent.grow(reservation, additional);
+        self.update_peak();
+    }
+
+    fn shrink(&self, reservation: &MemoryReservation, shrink: usize) {
+        self.local.shrink(reservation, shrink);
+        self.parent.shrink(reservation, shrink);
+    }
+
+    fn try_grow(&self, reservation: &MemoryReservation, additional: usize) -> Result<()> {
+        self.local
+            .try_grow(reservation, additional)
+            .map_err(|e| match e {
+                DataFusionError::ResourcesExhausted(e) => {
+                    DataFusionError::ResourcesExhausted(format!(
+                        "Query memory limit of {} exceeded: {e}",
+                        human_readable_size(self.limit)
+                    ))
+                }
+                _ => e,
+            })?;
+        if let Err(e) = self.parent.try_grow(reservation, additional) {
+            self.local.shrink(reservation, additional);
+            return Err(e);
+        }
+        self.update_peak();
+        Ok(())
+    }
+
+    fn reserved(&self) -> usize {
+        self.local.reserved()
+    }
+
+    fn memory_limit(&self) -> MemoryLimit {
+        match self.parent.memory_limit() {
+            MemoryLimit::Finite(parent_limit) => {
+                MemoryLimit::Finite(parent_limit.min(self.limit))
+            }
+            _ => MemoryLimit::Finite(self.limit),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct TrackedConsumer {
     name: String,
@@ -743,4 +855,50 @@ mod tests {
         r1#[ID](can spill: false) consumed 20.0 B.
         ");
     }
+
+    #[test]
+    fn test_query_pool() {
+        let parent: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(100));
+        let query1 = Arc::new(QueryMemoryPool::new(
+            Arc::clone(&parent),
+            30,
+            QueryMemoryPolicy::Greedy,
+        ));
+        let query2 = Arc::new(QueryMemoryPool::new(
+            Arc::clone(&parent),
+            80,
+            QueryMemoryPolicy::Greedy,
+        ));
+        let pool1: Arc<dyn MemoryPool> = Arc::clone(&query1) as _;
+        let pool2: Arc<dyn MemoryPool> = Arc::clone(&query2) as _;
+
+        let mut r1 = MemoryConsumer::new("r1").register(&pool1);
+        r1.try_grow(20).unwrap();
+        assert_eq!(pool1.reserved(), 20);
+        assert_eq!(parent.reserved(), 20);
+
+        // query 1 exceeds its own limit, although the parent has memory left
+        let err = r1.try_grow(20).unwrap_err().strip_backtrace();
+        assert_snapshot!(err, @"Resources exhausted: Query memory limit of 30.0 B exceeded: Failed to allocate additional 20.0 B for r1 with 20.0 B already allocated for this reservation - 10.0 B remain available for the total pool");
+        assert_eq!(parent.reserved(), 20);
+
+        // query 2 is limited by its own limit and by the memory left in the parent
+        let mut other = MemoryConsumer::new("other").register(&parent);
+        other.grow(10);
+        let mut r2 = MemoryConsumer::new("r2").register(&pool2);
+        let err = r2.try_grow(81).unwrap_err().strip_backtrace();
+        assert_snapshot!(err, @"Resources exhausted: Query memory limit of 80.0 B exceeded: Failed to allocate additional 81.0 B for r2 with 0.0 B already allocated for this reservation - 80.0 B remain available for the total pool");
+        let err = r2.try_grow(80).unwrap_err().strip_backtrace();
+        assert_snapshot!(err, @"Resources exhausted: Failed to allocate additional 80.0 B for r2 with 0.0 B already allocated for this reservation - 70.0 B remain available for the total pool");
+        // the failed reservation is not retained by the query pool
+        assert_eq!(pool2.reserved(), 0);
+        r2.try_grow(70).unwrap();
+        assert_eq!(parent.reserved(), 100);
+
+        r1.free();
+        r2.shrink(60);
+        assert_eq!(parent.reserved(), 20);
+        assert_eq!(query1.peak(), 20);
+        assert_eq!(query2.peak(), 70);
+    }
 }
@@ -18,13 +18,28 @@
 //! ObjectStoreRegistry holds all the object stores at Runtime with a scheme for each store.
 //! This allows the user to extend DataFusion with different storage systems such as S3 or HDFS
 //! and query data inside these systems.
+//!
+//! It also provides [`RateLimitedObjectStore`], which caps the rate of requests
+//! issued against a store.
 
+use async_trait::async_trait;
+use bytes::Bytes;
 use dashmap::DashMap;
 use datafusion_common::{exec_err, DataFusionError, Result};
+use futures::stream::{self, BoxStream, StreamExt};
 #[cfg(not(target_arch = "wasm32"))]
 use object_store::local::LocalFileSystem;
-use object_store::ObjectStore;
+use object_store::path::Path;
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOpts, PutOptions, PutPayload, PutResult,
+};
+use parking_lot::Mutex;
+use std::num::NonZeroU32;
+use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
 use url::Url;
 
 /// A parsed URL identifying a particular [`ObjectStore`] instance
@@ -253,6 +268,258 @@ fn get_url_key(url: &Url) -> String {
     )
 }
 
+/// Limits the rate at which object store requests are issued.
+///
+/// This is a token bucket holding a single token that refills at the
+/// configured rate: a request arriving once the token is available proceeds
+/// immediately, otherwise it waits until the next token is due. Requests are
+/// therefore spaced at least `1 / requests_per_second` apart.
+///
+/// A single limiter is typically shared by every [`RateLimitedObjectStore`]
+/// handed out by a [`RuntimeEnv`], so the cap applies across all files and
+/// partitions of every concurrently running scan.
+///
+/// [`RuntimeEnv`]: crate::runtime_env::RuntimeEnv
+#[derive(Debug)]
+pub struct RequestRateLimiter {
+    requests_per_second: NonZeroU32,
+    interval: Duration,
+    /// The instant at which the next request may be issued
+    next_permit: Mutex<Instant>,
+}
+
+impl RequestRateLimiter {
+    /// Create a limiter allowing at most `requests_per_second` requests
+    pub fn new(requests_per_second: NonZeroU32) -> Self {
+        Self {
+            requests_per_second,
+            interval: Duration::from_secs(1) / requests_per_second.get(),
+            next_permit: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Return the maximum number of requests per second
+    pub fn requests_per_second(&self) -> u32 {
+        self.requests_per_second.get()
+    }
+
+    /// Wait until the next request may be issued.
+    ///
+    /// Each call consumes a permit, even if the returned future is dropped
+    /// before it completes.
+    pub async fn acquire(&self) {
+        let permit_at = {
+            let mut next_permit = self.next_permit.lock();
+            let permit_at = (*next_permit).max(Instant::now());
+            *next_permit = permit_at + self.interval;
+            permit_at
+        };
+        if permit_at > Instant::now() {
+            tokio::time::sleep_until(permit_at).await;
+        }
+    }
+}
+
+/// An [`ObjectStore`] that acquires a permit from a [`RequestRateLimiter`]
+/// before each request it forwards to the wrapped store.
+///
+/// Listing acquires a single permit when the listing starts. Requests made
+/// through a returned [`MultipartUpload`] and by [`ObjectStore::delete_stream`]
+/// are not limited.
+#[derive(Debug)]
+pub struct RateLimitedObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    limiter: Arc<RequestRateLimiter>,
+}
+
+impl RateLimitedObjectStore {
+    /// Wrap `inner` so that its requests are limited by `limiter`
+    pub fn new(inner: Arc<dyn ObjectStore>, limiter: Arc<RequestRateLimiter>) -> Self {
+        Self { inner, limiter }
+    }
+
+    /// Return the wrapped store
+    pub fn inner(&self) -> &Arc<dyn ObjectStore> {
+        &self.inner
+    }
+
+    /// Return the limiter requests are acquired from
+    pub fn limiter(&self) -> &Arc<RequestRateLimiter> {
+        &self.limiter
+    }
+
+    fn limit_list(
+        &self,
+        list: impl FnOnce(
+                &dyn ObjectStore,
+            ) -> BoxStream<'static, object_store::Result<ObjectMeta>>
+            + Send
+            + 'static,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        let inner = Arc::clone(&self.inner);
+        let limiter = Arc::clone(&self.limiter);
+        stream::once(async move {
+            limiter.acquire().await;
+            list(inner.as_ref())
+        })
+        .flatten()
+        .boxed()
+    }
+}
+
+impl std::fmt::Display for RateLimitedObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "RateLimitedObjectStore({} req/s, {})",
+            self.limiter.requests_per_second(),
+            self.inner
+        )
+    }
+}
+
+#[async_trait]
+impl ObjectStore for RateLimitedObjectStore {
+    async fn put(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+    ) -> object_store::Result<PutResult> {
+        self.limiter.acquire().await;
+        self.inner.put(location, payload).await
+    }
+
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.limiter.acquire().await;
+        self.inner.put_opts(location, payload, opts).await
+    }
+
+    async fn put_multipart(
+        &self,
+        location: &Path,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.limiter.acquire().await;
+        self.inner.put_multipart(location).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.limiter.acquire().await;
+        self.inner.put_multipart_opts(location, opts).await
+    }
+
+    async fn get(&self, location: &Path) -> object_store::Result<GetResult> {
+        self.limiter.acquire().await;
+        self.inner.get(location).await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        self.limiter.acquire().await;
+        self.inner.get_opts(location, options).await
+    }
+
+    async fn get_range(
+        &self,
+        location: &Path,
+        range: Range<u64>,
+    ) -> object_store::Result<Bytes> {
+        self.limiter.acquire().await;
+        self.inner.get_range(location, range).await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<u64>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        self.limiter.acquire().await;
+        self.inner.get_ranges(location, ranges).await
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.limiter.acquire().await;
+        self.inner.head(location).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.limiter.acquire().await;
+        self.inner.delete(location).await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, object_store::Result<Path>>,
+    ) -> BoxStream<'a, object_store::Result<Path>> {
+        self.inner.delete_stream(locations)
+    }
+
+    fn list(
+        &self,
+        prefix: Option<&Path>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        let prefix = prefix.cloned();
+        self.limit_list(move |inner| inner.list(prefix.as_ref()))
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        let prefix = prefix.cloned();
+        let offset = offset.clone();
+        self.limit_list(move |inner| inner.list_with_offset(prefix.as_ref(), &offset))
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<ListResult> {
+        self.limiter.acquire().await;
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.limiter.acquire().await;
+        self.inner.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.limiter.acquire().await;
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        self.limiter.acquire().await;
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        self.limiter.acquire().await;
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -299,4 +566,125 @@ mod tests {
         let key = get_url_key(&url.url);
         assert_eq!(key.as_str(), "s3://host:123");
     }
+
+    /// An [`ObjectStore`] recording when each `get_range` request arrives
+    #[derive(Debug)]
+    struct RecordingStore {
+        inner: object_store::memory::InMemory,
+        requests: Mutex<Vec<Instant>>,
+    }
+
+    impl std::fmt::Display for RecordingStore {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "RecordingStore")
+        }
+    }
+
+    #[async_trait]
+    impl ObjectStore for RecordingStore {
+        async fn put_opts(
+            &self,
+            location: &Path,
+            payload: PutPayload,
+            opts: PutOptions,
+        ) -> object_store::Result<PutResult> {
+            self.inner.put_opts(location, payload, opts).await
+        }
+
+        async fn put_multipart_opts(
+            &self,
+            location: &Path,
+            opts: PutMultipartOpts,
+        ) -> object_store::Result<Box<dyn MultipartUpload>> {
+            self.inner.put_multipart_opts(location, opts).await
+        }
+
+        async fn get_opts(
+            &self,
+            location: &Path,
+            options: GetOptions,
+        ) -> object_store::Result<GetResult> {
+            self.requests.lock().push(Instant::now());
+            self.inner.get_opts(location, options).await
+        }
+
+        async fn delete(&self, location: &Path) -> object_store::Result<()> {
+            self.inner.delete(location).await
+        }
+
+        fn list(
+            &self,
+            prefix: Option<&Path>,
+        ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+            self.requests.lock().push(Instant::now());
+            self.inner.list(prefix)
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            prefix: Option<&Path>,
+        ) -> object_store::Result<ListResult> {
+            self.inner.list_with_delimiter(prefix).await
+        }
+
+        async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+            self.inner.copy(from, to).await
+        }
+
+        async fn copy_if_not_exists(
+            &self,
+            from: &Path,
+            to: &Path,
+        ) -> object_store::Result<()> {
+            self.inner.copy_if_not_exists(from, to).await
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limited_object_store() {
+        let recording = Arc::new(RecordingStore {
+            inner: object_store::memory::InMemory::new(),
+            requests: Mutex::new(vec![]),
+        });
+        let path = Path::from("file.bin");
+        recording
+            .put(&path, PutPayload::from_static(b"0123456789"))
+            .await
+            .unwrap();
+
+        let requests_per_second = 4;
+        let limiter = Arc::new(RequestRateLimiter::new(
+            NonZeroU32::new(requests_per_second).unwrap(),
+        ));
+        let store = RateLimitedObjectStore::new(
+            Arc::clone(&recording) as Arc<dyn ObjectStore>,
+            limiter,
+        );
+        assert_eq!(
+            store.to_string(),
+            "RateLimitedObjectStore(4 req/s, RecordingStore)"
+        );
+
+        // issue all requests concurrently, as the partitions of a scan would
+        let start = Instant::now();
+        let reads = (0..10u64).map(|i| store.get_range(&path, i..i + 1));
+        let listing = store.list(None).collect::<Vec<_>>();
+        let (reads, listing) = futures::join!(futures::future::join_all(reads), listing);
+        assert!(reads.into_iter().all(|r| r.is_ok()));
+        assert_eq!(listing.len(), 1);
+
+        let requests = recording.requests.lock().clone();
+        assert_eq!(requests.len(), 11);
+        let min_interval = Duration::from_secs(1) / requests_per_second;
+        for pair in requests.windows(2) {
+            assert!(pair[1] - pair[0] >= min_interval, "{requests:?}");
+        }
+        // 11 requests at 4 per second need at least 2.5 seconds
+        let elapsed = requests.last().unwrap().duration_since(start);
+        assert!(elapsed >= min_interval * 10, "{elapsed:?}");
+        assert!(
+            requests.len() as f64 - 1.0
+                <= elapsed.as_secs_f64() * requests_per_second as f64
+        );
+    }
 }
@@ -0,0 +1,192 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+//! Reports the progress of the execution of a query, see [`ProgressTracker`]
+
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+/// A callback registered with [`ProgressTracker::register_callback`]
+pub type ProgressCallback = Arc<dyn Fn(&ProgressSnapshot) + Send + Sync>;
+
+/// Tracks the progress of the execution of a query.
+///
+/// The operators of a query report their progress into the tracker of their
+/// [`TaskContext`]: scans of files register the bytes of the files they will
+/// scan, and report the bytes scanned and rows produced as they go. Users can
+/// poll [`Self::snapshot`], or register a callback invoked on each report, for
+/// example to render a progress bar.
+///
+/// All the counters of the tracker are monotonic. The progress is an estimate:
+/// the bytes of a file are reported as scanned once the file was read, and
+/// scans only register their files once they start executing.
+///
+/// Cloning a tracker returns a handle to the same counters.
+///
+/// [`TaskContext`]: crate::TaskContext
+#[derive(Clone, Default)]
+pub struct ProgressTracker {
+    inner: Arc<ProgressTrackerInner>,
+}
+
+#[derive(Default)]
+struct ProgressTrackerInner {
+    bytes_scanned: AtomicU64,
+    total_bytes: AtomicU64,
+    rows_produced: AtomicU64,
+    operators_completed: AtomicUsize,
+    callbacks: RwLock<Vec<ProgressCallback>>,
+}
+
+/// The progress of a query at a point in time, see [`ProgressTracker`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProgressSnapshot {
+    /// Bytes of the files scanned so far
+    pub bytes_scanned: u64,
+    /// Bytes of the files to scan, as known so far
+    pub total_bytes: u64,
+    /// Rows produced by the scans so far
+    pub rows_produced: u64,
+    /// Number of scan partitions that completed
+    pub operators_completed: usize,
+}
+
+impl ProgressSnapshot {
+    /// Returns the fraction of the bytes to scan that were scanned, between
+    /// 0 and 1, or `None` if no bytes are known to be scanned
+    pub fn fraction(&self) -> Option<f64> {
+        (self.total_bytes > 0)
+            .then(|| (self.bytes_scanned as f64 / self.total_bytes as f64).min(1.0))
+    }
+}
+
+impl ProgressTracker {
+    /// Create a tracker with no progress
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a callback invoked with a snapshot of the progress each time
+    /// progress is reported.
+    ///
+    /// The callback is invoked by the operators reporting progress, so it
+    /// should return quickly.
+    pub fn register_callback(
+        &self,
+        callback: impl Fn(&ProgressSnapshot) + Send + Sync + 'static,
+    ) {
+        self.inner.callbacks.write().push(Arc::new(callback));
+    }
+
+    /// Returns the progress reported so far
+    pub fn snapshot(&self) -> ProgressSnapshot {
+        let inner = &self.inner;
+        ProgressSnapshot {
+            bytes_scanned: inner.bytes_scanned.load(Ordering::Relaxed),
+            total_bytes: inner.total_bytes.load(Ordering::Relaxed),
+            rows_produced: inner.rows_produced.load(Ordering::Relaxed),
+            operators_completed: inner.operators_completed.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Report `bytes` more bytes to scan
+    pub fn add_total_bytes(&self, bytes: u64) {
+        self.inner.total_bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.notify();
+    }
+
+    /// Report `bytes` more bytes scanned
+    pub fn add_bytes_scanned(&self, bytes: u64) {
+        self.inner.bytes_scanned.fetch_add(bytes, Ordering::Relaxed);
+        self.notify();
+    }
+
+    /// Report `rows` more rows produced
+    pub fn add_rows_produced(&self, rows: usize) {
+        self.inner
+            .rows_produced
+            .fetch_add(rows as u64, Ordering::Relaxed);
+        self.notify();
+    }
+
+    /// Report that an operator partition completed
+    pub fn operator_completed(&self) {
+        self.inner
+            .operators_completed
+            .fetch_add(1, Ordering::Relaxed);
+        self.notify();
+    }
+
+    fn notify(&self) {
+        let callbacks = self.inner.callbacks.read();
+        if !callbacks.is_empty() {
+            let snapshot = self.snapshot();
+            callbacks.iter().for_each(|callback| callback(&snapshot));
+        }
+    }
+}
+
+impl Debug for ProgressTracker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressTracker")
+            .field("progress", &self.snapshot())
+            .field("callbacks", &self.inner.callbacks.read().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_tracker() {
+        let tracker = ProgressTracker::new();
+        assert_eq!(tracker.snapshot(), ProgressSnapshot::default());
+        assert_eq!(tracker.snapshot().fraction(), None);
+
+        let reported = Arc::new(RwLock::new(vec![]));
+        let captured = Arc::clone(&reported);
+        tracker.register_callback(move |snapshot| captured.write().push(*snapshot));
+
+        // Clones share the counters
+        let clone = tracker.clone();
+        clone.add_total_bytes(200);
+        clone.add_bytes_scanned(50);
+        clone.add_rows_produced(10);
+        assert_eq!(tracker.snapshot().fraction(), Some(0.25));
+        clone.add_bytes_scanned(150);
+        clone.operator_completed();
+
+        let expected = ProgressSnapshot {
+            bytes_scanned: 200,
+            total_bytes: 200,
+            rows_produced: 10,
+            operators_completed: 1,
+        };
+        assert_eq!(tracker.snapshot(), expected);
+        assert_eq!(tracker.snapshot().fraction(), Some(1.0));
+        let reported = reported.read();
+        assert_eq!(reported.len(), 5);
+        assert_eq!(reported.last(), Some(&expected));
+        assert!(reported
+            .windows(2)
+            .all(|w| w[0].bytes_scanned <= w[1].bytes_scanned));
+    }
+}
@@ -21,11 +21,14 @@
 #[allow(deprecated)]
 use crate::disk_manager::DiskManagerConfig;
 use crate::{
-    disk_manager::{DiskManager, DiskManagerBuilder, DiskManagerMode},
+    disk_manager::{DiskManager, DiskManagerBuilder, DiskManagerMode, DiskUsage},
     memory_pool::{
         GreedyMemoryPool, MemoryPool, TrackConsumersPool, UnboundedMemoryPool,
     },
-    object_store::{DefaultObjectStoreRegistry, ObjectStoreRegistry},
+    object_store::{
+        DefaultObjectStoreRegistry, ObjectStoreRegistry, RateLimitedObjectStore,
+        RequestRateLimiter,
+    },
 };
 
 use crate::cache::cache_manager::{CacheManager, CacheManagerConfig};
@@ -35,7 +38,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::{
     fmt::{Debug, Formatter},
-    num::NonZeroUsize,
+    num::{NonZeroU32, NonZeroUsize},
 };
 use url::Url;
 
@@ -50,6 +53,7 @@ use url::Url;
 /// * [`DiskManager`]: Manage temporary files on local disk
 /// * [`CacheManager`]: Manage temporary cache data during the session lifetime
 /// * [`ObjectStoreRegistry`]: Manage mapping URLs to object store instances
+/// * [`RequestRateLimiter`]: Optionally cap the rate of object store requests
 ///
 /// # Example: Create default `RuntimeEnv`
 /// ```
@@ -78,6 +82,9 @@ pub struct RuntimeEnv {
     pub cache_manager: Arc<CacheManager>,
     /// Object Store Registry
     pub object_store_registry: Arc<dyn ObjectStoreRegistry>,
+    /// Limits the rate of requests made to the stores returned by
+    /// [`Self::object_store`], if set
+    pub object_store_rate_limiter: Option<Arc<RequestRateLimiter>>,
 }
 
 impl Debug for RuntimeEnv {
@@ -151,8 +158,23 @@ impl RuntimeEnv {
     /// Retrieves a `ObjectStore` instance for a url by consulting the
     /// registry. See [`ObjectStoreRegistry::get_store`] for more
     /// details.
+    ///
+    /// If an object store rate limiter is configured the returned store is
+    /// wrapped in a [`RateLimitedObjectStore`] sharing that limiter.
     pub fn object_store(&self, url: impl AsRef<Url>) -> Result<Arc<dyn ObjectStore>> {
-        self.object_store_registry.get_store(url.as_ref())
+        let store = self.object_store_registry.get_store(url.as_ref())?;
+        Ok(match &self.object_store_rate_limiter {
+            Some(limiter) => {
+                Arc::new(RateLimitedObjectStore::new(store, Arc::clone(limiter)))
+            }
+            None => store,
+        })
+    }
+
+    /// Returns the disk space used by the spill files of all queries, and
+    /// of each running query. See [`DiskManager::disk_usage`].
+    pub fn disk_usage(&self) -> DiskUsage {
+        self.disk_manager.disk_usage()
     }
 }
 
@@ -185,6 +207,8 @@ pub struct RuntimeEnvBuilder {
     pub cache_manager: CacheManagerConfig,
     /// ObjectStoreRegistry to get object store based on url
     pub object_store_registry: Arc<dyn ObjectStoreRegistry>,
+    /// Limits the rate of object store requests, unlimited if `None`
+    pub object_store_rate_limiter: Option<Arc<RequestRateLimiter>>,
 }
 
 impl Default for RuntimeEnvBuilder {
@@ -202,6 +226,7 @@ impl RuntimeEnvBuilder {
             memory_pool: Default::default(),
             cache_manager: Default::default(),
             object_store_registry: Arc::new(DefaultObjectStoreRegistry::default()),
+            object_store_rate_limiter: None,
         }
     }
 
@@ -240,6 +265,28 @@ impl RuntimeEnvBuilder {
         self
     }
 
+    /// Limit the rate of object store requests using `limiter`
+    pub fn with_object_store_rate_limiter(
+        mut self,
+        limiter: Arc<RequestRateLimiter>,
+    ) -> Self {
+        self.object_store_rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Allow at most `requests_per_second` object store requests per second
+    /// across all queries using the built [`RuntimeEnv`].
+    ///
+    /// A value of `0` removes any limit.
+    pub fn with_object_store_requests_per_second(
+        mut self,
+        requests_per_second: u32,
+    ) -> Self {
+        self.object_store_rate_limiter = NonZeroU32::new(requests_per_second)
+            .map(|rps| Arc::new(RequestRateLimiter::new(rps)));
+        self
+    }
+
     /// Specify the total memory to use while running the DataFusion
     /// plan to `max_memory * memory_fraction` in bytes.
     ///
@@ -254,12 +301,38 @@ impl RuntimeEnvBuilder {
         )))
     }
 
+    /// Limit the data spilled to the temporary directories by all queries
+    /// to `max_temp_directory_size` bytes. Queries spilling more fail with
+    /// a resources exhausted error.
+    ///
+    /// See [`DiskManagerBuilder::with_max_temp_directory_size`]
+    pub fn with_max_temp_directory_size(mut self, max_temp_directory_size: u64) -> Self {
+        self.disk_manager_builder
+            .get_or_insert_with(DiskManagerBuilder::default)
+            .set_max_temp_directory_size(max_temp_directory_size);
+        self
+    }
+
+    /// Limit the data spilled to the temporary directories by each query to
+    /// `max_temp_directory_size` bytes.
+    ///
+    /// See [`DiskManagerBuilder::with_max_temp_directory_size_per_query`]
+    pub fn with_max_temp_directory_size_per_query(
+        mut self,
+        max_temp_directory_size: u64,
+    ) -> Self {
+        self.disk_manager_builder
+            .get_or_insert_with(DiskManagerBuilder::default)
+            .set_max_temp_directory_size_per_query(Some(max_temp_directory_size));
+        self
+    }
+
     /// Use the specified path to create any needed temporary files
-    pub fn with_temp_file_path(self, path: impl Into<PathBuf>) -> Self {
-        self.with_disk_manager_builder(
-            DiskManagerBuilder::default()
-                .with_mode(DiskManagerMode::Directories(vec![path.into()])),
-        )
+    pub fn with_temp_file_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.disk_manager_builder
+            .get_or_insert_with(DiskManagerBuilder::default)
+            .set_mode(DiskManagerMode::Directories(vec![path.into()]));
+        self
     }
 
     /// Build a RuntimeEnv
@@ -270,6 +343,7 @@ impl RuntimeEnvBuilder {
             memory_pool,
             cache_manager,
             object_store_registry,
+            object_store_rate_limiter,
         } = self;
         let memory_pool =
             memory_pool.unwrap_or_else(|| Arc::new(UnboundedMemoryPool::default()));
@@ -284,6 +358,7 @@ impl RuntimeEnvBuilder {
             },
             cache_manager: CacheManager::try_new(&cache_manager)?,
             object_store_registry,
+            object_store_rate_limiter,
         })
     }
 
@@ -310,17 +385,31 @@ impl RuntimeEnvBuilder {
             memory_pool: Some(Arc::clone(&runtime_env.memory_pool)),
             cache_manager: cache_config,
             object_store_registry: Arc::clone(&runtime_env.object_store_registry),
+            object_store_rate_limiter: runtime_env.object_store_rate_limiter.clone(),
         }
     }
 
     /// Returns a list of all available runtime configurations with their current values and descriptions
     pub fn entries(&self) -> Vec<ConfigEntry> {
-        // Memory pool configuration
-        vec![ConfigEntry {
-            key: "datafusion.runtime.memory_limit".to_string(),
-            value: None, // Default is system-dependent
-            description: "Maximum memory limit for query execution. Supports suffixes K (kilobytes), M (megabytes), and G (gigabytes). Example: '2G' for 2 gigabytes.",
-        }]
+        vec![
+            // Memory pool configuration
+            ConfigEntry {
+                key: "datafusion.runtime.memory_limit".to_string(),
+                value: None, // Default is system-dependent
+                description: "Maximum memory limit for query execution. Supports suffixes K (kilobytes), M (megabytes), and G (gigabytes). Example: '2G' for 2 gigabytes.",
+            },
+            // Object store request rate limit
+            ConfigEntry {
+                key: "datafusion.runtime.object_store_requests_per_second".to_string(),
+                value: Some(
+                    self.object_store_rate_limiter
+                        .as_ref()
+                        .map_or(0, |limiter| limiter.requests_per_second())
+                        .to_string(),
+                ),
+                description: "Maximum number of object store requests (such as reading a file range or listing a directory) issued per second across all queries. Requests exceeding the limit wait for a permit. 0 means unlimited.",
+            },
+        ]
     }
 
     /// Generate documentation that can be included in the user guide
@@ -16,9 +16,13 @@
 // under the License.
 
 use crate::{
-    config::SessionConfig, memory_pool::MemoryPool, registry::FunctionRegistry,
+    config::SessionConfig,
+    memory_pool::{MemoryPool, QueryMemoryPool},
+    progress::ProgressTracker,
+    registry::FunctionRegistry,
     runtime_env::RuntimeEnv,
 };
+use datafusion_common::cancellation::CancellationToken;
 use datafusion_common::{plan_datafusion_err, DataFusionError, Result};
 use datafusion_expr::planner::ExprPlanner;
 use datafusion_expr::{AggregateUDF, ScalarUDF, WindowUDF};
@@ -48,6 +52,15 @@ pub struct TaskContext {
     window_functions: HashMap<String, Arc<WindowUDF>>,
     /// Runtime environment associated with this task context
     runtime: Arc<RuntimeEnv>,
+    /// Memory pool limiting the memory of this task within the pool of the
+    /// runtime, if the session configures a per query memory limit
+    query_memory_pool: Option<Arc<QueryMemoryPool>>,
+    /// The memory pool operators of this task reserve memory from
+    memory_pool: Arc<dyn MemoryPool>,
+    /// Token cancelling the execution of this task
+    cancellation_token: CancellationToken,
+    /// Tracker the operators of this task report their progress into
+    progress_tracker: ProgressTracker,
 }
 
 impl Default for TaskContext {
@@ -62,7 +75,11 @@ impl Default for TaskContext {
             scalar_functions: HashMap::new(),
             aggregate_functions: HashMap::new(),
             window_functions: HashMap::new(),
+            memory_pool: Arc::clone(&runtime.memory_pool),
             runtime,
+            query_memory_pool: None,
+            cancellation_token: CancellationToken::new(),
+            progress_tracker: ProgressTracker::new(),
         }
     }
 }
@@ -82,6 +99,8 @@ impl TaskContext {
         window_functions: HashMap<String, Arc<WindowUDF>>,
         runtime: Arc<RuntimeEnv>,
     ) -> Self {
+        let (query_memory_pool, memory_pool) = new_memory_pool(&session_config, &runtime);
+        let runtime = query_runtime(&runtime, &session_id, task_id.as_deref());
         Self {
             task_id,
             session_id,
@@ -90,6 +109,10 @@ impl TaskContext {
             aggregate_functions,
             window_functions,
             runtime,
+            query_memory_pool,
+            memory_pool,
+            cancellation_token: CancellationToken::new(),
+            progress_tracker: ProgressTracker::new(),
         }
     }
 
@@ -109,15 +132,42 @@ impl TaskContext {
     }
 
     /// Return the [`MemoryPool`] associated with this [TaskContext]
+    ///
+    /// This is the [`QueryMemoryPool`] of the task if the session configures
+    /// a per query memory limit, and the pool of the [`RuntimeEnv`] otherwise
     pub fn memory_pool(&self) -> &Arc<dyn MemoryPool> {
-        &self.runtime.memory_pool
+        &self.memory_pool
+    }
+
+    /// Return the [`QueryMemoryPool`] limiting the memory of this task, if
+    /// the session configures a per query memory limit
+    ///
+    /// The pool reports the peak memory used by the query, for example to
+    /// log it after the query has executed.
+    pub fn query_memory_pool(&self) -> Option<&Arc<QueryMemoryPool>> {
+        self.query_memory_pool.as_ref()
     }
 
     /// Return the [RuntimeEnv] associated with this [TaskContext]
+    ///
+    /// Its disk manager accounts the spill files of this task as the files
+    /// of a single query, limited by the per query disk limit if any.
     pub fn runtime_env(&self) -> Arc<RuntimeEnv> {
         Arc::clone(&self.runtime)
     }
 
+    /// Return the [`CancellationToken`] cancelling the execution of this
+    /// [TaskContext]
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation_token
+    }
+
+    /// Return the [`ProgressTracker`] the operators of this [TaskContext]
+    /// report their progress into
+    pub fn progress_tracker(&self) -> &ProgressTracker {
+        &self.progress_tracker
+    }
+
     pub fn scalar_functions(&self) -> &HashMap<String, Arc<ScalarUDF>> {
         &self.scalar_functions
     }
@@ -132,15 +182,72 @@ impl TaskContext {
 
     /// Update the [`SessionConfig`]
     pub fn with_session_config(mut self, session_config: SessionConfig) -> Self {
+        (self.query_memory_pool, self.memory_pool) =
+            new_memory_pool(&session_config, &self.runtime);
         self.session_config = session_config;
         self
     }
 
     /// Update the [`RuntimeEnv`]
     pub fn with_runtime(mut self, runtime: Arc<RuntimeEnv>) -> Self {
-        self.runtime = runtime;
+        (self.query_memory_pool, self.memory_pool) =
+            new_memory_pool(&self.session_config, &runtime);
+        self.runtime = query_runtime(&runtime, &self.session_id, self.task_id.as_deref());
         self
     }
+
+    /// Update the [`CancellationToken`], so that cancelling it cancels the
+    /// execution of this task
+    pub fn with_cancellation_token(
+        mut self,
+        cancellation_token: CancellationToken,
+    ) -> Self {
+        self.cancellation_token = cancellation_token;
+        self
+    }
+
+    /// Update the [`ProgressTracker`], so that the progress of the execution
+    /// of this task can be followed through `progress_tracker`
+    pub fn with_progress_tracker(mut self, progress_tracker: ProgressTracker) -> Self {
+        self.progress_tracker = progress_tracker;
+        self
+    }
+}
+
+/// Returns the memory pool of a task: a new [`QueryMemoryPool`] carved from
+/// the pool of `runtime` if `session_config` configures a per query memory
+/// limit, and the pool of `runtime` otherwise
+fn new_memory_pool(
+    session_config: &SessionConfig,
+    runtime: &RuntimeEnv,
+) -> (Option<Arc<QueryMemoryPool>>, Arc<dyn MemoryPool>) {
+    let options = &session_config.options().execution;
+    match options.per_query_memory_limit {
+        Some(limit) => {
+            let pool = Arc::new(QueryMemoryPool::new(
+                Arc::clone(&runtime.memory_pool),
+                limit,
+                options.per_query_memory_policy,
+            ));
+            (Some(Arc::clone(&pool)), pool)
+        }
+        None => (None, Arc::clone(&runtime.memory_pool)),
+    }
+}
+
+/// Returns `runtime` with a disk manager accounting the spill files of the
+/// task as the files of a query, see [`DiskManager::for_query`]
+///
+/// [`DiskManager::for_query`]: crate::disk_manager::DiskManager::for_query
+fn query_runtime(
+    runtime: &RuntimeEnv,
+    session_id: &str,
+    task_id: Option<&str>,
+) -> Arc<RuntimeEnv> {
+    Arc::new(RuntimeEnv {
+        disk_manager: runtime.disk_manager.for_query(session_id, task_id),
+        ..runtime.clone()
+    })
 }
 
 impl FunctionRegistry for TaskContext {
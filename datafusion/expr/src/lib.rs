@@ -101,7 +101,10 @@ pub use literal::{
 pub use logical_plan::*;
 pub use partition_evaluator::PartitionEvaluator;
 pub use sqlparser;
-pub use table_source::{TableProviderFilterPushDown, TableSource, TableType};
+pub use table_source::{
+    schema_comment, TableProviderFilterPushDown, TableSource, TableType,
+    COMMENT_METADATA_KEY,
+};
 pub use udaf::{
     aggregate_doc_sections, AggregateUDF, AggregateUDFImpl, ReversedUDAF,
     SetMonotonicity, StatisticsArgs,
@@ -1277,6 +1277,7 @@ impl LogicalPlanBuilder {
         if explain_option.analyze {
             Ok(Self::new(LogicalPlan::Analyze(Analyze {
                 verbose: explain_option.verbose,
+                format: explain_option.analyze_format,
                 input: self.plan,
                 schema,
             })))
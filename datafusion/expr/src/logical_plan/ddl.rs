@@ -53,10 +53,14 @@ pub enum DdlStatement {
     DropView(DropView),
     /// Drops a catalog schema
     DropCatalogSchema(DropCatalogSchema),
+    /// Drops a catalog (aka "Database")
+    DropCatalog(DropCatalog),
     /// Create function statement
     CreateFunction(CreateFunction),
     /// Drop function statement
     DropFunction(DropFunction),
+    /// Sets the comment of a table or of one of its columns.
+    CommentOn(CommentOn),
 }
 
 impl DdlStatement {
@@ -76,8 +80,10 @@ impl DdlStatement {
             DdlStatement::DropTable(DropTable { schema, .. }) => schema,
             DdlStatement::DropView(DropView { schema, .. }) => schema,
             DdlStatement::DropCatalogSchema(DropCatalogSchema { schema, .. }) => schema,
+            DdlStatement::DropCatalog(DropCatalog { schema, .. }) => schema,
             DdlStatement::CreateFunction(CreateFunction { schema, .. }) => schema,
             DdlStatement::DropFunction(DropFunction { schema, .. }) => schema,
+            DdlStatement::CommentOn(CommentOn { schema, .. }) => schema,
         }
     }
 
@@ -94,8 +100,10 @@ impl DdlStatement {
             DdlStatement::DropTable(_) => "DropTable",
             DdlStatement::DropView(_) => "DropView",
             DdlStatement::DropCatalogSchema(_) => "DropCatalogSchema",
+            DdlStatement::DropCatalog(_) => "DropCatalog",
             DdlStatement::CreateFunction(_) => "CreateFunction",
             DdlStatement::DropFunction(_) => "DropFunction",
+            DdlStatement::CommentOn(_) => "CommentOn",
         }
     }
 
@@ -113,8 +121,10 @@ impl DdlStatement {
             DdlStatement::DropTable(_) => vec![],
             DdlStatement::DropView(_) => vec![],
             DdlStatement::DropCatalogSchema(_) => vec![],
+            DdlStatement::DropCatalog(_) => vec![],
             DdlStatement::CreateFunction(_) => vec![],
             DdlStatement::DropFunction(_) => vec![],
+            DdlStatement::CommentOn(_) => vec![],
         }
     }
 
@@ -185,12 +195,28 @@ impl DdlStatement {
                     }) => {
                         write!(f, "DropCatalogSchema: {name:?} if not exist:={if_exists} cascade:={cascade}")
                     }
+                    DdlStatement::DropCatalog(DropCatalog {
+                        catalog_name,
+                        if_exists,
+                        cascade,
+                        ..
+                    }) => {
+                        write!(f, "DropCatalog: {catalog_name:?} if not exist:={if_exists} cascade:={cascade}")
+                    }
                     DdlStatement::CreateFunction(CreateFunction { name, .. }) => {
                         write!(f, "CreateFunction: name {name:?}")
                     }
                     DdlStatement::DropFunction(DropFunction { name, .. }) => {
                         write!(f, "DropFunction: name {name:?}")
                     }
+                    DdlStatement::CommentOn(CommentOn { table, column, .. }) => {
+                        match column {
+                            Some(column) => {
+                                write!(f, "CommentOn: column {table:?}.{column:?}")
+                            }
+                            None => write!(f, "CommentOn: table {table:?}"),
+                        }
+                    }
                 }
             }
         }
@@ -311,6 +337,9 @@ pub struct CreateMemoryTable {
     pub or_replace: bool,
     /// Default values for columns
     pub column_defaults: Vec<(String, Expr)>,
+    /// Expressions computing the values of generated columns from the other
+    /// columns of a row, as in `GENERATED ALWAYS AS (expr)`
+    pub generated_columns: Vec<(String, Expr)>,
     /// Whether the table is `TableType::Temporary`
     pub temporary: bool,
 }
@@ -440,6 +469,32 @@ impl PartialOrd for DropCatalogSchema {
     }
 }
 
+/// Drops a catalog (aka "Database")
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DropCatalog {
+    /// The catalog name
+    pub catalog_name: String,
+    /// If the catalog exists
+    pub if_exists: bool,
+    /// Whether drop should cascade to the schemas of the catalog
+    pub cascade: bool,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+// Manual implementation needed because of `schema` field. Comparison excludes this field.
+impl PartialOrd for DropCatalog {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.catalog_name.partial_cmp(&other.catalog_name) {
+            Some(Ordering::Equal) => match self.if_exists.partial_cmp(&other.if_exists) {
+                Some(Ordering::Equal) => self.cascade.partial_cmp(&other.cascade),
+                cmp => cmp,
+            },
+            cmp => cmp,
+        }
+    }
+}
+
 /// Arguments passed to `CREATE FUNCTION`
 ///
 /// Note this meant to be the same as from sqlparser's [`sqlparser::ast::Statement::CreateFunction`]
@@ -569,6 +624,33 @@ impl PartialOrd for DropFunction {
     }
 }
 
+/// Sets the comment documenting a table, or one of its columns.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CommentOn {
+    /// The table that is commented, or whose column is
+    pub table: TableReference,
+    /// The commented column, or `None` if the table itself is commented
+    pub column: Option<String>,
+    /// The comment, or `None` to remove the comment
+    pub comment: Option<String>,
+    /// Do nothing, rather than fail, if the table does not exist
+    pub if_exists: bool,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+// Manual implementation needed because of `schema` field. Comparison excludes this field.
+impl PartialOrd for CommentOn {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (&self.table, &self.column, &self.comment, &self.if_exists).partial_cmp(&(
+            &other.table,
+            &other.column,
+            &other.comment,
+            &other.if_exists,
+        ))
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct CreateIndex {
     pub name: Option<String>,
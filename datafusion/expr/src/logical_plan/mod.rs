@@ -31,17 +31,19 @@ pub use builder::{
     LogicalPlanBuilder, LogicalPlanBuilderOptions, LogicalTableSource, UNNAMED_TABLE,
 };
 pub use ddl::{
-    CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
+    CommentOn, CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
     CreateFunctionBody, CreateIndex, CreateMemoryTable, CreateView, DdlStatement,
-    DropCatalogSchema, DropFunction, DropTable, DropView, OperateFunctionArg,
+    DropCatalog, DropCatalogSchema, DropFunction, DropTable, DropView,
+    OperateFunctionArg,
 };
 pub use dml::{DmlStatement, WriteOp};
 pub use plan::{
-    projection_schema, Aggregate, Analyze, ColumnUnnestList, DescribeTable, Distinct,
-    DistinctOn, EmptyRelation, Explain, ExplainFormat, ExplainOption, Extension,
-    FetchType, Filter, Join, JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning,
-    PlanType, Projection, RecursiveQuery, Repartition, SkipType, Sort, StringifiedPlan,
-    Subquery, SubqueryAlias, TableScan, ToStringifiedPlan, Union, Unnest, Values, Window,
+    projection_schema, Aggregate, Analyze, AnalyzeFormat, ColumnUnnestList,
+    DescribeTable, Distinct, DistinctOn, EmptyRelation, Explain, ExplainFormat,
+    ExplainOption, Extension, FetchType, Filter, Join, JoinConstraint, JoinType, Limit,
+    LogicalPlan, Partitioning, PlanType, Projection, RecursiveQuery, Repartition,
+    SkipType, Sort, StringifiedPlan, Subquery, SubqueryAlias, TableScan,
+    ToStringifiedPlan, Union, Unnest, Values, Window,
 };
 pub use statement::{
     Deallocate, Execute, Prepare, SetVariable, Statement, TransactionAccessMode,
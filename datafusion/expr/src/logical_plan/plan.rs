@@ -388,6 +388,17 @@ impl LogicalPlan {
         ])
     }
 
+    /// Returns the output schema of `DESCRIBE EXTENDED`, which adds the
+    /// comment of each column to [`Self::describe_schema`]
+    pub fn describe_extended_schema() -> Schema {
+        Schema::new(vec![
+            Field::new("column_name", DataType::Utf8, false),
+            Field::new("data_type", DataType::Utf8, false),
+            Field::new("is_nullable", DataType::Utf8, false),
+            Field::new("comment", DataType::Utf8, true),
+        ])
+    }
+
     /// Returns all expressions (non-recursively) evaluated by the current
     /// logical plan node. This does not include expressions in any children.
     ///
@@ -982,6 +993,7 @@ impl LogicalPlan {
                 if_not_exists,
                 or_replace,
                 column_defaults,
+                generated_columns,
                 temporary,
                 ..
             })) => {
@@ -995,6 +1007,7 @@ impl LogicalPlan {
                         if_not_exists: *if_not_exists,
                         or_replace: *or_replace,
                         column_defaults: column_defaults.clone(),
+                        generated_columns: generated_columns.clone(),
                         temporary: *temporary,
                     },
                 )))
@@ -1076,6 +1089,7 @@ impl LogicalPlan {
                 let input = self.only_input(inputs)?;
                 Ok(LogicalPlan::Analyze(Analyze {
                     verbose: a.verbose,
+                    format: a.format,
                     schema: Arc::clone(&a.schema),
                     input: Arc::new(input),
                 }))
@@ -3095,6 +3109,42 @@ impl FromStr for ExplainFormat {
     }
 }
 
+/// Output formats of `EXPLAIN ANALYZE`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Hash)]
+pub enum AnalyzeFormat {
+    /// The physical plan annotated with the metrics of its operators, as in
+    /// [`ExplainFormat::Indent`]
+    #[default]
+    Indent,
+    /// A versioned JSON document with the name, metrics (in total and per
+    /// partition) and children of each operator, see `MetricsNode::to_json`
+    /// in `datafusion-physical-plan`
+    Json,
+}
+
+impl FromStr for AnalyzeFormat {
+    type Err = DataFusionError;
+
+    fn from_str(format: &str) -> std::result::Result<Self, Self::Err> {
+        match format.to_lowercase().as_str() {
+            "indent" => Ok(AnalyzeFormat::Indent),
+            "json" => Ok(AnalyzeFormat::Json),
+            _ => {
+                plan_err!("Invalid explain analyze format. Expected 'indent' or 'json'. Got '{format}'")
+            }
+        }
+    }
+}
+
+impl Display for AnalyzeFormat {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AnalyzeFormat::Indent => write!(f, "indent"),
+            AnalyzeFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
 /// Options for EXPLAIN
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExplainOption {
@@ -3104,6 +3154,8 @@ pub struct ExplainOption {
     pub analyze: bool,
     /// Output syntax/format
     pub format: ExplainFormat,
+    /// Output format when `analyze` is true
+    pub analyze_format: AnalyzeFormat,
 }
 
 impl Default for ExplainOption {
@@ -3112,6 +3164,7 @@ impl Default for ExplainOption {
             verbose: false,
             analyze: false,
             format: ExplainFormat::Indent,
+            analyze_format: AnalyzeFormat::Indent,
         }
     }
 }
@@ -3134,6 +3187,12 @@ impl ExplainOption {
         self.format = format;
         self
     }
+
+    /// Builder‐style setter for `analyze_format`
+    pub fn with_analyze_format(mut self, analyze_format: AnalyzeFormat) -> Self {
+        self.analyze_format = analyze_format;
+        self
+    }
 }
 
 /// Produces a relation with string representations of
@@ -3195,6 +3254,8 @@ impl PartialOrd for Explain {
 pub struct Analyze {
     /// Should extra detail be included?
     pub verbose: bool,
+    /// The format of the output
+    pub format: AnalyzeFormat,
     /// The logical plan that is being EXPLAIN ANALYZE'd
     pub input: Arc<LogicalPlan>,
     /// The output schema of the explain (2 columns of text)
@@ -3204,7 +3265,7 @@ pub struct Analyze {
 // Manual implementation needed because of `schema` field. Comparison excludes this field.
 impl PartialOrd for Analyze {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match self.verbose.partial_cmp(&other.verbose) {
+        match (self.verbose, self.format).partial_cmp(&(other.verbose, other.format)) {
             Some(Ordering::Equal) => self.input.partial_cmp(&other.input),
             cmp => cmp,
         }
@@ -19,11 +19,26 @@
 
 use crate::{Expr, LogicalPlan};
 
-use arrow::datatypes::SchemaRef;
-use datafusion_common::{Constraints, Result};
+use arrow::datatypes::{Schema, SchemaRef};
+use datafusion_common::{Constraints, Result, Statistics};
 
 use std::{any::Any, borrow::Cow};
 
+/// Key of the field metadata holding the comment documenting a column, and
+/// of the schema metadata holding the comment documenting a table, as set by
+/// `COMMENT ON`
+pub const COMMENT_METADATA_KEY: &str = "comment";
+
+/// Returns the [`COMMENT_METADATA_KEY`] metadata of the field named `column`
+/// of `schema`, or of `schema` itself when `column` is `None`
+pub fn schema_comment(schema: &Schema, column: Option<&str>) -> Option<String> {
+    let metadata = match column {
+        Some(column) => schema.field_with_name(column).ok()?.metadata(),
+        None => schema.metadata(),
+    };
+    metadata.get(COMMENT_METADATA_KEY).cloned()
+}
+
 /// Indicates how a filter expression is handled by
 /// [`TableProvider::scan`].
 ///
@@ -129,4 +144,27 @@ pub trait TableSource: Sync + Send {
     fn get_column_default(&self, _column: &str) -> Option<&Expr> {
         None
     }
+
+    /// Get the expression computing the value of a generated column from
+    /// the other columns of a row, if `column` is generated.
+    fn get_generated_column_expr(&self, _column: &str) -> Option<&Expr> {
+        None
+    }
+
+    /// Get the comment documenting `column`, or the table itself when
+    /// `column` is `None`, if any.
+    ///
+    /// Defaults to the [`COMMENT_METADATA_KEY`] metadata of the field or of
+    /// the schema, see [`schema_comment`].
+    fn comment(&self, column: Option<&str>) -> Option<String> {
+        schema_comment(&self.schema(), column)
+    }
+
+    /// Get statistics for this table, if available.
+    ///
+    /// Logical optimizer rules may use these to decide whether a rewrite is
+    /// worthwhile.
+    fn statistics(&self) -> Option<Statistics> {
+        None
+    }
 }
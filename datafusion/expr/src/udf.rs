@@ -23,6 +23,7 @@ use crate::simplify::{ExprSimplifyResult, SimplifyInfo};
 use crate::sort_properties::{ExprProperties, SortProperties};
 use crate::{ColumnarValue, Documentation, Expr, Signature};
 use arrow::datatypes::{DataType, Field, FieldRef};
+use datafusion_common::cancellation::CancellationToken;
 use datafusion_common::{not_impl_err, ExprSchema, Result, ScalarValue};
 use datafusion_expr_common::interval_arithmetic::Interval;
 use std::any::Any;
@@ -209,6 +210,16 @@ impl ScalarUDF {
         self.inner.invoke_with_args(args)
     }
 
+    /// Returns this function bound to the [`CancellationToken`] of the query
+    /// executing it, if it checks whether the query is cancelled.
+    ///
+    /// See [`ScalarUDFImpl::with_cancellation`] for details.
+    pub fn with_cancellation(&self, cancellation: &CancellationToken) -> Option<Self> {
+        self.inner
+            .with_cancellation(cancellation)
+            .map(|inner| Self { inner })
+    }
+
     /// Get the circuits of inner implementation
     pub fn short_circuits(&self) -> bool {
         self.inner.short_circuits()
@@ -556,6 +567,21 @@ pub trait ScalarUDFImpl: Debug + Send + Sync {
     /// to arrays, which will likely be simpler code, but be slower.
     fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue>;
 
+    /// Returns this function bound to the [`CancellationToken`] of the query
+    /// executing it, for functions whose invocations take long enough that
+    /// they should stop early once the query is cancelled, by checking the
+    /// token with [`CancellationToken::check`].
+    ///
+    /// The functions evaluated by projections and filters are bound when
+    /// they are executed. Returns `None` by default, for functions that don't
+    /// check the token, which only stop once their invocation returns.
+    fn with_cancellation(
+        &self,
+        _cancellation: &CancellationToken,
+    ) -> Option<Arc<dyn ScalarUDFImpl>> {
+        None
+    }
+
     /// Optionally apply per-UDF simplification / rewrite rules.
     ///
     /// This can be used to apply function specific simplification rules during
@@ -782,6 +808,18 @@ impl ScalarUDFImpl for AliasedScalarUDFImpl {
         self.inner.invoke_with_args(args)
     }
 
+    fn with_cancellation(
+        &self,
+        cancellation: &CancellationToken,
+    ) -> Option<Arc<dyn ScalarUDFImpl>> {
+        self.inner.with_cancellation(cancellation).map(|inner| {
+            Arc::new(Self {
+                inner,
+                aliases: self.aliases.clone(),
+            }) as _
+        })
+    }
+
     fn aliases(&self) -> &[String] {
         &self.aliases
     }
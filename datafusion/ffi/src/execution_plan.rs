@@ -31,7 +31,7 @@ use tokio::runtime::Handle;
 
 use crate::{
     df_result, plan_properties::FFI_PlanProperties,
-    record_batch_stream::FFI_RecordBatchStream, rresult,
+    record_batch_stream::FFI_RecordBatchStream, rresult, util::catch_panic,
 };
 
 /// A stable struct for sharing a [`ExecutionPlan`] across FFI boundaries.
@@ -113,9 +113,11 @@ unsafe extern "C" fn execute_fn_wrapper(
     let ctx = &(*private_data).context;
     let runtime = (*private_data).runtime.clone();
 
-    rresult!(plan
-        .execute(partition, Arc::clone(ctx))
-        .map(|rbs| FFI_RecordBatchStream::new(rbs, runtime)))
+    catch_panic(|| {
+        rresult!(plan
+            .execute(partition, Arc::clone(ctx))
+            .map(|rbs| FFI_RecordBatchStream::new(rbs, runtime)))
+    })
 }
 
 unsafe extern "C" fn name_fn_wrapper(plan: &FFI_ExecutionPlan) -> RString {
@@ -15,7 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::{ffi::c_void, task::Poll};
+use std::{
+    ffi::c_void,
+    panic::{catch_unwind, AssertUnwindSafe},
+    task::Poll,
+};
 
 use abi_stable::{
     std_types::{ROption, RResult, RString},
@@ -38,6 +42,7 @@ use tokio::runtime::Handle;
 use crate::{
     arrow_wrappers::{WrappedArray, WrappedSchema},
     rresult,
+    util::panic_error,
 };
 
 /// A stable struct for sharing [`RecordBatchStream`] across FFI boundaries.
@@ -130,11 +135,16 @@ unsafe extern "C" fn poll_next_fn_wrapper(
 
     let _guard = (*private_data).runtime.as_ref().map(|rt| rt.enter());
 
-    let poll_result = cx.with_context(|std_cx| {
-        (*stream)
-            .try_poll_next_unpin(std_cx)
-            .map(maybe_record_batch_to_wrapped_stream)
-    });
+    // A panic of the stream is returned as its next item, rather than
+    // unwinding into the foreign library
+    let poll_result = catch_unwind(AssertUnwindSafe(|| {
+        cx.with_context(|std_cx| {
+            (*stream)
+                .try_poll_next_unpin(std_cx)
+                .map(maybe_record_batch_to_wrapped_stream)
+        })
+    }))
+    .unwrap_or_else(|payload| Poll::Ready(ROption::RSome(panic_error(payload))));
 
     poll_result.into()
 }
@@ -200,11 +210,13 @@ impl Stream for FFI_RecordBatchStream {
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
+    use std::task::Poll;
 
+    use arrow::array::RecordBatch;
     use arrow::datatypes::{DataType, Field, Schema};
     use datafusion::{
         common::record_batch, error::Result, execution::SendableRecordBatchStream,
-        test_util::bounded_stream,
+        physical_plan::stream::RecordBatchStreamAdapter, test_util::bounded_stream,
     };
 
     use super::FFI_RecordBatchStream;
@@ -241,4 +253,24 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_panic_of_record_batch_stream() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let panicking: SendableRecordBatchStream =
+            Box::pin(RecordBatchStreamAdapter::new(
+                schema,
+                futures::stream::poll_fn(|_| -> Poll<Option<Result<RecordBatch>>> {
+                    panic!("poll of the stream")
+                }),
+            ));
+
+        let mut ffi_rbs: SendableRecordBatchStream =
+            Box::pin(FFI_RecordBatchStream::from(panicking));
+        let err = ffi_rbs.next().await.unwrap().unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Execution error: Panic across the FFI boundary: poll of the stream"
+        );
+    }
 }
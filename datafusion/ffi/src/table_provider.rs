@@ -47,6 +47,7 @@ use crate::{
     df_result, rresult_return,
     session_config::ForeignSessionConfig,
     table_source::{FFI_TableProviderFilterPushDown, FFI_TableType},
+    util::{catch_panic, catch_panic_async},
 };
 
 use super::{
@@ -108,15 +109,19 @@ pub struct FFI_TableProvider {
     ///
     /// * `provider` - the table provider
     /// * `session_config` - session configuration
-    /// * `projections` - if specified, only a subset of the columns are returned
+    /// * `projections` - if specified, only a subset of the columns are returned,
+    ///   which may be none of them
     /// * `filters_serialized` - filters to apply to the scan, which are a
     ///   [`LogicalExprList`] protobuf message serialized into bytes to pass
     ///   across the FFI boundary.
     /// * `limit` - if specified, limit the number of rows returned
+    ///
+    /// The batches of the returned plan are produced as its stream is polled.
+    /// A panic of the provider is returned as an error.
     pub scan: unsafe extern "C" fn(
         provider: &Self,
         session_config: &FFI_SessionConfig,
-        projections: RVec<usize>,
+        projections: ROption<RVec<usize>>,
         filters_serialized: RVec<u8>,
         limit: ROption<usize>,
     ) -> FfiFuture<RResult<FFI_ExecutionPlan, RString>>,
@@ -126,7 +131,8 @@ pub struct FFI_TableProvider {
 
     /// Based upon the input filters, identify which are supported. The filters
     /// are a [`LogicalExprList`] protobuf message serialized into bytes to pass
-    /// across the FFI boundary.
+    /// across the FFI boundary. The filters that are supported are passed to
+    /// [`Self::scan`].
     pub supports_filters_pushdown: Option<
         unsafe extern "C" fn(
             provider: &FFI_TableProvider,
@@ -216,15 +222,17 @@ unsafe extern "C" fn supports_filters_pushdown_fn_wrapper(
     let private_data = provider.private_data as *const ProviderPrivateData;
     let provider = &(*private_data).provider;
 
-    supports_filters_pushdown_internal(provider, &filters_serialized)
-        .map_err(|e| e.to_string().into())
-        .into()
+    catch_panic(|| {
+        supports_filters_pushdown_internal(provider, &filters_serialized)
+            .map_err(|e| e.to_string().into())
+            .into()
+    })
 }
 
 unsafe extern "C" fn scan_fn_wrapper(
     provider: &FFI_TableProvider,
     session_config: &FFI_SessionConfig,
-    projections: RVec<usize>,
+    projections: ROption<RVec<usize>>,
     filters_serialized: RVec<u8>,
     limit: ROption<usize>,
 ) -> FfiFuture<RResult<FFI_ExecutionPlan, RString>> {
@@ -233,7 +241,7 @@ unsafe extern "C" fn scan_fn_wrapper(
     let session_config = session_config.clone();
     let runtime = &(*private_data).runtime;
 
-    async move {
+    catch_panic_async(async move {
         let config = rresult_return!(ForeignSessionConfig::try_from(&session_config));
         let session = SessionStateBuilder::new()
             .with_default_features()
@@ -258,11 +266,12 @@ unsafe extern "C" fn scan_fn_wrapper(
             }
         };
 
-        let projections: Vec<_> = projections.into_iter().collect();
+        let projections: Option<Vec<_>> =
+            projections.into_option().map(|p| p.into_iter().collect());
 
         let plan = rresult_return!(
             internal_provider
-                .scan(&ctx.state(), Some(&projections), &filters, limit.into())
+                .scan(&ctx.state(), projections.as_ref(), &filters, limit.into())
                 .await
         );
 
@@ -271,7 +280,7 @@ unsafe extern "C" fn scan_fn_wrapper(
             ctx.task_ctx(),
             runtime.clone(),
         ))
-    }
+    })
     .into_ffi()
 }
 
@@ -287,7 +296,7 @@ unsafe extern "C" fn insert_into_fn_wrapper(
     let input = input.clone();
     let runtime = &(*private_data).runtime;
 
-    async move {
+    catch_panic_async(async move {
         let config = rresult_return!(ForeignSessionConfig::try_from(&session_config));
         let session = SessionStateBuilder::new()
             .with_default_features()
@@ -310,7 +319,7 @@ unsafe extern "C" fn insert_into_fn_wrapper(
             ctx.task_ctx(),
             runtime.clone(),
         ))
-    }
+    })
     .into_ffi()
 }
 
@@ -432,7 +441,7 @@ impl TableProvider for ForeignTableProvider {
             let maybe_plan = (self.0.scan)(
                 &self.0,
                 &session_config,
-                projections.unwrap_or_default(),
+                projections.into(),
                 filters_serialized,
                 limit.into(),
             )
@@ -641,4 +650,173 @@ mod tests {
         assert_batches_eq!(expected, &result);
         Ok(())
     }
+
+    /// The arguments of the last scan of a [`RecordingProvider`]
+    #[derive(Debug, Default, PartialEq)]
+    struct ScanArgs {
+        projection: Option<Vec<usize>>,
+        filters: Vec<Expr>,
+        limit: Option<usize>,
+    }
+
+    /// Records the arguments of its scans, supporting all filters exactly
+    #[derive(Debug)]
+    struct RecordingProvider {
+        inner: Arc<dyn TableProvider>,
+        scanned: Arc<std::sync::Mutex<Option<ScanArgs>>>,
+        panic_on_scan: bool,
+    }
+
+    #[async_trait]
+    impl TableProvider for RecordingProvider {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            self.inner.schema()
+        }
+
+        fn table_type(&self) -> TableType {
+            self.inner.table_type()
+        }
+
+        async fn scan(
+            &self,
+            state: &dyn Session,
+            projection: Option<&Vec<usize>>,
+            filters: &[Expr],
+            limit: Option<usize>,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            if self.panic_on_scan {
+                panic!("scan of RecordingProvider");
+            }
+            *self.scanned.lock().unwrap() = Some(ScanArgs {
+                projection: projection.cloned(),
+                filters: filters.to_vec(),
+                limit,
+            });
+            let Some(predicate) = filters.iter().cloned().reduce(Expr::and) else {
+                return self.inner.scan(state, projection, &[], limit).await;
+            };
+            // The filtered columns may not be projected
+            let plan = self.inner.scan(state, None, &[], None).await?;
+            let df_schema =
+                datafusion::common::DFSchema::try_from(self.schema().as_ref().clone())?;
+            let predicate = state.create_physical_expr(predicate, &df_schema)?;
+            Ok(Arc::new(
+                datafusion::physical_plan::filter::FilterExec::try_new(predicate, plan)?
+                    .with_projection(projection.cloned())?,
+            ))
+        }
+
+        fn supports_filters_pushdown(
+            &self,
+            filters: &[&Expr],
+        ) -> Result<Vec<TableProviderFilterPushDown>> {
+            Ok(vec![TableProviderFilterPushDown::Exact; filters.len()])
+        }
+    }
+
+    fn recording_provider(
+        panic_on_scan: bool,
+    ) -> Result<(RecordingProvider, Arc<std::sync::Mutex<Option<ScanArgs>>>)> {
+        use arrow::datatypes::{DataType, Field};
+        use datafusion::arrow::array::{Float32Array, Int32Array, RecordBatch};
+        use datafusion::datasource::MemTable;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Float32, false),
+            Field::new("b", DataType::Int32, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(Float32Array::from(vec![2.0, 4.0, 8.0])),
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+            ],
+        )?;
+        let scanned = Arc::new(std::sync::Mutex::new(None));
+        let provider = RecordingProvider {
+            inner: Arc::new(MemTable::try_new(schema, vec![vec![batch]])?),
+            scanned: Arc::clone(&scanned),
+            panic_on_scan,
+        };
+        Ok((provider, scanned))
+    }
+
+    #[tokio::test]
+    async fn test_pushdown_reaches_foreign_provider() -> Result<()> {
+        use arrow::datatypes::DataType;
+        use datafusion::common::assert_batches_eq;
+        use datafusion::prelude::cast;
+
+        let (provider, scanned) = recording_provider(false)?;
+        let ffi_provider = FFI_TableProvider::new(Arc::new(provider), true, None);
+        let foreign_table_provider: ForeignTableProvider = (&ffi_provider).into();
+
+        let ctx = SessionContext::new();
+        ctx.register_table("t", Arc::new(foreign_table_provider))?;
+
+        let result = ctx
+            .sql("SELECT b FROM t WHERE a > 3.0 LIMIT 1")
+            .await?
+            .collect()
+            .await?;
+        #[rustfmt::skip]
+        let expected = [
+            "+---+",
+            "| b |",
+            "+---+",
+            "| 2 |",
+            "+---+"
+        ];
+        assert_batches_eq!(expected, &result);
+        assert_eq!(
+            scanned.lock().unwrap().take(),
+            Some(ScanArgs {
+                projection: Some(vec![1]),
+                filters: vec![cast(col("a"), DataType::Float64).gt(lit(3.0))],
+                limit: Some(1),
+            })
+        );
+
+        // All the columns are scanned without a projection, and none with an
+        // empty one
+        let state = ctx.state();
+        let foreign_table_provider: ForeignTableProvider = (&ffi_provider).into();
+        foreign_table_provider.scan(&state, None, &[], None).await?;
+        assert_eq!(scanned.lock().unwrap().take().unwrap().projection, None);
+        foreign_table_provider
+            .scan(&state, Some(&vec![]), &[], None)
+            .await?;
+        assert_eq!(
+            scanned.lock().unwrap().take().unwrap().projection,
+            Some(vec![])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_foreign_panic_is_error() -> Result<()> {
+        let (provider, _) = recording_provider(true)?;
+        let ffi_provider = FFI_TableProvider::new(Arc::new(provider), true, None);
+        let foreign_table_provider: ForeignTableProvider = (&ffi_provider).into();
+
+        let ctx = SessionContext::new();
+        ctx.register_table("t", Arc::new(foreign_table_provider))?;
+
+        let err = ctx
+            .sql("SELECT * FROM t")
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Panic across the FFI boundary: scan of RecordingProvider"),
+            "{err}"
+        );
+        Ok(())
+    }
 }
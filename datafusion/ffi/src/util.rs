@@ -16,10 +16,14 @@
 // under the License.
 
 use crate::arrow_wrappers::WrappedSchema;
-use abi_stable::std_types::RVec;
+use abi_stable::std_types::{RResult, RString, RVec};
 use arrow::datatypes::Field;
 use arrow::{datatypes::DataType, ffi::FFI_ArrowSchema};
 use arrow_schema::FieldRef;
+use futures::FutureExt;
+use std::any::Any;
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::Arc;
 
 /// This macro is a helpful conversion utility to convert from an abi_stable::RResult to a
@@ -66,6 +70,34 @@ macro_rules! rresult_return {
     };
 }
 
+/// Calls `f`, returning its panic as an error rather than letting it unwind
+/// across the FFI boundary, which would abort the process.
+pub fn catch_panic<T>(f: impl FnOnce() -> RResult<T, RString>) -> RResult<T, RString> {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| panic_error(payload))
+}
+
+/// Awaits `fut`, returning its panic as an error like [`catch_panic`], for
+/// the futures polled across the FFI boundary, which would otherwise panic
+/// again when they are awaited by the foreign library.
+pub async fn catch_panic_async<T>(
+    fut: impl Future<Output = RResult<T, RString>>,
+) -> RResult<T, RString> {
+    AssertUnwindSafe(fut)
+        .catch_unwind()
+        .await
+        .unwrap_or_else(|payload| panic_error(payload))
+}
+
+/// Returns the error reported for a panic with `payload`
+pub(crate) fn panic_error<T>(payload: Box<dyn Any + Send>) -> RResult<T, RString> {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    RResult::RErr(format!("Panic across the FFI boundary: {message}").into())
+}
+
 /// This is a utility function to convert a slice of [`Field`] to its equivalent
 /// FFI friendly counterpart, [`WrappedSchema`]
 pub fn vec_fieldref_to_rvec_wrapped(
@@ -0,0 +1,830 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`EagerAggregation`] pre-aggregates one input of an inner join below the join
+use std::sync::Arc;
+
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use arrow::datatypes::DataType;
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::{get_target_functional_dependencies, Column, DFSchema, Result};
+use datafusion_expr::logical_plan::{Aggregate, Join, JoinType, LogicalPlan, Projection};
+use datafusion_expr::{cast, AggregateUDF, Expr, ExprSchemable};
+
+/// Only pre-aggregate when statistics estimate that the number of groups is
+/// at most `1 / MIN_REDUCTION_FACTOR` of the number of input rows
+const MIN_REDUCTION_FACTOR: usize = 2;
+
+/// Optimizer rule that computes aggregates below an inner join ("eager
+/// aggregation"), so that the join processes one row per group instead of
+/// every input row.
+///
+/// For example
+///
+/// ```text
+/// SELECT d.name, SUM(f.amount) FROM fact f JOIN dim d ON f.d_id = d.id GROUP BY d.name
+/// ```
+///
+/// is rewritten to
+///
+/// ```text
+/// SELECT d.name, SUM(f.sum_amount)
+/// FROM (SELECT d_id, SUM(amount) AS sum_amount FROM fact GROUP BY d_id) f
+/// JOIN dim d ON f.d_id = d.id
+/// GROUP BY d.name
+/// ```
+///
+/// All rows of a pre-aggregated group have the same join keys and thus join
+/// with the same rows of the other input, which makes the rewrite correct for
+/// `SUM`, `COUNT`, `MIN` and `MAX`. `AVG` is split into a `SUM` and a `COUNT`.
+///
+/// The rule applies when
+/// - it is enabled with `datafusion.optimizer.enable_eager_aggregation`,
+/// - all aggregate arguments come from one input of the join (the "fact"
+///   side) and the join keys of the other input functionally determine its
+///   grouping columns (e.g. the keys are the primary key of a dimension table),
+/// - the aggregate has grouping columns,
+/// - statistics indicate that grouping the fact side by its join keys and
+///   grouping columns reduces its number of rows.
+#[derive(Default, Debug)]
+pub struct EagerAggregation {}
+
+impl EagerAggregation {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for EagerAggregation {
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        if !config.options().optimizer.enable_eager_aggregation {
+            return Ok(Transformed::no(plan));
+        }
+
+        let LogicalPlan::Aggregate(aggregate) = plan else {
+            return Ok(Transformed::no(plan));
+        };
+
+        match try_eager_aggregation(&aggregate, config)? {
+            Some(new_plan) => Ok(Transformed::yes(new_plan)),
+            None => Ok(Transformed::no(LogicalPlan::Aggregate(aggregate))),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "eager_aggregation"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+}
+
+/// The aggregate functions supported by the rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EagerFunction {
+    Sum,
+    Count,
+    Min,
+    Max,
+    Avg,
+}
+
+impl EagerFunction {
+    fn try_new(func: &AggregateUDF) -> Option<Self> {
+        match func.name() {
+            "sum" => Some(Self::Sum),
+            "count" => Some(Self::Count),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "avg" => Some(Self::Avg),
+            _ => None,
+        }
+    }
+}
+
+/// Rewrites `aggregate` if its input is an inner join that one side of can
+/// be pre-aggregated, returning `None` otherwise.
+fn try_eager_aggregation(
+    aggregate: &Aggregate,
+    config: &dyn OptimizerConfig,
+) -> Result<Option<LogicalPlan>> {
+    let LogicalPlan::Join(join) = aggregate.input.as_ref() else {
+        return Ok(None);
+    };
+    // Without grouping, the aggregate of an empty join is one row, whose
+    // `COUNT` re-aggregated with `SUM` would be null instead of 0
+    if join.join_type != JoinType::Inner
+        || join.filter.is_some()
+        || join.on.is_empty()
+        || aggregate.group_expr.is_empty()
+        || aggregate.aggr_expr.is_empty()
+    {
+        return Ok(None);
+    }
+
+    // Collect the supported aggregate functions and the columns they reference
+    let mut functions = Vec::with_capacity(aggregate.aggr_expr.len());
+    let mut aggr_columns = vec![];
+    for expr in &aggregate.aggr_expr {
+        let Expr::AggregateFunction(function) = expr.clone().unalias() else {
+            return Ok(None);
+        };
+        let Some(kind) = EagerFunction::try_new(&function.func) else {
+            return Ok(None);
+        };
+        let params = &function.params;
+        if params.distinct
+            || params.filter.is_some()
+            || params.order_by.is_some()
+            || params.args.iter().any(|arg| arg.is_volatile())
+        {
+            return Ok(None);
+        }
+        for arg in &params.args {
+            aggr_columns.extend(arg.column_refs().into_iter().cloned());
+        }
+        functions.push((kind, function));
+    }
+
+    // The fact side is the join input providing all aggregate arguments
+    let fact_is_left = if aggr_columns.is_empty() {
+        return Ok(None);
+    } else if aggr_columns
+        .iter()
+        .all(|c| join.left.schema().has_column(c))
+    {
+        true
+    } else if aggr_columns
+        .iter()
+        .all(|c| join.right.schema().has_column(c))
+    {
+        false
+    } else {
+        return Ok(None);
+    };
+    let (fact, other) = if fact_is_left {
+        (&join.left, &join.right)
+    } else {
+        (&join.right, &join.left)
+    };
+
+    // Avoid pre-aggregating an input which already is an aggregate, in
+    // particular one created by a previous application of this rule
+    if is_aggregate(fact) {
+        return Ok(None);
+    }
+
+    let mut fact_keys = vec![];
+    let mut other_keys = vec![];
+    for (left_key, right_key) in &join.on {
+        let (fact_key, other_key) = if fact_is_left {
+            (left_key, right_key)
+        } else {
+            (right_key, left_key)
+        };
+        match (fact_key, other_key) {
+            (Expr::Column(fact_key), Expr::Column(other_key)) => {
+                fact_keys.push(fact_key.clone());
+                other_keys.push(other_key.clone());
+            }
+            _ => return Ok(None),
+        }
+    }
+
+    // Split the grouping columns by join input
+    let mut fact_group_columns = fact_keys.clone();
+    let mut other_group_columns = vec![];
+    for expr in &aggregate.group_expr {
+        let Expr::Column(column) = expr else {
+            return Ok(None);
+        };
+        if fact.schema().has_column(column) {
+            if !fact_group_columns.contains(column) {
+                fact_group_columns.push(column.clone());
+            }
+        } else if other.schema().has_column(column) {
+            other_group_columns.push(column.clone());
+        } else {
+            return Ok(None);
+        }
+    }
+
+    if !keys_determine_columns(other.schema(), &other_keys, &other_group_columns) {
+        return Ok(None);
+    }
+    if estimate_reduction(fact, &fact_group_columns) != Some(true) {
+        return Ok(None);
+    }
+
+    // `COUNT` is re-aggregated with `SUM`, which is looked up in the registry
+    let needs_sum_and_count = functions
+        .iter()
+        .any(|(kind, _)| matches!(kind, EagerFunction::Count | EagerFunction::Avg));
+    let registry_udafs = if needs_sum_and_count {
+        let Some(registry) = config.function_registry() else {
+            return Ok(None);
+        };
+        match (registry.udaf("sum"), registry.udaf("count")) {
+            (Ok(sum), Ok(count)) => Some((sum, count)),
+            _ => return Ok(None),
+        }
+    } else {
+        None
+    };
+
+    let alias_generator = config.alias_generator();
+    let mut partial_aggr_expr = vec![];
+    let mut final_aggr_expr = vec![];
+    // For each original aggregate, the expression computing it from the
+    // output of the final aggregate
+    let mut output_expr = vec![];
+    for (i, (kind, function)) in functions.into_iter().enumerate() {
+        let (original_qualifier, original_field) = aggregate
+            .schema
+            .qualified_field(aggregate.group_expr.len() + i);
+        let original_type = original_field.data_type().clone();
+
+        let mut push_partial = |partial: Expr, final_func: &Arc<AggregateUDF>| {
+            let partial_alias = alias_generator.next("__eager_agg");
+            let final_alias = alias_generator.next("__eager_agg");
+            partial_aggr_expr.push(partial.alias(&partial_alias));
+            final_aggr_expr.push(
+                final_func
+                    .call(vec![Expr::Column(Column::from_name(partial_alias))])
+                    .alias(&final_alias),
+            );
+            Expr::Column(Column::from_name(final_alias))
+        };
+
+        let expr = match kind {
+            EagerFunction::Sum | EagerFunction::Min | EagerFunction::Max => {
+                let func = Arc::clone(&function.func);
+                push_partial(Expr::AggregateFunction(function), &func)
+            }
+            EagerFunction::Count => {
+                let (sum, _) = registry_udafs.as_ref().unwrap();
+                push_partial(Expr::AggregateFunction(function), sum)
+            }
+            EagerFunction::Avg => {
+                // Only `AVG`s computed as floating point division are supported
+                if original_type != DataType::Float64 {
+                    return Ok(None);
+                }
+                let (sum, count) = registry_udafs.as_ref().unwrap();
+                let args = function.params.args;
+                let total = push_partial(sum.call(args.clone()), sum);
+                let rows = push_partial(count.call(args), sum);
+                cast(total, DataType::Float64) / cast(rows, DataType::Float64)
+            }
+        };
+        output_expr.push((expr, original_qualifier.cloned(), original_field.name()));
+    }
+
+    let partial_aggregate = LogicalPlan::Aggregate(Aggregate::try_new(
+        Arc::clone(fact),
+        fact_group_columns.into_iter().map(Expr::Column).collect(),
+        partial_aggr_expr,
+    )?);
+    let (left, right) = if fact_is_left {
+        (Arc::new(partial_aggregate), Arc::clone(&join.right))
+    } else {
+        (Arc::clone(&join.left), Arc::new(partial_aggregate))
+    };
+    let new_join = LogicalPlan::Join(Join::try_new(
+        left,
+        right,
+        join.on.clone(),
+        None,
+        join.join_type,
+        join.join_constraint,
+        join.null_equality,
+    )?);
+    let final_aggregate = LogicalPlan::Aggregate(Aggregate::try_new(
+        Arc::new(new_join),
+        aggregate.group_expr.clone(),
+        final_aggr_expr,
+    )?);
+
+    // Restore the original output schema, as the re-aggregation may widen
+    // types (e.g. `SUM` of a decimal `SUM`)
+    let group_output = (0..aggregate.group_expr.len())
+        .map(|i| Expr::Column(Column::from(aggregate.schema.qualified_field(i))));
+    let mut projection_expr = group_output.collect::<Vec<_>>();
+    for (expr, qualifier, name) in output_expr {
+        let (data_type, _) = expr.data_type_and_nullable(final_aggregate.schema())?;
+        let original_type = aggregate
+            .schema
+            .field_with_name(qualifier.as_ref(), name)?
+            .data_type();
+        let expr = if &data_type == original_type {
+            expr
+        } else {
+            cast(expr, original_type.clone())
+        };
+        projection_expr.push(expr.alias_qualified(qualifier, name));
+    }
+
+    Ok(Some(LogicalPlan::Projection(Projection::try_new(
+        projection_expr,
+        Arc::new(final_aggregate),
+    )?)))
+}
+
+/// Returns true if `plan` is an aggregate, possibly below projections,
+/// filters or aliases
+fn is_aggregate(plan: &LogicalPlan) -> bool {
+    match plan {
+        LogicalPlan::Aggregate(_) => true,
+        LogicalPlan::Projection(Projection { input, .. })
+        | LogicalPlan::Filter(datafusion_expr::Filter { input, .. })
+        | LogicalPlan::SubqueryAlias(datafusion_expr::SubqueryAlias { input, .. }) => {
+            is_aggregate(input)
+        }
+        _ => false,
+    }
+}
+
+/// Returns true if the functional dependencies of `schema` show that
+/// `keys` determine all of `columns`
+fn keys_determine_columns(
+    schema: &DFSchema,
+    keys: &[Column],
+    columns: &[Column],
+) -> bool {
+    let undetermined = columns
+        .iter()
+        .filter(|column| !keys.contains(column))
+        .collect::<Vec<_>>();
+    if undetermined.is_empty() {
+        return true;
+    }
+
+    let key_names = keys.iter().map(|key| key.flat_name()).collect::<Vec<_>>();
+    let Some(targets) = get_target_functional_dependencies(schema, &key_names) else {
+        return false;
+    };
+    undetermined.into_iter().all(|column| {
+        schema
+            .index_of_column(column)
+            .is_ok_and(|index| targets.contains(&index))
+    })
+}
+
+/// Estimates whether grouping `plan` by `group_columns` reduces its number
+/// of rows by at least [`MIN_REDUCTION_FACTOR`], using the statistics of the
+/// underlying table.
+///
+/// Returns `None` if the statistics required for the estimate are not
+/// available.
+fn estimate_reduction(plan: &LogicalPlan, group_columns: &[Column]) -> Option<bool> {
+    match plan {
+        LogicalPlan::Filter(datafusion_expr::Filter { input, .. })
+        | LogicalPlan::SubqueryAlias(datafusion_expr::SubqueryAlias { input, .. }) => {
+            estimate_reduction(input, group_columns)
+        }
+        LogicalPlan::TableScan(scan) => {
+            let statistics = scan.source.statistics()?;
+            let num_rows = *statistics.num_rows.get_value()?;
+            let schema = scan.source.schema();
+            let mut num_groups: usize = 1;
+            for column in group_columns {
+                let index = schema.index_of(&column.name).ok()?;
+                let distinct_count = *statistics
+                    .column_statistics
+                    .get(index)?
+                    .distinct_count
+                    .get_value()?;
+                num_groups = num_groups.saturating_mul(distinct_count);
+            }
+            let num_groups = num_groups.min(num_rows);
+            Some(num_groups.saturating_mul(MIN_REDUCTION_FACTOR) <= num_rows)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::assert_optimized_plan_eq_snapshot;
+
+    use arrow::datatypes::{Field, Schema, SchemaRef};
+    use chrono::{DateTime, Utc};
+    use datafusion_common::alias::AliasGenerator;
+    use datafusion_common::config::ConfigOptions;
+    use datafusion_common::stats::Precision;
+    use datafusion_common::{
+        plan_err, ColumnStatistics, Constraint, Constraints, Statistics,
+    };
+    use datafusion_expr::planner::ExprPlanner;
+    use datafusion_expr::registry::FunctionRegistry;
+    use datafusion_expr::{col, LogicalPlanBuilder, ScalarUDF, TableSource, WindowUDF};
+    use datafusion_functions_aggregate::average::avg;
+    use datafusion_functions_aggregate::count::{count, count_udaf};
+    use datafusion_functions_aggregate::min_max::{max, min};
+    use datafusion_functions_aggregate::sum::{sum, sum_udaf};
+
+    macro_rules! assert_optimized_plan_equal {
+        (
+            $config:expr,
+            $plan:expr,
+            @ $expected:literal $(,)?
+        ) => {{
+            let rules: Vec<Arc<dyn crate::OptimizerRule + Send + Sync>> =
+                vec![Arc::new(EagerAggregation::new())];
+            assert_optimized_plan_eq_snapshot!($config, rules, $plan, @ $expected,)
+        }};
+    }
+
+    /// An [`OptimizerConfig`] with eager aggregation enabled and a registry
+    /// providing the aggregate functions used by the rule
+    struct TestConfig {
+        alias_generator: Arc<AliasGenerator>,
+        options: ConfigOptions,
+    }
+
+    impl TestConfig {
+        fn new(enabled: bool) -> Self {
+            let mut options = ConfigOptions::default();
+            options.optimizer.max_passes = 1;
+            options.optimizer.skip_failed_rules = false;
+            options.optimizer.enable_eager_aggregation = enabled;
+            Self {
+                alias_generator: Arc::new(AliasGenerator::new()),
+                options,
+            }
+        }
+    }
+
+    impl OptimizerConfig for TestConfig {
+        fn query_execution_start_time(&self) -> DateTime<Utc> {
+            Utc::now()
+        }
+
+        fn alias_generator(&self) -> &Arc<AliasGenerator> {
+            &self.alias_generator
+        }
+
+        fn options(&self) -> &ConfigOptions {
+            &self.options
+        }
+
+        fn function_registry(&self) -> Option<&dyn FunctionRegistry> {
+            Some(self)
+        }
+    }
+
+    impl FunctionRegistry for TestConfig {
+        fn udfs(&self) -> HashSet<String> {
+            HashSet::new()
+        }
+
+        fn udf(&self, name: &str) -> Result<Arc<ScalarUDF>> {
+            plan_err!("no function {name}")
+        }
+
+        fn udaf(&self, name: &str) -> Result<Arc<AggregateUDF>> {
+            match name {
+                "sum" => Ok(sum_udaf()),
+                "count" => Ok(count_udaf()),
+                _ => plan_err!("no function {name}"),
+            }
+        }
+
+        fn udwf(&self, name: &str) -> Result<Arc<WindowUDF>> {
+            plan_err!("no function {name}")
+        }
+
+        fn expr_planners(&self) -> Vec<Arc<dyn ExprPlanner>> {
+            vec![]
+        }
+    }
+
+    /// A table with optional constraints and statistics
+    struct TestSource {
+        schema: SchemaRef,
+        constraints: Constraints,
+        statistics: Option<Statistics>,
+    }
+
+    impl TableSource for TestSource {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            Arc::clone(&self.schema)
+        }
+
+        fn constraints(&self) -> Option<&Constraints> {
+            Some(&self.constraints)
+        }
+
+        fn statistics(&self) -> Option<Statistics> {
+            self.statistics.clone()
+        }
+    }
+
+    fn scan(name: &str, source: TestSource) -> Result<LogicalPlanBuilder> {
+        LogicalPlanBuilder::scan(name, Arc::new(source), None)
+    }
+
+    /// `fact(d_id, o_id, amount, price)`, optionally with statistics
+    /// reporting `num_rows` rows and `distinct_ids` distinct `d_id`s and
+    /// `o_id`s
+    fn fact(statistics: Option<(usize, usize)>) -> Result<LogicalPlanBuilder> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("d_id", DataType::Int32, false),
+            Field::new("o_id", DataType::Int32, false),
+            Field::new("amount", DataType::Int64, true),
+            Field::new("price", DataType::Decimal128(10, 2), true),
+        ]));
+        let statistics = statistics.map(|(num_rows, distinct_ids)| {
+            let mut statistics = Statistics::new_unknown(&schema);
+            statistics.num_rows = Precision::Exact(num_rows);
+            for column_statistics in &mut statistics.column_statistics[..2] {
+                *column_statistics = ColumnStatistics::new_unknown()
+                    .with_distinct_count(Precision::Inexact(distinct_ids));
+            }
+            statistics
+        });
+        scan(
+            "fact",
+            TestSource {
+                schema,
+                constraints: Constraints::default(),
+                statistics,
+            },
+        )
+    }
+
+    /// `dim(id, name)`, with `id` as primary key if `primary_key` is set
+    fn dim(primary_key: bool) -> Result<LogicalPlanBuilder> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let constraints = if primary_key {
+            Constraints::new_unverified(vec![Constraint::PrimaryKey(vec![0])])
+        } else {
+            Constraints::default()
+        };
+        scan(
+            "dim",
+            TestSource {
+                schema,
+                constraints,
+                statistics: None,
+            },
+        )
+    }
+
+    fn fact_join_dim(
+        fact: LogicalPlanBuilder,
+        dim: LogicalPlanBuilder,
+    ) -> Result<LogicalPlanBuilder> {
+        fact.join(
+            dim.build()?,
+            JoinType::Inner,
+            (vec!["fact.d_id"], vec!["dim.id"]),
+            None,
+        )
+    }
+
+    #[test]
+    fn sum_below_join() -> Result<()> {
+        let plan = fact_join_dim(fact(Some((1000, 10)))?, dim(true)?)?
+            .aggregate(vec![col("dim.name")], vec![sum(col("fact.amount"))])?
+            .build()?;
+
+        assert_optimized_plan_equal!(TestConfig::new(true), plan, @r"
+        Projection: dim.name, __eager_agg_2 AS sum(fact.amount)
+          Aggregate: groupBy=[[dim.name]], aggr=[[sum(__eager_agg_1) AS __eager_agg_2]]
+            Inner Join: fact.d_id = dim.id
+              Aggregate: groupBy=[[fact.d_id]], aggr=[[sum(fact.amount) AS __eager_agg_1]]
+                TableScan: fact
+              TableScan: dim
+        ")
+    }
+
+    #[test]
+    fn disabled_by_default() -> Result<()> {
+        let plan = fact_join_dim(fact(Some((1000, 10)))?, dim(true)?)?
+            .aggregate(vec![col("dim.name")], vec![sum(col("fact.amount"))])?
+            .build()?;
+
+        assert_optimized_plan_equal!(TestConfig::new(false), plan, @r"
+        Aggregate: groupBy=[[dim.name]], aggr=[[sum(fact.amount)]]
+          Inner Join: fact.d_id = dim.id
+            TableScan: fact
+            TableScan: dim
+        ")
+    }
+
+    #[test]
+    fn fact_on_right_side() -> Result<()> {
+        let plan = dim(true)?
+            .join(
+                fact(Some((1000, 10)))?.build()?,
+                JoinType::Inner,
+                (vec!["dim.id"], vec!["fact.d_id"]),
+                None,
+            )?
+            .aggregate(
+                vec![col("dim.name")],
+                vec![min(col("fact.amount")), max(col("fact.amount"))],
+            )?
+            .build()?;
+
+        assert_optimized_plan_equal!(TestConfig::new(true), plan, @r"
+        Projection: dim.name, __eager_agg_2 AS min(fact.amount), __eager_agg_4 AS max(fact.amount)
+          Aggregate: groupBy=[[dim.name]], aggr=[[min(__eager_agg_1) AS __eager_agg_2, max(__eager_agg_3) AS __eager_agg_4]]
+            Inner Join: dim.id = fact.d_id
+              TableScan: dim
+              Aggregate: groupBy=[[fact.d_id]], aggr=[[min(fact.amount) AS __eager_agg_1, max(fact.amount) AS __eager_agg_3]]
+                TableScan: fact
+        ")
+    }
+
+    #[test]
+    fn count_and_avg_below_join() -> Result<()> {
+        let plan = fact_join_dim(fact(Some((1000, 10)))?, dim(true)?)?
+            .aggregate(
+                vec![col("dim.name")],
+                vec![
+                    count(col("fact.amount")),
+                    avg(cast(col("fact.amount"), DataType::Float64)),
+                ],
+            )?
+            .build()?;
+
+        assert_optimized_plan_equal!(TestConfig::new(true), plan, @r"
+        Projection: dim.name, __eager_agg_2 AS count(fact.amount), CAST(__eager_agg_4 AS Float64) / CAST(__eager_agg_6 AS Float64) AS avg(fact.amount)
+          Aggregate: groupBy=[[dim.name]], aggr=[[sum(__eager_agg_1) AS __eager_agg_2, sum(__eager_agg_3) AS __eager_agg_4, sum(__eager_agg_5) AS __eager_agg_6]]
+            Inner Join: fact.d_id = dim.id
+              Aggregate: groupBy=[[fact.d_id]], aggr=[[count(fact.amount) AS __eager_agg_1, sum(CAST(fact.amount AS Float64)) AS __eager_agg_3, count(CAST(fact.amount AS Float64)) AS __eager_agg_5]]
+                TableScan: fact
+              TableScan: dim
+        ")
+    }
+
+    #[test]
+    fn decimal_sum_keeps_output_type() -> Result<()> {
+        let plan = fact_join_dim(fact(Some((1000, 10)))?, dim(true)?)?
+            .aggregate(vec![col("dim.name")], vec![sum(col("fact.price"))])?
+            .build()?;
+
+        assert_optimized_plan_equal!(TestConfig::new(true), plan, @r"
+        Projection: dim.name, CAST(__eager_agg_2 AS Decimal128(20, 2)) AS sum(fact.price)
+          Aggregate: groupBy=[[dim.name]], aggr=[[sum(__eager_agg_1) AS __eager_agg_2]]
+            Inner Join: fact.d_id = dim.id
+              Aggregate: groupBy=[[fact.d_id]], aggr=[[sum(fact.price) AS __eager_agg_1]]
+                TableScan: fact
+              TableScan: dim
+        ")
+    }
+
+    #[test]
+    fn group_by_columns_of_both_sides() -> Result<()> {
+        let plan = fact_join_dim(fact(Some((1000, 10)))?, dim(true)?)?
+            .aggregate(
+                vec![col("fact.o_id"), col("dim.name")],
+                vec![sum(col("fact.amount"))],
+            )?
+            .build()?;
+
+        assert_optimized_plan_equal!(TestConfig::new(true), plan, @r"
+        Projection: fact.o_id, dim.name, __eager_agg_2 AS sum(fact.amount)
+          Aggregate: groupBy=[[fact.o_id, dim.name]], aggr=[[sum(__eager_agg_1) AS __eager_agg_2]]
+            Inner Join: fact.d_id = dim.id
+              Aggregate: groupBy=[[fact.d_id, fact.o_id]], aggr=[[sum(fact.amount) AS __eager_agg_1]]
+                TableScan: fact
+              TableScan: dim
+        ")
+    }
+
+    #[test]
+    fn group_column_not_determined_by_join_key() -> Result<()> {
+        // without a primary key, `dim.id` does not determine `dim.name`
+        let plan = fact_join_dim(fact(Some((1000, 10)))?, dim(false)?)?
+            .aggregate(vec![col("dim.name")], vec![sum(col("fact.amount"))])?
+            .build()?;
+
+        assert_optimized_plan_equal!(TestConfig::new(true), plan, @r"
+        Aggregate: groupBy=[[dim.name]], aggr=[[sum(fact.amount)]]
+          Inner Join: fact.d_id = dim.id
+            TableScan: fact
+            TableScan: dim
+        ")
+    }
+
+    #[test]
+    fn aggregate_arguments_from_both_sides() -> Result<()> {
+        let plan = fact_join_dim(fact(Some((1000, 10)))?, dim(true)?)?
+            .aggregate(
+                vec![col("dim.name")],
+                vec![sum(col("fact.amount")), max(col("dim.id"))],
+            )?
+            .build()?;
+
+        assert_optimized_plan_equal!(TestConfig::new(true), plan, @r"
+        Aggregate: groupBy=[[dim.name]], aggr=[[sum(fact.amount), max(dim.id)]]
+          Inner Join: fact.d_id = dim.id
+            TableScan: fact
+            TableScan: dim
+        ")
+    }
+
+    #[test]
+    fn outer_join_is_not_rewritten() -> Result<()> {
+        let plan = fact(Some((1000, 10)))?
+            .join(
+                dim(true)?.build()?,
+                JoinType::Left,
+                (vec!["fact.d_id"], vec!["dim.id"]),
+                None,
+            )?
+            .aggregate(vec![col("dim.name")], vec![sum(col("fact.amount"))])?
+            .build()?;
+
+        assert_optimized_plan_equal!(TestConfig::new(true), plan, @r"
+        Aggregate: groupBy=[[dim.name]], aggr=[[sum(fact.amount)]]
+          Left Join: fact.d_id = dim.id
+            TableScan: fact
+            TableScan: dim
+        ")
+    }
+
+    #[test]
+    fn ungrouped_aggregate_is_not_rewritten() -> Result<()> {
+        // `COUNT` re-aggregated with `SUM` would be null for an empty join
+        let plan = fact_join_dim(fact(Some((1000, 10)))?, dim(true)?)?
+            .aggregate(Vec::<Expr>::new(), vec![count(col("fact.amount"))])?
+            .build()?;
+
+        assert_optimized_plan_equal!(TestConfig::new(true), plan, @r"
+        Aggregate: groupBy=[[]], aggr=[[count(fact.amount)]]
+          Inner Join: fact.d_id = dim.id
+            TableScan: fact
+            TableScan: dim
+        ")
+    }
+
+    #[test]
+    fn no_statistics_is_not_rewritten() -> Result<()> {
+        let plan = fact_join_dim(fact(None)?, dim(true)?)?
+            .aggregate(vec![col("dim.name")], vec![sum(col("fact.amount"))])?
+            .build()?;
+
+        assert_optimized_plan_equal!(TestConfig::new(true), plan, @r"
+        Aggregate: groupBy=[[dim.name]], aggr=[[sum(fact.amount)]]
+          Inner Join: fact.d_id = dim.id
+            TableScan: fact
+            TableScan: dim
+        ")
+    }
+
+    #[test]
+    fn statistics_show_no_reduction() -> Result<()> {
+        // every `d_id` is distinct, pre-aggregating would not reduce the rows
+        let plan = fact_join_dim(fact(Some((1000, 1000)))?, dim(true)?)?
+            .aggregate(vec![col("dim.name")], vec![sum(col("fact.amount"))])?
+            .build()?;
+
+        assert_optimized_plan_equal!(TestConfig::new(true), plan, @r"
+        Aggregate: groupBy=[[dim.name]], aggr=[[sum(fact.amount)]]
+          Inner Join: fact.d_id = dim.id
+            TableScan: fact
+            TableScan: dim
+        ")
+    }
+}
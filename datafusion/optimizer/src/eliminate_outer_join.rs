@@ -19,9 +19,11 @@
 use crate::{OptimizerConfig, OptimizerRule};
 use datafusion_common::{Column, DFSchema, Result};
 use datafusion_expr::logical_plan::{Join, JoinType, LogicalPlan};
+use datafusion_expr::utils::split_conjunction;
 use datafusion_expr::{Expr, Filter, Operator};
 
 use crate::optimizer::ApplyOrder;
+use crate::utils::is_restrict_null_predicate;
 use datafusion_common::tree_node::Transformed;
 use datafusion_expr::expr::{BinaryExpr, Cast, TryCast};
 use std::sync::Arc;
@@ -48,6 +50,10 @@ use std::sync::Arc;
 /// filters from the WHERE clause return false while any inputs are
 /// null and columns of those quals are come from nullable side of
 /// outer join.
+///
+/// `FULL` joins are downgraded to `LEFT`, `RIGHT` or `INNER` joins
+/// depending on which of the inputs have their null-extended rows
+/// rejected by the filter.
 #[derive(Default, Debug)]
 pub struct EliminateOuterJoin;
 
@@ -80,27 +86,13 @@ impl OptimizerRule for EliminateOuterJoin {
         match plan {
             LogicalPlan::Filter(mut filter) => match Arc::unwrap_or_clone(filter.input) {
                 LogicalPlan::Join(join) => {
-                    let mut non_nullable_cols: Vec<Column> = vec![];
-
-                    extract_non_nullable_columns(
-                        &filter.predicate,
-                        &mut non_nullable_cols,
-                        join.left.schema(),
-                        join.right.schema(),
-                        true,
-                    );
-
                     let new_join_type = if join.join_type.is_outer() {
-                        let mut left_non_nullable = false;
-                        let mut right_non_nullable = false;
-                        for col in non_nullable_cols.iter() {
-                            if join.left.schema().has_column(col) {
-                                left_non_nullable = true;
-                            }
-                            if join.right.schema().has_column(col) {
-                                right_non_nullable = true;
-                            }
-                        }
+                        let (left_non_nullable, right_non_nullable) =
+                            null_rejected_inputs(
+                                &filter.predicate,
+                                join.left.schema(),
+                                join.right.schema(),
+                            );
                         eliminate_outer(
                             join.join_type,
                             left_non_nullable,
@@ -164,6 +156,64 @@ pub fn eliminate_outer(
     new_join_type
 }
 
+/// Returns whether `predicate` rejects the null-extended rows of the left
+/// and right join inputs respectively, i.e. whether it evaluates to `false`
+/// or `NULL` whenever all the columns of that input are `NULL`.
+///
+/// Each conjunct that only references columns of a single input is evaluated
+/// with those columns set to `NULL`. This covers arbitrary null-propagating
+/// expressions (e.g. `b.v + 1 > 10`) and correctly treats expressions such as
+/// `COALESCE(b.v, 20) > 10` or `b.v IS NOT DISTINCT FROM NULL`, which are true
+/// for null inputs, as not null-rejecting. Conjuncts referencing both inputs
+/// fall back to [`extract_non_nullable_columns`].
+fn null_rejected_inputs(
+    predicate: &Expr,
+    left_schema: &Arc<DFSchema>,
+    right_schema: &Arc<DFSchema>,
+) -> (bool, bool) {
+    let mut left_non_nullable = false;
+    let mut right_non_nullable = false;
+    for conjunct in split_conjunction(predicate) {
+        // the result of a volatile expression for null inputs can not be
+        // determined ahead of time
+        if conjunct.is_volatile() {
+            continue;
+        }
+
+        let columns = conjunct.column_refs();
+        if columns.is_empty() {
+            continue;
+        }
+        if columns.iter().all(|col| left_schema.has_column(col)) {
+            left_non_nullable |=
+                is_restrict_null_predicate(conjunct.clone(), columns.iter().copied())
+                    .unwrap_or(false);
+        } else if columns.iter().all(|col| right_schema.has_column(col)) {
+            right_non_nullable |=
+                is_restrict_null_predicate(conjunct.clone(), columns.iter().copied())
+                    .unwrap_or(false);
+        } else {
+            let mut non_nullable_cols: Vec<Column> = vec![];
+            extract_non_nullable_columns(
+                conjunct,
+                &mut non_nullable_cols,
+                left_schema,
+                right_schema,
+                true,
+            );
+            for col in non_nullable_cols.iter() {
+                if left_schema.has_column(col) {
+                    left_non_nullable = true;
+                }
+                if right_schema.has_column(col) {
+                    right_non_nullable = true;
+                }
+            }
+        }
+    }
+    (left_non_nullable, right_non_nullable)
+}
+
 /// Recursively traverses expr, if expr returns false when
 /// any inputs are null, treats columns of both sides as non_nullable columns.
 ///
@@ -308,11 +358,14 @@ mod tests {
     use crate::test::*;
     use crate::OptimizerContext;
     use arrow::datatypes::DataType;
+    use datafusion_common::ScalarValue;
+    use datafusion_expr::expr::ScalarFunction;
     use datafusion_expr::{
         binary_expr, cast, col, lit,
         logical_plan::builder::LogicalPlanBuilder,
-        try_cast,
-        Operator::{And, Or},
+        not, try_cast, ColumnarValue,
+        Operator::{And, IsDistinctFrom, IsNotDistinctFrom, Or},
+        ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, Signature, Volatility,
     };
 
     macro_rules! assert_optimized_plan_equal {
@@ -462,4 +515,272 @@ mod tests {
             TableScan: t2
         ")
     }
+
+    #[test]
+    fn eliminate_left_with_arithmetic() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        // eliminate to inner join: `t2.b + 1` is null for null-extended rows
+        let plan = LogicalPlanBuilder::from(t1)
+            .join(
+                t2,
+                JoinType::Left,
+                (vec![Column::from_name("a")], vec![Column::from_name("a")]),
+                None,
+            )?
+            .filter((col("t2.b") + lit(1u32)).gt(lit(10u32)))?
+            .build()?;
+
+        assert_optimized_plan_equal!(plan, @r"
+        Filter: t2.b + UInt32(1) > UInt32(10)
+          Inner Join: t1.a = t2.a
+            TableScan: t1
+            TableScan: t2
+        ")
+    }
+
+    #[test]
+    fn eliminate_left_with_is_not_distinct_from_literal() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        // eliminate to inner join: `NULL IS NOT DISTINCT FROM 10` is false
+        let plan = LogicalPlanBuilder::from(t1)
+            .join(
+                t2,
+                JoinType::Left,
+                (vec![Column::from_name("a")], vec![Column::from_name("a")]),
+                None,
+            )?
+            .filter(binary_expr(col("t2.b"), IsNotDistinctFrom, lit(10u32)))?
+            .build()?;
+
+        assert_optimized_plan_equal!(plan, @r"
+        Filter: t2.b IS NOT DISTINCT FROM UInt32(10)
+          Inner Join: t1.a = t2.a
+            TableScan: t1
+            TableScan: t2
+        ")
+    }
+
+    #[test]
+    fn eliminate_left_with_is_not_distinct_from_null() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        // could not eliminate to inner join: `NULL IS NOT DISTINCT FROM NULL`
+        // is true
+        let plan = LogicalPlanBuilder::from(t1)
+            .join(
+                t2,
+                JoinType::Left,
+                (vec![Column::from_name("a")], vec![Column::from_name("a")]),
+                None,
+            )?
+            .filter(binary_expr(
+                col("t2.b"),
+                IsNotDistinctFrom,
+                lit(ScalarValue::UInt32(None)),
+            ))?
+            .build()?;
+
+        assert_optimized_plan_equal!(plan, @r"
+        Filter: t2.b IS NOT DISTINCT FROM UInt32(NULL)
+          Left Join: t1.a = t2.a
+            TableScan: t1
+            TableScan: t2
+        ")
+    }
+
+    #[test]
+    fn eliminate_left_with_is_distinct_from() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        // could not eliminate to inner join: `NULL IS DISTINCT FROM 10` is true
+        let plan = LogicalPlanBuilder::from(t1)
+            .join(
+                t2,
+                JoinType::Left,
+                (vec![Column::from_name("a")], vec![Column::from_name("a")]),
+                None,
+            )?
+            .filter(binary_expr(col("t2.b"), IsDistinctFrom, lit(10u32)))?
+            .build()?;
+
+        assert_optimized_plan_equal!(plan, @r"
+        Filter: t2.b IS DISTINCT FROM UInt32(10)
+          Left Join: t1.a = t2.a
+            TableScan: t1
+            TableScan: t2
+        ")
+    }
+
+    #[test]
+    fn eliminate_left_with_preserved_side_filter() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        // could not eliminate to inner join: the filter only rejects nulls
+        // from the preserved side
+        let plan = LogicalPlanBuilder::from(t1)
+            .join(
+                t2,
+                JoinType::Left,
+                (vec![Column::from_name("a")], vec![Column::from_name("a")]),
+                None,
+            )?
+            .filter(col("t1.b").gt(lit(10u32)))?
+            .build()?;
+
+        assert_optimized_plan_equal!(plan, @r"
+        Filter: t1.b > UInt32(10)
+          Left Join: t1.a = t2.a
+            TableScan: t1
+            TableScan: t2
+        ")
+    }
+
+    #[test]
+    fn eliminate_right_with_null_supplying_side_filter() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        // eliminate to inner join
+        let plan = LogicalPlanBuilder::from(t1)
+            .join(
+                t2,
+                JoinType::Right,
+                (vec![Column::from_name("a")], vec![Column::from_name("a")]),
+                None,
+            )?
+            .filter(col("t1.b").in_list(vec![lit(1u32), lit(2u32)], false))?
+            .build()?;
+
+        assert_optimized_plan_equal!(plan, @r"
+        Filter: t1.b IN ([UInt32(1), UInt32(2)])
+          Inner Join: t1.a = t2.a
+            TableScan: t1
+            TableScan: t2
+        ")
+    }
+
+    #[test]
+    fn eliminate_full_to_left() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        // only the null-extended rows of t1 are rejected, so rows of t1 are
+        // preserved
+        let plan = LogicalPlanBuilder::from(t1)
+            .join(
+                t2,
+                JoinType::Full,
+                (vec![Column::from_name("a")], vec![Column::from_name("a")]),
+                None,
+            )?
+            .filter(col("t1.b").gt(lit(10u32)))?
+            .build()?;
+
+        assert_optimized_plan_equal!(plan, @r"
+        Filter: t1.b > UInt32(10)
+          Left Join: t1.a = t2.a
+            TableScan: t1
+            TableScan: t2
+        ")
+    }
+
+    #[test]
+    fn eliminate_full_to_right() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        // only the null-extended rows of t2 are rejected, so rows of t2 are
+        // preserved
+        let plan = LogicalPlanBuilder::from(t1)
+            .join(
+                t2,
+                JoinType::Full,
+                (vec![Column::from_name("a")], vec![Column::from_name("a")]),
+                None,
+            )?
+            .filter(not(col("t2.c").is_null()))?
+            .build()?;
+
+        assert_optimized_plan_equal!(plan, @r"
+        Filter: NOT t2.c IS NULL
+          Right Join: t1.a = t2.a
+            TableScan: t1
+            TableScan: t2
+        ")
+    }
+
+    #[test]
+    fn eliminate_full_with_volatile_predicate() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        // could not eliminate: the result of a volatile predicate for null
+        // inputs is unknown
+        let plan = LogicalPlanBuilder::from(t1)
+            .join(
+                t2,
+                JoinType::Full,
+                (vec![Column::from_name("a")], vec![Column::from_name("a")]),
+                None,
+            )?
+            .filter(binary_expr(
+                col("t2.c").gt(lit(10u32)),
+                Or,
+                Expr::ScalarFunction(ScalarFunction::new_udf(
+                    Arc::new(ScalarUDF::from(VolatileBoolUdf::new())),
+                    vec![],
+                )),
+            ))?
+            .build()?;
+
+        assert_optimized_plan_equal!(plan, @r"
+        Filter: t2.c > UInt32(10) OR volatile_bool()
+          Full Join: t1.a = t2.a
+            TableScan: t1
+            TableScan: t2
+        ")
+    }
+
+    /// A volatile function returning a random boolean
+    #[derive(Debug)]
+    struct VolatileBoolUdf {
+        signature: Signature,
+    }
+
+    impl VolatileBoolUdf {
+        fn new() -> Self {
+            Self {
+                signature: Signature::exact(vec![], Volatility::Volatile),
+            }
+        }
+    }
+
+    impl ScalarUDFImpl for VolatileBoolUdf {
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn name(&self) -> &str {
+            "volatile_bool"
+        }
+
+        fn signature(&self) -> &Signature {
+            &self.signature
+        }
+
+        fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+            Ok(DataType::Boolean)
+        }
+
+        fn invoke_with_args(&self, _args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+            unimplemented!("volatile_bool is only used for planning")
+        }
+    }
 }
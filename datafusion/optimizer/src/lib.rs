@@ -42,6 +42,7 @@ pub mod common_subexpr_eliminate;
 pub mod decorrelate;
 pub mod decorrelate_lateral_join;
 pub mod decorrelate_predicate_subquery;
+pub mod eager_aggregation;
 pub mod eliminate_cross_join;
 pub mod eliminate_duplicated_expr;
 pub mod eliminate_filter;
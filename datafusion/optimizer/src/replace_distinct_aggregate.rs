@@ -22,11 +22,11 @@ use crate::{OptimizerConfig, OptimizerRule};
 use std::sync::Arc;
 
 use datafusion_common::tree_node::Transformed;
-use datafusion_common::{Column, Result};
+use datafusion_common::{Column, DFSchemaRef, Result};
 use datafusion_expr::expr_rewriter::normalize_cols;
 use datafusion_expr::utils::expand_wildcard;
-use datafusion_expr::{col, ExprFunctionExt, LogicalPlanBuilder};
-use datafusion_expr::{Aggregate, Distinct, DistinctOn, Expr, LogicalPlan};
+use datafusion_expr::{col, lit, ExprFunctionExt, LogicalPlanBuilder, SortExpr};
+use datafusion_expr::{Aggregate, Distinct, DistinctOn, Expr, LogicalPlan, WindowUDF};
 
 /// Optimizer that replaces logical [[Distinct]] with a logical [[Aggregate]]
 ///
@@ -54,6 +54,19 @@ use datafusion_expr::{Aggregate, Distinct, DistinctOn, Expr, LogicalPlan};
 /// )
 /// ORDER BY a DESC
 /// ```
+///
+/// when `datafusion.optimizer.enable_grouped_topk` is disabled. Otherwise, by
+/// default, a `DISTINCT ON` query with an `ORDER BY` is instead converted into
+/// ```text
+/// SELECT b FROM (
+///     SELECT *, ROW_NUMBER() OVER (PARTITION BY a ORDER BY a DESC, c) AS rn
+///     FROM tab
+/// )
+/// WHERE rn = 1
+/// ORDER BY a DESC
+/// ```
+/// which is executed as a grouped TopK, only keeping the first row of each
+/// group in memory
 #[derive(Default, Debug)]
 pub struct ReplaceDistinctWithAggregate {}
 
@@ -107,6 +120,27 @@ impl OptimizerRule for ReplaceDistinctWithAggregate {
                 input,
                 schema,
             })) => {
+                let row_number_udwf = config
+                    .options()
+                    .optimizer
+                    .enable_grouped_topk
+                    .then(|| config.function_registry())
+                    .flatten()
+                    .and_then(|registry| registry.udwf("row_number").ok());
+                if let (Some(sort_expr), Some(row_number_udwf)) =
+                    (&sort_expr, row_number_udwf)
+                {
+                    let plan = distinct_on_with_row_number(
+                        select_expr,
+                        on_expr,
+                        sort_expr.clone(),
+                        input,
+                        &schema,
+                        row_number_udwf,
+                    )?;
+                    return Ok(Transformed::yes(plan));
+                }
+
                 let expr_cnt = on_expr.len();
 
                 // Construct the aggregation expression to be used to fetch the selected expressions.
@@ -182,6 +216,57 @@ impl OptimizerRule for ReplaceDistinctWithAggregate {
     }
 }
 
+/// Name of the row number column of the plan replacing a `DISTINCT ON`
+const DISTINCT_ON_ROW_NUMBER: &str = "__distinct_on_row_number";
+
+/// Replaces a `DISTINCT ON` with a `ROW_NUMBER` window function partitioned by
+/// the `ON` expressions, followed by a filter keeping the first row of each
+/// partition
+fn distinct_on_with_row_number(
+    select_expr: Vec<Expr>,
+    on_expr: Vec<Expr>,
+    mut sort_expr: Vec<SortExpr>,
+    input: Arc<LogicalPlan>,
+    schema: &DFSchemaRef,
+    row_number_udwf: Arc<WindowUDF>,
+) -> Result<LogicalPlan> {
+    let expr_cnt = on_expr.len();
+    let select_expr = normalize_cols(select_expr, input.as_ref())?;
+    let on_expr = normalize_cols(on_expr, input.as_ref())?;
+
+    let row_number = row_number_udwf
+        .call(vec![])
+        .partition_by(on_expr)
+        .order_by(sort_expr.clone())
+        .build()?
+        .alias(DISTINCT_ON_ROW_NUMBER);
+
+    // the first row of each group is kept, so sorting by the `ON` expressions
+    // is enough to order the output
+    sort_expr.truncate(expr_cnt);
+
+    let project_exprs = select_expr
+        .into_iter()
+        .zip(schema.iter())
+        .map(|(expr, (qualifier, field))| match expr {
+            Expr::Column(column)
+                if column.relation.as_ref() == qualifier
+                    && &column.name == field.name() =>
+            {
+                Expr::Column(column)
+            }
+            expr => expr.alias_qualified(qualifier.cloned(), field.name()),
+        })
+        .collect::<Vec<Expr>>();
+
+    LogicalPlanBuilder::from(input)
+        .window(vec![row_number])?
+        .filter(col(DISTINCT_ON_ROW_NUMBER).eq(lit(1u64)))?
+        .sort(sort_expr)?
+        .project(project_exprs)?
+        .build()
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
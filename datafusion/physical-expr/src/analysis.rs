@@ -20,17 +20,19 @@
 use std::fmt::Debug;
 use std::sync::Arc;
 
-use crate::expressions::Column;
+use crate::expressions::{BinaryExpr, Column, InListExpr, LikeExpr, Literal, NotExpr};
 use crate::intervals::cp_solver::{ExprIntervalGraph, PropagationResult};
+use crate::intervals::utils::check_support;
 use crate::utils::collect_columns;
 use crate::PhysicalExpr;
 
-use arrow::datatypes::Schema;
+use arrow::datatypes::{DataType, Schema, SchemaRef};
 use datafusion_common::stats::Precision;
 use datafusion_common::{
     internal_datafusion_err, internal_err, ColumnStatistics, Result, ScalarValue,
 };
 use datafusion_expr::interval_arithmetic::{cardinality_ratio, Interval};
+use datafusion_expr::Operator;
 
 /// The shared context used during the analysis of an expression. Includes
 /// the boundaries for all known columns.
@@ -282,8 +284,22 @@ fn calculate_selectivity(
     let mut acc: f64 = 1.0;
     for (initial, target) in initial_boundaries.iter().zip(target_boundaries) {
         match (initial.interval.as_ref(), target.interval.as_ref()) {
-            (Some(initial), Some(target)) => {
-                acc *= cardinality_ratio(initial, target);
+            (Some(initial_interval), Some(target_interval)) => {
+                // A column restricted to a single value selects one of its
+                // distinct values, if their number is known
+                let distinct_count = initial
+                    .distinct_count
+                    .get_value()
+                    .filter(|distinct_count| **distinct_count > 0);
+                acc *= match distinct_count {
+                    Some(distinct_count)
+                        if is_single_value(target_interval)
+                            && !is_single_value(initial_interval) =>
+                    {
+                        1.0 / *distinct_count as f64
+                    }
+                    _ => cardinality_ratio(initial_interval, target_interval),
+                };
             }
             (None, Some(_)) => {
                 return internal_err!(
@@ -297,19 +313,362 @@ fn calculate_selectivity(
     Ok(acc)
 }
 
+/// Returns whether `interval` only contains a single, non-null, value
+fn is_single_value(interval: &Interval) -> bool {
+    interval.lower() == interval.upper() && !interval.lower().is_null()
+}
+
+/// Estimates the selectivity of the boolean predicate `expr` over the columns
+/// described by the boundaries of `context`, as a value between 0.0 (selects
+/// nothing) and 1.0 (selects everything).
+///
+/// Predicates supported by interval arithmetic are evaluated with [`analyze`].
+/// Additionally:
+///
+/// * `col = literal` selects `1 / distinct_count` of the rows, and `col IN
+///   (literals)` selects as many distinct values as there are in the list.
+/// * Range predicates over numeric and temporal columns are interpolated
+///   between the minimum and maximum of the column, assuming a uniform
+///   distribution.
+/// * `col LIKE 'prefix%'` is estimated as the range of strings starting with
+///   `prefix`.
+/// * Conjunctions and disjunctions assume that their operands are
+///   independent.
+///
+/// Predicates that cannot be estimated select `default_selectivity` of the
+/// rows.
+pub fn estimate_selectivity(
+    expr: &Arc<dyn PhysicalExpr>,
+    context: &AnalysisContext,
+    schema: &SchemaRef,
+    default_selectivity: f64,
+) -> f64 {
+    if context
+        .boundaries
+        .iter()
+        .any(|bound| bound.interval.is_none())
+    {
+        // The input is empty
+        return 0.0;
+    }
+    SelectivityEstimator {
+        context,
+        schema,
+        default_selectivity,
+    }
+    .estimate(expr)
+}
+
+/// Recursively estimates the selectivity of predicates, see
+/// [`estimate_selectivity`]
+struct SelectivityEstimator<'a> {
+    context: &'a AnalysisContext,
+    schema: &'a SchemaRef,
+    default_selectivity: f64,
+}
+
+impl SelectivityEstimator<'_> {
+    fn estimate(&self, expr: &Arc<dyn PhysicalExpr>) -> f64 {
+        self.try_estimate(expr)
+            .unwrap_or(self.default_selectivity)
+            .clamp(0.0, 1.0)
+    }
+
+    fn try_estimate(&self, expr: &Arc<dyn PhysicalExpr>) -> Option<f64> {
+        let expr_any = expr.as_any();
+        if let Some(binary) = expr_any.downcast_ref::<BinaryExpr>() {
+            match binary.op() {
+                // Conjunctions of supported predicates are analyzed together,
+                // so that the ranges of the same column are intersected
+                Operator::And if !check_support(expr, self.schema) => {
+                    let left = self.estimate(binary.left());
+                    let right = self.estimate(binary.right());
+                    return Some(left * right);
+                }
+                Operator::Or => {
+                    let left = self.estimate(binary.left());
+                    let right = self.estimate(binary.right());
+                    return Some(left + right - left * right);
+                }
+                _ => {}
+            }
+        }
+        if check_support(expr, self.schema) {
+            return analyze(expr, self.context.clone(), self.schema)
+                .ok()?
+                .selectivity;
+        }
+        if let Some(binary) = expr_any.downcast_ref::<BinaryExpr>() {
+            self.estimate_comparison(binary.left(), binary.op(), binary.right())
+        } else if let Some(not) = expr_any.downcast_ref::<NotExpr>() {
+            Some(1.0 - self.estimate(not.arg()))
+        } else if let Some(in_list) = expr_any.downcast_ref::<InListExpr>() {
+            self.estimate_in_list(in_list)
+        } else if let Some(like) = expr_any.downcast_ref::<LikeExpr>() {
+            self.estimate_like(like)
+        } else {
+            None
+        }
+    }
+
+    /// Estimates the selectivity of the comparison of a column to a literal
+    fn estimate_comparison(
+        &self,
+        left: &Arc<dyn PhysicalExpr>,
+        op: &Operator,
+        right: &Arc<dyn PhysicalExpr>,
+    ) -> Option<f64> {
+        let (column, value, op) = match (as_column(left), as_literal(right)) {
+            (Some(column), Some(value)) => (column, value, *op),
+            _ => (as_column(right)?, as_literal(left)?, op.swap()?),
+        };
+        let boundary = self.boundary(column)?;
+        let interval = boundary.interval.as_ref()?;
+        let value = value.cast_to(&interval.data_type()).ok()?;
+        if value.is_null() {
+            // Comparisons to nulls are never true
+            return Some(0.0);
+        }
+        match op {
+            Operator::Eq => self.equality_selectivity(boundary, &value),
+            Operator::NotEq => self
+                .equality_selectivity(boundary, &value)
+                .map(|selectivity| 1.0 - selectivity),
+            Operator::Lt | Operator::LtEq => {
+                range_selectivity(interval, None, Some(&value))
+            }
+            Operator::Gt | Operator::GtEq => {
+                range_selectivity(interval, Some(&value), None)
+            }
+            _ => None,
+        }
+    }
+
+    /// Estimates the selectivity of `col = value`
+    fn equality_selectivity(
+        &self,
+        boundary: &ExprBoundaries,
+        value: &ScalarValue,
+    ) -> Option<f64> {
+        let interval = boundary.interval.as_ref()?;
+        if !interval.contains_value(value).ok()? {
+            return Some(0.0);
+        }
+        match boundary.distinct_count.get_value() {
+            Some(distinct_count) if *distinct_count > 0 => {
+                Some(1.0 / *distinct_count as f64)
+            }
+            _ if has_known_bounds(interval) => interval
+                .cardinality()
+                .map(|cardinality| 1.0 / cardinality as f64),
+            _ => None,
+        }
+    }
+
+    /// Estimates the selectivity of `col [NOT] IN (literals)`
+    fn estimate_in_list(&self, in_list: &InListExpr) -> Option<f64> {
+        let boundary = self.boundary(as_column(in_list.expr())?)?;
+        let interval = boundary.interval.as_ref()?;
+        let mut values = vec![];
+        for item in in_list.list() {
+            let value = as_literal(item)?.cast_to(&interval.data_type()).ok()?;
+            if !value.is_null()
+                && interval.contains_value(&value).ok()?
+                && !values.contains(&value)
+            {
+                values.push(value);
+            }
+        }
+        let selectivity = match boundary.distinct_count.get_value() {
+            Some(distinct_count) if *distinct_count > 0 => {
+                values.len().min(*distinct_count) as f64 / *distinct_count as f64
+            }
+            _ => values
+                .iter()
+                .map(|value| self.equality_selectivity(boundary, value))
+                .sum::<Option<f64>>()?,
+        };
+        Some(if in_list.negated() {
+            1.0 - selectivity
+        } else {
+            selectivity
+        })
+    }
+
+    /// Estimates the selectivity of `col [NOT] LIKE 'prefix%'`, and of
+    /// patterns without wildcards
+    fn estimate_like(&self, like: &LikeExpr) -> Option<f64> {
+        if like.case_insensitive() {
+            return None;
+        }
+        let boundary = self.boundary(as_column(like.expr())?)?;
+        let interval = boundary.interval.as_ref()?;
+        let pattern = as_literal(like.pattern())?.try_as_str()??;
+        let prefix_len = pattern.find(['%', '_', '\\']).unwrap_or(pattern.len());
+        let (prefix, wildcards) = pattern.split_at(prefix_len);
+        let selectivity = match wildcards {
+            "" => {
+                let value = ScalarValue::from(prefix).cast_to(&interval.data_type());
+                self.equality_selectivity(boundary, &value.ok()?)?
+            }
+            "%" => prefix_selectivity(interval, prefix.as_bytes())?,
+            _ => return None,
+        };
+        Some(if like.negated() {
+            1.0 - selectivity
+        } else {
+            selectivity
+        })
+    }
+
+    fn boundary(&self, column: &Column) -> Option<&ExprBoundaries> {
+        self.context
+            .boundaries
+            .iter()
+            .find(|bound| bound.column == *column)
+    }
+}
+
+fn as_column(expr: &Arc<dyn PhysicalExpr>) -> Option<&Column> {
+    expr.as_any().downcast_ref::<Column>()
+}
+
+fn as_literal(expr: &Arc<dyn PhysicalExpr>) -> Option<&ScalarValue> {
+    expr.as_any()
+        .downcast_ref::<Literal>()
+        .map(|literal| literal.value())
+}
+
+/// Returns whether both bounds of `interval` are known
+fn has_known_bounds(interval: &Interval) -> bool {
+    !interval.lower().is_null() && !interval.upper().is_null()
+}
+
+/// Estimates the fraction of the values of `interval` between `lower` and
+/// `upper`, assuming that they are uniformly distributed
+fn range_selectivity(
+    interval: &Interval,
+    lower: Option<&ScalarValue>,
+    upper: Option<&ScalarValue>,
+) -> Option<f64> {
+    if !has_known_bounds(interval) {
+        return None;
+    }
+    let lower = match lower {
+        Some(lower) => interpolate(lower, interval)?,
+        None => 0.0,
+    };
+    let upper = match upper {
+        Some(upper) => interpolate(upper, interval)?,
+        None => 1.0,
+    };
+    Some((upper - lower).max(0.0))
+}
+
+/// Estimates the fraction of the strings of `interval` starting with `prefix`
+fn prefix_selectivity(interval: &Interval, prefix: &[u8]) -> Option<f64> {
+    let (Some(Some(min)), Some(Some(max))) =
+        (interval.lower().try_as_str(), interval.upper().try_as_str())
+    else {
+        return None;
+    };
+    let (min, max) = (min.as_bytes(), max.as_bytes());
+    let lower = interpolate_bytes(prefix, min, max)?;
+    // The strings starting with `prefix` are smaller than the prefix with its
+    // last byte incremented
+    let mut successor = prefix.to_vec();
+    while successor.last() == Some(&u8::MAX) {
+        successor.pop();
+    }
+    let upper = match successor.last_mut() {
+        Some(last) => {
+            *last += 1;
+            interpolate_bytes(&successor, min, max)?
+        }
+        None => 1.0,
+    };
+    Some((upper - lower).max(0.0))
+}
+
+/// Returns the position of `value` between the bounds of `interval`, from 0.0
+/// at its lower bound to 1.0 at its upper bound
+fn interpolate(value: &ScalarValue, interval: &Interval) -> Option<f64> {
+    let (min, max) = (interval.lower(), interval.upper());
+    if value <= min {
+        return Some(0.0);
+    } else if value >= max {
+        return Some(1.0);
+    }
+    if let (Some(Some(value)), Some(Some(min)), Some(Some(max))) =
+        (value.try_as_str(), min.try_as_str(), max.try_as_str())
+    {
+        return interpolate_bytes(value.as_bytes(), min.as_bytes(), max.as_bytes());
+    }
+    let (value, min, max) = (to_f64(value)?, to_f64(min)?, to_f64(max)?);
+    (max > min).then(|| ((value - min) / (max - min)).clamp(0.0, 1.0))
+}
+
+/// Returns the position of `value` between `min` and `max` in lexicographic
+/// order, using the first 8 bytes following their common prefix
+fn interpolate_bytes(value: &[u8], min: &[u8], max: &[u8]) -> Option<f64> {
+    if value <= min {
+        return Some(0.0);
+    } else if value >= max {
+        return Some(1.0);
+    }
+    let common_prefix_len = min.iter().zip(max).take_while(|(a, b)| a == b).count();
+    let to_f64 = |bytes: &[u8]| {
+        let suffix = bytes.get(common_prefix_len..).unwrap_or_default();
+        let mut buffer = [0u8; 8];
+        let len = suffix.len().min(buffer.len());
+        buffer[..len].copy_from_slice(&suffix[..len]);
+        u64::from_be_bytes(buffer) as f64
+    };
+    let (value, min, max) = (to_f64(value), to_f64(min), to_f64(max));
+    (max > min).then(|| ((value - min) / (max - min)).clamp(0.0, 1.0))
+}
+
+/// Converts numeric and temporal values to `f64`
+fn to_f64(value: &ScalarValue) -> Option<f64> {
+    match value {
+        ScalarValue::Date32(Some(value))
+        | ScalarValue::Time32Second(Some(value))
+        | ScalarValue::Time32Millisecond(Some(value)) => Some(*value as f64),
+        ScalarValue::Date64(Some(value))
+        | ScalarValue::Time64Microsecond(Some(value))
+        | ScalarValue::Time64Nanosecond(Some(value))
+        | ScalarValue::TimestampSecond(Some(value), _)
+        | ScalarValue::TimestampMillisecond(Some(value), _)
+        | ScalarValue::TimestampMicrosecond(Some(value), _)
+        | ScalarValue::TimestampNanosecond(Some(value), _)
+        | ScalarValue::DurationSecond(Some(value))
+        | ScalarValue::DurationMillisecond(Some(value))
+        | ScalarValue::DurationMicrosecond(Some(value))
+        | ScalarValue::DurationNanosecond(Some(value)) => Some(*value as f64),
+        _ if value.data_type().is_numeric() => {
+            match value.cast_to(&DataType::Float64).ok()? {
+                ScalarValue::Float64(value) => value,
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
     use arrow::datatypes::{DataType, Field, Schema};
-    use datafusion_common::{assert_contains, DFSchema};
+    use datafusion_common::stats::Precision;
+    use datafusion_common::{assert_contains, ColumnStatistics, DFSchema, ScalarValue};
     use datafusion_expr::{
         col, execution_props::ExecutionProps, interval_arithmetic::Interval, lit, Expr,
     };
 
     use crate::{create_physical_expr, AnalysisContext};
 
-    use super::{analyze, ExprBoundaries};
+    use super::{analyze, estimate_selectivity, ExprBoundaries};
 
     fn make_field(name: &str, data_type: DataType) -> Field {
         let nullable = false;
@@ -438,4 +797,82 @@ mod tests {
         .unwrap_err();
         assert_contains!(analysis_error.to_string(), expected_error);
     }
+
+    #[test]
+    fn test_estimate_selectivity() {
+        let schema = Arc::new(Schema::new(vec![
+            make_field("id", DataType::Int32),
+            make_field("date", DataType::Date32),
+            make_field("name", DataType::Utf8),
+        ]));
+        let column_statistics =
+            |min: ScalarValue, max: ScalarValue, ndv: Option<usize>| ColumnStatistics {
+                min_value: Precision::Exact(min),
+                max_value: Precision::Exact(max),
+                distinct_count: ndv.map(Precision::Exact).unwrap_or_default(),
+                ..Default::default()
+            };
+        // id in [1, 1000] with 500 distinct values, date in [0, 100] and name
+        // in ['a', 'c']
+        let statistics = vec![
+            column_statistics(ScalarValue::from(1), ScalarValue::from(1000), Some(500)),
+            column_statistics(
+                ScalarValue::Date32(Some(0)),
+                ScalarValue::Date32(Some(100)),
+                None,
+            ),
+            column_statistics(ScalarValue::from("a"), ScalarValue::from("c"), None),
+        ];
+        let context = AnalysisContext::try_from_statistics(&schema, &statistics).unwrap();
+        let date = |days| lit(ScalarValue::Date32(Some(days)));
+
+        let test_cases = vec![
+            // 1 of the 500 distinct values
+            (col("id").eq(lit(5)), 0.002),
+            (col("id").not_eq(lit(5)), 0.998),
+            (col("id").eq(lit(2000)), 0.0),
+            (
+                col("id").in_list(vec![lit(1), lit(2), lit(3)], false),
+                0.006,
+            ),
+            // 2000 is out of the range of id
+            (
+                col("id").in_list(vec![lit(1), lit(2), lit(2000)], false),
+                0.004,
+            ),
+            (col("id").in_list(vec![lit(1), lit(2), lit(3)], true), 0.994),
+            // [751, 1000]
+            (col("id").gt(lit(750)), 0.25),
+            (col("date").lt(date(25)), 0.25),
+            (col("date").gt_eq(date(25)), 0.75),
+            (col("date").lt(date(200)), 1.0),
+            // ['b', 'c')
+            (col("name").like(lit("b%")), 0.5),
+            (col("name").not_like(lit("b%")), 0.5),
+            (col("id").gt(lit(750)).and(col("date").lt(date(25))), 0.0625),
+            (
+                col("id").eq(lit(5)).or(col("id").eq(lit(6))),
+                0.002 + 0.002 - 0.002 * 0.002,
+            ),
+            (
+                col("id")
+                    .in_list(vec![lit(1), lit(2), lit(3)], false)
+                    .or(col("date").lt(date(25))),
+                0.006 + 0.25 - 0.006 * 0.25,
+            ),
+            // Unsupported predicates select the default selectivity
+            (col("name").like(lit("%b")), 0.2),
+            ((col("id") % lit(2)).eq(lit(0)), 0.2),
+        ];
+        let df_schema = DFSchema::try_from(Arc::clone(&schema)).unwrap();
+        for (expr, expected) in test_cases {
+            let physical_expr =
+                create_physical_expr(&expr, &df_schema, &ExecutionProps::new()).unwrap();
+            let actual = estimate_selectivity(&physical_expr, &context, &schema, 0.2);
+            assert!(
+                (actual - expected).abs() < 1e-9,
+                "expected selectivity {expected} for {expr}, got {actual}"
+            );
+        }
+    }
 }
@@ -199,6 +199,7 @@ impl PhysicalExpr for CastExpr {
         if (source_datatype.is_numeric() || source_datatype == Boolean)
             && target_type.is_numeric()
             || source_datatype.is_temporal() && target_type.is_temporal()
+            || is_string_or_binary_cast(&source_datatype, target_type)
             || source_datatype.eq(target_type)
         {
             Ok(children[0].clone().with_range(unbounded))
@@ -216,6 +217,24 @@ impl PhysicalExpr for CastExpr {
     }
 }
 
+/// Returns true if casting `from` to `to` only changes the physical
+/// representation of string or binary values (e.g. `Utf8` to `Utf8View`),
+/// which keeps the values, and therefore their ordering, unchanged
+fn is_string_or_binary_cast(from: &DataType, to: &DataType) -> bool {
+    let from = match from {
+        Dictionary(_, value_type) => value_type.as_ref(),
+        other => other,
+    };
+    matches!(
+        (from, to),
+        (Utf8 | LargeUtf8 | Utf8View, Utf8 | LargeUtf8 | Utf8View)
+            | (
+                Binary | LargeBinary | BinaryView,
+                Binary | LargeBinary | BinaryView
+            )
+    )
+}
+
 /// Return a PhysicalExpression representing `expr` casted to
 /// `cast_type`, if any casting is needed.
 ///
@@ -254,6 +273,7 @@ mod tests {
 
     use crate::expressions::column::col;
 
+    use arrow::compute::SortOptions;
     use arrow::{
         array::{
             Array, Decimal128Array, Float32Array, Float64Array, Int16Array, Int32Array,
@@ -263,6 +283,7 @@ mod tests {
         datatypes::*,
     };
     use datafusion_common::assert_contains;
+    use datafusion_expr_common::sort_properties::SortProperties;
     use datafusion_physical_expr_common::physical_expr::fmt_sql;
 
     // runs an end-to-end test of physical type cast
@@ -809,4 +830,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_cast_string_types_preserve_order() -> Result<()> {
+        let ordered = SortProperties::Ordered(SortOptions::default());
+        for (from, to, preserves_order) in [
+            (Utf8, Utf8View, true),
+            (Utf8View, LargeUtf8, true),
+            (Dictionary(Box::new(Int32), Box::new(Utf8)), Utf8View, true),
+            (Binary, BinaryView, true),
+            (Utf8, Int32, false),
+            (Int32, Utf8View, false),
+        ] {
+            let schema = Schema::new(vec![Field::new("a", from.clone(), true)]);
+            let expr = CastExpr::new(col("a", &schema)?, to.clone(), None);
+            let child = ExprProperties::new_unknown()
+                .with_order(ordered)
+                .with_range(Interval::make_unbounded(&from)?);
+            let properties = expr.get_properties(&[child])?;
+            assert_eq!(
+                properties.sort_properties == ordered,
+                preserves_order,
+                "CAST from {from} to {to}"
+            );
+        }
+        Ok(())
+    }
 }
@@ -50,7 +50,7 @@ pub mod execution_props {
 }
 
 pub use aggregate::groups_accumulator::{GroupsAccumulatorAdapter, NullState};
-pub use analysis::{analyze, AnalysisContext, ExprBoundaries};
+pub use analysis::{analyze, estimate_selectivity, AnalysisContext, ExprBoundaries};
 pub use equivalence::{
     calculate_union, AcrossPartitions, ConstExpr, EquivalenceProperties,
 };
@@ -68,7 +68,7 @@ pub use datafusion_physical_expr_common::sort_expr::{
 };
 
 pub use planner::{create_physical_expr, create_physical_exprs};
-pub use scalar_function::ScalarFunctionExpr;
+pub use scalar_function::{bind_cancellation, ScalarFunctionExpr};
 pub use schema_rewriter::PhysicalExprSchemaRewriter;
 pub use utils::{conjunction, conjunction_opt, split_conjunction};
 
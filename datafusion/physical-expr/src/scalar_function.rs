@@ -39,6 +39,8 @@ use crate::PhysicalExpr;
 
 use arrow::array::{Array, RecordBatch};
 use arrow::datatypes::{DataType, FieldRef, Schema};
+use datafusion_common::cancellation::CancellationToken;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
 use datafusion_common::{internal_err, Result, ScalarValue};
 use datafusion_expr::interval_arithmetic::Interval;
 use datafusion_expr::sort_properties::ExprProperties;
@@ -280,3 +282,26 @@ impl PhysicalExpr for ScalarFunctionExpr {
         write!(f, ")")
     }
 }
+
+/// Returns `expr` with the scalar functions opting in to cancellation, through
+/// [`ScalarUDF::with_cancellation`], bound to `cancellation`
+pub fn bind_cancellation(
+    expr: Arc<dyn PhysicalExpr>,
+    cancellation: &CancellationToken,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    expr.transform_up(|expr| {
+        let Some(func) = expr.as_any().downcast_ref::<ScalarFunctionExpr>() else {
+            return Ok(Transformed::no(expr));
+        };
+        Ok(match func.fun.with_cancellation(cancellation) {
+            Some(fun) => Transformed::yes(Arc::new(ScalarFunctionExpr::new(
+                &func.name,
+                Arc::new(fun),
+                func.args.clone(),
+                Arc::clone(&func.return_field),
+            )) as _),
+            None => Transformed::no(expr),
+        })
+    })
+    .data()
+}
@@ -16,15 +16,23 @@
 // under the License.
 
 //! Utilizing exact statistics from sources to avoid scanning data
+use arrow::array::RecordBatch;
+use arrow::compute::cast;
+use arrow::datatypes::SchemaRef;
 use datafusion_common::config::ConfigOptions;
 use datafusion_common::scalar::ScalarValue;
+use datafusion_common::stats::Precision;
 use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
-use datafusion_common::Result;
+use datafusion_common::{Result, Statistics};
+use datafusion_execution::TaskContext;
+use datafusion_physical_expr::expressions::Column;
 use datafusion_physical_plan::aggregates::AggregateExec;
 use datafusion_physical_plan::placeholder_row::PlaceholderRowExec;
 use datafusion_physical_plan::projection::ProjectionExec;
+use datafusion_physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion_physical_plan::streaming::{PartitionStream, StreamingTableExec};
 use datafusion_physical_plan::udaf::{AggregateFunctionExpr, StatisticsArgs};
-use datafusion_physical_plan::{expressions, ExecutionPlan};
+use datafusion_physical_plan::{expressions, ExecutionPlan, SendableRecordBatchStream};
 use std::sync::Arc;
 
 use crate::PhysicalOptimizerRule;
@@ -88,6 +96,8 @@ impl PhysicalOptimizerRule for AggregateStatistics {
                 })
                 .data()
             }
+        } else if let Some(partition_counts) = take_partition_counts(&plan)? {
+            Ok(partition_counts)
         } else {
             plan.map_children(|child| self.optimize(child, config).map(Transformed::yes))
                 .data()
@@ -139,6 +149,117 @@ fn take_optimizable(node: &dyn ExecutionPlan) -> Option<Arc<dyn ExecutionPlan>>
     None
 }
 
+/// If the node passed as argument is a final `AggregateExec` node grouping by
+/// columns only, over a partial `AggregateExec` node whose input knows its
+/// number of rows for each combination of values of these columns, returns
+/// the result of the aggregation computed from these numbers of rows.
+///
+/// This answers queries like `SELECT part, count(*) FROM t GROUP BY part`
+/// without reading the files of `t`, see [`ExecutionPlan::num_rows_by_values`],
+/// provided that the aggregate functions only depend on the number of rows.
+fn take_partition_counts(
+    node: &Arc<dyn ExecutionPlan>,
+) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+    let Some(final_agg_exec) = node.as_any().downcast_ref::<AggregateExec>() else {
+        return Ok(None);
+    };
+    if final_agg_exec.mode().is_first_stage() || final_agg_exec.group_expr().is_empty() {
+        return Ok(None);
+    }
+    let mut child = final_agg_exec.input();
+    let partial_agg_exec = loop {
+        if let Some(partial_agg_exec) = child.as_any().downcast_ref::<AggregateExec>() {
+            break partial_agg_exec;
+        }
+        match child.children().as_slice() {
+            [childrens_child] => child = *childrens_child,
+            _ => return Ok(None),
+        }
+    };
+    if !partial_agg_exec.mode().is_first_stage()
+        || !partial_agg_exec.group_expr().is_single()
+        || partial_agg_exec.filter_expr().iter().any(|e| e.is_some())
+    {
+        return Ok(None);
+    }
+    let mut columns = vec![];
+    for (expr, _) in partial_agg_exec.group_expr().expr() {
+        let Some(column) = expr.as_any().downcast_ref::<Column>() else {
+            return Ok(None);
+        };
+        columns.push(column.index());
+    }
+    let Some(groups) = partial_agg_exec.input().num_rows_by_values(&columns)? else {
+        return Ok(None);
+    };
+
+    // The values of the aggregate functions for each group, computed from its
+    // number of rows alone
+    let input_schema = partial_agg_exec.input().schema();
+    let mut rows = vec![];
+    for (mut values, num_rows) in groups {
+        let stats = Statistics::new_unknown(&input_schema)
+            .with_num_rows(Precision::Exact(num_rows));
+        for expr in partial_agg_exec.aggr_expr() {
+            let field = expr.field();
+            let args = expr.expressions();
+            let statistics_args = StatisticsArgs {
+                statistics: &stats,
+                return_type: field.data_type(),
+                is_distinct: expr.is_distinct(),
+                exprs: args.as_slice(),
+            };
+            let Some((value, _)) =
+                take_optimizable_value_from_statistics(&statistics_args, expr)
+            else {
+                return Ok(None);
+            };
+            values.push(value);
+        }
+        rows.push(values);
+    }
+
+    let schema = final_agg_exec.schema();
+    let batch = if rows.is_empty() {
+        RecordBatch::new_empty(Arc::clone(&schema))
+    } else {
+        let mut columns = vec![];
+        for (index, field) in schema.fields().iter().enumerate() {
+            let array =
+                ScalarValue::iter_to_array(rows.iter().map(|row| row[index].clone()))?;
+            columns.push(cast(&array, field.data_type())?);
+        }
+        // Null values of non-nullable columns are left to the input
+        let Ok(batch) = RecordBatch::try_new(Arc::clone(&schema), columns) else {
+            return Ok(None);
+        };
+        batch
+    };
+    let partition: Arc<dyn PartitionStream> = Arc::new(BatchPartition { batch });
+    let exec =
+        StreamingTableExec::try_new(schema, vec![partition], None, [], false, None)?;
+    Ok(Some(Arc::new(exec)))
+}
+
+/// A partition returning a single batch, computed from statistics
+#[derive(Debug)]
+struct BatchPartition {
+    batch: RecordBatch,
+}
+
+impl PartitionStream for BatchPartition {
+    fn schema(&self) -> &SchemaRef {
+        self.batch.schema_ref()
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let batch = self.batch.clone();
+        let schema = batch.schema();
+        let stream = futures::stream::once(async move { Ok(batch) });
+        Box::pin(RecordBatchStreamAdapter::new(schema, stream))
+    }
+}
+
 /// If this agg_expr is a max that is exactly defined in the statistics, return it.
 fn take_optimizable_value_from_statistics(
     statistics_args: &StatisticsArgs,
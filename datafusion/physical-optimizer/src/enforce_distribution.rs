@@ -1096,6 +1096,7 @@ fn get_repartition_requirement_status(
     plan: &Arc<dyn ExecutionPlan>,
     batch_size: usize,
     should_use_estimates: bool,
+    threshold_rows: usize,
 ) -> Result<Vec<RepartitionRequirementStatus>> {
     let mut needs_alignment = false;
     let children = plan.children();
@@ -1106,11 +1107,16 @@ fn get_repartition_requirement_status(
         izip!(children.into_iter(), requirements, rr_beneficial)
     {
         // Decide whether adding a round robin is beneficial depending on
-        // the statistical information we have on the number of rows:
+        // the statistical information we have on the number of rows. Inputs
+        // estimated to have fewer than `threshold_rows` rows are not worth
+        // repartitioning, even when the estimate is inexact:
         let roundrobin_beneficial_stats = match child.partition_statistics(None)?.num_rows
         {
-            Precision::Exact(n_rows) => n_rows > batch_size,
-            Precision::Inexact(n_rows) => !should_use_estimates || (n_rows > batch_size),
+            Precision::Exact(n_rows) => n_rows > batch_size && n_rows >= threshold_rows,
+            Precision::Inexact(n_rows) => {
+                (!should_use_estimates || (n_rows > batch_size))
+                    && n_rows >= threshold_rows
+            }
             Precision::Absent => true,
         };
         let is_hash = matches!(requirement, Distribution::HashPartitioned(_));
@@ -1173,6 +1179,7 @@ pub fn ensure_distribution(
     let should_use_estimates = config
         .execution
         .use_row_number_estimates_to_optimize_partitioning;
+    let repartition_threshold_rows = config.optimizer.repartition_threshold_rows;
     let unbounded_and_pipeline_friendly = dist_context.plan.boundedness().is_unbounded()
         && matches!(
             dist_context.plan.pipeline_behavior(),
@@ -1210,8 +1217,12 @@ pub fn ensure_distribution(
         }
     };
 
-    let repartition_status_flags =
-        get_repartition_requirement_status(&plan, batch_size, should_use_estimates)?;
+    let repartition_status_flags = get_repartition_requirement_status(
+        &plan,
+        batch_size,
+        should_use_estimates,
+        repartition_threshold_rows,
+    )?;
     // This loop iterates over all the children to:
     // - Increase parallelism for every child if it is beneficial.
     // - Satisfy the distribution requirements of every child, if it is not
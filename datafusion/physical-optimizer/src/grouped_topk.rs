@@ -0,0 +1,254 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! An optimizer rule that replaces a `ROW_NUMBER` window function followed by
+//! a filter on the row number with a grouped TopK
+
+use std::sync::Arc;
+
+use crate::PhysicalOptimizerRule;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{Result, ScalarValue};
+use datafusion_expr::Operator;
+use datafusion_physical_expr::expressions::{BinaryExpr, Column, Literal};
+use datafusion_physical_expr::window::{StandardWindowExpr, WindowExpr};
+use datafusion_physical_expr::{conjunction, split_conjunction, PhysicalExpr};
+use datafusion_physical_plan::filter::FilterExec;
+use datafusion_physical_plan::projection::ProjectionExec;
+use datafusion_physical_plan::sorts::grouped_topk::GroupedTopKExec;
+use datafusion_physical_plan::sorts::sort::SortExec;
+use datafusion_physical_plan::windows::{
+    BoundedWindowAggExec, WindowAggExec, WindowUDFExpr,
+};
+use datafusion_physical_plan::{ExecutionPlan, ExecutionPlanProperties, InputOrderMode};
+
+/// An optimizer rule that replaces the sort, the `ROW_NUMBER` window function
+/// and the filter of plans keeping the first rows of each group, such as
+///
+/// ```text
+/// FilterExec: rn <= 3
+///   BoundedWindowAggExec: ROW_NUMBER() PARTITION BY [a] ORDER BY [b DESC]
+///     SortExec: expr=[a ASC, b DESC]
+/// ```
+///
+/// with a [`GroupedTopKExec`] only keeping the first rows of each group:
+///
+/// ```text
+/// GroupedTopKExec: k=3, partition_by=[a], expr=[a ASC, b DESC], row_number=rn
+/// ```
+#[derive(Default, Debug)]
+pub struct GroupedTopK {}
+
+impl GroupedTopK {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn transform_filter(plan: &Arc<dyn ExecutionPlan>) -> Option<Arc<dyn ExecutionPlan>> {
+        let filter = plan.as_any().downcast_ref::<FilterExec>()?;
+        if filter.projection().is_some() {
+            return None;
+        }
+
+        // a projection of columns may sit between the filter and the window
+        let (projection, window) =
+            match filter.input().as_any().downcast_ref::<ProjectionExec>() {
+                Some(projection) => (Some(projection), projection.input()),
+                None => (None, filter.input()),
+            };
+        let (window_expr, window_input) = if let Some(window) =
+            window.as_any().downcast_ref::<BoundedWindowAggExec>()
+        {
+            if window.input_order_mode != InputOrderMode::Sorted {
+                return None;
+            }
+            (window.window_expr(), window.input())
+        } else if let Some(window) = window.as_any().downcast_ref::<WindowAggExec>() {
+            (window.window_expr(), window.input())
+        } else {
+            return None;
+        };
+        let [window_expr] = window_expr else {
+            return None;
+        };
+        if !is_row_number(window_expr) || window_expr.partition_by().is_empty() {
+            return None;
+        }
+
+        // the row number is the first column after the columns of the input
+        let row_number_index = window_input.schema().fields().len();
+        let filter_row_number_index = match projection {
+            Some(projection) => projected_index(projection, row_number_index)?,
+            None => row_number_index,
+        };
+        let mut k = None;
+        let mut other_predicates = vec![];
+        for predicate in split_conjunction(filter.predicate()) {
+            match row_number_limit(predicate, filter_row_number_index) {
+                Some(limit) if k.is_none() => k = Some(limit),
+                _ => other_predicates.push(Arc::clone(predicate)),
+            }
+        }
+        let k = k?;
+
+        // the window function must be computed over a sort by the group
+        // expressions, followed by its own sort expressions on other expressions
+        let sort = window_input.as_any().downcast_ref::<SortExec>()?;
+        if sort.fetch().is_some()
+            || !(sort.preserve_partitioning()
+                || sort.input().output_partitioning().partition_count() == 1)
+        {
+            return None;
+        }
+        let partition_by = window_expr.partition_by();
+        let is_group_expr =
+            |expr: &Arc<dyn PhysicalExpr>| partition_by.iter().any(|e| e.eq(expr));
+        let (group_exprs, order_exprs) =
+            sort.expr().split_at_checked(partition_by.len())?;
+        let window_order_exprs = window_expr
+            .order_by()
+            .iter()
+            .filter(|sort_expr| !is_group_expr(&sort_expr.expr))
+            .collect::<Vec<_>>();
+        if !group_exprs
+            .iter()
+            .all(|sort_expr| is_group_expr(&sort_expr.expr))
+            || order_exprs.len() < window_order_exprs.len()
+            || !order_exprs
+                .iter()
+                .zip(window_order_exprs)
+                .all(|(a, b)| a == b)
+        {
+            return None;
+        }
+
+        let row_number = Arc::clone(&window.schema().fields()[row_number_index]);
+        let mut topk: Arc<dyn ExecutionPlan> = Arc::new(
+            GroupedTopKExec::try_new(
+                Arc::clone(sort.input()),
+                partition_by.to_vec(),
+                sort.expr().clone(),
+                k,
+                row_number,
+            )
+            .ok()?,
+        );
+        if let Some(projection) = projection {
+            topk =
+                Arc::new(ProjectionExec::try_new(projection.expr().to_vec(), topk).ok()?);
+        }
+        if other_predicates.is_empty() {
+            Some(topk)
+        } else {
+            let filter = FilterExec::try_new(conjunction(other_predicates), topk)
+                .ok()?
+                .with_default_selectivity(filter.default_selectivity())
+                .ok()?;
+            Some(Arc::new(filter))
+        }
+    }
+}
+
+/// Returns the index of the output column of a projection of columns that is
+/// the input column `index`
+fn projected_index(projection: &ProjectionExec, index: usize) -> Option<usize> {
+    let mut projected_index = None;
+    for (i, (expr, _)) in projection.expr().iter().enumerate() {
+        let column = expr.as_any().downcast_ref::<Column>()?;
+        if column.index() == index {
+            projected_index = Some(i);
+        }
+    }
+    projected_index
+}
+
+/// Returns whether the window expression is a `ROW_NUMBER` window function
+fn is_row_number(window_expr: &Arc<dyn WindowExpr>) -> bool {
+    window_expr
+        .as_any()
+        .downcast_ref::<StandardWindowExpr>()
+        .and_then(|expr| {
+            expr.get_standard_func_expr()
+                .as_any()
+                .downcast_ref::<WindowUDFExpr>()
+        })
+        .is_some_and(|expr| expr.fun().name() == "row_number")
+}
+
+/// Returns the number of rows kept by the predicate if it only keeps the rows
+/// whose row number, in column `row_number_index`, is at most some constant
+fn row_number_limit(
+    predicate: &Arc<dyn PhysicalExpr>,
+    row_number_index: usize,
+) -> Option<usize> {
+    let binary = predicate.as_any().downcast_ref::<BinaryExpr>()?;
+    let is_row_number = |expr: &Arc<dyn PhysicalExpr>| {
+        expr.as_any()
+            .downcast_ref::<Column>()
+            .is_some_and(|column| column.index() == row_number_index)
+    };
+    let (op, literal) = if is_row_number(binary.left()) {
+        (*binary.op(), binary.right())
+    } else if is_row_number(binary.right()) {
+        (binary.op().swap()?, binary.left())
+    } else {
+        return None;
+    };
+    let value = match literal.as_any().downcast_ref::<Literal>()?.value() {
+        ScalarValue::UInt64(Some(value)) => usize::try_from(*value).ok()?,
+        ScalarValue::Int64(Some(value)) => usize::try_from(*value).ok()?,
+        _ => return None,
+    };
+    let k = match op {
+        Operator::Eq if value == 1 => 1,
+        Operator::LtEq => value,
+        Operator::Lt => value.checked_sub(1)?,
+        _ => return None,
+    };
+    (k > 0).then_some(k)
+}
+
+impl PhysicalOptimizerRule for GroupedTopK {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.enable_grouped_topk {
+            return Ok(plan);
+        }
+        plan.transform_down(|plan| {
+            Ok(match GroupedTopK::transform_filter(&plan) {
+                Some(plan) => Transformed::yes(plan),
+                None => Transformed::no(plan),
+            })
+        })
+        .data()
+    }
+
+    fn name(&self) -> &str {
+        "GroupedTopK"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+// see `grouped_topk.slt` for tests
@@ -0,0 +1,180 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The [`InstrumentStreams`] optimizer rule attaches a [`StreamInstrumentation`]
+//! to every operator of a plan.
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use crate::PhysicalOptimizerRule;
+
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::{Transformed, TreeNode};
+use datafusion_common::Result;
+use datafusion_physical_plan::instrument::{InstrumentedExec, StreamInstrumentation};
+use datafusion_physical_plan::ExecutionPlan;
+
+/// `InstrumentStreams` is a [`PhysicalOptimizerRule`] that wraps every operator
+/// of the plan in an [`InstrumentedExec`], so that `instrumentation` observes
+/// the streams of all the operators when the plan runs.
+///
+/// The rule is not part of the default rules. It should be added after all
+/// the other rules, for example with `SessionStateBuilder::with_physical_optimizer_rule`,
+/// as rules running after it don't recognize the wrapped operators.
+pub struct InstrumentStreams {
+    instrumentation: Arc<dyn StreamInstrumentation>,
+}
+
+impl InstrumentStreams {
+    pub fn new(instrumentation: Arc<dyn StreamInstrumentation>) -> Self {
+        Self { instrumentation }
+    }
+}
+
+impl Debug for InstrumentStreams {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(self.name())
+            .field("instrumentation", &self.instrumentation)
+            .finish()
+    }
+}
+
+impl PhysicalOptimizerRule for InstrumentStreams {
+    fn name(&self) -> &str {
+        "InstrumentStreams"
+    }
+
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        _config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        plan.transform_up(|plan| {
+            if plan.as_any().is::<InstrumentedExec>() {
+                return Ok(Transformed::no(plan));
+            }
+            Ok(Transformed::yes(Arc::new(InstrumentedExec::new(
+                plan,
+                Arc::clone(&self.instrumentation),
+            ))))
+        })
+        .map(|t| t.data)
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use arrow::record_batch::RecordBatch;
+    use datafusion_common::DataFusionError;
+    use datafusion_execution::TaskContext;
+    use datafusion_physical_plan::coalesce_partitions::CoalescePartitionsExec;
+    use datafusion_physical_plan::instrument::StreamObserver;
+    use datafusion_physical_plan::test::{make_partition, TestMemoryExec};
+    use datafusion_physical_plan::{collect, displayable};
+
+    /// Counts the calls of each callback per operator name
+    #[derive(Debug, Default)]
+    struct CountingInstrumentation {
+        calls: Arc<Mutex<HashMap<(String, &'static str), usize>>>,
+    }
+
+    struct CountingObserver {
+        name: String,
+        calls: Arc<Mutex<HashMap<(String, &'static str), usize>>>,
+    }
+
+    impl CountingObserver {
+        fn count(&self, callback: &'static str) {
+            *self
+                .calls
+                .lock()
+                .unwrap()
+                .entry((self.name.clone(), callback))
+                .or_default() += 1;
+        }
+    }
+
+    impl StreamInstrumentation for CountingInstrumentation {
+        fn on_start(
+            &self,
+            plan: &Arc<dyn ExecutionPlan>,
+            _partition: usize,
+        ) -> Box<dyn StreamObserver> {
+            let observer = CountingObserver {
+                name: plan.name().to_string(),
+                calls: Arc::clone(&self.calls),
+            };
+            observer.count("on_start");
+            Box::new(observer)
+        }
+    }
+
+    impl StreamObserver for CountingObserver {
+        fn on_batch(&mut self, _batch: &RecordBatch) {
+            self.count("on_batch");
+        }
+
+        fn on_end(&mut self, _error: Option<&DataFusionError>) {
+            self.count("on_end");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_instrument_streams() -> Result<()> {
+        let batch = make_partition(100);
+        let scan = TestMemoryExec::try_new_exec(
+            &[vec![batch.clone()], vec![batch.clone()]],
+            batch.schema(),
+            None,
+        )?;
+        let plan: Arc<dyn ExecutionPlan> = Arc::new(CoalescePartitionsExec::new(scan));
+        let display = displayable(plan.as_ref()).indent(true).to_string();
+
+        let instrumentation = Arc::new(CountingInstrumentation::default());
+        let rule = InstrumentStreams::new(Arc::clone(&instrumentation) as _);
+        let optimized = rule.optimize(plan, &ConfigOptions::new())?;
+        // Instrumenting twice doesn't wrap the operators again
+        let optimized = rule.optimize(optimized, &ConfigOptions::new())?;
+        assert_eq!(
+            displayable(optimized.as_ref()).indent(true).to_string(),
+            display
+        );
+
+        let batches = collect(optimized, Arc::new(TaskContext::default())).await?;
+        assert_eq!(batches.len(), 2);
+
+        let calls = instrumentation.calls.lock().unwrap();
+        let count = |name: &str, callback| calls[&(name.to_string(), callback)];
+        assert_eq!(count("CoalescePartitionsExec", "on_start"), 1);
+        assert_eq!(count("CoalescePartitionsExec", "on_batch"), 2);
+        assert_eq!(count("CoalescePartitionsExec", "on_end"), 1);
+        assert_eq!(count("DataSourceExec", "on_start"), 2);
+        assert_eq!(count("DataSourceExec", "on_batch"), 2);
+        assert_eq!(count("DataSourceExec", "on_end"), 2);
+        Ok(())
+    }
+}
@@ -32,6 +32,8 @@ pub mod enforce_distribution;
 pub mod enforce_sorting;
 pub mod ensure_coop;
 pub mod filter_pushdown;
+pub mod grouped_topk;
+pub mod instrument_streams;
 pub mod join_selection;
 pub mod limit_pushdown;
 pub mod limited_distinct_aggregation;
@@ -40,6 +42,7 @@ pub mod output_requirements;
 pub mod projection_pushdown;
 pub use datafusion_pruning as pruning;
 pub mod sanity_checker;
+pub mod share_identical_subplans;
 pub mod topk_aggregation;
 pub mod update_aggr_exprs;
 pub mod utils;
@@ -0,0 +1,300 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The [`ShareIdenticalSubplans`] optimizer rule executes identical subplans,
+//! such as a common table expression referenced more than once, only once.
+
+use std::fmt::Write;
+use std::sync::Arc;
+
+use crate::PhysicalOptimizerRule;
+
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_common::{HashMap, Result};
+use datafusion_physical_plan::shared::SharedExec;
+use datafusion_physical_plan::{displayable, ExecutionPlan, ExecutionPlanProperties};
+
+/// Replaces subplans that occur more than once in the plan with a single
+/// [`SharedExec`] referenced by each of their consumers, so that they are
+/// executed once and their buffered output is fed to every consumer.
+///
+/// Common table expressions are inlined into the plan, so this makes
+/// `WITH t AS (...) SELECT ... FROM t a JOIN t b ON ...` execute the
+/// subquery of `t` once.
+///
+/// Two subplans are considered identical when their verbose display is the
+/// same and all their leaves are known to produce the same data, see
+/// [`ExecutionPlan::output_identity`], which is the case for:
+/// * scans of the same files
+/// * scans of the same in-memory batches
+/// * `EmptyExec` and `PlaceholderRowExec`
+///
+/// Subplans with other leaves, unbounded subplans and subplans that contain
+/// dynamic filters, which are updated by their consumer at execution time,
+/// are never shared.
+///
+/// This rule is enabled by the `datafusion.optimizer.enable_subplan_sharing`
+/// option.
+#[derive(Default, Debug)]
+pub struct ShareIdenticalSubplans {}
+
+impl ShareIdenticalSubplans {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for ShareIdenticalSubplans {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.enable_subplan_sharing {
+            return Ok(plan);
+        }
+
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        plan.apply(|node| {
+            if let Some(key) = sharing_key(node) {
+                *occurrences.entry(key).or_default() += 1;
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+        let repeated_key = |node: &Arc<dyn ExecutionPlan>| {
+            sharing_key(node).filter(|key| occurrences[key] > 1)
+        };
+
+        // Only the outermost repeated subplans are shared: the subplans they
+        // contain are executed once as part of them
+        let mut consumers: HashMap<String, usize> = HashMap::new();
+        plan.apply(|node| match repeated_key(node) {
+            Some(key) => {
+                *consumers.entry(key).or_default() += 1;
+                Ok(TreeNodeRecursion::Jump)
+            }
+            None => Ok(TreeNodeRecursion::Continue),
+        })?;
+
+        let mut shared: HashMap<String, Arc<dyn ExecutionPlan>> = HashMap::new();
+        plan.transform_down(|node| {
+            let Some(key) = repeated_key(&node) else {
+                return Ok(Transformed::no(node));
+            };
+            let consumers = consumers[&key];
+            if consumers < 2 {
+                return Ok(Transformed::new(node, false, TreeNodeRecursion::Jump));
+            }
+            let shared = shared
+                .entry(key)
+                .or_insert_with(|| Arc::new(SharedExec::new(node, consumers)));
+            Ok(Transformed::new(
+                Arc::clone(shared),
+                true,
+                TreeNodeRecursion::Jump,
+            ))
+        })
+        .map(|t| t.data)
+    }
+
+    fn name(&self) -> &str {
+        "ShareIdenticalSubplans"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// Returns a key identifying the output of `plan`, or `None` if `plan` must
+/// not be shared
+fn sharing_key(plan: &Arc<dyn ExecutionPlan>) -> Option<String> {
+    // Sharing a leaf saves nothing, it is executed once in any case
+    if plan.children().is_empty()
+        || plan.as_any().is::<SharedExec>()
+        || plan.boundedness().is_unbounded()
+    {
+        return None;
+    }
+    let mut key = displayable(plan.as_ref()).indent(true).to_string();
+    if key.contains("DynamicFilterPhysicalExpr") {
+        return None;
+    }
+    write!(key, "{:?}", plan.schema()).ok()?;
+
+    let mut shareable = true;
+    plan.apply(|node| {
+        if node.children().is_empty() {
+            match node.output_identity() {
+                Some(identity) => {
+                    key.push(',');
+                    key.push_str(&identity);
+                }
+                None => shareable = false,
+            }
+        }
+        Ok(if shareable {
+            TreeNodeRecursion::Continue
+        } else {
+            TreeNodeRecursion::Stop
+        })
+    })
+    .ok()?;
+    shareable.then_some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use datafusion_common::{JoinType, NullEquality};
+    use datafusion_physical_expr::expressions::col;
+    use datafusion_physical_plan::aggregates::{
+        AggregateExec, AggregateMode, PhysicalGroupBy,
+    };
+    use datafusion_physical_plan::joins::{CrossJoinExec, HashJoinExec, PartitionMode};
+    use datafusion_physical_plan::test::{make_partition, TestMemoryExec};
+    use insta::assert_snapshot;
+
+    /// Scans a new batch of `sz` rows
+    fn scan(sz: i32) -> Result<Arc<dyn ExecutionPlan>> {
+        let batch = make_partition(sz);
+        let schema = batch.schema();
+        Ok(TestMemoryExec::try_new_exec(&[vec![batch]], schema, None)?)
+    }
+
+    fn config() -> ConfigOptions {
+        let mut config = ConfigOptions::new();
+        config.optimizer.enable_subplan_sharing = true;
+        config
+    }
+
+    fn aggregate(input: Arc<dyn ExecutionPlan>) -> Result<Arc<dyn ExecutionPlan>> {
+        let group_by = PhysicalGroupBy::new_single(vec![(
+            col("i", &input.schema())?,
+            "i".to_string(),
+        )]);
+        let schema = input.schema();
+        Ok(Arc::new(AggregateExec::try_new(
+            AggregateMode::Single,
+            group_by,
+            vec![],
+            vec![],
+            input,
+            schema,
+        )?))
+    }
+
+    fn self_join(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let on = vec![(col("i", &left.schema())?, col("i", &right.schema())?)];
+        Ok(Arc::new(HashJoinExec::try_new(
+            left,
+            right,
+            on,
+            None,
+            &JoinType::Inner,
+            None,
+            PartitionMode::CollectLeft,
+            NullEquality::NullEqualsNothing,
+        )?))
+    }
+
+    #[test]
+    fn shares_identical_subplans() -> Result<()> {
+        let input = scan(100)?;
+        let plan = self_join(
+            aggregate(Arc::clone(&input))?,
+            aggregate(Arc::clone(&input))?,
+        )?;
+
+        let optimized = ShareIdenticalSubplans::new().optimize(plan, &config())?;
+        let children = optimized.children();
+        assert!(Arc::ptr_eq(children[0], children[1]));
+        assert_snapshot!(displayable(optimized.as_ref()).indent(true), @r"
+        HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(i@0, i@0)]
+          SharedExec: consumers=2
+            AggregateExec: mode=Single, gby=[i@0 as i], aggr=[]
+              DataSourceExec: partitions=1, partition_sizes=[1]
+          SharedExec: consumers=2
+            AggregateExec: mode=Single, gby=[i@0 as i], aggr=[]
+              DataSourceExec: partitions=1, partition_sizes=[1]
+        ");
+        Ok(())
+    }
+
+    #[test]
+    fn disabled_by_default() -> Result<()> {
+        let input = scan(100)?;
+        let plan = self_join(
+            aggregate(Arc::clone(&input))?,
+            aggregate(Arc::clone(&input))?,
+        )?;
+
+        let optimized = ShareIdenticalSubplans::new()
+            .optimize(Arc::clone(&plan), &ConfigOptions::new())?;
+        assert!(Arc::ptr_eq(&plan, &optimized));
+        Ok(())
+    }
+
+    #[test]
+    fn does_not_share_scans_of_different_data() -> Result<()> {
+        // Both scans display the same, but scan different batches
+        let plan = self_join(aggregate(scan(100)?)?, aggregate(scan(100)?)?)?;
+
+        let optimized = ShareIdenticalSubplans::new().optimize(plan, &config())?;
+        assert_snapshot!(displayable(optimized.as_ref()).indent(true), @r"
+        HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(i@0, i@0)]
+          AggregateExec: mode=Single, gby=[i@0 as i], aggr=[]
+            DataSourceExec: partitions=1, partition_sizes=[1]
+          AggregateExec: mode=Single, gby=[i@0 as i], aggr=[]
+            DataSourceExec: partitions=1, partition_sizes=[1]
+        ");
+        Ok(())
+    }
+
+    #[test]
+    fn shares_outermost_subplans_only() -> Result<()> {
+        let input = scan(10)?;
+        let shared = aggregate(aggregate(Arc::clone(&input))?)?;
+        let plan: Arc<dyn ExecutionPlan> = Arc::new(CrossJoinExec::new(
+            Arc::clone(&shared),
+            Arc::new(CrossJoinExec::new(Arc::clone(&shared), aggregate(input)?)),
+        ));
+
+        let optimized = ShareIdenticalSubplans::new().optimize(plan, &config())?;
+        assert_snapshot!(displayable(optimized.as_ref()).indent(true), @r"
+        CrossJoinExec
+          SharedExec: consumers=2
+            AggregateExec: mode=Single, gby=[i@0 as i], aggr=[]
+              AggregateExec: mode=Single, gby=[i@0 as i], aggr=[]
+                DataSourceExec: partitions=1, partition_sizes=[1]
+          CrossJoinExec
+            SharedExec: consumers=2
+              AggregateExec: mode=Single, gby=[i@0 as i], aggr=[]
+                AggregateExec: mode=Single, gby=[i@0 as i], aggr=[]
+                  DataSourceExec: partitions=1, partition_sizes=[1]
+            AggregateExec: mode=Single, gby=[i@0 as i], aggr=[]
+              DataSourceExec: partitions=1, partition_sizes=[1]
+        ");
+        Ok(())
+    }
+}
@@ -110,6 +110,10 @@ pub(crate) trait GroupValues: Send {
 
     /// Clear the contents and shrink the capacity to the size of the batch (free up memory usage)
     fn clear_shrink(&mut self, batch: &RecordBatch);
+
+    /// Shrink the capacity to the groups left after emitting some of them
+    /// (free up memory usage)
+    fn shrink_to_fit(&mut self) {}
 }
 
 /// Return a specialized implementation of [`GroupValues`] for the given schema.
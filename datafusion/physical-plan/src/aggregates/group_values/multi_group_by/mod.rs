@@ -1176,6 +1176,12 @@ impl<const STREAMING: bool> GroupValues for GroupValuesColumn<STREAMING> {
         Ok(output)
     }
 
+    fn shrink_to_fit(&mut self) {
+        self.map.shrink_to_fit(|(hash, _)| *hash);
+        self.map_size = self.map.capacity() * size_of::<(u64, usize)>();
+        self.hashes_buffer.shrink_to_fit();
+    }
+
     fn clear_shrink(&mut self, batch: &RecordBatch) {
         let count = batch.num_rows();
         self.group_values.clear();
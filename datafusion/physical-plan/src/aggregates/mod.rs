@@ -1432,15 +1432,16 @@ mod tests {
     use crate::RecordBatchStream;
 
     use arrow::array::{
-        DictionaryArray, Float32Array, Float64Array, Int32Array, StructArray,
-        UInt32Array, UInt64Array,
+        DictionaryArray, Float32Array, Float64Array, Int32Array, StringViewArray,
+        StructArray, UInt32Array, UInt64Array,
     };
     use arrow::compute::{concat_batches, SortOptions};
     use arrow::datatypes::{DataType, Int32Type};
     use datafusion_common::test_util::{batches_to_sort_string, batches_to_string};
     use datafusion_common::{internal_err, DataFusionError, ScalarValue};
     use datafusion_execution::config::SessionConfig;
-    use datafusion_execution::memory_pool::FairSpillPool;
+    use datafusion_execution::disk_manager::{DiskManagerBuilder, DiskManagerMode};
+    use datafusion_execution::memory_pool::{FairSpillPool, GreedyMemoryPool};
     use datafusion_execution::runtime_env::RuntimeEnvBuilder;
     use datafusion_functions_aggregate::array_agg::array_agg_udaf;
     use datafusion_functions_aggregate::average::avg_udaf;
@@ -3027,4 +3028,175 @@ mod tests {
         run_test_with_spill_pool_if_necessary(20_000, false).await?;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_aggregate_spill_high_cardinality_string_view() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("k", DataType::Utf8View, false),
+            Field::new("v", DataType::Int32, false),
+        ]));
+        // 20,000 keys, each repeated twice, too long to be inlined in the views
+        let batches = (0..40)
+            .map(|batch| {
+                let rows = batch * 1000..(batch + 1) * 1000;
+                RecordBatch::try_new(
+                    Arc::clone(&schema),
+                    vec![
+                        Arc::new(StringViewArray::from_iter_values(rows.clone().map(
+                            |row| format!("high cardinality key {:08}", row % 20_000),
+                        ))),
+                        Arc::new(Int32Array::from_iter_values(rows)),
+                    ],
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let input = TestMemoryExec::try_new_exec(&[batches], Arc::clone(&schema), None)?;
+
+        let aggregate = Arc::new(AggregateExec::try_new(
+            AggregateMode::Single,
+            PhysicalGroupBy::new_single(vec![(col("k", &schema)?, "k".to_string())]),
+            vec![Arc::new(
+                AggregateExprBuilder::new(count_udaf(), vec![col("v", &schema)?])
+                    .schema(Arc::clone(&schema))
+                    .alias("COUNT(v)")
+                    .build()?,
+            )],
+            vec![None],
+            input,
+            Arc::clone(&schema),
+        )?);
+
+        let task_ctx = new_spill_ctx(1000, 1_000_000);
+        let result = collect(aggregate.execute(0, task_ctx)?).await?;
+        let num_groups: usize = result.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(num_groups, 20_000);
+        assert_eq!(result[0].schema().field(0).data_type(), &DataType::Utf8View);
+
+        let metrics = aggregate.metrics().unwrap();
+        let spilled_rows = metrics.spilled_rows().unwrap();
+        assert!(spilled_rows > 0);
+        // the spill files hold the views, their own payload and the counts of
+        // the spilled groups, rather than the payload of all groups emitted by
+        // a spill for each spilled batch
+        assert!(metrics.spilled_bytes().unwrap() < 100 * spilled_rows);
+
+        Ok(())
+    }
+
+    /// Input of 20,000 rows in batches of 500, sorted on the `Utf8View`
+    /// column `k` with each value repeated twice
+    fn sorted_string_view_batches() -> (SchemaRef, Vec<RecordBatch>) {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("k", DataType::Utf8View, false),
+            Field::new("j", DataType::Int32, false),
+            Field::new("v", DataType::Int32, false),
+        ]));
+        let batches = (0..40)
+            .map(|batch| {
+                let rows = batch * 500..(batch + 1) * 500;
+                RecordBatch::try_new(
+                    Arc::clone(&schema),
+                    vec![
+                        Arc::new(StringViewArray::from_iter_values(
+                            rows.clone().map(|row| format!("key-{:08}", row / 2)),
+                        )),
+                        Arc::new(Int32Array::from_iter_values(
+                            rows.clone().map(|row| row % 3),
+                        )),
+                        Arc::new(Int32Array::from_iter_values(rows)),
+                    ],
+                )
+                .unwrap()
+            })
+            .collect();
+        (schema, batches)
+    }
+
+    #[tokio::test]
+    async fn test_sorted_group_by_bounded_memory() -> Result<()> {
+        let (schema, batches) = sorted_string_view_batches();
+        let sort_information =
+            vec![[PhysicalSortExpr::new_default(col("k", &schema)?)].into()];
+
+        for (sorted, group_cols, mode, expected_order_mode, expected_groups) in [
+            (
+                true,
+                vec!["k"],
+                AggregateMode::Single,
+                InputOrderMode::Sorted,
+                10_000,
+            ),
+            (
+                true,
+                vec!["k"],
+                AggregateMode::Partial,
+                InputOrderMode::Sorted,
+                10_000,
+            ),
+            (
+                true,
+                vec!["j", "k"],
+                AggregateMode::Single,
+                InputOrderMode::PartiallySorted(vec![1]),
+                20_000,
+            ),
+            (
+                false,
+                vec!["k"],
+                AggregateMode::Single,
+                InputOrderMode::Linear,
+                10_000,
+            ),
+        ] {
+            let mut input =
+                TestMemoryExec::try_new(&[batches.clone()], Arc::clone(&schema), None)?;
+            if sorted {
+                input = input.try_with_sort_information(sort_information.clone())?;
+            }
+            let input = Arc::new(TestMemoryExec::update_cache(Arc::new(input)));
+            let group_by = PhysicalGroupBy::new_single(
+                group_cols
+                    .iter()
+                    .map(|name| Ok((col(name, &schema)?, name.to_string())))
+                    .collect::<Result<_>>()?,
+            );
+            let aggregate = Arc::new(AggregateExec::try_new(
+                mode,
+                group_by,
+                vec![Arc::new(
+                    AggregateExprBuilder::new(count_udaf(), vec![col("v", &schema)?])
+                        .schema(Arc::clone(&schema))
+                        .alias("COUNT(v)")
+                        .build()?,
+                )],
+                vec![None],
+                input,
+                Arc::clone(&schema),
+            )?);
+            assert_eq!(aggregate.input_order_mode(), &expected_order_mode);
+
+            // far less than needed to hold all groups, and spilling is disabled
+            let runtime = RuntimeEnvBuilder::new()
+                .with_memory_pool(Arc::new(GreedyMemoryPool::new(10_000)))
+                .with_disk_manager_builder(
+                    DiskManagerBuilder::default().with_mode(DiskManagerMode::Disabled),
+                )
+                .build_arc()?;
+            let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+            let result = collect(aggregate.execute(0, task_ctx)?).await;
+
+            if sorted {
+                let num_groups: usize = result?.iter().map(|b| b.num_rows()).sum();
+                assert_eq!(num_groups, expected_groups);
+            } else {
+                let err = result.unwrap_err();
+                assert!(
+                    matches!(err.find_root(), DataFusionError::ResourcesExhausted(_)),
+                    "{err}"
+                );
+            }
+        }
+        Ok(())
+    }
 }
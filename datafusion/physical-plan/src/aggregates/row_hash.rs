@@ -663,6 +663,13 @@ impl Stream for GroupedHashAggregateStream {
 
                             self.update_skip_aggregation_probe(input_rows);
 
+                            // Groups completed by the batch may have been
+                            // emitted to stay within the memory limit
+                            if let ExecutionState::ProducingOutput(_) = self.exec_state {
+                                timer.done();
+                                break 'reading_input;
+                            }
+
                             // If we can begin emitting rows, do so,
                             // otherwise keep consuming input
                             assert!(!self.input_done);
@@ -704,6 +711,13 @@ impl Stream for GroupedHashAggregateStream {
                             // Do the grouping
                             self.group_aggregate_batch(batch)?;
 
+                            // Groups completed by the batch may have been
+                            // emitted to stay within the memory limit
+                            if let ExecutionState::ProducingOutput(_) = self.exec_state {
+                                timer.done();
+                                break 'reading_input;
+                            }
+
                             // If we can begin emitting rows, do so,
                             // otherwise keep consuming input
                             assert!(!self.input_done);
@@ -901,11 +915,26 @@ impl GroupedHashAggregateStream {
             }
         }
 
-        match self.update_memory_reservation() {
+        let mut reservation_result = self.update_memory_reservation();
+        if let Err(DataFusionError::ResourcesExhausted(_)) = reservation_result {
+            // When the input is ordered by (a prefix of) the group keys, emit
+            // the groups completed by this batch right away, so that only the
+            // groups still in progress need to fit in the memory
+            if let Some(to_emit) = self.group_ordering.emit_to() {
+                if let Some(batch) = self.emit(to_emit, false)? {
+                    self.exec_state = ExecutionState::ProducingOutput(batch);
+                }
+                self.group_values.shrink_to_fit();
+                reservation_result = self.update_memory_reservation();
+            }
+        }
+        match reservation_result {
             // Here we can ignore `insufficient_capacity_err` because we will spill later,
-            // but at least one batch should fit in the memory
+            // but at least one batch should fit in the memory. Ordered groups are not
+            // spilled, see `spill_previous_if_necessary`
             Err(DataFusionError::ResourcesExhausted(_))
-                if self.group_values.len() >= self.batch_size =>
+                if self.group_values.len() >= self.batch_size
+                    && matches!(self.group_ordering, GroupOrdering::None) =>
             {
                 Ok(())
             }
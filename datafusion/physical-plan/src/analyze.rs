@@ -26,12 +26,14 @@ use super::{
     SendableRecordBatchStream,
 };
 use crate::display::DisplayableExecutionPlan;
+use crate::metrics::collect_plan_metrics;
 use crate::{DisplayFormatType, ExecutionPlan, Partitioning};
 
 use arrow::{array::StringBuilder, datatypes::SchemaRef, record_batch::RecordBatch};
 use datafusion_common::instant::Instant;
 use datafusion_common::{internal_err, DataFusionError, Result};
 use datafusion_execution::TaskContext;
+use datafusion_expr::AnalyzeFormat;
 use datafusion_physical_expr::EquivalenceProperties;
 
 use futures::StreamExt;
@@ -44,6 +46,8 @@ pub struct AnalyzeExec {
     verbose: bool,
     /// If statistics should be displayed
     show_statistics: bool,
+    /// The format of the output
+    format: AnalyzeFormat,
     /// The input plan (the plan being analyzed)
     pub(crate) input: Arc<dyn ExecutionPlan>,
     /// The output schema for RecordBatches of this exec node
@@ -63,6 +67,7 @@ impl AnalyzeExec {
         AnalyzeExec {
             verbose,
             show_statistics,
+            format: AnalyzeFormat::Indent,
             input,
             schema,
             cache,
@@ -79,6 +84,22 @@ impl AnalyzeExec {
         self.show_statistics
     }
 
+    /// Set the format of the output, [`AnalyzeFormat::Indent`] by default.
+    ///
+    /// With [`AnalyzeFormat::Json`], the output is a single row with the
+    /// document of [`MetricsNode::to_json`] and `verbose` is ignored.
+    ///
+    /// [`MetricsNode::to_json`]: crate::metrics::MetricsNode::to_json
+    pub fn with_format(mut self, format: AnalyzeFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Access to format
+    pub fn format(&self) -> AnalyzeFormat {
+        self.format
+    }
+
     /// The input plan
     pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
         &self.input
@@ -106,7 +127,11 @@ impl DisplayAs for AnalyzeExec {
     ) -> std::fmt::Result {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
-                write!(f, "AnalyzeExec verbose={}", self.verbose)
+                write!(f, "AnalyzeExec verbose={}", self.verbose)?;
+                if self.format != AnalyzeFormat::Indent {
+                    write!(f, ", format={}", self.format)?;
+                }
+                Ok(())
             }
             DisplayFormatType::TreeRender => {
                 // TODO: collect info
@@ -143,12 +168,15 @@ impl ExecutionPlan for AnalyzeExec {
         self: Arc<Self>,
         mut children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(Self::new(
-            self.verbose,
-            self.show_statistics,
-            children.pop().unwrap(),
-            Arc::clone(&self.schema),
-        )))
+        Ok(Arc::new(
+            Self::new(
+                self.verbose,
+                self.show_statistics,
+                children.pop().unwrap(),
+                Arc::clone(&self.schema),
+            )
+            .with_format(self.format),
+        ))
     }
 
     fn execute(
@@ -183,6 +211,7 @@ impl ExecutionPlan for AnalyzeExec {
         let captured_schema = Arc::clone(&self.schema);
         let verbose = self.verbose;
         let show_statistics = self.show_statistics;
+        let format = self.format;
 
         // future that gathers the results from all the tasks in the
         // JoinSet that computes the overall row count and final
@@ -195,6 +224,9 @@ impl ExecutionPlan for AnalyzeExec {
             }
 
             let duration = Instant::now() - start;
+            if format == AnalyzeFormat::Json {
+                return create_json_output_batch(captured_input, captured_schema);
+            }
             create_output_batch(
                 verbose,
                 show_statistics,
@@ -261,6 +293,27 @@ fn create_output_batch(
     .map_err(DataFusionError::from)
 }
 
+/// Creates the output of AnalyzeExec in [`AnalyzeFormat::Json`] as a RecordBatch
+fn create_json_output_batch(
+    input: Arc<dyn ExecutionPlan>,
+    schema: SchemaRef,
+) -> Result<RecordBatch> {
+    let mut type_builder = StringBuilder::with_capacity(1, 1024);
+    let mut plan_builder = StringBuilder::with_capacity(1, 1024);
+
+    type_builder.append_value("Plan with Metrics");
+    plan_builder.append_value(collect_plan_metrics(input.as_ref()).to_json());
+
+    RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(type_builder.finish()),
+            Arc::new(plan_builder.finish()),
+        ],
+    )
+    .map_err(DataFusionError::from)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,6 +120,8 @@ pub struct DisplayableExecutionPlan<'a> {
     show_statistics: bool,
     /// If schema should be displayed. See [`Self::set_show_schema`]
     show_schema: bool,
+    /// If plan properties should be displayed. See [`Self::set_show_properties`]
+    show_properties: bool,
 }
 
 impl<'a> DisplayableExecutionPlan<'a> {
@@ -131,6 +133,7 @@ impl<'a> DisplayableExecutionPlan<'a> {
             show_metrics: ShowMetrics::None,
             show_statistics: false,
             show_schema: false,
+            show_properties: false,
         }
     }
 
@@ -143,6 +146,7 @@ impl<'a> DisplayableExecutionPlan<'a> {
             show_metrics: ShowMetrics::Aggregated,
             show_statistics: false,
             show_schema: false,
+            show_properties: false,
         }
     }
 
@@ -155,6 +159,7 @@ impl<'a> DisplayableExecutionPlan<'a> {
             show_metrics: ShowMetrics::Full,
             show_statistics: false,
             show_schema: false,
+            show_properties: false,
         }
     }
 
@@ -173,6 +178,18 @@ impl<'a> DisplayableExecutionPlan<'a> {
         self
     }
 
+    /// Enable display of plan properties in [`Self::tree_render`]
+    ///
+    /// If true, each node of the tree also shows its output partitioning,
+    /// its output ordering, its estimated number of rows and whether its
+    /// predicate is pushed down into a scan. Nodes with many details, such
+    /// as projections of many expressions, only show the first ones followed
+    /// by the number of omitted ones.
+    pub fn set_show_properties(mut self, show_properties: bool) -> Self {
+        self.show_properties = show_properties;
+        self
+    }
+
     /// Return a `format`able structure that produces a single line
     /// per node.
     ///
@@ -270,14 +287,21 @@ impl<'a> DisplayableExecutionPlan<'a> {
     pub fn tree_render(&self) -> impl fmt::Display + 'a {
         struct Wrapper<'a> {
             plan: &'a dyn ExecutionPlan,
+            show_properties: bool,
         }
         impl fmt::Display for Wrapper<'_> {
             fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-                let mut visitor = TreeRenderVisitor { f };
+                let mut visitor = TreeRenderVisitor {
+                    f,
+                    show_properties: self.show_properties,
+                };
                 visitor.visit(self.plan)
             }
         }
-        Wrapper { plan: self.inner }
+        Wrapper {
+            plan: self.inner,
+            show_properties: self.show_properties,
+        }
     }
 
     /// Return a single-line summary of the root of the plan
@@ -540,6 +564,8 @@ impl ExecutionPlanVisitor for GraphvizVisitor<'_, '_> {
 struct TreeRenderVisitor<'a, 'b> {
     /// Write to this formatter
     f: &'a mut Formatter<'b>,
+    /// Render the partitioning, ordering and estimated rows of each node
+    show_properties: bool,
 }
 
 impl TreeRenderVisitor<'_, '_> {
@@ -567,7 +593,7 @@ impl TreeRenderVisitor<'_, '_> {
     /// 2. Render node content and vertical connections
     /// 3. Render bottom borders and connections
     pub fn visit(&mut self, plan: &dyn ExecutionPlan) -> Result<(), fmt::Error> {
-        let root = RenderTree::create_tree(plan);
+        let root = RenderTree::create_tree(plan, self.show_properties);
 
         for y in 0..root.height {
             // Start by rendering the top layer.
@@ -651,6 +677,10 @@ impl TreeRenderVisitor<'_, '_> {
                     extra_info_item,
                     Self::MAX_EXTRA_LINES,
                 );
+                if node.omitted_details > 0 {
+                    extra_info_item.push(String::new());
+                    extra_info_item.push(format!("... {} more", node.omitted_details));
+                }
                 if extra_info_item.len() > extra_height {
                     extra_height = extra_info_item.len();
                 }
@@ -151,6 +151,11 @@ impl ExecutionPlan for EmptyExec {
         )?))
     }
 
+    fn output_identity(&self) -> Option<String> {
+        // There is no data
+        Some(String::new())
+    }
+
     fn statistics(&self) -> Result<Statistics> {
         self.partition_statistics(None)
     }
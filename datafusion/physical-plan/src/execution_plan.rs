@@ -47,7 +47,7 @@ use crate::stream::RecordBatchStreamAdapter;
 use arrow::array::{Array, RecordBatch};
 use arrow::datatypes::SchemaRef;
 use datafusion_common::config::ConfigOptions;
-use datafusion_common::{exec_err, Constraints, Result};
+use datafusion_common::{exec_err, Constraints, Result, ScalarValue};
 use datafusion_common_runtime::JoinSet;
 use datafusion_execution::TaskContext;
 use datafusion_physical_expr::EquivalenceProperties;
@@ -477,6 +477,32 @@ pub trait ExecutionPlan: Debug + DisplayAs + Send + Sync {
         CardinalityEffect::Unknown
     }
 
+    /// Returns what identifies the data produced by this leaf beyond its
+    /// display, or `None` (the default) if it is not known to produce the
+    /// same data as another leaf with the same display and identity.
+    ///
+    /// Identical subplans whose leaves all have an identity are executed once,
+    /// and their output fed to each of their consumers by a [`SharedExec`].
+    ///
+    /// [`SharedExec`]: crate::shared::SharedExec
+    fn output_identity(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the number of rows produced by this plan for each combination
+    /// of values of the given output `columns`, if it is known exactly without
+    /// reading any data, or `None` (the default) otherwise.
+    ///
+    /// Combinations of values without any row may be omitted. This allows
+    /// answering queries like `SELECT part, count(*) FROM t GROUP BY part`
+    /// from the partition values of the files of `t`.
+    fn num_rows_by_values(
+        &self,
+        _columns: &[usize],
+    ) -> Result<Option<Vec<(Vec<ScalarValue>, usize)>>> {
+        Ok(None)
+    }
+
     /// Attempts to push down the given projection into the input of this `ExecutionPlan`.
     ///
     /// If the operator supports this optimization, the resulting plan will be:
@@ -53,14 +53,16 @@ use datafusion_common::{
     internal_err, plan_err, project_schema, DataFusionError, Result, ScalarValue,
 };
 use datafusion_execution::TaskContext;
+use datafusion_expr::interval_arithmetic::Interval;
 use datafusion_expr::Operator;
 use datafusion_physical_expr::equivalence::ProjectionMapping;
 use datafusion_physical_expr::expressions::{lit, BinaryExpr, Column};
+use datafusion_physical_expr::intervals::cp_solver::ExprIntervalGraph;
 use datafusion_physical_expr::intervals::utils::check_support;
 use datafusion_physical_expr::utils::collect_columns;
 use datafusion_physical_expr::{
-    analyze, conjunction, split_conjunction, AcrossPartitions, AnalysisContext,
-    ConstExpr, ExprBoundaries, PhysicalExpr,
+    analyze, bind_cancellation, conjunction, estimate_selectivity, split_conjunction,
+    AcrossPartitions, AnalysisContext, ConstExpr, ExprBoundaries, PhysicalExpr,
 };
 
 use datafusion_physical_expr_common::physical_expr::fmt_sql;
@@ -188,15 +190,37 @@ impl FilterExec {
         default_selectivity: u8,
     ) -> Result<Statistics> {
         if !check_support(predicate, &schema) {
-            let selectivity = default_selectivity as f64 / 100.0;
+            let default_selectivity = default_selectivity as f64 / 100.0;
+            let selectivity = AnalysisContext::try_from_statistics(
+                &schema,
+                &input_stats.column_statistics,
+            )
+            .map(|context| {
+                estimate_selectivity(predicate, &context, &schema, default_selectivity)
+            })
+            .unwrap_or(default_selectivity);
             let mut stats = input_stats.to_inexact();
             stats.num_rows = stats.num_rows.with_estimated_selectivity(selectivity);
             stats.total_byte_size = stats
                 .total_byte_size
                 .with_estimated_selectivity(selectivity);
+            for col_stats in stats.column_statistics.iter_mut() {
+                col_stats.null_count =
+                    col_stats.null_count.with_estimated_selectivity(selectivity);
+                col_stats.distinct_count = col_stats
+                    .distinct_count
+                    .with_estimated_selectivity(selectivity);
+            }
             return Ok(stats);
         }
 
+        // A predicate holding for every row keeps the statistics as they are,
+        // exact ones included, such as a filter on a partition column after
+        // the files were pruned to the filtered value
+        if is_certainly_true(predicate, &input_stats, &schema)? {
+            return Ok(input_stats);
+        }
+
         let num_rows = input_stats.num_rows;
         let total_byte_size = input_stats.total_byte_size;
         let input_analysis_ctx = AnalysisContext::try_from_statistics(
@@ -214,6 +238,7 @@ impl FilterExec {
         let column_statistics = collect_new_statistics(
             &input_stats.column_statistics,
             analysis_ctx.boundaries,
+            selectivity,
         );
         Ok(Statistics {
             num_rows,
@@ -390,7 +415,10 @@ impl ExecutionPlan for FilterExec {
         let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
         Ok(Box::pin(FilterExecStream {
             schema: self.schema(),
-            predicate: Arc::clone(&self.predicate),
+            predicate: bind_cancellation(
+                Arc::clone(&self.predicate),
+                context.cancellation_token(),
+            )?,
             input: self.input.execute(partition, context)?,
             baseline_metrics,
             projection: self.projection.clone(),
@@ -590,13 +618,54 @@ impl EmbeddedProjection for FilterExec {
     }
 }
 
+/// Returns whether `predicate` holds for every row of an input with the
+/// statistics `input_stats`, which requires the exact bounds of the columns it
+/// references, and that these columns have no nulls
+fn is_certainly_true(
+    predicate: &Arc<dyn PhysicalExpr>,
+    input_stats: &Statistics,
+    schema: &SchemaRef,
+) -> Result<bool> {
+    let columns = collect_columns(predicate)
+        .into_iter()
+        .map(|column| Arc::new(column) as _)
+        .collect::<Vec<_>>();
+    let mut graph = ExprIntervalGraph::try_new(Arc::clone(predicate), schema)?;
+    let mut intervals = vec![];
+    for (expr, index) in graph.gather_node_indices(&columns) {
+        let Some(column) = expr.as_any().downcast_ref::<Column>() else {
+            return Ok(false);
+        };
+        let Some(ColumnStatistics {
+            null_count: Precision::Exact(0),
+            min_value: Precision::Exact(min),
+            max_value: Precision::Exact(max),
+            ..
+        }) = input_stats.column_statistics.get(column.index())
+        else {
+            return Ok(false);
+        };
+        if min.is_null() || max.is_null() {
+            return Ok(false);
+        }
+        let Ok(interval) = Interval::try_new(min.clone(), max.clone()) else {
+            return Ok(false);
+        };
+        intervals.push((index, interval));
+    }
+    graph.assign_intervals(&intervals);
+    Ok(graph.evaluate_bounds()? == &Interval::CERTAINLY_TRUE)
+}
+
 /// This function ensures that all bounds in the `ExprBoundaries` vector are
 /// converted to closed bounds. If a lower/upper bound is initially open, it
 /// is adjusted by using the next/previous value for its data type to convert
-/// it into a closed bound.
+/// it into a closed bound. The null and distinct counts of the columns are
+/// scaled by the estimated `selectivity` of the predicate.
 fn collect_new_statistics(
     input_column_stats: &[ColumnStatistics],
     analysis_boundaries: Vec<ExprBoundaries>,
+    selectivity: f64,
 ) -> Vec<ColumnStatistics> {
     analysis_boundaries
         .into_iter()
@@ -621,17 +690,26 @@ fn collect_new_statistics(
                     };
                 };
                 let (lower, upper) = interval.into_bounds();
+                // A column restricted to a single (non-null) value has at most
+                // one distinct value
+                let distinct_count = if lower.eq(&upper) && !lower.is_null() {
+                    distinct_count.map(|count| count.min(1)).to_inexact()
+                } else {
+                    distinct_count.with_estimated_selectivity(selectivity)
+                };
                 let (min_value, max_value) = if lower.eq(&upper) {
                     (Precision::Exact(lower), Precision::Exact(upper))
                 } else {
                     (Precision::Inexact(lower), Precision::Inexact(upper))
                 };
                 ColumnStatistics {
-                    null_count: input_column_stats[idx].null_count.to_inexact(),
+                    null_count: input_column_stats[idx]
+                        .null_count
+                        .with_estimated_selectivity(selectivity),
                     max_value,
                     min_value,
                     sum_value: Precision::Absent,
-                    distinct_count: distinct_count.to_inexact(),
+                    distinct_count,
                 }
             },
         )
@@ -1188,6 +1266,51 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_filter_statistics_certainly_true() -> Result<()> {
+        // Table:
+        //      a: min=1, max=100, no nulls
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let input_statistics = |null_count| Statistics {
+            num_rows: Precision::Exact(1000),
+            total_byte_size: Precision::Exact(4000),
+            column_statistics: vec![ColumnStatistics {
+                min_value: Precision::Exact(ScalarValue::Int32(Some(1))),
+                max_value: Precision::Exact(ScalarValue::Int32(Some(100))),
+                null_count,
+                ..Default::default()
+            }],
+        };
+        // WHERE a>=1
+        let predicate: Arc<dyn PhysicalExpr> = Arc::new(BinaryExpr::new(
+            Arc::new(Column::new("a", 0)),
+            Operator::GtEq,
+            Arc::new(Literal::new(ScalarValue::Int32(Some(1)))),
+        ));
+
+        // The predicate holds for every row, so the statistics stay exact
+        let input = Arc::new(StatisticsExec::new(
+            input_statistics(Precision::Exact(0)),
+            schema.clone(),
+        ));
+        let filter: Arc<dyn ExecutionPlan> =
+            Arc::new(FilterExec::try_new(Arc::clone(&predicate), input)?);
+        let statistics = filter.partition_statistics(None)?;
+        assert_eq!(statistics, input_statistics(Precision::Exact(0)));
+
+        // Null values of `a` are filtered out
+        let input = Arc::new(StatisticsExec::new(
+            input_statistics(Precision::Exact(10)),
+            schema,
+        ));
+        let filter: Arc<dyn ExecutionPlan> =
+            Arc::new(FilterExec::try_new(predicate, input)?);
+        let statistics = filter.partition_statistics(None)?;
+        assert_eq!(statistics.num_rows, Precision::Inexact(1000));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_filter_statistics_zero_selective() -> Result<()> {
         // Table:
@@ -0,0 +1,392 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Hooks observing the streams of the operators of a plan while it runs, for
+//! example to export a span per operator and partition to a tracing system.
+//!
+//! A [`StreamInstrumentation`] is attached to an operator by wrapping it in an
+//! [`InstrumentedExec`], usually for all the operators of a plan with the
+//! `InstrumentStreams` physical optimizer rule. Plans that are not wrapped
+//! run exactly as before.
+//!
+//! With the `tracing` feature, [`TracingInstrumentation`] emits the spans with
+//! the [`tracing`](https://docs.rs/tracing) crate.
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::execution_plan::CardinalityEffect;
+use crate::metrics::MetricsSet;
+use crate::{
+    DisplayAs, DisplayFormatType, Distribution, ExecutionPlan, PlanProperties,
+    RecordBatchStream, SendableRecordBatchStream,
+};
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{internal_err, DataFusionError, Result, Statistics};
+use datafusion_execution::TaskContext;
+use datafusion_physical_expr_common::sort_expr::OrderingRequirements;
+use futures::{Stream, StreamExt};
+
+/// Observes the streams that operators produce when they are executed.
+///
+/// [`Self::on_start`] is called each time an instrumented operator executes
+/// one of its partitions, and returns the [`StreamObserver`] that sees the
+/// batches of that stream.
+pub trait StreamInstrumentation: Debug + Send + Sync {
+    /// Called when `plan` executes `partition`, before the stream of its
+    /// input is created
+    fn on_start(
+        &self,
+        plan: &Arc<dyn ExecutionPlan>,
+        partition: usize,
+    ) -> Box<dyn StreamObserver>;
+}
+
+/// Observes a single stream of an operator, see [`StreamInstrumentation`]
+pub trait StreamObserver: Send {
+    /// Called for each batch the stream returns
+    fn on_batch(&mut self, _batch: &RecordBatch) {}
+
+    /// Called once when the stream ends: after its last batch, when it returns
+    /// `error`, or when it is dropped before being exhausted
+    fn on_end(&mut self, _error: Option<&DataFusionError>) {}
+
+    /// Runs `f`, which executes the operator or polls its stream, for example
+    /// within a span of the observer
+    fn in_scope(&self, f: &mut dyn FnMut()) {
+        f()
+    }
+}
+
+/// Decorates an [`ExecutionPlan`], calling a [`StreamInstrumentation`] for
+/// the streams of each of its partitions.
+///
+/// The decorator is transparent: it has the name, properties, display and
+/// metrics of the operator it wraps, and the children of that operator as
+/// its own children, so plans with instrumented operators display as the
+/// original plans do.
+#[derive(Debug)]
+pub struct InstrumentedExec {
+    inner: Arc<dyn ExecutionPlan>,
+    instrumentation: Arc<dyn StreamInstrumentation>,
+}
+
+impl InstrumentedExec {
+    /// Create an operator calling `instrumentation` for the streams of `inner`
+    pub fn new(
+        inner: Arc<dyn ExecutionPlan>,
+        instrumentation: Arc<dyn StreamInstrumentation>,
+    ) -> Self {
+        Self {
+            inner,
+            instrumentation,
+        }
+    }
+
+    /// The instrumented operator
+    pub fn inner(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.inner
+    }
+
+    /// The instrumentation called for the streams of the operator
+    pub fn instrumentation(&self) -> &Arc<dyn StreamInstrumentation> {
+        &self.instrumentation
+    }
+
+    fn rewrap(&self, inner: Arc<dyn ExecutionPlan>) -> Arc<dyn ExecutionPlan> {
+        Arc::new(Self::new(inner, Arc::clone(&self.instrumentation)))
+    }
+}
+
+impl DisplayAs for InstrumentedExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        self.inner.fmt_as(t, f)
+    }
+}
+
+impl ExecutionPlan for InstrumentedExec {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.inner.schema()
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        self.inner.properties()
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        self.inner.required_input_distribution()
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<OrderingRequirements>> {
+        self.inner.required_input_ordering()
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        self.inner.maintains_input_order()
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        self.inner.benefits_from_input_partitioning()
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        self.inner.children()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let inner = Arc::clone(&self.inner).with_new_children(children)?;
+        Ok(self.rewrap(inner))
+    }
+
+    fn repartitioned(
+        &self,
+        target_partitions: usize,
+        config: &datafusion_common::config::ConfigOptions,
+    ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+        Ok(self
+            .inner
+            .repartitioned(target_partitions, config)?
+            .map(|inner| self.rewrap(inner)))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let mut observer = self.instrumentation.on_start(&self.inner, partition);
+        let mut stream = internal_err!("{} was not executed in scope", self.name());
+        observer.in_scope(&mut || {
+            stream = self.inner.execute(partition, Arc::clone(&context))
+        });
+        match stream {
+            Ok(inner) => Ok(Box::pin(InstrumentedStream {
+                inner,
+                observer,
+                ended: false,
+            })),
+            Err(e) => {
+                observer.on_end(Some(&e));
+                Err(e)
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        self.inner.metrics()
+    }
+
+    fn partition_statistics(&self, partition: Option<usize>) -> Result<Statistics> {
+        self.inner.partition_statistics(partition)
+    }
+
+    fn supports_limit_pushdown(&self) -> bool {
+        self.inner.supports_limit_pushdown()
+    }
+
+    fn with_fetch(&self, limit: Option<usize>) -> Option<Arc<dyn ExecutionPlan>> {
+        self.inner.with_fetch(limit).map(|inner| self.rewrap(inner))
+    }
+
+    fn fetch(&self) -> Option<usize> {
+        self.inner.fetch()
+    }
+
+    fn cardinality_effect(&self) -> CardinalityEffect {
+        self.inner.cardinality_effect()
+    }
+}
+
+/// The stream of an [`InstrumentedExec`], passing the batches of the stream
+/// of the operator through unchanged
+struct InstrumentedStream {
+    inner: SendableRecordBatchStream,
+    observer: Box<dyn StreamObserver>,
+    ended: bool,
+}
+
+impl Stream for InstrumentedStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let mut poll = Poll::Pending;
+        this.observer
+            .in_scope(&mut || poll = this.inner.poll_next_unpin(cx));
+        if !self.ended {
+            match &poll {
+                Poll::Ready(Some(Ok(batch))) => self.observer.on_batch(batch),
+                Poll::Ready(Some(Err(e))) => {
+                    self.ended = true;
+                    self.observer.on_end(Some(e));
+                }
+                Poll::Ready(None) => {
+                    self.ended = true;
+                    self.observer.on_end(None);
+                }
+                Poll::Pending => {}
+            }
+        }
+        poll
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl RecordBatchStream for InstrumentedStream {
+    fn schema(&self) -> SchemaRef {
+        self.inner.schema()
+    }
+}
+
+impl Drop for InstrumentedStream {
+    fn drop(&mut self) {
+        if !self.ended {
+            self.observer.on_end(None);
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+pub use tracing_instrumentation::TracingInstrumentation;
+
+#[cfg(feature = "tracing")]
+mod tracing_instrumentation {
+    use super::*;
+
+    use tracing::field::Empty;
+    use tracing::Span;
+
+    /// A [`StreamInstrumentation`] that opens a `tracing` span at the `INFO`
+    /// level for each stream of each operator.
+    ///
+    /// The spans are named `datafusion.operator` and have the fields
+    /// `operator`, `partition`, `output_rows`, `output_batches`, `error` and
+    /// `metrics`, the last being the metrics of the operator for the
+    /// partition when the stream ends. Spans are closed when their stream
+    /// ends.
+    ///
+    /// Operators are executed and polled within their span, so the spans of
+    /// inputs executed at that time are children of it, which lets
+    /// subscribers exporting to OpenTelemetry build a trace of the query.
+    /// Inputs executed in tasks spawned by an operator are only children of
+    /// it when a [`JoinSetTracer`] propagates spans to the tasks.
+    ///
+    /// [`JoinSetTracer`]: datafusion_common_runtime::JoinSetTracer
+    #[derive(Debug, Default)]
+    pub struct TracingInstrumentation {}
+
+    impl TracingInstrumentation {
+        pub fn new() -> Self {
+            Self {}
+        }
+    }
+
+    impl StreamInstrumentation for TracingInstrumentation {
+        fn on_start(
+            &self,
+            plan: &Arc<dyn ExecutionPlan>,
+            partition: usize,
+        ) -> Box<dyn StreamObserver> {
+            let span = tracing::info_span!(
+                "datafusion.operator",
+                operator = plan.name(),
+                partition,
+                output_rows = Empty,
+                output_batches = Empty,
+                error = Empty,
+                metrics = Empty,
+            );
+            Box::new(TracingObserver {
+                plan: Arc::clone(plan),
+                partition,
+                span,
+                output_rows: 0,
+                output_batches: 0,
+            })
+        }
+    }
+
+    struct TracingObserver {
+        plan: Arc<dyn ExecutionPlan>,
+        partition: usize,
+        span: Span,
+        output_rows: usize,
+        output_batches: usize,
+    }
+
+    impl StreamObserver for TracingObserver {
+        fn on_batch(&mut self, batch: &RecordBatch) {
+            self.output_rows += batch.num_rows();
+            self.output_batches += 1;
+        }
+
+        fn on_end(&mut self, error: Option<&DataFusionError>) {
+            self.span.record("output_rows", self.output_rows);
+            self.span.record("output_batches", self.output_batches);
+            if let Some(error) = error {
+                self.span.record("error", tracing::field::display(error));
+            }
+            if let Some(metrics) = self.plan.metrics() {
+                let mut partition_metrics = MetricsSet::new();
+                metrics
+                    .iter()
+                    .filter(|metric| metric.partition() == Some(self.partition))
+                    .for_each(|metric| partition_metrics.push(Arc::clone(metric)));
+                let metrics = partition_metrics
+                    .aggregate_by_name()
+                    .sorted_for_display()
+                    .timestamps_removed();
+                if metrics.iter().next().is_some() {
+                    self.span
+                        .record("metrics", tracing::field::display(metrics));
+                }
+            }
+            // Close the span now rather than when the stream is dropped
+            self.span = Span::none();
+        }
+
+        fn in_scope(&self, f: &mut dyn FnMut()) {
+            self.span.in_scope(f)
+        }
+    }
+}
@@ -0,0 +1,414 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Dynamic filters a [`HashJoinExec`] pushes into its probe side.
+//!
+//! Once the build side of the join has been collected, the bounds of its join
+//! keys are known, and probe rows whose keys fall outside of them can never
+//! find a match. These bounds are published through a
+//! [`DynamicFilterPhysicalExpr`] that the probe side scan can use to prune
+//! files and row groups and to filter rows while decoding.
+//!
+//! [`HashJoinExec`]: super::HashJoinExec
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use arrow::array::ArrayRef;
+use arrow::datatypes::SchemaRef;
+use datafusion_common::{
+    internal_datafusion_err, JoinType, NullEquality, Result, ScalarValue,
+};
+use datafusion_expr::Operator;
+use datafusion_functions_aggregate_common::min_max::{max_batch, min_batch};
+use datafusion_physical_expr::expressions::{
+    in_list, lit, BinaryExpr, DynamicFilterPhysicalExpr,
+};
+use datafusion_physical_expr::utils::conjunction;
+use datafusion_physical_expr::{PhysicalExpr, PhysicalExprRef};
+
+use super::JoinOnRef;
+
+/// Maximum number of build side rows for which the distinct key values are
+/// added to the dynamic filter as an `IN` list. This matches the number of
+/// values up to which `PruningPredicate` rewrites `IN` lists into
+/// equalities that can be checked against statistics.
+const MAX_IN_LIST_VALUES: usize = 20;
+
+/// Returns true if probe rows whose join keys do not match any build side key
+/// can be discarded before the join for this join type and null handling
+pub(super) fn supports_dynamic_filter(
+    join_type: JoinType,
+    null_equality: NullEquality,
+) -> bool {
+    // Joins that emit unmatched probe rows need to see every probe row, and
+    // null keys can only be filtered out if they never match.
+    null_equality == NullEquality::NullEqualsNothing
+        && matches!(
+            join_type,
+            JoinType::Inner
+                | JoinType::Left
+                | JoinType::LeftSemi
+                | JoinType::LeftAnti
+                | JoinType::LeftMark
+                | JoinType::RightSemi
+        )
+}
+
+/// Creates the dynamic filter over the probe side join keys `on`, which
+/// initially lets every row through, or `None` if the keys of both sides do
+/// not have the same type and can not be compared with each other.
+pub(super) fn create_dynamic_filter(
+    on: JoinOnRef,
+    build_schema: &SchemaRef,
+    probe_schema: &SchemaRef,
+) -> Result<Option<Arc<dyn PhysicalExpr>>> {
+    for (build_key, probe_key) in on {
+        if build_key.data_type(build_schema)? != probe_key.data_type(probe_schema)? {
+            return Ok(None);
+        }
+    }
+    let probe_keys = on
+        .iter()
+        .map(|(_, probe_key)| Arc::clone(probe_key))
+        .collect();
+    Ok(Some(Arc::new(DynamicFilterPhysicalExpr::new(
+        probe_keys,
+        lit(true),
+    ))))
+}
+
+/// Bounds of a single build side join key
+#[derive(Debug, Clone)]
+pub(super) struct KeyBounds {
+    /// Smallest non null key, null if all keys are null
+    min: ScalarValue,
+    /// Largest non null key, null if all keys are null
+    max: ScalarValue,
+    /// Distinct non null keys, if there are few enough build side rows
+    values: Option<Vec<ScalarValue>>,
+}
+
+impl KeyBounds {
+    fn try_new(values: &ArrayRef) -> Result<Self> {
+        let distinct = if values.len() <= MAX_IN_LIST_VALUES {
+            let mut distinct = Vec::with_capacity(values.len());
+            for row in 0..values.len() {
+                let value = ScalarValue::try_from_array(values, row)?;
+                if !value.is_null() && !distinct.contains(&value) {
+                    distinct.push(value);
+                }
+            }
+            Some(distinct)
+        } else {
+            None
+        };
+        Ok(Self {
+            min: min_batch(values)?,
+            max: max_batch(values)?,
+            values: distinct,
+        })
+    }
+
+    fn merge(self, other: Self) -> Self {
+        let pick = |a: ScalarValue, b: ScalarValue, keep: Ordering| {
+            if a.is_null() || (!b.is_null() && b.partial_cmp(&a) == Some(keep)) {
+                b
+            } else {
+                a
+            }
+        };
+        let values = match (self.values, other.values) {
+            (Some(mut values), Some(other)) => {
+                for value in other {
+                    if !values.contains(&value) {
+                        values.push(value);
+                    }
+                }
+                (values.len() <= MAX_IN_LIST_VALUES).then_some(values)
+            }
+            _ => None,
+        };
+        Self {
+            min: pick(self.min, other.min, Ordering::Less),
+            max: pick(self.max, other.max, Ordering::Greater),
+            values,
+        }
+    }
+
+    /// Returns true if all build side keys are null, which never match
+    fn is_all_null(&self) -> bool {
+        self.min.is_null() || self.max.is_null()
+    }
+
+    /// Returns the predicate that probe side `key` has to satisfy to possibly
+    /// match a build side row
+    fn predicate(
+        &self,
+        key: &PhysicalExprRef,
+        probe_schema: &SchemaRef,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        let mut predicates: Vec<Arc<dyn PhysicalExpr>> = vec![
+            Arc::new(BinaryExpr::new(
+                Arc::clone(key),
+                Operator::GtEq,
+                lit(self.min.clone()),
+            )),
+            Arc::new(BinaryExpr::new(
+                Arc::clone(key),
+                Operator::LtEq,
+                lit(self.max.clone()),
+            )),
+        ];
+        if let Some(values) = &self.values {
+            let mut values = values.clone();
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            predicates.push(in_list(
+                Arc::clone(key),
+                values.into_iter().map(lit).collect(),
+                &false,
+                probe_schema,
+            )?);
+        }
+        Ok(conjunction(predicates))
+    }
+}
+
+/// Bounds of the join keys of (one partition of) the build side
+#[derive(Debug, Clone)]
+pub(super) enum BuildSideBounds {
+    /// The build side has no rows, so no probe row can match
+    Empty,
+    /// The bounds of each join key, `None` for keys whose bounds could not be
+    /// computed, for example because their type does not support ordering
+    Keys(Vec<Option<KeyBounds>>),
+}
+
+impl BuildSideBounds {
+    /// Computes the bounds of the evaluated build side join keys
+    pub(super) fn new(key_values: &[ArrayRef], num_rows: usize) -> Self {
+        if num_rows == 0 {
+            return Self::Empty;
+        }
+        Self::Keys(
+            key_values
+                .iter()
+                .map(|values| KeyBounds::try_new(values).ok())
+                .collect(),
+        )
+    }
+
+    /// Merges the bounds of two parts of the build side
+    pub(super) fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Empty, bounds) | (bounds, Self::Empty) => bounds,
+            (Self::Keys(keys), Self::Keys(other)) => Self::Keys(
+                keys.into_iter()
+                    .zip(other)
+                    .map(|(a, b)| Some(a?.merge(b?)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the predicate probe rows have to satisfy to possibly match a
+    /// build side row with these bounds
+    fn predicate(
+        &self,
+        probe_keys: &[PhysicalExprRef],
+        probe_schema: &SchemaRef,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        match self {
+            Self::Empty => Ok(lit(false)),
+            Self::Keys(keys) if keys.iter().flatten().any(KeyBounds::is_all_null) => {
+                Ok(lit(false))
+            }
+            Self::Keys(keys) => {
+                let predicates = keys
+                    .iter()
+                    .zip(probe_keys)
+                    .filter_map(|(bounds, key)| {
+                        bounds
+                            .as_ref()
+                            .map(|bounds| bounds.predicate(key, probe_schema))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(conjunction(predicates))
+            }
+        }
+    }
+}
+
+/// The dynamic filter of a [`HashJoinExec`], which is published once the
+/// build side has been collected.
+///
+/// Only joins in [`PartitionMode::CollectLeft`] use a dynamic filter: the
+/// build side is collected once for all partitions, so its bounds are known
+/// as soon as the first probe side partition is polled, without waiting for
+/// the other partitions. Each execution of the join publishes the bounds of
+/// its own build side.
+///
+/// [`HashJoinExec`]: super::HashJoinExec
+/// [`PartitionMode::CollectLeft`]: super::PartitionMode::CollectLeft
+#[derive(Debug)]
+pub(super) struct SharedBuildSideFilter {
+    /// The [`DynamicFilterPhysicalExpr`] pushed into the probe side
+    filter: Arc<dyn PhysicalExpr>,
+    /// The probe side join keys, which are the children of `filter`
+    probe_keys: Vec<PhysicalExprRef>,
+    probe_schema: SchemaRef,
+}
+
+impl SharedBuildSideFilter {
+    /// Create the shared state for `filter`
+    pub(super) fn new(
+        filter: Arc<dyn PhysicalExpr>,
+        probe_keys: Vec<PhysicalExprRef>,
+        probe_schema: SchemaRef,
+    ) -> Self {
+        Self {
+            filter,
+            probe_keys,
+            probe_schema,
+        }
+    }
+
+    /// The filter expression pushed into the probe side
+    pub(super) fn filter(&self) -> &Arc<dyn PhysicalExpr> {
+        &self.filter
+    }
+
+    /// Publishes the bounds of the build side to the probe side
+    pub(super) fn report(&self, bounds: BuildSideBounds) -> Result<()> {
+        let predicate = bounds.predicate(&self.probe_keys, &self.probe_schema)?;
+        self.filter
+            .as_any()
+            .downcast_ref::<DynamicFilterPhysicalExpr>()
+            .ok_or_else(|| {
+                internal_datafusion_err!(
+                    "Expected the hash join filter to be a DynamicFilterPhysicalExpr"
+                )
+            })?
+            .update(predicate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_physical_expr::expressions::col;
+    use datafusion_physical_expr_common::physical_expr::fmt_sql;
+
+    fn probe_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]))
+    }
+
+    fn filter() -> Result<SharedBuildSideFilter> {
+        let schema = probe_schema();
+        let probe_keys = vec![col("a", &schema)?, col("b", &schema)?];
+        let filter = Arc::new(DynamicFilterPhysicalExpr::new(
+            probe_keys.clone(),
+            lit(true),
+        ));
+        Ok(SharedBuildSideFilter::new(filter, probe_keys, schema))
+    }
+
+    fn current(filter: &SharedBuildSideFilter) -> String {
+        let current = filter
+            .filter()
+            .as_any()
+            .downcast_ref::<DynamicFilterPhysicalExpr>()
+            .unwrap()
+            .current()
+            .unwrap();
+        to_sql(&current)
+    }
+
+    fn to_sql(expr: &Arc<dyn PhysicalExpr>) -> String {
+        fmt_sql(expr.as_ref()).to_string()
+    }
+
+    fn bounds(a: Vec<Option<i32>>, b: Vec<Option<&str>>) -> BuildSideBounds {
+        let num_rows = a.len();
+        BuildSideBounds::new(
+            &[
+                Arc::new(Int32Array::from(a)) as ArrayRef,
+                Arc::new(StringArray::from(b)) as ArrayRef,
+            ],
+            num_rows,
+        )
+    }
+
+    #[test]
+    fn test_build_side_bounds() -> Result<()> {
+        let filter = filter()?;
+        assert_eq!(current(&filter), "true");
+
+        filter.report(bounds(
+            vec![Some(3), Some(1), None, Some(3)],
+            vec![Some("x"), Some("y"), Some("x"), None],
+        ))?;
+        assert_eq!(
+            current(&filter),
+            "a >= 1 AND a <= 3 AND a IN (1, 3) AND b >= x AND b <= y AND b IN (x, y)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_large_and_empty_build_side() -> Result<()> {
+        let filter = filter()?;
+        filter.report(bounds(
+            (0..100).map(Some).collect(),
+            (0..100).map(|_| Some("v")).collect(),
+        ))?;
+        assert_eq!(current(&filter), "a >= 0 AND a <= 99 AND b >= v AND b <= v");
+
+        let filter = self::filter()?;
+        filter.report(bounds(vec![], vec![]))?;
+        assert_eq!(current(&filter), "false");
+
+        let filter = self::filter()?;
+        filter.report(bounds(vec![None], vec![Some("v")]))?;
+        assert_eq!(current(&filter), "false");
+        Ok(())
+    }
+
+    #[test]
+    fn test_bounds_published_per_execution() -> Result<()> {
+        let filter = filter()?;
+        filter.report(bounds(vec![Some(5)], vec![Some("m")]))?;
+        assert_eq!(
+            current(&filter),
+            "a >= 5 AND a <= 5 AND a IN (5) AND b >= m AND b <= m AND b IN (m)"
+        );
+
+        // Executing the join again publishes the bounds of the new build side
+        filter.report(bounds(vec![Some(7), Some(9)], vec![Some("a"), Some("b")]))?;
+        assert_eq!(
+            current(&filter),
+            "a >= 7 AND a <= 9 AND a IN (7, 9) AND b >= a AND b <= b AND b IN (a, b)"
+        );
+        Ok(())
+    }
+}
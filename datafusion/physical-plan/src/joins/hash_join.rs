@@ -24,6 +24,11 @@ use std::sync::Arc;
 use std::task::Poll;
 use std::{any::Any, vec};
 
+use super::dynamic_filter::{
+    create_dynamic_filter, supports_dynamic_filter, BuildSideBounds,
+    SharedBuildSideFilter,
+};
+use super::hash_join_spill::{read_spill_file, SpillPartitioner, MAX_SPILL_DEPTH};
 use super::utils::{
     asymmetric_join_output_partitioning, get_final_indices_from_shared_bitmap,
     reorder_output_after_swap, swap_join_projection,
@@ -34,11 +39,17 @@ use super::{
 };
 use super::{JoinOn, JoinOnRef};
 use crate::execution_plan::{boundedness_from_children, EmissionType};
+use crate::filter_pushdown::{
+    ChildPushdownResult, FilterDescription, FilterPushdownPhase,
+    FilterPushdownPropagation,
+};
+use crate::metrics::SpillMetrics;
 use crate::projection::{
     try_embed_projection, try_pushdown_through_join, EmbeddedProjection, JoinData,
     ProjectionExec,
 };
 use crate::spill::get_record_batch_memory_size;
+use crate::spill::spill_manager::SpillManager;
 use crate::ExecutionPlanProperties;
 use crate::{
     common::can_project,
@@ -67,23 +78,25 @@ use arrow::datatypes::{Schema, SchemaRef};
 use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
 use arrow::util::bit_util;
+use datafusion_common::config::ConfigOptions;
 use datafusion_common::utils::memory::estimate_memory_size;
 use datafusion_common::{
     internal_datafusion_err, internal_err, plan_err, project_schema, DataFusionError,
     JoinSide, JoinType, NullEquality, Result,
 };
-use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation};
 use datafusion_execution::TaskContext;
 use datafusion_expr::Operator;
 use datafusion_physical_expr::equivalence::{
     join_equivalence_properties, ProjectionMapping,
 };
-use datafusion_physical_expr::PhysicalExprRef;
+use datafusion_physical_expr::{PhysicalExpr, PhysicalExprRef};
 use datafusion_physical_expr_common::datum::compare_op_for_nested;
 
 use ahash::RandomState;
 use datafusion_physical_expr_common::physical_expr::fmt_sql;
-use futures::{ready, Stream, StreamExt, TryStreamExt};
+use futures::{ready, Stream, StreamExt};
 use parking_lot::Mutex;
 
 /// Hard-coded seed to ensure hash values from the hash join differ from `RepartitionExec`, avoiding collisions.
@@ -157,6 +170,88 @@ impl JoinLeftData {
     }
 }
 
+/// The build side of a join, collected into a hash table unless it does not
+/// fit in memory
+enum JoinLeftInput {
+    /// The build side was collected into a hash table
+    InMemory(Arc<JoinLeftData>),
+    /// The build side was spilled to disk, to be joined partition by
+    /// partition (see [`hash_join_spill`](super::hash_join_spill))
+    Spilled(Arc<SpilledJoinLeftData>),
+}
+
+/// The build side of a join spilled to disk, split into partitions by the
+/// hash of its join keys
+struct SpilledJoinLeftData {
+    /// Number of times the rows were spilled before, which determines the
+    /// hash used to split them into partitions
+    depth: usize,
+    partitions: Vec<SpilledBuildPartition>,
+}
+
+impl SpilledJoinLeftData {
+    /// Create a new `SpilledJoinLeftData` from the spill files of its
+    /// partitions
+    fn new(
+        depth: usize,
+        files: Vec<Option<RefCountedTempFile>>,
+        probe_threads_count: usize,
+    ) -> Self {
+        let partitions = files
+            .into_iter()
+            .map(|file| SpilledBuildPartition {
+                file: Mutex::new(file),
+                data: Mutex::new(None),
+                probe_threads_counter: AtomicUsize::new(probe_threads_count),
+            })
+            .collect();
+        Self { depth, partitions }
+    }
+}
+
+/// A partition of a [`SpilledJoinLeftData`]
+struct SpilledBuildPartition {
+    /// The spilled rows, `None` once loaded or if the partition has no rows
+    file: Mutex<Option<RefCountedTempFile>>,
+    /// The loaded partition, shared by the probe threads until all of them
+    /// joined it with their probe side partition
+    data: Mutex<Option<OnceFut<JoinLeftInput>>>,
+    /// Counter of probe threads that did not join the partition yet
+    probe_threads_counter: AtomicUsize,
+}
+
+impl SpilledBuildPartition {
+    /// Returns the future loading the partition, which is created by `load`
+    /// from the spill file of the partition on the first call
+    fn load<F>(&self, load: F) -> Result<OnceFut<JoinLeftInput>>
+    where
+        F: FnOnce(Option<RefCountedTempFile>) -> Result<OnceFut<JoinLeftInput>>,
+    {
+        let mut data = self.data.lock();
+        if data.is_none() {
+            *data = Some(load(self.file.lock().take())?);
+        }
+        data.clone()
+            .ok_or_else(|| internal_datafusion_err!("Spilled partition not loaded"))
+    }
+
+    /// Decrements the counter of probe threads that did not join the
+    /// partition yet, releasing the loaded partition after the last one
+    fn report_probe_completed(&self) {
+        if self.probe_threads_counter.fetch_sub(1, Ordering::Relaxed) == 1 {
+            self.data.lock().take();
+        }
+    }
+}
+
+/// How the build side is spilled if it does not fit in memory
+#[derive(Clone)]
+struct BuildSideSpill {
+    spill_manager: SpillManager,
+    /// Number of times the rows were spilled before
+    depth: usize,
+}
+
 #[allow(rustdoc::private_intra_doc_links)]
 /// Join execution plan: Evaluates equijoin predicates in parallel on multiple
 /// partitions using a hash table and an optional filter list to apply post
@@ -342,7 +437,7 @@ pub struct HashJoinExec {
     ///
     /// Each output stream waits on the `OnceAsync` to signal the completion of
     /// the hash table creation.
-    left_fut: OnceAsync<JoinLeftData>,
+    left_fut: OnceAsync<JoinLeftInput>,
     /// Shared the `RandomState` for the hashing algorithm
     random_state: RandomState,
     /// Partitioning mode to use
@@ -357,6 +452,9 @@ pub struct HashJoinExec {
     pub null_equality: NullEquality,
     /// Cache holding plan properties like equivalences, output partitioning etc.
     cache: PlanProperties,
+    /// Dynamic filter on the probe side join keys, which is updated with the
+    /// bounds of the build side keys once the build side is collected
+    dynamic_filter: Option<Arc<SharedBuildSideFilter>>,
 }
 
 impl HashJoinExec {
@@ -418,9 +516,31 @@ impl HashJoinExec {
             column_indices,
             null_equality,
             cache,
+            dynamic_filter: None,
         })
     }
 
+    /// Use `filter`, a [`DynamicFilterPhysicalExpr`] over the probe side join
+    /// keys that was pushed into the probe side, to publish the bounds of the
+    /// build side keys
+    ///
+    /// The filter is only used in [`PartitionMode::CollectLeft`], where the
+    /// build side is collected once for all partitions.
+    ///
+    /// [`DynamicFilterPhysicalExpr`]: datafusion_physical_expr::expressions::DynamicFilterPhysicalExpr
+    fn with_dynamic_filter(mut self, filter: Option<Arc<dyn PhysicalExpr>>) -> Self {
+        self.dynamic_filter = filter
+            .filter(|_| self.mode == PartitionMode::CollectLeft)
+            .map(|filter| {
+                Arc::new(SharedBuildSideFilter::new(
+                    filter,
+                    self.on.iter().map(|(_, r)| Arc::clone(r)).collect(),
+                    self.right.schema(),
+                ))
+            });
+        self
+    }
+
     /// left (build) side which gets hashed
     pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
         &self.left
@@ -756,16 +876,23 @@ impl ExecutionPlan for HashJoinExec {
         self: Arc<Self>,
         children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(HashJoinExec::try_new(
-            Arc::clone(&children[0]),
-            Arc::clone(&children[1]),
-            self.on.clone(),
-            self.filter.clone(),
-            &self.join_type,
-            self.projection.clone(),
-            self.mode,
-            self.null_equality,
-        )?))
+        Ok(Arc::new(
+            HashJoinExec::try_new(
+                Arc::clone(&children[0]),
+                Arc::clone(&children[1]),
+                self.on.clone(),
+                self.filter.clone(),
+                &self.join_type,
+                self.projection.clone(),
+                self.mode,
+                self.null_equality,
+            )?
+            .with_dynamic_filter(
+                self.dynamic_filter
+                    .as_ref()
+                    .map(|dynamic_filter| Arc::clone(dynamic_filter.filter())),
+            ),
+        ))
     }
 
     fn execute(
@@ -802,12 +929,60 @@ impl ExecutionPlan for HashJoinExec {
         }
 
         let join_metrics = BuildProbeJoinMetrics::new(partition, &self.metrics);
+        let (reservation_name, probe_threads_count) = match self.mode {
+            PartitionMode::CollectLeft => ("HashJoinInput".to_string(), right_partitions),
+            PartitionMode::Partitioned => (format!("HashJoinInput[{partition}]"), 1),
+            PartitionMode::Auto => {
+                return plan_err!(
+                    "Invalid HashJoinExec, unsupported PartitionMode {:?} in execute()",
+                    PartitionMode::Auto
+                );
+            }
+        };
+        let with_visited_indices_bitmap = need_produce_result_in_final(self.join_type);
+
+        // Spill both inputs to disk if the build side does not fit in memory,
+        // unless the order of the probe side has to be maintained
+        let spill = (context.runtime_env().disk_manager.tmp_files_enabled()
+            && !(self.right.output_ordering().is_some()
+                && Self::maintains_input_order(self.join_type)[1]))
+            .then(|| {
+                let spill_metrics = SpillMetrics::new(&self.metrics, partition);
+                let spill_manager = |schema| {
+                    SpillManager::new(
+                        context.runtime_env(),
+                        spill_metrics.clone(),
+                        schema,
+                    )
+                    .with_compression_type(context.session_config().spill_compression())
+                };
+                HashJoinSpill {
+                    build_spill_manager: spill_manager(self.left.schema()),
+                    probe_spill_manager: spill_manager(self.right.schema()),
+                    build_schema: self.left.schema(),
+                    probe_schema: self.right.schema(),
+                    on_left: on_left.clone(),
+                    reservation_name: reservation_name.clone(),
+                    memory_pool: Arc::clone(context.memory_pool()),
+                    with_visited_indices_bitmap,
+                    probe_threads_count,
+                    partitioner: None,
+                    pending: vec![],
+                    current: None,
+                    probing_spilled: false,
+                }
+            });
+        let build_spill = spill.as_ref().map(|spill| BuildSideSpill {
+            spill_manager: spill.build_spill_manager.clone(),
+            depth: 0,
+        });
+
         let left_fut = match self.mode {
             PartitionMode::CollectLeft => self.left_fut.try_once(|| {
                 let left_stream = self.left.execute(0, Arc::clone(&context))?;
 
                 let reservation =
-                    MemoryConsumer::new("HashJoinInput").register(context.memory_pool());
+                    MemoryConsumer::new(reservation_name).register(context.memory_pool());
 
                 Ok(collect_left_input(
                     self.random_state.clone(),
@@ -815,16 +990,17 @@ impl ExecutionPlan for HashJoinExec {
                     on_left.clone(),
                     join_metrics.clone(),
                     reservation,
-                    need_produce_result_in_final(self.join_type),
-                    self.right().output_partitioning().partition_count(),
+                    with_visited_indices_bitmap,
+                    probe_threads_count,
+                    self.dynamic_filter.clone(),
+                    build_spill,
                 ))
             })?,
             PartitionMode::Partitioned => {
                 let left_stream = self.left.execute(partition, Arc::clone(&context))?;
 
                 let reservation =
-                    MemoryConsumer::new(format!("HashJoinInput[{partition}]"))
-                        .register(context.memory_pool());
+                    MemoryConsumer::new(reservation_name).register(context.memory_pool());
 
                 OnceFut::new(collect_left_input(
                     self.random_state.clone(),
@@ -832,16 +1008,13 @@ impl ExecutionPlan for HashJoinExec {
                     on_left.clone(),
                     join_metrics.clone(),
                     reservation,
-                    need_produce_result_in_final(self.join_type),
-                    1,
+                    with_visited_indices_bitmap,
+                    probe_threads_count,
+                    self.dynamic_filter.clone(),
+                    build_spill,
                 ))
             }
-            PartitionMode::Auto => {
-                return plan_err!(
-                    "Invalid HashJoinExec, unsupported PartitionMode {:?} in execute()",
-                    PartitionMode::Auto
-                );
-            }
+            PartitionMode::Auto => unreachable!(),
         };
 
         let batch_size = context.session_config().batch_size();
@@ -874,6 +1047,7 @@ impl ExecutionPlan for HashJoinExec {
             batch_size,
             hashes_buffer: vec![],
             right_side_ordered: self.right.output_ordering().is_some(),
+            spill,
         }))
     }
 
@@ -943,50 +1117,158 @@ impl ExecutionPlan for HashJoinExec {
             try_embed_projection(projection, self)
         }
     }
+
+    fn gather_filters_for_pushdown(
+        &self,
+        phase: FilterPushdownPhase,
+        parent_filters: Vec<Arc<dyn PhysicalExpr>>,
+        config: &ConfigOptions,
+    ) -> Result<FilterDescription> {
+        let description = FilterDescription::new_with_child_count(2)
+            .all_parent_filters_unsupported(parent_filters);
+        // In partitioned mode, every partition collects its own part of the
+        // build side, and the filter below the repartitioning of the probe
+        // side could only be published once all of them are collected
+        if !matches!(phase, FilterPushdownPhase::Post)
+            || !config.optimizer.enable_dynamic_filter_pushdown
+            || self.mode != PartitionMode::CollectLeft
+            || !supports_dynamic_filter(self.join_type, self.null_equality)
+        {
+            return Ok(description);
+        }
+        let filter = match &self.dynamic_filter {
+            Some(dynamic_filter) => Some(Arc::clone(dynamic_filter.filter())),
+            None => create_dynamic_filter(
+                &self.on,
+                &self.left.schema(),
+                &self.right.schema(),
+            )?,
+        };
+        // The filter only applies to the probe side
+        Ok(match filter {
+            Some(filter) => {
+                description.with_self_filters_for_children(vec![vec![], vec![filter]])
+            }
+            None => description,
+        })
+    }
+
+    fn handle_child_pushdown_result(
+        &self,
+        phase: FilterPushdownPhase,
+        child_pushdown_result: ChildPushdownResult,
+        _config: &ConfigOptions,
+    ) -> Result<FilterPushdownPropagation<Arc<dyn ExecutionPlan>>> {
+        let pushed_filter = child_pushdown_result
+            .self_filters
+            .get(1)
+            .and_then(|filters| filters.iter().next())
+            .map(|filter| filter.clone().into_inner());
+        let propagation = FilterPushdownPropagation::transparent(child_pushdown_result);
+        match pushed_filter {
+            Some(filter)
+                if matches!(phase, FilterPushdownPhase::Post)
+                    && self.dynamic_filter.is_none() =>
+            {
+                // Even when the probe side reports the filter as unsupported
+                // it may still use it, for example to prune by statistics
+                let join = HashJoinExec::try_new(
+                    Arc::clone(&self.left),
+                    Arc::clone(&self.right),
+                    self.on.clone(),
+                    self.filter.clone(),
+                    &self.join_type,
+                    self.projection.clone(),
+                    self.mode,
+                    self.null_equality,
+                )?
+                .with_dynamic_filter(Some(filter));
+                Ok(propagation.with_updated_node(Arc::new(join)))
+            }
+            _ => Ok(propagation),
+        }
+    }
 }
 
 /// Reads the left (build) side of the input, buffering it in memory, to build a
 /// hash table (`LeftJoinData`)
+///
+/// If the memory reservation can not grow and `spill` is set, the build side
+/// is spilled to disk instead, split into partitions by hash of the join keys.
+#[allow(clippy::too_many_arguments)]
 async fn collect_left_input(
     random_state: RandomState,
-    left_stream: SendableRecordBatchStream,
+    mut left_stream: SendableRecordBatchStream,
     on_left: Vec<PhysicalExprRef>,
     metrics: BuildProbeJoinMetrics,
-    reservation: MemoryReservation,
+    mut reservation: MemoryReservation,
     with_visited_indices_bitmap: bool,
     probe_threads_count: usize,
-) -> Result<JoinLeftData> {
+    dynamic_filter: Option<Arc<SharedBuildSideFilter>>,
+    spill: Option<BuildSideSpill>,
+) -> Result<JoinLeftInput> {
     let schema = left_stream.schema();
 
     // This operation performs 2 steps at once:
     // 1. creates a [JoinHashMap] of all batches from the stream
     // 2. stores the batches in a vector.
-    let initial = (Vec::new(), 0, metrics, reservation);
-    let (batches, num_rows, metrics, mut reservation) = left_stream
-        .try_fold(initial, |mut acc, batch| async {
-            let batch_size = get_record_batch_memory_size(&batch);
-            // Reserve memory for incoming batch
-            acc.3.try_grow(batch_size)?;
-            // Update metrics
-            acc.2.build_mem_used.add(batch_size);
-            acc.2.build_input_batches.add(1);
-            acc.2.build_input_rows.add(batch.num_rows());
-            // Update row count
-            acc.1 += batch.num_rows();
-            // Push batch to output
-            acc.0.push(batch);
-            Ok(acc)
-        })
-        .await?;
+    let mut batches = vec![];
+    let mut num_rows = 0;
+    while let Some(batch) = left_stream.next().await.transpose()? {
+        let batch_size = get_record_batch_memory_size(&batch);
+        // Reserve memory for incoming batch
+        if let Err(e) = reservation.try_grow(batch_size) {
+            batches.push(batch);
+            return spill_left_input(
+                e,
+                spill,
+                batches,
+                Some(left_stream),
+                &on_left,
+                reservation,
+                probe_threads_count,
+                dynamic_filter,
+            )
+            .await;
+        }
+        // Update metrics
+        metrics.build_mem_used.add(batch_size);
+        metrics.build_input_batches.add(1);
+        metrics.build_input_rows.add(batch.num_rows());
+        // Update row count
+        num_rows += batch.num_rows();
+        // Push batch to output
+        batches.push(batch);
+    }
 
     // Estimation of memory size, required for hashtable, prior to allocation.
     // Final result can be verified using `RawTable.allocation_info()`
     let fixed_size = size_of::<JoinHashMap>();
     let estimated_hashtable_size =
         estimate_memory_size::<(u64, u64)>(num_rows, fixed_size)?;
+    // Reserve additional memory for visited indices bitmap
+    let bitmap_size = if with_visited_indices_bitmap {
+        bit_util::ceil(num_rows, 8)
+    } else {
+        0
+    };
 
-    reservation.try_grow(estimated_hashtable_size)?;
-    metrics.build_mem_used.add(estimated_hashtable_size);
+    for size in [estimated_hashtable_size, bitmap_size] {
+        if let Err(e) = reservation.try_grow(size) {
+            return spill_left_input(
+                e,
+                spill,
+                batches,
+                None,
+                &on_left,
+                reservation,
+                probe_threads_count,
+                dynamic_filter,
+            )
+            .await;
+        }
+        metrics.build_mem_used.add(size);
+    }
 
     let mut hashmap = JoinHashMap::with_capacity(num_rows);
     let mut hashes_buffer = Vec::new();
@@ -1012,12 +1294,8 @@ async fn collect_left_input(
     // Merge all batches into a single batch, so we can directly index into the arrays
     let single_batch = concat_batches(&schema, batches_iter)?;
 
-    // Reserve additional memory for visited indices bitmap and create shared builder
+    // Create shared builder for visited indices bitmap
     let visited_indices_bitmap = if with_visited_indices_bitmap {
-        let bitmap_size = bit_util::ceil(single_batch.num_rows(), 8);
-        reservation.try_grow(bitmap_size)?;
-        metrics.build_mem_used.add(bitmap_size);
-
         let mut bitmap_buffer = BooleanBufferBuilder::new(single_batch.num_rows());
         bitmap_buffer.append_n(num_rows, false);
         bitmap_buffer
@@ -1025,13 +1303,12 @@ async fn collect_left_input(
         BooleanBufferBuilder::new(0)
     };
 
-    let left_values = on_left
-        .iter()
-        .map(|c| {
-            c.evaluate(&single_batch)?
-                .into_array(single_batch.num_rows())
-        })
-        .collect::<Result<Vec<_>>>()?;
+    let left_values = evaluate_join_keys(&on_left, &single_batch)?;
+
+    // Publish the bounds of the keys to the probe side before it is read
+    if let Some(dynamic_filter) = dynamic_filter {
+        dynamic_filter.report(BuildSideBounds::new(&left_values, num_rows))?;
+    }
 
     let data = JoinLeftData::new(
         hashmap,
@@ -1042,7 +1319,71 @@ async fn collect_left_input(
         reservation,
     );
 
-    Ok(data)
+    Ok(JoinLeftInput::InMemory(Arc::new(data)))
+}
+
+/// Spills the build side to disk after the memory reservation failed to grow
+/// with `err`, which is returned if spilling is not possible
+///
+/// `batches` are the buffered build side batches, and `left_stream` the rest
+/// of the build side, if it was not read entirely yet.
+#[allow(clippy::too_many_arguments)]
+async fn spill_left_input(
+    err: DataFusionError,
+    spill: Option<BuildSideSpill>,
+    batches: Vec<RecordBatch>,
+    left_stream: Option<SendableRecordBatchStream>,
+    on_left: &[PhysicalExprRef],
+    mut reservation: MemoryReservation,
+    probe_threads_count: usize,
+    dynamic_filter: Option<Arc<SharedBuildSideFilter>>,
+) -> Result<JoinLeftInput> {
+    let Some(spill) = spill else {
+        return Err(err);
+    };
+    let mut partitioner =
+        SpillPartitioner::new(on_left.to_vec(), spill.depth, &spill.spill_manager);
+    // The bounds of the keys are still published, as the probe side is only
+    // read after the build side
+    let mut bounds = dynamic_filter.as_ref().map(|_| BuildSideBounds::Empty);
+    let mut append = |batch: &RecordBatch| -> Result<()> {
+        if let Some(merged) = bounds.take() {
+            let values = evaluate_join_keys(on_left, batch)?;
+            bounds = Some(merged.merge(BuildSideBounds::new(&values, batch.num_rows())));
+        }
+        partitioner.append(batch)
+    };
+
+    for batch in batches {
+        append(&batch)?;
+    }
+    reservation.free();
+    if let Some(mut left_stream) = left_stream {
+        while let Some(batch) = left_stream.next().await.transpose()? {
+            append(&batch)?;
+        }
+    }
+    let files = partitioner.finish()?;
+
+    if let (Some(dynamic_filter), Some(bounds)) = (dynamic_filter, bounds) {
+        dynamic_filter.report(bounds)?;
+    }
+
+    Ok(JoinLeftInput::Spilled(Arc::new(SpilledJoinLeftData::new(
+        spill.depth,
+        files,
+        probe_threads_count,
+    ))))
+}
+
+/// Evaluates the join keys `on` against `batch`
+fn evaluate_join_keys(
+    on: &[PhysicalExprRef],
+    batch: &RecordBatch,
+) -> Result<Vec<ArrayRef>> {
+    on.iter()
+        .map(|c| c.evaluate(batch)?.into_array(batch.num_rows()))
+        .collect()
 }
 
 /// Updates `hash_map` with new entries from `batch` evaluated against the expressions `on`
@@ -1103,7 +1444,7 @@ enum BuildSide {
 /// Container for BuildSide::Initial related data
 struct BuildSideInitialState {
     /// Future for building hash table from build-side input
-    left_fut: OnceFut<JoinLeftData>,
+    left_fut: OnceFut<JoinLeftInput>,
 }
 
 /// Container for BuildSide::Ready related data
@@ -1147,15 +1488,21 @@ impl BuildSide {
 ///
 /// ```text
 ///
-///       WaitBuildSide
-///             │
-///             ▼
-///  ┌─► FetchProbeBatch ───► ExhaustedProbeSide ───► Completed
+///    ┌──────────────────────────────────────────────┐
+///    ▼                                              │
+///  WaitBuildSide ───► SpillProbeSide ───► NextSpilledPartition ───► Completed
+///       │                                           ▲
+///       ▼                                           │
+///  ┌─► FetchProbeBatch ───► ExhaustedProbeSide ─────┴─► Completed
 ///  │          │
 ///  │          ▼
 ///  └─ ProcessProbeBatch
 ///
 /// ```
+///
+/// If the build side is spilled to disk, the probe side is spilled as well
+/// in `SpillProbeSide`, after which the pairs of spilled partitions are
+/// joined one after the other, starting again from `WaitBuildSide`.
 #[derive(Debug, Clone)]
 enum HashJoinStreamState {
     /// Initial state for HashJoinStream indicating that build-side data not collected yet
@@ -1166,6 +1513,11 @@ enum HashJoinStreamState {
     ProcessProbeBatch(ProcessProbeBatchState),
     /// Indicates that probe-side has been fully processed
     ExhaustedProbeSide,
+    /// Indicates that the build side was spilled, and the probe side is being
+    /// spilled into the same partitions
+    SpillProbeSide,
+    /// Indicates that the next pair of spilled partitions is to be joined
+    NextSpilledPartition,
     /// Indicates that HashJoinStream execution is completed
     Completed,
 }
@@ -1240,6 +1592,76 @@ struct HashJoinStream {
     hashes_buffer: Vec<u64>,
     /// Specifies whether the right side has an ordering to potentially preserve
     right_side_ordered: bool,
+    /// Spilling of the inputs, if the join may spill to disk
+    spill: Option<HashJoinSpill>,
+}
+
+/// State of a [`HashJoinStream`] for joining inputs spilled to disk
+struct HashJoinSpill {
+    build_spill_manager: SpillManager,
+    probe_spill_manager: SpillManager,
+    build_schema: SchemaRef,
+    probe_schema: SchemaRef,
+    /// equijoin columns from the left (build side)
+    on_left: Vec<PhysicalExprRef>,
+    /// Name of the reservations of the loaded build side partitions
+    reservation_name: String,
+    memory_pool: Arc<dyn MemoryPool>,
+    with_visited_indices_bitmap: bool,
+    probe_threads_count: usize,
+    /// Spills the probe side into the partitions of a spilled build side
+    partitioner: Option<(Arc<SpilledJoinLeftData>, SpillPartitioner)>,
+    /// The pairs of spilled partitions still to be joined, the next one last,
+    /// with the spill file of the probe side partition
+    pending: Vec<(Arc<SpilledJoinLeftData>, usize, Option<RefCountedTempFile>)>,
+    /// The spilled partition being joined
+    current: Option<(Arc<SpilledJoinLeftData>, usize)>,
+    /// Whether the probe side is read from a spill file, rather than from the
+    /// input of the join
+    probing_spilled: bool,
+}
+
+impl HashJoinSpill {
+    /// Returns the future loading the build side partition `index`, which
+    /// may be spilled again if it does not fit in memory either
+    fn load_partition(
+        &self,
+        build: &SpilledJoinLeftData,
+        index: usize,
+        random_state: &RandomState,
+        metrics: &BuildProbeJoinMetrics,
+    ) -> Result<OnceFut<JoinLeftInput>> {
+        build.partitions[index].load(|file| {
+            let stream =
+                read_spill_file(&self.build_spill_manager, &self.build_schema, file)?;
+            let reservation =
+                MemoryConsumer::new(&self.reservation_name).register(&self.memory_pool);
+            let depth = build.depth + 1;
+            let spill = (depth < MAX_SPILL_DEPTH).then(|| BuildSideSpill {
+                spill_manager: self.build_spill_manager.clone(),
+                depth,
+            });
+            Ok(OnceFut::new(collect_left_input(
+                random_state.clone(),
+                stream,
+                self.on_left.clone(),
+                metrics.clone(),
+                reservation,
+                self.with_visited_indices_bitmap,
+                self.probe_threads_count,
+                None,
+                spill,
+            )))
+        })
+    }
+
+    /// Reports that the spilled partition being joined, if any, has been
+    /// joined by this probe thread
+    fn release_current(&mut self) {
+        if let Some((build, index)) = self.current.take() {
+            build.partitions[index].report_probe_completed();
+        }
+    }
 }
 
 impl RecordBatchStream for HashJoinStream {
@@ -1408,6 +1830,12 @@ impl HashJoinStream {
                 HashJoinStreamState::ExhaustedProbeSide => {
                     handle_state!(self.process_unmatched_build_batch())
                 }
+                HashJoinStreamState::SpillProbeSide => {
+                    handle_state!(ready!(self.spill_probe_side(cx)))
+                }
+                HashJoinStreamState::NextSpilledPartition => {
+                    handle_state!(self.next_spilled_partition())
+                }
                 HashJoinStreamState::Completed => Poll::Ready(None),
             };
         }
@@ -1415,26 +1843,146 @@ impl HashJoinStream {
 
     /// Collects build-side data by polling `OnceFut` future from initialized build-side
     ///
-    /// Updates build-side to `Ready`, and state to `FetchProbeSide`
+    /// Updates build-side to `Ready`, and state to `FetchProbeSide`, or state
+    /// to `SpillProbeSide` if the build side was spilled
     fn collect_build_side(
         &mut self,
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
         let build_timer = self.join_metrics.build_time.timer();
         // build hash table from left (build) side, if not yet done
-        let left_data = ready!(self
+        let left_input = ready!(self
             .build_side
             .try_as_initial_mut()?
             .left_fut
             .get_shared(cx))?;
         build_timer.done();
 
-        self.state = HashJoinStreamState::FetchProbeBatch;
-        self.build_side = BuildSide::Ready(BuildSideReadyState { left_data });
+        match left_input.as_ref() {
+            JoinLeftInput::InMemory(left_data) => {
+                self.state = HashJoinStreamState::FetchProbeBatch;
+                self.build_side = BuildSide::Ready(BuildSideReadyState {
+                    left_data: Arc::clone(left_data),
+                });
+            }
+            JoinLeftInput::Spilled(build) => {
+                let spill = self.spill.as_mut().ok_or_else(|| {
+                    internal_datafusion_err!("Build side spilled without spill state")
+                })?;
+                // The partition the build side was loaded from is split again
+                spill.release_current();
+                let partitioner = SpillPartitioner::new(
+                    self.on_right.clone(),
+                    build.depth,
+                    &spill.probe_spill_manager,
+                );
+                spill.partitioner = Some((Arc::clone(build), partitioner));
+                self.state = HashJoinStreamState::SpillProbeSide;
+            }
+        }
 
         Poll::Ready(Ok(StatefulStreamResult::Continue))
     }
 
+    /// Spills the probe side into the partitions of the spilled build side
+    ///
+    /// Updates state to `NextSpilledPartition` once the probe side is exhausted
+    fn spill_probe_side(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
+        let spill = self.spill.as_mut().ok_or_else(|| {
+            internal_datafusion_err!("Expected hash join stream with spill state")
+        })?;
+        let Some((_, partitioner)) = spill.partitioner.as_mut() else {
+            return Poll::Ready(internal_err!("Expected probe side partitioner"));
+        };
+        loop {
+            match ready!(self.right.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    if !spill.probing_spilled {
+                        self.join_metrics.input_batches.add(1);
+                        self.join_metrics.input_rows.add(batch.num_rows());
+                    }
+                    partitioner.append(&batch)?;
+                }
+                Some(Err(err)) => return Poll::Ready(Err(err)),
+                None => break,
+            }
+        }
+
+        let Some((build, partitioner)) = spill.partitioner.take() else {
+            return Poll::Ready(internal_err!("Expected probe side partitioner"));
+        };
+        let files = partitioner.finish()?;
+        spill.pending.extend(
+            files
+                .into_iter()
+                .enumerate()
+                .rev()
+                .map(|(index, file)| (Arc::clone(&build), index, file)),
+        );
+        self.state = HashJoinStreamState::NextSpilledPartition;
+
+        Poll::Ready(Ok(StatefulStreamResult::Continue))
+    }
+
+    /// Starts joining the next pair of spilled partitions
+    ///
+    /// Updates state to `WaitBuildSide`, or to `Completed` if all the spilled
+    /// partitions have been joined
+    fn next_spilled_partition(
+        &mut self,
+    ) -> Result<StatefulStreamResult<Option<RecordBatch>>> {
+        let spill = self.spill.as_mut().ok_or_else(|| {
+            internal_datafusion_err!("Expected hash join stream with spill state")
+        })?;
+        while let Some((build, index, probe_file)) = spill.pending.pop() {
+            // Without probe rows, the partition only needs to be loaded to
+            // produce its unmatched build side rows
+            if probe_file.is_none() && !need_produce_result_in_final(self.join_type) {
+                build.partitions[index].report_probe_completed();
+                continue;
+            }
+
+            let left_fut = spill.load_partition(
+                &build,
+                index,
+                &self.random_state,
+                &self.join_metrics,
+            )?;
+            self.right = read_spill_file(
+                &spill.probe_spill_manager,
+                &spill.probe_schema,
+                probe_file,
+            )?;
+            spill.probing_spilled = true;
+            spill.current = Some((build, index));
+            self.build_side = BuildSide::Initial(BuildSideInitialState { left_fut });
+            self.state = HashJoinStreamState::WaitBuildSide;
+            return Ok(StatefulStreamResult::Continue);
+        }
+
+        self.state = HashJoinStreamState::Completed;
+        Ok(StatefulStreamResult::Continue)
+    }
+
+    /// Updates state to `NextSpilledPartition` if there are spilled
+    /// partitions still to be joined, and to `Completed` otherwise
+    fn build_side_completed(&mut self) {
+        self.state = match self.spill.as_mut() {
+            Some(spill) => {
+                spill.release_current();
+                if spill.pending.is_empty() {
+                    HashJoinStreamState::Completed
+                } else {
+                    HashJoinStreamState::NextSpilledPartition
+                }
+            }
+            None => HashJoinStreamState::Completed,
+        };
+    }
+
     /// Fetches next batch from probe-side
     ///
     /// If non-empty batch has been fetched, updates state to `ProcessProbeBatchState`,
@@ -1459,8 +2007,14 @@ impl HashJoinStream {
                 self.hashes_buffer.resize(batch.num_rows(), 0);
                 create_hashes(&keys_values, &self.random_state, &mut self.hashes_buffer)?;
 
-                self.join_metrics.input_batches.add(1);
-                self.join_metrics.input_rows.add(batch.num_rows());
+                if !self
+                    .spill
+                    .as_ref()
+                    .is_some_and(|spill| spill.probing_spilled)
+                {
+                    self.join_metrics.input_batches.add(1);
+                    self.join_metrics.input_rows.add(batch.num_rows());
+                }
 
                 self.state =
                     HashJoinStreamState::ProcessProbeBatch(ProcessProbeBatchState {
@@ -1600,23 +2154,29 @@ impl HashJoinStream {
 
     /// Processes unmatched build-side rows for certain join types and produces output batch
     ///
-    /// Updates state to `Completed`
+    /// Updates state to `Completed`, or to `NextSpilledPartition` if there are
+    /// spilled partitions still to be joined
     fn process_unmatched_build_batch(
         &mut self,
     ) -> Result<StatefulStreamResult<Option<RecordBatch>>> {
-        let timer = self.join_metrics.join_time.timer();
-
         if !need_produce_result_in_final(self.join_type) {
-            self.state = HashJoinStreamState::Completed;
+            self.build_side_completed();
             return Ok(StatefulStreamResult::Continue);
         }
 
-        let build_side = self.build_side.try_as_ready()?;
-        if !build_side.left_data.report_probe_completed() {
-            self.state = HashJoinStreamState::Completed;
+        if !self
+            .build_side
+            .try_as_ready()?
+            .left_data
+            .report_probe_completed()
+        {
+            self.build_side_completed();
             return Ok(StatefulStreamResult::Continue);
         }
 
+        let timer = self.join_metrics.join_time.timer();
+        let build_side = self.build_side.try_as_ready()?;
+
         // use the global left bitmap to produce the left indices and right indices
         let (left_side, right_side) = get_final_indices_from_shared_bitmap(
             build_side.left_data.visited_indices_bitmap(),
@@ -1643,7 +2203,7 @@ impl HashJoinStream {
         }
         timer.done();
 
-        self.state = HashJoinStreamState::Completed;
+        self.build_side_completed();
 
         Ok(StatefulStreamResult::Ready(Some(result?)))
     }
@@ -1685,6 +2245,7 @@ mod tests {
         ScalarValue,
     };
     use datafusion_execution::config::SessionConfig;
+    use datafusion_execution::disk_manager::{DiskManagerBuilder, DiskManagerMode};
     use datafusion_execution::runtime_env::RuntimeEnvBuilder;
     use datafusion_expr::Operator;
     use datafusion_physical_expr::expressions::{BinaryExpr, Literal};
@@ -4340,8 +4901,12 @@ mod tests {
         ];
 
         for join_type in join_types {
+            // Disable DiskManager to prevent spilling
             let runtime = RuntimeEnvBuilder::new()
                 .with_memory_limit(100, 1.0)
+                .with_disk_manager_builder(
+                    DiskManagerBuilder::default().with_mode(DiskManagerMode::Disabled),
+                )
                 .build_arc()?;
             let task_ctx = TaskContext::default().with_runtime(runtime);
             let task_ctx = Arc::new(task_ctx);
@@ -4415,8 +4980,12 @@ mod tests {
         ];
 
         for join_type in join_types {
+            // Disable DiskManager to prevent spilling
             let runtime = RuntimeEnvBuilder::new()
                 .with_memory_limit(100, 1.0)
+                .with_disk_manager_builder(
+                    DiskManagerBuilder::default().with_mode(DiskManagerMode::Disabled),
+                )
                 .build_arc()?;
             let session_config = SessionConfig::default().with_batch_size(50);
             let task_ctx = TaskContext::default()
@@ -4454,6 +5023,77 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn join_spills_build_side() -> Result<()> {
+        // The build keys 0..1000 occur twice and 1000..2000 once, and the
+        // probe keys are 500..3500
+        let left_batch = build_table_i32(
+            ("a1", &(0..3000).collect()),
+            ("b1", &(0..3000).map(|i| i % 2000).collect()),
+            ("c1", &(0..3000).collect()),
+        );
+        let left = TestMemoryExec::try_new_exec(
+            &[(0..30).map(|i| left_batch.slice(i * 100, 100)).collect()],
+            left_batch.schema(),
+            None,
+        )?;
+        let right_batch = build_table_i32(
+            ("a2", &(0..3000).collect()),
+            ("b2", &(500..3500).collect()),
+            ("c2", &(0..3000).collect()),
+        );
+        // The probe side partitions share the spilled build side partitions
+        let right = TestMemoryExec::try_new_exec(
+            &(0..3)
+                .map(|p| {
+                    (0..10)
+                        .map(|i| right_batch.slice(p * 1000 + i * 100, 100))
+                        .collect()
+                })
+                .collect::<Vec<_>>(),
+            right_batch.schema(),
+            None,
+        )?;
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left_batch.schema())?) as _,
+            Arc::new(Column::new_with_schema("b2", &right_batch.schema())?) as _,
+        )];
+
+        for (join_type, expected_rows) in [
+            (JoinType::Inner, 2000),
+            (JoinType::Left, 3000),
+            (JoinType::Right, 3500),
+            (JoinType::Full, 4500),
+            (JoinType::LeftSemi, 2000),
+            (JoinType::LeftAnti, 1000),
+            (JoinType::RightSemi, 1500),
+            (JoinType::RightAnti, 1500),
+        ] {
+            let runtime = RuntimeEnvBuilder::new()
+                .with_memory_limit(64 * 1024, 1.0)
+                .build_arc()?;
+            let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+
+            let join = join(
+                Arc::clone(&left) as Arc<dyn ExecutionPlan>,
+                Arc::clone(&right) as Arc<dyn ExecutionPlan>,
+                on.clone(),
+                &join_type,
+                NullEquality::NullEqualsNothing,
+            )?;
+            let join = Arc::new(join);
+            let batches = crate::collect(Arc::clone(&join) as _, task_ctx).await?;
+            let rows = batches.iter().map(|b| b.num_rows()).sum::<usize>();
+            assert_eq!(rows, expected_rows, "{join_type}");
+
+            let metrics = join.metrics().unwrap();
+            assert!(metrics.spill_count().unwrap() > 0, "{join_type}");
+            assert_eq!(metrics.output_rows().unwrap(), expected_rows, "{join_type}");
+        }
+
+        Ok(())
+    }
+
     fn build_table_struct(
         struct_name: &str,
         field_name_and_values: (&str, &Vec<Option<i32>>),
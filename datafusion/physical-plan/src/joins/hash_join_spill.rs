@@ -0,0 +1,147 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Spilling of the inputs of a [`HashJoinExec`] whose build side does not
+//! fit in memory.
+//!
+//! Both inputs are split into [`SPILL_PARTITIONS`] partitions by the hash of
+//! their join keys, so that rows can only match rows of the same partition on
+//! the other side. The pairs of partitions are then joined one after the
+//! other, spilling again with a different hash if a build side partition
+//! does not fit in memory either (Grace hash join).
+//!
+//! [`HashJoinExec`]: super::HashJoinExec
+
+use std::sync::Arc;
+
+use arrow::array::UInt32Array;
+use arrow::compute::take_record_batch;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::Result;
+use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_physical_expr::PhysicalExprRef;
+
+use ahash::RandomState;
+
+use crate::hash_utils::create_hashes;
+use crate::spill::in_progress_spill_file::InProgressSpillFile;
+use crate::spill::spill_manager::SpillManager;
+use crate::{EmptyRecordBatchStream, SendableRecordBatchStream};
+
+/// Number of partitions an input is split into each time it is spilled
+pub(super) const SPILL_PARTITIONS: usize = 16;
+
+/// Number of times the build side can be spilled, before failing with the
+/// error of the reservation. Partitions that do not fit in memory after that
+/// many spills usually consist of a single key, which can not be split any
+/// further.
+pub(super) const MAX_SPILL_DEPTH: usize = 4;
+
+/// Writes the batches of a join input into [`SPILL_PARTITIONS`] spill files,
+/// according to the hash of their join keys
+pub(super) struct SpillPartitioner {
+    /// The join keys of the input
+    on: Vec<PhysicalExprRef>,
+    /// Seed of the hash, which differs for each spill depth
+    random_state: RandomState,
+    spill_manager: SpillManager,
+    /// The spill file of each partition, created with its first row
+    files: Vec<Option<InProgressSpillFile>>,
+    /// Scratch space for computing hashes
+    hashes_buffer: Vec<u64>,
+}
+
+impl SpillPartitioner {
+    /// Create a partitioner for an input with the join keys `on`, which has
+    /// already been spilled `depth` times
+    pub(super) fn new(
+        on: Vec<PhysicalExprRef>,
+        depth: usize,
+        spill_manager: &SpillManager,
+    ) -> Self {
+        Self {
+            on,
+            random_state: RandomState::with_seeds(
+                'G' as u64,
+                'R' as u64,
+                'A' as u64,
+                depth as u64,
+            ),
+            spill_manager: spill_manager.clone(),
+            files: (0..SPILL_PARTITIONS).map(|_| None).collect(),
+            hashes_buffer: vec![],
+        }
+    }
+
+    /// Writes the rows of `batch` to the spill files of their partitions
+    pub(super) fn append(&mut self, batch: &RecordBatch) -> Result<()> {
+        let keys_values = self
+            .on
+            .iter()
+            .map(|c| c.evaluate(batch)?.into_array(batch.num_rows()))
+            .collect::<Result<Vec<_>>>()?;
+        self.hashes_buffer.clear();
+        self.hashes_buffer.resize(batch.num_rows(), 0);
+        create_hashes(&keys_values, &self.random_state, &mut self.hashes_buffer)?;
+
+        let mut indices = vec![vec![]; SPILL_PARTITIONS];
+        for (row, hash) in self.hashes_buffer.iter().enumerate() {
+            indices[(*hash % SPILL_PARTITIONS as u64) as usize].push(row as u32);
+        }
+        for (partition, indices) in indices.into_iter().enumerate() {
+            if indices.is_empty() {
+                continue;
+            }
+            let partition_batch = take_record_batch(batch, &UInt32Array::from(indices))?;
+            let file = match &mut self.files[partition] {
+                Some(file) => file,
+                file => file.insert(
+                    self.spill_manager
+                        .create_in_progress_file("HashJoinSpill")?,
+                ),
+            };
+            file.append_batch(&partition_batch)?;
+        }
+        Ok(())
+    }
+
+    /// Finishes the spill files, returning the file of each partition, or
+    /// `None` for partitions without rows
+    pub(super) fn finish(self) -> Result<Vec<Option<RefCountedTempFile>>> {
+        self.files
+            .into_iter()
+            .map(|file| match file {
+                Some(mut file) => file.finish(),
+                None => Ok(None),
+            })
+            .collect()
+    }
+}
+
+/// Reads the spill `file` of an input with `schema` as a stream, which is
+/// empty if the partition has no rows
+pub(super) fn read_spill_file(
+    spill_manager: &SpillManager,
+    schema: &SchemaRef,
+    file: Option<RefCountedTempFile>,
+) -> Result<SendableRecordBatchStream> {
+    match file {
+        Some(file) => spill_manager.read_spill_as_stream(file),
+        None => Ok(Box::pin(EmptyRecordBatchStream::new(Arc::clone(schema)))),
+    }
+}
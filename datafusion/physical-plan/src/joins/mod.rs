@@ -27,7 +27,9 @@ use parking_lot::Mutex;
 pub use sort_merge_join::SortMergeJoinExec;
 pub use symmetric_hash_join::SymmetricHashJoinExec;
 mod cross_join;
+mod dynamic_filter;
 mod hash_join;
+mod hash_join_spill;
 mod nested_loop_join;
 mod sort_merge_join;
 mod stream_join_utils;
@@ -24,8 +24,9 @@ use std::sync::Arc;
 use std::task::Poll;
 
 use super::utils::{
-    asymmetric_join_output_partitioning, get_final_indices_from_shared_bitmap,
-    need_produce_result_in_final, reorder_output_after_swap, swap_join_projection,
+    asymmetric_join_output_partitioning, get_final_indices_from_bit_map,
+    get_final_indices_from_shared_bitmap, need_produce_result_in_final,
+    need_produce_right_in_final, reorder_output_after_swap, swap_join_projection,
     BatchSplitter, BatchTransformer, NoopBatchTransformer, StatefulStreamResult,
 };
 use crate::common::can_project;
@@ -36,14 +37,18 @@ use crate::joins::utils::{
     BuildProbeJoinMetrics, ColumnIndex, JoinFilter, OnceAsync, OnceFut,
 };
 use crate::joins::SharedBitmapBuilder;
-use crate::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use crate::metrics::{
+    Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet, SpillMetrics,
+};
 use crate::projection::{
     try_embed_projection, try_pushdown_through_join, EmbeddedProjection, JoinData,
     ProjectionExec,
 };
+use crate::spill::in_progress_spill_file::InProgressSpillFile;
+use crate::spill::spill_manager::SpillManager;
 use crate::{
-    handle_state, DisplayAs, DisplayFormatType, Distribution, ExecutionPlan,
-    ExecutionPlanProperties, PlanProperties, RecordBatchStream,
+    handle_state, DisplayAs, DisplayFormatType, Distribution, EmptyRecordBatchStream,
+    ExecutionPlan, ExecutionPlanProperties, PlanProperties, RecordBatchStream,
     SendableRecordBatchStream,
 };
 
@@ -52,9 +57,11 @@ use arrow::compute::concat_batches;
 use arrow::datatypes::{Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use datafusion_common::{
-    exec_datafusion_err, internal_err, project_schema, JoinSide, Result, Statistics,
+    exec_datafusion_err, internal_datafusion_err, internal_err, project_schema,
+    DataFusionError, JoinSide, Result, Statistics,
 };
-use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation};
 use datafusion_execution::TaskContext;
 use datafusion_expr::JoinType;
 use datafusion_physical_expr::equivalence::{
@@ -109,6 +116,73 @@ impl JoinLeftData {
     }
 }
 
+/// The build side of a join, either collected in memory or spilled to disk
+enum JoinLeftInput {
+    InMemory(Arc<JoinLeftData>),
+    Spilled(Arc<SpilledJoinLeftData>),
+}
+
+/// The build side of a join spilled to disk, split into chunks that are
+/// joined with the whole probe side one after the other
+struct SpilledJoinLeftData {
+    chunks: Vec<SpilledBuildChunk>,
+}
+
+impl SpilledJoinLeftData {
+    /// Create a new `SpilledJoinLeftData` from the spill files of its chunks
+    fn new(files: Vec<RefCountedTempFile>, probe_threads_count: usize) -> Self {
+        let chunks = files
+            .into_iter()
+            .map(|file| SpilledBuildChunk {
+                file: Mutex::new(Some(file)),
+                data: Mutex::new(None),
+                probe_threads_counter: AtomicUsize::new(probe_threads_count),
+            })
+            .collect();
+        Self { chunks }
+    }
+}
+
+/// A chunk of a [`SpilledJoinLeftData`]
+struct SpilledBuildChunk {
+    /// The spilled rows, `None` once loaded
+    file: Mutex<Option<RefCountedTempFile>>,
+    /// The loaded chunk, shared by the probe threads until all of them
+    /// joined it with their probe side partition
+    data: Mutex<Option<OnceFut<JoinLeftData>>>,
+    /// Counter of probe threads that did not join the chunk yet
+    probe_threads_counter: AtomicUsize,
+}
+
+impl SpilledBuildChunk {
+    /// Returns the future loading the chunk, which is created by `load` from
+    /// the spill file of the chunk on the first call
+    fn load<F>(&self, load: F) -> Result<OnceFut<JoinLeftData>>
+    where
+        F: FnOnce(RefCountedTempFile) -> Result<OnceFut<JoinLeftData>>,
+    {
+        let mut data = self.data.lock();
+        if data.is_none() {
+            let file = self
+                .file
+                .lock()
+                .take()
+                .ok_or_else(|| internal_datafusion_err!("Spilled chunk released"))?;
+            *data = Some(load(file)?);
+        }
+        data.clone()
+            .ok_or_else(|| internal_datafusion_err!("Spilled chunk not loaded"))
+    }
+
+    /// Decrements the counter of probe threads that did not join the chunk
+    /// yet, releasing the loaded chunk after the last one
+    fn report_probe_completed(&self) {
+        if self.probe_threads_counter.fetch_sub(1, Ordering::Relaxed) == 1 {
+            self.data.lock().take();
+        }
+    }
+}
+
 #[allow(rustdoc::private_intra_doc_links)]
 /// NestedLoopJoinExec is build-probe join operator, whose main task is to
 /// perform joins without any equijoin conditions in `ON` clause.
@@ -145,6 +219,14 @@ impl JoinLeftData {
 /// "reports" about probe phase completion (which means that "visited" bitmap won't be
 /// updated anymore), and only the last thread, reporting about completion, will return output.
 ///
+/// #### Spilling
+/// If the build side does not fit in memory and the disk manager allows temporary files, it is
+/// spilled to disk in chunks, and the join is performed as a block nested loop join: each chunk
+/// is loaded and joined with the whole probe side in turn, the probe side being spilled while
+/// joining the first chunk and re-read from disk for the others. Unmatched build-side rows are
+/// produced after each chunk, and unmatched probe-side rows after the last one. Spilling is
+/// disabled when the order of the probe side has to be maintained.
+///
 /// # Clone / Shared State
 ///
 /// Note this structure includes a [`OnceAsync`] that is used to coordinate the
@@ -168,7 +250,7 @@ pub struct NestedLoopJoinExec {
     ///
     /// Each output stream waits on the `OnceAsync` to signal the completion of
     /// the hash table creation.
-    inner_table: OnceAsync<JoinLeftData>,
+    inner_table: OnceAsync<JoinLeftInput>,
     /// Information of index and left / right placement of columns
     column_indices: Vec<ColumnIndex>,
     /// Projection to apply to the output of the join
@@ -491,11 +573,51 @@ impl ExecutionPlan for NestedLoopJoinExec {
         }
 
         let join_metrics = BuildProbeJoinMetrics::new(partition, &self.metrics);
+        let probe_threads_count = self.right().output_partitioning().partition_count();
+        let with_visited_left_side = need_produce_result_in_final(self.join_type);
+        let reservation_name = format!("NestedLoopJoinLoad[{partition}]");
 
         // Initialization reservation for load of inner table
         let load_reservation =
-            MemoryConsumer::new(format!("NestedLoopJoinLoad[{partition}]"))
-                .register(context.memory_pool());
+            MemoryConsumer::new(&reservation_name).register(context.memory_pool());
+
+        // Right side has an order and it is maintained during operation.
+        let right_side_ordered =
+            self.maintains_input_order()[1] && self.right.output_ordering().is_some();
+
+        // Spill the build side to disk in chunks if it does not fit in memory,
+        // unless the order of the probe side has to be maintained
+        let spill = (context.runtime_env().disk_manager.tmp_files_enabled()
+            && !right_side_ordered)
+            .then(|| {
+                let spill_metrics = SpillMetrics::new(&self.metrics, partition);
+                let spill_manager = |schema| {
+                    SpillManager::new(
+                        context.runtime_env(),
+                        spill_metrics.clone(),
+                        schema,
+                    )
+                    .with_compression_type(context.session_config().spill_compression())
+                };
+                NestedLoopJoinSpill {
+                    build_spill_manager: spill_manager(self.left.schema()),
+                    probe_spill_manager: spill_manager(self.right.schema()),
+                    build_schema: self.left.schema(),
+                    probe_schema: self.right.schema(),
+                    reservation_name,
+                    memory_pool: Arc::clone(context.memory_pool()),
+                    with_visited_left_side,
+                    probe_threads_count,
+                    current: None,
+                    loading: None,
+                    probe_spill: None,
+                    probe_file: None,
+                    visited_probe_rows: vec![],
+                    probe_batch_index: 0,
+                    probe_rescans: MetricBuilder::new(&self.metrics)
+                        .counter("probe_rescans", partition),
+                }
+            });
 
         let inner_table = self.inner_table.try_once(|| {
             let stream = self.left.execute(0, Arc::clone(&context))?;
@@ -504,8 +626,11 @@ impl ExecutionPlan for NestedLoopJoinExec {
                 stream,
                 join_metrics.clone(),
                 load_reservation,
-                need_produce_result_in_final(self.join_type),
-                self.right().output_partitioning().partition_count(),
+                with_visited_left_side,
+                probe_threads_count,
+                spill
+                    .as_ref()
+                    .map(|spill| spill.build_spill_manager.clone()),
             ))
         })?;
 
@@ -517,10 +642,6 @@ impl ExecutionPlan for NestedLoopJoinExec {
 
         let indices_cache = (UInt64Array::new_null(0), UInt32Array::new_null(0));
 
-        // Right side has an order and it is maintained during operation.
-        let right_side_ordered =
-            self.maintains_input_order()[1] && self.right.output_ordering().is_some();
-
         // update column indices to reflect the projection
         let column_indices_after_projection = match &self.projection {
             Some(projection) => projection
@@ -544,6 +665,7 @@ impl ExecutionPlan for NestedLoopJoinExec {
                 state: NestedLoopJoinStreamState::WaitBuildSide,
                 batch_transformer: BatchSplitter::new(batch_size),
                 left_data: None,
+                spill,
             }))
         } else {
             Ok(Box::pin(NestedLoopJoinStream {
@@ -559,6 +681,7 @@ impl ExecutionPlan for NestedLoopJoinExec {
                 state: NestedLoopJoinStreamState::WaitBuildSide,
                 batch_transformer: NoopBatchTransformer::new(),
                 left_data: None,
+                spill,
             }))
         }
     }
@@ -624,43 +747,151 @@ impl ExecutionPlan for NestedLoopJoinExec {
 }
 
 /// Asynchronously collect input into a single batch, and creates `JoinLeftData` from it
+///
+/// If the input does not fit in memory and `spill_manager` is provided, it is
+/// spilled to disk in chunks instead.
 async fn collect_left_input(
-    stream: SendableRecordBatchStream,
+    mut stream: SendableRecordBatchStream,
     join_metrics: BuildProbeJoinMetrics,
-    reservation: MemoryReservation,
+    mut reservation: MemoryReservation,
     with_visited_left_side: bool,
     probe_threads_count: usize,
-) -> Result<JoinLeftData> {
+    spill_manager: Option<SpillManager>,
+) -> Result<JoinLeftInput> {
     let schema = stream.schema();
 
     // Load all batches and count the rows
-    let (batches, metrics, mut reservation) = stream
-        .try_fold(
-            (Vec::new(), join_metrics, reservation),
-            |(mut batches, metrics, mut reservation), batch| async {
-                let batch_size = batch.get_array_memory_size();
-                // Reserve memory for incoming batch
-                reservation.try_grow(batch_size)?;
-                // Update metrics
-                metrics.build_mem_used.add(batch_size);
-                metrics.build_input_batches.add(1);
-                metrics.build_input_rows.add(batch.num_rows());
-                // Push batch to output
-                batches.push(batch);
-                Ok((batches, metrics, reservation))
-            },
-        )
-        .await?;
+    let mut batches = vec![];
+    while let Some(batch) = stream.next().await.transpose()? {
+        let batch_size = batch.get_array_memory_size();
+        join_metrics.build_input_batches.add(1);
+        join_metrics.build_input_rows.add(batch.num_rows());
+        // Reserve memory for incoming batch
+        if let Err(e) = reservation.try_grow(batch_size) {
+            batches.push(batch);
+            return spill_left_input(
+                e,
+                spill_manager,
+                batches,
+                Some(stream),
+                &join_metrics,
+                reservation,
+                probe_threads_count,
+            )
+            .await;
+        }
+        join_metrics.build_mem_used.add(batch_size);
+        // Push batch to output
+        batches.push(batch);
+    }
 
+    // Reserve memory for visited_left_side bitmap if required by join type
+    if with_visited_left_side {
+        let n_rows = batches.iter().map(|batch| batch.num_rows()).sum::<usize>();
+        let buffer_size = n_rows.div_ceil(8);
+        if let Err(e) = reservation.try_grow(buffer_size) {
+            return spill_left_input(
+                e,
+                spill_manager,
+                batches,
+                None,
+                &join_metrics,
+                reservation,
+                probe_threads_count,
+            )
+            .await;
+        }
+        join_metrics.build_mem_used.add(buffer_size);
+    }
+
+    Ok(JoinLeftInput::InMemory(Arc::new(join_left_data(
+        concat_batches(&schema, &batches)?,
+        with_visited_left_side,
+        probe_threads_count,
+        reservation,
+    )?)))
+}
+
+/// Spills the buffered `batches` and the rest of the build side `stream` to
+/// disk in chunks, after `reservation` failed to grow with `err`, which is
+/// returned if spilling is disabled
+///
+/// Each chunk holds about half of the memory that could be reserved, so that
+/// it can be loaded while other operators hold some memory as well.
+async fn spill_left_input(
+    err: DataFusionError,
+    spill_manager: Option<SpillManager>,
+    batches: Vec<RecordBatch>,
+    stream: Option<SendableRecordBatchStream>,
+    join_metrics: &BuildProbeJoinMetrics,
+    mut reservation: MemoryReservation,
+    probe_threads_count: usize,
+) -> Result<JoinLeftInput> {
+    let Some(spill_manager) = spill_manager else {
+        return Err(err);
+    };
+
+    let mut writer = BuildChunkWriter::new(spill_manager, reservation.size() / 2);
+    for batch in batches {
+        writer.append(&batch)?;
+    }
+    reservation.free();
+
+    if let Some(mut stream) = stream {
+        while let Some(batch) = stream.next().await.transpose()? {
+            join_metrics.build_input_batches.add(1);
+            join_metrics.build_input_rows.add(batch.num_rows());
+            writer.append(&batch)?;
+        }
+    }
+
+    Ok(JoinLeftInput::Spilled(Arc::new(SpilledJoinLeftData::new(
+        writer.finish()?,
+        probe_threads_count,
+    ))))
+}
+
+/// Loads a chunk of a spilled build side from `stream`, failing if it does
+/// not fit in memory
+async fn load_build_chunk(
+    stream: SendableRecordBatchStream,
+    join_metrics: BuildProbeJoinMetrics,
+    mut reservation: MemoryReservation,
+    with_visited_left_side: bool,
+    probe_threads_count: usize,
+) -> Result<JoinLeftData> {
+    let schema = stream.schema();
+    let batches: Vec<_> = stream.try_collect().await?;
+    // The batches read from a spill file share the buffers of their IPC
+    // messages, so only the merged batch has a meaningful memory size
     let merged_batch = concat_batches(&schema, &batches)?;
+    drop(batches);
 
-    // Reserve memory for visited_left_side bitmap if required by join type
+    let mut size = merged_batch.get_array_memory_size();
+    if with_visited_left_side {
+        size += merged_batch.num_rows().div_ceil(8);
+    }
+    reservation.try_grow(size)?;
+    join_metrics.build_mem_used.set_max(size);
+
+    join_left_data(
+        merged_batch,
+        with_visited_left_side,
+        probe_threads_count,
+        reservation,
+    )
+}
+
+/// Creates `JoinLeftData` from the build side `merged_batch`, whose memory is
+/// tracked by `reservation`
+fn join_left_data(
+    merged_batch: RecordBatch,
+    with_visited_left_side: bool,
+    probe_threads_count: usize,
+    reservation: MemoryReservation,
+) -> Result<JoinLeftData> {
     let visited_left_side = if with_visited_left_side {
         let n_rows = merged_batch.num_rows();
-        let buffer_size = n_rows.div_ceil(8);
-        reservation.try_grow(buffer_size)?;
-        metrics.build_mem_used.add(buffer_size);
-
         let mut buffer = BooleanBufferBuilder::new(n_rows);
         buffer.append_n(n_rows, false);
         buffer
@@ -676,11 +907,73 @@ async fn collect_left_input(
     ))
 }
 
+/// Writes the batches of a build side into spill files of bounded size
+struct BuildChunkWriter {
+    spill_manager: SpillManager,
+    /// Maximum memory size of the batches of a chunk, exceeded only by
+    /// chunks of a single batch
+    chunk_size: usize,
+    /// The chunk being written, with the memory size of its batches
+    current: Option<(InProgressSpillFile, usize)>,
+    /// The chunks written so far
+    files: Vec<RefCountedTempFile>,
+}
+
+impl BuildChunkWriter {
+    fn new(spill_manager: SpillManager, chunk_size: usize) -> Self {
+        Self {
+            spill_manager,
+            chunk_size,
+            current: None,
+            files: vec![],
+        }
+    }
+
+    /// Writes `batch` to the current chunk, starting a new one if it would
+    /// exceed the chunk size
+    fn append(&mut self, batch: &RecordBatch) -> Result<()> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+        let batch_size = batch.get_array_memory_size();
+        if matches!(&self.current, Some((_, size)) if size + batch_size > self.chunk_size)
+        {
+            self.finish_chunk()?;
+        }
+        let (file, size) = match &mut self.current {
+            Some(current) => current,
+            current => current.insert((
+                self.spill_manager
+                    .create_in_progress_file("NestedLoopJoinSpill")?,
+                0,
+            )),
+        };
+        file.append_batch(batch)?;
+        *size += batch_size;
+        Ok(())
+    }
+
+    fn finish_chunk(&mut self) -> Result<()> {
+        if let Some((mut file, _)) = self.current.take() {
+            self.files.extend(file.finish()?);
+        }
+        Ok(())
+    }
+
+    /// Finishes the last chunk, returning the spill files of all the chunks
+    fn finish(mut self) -> Result<Vec<RefCountedTempFile>> {
+        self.finish_chunk()?;
+        Ok(self.files)
+    }
+}
+
 /// This enumeration represents various states of the nested loop join algorithm.
 #[derive(Debug, Clone)]
 enum NestedLoopJoinStreamState {
     /// The initial state, indicating that build-side data not collected yet
     WaitBuildSide,
+    /// Indicates that the next chunk of a spilled build-side is being loaded
+    LoadBuildChunk,
     /// Indicates that build-side has been collected, and stream is ready for
     /// fetching probe-side
     FetchProbeBatch,
@@ -716,7 +1009,7 @@ struct NestedLoopJoinStream<T> {
     /// the outer table data of the nested loop join
     outer_table: SendableRecordBatchStream,
     /// the inner table data of the nested loop join
-    inner_table: OnceFut<JoinLeftData>,
+    inner_table: OnceFut<JoinLeftInput>,
     /// Information of index and left / right placement of columns
     column_indices: Vec<ColumnIndex>,
     // TODO: support null aware equal
@@ -733,6 +1026,142 @@ struct NestedLoopJoinStream<T> {
     batch_transformer: T,
     /// Result of the left data future
     left_data: Option<Arc<JoinLeftData>>,
+    /// State of the join of a build side spilled to disk, `None` if spilling
+    /// is disabled
+    spill: Option<NestedLoopJoinSpill>,
+}
+
+/// State of a [`NestedLoopJoinStream`] joining a build side spilled to disk,
+/// whose chunks are joined with the whole probe side one after the other
+struct NestedLoopJoinSpill {
+    build_spill_manager: SpillManager,
+    probe_spill_manager: SpillManager,
+    build_schema: SchemaRef,
+    probe_schema: SchemaRef,
+    /// Name of the reservations of the loaded build side chunks
+    reservation_name: String,
+    memory_pool: Arc<dyn MemoryPool>,
+    with_visited_left_side: bool,
+    probe_threads_count: usize,
+    /// The spilled build side and the index of the chunk being joined
+    current: Option<(Arc<SpilledJoinLeftData>, usize)>,
+    /// The future loading the chunk being joined
+    loading: Option<OnceFut<JoinLeftData>>,
+    /// Spill file of the probe side, written while joining the first chunk
+    probe_spill: Option<InProgressSpillFile>,
+    /// The spilled probe side, re-read for each chunk after the first one,
+    /// `None` if the probe side has no rows
+    probe_file: Option<Arc<RefCountedTempFile>>,
+    /// Matched rows of each probe batch, for joins producing unmatched
+    /// probe side rows after the last chunk
+    visited_probe_rows: Vec<BooleanBufferBuilder>,
+    /// Index of the next probe batch of the current chunk
+    probe_batch_index: usize,
+    /// Number of times the probe side was re-read from disk
+    probe_rescans: Count,
+}
+
+impl NestedLoopJoinSpill {
+    /// Starts joining the chunk `index` of `build`, creating the future
+    /// loading it if no other probe thread did
+    fn start_chunk(
+        &mut self,
+        build: Arc<SpilledJoinLeftData>,
+        index: usize,
+        metrics: &BuildProbeJoinMetrics,
+    ) -> Result<()> {
+        self.loading = Some(build.chunks[index].load(|file| {
+            let stream = self.build_spill_manager.read_spill_as_stream(file)?;
+            let reservation =
+                MemoryConsumer::new(&self.reservation_name).register(&self.memory_pool);
+            Ok(OnceFut::new(load_build_chunk(
+                stream,
+                metrics.clone(),
+                reservation,
+                self.with_visited_left_side,
+                self.probe_threads_count,
+            )))
+        })?);
+        self.current = Some((build, index));
+        Ok(())
+    }
+
+    /// Returns true if a spilled build side is being joined
+    fn is_active(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// Returns true if the probe side is re-read from disk
+    fn is_rescanning(&self) -> bool {
+        matches!(self.current, Some((_, index)) if index > 0)
+    }
+
+    /// Returns true if the chunk being joined is the last one
+    fn is_last_chunk(&self) -> bool {
+        matches!(&self.current, Some((build, index)) if index + 1 == build.chunks.len())
+    }
+
+    /// Writes `batch` to the spill file of the probe side, while joining the
+    /// first of several chunks
+    fn spill_probe_batch(&mut self, batch: &RecordBatch) -> Result<()> {
+        if !matches!(&self.current, Some((build, 0)) if build.chunks.len() > 1) {
+            return Ok(());
+        }
+        let file = match &mut self.probe_spill {
+            Some(file) => file,
+            file => file.insert(
+                self.probe_spill_manager
+                    .create_in_progress_file("NestedLoopJoinProbeSpill")?,
+            ),
+        };
+        file.append_batch(batch)
+    }
+
+    /// Returns the bitmap of matched rows of the next probe batch of
+    /// `num_rows` rows, creating it while joining the first chunk
+    fn next_visited_probe_rows(&mut self, num_rows: usize) -> &mut BooleanBufferBuilder {
+        let index = self.probe_batch_index;
+        self.probe_batch_index += 1;
+        if index == self.visited_probe_rows.len() {
+            let mut bitmap = BooleanBufferBuilder::new(num_rows);
+            bitmap.append_n(num_rows, false);
+            self.visited_probe_rows.push(bitmap);
+        }
+        &mut self.visited_probe_rows[index]
+    }
+
+    /// Reports that the chunk being joined has been joined with the whole
+    /// probe side by this probe thread, and starts joining the next one.
+    ///
+    /// Returns the probe side to read again, or `None` once all the chunks
+    /// have been joined.
+    fn next_chunk(
+        &mut self,
+        metrics: &BuildProbeJoinMetrics,
+    ) -> Result<Option<SendableRecordBatchStream>> {
+        let Some((build, index)) = self.current.take() else {
+            return Ok(None);
+        };
+        build.chunks[index].report_probe_completed();
+        if index + 1 == build.chunks.len() {
+            return Ok(None);
+        }
+
+        if let Some(mut probe_spill) = self.probe_spill.take() {
+            self.probe_file = probe_spill.finish()?.map(Arc::new);
+        }
+        self.probe_batch_index = 0;
+        self.probe_rescans.add(1);
+        self.start_chunk(build, index + 1, metrics)?;
+
+        let probe = match &self.probe_file {
+            Some(file) => self
+                .probe_spill_manager
+                .read_shared_spill_as_stream(Arc::clone(file))?,
+            None => Box::pin(EmptyRecordBatchStream::new(Arc::clone(&self.probe_schema))),
+        };
+        Ok(Some(probe))
+    }
 }
 
 /// Creates a Cartesian product of two input batches, preserving the order of the right batch,
@@ -821,6 +1250,9 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
                 NestedLoopJoinStreamState::WaitBuildSide => {
                     handle_state!(ready!(self.collect_build_side(cx)))
                 }
+                NestedLoopJoinStreamState::LoadBuildChunk => {
+                    handle_state!(ready!(self.load_build_chunk(cx)))
+                }
                 NestedLoopJoinStreamState::FetchProbeBatch => {
                     handle_state!(ready!(self.fetch_probe_batch(cx)))
                 }
@@ -841,14 +1273,88 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
     ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
         let build_timer = self.join_metrics.build_time.timer();
         // build hash table from left (build) side, if not yet done
-        self.left_data = Some(ready!(self.inner_table.get_shared(cx))?);
+        let left_input = ready!(self.inner_table.get_shared(cx))?;
         build_timer.done();
 
+        match left_input.as_ref() {
+            JoinLeftInput::InMemory(left_data) => {
+                self.left_data = Some(Arc::clone(left_data));
+                self.state = NestedLoopJoinStreamState::FetchProbeBatch;
+            }
+            JoinLeftInput::Spilled(build) => {
+                let Some(spill) = self.spill.as_mut() else {
+                    return Poll::Ready(internal_err!(
+                        "Build side of NestedLoopJoinExec spilled without spill state"
+                    ));
+                };
+                if build.chunks.is_empty() {
+                    // All the spilled batches were empty
+                    let reservation = MemoryConsumer::new(&spill.reservation_name)
+                        .register(&spill.memory_pool);
+                    self.left_data = Some(Arc::new(join_left_data(
+                        RecordBatch::new_empty(Arc::clone(&spill.build_schema)),
+                        spill.with_visited_left_side,
+                        1,
+                        reservation,
+                    )?));
+                    self.state = NestedLoopJoinStreamState::FetchProbeBatch;
+                } else {
+                    spill.start_chunk(Arc::clone(build), 0, &self.join_metrics)?;
+                    self.state = NestedLoopJoinStreamState::LoadBuildChunk;
+                }
+            }
+        }
+
+        Poll::Ready(Ok(StatefulStreamResult::Continue))
+    }
+
+    /// Waits for the chunk of a spilled build-side being joined to be
+    /// loaded, and updates state to `FetchProbeBatch`
+    fn load_build_chunk(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
+        let Some(loading) = self.spill.as_mut().and_then(|spill| spill.loading.as_mut())
+        else {
+            return Poll::Ready(internal_err!(
+                "Expected a build chunk to be loading in LoadBuildChunk state"
+            ));
+        };
+        let build_timer = self.join_metrics.build_time.timer();
+        let left_data = ready!(loading.get_shared(cx))?;
+        build_timer.done();
+
+        self.left_data = Some(left_data);
+        if let Some(spill) = self.spill.as_mut() {
+            spill.loading = None;
+        }
         self.state = NestedLoopJoinStreamState::FetchProbeBatch;
 
         Poll::Ready(Ok(StatefulStreamResult::Continue))
     }
 
+    /// Finishes the join of the build-side data with the whole probe side,
+    /// and updates state to `LoadBuildChunk` if chunks of a spilled
+    /// build-side remain to be joined, otherwise to `Completed`.
+    fn build_side_completed(&mut self) -> Result<()> {
+        let Some(spill) = self.spill.as_mut().filter(|spill| spill.is_active()) else {
+            self.state = NestedLoopJoinStreamState::Completed;
+            return Ok(());
+        };
+
+        self.left_data = None;
+        // The cached indices depend on the number of rows of the chunk
+        self.indices_cache = (UInt64Array::new_null(0), UInt32Array::new_null(0));
+        match spill.next_chunk(&self.join_metrics)? {
+            Some(probe) => {
+                self.outer_table = probe;
+                self.state = NestedLoopJoinStreamState::LoadBuildChunk;
+            }
+            None => self.state = NestedLoopJoinStreamState::Completed,
+        }
+        Ok(())
+    }
+
     /// Fetches next batch from probe-side
     ///
     /// If a non-empty batch has been fetched, updates state to
@@ -862,6 +1368,14 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
                 self.state = NestedLoopJoinStreamState::ExhaustedProbeSide;
             }
             Some(Ok(right_batch)) => {
+                if let Some(spill) = self.spill.as_mut().filter(|spill| spill.is_active())
+                {
+                    // Empty batches are neither spilled nor tracked
+                    if right_batch.num_rows() == 0 {
+                        return Poll::Ready(Ok(StatefulStreamResult::Continue));
+                    }
+                    spill.spill_probe_batch(&right_batch)?;
+                }
                 self.state = NestedLoopJoinStreamState::ProcessProbeBatch(right_batch);
             }
             Some(Err(err)) => return Poll::Ready(Err(err)),
@@ -885,22 +1399,49 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
 
         match self.batch_transformer.next() {
             None => {
-                // Setting up timer & updating input metrics
-                self.join_metrics.input_batches.add(1);
-                self.join_metrics.input_rows.add(batch.num_rows());
+                // Setting up timer & updating input metrics, once per probe
+                // batch even if it is re-read for spilled build-side chunks
+                if !self
+                    .spill
+                    .as_ref()
+                    .is_some_and(|spill| spill.is_rescanning())
+                {
+                    self.join_metrics.input_batches.add(1);
+                    self.join_metrics.input_rows.add(batch.num_rows());
+                }
                 let timer = self.join_metrics.join_time.timer();
 
-                let result = join_left_and_right_batch(
-                    left_data.batch(),
-                    batch,
-                    self.join_type,
-                    self.filter.as_ref(),
-                    &self.column_indices,
-                    &self.schema,
-                    visited_left_side,
-                    &mut self.indices_cache,
-                    self.right_side_ordered,
-                );
+                let result = match self.spill.as_mut().filter(|spill| spill.is_active()) {
+                    Some(spill) => {
+                        let last_chunk = spill.is_last_chunk();
+                        let visited_right_side =
+                            need_produce_right_in_final(self.join_type)
+                                .then(|| spill.next_visited_probe_rows(batch.num_rows()));
+                        join_build_chunk_and_probe_batch(
+                            left_data.batch(),
+                            batch,
+                            self.join_type,
+                            self.filter.as_ref(),
+                            &self.column_indices,
+                            &self.schema,
+                            visited_left_side,
+                            visited_right_side,
+                            last_chunk,
+                            &mut self.indices_cache,
+                        )
+                    }
+                    None => join_left_and_right_batch(
+                        left_data.batch(),
+                        batch,
+                        self.join_type,
+                        self.filter.as_ref(),
+                        &self.column_indices,
+                        &self.schema,
+                        visited_left_side,
+                        &mut self.indices_cache,
+                        self.right_side_ordered,
+                    ),
+                };
                 timer.done();
 
                 self.batch_transformer.set_batch(result?);
@@ -919,7 +1460,7 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
     }
 
     /// Processes unmatched build-side rows for certain join types and produces
-    /// output batch, updates state to `Completed`.
+    /// output batch, updates state to `LoadBuildChunk` or `Completed`.
     fn process_unmatched_build_batch(
         &mut self,
     ) -> Result<StatefulStreamResult<Option<RecordBatch>>> {
@@ -936,16 +1477,18 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
             // Setting `is_exhausted` / returning None will prevent from
             // multiple calls of `report_probe_completed()`
             if !left_data.report_probe_completed() {
-                self.state = NestedLoopJoinStreamState::Completed;
-                return Ok(StatefulStreamResult::Ready(None));
+                self.build_side_completed()?;
+                return Ok(StatefulStreamResult::Continue);
             };
 
+            let empty_right_batch = RecordBatch::new_empty(self.outer_table.schema());
+            self.build_side_completed()?;
+
             // Only setting up timer, input is exhausted
             let timer = self.join_metrics.join_time.timer();
             // use the global left bitmap to produce the left indices and right indices
             let (left_side, right_side) =
                 get_final_indices_from_shared_bitmap(visited_left_side, self.join_type);
-            let empty_right_batch = RecordBatch::new_empty(self.outer_table.schema());
             // use the left and right indices to produce the batch result
             let result = build_batch_from_indices(
                 &self.schema,
@@ -956,7 +1499,6 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
                 &self.column_indices,
                 JoinSide::Left,
             );
-            self.state = NestedLoopJoinStreamState::Completed;
 
             // Recording time
             if result.is_ok() {
@@ -966,8 +1508,8 @@ impl<T: BatchTransformer> NestedLoopJoinStream<T> {
             Ok(StatefulStreamResult::Ready(Some(result?)))
         } else {
             // end of the join loop
-            self.state = NestedLoopJoinStreamState::Completed;
-            Ok(StatefulStreamResult::Ready(None))
+            self.build_side_completed()?;
+            Ok(StatefulStreamResult::Continue)
         }
     }
 }
@@ -1036,6 +1578,80 @@ fn join_left_and_right_batch(
     }
 }
 
+/// Joins a chunk of a spilled build side with a probe batch.
+///
+/// The matched probe rows are recorded in `visited_right_side`, and the
+/// unmatched ones are only produced when joining the `last_chunk`, as they may
+/// still match rows of the following chunks.
+#[allow(clippy::too_many_arguments)]
+fn join_build_chunk_and_probe_batch(
+    left_batch: &RecordBatch,
+    right_batch: &RecordBatch,
+    join_type: JoinType,
+    filter: Option<&JoinFilter>,
+    column_indices: &[ColumnIndex],
+    schema: &SchemaRef,
+    visited_left_side: &SharedBitmapBuilder,
+    visited_right_side: Option<&mut BooleanBufferBuilder>,
+    last_chunk: bool,
+    indices_cache: &mut (UInt64Array, UInt32Array),
+) -> Result<RecordBatch> {
+    let (left_side, right_side) =
+        build_join_indices(left_batch, right_batch, filter, indices_cache).map_err(
+            |e| {
+                exec_datafusion_err!(
+                    "Fail to build join indices in NestedLoopJoinExec, error: {e}"
+                )
+            },
+        )?;
+
+    if need_produce_result_in_final(join_type) {
+        let mut bitmap = visited_left_side.lock();
+        left_side.values().iter().for_each(|x| {
+            bitmap.set_bit(*x as usize, true);
+        });
+    }
+
+    let mut batches = vec![];
+    if let Some(visited_right_side) = visited_right_side {
+        right_side.values().iter().for_each(|x| {
+            visited_right_side.set_bit(*x as usize, true);
+        });
+        if last_chunk {
+            // Reuse the build-side logic, with the probe side as the left one
+            let (right_side, left_side) =
+                get_final_indices_from_bit_map(visited_right_side, join_type.swap());
+            batches.push(build_batch_from_indices(
+                schema,
+                right_batch,
+                &RecordBatch::new_empty(left_batch.schema()),
+                &right_side,
+                &left_side,
+                column_indices,
+                JoinSide::Right,
+            )?);
+        }
+    }
+
+    // Semi, anti and mark joins only produce rows from the bitmaps
+    if matches!(
+        join_type,
+        JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full
+    ) {
+        batches.push(build_batch_from_indices(
+            schema,
+            left_batch,
+            right_batch,
+            &left_side,
+            &right_side,
+            column_indices,
+            JoinSide::Left,
+        )?);
+    }
+
+    Ok(concat_batches(schema, &batches)?)
+}
+
 impl<T: BatchTransformer + Unpin + Send> Stream for NestedLoopJoinStream<T> {
     type Item = Result<RecordBatch>;
 
@@ -1072,6 +1688,7 @@ pub(crate) mod tests {
     use arrow::datatypes::{DataType, Field};
     use datafusion_common::test_util::batches_to_sort_string;
     use datafusion_common::{assert_contains, ScalarValue};
+    use datafusion_execution::disk_manager::{DiskManagerBuilder, DiskManagerMode};
     use datafusion_execution::runtime_env::RuntimeEnvBuilder;
     use datafusion_expr::Operator;
     use datafusion_physical_expr::expressions::{BinaryExpr, Literal};
@@ -1538,8 +2155,12 @@ pub(crate) mod tests {
         ];
 
         for join_type in join_types {
+            // Disable DiskManager to prevent spilling
             let runtime = RuntimeEnvBuilder::new()
                 .with_memory_limit(100, 1.0)
+                .with_disk_manager_builder(
+                    DiskManagerBuilder::default().with_mode(DiskManagerMode::Disabled),
+                )
                 .build_arc()?;
             let task_ctx = TaskContext::default().with_runtime(runtime);
             let task_ctx = Arc::new(task_ctx);
@@ -1563,6 +2184,110 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn join_spills_build_side() -> Result<()> {
+        let left: Arc<dyn ExecutionPlan> = TestMemoryExec::try_new_exec(
+            &[(0..20)
+                .map(|i| {
+                    let rows = (i * 20..(i + 1) * 20).collect::<Vec<_>>();
+                    build_table_i32(("a1", &rows), ("b1", &rows), ("c1", &rows))
+                })
+                .collect()],
+            build_left_table().schema(),
+            None,
+        )?;
+        // The probe side partitions share the spilled build side chunks
+        let right: Arc<dyn ExecutionPlan> = TestMemoryExec::try_new_exec(
+            &(0..3)
+                .map(|p| {
+                    (0..10)
+                        .map(|i| {
+                            let rows = (200 + p * 100 + i * 10
+                                ..200 + p * 100 + (i + 1) * 10)
+                                .collect::<Vec<_>>();
+                            build_table_i32(("a2", &rows), ("b2", &rows), ("c2", &rows))
+                        })
+                        .collect()
+                })
+                .collect::<Vec<_>>(),
+            build_right_table().schema(),
+            None,
+        )?;
+        // left.b1 > right.b2, which leaves rows of both sides unmatched
+        let filter = JoinFilter::new(
+            Arc::new(BinaryExpr::new(
+                Arc::new(Column::new("x", 0)),
+                Operator::Gt,
+                Arc::new(Column::new("x", 1)),
+            )),
+            vec![
+                ColumnIndex {
+                    index: 1,
+                    side: JoinSide::Left,
+                },
+                ColumnIndex {
+                    index: 1,
+                    side: JoinSide::Right,
+                },
+            ],
+            Arc::new(Schema::new(vec![
+                Field::new("x", DataType::Int32, true),
+                Field::new("x", DataType::Int32, true),
+            ])),
+        );
+
+        for join_type in [
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+            JoinType::LeftMark,
+            JoinType::RightSemi,
+            JoinType::RightAnti,
+            JoinType::RightMark,
+        ] {
+            let join = Arc::new(NestedLoopJoinExec::try_new(
+                Arc::clone(&left),
+                Arc::clone(&right),
+                Some(filter.clone()),
+                &join_type,
+                None,
+            )?);
+            let expected =
+                crate::collect(Arc::clone(&join) as _, Arc::new(TaskContext::default()))
+                    .await?;
+
+            let join = Arc::new(NestedLoopJoinExec::try_new(
+                Arc::clone(&left),
+                Arc::clone(&right),
+                Some(filter.clone()),
+                &join_type,
+                None,
+            )?);
+            let runtime = RuntimeEnvBuilder::new()
+                .with_memory_limit(8 * 1024, 1.0)
+                .build_arc()?;
+            let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+            let batches = crate::collect(Arc::clone(&join) as _, task_ctx).await?;
+            assert_eq!(
+                batches_to_sort_string(&batches),
+                batches_to_sort_string(&expected),
+                "{join_type}"
+            );
+
+            let metrics = join.metrics().unwrap();
+            assert!(metrics.spill_count().unwrap() > 0, "{join_type}");
+            assert!(
+                metrics.sum_by_name("probe_rescans").unwrap().as_usize() > 0,
+                "{join_type}"
+            );
+        }
+
+        Ok(())
+    }
+
     fn prepare_mod_join_filter() -> JoinFilter {
         let column_indices = vec![
             ColumnIndex {
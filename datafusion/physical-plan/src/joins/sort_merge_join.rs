@@ -2130,7 +2130,7 @@ impl SortMergeJoinStream {
                 let mut left_columns = null_joined_batch
                     .columns()
                     .iter()
-                    .take(right_columns_length)
+                    .take(left_columns_length)
                     .cloned()
                     .collect::<Vec<_>>();
 
@@ -2140,7 +2140,7 @@ impl SortMergeJoinStream {
                 let left_columns = null_joined_batch
                     .columns()
                     .iter()
-                    .skip(left_columns_length)
+                    .skip(right_columns_length)
                     .cloned()
                     .collect::<Vec<_>>();
 
@@ -3830,6 +3830,91 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn join_with_filter_inputs_of_different_widths() -> Result<()> {
+        let left = build_table_two_cols(("a1", &vec![1, 2, 3]), ("b1", &vec![4, 5, 7]));
+        let right = build_table(
+            ("a2", &vec![10, 20, 30]),
+            ("b1", &vec![4, 5, 6]),
+            ("c2", &vec![1, 3, 90]),
+        );
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b1", &right.schema())?) as _,
+        )];
+        // c2 > a1
+        let filter = JoinFilter::new(
+            Arc::new(BinaryExpr::new(
+                Arc::new(Column::new("c2", 1)),
+                Operator::Gt,
+                Arc::new(Column::new("a1", 0)),
+            )),
+            vec![
+                ColumnIndex {
+                    index: 0,
+                    side: JoinSide::Left,
+                },
+                ColumnIndex {
+                    index: 2,
+                    side: JoinSide::Right,
+                },
+            ],
+            Arc::new(Schema::new(vec![
+                Field::new("a1", DataType::Int32, true),
+                Field::new("c2", DataType::Int32, true),
+            ])),
+        );
+
+        let (_, batches) = join_collect_with_filter(
+            Arc::clone(&left),
+            Arc::clone(&right),
+            on.clone(),
+            filter.clone(),
+            LeftMark,
+        )
+        .await?;
+        assert_snapshot!(batches_to_sort_string(&batches), @r#"
+            +----+----+-------+
+            | a1 | b1 | mark  |
+            +----+----+-------+
+            | 1  | 4  | false |
+            | 2  | 5  | true  |
+            | 3  | 7  | false |
+            +----+----+-------+
+            "#);
+
+        let (_, batches) = join_collect_with_filter(
+            Arc::clone(&left),
+            Arc::clone(&right),
+            on.clone(),
+            filter.clone(),
+            Left,
+        )
+        .await?;
+        assert_snapshot!(batches_to_sort_string(&batches), @r#"
+            +----+----+----+----+----+
+            | a1 | b1 | a2 | b1 | c2 |
+            +----+----+----+----+----+
+            | 1  | 4  |    |    |    |
+            | 2  | 5  | 20 | 5  | 3  |
+            | 3  | 7  |    |    |    |
+            +----+----+----+----+----+
+            "#);
+
+        let (_, batches) =
+            join_collect_with_filter(left, right, on, filter, Right).await?;
+        assert_snapshot!(batches_to_sort_string(&batches), @r#"
+            +----+----+----+----+----+
+            | a1 | b1 | a2 | b1 | c2 |
+            +----+----+----+----+----+
+            |    |    | 10 | 4  | 1  |
+            |    |    | 30 | 6  | 90 |
+            | 2  | 5  | 20 | 5  | 3  |
+            +----+----+----+----+----+
+            "#);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn join_with_duplicated_column_names() -> Result<()> {
         let left = build_table(
@@ -4468,6 +4553,117 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn overallocation_multi_batch_spill_with_filter() -> Result<()> {
+        let left = build_table_from_batches(
+            (0..3)
+                .map(|i| {
+                    build_table_i32(
+                        ("a1", &vec![i * 2, i * 2 + 1]),
+                        ("b1", &vec![1, 1]),
+                        ("c1", &vec![i * 2 + 4, i * 2 + 5]),
+                    )
+                })
+                .collect(),
+        );
+        let right = build_table_from_batches(
+            (0..3)
+                .map(|i| {
+                    build_table_i32(
+                        ("a2", &vec![i * 2, i * 2 + 1]),
+                        ("b2", &vec![1, 1]),
+                        ("c2", &vec![i * 10 + 50, i * 10 + 55]),
+                    )
+                })
+                .collect(),
+        );
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b2", &right.schema())?) as _,
+        )];
+        let sort_options = vec![SortOptions::default(); on.len()];
+        // a1 < a2, which leaves rows of both sides without a passing match
+        let filter = JoinFilter::new(
+            Arc::new(BinaryExpr::new(
+                Arc::new(Column::new("a1", 0)),
+                Operator::Lt,
+                Arc::new(Column::new("a2", 1)),
+            )),
+            vec![
+                ColumnIndex {
+                    index: 0,
+                    side: JoinSide::Left,
+                },
+                ColumnIndex {
+                    index: 0,
+                    side: JoinSide::Right,
+                },
+            ],
+            Arc::new(Schema::new(vec![
+                Field::new("a1", DataType::Int32, true),
+                Field::new("a2", DataType::Int32, true),
+            ])),
+        );
+
+        let join_types = [
+            Inner, Left, Right, RightSemi, Full, LeftSemi, LeftAnti, LeftMark,
+        ];
+
+        // Enable DiskManager to allow spilling
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(500, 1.0)
+            .with_disk_manager_builder(
+                DiskManagerBuilder::default().with_mode(DiskManagerMode::OsTmpDirectory),
+            )
+            .build_arc()?;
+
+        for batch_size in [1, 50] {
+            let session_config = SessionConfig::default().with_batch_size(batch_size);
+
+            for join_type in &join_types {
+                let task_ctx = TaskContext::default()
+                    .with_session_config(session_config.clone())
+                    .with_runtime(Arc::clone(&runtime));
+                let join = join_with_filter(
+                    Arc::clone(&left),
+                    Arc::clone(&right),
+                    on.clone(),
+                    filter.clone(),
+                    *join_type,
+                    sort_options.clone(),
+                    NullEquality::NullEqualsNothing,
+                )?;
+                let stream = join.execute(0, Arc::new(task_ctx))?;
+                let spilled_join_result = common::collect(stream).await?;
+                assert!(join.metrics().unwrap().spill_count().unwrap() > 0);
+
+                let task_ctx_no_spill =
+                    TaskContext::default().with_session_config(session_config.clone());
+                let join = join_with_filter(
+                    Arc::clone(&left),
+                    Arc::clone(&right),
+                    on.clone(),
+                    filter.clone(),
+                    *join_type,
+                    sort_options.clone(),
+                    NullEquality::NullEqualsNothing,
+                )?;
+                let stream = join.execute(0, Arc::new(task_ctx_no_spill))?;
+                let no_spilled_join_result = common::collect(stream).await?;
+                assert_eq!(join.metrics().unwrap().spill_count(), Some(0));
+
+                // Compare spilled and non spilled data to check spill logic doesn't corrupt the data
+                assert_eq!(
+                    batches_to_sort_string(&spilled_join_result),
+                    batches_to_sort_string(&no_spilled_join_result),
+                    "{join_type}, batch_size: {batch_size}"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     fn build_joined_record_batches() -> Result<JoinedRecordBatches> {
         let schema = Arc::new(Schema::new(vec![
             Field::new("a", DataType::Int32, true),
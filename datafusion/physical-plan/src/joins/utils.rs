@@ -397,9 +397,19 @@ pub(crate) fn estimate_join_statistics(
     join_type: &JoinType,
     schema: &Schema,
 ) -> Result<Statistics> {
+    let left_columns = left_stats.column_statistics.len();
     let join_stats = estimate_join_cardinality(join_type, left_stats, right_stats, &on);
     let (num_rows, column_statistics) = match join_stats {
-        Some(stats) => (Precision::Inexact(stats.num_rows), stats.column_statistics),
+        Some(stats) => {
+            let column_statistics = estimate_join_column_statistics(
+                join_type,
+                stats.column_statistics,
+                left_columns,
+                &on,
+                stats.num_rows,
+            );
+            (Precision::Inexact(stats.num_rows), column_statistics)
+        }
         None => (Precision::Absent, Statistics::unknown_column(schema)),
     };
     Ok(Statistics {
@@ -409,6 +419,73 @@ pub(crate) fn estimate_join_statistics(
     })
 }
 
+/// Estimate the statistics of the columns of a join's output of `num_rows`
+/// rows from the `column_statistics` of its inputs, the left input having
+/// `left_columns` columns.
+///
+/// The join keys of the output of an inner join only hold the values found
+/// on both sides, so their distinct count is at most the smallest of the
+/// distinct counts of the inputs. This also applies to the keys of the side
+/// of an outer join that is not preserved, whose columns are padded with
+/// nulls for the unmatched rows.
+fn estimate_join_column_statistics(
+    join_type: &JoinType,
+    column_statistics: Vec<ColumnStatistics>,
+    left_columns: usize,
+    on: JoinOnRef,
+    num_rows: usize,
+) -> Vec<ColumnStatistics> {
+    let mut column_statistics = column_statistics
+        .into_iter()
+        .map(ColumnStatistics::to_inexact)
+        .collect::<Vec<_>>();
+    let (left_preserved, right_preserved) = match join_type {
+        JoinType::Inner => (false, false),
+        JoinType::Left => (true, false),
+        JoinType::Right => (false, true),
+        JoinType::Full => (true, true),
+        // The output of the other joins only holds the columns of one side
+        _ => return column_statistics,
+    };
+    for (left, right) in on {
+        let (Some(left), Some(right)) = (
+            left.as_any().downcast_ref::<Column>(),
+            right.as_any().downcast_ref::<Column>(),
+        ) else {
+            continue;
+        };
+        let (left, right) = (left.index(), left_columns + right.index());
+        let distinct_count = match (
+            column_statistics[left].distinct_count,
+            column_statistics[right].distinct_count,
+        ) {
+            (Precision::Absent, count) | (count, Precision::Absent) => count,
+            (left_count, right_count) => left_count.min(&right_count),
+        };
+        if !left_preserved {
+            column_statistics[left].distinct_count = distinct_count;
+        }
+        if !right_preserved {
+            column_statistics[right].distinct_count = distinct_count;
+        }
+    }
+    for (idx, col_stats) in column_statistics.iter_mut().enumerate() {
+        // The columns of one side are padded with nulls when the other side
+        // is preserved
+        let padded = if idx < left_columns {
+            right_preserved
+        } else {
+            left_preserved
+        };
+        if padded {
+            col_stats.null_count = Precision::Absent;
+        }
+        col_stats.distinct_count =
+            col_stats.distinct_count.min(&Precision::Inexact(num_rows));
+    }
+    column_statistics
+}
+
 // Estimate the cardinality for the given join with input statistics.
 fn estimate_join_cardinality(
     join_type: &JoinType,
@@ -788,6 +865,21 @@ pub(crate) fn need_produce_result_in_final(join_type: JoinType) -> bool {
     )
 }
 
+/// Some type `join_type` of join need to know which rows of the right side matched, after
+/// probing all the left side rows, to generate the part of result of the join.
+///
+/// This is the counterpart of [`need_produce_result_in_final`] for the right side.
+pub(crate) fn need_produce_right_in_final(join_type: JoinType) -> bool {
+    matches!(
+        join_type,
+        JoinType::Right
+            | JoinType::RightAnti
+            | JoinType::RightSemi
+            | JoinType::RightMark
+            | JoinType::Full
+    )
+}
+
 pub(crate) fn get_final_indices_from_shared_bitmap(
     shared_bitmap: &SharedBitmapBuilder,
     join_type: JoinType,
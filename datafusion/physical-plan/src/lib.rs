@@ -72,6 +72,7 @@ pub mod execution_plan;
 pub mod explain;
 pub mod filter;
 pub mod filter_pushdown;
+pub mod instrument;
 pub mod joins;
 pub mod limit;
 pub mod memory;
@@ -80,6 +81,8 @@ pub mod placeholder_row;
 pub mod projection;
 pub mod recursive_query;
 pub mod repartition;
+pub mod result_cache;
+pub mod shared;
 pub mod sorts;
 pub mod spill;
 pub mod stream;
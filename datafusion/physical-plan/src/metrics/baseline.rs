@@ -156,6 +156,10 @@ pub struct SpillMetrics {
 
     /// total spilled rows during the execution of the operator
     pub spilled_rows: Count,
+
+    /// total in-memory size of the spilled batches, before they are
+    /// compressed and written to disk
+    pub spilled_uncompressed_bytes: Count,
 }
 
 impl SpillMetrics {
@@ -165,6 +169,8 @@ impl SpillMetrics {
             spill_file_count: MetricBuilder::new(metrics).spill_count(partition),
             spilled_bytes: MetricBuilder::new(metrics).spilled_bytes(partition),
             spilled_rows: MetricBuilder::new(metrics).spilled_rows(partition),
+            spilled_uncompressed_bytes: MetricBuilder::new(metrics)
+                .counter("spilled_uncompressed_bytes", partition),
         }
     }
 }
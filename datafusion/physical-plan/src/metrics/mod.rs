@@ -20,6 +20,7 @@
 mod baseline;
 mod builder;
 mod custom;
+mod tree;
 mod value;
 
 use parking_lot::Mutex;
@@ -35,6 +36,7 @@ use datafusion_common::HashMap;
 pub use baseline::{BaselineMetrics, RecordOutput, SpillMetrics};
 pub use builder::MetricBuilder;
 pub use custom::CustomMetricValue;
+pub use tree::{collect_plan_metrics, MetricsNode, METRICS_JSON_VERSION};
 pub use value::{Count, Gauge, MetricValue, ScopedTimerGuard, Time, Timestamp};
 
 /// Something that tracks a value of interest (metric) of a DataFusion
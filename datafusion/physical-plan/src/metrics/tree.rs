@@ -0,0 +1,150 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The metrics of an executed plan as a tree, see [`collect_plan_metrics`]
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use super::MetricsSet;
+use crate::display::DisplayableExecutionPlan;
+use crate::ExecutionPlan;
+
+use serde_json::{json, Value};
+
+/// The version of the documents of [`MetricsNode::to_json`], incremented
+/// when a change of their structure could break their readers
+pub const METRICS_JSON_VERSION: u32 = 1;
+
+/// The metrics of an operator of an executed plan and of its inputs, as
+/// returned by [`collect_plan_metrics`].
+///
+/// Metrics are identified by name, with the labels of metrics dropped, as in
+/// `EXPLAIN ANALYZE`. Their values are nanoseconds for times and timestamps
+/// (since the Unix epoch), bytes for sizes and counts otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetricsNode {
+    /// The name of the operator, such as `ProjectionExec`
+    pub operator: String,
+    /// The description of the operator in `EXPLAIN`, such as
+    /// `ProjectionExec: expr=[a@0 as a]`
+    pub description: String,
+    /// The metrics of the operator, aggregated over its partitions
+    pub metrics: BTreeMap<String, usize>,
+    /// The metrics of each partition of the operator that has any
+    pub partitions: BTreeMap<usize, BTreeMap<String, usize>>,
+    /// The metrics of the inputs of the operator
+    pub children: Vec<MetricsNode>,
+}
+
+impl MetricsNode {
+    /// Returns the tree as a JSON document of the form
+    ///
+    /// ```json
+    /// {
+    ///   "version": 1,
+    ///   "plan": {
+    ///     "operator": "ProjectionExec",
+    ///     "description": "ProjectionExec: expr=[a@0 as a]",
+    ///     "metrics": { "elapsed_compute": 1370, "output_rows": 8 },
+    ///     "partitions": [
+    ///       { "partition": 0, "metrics": { "elapsed_compute": 1370, "output_rows": 8 } }
+    ///     ],
+    ///     "children": [ ... ]
+    ///   }
+    /// }
+    /// ```
+    ///
+    /// where `version` is [`METRICS_JSON_VERSION`] and `children` holds
+    /// objects of the same form as `plan`
+    pub fn to_json(&self) -> String {
+        let document = json!({
+            "version": METRICS_JSON_VERSION,
+            "plan": self.to_json_value(),
+        });
+        serde_json::to_string_pretty(&document).expect("JSON values are serializable")
+    }
+
+    fn to_json_value(&self) -> Value {
+        let partitions = self
+            .partitions
+            .iter()
+            .map(|(partition, metrics)| {
+                json!({ "partition": partition, "metrics": metrics })
+            })
+            .collect::<Vec<_>>();
+        let children = self
+            .children
+            .iter()
+            .map(Self::to_json_value)
+            .collect::<Vec<_>>();
+        json!({
+            "operator": self.operator,
+            "description": self.description,
+            "metrics": self.metrics,
+            "partitions": partitions,
+            "children": children,
+        })
+    }
+}
+
+/// Returns the metrics of `plan` and of its inputs, which are only complete
+/// once all the partitions of `plan` have been executed.
+///
+/// See [`MetricsNode::to_json`] for the document that `EXPLAIN (ANALYZE,
+/// FORMAT JSON)` produces from the tree.
+pub fn collect_plan_metrics(plan: &dyn ExecutionPlan) -> MetricsNode {
+    let metrics = plan.metrics().unwrap_or_default();
+
+    let mut partitions: BTreeMap<usize, MetricsSet> = BTreeMap::new();
+    for metric in metrics.iter() {
+        if let Some(partition) = metric.partition() {
+            partitions
+                .entry(partition)
+                .or_default()
+                .push(Arc::clone(metric));
+        }
+    }
+
+    MetricsNode {
+        operator: plan.name().to_string(),
+        description: DisplayableExecutionPlan::new(plan)
+            .one_line()
+            .to_string()
+            .trim_end()
+            .to_string(),
+        metrics: by_name(&metrics),
+        partitions: partitions
+            .into_iter()
+            .map(|(partition, metrics)| (partition, by_name(&metrics)))
+            .collect(),
+        children: plan
+            .children()
+            .into_iter()
+            .map(|child| collect_plan_metrics(child.as_ref()))
+            .collect(),
+    }
+}
+
+/// The values of the metrics of `metrics` aggregated by name
+fn by_name(metrics: &MetricsSet) -> BTreeMap<String, usize> {
+    metrics
+        .aggregate_by_name()
+        .iter()
+        .map(|metric| (metric.value().name().to_string(), metric.value().as_usize()))
+        .collect()
+}
@@ -166,6 +166,11 @@ impl ExecutionPlan for PlaceholderRowExec {
         Ok(Box::pin(cooperative(ms)))
     }
 
+    fn output_identity(&self) -> Option<String> {
+        // The data is a single row of nulls
+        Some(String::new())
+    }
+
     fn statistics(&self) -> Result<Statistics> {
         self.partition_statistics(None)
     }
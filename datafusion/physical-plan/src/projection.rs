@@ -26,7 +26,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
-use super::expressions::{Column, Literal};
+use super::expressions::{CastExpr, Column, Literal};
 use super::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
 use super::{
     DisplayAs, ExecutionPlanProperties, PlanProperties, RecordBatchStream,
@@ -44,6 +44,7 @@ use datafusion_common::tree_node::{
 };
 use datafusion_common::{internal_err, JoinSide, Result};
 use datafusion_execution::TaskContext;
+use datafusion_physical_expr::bind_cancellation;
 use datafusion_physical_expr::equivalence::ProjectionMapping;
 use datafusion_physical_expr::utils::collect_columns;
 use datafusion_physical_expr_common::physical_expr::{fmt_sql, PhysicalExprRef};
@@ -230,7 +231,13 @@ impl ExecutionPlan for ProjectionExec {
         trace!("Start ProjectionExec::execute for partition {} of context session_id {} and task_id {:?}", partition, context.session_id(), context.task_id());
         Ok(Box::pin(ProjectionStream {
             schema: Arc::clone(&self.schema),
-            expr: self.expr.iter().map(|x| Arc::clone(&x.0)).collect(),
+            expr: self
+                .expr
+                .iter()
+                .map(|x| {
+                    bind_cancellation(Arc::clone(&x.0), context.cancellation_token())
+                })
+                .collect::<Result<_>>()?,
             input: self.input.execute(partition, context)?,
             baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
         }))
@@ -286,6 +293,15 @@ fn stats_projection(
     for expr in exprs {
         let col_stats = if let Some(col) = expr.as_any().downcast_ref::<Column>() {
             stats.column_statistics[col.index()].clone()
+        } else if let Some(col) = cast_column(&expr) {
+            // Casts keep the nulls of their input, and do not create distinct
+            // values, although lossy casts may merge some
+            let input_stats = &stats.column_statistics[col.index()];
+            ColumnStatistics {
+                null_count: input_stats.null_count.to_inexact(),
+                distinct_count: input_stats.distinct_count.to_inexact(),
+                ..ColumnStatistics::new_unknown()
+            }
         } else {
             // TODO stats: estimate more statistics from expressions
             // (expressions should compute their statistics themselves)
@@ -309,6 +325,13 @@ fn stats_projection(
     stats
 }
 
+/// Returns the column cast by `expr`, if it is a cast of a column
+fn cast_column(expr: &Arc<dyn PhysicalExpr>) -> Option<&Column> {
+    expr.as_any()
+        .downcast_ref::<CastExpr>()
+        .and_then(|cast| cast.expr().as_any().downcast_ref::<Column>())
+}
+
 impl ProjectionStream {
     fn batch_project(&self, batch: &RecordBatch) -> Result<RecordBatch> {
         // Records time on drop
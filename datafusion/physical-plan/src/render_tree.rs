@@ -26,7 +26,13 @@ use std::fmt::Formatter;
 use std::sync::Arc;
 use std::{cmp, fmt};
 
-use crate::{DisplayFormatType, ExecutionPlan};
+use crate::{DisplayFormatType, ExecutionPlan, ExecutionPlanProperties};
+
+use datafusion_common::stats::Precision;
+
+/// The maximum number of details of an operator rendered when the plan
+/// properties are shown, the others being counted instead
+const MAX_DETAILS_WITH_PROPERTIES: usize = 8;
 
 // TODO: It's never used.
 /// Represents a 2D coordinate in the rendered tree.
@@ -52,6 +58,8 @@ pub struct RenderTreeNode {
     pub name: String,
     /// Execution info collected from `ExecutionPlan`.
     pub extra_text: HashMap<String, String>,
+    /// Number of details of the `ExecutionPlan` left out of `extra_text`.
+    pub omitted_details: usize,
     /// Positions of child nodes in the rendered tree.
     pub child_positions: Vec<Coordinate>,
 }
@@ -61,6 +69,7 @@ impl RenderTreeNode {
         RenderTreeNode {
             name,
             extra_text,
+            omitted_details: 0,
             child_positions: vec![],
         }
     }
@@ -82,13 +91,18 @@ pub struct RenderTree {
 }
 
 impl RenderTree {
-    /// Creates a new render tree from an execution plan.
-    pub fn create_tree(plan: &dyn ExecutionPlan) -> Self {
+    /// Creates a new render tree from an execution plan, with the output
+    /// partitioning, output ordering, estimated number of rows and predicate
+    /// pushdown status of each operator if `show_properties` is set.
+    ///
+    /// Operators with more than [`MAX_DETAILS_WITH_PROPERTIES`] details then
+    /// only render the first ones, followed by the number of omitted ones.
+    pub fn create_tree(plan: &dyn ExecutionPlan, show_properties: bool) -> Self {
         let (width, height) = get_tree_width_height(plan);
 
         let mut result = Self::new(width, height);
 
-        create_tree_recursive(&mut result, plan, 0, 0);
+        create_tree_recursive(&mut result, plan, 0, 0, show_properties);
 
         result
     }
@@ -185,6 +199,7 @@ fn fmt_display(plan: &dyn ExecutionPlan) -> impl fmt::Display + '_ {
 /// * `plan` - Current execution plan node being processed
 /// * `x` - Horizontal position in the tree
 /// * `y` - Vertical position in the tree
+/// * `show_properties` - Whether to render the properties of the plan
 ///
 /// # Returns
 /// * The width of the subtree rooted at the current node
@@ -193,21 +208,33 @@ fn create_tree_recursive(
     plan: &dyn ExecutionPlan,
     x: usize,
     y: usize,
+    show_properties: bool,
 ) -> usize {
     let display_info = fmt_display(plan).to_string();
     let mut extra_info = HashMap::new();
 
+    let lines = display_info.lines().collect::<Vec<_>>();
+    let max_details = if show_properties {
+        MAX_DETAILS_WITH_PROPERTIES
+    } else {
+        lines.len()
+    };
+
     // Parse the key-value pairs from the formatted string.
     // See DisplayFormatType::TreeRender for details
-    for line in display_info.lines() {
+    for line in lines.iter().take(max_details) {
         if let Some((key, value)) = line.split_once('=') {
             extra_info.insert(key.to_string(), value.to_string());
         } else {
             extra_info.insert(line.to_string(), "".to_string());
         }
     }
+    if show_properties {
+        add_plan_properties(plan, &mut extra_info);
+    }
 
     let mut node = RenderTreeNode::new(plan.name().to_string(), extra_info);
+    node.omitted_details = lines.len().saturating_sub(max_details);
 
     let children = plan.children();
 
@@ -221,10 +248,46 @@ fn create_tree_recursive(
         let child_x = x + width;
         let child_y = y + 1;
         node.add_child_position(child_x, child_y);
-        width += create_tree_recursive(result, child.as_ref(), child_x, child_y);
+        width += create_tree_recursive(
+            result,
+            child.as_ref(),
+            child_x,
+            child_y,
+            show_properties,
+        );
     }
 
     result.set_node(x, y, Arc::new(node));
 
     width
 }
+
+/// Adds the output partitioning, output ordering and estimated number of
+/// rows of `plan` to its rendered details, and whether its predicate is
+/// pushed down into a scan, that is if it is a leaf.
+fn add_plan_properties(
+    plan: &dyn ExecutionPlan,
+    extra_info: &mut HashMap<String, String>,
+) {
+    if extra_info.contains_key("predicate") {
+        let pushdown = if plan.children().is_empty() {
+            "pushed into scan"
+        } else {
+            "not pushed down"
+        };
+        extra_info.insert("predicate_pushdown".to_string(), pushdown.to_string());
+    }
+    extra_info.insert(
+        "output_partitioning".to_string(),
+        plan.output_partitioning().to_string(),
+    );
+    if let Some(ordering) = plan.output_ordering() {
+        extra_info.insert("output_ordering".to_string(), ordering.to_string());
+    }
+    let estimated_rows = match plan.partition_statistics(None).map(|s| s.num_rows) {
+        Ok(Precision::Exact(rows)) => rows.to_string(),
+        Ok(Precision::Inexact(rows)) => format!("~{rows}"),
+        Ok(Precision::Absent) | Err(_) => return,
+    };
+    extra_info.insert("estimated_rows".to_string(), estimated_rows);
+}
@@ -37,6 +37,12 @@
 //! There are `N` virtual MPSC (multi-producer, single consumer) channels with unbounded capacity. However, if all
 //! buffers/channels are non-empty, than a global gate will be closed preventing new data from being written (the
 //! sender futures will be [pending](Poll::Pending)) until at least one channel is empty (and not closed).
+//!
+//! Channels may additionally have a capacity. Data sent to a channel holding `capacity` elements is only written
+//! once the receiver has consumed some of them, unless another receiver is starving, i.e. is waiting for data on an
+//! empty channel. Consumers of different channels may depend on each other, for example when a consumer waits for
+//! data of one channel before consuming another one: the capacity is exceeded rather than letting a starving
+//! receiver wait for senders that may never be unblocked.
 use std::{
     collections::VecDeque,
     future::Future,
@@ -51,16 +57,19 @@ use std::{
 
 use parking_lot::Mutex;
 
-/// Create `n` empty channels.
+/// Create `n` empty channels, holding at most `capacity` elements each unless a receiver is starving.
 pub fn channels<T>(
     n: usize,
+    capacity: Option<usize>,
 ) -> (Vec<DistributionSender<T>>, Vec<DistributionReceiver<T>>) {
     let channels = (0..n)
-        .map(|id| Arc::new(Channel::new_with_one_sender(id)))
+        .map(|id| Arc::new(Channel::new_with_one_sender(id, capacity)))
         .collect::<Vec<_>>();
     let gate = Arc::new(Gate {
         empty_channels: AtomicUsize::new(n),
         send_wakers: Mutex::new(None),
+        starving_channels: AtomicUsize::new(0),
+        capacity_wakers: Mutex::new(Vec::new()),
     });
     let senders = channels
         .iter()
@@ -88,8 +97,9 @@ type PartitionAwareReceivers<T> = Vec<Vec<DistributionReceiver<T>>>;
 pub fn partition_aware_channels<T>(
     n_in: usize,
     n_out: usize,
+    capacity: Option<usize>,
 ) -> (PartitionAwareSenders<T>, PartitionAwareReceivers<T>) {
-    (0..n_in).map(|_| channels(n_out)).unzip()
+    (0..n_in).map(|_| channels(n_out, capacity)).unzip()
 }
 
 /// Erroring during [send](DistributionSender::send).
@@ -135,6 +145,52 @@ impl<T> DistributionSender<T> {
             element: Box::new(Some(element)),
         }
     }
+
+    /// Returns true if the channel holds as many elements as its capacity, or more, although its receiver has
+    /// started receiving data, i.e. if the receiver is slower than the senders.
+    ///
+    /// Data sent to a full channel is only written once the receiver consumed some elements, unless another
+    /// receiver is starving.
+    pub fn is_backlogged(&self) -> bool {
+        let Some(capacity) = self.channel.capacity else {
+            return false;
+        };
+        let state = self.channel.state.lock();
+        state.receiving
+            && state
+                .data
+                .as_ref()
+                .is_some_and(|data| data.len() >= capacity)
+    }
+
+    /// Returns true if the receiver is waiting for data on the empty channel.
+    pub fn is_starving(&self) -> bool {
+        self.channel.state.lock().starving
+    }
+
+    /// Waits until data sent to the channel would be written right away regarding its capacity, i.e. until the
+    /// channel has room again, a receiver is starving or the receiver is gone.
+    ///
+    /// This allows senders that are free to choose a channel to pick another one once a receiver starves, rather
+    /// than exceeding the capacity of a full channel.
+    pub fn wait_for_capacity(&self) -> impl Future<Output = ()> + '_ {
+        std::future::poll_fn(move |cx| {
+            let state = self.channel.state.lock();
+            let full = state.data.as_ref().is_some_and(|data| {
+                self.channel
+                    .capacity
+                    .is_some_and(|capacity| data.len() >= capacity)
+            });
+            if full {
+                let mut guard = self.gate.capacity_wakers.lock();
+                if self.gate.starving_channels.load(Ordering::SeqCst) == 0 {
+                    guard.push(cx.waker().clone());
+                    return Poll::Pending;
+                }
+            }
+            Poll::Ready(())
+        })
+    }
 }
 
 impl<T> Clone for DistributionSender<T> {
@@ -183,6 +239,12 @@ impl<T> Drop for DistributionSender<T> {
                 self.gate.decr_empty_channels();
             }
 
+            // the receiver will not wait for data anymore
+            if state.starving {
+                state.starving = false;
+                self.gate.starving_channels.fetch_sub(1, Ordering::SeqCst);
+            }
+
             // make sure that nobody can add wakers anymore
             state.recv_wakers.take().expect("not closed yet")
         };
@@ -221,6 +283,20 @@ impl<T> Future for SendFuture<'_, T> {
                 )));
             };
 
+            // is the channel full?
+            // if so, only allow sender to exceed the capacity if a receiver is starving
+            if this
+                .channel
+                .capacity
+                .is_some_and(|capacity| data.len() >= capacity)
+            {
+                let mut guard = this.gate.capacity_wakers.lock();
+                if this.gate.starving_channels.load(Ordering::SeqCst) == 0 {
+                    guard.push(cx.waker().clone());
+                    return Poll::Pending;
+                }
+            }
+
             // does ANY receiver need data?
             // if so, allow sender to create another
             if this.gate.empty_channels.load(Ordering::SeqCst) == 0 {
@@ -234,6 +310,11 @@ impl<T> Future for SendFuture<'_, T> {
             let was_empty = data.is_empty();
             data.push_back(this.element.take().expect("just checked"));
 
+            if guard_channel_state.starving {
+                guard_channel_state.starving = false;
+                this.gate.starving_channels.fetch_sub(1, Ordering::SeqCst);
+            }
+
             if was_empty {
                 this.gate.decr_empty_channels();
                 guard_channel_state.take_recv_wakers()
@@ -283,8 +364,15 @@ impl<T> Drop for DistributionReceiver<T> {
             self.gate.decr_empty_channels();
         }
 
+        // the receiver will not wait for data anymore
+        if guard_channel_state.starving {
+            guard_channel_state.starving = false;
+            self.gate.starving_channels.fetch_sub(1, Ordering::SeqCst);
+        }
+
         // senders may be waiting for gate to open but should error now that the channel is closed
         self.gate.wake_channel_senders(self.channel.id);
+        self.gate.wake_capacity_senders();
     }
 }
 
@@ -304,10 +392,20 @@ impl<T> Future for RecvFuture<'_, T> {
 
         let mut guard_channel_state = this.channel.state.lock();
         let channel_state = guard_channel_state.deref_mut();
+        channel_state.receiving = true;
         let data = channel_state.data.as_mut().expect("not dropped yet");
 
         match data.pop_front() {
             Some(element) => {
+                // wake senders waiting for the channel to have room again?
+                if this
+                    .channel
+                    .capacity
+                    .is_some_and(|capacity| data.len() + 1 == capacity)
+                {
+                    this.gate.wake_capacity_senders();
+                }
+
                 // change "empty" signal for this channel?
                 if data.is_empty() && channel_state.recv_wakers.is_some() {
                     // update counter
@@ -342,6 +440,13 @@ impl<T> Future for RecvFuture<'_, T> {
             None => {
                 if let Some(recv_wakers) = channel_state.recv_wakers.as_mut() {
                     recv_wakers.push(cx.waker().clone());
+
+                    // senders waiting on full channels must now exceed their capacity
+                    if !channel_state.starving {
+                        channel_state.starving = true;
+                        this.gate.starve();
+                    }
+
                     Poll::Pending
                 } else {
                     this.rdy = true;
@@ -363,19 +468,25 @@ struct Channel<T> {
     /// This is used to address [send wakers](Gate::send_wakers).
     id: usize,
 
+    /// Number of elements above which senders wait for the receiver, unless another receiver is starving.
+    capacity: Option<usize>,
+
     /// Mutable state.
     state: Mutex<ChannelState<T>>,
 }
 
 impl<T> Channel<T> {
     /// Create new channel with one sender (so we don't need to [fetch-add](AtomicUsize::fetch_add) directly afterwards).
-    fn new_with_one_sender(id: usize) -> Self {
+    fn new_with_one_sender(id: usize, capacity: Option<usize>) -> Self {
         Channel {
             n_senders: AtomicUsize::new(1),
             id,
+            capacity,
             state: Mutex::new(ChannelState {
                 data: Some(VecDeque::default()),
                 recv_wakers: Some(Vec::default()),
+                receiving: false,
+                starving: false,
             }),
         }
     }
@@ -393,6 +504,14 @@ struct ChannelState<T> {
     /// The receiver will be pending if the [buffer](Self::data) is empty and
     /// there are senders left (otherwise this is set to [`None`]).
     recv_wakers: Option<Vec<Waker>>,
+
+    /// Whether the receiver has started receiving data.
+    receiving: bool,
+
+    /// Whether the receiver is waiting for data on the empty channel.
+    ///
+    /// Starving channels are counted by the [gate](Gate::starving_channels).
+    starving: bool,
 }
 
 impl<T> ChannelState<T> {
@@ -425,6 +544,14 @@ struct Gate {
     ///
     /// This is `None` if the there are non-empty channels.
     send_wakers: Mutex<Option<Vec<(Waker, usize)>>>,
+
+    /// Number of channels whose receiver is waiting for data.
+    starving_channels: AtomicUsize,
+
+    /// Wakers for the sender side waiting for a full channel to have room.
+    ///
+    /// They are woken when any full channel has room again, a receiver is dropped or starts starving.
+    capacity_wakers: Mutex<Vec<Waker>>,
 }
 
 impl Gate {
@@ -455,6 +582,32 @@ impl Gate {
         }
     }
 
+    /// Wake all senders waiting for a full channel to have room.
+    fn wake_capacity_senders(&self) {
+        let to_wake = std::mem::take(self.capacity_wakers.lock().deref_mut());
+
+        // wake outside of lock scope
+        for waker in to_wake {
+            waker.wake();
+        }
+    }
+
+    /// Count a starving channel and wake the senders waiting for full channels.
+    ///
+    /// The counter is updated under the lock of the wakers so that no sender can wait after checking the counter.
+    fn starve(&self) {
+        let to_wake = {
+            let mut guard = self.capacity_wakers.lock();
+            self.starving_channels.fetch_add(1, Ordering::SeqCst);
+            std::mem::take(guard.deref_mut())
+        };
+
+        // wake outside of lock scope
+        for waker in to_wake {
+            waker.wake();
+        }
+    }
+
     fn decr_empty_channels(&self) {
         let old_count = self.empty_channels.fetch_sub(1, Ordering::SeqCst);
 
@@ -483,7 +636,7 @@ mod tests {
     #[test]
     fn test_single_channel_no_gate() {
         // use two channels so that the first one never hits the gate
-        let (mut txs, mut rxs) = channels(2);
+        let (mut txs, mut rxs) = channels(2, None);
 
         let mut recv_fut = rxs[0].recv();
         let waker = poll_pending(&mut recv_fut);
@@ -508,7 +661,7 @@ mod tests {
     #[test]
     fn test_multi_sender() {
         // use two channels so that the first one never hits the gate
-        let (txs, mut rxs) = channels(2);
+        let (txs, mut rxs) = channels(2, None);
 
         let tx_clone = txs[0].clone();
 
@@ -521,7 +674,7 @@ mod tests {
 
     #[test]
     fn test_gate() {
-        let (txs, mut rxs) = channels(2);
+        let (txs, mut rxs) = channels(2, None);
 
         // gate initially open
         poll_ready(&mut txs[0].send("0_a")).unwrap();
@@ -549,7 +702,7 @@ mod tests {
 
     #[test]
     fn test_close_channel_by_dropping_tx() {
-        let (mut txs, mut rxs) = channels(2);
+        let (mut txs, mut rxs) = channels(2, None);
 
         let tx0 = txs.remove(0);
         let tx1 = txs.remove(0);
@@ -591,7 +744,7 @@ mod tests {
 
     #[test]
     fn test_close_channel_by_dropping_rx_on_open_gate() {
-        let (txs, mut rxs) = channels(2);
+        let (txs, mut rxs) = channels(2, None);
 
         let rx0 = rxs.remove(0);
         let _rx1 = rxs.remove(0);
@@ -607,7 +760,7 @@ mod tests {
 
     #[test]
     fn test_close_channel_by_dropping_rx_on_closed_gate() {
-        let (txs, mut rxs) = channels(2);
+        let (txs, mut rxs) = channels(2, None);
 
         let rx0 = rxs.remove(0);
         let mut rx1 = rxs.remove(0);
@@ -637,7 +790,7 @@ mod tests {
 
     #[test]
     fn test_drop_rx_three_channels() {
-        let (mut txs, mut rxs) = channels(3);
+        let (mut txs, mut rxs) = channels(3, None);
 
         let tx0 = txs.remove(0);
         let tx1 = txs.remove(0);
@@ -665,7 +818,7 @@ mod tests {
 
     #[test]
     fn test_close_channel_by_dropping_rx_clears_data() {
-        let (txs, rxs) = channels(1);
+        let (txs, rxs) = channels(1, None);
 
         let obj = Arc::new(());
         let counter = Arc::downgrade(&obj);
@@ -684,7 +837,7 @@ mod tests {
     /// Ensure that polling "pending" futures work even when you poll them too often (which happens under some circumstances).
     #[test]
     fn test_poll_empty_channel_twice() {
-        let (txs, mut rxs) = channels(1);
+        let (txs, mut rxs) = channels(1, None);
 
         let mut recv_fut = rxs[0].recv();
         let waker_1a = poll_pending(&mut recv_fut);
@@ -730,7 +883,7 @@ mod tests {
     #[test]
     #[should_panic(expected = "polled ready future")]
     fn test_panic_poll_send_future_after_ready_ok() {
-        let (txs, _rxs) = channels(1);
+        let (txs, _rxs) = channels(1, None);
         let mut fut = txs[0].send("foo");
         poll_ready(&mut fut).unwrap();
         poll_ready(&mut fut).ok();
@@ -739,7 +892,7 @@ mod tests {
     #[test]
     #[should_panic(expected = "polled ready future")]
     fn test_panic_poll_send_future_after_ready_err() {
-        let (txs, rxs) = channels(1);
+        let (txs, rxs) = channels(1, None);
 
         drop(rxs);
 
@@ -751,7 +904,7 @@ mod tests {
     #[test]
     #[should_panic(expected = "polled ready future")]
     fn test_panic_poll_recv_future_after_ready_some() {
-        let (txs, mut rxs) = channels(1);
+        let (txs, mut rxs) = channels(1, None);
 
         poll_ready(&mut txs[0].send("foo")).unwrap();
 
@@ -763,7 +916,7 @@ mod tests {
     #[test]
     #[should_panic(expected = "polled ready future")]
     fn test_panic_poll_recv_future_after_ready_none() {
-        let (txs, mut rxs) = channels::<u8>(1);
+        let (txs, mut rxs) = channels::<u8>(1, None);
 
         drop(txs);
 
@@ -772,6 +925,94 @@ mod tests {
         poll_ready(&mut fut);
     }
 
+    #[test]
+    fn test_capacity() {
+        let (txs, mut rxs) = channels(2, Some(1));
+
+        poll_ready(&mut txs[0].send("a")).unwrap();
+        assert!(!txs[0].is_backlogged());
+
+        // channel 0 is full, and the gate is still open
+        let mut send_fut = txs[0].send("b");
+        let waker = poll_pending(&mut send_fut);
+
+        // consuming data wakes the sender
+        assert_eq!(poll_ready(&mut rxs[0].recv()), Some("a"));
+        assert!(waker.woken());
+        poll_ready(&mut send_fut).unwrap();
+
+        // the receiver started receiving data but is slower than the sender
+        assert!(txs[0].is_backlogged());
+        assert!(!txs[1].is_backlogged());
+    }
+
+    #[test]
+    fn test_capacity_exceeded_for_starving_receiver() {
+        // use three channels so that the gate stays open
+        let (txs, mut rxs) = channels(3, Some(1));
+
+        poll_ready(&mut txs[0].send("a")).unwrap();
+        let mut send_fut = txs[0].send("b");
+        let waker = poll_pending(&mut send_fut);
+
+        // receiver 1 waits for data, which the sender of channel 0 may produce
+        let mut recv_fut = rxs[1].recv();
+        poll_pending(&mut recv_fut);
+        assert!(txs[1].is_starving());
+        assert!(waker.woken());
+        poll_ready(&mut send_fut).unwrap();
+
+        // receiver 1 gets data and does not starve anymore
+        poll_ready(&mut txs[1].send("c")).unwrap();
+        assert!(!txs[1].is_starving());
+        assert_eq!(poll_ready(&mut recv_fut), Some("c"));
+        poll_pending(&mut txs[0].send("d"));
+
+        // channel 0 holds more elements than its capacity
+        assert_eq!(poll_ready(&mut rxs[0].recv()), Some("a"));
+        assert_eq!(poll_ready(&mut rxs[0].recv()), Some("b"));
+    }
+
+    #[test]
+    fn test_wait_for_capacity() {
+        let (txs, mut rxs) = channels(3, Some(1));
+
+        poll_ready(&mut txs[0].wait_for_capacity());
+        poll_ready(&mut txs[0].send("a")).unwrap();
+        let mut wait_fut = txs[0].wait_for_capacity();
+        let waker = poll_pending(&mut wait_fut);
+
+        // receiver 1 starts starving, so data may be sent to it instead
+        let mut recv_fut = rxs[1].recv();
+        poll_pending(&mut recv_fut);
+        assert!(waker.woken());
+        poll_ready(&mut wait_fut);
+
+        poll_ready(&mut txs[1].send("b")).unwrap();
+        assert_eq!(poll_ready(&mut recv_fut), Some("b"));
+        let mut wait_fut = txs[0].wait_for_capacity();
+        let waker = poll_pending(&mut wait_fut);
+
+        // consuming data makes room again
+        assert_eq!(poll_ready(&mut rxs[0].recv()), Some("a"));
+        assert!(waker.woken());
+        poll_ready(&mut wait_fut);
+    }
+
+    #[test]
+    fn test_close_full_channel_by_dropping_rx() {
+        let (txs, mut rxs) = channels(2, Some(1));
+
+        poll_ready(&mut txs[0].send("a")).unwrap();
+        let mut send_fut = txs[0].send("b");
+        let waker = poll_pending(&mut send_fut);
+
+        // dropping the receiver wakes the sender, which errors
+        drop(rxs.remove(0));
+        assert!(waker.woken());
+        assert_eq!(poll_ready(&mut send_fut), Err(SendError("b")));
+    }
+
     #[test]
     #[should_panic(expected = "future is pending")]
     fn test_meta_poll_ready_wrong_state() {
@@ -79,6 +79,7 @@ struct ConsumingInputStreamsState {
             InputPartitionsToCurrentPartitionSender,
             InputPartitionsToCurrentPartitionReceiver,
             SharedMemoryReservation,
+            metrics::Gauge,
         ),
     >,
 
@@ -161,7 +162,7 @@ impl RepartitionExecState {
             RepartitionExecState::NotInitialized => {
                 self.ensure_input_streams_initialized(
                     input,
-                    metrics,
+                    metrics.clone(),
                     partitioning.partition_count(),
                     Arc::clone(&context),
                 )?;
@@ -178,10 +179,22 @@ impl RepartitionExecState {
 
         let num_input_partitions = streams_and_metrics.len();
         let num_output_partitions = partitioning.partition_count();
+        let capacity = match context
+            .session_config()
+            .options()
+            .execution
+            .repartition_max_buffered_batches
+        {
+            0 => None,
+            max_buffered_batches => Some(max_buffered_batches),
+        };
 
         let (txs, rxs) = if preserve_order {
-            let (txs, rxs) =
-                partition_aware_channels(num_input_partitions, num_output_partitions);
+            let (txs, rxs) = partition_aware_channels(
+                num_input_partitions,
+                num_output_partitions,
+                capacity,
+            );
             // Take transpose of senders and receivers. `state.channels` keeps track of entries per output partition
             let txs = transpose(txs);
             let rxs = transpose(rxs);
@@ -190,7 +203,7 @@ impl RepartitionExecState {
             // create one channel per *output* partition
             // note we use a custom channel that ensures there is always data for each receiver
             // but limits the amount of buffering if required.
-            let (txs, rxs) = channels(num_output_partitions);
+            let (txs, rxs) = channels(num_output_partitions, capacity);
             // Clone sender for each input partitions
             let txs = txs
                 .into_iter()
@@ -206,7 +219,10 @@ impl RepartitionExecState {
                 MemoryConsumer::new(format!("{name}[{partition}]"))
                     .register(context.memory_pool()),
             ));
-            channels.insert(partition, (tx, rx, reservation));
+            // High watermark of the memory of the batches buffered for the partition
+            let peak_buffered_bytes =
+                MetricBuilder::new(&metrics).gauge("peak_buffered_bytes", partition);
+            channels.insert(partition, (tx, rx, reservation, peak_buffered_bytes));
         }
 
         // launch one async task per *input* partition
@@ -216,8 +232,15 @@ impl RepartitionExecState {
         {
             let txs: HashMap<_, _> = channels
                 .iter()
-                .map(|(partition, (tx, _rx, reservation))| {
-                    (*partition, (tx[i].clone(), Arc::clone(reservation)))
+                .map(|(partition, (tx, _rx, reservation, peak_buffered_bytes))| {
+                    (
+                        *partition,
+                        (
+                            tx[i].clone(),
+                            Arc::clone(reservation),
+                            peak_buffered_bytes.clone(),
+                        ),
+                    )
                 })
                 .collect();
 
@@ -233,7 +256,9 @@ impl RepartitionExecState {
             let wait_for_task = SpawnedTask::spawn(RepartitionExec::wait_for_task(
                 input_task,
                 txs.into_iter()
-                    .map(|(partition, (tx, _reservation))| (partition, tx))
+                    .map(|(partition, (tx, _reservation, _peak_buffered_bytes))| {
+                        (partition, tx)
+                    })
                     .collect(),
             ));
             spawned_tasks.push(wait_for_task);
@@ -688,7 +713,7 @@ impl ExecutionPlan for RepartitionExec {
 
                 // now return stream for the specified *output* partition which will
                 // read from the channel
-                let (_tx, rx, reservation) = state
+                let (_tx, rx, reservation, _peak_buffered_bytes) = state
                     .channels
                     .remove(&partition)
                     .expect("partition not used yet");
@@ -922,15 +947,25 @@ impl RepartitionExec {
     /// output partitions based on the desired partitioning
     ///
     /// txs hold the output sending channels for each output partition
+    ///
+    /// With round robin partitioning, a batch for an output partition whose
+    /// consumer is slower than the inputs is sent to a partition whose
+    /// consumer waits for data instead, so that a slow consumer does not
+    /// hold back the others.
     async fn pull_from_input(
         mut stream: SendableRecordBatchStream,
         mut output_channels: HashMap<
             usize,
-            (DistributionSender<MaybeBatch>, SharedMemoryReservation),
+            (
+                DistributionSender<MaybeBatch>,
+                SharedMemoryReservation,
+                metrics::Gauge,
+            ),
         >,
         partitioning: Partitioning,
         metrics: RepartitionMetrics,
     ) -> Result<()> {
+        let round_robin = matches!(partitioning, Partitioning::RoundRobinBatch(_));
         let mut partitioner =
             BatchPartitioner::try_new(partitioning, metrics.repartition_time.clone())?;
 
@@ -949,13 +984,24 @@ impl RepartitionExec {
             };
 
             for res in partitioner.partition_iter(batch)? {
-                let (partition, batch) = res?;
+                let (mut partition, batch) = res?;
                 let size = batch.get_array_memory_size();
 
+                if round_robin {
+                    partition =
+                        Self::redirect_round_robin(&output_channels, partition).await;
+                }
+
                 let timer = metrics.send_time[partition].timer();
                 // if there is still a receiver, send to it
-                if let Some((tx, reservation)) = output_channels.get_mut(&partition) {
-                    reservation.lock().try_grow(size)?;
+                if let Some((tx, reservation, peak_buffered_bytes)) =
+                    output_channels.get_mut(&partition)
+                {
+                    {
+                        let mut reservation = reservation.lock();
+                        reservation.try_grow(size)?;
+                        peak_buffered_bytes.set_max(reservation.size());
+                    }
 
                     if tx.send(Some(Ok(batch))).await.is_err() {
                         // If the other end has hung up, it was an early shutdown (e.g. LIMIT)
@@ -993,6 +1039,33 @@ impl RepartitionExec {
         Ok(())
     }
 
+    /// Returns `partition`, unless its channel is backlogged, in which case
+    /// this waits for the channel to have room again or for the receiver of
+    /// another output partition to starve, and returns that partition
+    async fn redirect_round_robin<T>(
+        output_channels: &HashMap<
+            usize,
+            (DistributionSender<MaybeBatch>, T, metrics::Gauge),
+        >,
+        partition: usize,
+    ) -> usize {
+        loop {
+            let Some((tx, _, _)) = output_channels.get(&partition) else {
+                return partition;
+            };
+            if !tx.is_backlogged() {
+                return partition;
+            }
+            if let Some((starving, _)) = output_channels
+                .iter()
+                .find(|(_, (tx, _, _))| tx.is_starving())
+            {
+                return *starving;
+            }
+            tx.wait_for_capacity().await;
+        }
+    }
+
     /// Waits for `input_task` which is consuming one of the inputs to
     /// complete. Upon each successful completion, sends a `None` to
     /// each of the output tx channels to signal one of the inputs is
@@ -1179,6 +1252,7 @@ mod tests {
     use datafusion_common::test_util::batches_to_sort_string;
     use datafusion_common::{arrow_datafusion_err, exec_err};
     use datafusion_common_runtime::JoinSet;
+    use datafusion_execution::config::SessionConfig;
     use datafusion_execution::runtime_env::RuntimeEnvBuilder;
     use insta::assert_snapshot;
     use itertools::Itertools;
@@ -1694,6 +1768,69 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn slow_consumer_bounded_buffer() -> Result<()> {
+        let schema = test_schema();
+        let batch_size = create_batch().get_array_memory_size();
+        let input_partitions = vec![create_vec_batches(100)];
+
+        // Buffering the 25 batches of the slow consumer would exceed the limit
+        let runtime = RuntimeEnvBuilder::default()
+            .with_memory_limit(20 * batch_size, 1.0)
+            .build_arc()?;
+        let config = SessionConfig::new().with_repartition_max_buffered_batches(2);
+        let task_ctx = TaskContext::default()
+            .with_session_config(config)
+            .with_runtime(runtime);
+        let task_ctx = Arc::new(task_ctx);
+
+        let exec =
+            TestMemoryExec::try_new_exec(&input_partitions, Arc::clone(&schema), None)?;
+        let exec = RepartitionExec::try_new(exec, Partitioning::RoundRobinBatch(4))?;
+
+        // consume all partitions concurrently, partition 0 slowly
+        let consumers = (0..4).map(|partition| {
+            let stream = exec.execute(partition, Arc::clone(&task_ctx));
+            async move {
+                let mut stream = stream?;
+                let mut num_batches = 0;
+                while let Some(batch) = stream.next().await {
+                    batch?;
+                    num_batches += 1;
+                    if partition == 0 {
+                        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                    }
+                }
+                Ok::<_, DataFusionError>(num_batches)
+            }
+        });
+        let num_batches = futures::future::try_join_all(consumers).await?;
+
+        // the other consumers took over batches of the slow one
+        assert_eq!(num_batches.iter().sum::<usize>(), 100);
+        assert!(num_batches[0] < 25, "{num_batches:?}");
+
+        // at most one batch over the limit is reserved while a sender waits
+        let metrics = exec.metrics().unwrap();
+        let peak_buffered_bytes = metrics
+            .iter()
+            .find(|metric| {
+                metric.value().name() == "peak_buffered_bytes"
+                    && metric.partition() == Some(0)
+            })
+            .unwrap()
+            .value()
+            .as_usize();
+        assert!(peak_buffered_bytes > 0);
+        assert!(
+            peak_buffered_bytes <= 3 * batch_size,
+            "{peak_buffered_bytes}"
+        );
+        assert_eq!(task_ctx.runtime_env().memory_pool.reserved(), 0);
+
+        Ok(())
+    }
+
     /// Create vector batches
     fn create_vec_batches(n: usize) -> Vec<RecordBatch> {
         let batch = create_batch();
@@ -0,0 +1,426 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the [`ResultCacheExec`] operator, which returns the result of a
+//! query from a [`ResultCache`], or stores the result of its input in it
+
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::execution_plan::{Boundedness, CardinalityEffect, EmissionType};
+use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use crate::stream::{ObservedStream, RecordBatchStreamAdapter};
+use crate::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties, Partitioning,
+    PlanProperties, RecordBatchStream, SendableRecordBatchStream, Statistics,
+};
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::stats::Precision;
+use datafusion_common::{internal_err, Result};
+use datafusion_execution::cache::result_cache::{CachedResult, ResultCache, ResultKey};
+use datafusion_execution::TaskContext;
+use datafusion_physical_expr::EquivalenceProperties;
+
+use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
+
+/// Returns the result of a query from a [`ResultCache`], or executes its input
+/// and stores its complete output in the cache.
+///
+/// On a cache hit, the operator has no input and returns the stored batches
+/// of each partition. On a miss, the output of the input is passed through
+/// and buffered, and stored in the cache once every partition has been read
+/// to completion. The result is not stored if a partition fails, or if the
+/// output is larger than the cache.
+#[derive(Debug)]
+pub struct ResultCacheExec {
+    source: ResultSource,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    cache: PlanProperties,
+}
+
+#[derive(Debug)]
+enum ResultSource {
+    /// The result stored in the cache
+    Hit(Arc<CachedResult>),
+    /// The plan computing the result, to store in the cache
+    Miss {
+        input: Arc<dyn ExecutionPlan>,
+        fill: Arc<ResultFill>,
+    },
+}
+
+impl ResultCacheExec {
+    /// Create a `ResultCacheExec` returning `result`, found in the cache
+    pub fn hit(result: Arc<CachedResult>) -> Self {
+        let cache = PlanProperties::new(
+            EquivalenceProperties::new(Arc::clone(result.schema())),
+            Partitioning::UnknownPartitioning(result.partitions().len()),
+            EmissionType::Incremental,
+            Boundedness::Bounded,
+        );
+        Self {
+            source: ResultSource::Hit(result),
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        }
+    }
+
+    /// Create a `ResultCacheExec` storing the output of `input` in `cache`,
+    /// for `key` and the `snapshot` tokens of the tables scanned by `input`
+    pub fn miss(
+        input: Arc<dyn ExecutionPlan>,
+        cache: Arc<ResultCache>,
+        key: ResultKey,
+        snapshot: Vec<String>,
+    ) -> Self {
+        let properties = PlanProperties::new(
+            input.equivalence_properties().clone(),
+            input.output_partitioning().clone(),
+            input.pipeline_behavior(),
+            input.boundedness(),
+        );
+        let partition_count = input.output_partitioning().partition_count();
+        let fill = ResultFill {
+            cache,
+            key,
+            snapshot,
+            state: Mutex::new(FillState {
+                partitions: vec![None; partition_count],
+                abandoned: false,
+            }),
+        };
+        Self {
+            source: ResultSource::Miss {
+                input,
+                fill: Arc::new(fill),
+            },
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache: properties,
+        }
+    }
+
+    /// Returns true if the result was found in the cache
+    pub fn is_hit(&self) -> bool {
+        matches!(self.source, ResultSource::Hit(_))
+    }
+}
+
+impl DisplayAs for ResultCacheExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "ResultCacheExec: hit={}", self.is_hit())
+            }
+            DisplayFormatType::TreeRender => {
+                write!(f, "hit={}", self.is_hit())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for ResultCacheExec {
+    fn name(&self) -> &'static str {
+        "ResultCacheExec"
+    }
+
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        match &self.source {
+            ResultSource::Hit(_) => vec![],
+            ResultSource::Miss { input, .. } => vec![input],
+        }
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true; self.children().len()]
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        vec![false; self.children().len()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match &self.source {
+            ResultSource::Hit(_) if children.is_empty() => Ok(self),
+            ResultSource::Miss { fill, .. } if children.len() == 1 => {
+                Ok(Arc::new(ResultCacheExec::miss(
+                    Arc::clone(&children[0]),
+                    Arc::clone(&fill.cache),
+                    fill.key.clone(),
+                    fill.snapshot.clone(),
+                )))
+            }
+            _ => internal_err!("ResultCacheExec wrong number of children"),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let stream: SendableRecordBatchStream = match &self.source {
+            ResultSource::Hit(result) => {
+                let Some(batches) = result.partitions().get(partition) else {
+                    return internal_err!(
+                        "ResultCacheExec invalid partition {partition}"
+                    );
+                };
+                let batches = batches.clone();
+                Box::pin(RecordBatchStreamAdapter::new(
+                    Arc::clone(result.schema()),
+                    futures::stream::iter(batches.into_iter().map(Ok)),
+                ))
+            }
+            ResultSource::Miss { input, fill } => Box::pin(FillStream {
+                input: input.execute(partition, context)?,
+                fill: Arc::clone(fill),
+                partition,
+                batches: vec![],
+                size: 0,
+            }),
+        };
+        let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+        Ok(Box::pin(ObservedStream::new(
+            stream,
+            baseline_metrics,
+            None,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        self.partition_statistics(None)
+    }
+
+    fn partition_statistics(&self, partition: Option<usize>) -> Result<Statistics> {
+        match &self.source {
+            ResultSource::Hit(result) => {
+                let num_rows = match partition {
+                    Some(partition) => result.partitions()[partition]
+                        .iter()
+                        .map(|b| b.num_rows())
+                        .sum(),
+                    None => result.num_rows(),
+                };
+                Ok(Statistics::new_unknown(result.schema())
+                    .with_num_rows(Precision::Exact(num_rows)))
+            }
+            ResultSource::Miss { input, .. } => input.partition_statistics(partition),
+        }
+    }
+
+    fn cardinality_effect(&self) -> CardinalityEffect {
+        CardinalityEffect::Equal
+    }
+}
+
+/// Collects the output of each partition of the input of a
+/// [`ResultCacheExec`], to store it in the cache once complete
+#[derive(Debug)]
+struct ResultFill {
+    cache: Arc<ResultCache>,
+    key: ResultKey,
+    snapshot: Vec<String>,
+    state: Mutex<FillState>,
+}
+
+#[derive(Debug)]
+struct FillState {
+    /// The complete output of each partition read so far
+    partitions: Vec<Option<Vec<RecordBatch>>>,
+    /// Set once the result can no longer be stored
+    abandoned: bool,
+}
+
+impl ResultFill {
+    fn abandon(&self) {
+        let mut state = self.state.lock();
+        state.abandoned = true;
+        state.partitions.iter_mut().for_each(|p| *p = None);
+    }
+
+    /// Records the complete output of `partition`, storing the result once
+    /// every partition is complete
+    fn complete(&self, schema: SchemaRef, partition: usize, batches: Vec<RecordBatch>) {
+        let partitions = {
+            let mut state = self.state.lock();
+            if state.abandoned {
+                return;
+            }
+            state.partitions[partition] = Some(batches);
+            if state.partitions.iter().any(|p| p.is_none()) {
+                return;
+            }
+            state.partitions.iter_mut().flat_map(|p| p.take()).collect()
+        };
+        let result = CachedResult::new(schema, partitions);
+        self.cache
+            .put(self.key.clone(), self.snapshot.clone(), result);
+    }
+}
+
+/// Passes the output of a partition through, buffering it for a
+/// [`ResultFill`]
+struct FillStream {
+    input: SendableRecordBatchStream,
+    fill: Arc<ResultFill>,
+    partition: usize,
+    batches: Vec<RecordBatch>,
+    /// Memory used by `batches`
+    size: usize,
+}
+
+impl Stream for FillStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = self.input.poll_next_unpin(cx);
+        match &poll {
+            Poll::Ready(Some(Ok(batch))) => {
+                self.size += batch.get_array_memory_size();
+                if self.size > self.fill.cache.max_size() {
+                    // The result is too large to be cached
+                    self.batches = vec![];
+                    self.fill.abandon();
+                } else {
+                    self.batches.push(batch.clone());
+                }
+            }
+            Poll::Ready(Some(Err(_))) => self.fill.abandon(),
+            Poll::Ready(None) => {
+                let batches = std::mem::take(&mut self.batches);
+                self.fill
+                    .complete(self.input.schema(), self.partition, batches);
+            }
+            Poll::Pending => {}
+        }
+        poll
+    }
+}
+
+impl RecordBatchStream for FillStream {
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use crate::{collect, common};
+
+    use datafusion_common::test_util::batches_to_sort_string;
+    use datafusion_execution::memory_pool::{MemoryPool, UnboundedMemoryPool};
+    use std::time::Duration;
+
+    fn new_cache(max_size: usize) -> Arc<ResultCache> {
+        let pool: Arc<dyn MemoryPool> = Arc::new(UnboundedMemoryPool::default());
+        Arc::new(ResultCache::new(max_size, Duration::from_secs(3600), &pool))
+    }
+
+    fn key() -> ResultKey {
+        ResultKey::new(1, "plan")
+    }
+
+    #[tokio::test]
+    async fn stores_complete_result() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let cache = new_cache(1 << 20);
+        let snapshot = vec!["v1".to_string()];
+        let input = test::scan_partitioned(4);
+        let exec = Arc::new(ResultCacheExec::miss(
+            input,
+            Arc::clone(&cache),
+            key(),
+            snapshot.clone(),
+        ));
+
+        // Nothing is stored until every partition is complete
+        let stream = exec.execute(0, Arc::clone(&task_ctx))?;
+        common::collect(stream).await?;
+        assert!(cache.is_empty());
+        let expected =
+            batches_to_sort_string(&collect(exec, Arc::clone(&task_ctx)).await?);
+        assert_eq!(cache.len(), 1);
+
+        let result = cache.get(&key(), &snapshot).unwrap();
+        let exec = Arc::new(ResultCacheExec::hit(result));
+        assert_eq!(exec.properties().output_partitioning().partition_count(), 4);
+        let batches = collect(Arc::clone(&exec) as _, task_ctx).await?;
+        assert_eq!(batches_to_sort_string(&batches), expected);
+        assert_eq!(exec.metrics().unwrap().output_rows(), Some(400));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn does_not_store_incomplete_or_large_result() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let cache = new_cache(1 << 20);
+        let exec = ResultCacheExec::miss(
+            test::scan_partitioned(1),
+            Arc::clone(&cache),
+            key(),
+            vec![],
+        );
+        // The partition is not read to completion
+        let mut stream = exec.execute(0, Arc::clone(&task_ctx))?;
+        stream.next().await.unwrap()?;
+        drop(stream);
+        assert!(cache.is_empty());
+
+        let cache = new_cache(100);
+        let exec = Arc::new(ResultCacheExec::miss(
+            test::scan_partitioned(1),
+            Arc::clone(&cache),
+            key(),
+            vec![],
+        ));
+        let batches = collect(exec, task_ctx).await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 100);
+        assert!(cache.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,547 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the [`SharedExec`] operator, which executes its input once and
+//! feeds the buffered output to several consumers
+
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::execution_plan::{CardinalityEffect, EmissionType};
+use crate::joins::utils::OnceFut;
+use crate::metrics::{
+    BaselineMetrics, Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+    SpillMetrics,
+};
+use crate::spill::get_record_batch_memory_size;
+use crate::spill::spill_manager::SpillManager;
+use crate::stream::{ObservedStream, RecordBatchStreamAdapter};
+use crate::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties, PlanProperties,
+    SendableRecordBatchStream, Statistics,
+};
+
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{internal_err, Result};
+use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::TaskContext;
+
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt, TryStreamExt};
+use parking_lot::Mutex;
+
+/// Executes its input once per partition and feeds the output to several
+/// consumers, for example the two sides of a self join on a common table
+/// expression.
+///
+/// The same `SharedExec` instance is referenced by each of its consumers in
+/// the plan. The first consumer to execute a partition starts executing the
+/// input partition, and every consumer of that partition reads the buffered
+/// output once it is complete.
+///
+/// The output of each partition is buffered in memory, accounted in the
+/// memory pool, and spilled to disk once the memory pool is exhausted. As the
+/// input is always read to completion, regardless of how fast each consumer
+/// reads the output, a slow consumer can never block another consumer.
+///
+/// The buffer of a partition is released once `consumers` streams have been
+/// created for it and all of them have been dropped. Executing the partition
+/// after that executes the input again.
+#[derive(Debug)]
+pub struct SharedExec {
+    /// Input execution plan
+    input: Arc<dyn ExecutionPlan>,
+    /// Number of consumers that execute each partition of this plan
+    consumers: usize,
+    /// The buffered output of each partition, shared by its consumers
+    partitions: Arc<Mutex<Vec<Option<SharedPartition>>>>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    cache: PlanProperties,
+}
+
+/// The buffered output of a partition, along with the number of streams
+/// created for it
+struct SharedPartition {
+    buffer: OnceFut<SharedBuffer>,
+    /// Number of streams created for the partition
+    executions: usize,
+    /// Number of streams created for the partition that are not dropped yet
+    open_streams: usize,
+}
+
+impl std::fmt::Debug for SharedPartition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedPartition")
+            .field("executions", &self.executions)
+            .field("open_streams", &self.open_streams)
+            .finish()
+    }
+}
+
+impl SharedExec {
+    /// Create a new `SharedExec` feeding the output of `input` to `consumers`
+    /// consumers
+    pub fn new(input: Arc<dyn ExecutionPlan>, consumers: usize) -> Self {
+        let cache = Self::compute_properties(&input);
+        let partition_count = input.output_partitioning().partition_count();
+        Self {
+            input,
+            consumers,
+            partitions: Arc::new(Mutex::new(
+                (0..partition_count).map(|_| None).collect(),
+            )),
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        }
+    }
+
+    /// Input execution plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// Number of consumers that execute each partition of this plan
+    pub fn consumers(&self) -> usize {
+        self.consumers
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(input: &Arc<dyn ExecutionPlan>) -> PlanProperties {
+        PlanProperties::new(
+            input.equivalence_properties().clone(),
+            input.output_partitioning().clone(),
+            // The output is only emitted once the input partition is buffered
+            EmissionType::Final,
+            input.boundedness(),
+        )
+    }
+}
+
+impl DisplayAs for SharedExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "SharedExec: consumers={}", self.consumers)
+            }
+            DisplayFormatType::TreeRender => {
+                write!(f, "consumers={}", self.consumers)
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for SharedExec {
+    fn name(&self) -> &'static str {
+        "SharedExec"
+    }
+
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.input]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        vec![false]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(SharedExec::new(
+            Arc::clone(&children[0]),
+            self.consumers,
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let buffer = {
+            let mut partitions = self.partitions.lock();
+            let Some(slot) = partitions.get_mut(partition) else {
+                return internal_err!("SharedExec invalid partition {partition}");
+            };
+            let shared = match slot {
+                Some(shared) => shared,
+                None => slot.insert(SharedPartition {
+                    buffer: OnceFut::new(materialize(
+                        self.input.execute(partition, Arc::clone(&context))?,
+                        partition,
+                        &context,
+                        &self.metrics,
+                    )),
+                    executions: 0,
+                    open_streams: 0,
+                }),
+            };
+            shared.executions += 1;
+            shared.open_streams += 1;
+            shared.buffer.clone()
+        };
+        let consumer = SharedConsumer {
+            partitions: Arc::clone(&self.partitions),
+            partition,
+            consumers: self.consumers,
+        };
+
+        let stream = futures::stream::once(async move {
+            let mut buffer = buffer;
+            let buffer = futures::future::poll_fn(|cx| buffer.get_shared(cx)).await?;
+            SharedBuffer::stream(buffer)
+        })
+        .try_flatten()
+        .boxed();
+        let stream = SharedStream {
+            stream,
+            _consumer: consumer,
+        };
+        let stream = Box::pin(RecordBatchStreamAdapter::new(self.schema(), stream));
+        let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+        Ok(Box::pin(ObservedStream::new(
+            stream,
+            baseline_metrics,
+            None,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        self.partition_statistics(None)
+    }
+
+    fn partition_statistics(&self, partition: Option<usize>) -> Result<Statistics> {
+        self.input.partition_statistics(partition)
+    }
+
+    fn cardinality_effect(&self) -> CardinalityEffect {
+        CardinalityEffect::Equal
+    }
+}
+
+/// A consumer of a partition of a [`SharedExec`], releasing the buffer of the
+/// partition when dropped after the other consumers
+struct SharedConsumer {
+    partitions: Arc<Mutex<Vec<Option<SharedPartition>>>>,
+    partition: usize,
+    consumers: usize,
+}
+
+impl Drop for SharedConsumer {
+    fn drop(&mut self) {
+        let mut partitions = self.partitions.lock();
+        let slot = &mut partitions[self.partition];
+        let Some(shared) = slot else {
+            return;
+        };
+        shared.open_streams -= 1;
+        if shared.open_streams == 0 && shared.executions >= self.consumers {
+            // The buffer is dropped once the lock is released
+            let released = slot.take();
+            drop(partitions);
+            drop(released);
+        }
+    }
+}
+
+/// The output stream of a partition of a [`SharedExec`] for one consumer
+struct SharedStream {
+    stream: BoxStream<'static, Result<RecordBatch>>,
+    _consumer: SharedConsumer,
+}
+
+impl Stream for SharedStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
+/// Reads `input` to completion, buffering its output in memory until the
+/// memory pool is exhausted, and spilling the remaining batches to disk
+async fn materialize_inner(
+    mut input: SendableRecordBatchStream,
+    mut reservation: MemoryReservation,
+    spill_manager: SpillManager,
+    executions: Count,
+) -> Result<SharedBuffer> {
+    executions.add(1);
+    let mut batches = vec![];
+    let mut spill_file = None;
+    while let Some(batch) = input.next().await {
+        let batch = batch?;
+        if spill_file.is_none() {
+            let size = get_record_batch_memory_size(&batch);
+            if reservation.try_grow(size).is_ok() {
+                batches.push(batch);
+                continue;
+            }
+            spill_file = Some(spill_manager.create_in_progress_file("SharedExec")?);
+        }
+        if let Some(spill_file) = spill_file.as_mut() {
+            spill_file.append_batch(&batch)?;
+        }
+    }
+    let spill_file = match spill_file {
+        Some(mut spill_file) => spill_file.finish()?.map(Arc::new),
+        None => None,
+    };
+    Ok(SharedBuffer {
+        batches,
+        spill_file,
+        spill_manager,
+        _reservation: reservation,
+    })
+}
+
+/// Returns the future buffering the output of partition `partition` of the
+/// input
+fn materialize(
+    input: SendableRecordBatchStream,
+    partition: usize,
+    context: &Arc<TaskContext>,
+    metrics: &ExecutionPlanMetricsSet,
+) -> impl std::future::Future<Output = Result<SharedBuffer>> + Send + 'static {
+    let reservation = MemoryConsumer::new(format!("SharedExec[{partition}]"))
+        .with_can_spill(true)
+        .register(context.memory_pool());
+    let spill_manager = SpillManager::new(
+        context.runtime_env(),
+        SpillMetrics::new(metrics, partition),
+        input.schema(),
+    )
+    .with_compression_type(context.session_config().spill_compression());
+    let executions = MetricBuilder::new(metrics).counter("input_executions", partition);
+    materialize_inner(input, reservation, spill_manager, executions)
+}
+
+/// Returns the [`ExecutionPlan::output_identity`] of a scan of the in-memory
+/// `partitions`: the addresses of the arrays of their batches, which are
+/// shared by the scans of the same table
+pub fn batches_identity(partitions: &[Vec<RecordBatch>]) -> String {
+    partitions
+        .iter()
+        .flatten()
+        .flat_map(|batch| batch.columns())
+        .map(|column| format!("{:p}", Arc::as_ptr(column)))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The complete output of a partition of the input of a [`SharedExec`]
+#[derive(Debug)]
+struct SharedBuffer {
+    /// Batches kept in memory, which precede the spilled batches
+    batches: Vec<RecordBatch>,
+    /// Batches spilled to disk once the memory pool was exhausted
+    spill_file: Option<Arc<RefCountedTempFile>>,
+    spill_manager: SpillManager,
+    /// Accounts for the memory used by `batches`
+    _reservation: MemoryReservation,
+}
+
+impl SharedBuffer {
+    /// Returns a stream over the buffered batches, in input order
+    fn stream(this: Arc<Self>) -> Result<BoxStream<'static, Result<RecordBatch>>> {
+        let spilled = this
+            .spill_file
+            .as_ref()
+            .map(|spill_file| {
+                this.spill_manager
+                    .read_shared_spill_as_stream(Arc::clone(spill_file))
+            })
+            .transpose()?;
+        let num_batches = this.batches.len();
+        let in_memory = futures::stream::iter(0..num_batches)
+            .map(move |i| Ok(this.batches[i].clone()));
+        Ok(in_memory
+            .chain(futures::stream::iter(spilled).flatten())
+            .boxed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{self, TestMemoryExec};
+    use crate::{collect, common};
+
+    use datafusion_common::test_util::batches_to_sort_string;
+    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+
+    /// Executes every partition of `plan` once for each of `consumers`
+    /// consumers, interleaving the consumers
+    async fn execute_consumers(
+        plan: &Arc<SharedExec>,
+        consumers: usize,
+        context: &Arc<TaskContext>,
+    ) -> Result<Vec<Vec<RecordBatch>>> {
+        let partition_count = plan.properties().output_partitioning().partition_count();
+        let mut streams = vec![];
+        for partition in 0..partition_count {
+            for _ in 0..consumers {
+                streams.push(plan.execute(partition, Arc::clone(context))?);
+            }
+        }
+        let mut results = vec![vec![]; consumers];
+        for (i, stream) in streams.into_iter().enumerate() {
+            results[i % consumers].extend(common::collect(stream).await?);
+        }
+        Ok(results)
+    }
+
+    fn input_executions(plan: &SharedExec) -> usize {
+        plan.metrics()
+            .unwrap()
+            .sum_by_name("input_executions")
+            .unwrap()
+            .as_usize()
+    }
+
+    #[tokio::test]
+    async fn executes_input_once() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let input = test::scan_partitioned(4);
+        let expected = batches_to_sort_string(
+            &collect(Arc::clone(&input), Arc::clone(&task_ctx)).await?,
+        );
+
+        let shared = Arc::new(SharedExec::new(input, 2));
+        let results = execute_consumers(&shared, 2, &task_ctx).await?;
+        for result in &results {
+            assert_eq!(batches_to_sort_string(result), expected);
+        }
+        assert_eq!(input_executions(&shared), 4);
+        assert_eq!(shared.metrics().unwrap().output_rows().unwrap(), 800);
+
+        // All consumers executed the partitions, so executing them again
+        // executes the input again
+        let results = execute_consumers(&shared, 1, &task_ctx).await?;
+        assert_eq!(batches_to_sort_string(&results[0]), expected);
+        assert_eq!(input_executions(&shared), 8);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn releases_buffer_once_consumers_are_done() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let memory_pool = Arc::clone(task_ctx.memory_pool());
+        let shared = Arc::new(SharedExec::new(test::scan_partitioned(1), 2));
+
+        // The buffer is kept for the second consumer
+        let first = common::collect(shared.execute(0, Arc::clone(&task_ctx))?).await?;
+        assert!(memory_pool.reserved() > 0);
+
+        // Streams created while the buffer is kept read it, and the buffer is
+        // released once all of them are dropped
+        let second = shared.execute(0, Arc::clone(&task_ctx))?;
+        let third = shared.execute(0, Arc::clone(&task_ctx))?;
+        let second = common::collect(second).await?;
+        assert_eq!(
+            batches_to_sort_string(&first),
+            batches_to_sort_string(&second)
+        );
+        assert!(memory_pool.reserved() > 0);
+        drop(third);
+        assert_eq!(memory_pool.reserved(), 0);
+        assert_eq!(input_executions(&shared), 1);
+
+        // Executing the partition again executes the input again
+        let again = common::collect(shared.execute(0, Arc::clone(&task_ctx))?).await?;
+        assert_eq!(
+            batches_to_sort_string(&first),
+            batches_to_sort_string(&again)
+        );
+        assert_eq!(input_executions(&shared), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn slow_consumer_does_not_block() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let batches = (0..10)
+            .map(|_| test::make_partition(100))
+            .collect::<Vec<_>>();
+        let schema = batches[0].schema();
+        let input = TestMemoryExec::try_new_exec(&[batches], schema, None)?;
+        let shared = Arc::new(SharedExec::new(input, 2));
+
+        // The second consumer is only polled once the first one is complete
+        let slow = shared.execute(0, Arc::clone(&task_ctx))?;
+        let fast = shared.execute(0, Arc::clone(&task_ctx))?;
+        let fast = common::collect(fast).await?;
+        let slow = common::collect(slow).await?;
+        assert_eq!(fast.iter().map(|b| b.num_rows()).sum::<usize>(), 1000);
+        assert_eq!(batches_to_sort_string(&fast), batches_to_sort_string(&slow));
+        assert_eq!(input_executions(&shared), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn spills_when_memory_is_exhausted() -> Result<()> {
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(2000, 1.0)
+            .build_arc()?;
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+        let batches = (0..10)
+            .map(|_| test::make_partition(100))
+            .collect::<Vec<_>>();
+        let schema = batches[0].schema();
+        let expected = batches_to_sort_string(&batches);
+        let input = TestMemoryExec::try_new_exec(&[batches], schema, None)?;
+        let shared = Arc::new(SharedExec::new(input, 2));
+
+        let results = execute_consumers(&shared, 2, &task_ctx).await?;
+        for result in &results {
+            assert_eq!(batches_to_sort_string(result), expected);
+        }
+        let metrics = shared.metrics().unwrap();
+        assert_eq!(metrics.spill_count(), Some(1));
+        assert!(metrics.spilled_rows().unwrap() > 0);
+        assert_eq!(input_executions(&shared), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,378 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the grouped TopK plan, which keeps the first rows of each group
+
+use std::any::Any;
+use std::fmt::{self, Formatter};
+use std::sync::Arc;
+
+use crate::execution_plan::{CardinalityEffect, EmissionType};
+use crate::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use crate::stream::RecordBatchStreamAdapter;
+use crate::topk::GroupedTopK;
+use crate::{
+    DisplayAs, DisplayFormatType, Distribution, ExecutionPlan, ExecutionPlanProperties,
+    PlanProperties, SendableRecordBatchStream, Statistics,
+};
+
+use arrow::datatypes::{FieldRef, Schema, SchemaRef};
+use datafusion_common::{internal_err, plan_err, ColumnStatistics, Result};
+use datafusion_execution::TaskContext;
+use datafusion_physical_expr::{EquivalenceProperties, PhysicalExpr};
+use datafusion_physical_expr_common::sort_expr::LexOrdering;
+use futures::{StreamExt, TryStreamExt};
+
+/// Keeps the first `k` rows of each group of rows with the same values of
+/// the `partition_by` expressions, according to the sort expressions `expr`,
+/// and numbers them within their group.
+///
+/// This computes the same rows as a `ROW_NUMBER` window function partitioned
+/// by `partition_by` and ordered by `expr`, followed by a filter on the row
+/// number, such as the following query:
+///
+/// ```sql
+/// SELECT * FROM (
+///   SELECT *, ROW_NUMBER() OVER (PARTITION BY sensor ORDER BY ts DESC) AS rn
+///   FROM readings
+/// ) WHERE rn <= 3
+/// ```
+///
+/// Rather than sorting all input rows, only the first `k` rows of each group
+/// are kept in memory, spilling them to disk if the memory pool is
+/// exhausted. The output holds the columns of the input followed by the row
+/// number column, and is sorted according to `expr`, which must start with
+/// sort expressions on the `partition_by` expressions.
+#[derive(Debug, Clone)]
+pub struct GroupedTopKExec {
+    /// Input plan
+    input: Arc<dyn ExecutionPlan>,
+    /// Expressions whose values define the groups
+    partition_by: Vec<Arc<dyn PhysicalExpr>>,
+    /// Sort expressions, starting with the `partition_by` expressions
+    expr: LexOrdering,
+    /// Number of rows to keep per group
+    k: usize,
+    /// Field of the row number column
+    row_number: FieldRef,
+    /// Execution metrics
+    metrics_set: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl GroupedTopKExec {
+    /// Create a new [`GroupedTopKExec`] keeping the first `k` rows of each
+    /// group, numbered in a `row_number` column
+    pub fn try_new(
+        input: Arc<dyn ExecutionPlan>,
+        partition_by: Vec<Arc<dyn PhysicalExpr>>,
+        expr: LexOrdering,
+        k: usize,
+        row_number: FieldRef,
+    ) -> Result<Self> {
+        if k == 0 {
+            return plan_err!("GroupedTopKExec must keep at least one row per group");
+        }
+        if partition_by.is_empty() {
+            return plan_err!("GroupedTopKExec requires at least one group expression");
+        }
+        if expr.len() < partition_by.len()
+            || !expr
+                .iter()
+                .take(partition_by.len())
+                .all(|sort_expr| partition_by.iter().any(|e| e.eq(&sort_expr.expr)))
+        {
+            return internal_err!(
+                "The sort expressions of GroupedTopKExec [{expr}] must start with its group expressions"
+            );
+        }
+
+        let mut fields = input.schema().fields().to_vec();
+        fields.push(Arc::clone(&row_number));
+        let schema = Arc::new(Schema::new_with_metadata(
+            fields,
+            input.schema().metadata().clone(),
+        ));
+        let cache = Self::compute_properties(&input, schema, expr.clone());
+        Ok(Self {
+            input,
+            partition_by,
+            expr,
+            k,
+            row_number,
+            metrics_set: ExecutionPlanMetricsSet::new(),
+            cache,
+        })
+    }
+
+    /// Input plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// Expressions whose values define the groups
+    pub fn partition_by(&self) -> &[Arc<dyn PhysicalExpr>] {
+        &self.partition_by
+    }
+
+    /// Sort expressions
+    pub fn expr(&self) -> &LexOrdering {
+        &self.expr
+    }
+
+    /// Number of rows kept per group
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Field of the row number column
+    pub fn row_number(&self) -> &FieldRef {
+        &self.row_number
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        input: &Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+        expr: LexOrdering,
+    ) -> PlanProperties {
+        // Each partition is sorted by the sort expressions
+        let eq_properties = EquivalenceProperties::new_with_orderings(schema, [expr]);
+
+        PlanProperties::new(
+            eq_properties,
+            input.output_partitioning().clone(),
+            // the whole input must be consumed before emitting output
+            EmissionType::Final,
+            input.boundedness(),
+        )
+    }
+}
+
+impl DisplayAs for GroupedTopKExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> fmt::Result {
+        let partition_by = self
+            .partition_by
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "GroupedTopKExec: k={}, partition_by=[{partition_by}], expr=[{}], row_number={}",
+                    self.k,
+                    self.expr,
+                    self.row_number.name()
+                )
+            }
+            DisplayFormatType::TreeRender => {
+                writeln!(f, "k={}", self.k)?;
+                writeln!(f, "partition_by={partition_by}")?;
+                writeln!(f, "{}", self.expr)
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for GroupedTopKExec {
+    fn name(&self) -> &'static str {
+        "GroupedTopKExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::HashPartitioned(self.partition_by.clone())]
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.input]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(GroupedTopKExec::try_new(
+            Arc::clone(&children[0]),
+            self.partition_by.clone(),
+            self.expr.clone(),
+            self.k,
+            Arc::clone(&self.row_number),
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let mut input = self.input.execute(partition, Arc::clone(&context))?;
+        let mut topk = GroupedTopK::try_new(
+            partition,
+            input.schema(),
+            self.schema(),
+            self.partition_by.clone(),
+            self.expr.clone(),
+            self.k,
+            context.session_config().batch_size(),
+            context.session_config().spill_compression(),
+            context.runtime_env(),
+            context.memory_pool(),
+            &self.metrics_set,
+        )?;
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            futures::stream::once(async move {
+                while let Some(batch) = input.next().await {
+                    topk.insert_batch(batch?)?;
+                }
+                topk.emit()
+            })
+            .try_flatten(),
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics_set.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        self.partition_statistics(None)
+    }
+
+    fn partition_statistics(&self, partition: Option<usize>) -> Result<Statistics> {
+        let input_statistics = self.input.partition_statistics(partition)?;
+        let mut column_statistics = input_statistics.column_statistics;
+        column_statistics.push(ColumnStatistics::new_unknown());
+        Ok(Statistics {
+            num_rows: input_statistics.num_rows,
+            total_byte_size: input_statistics.total_byte_size,
+            column_statistics,
+        }
+        .to_inexact())
+    }
+
+    fn cardinality_effect(&self) -> CardinalityEffect {
+        CardinalityEffect::LowerEqual
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect;
+    use crate::test::TestMemoryExec;
+    use arrow::array::{Int32Array, RecordBatch};
+    use arrow::datatypes::{DataType, Field};
+    use arrow_schema::SortOptions;
+    use datafusion_common::assert_batches_eq;
+    use datafusion_physical_expr::expressions::col;
+    use datafusion_physical_expr_common::sort_expr::PhysicalSortExpr;
+
+    #[tokio::test]
+    async fn test_grouped_topk_exec() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]));
+        let batch = |a: Vec<i32>, b: Vec<i32>| {
+            RecordBatch::try_new(
+                Arc::clone(&schema),
+                vec![Arc::new(Int32Array::from(a)), Arc::new(Int32Array::from(b))],
+            )
+            .unwrap()
+        };
+        let input = TestMemoryExec::try_new_exec(
+            &[vec![
+                batch(vec![1, 2, 1, 2], vec![10, 20, 30, 40]),
+                batch(vec![1, 3, 1], vec![50, 60, 5]),
+            ]],
+            Arc::clone(&schema),
+            None,
+        )?;
+
+        let a = col("a", &schema)?;
+        let expr = LexOrdering::from([
+            PhysicalSortExpr::new(Arc::clone(&a), SortOptions::new(true, true)),
+            PhysicalSortExpr::new(col("b", &schema)?, SortOptions::default()),
+        ]);
+        let row_number = Arc::new(Field::new("rn", DataType::UInt64, false));
+        let exec = Arc::new(GroupedTopKExec::try_new(
+            input,
+            vec![a],
+            expr.clone(),
+            2,
+            row_number,
+        )?);
+        assert_eq!(exec.properties().output_ordering(), Some(&expr));
+
+        let results = collect(exec, Arc::new(TaskContext::default())).await?;
+        assert_batches_eq!(
+            &[
+                "+---+----+----+",
+                "| a | b  | rn |",
+                "+---+----+----+",
+                "| 3 | 60 | 1  |",
+                "| 2 | 20 | 1  |",
+                "| 2 | 40 | 2  |",
+                "| 1 | 5  | 1  |",
+                "| 1 | 10 | 2  |",
+                "+---+----+----+",
+            ],
+            &results
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_grouped_topk_exec_invalid_expr() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]));
+        let input = TestMemoryExec::try_new_exec(&[vec![]], Arc::clone(&schema), None)?;
+        let expr = LexOrdering::from([PhysicalSortExpr::new(
+            col("b", &schema)?,
+            SortOptions::default(),
+        )]);
+        let row_number = Arc::new(Field::new("rn", DataType::UInt64, false));
+        let err = GroupedTopKExec::try_new(
+            input,
+            vec![col("a", &schema)?],
+            expr,
+            1,
+            row_number,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("must start with its group expressions"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
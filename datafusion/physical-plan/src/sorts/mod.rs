@@ -19,6 +19,7 @@
 
 mod builder;
 mod cursor;
+pub mod grouped_topk;
 mod merge;
 pub mod partial_sort;
 pub mod sort;
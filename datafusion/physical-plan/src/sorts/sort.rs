@@ -30,7 +30,8 @@ use crate::expressions::PhysicalSortExpr;
 use crate::filter_pushdown::{FilterDescription, FilterPushdownPhase};
 use crate::limit::LimitStream;
 use crate::metrics::{
-    BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet, SpillMetrics,
+    BaselineMetrics, Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+    SpillMetrics,
 };
 use crate::projection::{make_with_child, update_ordering, ProjectionExec};
 use crate::sorts::streaming_merge::StreamingMergeBuilder;
@@ -45,13 +46,14 @@ use crate::{
     Statistics,
 };
 
-use arrow::array::{Array, RecordBatch, RecordBatchOptions, StringViewArray};
-use arrow::compute::{concat_batches, lexsort_to_indices, take_arrays};
+use arrow::array::{Array, RecordBatch, RecordBatchOptions, UInt32Array};
+use arrow::compute::{concat_batches, lexsort_to_indices, take_arrays, SortColumn};
 use arrow::datatypes::SchemaRef;
+use arrow::row::{Row, RowConverter, SortField};
 use datafusion_common::config::SpillCompression;
 use datafusion_common::{internal_datafusion_err, internal_err, DataFusionError, Result};
 use datafusion_execution::disk_manager::RefCountedTempFile;
-use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation};
 use datafusion_execution::runtime_env::RuntimeEnv;
 use datafusion_execution::TaskContext;
 use datafusion_physical_expr::expressions::{lit, DynamicFilterPhysicalExpr};
@@ -59,6 +61,7 @@ use datafusion_physical_expr::LexOrdering;
 use datafusion_physical_expr::PhysicalExpr;
 
 use futures::{StreamExt, TryStreamExt};
+use hashbrown::HashMap;
 use log::{debug, trace};
 
 struct ExternalSorterMetrics {
@@ -66,6 +69,9 @@ struct ExternalSorterMetrics {
     baseline: BaselineMetrics,
 
     spill_metrics: SpillMetrics,
+
+    /// Number of passes merging spill files, including the final merge
+    merge_passes: Count,
 }
 
 impl ExternalSorterMetrics {
@@ -73,6 +79,7 @@ impl ExternalSorterMetrics {
         Self {
             baseline: BaselineMetrics::new(metrics, partition),
             spill_metrics: SpillMetrics::new(metrics, partition),
+            merge_passes: MetricBuilder::new(metrics).counter("merge_passes", partition),
         }
     }
 }
@@ -157,7 +164,9 @@ impl ExternalSorterMetrics {
 ///
 /// Once the input is completely read, the spill files are read and
 /// merged with any in memory batches to produce a single total sorted
-/// output:
+/// output. If there are more than `sort_merge_fan_in` spill files, groups
+/// of them are first merged into new spill files, in as many passes as
+/// needed:
 ///
 /// ```text
 ///   .─────────────────.
@@ -210,6 +219,8 @@ struct ExternalSorter {
     /// the data will be concatenated and sorted in place rather than
     /// sort/merged.
     sort_in_place_threshold_bytes: usize,
+    /// Maximum number of spill files merged at once
+    sort_merge_fan_in: usize,
 
     // ========================================================================
     // STATE BUFFERS:
@@ -259,19 +270,22 @@ impl ExternalSorter {
         batch_size: usize,
         sort_spill_reservation_bytes: usize,
         sort_in_place_threshold_bytes: usize,
+        sort_merge_fan_in: usize,
         // Configured via `datafusion.execution.spill_compression`.
         spill_compression: SpillCompression,
         metrics: &ExecutionPlanMetricsSet,
         runtime: Arc<RuntimeEnv>,
+        // The pool of the task, limiting the memory of the query if configured
+        memory_pool: &Arc<dyn MemoryPool>,
     ) -> Result<Self> {
         let metrics = ExternalSorterMetrics::new(metrics, partition_id);
         let reservation = MemoryConsumer::new(format!("ExternalSorter[{partition_id}]"))
             .with_can_spill(true)
-            .register(&runtime.memory_pool);
+            .register(memory_pool);
 
         let merge_reservation =
             MemoryConsumer::new(format!("ExternalSorterMerge[{partition_id}]"))
-                .register(&runtime.memory_pool);
+                .register(memory_pool);
 
         let spill_manager = SpillManager::new(
             Arc::clone(&runtime),
@@ -294,6 +308,7 @@ impl ExternalSorter {
             batch_size,
             sort_spill_reservation_bytes,
             sort_in_place_threshold_bytes,
+            sort_merge_fan_in: sort_merge_fan_in.max(2),
         })
     }
 
@@ -342,6 +357,10 @@ impl ExternalSorter {
                 self.sort_and_spill_in_mem_batches().await?;
             }
 
+            while self.finished_spill_files.len() > self.sort_merge_fan_in {
+                self.merge_spill_files().await?;
+            }
+
             for spill in self.finished_spill_files.drain(..) {
                 if !spill.path().exists() {
                     return internal_err!("Spill file {:?} does not exist", spill.path());
@@ -350,6 +369,7 @@ impl ExternalSorter {
                 streams.push(stream);
             }
 
+            self.metrics.merge_passes.add(1);
             StreamingMergeBuilder::new()
                 .with_streams(streams)
                 .with_schema(Arc::clone(&self.schema))
@@ -364,6 +384,54 @@ impl ExternalSorter {
         }
     }
 
+    /// Merges the spill files in groups of `sort_merge_fan_in` files, writing
+    /// each group into a new spill file
+    async fn merge_spill_files(&mut self) -> Result<()> {
+        // Release the memory reserved for merge back to the pool, which is
+        // reserved again after spilling the in memory batches
+        self.merge_reservation.free();
+
+        let spill_files = std::mem::take(&mut self.finished_spill_files);
+        let mut spill_files = spill_files.into_iter().peekable();
+        while spill_files.peek().is_some() {
+            let group = spill_files
+                .by_ref()
+                .take(self.sort_merge_fan_in)
+                .collect::<Vec<_>>();
+            if group.len() == 1 {
+                self.finished_spill_files.extend(group);
+                continue;
+            }
+
+            let streams = group
+                .into_iter()
+                .map(|spill| self.spill_manager.read_spill_as_stream(spill))
+                .collect::<Result<Vec<_>>>()?;
+            let mut merged = StreamingMergeBuilder::new()
+                .with_streams(streams)
+                .with_schema(Arc::clone(&self.schema))
+                .with_expressions(&self.expr.clone())
+                .with_metrics(self.metrics.baseline.intermediate())
+                .with_batch_size(self.batch_size)
+                .with_fetch(None)
+                .with_reservation(self.merge_reservation.new_empty())
+                .build()?;
+
+            let mut spill_file = self.spill_manager.create_in_progress_file("Sorting")?;
+            while let Some(batch) = merged.next().await {
+                spill_file.append_batch(&batch?)?;
+            }
+            self.finished_spill_files.extend(spill_file.finish()?);
+        }
+
+        debug!(
+            "Merged spill files of ExternalSorter into {} files",
+            self.finished_spill_files.len()
+        );
+        self.metrics.merge_passes.add(1);
+        Ok(())
+    }
+
     /// How much memory is buffered in this `ExternalSorter`?
     fn used(&self) -> usize {
         self.reservation.size()
@@ -400,8 +468,6 @@ impl ExternalSorter {
                 Some(self.spill_manager.create_in_progress_file("Sorting")?);
         }
 
-        Self::organize_stringview_arrays(globally_sorted_batches)?;
-
         debug!("Spilling sort data of ExternalSorter to disk whilst inserting");
 
         let batches_to_spill = std::mem::take(globally_sorted_batches);
@@ -437,71 +503,6 @@ impl ExternalSorter {
         Ok(())
     }
 
-    /// Reconstruct `globally_sorted_batches` to organize the payload buffers of each
-    /// `StringViewArray` in sequential order by calling `gc()` on them.
-    ///
-    /// Note this is a workaround until <https://github.com/apache/arrow-rs/issues/7185> is
-    /// available
-    ///
-    /// # Rationale
-    /// After (merge-based) sorting, all batches will be sorted into a single run,
-    /// but physically this sorted run is chunked into many small batches. For
-    /// `StringViewArray`s inside each sorted run, their inner buffers are not
-    /// re-constructed by default, leading to non-sequential payload locations
-    /// (permutated by `interleave()` Arrow kernel). A single payload buffer might
-    /// be shared by multiple `RecordBatch`es.
-    /// When writing each batch to disk, the writer has to write all referenced buffers,
-    /// because they have to be read back one by one to reduce memory usage. This
-    /// causes extra disk reads and writes, and potentially execution failure.
-    ///
-    /// # Example
-    /// Before sorting:
-    /// batch1 -> buffer1
-    /// batch2 -> buffer2
-    ///
-    /// sorted_batch1 -> buffer1
-    ///               -> buffer2
-    /// sorted_batch2 -> buffer1
-    ///               -> buffer2
-    ///
-    /// Then when spilling each batch, the writer has to write all referenced buffers
-    /// repeatedly.
-    fn organize_stringview_arrays(
-        globally_sorted_batches: &mut Vec<RecordBatch>,
-    ) -> Result<()> {
-        let mut organized_batches = Vec::with_capacity(globally_sorted_batches.len());
-
-        for batch in globally_sorted_batches.drain(..) {
-            let mut new_columns: Vec<Arc<dyn Array>> =
-                Vec::with_capacity(batch.num_columns());
-
-            let mut arr_mutated = false;
-            for array in batch.columns() {
-                if let Some(string_view_array) =
-                    array.as_any().downcast_ref::<StringViewArray>()
-                {
-                    let new_array = string_view_array.gc();
-                    new_columns.push(Arc::new(new_array));
-                    arr_mutated = true;
-                } else {
-                    new_columns.push(Arc::clone(array));
-                }
-            }
-
-            let organized_batch = if arr_mutated {
-                RecordBatch::try_new(batch.schema(), new_columns)?
-            } else {
-                batch
-            };
-
-            organized_batches.push(organized_batch);
-        }
-
-        *globally_sorted_batches = organized_batches;
-
-        Ok(())
-    }
-
     /// Sorts the in-memory batches and merges them into a single sorted run, then writes
     /// the result to spill files.
     async fn sort_and_spill_in_mem_batches(&mut self) -> Result<()> {
@@ -810,7 +811,10 @@ pub fn sort_batch(
         .map(|expr| expr.evaluate_to_sort_column(batch))
         .collect::<Result<Vec<_>>>()?;
 
-    let indices = lexsort_to_indices(&sort_columns, fetch)?;
+    let indices = match lexsort_low_cardinality(&sort_columns, fetch)? {
+        Some(indices) => indices,
+        None => lexsort_to_indices(&sort_columns, fetch)?,
+    };
     let mut columns = take_arrays(batch.columns(), &indices, None)?;
 
     // The columns may be larger than the unsorted columns in `batch` especially for variable length
@@ -829,6 +833,88 @@ pub fn sort_batch(
     )?)
 }
 
+/// Minimum number of rows to sort with [`lexsort_low_cardinality`]
+const LOW_CARDINALITY_MIN_ROWS: usize = 1024;
+
+/// The leading sort column is considered to have a low cardinality if it has
+/// at most one distinct value per `LOW_CARDINALITY_RATIO` rows
+const LOW_CARDINALITY_RATIO: usize = 16;
+
+/// Sorts `sort_columns` in two levels if the leading column has a low
+/// cardinality: the rows are grouped by the value of the leading column using
+/// a hash table, and only the rows of each group are sorted by the remaining
+/// columns, so that the leading column is compared once per group rather
+/// than once per row comparison.
+///
+/// The rows of each group are sorted with a stable sort, so the result is
+/// the same as the one of a stable lexicographical sort, also with `fetch`.
+///
+/// Returns `None` if the sort does not benefit from this, that is for single
+/// column sorts, small inputs, or leading columns with a high cardinality.
+fn lexsort_low_cardinality(
+    sort_columns: &[SortColumn],
+    fetch: Option<usize>,
+) -> Result<Option<UInt32Array>> {
+    let num_rows = sort_columns.first().map_or(0, |c| c.values.len());
+    if sort_columns.len() < 2 || num_rows < LOW_CARDINALITY_MIN_ROWS {
+        return Ok(None);
+    }
+    let sort_fields = sort_columns
+        .iter()
+        .map(|c| {
+            SortField::new_with_options(
+                c.values.data_type().clone(),
+                c.options.unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<_>>();
+    if !RowConverter::supports_fields(&sort_fields) {
+        return Ok(None);
+    }
+
+    // Group the rows by the leading column, in the row format so that the
+    // groups can be ordered by comparing their keys
+    let leading_rows = RowConverter::new(sort_fields[..1].to_vec())?
+        .convert_columns(&[Arc::clone(&sort_columns[0].values)])?;
+    let max_groups = num_rows / LOW_CARDINALITY_RATIO;
+    let mut groups: HashMap<Row<'_>, Vec<u32>> = HashMap::new();
+    for (index, row) in leading_rows.iter().enumerate() {
+        if let Some(indices) = groups.get_mut(&row) {
+            indices.push(index as u32);
+            continue;
+        }
+        if groups.len() >= max_groups {
+            return Ok(None);
+        }
+        groups.insert(row, vec![index as u32]);
+    }
+    let mut groups = groups.into_iter().collect::<Vec<_>>();
+    groups.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+    let remaining_columns = sort_columns[1..]
+        .iter()
+        .map(|c| Arc::clone(&c.values))
+        .collect::<Vec<_>>();
+    let remaining_rows = RowConverter::new(sort_fields[1..].to_vec())?
+        .convert_columns(&remaining_columns)?;
+
+    let limit = fetch.unwrap_or(num_rows).min(num_rows);
+    let mut indices = Vec::with_capacity(limit);
+    for (_, mut group) in groups {
+        if indices.len() >= limit {
+            break;
+        }
+        group.sort_by(|a, b| {
+            remaining_rows
+                .row(*a as usize)
+                .cmp(&remaining_rows.row(*b as usize))
+        });
+        group.truncate(limit - indices.len());
+        indices.extend(group);
+    }
+    Ok(Some(UInt32Array::from(indices)))
+}
+
 /// Sort execution plan.
 ///
 /// Support sorting datasets that are larger than the memory allotted
@@ -1150,7 +1236,7 @@ impl ExecutionPlan for SortExec {
                     self.expr.clone(),
                     *fetch,
                     context.session_config().batch_size(),
-                    context.runtime_env(),
+                    context.memory_pool(),
                     &self.metrics_set,
                     self.filter.clone(),
                 )?;
@@ -1177,9 +1263,11 @@ impl ExecutionPlan for SortExec {
                     context.session_config().batch_size(),
                     execution_options.sort_spill_reservation_bytes,
                     execution_options.sort_in_place_threshold_bytes,
+                    execution_options.sort_merge_fan_in,
                     context.session_config().spill_compression(),
                     &self.metrics_set,
                     context.runtime_env(),
+                    context.memory_pool(),
                 )?;
                 Ok(Box::pin(RecordBatchStreamAdapter::new(
                     self.schema(),
@@ -1530,6 +1618,143 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_sort_spill_multi_pass_merge() -> Result<()> {
+        // Merging 2 spill files at a time needs several passes
+        let session_config = SessionConfig::new().with_sort_merge_fan_in(2);
+        let sort_spill_reservation_bytes = session_config
+            .options()
+            .execution
+            .sort_spill_reservation_bytes;
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(sort_spill_reservation_bytes + 12288, 1.0)
+            .build_arc()?;
+        let task_ctx = Arc::new(
+            TaskContext::default()
+                .with_session_config(session_config)
+                .with_runtime(runtime),
+        );
+
+        let input = test::scan_partitioned(100);
+        let schema = input.schema();
+        let sort_exec = Arc::new(SortExec::new(
+            [PhysicalSortExpr {
+                expr: col("i", &schema)?,
+                options: SortOptions::default(),
+            }]
+            .into(),
+            Arc::new(CoalescePartitionsExec::new(input)),
+        ));
+
+        let result = collect(
+            Arc::clone(&sort_exec) as Arc<dyn ExecutionPlan>,
+            Arc::clone(&task_ctx),
+        )
+        .await?;
+
+        let metrics = sort_exec.metrics().unwrap();
+        assert_eq!(metrics.output_rows().unwrap(), 10000);
+        let spill_count = metrics.spill_count().unwrap();
+        assert!(spill_count >= 3);
+        // At least one pass merges spill files into new spill files, before
+        // the final merge
+        let merge_passes = metrics.sum_by_name("merge_passes").unwrap().as_usize();
+        assert!(merge_passes >= 2);
+
+        let values = result
+            .iter()
+            .flat_map(|batch| {
+                as_primitive_array::<Int32Type>(batch.column(0))
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values.len(), 10000);
+        assert!(values.is_sorted());
+        assert_eq!(
+            task_ctx.runtime_env().memory_pool.reserved(),
+            0,
+            "The sort should have returned all memory used back to the memory manager"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_low_cardinality_leading_column() -> Result<()> {
+        let num_rows = 5000;
+        let keys = StringArray::from_iter(
+            (0..num_rows).map(|i| (i % 7 != 0).then(|| format!("key{}", (i * 31) % 10))),
+        );
+        let values = Int64Array::from_iter(
+            (0..num_rows).map(|i| (i % 11 != 0).then_some(((i * 17) % 100) as i64)),
+        );
+        let ids = Int64Array::from_iter_values(0..num_rows as i64);
+        let batch = RecordBatch::try_from_iter(vec![
+            ("k", Arc::new(keys) as ArrayRef),
+            ("v", Arc::new(values) as ArrayRef),
+            ("id", Arc::new(ids) as ArrayRef),
+        ])?;
+        let schema = batch.schema();
+
+        for (descending, nulls_first) in
+            [(false, false), (false, true), (true, false), (true, true)]
+        {
+            let options = SortOptions {
+                descending,
+                nulls_first,
+            };
+            let sort_expr = |name| -> Result<PhysicalSortExpr> {
+                Ok(PhysicalSortExpr::new(col(name, &schema)?, options))
+            };
+            let expressions =
+                LexOrdering::new([sort_expr("k")?, sort_expr("v")?]).unwrap();
+            // Sorting by the row index as well gives the result of a stable sort
+            let stable_expressions = LexOrdering::new([
+                sort_expr("k")?,
+                sort_expr("v")?,
+                PhysicalSortExpr::new(col("id", &schema)?, SortOptions::default()),
+            ])
+            .unwrap();
+
+            for fetch in [None, Some(100), Some(num_rows + 1)] {
+                let sort_columns = expressions
+                    .iter()
+                    .map(|expr| expr.evaluate_to_sort_column(&batch))
+                    .collect::<Result<Vec<_>>>()?;
+                assert!(lexsort_low_cardinality(&sort_columns, fetch)?.is_some());
+
+                let sorted = sort_batch(&batch, &expressions, fetch)?;
+                let stable_columns = stable_expressions
+                    .iter()
+                    .map(|expr| expr.evaluate_to_sort_column(&batch))
+                    .collect::<Result<Vec<_>>>()?;
+                let indices = lexsort_to_indices(&stable_columns, fetch)?;
+                let expected = RecordBatch::try_new(
+                    Arc::clone(&schema),
+                    take_arrays(batch.columns(), &indices, None)?,
+                )?;
+                assert_eq!(sorted, expected);
+            }
+        }
+
+        // A leading column with many distinct values is sorted as usual
+        let sort_columns = [
+            SortColumn {
+                values: Arc::clone(batch.column(2)),
+                options: None,
+            },
+            SortColumn {
+                values: Arc::clone(batch.column(1)),
+                options: None,
+            },
+        ];
+        assert!(lexsort_low_cardinality(&sort_columns, None)?.is_none());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_batch_reservation_error() -> Result<()> {
         // Pick a memory limit and sort_spill_reservation that make the first batch reservation fail.
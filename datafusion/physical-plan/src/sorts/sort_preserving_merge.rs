@@ -294,7 +294,7 @@ impl ExecutionPlan for SortPreservingMergeExec {
 
         let reservation =
             MemoryConsumer::new(format!("SortPreservingMergeExec[{partition}]"))
-                .register(&context.runtime_env().memory_pool);
+                .register(context.memory_pool());
 
         match input_partitions {
             0 => internal_err!(
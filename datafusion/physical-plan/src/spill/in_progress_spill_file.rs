@@ -24,7 +24,7 @@ use arrow::array::RecordBatch;
 use datafusion_common::exec_datafusion_err;
 use datafusion_execution::disk_manager::RefCountedTempFile;
 
-use super::{spill_manager::SpillManager, IPCStreamWriter};
+use super::{compact_view_arrays, spill_manager::SpillManager, IPCStreamWriter};
 
 /// Represents an in-progress spill file used for writing `RecordBatch`es to disk, created by `SpillManager`.
 /// Caller is able to use this struct to incrementally append in-memory batches to
@@ -75,7 +75,8 @@ impl InProgressSpillFile {
             }
         }
         if let Some(writer) = &mut self.writer {
-            let (spilled_rows, _) = writer.write(batch)?;
+            let batch = compact_view_arrays(batch)?;
+            let (spilled_rows, spilled_bytes) = writer.write(&batch)?;
             if let Some(in_progress_file) = &mut self.in_progress_file {
                 in_progress_file.update_disk_usage()?;
             } else {
@@ -84,6 +85,10 @@ impl InProgressSpillFile {
 
             // Update metrics
             self.spill_writer.metrics.spilled_rows.add(spilled_rows);
+            self.spill_writer
+                .metrics
+                .spilled_uncompressed_bytes
+                .add(spilled_bytes);
         }
         Ok(())
     }
@@ -28,8 +28,10 @@ use std::ptr::NonNull;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
-use arrow::array::ArrayData;
-use arrow::datatypes::{Schema, SchemaRef};
+use arrow::array::{Array, ArrayData, ArrayRef, AsArray, GenericByteViewArray};
+use arrow::datatypes::{
+    BinaryViewType, ByteViewType, DataType, Schema, SchemaRef, StringViewType,
+};
 use arrow::ipc::{
     reader::StreamReader,
     writer::{IpcWriteOptions, StreamWriter},
@@ -54,6 +56,9 @@ use futures::{FutureExt as _, Stream};
 struct SpillReaderStream {
     schema: SchemaRef,
     state: SpillReaderStreamState,
+    /// The file being read. It is held until the stream is done so that its
+    /// disk usage stays accounted by the disk manager while it is read.
+    spill_file: Option<Arc<RefCountedTempFile>>,
 }
 
 /// When we poll for the next batch, we will get back both the batch and the reader,
@@ -63,7 +68,7 @@ type NextRecordBatchResult = Result<(StreamReader<BufReader<File>>, Option<Recor
 enum SpillReaderStreamState {
     /// Initial state: the stream was not initialized yet
     /// and the file was not opened
-    Uninitialized(RefCountedTempFile),
+    Uninitialized(Arc<RefCountedTempFile>),
 
     /// A read is in progress in a spawned blocking task for which we hold the handle.
     ReadInProgress(SpawnedTask<NextRecordBatchResult>),
@@ -76,10 +81,11 @@ enum SpillReaderStreamState {
 }
 
 impl SpillReaderStream {
-    fn new(schema: SchemaRef, spill_file: RefCountedTempFile) -> Self {
+    fn new(schema: SchemaRef, spill_file: Arc<RefCountedTempFile>) -> Self {
         Self {
             schema,
             state: SpillReaderStreamState::Uninitialized(spill_file),
+            spill_file: None,
         }
     }
 
@@ -95,6 +101,7 @@ impl SpillReaderStream {
                 else {
                     unreachable!()
                 };
+                self.spill_file = Some(Arc::clone(&spill_file));
 
                 let task = SpawnedTask::spawn_blocking(move || {
                     let file = BufReader::new(File::open(spill_file.path())?);
@@ -132,6 +139,7 @@ impl SpillReaderStream {
                             None => {
                                 // Stream is done
                                 self.state = SpillReaderStreamState::Done;
+                                self.spill_file = None;
 
                                 Poll::Ready(None)
                             }
@@ -139,6 +147,7 @@ impl SpillReaderStream {
                     }
                     Err(err) => {
                         self.state = SpillReaderStreamState::Done;
+                        self.spill_file = None;
 
                         Poll::Ready(Some(Err(err)))
                     }
@@ -281,6 +290,57 @@ fn count_array_data_memory_size(
     }
 }
 
+/// Returns `batch` with the payload buffers of its `StringViewArray`s and
+/// `BinaryViewArray`s compacted to the values of the array, by calling `gc()`
+/// on the arrays that reference more payload than they use.
+///
+/// Spilled batches are often slices of a larger batch, or the output of
+/// `take`/`interleave` over many batches, and so share payload buffers with
+/// each other. The IPC writer writes all buffers referenced by each batch,
+/// so spilling such arrays as they are writes the shared payload repeatedly,
+/// while the compacted arrays keep the view encoding at the size of their
+/// values.
+pub(crate) fn compact_view_arrays(batch: &RecordBatch) -> Result<RecordBatch> {
+    let mut compacted = false;
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|array| {
+            let new_array = match array.data_type() {
+                DataType::Utf8View => {
+                    compact_view_array(array.as_byte_view::<StringViewType>())
+                }
+                DataType::BinaryView => {
+                    compact_view_array(array.as_byte_view::<BinaryViewType>())
+                }
+                _ => None,
+            };
+            compacted |= new_array.is_some();
+            new_array.unwrap_or_else(|| Arc::clone(array))
+        })
+        .collect();
+
+    if !compacted {
+        return Ok(batch.clone());
+    }
+    Ok(RecordBatch::try_new(batch.schema(), columns)?)
+}
+
+/// Returns the compacted `array`, if its payload buffers are larger than its
+/// values that are not inlined in the views
+fn compact_view_array<T: ByteViewType + ?Sized>(
+    array: &GenericByteViewArray<T>,
+) -> Option<ArrayRef> {
+    let buffers_size: usize = array.data_buffers().iter().map(|b| b.len()).sum();
+    let used_size: usize = array
+        .views()
+        .iter()
+        .map(|view| *view as u32 as usize)
+        .filter(|len| *len > 12)
+        .sum();
+    (buffers_size > used_size).then(|| Arc::new(array.gc()) as ArrayRef)
+}
+
 /// Write in Arrow IPC Stream format to a file.
 ///
 /// Stream format is used for spill because it supports dictionary replacement, and the random
@@ -350,8 +410,10 @@ mod tests {
     use crate::metrics::SpillMetrics;
     use crate::spill::spill_manager::SpillManager;
     use crate::test::build_table_i32;
-    use arrow::array::{ArrayRef, Float64Array, Int32Array, ListArray, StringArray};
-    use arrow::compute::cast;
+    use arrow::array::{
+        ArrayRef, Float64Array, Int32Array, ListArray, StringArray, StringViewArray,
+    };
+    use arrow::compute::{cast, concat_batches};
     use arrow::datatypes::{DataType, Field, Int32Type, Schema};
     use arrow::record_batch::RecordBatch;
     use datafusion_common::Result;
@@ -488,6 +550,42 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_batch_spill_by_size_compacts_view_arrays() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "a",
+            DataType::Utf8View,
+            false,
+        )]));
+        let values: ArrayRef = Arc::new(StringViewArray::from_iter_values(
+            (0..1000).map(|i| format!("a string too long to be inlined {i:08}")),
+        ));
+        let batch = RecordBatch::try_new(Arc::clone(&schema), vec![values])?;
+
+        let env = Arc::new(RuntimeEnv::default());
+        let metrics = SpillMetrics::new(&ExecutionPlanMetricsSet::new(), 0);
+        let spill_manager = SpillManager::new(env, metrics, Arc::clone(&schema));
+
+        let spill_file = spill_manager
+            .spill_record_batch_by_size(&batch, "Test Spill", 100)?
+            .unwrap();
+
+        // each of the 10 slices only writes its own values, not the payload
+        // shared by all slices
+        let spilled_bytes = spill_manager.metrics.spilled_bytes.value();
+        assert!(spilled_bytes < 2 * get_record_batch_memory_size(&batch));
+        assert!(spill_manager.metrics.spilled_uncompressed_bytes.value() > 0);
+
+        let stream = spill_manager.read_spill_as_stream(spill_file)?;
+        assert_eq!(stream.schema(), schema);
+
+        let batches = collect(stream).await?;
+        assert_eq!(batches.len(), 10);
+        assert_eq!(concat_batches(&schema, &batches)?, batch);
+
+        Ok(())
+    }
+
     fn build_compressible_batch() -> RecordBatch {
         let schema = Arc::new(Schema::new(vec![
             Field::new("a", DataType::Utf8, false),
@@ -131,10 +131,19 @@ impl SpillManager {
     pub fn read_spill_as_stream(
         &self,
         spill_file_path: RefCountedTempFile,
+    ) -> Result<SendableRecordBatchStream> {
+        self.read_shared_spill_as_stream(Arc::new(spill_file_path))
+    }
+
+    /// Reads a spill file that may be read by several streams concurrently.
+    /// See [`Self::read_spill_as_stream`].
+    pub fn read_shared_spill_as_stream(
+        &self,
+        spill_file: Arc<RefCountedTempFile>,
     ) -> Result<SendableRecordBatchStream> {
         let stream = Box::pin(cooperative(SpillReaderStream::new(
             Arc::clone(&self.schema),
-            spill_file_path,
+            spill_file,
         )));
 
         Ok(spawn_buffered(stream, self.batch_read_buffer_capacity))
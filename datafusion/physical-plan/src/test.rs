@@ -29,6 +29,7 @@ use crate::common;
 use crate::execution_plan::{Boundedness, EmissionType};
 use crate::memory::MemoryStream;
 use crate::metrics::MetricsSet;
+use crate::shared::batches_identity;
 use crate::stream::RecordBatchStreamAdapter;
 use crate::streaming::PartitionStream;
 use crate::ExecutionPlan;
@@ -131,7 +132,7 @@ impl ExecutionPlan for TestMemoryExec {
     }
 
     fn as_any(&self) -> &dyn Any {
-        unimplemented!()
+        self
     }
 
     fn properties(&self) -> &PlanProperties {
@@ -184,6 +185,10 @@ impl ExecutionPlan for TestMemoryExec {
     fn fetch(&self) -> Option<usize> {
         self.fetch
     }
+
+    fn output_identity(&self) -> Option<String> {
+        Some(batches_identity(&self.partitions))
+    }
 }
 
 impl TestMemoryExec {
@@ -0,0 +1,606 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Grouped TopK: the first K rows of each group, as defined by an ordering
+
+use std::collections::BinaryHeap;
+use std::mem::size_of;
+use std::sync::Arc;
+
+use super::{build_sort_fields, RecordBatchStore, TopKRow};
+use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, SpillMetrics};
+use crate::sorts::streaming_merge::StreamingMergeBuilder;
+use crate::spill::spill_manager::SpillManager;
+use crate::stream::RecordBatchStreamAdapter;
+use crate::SendableRecordBatchStream;
+
+use arrow::array::{ArrayRef, BooleanArray, RecordBatch, UInt64Array};
+use arrow::compute::{filter_record_batch, interleave_record_batch};
+use arrow::datatypes::SchemaRef;
+use arrow::row::{OwnedRow, RowConverter, Rows, SortField};
+use datafusion_common::config::SpillCompression;
+use datafusion_common::{HashMap, Result};
+use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation};
+use datafusion_execution::runtime_env::RuntimeEnv;
+use datafusion_physical_expr::PhysicalExpr;
+use datafusion_physical_expr_common::sort_expr::LexOrdering;
+use futures::TryStreamExt;
+use hashbrown::hash_map::EntryRef;
+
+/// Grouped TopK
+///
+/// Keeps the first `k` rows of each group of rows with the same values of
+/// the `partition_by` expressions, according to the sort expressions `expr`,
+/// for example to find the latest 3 readings of each sensor.
+///
+/// `expr` must start with sort expressions on the `partition_by` expressions,
+/// so that the rows of each group are contiguous in the output, which is
+/// sorted according to `expr`. Each output row is numbered within its
+/// group, starting at 1, like with the `ROW_NUMBER` window function.
+///
+/// # Structure
+///
+/// Each group holds at most `k` rows in a heap, keyed by the [arrow::row]
+/// format of the sort keys. Like [`TopK`](super::TopK), the heaps only hold
+/// references into the input batches, which are compacted once they hold too
+/// many rows that are not among the top rows of any group.
+///
+/// # Spilling
+///
+/// When the memory reservation for the heaps cannot grow, the rows of every
+/// group are spilled to disk as a sorted run, and the heaps are cleared. The
+/// runs are merged when emitting the output, keeping the first `k` rows of
+/// each group.
+pub(crate) struct GroupedTopK {
+    /// schema of the input
+    schema: SchemaRef,
+    /// schema of the output: the input and the row numbers
+    output_schema: SchemaRef,
+    /// Runtime metrics
+    metrics: BaselineMetrics,
+    /// Reservation for the heaps and the batches they refer to
+    reservation: MemoryReservation,
+    /// The target number of rows for output batches
+    batch_size: usize,
+    /// expressions whose values define the groups
+    partition_by: Vec<Arc<dyn PhysicalExpr>>,
+    /// sort expressions
+    expr: LexOrdering,
+    /// number of rows to keep per group
+    k: usize,
+    /// row converter, for group keys
+    group_converter: RowConverter,
+    /// row converter, for sort keys
+    row_converter: RowConverter,
+    /// the top rows of each group, by group key
+    groups: HashMap<Vec<u8>, BinaryHeap<TopKRow>>,
+    /// storage of the batches the top rows refer to
+    store: RecordBatchStore,
+    /// number of rows held by all groups
+    num_rows: usize,
+    /// size of the group keys and rows held by all groups
+    owned_bytes: usize,
+    /// Manages the process of spilling and reading back intermediate data
+    spill_manager: SpillManager,
+    /// sorted runs of spilled rows
+    spills: Vec<RefCountedTempFile>,
+}
+
+impl GroupedTopK {
+    /// Create a new [`GroupedTopK`] that stores the first `k` rows of each
+    /// group defined by `partition_by`, as defined by the sort expressions
+    /// in `expr`, numbering them in the `output_schema`'s last column.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn try_new(
+        partition_id: usize,
+        schema: SchemaRef,
+        output_schema: SchemaRef,
+        partition_by: Vec<Arc<dyn PhysicalExpr>>,
+        expr: LexOrdering,
+        k: usize,
+        batch_size: usize,
+        spill_compression: SpillCompression,
+        runtime: Arc<RuntimeEnv>,
+        // The pool of the task, limiting the memory of the query if configured
+        memory_pool: &Arc<dyn MemoryPool>,
+        metrics: &ExecutionPlanMetricsSet,
+    ) -> Result<Self> {
+        assert!(k > 0);
+        let reservation = MemoryConsumer::new(format!("GroupedTopK[{partition_id}]"))
+            .with_can_spill(true)
+            .register(memory_pool);
+        let group_converter = group_converter(&partition_by, &schema)?;
+        let row_converter = RowConverter::new(build_sort_fields(&expr, &schema)?)?;
+        let spill_manager = SpillManager::new(
+            runtime,
+            SpillMetrics::new(metrics, partition_id),
+            Arc::clone(&schema),
+        )
+        .with_compression_type(spill_compression);
+
+        Ok(Self {
+            schema,
+            output_schema,
+            metrics: BaselineMetrics::new(metrics, partition_id),
+            reservation,
+            batch_size,
+            partition_by,
+            expr,
+            k,
+            group_converter,
+            row_converter,
+            groups: HashMap::new(),
+            store: RecordBatchStore::new(),
+            num_rows: 0,
+            owned_bytes: 0,
+            spill_manager,
+            spills: vec![],
+        })
+    }
+
+    /// Insert `batch`, remembering the rows that are among the top k rows
+    /// of their group seen so far.
+    pub(crate) fn insert_batch(&mut self, batch: RecordBatch) -> Result<()> {
+        // Updates on drop
+        let baseline = self.metrics.clone();
+        let _timer = baseline.elapsed_compute().timer();
+
+        let group_rows = convert_rows(&self.group_converter, &self.partition_by, &batch)?;
+        let sort_keys = self
+            .expr
+            .iter()
+            .map(|expr| expr.expr.evaluate(&batch)?.into_array(batch.num_rows()))
+            .collect::<Result<Vec<_>>>()?;
+        let rows = self.row_converter.convert_columns(&sort_keys)?;
+
+        let mut batch_entry = self.store.register(batch);
+        for (index, (group, row)) in group_rows.iter().zip(rows.iter()).enumerate() {
+            let heap = match self.groups.entry_ref(group.as_ref()) {
+                EntryRef::Occupied(entry) => entry.into_mut(),
+                EntryRef::Vacant(entry) => {
+                    self.owned_bytes += group.as_ref().len();
+                    entry.insert(BinaryHeap::new())
+                }
+            };
+
+            if heap.len() < self.k {
+                let new_row = TopKRow::new(row, batch_entry.id, index);
+                self.owned_bytes += size_of::<TopKRow>() + new_row.owned_size();
+                self.num_rows += 1;
+                batch_entry.uses += 1;
+                heap.push(new_row);
+            } else if row.as_ref() < heap.peek().expect("k > 0").row() {
+                // replace the last row of the group, reusing its allocation
+                let prev_last = heap.pop().expect("k > 0");
+                if prev_last.batch_id == batch_entry.id {
+                    batch_entry.uses -= 1;
+                } else {
+                    self.store.unuse(prev_last.batch_id);
+                }
+                self.owned_bytes -= prev_last.owned_size();
+                let new_row = prev_last.with_new_row(row, batch_entry.id, index);
+                self.owned_bytes += new_row.owned_size();
+                batch_entry.uses += 1;
+                heap.push(new_row);
+            }
+        }
+        self.store.insert(batch_entry);
+
+        // conserve memory
+        self.maybe_compact()?;
+
+        // update memory reservation, spilling the rows if it cannot grow
+        if self.reservation.try_resize(self.size()).is_err() {
+            self.spill()?;
+            self.reservation.try_resize(self.size())?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the top k rows of each group, sorted and numbered, broken
+    /// into `batch_size` [`RecordBatch`]es
+    pub(crate) fn emit(mut self) -> Result<SendableRecordBatchStream> {
+        let sorted = if self.spills.is_empty() {
+            let baseline = self.metrics.clone();
+            let _timer = baseline.elapsed_compute().timer(); // time updated on drop
+            let batches = self
+                .take_sorted_rows()?
+                .map(|batch| split_batch(batch, self.batch_size))
+                .unwrap_or_default();
+            Box::pin(RecordBatchStreamAdapter::new(
+                Arc::clone(&self.schema),
+                futures::stream::iter(batches.into_iter().map(Ok)),
+            ))
+        } else {
+            // spill the last rows as well, rather than holding them while
+            // merging the runs
+            self.spill()?;
+            self.reservation.free();
+            let streams = std::mem::take(&mut self.spills)
+                .into_iter()
+                .map(|spill| self.spill_manager.read_spill_as_stream(spill))
+                .collect::<Result<Vec<_>>>()?;
+            StreamingMergeBuilder::new()
+                .with_streams(streams)
+                .with_schema(Arc::clone(&self.schema))
+                .with_expressions(&self.expr)
+                .with_metrics(self.metrics.intermediate())
+                .with_batch_size(self.batch_size)
+                .with_fetch(None)
+                .with_reservation(self.reservation.new_empty())
+                .build()?
+        };
+
+        let mut numbering = RowNumbering {
+            schema: Arc::clone(&self.output_schema),
+            partition_by: self.partition_by,
+            group_converter: self.group_converter,
+            k: self.k,
+            last_group: None,
+            number: 0,
+            metrics: self.metrics,
+        };
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.output_schema),
+            sorted.and_then(move |batch| futures::future::ready(numbering.number(batch))),
+        )))
+    }
+
+    /// Writes the rows of every group to disk as a sorted run, and clears
+    /// the groups
+    fn spill(&mut self) -> Result<()> {
+        let Some(batch) = self.take_sorted_rows()? else {
+            return Ok(());
+        };
+        if let Some(spill) = self.spill_manager.spill_record_batch_by_size(
+            &batch,
+            "GroupedTopK",
+            self.batch_size,
+        )? {
+            self.spills.push(spill);
+        }
+        Ok(())
+    }
+
+    /// Returns the rows of every group, sorted according to the sort
+    /// expressions, as a single [`RecordBatch`], and clears the groups
+    fn take_sorted_rows(&mut self) -> Result<Option<RecordBatch>> {
+        let mut rows = std::mem::take(&mut self.groups)
+            .into_values()
+            .flat_map(BinaryHeap::into_vec)
+            .collect::<Vec<_>>();
+        rows.sort_unstable();
+        let batch = self.interleave(&rows)?;
+        self.store.clear();
+        self.num_rows = 0;
+        self.owned_bytes = 0;
+        Ok(batch)
+    }
+
+    /// Compacts the stored batches into a single batch holding the rows of
+    /// every group, if the stored batches hold too many other rows
+    fn maybe_compact(&mut self) -> Result<()> {
+        // like `TopKHeap::maybe_compact`, target holding up to around 20
+        // batches besides the rows of the groups
+        let max_unused_rows = (20 * self.batch_size) + self.num_rows;
+        if self.store.len() <= 2 || self.store.unused_rows() < max_unused_rows {
+            return Ok(());
+        }
+
+        let groups = std::mem::take(&mut self.groups)
+            .into_iter()
+            .map(|(group, heap)| (group, heap.into_vec()))
+            .collect::<Vec<_>>();
+        let batch = self.interleave(groups.iter().flat_map(|(_, rows)| rows))?;
+        let Some(batch) = batch else {
+            return Ok(());
+        };
+
+        // clear all old entries in store (this invalidates all
+        // store_ids in the groups)
+        self.store.clear();
+        let mut batch_entry = self.store.register(batch);
+        batch_entry.uses = self.num_rows;
+
+        // rewrite all rows to refer to the new batch, in which they are
+        // in the order of `groups`
+        let mut index = 0;
+        for (group, mut rows) in groups {
+            for row in rows.iter_mut() {
+                row.batch_id = batch_entry.id;
+                row.index = index;
+                index += 1;
+            }
+            self.groups.insert(group, BinaryHeap::from(rows));
+        }
+        self.store.insert(batch_entry);
+
+        Ok(())
+    }
+
+    /// Returns a single [`RecordBatch`] holding the stored rows `rows`, in
+    /// order
+    fn interleave<'a>(
+        &self,
+        rows: impl IntoIterator<Item = &'a TopKRow>,
+    ) -> Result<Option<RecordBatch>> {
+        let (record_batches, indices): (Vec<_>, Vec<_>) = rows
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let entry = self
+                    .store
+                    .get(row.batch_id)
+                    .expect("invalid stored batch id");
+                (&entry.batch, (i, row.index))
+            })
+            .unzip();
+        if record_batches.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(interleave_record_batch(&record_batches, &indices)?))
+    }
+
+    /// return the size of memory used by this operator, in bytes
+    fn size(&self) -> usize {
+        size_of::<Self>()
+            + self.group_converter.size()
+            + self.row_converter.size()
+            + self.groups.capacity()
+                * (size_of::<Vec<u8>>() + size_of::<BinaryHeap<TopKRow>>())
+            + self.store.size()
+            + self.owned_bytes
+    }
+}
+
+/// Numbers the rows of each group of a stream sorted by group, and
+/// discards the rows after the first `k` of each group
+struct RowNumbering {
+    /// schema of the output: the input and the row numbers
+    schema: SchemaRef,
+    /// expressions whose values define the groups
+    partition_by: Vec<Arc<dyn PhysicalExpr>>,
+    /// row converter, for group keys
+    group_converter: RowConverter,
+    /// number of rows to keep per group
+    k: usize,
+    /// group of the last numbered row
+    last_group: Option<OwnedRow>,
+    /// number of the last numbered row
+    number: u64,
+    /// Runtime metrics
+    metrics: BaselineMetrics,
+}
+
+impl RowNumbering {
+    fn number(&mut self, batch: RecordBatch) -> Result<RecordBatch> {
+        let _timer = self.metrics.elapsed_compute().timer();
+        let group_rows = convert_rows(&self.group_converter, &self.partition_by, &batch)?;
+
+        let mut keep = Vec::with_capacity(batch.num_rows());
+        let mut numbers = Vec::with_capacity(batch.num_rows());
+        for group in group_rows.iter() {
+            if self
+                .last_group
+                .as_ref()
+                .is_none_or(|last_group| last_group.row() != group)
+            {
+                self.last_group = Some(group.owned());
+                self.number = 0;
+            }
+            self.number += 1;
+            let is_top = self.number <= self.k as u64;
+            keep.push(is_top);
+            if is_top {
+                numbers.push(self.number);
+            }
+        }
+
+        let batch = if numbers.len() == batch.num_rows() {
+            batch
+        } else {
+            filter_record_batch(&batch, &BooleanArray::from(keep))?
+        };
+        let mut columns = batch.columns().to_vec();
+        columns.push(Arc::new(UInt64Array::from(numbers)) as ArrayRef);
+        let batch = RecordBatch::try_new(Arc::clone(&self.schema), columns)?;
+        self.metrics.record_output(batch.num_rows());
+        Ok(batch)
+    }
+}
+
+/// Returns a [`RowConverter`] for the values of `exprs`, only used to find
+/// equal values
+fn group_converter(
+    exprs: &[Arc<dyn PhysicalExpr>],
+    schema: &SchemaRef,
+) -> Result<RowConverter> {
+    let fields = exprs
+        .iter()
+        .map(|expr| Ok(SortField::new(expr.data_type(schema)?)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(RowConverter::new(fields)?)
+}
+
+/// Evaluates `exprs` on `batch` and converts their values with `converter`
+fn convert_rows(
+    converter: &RowConverter,
+    exprs: &[Arc<dyn PhysicalExpr>],
+    batch: &RecordBatch,
+) -> Result<Rows> {
+    let columns = exprs
+        .iter()
+        .map(|expr| expr.evaluate(batch)?.into_array(batch.num_rows()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(converter.convert_columns(&columns)?)
+}
+
+/// Splits `batch` into batches of at most `batch_size` rows
+fn split_batch(batch: RecordBatch, batch_size: usize) -> Vec<RecordBatch> {
+    (0..batch.num_rows())
+        .step_by(batch_size)
+        .map(|offset| batch.slice(offset, batch_size.min(batch.num_rows() - offset)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow_schema::SortOptions;
+    use datafusion_common::assert_batches_eq;
+    use datafusion_execution::memory_pool::FairSpillPool;
+    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+    use datafusion_physical_expr::expressions::col;
+    use datafusion_physical_expr_common::sort_expr::PhysicalSortExpr;
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("key", DataType::Utf8, true),
+            Field::new("ts", DataType::Int32, false),
+        ]))
+    }
+
+    fn output_schema() -> SchemaRef {
+        let mut fields = schema().fields().to_vec();
+        fields.push(Arc::new(Field::new("rn", DataType::UInt64, false)));
+        Arc::new(Schema::new(fields))
+    }
+
+    fn batch(keys: Vec<Option<&str>>, ts: Vec<i32>) -> RecordBatch {
+        RecordBatch::try_new(
+            schema(),
+            vec![
+                Arc::new(StringArray::from(keys)),
+                Arc::new(Int32Array::from(ts)),
+            ],
+        )
+        .unwrap()
+    }
+
+    /// Latest `k` rows per key
+    fn grouped_topk(
+        k: usize,
+        batch_size: usize,
+        runtime: Arc<RuntimeEnv>,
+        metrics: &ExecutionPlanMetricsSet,
+    ) -> Result<GroupedTopK> {
+        let schema = schema();
+        let key = col("key", &schema)?;
+        let expr = LexOrdering::from([
+            PhysicalSortExpr::new(Arc::clone(&key), SortOptions::default()),
+            PhysicalSortExpr::new(col("ts", &schema)?, SortOptions::new(true, false)),
+        ]);
+        GroupedTopK::try_new(
+            0,
+            schema,
+            output_schema(),
+            vec![key],
+            expr,
+            k,
+            batch_size,
+            SpillCompression::Uncompressed,
+            Arc::clone(&runtime),
+            &runtime.memory_pool,
+            metrics,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_grouped_topk() -> Result<()> {
+        let metrics = ExecutionPlanMetricsSet::new();
+        let mut topk = grouped_topk(2, 3, Arc::new(RuntimeEnv::default()), &metrics)?;
+        topk.insert_batch(batch(
+            vec![Some("a"), Some("b"), Some("a"), None, Some("a")],
+            vec![1, 2, 3, 4, 5],
+        ))?;
+        topk.insert_batch(batch(
+            vec![Some("c"), Some("a"), Some("b"), None],
+            vec![6, 4, 1, 3],
+        ))?;
+
+        let results: Vec<_> = topk.emit()?.try_collect().await?;
+        assert_batches_eq!(
+            &[
+                "+-----+----+----+",
+                "| key | ts | rn |",
+                "+-----+----+----+",
+                "|     | 4  | 1  |",
+                "|     | 3  | 2  |",
+                "| a   | 5  | 1  |",
+                "| a   | 4  | 2  |",
+                "| b   | 2  | 1  |",
+                "| b   | 1  | 2  |",
+                "| c   | 6  | 1  |",
+                "+-----+----+----+",
+            ],
+            &results
+        );
+        assert!(results.iter().all(|batch| batch.num_rows() <= 3));
+        assert!(metrics
+            .clone_inner()
+            .spill_count()
+            .is_none_or(|count| count == 0));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_grouped_topk_spill() -> Result<()> {
+        // 100 groups of 3 rows do not fit in the memory pool
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_pool(Arc::new(FairSpillPool::new(24 * 1024)))
+            .build_arc()?;
+        let metrics = ExecutionPlanMetricsSet::new();
+        let mut topk = grouped_topk(3, 64, Arc::clone(&runtime), &metrics)?;
+
+        let keys = (0..100)
+            .map(|key| format!("key{key:03}"))
+            .collect::<Vec<_>>();
+        for ts in 0..20 {
+            topk.insert_batch(batch(
+                keys.iter().map(|key| Some(key.as_str())).collect(),
+                vec![ts; keys.len()],
+            ))?;
+        }
+        let results: Vec<_> = topk.emit()?.try_collect().await?;
+        assert!(metrics.clone_inner().spill_count().unwrap() > 0);
+        assert_eq!(runtime.memory_pool.reserved(), 0);
+
+        // the three latest rows of each key, in order
+        let expected = keys
+            .iter()
+            .flat_map(|key| (1..=3).map(move |rn| (key.clone(), 20 - rn, rn as u64)))
+            .collect::<Vec<_>>();
+        let actual = results
+            .iter()
+            .flat_map(|batch| {
+                let keys = batch.column(0).as_any().downcast_ref::<StringArray>();
+                let ts = batch.column(1).as_any().downcast_ref::<Int32Array>();
+                let rn = batch.column(2).as_any().downcast_ref::<UInt64Array>();
+                let (keys, ts, rn) = (keys.unwrap(), ts.unwrap(), rn.unwrap());
+                (0..batch.num_rows())
+                    .map(|i| (keys.value(i).to_string(), ts.value(i), rn.value(i)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+}
@@ -17,6 +17,10 @@
 
 //! TopK: Combination of Sort / LIMIT
 
+mod grouped;
+
+pub(crate) use grouped::GroupedTopK;
+
 use arrow::{
     array::{Array, AsArray},
     compute::{interleave_record_batch, prep_null_mask_filter, FilterBuilder},
@@ -35,10 +39,7 @@ use arrow::datatypes::SchemaRef;
 use datafusion_common::{
     internal_datafusion_err, internal_err, HashMap, Result, ScalarValue,
 };
-use datafusion_execution::{
-    memory_pool::{MemoryConsumer, MemoryReservation},
-    runtime_env::RuntimeEnv,
-};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation};
 use datafusion_physical_expr::{
     expressions::{is_not_null, is_null, lit, BinaryExpr, DynamicFilterPhysicalExpr},
     PhysicalExpr,
@@ -158,12 +159,12 @@ impl TopK {
         expr: LexOrdering,
         k: usize,
         batch_size: usize,
-        runtime: Arc<RuntimeEnv>,
+        memory_pool: &Arc<dyn MemoryPool>,
         metrics: &ExecutionPlanMetricsSet,
         filter: Option<Arc<DynamicFilterPhysicalExpr>>,
     ) -> Result<Self> {
-        let reservation = MemoryConsumer::new(format!("TopK[{partition_id}]"))
-            .register(&runtime.memory_pool);
+        let reservation =
+            MemoryConsumer::new(format!("TopK[{partition_id}]")).register(memory_pool);
 
         let sort_fields = build_sort_fields(&expr, &schema)?;
 
@@ -977,6 +978,7 @@ mod tests {
     use arrow::datatypes::{DataType, Field, Schema};
     use arrow_schema::SortOptions;
     use datafusion_common::assert_batches_eq;
+    use datafusion_execution::runtime_env::RuntimeEnv;
     use datafusion_physical_expr::expressions::col;
     use futures::TryStreamExt;
 
@@ -1051,7 +1053,7 @@ mod tests {
             full_expr,
             3,
             2,
-            runtime,
+            &runtime.memory_pool,
             &metrics,
             None,
         )?;
@@ -988,6 +988,9 @@ impl TryFrom<&protobuf::ParquetOptions> for ParquetOptions {
                 protobuf::parquet_options::CoerceInt96Opt::CoerceInt96(v) => Some(v),
             }).unwrap_or(None),
             skip_arrow_metadata: value.skip_arrow_metadata,
+            data_page_version: value.data_page_version_opt.clone().map(|opt| match opt {
+                protobuf::parquet_options::DataPageVersionOpt::DataPageVersion(v) => Some(v),
+            }).unwrap_or(None),
         })
     }
 }
@@ -5040,6 +5040,9 @@ impl serde::Serialize for ParquetOptions {
         if self.coerce_int96_opt.is_some() {
             len += 1;
         }
+        if self.data_page_version_opt.is_some() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion_common.ParquetOptions", len)?;
         if self.enable_page_index {
             struct_ser.serialize_field("enablePageIndex", &self.enable_page_index)?;
@@ -5202,6 +5205,13 @@ impl serde::Serialize for ParquetOptions {
                 }
             }
         }
+        if let Some(v) = self.data_page_version_opt.as_ref() {
+            match v {
+                parquet_options::DataPageVersionOpt::DataPageVersion(v) => {
+                    struct_ser.serialize_field("dataPageVersion", v)?;
+                }
+            }
+        }
         struct_ser.end()
     }
 }
@@ -5271,6 +5281,8 @@ impl<'de> serde::Deserialize<'de> for ParquetOptions {
             "bloomFilterNdv",
             "coerce_int96",
             "coerceInt96",
+            "data_page_version",
+            "dataPageVersion",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -5306,6 +5318,7 @@ impl<'de> serde::Deserialize<'de> for ParquetOptions {
             BloomFilterFpp,
             BloomFilterNdv,
             CoerceInt96,
+            DataPageVersion,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -5358,6 +5371,7 @@ impl<'de> serde::Deserialize<'de> for ParquetOptions {
                             "bloomFilterFpp" | "bloom_filter_fpp" => Ok(GeneratedField::BloomFilterFpp),
                             "bloomFilterNdv" | "bloom_filter_ndv" => Ok(GeneratedField::BloomFilterNdv),
                             "coerceInt96" | "coerce_int96" => Ok(GeneratedField::CoerceInt96),
+                            "dataPageVersion" | "data_page_version" => Ok(GeneratedField::DataPageVersion),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -5408,6 +5422,7 @@ impl<'de> serde::Deserialize<'de> for ParquetOptions {
                 let mut bloom_filter_fpp_opt__ = None;
                 let mut bloom_filter_ndv_opt__ = None;
                 let mut coerce_int96_opt__ = None;
+                let mut data_page_version_opt__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::EnablePageIndex => {
@@ -5610,6 +5625,12 @@ impl<'de> serde::Deserialize<'de> for ParquetOptions {
                             }
                             coerce_int96_opt__ = map_.next_value::<::std::option::Option<_>>()?.map(parquet_options::CoerceInt96Opt::CoerceInt96);
                         }
+                        GeneratedField::DataPageVersion => {
+                            if data_page_version_opt__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("dataPageVersion"));
+                            }
+                            data_page_version_opt__ = map_.next_value::<::std::option::Option<_>>()?.map(parquet_options::DataPageVersionOpt::DataPageVersion);
+                        }
                     }
                 }
                 Ok(ParquetOptions {
@@ -5644,6 +5665,7 @@ impl<'de> serde::Deserialize<'de> for ParquetOptions {
                     bloom_filter_fpp_opt: bloom_filter_fpp_opt__,
                     bloom_filter_ndv_opt: bloom_filter_ndv_opt__,
                     coerce_int96_opt: coerce_int96_opt__,
+                    data_page_version_opt: data_page_version_opt__,
                 })
             }
         }
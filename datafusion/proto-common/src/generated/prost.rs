@@ -804,6 +804,10 @@ pub struct ParquetOptions {
     pub bloom_filter_ndv_opt: ::core::option::Option<parquet_options::BloomFilterNdvOpt>,
     #[prost(oneof = "parquet_options::CoerceInt96Opt", tags = "32")]
     pub coerce_int96_opt: ::core::option::Option<parquet_options::CoerceInt96Opt>,
+    #[prost(oneof = "parquet_options::DataPageVersionOpt", tags = "33")]
+    pub data_page_version_opt: ::core::option::Option<
+        parquet_options::DataPageVersionOpt,
+    >,
 }
 /// Nested message and enum types in `ParquetOptions`.
 pub mod parquet_options {
@@ -862,6 +866,11 @@ pub mod parquet_options {
         #[prost(string, tag = "32")]
         CoerceInt96(::prost::alloc::string::String),
     }
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum DataPageVersionOpt {
+        #[prost(string, tag = "33")]
+        DataPageVersion(::prost::alloc::string::String),
+    }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Precision {
@@ -414,6 +414,9 @@ impl TableParquetOptionsProto {
                 coerce_int96_opt: global_options.global.coerce_int96.map(|compression| {
                     parquet_options::CoerceInt96Opt::CoerceInt96(compression)
                 }),
+                data_page_version_opt: global_options.global.data_page_version.map(|version| {
+                    parquet_options::DataPageVersionOpt::DataPageVersion(version)
+                }),
             }),
             column_specific_options: column_specific_options.into_iter().map(|(column_name, options)| {
                 ParquetColumnSpecificOptions {
@@ -513,6 +516,9 @@ impl From<&ParquetOptionsProto> for ParquetOptions {
             coerce_int96: proto.coerce_int96_opt.as_ref().map(|opt| match opt {
                 parquet_options::CoerceInt96Opt::CoerceInt96(coerce_int96) => coerce_int96.clone(),
             }),
+            data_page_version: proto.data_page_version_opt.as_ref().map(|opt| match opt {
+                parquet_options::DataPageVersionOpt::DataPageVersion(version) => version.clone(),
+            }),
         }
     }
 }
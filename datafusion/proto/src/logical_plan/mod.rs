@@ -71,8 +71,8 @@ use datafusion_expr::{
     Statement, WindowUDF,
 };
 use datafusion_expr::{
-    AggregateUDF, ColumnUnnestList, DmlStatement, FetchType, RecursiveQuery, SkipType,
-    TableSource, Unnest,
+    AggregateUDF, AnalyzeFormat, ColumnUnnestList, DmlStatement, ExplainOption,
+    FetchType, RecursiveQuery, SkipType, TableSource, Unnest,
 };
 
 use self::to_proto::{serialize_expr, serialize_exprs};
@@ -691,8 +691,17 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlanType::Analyze(analyze) => {
                 let input: LogicalPlan =
                     into_logical_plan!(analyze.input, ctx, extension_codec)?;
+                let analyze_format = match analyze.format.as_str() {
+                    "" => AnalyzeFormat::default(),
+                    format => format.parse()?,
+                };
                 LogicalPlanBuilder::from(input)
-                    .explain(analyze.verbose, true)?
+                    .explain_option_format(
+                        ExplainOption::default()
+                            .with_verbose(analyze.verbose)
+                            .with_analyze(true)
+                            .with_analyze_format(analyze_format),
+                    )?
                     .build()
             }
             LogicalPlanType::Explain(explain) => {
@@ -1590,6 +1599,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                         protobuf::AnalyzeNode {
                             input: Some(Box::new(input)),
                             verbose: a.verbose,
+                            format: a.format.to_string(),
                         },
                     ))),
                 })
@@ -1726,12 +1736,18 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlan::Ddl(DdlStatement::DropCatalogSchema(_)) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for DropCatalogSchema",
             )),
+            LogicalPlan::Ddl(DdlStatement::DropCatalog(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for DropCatalog",
+            )),
             LogicalPlan::Ddl(DdlStatement::CreateFunction(_)) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for CreateFunction",
             )),
             LogicalPlan::Ddl(DdlStatement::DropFunction(_)) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for DropFunction",
             )),
+            LogicalPlan::Ddl(DdlStatement::CommentOn(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for CommentOn",
+            )),
             LogicalPlan::Statement(_) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for Statement",
             )),
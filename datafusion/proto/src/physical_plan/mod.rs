@@ -88,7 +88,7 @@ use datafusion::physical_plan::{
 };
 use datafusion_common::config::TableParquetOptions;
 use datafusion_common::{internal_err, not_impl_err, DataFusionError, Result};
-use datafusion_expr::{AggregateUDF, ScalarUDF, WindowUDF};
+use datafusion_expr::{AggregateUDF, AnalyzeFormat, ScalarUDF, WindowUDF};
 
 use prost::bytes::BufMut;
 use prost::Message;
@@ -331,6 +331,13 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
                     runtime,
                     extension_codec,
                 ),
+            PhysicalPlanType::TableProviderScan(scan) => self
+                .try_into_table_provider_scan_physical_plan(
+                    scan,
+                    registry,
+                    runtime,
+                    extension_codec,
+                ),
         }
     }
 
@@ -528,6 +535,19 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
         }
 
         let mut buf: Vec<u8> = vec![];
+        if let Some(provider_name) =
+            extension_codec.try_encode_table_provider_scan(&plan_clone, &mut buf)?
+        {
+            return Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::TableProviderScan(
+                    protobuf::TableProviderScanExecNode {
+                        provider_name,
+                        scan: buf,
+                    },
+                )),
+            });
+        }
+
         match extension_codec.try_encode(Arc::clone(&plan_clone), &mut buf) {
             Ok(_) => {
                 let inputs: Vec<protobuf::PhysicalPlanNode> = plan_clone
@@ -1640,12 +1660,19 @@ impl protobuf::PhysicalPlanNode {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let input: Arc<dyn ExecutionPlan> =
             into_physical_plan(&analyze.input, registry, runtime, extension_codec)?;
-        Ok(Arc::new(AnalyzeExec::new(
-            analyze.verbose,
-            analyze.show_statistics,
-            input,
-            Arc::new(convert_required!(analyze.schema)?),
-        )))
+        let format = match analyze.format.as_str() {
+            "" => AnalyzeFormat::default(),
+            format => format.parse()?,
+        };
+        Ok(Arc::new(
+            AnalyzeExec::new(
+                analyze.verbose,
+                analyze.show_statistics,
+                input,
+                Arc::new(convert_required!(analyze.schema)?),
+            )
+            .with_format(format),
+        ))
     }
 
     fn try_into_json_sink_physical_plan(
@@ -1806,6 +1833,20 @@ impl protobuf::PhysicalPlanNode {
         Ok(Arc::new(CooperativeExec::new(input)))
     }
 
+    fn try_into_table_provider_scan_physical_plan(
+        &self,
+        scan: &protobuf::TableProviderScanExecNode,
+        registry: &dyn FunctionRegistry,
+        _runtime: &RuntimeEnv,
+        extension_codec: &dyn PhysicalExtensionCodec,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        extension_codec.try_decode_table_provider_scan(
+            &scan.provider_name,
+            &scan.scan,
+            registry,
+        )
+    }
+
     fn try_from_explain_exec(
         exec: &ExplainExec,
         _extension_codec: &dyn PhysicalExtensionCodec,
@@ -1865,6 +1906,7 @@ impl protobuf::PhysicalPlanNode {
                     show_statistics: exec.show_statistics(),
                     input: Some(Box::new(input)),
                     schema: Some(exec.schema().as_ref().try_into()?),
+                    format: exec.format().to_string(),
                 },
             ))),
         })
@@ -2883,6 +2925,37 @@ pub trait PhysicalExtensionCodec: Debug + Send + Sync {
     fn try_encode_udwf(&self, _node: &WindowUDF, _buf: &mut Vec<u8>) -> Result<()> {
         Ok(())
     }
+
+    /// Decodes a scan of a custom [`TableProvider`] encoded by
+    /// [`Self::try_encode_table_provider_scan`] under `provider_name`.
+    ///
+    /// [`TableProvider`]: datafusion::datasource::TableProvider
+    fn try_decode_table_provider_scan(
+        &self,
+        provider_name: &str,
+        _buf: &[u8],
+        _registry: &dyn FunctionRegistry,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!(
+            "PhysicalExtensionCodec is not provided for scans of table provider {provider_name}"
+        )
+    }
+
+    /// Encodes `node` if it is the scan of a custom [`TableProvider`], such
+    /// as one returned by a user defined table function, writing its state
+    /// to `buf` and returning the name of the provider.
+    ///
+    /// Returns `None` for any other plan, which is then encoded by
+    /// [`Self::try_encode`].
+    ///
+    /// [`TableProvider`]: datafusion::datasource::TableProvider
+    fn try_encode_table_provider_scan(
+        &self,
+        _node: &Arc<dyn ExecutionPlan>,
+        _buf: &mut Vec<u8>,
+    ) -> Result<Option<String>> {
+        Ok(None)
+    }
 }
 
 #[derive(Debug)]
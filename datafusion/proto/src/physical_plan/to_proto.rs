@@ -35,7 +35,7 @@ use datafusion::{
     datasource::{
         file_format::{csv::CsvSink, json::JsonSink},
         listing::{FileRange, PartitionedFile},
-        physical_plan::{FileScanConfig, FileSinkConfig},
+        physical_plan::{FileOutputMode, FileScanConfig, FileSinkConfig},
     },
     physical_plan::expressions::LikeExpr,
 };
@@ -615,6 +615,11 @@ impl TryFrom<&FileSinkConfig> for protobuf::FileSinkConfig {
             keep_partition_by_columns: conf.keep_partition_by_columns,
             insert_op: conf.insert_op as i32,
             file_extension: conf.file_extension.to_string(),
+            file_output_mode: match conf.file_output_mode {
+                FileOutputMode::Automatic => protobuf::FileOutputMode::Automatic,
+                FileOutputMode::SingleFile => protobuf::FileOutputMode::SingleFile,
+                FileOutputMode::Directory => protobuf::FileOutputMode::Directory,
+            } as i32,
         })
     }
 }
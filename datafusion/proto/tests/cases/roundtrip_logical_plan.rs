@@ -74,10 +74,10 @@ use datafusion_expr::expr::{
 };
 use datafusion_expr::logical_plan::{Extension, UserDefinedLogicalNodeCore};
 use datafusion_expr::{
-    Accumulator, AggregateUDF, ColumnarValue, ExprFunctionExt, ExprSchemable, Literal,
-    LogicalPlan, Operator, PartitionEvaluator, ScalarUDF, Signature, TryCast, Volatility,
-    WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunctionDefinition, WindowUDF,
-    WindowUDFImpl,
+    Accumulator, AggregateUDF, AnalyzeFormat, ColumnarValue, ExprFunctionExt,
+    ExprSchemable, Literal, LogicalPlan, Operator, PartitionEvaluator, ScalarUDF,
+    Signature, TryCast, Volatility, WindowFrame, WindowFrameBound, WindowFrameUnits,
+    WindowFunctionDefinition, WindowUDF, WindowUDFImpl,
 };
 use datafusion_functions_aggregate::average::avg_udaf;
 use datafusion_functions_aggregate::expr_fn::{
@@ -155,6 +155,24 @@ async fn roundtrip_logical_plan() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn roundtrip_explain_analyze_json() -> Result<()> {
+    let ctx = SessionContext::new();
+    let plan = ctx
+        .sql("EXPLAIN (ANALYZE, FORMAT JSON) SELECT 1")
+        .await?
+        .into_unoptimized_plan();
+    let LogicalPlan::Analyze(analyze) = &plan else {
+        panic!("Expected Analyze, got {plan:?}");
+    };
+    assert_eq!(analyze.format, AnalyzeFormat::Json);
+
+    let bytes = logical_plan_to_bytes(&plan)?;
+    let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+    assert_eq!(format!("{plan:?}"), format!("{logical_round_trip:?}"));
+    Ok(())
+}
+
 #[derive(Clone, PartialEq, Eq, ::prost::Message)]
 pub struct TestTableProto {
     /// URL of the table root
@@ -459,6 +477,7 @@ async fn roundtrip_logical_plan_copy_to_writer_options() -> Result<()> {
     parquet_format.global.bloom_filter_on_read = true;
     parquet_format.global.created_by = "DataFusion Test".to_string();
     parquet_format.global.writer_version = "PARQUET_2_0".to_string();
+    parquet_format.global.data_page_version = Some("2.0".to_string());
     parquet_format.global.write_batch_size = 111;
     parquet_format.global.data_pagesize_limit = 222;
     parquet_format.global.data_page_row_count_limit = 333;
@@ -47,7 +47,7 @@ use datafusion::datasource::file_format::parquet::ParquetSink;
 use datafusion::datasource::listing::{ListingTableUrl, PartitionedFile};
 use datafusion::datasource::object_store::ObjectStoreUrl;
 use datafusion::datasource::physical_plan::{
-    wrap_partition_type_in_dict, wrap_partition_value_in_dict, FileGroup,
+    wrap_partition_type_in_dict, wrap_partition_value_in_dict, FileGroup, FileOutputMode,
     FileScanConfigBuilder, FileSinkConfig, FileSource, ParquetSource,
 };
 use datafusion::datasource::sink::DataSinkExec;
@@ -56,7 +56,9 @@ use datafusion::execution::FunctionRegistry;
 use datafusion::functions_aggregate::sum::sum_udaf;
 use datafusion::functions_window::nth_value::nth_value_udwf;
 use datafusion::functions_window::row_number::row_number_udwf;
-use datafusion::logical_expr::{create_udf, JoinType, Operator, Volatility};
+use datafusion::logical_expr::{
+    create_udf, AnalyzeFormat, JoinType, Operator, Volatility,
+};
 use datafusion::physical_expr::expressions::Literal;
 use datafusion::physical_expr::window::{SlidingAggregateWindowExpr, StandardWindowExpr};
 use datafusion::physical_expr::{
@@ -1301,9 +1303,13 @@ fn roundtrip_analyze() -> Result<()> {
     roundtrip_test(Arc::new(AnalyzeExec::new(
         false,
         false,
-        input,
-        Arc::new(schema),
-    )))
+        Arc::clone(&input) as _,
+        Arc::new(schema.clone()),
+    )))?;
+    roundtrip_test(Arc::new(
+        AnalyzeExec::new(false, false, input, Arc::new(schema))
+            .with_format(AnalyzeFormat::Json),
+    ))
 }
 
 #[tokio::test]
@@ -1332,6 +1338,7 @@ fn roundtrip_json_sink() -> Result<()> {
         insert_op: InsertOp::Overwrite,
         keep_partition_by_columns: true,
         file_extension: "json".into(),
+        file_output_mode: FileOutputMode::SingleFile,
     };
     let data_sink = Arc::new(JsonSink::new(
         file_sink_config,
@@ -1370,6 +1377,7 @@ fn roundtrip_csv_sink() -> Result<()> {
         insert_op: InsertOp::Overwrite,
         keep_partition_by_columns: true,
         file_extension: "csv".into(),
+        file_output_mode: FileOutputMode::Directory,
     };
     let data_sink = Arc::new(CsvSink::new(
         file_sink_config,
@@ -1427,6 +1435,7 @@ fn roundtrip_parquet_sink() -> Result<()> {
         insert_op: InsertOp::Overwrite,
         keep_partition_by_columns: true,
         file_extension: "parquet".into(),
+        file_output_mode: FileOutputMode::Automatic,
     };
     let data_sink = Arc::new(ParquetSink::new(
         file_sink_config,
@@ -1736,3 +1745,275 @@ async fn roundtrip_physical_plan_node() {
 
     let _ = plan.execute(0, ctx.task_ctx()).unwrap();
 }
+
+/// Scans of custom table providers, here one returned by a user defined
+/// table function, are encoded by the extension codec under the provider
+/// name, and user defined window functions by name to be decoded from the
+/// registry.
+#[tokio::test]
+async fn roundtrip_udwf_over_udtf_scan() -> Result<()> {
+    use arrow::array::{Int64Array, UInt64Array};
+    use arrow::datatypes::{FieldRef, SchemaRef};
+    use arrow::util::pretty::pretty_format_batches;
+    use datafusion::catalog::{Session, TableFunctionImpl, TableProvider};
+    use datafusion::datasource::TableType;
+    use datafusion::execution::{SendableRecordBatchStream, TaskContext};
+    use datafusion::physical_expr::EquivalenceProperties;
+    use datafusion::physical_plan::execution_plan::{Boundedness, EmissionType};
+    use datafusion::physical_plan::memory::MemoryStream;
+    use datafusion::physical_plan::{
+        collect, DisplayAs, DisplayFormatType, PlanProperties,
+    };
+    use datafusion_common::{exec_err, plan_err, DataFusionError, ScalarValue};
+    use datafusion_expr::{
+        Expr, PartitionEvaluator, Signature, Volatility, WindowUDFImpl,
+    };
+    use datafusion_functions_window_common::field::WindowUDFFieldArgs;
+    use datafusion_functions_window_common::partition::PartitionEvaluatorArgs;
+
+    /// Numbers each row of its partition from 1
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    struct RowIndex {
+        signature: Signature,
+    }
+
+    impl WindowUDFImpl for RowIndex {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn name(&self) -> &str {
+            "row_index"
+        }
+
+        fn signature(&self) -> &Signature {
+            &self.signature
+        }
+
+        fn partition_evaluator(
+            &self,
+            _partition_evaluator_args: PartitionEvaluatorArgs,
+        ) -> Result<Box<dyn PartitionEvaluator>> {
+            Ok(Box::new(RowIndexEvaluator))
+        }
+
+        fn field(&self, field_args: WindowUDFFieldArgs) -> Result<FieldRef> {
+            Ok(Field::new(field_args.name(), DataType::UInt64, false).into())
+        }
+    }
+
+    #[derive(Debug)]
+    struct RowIndexEvaluator;
+
+    impl PartitionEvaluator for RowIndexEvaluator {
+        fn evaluate_all(
+            &mut self,
+            _values: &[ArrayRef],
+            num_rows: usize,
+        ) -> Result<ArrayRef> {
+            Ok(Arc::new(UInt64Array::from_iter_values(1..=num_rows as u64)))
+        }
+    }
+
+    /// `numbers(n)` returns the numbers `0..n` in a column `x`
+    #[derive(Debug)]
+    struct NumbersFunc;
+
+    impl TableFunctionImpl for NumbersFunc {
+        fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+            let [Expr::Literal(ScalarValue::Int64(Some(n)), _)] = args else {
+                return plan_err!("numbers expects a single integer argument");
+            };
+            Ok(Arc::new(NumbersTable { n: *n }))
+        }
+    }
+
+    fn numbers_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![Field::new("x", DataType::Int64, false)]))
+    }
+
+    #[derive(Debug)]
+    struct NumbersTable {
+        n: i64,
+    }
+
+    #[async_trait::async_trait]
+    impl TableProvider for NumbersTable {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            numbers_schema()
+        }
+
+        fn table_type(&self) -> TableType {
+            TableType::Temporary
+        }
+
+        async fn scan(
+            &self,
+            _state: &dyn Session,
+            _projection: Option<&Vec<usize>>,
+            _filters: &[Expr],
+            _limit: Option<usize>,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            Ok(Arc::new(NumbersExec::new(self.n)))
+        }
+    }
+
+    #[derive(Debug)]
+    struct NumbersExec {
+        n: i64,
+        properties: PlanProperties,
+    }
+
+    impl NumbersExec {
+        fn new(n: i64) -> Self {
+            let properties = PlanProperties::new(
+                EquivalenceProperties::new(numbers_schema()),
+                Partitioning::UnknownPartitioning(1),
+                EmissionType::Incremental,
+                Boundedness::Bounded,
+            );
+            Self { n, properties }
+        }
+    }
+
+    impl DisplayAs for NumbersExec {
+        fn fmt_as(&self, _t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
+            write!(f, "NumbersExec: n={}", self.n)
+        }
+    }
+
+    impl ExecutionPlan for NumbersExec {
+        fn name(&self) -> &str {
+            "NumbersExec"
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn properties(&self) -> &PlanProperties {
+            &self.properties
+        }
+
+        fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+            vec![]
+        }
+
+        fn with_new_children(
+            self: Arc<Self>,
+            _children: Vec<Arc<dyn ExecutionPlan>>,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            Ok(self)
+        }
+
+        fn execute(
+            &self,
+            partition: usize,
+            _context: Arc<TaskContext>,
+        ) -> Result<SendableRecordBatchStream> {
+            if partition != 0 {
+                return exec_err!("NumbersExec has a single partition");
+            }
+            let batch = RecordBatch::try_new(
+                numbers_schema(),
+                vec![Arc::new(Int64Array::from_iter_values(0..self.n))],
+            )?;
+            Ok(Box::pin(MemoryStream::try_new(
+                vec![batch],
+                numbers_schema(),
+                None,
+            )?))
+        }
+    }
+
+    #[derive(Debug)]
+    struct NumbersCodec;
+
+    impl PhysicalExtensionCodec for NumbersCodec {
+        fn try_decode(
+            &self,
+            _buf: &[u8],
+            _inputs: &[Arc<dyn ExecutionPlan>],
+            _registry: &dyn FunctionRegistry,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            not_impl_err!("No extension codec provided")
+        }
+
+        fn try_encode(
+            &self,
+            _node: Arc<dyn ExecutionPlan>,
+            _buf: &mut Vec<u8>,
+        ) -> Result<()> {
+            not_impl_err!("No extension codec provided")
+        }
+
+        fn try_decode_table_provider_scan(
+            &self,
+            provider_name: &str,
+            buf: &[u8],
+            _registry: &dyn FunctionRegistry,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            if provider_name != "numbers" {
+                return not_impl_err!("unrecognized table provider {provider_name}");
+            }
+            let n = buf.try_into().map(i64::from_le_bytes).map_err(|err| {
+                DataFusionError::Internal(format!("failed to decode numbers: {err}"))
+            })?;
+            Ok(Arc::new(NumbersExec::new(n)))
+        }
+
+        fn try_encode_table_provider_scan(
+            &self,
+            node: &Arc<dyn ExecutionPlan>,
+            buf: &mut Vec<u8>,
+        ) -> Result<Option<String>> {
+            let Some(exec) = node.as_any().downcast_ref::<NumbersExec>() else {
+                return Ok(None);
+            };
+            buf.extend_from_slice(&exec.n.to_le_bytes());
+            Ok(Some("numbers".to_string()))
+        }
+    }
+
+    let ctx = SessionContext::new();
+    ctx.register_udtf("numbers", Arc::new(NumbersFunc));
+    ctx.register_udwf(WindowUDF::from(RowIndex {
+        signature: Signature::nullary(Volatility::Immutable),
+    }));
+
+    let sql = "SELECT x, row_index() OVER (PARTITION BY x % 2 ORDER BY x DESC) AS idx \
+        FROM numbers(5) ORDER BY x";
+    let plan = ctx.sql(sql).await?.create_physical_plan().await?;
+    let result_plan = roundtrip_test_and_return(plan.clone(), &ctx, &NumbersCodec)?;
+    let expected = pretty_format_batches(&collect(plan, ctx.task_ctx()).await?)?;
+    let result = pretty_format_batches(&collect(result_plan, ctx.task_ctx()).await?)?;
+    assert_eq!(expected.to_string(), result.to_string());
+    assert_eq!(
+        expected.to_string(),
+        [
+            "+---+-----+",
+            "| x | idx |",
+            "+---+-----+",
+            "| 0 | 3   |",
+            "| 1 | 2   |",
+            "| 2 | 2   |",
+            "| 3 | 1   |",
+            "| 4 | 1   |",
+            "+---+-----+",
+        ]
+        .join("\n")
+    );
+
+    // Without a codec for the provider the scan can not be encoded
+    let plan = ctx.sql(sql).await?.create_physical_plan().await?;
+    let err =
+        PhysicalPlanNode::try_from_physical_plan(plan, &DefaultPhysicalExtensionCodec {})
+            .unwrap_err();
+    assert!(err.to_string().contains("NumbersExec"), "{err}");
+
+    Ok(())
+}
@@ -58,7 +58,10 @@ fn parse_file_type(s: &str) -> Result<String, DataFusionError> {
 /// Syntax:
 /// ```sql
 /// EXPLAIN <ANALYZE> <VERBOSE> [FORMAT format] statement
+/// EXPLAIN ( option [, ...] ) statement
 ///```
+///
+/// where `option` is one of `ANALYZE`, `VERBOSE` or `FORMAT format`
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExplainStatement {
     /// `EXPLAIN ANALYZE ..`
@@ -183,6 +186,63 @@ impl fmt::Display for CopyToSource {
 /// This type defines a lexicographical ordering.
 pub(crate) type LexOrdering = Vec<OrderByExpr>;
 
+/// DataFusion extension DDL for `CREATE CATALOG`
+///
+/// Syntax:
+///
+/// ```text
+/// CREATE CATALOG [ IF NOT EXISTS ] <catalog_name>
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateCatalogStatement {
+    /// Catalog name
+    pub name: ObjectName,
+    /// Option to not error if catalog already exists
+    pub if_not_exists: bool,
+}
+
+impl fmt::Display for CreateCatalogStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CREATE CATALOG ")?;
+        if self.if_not_exists {
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        write!(f, "{}", self.name)
+    }
+}
+
+/// DataFusion extension DDL for `DROP CATALOG`
+///
+/// Syntax:
+///
+/// ```text
+/// DROP CATALOG [ IF EXISTS ] <catalog_name> [ CASCADE | RESTRICT ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropCatalogStatement {
+    /// Catalog name
+    pub name: ObjectName,
+    /// Option to not error if catalog does not exist
+    pub if_exists: bool,
+    /// Whether the schemas of the catalog, and their tables, are dropped
+    /// too, rather than failing if there are any
+    pub cascade: bool,
+}
+
+impl fmt::Display for DropCatalogStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DROP CATALOG ")?;
+        if self.if_exists {
+            write!(f, "IF EXISTS ")?;
+        }
+        write!(f, "{}", self.name)?;
+        if self.cascade {
+            write!(f, " CASCADE")?;
+        }
+        Ok(())
+    }
+}
+
 /// DataFusion extension DDL for `CREATE EXTERNAL TABLE`
 ///
 /// Syntax:
@@ -233,13 +293,62 @@ pub struct CreateExternalTable {
 
 impl fmt::Display for CreateExternalTable {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "CREATE EXTERNAL TABLE ")?;
+        write!(f, "CREATE ")?;
+        if self.unbounded {
+            write!(f, "UNBOUNDED ")?;
+        }
+        write!(f, "EXTERNAL ")?;
+        if self.temporary {
+            write!(f, "TEMPORARY ")?;
+        }
+        write!(f, "TABLE ")?;
         if self.if_not_exists {
             write!(f, "IF NOT EXISTS ")?;
         }
-        write!(f, "{} ", self.name)?;
-        write!(f, "STORED AS {} ", self.file_type)?;
-        write!(f, "LOCATION {} ", self.location)
+        write!(f, "{}", self.name)?;
+
+        // Partition columns declared with a type are parsed into `columns`,
+        // print them back in the `PARTITIONED BY` clause
+        let (partition_columns, columns): (Vec<_>, Vec<_>) = self
+            .columns
+            .iter()
+            .partition(|column| self.table_partition_cols.contains(&column.name.value));
+        let elements: Vec<_> = columns
+            .iter()
+            .map(|column| column.to_string())
+            .chain(self.constraints.iter().map(|c| c.to_string()))
+            .collect();
+        if !elements.is_empty() {
+            write!(f, " ({})", elements.join(", "))?;
+        }
+        write!(f, " STORED AS {}", self.file_type)?;
+        if !self.table_partition_cols.is_empty() {
+            let partitions: Vec<_> = self
+                .table_partition_cols
+                .iter()
+                .map(|name| {
+                    partition_columns
+                        .iter()
+                        .find(|column| &column.name.value == name)
+                        .map_or_else(|| name.clone(), |column| column.to_string())
+                })
+                .collect();
+            write!(f, " PARTITIONED BY ({})", partitions.join(", "))?;
+        }
+        for order in &self.order_exprs {
+            let exprs: Vec<_> = order.iter().map(|expr| expr.to_string()).collect();
+            write!(f, " WITH ORDER ({})", exprs.join(", "))?;
+        }
+        write!(f, " LOCATION '{}'", self.location.replace('\'', "''"))?;
+        if !self.options.is_empty() {
+            let opts: Vec<_> = self
+                .options
+                .iter()
+                .map(|(k, v)| format!("'{k}' {v}"))
+                .collect();
+            write!(f, " OPTIONS ({})", opts.join(", "))?;
+        }
+        Ok(())
     }
 }
 
@@ -256,6 +365,10 @@ pub enum Statement {
     Statement(Box<SQLStatement>),
     /// Extension: `CREATE EXTERNAL TABLE`
     CreateExternalTable(CreateExternalTable),
+    /// Extension: `CREATE CATALOG`
+    CreateCatalog(CreateCatalogStatement),
+    /// Extension: `DROP CATALOG`
+    DropCatalog(DropCatalogStatement),
     /// Extension: `COPY TO`
     CopyTo(CopyToStatement),
     /// EXPLAIN for extensions
@@ -267,6 +380,8 @@ impl fmt::Display for Statement {
         match self {
             Statement::Statement(stmt) => write!(f, "{stmt}"),
             Statement::CreateExternalTable(stmt) => write!(f, "{stmt}"),
+            Statement::CreateCatalog(stmt) => write!(f, "{stmt}"),
+            Statement::DropCatalog(stmt) => write!(f, "{stmt}"),
             Statement::CopyTo(stmt) => write!(f, "{stmt}"),
             Statement::Explain(stmt) => write!(f, "{stmt}"),
         }
@@ -488,6 +603,16 @@ impl<'a> DFParser<'a> {
                         self.parser.next_token(); // EXPLAIN
                         self.parse_explain()
                     }
+                    Keyword::DROP
+                        if matches!(
+                            self.parser.peek_nth_token(1).token,
+                            Token::Word(w) if w.keyword == Keyword::CATALOG
+                        ) =>
+                    {
+                        self.parser.next_token(); // DROP
+                        self.parser.next_token(); // CATALOG
+                        self.parse_drop_catalog()
+                    }
                     _ => {
                         // use sqlparser-rs parser
                         self.parse_and_handle_statement()
@@ -665,9 +790,19 @@ impl<'a> DFParser<'a> {
 
     /// Parse a SQL `EXPLAIN`
     pub fn parse_explain(&mut self) -> Result<Statement, DataFusionError> {
-        let analyze = self.parser.parse_keyword(Keyword::ANALYZE);
-        let verbose = self.parser.parse_keyword(Keyword::VERBOSE);
-        let format = self.parse_explain_format()?;
+        let has_options = self.parser.peek_token().token == Token::LParen
+            && matches!(
+                self.parser.peek_nth_token(1).token,
+                Token::Word(w) if matches!(w.keyword, Keyword::ANALYZE | Keyword::VERBOSE | Keyword::FORMAT)
+            );
+        let (analyze, verbose, format) = if has_options {
+            self.parser.next_token(); // (
+            self.parse_explain_options()?
+        } else {
+            let analyze = self.parser.parse_keyword(Keyword::ANALYZE);
+            let verbose = self.parser.parse_keyword(Keyword::VERBOSE);
+            (analyze, verbose, self.parse_explain_format()?)
+        };
 
         let statement = self.parse_statement()?;
 
@@ -679,6 +814,34 @@ impl<'a> DFParser<'a> {
         }))
     }
 
+    /// Parse the options of `EXPLAIN ( option [, ...] )`, after the `(`,
+    /// returning whether `ANALYZE` and `VERBOSE` were set and the format
+    fn parse_explain_options(
+        &mut self,
+    ) -> Result<(bool, bool, Option<String>), DataFusionError> {
+        let mut analyze = false;
+        let mut verbose = false;
+        let mut format = None;
+        loop {
+            if self.parser.parse_keyword(Keyword::ANALYZE) {
+                analyze = true;
+            } else if self.parser.parse_keyword(Keyword::VERBOSE) {
+                verbose = true;
+            } else if let Some(explain_format) = self.parse_explain_format()? {
+                format = Some(explain_format);
+            } else {
+                let token = self.parser.next_token();
+                return self
+                    .expected("an explain option such as ANALYZE or FORMAT", token);
+            }
+            if !self.parser.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        self.parser.expect_token(&Token::RParen)?;
+        Ok((analyze, verbose, format))
+    }
+
     pub fn parse_explain_format(&mut self) -> Result<Option<String>, DataFusionError> {
         if !self.parser.parse_keyword(Keyword::FORMAT) {
             return Ok(None);
@@ -694,18 +857,51 @@ impl<'a> DFParser<'a> {
         Ok(Some(format))
     }
 
-    /// Parse a SQL `CREATE` statement handling `CREATE EXTERNAL TABLE`
+    /// Parse a SQL `CREATE` statement handling `CREATE EXTERNAL TABLE` and
+    /// `CREATE CATALOG`
     pub fn parse_create(&mut self) -> Result<Statement, DataFusionError> {
         if self.parser.parse_keyword(Keyword::EXTERNAL) {
             self.parse_create_external_table(false)
         } else if self.parser.parse_keyword(Keyword::UNBOUNDED) {
             self.parser.expect_keyword(Keyword::EXTERNAL)?;
             self.parse_create_external_table(true)
+        } else if self.parser.parse_keyword(Keyword::CATALOG) {
+            self.parse_create_catalog()
         } else {
             Ok(Statement::Statement(Box::from(self.parser.parse_create()?)))
         }
     }
 
+    /// Parse a SQL `CREATE CATALOG` statement, after `CREATE CATALOG`
+    fn parse_create_catalog(&mut self) -> Result<Statement, DataFusionError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_object_name(false)?;
+        Ok(Statement::CreateCatalog(CreateCatalogStatement {
+            name,
+            if_not_exists,
+        }))
+    }
+
+    /// Parse a SQL `DROP CATALOG` statement, after `DROP CATALOG`
+    fn parse_drop_catalog(&mut self) -> Result<Statement, DataFusionError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parser.parse_object_name(false)?;
+        let cascade = match self
+            .parser
+            .parse_one_of_keywords(&[Keyword::CASCADE, Keyword::RESTRICT])
+        {
+            Some(Keyword::CASCADE) => true,
+            Some(_) | None => false,
+        };
+        Ok(Statement::DropCatalog(DropCatalogStatement {
+            name,
+            if_exists,
+            cascade,
+        }))
+    }
+
     fn parse_partitions(&mut self) -> Result<Vec<String>, DataFusionError> {
         let mut partitions: Vec<String> = vec![];
         if !self.parser.consume_token(&Token::LParen)
@@ -1539,6 +1735,57 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn create_external_table_display() {
+        verified_stmt("CREATE EXTERNAL TABLE t STORED AS CSV LOCATION 'foo.csv'");
+        verified_stmt(
+            "CREATE UNBOUNDED EXTERNAL TABLE IF NOT EXISTS t (c1 INT NOT NULL, c2 VARCHAR) \
+             STORED AS CSV WITH ORDER (c1 DESC NULLS FIRST) LOCATION 'foo.csv' \
+             OPTIONS ('format.has_header' 'true')",
+        );
+        // Partition columns declared with a type are printed in PARTITIONED BY
+        one_statement_parses_to(
+            "CREATE EXTERNAL TABLE t(c1 int, p1 int) STORED AS PARQUET \
+             PARTITIONED BY (p1) LOCATION 'foo'",
+            "CREATE EXTERNAL TABLE t (c1 INT) STORED AS PARQUET \
+             PARTITIONED BY (p1 INT) LOCATION 'foo'",
+        );
+        verified_stmt(
+            "CREATE EXTERNAL TABLE t STORED AS PARQUET PARTITIONED BY (p1, p2) \
+             LOCATION 'it''s here'",
+        );
+    }
+
+    #[test]
+    fn create_and_drop_catalog() -> Result<(), DataFusionError> {
+        let expected = Statement::CreateCatalog(CreateCatalogStatement {
+            name: ObjectName::from(vec![Ident::new("analytics")]),
+            if_not_exists: true,
+        });
+        expect_parse_ok("CREATE CATALOG IF NOT EXISTS analytics", expected)?;
+        verified_stmt("CREATE CATALOG analytics");
+
+        let expected = Statement::DropCatalog(DropCatalogStatement {
+            name: ObjectName::from(vec![Ident::new("analytics")]),
+            if_exists: true,
+            cascade: true,
+        });
+        expect_parse_ok("DROP CATALOG IF EXISTS analytics CASCADE", expected)?;
+        verified_stmt("DROP CATALOG analytics");
+        one_statement_parses_to(
+            "DROP CATALOG analytics RESTRICT",
+            "DROP CATALOG analytics",
+        );
+
+        // Other DROP statements are parsed by sqlparser
+        assert!(matches!(
+            &DFParser::parse_sql("DROP SCHEMA s CASCADE")?[0],
+            Statement::Statement(_)
+        ));
+        expect_parse_error("CREATE CATALOG a.b.c d", "Expected: end of statement");
+        Ok(())
+    }
+
     #[test]
     fn copy_to_table_to_table() -> Result<(), DataFusionError> {
         // positive case
@@ -1613,6 +1860,49 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn explain_options() -> Result<(), DataFusionError> {
+        let cases = vec![
+            ("EXPLAIN (ANALYZE) SELECT 1", true, false, None),
+            ("EXPLAIN (VERBOSE) SELECT 1", false, true, None),
+            (
+                "EXPLAIN (ANALYZE, FORMAT JSON) SELECT 1",
+                true,
+                false,
+                Some("JSON"),
+            ),
+            ("EXPLAIN (FORMAT tree) SELECT 1", false, false, Some("tree")),
+        ];
+        for (sql, analyze, verbose, format) in cases {
+            let Statement::Explain(explain) =
+                DFParser::parse_sql(sql)?.pop_front().unwrap()
+            else {
+                panic!("Expected EXPLAIN for {sql}");
+            };
+            assert_eq!(explain.analyze, analyze, "{sql}");
+            assert_eq!(explain.verbose, verbose, "{sql}");
+            assert_eq!(explain.format.as_deref(), format, "{sql}");
+            assert_eq!(explain.statement.to_string(), "SELECT 1");
+        }
+
+        // A parenthesized query is not a list of options
+        let Statement::Explain(explain) = DFParser::parse_sql("EXPLAIN (SELECT 1)")?
+            .pop_front()
+            .unwrap()
+        else {
+            panic!("Expected EXPLAIN");
+        };
+        assert!(!explain.analyze);
+        assert_eq!(explain.statement.to_string(), "(SELECT 1)");
+
+        expect_parse_error(
+            "EXPLAIN (ANALYZE, COSTS) SELECT 1",
+            "Expected: an explain option such as ANALYZE or FORMAT, found: COSTS",
+        );
+        expect_parse_error("EXPLAIN (ANALYZE SELECT 1", "Expected: ), found: SELECT");
+        Ok(())
+    }
+
     #[test]
     fn copy_to_query_to_table() -> Result<(), DataFusionError> {
         let statement = verified_stmt("SELECT 1");
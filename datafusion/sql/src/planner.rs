@@ -31,7 +31,7 @@ use datafusion_common::{
 use datafusion_common::{not_impl_err, plan_err, DFSchema, DataFusionError, Result};
 use datafusion_expr::logical_plan::{LogicalPlan, LogicalPlanBuilder};
 use datafusion_expr::utils::find_column_exprs;
-use datafusion_expr::{col, Expr};
+use datafusion_expr::{col, Expr, COMMENT_METADATA_KEY};
 use sqlparser::ast::{ArrayElemTypeDef, ExactNumberInfo, TimezoneInfo};
 use sqlparser::ast::{ColumnDef as SQLColumnDef, ColumnOption};
 use sqlparser::ast::{DataType as SQLDataType, Ident, ObjectName, TableAlias};
@@ -371,11 +371,24 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 .options
                 .iter()
                 .any(|x| x.option == ColumnOption::NotNull);
-            fields.push(Field::new(
-                self.ident_normalizer.normalize(column.name),
-                data_type,
-                !not_nullable,
-            ));
+            let metadata = column
+                .options
+                .iter()
+                .filter_map(|x| match &x.option {
+                    ColumnOption::Comment(comment) => {
+                        Some((COMMENT_METADATA_KEY.to_string(), comment.clone()))
+                    }
+                    _ => None,
+                })
+                .collect();
+            fields.push(
+                Field::new(
+                    self.ident_normalizer.normalize(column.name),
+                    data_type,
+                    !not_nullable,
+                )
+                .with_metadata(metadata),
+            );
         }
 
         Ok(Schema::new(fields))
@@ -419,6 +432,62 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         Ok(column_defaults)
     }
 
+    /// Returns a vector of (column_name, generation_expr) pairs for the
+    /// `GENERATED ALWAYS AS (expr)` columns, whose expressions refer to the
+    /// other columns of `schema`
+    pub(super) fn build_generated_columns(
+        &self,
+        columns: &[SQLColumnDef],
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Vec<(String, Expr)>> {
+        let mut generated = vec![];
+        for column in columns {
+            let name = self.ident_normalizer.normalize(column.name.clone());
+            let mut has_default = false;
+            for option in &column.options {
+                match &option.option {
+                    ColumnOption::Default(_) => has_default = true,
+                    ColumnOption::Generated {
+                        generation_expr: Some(expr),
+                        ..
+                    } => generated.push((name.clone(), expr.clone())),
+                    ColumnOption::Generated { .. } => {
+                        return not_impl_err!("Identity column '{name}' not supported");
+                    }
+                    _ => {}
+                }
+            }
+            if has_default && generated.iter().any(|(n, _)| *n == name) {
+                return plan_err!(
+                    "Both default and generation expression specified for column '{name}'"
+                );
+            }
+        }
+
+        let generated_columns = generated
+            .into_iter()
+            .map(|(name, expr)| {
+                let expr = self.sql_to_expr(expr, schema, planner_context)?;
+                Ok((name, expr))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // A generated column can only refer to columns that are inserted
+        for (name, expr) in &generated_columns {
+            if let Some(column) = expr
+                .column_refs()
+                .into_iter()
+                .find(|column| generated_columns.iter().any(|(n, _)| *n == column.name))
+            {
+                return plan_err!(
+                    "Generated column '{name}' can not refer to generated column '{}'",
+                    column.name
+                );
+            }
+        }
+        Ok(generated_columns)
+    }
+
     /// Apply the given TableAlias to the input plan
     pub(crate) fn apply_table_alias(
         &self,
@@ -144,6 +144,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     or_replace: false,
                     temporary: false,
                     column_defaults: vec![],
+                    generated_columns: vec![],
                 },
             ))),
             _ => Ok(plan),
@@ -33,6 +33,8 @@ const DF_SETTINGS: &str = "df_settings";
 const SCHEMATA: &str = "schemata";
 const ROUTINES: &str = "routines";
 const PARAMETERS: &str = "parameters";
+const TABLE_CONSTRAINTS: &str = "table_constraints";
+const KEY_COLUMN_USAGE: &str = "key_column_usage";
 
 /// All information schema tables
 const INFORMATION_SCHEMA_TABLES: &[&str] = &[
@@ -43,6 +45,8 @@ const INFORMATION_SCHEMA_TABLES: &[&str] = &[
     SCHEMATA,
     ROUTINES,
     PARAMETERS,
+    TABLE_CONSTRAINTS,
+    KEY_COLUMN_USAGE,
 ];
 
 struct RelationVisitor {
@@ -147,6 +151,7 @@ fn visit_statement(statement: &DFStatement, visitor: &mut RelationVisitor) {
             }
         },
         DFStatement::Explain(explain) => visit_statement(&explain.statement, visitor),
+        DFStatement::CreateCatalog(_) | DFStatement::DropCatalog(_) => {}
     }
 }
 
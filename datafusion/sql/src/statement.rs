@@ -21,15 +21,15 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::parser::{
-    CopyToSource, CopyToStatement, CreateExternalTable, DFParser, ExplainStatement,
-    LexOrdering, Statement as DFStatement,
+    CopyToSource, CopyToStatement, CreateCatalogStatement, CreateExternalTable, DFParser,
+    DropCatalogStatement, ExplainStatement, LexOrdering, Statement as DFStatement,
 };
 use crate::planner::{
     object_name_to_qualifier, ContextProvider, PlannerContext, SqlToRel,
 };
 use crate::utils::normalize_ident;
 
-use arrow::datatypes::{DataType, Fields};
+use arrow::datatypes::{DataType, Field, Fields, Schema};
 use datafusion_common::error::_plan_err;
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::{
@@ -39,20 +39,22 @@ use datafusion_common::{
     ToDFSchema,
 };
 use datafusion_expr::dml::{CopyTo, InsertOp};
+use datafusion_expr::expr::FieldMetadata;
 use datafusion_expr::expr_rewriter::normalize_col_with_schemas_and_ambiguity_check;
 use datafusion_expr::logical_plan::builder::project;
 use datafusion_expr::logical_plan::DdlStatement;
 use datafusion_expr::utils::expr_to_columns;
 use datafusion_expr::{
-    cast, col, Analyze, CreateCatalog, CreateCatalogSchema,
+    cast, col, Analyze, AnalyzeFormat, CommentOn, CreateCatalog, CreateCatalogSchema,
     CreateExternalTable as PlanCreateExternalTable, CreateFunction, CreateFunctionBody,
     CreateIndex as PlanCreateIndex, CreateMemoryTable, CreateView, Deallocate,
-    DescribeTable, DmlStatement, DropCatalogSchema, DropFunction, DropTable, DropView,
-    EmptyRelation, Execute, Explain, ExplainFormat, Expr, ExprSchemable, Filter,
-    LogicalPlan, LogicalPlanBuilder, OperateFunctionArg, PlanType, Prepare, SetVariable,
-    SortExpr, Statement as PlanStatement, ToStringifiedPlan, TransactionAccessMode,
-    TransactionConclusion, TransactionEnd, TransactionIsolationLevel, TransactionStart,
-    Volatility, WriteOp,
+    DescribeTable, DmlStatement, DropCatalog, DropCatalogSchema, DropFunction, DropTable,
+    DropView, EmptyRelation, Execute, Explain, ExplainFormat, Expr, ExprSchemable,
+    Filter, LogicalPlan, LogicalPlanBuilder, OperateFunctionArg, PlanType, Prepare,
+    SetVariable, SortExpr, Statement as PlanStatement, ToStringifiedPlan,
+    TransactionAccessMode, TransactionConclusion, TransactionEnd,
+    TransactionIsolationLevel, TransactionStart, Volatility, WriteOp,
+    COMMENT_METADATA_KEY,
 };
 use sqlparser::ast::{
     self, BeginTransactionKind, NullsDistinctOption, ShowStatementIn,
@@ -182,6 +184,26 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             DFStatement::CreateExternalTable(s) => self.external_table_to_plan(s),
             DFStatement::Statement(s) => self.sql_statement_to_plan(*s),
             DFStatement::CopyTo(s) => self.copy_to_plan(s),
+            DFStatement::CreateCatalog(CreateCatalogStatement {
+                name,
+                if_not_exists,
+            }) => Ok(LogicalPlan::Ddl(DdlStatement::CreateCatalog(
+                CreateCatalog {
+                    catalog_name: object_name_to_string(&name),
+                    if_not_exists,
+                    schema: Arc::new(DFSchema::empty()),
+                },
+            ))),
+            DFStatement::DropCatalog(DropCatalogStatement {
+                name,
+                if_exists,
+                cascade,
+            }) => Ok(LogicalPlan::Ddl(DdlStatement::DropCatalog(DropCatalog {
+                catalog_name: object_name_to_string(&name),
+                if_exists,
+                cascade,
+                schema: DFSchemaRef::new(DFSchema::empty()),
+            }))),
             DFStatement::Explain(ExplainStatement {
                 verbose,
                 analyze,
@@ -216,9 +238,19 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         match statement {
             Statement::ExplainTable {
                 describe_alias: DescribeAlias::Describe | DescribeAlias::Desc, // only parse 'DESCRIBE table_name' or 'DESC table_name' and not 'EXPLAIN table_name'
+                hive_format,
                 table_name,
                 ..
-            } => self.describe_table_to_plan(table_name),
+            } => {
+                let extended = match hive_format {
+                    None => false,
+                    Some(ast::HiveDescribeFormat::Extended) => true,
+                    Some(hive_format) => {
+                        return not_impl_err!("DESCRIBE {hive_format} not supported")
+                    }
+                };
+                self.describe_table_to_plan(table_name, extended)
+            }
             Statement::Explain {
                 verbose,
                 statement,
@@ -291,9 +323,6 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 catalog_sync,
                 storage_serialization_policy,
             }) if table_properties.is_empty() && with_options.is_empty() => {
-                if temporary {
-                    return not_impl_err!("Temporary tables not supported")?;
-                }
                 if external {
                     return not_impl_err!("External tables not supported")?;
                 }
@@ -438,7 +467,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     self.build_column_defaults(&columns, planner_context)?;
 
                 let has_columns = !columns.is_empty();
-                let schema = self.build_schema(columns)?.to_dfschema_ref()?;
+                let schema = self.build_schema(columns.clone())?.to_dfschema_ref()?;
+                let generated_columns =
+                    self.build_generated_columns(&columns, &schema, planner_context)?;
                 if has_columns {
                     planner_context.set_table_schema(Some(Arc::clone(&schema)));
                 }
@@ -462,11 +493,14 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                                 .iter()
                                 .zip(input_fields)
                                 .map(|(field, input_field)| {
+                                    // Keep the comments of the columns
+                                    let metadata = (!field.metadata().is_empty())
+                                        .then(|| FieldMetadata::from(field.metadata()));
                                     cast(
                                         col(input_field.name()),
                                         field.data_type().clone(),
                                     )
-                                    .alias(field.name())
+                                    .alias_with_metadata(field.name(), metadata)
                                 })
                                 .collect::<Vec<_>>();
 
@@ -490,6 +524,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                                 if_not_exists,
                                 or_replace,
                                 column_defaults,
+                                generated_columns,
                                 temporary,
                             },
                         )))
@@ -513,6 +548,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                                 if_not_exists,
                                 or_replace,
                                 column_defaults,
+                                generated_columns,
                                 temporary,
                             },
                         )))
@@ -644,8 +680,25 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 purge: _,
                 temporary: _,
             } => {
-                // We don't support cascade and purge for now.
+                // We don't support purge for now.
                 // nor do we support multiple object names
+                if object_type == ObjectType::Database {
+                    let name = match names.len() {
+                        0 => Err(ParserError("Missing catalog name.".to_string())),
+                        1 => Ok(object_name_to_string(&names[0])),
+                        _ => {
+                            Err(ParserError("Multiple objects not supported".to_string()))
+                        }
+                    }?;
+                    return Ok(LogicalPlan::Ddl(DdlStatement::DropCatalog(
+                        DropCatalog {
+                            catalog_name: name,
+                            if_exists,
+                            cascade,
+                            schema: DFSchemaRef::new(DFSchema::empty()),
+                        },
+                    )));
+                }
                 let name = match names.len() {
                     0 => Err(ParserError("Missing table name.".to_string()).into()),
                     1 => self.object_name_to_table_reference(names.pop().unwrap()),
@@ -686,7 +739,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                         })))
                     }
                     _ => not_impl_err!(
-                        "Only `DROP TABLE/VIEW/SCHEMA  ...` statement is supported currently"
+                        "Only `DROP TABLE/VIEW/SCHEMA/DATABASE  ...` statement is supported currently"
                     ),
                 }
             }
@@ -1235,6 +1288,12 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     exec_err!("Function name not provided")
                 }
             }
+            Statement::Comment {
+                object_type,
+                object_name,
+                comment,
+                if_exists,
+            } => self.comment_to_plan(object_type, object_name, comment, if_exists),
             Statement::CreateIndex(CreateIndex {
                 name,
                 table_name,
@@ -1314,14 +1373,40 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
     }
 
-    fn describe_table_to_plan(&self, table_name: ObjectName) -> Result<LogicalPlan> {
+    fn describe_table_to_plan(
+        &self,
+        table_name: ObjectName,
+        extended: bool,
+    ) -> Result<LogicalPlan> {
         let table_ref = self.object_name_to_table_reference(table_name)?;
 
         let table_source = self.context_provider.get_table_source(table_ref)?;
 
-        let schema = table_source.schema();
+        let mut schema = table_source.schema();
 
-        let output_schema = DFSchema::try_from(LogicalPlan::describe_schema()).unwrap();
+        let output_schema = if extended {
+            // The comments reported by the table source are described from
+            // the metadata of the fields, see `TableSource::comment`
+            let fields = schema
+                .fields()
+                .iter()
+                .map(|field| {
+                    let mut metadata = field.metadata().clone();
+                    match table_source.comment(Some(field.name())) {
+                        Some(comment) => {
+                            metadata.insert(COMMENT_METADATA_KEY.to_string(), comment)
+                        }
+                        None => metadata.remove(COMMENT_METADATA_KEY),
+                    };
+                    Arc::new(Field::clone(field).with_metadata(metadata))
+                })
+                .collect::<Vec<_>>();
+            schema =
+                Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()));
+            DFSchema::try_from(LogicalPlan::describe_extended_schema())?
+        } else {
+            DFSchema::try_from(LogicalPlan::describe_schema())?
+        };
 
         Ok(LogicalPlan::DescribeTable(DescribeTable {
             schema,
@@ -1329,6 +1414,43 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }))
     }
 
+    fn comment_to_plan(
+        &self,
+        object_type: ast::CommentObject,
+        object_name: ObjectName,
+        comment: Option<String>,
+        if_exists: bool,
+    ) -> Result<LogicalPlan> {
+        let (table, column) = match object_type {
+            ast::CommentObject::Table => {
+                (self.object_name_to_table_reference(object_name)?, None)
+            }
+            ast::CommentObject::Column => {
+                let ObjectName(mut parts) = object_name;
+                let column = match parts.pop() {
+                    Some(part) if !parts.is_empty() => part,
+                    _ => return plan_err!("COMMENT ON COLUMN requires a table name"),
+                };
+                let Some(column) = column.as_ident() else {
+                    return plan_err!("Expected identifier, but found: {column:?}");
+                };
+                let column = self.ident_normalizer.normalize(column.clone());
+                let table = self.object_name_to_table_reference(ObjectName(parts))?;
+                (table, Some(column))
+            }
+            object_type => {
+                return not_impl_err!("COMMENT ON {object_type} not supported")
+            }
+        };
+        Ok(LogicalPlan::Ddl(DdlStatement::CommentOn(CommentOn {
+            table,
+            column,
+            comment,
+            if_exists,
+            schema: DFSchemaRef::new(DFSchema::empty()),
+        })))
+    }
+
     fn copy_to_plan(&self, statement: CopyToStatement) -> Result<LogicalPlan> {
         // Determine if source is table or query and handle accordingly
         let copy_source = statement.source;
@@ -1352,11 +1474,13 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
         let options_map = self.parse_options_map(statement.options, true)?;
 
-        let maybe_file_type = if let Some(stored_as) = &statement.stored_as {
-            self.context_provider.get_file_type(stored_as).ok()
-        } else {
-            None
-        };
+        // The format named by STORED AS must be registered, rather than
+        // falling back to the extension of the target
+        let maybe_file_type = statement
+            .stored_as
+            .as_ref()
+            .map(|stored_as| self.context_provider.get_file_type(stored_as))
+            .transpose()?;
 
         let file_type = match maybe_file_type {
             Some(ft) => ft,
@@ -1657,11 +1781,13 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
 
         if analyze {
-            if format.is_some() {
-                return plan_err!("EXPLAIN ANALYZE with FORMAT is not supported");
-            }
+            let format = format
+                .map(|format| format.parse::<AnalyzeFormat>())
+                .transpose()?
+                .unwrap_or_default();
             Ok(LogicalPlan::Analyze(Analyze {
                 verbose,
+                format,
                 input: plan,
                 schema,
             }))
@@ -1970,6 +2096,12 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         let table_source = self.context_provider.get_table_source(table_name.clone())?;
         let arrow_schema = (*table_source.schema()).clone();
         let table_schema = DFSchema::try_from(arrow_schema)?;
+        // The values of generated columns are computed from the inserted rows
+        let generated_exprs = table_schema
+            .fields()
+            .iter()
+            .map(|field| table_source.get_generated_column_expr(field.name()))
+            .collect::<Vec<_>>();
 
         // Get insert fields and target table's value indices
         //
@@ -1979,13 +2111,21 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         // If value_indices[i] = None, it means that the value of the i-th target table's column is
         // not provided, and should be filled with a default value later.
         let (fields, value_indices) = if columns.is_empty() {
-            // Empty means we're inserting into all columns of the table
-            (
-                table_schema.fields().clone(),
-                (0..table_schema.fields().len())
-                    .map(Some)
-                    .collect::<Vec<_>>(),
-            )
+            // Empty means we're inserting into all columns of the table, but
+            // the generated ones
+            let mut value_indices = vec![None; table_schema.fields().len()];
+            let fields = table_schema
+                .fields()
+                .iter()
+                .enumerate()
+                .filter(|(column_index, _)| generated_exprs[*column_index].is_none())
+                .enumerate()
+                .map(|(i, (column_index, field))| {
+                    value_indices[column_index] = Some(i);
+                    Arc::clone(field)
+                })
+                .collect::<Vec<_>>();
+            (Fields::from(fields), value_indices)
         } else {
             let mut value_indices = vec![None; table_schema.fields().len()];
             let fields = columns
@@ -1997,6 +2137,11 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                         .index_of_column_by_name(None, &c)
                         .ok_or_else(|| unqualified_field_not_found(&c, &table_schema))?;
 
+                    if generated_exprs[column_index].is_some() {
+                        return plan_err!(
+                            "Cannot insert a value into generated column '{c}'"
+                        );
+                    }
                     if value_indices[column_index].is_some() {
                         return schema_err!(SchemaError::DuplicateUnqualifiedField {
                             name: c,
@@ -2052,6 +2197,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         let exprs = value_indices
             .into_iter()
             .enumerate()
+            .filter(|(i, _)| generated_exprs[*i].is_none())
             .map(|(i, value_index)| {
                 let target_field = table_schema.field(i);
                 let expr = match value_index {
@@ -2072,7 +2218,24 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 Ok(expr.alias(target_field.name()))
             })
             .collect::<Result<Vec<Expr>>>()?;
-        let source = project(source, exprs)?;
+        let mut source = project(source, exprs)?;
+
+        // Compute the generated columns from the other columns of each row
+        if generated_exprs.iter().any(Option::is_some) {
+            let exprs = generated_exprs
+                .iter()
+                .zip(table_schema.fields())
+                .map(|(generated_expr, target_field)| {
+                    let expr = match generated_expr {
+                        Some(expr) => Expr::clone(expr)
+                            .cast_to(target_field.data_type(), source.schema())?,
+                        None => Expr::Column(Column::from_name(target_field.name())),
+                    };
+                    Ok(expr.alias(target_field.name()))
+                })
+                .collect::<Result<Vec<Expr>>>()?;
+            source = project(source, exprs)?;
+        }
 
         let insert_op = match (overwrite, replace_into) {
             (false, false) => InsertOp::Append,
@@ -2231,8 +2394,11 @@ ON p.function_name = r.routine_name
         let table_ref = self.object_name_to_table_reference(sql_table_name)?;
         let _ = self.context_provider.get_table_source(table_ref)?;
 
+        // Tables that cannot produce a statement show a description instead
         let query = format!(
-            "SELECT table_catalog, table_schema, table_name, definition FROM information_schema.views WHERE {where_clause}"
+            "SELECT table_catalog, table_schema, table_name, \
+             coalesce(definition, '-- ' || table_name || ' does not expose its CREATE statement') AS definition \
+             FROM information_schema.views WHERE {where_clause}"
         );
 
         let mut rewrite = DFParser::parse_sql(&query)?;
@@ -135,6 +135,12 @@ pub fn should_skip_record<D: AsyncDB>(
 }
 
 fn statement_is_skippable(statement: &Statement) -> bool {
+    // EXPLAIN statements can be skipped in all their modes, such as
+    // `EXPLAIN (ANALYZE, FORMAT JSON)`, unless they run a statement that can't
+    if let Statement::Explain(explain) = statement {
+        return !explain.analyze || statement_is_skippable(&explain.statement);
+    }
+
     // Only SQL statements can be skipped.
     let Statement::Statement(sql_stmt) = statement else {
         return false;
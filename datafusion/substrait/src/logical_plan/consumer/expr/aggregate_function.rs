@@ -44,13 +44,14 @@ pub async fn from_substrait_agg_func(
         );
     };
 
-    let fn_name = substrait_fun_name(fn_signature);
+    let fn_name = consumer.resolve_function_name(substrait_fun_name(fn_signature));
     let udaf = consumer.get_function_registry().udaf(fn_name);
     let udaf = udaf.map_err(|_| {
         not_impl_datafusion_err!(
-            "Aggregate function {} is not supported: function anchor = {:?}",
+            "Aggregate function {} is not supported: function anchor = {:?}, it is not mapped to a registered UDAF (resolved name {:?})",
             fn_signature,
-            f.function_reference
+            f.function_reference,
+            fn_name
         )
     })?;
 
@@ -16,10 +16,13 @@
 // under the License.
 
 use crate::logical_plan::consumer::types::from_substrait_type;
-use crate::logical_plan::consumer::utils::{next_struct_field_name, DEFAULT_TIMEZONE};
+use crate::logical_plan::consumer::utils::{
+    next_struct_field_name, resolve_type_variation, DEFAULT_TIMEZONE,
+};
 use crate::logical_plan::consumer::SubstraitConsumer;
 #[allow(deprecated)]
 use crate::variation_const::{
+    DECIMAL_128_TYPE_VARIATION_REF, DECIMAL_256_TYPE_VARIATION_REF,
     DEFAULT_CONTAINER_TYPE_VARIATION_REF, DEFAULT_TYPE_VARIATION_REF,
     INTERVAL_DAY_TIME_TYPE_REF, INTERVAL_MONTH_DAY_NANO_TYPE_NAME,
     INTERVAL_MONTH_DAY_NANO_TYPE_REF, INTERVAL_YEAR_MONTH_TYPE_REF,
@@ -30,7 +33,7 @@ use crate::variation_const::{
 };
 use datafusion::arrow::array::{new_empty_array, AsArray, MapArray};
 use datafusion::arrow::buffer::OffsetBuffer;
-use datafusion::arrow::datatypes::{Field, IntervalDayTime, IntervalMonthDayNano};
+use datafusion::arrow::datatypes::{i256, Field, IntervalDayTime, IntervalMonthDayNano};
 use datafusion::arrow::temporal_conversions::NANOSECONDS;
 use datafusion::common::scalar::ScalarStructBuilder;
 use datafusion::common::{
@@ -155,24 +158,38 @@ pub(crate) fn from_substrait_literal(
             }
         },
         Some(LiteralType::Date(d)) => ScalarValue::Date32(Some(*d)),
-        Some(LiteralType::String(s)) => match lit.type_variation_reference {
-            DEFAULT_CONTAINER_TYPE_VARIATION_REF => ScalarValue::Utf8(Some(s.clone())),
-            LARGE_CONTAINER_TYPE_VARIATION_REF => ScalarValue::LargeUtf8(Some(s.clone())),
-            VIEW_CONTAINER_TYPE_VARIATION_REF => ScalarValue::Utf8View(Some(s.clone())),
-            others => {
-                return substrait_err!("Unknown type variation reference {others}");
-            }
-        },
-        Some(LiteralType::Binary(b)) => match lit.type_variation_reference {
-            DEFAULT_CONTAINER_TYPE_VARIATION_REF => ScalarValue::Binary(Some(b.clone())),
-            LARGE_CONTAINER_TYPE_VARIATION_REF => {
-                ScalarValue::LargeBinary(Some(b.clone()))
+        Some(LiteralType::String(s)) => {
+            match resolve_type_variation(consumer, lit.type_variation_reference)? {
+                DEFAULT_CONTAINER_TYPE_VARIATION_REF => {
+                    ScalarValue::Utf8(Some(s.clone()))
+                }
+                LARGE_CONTAINER_TYPE_VARIATION_REF => {
+                    ScalarValue::LargeUtf8(Some(s.clone()))
+                }
+                VIEW_CONTAINER_TYPE_VARIATION_REF => {
+                    ScalarValue::Utf8View(Some(s.clone()))
+                }
+                others => {
+                    return substrait_err!("Unknown type variation reference {others}");
+                }
             }
-            VIEW_CONTAINER_TYPE_VARIATION_REF => ScalarValue::BinaryView(Some(b.clone())),
-            others => {
-                return substrait_err!("Unknown type variation reference {others}");
+        }
+        Some(LiteralType::Binary(b)) => {
+            match resolve_type_variation(consumer, lit.type_variation_reference)? {
+                DEFAULT_CONTAINER_TYPE_VARIATION_REF => {
+                    ScalarValue::Binary(Some(b.clone()))
+                }
+                LARGE_CONTAINER_TYPE_VARIATION_REF => {
+                    ScalarValue::LargeBinary(Some(b.clone()))
+                }
+                VIEW_CONTAINER_TYPE_VARIATION_REF => {
+                    ScalarValue::BinaryView(Some(b.clone()))
+                }
+                others => {
+                    return substrait_err!("Unknown type variation reference {others}");
+                }
             }
-        },
+        }
         Some(LiteralType::FixedBinary(b)) => {
             ScalarValue::FixedSizeBinary(b.len() as _, Some(b.clone()))
         }
@@ -188,7 +205,18 @@ pub(crate) fn from_substrait_literal(
             let s = d.scale.try_into().map_err(|e| {
                 substrait_datafusion_err!("Failed to parse decimal scale: {e}")
             })?;
-            ScalarValue::Decimal128(Some(i128::from_le_bytes(value)), p, s)
+            let value = i128::from_le_bytes(value);
+            match resolve_type_variation(consumer, lit.type_variation_reference)? {
+                DECIMAL_128_TYPE_VARIATION_REF => {
+                    ScalarValue::Decimal128(Some(value), p, s)
+                }
+                DECIMAL_256_TYPE_VARIATION_REF => {
+                    ScalarValue::Decimal256(Some(i256::from_i128(value)), p, s)
+                }
+                others => {
+                    return substrait_err!("Unknown type variation reference {others}");
+                }
+            }
         }
         Some(LiteralType::List(l)) => {
             // Each element should start the name index from the same value, then we increase it
@@ -209,7 +237,7 @@ pub(crate) fn from_substrait_literal(
                 );
             }
             let element_type = elements[0].data_type();
-            match lit.type_variation_reference {
+            match resolve_type_variation(consumer, lit.type_variation_reference)? {
                 DEFAULT_CONTAINER_TYPE_VARIATION_REF => ScalarValue::List(
                     ScalarValue::new_list_nullable(elements.as_slice(), &element_type),
                 ),
@@ -228,7 +256,7 @@ pub(crate) fn from_substrait_literal(
                 dfs_names,
                 name_idx,
             )?;
-            match lit.type_variation_reference {
+            match resolve_type_variation(consumer, lit.type_variation_reference)? {
                 DEFAULT_CONTAINER_TYPE_VARIATION_REF => {
                     ScalarValue::List(ScalarValue::new_list_nullable(&[], &element_type))
                 }
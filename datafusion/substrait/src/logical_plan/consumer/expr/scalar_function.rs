@@ -41,7 +41,7 @@ pub async fn from_scalar_function(
             f.function_reference
         );
     };
-    let fn_name = substrait_fun_name(fn_signature);
+    let fn_name = consumer.resolve_function_name(substrait_fun_name(fn_signature));
     let args = from_substrait_func_args(consumer, &f.arguments, input_schema).await?;
 
     // try to first match the requested function into registered udfs, then built-in ops
@@ -63,7 +63,9 @@ pub async fn from_scalar_function(
     } else if let Some(builder) = BuiltinExprBuilder::try_from_name(fn_name) {
         builder.build(consumer, f, input_schema).await
     } else {
-        not_impl_err!("Unsupported function name: {fn_name:?}")
+        not_impl_err!(
+            "Unsupported function name: {fn_name:?}, the Substrait extension function {fn_signature:?} is neither a built-in nor mapped to a registered UDF"
+        )
     }
 }
 
@@ -48,7 +48,7 @@ pub async fn from_window_function(
             window.function_reference
         );
     };
-    let fn_name = substrait_fun_name(fn_signature);
+    let fn_name = consumer.resolve_function_name(substrait_fun_name(fn_signature));
 
     // check udwf first, then udaf, then built-in window and aggregate functions
     let fun = if let Ok(udwf) = consumer.get_function_registry().udwf(fn_name) {
@@ -57,9 +57,10 @@ pub async fn from_window_function(
         Ok(WindowFunctionDefinition::AggregateUDF(udaf))
     } else {
         not_impl_err!(
-            "Window function {} is not supported: function anchor = {:?}",
+            "Window function {} is not supported: function anchor = {:?}, the Substrait extension function {:?} is not mapped to a registered UDWF or UDAF",
             fn_name,
-            window.function_reference
+            window.function_reference,
+            fn_signature
         )
     }?;
 
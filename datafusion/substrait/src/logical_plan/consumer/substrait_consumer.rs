@@ -27,7 +27,7 @@ use async_trait::async_trait;
 use datafusion::arrow::datatypes::DataType;
 use datafusion::catalog::TableProvider;
 use datafusion::common::{
-    not_impl_err, substrait_err, DFSchema, ScalarValue, TableReference,
+    not_impl_err, substrait_err, DFSchema, HashMap, ScalarValue, TableReference,
 };
 use datafusion::execution::{FunctionRegistry, SessionState};
 use datafusion::logical_expr::{Expr, Extension, LogicalPlan};
@@ -165,6 +165,15 @@ pub trait SubstraitConsumer: Send + Sync + Sized {
     fn get_extensions(&self) -> &Extensions;
     fn get_function_registry(&self) -> &impl FunctionRegistry;
 
+    /// Resolves the name of a Substrait extension function, without its signature, to the name
+    /// of the function to look up in the [FunctionRegistry].
+    ///
+    /// Override this to consume plans of other producers whose extension functions are
+    /// registered in DataFusion under a different name.
+    fn resolve_function_name<'a>(&'a self, name: &'a str) -> &'a str {
+        name
+    }
+
     // Relation Methods
     // There is one method per Substrait relation to allow for easy overriding of consumer behaviour.
     // These methods have default implementations calling the common handler code, to allow for users
@@ -438,11 +447,32 @@ pub trait SubstraitConsumer: Send + Sync + Sized {
 pub struct DefaultSubstraitConsumer<'a> {
     pub(super) extensions: &'a Extensions,
     pub(super) state: &'a SessionState,
+    pub(super) function_names: HashMap<String, String>,
 }
 
 impl<'a> DefaultSubstraitConsumer<'a> {
     pub fn new(extensions: &'a Extensions, state: &'a SessionState) -> Self {
-        DefaultSubstraitConsumer { extensions, state }
+        DefaultSubstraitConsumer {
+            extensions,
+            state,
+            function_names: HashMap::new(),
+        }
+    }
+
+    /// Maps the names of Substrait extension functions to the names of the registered
+    /// functions they resolve to, e.g. `("my_engine_strlen", "character_length")`.
+    ///
+    /// Extension functions that are not mapped are resolved by their own name.
+    pub fn with_function_name_mapping(
+        mut self,
+        mapping: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.function_names.extend(
+            mapping
+                .into_iter()
+                .map(|(from, to)| (from.into(), to.into())),
+        );
+        self
     }
 }
 
@@ -466,6 +496,13 @@ impl SubstraitConsumer for DefaultSubstraitConsumer<'_> {
         self.state
     }
 
+    fn resolve_function_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.function_names
+            .get(name)
+            .map(String::as_str)
+            .unwrap_or(name)
+    }
+
     async fn consume_extension_leaf(
         &self,
         rel: &ExtensionLeafRel,
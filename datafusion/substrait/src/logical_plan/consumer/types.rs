@@ -15,7 +15,10 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use super::utils::{from_substrait_precision, next_struct_field_name, DEFAULT_TIMEZONE};
+use super::utils::{
+    from_substrait_precision, next_struct_field_name, resolve_type_variation,
+    DEFAULT_TIMEZONE,
+};
 use super::SubstraitConsumer;
 #[allow(deprecated)]
 use crate::variation_const::{
@@ -133,25 +136,29 @@ pub fn from_substrait_type(
                     "Unsupported Substrait type variation {v} of type {s_kind:?}"
                 ),
             },
-            r#type::Kind::Binary(binary) => match binary.type_variation_reference {
-                DEFAULT_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::Binary),
-                LARGE_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::LargeBinary),
-                VIEW_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::BinaryView),
-                v => not_impl_err!(
-                    "Unsupported Substrait type variation {v} of type {s_kind:?}"
-                ),
-            },
+            r#type::Kind::Binary(binary) => {
+                match resolve_type_variation(consumer, binary.type_variation_reference)? {
+                    DEFAULT_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::Binary),
+                    LARGE_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::LargeBinary),
+                    VIEW_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::BinaryView),
+                    v => not_impl_err!(
+                        "Unsupported Substrait type variation {v} of type {s_kind:?}"
+                    ),
+                }
+            }
             r#type::Kind::FixedBinary(fixed) => {
                 Ok(DataType::FixedSizeBinary(fixed.length))
             }
-            r#type::Kind::String(string) => match string.type_variation_reference {
-                DEFAULT_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::Utf8),
-                LARGE_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::LargeUtf8),
-                VIEW_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::Utf8View),
-                v => not_impl_err!(
-                    "Unsupported Substrait type variation {v} of type {s_kind:?}"
-                ),
-            },
+            r#type::Kind::String(string) => {
+                match resolve_type_variation(consumer, string.type_variation_reference)? {
+                    DEFAULT_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::Utf8),
+                    LARGE_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::LargeUtf8),
+                    VIEW_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::Utf8View),
+                    v => not_impl_err!(
+                        "Unsupported Substrait type variation {v} of type {s_kind:?}"
+                    ),
+                }
+            }
             r#type::Kind::List(list) => {
                 let inner_type = list.r#type.as_ref().ok_or_else(|| {
                     substrait_datafusion_err!("List type must have inner type")
@@ -162,7 +169,7 @@ pub fn from_substrait_type(
                     // which always creates nullable lists
                     true,
                 ));
-                match list.type_variation_reference {
+                match resolve_type_variation(consumer, list.type_variation_reference)? {
                     DEFAULT_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::List(field)),
                     LARGE_CONTAINER_TYPE_VARIATION_REF => Ok(DataType::LargeList(field)),
                     v => not_impl_err!(
@@ -196,17 +203,19 @@ pub fn from_substrait_type(
                     false, // whether keys are sorted
                 ))
             }
-            r#type::Kind::Decimal(d) => match d.type_variation_reference {
-                DECIMAL_128_TYPE_VARIATION_REF => {
-                    Ok(DataType::Decimal128(d.precision as u8, d.scale as i8))
-                }
-                DECIMAL_256_TYPE_VARIATION_REF => {
-                    Ok(DataType::Decimal256(d.precision as u8, d.scale as i8))
+            r#type::Kind::Decimal(d) => {
+                match resolve_type_variation(consumer, d.type_variation_reference)? {
+                    DECIMAL_128_TYPE_VARIATION_REF => {
+                        Ok(DataType::Decimal128(d.precision as u8, d.scale as i8))
+                    }
+                    DECIMAL_256_TYPE_VARIATION_REF => {
+                        Ok(DataType::Decimal256(d.precision as u8, d.scale as i8))
+                    }
+                    v => not_impl_err!(
+                        "Unsupported Substrait type variation {v} of type {s_kind:?}"
+                    ),
                 }
-                v => not_impl_err!(
-                    "Unsupported Substrait type variation {v} of type {s_kind:?}"
-                ),
-            },
+            }
             r#type::Kind::IntervalYear(_) => {
                 Ok(DataType::Interval(IntervalUnit::YearMonth))
             }
@@ -16,6 +16,11 @@
 // under the License.
 
 use crate::logical_plan::consumer::SubstraitConsumer;
+use crate::variation_const::{
+    DECIMAL_256_TYPE_VARIATION_NAME, DECIMAL_256_TYPE_VARIATION_REF,
+    LARGE_CONTAINER_TYPE_VARIATION_NAME, LARGE_CONTAINER_TYPE_VARIATION_REF,
+    VIEW_CONTAINER_TYPE_VARIATION_NAME, VIEW_CONTAINER_TYPE_VARIATION_REF,
+};
 use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit, UnionFields};
 use datafusion::common::{
     exec_err, not_impl_err, substrait_datafusion_err, substrait_err, DFSchema,
@@ -346,10 +351,8 @@ fn ensure_field_compatibility(
     datafusion_field: &Field,
     substrait_field: &Field,
 ) -> datafusion::common::Result<()> {
-    if !DFSchema::datatype_is_logically_equal(
-        datafusion_field.data_type(),
-        substrait_field.data_type(),
-    ) {
+    if !datatype_is_compatible(datafusion_field.data_type(), substrait_field.data_type())
+    {
         return substrait_err!(
             "Field '{}' in Substrait schema has a different type ({}) than the corresponding field in the table schema ({}).",
             substrait_field.name(),
@@ -371,6 +374,18 @@ fn ensure_field_compatibility(
     Ok(())
 }
 
+/// Returns true if the DataFusion type can be read as the Substrait type, false otherwise
+///
+/// Substrait has no fixed size lists, they are represented as lists.
+fn datatype_is_compatible(datafusion_type: &DataType, substrait_type: &DataType) -> bool {
+    match (datafusion_type, substrait_type) {
+        (DataType::FixedSizeList(f1, _), DataType::List(f2)) => {
+            datatype_is_compatible(f1.data_type(), f2.data_type())
+        }
+        _ => DFSchema::datatype_is_logically_equal(datafusion_type, substrait_type),
+    }
+}
+
 /// Returns true if the DataFusion and Substrait nullabilities are compatible, false otherwise
 fn compatible_nullabilities(
     datafusion_nullability: bool,
@@ -491,6 +506,28 @@ pub(crate) fn from_substrait_precision(
     }
 }
 
+/// Resolves the type variation reference of a Substrait type or literal to the reference
+/// DataFusion uses for it in [crate::variation_const].
+///
+/// Plans produced by DataFusion use those references directly, while plans of other producers
+/// declare their type variations as extensions, which are resolved by name.
+pub(crate) fn resolve_type_variation(
+    consumer: &impl SubstraitConsumer,
+    reference: u32,
+) -> datafusion::common::Result<u32> {
+    let Some(name) = consumer.get_extensions().type_variations.get(&reference) else {
+        return Ok(reference);
+    };
+    match name.as_str() {
+        LARGE_CONTAINER_TYPE_VARIATION_NAME => Ok(LARGE_CONTAINER_TYPE_VARIATION_REF),
+        VIEW_CONTAINER_TYPE_VARIATION_NAME => Ok(VIEW_CONTAINER_TYPE_VARIATION_REF),
+        DECIMAL_256_TYPE_VARIATION_NAME => Ok(DECIMAL_256_TYPE_VARIATION_REF),
+        _ => not_impl_err!(
+            "Unsupported Substrait type variation extension {name} with anchor {reference}"
+        ),
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use super::make_renamed_schema;
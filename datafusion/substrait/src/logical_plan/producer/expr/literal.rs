@@ -18,9 +18,9 @@
 use crate::logical_plan::producer::{to_substrait_type, SubstraitProducer};
 use crate::variation_const::{
     DATE_32_TYPE_VARIATION_REF, DECIMAL_128_TYPE_VARIATION_REF,
-    DEFAULT_CONTAINER_TYPE_VARIATION_REF, DEFAULT_TYPE_VARIATION_REF,
-    LARGE_CONTAINER_TYPE_VARIATION_REF, UNSIGNED_INTEGER_TYPE_VARIATION_REF,
-    VIEW_CONTAINER_TYPE_VARIATION_REF,
+    DECIMAL_256_TYPE_VARIATION_REF, DEFAULT_CONTAINER_TYPE_VARIATION_REF,
+    DEFAULT_TYPE_VARIATION_REF, LARGE_CONTAINER_TYPE_VARIATION_REF,
+    UNSIGNED_INTEGER_TYPE_VARIATION_REF, VIEW_CONTAINER_TYPE_VARIATION_REF,
 };
 use datafusion::arrow::array::{Array, GenericListArray, OffsetSizeTrait};
 use datafusion::arrow::temporal_conversions::NANOSECONDS;
@@ -230,6 +230,22 @@ pub(crate) fn to_substrait_literal(
             }),
             DECIMAL_128_TYPE_VARIATION_REF,
         ),
+        ScalarValue::Decimal256(Some(v), p, s) => {
+            // Substrait decimals are 16 bytes, so only values that fit in an i128 can be encoded
+            let Some(v) = v.to_i128() else {
+                return not_impl_err!(
+                    "Unsupported literal: {value:?}, the value does not fit in a Substrait decimal"
+                );
+            };
+            (
+                LiteralType::Decimal(Decimal {
+                    value: v.to_le_bytes().to_vec(),
+                    precision: *p as i32,
+                    scale: *s as i32,
+                }),
+                DECIMAL_256_TYPE_VARIATION_REF,
+            )
+        }
         ScalarValue::List(l) => (
             convert_array_to_literal_list(producer, l)?,
             DEFAULT_CONTAINER_TYPE_VARIATION_REF,
@@ -345,7 +361,7 @@ mod tests {
     use crate::logical_plan::producer::DefaultSubstraitProducer;
     use datafusion::arrow::array::{Int64Builder, MapBuilder, StringBuilder};
     use datafusion::arrow::datatypes::{
-        DataType, Field, IntervalDayTime, IntervalMonthDayNano,
+        i256, DataType, Field, IntervalDayTime, IntervalMonthDayNano,
     };
     use datafusion::common::scalar::ScalarStructBuilder;
     use datafusion::common::Result;
@@ -398,6 +414,13 @@ mod tests {
             round_trip_literal(ScalarValue::TimestampNanosecond(ts, tz))?;
         }
 
+        round_trip_literal(ScalarValue::Decimal128(Some(12345), 10, 2))?;
+        round_trip_literal(ScalarValue::Decimal256(
+            Some(i256::from_i128(-12345)),
+            50,
+            2,
+        ))?;
+
         round_trip_literal(ScalarValue::List(ScalarValue::new_list_nullable(
             &[ScalarValue::Float32(Some(1.0))],
             &DataType::Float32,
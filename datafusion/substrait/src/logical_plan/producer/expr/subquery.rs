@@ -17,8 +17,12 @@
 
 use crate::logical_plan::producer::SubstraitProducer;
 use datafusion::common::DFSchemaRef;
-use datafusion::logical_expr::expr::InSubquery;
-use substrait::proto::expression::subquery::InPredicate;
+use datafusion::logical_expr::expr::{Exists, InSubquery};
+use datafusion::logical_expr::Subquery;
+use substrait::proto::expression::subquery::set_predicate::PredicateOp;
+use substrait::proto::expression::subquery::{
+    InPredicate, Scalar, SetPredicate, SubqueryType,
+};
 use substrait::proto::expression::{RexType, ScalarFunction};
 use substrait::proto::function_argument::ArgType;
 use substrait::proto::{Expression, FunctionArgument};
@@ -37,36 +41,76 @@ pub fn from_in_subquery(
 
     let subquery_plan = producer.handle_plan(subquery.subquery.as_ref())?;
 
-    let substrait_subquery = Expression {
+    let substrait_subquery =
+        make_substrait_subquery(SubqueryType::InPredicate(Box::new(InPredicate {
+            needles: (vec![substrait_expr]),
+            haystack: Some(subquery_plan),
+        })));
+    if *negated {
+        Ok(negate(producer, substrait_subquery))
+    } else {
+        Ok(substrait_subquery)
+    }
+}
+
+pub fn from_exists(
+    producer: &mut impl SubstraitProducer,
+    exists: &Exists,
+    _schema: &DFSchemaRef,
+) -> datafusion::common::Result<Expression> {
+    let Exists { subquery, negated } = exists;
+    let subquery_plan = producer.handle_plan(subquery.subquery.as_ref())?;
+
+    let substrait_subquery =
+        make_substrait_subquery(SubqueryType::SetPredicate(Box::new(SetPredicate {
+            predicate_op: PredicateOp::Exists as i32,
+            tuples: Some(subquery_plan),
+        })));
+    if *negated {
+        Ok(negate(producer, substrait_subquery))
+    } else {
+        Ok(substrait_subquery)
+    }
+}
+
+pub fn from_scalar_subquery(
+    producer: &mut impl SubstraitProducer,
+    subquery: &Subquery,
+    _schema: &DFSchemaRef,
+) -> datafusion::common::Result<Expression> {
+    let subquery_plan = producer.handle_plan(subquery.subquery.as_ref())?;
+
+    Ok(make_substrait_subquery(SubqueryType::Scalar(Box::new(
+        Scalar {
+            input: Some(subquery_plan),
+        },
+    ))))
+}
+
+fn make_substrait_subquery(subquery_type: SubqueryType) -> Expression {
+    Expression {
         rex_type: Some(RexType::Subquery(Box::new(
             substrait::proto::expression::Subquery {
-                subquery_type: Some(
-                    substrait::proto::expression::subquery::SubqueryType::InPredicate(
-                        Box::new(InPredicate {
-                            needles: (vec![substrait_expr]),
-                            haystack: Some(subquery_plan),
-                        }),
-                    ),
-                ),
+                subquery_type: Some(subquery_type),
             },
         ))),
-    };
-    if *negated {
-        let function_anchor = producer.register_function("not".to_string());
+    }
+}
 
-        #[allow(deprecated)]
-        Ok(Expression {
-            rex_type: Some(RexType::ScalarFunction(ScalarFunction {
-                function_reference: function_anchor,
-                arguments: vec![FunctionArgument {
-                    arg_type: Some(ArgType::Value(substrait_subquery)),
-                }],
-                output_type: None,
-                args: vec![],
-                options: vec![],
-            })),
-        })
-    } else {
-        Ok(substrait_subquery)
+/// Wraps `expr` in a call to `not`
+fn negate(producer: &mut impl SubstraitProducer, expr: Expression) -> Expression {
+    let function_anchor = producer.register_function("not".to_string());
+
+    #[allow(deprecated)]
+    Expression {
+        rex_type: Some(RexType::ScalarFunction(ScalarFunction {
+            function_reference: function_anchor,
+            arguments: vec![FunctionArgument {
+                arg_type: Some(ArgType::Value(expr)),
+            }],
+            output_type: None,
+            args: vec![],
+            options: vec![],
+        })),
     }
 }
@@ -118,45 +118,47 @@ fn to_substrait_bounds(
     window_frame: &WindowFrame,
 ) -> datafusion::common::Result<(Bound, Bound)> {
     Ok((
-        to_substrait_bound(&window_frame.start_bound),
-        to_substrait_bound(&window_frame.end_bound),
+        to_substrait_bound(&window_frame.start_bound)?,
+        to_substrait_bound(&window_frame.end_bound)?,
     ))
 }
 
-fn to_substrait_bound(bound: &WindowFrameBound) -> Bound {
-    match bound {
-        WindowFrameBound::CurrentRow => Bound {
-            kind: Some(BoundKind::CurrentRow(SubstraitBound::CurrentRow {})),
+fn to_substrait_bound(bound: &WindowFrameBound) -> datafusion::common::Result<Bound> {
+    let kind = match bound {
+        WindowFrameBound::CurrentRow => {
+            BoundKind::CurrentRow(SubstraitBound::CurrentRow {})
+        }
+        WindowFrameBound::Preceding(s) => match to_substrait_bound_offset(s)? {
+            Some(offset) => BoundKind::Preceding(SubstraitBound::Preceding { offset }),
+            None => BoundKind::Unbounded(SubstraitBound::Unbounded {}),
         },
-        WindowFrameBound::Preceding(s) => match to_substrait_bound_offset(s) {
-            Some(offset) => Bound {
-                kind: Some(BoundKind::Preceding(SubstraitBound::Preceding { offset })),
-            },
-            None => Bound {
-                kind: Some(BoundKind::Unbounded(SubstraitBound::Unbounded {})),
-            },
+        WindowFrameBound::Following(s) => match to_substrait_bound_offset(s)? {
+            Some(offset) => BoundKind::Following(SubstraitBound::Following { offset }),
+            None => BoundKind::Unbounded(SubstraitBound::Unbounded {}),
         },
-        WindowFrameBound::Following(s) => match to_substrait_bound_offset(s) {
-            Some(offset) => Bound {
-                kind: Some(BoundKind::Following(SubstraitBound::Following { offset })),
-            },
-            None => Bound {
-                kind: Some(BoundKind::Unbounded(SubstraitBound::Unbounded {})),
-            },
-        },
-    }
+    };
+    Ok(Bound { kind: Some(kind) })
 }
 
-fn to_substrait_bound_offset(value: &ScalarValue) -> Option<i64> {
-    match value {
-        ScalarValue::UInt8(Some(v)) => Some(*v as i64),
-        ScalarValue::UInt16(Some(v)) => Some(*v as i64),
-        ScalarValue::UInt32(Some(v)) => Some(*v as i64),
-        ScalarValue::UInt64(Some(v)) => Some(*v as i64),
-        ScalarValue::Int8(Some(v)) => Some(*v as i64),
-        ScalarValue::Int16(Some(v)) => Some(*v as i64),
-        ScalarValue::Int32(Some(v)) => Some(*v as i64),
-        ScalarValue::Int64(Some(v)) => Some(*v),
-        _ => None,
-    }
+/// Returns the offset of a bound, or `None` for an unbounded one.
+///
+/// Substrait bounds only carry integer offsets, so frames offset by other
+/// values, such as `RANGE BETWEEN INTERVAL '1' DAY PRECEDING AND CURRENT ROW`,
+/// cannot be represented.
+fn to_substrait_bound_offset(
+    value: &ScalarValue,
+) -> datafusion::common::Result<Option<i64>> {
+    let offset = match value {
+        v if v.is_null() => return Ok(None),
+        ScalarValue::UInt8(Some(v)) => *v as i64,
+        ScalarValue::UInt16(Some(v)) => *v as i64,
+        ScalarValue::UInt32(Some(v)) => *v as i64,
+        ScalarValue::UInt64(Some(v)) => *v as i64,
+        ScalarValue::Int8(Some(v)) => *v as i64,
+        ScalarValue::Int16(Some(v)) => *v as i64,
+        ScalarValue::Int32(Some(v)) => *v as i64,
+        ScalarValue::Int64(Some(v)) => *v,
+        v => return not_impl_err!("Unsupported window frame bound offset: {v:?}"),
+    };
+    Ok(Some(offset))
 }
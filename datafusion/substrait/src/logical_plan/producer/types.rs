@@ -262,6 +262,17 @@ pub(crate) fn to_substrait_type(
                 }))),
             })
         }
+        // Substrait has no fixed size lists, so they are produced as lists
+        DataType::FixedSizeList(inner, _) => {
+            let inner_type = to_substrait_type(inner.data_type(), inner.is_nullable())?;
+            Ok(substrait::proto::Type {
+                kind: Some(r#type::Kind::List(Box::new(r#type::List {
+                    r#type: Some(Box::new(inner_type)),
+                    type_variation_reference: DEFAULT_CONTAINER_TYPE_VARIATION_REF,
+                    nullability,
+                }))),
+            })
+        }
         DataType::Map(inner, _) => match inner.data_type() {
             DataType::Struct(key_and_value) if key_and_value.len() == 2 => {
                 let key_type = to_substrait_type(
@@ -428,6 +439,17 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn fixed_size_list_as_list() -> Result<()> {
+        let field = Arc::new(Field::new_list_field(DataType::Int32, true));
+        let substrait =
+            to_substrait_type(&DataType::FixedSizeList(Arc::clone(&field), 3), true)?;
+        let consumer = test_consumer();
+        let roundtrip_dt = from_substrait_type_without_names(&consumer, &substrait)?;
+        assert_eq!(DataType::List(field), roundtrip_dt);
+        Ok(())
+    }
+
     fn round_trip_type(dt: DataType) -> Result<()> {
         println!("Checking round trip of {dt:?}");
 
@@ -42,6 +42,7 @@ pub(crate) fn flatten_names(
         }
         DataType::List(l) => flatten_names(l, true, names),
         DataType::LargeList(l) => flatten_names(l, true, names),
+        DataType::FixedSizeList(l, _) => flatten_names(l, true, names),
         DataType::Map(m, _) => match m.data_type() {
             DataType::Struct(key_and_value) if key_and_value.len() == 2 => {
                 flatten_names(&key_and_value[0], true, names)?;
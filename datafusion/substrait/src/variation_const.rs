@@ -57,6 +57,16 @@ pub const LARGE_CONTAINER_TYPE_VARIATION_REF: u32 = 1;
 pub const VIEW_CONTAINER_TYPE_VARIATION_REF: u32 = 2;
 pub const DECIMAL_128_TYPE_VARIATION_REF: u32 = 0;
 pub const DECIMAL_256_TYPE_VARIATION_REF: u32 = 1;
+
+// Names of the type variations above, for plans of other producers that declare them as
+// [type variation extensions](https://substrait.io/types/type_variations/) instead of
+// referring to the references used by DataFusion.
+/// The name of the variation with [`LARGE_CONTAINER_TYPE_VARIATION_REF`].
+pub const LARGE_CONTAINER_TYPE_VARIATION_NAME: &str = "large";
+/// The name of the variation with [`VIEW_CONTAINER_TYPE_VARIATION_REF`].
+pub const VIEW_CONTAINER_TYPE_VARIATION_NAME: &str = "view";
+/// The name of the variation with [`DECIMAL_256_TYPE_VARIATION_REF`].
+pub const DECIMAL_256_TYPE_VARIATION_NAME: &str = "decimal256";
 /// Used for the arrow type [`DataType::Interval`] with [`IntervalUnit::DayTime`].
 ///
 /// [`DataType::Interval`]: datafusion::arrow::datatypes::DataType::Interval
@@ -24,13 +24,19 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::test::add_plan_schemas_to_ctx;
-    use datafusion::common::Result;
+    use crate::utils::test::{add_plan_schemas_to_ctx, read_json};
+    use datafusion::arrow::datatypes::{DataType, Field, Schema};
+    use datafusion::common::{assert_contains, Result};
+    use datafusion::datasource::empty::EmptyTable;
     use datafusion::prelude::SessionContext;
-    use datafusion_substrait::logical_plan::consumer::from_substrait_plan;
+    use datafusion_substrait::extensions::Extensions;
+    use datafusion_substrait::logical_plan::consumer::{
+        from_substrait_plan, from_substrait_plan_with_consumer, DefaultSubstraitConsumer,
+    };
     use insta::assert_snapshot;
     use std::fs::File;
     use std::io::BufReader;
+    use std::sync::Arc;
     use substrait::proto::Plan;
 
     async fn tpch_plan_to_string(query_id: i32) -> Result<String> {
@@ -584,4 +590,57 @@ mod tests {
 
         Ok(())
     }
+
+    /// Registers the table read by `foreign_extensions.substrait.json`, a plan of another
+    /// engine with its own extension function and type variation declarations
+    fn foreign_extensions_ctx() -> Result<SessionContext> {
+        let ctx = SessionContext::new();
+        let schema = Schema::new(vec![
+            Field::new("S", DataType::Utf8View, true),
+            Field::new("D", DataType::Decimal256(40, 2), true),
+            Field::new(
+                "L",
+                DataType::FixedSizeList(
+                    Arc::new(Field::new_list_field(DataType::Int64, true)),
+                    2,
+                ),
+                true,
+            ),
+        ]);
+        ctx.register_table("DATA", Arc::new(EmptyTable::new(Arc::new(schema))))?;
+        Ok(ctx)
+    }
+
+    #[tokio::test]
+    async fn test_foreign_extensions() -> Result<()> {
+        let proto =
+            read_json("tests/testdata/test_plans/foreign_extensions.substrait.json");
+        let ctx = foreign_extensions_ctx()?;
+        let state = ctx.state();
+        let extensions = Extensions::try_from(&proto.extensions)?;
+        let consumer = DefaultSubstraitConsumer::new(&extensions, &state)
+            .with_function_name_mapping([("engine_upper", "upper")]);
+        let plan = from_substrait_plan_with_consumer(&consumer, &proto).await?;
+        state.create_physical_plan(&plan).await?;
+
+        assert_contains!(plan.to_string(), "upper(DATA.S)");
+        let schema = plan.schema();
+        assert_eq!(schema.field(0).name(), "UPPER_S");
+        assert_eq!(schema.field(1).name(), "D");
+        assert_eq!(schema.field(1).data_type(), &DataType::Decimal256(40, 2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_foreign_extensions_without_function_mapping() -> Result<()> {
+        let proto =
+            read_json("tests/testdata/test_plans/foreign_extensions.substrait.json");
+        let ctx = foreign_extensions_ctx()?;
+        let err = from_substrait_plan(&ctx.state(), &proto).await.unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "the Substrait extension function \"engine_upper:str\" is neither a built-in nor mapped to a registered UDF"
+        );
+        Ok(())
+    }
 }
@@ -26,7 +26,10 @@ use std::cmp::Ordering;
 use std::mem::size_of_val;
 
 use datafusion::arrow::datatypes::{DataType, Field, IntervalUnit, Schema, TimeUnit};
-use datafusion::common::{not_impl_err, plan_err, DFSchema, DFSchemaRef};
+use datafusion::arrow::util::pretty::pretty_format_batches;
+use datafusion::common::{
+    assert_contains, not_impl_err, plan_err, DFSchema, DFSchemaRef,
+};
 use datafusion::error::Result;
 use datafusion::execution::registry::SerializerRegistry;
 use datafusion::execution::runtime_env::RuntimeEnv;
@@ -1022,6 +1025,56 @@ async fn window_with_rows() -> Result<()> {
     roundtrip("SELECT sum(b) OVER (PARTITION BY a ROWS BETWEEN 4 PRECEDING AND 2 PRECEDING) FROM data;").await
 }
 
+#[tokio::test]
+async fn window_with_range() -> Result<()> {
+    roundtrip("SELECT sum(b) OVER (ORDER BY a RANGE BETWEEN 1 PRECEDING AND CURRENT ROW) FROM data;").await?;
+    roundtrip("SELECT sum(b) OVER (ORDER BY a RANGE BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING) FROM data;").await
+}
+
+#[tokio::test]
+async fn window_with_unsupported_bound_offset() -> Result<()> {
+    let ctx = create_context().await?;
+    let plan = ctx
+        .sql("SELECT count(a) OVER (ORDER BY c RANGE BETWEEN INTERVAL '1' DAY PRECEDING AND CURRENT ROW) FROM data")
+        .await?
+        .into_optimized_plan()?;
+    let err = to_substrait_plan(&plan, &ctx.state()).unwrap_err();
+    assert_contains!(err.to_string(), "Unsupported window frame bound offset");
+    Ok(())
+}
+
+#[tokio::test]
+async fn unoptimized_window_with_rows() -> Result<()> {
+    roundtrip_unoptimized("SELECT a, sum(b) OVER (PARTITION BY a ORDER BY b ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING) FROM data").await?;
+    roundtrip_unoptimized("SELECT a, sum(b) OVER (ORDER BY a ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) FROM data").await
+}
+
+#[tokio::test]
+async fn unoptimized_exists() -> Result<()> {
+    roundtrip_unoptimized(
+        "SELECT a FROM data WHERE EXISTS (SELECT a FROM data2 WHERE a > 1)",
+    )
+    .await?;
+    roundtrip_unoptimized(
+        "SELECT a FROM data WHERE NOT EXISTS (SELECT a FROM data2 WHERE a > 10)",
+    )
+    .await
+}
+
+#[tokio::test]
+async fn unoptimized_in_subquery() -> Result<()> {
+    roundtrip_unoptimized("SELECT a FROM data WHERE a IN (SELECT a FROM data2)").await?;
+    roundtrip_unoptimized(
+        "SELECT a FROM data WHERE a NOT IN (SELECT a FROM data2 WHERE a > 1)",
+    )
+    .await
+}
+
+#[tokio::test]
+async fn unoptimized_scalar_subquery() -> Result<()> {
+    roundtrip_unoptimized("SELECT a FROM data WHERE a > (SELECT min(a) FROM data2)").await
+}
+
 #[tokio::test]
 async fn qualified_schema_table_reference() -> Result<()> {
     roundtrip("SELECT * FROM public.data;").await
@@ -1665,6 +1718,28 @@ async fn roundtrip(sql: &str) -> Result<()> {
     Ok(())
 }
 
+/// Round trips the plan of `sql` before it is optimized, so that subquery
+/// expressions reach the producer, and checks that the optimized plans and the
+/// results of both plans match
+async fn roundtrip_unoptimized(sql: &str) -> Result<()> {
+    let ctx = create_context().await?;
+    let plan = ctx.sql(sql).await?.into_unoptimized_plan();
+    let proto = to_substrait_plan(&plan, &ctx.state())?;
+    let plan2 = from_substrait_plan(&ctx.state(), &proto).await?;
+
+    let optimized = ctx.state().optimize(&plan)?;
+    let optimized2 = ctx.state().optimize(&plan2)?;
+    assert_eq!(format!("{optimized}"), format!("{optimized2}"));
+
+    let results = DataFrame::new(ctx.state(), plan).collect().await?;
+    let results2 = DataFrame::new(ctx.state(), plan2).collect().await?;
+    assert_eq!(
+        pretty_format_batches(&results)?.to_string(),
+        pretty_format_batches(&results2)?.to_string()
+    );
+    Ok(())
+}
+
 async fn roundtrip_verify_post_join_filter(sql: &str) -> Result<()> {
     let ctx = create_context().await?;
     let proto = roundtrip_with_ctx(sql, ctx).await?;